@@ -0,0 +1,82 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::runtime::account_already_in_use;
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_owner_violation, assert_signer_violation, Fixture,
+    TestAccount,
+};
+
+use crate::{SecureDeposit, SecureInitialize, SecureWithdraw, Vault};
+
+fn initialize_fixture() -> Fixture {
+    Fixture::new()
+        .with("vault", TestAccount::uninitialized(8 + 32 + 8).signed())
+        .with("owner", TestAccount::signer())
+        .with("payer", TestAccount::signer())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn vault_fixture() -> Fixture {
+    let owner = TestAccount::signer();
+    let vault = TestAccount::anchor(&Vault {
+        owner: owner.key,
+        balance: 0,
+    });
+    Fixture::new().with("vault", vault).with("owner", owner)
+}
+
+#[test]
+fn secure_initialize_rejects_existing_vaults() {
+    let existing = TestAccount::anchor(&Vault {
+        owner: Pubkey::new_unique(),
+        balance: 0,
+    })
+    .signed();
+    assert_constraint_violation!(SecureInitialize, initialize_fixture(), "vault" => existing, account_already_in_use());
+}
+
+#[test]
+fn secure_initialize_requires_an_owner_signature() {
+    assert_signer_violation!(SecureInitialize, initialize_fixture(), "owner");
+}
+
+#[test]
+fn secure_initialize_requires_the_system_program() {
+    assert_constraint_violation!(
+        SecureInitialize,
+        initialize_fixture(),
+        "system_program" => TestAccount::program(Pubkey::new_unique()),
+        ErrorCode::InvalidProgramId
+    );
+}
+
+#[test]
+fn secure_deposit_requires_the_vault_owner() {
+    assert_has_one_violation!(SecureDeposit, vault_fixture(), "owner");
+}
+
+#[test]
+fn secure_deposit_requires_an_owner_signature() {
+    assert_signer_violation!(SecureDeposit, vault_fixture(), "owner");
+}
+
+#[test]
+fn secure_deposit_rejects_vaults_of_other_programs() {
+    assert_owner_violation!(SecureDeposit, vault_fixture(), "vault");
+}
+
+#[test]
+fn secure_withdraw_requires_the_vault_owner() {
+    assert_has_one_violation!(SecureWithdraw, vault_fixture(), "owner");
+}
+
+#[test]
+fn secure_withdraw_requires_an_owner_signature() {
+    assert_signer_violation!(SecureWithdraw, vault_fixture(), "owner");
+}
+
+#[test]
+fn secure_withdraw_rejects_vaults_of_other_programs() {
+    assert_owner_violation!(SecureWithdraw, vault_fixture(), "vault");
+}
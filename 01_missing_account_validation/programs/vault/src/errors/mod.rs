@@ -0,0 +1,15 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+    #[msg("Arithmetic underflow")]
+    ArithmeticUnderflow,
+    #[msg("Account data is not exactly one vault in this encoding")]
+    InvalidEncoding,
+}
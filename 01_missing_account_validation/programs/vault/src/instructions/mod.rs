@@ -0,0 +1,44 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct SecureInitialize<'info> {
+    /// SECURITY: `init` constraint ensures proper initialization and prevents
+    /// reinitialization attacks. `payer` and `space` are required for init.
+    /// No `has_one` here: the new vault's owner is still zeroed when
+    /// constraints run, so it could only ever fail.
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 8 // discriminator + owner + balance
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    /// SECURITY: Must be a signer to prove ownership authorization
+    pub owner: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// A vault stored in one of the encodings of `crate::serialization`
+#[derive(Accounts)]
+pub struct EncodedDeposit<'info> {
+    /// CHECK: Bytes in the handler's encoding, in an account the client created for the program
+    #[account(mut, owner = crate::ID)]
+    pub vault: UncheckedAccount<'info>,
+}
@@ -0,0 +1,33 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+// These contexts use proper Anchor constraints for security
+
+#[derive(Accounts)]
+pub struct SecureDeposit<'info> {
+    /// SECURITY: `has_one = owner` constraint validates that vault.owner
+    /// matches the owner account passed in the context
+    #[account(
+        mut,
+        has_one = owner
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    /// SECURITY: Must be a signer and must match vault.owner
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SecureWithdraw<'info> {
+    /// SECURITY: `has_one = owner` constraint validates that vault.owner
+    /// matches the owner account passed in the context
+    #[account(
+        mut,
+        has_one = owner
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    /// SECURITY: Must be a signer and must match vault.owner
+    pub owner: Signer<'info>,
+}
@@ -0,0 +1,44 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+// These contexts lack proper validation constraints
+
+#[derive(Accounts)]
+pub struct VulnerableInitialize<'info> {
+    /// VULNERABILITY: Using `zero` constraint instead of `init` means we don't
+    /// get proper initialization protection. An attacker could potentially
+    /// reinitialize an existing account.
+    #[account(zero)]
+    pub vault: Account<'info, Vault>,
+    
+    /// VULNERABILITY: No validation that this owner is actually authorized
+    /// to create this vault. Any pubkey can be passed as owner.
+    /// CHECK: This is intentionally unsafe for demonstration
+    pub owner: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableDeposit<'info> {
+    /// VULNERABILITY: No `has_one` constraint to validate vault ownership
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    
+    /// VULNERABILITY: We accept any account as owner without validation
+    /// CHECK: This is intentionally unsafe for demonstration
+    pub owner: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableWithdraw<'info> {
+    /// VULNERABILITY: No `has_one` constraint to validate vault ownership
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+    
+    /// VULNERABILITY: We require a signer but don't validate it's the vault owner
+    pub owner: Signer<'info>,
+}
@@ -0,0 +1,15 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::Vault;
+
+#[test]
+fn vault_layout_is_stable() {
+    let vault = Vault {
+        owner: Pubkey::new_from_array([1; 32]),
+        balance: 0x2122232425262728,
+    };
+    assert_golden!("Vault", golden::anchor(&vault));
+}
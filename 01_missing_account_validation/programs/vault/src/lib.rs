@@ -1,7 +1,29 @@
-use anchor_lang::prelude::*;
+use secref_prelude::*;
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
+pub mod errors;
+pub mod instructions;
+pub mod narrative;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod serialization;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use narrative::attack_narrative;
+pub use serialization::{BorshVault, PackedVault, ZeroCopyVault};
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod serialization_tests;
+
+#[feature_gated]
 #[program]
 pub mod vault {
     use super::*;
@@ -17,6 +39,7 @@ pub mod vault {
     /// Security Issue: This function doesn't validate the relationship between
     /// the vault account and the owner. An attacker could potentially create
     /// a vault account with any owner they choose, leading to unauthorized access.
+    #[vulnerable]
     pub fn vulnerable_initialize(ctx: Context<VulnerableInitialize>, initial_balance: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -35,6 +58,7 @@ pub mod vault {
     /// Security Issue: This function doesn't verify that the vault actually
     /// belongs to the claimed owner. An attacker could deposit to any vault
     /// and then potentially withdraw from it if they can manipulate the owner field.
+    #[vulnerable]
     pub fn vulnerable_deposit(ctx: Context<VulnerableDeposit>, amount: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -53,6 +77,7 @@ pub mod vault {
     /// checks that someone signed the transaction, but doesn't verify that
     /// the signer is actually the owner of the vault. An attacker could
     /// withdraw from any vault if they can pass the vault account.
+    #[vulnerable]
     pub fn vulnerable_withdraw(ctx: Context<VulnerableWithdraw>, amount: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -77,13 +102,13 @@ pub mod vault {
     /// SECURE: Initialize a new vault with proper validation
     /// 
     /// Security Fix: Uses Anchor's `init` constraint which ensures the account
-    /// is properly initialized and the `has_one` constraint validates ownership.
+    /// is new, and records the signing owner that `has_one` checks from then on.
+    // Built with either feature: the CTF server seeds its flag vaults through it
     pub fn secure_initialize(ctx: Context<SecureInitialize>, initial_balance: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
-        // SECURITY: The owner is automatically set and validated by Anchor constraints
-        // The `init` constraint ensures this is a new account
-        // The `has_one` constraint will validate the owner relationship
+        // SECURITY: The `init` constraint ensures this is a new account
+        // The owner must sign, and every later instruction checks it with `has_one`
         vault.owner = ctx.accounts.owner.key();
         vault.balance = initial_balance;
         
@@ -95,6 +120,7 @@ pub mod vault {
     /// 
     /// Security Fix: Uses `has_one = owner` constraint to ensure the vault
     /// actually belongs to the signer before allowing deposits.
+    #[secure]
     pub fn secure_deposit(ctx: Context<SecureDeposit>, amount: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -111,6 +137,7 @@ pub mod vault {
     /// 
     /// Security Fix: Uses `has_one = owner` constraint to ensure only the
     /// actual vault owner can withdraw funds.
+    #[secure]
     pub fn secure_withdraw(ctx: Context<SecureWithdraw>, amount: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -124,127 +151,34 @@ pub mod vault {
         msg!("Securely withdrew {} from vault. New balance: {}", amount, vault.balance);
         Ok(())
     }
-}
-
-// ========================================
-// VULNERABLE ACCOUNT CONTEXTS
-// ========================================
-// These contexts lack proper validation constraints
-
-#[derive(Accounts)]
-pub struct VulnerableInitialize<'info> {
-    /// VULNERABILITY: Using `zero` constraint instead of `init` means we don't
-    /// get proper initialization protection. An attacker could potentially
-    /// reinitialize an existing account.
-    #[account(zero)]
-    pub vault: Account<'info, Vault>,
-    
-    /// VULNERABILITY: No validation that this owner is actually authorized
-    /// to create this vault. Any pubkey can be passed as owner.
-    /// CHECK: This is intentionally unsafe for demonstration
-    pub owner: UncheckedAccount<'info>,
-    
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct VulnerableDeposit<'info> {
-    /// VULNERABILITY: No `has_one` constraint to validate vault ownership
-    #[account(mut)]
-    pub vault: Account<'info, Vault>,
-    
-    /// VULNERABILITY: We accept any account as owner without validation
-    /// CHECK: This is intentionally unsafe for demonstration
-    pub owner: UncheckedAccount<'info>,
-}
 
-#[derive(Accounts)]
-pub struct VulnerableWithdraw<'info> {
-    /// VULNERABILITY: No `has_one` constraint to validate vault ownership
-    #[account(mut)]
-    pub vault: Account<'info, Vault>,
-    
-    /// VULNERABILITY: We require a signer but don't validate it's the vault owner
-    pub owner: Signer<'info>,
-}
-
-// ========================================
-// SECURE ACCOUNT CONTEXTS
-// ========================================
-// These contexts use proper Anchor constraints for security
-
-#[derive(Accounts)]
-pub struct SecureInitialize<'info> {
-    /// SECURITY: `init` constraint ensures proper initialization and prevents
-    /// reinitialization attacks. `payer` and `space` are required for init.
-    #[account(
-        init,
-        payer = payer,
-        space = 8 + 32 + 8, // discriminator + owner + balance
-        has_one = owner
-    )]
-    pub vault: Account<'info, Vault>,
-    
-    /// SECURITY: Must be a signer to prove ownership authorization
-    pub owner: Signer<'info>,
-    
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct SecureDeposit<'info> {
-    /// SECURITY: `has_one = owner` constraint validates that vault.owner
-    /// matches the owner account passed in the context
-    #[account(
-        mut,
-        has_one = owner
-    )]
-    pub vault: Account<'info, Vault>,
-    
-    /// SECURITY: Must be a signer and must match vault.owner
-    pub owner: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct SecureWithdraw<'info> {
-    /// SECURITY: `has_one = owner` constraint validates that vault.owner
-    /// matches the owner account passed in the context
-    #[account(
-        mut,
-        has_one = owner
-    )]
-    pub vault: Account<'info, Vault>,
-    
-    /// SECURITY: Must be a signer and must match vault.owner
-    pub owner: Signer<'info>,
-}
+    // ========================================
+    // SERIALIZATION COMPARISON
+    // ========================================
+    // The same deposit over a vault in each encoding of `serialization`,
+    // so the compute units each costs can be compared.
+
+    /// Deposit `amount` into a Borsh vault, decoded and re-encoded whole
+    pub fn borsh_deposit(ctx: Context<EncodedDeposit>, amount: u64) -> Result<()> {
+        let mut data = ctx.accounts.vault.try_borrow_mut_data()?;
+        let mut vault = BorshVault::decode(&data)?;
+        vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+        vault.encode(&mut data)
+    }
 
-// ========================================
-// ACCOUNT STRUCTURES
-// ========================================
+    /// Deposit `amount` into a zero-copy vault, in place
+    pub fn zero_copy_deposit(ctx: Context<EncodedDeposit>, amount: u64) -> Result<()> {
+        let mut data = ctx.accounts.vault.try_borrow_mut_data()?;
+        let vault = ZeroCopyVault::load_mut(&mut data)?;
+        vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
 
-#[account]
-pub struct Vault {
-    /// The owner of this vault (32 bytes)
-    pub owner: Pubkey,
-    /// The current balance in lamports (8 bytes)
-    pub balance: u64,
+    /// Deposit `amount` into a vault packed by hand, unpacked and packed whole
+    pub fn packed_deposit(ctx: Context<EncodedDeposit>, amount: u64) -> Result<()> {
+        let mut data = ctx.accounts.vault.try_borrow_mut_data()?;
+        let mut vault = PackedVault::unpack(&data)?;
+        vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+        vault.pack(&mut data)
+    }
 }
-
-// ========================================
-// ERROR DEFINITIONS
-// ========================================
-
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Insufficient funds in vault")]
-    InsufficientFunds,
-    #[msg("Arithmetic overflow")]
-    ArithmeticOverflow,
-    #[msg("Arithmetic underflow")]
-    ArithmeticUnderflow,
-}
\ No newline at end of file
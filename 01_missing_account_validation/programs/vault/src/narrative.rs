@@ -0,0 +1,89 @@
+//! The attack this example teaches, as data (see `shared/secref-narrative`)
+
+use secref_prelude::narrative::{Account::*, Effect, Expect, Meta::*, Value::*, *};
+
+/// Mallory withdraws from Alice's vault by signing as herself
+pub fn attack_narrative() -> Narrative {
+    Narrative {
+        title: "Withdrawing from someone else's vault",
+        program: "vault",
+        summary: "The vulnerable withdrawal never compares the signer with the vault's owner, \
+                  so any signer can empty any vault; the secure one checks it with `has_one = owner`.",
+        actors: &[
+            Actor {
+                name: "alice",
+                lamports: 10_000_000_000,
+                role: "a depositor",
+            },
+            Actor {
+                name: "mallory",
+                lamports: 1_000_000_000,
+                role: "the attacker",
+            },
+        ],
+        // `vulnerable_initialize` takes a `zero` account the client created for the program
+        accounts: &[("alices_vault", Zeroed(48)), ("alices_secure_vault", Keypair)],
+        steps: &[
+            Step {
+                actor: "alice",
+                action: "opens a vault holding 1000",
+                instruction: "vulnerable_initialize",
+                accounts: &[Mut("alices_vault"), Read("alice"), SignerMut("alice"), Read("system_program")],
+                args: &[U64(1000)],
+                expect: Expect::Ok,
+                effects: &[],
+            },
+            Step {
+                actor: "mallory",
+                action: "withdraws all of it, signing only as herself",
+                instruction: "vulnerable_withdraw",
+                accounts: &[Mut("alices_vault"), Signer("mallory")],
+                args: &[U64(1000)],
+                expect: Expect::Ok,
+                effects: &[Effect::Field {
+                    account: "alices_vault",
+                    field: "balance",
+                    offset: 32,
+                    value: U64(0),
+                }],
+            },
+            Step {
+                actor: "alice",
+                action: "opens a secure vault, which records her as its owner",
+                instruction: "secure_initialize",
+                accounts: &[
+                    SignerMut("alices_secure_vault"),
+                    Signer("alice"),
+                    SignerMut("alice"),
+                    Read("system_program"),
+                ],
+                args: &[U64(1000)],
+                expect: Expect::Ok,
+                effects: &[],
+            },
+            Step {
+                actor: "mallory",
+                action: "tries the same withdrawal from it",
+                instruction: "secure_withdraw",
+                accounts: &[Mut("alices_secure_vault"), Signer("mallory")],
+                args: &[U64(1000)],
+                expect: Expect::Fails("ConstraintHasOne"),
+                effects: &[],
+            },
+            Step {
+                actor: "alice",
+                action: "withdraws her own balance",
+                instruction: "secure_withdraw",
+                accounts: &[Mut("alices_secure_vault"), Signer("alice")],
+                args: &[U64(1000)],
+                expect: Expect::Ok,
+                effects: &[Effect::Field {
+                    account: "alices_secure_vault",
+                    field: "balance",
+                    offset: 32,
+                    value: U64(0),
+                }],
+            },
+        ],
+    }
+}
@@ -0,0 +1,9 @@
+//! The example's write-up, rendered beside the code by `cargo doc`.
+//!
+//! Compiled only for documentation: this page is the example's README,
+//! and [`exploit`] its EXPLOIT.md.
+#![doc = include_str!("../../../../README.md")]
+#![allow(rustdoc::invalid_rust_codeblocks)]
+
+#[doc = include_str!("../../../../EXPLOIT.md")]
+pub mod exploit {}
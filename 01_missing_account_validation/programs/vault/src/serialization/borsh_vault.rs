@@ -0,0 +1,36 @@
+//! The vault as Borsh, as an Anchor `#[account]` stores it
+
+use super::*;
+
+/// Owner, balance and bump, back to back with no padding
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BorshVault {
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+impl BorshVault {
+    pub const LEN: usize = 32 + 8 + 1;
+
+    /// CARELESS: Read the vault off the front of `data`, as `AccountDeserialize` does
+    ///
+    /// Borsh reads what the type needs and stops. Trailing bytes are never
+    /// looked at, so any account that starts with 41 plausible bytes, a
+    /// vault of another encoding or a longer account of another type,
+    /// reads as a vault.
+    pub fn decode_prefix(data: &[u8]) -> Result<Self> {
+        Self::deserialize(&mut &data[..]).map_err(|_| error!(ErrorCode::InvalidEncoding))
+    }
+
+    /// CAREFUL: Read `data` as exactly one vault; trailing bytes are an error
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        Self::try_from_slice(data).map_err(|_| error!(ErrorCode::InvalidEncoding))
+    }
+
+    /// Write the vault over `data`, which must be exactly [`LEN`](Self::LEN) bytes
+    pub fn encode(&self, data: &mut [u8]) -> Result<()> {
+        require_eq!(data.len(), Self::LEN, ErrorCode::InvalidEncoding);
+        self.serialize(&mut &mut data[..]).map_err(|_| error!(ErrorCode::InvalidEncoding))
+    }
+}
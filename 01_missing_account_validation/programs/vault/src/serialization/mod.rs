@@ -0,0 +1,30 @@
+//! The vault three ways: Borsh, zero-copy, and bytes packed by hand.
+//!
+//! Each module stores the same `owner`, `balance` and `bump`, and each
+//! encoding fails in a way of its own when read or written carelessly. The
+//! careless version is kept beside the careful one:
+//!
+//! - [`borsh_vault`], 41 bytes: `decode_prefix` reads a vault off the
+//!   front of any longer account and ignores the rest, where `decode`
+//!   rejects trailing bytes
+//! - [`zero_copy_vault`], 48 bytes: `reassign` writes the fields and
+//!   leaves the 7 bytes of padding holding whatever they held, where
+//!   `reset` writes every byte
+//! - [`packed_vault`], 41 bytes: `unpack_partial` defaults the fields the
+//!   data is too short for, where `unpack` requires the exact length
+//!
+//! None of them stores a discriminator: each is the whole of an account
+//! the client created for the program. `borsh_deposit`,
+//! `zero_copy_deposit` and `packed_deposit` each add to a vault in one
+//! encoding, read and written the careful way, so the exploit suite
+//! records what each costs in compute units.
+
+use super::*;
+
+pub mod borsh_vault;
+pub mod packed_vault;
+pub mod zero_copy_vault;
+
+pub use borsh_vault::BorshVault;
+pub use packed_vault::PackedVault;
+pub use zero_copy_vault::ZeroCopyVault;
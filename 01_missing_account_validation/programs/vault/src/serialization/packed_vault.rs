@@ -0,0 +1,45 @@
+//! The vault packed by hand, field by field at fixed offsets
+
+use super::*;
+
+/// Owner, balance and bump at offsets 0, 32 and 40
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PackedVault {
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+impl PackedVault {
+    pub const LEN: usize = 32 + 8 + 1;
+
+    /// CARELESS: Read the vault, defaulting the fields `data` is too short for
+    ///
+    /// This is how a reader written for the 40-byte vault is extended when
+    /// `bump` is added: the old length check stays, and the new field is
+    /// read if it is there. A vault of the old layout, or any 40 bytes at
+    /// all, reads as a vault with bump 0, and trailing bytes are ignored.
+    pub fn unpack_partial(data: &[u8]) -> Result<Self> {
+        require_gte!(data.len(), 40, ErrorCode::InvalidEncoding);
+        Ok(Self {
+            owner: Pubkey::try_from(&data[..32]).map_err(|_| error!(ErrorCode::InvalidEncoding))?,
+            balance: u64::from_le_bytes(data[32..40].try_into().map_err(|_| error!(ErrorCode::InvalidEncoding))?),
+            bump: data.get(40).copied().unwrap_or(0),
+        })
+    }
+
+    /// CAREFUL: Read `data` as exactly one vault
+    pub fn unpack(data: &[u8]) -> Result<Self> {
+        require_eq!(data.len(), Self::LEN, ErrorCode::InvalidEncoding);
+        Self::unpack_partial(data)
+    }
+
+    /// Write the vault over `data`, which must be exactly [`LEN`](Self::LEN) bytes
+    pub fn pack(&self, data: &mut [u8]) -> Result<()> {
+        require_eq!(data.len(), Self::LEN, ErrorCode::InvalidEncoding);
+        data[..32].copy_from_slice(self.owner.as_ref());
+        data[32..40].copy_from_slice(&self.balance.to_le_bytes());
+        data[40] = self.bump;
+        Ok(())
+    }
+}
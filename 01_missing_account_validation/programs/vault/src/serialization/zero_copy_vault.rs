@@ -0,0 +1,54 @@
+//! The vault as a zero-copy `#[repr(C)]` struct, as `#[account(zero_copy)]` stores it
+
+use bytemuck::{Pod, Zeroable};
+
+use super::*;
+
+/// Owner, balance and bump, laid out as in memory
+///
+/// `#[repr(C)]` rounds the struct up to its 8-byte alignment, so 7 bytes
+/// follow `bump`. `Pod` refuses implicit padding, so they are a field:
+/// they are stored, read and written like any other, but no code gives
+/// them a value unless it writes the whole struct.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Pod, Zeroable)]
+pub struct ZeroCopyVault {
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+    pub padding: [u8; 7],
+}
+
+impl ZeroCopyVault {
+    pub const LEN: usize = std::mem::size_of::<Self>();
+
+    /// The vault in `data`, in place: exactly [`LEN`](Self::LEN) bytes, 8-byte aligned
+    ///
+    /// Every bit pattern is a valid vault, so nothing about the bytes
+    /// themselves is checked: a zeroed account is a vault owned by the
+    /// default key.
+    pub fn load_mut(data: &mut [u8]) -> Result<&mut Self> {
+        bytemuck::try_from_bytes_mut(data).map_err(|_| error!(ErrorCode::InvalidEncoding))
+    }
+
+    /// CARELESS: Hand the vault to `owner`, field by field
+    ///
+    /// The padding keeps whatever it held: bytes of a closed account of
+    /// another type, or of a layout that stored a field there. They are
+    /// still in the account for anyone to read, and a later version that
+    /// gives them a meaning reads them back as set.
+    pub fn reassign(&mut self, owner: Pubkey, bump: u8) {
+        self.owner = owner;
+        self.balance = 0;
+        self.bump = bump;
+    }
+
+    /// CAREFUL: Hand the vault to `owner` as a whole new value, padding zeroed
+    pub fn reset(&mut self, owner: Pubkey, bump: u8) {
+        *self = Self {
+            owner,
+            bump,
+            ..Self::zeroed()
+        };
+    }
+}
@@ -0,0 +1,148 @@
+//! The vault in each encoding of `serialization`: the careless read or
+//! write of each and what it lets through, the careful one beside it, then
+//! the deposit each encoding's compute units are recorded for.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{execute, Fixture, TestAccount};
+
+use crate::{instruction, BorshVault, ErrorCode as VaultError, PackedVault, ZeroCopyVault};
+
+const OWNER: Pubkey = Pubkey::new_from_array([7; 32]);
+
+fn invalid() -> Error {
+    VaultError::InvalidEncoding.into()
+}
+
+/// Whole `u64`s, so the bytes are 8-byte aligned as account data is
+fn aligned(len: usize) -> Vec<u64> {
+    vec![0; len.div_ceil(8)]
+}
+
+#[test]
+fn borsh_prefix_reads_accept_trailing_bytes() {
+    let vault = BorshVault {
+        owner: OWNER,
+        balance: 1000,
+        bump: 254,
+    };
+    let mut data = vec![0; BorshVault::LEN];
+    vault.encode(&mut data).unwrap();
+    assert_eq!(BorshVault::decode(&data).unwrap(), vault);
+
+    // A zero-copy vault starts with the same 41 bytes, and a prefix read stops there
+    let zero_copy = ZeroCopyVault {
+        owner: OWNER,
+        balance: 1000,
+        bump: 254,
+        padding: [0xab; 7],
+    };
+    let longer = bytemuck::bytes_of(&zero_copy);
+    assert_eq!(BorshVault::decode_prefix(longer).unwrap(), vault);
+    assert_eq!(BorshVault::decode(longer).unwrap_err(), invalid());
+
+    // Too short is an error either way: Borsh never fills a field in
+    assert_eq!(BorshVault::decode_prefix(&data[..40]).unwrap_err(), invalid());
+    assert_eq!(vault.encode(&mut [0; 48]).unwrap_err(), invalid());
+}
+
+#[test]
+fn zero_copy_field_writes_leave_the_padding_behind() {
+    let mut words = aligned(ZeroCopyVault::LEN);
+    let data: &mut [u8] = bytemuck::cast_slice_mut(&mut words);
+    // What the previous occupant left where the padding now is
+    data[41..].copy_from_slice(b"secret!");
+
+    let vault = ZeroCopyVault::load_mut(data).unwrap();
+    vault.reassign(OWNER, 254);
+    assert_eq!((vault.owner, vault.balance, vault.bump), (OWNER, 0, 254));
+    assert_eq!(&data[41..], b"secret!");
+
+    let vault = ZeroCopyVault::load_mut(data).unwrap();
+    vault.reset(OWNER, 254);
+    assert_eq!(&data[41..], &[0; 7]);
+}
+
+#[test]
+fn zero_copy_views_need_exact_aligned_bytes() {
+    assert_eq!(ZeroCopyVault::LEN, 48);
+    let mut words = aligned(ZeroCopyVault::LEN + 8);
+    let data: &mut [u8] = bytemuck::cast_slice_mut(&mut words);
+    assert_eq!(ZeroCopyVault::load_mut(&mut data[..BorshVault::LEN]).unwrap_err(), invalid());
+    assert_eq!(ZeroCopyVault::load_mut(&mut data[1..49]).unwrap_err(), invalid());
+
+    // Any 48 aligned bytes are a vault: zeroes are one owned by the default key
+    let vault = ZeroCopyVault::load_mut(&mut data[8..]).unwrap();
+    assert_eq!(vault.owner, Pubkey::default());
+}
+
+#[test]
+fn packed_partial_reads_accept_the_old_layout() {
+    // The 40-byte vault, before `bump` was added
+    let mut old = OWNER.to_bytes().to_vec();
+    old.extend_from_slice(&1000u64.to_le_bytes());
+
+    let read = PackedVault::unpack_partial(&old).unwrap();
+    assert_eq!((read.owner, read.balance, read.bump), (OWNER, 1000, 0));
+    assert_eq!(PackedVault::unpack(&old).unwrap_err(), invalid());
+
+    let mut trailing = old.clone();
+    trailing.extend_from_slice(&[254, 1, 2, 3]);
+    assert_eq!(PackedVault::unpack_partial(&trailing).unwrap().bump, 254);
+    assert_eq!(PackedVault::unpack(&trailing).unwrap_err(), invalid());
+
+    let mut data = vec![0; PackedVault::LEN];
+    let vault = PackedVault { bump: 254, ..read };
+    vault.pack(&mut data).unwrap();
+    assert_eq!(PackedVault::unpack(&data).unwrap(), vault);
+    assert_eq!(&data[..41], &trailing[..41]);
+}
+
+#[test]
+fn each_encoding_deposits_into_its_own_layout() {
+    let borsh = BorshVault {
+        owner: OWNER,
+        balance: 1000,
+        bump: 254,
+    };
+    let mut bytes = vec![0; BorshVault::LEN];
+    borsh.encode(&mut bytes).unwrap();
+    let mut fixture = Fixture::new().with("vault", TestAccount::new(crate::ID, bytes.clone()));
+    execute!(&mut fixture, instruction::BorshDeposit { amount: 500 }).unwrap();
+    assert_eq!(BorshVault::decode(&fixture.account("vault").data).unwrap().balance, 1500);
+
+    let mut fixture = Fixture::new().with("vault", TestAccount::new(crate::ID, bytes.clone()));
+    execute!(&mut fixture, instruction::PackedDeposit { amount: 500 }).unwrap();
+    assert_eq!(PackedVault::unpack(&fixture.account("vault").data).unwrap().balance, 1500);
+
+    let zero_copy = ZeroCopyVault {
+        owner: OWNER,
+        balance: 1000,
+        bump: 254,
+        padding: [0; 7],
+    };
+    let mut fixture =
+        Fixture::new().with("vault", TestAccount::new(crate::ID, bytemuck::bytes_of(&zero_copy).to_vec()));
+    execute!(&mut fixture, instruction::ZeroCopyDeposit { amount: 500 }).unwrap();
+    let data = &fixture.account("vault").data;
+    assert_eq!(bytemuck::pod_read_unaligned::<ZeroCopyVault>(data).balance, 1500);
+
+    // Each careful read refuses the other encodings' bytes
+    assert_eq!(
+        execute!(&mut fixture.clone(), instruction::BorshDeposit { amount: 500 }),
+        Err(invalid().into())
+    );
+    let fixture = Fixture::new().with("vault", TestAccount::new(crate::ID, bytes));
+    assert_eq!(
+        execute!(&mut fixture.clone(), instruction::ZeroCopyDeposit { amount: 500 }),
+        Err(invalid().into())
+    );
+}
+
+#[test]
+fn deposits_only_touch_the_programs_accounts() {
+    let mut fixture = Fixture::new().with("vault", TestAccount::new(Pubkey::new_unique(), vec![0; PackedVault::LEN]));
+    assert_eq!(
+        execute!(&mut fixture, instruction::PackedDeposit { amount: 500 }),
+        Err(Error::from(anchor_lang::error::ErrorCode::ConstraintOwner).into())
+    );
+}
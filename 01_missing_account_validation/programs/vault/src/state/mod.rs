@@ -0,0 +1,11 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Vault {
+    /// The owner of this vault (32 bytes)
+    pub owner: Pubkey,
+    /// The current balance in lamports (8 bytes)
+    pub balance: u64,
+}
@@ -2,6 +2,9 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Vault } from "../target/types/vault";
 import { expect } from "chai";
+import { CuProfiler } from "../../harness/cu-profiler";
+import { PocRecorder } from "../../harness/poc-recorder";
+import { assertAnchorError, parseLogs } from "../../harness/secref-logs";
 import { Connection, PublicKey, Keypair } from "@solana/web3.js";
 
 describe("Missing Account Validation Exploit", () => {
@@ -24,6 +27,16 @@ describe("Missing Account Validation Exploit", () => {
   
   anchor.setProvider(provider);
 
+  // Records compute units for every instruction executed against a validator
+  const profiler = new CuProfiler("01_missing_account_validation");
+
+  // Records every transaction as a replayable PoC when SECREF_POC_RECORD=1
+  const recorder = new PocRecorder("01_missing_account_validation", provider);
+  beforeEach(function () {
+    // Steps are labelled with the test that sent them
+    recorder.test(this.currentTest?.fullTitle());
+  });
+
   // Mock program for testing
   let program: Program<Vault>;
   
@@ -43,6 +56,13 @@ describe("Missing Account Validation Exploit", () => {
     }
   });
 
+  after(() => {
+    recorder.writeFixture();
+    // Fails the suite if any recorded instruction regressed past its CU budget
+    profiler.writeSummary();
+    profiler.assertBudgets();
+  });
+
   beforeEach(async () => {
     // Generate fresh keypairs for each test
     legitimateOwner = anchor.web3.Keypair.generate();
@@ -83,7 +103,7 @@ describe("Missing Account Validation Exploit", () => {
 
       // Real test code would go here if program is available
       try {
-        await program.methods
+        const signature = await program.methods
           .vulnerableInitialize(new anchor.BN(1000))
           .accounts({
             vault: vulnerableVault.publicKey,
@@ -92,6 +112,7 @@ describe("Missing Account Validation Exploit", () => {
           })
           .signers([attacker, vulnerableVault])
           .rpc();
+        await profiler.record("vulnerable_initialize", provider.connection, signature, program.programId.toBase58());
 
         const vaultAccount = await program.account.vault.fetch(vulnerableVault.publicKey);
         console.log(`Vault owner set to: ${vaultAccount.owner.toString()}`);
@@ -151,14 +172,21 @@ describe("Missing Account Validation Exploit", () => {
         console.log("   2. Validates vault.owner matches signer");
         console.log("   3. Prevents unauthorized access");
         
-        // Mock the secure behavior
-        const mockError = "ConstraintHasOne: has_one constraint was violated";
+        // Mock the secure behavior: the logs a validator returns for the rejected transaction
+        const mockLogs = [
+          "Program Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS invoke [1]",
+          "Program log: Instruction: SecureWithdraw",
+          "Program log: AnchorError caused by account: vault. Error Code: ConstraintHasOne. Error Number: 2001. Error Message: A has one constraint was violated.",
+          "Program Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS consumed 4521 of 200000 compute units",
+          "Program Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS failed: custom program error: 0x7d1",
+        ];
+        const mockError = assertAnchorError(parseLogs(mockLogs), "ConstraintHasOne", "vault");
         
         console.log("🛡️  Attempting unauthorized access...");
-        console.log(`Expected error: ${mockError}`);
+        console.log(`Expected error: ${mockError.name} (${mockError.number})`);
         console.log("✅ PROTECTION SUCCESS: Unauthorized access blocked");
         
-        expect(mockError).to.include("has_one");
+        expect(mockError.number).to.equal(2001);
         return;
       }
 
@@ -201,6 +229,51 @@ describe("Missing Account Validation Exploit", () => {
     });
   });
 
+  describe("📦 SERIALIZATION COMPARISON", () => {
+    // The same vault in each encoding, and the size of its account
+    const encodings: [string, number][] = [
+      ["borsh_deposit", 41],
+      ["zero_copy_deposit", 48],
+      ["packed_deposit", 41],
+    ];
+
+    it("Should record what a deposit costs in each encoding", async () => {
+      if (!program) {
+        console.log("📝 MOCK TEST: Each encoding deposits into its own layout");
+        for (const [instruction, space] of encodings) {
+          console.log(`   ${instruction}: ${space}-byte vault`);
+        }
+        expect(encodings.map(([, space]) => space)).to.deep.equal([41, 48, 41]);
+        return;
+      }
+
+      try {
+        for (const [instruction, space] of encodings) {
+          // A zeroed account is a valid empty vault in all three encodings
+          const vault = anchor.web3.Keypair.generate();
+          const lamports = await provider.connection.getMinimumBalanceForRentExemption(space);
+          const create = anchor.web3.SystemProgram.createAccount({
+            fromPubkey: wallet.publicKey,
+            newAccountPubkey: vault.publicKey,
+            space,
+            lamports,
+            programId: program.programId,
+          });
+          const method = instruction.replace(/_(\w)/g, (_, c) => c.toUpperCase());
+          const signature = await program.methods[method](new anchor.BN(500))
+            .accounts({ vault: vault.publicKey })
+            .preInstructions([create])
+            .signers([vault])
+            .rpc();
+          await profiler.record(instruction, provider.connection, signature, program.programId.toBase58());
+          console.log(`✅ ${instruction} into a ${space}-byte vault`);
+        }
+      } catch (error) {
+        console.log(`⚠️  Test requires local validator: ${error.message}`);
+      }
+    });
+  });
+
   describe("📚 EDUCATIONAL SUMMARY", () => {
     it("Should summarize the vulnerability and protection", async () => {
       console.log("\n=== VULNERABILITY SUMMARY ===");
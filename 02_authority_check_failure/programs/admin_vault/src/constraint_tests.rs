@@ -0,0 +1,100 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::runtime::account_already_in_use;
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_owner_violation, assert_signer_violation, Fixture,
+    TestAccount,
+};
+
+use crate::{AdminVault, SecureAdminWithdraw, SecureChangeAdmin, SecureEmergencyDrain, SecureInitialize};
+
+fn initialize_fixture() -> Fixture {
+    Fixture::new()
+        .with("vault", TestAccount::uninitialized(8 + 32 + 32 + 8).signed())
+        .with("admin", TestAccount::signer())
+        .with("owner", TestAccount::signer())
+        .with("payer", TestAccount::signer())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn admin_fixture() -> Fixture {
+    let admin = TestAccount::signer();
+    let vault = TestAccount::anchor(&AdminVault {
+        admin: admin.key,
+        owner: Pubkey::new_unique(),
+        balance: 0,
+    });
+    Fixture::new().with("vault", vault).with("admin", admin)
+}
+
+#[test]
+fn secure_initialize_rejects_existing_vaults() {
+    let existing = TestAccount::anchor(&AdminVault {
+        admin: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        balance: 0,
+    })
+    .signed();
+    assert_constraint_violation!(SecureInitialize, initialize_fixture(), "vault" => existing, account_already_in_use());
+}
+
+#[test]
+fn secure_initialize_requires_an_admin_signature() {
+    assert_signer_violation!(SecureInitialize, initialize_fixture(), "admin");
+}
+
+#[test]
+fn secure_initialize_requires_an_owner_signature() {
+    assert_signer_violation!(SecureInitialize, initialize_fixture(), "owner");
+}
+
+#[test]
+fn secure_admin_withdraw_requires_the_vault_admin() {
+    assert_has_one_violation!(SecureAdminWithdraw, admin_fixture(), "admin");
+}
+
+#[test]
+fn secure_admin_withdraw_requires_an_admin_signature() {
+    assert_signer_violation!(SecureAdminWithdraw, admin_fixture(), "admin");
+}
+
+#[test]
+fn secure_admin_withdraw_rejects_vaults_of_other_programs() {
+    assert_owner_violation!(SecureAdminWithdraw, admin_fixture(), "vault");
+}
+
+#[test]
+fn secure_emergency_drain_requires_the_vault_admin() {
+    assert_has_one_violation!(SecureEmergencyDrain, admin_fixture(), "admin");
+}
+
+#[test]
+fn secure_emergency_drain_requires_an_admin_signature() {
+    assert_signer_violation!(SecureEmergencyDrain, admin_fixture(), "admin");
+}
+
+#[test]
+fn secure_emergency_drain_rejects_vaults_of_other_programs() {
+    assert_owner_violation!(SecureEmergencyDrain, admin_fixture(), "vault");
+}
+
+#[test]
+fn secure_change_admin_requires_the_vault_admin() {
+    assert_has_one_violation!(
+        SecureChangeAdmin,
+        admin_fixture(),
+        "admin",
+        crate::ErrorCode::UnauthorizedAdmin
+    );
+}
+
+#[test]
+fn secure_change_admin_requires_an_admin_signature() {
+    assert_signer_violation!(SecureChangeAdmin, admin_fixture(), "admin");
+}
+
+#[test]
+fn secure_change_admin_rejects_vaults_of_other_programs() {
+    assert_owner_violation!(SecureChangeAdmin, admin_fixture(), "vault");
+}
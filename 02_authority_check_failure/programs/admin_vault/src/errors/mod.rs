@@ -0,0 +1,29 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+    #[msg("Arithmetic underflow")]
+    ArithmeticUnderflow,
+    #[msg("Unauthorized admin - signer is not the vault admin")]
+    UnauthorizedAdmin,
+    #[msg("Approvers must be between 1 and 8 distinct keys")]
+    InvalidApprovers,
+    #[msg("Threshold must be between 1 and the number of approvers")]
+    InvalidThreshold,
+    #[msg("Signer is not one of the multisig's approvers")]
+    NotAnApprover,
+    #[msg("Withdrawal does not have enough approvals")]
+    NotEnoughApprovals,
+    #[msg("Approval does not match the proposal's contents")]
+    ProposalMismatch,
+    #[msg("Approver has already approved this proposal")]
+    AlreadyApproved,
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+}
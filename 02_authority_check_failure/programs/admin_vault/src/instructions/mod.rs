@@ -0,0 +1,57 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializeMultisig<'info> {
+    #[account(has_one = admin @ ErrorCode::UnauthorizedAdmin)]
+    pub vault: Account<'info, AdminVault>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Multisig::SPACE,
+        seeds = [b"multisig", vault.key().as_ref()],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SecureInitialize<'info> {
+    /// SECURITY: `init` constraint ensures proper initialization
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 32 + 8, // discriminator + admin + owner + balance
+    )]
+    pub vault: Account<'info, AdminVault>,
+    
+    /// SECURITY: Admin must be a signer to prove they authorize this vault creation
+    pub admin: Signer<'info>,
+    
+    /// SECURITY: Owner should also be a signer to authorize vault creation
+    pub owner: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,105 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+// These contexts use proper Anchor constraints for authority validation
+
+#[derive(Accounts)]
+pub struct SecureAdminWithdraw<'info> {
+    /// SECURITY: `has_one = admin` constraint validates that vault.admin
+    /// matches the admin account passed in the context
+    #[account(
+        mut,
+        has_one = admin
+    )]
+    pub vault: Account<'info, AdminVault>,
+    
+    /// SECURITY: Must be a signer AND must match vault.admin
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SecureEmergencyDrain<'info> {
+    /// SECURITY: `has_one = admin` constraint validates admin authority
+    #[account(
+        mut,
+        has_one = admin
+    )]
+    pub vault: Account<'info, AdminVault>,
+    
+    /// SECURITY: Must be a signer AND must match vault.admin
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SecureChangeAdmin<'info> {
+    /// SECURITY: `has_one = admin` constraint validates current admin authority
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub vault: Account<'info, AdminVault>,
+    
+    /// SECURITY: Must be a signer AND must match vault.admin
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SecureProposeWithdraw<'info> {
+    #[account(mut, seeds = [b"multisig", multisig.vault.as_ref()], bump = multisig.bump)]
+    pub multisig: Account<'info, Multisig>,
+
+    /// SECURITY: One account per proposal, at the next unused index
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::SPACE,
+        seeds = [b"proposal", multisig.key().as_ref(), &multisig.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Must be one of the multisig's approvers
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SecureApproveWithdraw<'info> {
+    #[account(seeds = [b"multisig", multisig.vault.as_ref()], bump = multisig.bump)]
+    pub multisig: Account<'info, Multisig>,
+
+    /// SECURITY: The approval is recorded on this proposal, which must be the multisig's
+    #[account(
+        mut,
+        has_one = multisig,
+        seeds = [b"proposal", multisig.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Must be one of the multisig's approvers
+    pub approver: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SecureExecuteWithdraw<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, AdminVault>,
+
+    #[account(has_one = vault, seeds = [b"multisig", vault.key().as_ref()], bump = multisig.bump)]
+    pub multisig: Account<'info, Multisig>,
+
+    /// SECURITY: The approved proposal supplies the amount
+    #[account(
+        mut,
+        has_one = multisig,
+        seeds = [b"proposal", multisig.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Must be one of the multisig's approvers
+    pub executor: Signer<'info>,
+}
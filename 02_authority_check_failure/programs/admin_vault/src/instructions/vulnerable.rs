@@ -0,0 +1,88 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+// These contexts lack proper authority validation constraints
+
+#[derive(Accounts)]
+pub struct VulnerableInitialize<'info> {
+    /// VULNERABILITY: Using `zero` constraint instead of `init` for demonstration
+    #[account(zero)]
+    pub vault: Account<'info, AdminVault>,
+    
+    /// VULNERABILITY: Admin is a signer but we don't validate their authority
+    /// to create vaults. Any signer can become an admin.
+    pub admin: Signer<'info>,
+    
+    /// VULNERABILITY: Owner doesn't need to be a signer, which could lead to
+    /// unauthorized vault creation for other users
+    /// CHECK: This is intentionally unsafe for demonstration
+    pub owner: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableAdminWithdraw<'info> {
+    /// VULNERABILITY: No `has_one` constraint to validate admin authority
+    #[account(mut)]
+    pub vault: Account<'info, AdminVault>,
+    
+    /// VULNERABILITY: We require a signer but don't validate it's THE admin
+    /// of THIS vault. Any signer can potentially act as admin.
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableEmergencyDrain<'info> {
+    /// VULNERABILITY: No `has_one` constraint to validate admin authority
+    #[account(mut)]
+    pub vault: Account<'info, AdminVault>,
+    
+    /// VULNERABILITY: Any signer can execute emergency drain on any vault
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableChangeAdmin<'info> {
+    /// VULNERABILITY: No `has_one` constraint to validate current admin authority
+    #[account(mut)]
+    pub vault: Account<'info, AdminVault>,
+    
+    /// VULNERABILITY: Any signer can change the admin of any vault
+    pub current_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableProposeWithdraw<'info> {
+    #[account(mut, seeds = [b"multisig", multisig.vault.as_ref()], bump = multisig.bump)]
+    pub multisig: Account<'info, Multisig>,
+
+    /// Must be one of the multisig's approvers
+    pub proposer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableApproveWithdraw<'info> {
+    /// VULNERABILITY: The approval lands on the multisig, not on a proposal
+    #[account(mut, seeds = [b"multisig", multisig.vault.as_ref()], bump = multisig.bump)]
+    pub multisig: Account<'info, Multisig>,
+
+    /// Must be one of the multisig's approvers
+    pub approver: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableExecuteWithdraw<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, AdminVault>,
+
+    /// VULNERABILITY: No proposal account; the approvals are the multisig's
+    #[account(has_one = vault, seeds = [b"multisig", vault.key().as_ref()], bump = multisig.bump)]
+    pub multisig: Account<'info, Multisig>,
+
+    /// Must be one of the multisig's approvers
+    pub executor: Signer<'info>,
+}
@@ -0,0 +1,44 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{AdminVault, Multisig, Proposal};
+
+#[test]
+fn admin_vault_layout_is_stable() {
+    let admin_vault = AdminVault {
+        admin: Pubkey::new_from_array([1; 32]),
+        owner: Pubkey::new_from_array([2; 32]),
+        balance: 0x3132333435363738,
+    };
+    assert_golden!("AdminVault", golden::anchor(&admin_vault));
+}
+
+#[test]
+fn multisig_layout_is_stable() {
+    let multisig = Multisig {
+        vault: Pubkey::new_from_array([4; 32]),
+        approvers: vec![Pubkey::new_from_array([5; 32]), Pubkey::new_from_array([6; 32])],
+        threshold: 113,
+        pending_hash: [129; 32],
+        approvals: 145,
+        proposal_count: 0xa1a2a3a4a5a6a7a8,
+        bump: 177,
+    };
+    assert_golden!("Multisig", golden::anchor(&multisig));
+}
+
+#[test]
+fn proposal_layout_is_stable() {
+    let proposal = Proposal {
+        multisig: Pubkey::new_from_array([12; 32]),
+        index: 0xd1d2d3d4d5d6d7d8,
+        amount: 0xe1e2e3e4e5e6e7e8,
+        proposer: Pubkey::new_from_array([15; 32]),
+        approvals: 1,
+        executed: true,
+        bump: 17,
+    };
+    assert_golden!("Proposal", golden::anchor(&proposal));
+}
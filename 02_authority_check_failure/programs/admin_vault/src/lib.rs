@@ -1,11 +1,63 @@
-use anchor_lang::prelude::*;
+use secref_prelude::*;
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
+pub mod errors;
+pub mod instructions;
+pub mod narrative;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use narrative::attack_narrative;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod multisig_tests;
+
+#[feature_gated]
 #[program]
 pub mod admin_vault {
     use super::*;
 
+    /// Put withdrawals from a vault behind `threshold` of `approvers`
+    ///
+    /// Only the vault's admin can create its multisig. From then on the
+    /// multisig withdraw flows below need approvals from the set, whoever
+    /// the admin is.
+    pub fn initialize_multisig(ctx: Context<InitializeMultisig>, approvers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        require!(
+            !approvers.is_empty() && approvers.len() <= Multisig::MAX_APPROVERS,
+            ErrorCode::InvalidApprovers
+        );
+        for (index, approver) in approvers.iter().enumerate() {
+            require!(!approvers[..index].contains(approver), ErrorCode::InvalidApprovers);
+        }
+        require!(
+            threshold > 0 && usize::from(threshold) <= approvers.len(),
+            ErrorCode::InvalidThreshold
+        );
+
+        let multisig = &mut ctx.accounts.multisig;
+        multisig.vault = ctx.accounts.vault.key();
+        multisig.approvers = approvers;
+        multisig.threshold = threshold;
+        multisig.pending_hash = [0; 32];
+        multisig.approvals = 0;
+        multisig.proposal_count = 0;
+        multisig.bump = ctx.bumps.multisig;
+
+        msg!("Multisig for vault {} requires {} of {} approvers",
+             multisig.vault, threshold, multisig.approvers.len());
+        Ok(())
+    }
+
     // ========================================
     // VULNERABLE IMPLEMENTATION
     // ========================================
@@ -17,6 +69,7 @@ pub mod admin_vault {
     /// Security Issue: This function sets up an admin vault but doesn't properly
     /// validate that the admin account has the authority to manage this vault.
     /// The vulnerability lies in the distinction between being a signer and having authority.
+    #[vulnerable]
     pub fn vulnerable_initialize(ctx: Context<VulnerableInitialize>, initial_balance: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -38,6 +91,7 @@ pub mod admin_vault {
     /// someone signed the transaction (Signer<'info>), but it doesn't verify that
     /// the signer is actually THE admin of THIS specific vault. Any signer can
     /// potentially withdraw funds if they can pass themselves as the admin.
+    #[vulnerable]
     pub fn vulnerable_admin_withdraw(ctx: Context<VulnerableAdminWithdraw>, amount: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -59,6 +113,7 @@ pub mod admin_vault {
     /// but it only validates that someone signed the transaction, not that they
     /// are the actual admin of the vault. This is a common pattern where developers
     /// confuse "being a signer" with "having authority".
+    #[vulnerable]
     pub fn vulnerable_emergency_drain(ctx: Context<VulnerableEmergencyDrain>) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -77,6 +132,7 @@ pub mod admin_vault {
     /// Security Issue: This function allows changing the admin but only validates
     /// that someone signed the transaction. It doesn't verify that the current
     /// signer is actually the current admin of the vault.
+    #[vulnerable]
     pub fn vulnerable_change_admin(ctx: Context<VulnerableChangeAdmin>, new_admin: Pubkey) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -89,6 +145,63 @@ pub mod admin_vault {
         Ok(())
     }
 
+    /// VULNERABLE: Propose a multisig withdrawal of `amount`
+    ///
+    /// Security Issue: The proposal is recorded only as a hash on the
+    /// multisig, and nothing that follows ever checks it. Approvals already
+    /// given for an earlier proposal carry over to this one.
+    #[vulnerable]
+    pub fn vulnerable_propose_withdraw(ctx: Context<VulnerableProposeWithdraw>, amount: u64) -> Result<()> {
+        let multisig = &mut ctx.accounts.multisig;
+        multisig.approver_bit(&ctx.accounts.proposer.key())?;
+
+        let index = multisig.proposal_count;
+        multisig.pending_hash = Proposal::hash(&multisig.key(), index, amount);
+        multisig.proposal_count = index.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Withdrawal {} of {} proposed", index, amount);
+        Ok(())
+    }
+
+    /// VULNERABLE: Approve "the" pending withdrawal
+    ///
+    /// Security Issue: The approval is a bit on the multisig. It names no
+    /// proposal and no amount, so it approves whatever is executed next -
+    /// and everything executed after that, since it is never cleared.
+    #[vulnerable]
+    pub fn vulnerable_approve_withdraw(ctx: Context<VulnerableApproveWithdraw>) -> Result<()> {
+        let multisig = &mut ctx.accounts.multisig;
+        let bit = multisig.approver_bit(&ctx.accounts.approver.key())?;
+
+        // VULNERABILITY: Nothing ties this approval to multisig.pending_hash
+        multisig.approvals |= bit;
+
+        msg!("Withdrawal approved by {}", ctx.accounts.approver.key());
+        Ok(())
+    }
+
+    /// VULNERABLE: Execute a withdrawal once enough approvals are in
+    ///
+    /// Security Issue: The amount is an argument, not the proposal's. Any
+    /// approver can propose 1 lamport, collect approvals, then execute a
+    /// withdrawal of the whole balance - and execute it again and again.
+    #[vulnerable]
+    pub fn vulnerable_execute_withdraw(ctx: Context<VulnerableExecuteWithdraw>, amount: u64) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        multisig.approver_bit(&ctx.accounts.executor.key())?;
+        require!(multisig.approvals.count_ones() >= u32::from(multisig.threshold), ErrorCode::NotEnoughApprovals);
+
+        // VULNERABILITY: `amount` is never hashed and compared with pending_hash,
+        // and the approvals are left in place for the next execution
+        let vault = &mut ctx.accounts.vault;
+        require!(vault.balance >= amount, ErrorCode::InsufficientFunds);
+        vault.balance = vault.balance.checked_sub(amount)
+            .ok_or(ErrorCode::ArithmeticUnderflow)?;
+
+        msg!("Multisig withdrew {} from vault. New balance: {}", amount, vault.balance);
+        Ok(())
+    }
+
     // ========================================
     // SECURE IMPLEMENTATION
     // ========================================
@@ -100,6 +213,7 @@ pub mod admin_vault {
     /// Security Fix: Uses Anchor's `init` constraint for proper initialization
     /// and establishes the relationship between vault and admin that will be
     /// validated in subsequent operations.
+    // Built with either feature: the CTF server seeds its flag vaults through it
     pub fn secure_initialize(ctx: Context<SecureInitialize>, initial_balance: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -118,6 +232,7 @@ pub mod admin_vault {
     /// 
     /// Security Fix: Uses `has_one = admin` constraint to ensure that the
     /// signer is actually THE admin of THIS specific vault before allowing withdrawal.
+    #[secure]
     pub fn secure_admin_withdraw(ctx: Context<SecureAdminWithdraw>, amount: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -136,6 +251,7 @@ pub mod admin_vault {
     /// 
     /// Security Fix: Uses `has_one = admin` constraint to ensure only the
     /// actual admin of the vault can execute emergency operations.
+    #[secure]
     pub fn secure_emergency_drain(ctx: Context<SecureEmergencyDrain>) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -152,6 +268,7 @@ pub mod admin_vault {
     /// 
     /// Security Fix: Uses `has_one = admin` constraint to ensure only the
     /// current admin can change the admin of the vault.
+    #[secure]
     pub fn secure_change_admin(ctx: Context<SecureChangeAdmin>, new_admin: Pubkey) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -163,156 +280,75 @@ pub mod admin_vault {
         msg!("Admin securely changed from {} to {}", old_admin, new_admin);
         Ok(())
     }
-}
 
-// ========================================
-// VULNERABLE ACCOUNT CONTEXTS
-// ========================================
-// These contexts lack proper authority validation constraints
-
-#[derive(Accounts)]
-pub struct VulnerableInitialize<'info> {
-    /// VULNERABILITY: Using `zero` constraint instead of `init` for demonstration
-    #[account(zero)]
-    pub vault: Account<'info, AdminVault>,
-    
-    /// VULNERABILITY: Admin is a signer but we don't validate their authority
-    /// to create vaults. Any signer can become an admin.
-    pub admin: Signer<'info>,
-    
-    /// VULNERABILITY: Owner doesn't need to be a signer, which could lead to
-    /// unauthorized vault creation for other users
-    /// CHECK: This is intentionally unsafe for demonstration
-    pub owner: UncheckedAccount<'info>,
-    
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
+    /// SECURE: Propose a multisig withdrawal of `amount`
+    ///
+    /// Security Fix: Each proposal is its own PDA at
+    /// `["proposal", multisig, index]`, holding the amount and the approvals
+    /// given for it. Nothing can change its amount once it is created.
+    #[secure]
+    pub fn secure_propose_withdraw(ctx: Context<SecureProposeWithdraw>, amount: u64) -> Result<()> {
+        let multisig = &mut ctx.accounts.multisig;
+        multisig.approver_bit(&ctx.accounts.proposer.key())?;
 
-#[derive(Accounts)]
-pub struct VulnerableAdminWithdraw<'info> {
-    /// VULNERABILITY: No `has_one` constraint to validate admin authority
-    #[account(mut)]
-    pub vault: Account<'info, AdminVault>,
-    
-    /// VULNERABILITY: We require a signer but don't validate it's THE admin
-    /// of THIS vault. Any signer can potentially act as admin.
-    pub admin: Signer<'info>,
-}
+        let index = multisig.proposal_count;
+        multisig.proposal_count = index.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
 
-#[derive(Accounts)]
-pub struct VulnerableEmergencyDrain<'info> {
-    /// VULNERABILITY: No `has_one` constraint to validate admin authority
-    #[account(mut)]
-    pub vault: Account<'info, AdminVault>,
-    
-    /// VULNERABILITY: Any signer can execute emergency drain on any vault
-    pub admin: Signer<'info>,
-}
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.multisig = multisig.key();
+        proposal.index = index;
+        proposal.amount = amount;
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.approvals = 0;
+        proposal.executed = false;
+        proposal.bump = ctx.bumps.proposal;
 
-#[derive(Accounts)]
-pub struct VulnerableChangeAdmin<'info> {
-    /// VULNERABILITY: No `has_one` constraint to validate current admin authority
-    #[account(mut)]
-    pub vault: Account<'info, AdminVault>,
-    
-    /// VULNERABILITY: Any signer can change the admin of any vault
-    pub current_admin: Signer<'info>,
-}
+        msg!("Withdrawal {} of {} proposed", index, amount);
+        Ok(())
+    }
 
-// ========================================
-// SECURE ACCOUNT CONTEXTS
-// ========================================
-// These contexts use proper Anchor constraints for authority validation
-
-#[derive(Accounts)]
-pub struct SecureInitialize<'info> {
-    /// SECURITY: `init` constraint ensures proper initialization
-    #[account(
-        init,
-        payer = payer,
-        space = 8 + 32 + 32 + 8, // discriminator + admin + owner + balance
-    )]
-    pub vault: Account<'info, AdminVault>,
-    
-    /// SECURITY: Admin must be a signer to prove they authorize this vault creation
-    pub admin: Signer<'info>,
-    
-    /// SECURITY: Owner should also be a signer to authorize vault creation
-    pub owner: Signer<'info>,
-    
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
+    /// SECURE: Approve one proposal, by its hash
+    ///
+    /// Security Fix: The approval is recorded on the proposal itself, and
+    /// only if `proposal_hash` - what the approver actually reviewed -
+    /// matches the proposal's multisig, index and amount.
+    #[secure]
+    pub fn secure_approve_withdraw(ctx: Context<SecureApproveWithdraw>, proposal_hash: [u8; 32]) -> Result<()> {
+        let bit = ctx.accounts.multisig.approver_bit(&ctx.accounts.approver.key())?;
+        let proposal = &mut ctx.accounts.proposal;
 
-#[derive(Accounts)]
-pub struct SecureAdminWithdraw<'info> {
-    /// SECURITY: `has_one = admin` constraint validates that vault.admin
-    /// matches the admin account passed in the context
-    #[account(
-        mut,
-        has_one = admin
-    )]
-    pub vault: Account<'info, AdminVault>,
-    
-    /// SECURITY: Must be a signer AND must match vault.admin
-    pub admin: Signer<'info>,
-}
+        // SECURITY: The approval is for this proposal's contents and nothing else
+        require!(proposal_hash == proposal.content_hash(), ErrorCode::ProposalMismatch);
+        require!(!proposal.executed, ErrorCode::ProposalAlreadyExecuted);
+        require!(proposal.approvals & bit == 0, ErrorCode::AlreadyApproved);
+        proposal.approvals |= bit;
 
-#[derive(Accounts)]
-pub struct SecureEmergencyDrain<'info> {
-    /// SECURITY: `has_one = admin` constraint validates admin authority
-    #[account(
-        mut,
-        has_one = admin
-    )]
-    pub vault: Account<'info, AdminVault>,
-    
-    /// SECURITY: Must be a signer AND must match vault.admin
-    pub admin: Signer<'info>,
-}
+        msg!("Withdrawal {} approved by {}", proposal.index, ctx.accounts.approver.key());
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct SecureChangeAdmin<'info> {
-    /// SECURITY: `has_one = admin` constraint validates current admin authority
-    #[account(
-        mut,
-        has_one = admin @ ErrorCode::UnauthorizedAdmin
-    )]
-    pub vault: Account<'info, AdminVault>,
-    
-    /// SECURITY: Must be a signer AND must match vault.admin
-    pub admin: Signer<'info>,
-}
+    /// SECURE: Execute an approved proposal, exactly once
+    ///
+    /// Security Fix: The amount comes from the proposal the approvals were
+    /// given for, and the proposal is marked executed so they cannot be
+    /// spent twice.
+    #[secure]
+    pub fn secure_execute_withdraw(ctx: Context<SecureExecuteWithdraw>) -> Result<()> {
+        let multisig = &ctx.accounts.multisig;
+        multisig.approver_bit(&ctx.accounts.executor.key())?;
 
-// ========================================
-// ACCOUNT STRUCTURES
-// ========================================
-
-#[account]
-pub struct AdminVault {
-    /// The admin who can perform administrative operations (32 bytes)
-    pub admin: Pubkey,
-    /// The owner of the vault (32 bytes)
-    pub owner: Pubkey,
-    /// The current balance in lamports (8 bytes)
-    pub balance: u64,
-}
+        let proposal = &mut ctx.accounts.proposal;
+        require!(!proposal.executed, ErrorCode::ProposalAlreadyExecuted);
+        require!(proposal.approvals.count_ones() >= u32::from(multisig.threshold), ErrorCode::NotEnoughApprovals);
+        proposal.executed = true;
 
-// ========================================
-// ERROR DEFINITIONS
-// ========================================
-
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Insufficient funds in vault")]
-    InsufficientFunds,
-    #[msg("Arithmetic overflow")]
-    ArithmeticOverflow,
-    #[msg("Arithmetic underflow")]
-    ArithmeticUnderflow,
-    #[msg("Unauthorized admin - signer is not the vault admin")]
-    UnauthorizedAdmin,
-}
\ No newline at end of file
+        // SECURITY: The amount is the one the approvers signed off on
+        let vault = &mut ctx.accounts.vault;
+        require!(vault.balance >= proposal.amount, ErrorCode::InsufficientFunds);
+        vault.balance = vault.balance.checked_sub(proposal.amount)
+            .ok_or(ErrorCode::ArithmeticUnderflow)?;
+
+        msg!("Multisig withdrew {} from vault. New balance: {}", proposal.amount, vault.balance);
+        Ok(())
+    }
+}
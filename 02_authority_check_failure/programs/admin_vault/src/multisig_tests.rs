@@ -0,0 +1,391 @@
+//! The multisig withdraw flows end to end: what an approval is good for in
+//! each, and who may propose, approve and execute.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_constraint_violation, assert_seeds_violation, assert_signer_violation, execute, Fixture, TestAccount,
+};
+
+use crate::{
+    instruction, AdminVault, ErrorCode as VaultError, Multisig, Proposal, SecureApproveWithdraw, SecureExecuteWithdraw,
+    SecureProposeWithdraw,
+};
+
+const BALANCE: u64 = 1_000;
+const AMOUNT: u64 = 100;
+const THRESHOLD: u8 = 2;
+
+/// A vault holding BALANCE behind a 2-of-3 multisig
+struct World {
+    vault: TestAccount,
+    multisig: TestAccount,
+    proposal: Option<TestAccount>,
+    approvers: [TestAccount; 3],
+    outsider: TestAccount,
+}
+
+impl World {
+    fn new() -> Self {
+        let approvers = [TestAccount::signer(), TestAccount::signer(), TestAccount::signer()];
+        let vault = TestAccount::anchor(&AdminVault {
+            admin: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            balance: BALANCE,
+        });
+        let (multisig, bump) = Pubkey::find_program_address(&[b"multisig", vault.key.as_ref()], &crate::ID);
+        let mut multisig_account = TestAccount::anchor(&Multisig {
+            vault: vault.key,
+            approvers: approvers.iter().map(|approver| approver.key).collect(),
+            threshold: THRESHOLD,
+            pending_hash: [0; 32],
+            approvals: 0,
+            proposal_count: 0,
+            bump,
+        })
+        .at(multisig);
+        multisig_account.data.resize(Multisig::SPACE, 0);
+        Self {
+            vault,
+            multisig: multisig_account,
+            proposal: None,
+            approvers,
+            outsider: TestAccount::signer(),
+        }
+    }
+
+    fn proposal_address(&self, index: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"proposal", self.multisig.key.as_ref(), &index.to_le_bytes()],
+            &crate::ID,
+        )
+        .0
+    }
+
+    fn vulnerable_propose(&self, proposer: &TestAccount) -> Fixture {
+        Fixture::new()
+            .with("multisig", self.multisig.clone())
+            .with("proposer", proposer.clone())
+    }
+
+    fn vulnerable_approve(&self, approver: &TestAccount) -> Fixture {
+        Fixture::new()
+            .with("multisig", self.multisig.clone())
+            .with("approver", approver.clone())
+    }
+
+    fn vulnerable_execute(&self, executor: &TestAccount) -> Fixture {
+        Fixture::new()
+            .with("vault", self.vault.clone())
+            .with("multisig", self.multisig.clone())
+            .with("executor", executor.clone())
+    }
+
+    fn propose(&self, proposer: &TestAccount) -> Fixture {
+        let index = self.multisig.state::<Multisig>().proposal_count;
+        Fixture::new()
+            .with("multisig", self.multisig.clone())
+            .with(
+                "proposal",
+                TestAccount::uninitialized(Proposal::SPACE).at(self.proposal_address(index)),
+            )
+            .with("proposer", proposer.clone())
+            .with("system_program", TestAccount::system_program())
+    }
+
+    fn approve(&self, approver: &TestAccount) -> Fixture {
+        Fixture::new()
+            .with("multisig", self.multisig.clone())
+            .with("proposal", self.proposal.clone().expect("no proposal yet"))
+            .with("approver", approver.clone())
+    }
+
+    fn execute(&self, executor: &TestAccount) -> Fixture {
+        Fixture::new()
+            .with("vault", self.vault.clone())
+            .with("multisig", self.multisig.clone())
+            .with("proposal", self.proposal.clone().expect("no proposal yet"))
+            .with("executor", executor.clone())
+    }
+
+    /// Carry the named accounts an instruction wrote into the next one's fixture
+    fn keep(&mut self, fixture: &Fixture, names: &[&str]) {
+        for &name in names {
+            let account = fixture.account(name).clone();
+            match name {
+                "vault" => self.vault = account,
+                "multisig" => self.multisig = account,
+                "proposal" => self.proposal = Some(account),
+                _ => panic!("the world does not track `{name}`"),
+            }
+        }
+    }
+
+    fn balance(&self) -> u64 {
+        self.vault.state::<AdminVault>().balance
+    }
+
+    fn proposal(&self) -> Proposal {
+        self.proposal.as_ref().expect("no proposal yet").state()
+    }
+
+    /// Propose AMOUNT through the secure flow and have the first THRESHOLD approvers approve it
+    fn approved(mut self) -> Self {
+        let mut fixture = self.propose(&self.approvers[0]);
+        execute!(&mut fixture, instruction::SecureProposeWithdraw { amount: AMOUNT }).unwrap();
+        self.keep(&fixture, &["multisig", "proposal"]);
+        let approvers = self.approvers.clone();
+        for approver in &approvers[..usize::from(THRESHOLD)] {
+            let proposal_hash = self.proposal().content_hash();
+            let mut fixture = self.approve(approver);
+            execute!(&mut fixture, instruction::SecureApproveWithdraw { proposal_hash }).unwrap();
+            self.keep(&fixture, &["proposal"]);
+        }
+        self
+    }
+}
+
+#[test]
+fn initialize_multisig_is_the_admins_and_checks_the_set() {
+    let admin = TestAccount::signer();
+    let vault = TestAccount::anchor(&AdminVault {
+        admin: admin.key,
+        owner: Pubkey::new_unique(),
+        balance: BALANCE,
+    });
+    let (multisig, _) = Pubkey::find_program_address(&[b"multisig", vault.key.as_ref()], &crate::ID);
+    let fixture = Fixture::new()
+        .with("vault", vault)
+        .with("multisig", TestAccount::uninitialized(Multisig::SPACE).at(multisig))
+        .with("admin", admin)
+        .with("payer", TestAccount::signer())
+        .with("system_program", TestAccount::system_program());
+    let initialize = |approvers: Vec<Pubkey>, threshold| {
+        execute!(
+            &mut fixture.clone(),
+            instruction::InitializeMultisig { approvers, threshold }
+        )
+    };
+
+    let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let invalid_approvers = Err(Error::from(VaultError::InvalidApprovers).into());
+    assert_eq!(initialize(vec![], 1), invalid_approvers);
+    assert_eq!(initialize(vec![a, a], 1), invalid_approvers);
+    assert_eq!(
+        initialize(vec![Pubkey::new_unique(); Multisig::MAX_APPROVERS + 1], 1),
+        invalid_approvers
+    );
+    let invalid_threshold = Err(Error::from(VaultError::InvalidThreshold).into());
+    assert_eq!(initialize(vec![a, b], 0), invalid_threshold);
+    assert_eq!(initialize(vec![a, b], 3), invalid_threshold);
+
+    let mut fixture = fixture;
+    execute!(
+        &mut fixture,
+        instruction::InitializeMultisig {
+            approvers: vec![a, b],
+            threshold: 2
+        }
+    )
+    .unwrap();
+    let stored = fixture.state::<Multisig>("multisig");
+    assert_eq!((stored.approvers, stored.threshold), (vec![a, b], 2));
+
+    // Anyone but the vault's admin is turned away
+    let mut impostor = fixture.clone();
+    *impostor.account_mut("admin") = TestAccount::signer();
+    *impostor.account_mut("multisig") = TestAccount::uninitialized(Multisig::SPACE).at(multisig);
+    assert_eq!(
+        execute!(
+            &mut impostor,
+            instruction::InitializeMultisig {
+                approvers: vec![a],
+                threshold: 1
+            }
+        ),
+        Err(Error::from(VaultError::UnauthorizedAdmin).into())
+    );
+}
+
+#[test]
+fn vulnerable_approvals_approve_any_amount_any_number_of_times() {
+    let mut world = World::new();
+    let [first, second, _] = world.approvers.clone();
+
+    let mut fixture = world.vulnerable_propose(&first);
+    execute!(&mut fixture, instruction::VulnerableProposeWithdraw { amount: 1 }).unwrap();
+    world.keep(&fixture, &["multisig"]);
+    for approver in [&first, &second] {
+        let mut fixture = world.vulnerable_approve(approver);
+        execute!(&mut fixture, instruction::VulnerableApproveWithdraw {}).unwrap();
+        world.keep(&fixture, &["multisig"]);
+    }
+
+    // Approved: a withdrawal of 1. Executed: half the vault, twice
+    for _ in 0..2 {
+        let mut fixture = world.vulnerable_execute(&first);
+        execute!(
+            &mut fixture,
+            instruction::VulnerableExecuteWithdraw { amount: BALANCE / 2 }
+        )
+        .unwrap();
+        world.keep(&fixture, &["vault", "multisig"]);
+    }
+    assert_eq!(world.balance(), 0);
+}
+
+#[test]
+fn vulnerable_flow_still_requires_approvers() {
+    let world = World::new();
+    assert_eq!(
+        execute!(
+            &mut world.vulnerable_approve(&world.outsider),
+            instruction::VulnerableApproveWithdraw {}
+        ),
+        Err(Error::from(VaultError::NotAnApprover).into())
+    );
+    assert_eq!(
+        execute!(
+            &mut world.vulnerable_execute(&world.approvers[0]),
+            instruction::VulnerableExecuteWithdraw { amount: 1 }
+        ),
+        Err(Error::from(VaultError::NotEnoughApprovals).into())
+    );
+}
+
+#[test]
+fn secure_propose_is_for_approvers_at_the_next_index() {
+    let world = World::new();
+    assert_signer_violation!(SecureProposeWithdraw, world.propose(&world.approvers[0]), "proposer");
+    assert_seeds_violation!(SecureProposeWithdraw, world.propose(&world.approvers[0]), "proposal");
+    assert_eq!(
+        execute!(
+            &mut world.propose(&world.outsider),
+            instruction::SecureProposeWithdraw { amount: AMOUNT }
+        ),
+        Err(Error::from(VaultError::NotAnApprover).into())
+    );
+
+    let world = world.approved();
+    let proposal = world.proposal();
+    assert_eq!(world.proposal.as_ref().unwrap().key, world.proposal_address(0));
+    assert_eq!((proposal.index, proposal.amount), (0, AMOUNT));
+    assert_eq!(proposal.proposer, world.approvers[0].key);
+    assert_eq!(world.multisig.state::<Multisig>().proposal_count, 1);
+}
+
+#[test]
+fn secure_approvals_are_bound_to_the_proposal_hash() {
+    let mut world = World::new();
+    let mut fixture = world.propose(&world.approvers[0]);
+    execute!(&mut fixture, instruction::SecureProposeWithdraw { amount: AMOUNT }).unwrap();
+    world.keep(&fixture, &["multisig", "proposal"]);
+
+    assert_seeds_violation!(SecureApproveWithdraw, world.approve(&world.approvers[1]), "multisig");
+    assert_signer_violation!(SecureApproveWithdraw, world.approve(&world.approvers[1]), "approver");
+    // A proposal recorded for another multisig, even at this one's address
+    assert_constraint_violation!(
+        SecureApproveWithdraw,
+        world.approve(&world.approvers[1]),
+        "proposal" => |proposal| proposal.edit::<Proposal>(|proposal| proposal.multisig = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+
+    // An approval for a different amount, or for another multisig's proposal 0, is not this one
+    let multisig = world.multisig.key;
+    for proposal_hash in [
+        Proposal::hash(&multisig, 0, BALANCE),
+        Proposal::hash(&Pubkey::new_unique(), 0, AMOUNT),
+    ] {
+        assert_eq!(
+            execute!(
+                &mut world.approve(&world.approvers[1]),
+                instruction::SecureApproveWithdraw { proposal_hash }
+            ),
+            Err(Error::from(VaultError::ProposalMismatch).into())
+        );
+    }
+
+    let proposal_hash = world.proposal().content_hash();
+    assert_eq!(proposal_hash, Proposal::hash(&multisig, 0, AMOUNT));
+    assert_eq!(
+        execute!(
+            &mut world.approve(&world.outsider),
+            instruction::SecureApproveWithdraw { proposal_hash }
+        ),
+        Err(Error::from(VaultError::NotAnApprover).into())
+    );
+    let mut fixture = world.approve(&world.approvers[1]);
+    execute!(&mut fixture, instruction::SecureApproveWithdraw { proposal_hash }).unwrap();
+    world.keep(&fixture, &["proposal"]);
+    assert_eq!(
+        execute!(
+            &mut world.approve(&world.approvers[1]),
+            instruction::SecureApproveWithdraw { proposal_hash }
+        ),
+        Err(Error::from(VaultError::AlreadyApproved).into())
+    );
+}
+
+#[test]
+fn secure_execute_withdraws_the_approved_amount_once() {
+    let mut world = World::new().approved();
+    assert_seeds_violation!(SecureExecuteWithdraw, world.execute(&world.approvers[2]), "vault");
+    assert_constraint_violation!(
+        SecureExecuteWithdraw,
+        world.execute(&world.approvers[2]),
+        "proposal" => |proposal| proposal.edit::<Proposal>(|proposal| proposal.multisig = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_seeds_violation!(SecureExecuteWithdraw, world.execute(&world.approvers[2]), "proposal");
+    assert_signer_violation!(SecureExecuteWithdraw, world.execute(&world.approvers[2]), "executor");
+
+    let mut fixture = world.execute(&world.approvers[2]);
+    execute!(&mut fixture, instruction::SecureExecuteWithdraw {}).unwrap();
+    world.keep(&fixture, &["vault", "proposal"]);
+    assert_eq!(world.balance(), BALANCE - AMOUNT);
+    assert!(world.proposal().executed);
+
+    let executed = Err(Error::from(VaultError::ProposalAlreadyExecuted).into());
+    assert_eq!(
+        execute!(
+            &mut world.execute(&world.approvers[2]),
+            instruction::SecureExecuteWithdraw {}
+        ),
+        executed
+    );
+    let proposal_hash = world.proposal().content_hash();
+    assert_eq!(
+        execute!(
+            &mut world.approve(&world.approvers[2]),
+            instruction::SecureApproveWithdraw { proposal_hash }
+        ),
+        executed
+    );
+}
+
+#[test]
+fn secure_execute_needs_the_threshold_on_that_proposal() {
+    let mut world = World::new();
+    let mut fixture = world.propose(&world.approvers[0]);
+    execute!(&mut fixture, instruction::SecureProposeWithdraw { amount: AMOUNT }).unwrap();
+    world.keep(&fixture, &["multisig", "proposal"]);
+    let proposal_hash = world.proposal().content_hash();
+    let mut fixture = world.approve(&world.approvers[0]);
+    execute!(&mut fixture, instruction::SecureApproveWithdraw { proposal_hash }).unwrap();
+    world.keep(&fixture, &["proposal"]);
+
+    assert_eq!(
+        execute!(
+            &mut world.execute(&world.approvers[0]),
+            instruction::SecureExecuteWithdraw {}
+        ),
+        Err(Error::from(VaultError::NotEnoughApprovals).into())
+    );
+    assert_eq!(
+        execute!(
+            &mut world.execute(&world.outsider),
+            instruction::SecureExecuteWithdraw {}
+        ),
+        Err(Error::from(VaultError::NotAnApprover).into())
+    );
+}
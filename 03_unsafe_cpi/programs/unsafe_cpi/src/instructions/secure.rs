@@ -0,0 +1,93 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use anchor_spl::token::Token;
+
+use super::*;
+
+// These contexts use proper program validation
+
+#[derive(Accounts)]
+pub struct SecureInitialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 1, // discriminator + authority + token_account + bump
+        seeds = [b"vault", authority.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenVault>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_account: Account<'info, TokenAccount>,
+    
+    /// SECURITY: Program<'info, Token> validates that this is the SPL Token program
+    pub token_program: Program<'info, Token>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SecureTransfer<'info> {
+    #[account(
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TokenVault>,
+    
+    #[account(mut)]
+    pub from_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub to_token_account: Account<'info, TokenAccount>,
+    
+    /// SECURITY: Program<'info, Token> ensures this is the legitimate SPL Token program
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SecureWithdraw<'info> {
+    #[account(
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TokenVault>,
+    
+    #[account(mut)]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    pub user: Signer<'info>,
+    
+    /// SECURITY: Program<'info, Token> ensures this is the legitimate SPL Token program
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SecureClosePosition<'info> {
+    /// SECURITY: `close` runs after the handler, so the state is closed
+    /// last and carries the token account's rent on to the authority
+    #[account(
+        mut,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump = vault.bump,
+        has_one = authority,
+        close = authority
+    )]
+    pub vault: Account<'info, TokenVault>,
+
+    #[account(mut, address = vault.token_account, token::authority = vault)]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = vault_token_account.mint, token::authority = authority)]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// SECURITY: Program<'info, Token> ensures this is the legitimate SPL Token program
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,95 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use anchor_spl::token::Token;
+
+use super::*;
+
+// These contexts accept any program without validation
+
+#[derive(Accounts)]
+pub struct VulnerableInitialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 1, // discriminator + authority + token_account + bump
+        seeds = [b"vault", authority.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenVault>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_account: Account<'info, TokenAccount>,
+    
+    /// VULNERABILITY: Using UncheckedAccount instead of Program<'info, Token>
+    /// This allows ANY program to be passed, including malicious ones
+    /// CHECK: This is intentionally unsafe for demonstration
+    pub token_program: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableTransfer<'info> {
+    #[account(
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TokenVault>,
+    
+    #[account(mut)]
+    pub from_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub to_token_account: Account<'info, TokenAccount>,
+    
+    /// VULNERABILITY: Accepts any program without validation
+    /// CHECK: This is intentionally unsafe for demonstration
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableWithdraw<'info> {
+    #[account(
+        seeds = [b"vault", vault.authority.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, TokenVault>,
+    
+    #[account(mut)]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    pub user: Signer<'info>,
+    
+    /// VULNERABILITY: Accepts any program without validation
+    /// CHECK: This is intentionally unsafe for demonstration
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableClosePosition<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", authority.key().as_ref()],
+        bump = vault.bump,
+        has_one = authority
+    )]
+    pub vault: Account<'info, TokenVault>,
+
+    #[account(mut, address = vault.token_account, token::authority = vault)]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = vault_token_account.mint, token::authority = authority)]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The token program is checked here: this instruction's flaw is the
+    /// order it closes accounts in, not the program it calls
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,16 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::TokenVault;
+
+#[test]
+fn token_vault_layout_is_stable() {
+    let token_vault = TokenVault {
+        authority: Pubkey::new_from_array([1; 32]),
+        token_account: Pubkey::new_from_array([2; 32]),
+        bump: 49,
+    };
+    assert_golden!("TokenVault", golden::anchor(&token_vault));
+}
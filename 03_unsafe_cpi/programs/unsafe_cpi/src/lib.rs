@@ -1,8 +1,26 @@
-use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, CloseAccount, TokenAccount, Transfer};
+use secref_prelude::*;
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod close_position_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+#[feature_gated]
 #[program]
 pub mod unsafe_cpi {
     use super::*;
@@ -19,6 +37,7 @@ pub mod unsafe_cpi {
     /// without validating that it's actually the legitimate SPL Token program.
     /// An attacker could pass a malicious program that mimics the token program
     /// interface but performs unauthorized operations.
+    #[vulnerable]
     pub fn vulnerable_initialize(ctx: Context<VulnerableInitialize>) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -43,6 +62,7 @@ pub mod unsafe_cpi {
     /// is actually the legitimate SPL Token program. An attacker could substitute
     /// a malicious program that appears to transfer tokens but actually steals them
     /// or performs other unauthorized operations.
+    #[vulnerable]
     pub fn vulnerable_transfer(ctx: Context<VulnerableTransfer>, amount: u64) -> Result<()> {
         let vault = &ctx.accounts.vault;
         
@@ -91,6 +111,7 @@ pub mod unsafe_cpi {
     /// CPI calls without validating the token program. This is particularly
     /// dangerous for withdrawal operations as it gives an attacker the ability
     /// to drain funds using a malicious program.
+    #[vulnerable]
     pub fn vulnerable_withdraw(ctx: Context<VulnerableWithdraw>, amount: u64) -> Result<()> {
         let vault = &ctx.accounts.vault;
         
@@ -125,6 +146,51 @@ pub mod unsafe_cpi {
         Ok(())
     }
 
+    /// VULNERABLE: Close a position: sweep its tokens, close its token account, close its state
+    ///
+    /// Security Issue: The state account is closed first. The token account
+    /// is then closed with its rent sent to the vault, the account that
+    /// signs for it - which no longer exists. Those lamports land at the
+    /// vault's address with no state behind it, where only creating the vault
+    /// again would recover them, and the authority is paid one account's
+    /// rent instead of two.
+    #[vulnerable]
+    pub fn vulnerable_close_position(ctx: Context<VulnerableClosePosition>) -> Result<()> {
+        let vault = &ctx.accounts.vault;
+        let seeds = &[
+            b"vault",
+            vault.authority.as_ref(),
+            &[vault.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // VULNERABILITY: The state goes first, its rent straight to the authority
+        ctx.accounts.vault.close(ctx.accounts.authority.to_account_info())?;
+
+        let remaining = ctx.accounts.vault_token_account.amount;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            to: ctx.accounts.authority_token_account.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::transfer(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer), remaining)?;
+
+        // VULNERABILITY: ...so the token account's rent follows it into a
+        // closed account, stranded at the vault's address
+        let cpi_accounts = CloseAccount {
+            account: ctx.accounts.vault_token_account.to_account_info(),
+            destination: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::close_account(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer))?;
+
+        msg!("VULNERABLE: Closed position after sweeping {} tokens", remaining);
+
+        Ok(())
+    }
+
     // ========================================
     // SECURE IMPLEMENTATION
     // ========================================
@@ -136,6 +202,7 @@ pub mod unsafe_cpi {
     /// Security Fix: Uses Anchor's Program<'info, Token> type which automatically
     /// validates that the program ID matches the expected SPL Token program.
     /// This prevents attackers from substituting malicious programs.
+    #[secure]
     pub fn secure_initialize(ctx: Context<SecureInitialize>) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -157,6 +224,7 @@ pub mod unsafe_cpi {
     /// Security Fix: Uses Program<'info, Token> to ensure the token_program
     /// is actually the legitimate SPL Token program before making CPI calls.
     /// This prevents malicious program substitution attacks.
+    #[secure]
     pub fn secure_transfer(ctx: Context<SecureTransfer>, amount: u64) -> Result<()> {
         let vault = &ctx.accounts.vault;
         
@@ -193,6 +261,7 @@ pub mod unsafe_cpi {
     /// 
     /// Security Fix: Uses Program<'info, Token> to ensure only legitimate
     /// SPL Token program can be used for withdrawal operations.
+    #[secure]
     pub fn secure_withdraw(ctx: Context<SecureWithdraw>, amount: u64) -> Result<()> {
         let vault = &ctx.accounts.vault;
         
@@ -220,166 +289,55 @@ pub mod unsafe_cpi {
         
         Ok(())
     }
-}
-
-// ========================================
-// VULNERABLE ACCOUNT CONTEXTS
-// ========================================
-// These contexts accept any program without validation
 
-#[derive(Accounts)]
-pub struct VulnerableInitialize<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + 32 + 32 + 1, // discriminator + authority + token_account + bump
-        seeds = [b"vault", authority.key().as_ref()],
-        bump
-    )]
-    pub vault: Account<'info, TokenVault>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub token_account: Account<'info, TokenAccount>,
-    
-    /// VULNERABILITY: Using UncheckedAccount instead of Program<'info, Token>
-    /// This allows ANY program to be passed, including malicious ones
-    /// CHECK: This is intentionally unsafe for demonstration
-    pub token_program: UncheckedAccount<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct VulnerableTransfer<'info> {
-    #[account(
-        seeds = [b"vault", vault.authority.as_ref()],
-        bump = vault.bump
-    )]
-    pub vault: Account<'info, TokenVault>,
-    
-    #[account(mut)]
-    pub from_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub to_token_account: Account<'info, TokenAccount>,
-    
-    /// VULNERABILITY: Accepts any program without validation
-    /// CHECK: This is intentionally unsafe for demonstration
-    pub token_program: UncheckedAccount<'info>,
-}
-
-#[derive(Accounts)]
-pub struct VulnerableWithdraw<'info> {
-    #[account(
-        seeds = [b"vault", vault.authority.as_ref()],
-        bump = vault.bump
-    )]
-    pub vault: Account<'info, TokenVault>,
-    
-    #[account(mut)]
-    pub vault_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    
-    pub user: Signer<'info>,
-    
-    /// VULNERABILITY: Accepts any program without validation
-    /// CHECK: This is intentionally unsafe for demonstration
-    pub token_program: UncheckedAccount<'info>,
-}
+    /// SECURE: Close a position: sweep its tokens, close its token account, close its state
+    ///
+    /// Security Fix: Each account is closed only once nothing is left in
+    /// it, and the state, which receives the token account's rent, last.
+    /// The handler sweeps the tokens and checks none remain, closes the
+    /// token account and checks it is gone; `close = authority` then closes
+    /// the state after the handler returns, so both rents reach the authority.
+    #[secure]
+    pub fn secure_close_position(ctx: Context<SecureClosePosition>) -> Result<()> {
+        let vault = &ctx.accounts.vault;
+        let seeds = &[
+            b"vault",
+            vault.authority.as_ref(),
+            &[vault.bump],
+        ];
+        let signer = &[&seeds[..]];
 
-// ========================================
-// SECURE ACCOUNT CONTEXTS
-// ========================================
-// These contexts use proper program validation
+        let remaining = ctx.accounts.vault_token_account.amount;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            to: ctx.accounts.authority_token_account.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::transfer(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer), remaining)?;
 
-#[derive(Accounts)]
-pub struct SecureInitialize<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + 32 + 32 + 1, // discriminator + authority + token_account + bump
-        seeds = [b"vault", authority.key().as_ref()],
-        bump
-    )]
-    pub vault: Account<'info, TokenVault>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub token_account: Account<'info, TokenAccount>,
-    
-    /// SECURITY: Program<'info, Token> validates that this is the SPL Token program
-    pub token_program: Program<'info, Token>,
-    
-    pub system_program: Program<'info, System>,
-}
+        // SECURITY: Post-condition - the sweep left nothing behind
+        ctx.accounts.vault_token_account.reload()?;
+        require_eq!(ctx.accounts.vault_token_account.amount, 0, ErrorCode::TokensRemaining);
 
-#[derive(Accounts)]
-pub struct SecureTransfer<'info> {
-    #[account(
-        seeds = [b"vault", vault.authority.as_ref()],
-        bump = vault.bump
-    )]
-    pub vault: Account<'info, TokenVault>,
-    
-    #[account(mut)]
-    pub from_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub to_token_account: Account<'info, TokenAccount>,
-    
-    /// SECURITY: Program<'info, Token> ensures this is the legitimate SPL Token program
-    pub token_program: Program<'info, Token>,
-}
+        // SECURITY: The rent goes to the state, which is still open
+        let cpi_accounts = CloseAccount {
+            account: ctx.accounts.vault_token_account.to_account_info(),
+            destination: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::close_account(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer))?;
 
-#[derive(Accounts)]
-pub struct SecureWithdraw<'info> {
-    #[account(
-        seeds = [b"vault", vault.authority.as_ref()],
-        bump = vault.bump
-    )]
-    pub vault: Account<'info, TokenVault>,
-    
-    #[account(mut)]
-    pub vault_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    
-    pub user: Signer<'info>,
-    
-    /// SECURITY: Program<'info, Token> ensures this is the legitimate SPL Token program
-    pub token_program: Program<'info, Token>,
-}
+        // SECURITY: Post-condition - the token account is gone
+        require_eq!(
+            ctx.accounts.vault_token_account.to_account_info().lamports(),
+            0,
+            ErrorCode::TokenAccountOpen
+        );
 
-// ========================================
-// ACCOUNT STRUCTURES
-// ========================================
+        msg!("SECURE: Closed position after sweeping {} tokens", remaining);
 
-#[account]
-pub struct TokenVault {
-    /// The authority that can manage this vault (32 bytes)
-    pub authority: Pubkey,
-    /// The token account associated with this vault (32 bytes)
-    pub token_account: Pubkey,
-    /// The bump seed for PDA derivation (1 byte)
-    pub bump: u8,
+        Ok(())
+    }
 }
-
-// ========================================
-// ERROR DEFINITIONS
-// ========================================
-
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Invalid token program - must be SPL Token program")]
-    InvalidTokenProgram,
-    #[msg("Unauthorized operation")]
-    Unauthorized,
-    #[msg("Insufficient token balance")]
-    InsufficientBalance,
-}
\ No newline at end of file
@@ -0,0 +1,13 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct TokenVault {
+    /// The authority that can manage this vault (32 bytes)
+    pub authority: Pubkey,
+    /// The token account associated with this vault (32 bytes)
+    pub token_account: Pubkey,
+    /// The bump seed for PDA derivation (1 byte)
+    pub bump: u8,
+}
@@ -2,6 +2,8 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { UnsafeCpi } from "../target/types/unsafe_cpi";
 import { expect } from "chai";
+import { CuProfiler } from "../../harness/cu-profiler";
+import { PocRecorder } from "../../harness/poc-recorder";
 import { Connection, PublicKey, Keypair } from "@solana/web3.js";
 
 describe("Unsafe CPI Vulnerability Tests", () => {
@@ -24,6 +26,16 @@ describe("Unsafe CPI Vulnerability Tests", () => {
   
   anchor.setProvider(provider);
 
+  // Records compute units for every instruction executed against a validator
+  const profiler = new CuProfiler("03_unsafe_cpi");
+
+  // Records every transaction as a replayable PoC when SECREF_POC_RECORD=1
+  const recorder = new PocRecorder("03_unsafe_cpi", provider);
+  beforeEach(function () {
+    // Steps are labelled with the test that sent them
+    recorder.test(this.currentTest?.fullTitle());
+  });
+
   // Mock program for testing
   let program: Program<UnsafeCpi>;
 
@@ -52,6 +64,13 @@ describe("Unsafe CPI Vulnerability Tests", () => {
     maliciousProgram = anchor.web3.Keypair.generate().publicKey;
   });
 
+  after(() => {
+    recorder.writeFixture();
+    // Fails the suite if any recorded instruction regressed past its CU budget
+    profiler.writeSummary();
+    profiler.assertBudgets();
+  });
+
   describe("🚨 EXPLOIT DEMONSTRATION - Unsafe CPI", () => {
     it("Should demonstrate unsafe cross-program invocation vulnerability", async () => {
       console.log("\n=== UNSAFE CPI EXPLOIT ===");
@@ -0,0 +1,75 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+//!
+//! This example's fixes are in the arithmetic, so the contexts only pin the
+//! vault's program and require a signature.
+
+use anchor_lang::prelude::*;
+use secref_testkit::runtime::account_already_in_use;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_signer_violation, Fixture, TestAccount,
+};
+
+use crate::{
+    ArithmeticVault, SecureApplyInterest, SecureBatchDeposit, SecureDeposit, SecureInitialize, SecureWithdraw,
+};
+
+fn vault_account() -> TestAccount {
+    TestAccount::anchor(&ArithmeticVault {
+        owner: Pubkey::new_unique(),
+        balance: 1_000,
+        total_deposits: 1_000,
+        total_withdrawals: 0,
+    })
+}
+
+fn initialize_fixture() -> Fixture {
+    Fixture::new()
+        .with("vault", TestAccount::uninitialized(8 + 32 + 8 + 8 + 8).signed())
+        .with("owner", TestAccount::signer())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn vault_fixture(signer: &'static str) -> Fixture {
+    Fixture::new()
+        .with("vault", vault_account())
+        .with(signer, TestAccount::signer())
+}
+
+#[test]
+fn secure_initialize_rejects_existing_vaults() {
+    assert_constraint_violation!(
+        SecureInitialize,
+        initialize_fixture(),
+        "vault" => vault_account().signed(),
+        account_already_in_use()
+    );
+}
+
+#[test]
+fn secure_initialize_requires_an_owner_signature() {
+    assert_signer_violation!(SecureInitialize, initialize_fixture(), "owner");
+}
+
+#[test]
+fn secure_deposit_validates_vault_and_signer() {
+    assert_owner_violation!(SecureDeposit, vault_fixture("depositor"), "vault");
+    assert_signer_violation!(SecureDeposit, vault_fixture("depositor"), "depositor");
+}
+
+#[test]
+fn secure_withdraw_validates_vault_and_signer() {
+    assert_owner_violation!(SecureWithdraw, vault_fixture("withdrawer"), "vault");
+    assert_signer_violation!(SecureWithdraw, vault_fixture("withdrawer"), "withdrawer");
+}
+
+#[test]
+fn secure_apply_interest_validates_vault_and_signer() {
+    assert_owner_violation!(SecureApplyInterest, vault_fixture("authority"), "vault");
+    assert_signer_violation!(SecureApplyInterest, vault_fixture("authority"), "authority");
+}
+
+#[test]
+fn secure_batch_deposit_validates_vault_and_signer() {
+    assert_owner_violation!(SecureBatchDeposit, vault_fixture("depositor"), "vault");
+    assert_signer_violation!(SecureBatchDeposit, vault_fixture("depositor"), "depositor");
+}
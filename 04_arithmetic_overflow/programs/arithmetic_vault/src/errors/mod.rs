@@ -0,0 +1,15 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow occurred")]
+    ArithmeticOverflow,
+    #[msg("Arithmetic underflow occurred")]
+    ArithmeticUnderflow,
+    #[msg("Division by zero")]
+    DivisionByZero,
+    #[msg("Insufficient funds for withdrawal")]
+    InsufficientFunds,
+}
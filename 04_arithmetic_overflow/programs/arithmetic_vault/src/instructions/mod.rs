@@ -0,0 +1,15 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
@@ -0,0 +1,40 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureInitialize<'info> {
+    #[account(init, payer = owner, space = 8 + 32 + 8 + 8 + 8)]
+    pub vault: Account<'info, ArithmeticVault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SecureDeposit<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, ArithmeticVault>,
+    pub depositor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SecureWithdraw<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, ArithmeticVault>,
+    pub withdrawer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SecureApplyInterest<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, ArithmeticVault>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SecureBatchDeposit<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, ArithmeticVault>,
+    pub depositor: Signer<'info>,
+}
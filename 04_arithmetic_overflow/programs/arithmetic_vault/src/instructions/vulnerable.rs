@@ -0,0 +1,40 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableInitialize<'info> {
+    #[account(init, payer = owner, space = 8 + 32 + 8 + 8 + 8)]
+    pub vault: Account<'info, ArithmeticVault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableDeposit<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, ArithmeticVault>,
+    pub depositor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableWithdraw<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, ArithmeticVault>,
+    pub withdrawer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableApplyInterest<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, ArithmeticVault>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableBatchDeposit<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, ArithmeticVault>,
+    pub depositor: Signer<'info>,
+}
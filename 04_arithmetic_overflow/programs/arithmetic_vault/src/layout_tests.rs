@@ -0,0 +1,17 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::ArithmeticVault;
+
+#[test]
+fn arithmetic_vault_layout_is_stable() {
+    let arithmetic_vault = ArithmeticVault {
+        owner: Pubkey::new_from_array([1; 32]),
+        balance: 0x2122232425262728,
+        total_deposits: 0x3132333435363738,
+        total_withdrawals: 0x4142434445464748,
+    };
+    assert_golden!("ArithmeticVault", golden::anchor(&arithmetic_vault));
+}
@@ -1,7 +1,23 @@
-use anchor_lang::prelude::*;
+use secref_prelude::*;
 
-declare_id!("ArithmeticVault11111111111111111111111111111");
+declare_id!("ArithmeticVau1t11111111111111111111111111111");
 
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+#[feature_gated]
 #[program]
 pub mod arithmetic_vault {
     use super::*;
@@ -18,6 +34,7 @@ pub mod arithmetic_vault {
     /// silently overflow or underflow in release mode, leading to corrupted state.
     /// In Rust, integer overflow panics in debug mode but wraps around silently
     /// in release mode, which can be exploited by attackers.
+    #[vulnerable]
     pub fn vulnerable_initialize(ctx: Context<VulnerableInitialize>, initial_balance: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -37,13 +54,14 @@ pub mod arithmetic_vault {
     /// Security Issue: Uses unchecked addition which can overflow silently in release mode.
     /// An attacker could deposit a large amount that causes the balance to wrap around
     /// to a small number, effectively stealing funds from the vault.
+    #[vulnerable]
     pub fn vulnerable_deposit(ctx: Context<VulnerableDeposit>, amount: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
         // VULNERABILITY: Unchecked arithmetic - can overflow silently
         // If vault.balance + amount > u64::MAX, this will wrap around to a small number
-        vault.balance = vault.balance + amount;
-        vault.total_deposits = vault.total_deposits + amount;
+        vault.balance += amount;
+        vault.total_deposits += amount;
         
         msg!("Deposited {}. New balance: {} (unchecked)", amount, vault.balance);
         Ok(())
@@ -54,13 +72,14 @@ pub mod arithmetic_vault {
     /// Security Issue: Uses unchecked subtraction which can underflow silently.
     /// An attacker could withdraw more than the balance, causing underflow that
     /// wraps around to a very large number, creating funds out of thin air.
+    #[vulnerable]
     pub fn vulnerable_withdraw(ctx: Context<VulnerableWithdraw>, amount: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
         // VULNERABILITY: No balance check and unchecked subtraction
         // If amount > vault.balance, this will underflow and wrap to a huge number
-        vault.balance = vault.balance - amount;
-        vault.total_withdrawals = vault.total_withdrawals + amount;
+        vault.balance -= amount;
+        vault.total_withdrawals += amount;
         
         msg!("Withdrew {}. New balance: {} (unchecked)", amount, vault.balance);
         Ok(())
@@ -71,13 +90,14 @@ pub mod arithmetic_vault {
     /// Security Issue: Multiplication can easily overflow with large balances or rates.
     /// This could be exploited to either drain funds (overflow to small number) or
     /// create excessive funds (if overflow detection is inconsistent).
+    #[vulnerable]
     pub fn vulnerable_apply_interest(ctx: Context<VulnerableApplyInterest>, rate_basis_points: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
         // VULNERABILITY: Unchecked multiplication can overflow
         // Large balance * rate could overflow, wrapping to unexpected values
         let interest = vault.balance * rate_basis_points / 10000;
-        vault.balance = vault.balance + interest;
+        vault.balance += interest;
         
         msg!("Applied interest. New balance: {} (unchecked)", vault.balance);
         Ok(())
@@ -87,13 +107,14 @@ pub mod arithmetic_vault {
     /// 
     /// Security Issue: Multiple unchecked operations compound the overflow risk.
     /// Even if individual operations seem safe, the accumulation can cause overflow.
+    #[vulnerable]
     pub fn vulnerable_batch_deposit(ctx: Context<VulnerableBatchDeposit>, amounts: Vec<u64>) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
         for amount in amounts {
             // VULNERABILITY: Multiple unchecked additions increase overflow probability
-            vault.balance = vault.balance + amount;
-            vault.total_deposits = vault.total_deposits + amount;
+            vault.balance += amount;
+            vault.total_deposits += amount;
         }
         
         msg!("Batch deposit completed. New balance: {} (unchecked)", vault.balance);
@@ -107,6 +128,7 @@ pub mod arithmetic_vault {
     // using checked arithmetic to prevent overflow/underflow vulnerabilities.
 
     /// SECURE: Initialize vault with proper validation
+    #[secure]
     pub fn secure_initialize(ctx: Context<SecureInitialize>, initial_balance: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -123,6 +145,7 @@ pub mod arithmetic_vault {
     /// 
     /// Security Fix: Uses checked_add() which returns None on overflow,
     /// allowing us to handle the error gracefully instead of silent corruption.
+    #[secure]
     pub fn secure_deposit(ctx: Context<SecureDeposit>, amount: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -143,6 +166,7 @@ pub mod arithmetic_vault {
     /// 
     /// Security Fix: Validates sufficient balance and uses checked_sub()
     /// to prevent underflow attacks.
+    #[secure]
     pub fn secure_withdraw(ctx: Context<SecureWithdraw>, amount: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -168,6 +192,7 @@ pub mod arithmetic_vault {
     /// 
     /// Security Fix: Uses checked arithmetic for all operations and validates
     /// intermediate results to prevent overflow in compound calculations.
+    #[secure]
     pub fn secure_apply_interest(ctx: Context<SecureApplyInterest>, rate_basis_points: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -192,6 +217,7 @@ pub mod arithmetic_vault {
     /// 
     /// Security Fix: Each operation uses checked arithmetic, and we validate
     /// the total before applying to prevent accumulated overflow.
+    #[secure]
     pub fn secure_batch_deposit(ctx: Context<SecureBatchDeposit>, amounts: Vec<u64>) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -216,113 +242,3 @@ pub mod arithmetic_vault {
         Ok(())
     }
 }
-
-// ========================================
-// ACCOUNT STRUCTURES
-// ========================================
-
-#[account]
-pub struct ArithmeticVault {
-    pub owner: Pubkey,           // 32 bytes
-    pub balance: u64,            // 8 bytes - main balance that can overflow
-    pub total_deposits: u64,     // 8 bytes - running total of deposits
-    pub total_withdrawals: u64,  // 8 bytes - running total of withdrawals
-}
-
-// ========================================
-// VULNERABLE CONTEXTS
-// ========================================
-
-#[derive(Accounts)]
-pub struct VulnerableInitialize<'info> {
-    #[account(init, payer = owner, space = 8 + 32 + 8 + 8 + 8)]
-    pub vault: Account<'info, ArithmeticVault>,
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct VulnerableDeposit<'info> {
-    #[account(mut)]
-    pub vault: Account<'info, ArithmeticVault>,
-    pub depositor: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct VulnerableWithdraw<'info> {
-    #[account(mut)]
-    pub vault: Account<'info, ArithmeticVault>,
-    pub withdrawer: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct VulnerableApplyInterest<'info> {
-    #[account(mut)]
-    pub vault: Account<'info, ArithmeticVault>,
-    pub authority: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct VulnerableBatchDeposit<'info> {
-    #[account(mut)]
-    pub vault: Account<'info, ArithmeticVault>,
-    pub depositor: Signer<'info>,
-}
-
-// ========================================
-// SECURE CONTEXTS
-// ========================================
-
-#[derive(Accounts)]
-pub struct SecureInitialize<'info> {
-    #[account(init, payer = owner, space = 8 + 32 + 8 + 8 + 8)]
-    pub vault: Account<'info, ArithmeticVault>,
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct SecureDeposit<'info> {
-    #[account(mut)]
-    pub vault: Account<'info, ArithmeticVault>,
-    pub depositor: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct SecureWithdraw<'info> {
-    #[account(mut)]
-    pub vault: Account<'info, ArithmeticVault>,
-    pub withdrawer: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct SecureApplyInterest<'info> {
-    #[account(mut)]
-    pub vault: Account<'info, ArithmeticVault>,
-    pub authority: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct SecureBatchDeposit<'info> {
-    #[account(mut)]
-    pub vault: Account<'info, ArithmeticVault>,
-    pub depositor: Signer<'info>,
-}
-
-// ========================================
-// ERROR DEFINITIONS
-// ========================================
-
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Arithmetic overflow occurred")]
-    ArithmeticOverflow,
-    #[msg("Arithmetic underflow occurred")]
-    ArithmeticUnderflow,
-    #[msg("Division by zero")]
-    DivisionByZero,
-    #[msg("Insufficient funds for withdrawal")]
-    InsufficientFunds,
-}
\ No newline at end of file
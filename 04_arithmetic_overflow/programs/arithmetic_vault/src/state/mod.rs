@@ -0,0 +1,11 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct ArithmeticVault {
+    pub owner: Pubkey,           // 32 bytes
+    pub balance: u64,            // 8 bytes - main balance that can overflow
+    pub total_deposits: u64,     // 8 bytes - running total of deposits
+    pub total_withdrawals: u64,  // 8 bytes - running total of withdrawals
+}
@@ -2,6 +2,8 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { ArithmeticVault } from "../target/types/arithmetic_vault";
 import { expect } from "chai";
+import { CuProfiler } from "../../harness/cu-profiler";
+import { PocRecorder } from "../../harness/poc-recorder";
 import { Connection, Keypair } from "@solana/web3.js";
 
 describe("Arithmetic Overflow Vulnerability Tests", () => {
@@ -24,6 +26,16 @@ describe("Arithmetic Overflow Vulnerability Tests", () => {
   
   anchor.setProvider(provider);
 
+  // Records compute units for every instruction executed against a validator
+  const profiler = new CuProfiler("04_arithmetic_overflow");
+
+  // Records every transaction as a replayable PoC when SECREF_POC_RECORD=1
+  const recorder = new PocRecorder("04_arithmetic_overflow", provider);
+  beforeEach(function () {
+    // Steps are labelled with the test that sent them
+    recorder.test(this.currentTest?.fullTitle());
+  });
+
   // Mock program for testing
   let program: Program<ArithmeticVault>;
   
@@ -42,6 +54,13 @@ describe("Arithmetic Overflow Vulnerability Tests", () => {
     }
   });
 
+  after(() => {
+    recorder.writeFixture();
+    // Fails the suite if any recorded instruction regressed past its CU budget
+    profiler.writeSummary();
+    profiler.assertBudgets();
+  });
+
   beforeEach(async () => {
     vulnerableVault = anchor.web3.Keypair.generate();
     secureVault = anchor.web3.Keypair.generate();
@@ -0,0 +1,108 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::runtime::account_already_in_use;
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_owner_violation, assert_signer_violation,
+    try_accounts, Fixture, TestAccount,
+};
+
+use crate::{ReinitVault, SecureDeposit, SecureInitialize, SecureManualInit, SecureReset, SecureWithdraw};
+
+const VAULT_SPACE: usize = 8 + 32 + 8 + 8 + 1;
+
+fn vault_account(owner: Pubkey) -> TestAccount {
+    TestAccount::anchor(&ReinitVault {
+        owner,
+        balance: 0,
+        total_deposits: 0,
+        is_initialized: true,
+    })
+}
+
+fn initialize_fixture() -> Fixture {
+    Fixture::new()
+        .with("vault", TestAccount::uninitialized(VAULT_SPACE).signed())
+        .with("owner", TestAccount::signer())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn owner_fixture() -> Fixture {
+    let owner = TestAccount::signer();
+    Fixture::new()
+        .with("vault", vault_account(owner.key))
+        .with("owner", owner)
+}
+
+#[test]
+fn secure_initialize_rejects_existing_vaults() {
+    assert_constraint_violation!(
+        SecureInitialize,
+        initialize_fixture(),
+        "vault" => vault_account(Pubkey::new_unique()).signed(),
+        account_already_in_use()
+    );
+}
+
+#[test]
+fn secure_initialize_requires_an_owner_signature() {
+    assert_signer_violation!(SecureInitialize, initialize_fixture(), "owner");
+}
+
+#[test]
+fn secure_deposit_validates_vault_and_signer() {
+    let fixture = || {
+        Fixture::new()
+            .with("vault", vault_account(Pubkey::new_unique()))
+            .with("depositor", TestAccount::signer())
+    };
+    assert_owner_violation!(SecureDeposit, fixture(), "vault");
+    assert_signer_violation!(SecureDeposit, fixture(), "depositor");
+}
+
+#[test]
+fn secure_withdraw_requires_the_vault_owner() {
+    assert_has_one_violation!(SecureWithdraw, owner_fixture(), "owner", crate::ErrorCode::Unauthorized);
+    assert_signer_violation!(SecureWithdraw, owner_fixture(), "owner");
+    assert_owner_violation!(SecureWithdraw, owner_fixture(), "vault");
+}
+
+#[test]
+fn secure_reset_requires_the_vault_owner() {
+    assert_has_one_violation!(SecureReset, owner_fixture(), "owner", crate::ErrorCode::Unauthorized);
+    assert_signer_violation!(SecureReset, owner_fixture(), "owner");
+    assert_owner_violation!(SecureReset, owner_fixture(), "vault");
+}
+
+#[test]
+fn secure_manual_init_leaves_existing_vaults_to_the_handler() {
+    // `init_if_needed` accepts an account that already exists; the
+    // `is_initialized` check in the handler is what refuses it
+    let mut fixture = initialize_fixture();
+    *fixture.account_mut("vault") = vault_account(Pubkey::new_unique()).signed();
+    assert_eq!(try_accounts!(SecureManualInit, fixture), Ok(()));
+}
+
+#[test]
+fn secure_manual_init_rejects_foreign_accounts() {
+    assert_owner_violation!(SecureManualInit, initialize_fixture(), "vault");
+}
+
+#[test]
+fn secure_manual_init_rejects_accounts_of_another_size() {
+    assert_constraint_violation!(
+        SecureManualInit,
+        initialize_fixture(),
+        "vault" => |vault| {
+            let mut data = vault_account(Pubkey::new_unique()).data;
+            data.push(0);
+            *vault = TestAccount::new(crate::ID, data).at(vault.key).signed();
+        },
+        ErrorCode::ConstraintSpace
+    );
+}
+
+#[test]
+fn secure_manual_init_requires_an_owner_signature() {
+    assert_signer_violation!(SecureManualInit, initialize_fixture(), "owner");
+}
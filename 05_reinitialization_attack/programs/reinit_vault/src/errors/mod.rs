@@ -0,0 +1,21 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Account is already initialized")]
+    AlreadyInitialized,
+    #[msg("Account is not initialized")]
+    NotInitialized,
+    #[msg("Unauthorized access attempt")]
+    Unauthorized,
+    #[msg("Insufficient funds for withdrawal")]
+    InsufficientFunds,
+    #[msg("Arithmetic overflow occurred")]
+    ArithmeticOverflow,
+    #[msg("Arithmetic underflow occurred")]
+    ArithmeticUnderflow,
+    #[msg("Vault must be empty before reset")]
+    VaultNotEmpty,
+}
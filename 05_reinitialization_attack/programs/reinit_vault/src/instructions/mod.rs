@@ -0,0 +1,30 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct SecureInitialize<'info> {
+    // SECURITY FIX: Using 'init' constraint prevents reinitialization
+    // Anchor will ensure this account doesn't already exist
+    #[account(
+        init, 
+        payer = owner, 
+        space = 8 + 32 + 8 + 8 + 1
+    )]
+    pub vault: Account<'info, ReinitVault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,45 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureDeposit<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, ReinitVault>,
+    pub depositor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SecureWithdraw<'info> {
+    // SECURITY FIX: Constraint ensures only the owner can withdraw
+    #[account(
+        mut,
+        has_one = owner @ ErrorCode::Unauthorized
+    )]
+    pub vault: Account<'info, ReinitVault>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SecureManualInit<'info> {
+    // SECURITY FIX: Using init_if_needed provides atomic initialization protection
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + 32 + 8 + 8 + 1
+    )]
+    pub vault: Account<'info, ReinitVault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SecureReset<'info> {
+    #[account(
+        mut,
+        has_one = owner @ ErrorCode::Unauthorized
+    )]
+    pub vault: Account<'info, ReinitVault>,
+    pub owner: Signer<'info>,
+}
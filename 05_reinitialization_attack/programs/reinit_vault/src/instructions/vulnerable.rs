@@ -0,0 +1,38 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableInitialize<'info> {
+    // VULNERABILITY: Using 'mut' instead of 'init' allows reinitialization
+    // This account can be called multiple times on the same account
+    #[account(mut)]
+    pub vault: Account<'info, ReinitVault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableDeposit<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, ReinitVault>,
+    pub depositor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableWithdraw<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, ReinitVault>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableManualInit<'info> {
+    // VULNERABILITY: Manual initialization without proper constraints
+    #[account(mut)]
+    pub vault: Account<'info, ReinitVault>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,17 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::ReinitVault;
+
+#[test]
+fn reinit_vault_layout_is_stable() {
+    let reinit_vault = ReinitVault {
+        owner: Pubkey::new_from_array([1; 32]),
+        balance: 0x2122232425262728,
+        total_deposits: 0x3132333435363738,
+        is_initialized: true,
+    };
+    assert_golden!("ReinitVault", golden::anchor(&reinit_vault));
+}
@@ -1,7 +1,23 @@
-use anchor_lang::prelude::*;
+use secref_prelude::*;
 
-declare_id!("ReinitVault11111111111111111111111111111111");
+declare_id!("ReinitVau1t11111111111111111111111111111111");
 
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+#[feature_gated]
 #[program]
 pub mod reinit_vault {
     use super::*;
@@ -22,6 +38,7 @@ pub mod reinit_vault {
     /// 1. No check for existing initialization state
     /// 2. Allows overwriting of critical fields like owner and balance
     /// 3. Can be called by anyone, not just the original owner
+    #[vulnerable]
     pub fn vulnerable_initialize(
         ctx: Context<VulnerableInitialize>, 
         initial_balance: u64
@@ -44,6 +61,7 @@ pub mod reinit_vault {
     /// 
     /// This function itself isn't vulnerable, but it demonstrates how
     /// reinitialization attacks can affect legitimate operations.
+    #[vulnerable]
     pub fn vulnerable_deposit(ctx: Context<VulnerableDeposit>, amount: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -66,6 +84,7 @@ pub mod reinit_vault {
     /// 
     /// Security Issue: After reinitialization, an attacker becomes the new owner
     /// and can withdraw all funds, even those deposited by the original owner.
+    #[vulnerable]
     pub fn vulnerable_withdraw(ctx: Context<VulnerableWithdraw>, amount: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -88,6 +107,7 @@ pub mod reinit_vault {
     /// Security Issue: This function attempts to provide manual initialization
     /// protection but does it incorrectly. The check can be bypassed by
     /// directly modifying the account data or through other vulnerabilities.
+    #[vulnerable]
     pub fn vulnerable_manual_init(
         ctx: Context<VulnerableManualInit>, 
         initial_balance: u64
@@ -123,6 +143,7 @@ pub mod reinit_vault {
     /// Security Fix: Uses Anchor's `init` constraint which automatically
     /// prevents reinitialization by ensuring the account doesn't already exist.
     /// The `init` constraint will fail if the account is already initialized.
+    // Built with either feature: the CTF server seeds its flag vaults through it
     pub fn secure_initialize(
         ctx: Context<SecureInitialize>, 
         initial_balance: u64
@@ -142,6 +163,7 @@ pub mod reinit_vault {
     }
 
     /// SECURE: Deposit function with proper initialization checks
+    #[secure]
     pub fn secure_deposit(ctx: Context<SecureDeposit>, amount: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -159,6 +181,7 @@ pub mod reinit_vault {
     }
 
     /// SECURE: Withdraw function with proper owner validation
+    #[secure]
     pub fn secure_withdraw(ctx: Context<SecureWithdraw>, amount: u64) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -179,6 +202,7 @@ pub mod reinit_vault {
     /// Security Fix: Uses a combination of account constraints and atomic
     /// operations to prevent reinitialization attacks. The discriminator
     /// and account validation provide multiple layers of protection.
+    #[secure]
     pub fn secure_manual_init(
         ctx: Context<SecureManualInit>, 
         initial_balance: u64
@@ -200,6 +224,7 @@ pub mod reinit_vault {
     /// 
     /// Security Fix: If reset functionality is needed, it should be properly
     /// authorized and have clear business logic constraints.
+    #[secure]
     pub fn secure_reset(ctx: Context<SecureReset>) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         
@@ -216,137 +241,3 @@ pub mod reinit_vault {
         Ok(())
     }
 }
-
-// ========================================
-// ACCOUNT STRUCTURES
-// ========================================
-
-#[account]
-pub struct ReinitVault {
-    pub owner: Pubkey,           // 32 bytes - vault owner
-    pub balance: u64,            // 8 bytes - current balance
-    pub total_deposits: u64,     // 8 bytes - lifetime deposits
-    pub is_initialized: bool,    // 1 byte - initialization flag
-}
-
-// ========================================
-// VULNERABLE CONTEXTS
-// ========================================
-
-#[derive(Accounts)]
-pub struct VulnerableInitialize<'info> {
-    // VULNERABILITY: Using 'mut' instead of 'init' allows reinitialization
-    // This account can be called multiple times on the same account
-    #[account(mut)]
-    pub vault: Account<'info, ReinitVault>,
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct VulnerableDeposit<'info> {
-    #[account(mut)]
-    pub vault: Account<'info, ReinitVault>,
-    pub depositor: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct VulnerableWithdraw<'info> {
-    #[account(mut)]
-    pub vault: Account<'info, ReinitVault>,
-    pub owner: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct VulnerableManualInit<'info> {
-    // VULNERABILITY: Manual initialization without proper constraints
-    #[account(mut)]
-    pub vault: Account<'info, ReinitVault>,
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
-
-// ========================================
-// SECURE CONTEXTS
-// ========================================
-
-#[derive(Accounts)]
-pub struct SecureInitialize<'info> {
-    // SECURITY FIX: Using 'init' constraint prevents reinitialization
-    // Anchor will ensure this account doesn't already exist
-    #[account(
-        init, 
-        payer = owner, 
-        space = 8 + 32 + 8 + 8 + 1
-    )]
-    pub vault: Account<'info, ReinitVault>,
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct SecureDeposit<'info> {
-    #[account(mut)]
-    pub vault: Account<'info, ReinitVault>,
-    pub depositor: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct SecureWithdraw<'info> {
-    // SECURITY FIX: Constraint ensures only the owner can withdraw
-    #[account(
-        mut,
-        has_one = owner @ ErrorCode::Unauthorized
-    )]
-    pub vault: Account<'info, ReinitVault>,
-    pub owner: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct SecureManualInit<'info> {
-    // SECURITY FIX: Using init_if_needed provides atomic initialization protection
-    #[account(
-        init_if_needed,
-        payer = owner,
-        space = 8 + 32 + 8 + 8 + 1
-    )]
-    pub vault: Account<'info, ReinitVault>,
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct SecureReset<'info> {
-    #[account(
-        mut,
-        has_one = owner @ ErrorCode::Unauthorized
-    )]
-    pub vault: Account<'info, ReinitVault>,
-    pub owner: Signer<'info>,
-}
-
-// ========================================
-// ERROR DEFINITIONS
-// ========================================
-
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Account is already initialized")]
-    AlreadyInitialized,
-    #[msg("Account is not initialized")]
-    NotInitialized,
-    #[msg("Unauthorized access attempt")]
-    Unauthorized,
-    #[msg("Insufficient funds for withdrawal")]
-    InsufficientFunds,
-    #[msg("Arithmetic overflow occurred")]
-    ArithmeticOverflow,
-    #[msg("Arithmetic underflow occurred")]
-    ArithmeticUnderflow,
-    #[msg("Vault must be empty before reset")]
-    VaultNotEmpty,
-}
\ No newline at end of file
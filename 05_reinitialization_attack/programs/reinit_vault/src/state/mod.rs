@@ -0,0 +1,11 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct ReinitVault {
+    pub owner: Pubkey,           // 32 bytes - vault owner
+    pub balance: u64,            // 8 bytes - current balance
+    pub total_deposits: u64,     // 8 bytes - lifetime deposits
+    pub is_initialized: bool,    // 1 byte - initialization flag
+}
@@ -2,6 +2,8 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { ReinitVault } from "../target/types/reinit_vault";
 import { expect } from "chai";
+import { CuProfiler } from "../../harness/cu-profiler";
+import { PocRecorder } from "../../harness/poc-recorder";
 import { Connection, Keypair } from "@solana/web3.js";
 
 describe("Reinitialization Attack Exploit", () => {
@@ -24,6 +26,16 @@ describe("Reinitialization Attack Exploit", () => {
   
   anchor.setProvider(provider);
 
+  // Records compute units for every instruction executed against a validator
+  const profiler = new CuProfiler("05_reinitialization_attack");
+
+  // Records every transaction as a replayable PoC when SECREF_POC_RECORD=1
+  const recorder = new PocRecorder("05_reinitialization_attack", provider);
+  beforeEach(function () {
+    // Steps are labelled with the test that sent them
+    recorder.test(this.currentTest?.fullTitle());
+  });
+
   // Mock program for testing
   let program: Program<ReinitVault>;
 
@@ -43,6 +55,13 @@ describe("Reinitialization Attack Exploit", () => {
     }
   });
 
+  after(() => {
+    recorder.writeFixture();
+    // Fails the suite if any recorded instruction regressed past its CU budget
+    profiler.writeSummary();
+    profiler.assertBudgets();
+  });
+
   beforeEach(async () => {
     // Generate fresh keypairs for each test
     legitimateOwner = anchor.web3.Keypair.generate();
@@ -0,0 +1,26 @@
+//! Fuzzes the bounded recipient parser.
+//!
+//! Properties checked for every input:
+//! - the parser never panics and never returns more than MAX_RECIPIENTS keys
+//! - accepted payloads are canonical: exactly prefix + keys, no trailing bytes
+//! - accepted payloads decode identically with Borsh and re-encode byte-for-byte
+
+#![no_main]
+
+use airdrop_list::{parse_recipients_bounded, LENGTH_PREFIX_SIZE, MAX_RECIPIENTS, PUBKEY_SIZE};
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, AnchorSerialize};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(recipients) = parse_recipients_bounded(data, MAX_RECIPIENTS) else {
+        return;
+    };
+
+    assert!(recipients.len() <= MAX_RECIPIENTS);
+    assert_eq!(data.len(), LENGTH_PREFIX_SIZE + recipients.len() * PUBKEY_SIZE);
+
+    let borsh = Vec::<Pubkey>::try_from_slice(data).expect("accepted payload must be valid Borsh");
+    assert_eq!(borsh, recipients);
+    assert_eq!(recipients.try_to_vec().expect("serializable"), data);
+});
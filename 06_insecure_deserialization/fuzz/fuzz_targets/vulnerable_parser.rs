@@ -0,0 +1,15 @@
+//! Fuzzes the unbounded recipient parser.
+//!
+//! This target is EXPECTED to fail. Run it with a memory limit, e.g.
+//! `cargo fuzz run vulnerable_parser -- -rss_limit_mb=64 -malloc_limit_mb=64`,
+//! and libFuzzer reports a crash within seconds once it mutates the length
+//! prefix - the same kind of input that aborts the on-chain program.
+
+#![no_main]
+
+use airdrop_list::parse_recipients_unbounded;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = parse_recipients_unbounded(data);
+});
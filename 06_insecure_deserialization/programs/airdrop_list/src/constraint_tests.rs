@@ -0,0 +1,29 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use secref_testkit::{assert_has_one_violation, assert_owner_violation, assert_signer_violation, Fixture, TestAccount};
+
+use crate::{RecipientList, SecureSetRecipients};
+
+fn list_fixture() -> Fixture {
+    let authority = TestAccount::signer();
+    let list = TestAccount::anchor(&RecipientList {
+        authority: authority.key,
+        recipients: vec![],
+    });
+    Fixture::new().with("list", list).with("authority", authority)
+}
+
+#[test]
+fn secure_set_recipients_requires_the_list_authority() {
+    assert_has_one_violation!(SecureSetRecipients, list_fixture(), "authority");
+}
+
+#[test]
+fn secure_set_recipients_requires_an_authority_signature() {
+    assert_signer_violation!(SecureSetRecipients, list_fixture(), "authority");
+}
+
+#[test]
+fn secure_set_recipients_rejects_lists_of_other_programs() {
+    assert_owner_violation!(SecureSetRecipients, list_fixture(), "list");
+}
@@ -0,0 +1,17 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Payload is too short to contain a length prefix")]
+    PayloadTooShort,
+    #[msg("Declared recipient count exceeds the maximum")]
+    TooManyRecipients,
+    #[msg("Payload length does not match the declared recipient count")]
+    LengthMismatch,
+    #[msg("Recipient appears more than once")]
+    DuplicateRecipient,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
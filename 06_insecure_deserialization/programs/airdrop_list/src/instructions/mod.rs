@@ -0,0 +1,29 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializeList<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + LENGTH_PREFIX_SIZE + PUBKEY_SIZE * MAX_RECIPIENTS, // discriminator + authority + vec prefix + recipients
+    )]
+    pub list: Account<'info, RecipientList>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
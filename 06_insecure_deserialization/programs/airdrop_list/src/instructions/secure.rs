@@ -0,0 +1,11 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureSetRecipients<'info> {
+    #[account(mut, has_one = authority)]
+    pub list: Account<'info, RecipientList>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,14 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+// Account validation is identical to the secure version; the vulnerability
+// is entirely in how the instruction data is decoded.
+
+#[derive(Accounts)]
+pub struct VulnerableSetRecipients<'info> {
+    #[account(mut, has_one = authority)]
+    pub list: Account<'info, RecipientList>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,15 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::RecipientList;
+
+#[test]
+fn recipient_list_layout_is_stable() {
+    let recipient_list = RecipientList {
+        authority: Pubkey::new_from_array([1; 32]),
+        recipients: vec![Pubkey::new_from_array([2; 32]), Pubkey::new_from_array([3; 32])],
+    };
+    assert_golden!("RecipientList", golden::anchor(&recipient_list));
+}
@@ -0,0 +1,179 @@
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Maximum number of recipients a list account has space for
+pub const MAX_RECIPIENTS: usize = 16;
+
+/// Size of the Borsh `u32` length prefix that precedes every vector
+pub const LENGTH_PREFIX_SIZE: usize = 4;
+
+/// Size of one serialized `Pubkey`
+pub const PUBKEY_SIZE: usize = 32;
+
+#[feature_gated]
+#[program]
+pub mod airdrop_list {
+    use super::*;
+
+    /// Create an empty recipient list owned by `authority`
+    ///
+    /// Shared by both implementations so the only difference between them is
+    /// how the recipient payload is decoded.
+    pub fn initialize_list(ctx: Context<InitializeList>) -> Result<()> {
+        let list = &mut ctx.accounts.list;
+        list.authority = ctx.accounts.authority.key();
+        list.recipients = Vec::new();
+
+        msg!("Recipient list initialized with authority: {}", list.authority);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Replace the recipient list from a raw, length-prefixed payload
+    ///
+    /// Security Issue: The payload uses the same layout as a Borsh `Vec<Pubkey>`
+    /// (a little-endian `u32` count followed by the keys), but it is decoded by
+    /// `parse_recipients_unbounded`, which trusts the declared count. A single
+    /// transaction can request a multi-gigabyte allocation (aborting the program
+    /// with an out-of-memory error), spin the decode loop until the compute
+    /// budget runs out, or submit a payload whose decoded contents differ from
+    /// what the signer reviewed.
+    #[vulnerable]
+    pub fn vulnerable_set_recipients(ctx: Context<VulnerableSetRecipients>, payload: Vec<u8>) -> Result<()> {
+        // VULNERABILITY: The declared length drives allocation and iteration
+        // before anything checks it against MAX_RECIPIENTS or the payload size.
+        let recipients = parse_recipients_unbounded(&payload)?;
+
+        let list = &mut ctx.accounts.list;
+        // VULNERABILITY: If more than MAX_RECIPIENTS keys were decoded, the
+        // account cannot hold them and Anchor fails while serializing the
+        // account on exit - an obscure error far away from the real cause.
+        list.recipients = recipients;
+
+        msg!("Recipient list replaced with {} recipients (unbounded parser)", list.recipients.len());
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version of the same instruction
+    // with the declared length validated before any allocation.
+
+    /// SECURE: Replace the recipient list after validating the length prefix
+    ///
+    /// Security Fix: `parse_recipients_bounded` rejects counts above
+    /// MAX_RECIPIENTS before allocating, requires the payload size to match the
+    /// declared count exactly (no truncation, no trailing bytes), and rejects
+    /// duplicate recipients that would otherwise be paid twice.
+    #[secure]
+    pub fn secure_set_recipients(ctx: Context<SecureSetRecipients>, payload: Vec<u8>) -> Result<()> {
+        // SECURITY: Every property of the payload is validated up front, so a
+        // malformed list fails with a specific error before touching state.
+        let recipients = parse_recipients_bounded(&payload, MAX_RECIPIENTS)?;
+
+        let list = &mut ctx.accounts.list;
+        list.recipients = recipients;
+
+        msg!("Recipient list securely replaced with {} recipients", list.recipients.len());
+        Ok(())
+    }
+}
+
+// ========================================
+// PAYLOAD PARSERS
+// ========================================
+// Public so the fuzz targets in `fuzz/` can exercise them directly.
+
+fn read_length_prefix(data: &[u8]) -> Result<usize> {
+    let prefix: [u8; LENGTH_PREFIX_SIZE] = data
+        .get(..LENGTH_PREFIX_SIZE)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ErrorCode::PayloadTooShort)?;
+    Ok(u32::from_le_bytes(prefix) as usize)
+}
+
+fn read_pubkey(bytes: &[u8]) -> Pubkey {
+    let mut key = [0u8; PUBKEY_SIZE];
+    key.copy_from_slice(bytes);
+    Pubkey::new_from_array(key)
+}
+
+/// VULNERABLE: Decode a length-prefixed list of public keys
+///
+/// Security Issue: Trusts the declared length for allocation and iteration,
+/// silently stops when the payload runs out, and ignores trailing bytes.
+pub fn parse_recipients_unbounded(data: &[u8]) -> Result<Vec<Pubkey>> {
+    let declared = read_length_prefix(data)?;
+
+    // VULNERABILITY: Allocation sized by an attacker-controlled value. A prefix
+    // of 0xFFFFFFFF requests ~137 GB; the 32 KB program heap aborts instead.
+    let mut recipients = Vec::with_capacity(declared);
+
+    // VULNERABILITY: Iterates `declared` times even though at most ~35 keys fit
+    // in a transaction - wasted compute that an attacker controls.
+    for index in 0..declared {
+        let start = LENGTH_PREFIX_SIZE + index * PUBKEY_SIZE;
+        match data.get(start..start + PUBKEY_SIZE) {
+            Some(bytes) => recipients.push(read_pubkey(bytes)),
+            // VULNERABILITY: A short payload is silently truncated instead of
+            // rejected, so the stored list can differ from the declared one.
+            None => continue,
+        }
+    }
+
+    // VULNERABILITY: Trailing bytes after the declared keys are never checked,
+    // so many distinct payloads decode to the same list (and vice versa).
+    Ok(recipients)
+}
+
+/// SECURE: Decode a length-prefixed list of public keys with strict bounds
+///
+/// Security Fix: Validates the declared length against `max_len` before
+/// allocating, requires an exact payload size, and rejects duplicates.
+pub fn parse_recipients_bounded(data: &[u8], max_len: usize) -> Result<Vec<Pubkey>> {
+    let declared = read_length_prefix(data)?;
+
+    // SECURITY: Bound the declared length BEFORE allocating anything
+    require!(declared <= max_len, ErrorCode::TooManyRecipients);
+
+    // SECURITY: The payload must be exactly prefix + declared keys - no
+    // truncated lists and no trailing bytes
+    let expected_len = declared
+        .checked_mul(PUBKEY_SIZE)
+        .and_then(|keys| keys.checked_add(LENGTH_PREFIX_SIZE))
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    require!(data.len() == expected_len, ErrorCode::LengthMismatch);
+
+    let mut recipients: Vec<Pubkey> = Vec::with_capacity(declared);
+    for bytes in data[LENGTH_PREFIX_SIZE..].chunks_exact(PUBKEY_SIZE) {
+        let recipient = read_pubkey(bytes);
+        // SECURITY: A duplicated recipient would be paid twice by any
+        // distribution that iterates the list
+        require!(!recipients.contains(&recipient), ErrorCode::DuplicateRecipient);
+        recipients.push(recipient);
+    }
+
+    Ok(recipients)
+}
@@ -0,0 +1,11 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct RecipientList {
+    /// The authority allowed to replace the recipient list (32 bytes)
+    pub authority: Pubkey,
+    /// Recipients of the next distribution (4 + 32 * MAX_RECIPIENTS bytes)
+    pub recipients: Vec<Pubkey>,
+}
@@ -0,0 +1,71 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::Space;
+use secref_testkit::{
+    assert_has_one_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, Fixture,
+    TestAccount,
+};
+
+use crate::{instruction, Profile, SecureRegister, SecureUpdateBio};
+
+const HANDLE: &str = "a-handle-long-enough-to-overflow-a-seed";
+
+fn profile_pda(handle: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"handle_hash", hash(handle.as_bytes()).as_ref()], &crate::ID)
+}
+
+fn register_fixture() -> Fixture {
+    let (address, _) = profile_pda(HANDLE);
+    Fixture::new()
+        .with(
+            "profile",
+            TestAccount::uninitialized(8 + Profile::INIT_SPACE).at(address),
+        )
+        .with("owner", TestAccount::signer())
+        .with("system_program", TestAccount::system_program())
+        .args(instruction::SecureRegister {
+            handle: HANDLE.to_string(),
+            bio: vec![],
+        })
+}
+
+fn update_fixture() -> Fixture {
+    let owner = TestAccount::signer();
+    let (address, bump) = profile_pda(HANDLE);
+    let profile = TestAccount::anchor(&Profile {
+        owner: owner.key,
+        handle: HANDLE.to_string(),
+        bio: String::new(),
+        bump,
+    })
+    .at(address);
+    Fixture::new().with("profile", profile).with("owner", owner)
+}
+
+#[test]
+fn secure_register_derives_the_profile_from_the_handle_hash() {
+    assert_seeds_violation!(SecureRegister, register_fixture(), "profile");
+}
+
+#[test]
+fn secure_register_requires_an_owner_signature() {
+    assert_signer_violation!(SecureRegister, register_fixture(), "owner");
+}
+
+#[test]
+fn secure_update_bio_requires_the_profile_owner() {
+    assert_has_one_violation!(SecureUpdateBio, update_fixture(), "owner");
+    assert_signer_violation!(SecureUpdateBio, update_fixture(), "owner");
+}
+
+#[test]
+fn secure_update_bio_derives_the_profile_from_its_handle() {
+    assert_seeds_violation!(SecureUpdateBio, update_fixture(), "profile");
+}
+
+#[test]
+fn secure_update_bio_rejects_profiles_of_other_programs() {
+    assert_owner_violation!(SecureUpdateBio, update_fixture(), "profile");
+}
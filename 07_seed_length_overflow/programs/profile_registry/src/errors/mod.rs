@@ -0,0 +1,17 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Handle must not be empty")]
+    HandleEmpty,
+    #[msg("Handle exceeds the maximum length in bytes")]
+    HandleTooLong,
+    #[msg("Handle may only contain ASCII letters, digits and underscores")]
+    InvalidHandleCharacter,
+    #[msg("Bio exceeds the maximum length in bytes")]
+    BioTooLong,
+    #[msg("Bio is not valid UTF-8")]
+    InvalidUtf8,
+}
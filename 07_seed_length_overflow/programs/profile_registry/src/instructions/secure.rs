@@ -0,0 +1,38 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use anchor_lang::solana_program::hash::hash;
+
+use super::*;
+
+#[derive(Accounts)]
+#[instruction(handle: String)]
+pub struct SecureRegister<'info> {
+    #[account(
+        init,
+        payer = owner,
+        // SECURITY: Space derived from the struct, including length prefixes
+        space = 8 + Profile::INIT_SPACE, // discriminator + owner + handle + bio + bump
+        // SECURITY: Seed derivation runs before the handler, so the handle is
+        // hashed to a fixed 32 bytes instead of being used (or cut) directly
+        seeds = [b"handle_hash", hash(handle.as_bytes()).as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SecureUpdateBio<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [b"handle_hash", hash(profile.handle.as_bytes()).as_ref()],
+        bump = profile.bump
+    )]
+    pub profile: Account<'info, Profile>,
+
+    pub owner: Signer<'info>,
+}
@@ -0,0 +1,31 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+#[instruction(handle: String)]
+pub struct VulnerableRegister<'info> {
+    #[account(
+        init,
+        payer = owner,
+        // VULNERABILITY: Hand-counted space with no room for the two 4-byte
+        // String length prefixes
+        space = 8 + 32 + MAX_HANDLE_LEN + MAX_BIO_LEN + 1, // discriminator + owner + handle + bio + bump
+        // VULNERABILITY: Truncated seed - handles sharing a 32-byte prefix collide
+        seeds = [b"handle", &handle.as_bytes()[..handle.len().min(MAX_SEED_LEN)]],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableUpdateBio<'info> {
+    #[account(mut, has_one = owner)]
+    pub profile: Account<'info, Profile>,
+
+    pub owner: Signer<'info>,
+}
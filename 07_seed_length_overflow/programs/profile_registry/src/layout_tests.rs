@@ -0,0 +1,17 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::Profile;
+
+#[test]
+fn profile_layout_is_stable() {
+    let profile = Profile {
+        owner: Pubkey::new_from_array([1; 32]),
+        handle: "handle".to_string(),
+        bio: "bio".to_string(),
+        bump: 33,
+    };
+    assert_golden!("Profile", golden::anchor(&profile));
+}
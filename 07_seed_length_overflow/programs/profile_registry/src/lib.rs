@@ -0,0 +1,155 @@
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Maximum length of a single PDA seed enforced by the runtime
+pub const MAX_SEED_LEN: usize = 32;
+
+/// Maximum handle length in BYTES (not characters)
+pub const MAX_HANDLE_LEN: usize = 32;
+
+/// Maximum bio length in BYTES (not characters)
+pub const MAX_BIO_LEN: usize = 64;
+
+#[feature_gated]
+#[program]
+pub mod profile_registry {
+    use super::*;
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Register a profile under a user-chosen handle
+    ///
+    /// Security Issue: The handle is cut to the 32-byte seed limit before it
+    /// is used as a seed, so every handle sharing its first 32 bytes maps to
+    /// the same PDA. Cutting at a byte offset can also split a multi-byte
+    /// character, and the lossy UTF-8 conversion then stores a handle that
+    /// differs from the one the user asked for. The account space is counted
+    /// by hand and forgets the 4-byte length prefix of each `String`, so a
+    /// maximum-length profile fails when Anchor serializes it on exit.
+    #[vulnerable]
+    pub fn vulnerable_register(ctx: Context<VulnerableRegister>, handle: String, bio: Vec<u8>) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+        profile.owner = ctx.accounts.owner.key();
+
+        // VULNERABILITY: Byte truncation instead of rejection. "alice" padded
+        // to 40 bytes and "alice" padded to 33 bytes become the same handle.
+        let truncated = &handle.as_bytes()[..handle.len().min(MAX_HANDLE_LEN)];
+        profile.handle = String::from_utf8_lossy(truncated).into_owned();
+
+        // VULNERABILITY: Invalid UTF-8 is silently replaced with U+FFFD. Each
+        // replacement is 3 bytes, so the stored bio can be LONGER than the input.
+        profile.bio = String::from_utf8_lossy(&bio).into_owned();
+        profile.bump = ctx.bumps.profile;
+
+        msg!("Profile registered for handle: {}", profile.handle);
+        Ok(())
+    }
+
+    /// VULNERABLE: Replace the bio of an existing profile
+    ///
+    /// Security Issue: No length or UTF-8 validation. Oversized bios are only
+    /// caught when the account fails to serialize, with an error that does
+    /// not point at the bio at all.
+    #[vulnerable]
+    pub fn vulnerable_update_bio(ctx: Context<VulnerableUpdateBio>, bio: Vec<u8>) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+
+        // VULNERABILITY: Lossy conversion hides malformed input
+        profile.bio = String::from_utf8_lossy(&bio).into_owned();
+
+        msg!("Bio updated ({} bytes stored)", profile.bio.len());
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version of the same instructions
+    // with lengths and encodings validated before any state is written.
+
+    /// SECURE: Register a profile under a validated handle
+    ///
+    /// Security Fix: The PDA is derived from the SHA-256 hash of the full
+    /// handle, which is always exactly 32 bytes, so distinct handles can never
+    /// share an address and oversized handles cannot abort seed derivation.
+    /// The handle and bio are validated for length (in bytes), character set
+    /// and UTF-8 before they are stored, and the account is sized with
+    /// `InitSpace` so the length prefixes are always counted.
+    #[secure]
+    pub fn secure_register(ctx: Context<SecureRegister>, handle: String, bio: Vec<u8>) -> Result<()> {
+        // SECURITY: Reject instead of truncating
+        validate_handle(&handle)?;
+        let bio = validate_bio(bio)?;
+
+        let profile = &mut ctx.accounts.profile;
+        profile.owner = ctx.accounts.owner.key();
+        profile.handle = handle;
+        profile.bio = bio;
+        profile.bump = ctx.bumps.profile;
+
+        msg!("Profile securely registered for handle: {}", profile.handle);
+        Ok(())
+    }
+
+    /// SECURE: Replace the bio of an existing profile
+    ///
+    /// Security Fix: The bio must be valid UTF-8 and fit MAX_BIO_LEN bytes,
+    /// so the account always has room for it and the error names the cause.
+    #[secure]
+    pub fn secure_update_bio(ctx: Context<SecureUpdateBio>, bio: Vec<u8>) -> Result<()> {
+        // SECURITY: Validate before mutating state
+        let bio = validate_bio(bio)?;
+
+        let profile = &mut ctx.accounts.profile;
+        profile.bio = bio;
+
+        msg!("Bio securely updated ({} bytes stored)", profile.bio.len());
+        Ok(())
+    }
+}
+
+// ========================================
+// INPUT VALIDATION
+// ========================================
+
+/// Handles are 1..=MAX_HANDLE_LEN bytes of ASCII letters, digits and '_'
+///
+/// Restricting the character set keeps byte length equal to character length
+/// and rules out look-alike Unicode handles.
+#[cfg(feature = "secure")]
+fn validate_handle(handle: &str) -> Result<()> {
+    require!(!handle.is_empty(), ErrorCode::HandleEmpty);
+    require!(handle.len() <= MAX_HANDLE_LEN, ErrorCode::HandleTooLong);
+    require!(
+        handle.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'_'),
+        ErrorCode::InvalidHandleCharacter
+    );
+    Ok(())
+}
+
+/// Bios are arbitrary UTF-8 up to MAX_BIO_LEN bytes
+#[cfg(feature = "secure")]
+fn validate_bio(bio: Vec<u8>) -> Result<String> {
+    require!(bio.len() <= MAX_BIO_LEN, ErrorCode::BioTooLong);
+    String::from_utf8(bio).map_err(|_| error!(ErrorCode::InvalidUtf8))
+}
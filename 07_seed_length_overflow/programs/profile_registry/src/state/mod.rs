@@ -0,0 +1,18 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Profile {
+    /// The wallet that registered the handle (32 bytes)
+    pub owner: Pubkey,
+    /// The registered handle (4 + MAX_HANDLE_LEN bytes)
+    #[max_len(MAX_HANDLE_LEN)]
+    pub handle: String,
+    /// Free-form profile text (4 + MAX_BIO_LEN bytes)
+    #[max_len(MAX_BIO_LEN)]
+    pub bio: String,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
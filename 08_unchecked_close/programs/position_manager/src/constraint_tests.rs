@@ -0,0 +1,49 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, Fixture,
+    TestAccount,
+};
+
+use crate::{Position, SecureClosePosition};
+
+fn close_fixture() -> Fixture {
+    let owner = TestAccount::signer();
+    let (address, bump) = Pubkey::find_program_address(&[b"position", owner.key.as_ref()], &crate::ID);
+    let position = TestAccount::anchor(&Position {
+        owner: owner.key,
+        deposited: 0,
+        bump,
+    })
+    .at(address);
+    Fixture::new().with("position", position).with("owner", owner)
+}
+
+#[test]
+fn secure_close_position_requires_the_position_owner() {
+    // Another signer fails the seeds first: they derive a different position
+    assert_seeds_violation!(SecureClosePosition, close_fixture(), "owner");
+    assert_signer_violation!(SecureClosePosition, close_fixture(), "owner");
+}
+
+#[test]
+fn secure_close_position_requires_the_recorded_owner() {
+    // At the signer's own address but recording someone else, only `has_one` catches it
+    assert_constraint_violation!(
+        SecureClosePosition,
+        close_fixture(),
+        "position" => |position| position.edit(|stored: &mut Position| stored.owner = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+}
+
+#[test]
+fn secure_close_position_derives_the_position_from_its_owner() {
+    assert_seeds_violation!(SecureClosePosition, close_fixture(), "position");
+}
+
+#[test]
+fn secure_close_position_rejects_positions_of_other_programs() {
+    assert_owner_violation!(SecureClosePosition, close_fixture(), "position");
+}
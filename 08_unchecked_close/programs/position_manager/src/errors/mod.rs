@@ -0,0 +1,13 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Position still records a deposit and cannot be closed")]
+    PositionNotEmpty,
+    #[msg("Withdrawal exceeds the deposited amount")]
+    InsufficientDeposit,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
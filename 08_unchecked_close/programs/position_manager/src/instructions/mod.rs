@@ -0,0 +1,82 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 8 + 1, // discriminator + total_deposits + bump
+        seeds = [b"pool"],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 32 + 8 + 1, // discriminator + owner + deposited + bump
+        seeds = [b"position", owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut, seeds = [b"pool"], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [b"position", owner.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, seeds = [b"pool"], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [b"position", owner.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
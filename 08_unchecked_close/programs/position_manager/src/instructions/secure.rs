@@ -0,0 +1,21 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureClosePosition<'info> {
+    #[account(
+        mut,
+        // SECURITY: Position must belong to the signer...
+        has_one = owner,
+        seeds = [b"position", owner.key().as_ref()],
+        bump = position.bump,
+        // SECURITY: ...and the rent can only go back to them
+        close = owner
+    )]
+    pub position: Account<'info, Position>,
+
+    // SECURITY: The owner must consent to closing their own position
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
@@ -0,0 +1,29 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableClosePosition<'info> {
+    // VULNERABILITY: No has_one, no seeds tied to the caller - any position
+    #[account(mut, close = destination)]
+    pub position: Account<'info, Position>,
+
+    /// CHECK: VULNERABILITY - Caller-chosen refund destination
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    // VULNERABILITY: Any signer at all
+    pub closer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableClosePositionToOwner<'info> {
+    #[account(mut, has_one = owner, close = owner)]
+    pub position: Account<'info, Position>,
+
+    /// CHECK: VULNERABILITY - Address matched by has_one, but never required to sign
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    pub closer: Signer<'info>,
+}
@@ -0,0 +1,25 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Pool, Position};
+
+#[test]
+fn pool_layout_is_stable() {
+    let pool = Pool {
+        total_deposits: 0x1112131415161718,
+        bump: 33,
+    };
+    assert_golden!("Pool", golden::anchor(&pool));
+}
+
+#[test]
+fn position_layout_is_stable() {
+    let position = Position {
+        owner: Pubkey::new_from_array([3; 32]),
+        deposited: 0x4142434445464748,
+        bump: 81,
+    };
+    assert_golden!("Position", golden::anchor(&position));
+}
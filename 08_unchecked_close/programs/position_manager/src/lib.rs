@@ -0,0 +1,154 @@
+use anchor_lang::system_program::{self, Transfer};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+#[feature_gated]
+#[program]
+pub mod position_manager {
+    use super::*;
+
+    /// Create the shared pool that holds every depositor's lamports
+    pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        pool.total_deposits = 0;
+        pool.bump = ctx.bumps.pool;
+
+        msg!("Pool initialized");
+        Ok(())
+    }
+
+    /// Open an empty position for `owner`
+    ///
+    /// The position is the only record of how much of the pool belongs to its
+    /// owner; deleting it while it still tracks a deposit strands that deposit.
+    pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.owner = ctx.accounts.owner.key();
+        position.deposited = 0;
+        position.bump = ctx.bumps.position;
+
+        msg!("Position opened for owner: {}", position.owner);
+        Ok(())
+    }
+
+    /// Move lamports from the owner into the pool and record them
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.pool.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let position = &mut ctx.accounts.position;
+        position.deposited = position.deposited.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+        let pool = &mut ctx.accounts.pool;
+        pool.total_deposits = pool.total_deposits.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Deposited {} lamports, position now holds {}", amount, position.deposited);
+        Ok(())
+    }
+
+    /// Return recorded lamports from the pool to the owner
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        require!(amount <= position.deposited, ErrorCode::InsufficientDeposit);
+        position.deposited -= amount;
+        let pool = &mut ctx.accounts.pool;
+        pool.total_deposits = pool.total_deposits.checked_sub(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // The pool is owned by this program, so lamports can be moved directly
+        **pool.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.owner.to_account_info().try_borrow_mut_lamports()? += amount;
+
+        msg!("Withdrew {} lamports, position now holds {}", amount, position.deposited);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Close a position and refund its rent to a caller-chosen account
+    ///
+    /// Security Issue: The `close` constraint sends the position's lamports to
+    /// whatever `destination` the caller passes, and nothing ties the caller to
+    /// the position's owner. Any signer can close any position and collect its
+    /// rent, and because the position may still record a deposit, the owner's
+    /// lamports are left in the pool with no record that they exist.
+    #[vulnerable]
+    pub fn vulnerable_close_position(ctx: Context<VulnerableClosePosition>) -> Result<()> {
+        // VULNERABILITY: No ownership check and no check that the position is
+        // empty - Anchor's `close = destination` does the rest on exit.
+        msg!(
+            "Position of {} closed by {}, rent sent to {}",
+            ctx.accounts.position.owner,
+            ctx.accounts.closer.key(),
+            ctx.accounts.destination.key()
+        );
+        Ok(())
+    }
+
+    /// VULNERABLE: Close a position and refund its rent to the recorded owner
+    ///
+    /// Security Issue: This "fixed" version pins the refund to the owner with
+    /// `has_one = owner` and `close = owner`, so an attacker gains nothing -
+    /// but the owner never has to sign. Anyone can still delete a position
+    /// that tracks a deposit, bricking the owner's claim on the pool purely
+    /// to grief them.
+    #[vulnerable]
+    pub fn vulnerable_close_position_to_owner(ctx: Context<VulnerableClosePositionToOwner>) -> Result<()> {
+        // VULNERABILITY: The owner is an UncheckedAccount matched by has_one,
+        // which proves the address is right but not that the owner consented.
+        msg!(
+            "Position of {} closed by {} with {} lamports still deposited",
+            ctx.accounts.owner.key(),
+            ctx.accounts.closer.key(),
+            ctx.accounts.position.deposited
+        );
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version of the close instruction
+    // with ownership, consent and emptiness all enforced.
+
+    /// SECURE: Close an empty position on behalf of its owner
+    ///
+    /// Security Fix: `has_one = owner` with the owner as a `Signer` means only
+    /// the owner can close the position, `close = owner` means the rent can
+    /// only go back to them, and the handler refuses to close a position that
+    /// still records a deposit.
+    #[secure]
+    pub fn secure_close_position(ctx: Context<SecureClosePosition>) -> Result<()> {
+        // SECURITY: Closing must never destroy the record of a live deposit
+        require!(ctx.accounts.position.deposited == 0, ErrorCode::PositionNotEmpty);
+
+        msg!("Position securely closed by owner: {}", ctx.accounts.owner.key());
+        Ok(())
+    }
+}
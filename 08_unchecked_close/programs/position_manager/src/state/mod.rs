@@ -0,0 +1,21 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Pool {
+    /// Sum of every position's recorded deposit (8 bytes)
+    pub total_deposits: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Position {
+    /// The depositor this position belongs to (32 bytes)
+    pub owner: Pubkey,
+    /// Lamports this owner has in the pool (8 bytes)
+    pub deposited: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
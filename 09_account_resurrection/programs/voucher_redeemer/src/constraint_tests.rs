@@ -0,0 +1,42 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_owner_violation, assert_seeds_violation, assert_signer_violation, Fixture, TestAccount};
+
+use crate::{SecureRedeem, Treasury, Voucher};
+
+fn redeem_fixture() -> Fixture {
+    let owner = TestAccount::signer();
+    let (address, bump) = Pubkey::find_program_address(&[b"treasury"], &crate::ID);
+    let treasury = TestAccount::anchor(&Treasury {
+        authority: Pubkey::new_unique(),
+        total_redeemed: 0,
+        bump,
+    })
+    .at(address);
+    let voucher = TestAccount::anchor(&Voucher {
+        owner: owner.key,
+        amount: 1_000,
+    });
+    Fixture::new()
+        .with("treasury", treasury)
+        .with("voucher", voucher)
+        .with("owner", owner)
+}
+
+#[test]
+fn secure_redeem_pays_from_the_treasury_pda() {
+    assert_seeds_violation!(SecureRedeem, redeem_fixture(), "treasury");
+    assert_owner_violation!(SecureRedeem, redeem_fixture(), "treasury");
+}
+
+#[test]
+fn secure_redeem_rejects_vouchers_of_other_programs() {
+    // `voucher` is unchecked, so the `owner = crate::ID` constraint reports it
+    assert_owner_violation!(SecureRedeem, redeem_fixture(), "voucher", ErrorCode::ConstraintOwner);
+}
+
+#[test]
+fn secure_redeem_requires_an_owner_signature() {
+    assert_signer_violation!(SecureRedeem, redeem_fixture(), "owner");
+}
@@ -0,0 +1,17 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Treasury cannot cover the voucher")]
+    InsufficientTreasury,
+    #[msg("Signer does not own this voucher")]
+    NotVoucherOwner,
+    #[msg("Account is not marked as closed")]
+    AccountNotClosed,
+    #[msg("Failed to write the closed-account discriminator")]
+    AccountDidNotClose,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
@@ -0,0 +1,27 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureRedeem<'info> {
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+
+    /// CHECK: Owned by this program; deserialized and owner-checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub voucher: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ForceDefund<'info> {
+    /// CHECK: Must be owned by this program and carry CLOSED_ACCOUNT_DISCRIMINATOR
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+
+    /// CHECK: Any account may collect lamports from a closed account
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+}
@@ -0,0 +1,15 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableRedeem<'info> {
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut, has_one = owner)]
+    pub voucher: Account<'info, Voucher>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
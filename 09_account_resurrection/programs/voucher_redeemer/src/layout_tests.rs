@@ -0,0 +1,25 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Treasury, Voucher};
+
+#[test]
+fn treasury_layout_is_stable() {
+    let treasury = Treasury {
+        authority: Pubkey::new_from_array([1; 32]),
+        total_redeemed: 0x2122232425262728,
+        bump: 49,
+    };
+    assert_golden!("Treasury", golden::anchor(&treasury));
+}
+
+#[test]
+fn voucher_layout_is_stable() {
+    let voucher = Voucher {
+        owner: Pubkey::new_from_array([4; 32]),
+        amount: 0x5152535455565758,
+    };
+    assert_golden!("Voucher", golden::anchor(&voucher));
+}
@@ -0,0 +1,205 @@
+use anchor_lang::system_program::{self, Transfer};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Discriminator written over closed accounts so they can never deserialize again
+pub const CLOSED_ACCOUNT_DISCRIMINATOR: [u8; 8] = [255; 8];
+
+#[feature_gated]
+#[program]
+pub mod voucher_redeemer {
+    use super::*;
+
+    /// Create the treasury that pays out vouchers
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.authority = ctx.accounts.authority.key();
+        treasury.total_redeemed = 0;
+        treasury.bump = ctx.bumps.treasury;
+
+        msg!("Treasury initialized with authority: {}", treasury.authority);
+        Ok(())
+    }
+
+    /// Move lamports from the authority into the treasury
+    pub fn fund_treasury(ctx: Context<FundTreasury>, amount: u64) -> Result<()> {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Treasury funded with {} lamports", amount);
+        Ok(())
+    }
+
+    /// Issue a single-use voucher worth `amount` lamports to `owner`
+    pub fn issue_voucher(ctx: Context<IssueVoucher>, amount: u64) -> Result<()> {
+        let voucher = &mut ctx.accounts.voucher;
+        voucher.owner = ctx.accounts.owner.key();
+        voucher.amount = amount;
+
+        msg!("Voucher for {} lamports issued to {}", amount, voucher.owner);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Redeem a voucher and "close" it by draining its lamports
+    ///
+    /// Security Issue: The runtime only garbage-collects zero-lamport accounts
+    /// when the transaction ends. Until then the voucher keeps its data and
+    /// its discriminator, so a second `vulnerable_redeem` instruction in the
+    /// same transaction deserializes it and pays out again. If the attacker
+    /// also transfers the rent back before the transaction ends, the voucher
+    /// survives garbage collection and can be redeemed forever.
+    #[vulnerable]
+    pub fn vulnerable_redeem(ctx: Context<VulnerableRedeem>) -> Result<()> {
+        let amount = ctx.accounts.voucher.amount;
+        pay_out(&mut ctx.accounts.treasury, &ctx.accounts.owner.to_account_info(), amount)?;
+
+        // VULNERABILITY: Draining lamports is not closing. The data - including
+        // the Voucher discriminator and `amount` - is untouched, and the
+        // account is still owned by this program until the transaction ends.
+        let voucher_info = ctx.accounts.voucher.to_account_info();
+        let owner_info = ctx.accounts.owner.to_account_info();
+        let rent = voucher_info.lamports();
+        **voucher_info.try_borrow_mut_lamports()? = 0;
+        **owner_info.try_borrow_mut_lamports()? = owner_info
+            .lamports()
+            .checked_add(rent)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Voucher redeemed for {} lamports (lamports drained, data left intact)", amount);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version of the same instruction
+    // with the voucher's data destroyed as part of closing it.
+
+    /// SECURE: Redeem a voucher and close it properly
+    ///
+    /// Security Fix: After draining the lamports, the account data is zeroed
+    /// and CLOSED_ACCOUNT_DISCRIMINATOR is written over the discriminator.
+    /// Any later instruction in the same transaction fails to deserialize the
+    /// voucher, and a voucher revived by refunding its rent stays unusable
+    /// until `force_defund` drains it again.
+    ///
+    /// The voucher is taken as an `UncheckedAccount` and deserialized by hand:
+    /// a mutable `Account<Voucher>` is serialized back on exit, which would
+    /// overwrite the closed marker with the old voucher data. Anchor's
+    /// `close = owner` constraint avoids this by reassigning the account to
+    /// the System Program and shrinking it to zero bytes; this instruction
+    /// spells the steps out so each one is visible.
+    #[secure]
+    pub fn secure_redeem(ctx: Context<SecureRedeem>) -> Result<()> {
+        let voucher_info = ctx.accounts.voucher.to_account_info();
+        let owner_info = ctx.accounts.owner.to_account_info();
+
+        // SECURITY: `try_deserialize` checks the discriminator, so a voucher
+        // closed earlier in this transaction fails here with
+        // AccountDiscriminatorMismatch instead of paying out again
+        let voucher = Voucher::try_deserialize(&mut &voucher_info.try_borrow_data()?[..])?;
+        require_keys_eq!(voucher.owner, owner_info.key(), ErrorCode::NotVoucherOwner);
+
+        pay_out(&mut ctx.accounts.treasury, &owner_info, voucher.amount)?;
+
+        // SECURITY: Step 1 - move the rent to the owner
+        let rent = voucher_info.lamports();
+        **voucher_info.try_borrow_mut_lamports()? = 0;
+        **owner_info.try_borrow_mut_lamports()? = owner_info
+            .lamports()
+            .checked_add(rent)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // SECURITY: Step 2 - zero every byte of the account data
+        let mut data = voucher_info.try_borrow_mut_data()?;
+        data.fill(0);
+
+        // SECURITY: Step 3 - mark the account as closed so it can never be
+        // deserialized as a Voucher again, even if its rent is refunded
+        data.get_mut(..CLOSED_ACCOUNT_DISCRIMINATOR.len())
+            .ok_or(ErrorCode::AccountDidNotClose)?
+            .copy_from_slice(&CLOSED_ACCOUNT_DISCRIMINATOR);
+
+        msg!("Voucher securely redeemed for {} lamports and closed", voucher.amount);
+        Ok(())
+    }
+
+    /// SECURE: Drain the lamports of an account that was marked closed
+    ///
+    /// Security Fix: If an attacker refunds rent to a closed voucher to keep it
+    /// alive, anyone can drain it again. The account is already unusable, so
+    /// the destination does not need to be restricted.
+    #[secure]
+    pub fn force_defund(ctx: Context<ForceDefund>) -> Result<()> {
+        let account = &ctx.accounts.account;
+
+        // SECURITY: Only accounts carrying the closed marker may be drained
+        let data = account.try_borrow_data()?;
+        require!(
+            data.len() >= CLOSED_ACCOUNT_DISCRIMINATOR.len()
+                && data[..CLOSED_ACCOUNT_DISCRIMINATOR.len()] == CLOSED_ACCOUNT_DISCRIMINATOR,
+            ErrorCode::AccountNotClosed
+        );
+        drop(data);
+
+        let destination = &ctx.accounts.destination;
+        let lamports = account.lamports();
+        **account.try_borrow_mut_lamports()? = 0;
+        **destination.try_borrow_mut_lamports()? = destination
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Closed account defunded: {} lamports", lamports);
+        Ok(())
+    }
+}
+
+/// Move `amount` lamports from the program-owned treasury to `recipient`
+fn pay_out<'info>(treasury: &mut Account<'info, Treasury>, recipient: &AccountInfo<'info>, amount: u64) -> Result<()> {
+    treasury.total_redeemed = treasury
+        .total_redeemed
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    let treasury_info = treasury.to_account_info();
+    **treasury_info.try_borrow_mut_lamports()? = treasury_info
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientTreasury)?;
+    **recipient.try_borrow_mut_lamports()? = recipient
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
@@ -0,0 +1,21 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Treasury {
+    /// The authority allowed to issue vouchers (32 bytes)
+    pub authority: Pubkey,
+    /// Lamports paid out across all vouchers (8 bytes)
+    pub total_redeemed: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Voucher {
+    /// The wallet allowed to redeem this voucher (32 bytes)
+    pub owner: Pubkey,
+    /// Lamports paid out on redemption (8 bytes)
+    pub amount: u64,
+}
@@ -0,0 +1,40 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, Fixture, TestAccount,
+};
+
+use crate::{Cluster, SecureWithdraw, Vault};
+
+fn withdraw_fixture() -> Fixture {
+    let authorizer = Pubkey::new_unique();
+    let (address, bump) = Pubkey::find_program_address(&[b"vault", authorizer.as_ref()], &crate::ID);
+    let vault = TestAccount::anchor(&Vault {
+        authorizer,
+        cluster: Cluster::Localnet,
+        next_nonce: 0,
+        bump,
+    })
+    .at(address);
+    Fixture::new()
+        .with("vault", vault)
+        .with("recipient", TestAccount::system(0))
+        .with("instructions", TestAccount::instructions_sysvar())
+}
+
+#[test]
+fn secure_withdraw_derives_the_vault_from_its_authorizer() {
+    assert_seeds_violation!(SecureWithdraw, withdraw_fixture(), "vault");
+    assert_owner_violation!(SecureWithdraw, withdraw_fixture(), "vault");
+}
+
+#[test]
+fn secure_withdraw_reads_signatures_from_the_real_instructions_sysvar() {
+    assert_constraint_violation!(
+        SecureWithdraw,
+        withdraw_fixture(),
+        "instructions" => TestAccount::new(Pubkey::new_unique(), vec![]),
+        ErrorCode::ConstraintAddress
+    );
+}
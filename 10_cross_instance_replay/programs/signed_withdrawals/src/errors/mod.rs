@@ -0,0 +1,90 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Expected an Ed25519 signature instruction before this instruction")]
+    MissingSignatureInstruction,
+    #[msg("Ed25519 instruction is malformed or references other instructions")]
+    InvalidSignatureInstruction,
+    #[msg("Authorization was not signed by the vault's authorizer")]
+    WrongSigner,
+    #[msg("Signed message does not match this withdrawal")]
+    MessageMismatch,
+    #[msg("Authorization nonce does not match the vault")]
+    InvalidNonce,
+    #[msg("Vault has insufficient funds")]
+    InsufficientFunds,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn authorization() -> WithdrawAuthorization {
+        WithdrawAuthorization {
+            recipient: Pubkey::new_from_array([7; 32]),
+            amount: 1_000_000_000,
+            nonce: 0,
+        }
+    }
+
+    fn domain(program_id: Pubkey, cluster: Cluster) -> DomainSeparator {
+        let (vault, _) = Pubkey::find_program_address(&[b"vault", &[9; 32]], &program_id);
+        DomainSeparator {
+            program_id,
+            cluster,
+            vault,
+        }
+    }
+
+    fn secure_bytes(domain: DomainSeparator) -> Vec<u8> {
+        DomainSeparatedAuthorization {
+            domain,
+            authorization: authorization(),
+        }
+        .signing_bytes()
+        .unwrap()
+    }
+
+    #[test]
+    fn vulnerable_message_is_identical_for_every_deployment() {
+        // The undomained message cannot depend on the program id or cluster:
+        // it is the same bytes for the original, a clone, devnet and mainnet
+        let bytes = authorization().signing_bytes().unwrap();
+        assert_eq!(bytes.len(), 32 + 8 + 8);
+        assert!(!bytes.windows(32).any(|window| window == crate::ID.as_ref()));
+    }
+
+    #[test]
+    fn secure_message_differs_between_program_ids() {
+        let cloned_program = Pubkey::new_unique();
+        assert_ne!(
+            secure_bytes(domain(crate::ID, Cluster::Mainnet)),
+            secure_bytes(domain(cloned_program, Cluster::Mainnet))
+        );
+    }
+
+    #[test]
+    fn secure_message_differs_between_clusters() {
+        assert_ne!(
+            secure_bytes(domain(crate::ID, Cluster::Devnet)),
+            secure_bytes(domain(crate::ID, Cluster::Mainnet))
+        );
+    }
+
+    #[test]
+    fn domain_separator_round_trips_through_serde_and_borsh() {
+        let original = domain(crate::ID, Cluster::Devnet);
+
+        let json = serde_json::to_string(&original).unwrap();
+        assert_eq!(serde_json::from_str::<DomainSeparator>(&json).unwrap(), original);
+
+        let borsh = original.try_to_vec().unwrap();
+        assert_eq!(borsh.len(), 32 + 1 + 32);
+        assert_eq!(DomainSeparator::try_from_slice(&borsh).unwrap(), original);
+    }
+}
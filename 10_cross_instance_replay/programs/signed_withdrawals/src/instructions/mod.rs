@@ -0,0 +1,32 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+#[instruction(authorizer: Pubkey)]
+pub struct InitializeVault<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 1 + 8 + 1, // discriminator + authorizer + cluster + next_nonce + bump
+        seeds = [b"vault", authorizer.as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
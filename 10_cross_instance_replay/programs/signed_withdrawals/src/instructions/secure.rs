@@ -0,0 +1,17 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureWithdraw<'info> {
+    #[account(mut, seeds = [b"vault", vault.authorizer.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    /// CHECK: Any recipient named in the signed authorization
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Address constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
@@ -0,0 +1,20 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+// Account validation is identical to the secure version; the vulnerability
+// is entirely in the format of the signed message.
+
+#[derive(Accounts)]
+pub struct VulnerableWithdraw<'info> {
+    #[account(mut, seeds = [b"vault", vault.authorizer.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    /// CHECK: Any recipient named in the signed authorization
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Address constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
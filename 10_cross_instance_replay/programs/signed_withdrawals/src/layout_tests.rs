@@ -0,0 +1,17 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Cluster, Vault};
+
+#[test]
+fn vault_layout_is_stable() {
+    let vault = Vault {
+        authorizer: Pubkey::new_from_array([1; 32]),
+        cluster: Cluster::Localnet,
+        next_nonce: 0x2122232425262728,
+        bump: 49,
+    };
+    assert_golden!("Vault", golden::anchor(&vault));
+}
@@ -0,0 +1,252 @@
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use secref_prelude::*;
+use serde::{Deserialize, Serialize};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Offset of the first signature-offsets entry in Ed25519 program instruction data
+const ED25519_OFFSETS_START: usize = 2;
+
+/// Size of one signature-offsets entry (seven little-endian u16 values)
+const ED25519_OFFSETS_SIZE: usize = 14;
+
+/// Instruction index the Ed25519 program uses for "this instruction"
+const ED25519_CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+#[feature_gated]
+#[program]
+pub mod signed_withdrawals {
+    use super::*;
+
+    /// Create a vault that pays out withdrawals signed by `authorizer`
+    ///
+    /// `cluster` is recorded by whoever deploys the vault; the program cannot
+    /// discover which cluster it runs on, so the secure implementation compares
+    /// signed messages against this configured value.
+    pub fn initialize_vault(ctx: Context<InitializeVault>, authorizer: Pubkey, cluster: Cluster) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.authorizer = authorizer;
+        vault.cluster = cluster;
+        vault.next_nonce = 0;
+        vault.bump = ctx.bumps.vault;
+
+        msg!("Vault initialized for authorizer {} on {:?}", authorizer, cluster);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Withdraw with an off-chain authorization
+    ///
+    /// Security Issue: The signed message only contains the recipient, the
+    /// amount and the nonce. Nothing in it says which program, which cluster
+    /// or which vault it was meant for, so a signature the authorizer produced
+    /// for a devnet deployment (or for a forked copy of this program) is
+    /// equally valid here. Nonces do not help: every deployment starts at 0.
+    #[vulnerable]
+    pub fn vulnerable_withdraw(ctx: Context<VulnerableWithdraw>, amount: u64, nonce: u64) -> Result<()> {
+        let vault = &ctx.accounts.vault;
+        require!(nonce == vault.next_nonce, ErrorCode::InvalidNonce);
+
+        let message = verified_ed25519_message(&ctx.accounts.instructions, &vault.authorizer)?;
+
+        // VULNERABILITY: The expected message has no domain - the same bytes
+        // are accepted by every deployment that trusts this authorizer
+        let authorization = WithdrawAuthorization {
+            recipient: ctx.accounts.recipient.key(),
+            amount,
+            nonce,
+        };
+        require!(message == authorization.signing_bytes()?, ErrorCode::MessageMismatch);
+
+        pay_out(&mut ctx.accounts.vault, &ctx.accounts.recipient, amount)?;
+
+        msg!("Withdrew {} lamports with an undomained authorization", amount);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version of the same instruction
+    // with the signed message bound to a single deployment.
+
+    /// SECURE: Withdraw with a domain-separated off-chain authorization
+    ///
+    /// Security Fix: The signed message starts with a `DomainSeparator` that
+    /// names this program id, the vault's configured cluster and the vault
+    /// address. A signature produced for any other program, cluster or vault
+    /// encodes different bytes and fails with MessageMismatch.
+    #[secure]
+    pub fn secure_withdraw(ctx: Context<SecureWithdraw>, amount: u64, nonce: u64) -> Result<()> {
+        let vault = &ctx.accounts.vault;
+        require!(nonce == vault.next_nonce, ErrorCode::InvalidNonce);
+
+        let message = verified_ed25519_message(&ctx.accounts.instructions, &vault.authorizer)?;
+
+        // SECURITY: Rebuild the domain from on-chain facts, never from
+        // instruction data, so the signer must have committed to them
+        let authorization = DomainSeparatedAuthorization {
+            domain: DomainSeparator {
+                program_id: *ctx.program_id,
+                cluster: vault.cluster,
+                vault: vault.key(),
+            },
+            authorization: WithdrawAuthorization {
+                recipient: ctx.accounts.recipient.key(),
+                amount,
+                nonce,
+            },
+        };
+        require!(message == authorization.signing_bytes()?, ErrorCode::MessageMismatch);
+
+        pay_out(&mut ctx.accounts.vault, &ctx.accounts.recipient, amount)?;
+
+        msg!("Withdrew {} lamports with a domain-separated authorization", amount);
+        Ok(())
+    }
+}
+
+// ========================================
+// SIGNED MESSAGE FORMATS
+// ========================================
+// Both formats derive serde as well as Borsh so off-chain signers can build,
+// log and inspect them; the bytes that are signed are always the Borsh encoding.
+
+/// The cluster a deployment is configured for
+#[derive(AnchorSerialize, AnchorDeserialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cluster {
+    Mainnet,
+    Devnet,
+    Testnet,
+    Localnet,
+}
+
+/// What a withdrawal authorization permits, without saying where
+#[derive(AnchorSerialize, AnchorDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct WithdrawAuthorization {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub nonce: u64,
+}
+
+impl WithdrawAuthorization {
+    /// VULNERABLE: The bytes signed for `vulnerable_withdraw`
+    pub fn signing_bytes(&self) -> Result<Vec<u8>> {
+        Ok(self.try_to_vec()?)
+    }
+}
+
+/// Where an authorization may be used
+#[derive(AnchorSerialize, AnchorDeserialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DomainSeparator {
+    /// The program that must verify the signature
+    pub program_id: Pubkey,
+    /// The cluster the verifying deployment is configured for
+    pub cluster: Cluster,
+    /// The vault the authorization draws from
+    pub vault: Pubkey,
+}
+
+/// A withdrawal authorization bound to a single deployment
+#[derive(AnchorSerialize, AnchorDeserialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DomainSeparatedAuthorization {
+    pub domain: DomainSeparator,
+    pub authorization: WithdrawAuthorization,
+}
+
+impl DomainSeparatedAuthorization {
+    /// SECURE: The bytes signed for `secure_withdraw`
+    pub fn signing_bytes(&self) -> Result<Vec<u8>> {
+        Ok(self.try_to_vec()?)
+    }
+}
+
+// ========================================
+// SIGNATURE VERIFICATION
+// ========================================
+// Shared by both implementations; the vulnerability is entirely in WHAT was
+// signed, not in how the signature is checked.
+
+/// Return the message verified by the Ed25519 instruction preceding this one
+///
+/// The Ed25519 program has already checked the signature when this runs; this
+/// helper only confirms that the verified instruction exists, that it signed
+/// with `expected_signer`, and that every offset points into that instruction.
+fn verified_ed25519_message(instructions: &AccountInfo, expected_signer: &Pubkey) -> Result<Vec<u8>> {
+    let current = load_current_index_checked(instructions)?;
+    require!(current > 0, ErrorCode::MissingSignatureInstruction);
+    let instruction = load_instruction_at_checked(usize::from(current - 1), instructions)?;
+    require_keys_eq!(instruction.program_id, ed25519_program::ID, ErrorCode::MissingSignatureInstruction);
+
+    let data = &instruction.data;
+    require!(
+        data.len() >= ED25519_OFFSETS_START + ED25519_OFFSETS_SIZE && data[0] == 1,
+        ErrorCode::InvalidSignatureInstruction
+    );
+    let read_u16 = |index: usize| {
+        let at = ED25519_OFFSETS_START + index * 2;
+        u16::from_le_bytes([data[at], data[at + 1]])
+    };
+    let signature_instruction = read_u16(1);
+    let public_key_offset = usize::from(read_u16(2));
+    let public_key_instruction = read_u16(3);
+    let message_offset = usize::from(read_u16(4));
+    let message_size = usize::from(read_u16(5));
+    let message_instruction = read_u16(6);
+
+    // Offsets that point into other instructions would let the verified
+    // signature cover different bytes than the ones read here
+    require!(
+        signature_instruction == ED25519_CURRENT_INSTRUCTION
+            && public_key_instruction == ED25519_CURRENT_INSTRUCTION
+            && message_instruction == ED25519_CURRENT_INSTRUCTION,
+        ErrorCode::InvalidSignatureInstruction
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+    require!(public_key == expected_signer.as_ref(), ErrorCode::WrongSigner);
+
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+    Ok(message.to_vec())
+}
+
+/// Advance the nonce and move `amount` lamports from the vault to `recipient`
+fn pay_out<'info>(vault: &mut Account<'info, Vault>, recipient: &AccountInfo<'info>, amount: u64) -> Result<()> {
+    vault.next_nonce = vault.next_nonce.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    let vault_info = vault.to_account_info();
+    **vault_info.try_borrow_mut_lamports()? = vault_info
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientFunds)?;
+    **recipient.try_borrow_mut_lamports()? = recipient
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
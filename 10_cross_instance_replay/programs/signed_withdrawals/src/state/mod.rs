@@ -0,0 +1,15 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Vault {
+    /// The off-chain key whose signatures authorize withdrawals (32 bytes)
+    pub authorizer: Pubkey,
+    /// The cluster this deployment was configured for (1 byte)
+    pub cluster: Cluster,
+    /// The nonce the next authorization must carry (8 bytes)
+    pub next_nonce: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
@@ -0,0 +1,319 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SignedWithdrawals } from "../target/types/signed_withdrawals";
+import { expect } from "chai";
+import { CuProfiler } from "../../harness/cu-profiler";
+import { PocRecorder } from "../../harness/poc-recorder";
+import {
+  Connection,
+  Ed25519Program,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  Transaction,
+} from "@solana/web3.js";
+
+// Borsh variant indices of the on-chain `Cluster` enum
+const CLUSTER = { mainnet: 0, devnet: 1, testnet: 2, localnet: 3 };
+
+/** Borsh encoding of `WithdrawAuthorization` (the vulnerable message) */
+function encodeAuthorization(recipient: PublicKey, amount: anchor.BN, nonce: anchor.BN): Buffer {
+  return Buffer.concat([recipient.toBuffer(), amount.toArrayLike(Buffer, "le", 8), nonce.toArrayLike(Buffer, "le", 8)]);
+}
+
+/** Borsh encoding of `DomainSeparatedAuthorization` (the secure message) */
+function encodeDomainSeparated(
+  programId: PublicKey,
+  cluster: number,
+  vault: PublicKey,
+  recipient: PublicKey,
+  amount: anchor.BN,
+  nonce: anchor.BN
+): Buffer {
+  return Buffer.concat([
+    programId.toBuffer(),
+    Buffer.from([cluster]),
+    vault.toBuffer(),
+    encodeAuthorization(recipient, amount, nonce),
+  ]);
+}
+
+describe("Cross-Instance Signature Replay Exploit", () => {
+  // Mock connection for testing without local validator
+  const connection = new Connection("https://api.devnet.solana.com", "confirmed");
+
+  // Create a mock wallet if ANCHOR_WALLET is not set
+  let wallet: anchor.Wallet;
+  try {
+    wallet = anchor.Wallet.local();
+  } catch (error) {
+    // Create a mock wallet for CI environments
+    const mockKeypair = Keypair.generate();
+    wallet = new anchor.Wallet(mockKeypair);
+  }
+
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: "confirmed",
+  });
+
+  anchor.setProvider(provider);
+
+  // Records compute units for every instruction executed against a validator
+  const profiler = new CuProfiler("10_cross_instance_replay");
+
+  // Records every transaction as a replayable PoC when SECREF_POC_RECORD=1
+  const recorder = new PocRecorder("10_cross_instance_replay", provider);
+  beforeEach(function () {
+    // Steps are labelled with the test that sent them
+    recorder.test(this.currentTest?.fullTitle());
+  });
+
+  // Mock program for testing
+  let program: Program<SignedWithdrawals>;
+
+  // Test accounts
+  let authorizer: Keypair;
+  let recipient: Keypair;
+
+  // A forked deployment of the same code under a different program id
+  const clonedProgramId = Keypair.generate().publicKey;
+  const mockProgramId = new PublicKey("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+  const amount = new anchor.BN(LAMPORTS_PER_SOL / 10);
+  const nonce = new anchor.BN(0);
+
+  before(async () => {
+    try {
+      // Try to load the program
+      program = anchor.workspace.SignedWithdrawals as Program<SignedWithdrawals>;
+    } catch (error) {
+      console.log("⚠️  Program not available in test environment, using mock tests");
+      program = null;
+    }
+  });
+
+  after(() => {
+    recorder.writeFixture();
+    // Fails the suite if any recorded instruction regressed past its CU budget
+    profiler.writeSummary();
+    profiler.assertBudgets();
+  });
+
+  beforeEach(async () => {
+    authorizer = Keypair.generate();
+    recipient = Keypair.generate();
+  });
+
+  function vaultPda(programId: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from("vault"), authorizer.publicKey.toBuffer()], programId)[0];
+  }
+
+  /** Creates and funds a vault that this deployment believes lives on `cluster` */
+  async function createVault(cluster: keyof typeof CLUSTER): Promise<PublicKey> {
+    const vault = vaultPda(program.programId);
+    await program.methods
+      .initializeVault(authorizer.publicKey, { [cluster]: {} } as any)
+      .accounts({ vault, payer: wallet.publicKey })
+      .rpc();
+    await provider.sendAndConfirm(
+      new Transaction().add(SystemProgram.transfer({ fromPubkey: wallet.publicKey, toPubkey: vault, lamports: LAMPORTS_PER_SOL }))
+    );
+    return vault;
+  }
+
+  /** Sends [Ed25519 verify(message), withdraw] in one transaction */
+  async function withdrawWith(method: "vulnerableWithdraw" | "secureWithdraw", vault: PublicKey, message: Buffer) {
+    const verify = Ed25519Program.createInstructionWithPrivateKey({ privateKey: authorizer.secretKey, message });
+    const withdraw = await program.methods[method](amount, nonce)
+      .accounts({ vault, recipient: recipient.publicKey, instructions: SYSVAR_INSTRUCTIONS_PUBKEY })
+      .instruction();
+    return provider.sendAndConfirm(new Transaction().add(verify, withdraw));
+  }
+
+  describe("🚨 EXPLOIT DEMONSTRATION - Vulnerable Implementation", () => {
+    it("Should accept a devnet authorization on a mainnet-configured vault", async () => {
+      console.log("\n=== CROSS-CLUSTER REPLAY EXPLOIT ===");
+
+      // What the authorizer signed for a devnet test withdrawal
+      const devnetMessage = encodeAuthorization(recipient.publicKey, amount, nonce);
+      // What the mainnet deployment expects
+      const mainnetMessage = encodeAuthorization(recipient.publicKey, amount, nonce);
+
+      if (!program) {
+        console.log("📝 MOCK TEST: Demonstrating cross-cluster replay");
+        console.log("✅ In a real exploit:");
+        console.log("   1. The authorizer signs a test withdrawal on devnet");
+        console.log("   2. The signature and message are public in the devnet transaction");
+        console.log("   3. The attacker submits the same Ed25519 instruction on mainnet");
+        console.log("   4. Both vaults start at nonce 0, so the nonce check passes too");
+
+        expect(devnetMessage.equals(mainnetMessage)).to.be.true;
+
+        console.log("🚨 VULNERABILITY DEMONSTRATED: Identical bytes on every cluster");
+        return;
+      }
+
+      try {
+        const vault = await createVault("mainnet");
+        const signature = await withdrawWith("vulnerableWithdraw", vault, devnetMessage);
+        await profiler.record("vulnerable_withdraw", provider.connection, signature, program.programId.toBase58());
+
+        const balance = await provider.connection.getBalance(recipient.publicKey);
+        expect(balance).to.equal(amount.toNumber());
+        console.log("✅ EXPLOIT SUCCESS: Devnet authorization paid out on the mainnet vault");
+      } catch (error) {
+        console.log(`⚠️  Test requires local validator: ${error.message}`);
+      }
+    });
+
+    it("Should accept an authorization issued for a cloned program", async () => {
+      console.log("\n=== CLONED PROGRAM REPLAY EXPLOIT ===");
+
+      const forOriginal = encodeAuthorization(recipient.publicKey, amount, nonce);
+      const forClone = encodeAuthorization(recipient.publicKey, amount, nonce);
+      console.log(`Original program: ${(program ? program.programId : mockProgramId).toBase58()}`);
+      console.log(`Cloned program:   ${clonedProgramId.toBase58()}`);
+
+      expect(forOriginal.equals(forClone)).to.be.true;
+      console.log("🚨 VULNERABILITY DEMONSTRATED: The message never names the program it is for");
+    });
+  });
+
+  describe("🛡️  PROTECTION VERIFICATION - Secure Implementation", () => {
+    it("Should produce different messages for each program id and cluster", async () => {
+      console.log("\n=== DOMAIN SEPARATION PROTECTION ===");
+
+      const programId = program ? program.programId : mockProgramId;
+      const domains: Array<[string, PublicKey, number]> = [
+        ["original/mainnet", programId, CLUSTER.mainnet],
+        ["original/devnet", programId, CLUSTER.devnet],
+        ["clone/mainnet", clonedProgramId, CLUSTER.mainnet],
+        ["clone/devnet", clonedProgramId, CLUSTER.devnet],
+      ];
+
+      const messages = domains.map(([name, id, cluster]) => {
+        const vault = vaultPda(id);
+        return [name, encodeDomainSeparated(id, cluster, vault, recipient.publicKey, amount, nonce).toString("hex")];
+      });
+      for (const [name, hex] of messages) {
+        console.log(`   ${name}: ${hex.slice(0, 16)}...`);
+      }
+
+      expect(new Set(messages.map(([, hex]) => hex)).size).to.equal(domains.length);
+      console.log("✅ PROTECTION SUCCESS: Every deployment signs distinct bytes");
+    });
+
+    it("Should reject a devnet authorization on a mainnet-configured vault", async () => {
+      console.log("\n=== CROSS-CLUSTER REPLAY PROTECTION ===");
+
+      if (!program) {
+        console.log("📝 MOCK TEST: Demonstrating rejected replay");
+        console.log("✅ Secure implementation features:");
+        console.log("   1. DomainSeparator { program_id, cluster, vault } prefixes every message");
+        console.log("   2. The domain is rebuilt from on-chain facts, not instruction data");
+        console.log("   3. A message for another domain fails with MessageMismatch");
+        console.log("Expected error: MessageMismatch");
+        return;
+      }
+
+      try {
+        const vault = await createVault("mainnet");
+        const devnetMessage = encodeDomainSeparated(
+          program.programId,
+          CLUSTER.devnet,
+          vault,
+          recipient.publicKey,
+          amount,
+          nonce
+        );
+        await withdrawWith("secureWithdraw", vault, devnetMessage);
+        expect.fail("Expected MessageMismatch");
+      } catch (error) {
+        expect(error.message).to.include("MessageMismatch");
+        console.log("✅ PROTECTION SUCCESS: Devnet authorization rejected on mainnet");
+      }
+    });
+
+    it("Should reject an authorization issued for a cloned program", async () => {
+      console.log("\n=== CLONED PROGRAM REPLAY PROTECTION ===");
+
+      if (!program) {
+        console.log("📝 MOCK TEST: Demonstrating rejected clone replay");
+        console.log("Expected error: MessageMismatch");
+        return;
+      }
+
+      try {
+        const vault = await createVault("localnet");
+        const cloneMessage = encodeDomainSeparated(
+          clonedProgramId,
+          CLUSTER.localnet,
+          vaultPda(clonedProgramId),
+          recipient.publicKey,
+          amount,
+          nonce
+        );
+        await withdrawWith("secureWithdraw", vault, cloneMessage);
+        expect.fail("Expected MessageMismatch");
+      } catch (error) {
+        expect(error.message).to.include("MessageMismatch");
+        console.log("✅ PROTECTION SUCCESS: Clone authorization rejected by the original program");
+      }
+    });
+
+    it("Should show legitimate usage works correctly", async () => {
+      console.log("\n=== LEGITIMATE USAGE DEMONSTRATION ===");
+
+      if (!program) {
+        console.log("📝 MOCK TEST: Demonstrating legitimate operations");
+        console.log("✅ Authorizer signs for this program, cluster and vault; withdrawal succeeds");
+        return;
+      }
+
+      try {
+        const vault = await createVault("localnet");
+        const message = encodeDomainSeparated(
+          program.programId,
+          CLUSTER.localnet,
+          vault,
+          recipient.publicKey,
+          amount,
+          nonce
+        );
+        const signature = await withdrawWith("secureWithdraw", vault, message);
+        await profiler.record("secure_withdraw", provider.connection, signature, program.programId.toBase58());
+
+        expect(await provider.connection.getBalance(recipient.publicKey)).to.equal(amount.toNumber());
+        console.log("✅ Domain-separated authorization accepted");
+      } catch (error) {
+        console.log(`⚠️  Test requires local validator: ${error.message}`);
+      }
+    });
+  });
+
+  describe("📚 EDUCATIONAL SUMMARY", () => {
+    it("Should summarize the vulnerability and protection", async () => {
+      console.log("\n=== VULNERABILITY SUMMARY ===");
+      console.log("🚨 VULNERABILITY: Cross-Instance Signature Replay");
+      console.log("   - Signed messages describe WHAT, not WHERE");
+      console.log("   - Devnet, mainnet and forked deployments accept the same bytes");
+      console.log("   - Per-deployment nonces all start at zero");
+
+      console.log("\n🛡️  PROTECTION: Domain Separation");
+      console.log("   - Prefix messages with program id, cluster and vault");
+      console.log("   - Rebuild the domain on-chain from trusted values");
+      console.log("   - Never reuse signing keys across environments");
+
+      console.log("\n📖 KEY LEARNING POINTS:");
+      console.log("   1. A signature is valid everywhere its bytes are accepted");
+      console.log("   2. Public testnets publish your signatures");
+      console.log("   3. Forks inherit your trust assumptions");
+      console.log("   4. Bind every signature to exactly one verifier");
+
+      // This test always passes as it's educational
+      expect(true).to.be.true;
+    });
+  });
+});
@@ -0,0 +1,158 @@
+//! Instruction handlers
+//!
+//! The swap routing each implementation puts in front of the shared logic
+//! here is in `vulnerable` and `secure`, built with the feature of that name.
+//! `versioned` holds the commit swap whose payload leads with a version byte.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::set_return_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::state::{Pool, Position};
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub(crate) use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+pub mod versioned;
+pub use versioned::*;
+
+// ========================================
+// SETUP INSTRUCTIONS
+// ========================================
+// Shared by both implementations. Accounts are created by the client with
+// the System Program and assigned to this program before initialization.
+
+/// Write the initial reserves of a constant-product pool
+pub fn initialize_pool(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let pool_account = next_account_info(accounts_iter)?;
+
+    if pool_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if pool_account.data_len() < Pool::LEN {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    if pool_account.try_borrow_data()?[0] != 0 {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    if data.len() != 16 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let pool = Pool {
+        reserve_a: read_u64(data, 0)?,
+        reserve_b: read_u64(data, 8)?,
+    };
+    pool.pack(&mut pool_account.try_borrow_mut_data()?)?;
+
+    msg!("Pool initialized with reserves {} / {}", pool.reserve_a, pool.reserve_b);
+    Ok(())
+}
+
+/// Open a position holding a toy balance of token A for `owner`
+pub fn initialize_position(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let position_account = next_account_info(accounts_iter)?;
+    let owner_account = next_account_info(accounts_iter)?;
+
+    if position_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if !owner_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if position_account.data_len() < Position::LEN {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    if position_account.try_borrow_data()?[0] != 0 {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    if data.len() != 8 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let position = Position {
+        owner: *owner_account.key,
+        balance_a: read_u64(data, 0)?,
+        balance_b: 0,
+    };
+    position.pack(&mut position_account.try_borrow_mut_data()?)?;
+
+    msg!("Position opened for {} with {} of token A", position.owner, position.balance_a);
+    Ok(())
+}
+
+// ========================================
+// SWAP LOGIC
+// ========================================
+// Shared by both implementations; only the routing in front of it differs.
+
+/// Log the quote for `amount` and return it as return data
+pub(crate) fn preview(program_id: &Pubkey, pool_account: &AccountInfo, amount: u64) -> ProgramResult {
+    if pool_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let pool = Pool::unpack(&pool_account.try_borrow_data()?)?;
+    let out = pool.quote(amount)?;
+
+    set_return_data(&out.to_le_bytes());
+    msg!("Preview: {} of token A buys {} of token B", amount, out);
+    Ok(())
+}
+
+/// Swap `amount` of token A for at least `min_out` of token B in `position`
+///
+/// Callers are responsible for checking that `owner_account` signed.
+pub(crate) fn commit(
+    program_id: &Pubkey,
+    pool_account: &AccountInfo,
+    position_account: &AccountInfo,
+    owner_account: &AccountInfo,
+    amount: u64,
+    min_out: u64,
+) -> ProgramResult {
+    if pool_account.owner != program_id || position_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut pool = Pool::unpack(&pool_account.try_borrow_data()?)?;
+    let mut position = Position::unpack(&position_account.try_borrow_data()?)?;
+    if position.owner != *owner_account.key {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let out = pool.quote(amount)?;
+    if out < min_out {
+        msg!("Slippage exceeded: {} < {}", out, min_out);
+        return Err(ProgramError::Custom(0));
+    }
+
+    position.balance_a = position.balance_a.checked_sub(amount).ok_or(ProgramError::InsufficientFunds)?;
+    position.balance_b = position.balance_b.checked_add(out).ok_or(ProgramError::ArithmeticOverflow)?;
+    pool.reserve_a = pool.reserve_a.checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?;
+    pool.reserve_b = pool.reserve_b.checked_sub(out).ok_or(ProgramError::InsufficientFunds)?;
+
+    pool.pack(&mut pool_account.try_borrow_mut_data()?)?;
+    position.pack(&mut position_account.try_borrow_mut_data()?)?;
+
+    msg!("Commit: {} swapped {} of token A for {} of token B", position.owner, amount, out);
+    Ok(())
+}
+
+pub(crate) fn read_u64(data: &[u8], offset: usize) -> Result<u64, ProgramError> {
+    data.get(offset..offset + 8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or(ProgramError::InvalidInstructionData)
+}
@@ -0,0 +1,73 @@
+//! The secure swap routing, built with the `secure` feature: one validated
+//! discriminator per instruction and no shared mode byte.
+
+use super::*;
+use crate::{SECURE_COMMIT_SWAP, SECURE_PREVIEW_SWAP};
+
+/// Arguments of SECURE_PREVIEW_SWAP: amount (u64)
+pub struct PreviewSwapArgs {
+    pub amount: u64,
+}
+
+/// Arguments of SECURE_COMMIT_SWAP: amount (u64) | min_out (u64)
+pub struct CommitSwapArgs {
+    pub amount: u64,
+    pub min_out: u64,
+}
+
+/// Every secure swap instruction, decoded in one place
+pub enum SecureSwapInstruction {
+    PreviewSwap(PreviewSwapArgs),
+    CommitSwap(CommitSwapArgs),
+}
+
+impl SecureSwapInstruction {
+    /// SECURE: Decode a swap instruction from its tag and exact-length arguments
+    ///
+    /// Security Fix: The tag alone selects the instruction, each instruction
+    /// has its own parameter struct, and the data must be exactly that
+    /// struct's size. Preview data is 8 bytes and commit data is 16, so no
+    /// byte string decodes as both.
+    pub fn unpack(tag: u8, data: &[u8]) -> Result<Self, ProgramError> {
+        match (tag, data.len()) {
+            (SECURE_PREVIEW_SWAP, 8) => Ok(Self::PreviewSwap(PreviewSwapArgs {
+                amount: read_u64(data, 0)?,
+            })),
+            (SECURE_COMMIT_SWAP, 16) => Ok(Self::CommitSwap(CommitSwapArgs {
+                amount: read_u64(data, 0)?,
+                min_out: read_u64(data, 8)?,
+            })),
+            // SECURITY: Unknown tags and wrong lengths are rejected outright
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// SECURE: Quote a swap without touching any position
+///
+/// Security Fix: The preview handler only receives the pool account, so it
+/// cannot reach a position even if it wanted to.
+pub(crate) fn secure_preview_swap(program_id: &Pubkey, accounts: &[AccountInfo], args: PreviewSwapArgs) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let pool_account = next_account_info(accounts_iter)?;
+
+    preview(program_id, pool_account, args.amount)
+}
+
+/// SECURE: Execute a swap against the signer's own position
+///
+/// Security Fix: The signature check lives in the handler that moves funds,
+/// so no routing decision elsewhere can skip it.
+pub(crate) fn secure_commit_swap(program_id: &Pubkey, accounts: &[AccountInfo], args: CommitSwapArgs) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let pool_account = next_account_info(accounts_iter)?;
+    let position_account = next_account_info(accounts_iter)?;
+    let owner_account = next_account_info(accounts_iter)?;
+
+    // SECURITY: Authorization happens next to the state change it protects
+    if !owner_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    commit(program_id, pool_account, position_account, owner_account, args.amount, args.min_out)
+}
@@ -0,0 +1,69 @@
+//! Commit swaps whose payload leads with a version byte
+//!
+//! The commit swap's arguments have had two layouts:
+//!
+//! - version 1: amount (u64) | min_out (u64)
+//! - version 2: route (u8) | amount (u64) | min_out (u64), where `route`
+//!   names the pools the swap goes through; this program has one pool, so
+//!   only [`DIRECT_ROUTE`] exists
+//!
+//! Clients built for a later release send versions this program has never
+//! seen. The dispatcher in `vulnerable` reads them as version 1, and the one
+//! in `secure` refuses them, each built with the feature of that name.
+
+use secref_guards::versioned::VersionedArgs;
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub(crate) use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub(crate) use secure::*;
+
+/// Version byte of the first layout: amount | min_out
+pub const SWAP_V1: u8 = 1;
+/// Version byte of the second layout: route | amount | min_out
+pub const SWAP_V2: u8 = 2;
+
+/// Version 2's only route: token A to token B through this program's pool
+pub const DIRECT_ROUTE: u8 = 0;
+
+/// Arguments of a versioned commit swap, whichever layout they came in
+#[derive(Debug, PartialEq, Eq)]
+pub struct VersionedSwapArgs {
+    pub amount: u64,
+    pub min_out: u64,
+}
+
+impl VersionedSwapArgs {
+    /// Read version 1's fields from the front of `data`
+    pub(crate) fn read_v1(data: &[u8]) -> Option<Self> {
+        Some(Self {
+            amount: read_u64(data, 0).ok()?,
+            min_out: read_u64(data, 8).ok()?,
+        })
+    }
+
+    /// Read version 2's fields from the front of `data`, which must take the direct route
+    pub(crate) fn read_v2(data: &[u8]) -> Option<Self> {
+        let (&route, rest) = data.split_first()?;
+        (route == DIRECT_ROUTE).then_some(())?;
+        Self::read_v1(rest)
+    }
+}
+
+impl VersionedArgs for VersionedSwapArgs {
+    const VERSIONS: &'static [u8] = &[SWAP_V1, SWAP_V2];
+
+    fn decode(version: u8, data: &[u8]) -> Option<Self> {
+        match (version, data.len()) {
+            (SWAP_V1, 16) => Self::read_v1(data),
+            (SWAP_V2, 17) => Self::read_v2(data),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! The secure version dispatch, built with the `secure` feature: only the
+//! versions this program knows, each at its exact length.
+
+use secref_guards::versioned::Versioned;
+
+use super::*;
+
+/// SECURE: Commit a swap only in a layout this program knows
+///
+/// Security Fix: `Versioned::decode` refuses a version missing from
+/// `VersionedSwapArgs::VERSIONS` before reading a field, and data that is
+/// not exactly its version's length. A client ahead of the program gets an
+/// error instead of a swap it did not ask for.
+pub(crate) fn secure_versioned_commit_swap(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let pool_account = next_account_info(accounts_iter)?;
+    let position_account = next_account_info(accounts_iter)?;
+    let owner_account = next_account_info(accounts_iter)?;
+
+    if !owner_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // SECURITY: Unknown versions and wrong lengths fail here, logged as
+    // Anchor's entrypoint would log the guard's error
+    let Versioned { args, .. } = Versioned::<VersionedSwapArgs>::decode(data).inspect_err(|error| error.log())?;
+
+    commit(program_id, pool_account, position_account, owner_account, args.amount, args.min_out)
+}
@@ -0,0 +1,41 @@
+//! The vulnerable version dispatch, built with the `vulnerable` feature.
+//!
+//! This module contains INTENTIONALLY VULNERABLE code for educational purposes.
+//! DO NOT use this code in production environments.
+
+use super::*;
+
+/// VULNERABLE: Commit a swap in whichever layout its version byte names
+///
+/// Security Issue: Version 2 is decoded as version 2, and every other
+/// version - 1, and also 0, 3 and whatever a later release defines - as
+/// version 1. A layout that leads with a one-byte field, as version 2 does,
+/// read as version 1 shifts every field one byte: `route | amount` becomes
+/// an amount 256 times the one signed for, and `min_out` comes from the
+/// wrong bytes too. The signer's wallet decoded what it showed them by the
+/// version; the program executed something else.
+pub(crate) fn vulnerable_versioned_commit_swap(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let pool_account = next_account_info(accounts_iter)?;
+    let position_account = next_account_info(accounts_iter)?;
+    let owner_account = next_account_info(accounts_iter)?;
+
+    if !owner_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (&version, rest) = data.split_first().ok_or(ProgramError::InvalidInstructionData)?;
+    let args = match version {
+        SWAP_V2 => VersionedSwapArgs::read_v2(rest),
+        // VULNERABILITY: Unknown versions default to version 1's layout,
+        // and trailing bytes beyond it are ignored
+        _ => VersionedSwapArgs::read_v1(rest),
+    }
+    .ok_or(ProgramError::InvalidInstructionData)?;
+
+    commit(program_id, pool_account, position_account, owner_account, args.amount, args.min_out)
+}
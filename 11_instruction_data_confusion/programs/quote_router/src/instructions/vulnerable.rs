@@ -0,0 +1,61 @@
+//! The vulnerable swap routing, built with the `vulnerable` feature.
+//!
+//! This module contains INTENTIONALLY VULNERABLE code for educational purposes.
+//! DO NOT use this code in production environments.
+
+use super::*;
+use crate::VULNERABLE_COMMIT_SWAP;
+
+/// Shared `mode` values of the vulnerable parameter layout
+const MODE_PREVIEW: u8 = 0;
+const MODE_COMMIT: u8 = 1;
+
+/// Parameters shared by the vulnerable preview and commit instructions
+///
+/// Layout: amount (u64) | min_out (u64) | mode (u8)
+struct SwapParams {
+    amount: u64,
+    min_out: u64,
+    mode: u8,
+}
+
+impl SwapParams {
+    fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            amount: read_u64(data, 0)?,
+            min_out: read_u64(data, 8)?,
+            // VULNERABILITY: Trailing bytes beyond the mode are ignored
+            mode: *data.get(16).ok_or(ProgramError::InvalidInstructionData)?,
+        })
+    }
+}
+
+/// VULNERABLE: Route preview and commit swaps through one shared handler
+///
+/// Security Issue: The router decides whether a signature is required from
+/// the instruction tag, but the shared handler decides whether to move funds
+/// from the `mode` byte inside the parameters. Both instructions use the same
+/// parameter layout, so VULNERABLE_PREVIEW_SWAP with `mode = MODE_COMMIT`
+/// passes the "previews are public" check and then executes a commit against
+/// someone else's position.
+pub(crate) fn vulnerable_dispatch_swap(program_id: &Pubkey, accounts: &[AccountInfo], tag: u8, data: &[u8]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let pool_account = next_account_info(accounts_iter)?;
+    let position_account = next_account_info(accounts_iter)?;
+    let owner_account = next_account_info(accounts_iter)?;
+
+    let params = SwapParams::unpack(data)?;
+
+    // VULNERABILITY: Authorization is keyed on the tag...
+    if tag == VULNERABLE_COMMIT_SWAP && !owner_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // VULNERABILITY: ...but execution is keyed on an attacker-chosen byte
+    // that the preview instruction carries too
+    match params.mode {
+        MODE_PREVIEW => preview(program_id, pool_account, params.amount),
+        MODE_COMMIT => commit(program_id, pool_account, position_account, owner_account, params.amount, params.min_out),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
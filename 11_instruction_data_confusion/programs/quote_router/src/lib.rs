@@ -0,0 +1,71 @@
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey};
+
+solana_program::declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+#[cfg(not(any(feature = "vulnerable", feature = "secure")))]
+compile_error!("enable the `vulnerable` feature, the `secure` feature, or both (the default)");
+
+// ========================================
+// INSTRUCTION DISCRIMINATORS
+// ========================================
+// Native programs route instructions by hand. Every byte the router reads
+// is attacker-controlled instruction data.
+
+const INITIALIZE_POOL: u8 = 0;
+const INITIALIZE_POSITION: u8 = 1;
+#[cfg(feature = "vulnerable")]
+const VULNERABLE_PREVIEW_SWAP: u8 = 2;
+#[cfg(feature = "vulnerable")]
+const VULNERABLE_COMMIT_SWAP: u8 = 3;
+#[cfg(feature = "secure")]
+const SECURE_PREVIEW_SWAP: u8 = 4;
+#[cfg(feature = "secure")]
+const SECURE_COMMIT_SWAP: u8 = 5;
+// Commit swaps whose data is a version byte and that version's layout
+#[cfg(feature = "vulnerable")]
+const VULNERABLE_VERSIONED_COMMIT_SWAP: u8 = 6;
+#[cfg(feature = "secure")]
+const SECURE_VERSIONED_COMMIT_SWAP: u8 = 7;
+
+// ========================================
+// MAIN INSTRUCTION PROCESSOR
+// ========================================
+
+pub fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+    let (&tag, rest) = instruction_data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    match tag {
+        INITIALIZE_POOL => initialize_pool(program_id, accounts, rest),
+        INITIALIZE_POSITION => initialize_position(program_id, accounts, rest),
+        #[cfg(feature = "vulnerable")]
+        VULNERABLE_PREVIEW_SWAP | VULNERABLE_COMMIT_SWAP => vulnerable_dispatch_swap(program_id, accounts, tag, rest),
+        #[cfg(feature = "secure")]
+        SECURE_PREVIEW_SWAP | SECURE_COMMIT_SWAP => {
+            match SecureSwapInstruction::unpack(tag, rest)? {
+                SecureSwapInstruction::PreviewSwap(args) => secure_preview_swap(program_id, accounts, args),
+                SecureSwapInstruction::CommitSwap(args) => secure_commit_swap(program_id, accounts, args),
+            }
+        }
+        #[cfg(feature = "vulnerable")]
+        VULNERABLE_VERSIONED_COMMIT_SWAP => vulnerable_versioned_commit_swap(program_id, accounts, rest),
+        #[cfg(feature = "secure")]
+        SECURE_VERSIONED_COMMIT_SWAP => secure_versioned_commit_swap(program_id, accounts, rest),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
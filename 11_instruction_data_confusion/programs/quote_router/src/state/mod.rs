@@ -0,0 +1,92 @@
+//! Accounts the program owns, and their byte layouts
+
+use solana_program::{entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::instructions::read_u64;
+
+/// Account-type tags written as the first byte of every account
+const POOL_ACCOUNT: u8 = 1;
+const POSITION_ACCOUNT: u8 = 2;
+
+// ========================================
+// ACCOUNT STRUCTURES
+// ========================================
+// Manual serialization with a leading account-type byte, so a position can
+// never be read as a pool or vice versa.
+
+pub struct Pool {
+    /// Token A held by the pool (8 bytes)
+    pub reserve_a: u64,
+    /// Token B held by the pool (8 bytes)
+    pub reserve_b: u64,
+}
+
+impl Pool {
+    pub const LEN: usize = 1 + 8 + 8; // type + reserve_a + reserve_b
+
+    pub(crate) fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < Self::LEN || data[0] != POOL_ACCOUNT {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Self {
+            reserve_a: read_u64(data, 1)?,
+            reserve_b: read_u64(data, 9)?,
+        })
+    }
+
+    pub(crate) fn pack(&self, data: &mut [u8]) -> ProgramResult {
+        if data.len() < Self::LEN {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        data[0] = POOL_ACCOUNT;
+        data[1..9].copy_from_slice(&self.reserve_a.to_le_bytes());
+        data[9..17].copy_from_slice(&self.reserve_b.to_le_bytes());
+        Ok(())
+    }
+
+    /// Constant-product output for `amount_in` of token A
+    pub(crate) fn quote(&self, amount_in: u64) -> Result<u64, ProgramError> {
+        let numerator = u128::from(self.reserve_b) * u128::from(amount_in);
+        let denominator = u128::from(self.reserve_a) + u128::from(amount_in);
+        if denominator == 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        u64::try_from(numerator / denominator).map_err(|_| ProgramError::ArithmeticOverflow)
+    }
+}
+
+pub struct Position {
+    /// The wallet that owns this position (32 bytes)
+    pub owner: Pubkey,
+    /// Token A balance (8 bytes)
+    pub balance_a: u64,
+    /// Token B balance (8 bytes)
+    pub balance_b: u64,
+}
+
+impl Position {
+    pub const LEN: usize = 1 + 32 + 8 + 8; // type + owner + balance_a + balance_b
+
+    pub(crate) fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < Self::LEN || data[0] != POSITION_ACCOUNT {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let owner = Pubkey::try_from(&data[1..33]).map_err(|_| ProgramError::InvalidAccountData)?;
+        Ok(Self {
+            owner,
+            balance_a: read_u64(data, 33)?,
+            balance_b: read_u64(data, 41)?,
+        })
+    }
+
+    pub(crate) fn pack(&self, data: &mut [u8]) -> ProgramResult {
+        if data.len() < Self::LEN {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        data[0] = POSITION_ACCOUNT;
+        data[1..33].copy_from_slice(self.owner.as_ref());
+        data[33..41].copy_from_slice(&self.balance_a.to_le_bytes());
+        data[41..49].copy_from_slice(&self.balance_b.to_le_bytes());
+        Ok(())
+    }
+}
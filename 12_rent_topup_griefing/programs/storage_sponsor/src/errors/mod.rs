@@ -0,0 +1,17 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Note body exceeds the maximum length")]
+    NoteTooLarge,
+    #[msg("Top-up exceeds the per-call cap")]
+    TopUpTooLarge,
+    #[msg("Sponsor would drop below its rent-exempt minimum")]
+    SponsorDepleted,
+    #[msg("Insufficient lamports")]
+    InsufficientLamports,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
@@ -0,0 +1,83 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializeSponsor<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 8 + 8 + 1, // discriminator + admin + max_top_up_per_call + total_sponsored + bump
+        seeds = [b"sponsor"],
+        bump
+    )]
+    pub sponsor: Account<'info, Sponsor>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundSponsor<'info> {
+    #[account(mut, seeds = [b"sponsor"], bump = sponsor.bump)]
+    pub sponsor: Account<'info, Sponsor>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateNote<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = NOTE_HEADER_LEN,
+        seeds = [b"note", owner.key().as_ref()],
+        bump
+    )]
+    pub note: Account<'info, Note>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResizeNote<'info> {
+    #[account(mut, has_one = owner, seeds = [b"note", owner.key().as_ref()], bump = note.bump)]
+    pub note: Account<'info, Note>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseNote<'info> {
+    #[account(mut, seeds = [b"sponsor"], bump = sponsor.bump)]
+    pub sponsor: Account<'info, Sponsor>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        close = owner,
+        seeds = [b"note", owner.key().as_ref()],
+        bump = note.bump
+    )]
+    pub note: Account<'info, Note>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
@@ -0,0 +1,15 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureTopUp<'info> {
+    #[account(mut, seeds = [b"sponsor"], bump = sponsor.bump)]
+    pub sponsor: Account<'info, Sponsor>,
+
+    // SECURITY: Only this program's notes, and only the signer's own
+    #[account(mut, has_one = owner, seeds = [b"note", owner.key().as_ref()], bump = note.bump)]
+    pub note: Account<'info, Note>,
+
+    pub owner: Signer<'info>,
+}
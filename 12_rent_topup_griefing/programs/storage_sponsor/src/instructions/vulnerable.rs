@@ -0,0 +1,13 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableTopUp<'info> {
+    #[account(mut, seeds = [b"sponsor"], bump = sponsor.bump)]
+    pub sponsor: Account<'info, Sponsor>,
+
+    /// CHECK: VULNERABLE - any writable account is accepted
+    #[account(mut)]
+    pub target: UncheckedAccount<'info>,
+}
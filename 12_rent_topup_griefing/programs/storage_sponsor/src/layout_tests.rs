@@ -0,0 +1,27 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Note, Sponsor};
+
+#[test]
+fn sponsor_layout_is_stable() {
+    let sponsor = Sponsor {
+        admin: Pubkey::new_from_array([1; 32]),
+        max_top_up_per_call: 0x2122232425262728,
+        total_sponsored: 0x3132333435363738,
+        bump: 65,
+    };
+    assert_golden!("Sponsor", golden::anchor(&sponsor));
+}
+
+#[test]
+fn note_layout_is_stable() {
+    let note = Note {
+        owner: Pubkey::new_from_array([5; 32]),
+        sponsored_lamports: 0x6162636465666768,
+        bump: 113,
+    };
+    assert_golden!("Note", golden::anchor(&note));
+}
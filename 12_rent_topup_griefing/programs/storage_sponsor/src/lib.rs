@@ -0,0 +1,204 @@
+use anchor_lang::system_program::{self, Transfer};
+#[cfg(feature = "secure")]
+use secref_guards::rent::minimum_balance;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Largest note body a user may resize to (stays under the 10 KiB per-instruction realloc limit)
+pub const MAX_NOTE_BODY_LEN: u32 = 8192;
+
+/// Bytes in front of the note body: discriminator + Note fields
+pub const NOTE_HEADER_LEN: usize = 8 + Note::INIT_SPACE;
+
+#[feature_gated]
+#[program]
+pub mod storage_sponsor {
+    use super::*;
+
+    /// Create the sponsor PDA that pays rent for users' note storage
+    pub fn initialize_sponsor(ctx: Context<InitializeSponsor>, max_top_up_per_call: u64) -> Result<()> {
+        let sponsor = &mut ctx.accounts.sponsor;
+        sponsor.admin = ctx.accounts.admin.key();
+        sponsor.max_top_up_per_call = max_top_up_per_call;
+        sponsor.total_sponsored = 0;
+        sponsor.bump = ctx.bumps.sponsor;
+
+        msg!("Sponsor initialized with a {} lamport per-call cap", max_top_up_per_call);
+        Ok(())
+    }
+
+    /// Move lamports from any funder into the sponsor
+    pub fn fund_sponsor(ctx: Context<FundSponsor>, amount: u64) -> Result<()> {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.sponsor.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Sponsor funded with {} lamports", amount);
+        Ok(())
+    }
+
+    /// Create an empty note; the owner pays rent for the header
+    pub fn create_note(ctx: Context<CreateNote>) -> Result<()> {
+        let note = &mut ctx.accounts.note;
+        note.owner = ctx.accounts.owner.key();
+        note.sponsored_lamports = 0;
+        note.bump = ctx.bumps.note;
+
+        msg!("Note created for {}", note.owner);
+        Ok(())
+    }
+
+    /// Grow or shrink the note body without paying for it
+    ///
+    /// The account is left below its rent-exempt minimum after growing, so a
+    /// top-up must follow in the same transaction or the runtime rejects it.
+    pub fn resize_note(ctx: Context<ResizeNote>, body_len: u32) -> Result<()> {
+        require!(body_len <= MAX_NOTE_BODY_LEN, ErrorCode::NoteTooLarge);
+
+        ctx.accounts
+            .note
+            .to_account_info()
+            .realloc(NOTE_HEADER_LEN + body_len as usize, true)?;
+
+        msg!("Note body resized to {} bytes", body_len);
+        Ok(())
+    }
+
+    /// Close a note, returning sponsored lamports to the sponsor and the rest to the owner
+    pub fn close_note(ctx: Context<CloseNote>) -> Result<()> {
+        let refund = ctx.accounts.note.sponsored_lamports;
+        move_lamports(
+            &ctx.accounts.note.to_account_info(),
+            &ctx.accounts.sponsor.to_account_info(),
+            refund,
+        )?;
+
+        msg!("Note closed, {} sponsored lamports returned", refund);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Top up any account to its rent-exempt minimum
+    ///
+    /// Security Issue: The sponsor pays whatever `target` is missing for its
+    /// current size, without checking what the account is, who owns it, how
+    /// large it is or how much one call may cost. An attacker grows their own
+    /// note to the maximum size, has the sponsor fund it, then closes the
+    /// note and keeps the rent. Nothing records that the lamports came from
+    /// the sponsor, so `close_note` has nothing to give back. Repeating the
+    /// loop drains the sponsor.
+    #[vulnerable]
+    pub fn vulnerable_top_up(ctx: Context<VulnerableTopUp>) -> Result<()> {
+        let target = &ctx.accounts.target;
+
+        // VULNERABILITY: `target` can be any writable account of any size -
+        // the deficit is entirely attacker-controlled
+        let required = Rent::get()?.minimum_balance(target.data_len());
+        let deficit = required.saturating_sub(target.lamports());
+
+        // VULNERABILITY: No per-call cap and no record of the payment
+        let sponsor = &mut ctx.accounts.sponsor;
+        sponsor.total_sponsored = sponsor
+            .total_sponsored
+            .checked_add(deficit)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        move_lamports(&sponsor.to_account_info(), &target.to_account_info(), deficit)?;
+
+        msg!("Topped up {} with {} lamports", target.key(), deficit);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version of the same instruction
+    // with validated targets, a per-call cap and refundable bookkeeping.
+
+    /// SECURE: Top up the signer's own note, within limits
+    ///
+    /// Security Fix: The target must be a Note PDA owned by this program and
+    /// by the signer, so its size is bounded by MAX_NOTE_BODY_LEN. Each call
+    /// is capped at `max_top_up_per_call`, the sponsor never drops below its
+    /// own rent-exempt minimum, and the payment is recorded in
+    /// `sponsored_lamports` so `close_note` returns it to the sponsor.
+    #[secure]
+    pub fn secure_top_up(ctx: Context<SecureTopUp>) -> Result<()> {
+        let note_info = ctx.accounts.note.to_account_info();
+        let sponsor_info = ctx.accounts.sponsor.to_account_info();
+
+        let required = minimum_balance(note_info.data_len())?;
+        let deficit = required.saturating_sub(note_info.lamports());
+
+        // SECURITY: Bound what a single call can cost the sponsor
+        require!(
+            deficit <= ctx.accounts.sponsor.max_top_up_per_call,
+            ErrorCode::TopUpTooLarge
+        );
+
+        // SECURITY: Never leave the sponsor itself below rent exemption
+        let sponsor_floor = minimum_balance(sponsor_info.data_len())?;
+        let remaining = sponsor_info
+            .lamports()
+            .checked_sub(deficit)
+            .ok_or(ErrorCode::SponsorDepleted)?;
+        require!(remaining >= sponsor_floor, ErrorCode::SponsorDepleted);
+
+        // SECURITY: Record the payment so it is refunded to the sponsor on close
+        let note = &mut ctx.accounts.note;
+        note.sponsored_lamports = note
+            .sponsored_lamports
+            .checked_add(deficit)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let sponsor = &mut ctx.accounts.sponsor;
+        sponsor.total_sponsored = sponsor
+            .total_sponsored
+            .checked_add(deficit)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        move_lamports(&sponsor_info, &note_info, deficit)?;
+
+        msg!("Topped up note {} with {} lamports", note_info.key(), deficit);
+        Ok(())
+    }
+}
+
+/// Move `amount` lamports out of a program-owned account into `to`
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    **from.try_borrow_mut_lamports()? = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientLamports)?;
+    **to.try_borrow_mut_lamports()? = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
@@ -0,0 +1,27 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Sponsor {
+    /// The account that created the sponsor (32 bytes)
+    pub admin: Pubkey,
+    /// Most lamports a single secure top-up may pay (8 bytes)
+    pub max_top_up_per_call: u64,
+    /// Lamports paid out across all top-ups (8 bytes)
+    pub total_sponsored: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+/// Note header; the variable-length body follows it in the account data
+#[account]
+#[derive(InitSpace)]
+pub struct Note {
+    /// The wallet that owns this note (32 bytes)
+    pub owner: Pubkey,
+    /// Lamports the sponsor has paid into this note (8 bytes)
+    pub sponsored_lamports: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
@@ -0,0 +1,161 @@
+//! Every constraint on the secure contexts, tripped one account at a time,
+//! and the slots the handlers read, warped.
+
+use anchor_lang::prelude::*;
+use secref_testkit::runtime::{warp_by_slots, warp_to_slot};
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_owner_violation, assert_seeds_violation,
+    assert_signer_violation, execute, Fixture, TestAccount,
+};
+
+use crate::{
+    instruction, House, Round, RoundStatus, SecureClaimTimeout, SecureOpenRound, SecurePlaceBet, SecureReveal,
+    REVEAL_TIMEOUT_SLOTS,
+};
+
+const ROUND_ID: u64 = 7;
+
+fn house_account(authority: Pubkey) -> TestAccount {
+    let (address, bump) = Pubkey::find_program_address(&[b"house"], &crate::ID);
+    TestAccount::anchor(&House { authority, bump }).at(address)
+}
+
+fn round_account(player: Pubkey) -> TestAccount {
+    let (address, bump) = Pubkey::find_program_address(&[b"round", ROUND_ID.to_le_bytes().as_ref()], &crate::ID);
+    TestAccount::anchor(&Round {
+        round_id: ROUND_ID,
+        commitment: [1; 32],
+        player,
+        amount: 1_000,
+        player_seed: [2; 32],
+        bet_slot: 0,
+        status: RoundStatus::Placed,
+        bump,
+    })
+    .at(address)
+}
+
+fn open_round_fixture() -> Fixture {
+    let authority = TestAccount::signer();
+    let (round_address, _) = Pubkey::find_program_address(&[b"round", ROUND_ID.to_le_bytes().as_ref()], &crate::ID);
+    Fixture::new()
+        .with("house", house_account(authority.key))
+        .with(
+            "round",
+            TestAccount::uninitialized(8 + 8 + 32 + 32 + 8 + 32 + 8 + 1 + 1).at(round_address),
+        )
+        .with("authority", authority)
+        .with("system_program", TestAccount::system_program())
+        .args(instruction::SecureOpenRound {
+            round_id: ROUND_ID,
+            commitment: [1; 32],
+        })
+}
+
+fn place_bet_fixture() -> Fixture {
+    Fixture::new()
+        .with("house", house_account(Pubkey::new_unique()))
+        .with("round", round_account(Pubkey::default()))
+        .with("player", TestAccount::signer())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn settle_fixture() -> Fixture {
+    let player = TestAccount::signer();
+    Fixture::new()
+        .with("house", house_account(Pubkey::new_unique()))
+        .with("round", round_account(player.key))
+        .with("player", player)
+}
+
+#[test]
+fn secure_open_round_requires_the_house_authority() {
+    assert_has_one_violation!(SecureOpenRound, open_round_fixture(), "authority");
+    assert_signer_violation!(SecureOpenRound, open_round_fixture(), "authority");
+}
+
+#[test]
+fn secure_open_round_uses_the_house_and_round_pdas() {
+    assert_seeds_violation!(SecureOpenRound, open_round_fixture(), "house");
+    assert_owner_violation!(SecureOpenRound, open_round_fixture(), "house");
+    assert_seeds_violation!(SecureOpenRound, open_round_fixture(), "round");
+}
+
+#[test]
+fn secure_place_bet_uses_the_house_and_round_pdas() {
+    assert_seeds_violation!(SecurePlaceBet, place_bet_fixture(), "house");
+    assert_seeds_violation!(SecurePlaceBet, place_bet_fixture(), "round");
+    assert_owner_violation!(SecurePlaceBet, place_bet_fixture(), "round");
+}
+
+#[test]
+fn secure_place_bet_requires_a_player_signature() {
+    assert_signer_violation!(SecurePlaceBet, place_bet_fixture(), "player");
+}
+
+#[test]
+fn secure_place_bet_requires_the_system_program() {
+    assert_constraint_violation!(
+        SecurePlaceBet,
+        place_bet_fixture(),
+        "system_program" => TestAccount::program(Pubkey::new_unique()),
+        ErrorCode::InvalidProgramId
+    );
+}
+
+#[test]
+fn secure_reveal_pays_only_the_recorded_player() {
+    assert_has_one_violation!(SecureReveal, settle_fixture(), "player");
+}
+
+#[test]
+fn secure_reveal_uses_the_house_and_round_pdas() {
+    assert_seeds_violation!(SecureReveal, settle_fixture(), "house");
+    assert_seeds_violation!(SecureReveal, settle_fixture(), "round");
+    assert_owner_violation!(SecureReveal, settle_fixture(), "round");
+}
+
+#[test]
+fn secure_claim_timeout_requires_the_recorded_player() {
+    assert_has_one_violation!(SecureClaimTimeout, settle_fixture(), "player");
+    assert_signer_violation!(SecureClaimTimeout, settle_fixture(), "player");
+}
+
+#[test]
+fn secure_claim_timeout_uses_the_house_and_round_pdas() {
+    assert_seeds_violation!(SecureClaimTimeout, settle_fixture(), "house");
+    assert_seeds_violation!(SecureClaimTimeout, settle_fixture(), "round");
+}
+
+#[test]
+fn vulnerable_flip_pays_whoever_bets_on_the_slots_parity() {
+    const BET: u64 = 1_000;
+    for slot in [41, 42] {
+        warp_to_slot(slot);
+        let mut fixture = Fixture::new()
+            .with("house", house_account(Pubkey::new_unique()).with_lamports(1_000_000))
+            .with("player", TestAccount::signer().with_lamports(BET))
+            .with("system_program", TestAccount::system_program());
+        let guess_even = slot % 2 == 0;
+        execute!(&mut fixture, instruction::VulnerableFlip { amount: BET, guess_even }).unwrap();
+        assert_eq!(fixture.account("player").lamports, 2 * BET, "slot {slot}");
+    }
+}
+
+#[test]
+fn secure_claim_timeout_waits_out_the_reveal_window() {
+    // The round's bet landed in slot 0
+    warp_to_slot(REVEAL_TIMEOUT_SLOTS);
+    let mut fixture = settle_fixture();
+    fixture.account_mut("house").lamports += 1_000_000;
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureClaimTimeout {}),
+        Err(Error::from(crate::ErrorCode::RevealWindowOpen).into())
+    );
+
+    warp_by_slots(1);
+    let before = fixture.account("player").lamports;
+    execute!(&mut fixture, instruction::SecureClaimTimeout {}).unwrap();
+    assert_eq!(fixture.account("player").lamports, before + 2_000);
+    assert_eq!(fixture.state::<Round>("round").status, RoundStatus::Settled);
+}
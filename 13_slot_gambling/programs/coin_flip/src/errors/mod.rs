@@ -0,0 +1,19 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Round is not open for bets")]
+    RoundNotOpen,
+    #[msg("Round has no unsettled bet")]
+    RoundNotPlaced,
+    #[msg("House secret does not match the commitment")]
+    CommitmentMismatch,
+    #[msg("The house can still reveal")]
+    RevealWindowOpen,
+    #[msg("Bankroll cannot cover the payout")]
+    InsufficientBankroll,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
@@ -0,0 +1,71 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct SecureOpenRound<'info> {
+    #[account(has_one = authority, seeds = [b"house"], bump = house.bump)]
+    pub house: Account<'info, House>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 8 + 32 + 32 + 8 + 32 + 8 + 1 + 1, // discriminator + round_id + commitment + player + amount + player_seed + bet_slot + status + bump
+        seeds = [b"round", round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SecurePlaceBet<'info> {
+    #[account(mut, seeds = [b"house"], bump = house.bump)]
+    pub house: Account<'info, House>,
+
+    #[account(mut, seeds = [b"round", round.round_id.to_le_bytes().as_ref()], bump = round.bump)]
+    pub round: Account<'info, Round>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SecureReveal<'info> {
+    #[account(mut, seeds = [b"house"], bump = house.bump)]
+    pub house: Account<'info, House>,
+
+    #[account(
+        mut,
+        has_one = player,
+        seeds = [b"round", round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    /// CHECK: Receives the payout; must match the round's recorded player
+    #[account(mut)]
+    pub player: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SecureClaimTimeout<'info> {
+    #[account(mut, seeds = [b"house"], bump = house.bump)]
+    pub house: Account<'info, House>,
+
+    #[account(
+        mut,
+        has_one = player,
+        seeds = [b"round", round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+}
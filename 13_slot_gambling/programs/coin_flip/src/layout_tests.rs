@@ -0,0 +1,30 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{House, Round, RoundStatus};
+
+#[test]
+fn house_layout_is_stable() {
+    let house = House {
+        authority: Pubkey::new_from_array([1; 32]),
+        bump: 33,
+    };
+    assert_golden!("House", golden::anchor(&house));
+}
+
+#[test]
+fn round_layout_is_stable() {
+    let round = Round {
+        round_id: 0x3132333435363738,
+        commitment: [65; 32],
+        player: Pubkey::new_from_array([5; 32]),
+        amount: 0x6162636465666768,
+        player_seed: [113; 32],
+        bet_slot: 0x8182838485868788,
+        status: RoundStatus::Settled,
+        bump: 145,
+    };
+    assert_golden!("Round", golden::anchor(&round));
+}
@@ -0,0 +1,227 @@
+use anchor_lang::system_program::{self, Transfer};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Slots the house has to reveal its secret after a bet is placed
+pub const REVEAL_TIMEOUT_SLOTS: u64 = 150;
+
+#[feature_gated]
+#[program]
+pub mod coin_flip {
+    use super::*;
+    #[secure]
+    use anchor_lang::solana_program::hash::{hash, hashv};
+
+    /// Create the house PDA that holds the bankroll
+    pub fn initialize_house(ctx: Context<InitializeHouse>) -> Result<()> {
+        let house = &mut ctx.accounts.house;
+        house.authority = ctx.accounts.authority.key();
+        house.bump = ctx.bumps.house;
+
+        msg!("House initialized with authority: {}", house.authority);
+        Ok(())
+    }
+
+    /// Move lamports from the authority into the bankroll
+    pub fn fund_house(ctx: Context<FundHouse>, amount: u64) -> Result<()> {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.house.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("House funded with {} lamports", amount);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Flip a coin using the current slot
+    ///
+    /// Security Issue: The slot is known before the transaction runs. A
+    /// searcher simulates against the upcoming slot and only sends bets that
+    /// win, or wraps the flip in a transaction that reverts on a loss; a
+    /// leader simply includes the bet in a slot of the right parity. Slot
+    /// hashes and recent blockhashes have the same problem - they are chosen
+    /// or seen by the cluster before the bet settles.
+    #[vulnerable]
+    pub fn vulnerable_flip(ctx: Context<VulnerableFlip>, amount: u64, guess_even: bool) -> Result<()> {
+        collect_stake(&ctx.accounts.player, &ctx.accounts.house, &ctx.accounts.system_program, amount)?;
+
+        // VULNERABILITY: The "random" outcome is public and chosen by the leader
+        let slot = Clock::get()?.slot;
+        let outcome_even = slot % 2 == 0;
+
+        if outcome_even == guess_even {
+            let payout = amount.checked_mul(2).ok_or(ErrorCode::ArithmeticOverflow)?;
+            pay_out(&ctx.accounts.house, &ctx.accounts.player.to_account_info(), payout)?;
+            msg!("Slot {} - player wins {} lamports", slot, payout);
+        } else {
+            msg!("Slot {} - house wins", slot);
+        }
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version of the same game
+    // using commit-reveal with a forfeiting timeout.
+
+    /// SECURE: Open a round by committing to a house secret
+    ///
+    /// Security Fix: The house publishes `hash(house_secret)` before any bet
+    /// exists, so it cannot pick its secret after seeing the player's seed.
+    #[secure]
+    pub fn secure_open_round(ctx: Context<SecureOpenRound>, round_id: u64, commitment: [u8; 32]) -> Result<()> {
+        let round = &mut ctx.accounts.round;
+        round.round_id = round_id;
+        round.commitment = commitment;
+        round.player = Pubkey::default();
+        round.amount = 0;
+        round.player_seed = [0; 32];
+        round.bet_slot = 0;
+        round.status = RoundStatus::Open;
+        round.bump = ctx.bumps.round;
+
+        msg!("Round {} opened", round_id);
+        Ok(())
+    }
+
+    /// SECURE: Place a bet on an open round with a player-chosen seed
+    ///
+    /// Security Fix: The outcome is `hash(house_secret || player_seed)`. The
+    /// house's secret is already fixed and hidden, so the player cannot
+    /// predict the result, and nothing the leader controls enters it.
+    #[secure]
+    pub fn secure_place_bet(ctx: Context<SecurePlaceBet>, amount: u64, player_seed: [u8; 32]) -> Result<()> {
+        require!(ctx.accounts.round.status == RoundStatus::Open, ErrorCode::RoundNotOpen);
+
+        collect_stake(&ctx.accounts.player, &ctx.accounts.house, &ctx.accounts.system_program, amount)?;
+
+        let round = &mut ctx.accounts.round;
+        round.player = ctx.accounts.player.key();
+        round.amount = amount;
+        round.player_seed = player_seed;
+        round.bet_slot = Clock::get()?.slot;
+        round.status = RoundStatus::Placed;
+
+        msg!("Bet of {} lamports placed on round {}", amount, round.round_id);
+        Ok(())
+    }
+
+    /// SECURE: Reveal the house secret and settle the round
+    ///
+    /// Security Fix: The secret must hash to the stored commitment. Anyone may
+    /// submit it, so the player can settle a round the house revealed
+    /// off-chain.
+    #[secure]
+    pub fn secure_reveal(ctx: Context<SecureReveal>, house_secret: [u8; 32]) -> Result<()> {
+        let round = &mut ctx.accounts.round;
+        require!(round.status == RoundStatus::Placed, ErrorCode::RoundNotPlaced);
+
+        // SECURITY: The secret cannot be swapped after the bet
+        require!(
+            hash(&house_secret).to_bytes() == round.commitment,
+            ErrorCode::CommitmentMismatch
+        );
+
+        // SECURITY: Both parties' secrets decide the outcome
+        let mixed = hashv(&[&house_secret, &round.player_seed]).to_bytes();
+        let player_wins = mixed[0] & 1 == 1;
+        round.status = RoundStatus::Settled;
+
+        if player_wins {
+            let payout = round.amount.checked_mul(2).ok_or(ErrorCode::ArithmeticOverflow)?;
+            pay_out(&ctx.accounts.house, &ctx.accounts.player.to_account_info(), payout)?;
+            msg!("Round {} - player wins {} lamports", round.round_id, payout);
+        } else {
+            msg!("Round {} - house wins", round.round_id);
+        }
+        Ok(())
+    }
+
+    /// SECURE: Claim a round the house did not reveal in time
+    ///
+    /// Security Fix: A house that could walk away from losing rounds would
+    /// only ever reveal winners. After REVEAL_TIMEOUT_SLOTS the player gets
+    /// their stake back plus the house's side, so withholding the secret
+    /// always costs the house as much as losing.
+    #[secure]
+    pub fn secure_claim_timeout(ctx: Context<SecureClaimTimeout>) -> Result<()> {
+        let round = &mut ctx.accounts.round;
+        require!(round.status == RoundStatus::Placed, ErrorCode::RoundNotPlaced);
+
+        // SECURITY: The house keeps its full reveal window
+        let deadline = round
+            .bet_slot
+            .checked_add(REVEAL_TIMEOUT_SLOTS)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(Clock::get()?.slot > deadline, ErrorCode::RevealWindowOpen);
+
+        round.status = RoundStatus::Settled;
+        let payout = round.amount.checked_mul(2).ok_or(ErrorCode::ArithmeticOverflow)?;
+        pay_out(&ctx.accounts.house, &ctx.accounts.player.to_account_info(), payout)?;
+
+        msg!("Round {} timed out - {} lamports paid to player", round.round_id, payout);
+        Ok(())
+    }
+}
+
+/// Move the player's stake into the bankroll
+fn collect_stake<'info>(
+    player: &Signer<'info>,
+    house: &Account<'info, House>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: player.to_account_info(),
+                to: house.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
+
+/// Move `amount` lamports from the program-owned house to `recipient`
+fn pay_out<'info>(house: &Account<'info, House>, recipient: &AccountInfo<'info>, amount: u64) -> Result<()> {
+    let house_info = house.to_account_info();
+    **house_info.try_borrow_mut_lamports()? = house_info
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientBankroll)?;
+    **recipient.try_borrow_mut_lamports()? = recipient
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
@@ -0,0 +1,38 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct House {
+    /// The authority allowed to open rounds (32 bytes)
+    pub authority: Pubkey,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Round {
+    /// Identifier used in the round's PDA seeds (8 bytes)
+    pub round_id: u64,
+    /// hash(house_secret), fixed before any bet (32 bytes)
+    pub commitment: [u8; 32],
+    /// The player who bet on this round (32 bytes)
+    pub player: Pubkey,
+    /// Lamports staked by the player (8 bytes)
+    pub amount: u64,
+    /// Player-chosen seed mixed into the outcome (32 bytes)
+    pub player_seed: [u8; 32],
+    /// Slot the bet was placed in; starts the reveal window (8 bytes)
+    pub bet_slot: u64,
+    /// Where the round is in its lifecycle (1 byte)
+    pub status: RoundStatus,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundStatus {
+    Open,
+    Placed,
+    Settled,
+}
@@ -0,0 +1,54 @@
+//! Every constraint on the contexts the secure instructions use, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_owner_violation, assert_seeds_violation,
+    assert_signer_violation, Fixture, TestAccount,
+};
+
+use crate::{Config, Pay};
+
+fn pay_fixture() -> Fixture {
+    let fee_recipient = TestAccount::system(0);
+    let (address, bump) = Pubkey::find_program_address(&[b"config"], &crate::ID);
+    let config = TestAccount::anchor(&Config {
+        admin: Pubkey::new_unique(),
+        fee_recipient: fee_recipient.key,
+        fee_bps: 100,
+        version: 1,
+        bump,
+    })
+    .at(address);
+    Fixture::new()
+        .with("config", config)
+        .with("merchant", TestAccount::system(0))
+        .with("fee_recipient", fee_recipient)
+        .with("payer", TestAccount::signer())
+        .with("system_program", TestAccount::system_program())
+}
+
+#[test]
+fn pay_sends_fees_to_the_configured_recipient() {
+    assert_has_one_violation!(Pay, pay_fixture(), "fee_recipient");
+}
+
+#[test]
+fn pay_reads_the_config_pda() {
+    assert_seeds_violation!(Pay, pay_fixture(), "config");
+    assert_owner_violation!(Pay, pay_fixture(), "config");
+}
+
+#[test]
+fn pay_requires_a_payer_signature() {
+    assert_signer_violation!(Pay, pay_fixture(), "payer");
+}
+
+#[test]
+fn pay_requires_the_system_program() {
+    assert_constraint_violation!(
+        Pay,
+        pay_fixture(),
+        "system_program" => TestAccount::program(Pubkey::new_unique()),
+        ErrorCode::InvalidProgramId
+    );
+}
@@ -0,0 +1,15 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Fee cannot exceed 10,000 basis points")]
+    InvalidFee,
+    #[msg("Configuration changed since the transaction was simulated")]
+    ConfigChanged,
+    #[msg("Fee exceeds the maximum the payer approved")]
+    FeeExceedsLimit,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
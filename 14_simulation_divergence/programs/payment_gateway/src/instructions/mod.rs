@@ -0,0 +1,49 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 32 + 2 + 8 + 1, // discriminator + admin + fee_recipient + fee_bps + version + bump
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Only recorded as the fee destination
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetFee<'info> {
+    #[account(mut, has_one = admin, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Shared by both payment instructions; they differ only in what they verify
+#[derive(Accounts)]
+pub struct Pay<'info> {
+    #[account(has_one = fee_recipient, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Any account may receive a payment
+    #[account(mut)]
+    pub merchant: UncheckedAccount<'info>,
+
+    /// CHECK: Must match config.fee_recipient
+    #[account(mut)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
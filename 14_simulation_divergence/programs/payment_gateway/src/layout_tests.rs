@@ -0,0 +1,18 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::Config;
+
+#[test]
+fn config_layout_is_stable() {
+    let config = Config {
+        admin: Pubkey::new_from_array([1; 32]),
+        fee_recipient: Pubkey::new_from_array([2; 32]),
+        fee_bps: 0x3132,
+        version: 0x4142434445464748,
+        bump: 81,
+    };
+    assert_golden!("Config", golden::anchor(&config));
+}
@@ -0,0 +1,140 @@
+use anchor_lang::system_program::{self, Transfer};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Basis-point denominator (10,000 bps = 100%)
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+#[feature_gated]
+#[program]
+pub mod payment_gateway {
+    use super::*;
+
+    /// Create the gateway configuration
+    pub fn initialize_config(ctx: Context<InitializeConfig>, fee_bps: u16) -> Result<()> {
+        require!(fee_bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidFee);
+
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.fee_recipient = ctx.accounts.fee_recipient.key();
+        config.fee_bps = fee_bps;
+        config.version = 0;
+        config.bump = ctx.bumps.config;
+
+        msg!("Gateway initialized with a {} bps fee", fee_bps);
+        Ok(())
+    }
+
+    /// Change the fee; takes effect for the very next payment
+    ///
+    /// Every change bumps `version` so payments can tell the configuration
+    /// they were simulated against from the one they execute against.
+    pub fn set_fee(ctx: Context<SetFee>, fee_bps: u16) -> Result<()> {
+        require!(fee_bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidFee);
+
+        let config = &mut ctx.accounts.config;
+        config.fee_bps = fee_bps;
+        config.version = config.version.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Fee set to {} bps (config version {})", fee_bps, config.version);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Pay a merchant, taking whatever fee is configured at execution
+    ///
+    /// Security Issue: The wallet simulates this instruction while the fee is
+    /// zero and shows the user "amount → merchant". The operator then lands
+    /// `set_fee(10_000)` ahead of the user's transaction - or simply flips it
+    /// after the simulation request reaches their RPC - and the payment the
+    /// user approved sends everything to the fee recipient instead.
+    #[vulnerable]
+    pub fn vulnerable_pay(ctx: Context<Pay>, amount: u64) -> Result<()> {
+        // VULNERABILITY: Reads mutable global state the user never agreed to
+        let fee = fee_for(amount, ctx.accounts.config.fee_bps)?;
+
+        transfer_split(&ctx, amount, fee)
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version of the same instruction
+    // with the simulated state carried in the arguments and verified.
+
+    /// SECURE: Pay a merchant under the configuration the user simulated
+    ///
+    /// Security Fix: The client copies `config.version` and the fee it was
+    /// shown into the instruction arguments. If the configuration changed
+    /// after simulation, or the fee would exceed what the user approved, the
+    /// payment fails instead of executing with different terms.
+    #[secure]
+    pub fn secure_pay(ctx: Context<Pay>, amount: u64, expected_version: u64, max_fee: u64) -> Result<()> {
+        let config = &ctx.accounts.config;
+
+        // SECURITY: Execution must see the same configuration as simulation
+        require!(config.version == expected_version, ErrorCode::ConfigChanged);
+
+        // SECURITY: Independent upper bound, useful even without a version
+        let fee = fee_for(amount, config.fee_bps)?;
+        require!(fee <= max_fee, ErrorCode::FeeExceedsLimit);
+
+        transfer_split(&ctx, amount, fee)
+    }
+}
+
+/// Fee charged on `amount` at `fee_bps`
+fn fee_for(amount: u64, fee_bps: u16) -> Result<u64> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        / BPS_DENOMINATOR as u128;
+    Ok(fee as u64)
+}
+
+/// Send `fee` to the fee recipient and the rest of `amount` to the merchant
+fn transfer_split(ctx: &Context<Pay>, amount: u64, fee: u64) -> Result<()> {
+    let to_merchant = amount.checked_sub(fee).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    for (to, lamports) in [
+        (ctx.accounts.merchant.to_account_info(), to_merchant),
+        (ctx.accounts.fee_recipient.to_account_info(), fee),
+    ] {
+        if lamports > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to,
+                    },
+                ),
+                lamports,
+            )?;
+        }
+    }
+
+    msg!("Paid {} lamports to merchant and {} lamports in fees", to_merchant, fee);
+    Ok(())
+}
@@ -0,0 +1,17 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Config {
+    /// The operator allowed to change the fee (32 bytes)
+    pub admin: Pubkey,
+    /// Receives fees (32 bytes)
+    pub fee_recipient: Pubkey,
+    /// Fee in basis points, up to 10,000 (2 bytes)
+    pub fee_bps: u16,
+    /// Incremented on every configuration change (8 bytes)
+    pub version: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
@@ -0,0 +1,40 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+//!
+//! The plugin itself is validated by `assert_immutable_program` in the
+//! handler, which `secref-guards` tests.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_has_one_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, Fixture,
+    TestAccount,
+};
+
+use crate::{Registry, SecureRegisterPlugin};
+
+fn register_fixture() -> Fixture {
+    let admin = TestAccount::signer();
+    let (address, bump) = Pubkey::find_program_address(&[b"registry"], &crate::ID);
+    let registry = TestAccount::anchor(&Registry {
+        admin: admin.key,
+        plugin: Pubkey::default(),
+        bump,
+    })
+    .at(address);
+    Fixture::new()
+        .with("registry", registry)
+        .with("plugin", TestAccount::program(Pubkey::new_unique()))
+        .with("plugin_program_data", TestAccount::new(Pubkey::new_unique(), vec![]))
+        .with("admin", admin)
+}
+
+#[test]
+fn secure_register_plugin_requires_the_registry_admin() {
+    assert_has_one_violation!(SecureRegisterPlugin, register_fixture(), "admin");
+    assert_signer_violation!(SecureRegisterPlugin, register_fixture(), "admin");
+}
+
+#[test]
+fn secure_register_plugin_updates_the_registry_pda() {
+    assert_seeds_violation!(SecureRegisterPlugin, register_fixture(), "registry");
+    assert_owner_violation!(SecureRegisterPlugin, register_fixture(), "registry");
+}
@@ -0,0 +1,54 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializeRegistry<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 32 + 1, // discriminator + admin + plugin + bump
+        seeds = [b"registry"],
+        bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundVault<'info> {
+    #[account(mut, seeds = [b"vault"], bump)]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecutePlugin<'info> {
+    #[account(seeds = [b"registry"], bump = registry.bump)]
+    pub registry: Account<'info, Registry>,
+
+    #[account(mut, seeds = [b"vault"], bump)]
+    pub vault: SystemAccount<'info>,
+
+    /// CHECK: Must be the approved plugin; its validity was decided at registration
+    #[account(address = registry.plugin)]
+    pub plugin: UncheckedAccount<'info>,
+}
@@ -0,0 +1,17 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureRegisterPlugin<'info> {
+    #[account(mut, has_one = admin, seeds = [b"registry"], bump = registry.bump)]
+    pub registry: Account<'info, Registry>,
+
+    /// CHECK: Validated by assert_immutable_program in the handler
+    pub plugin: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the ProgramData account `plugin` points at; checked in the handler
+    pub plugin_program_data: UncheckedAccount<'info>,
+
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,14 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableRegisterPlugin<'info> {
+    #[account(mut, has_one = admin, seeds = [b"registry"], bump = registry.bump)]
+    pub registry: Account<'info, Registry>,
+
+    /// CHECK: VULNERABLE - not checked to be an executable, immutable program
+    pub plugin: UncheckedAccount<'info>,
+
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,16 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::Registry;
+
+#[test]
+fn registry_layout_is_stable() {
+    let registry = Registry {
+        admin: Pubkey::new_from_array([1; 32]),
+        plugin: Pubkey::new_from_array([2; 32]),
+        bump: 49,
+    };
+    assert_golden!("Registry", golden::anchor(&registry));
+}
@@ -0,0 +1,137 @@
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::system_program::{self, Transfer};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+#[feature_gated]
+#[program]
+pub mod plugin_registry {
+    use super::*;
+    #[secure]
+    use secref_guards::program_account::assert_immutable_program;
+
+    /// Create the registry that records the approved plugin
+    pub fn initialize_registry(ctx: Context<InitializeRegistry>) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        registry.admin = ctx.accounts.admin.key();
+        registry.plugin = Pubkey::default();
+        registry.bump = ctx.bumps.registry;
+
+        msg!("Registry initialized with admin: {}", registry.admin);
+        Ok(())
+    }
+
+    /// Move lamports into the vault the plugin manages
+    pub fn fund_vault(ctx: Context<FundVault>, amount: u64) -> Result<()> {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Vault funded with {} lamports", amount);
+        Ok(())
+    }
+
+    /// Invoke the approved plugin with the vault as a signer
+    ///
+    /// The plugin receives the vault's signature and any remaining accounts.
+    /// Whatever code lives at the approved address when this runs can move
+    /// the vault's lamports, which is why approval must pin that code down.
+    pub fn execute_plugin<'info>(ctx: Context<'_, '_, '_, 'info, ExecutePlugin<'info>>, data: Vec<u8>) -> Result<()> {
+        let vault = &ctx.accounts.vault;
+        let plugin = &ctx.accounts.plugin;
+
+        let mut metas = vec![AccountMeta::new(vault.key(), true)];
+        metas.extend(ctx.remaining_accounts.iter().map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        }));
+
+        let mut infos = vec![vault.to_account_info(), plugin.to_account_info()];
+        infos.extend(ctx.remaining_accounts.iter().cloned());
+
+        invoke_signed(
+            &Instruction {
+                program_id: plugin.key(),
+                accounts: metas,
+                data,
+            },
+            &infos,
+            &[&[b"vault", &[ctx.bumps.vault]]],
+        )?;
+
+        msg!("Plugin {} executed", plugin.key());
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Approve the plugin address supplied by its author
+    ///
+    /// Security Issue: The admin reviews the code the author points them to,
+    /// but the program only records an address. Nothing checks that the
+    /// address is executable today, that it is a BPF program rather than a
+    /// data account or builtin, or that its code cannot change. The author
+    /// can submit a keypair address and deploy to it after approval, or keep
+    /// the upgrade authority and swap in a drainer later - either way
+    /// `execute_plugin` hands the vault's signature to unreviewed code.
+    #[vulnerable]
+    pub fn vulnerable_register_plugin(ctx: Context<VulnerableRegisterPlugin>) -> Result<()> {
+        // VULNERABILITY: An unchecked account is trusted as a program
+        let registry = &mut ctx.accounts.registry;
+        registry.plugin = ctx.accounts.plugin.key();
+
+        msg!("Plugin approved: {}", registry.plugin);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version of the same instruction
+    // with full program-account validation from secref-guards.
+
+    /// SECURE: Approve a plugin only if it is a deployed, immutable program
+    ///
+    /// Security Fix: `assert_immutable_program` requires the account to be
+    /// executable, owned by a BPF loader, and - for upgradeable programs -
+    /// backed by the ProgramData account it points at with no upgrade
+    /// authority left. The code the admin reviewed is the code that will run.
+    #[secure]
+    pub fn secure_register_plugin(ctx: Context<SecureRegisterPlugin>) -> Result<()> {
+        // SECURITY: Executable, loader-owned and frozen
+        assert_immutable_program(&ctx.accounts.plugin, &ctx.accounts.plugin_program_data)?;
+
+        let registry = &mut ctx.accounts.registry;
+        registry.plugin = ctx.accounts.plugin.key();
+
+        msg!("Verified immutable plugin approved: {}", registry.plugin);
+        Ok(())
+    }
+}
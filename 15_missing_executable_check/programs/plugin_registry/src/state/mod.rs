@@ -0,0 +1,13 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Registry {
+    /// The authority allowed to approve plugins (32 bytes)
+    pub admin: Pubkey,
+    /// The approved plugin program (32 bytes)
+    pub plugin: Pubkey,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
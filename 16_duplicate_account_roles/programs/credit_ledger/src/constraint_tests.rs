@@ -0,0 +1,121 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, Fixture,
+    TestAccount,
+};
+
+use crate::{Balance, Ledger, SecureTransfer, COUNCIL_SIZE};
+
+fn balance_account(authority: Pubkey) -> TestAccount {
+    let (address, bump) = Pubkey::find_program_address(&[b"balance", authority.as_ref()], &crate::ID);
+    TestAccount::anchor(&Balance {
+        authority,
+        amount: 1_000,
+        bump,
+    })
+    .at(address)
+}
+
+fn transfer_fixture() -> Fixture {
+    let authority = TestAccount::signer();
+    let relayer = TestAccount::signer();
+    let (ledger_address, ledger_bump) = Pubkey::find_program_address(&[b"ledger"], &crate::ID);
+    let ledger = TestAccount::anchor(&Ledger {
+        admin: Pubkey::new_unique(),
+        council: [Pubkey::new_unique(); COUNCIL_SIZE],
+        bump: ledger_bump,
+    })
+    .at(ledger_address);
+    Fixture::new()
+        .with("ledger", ledger)
+        .with("from", balance_account(authority.key))
+        .with("to", balance_account(Pubkey::new_unique()))
+        .with("relayer_balance", balance_account(relayer.key))
+        .with("authority", authority)
+        .with("relayer", relayer)
+        .with("approver", TestAccount::signer())
+}
+
+/// The owner of `party` relays the transfer, paying out of another balance
+/// they control, which is allowed
+fn relayed_by(party: &'static str) -> Fixture {
+    let mut fixture = transfer_fixture();
+    let authority = Balance::try_deserialize(&mut &fixture.account(party).data[..])
+        .unwrap()
+        .authority;
+    *fixture.account_mut("relayer") = TestAccount::signer().at(authority);
+    *fixture.account_mut("relayer_balance") = balance_account(authority).rekeyed();
+    fixture
+}
+
+#[test]
+fn secure_transfer_reads_the_ledger_pda() {
+    assert_seeds_violation!(SecureTransfer, transfer_fixture(), "ledger");
+    assert_owner_violation!(SecureTransfer, transfer_fixture(), "ledger");
+}
+
+#[test]
+fn secure_transfer_debits_the_signers_balance() {
+    assert_seeds_violation!(SecureTransfer, transfer_fixture(), "from");
+    assert_seeds_violation!(SecureTransfer, transfer_fixture(), "authority");
+    assert_signer_violation!(SecureTransfer, transfer_fixture(), "authority");
+    assert_constraint_violation!(
+        SecureTransfer,
+        transfer_fixture(),
+        "from" => |from| from.edit(|stored: &mut Balance| stored.authority = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+}
+
+#[test]
+fn secure_transfer_credits_a_balance_pda() {
+    assert_seeds_violation!(SecureTransfer, transfer_fixture(), "to");
+    assert_owner_violation!(SecureTransfer, transfer_fixture(), "to");
+}
+
+#[test]
+fn secure_transfer_rejects_transfers_to_self() {
+    let fixture = transfer_fixture();
+    let from = fixture.account("from").clone();
+    assert_constraint_violation!(SecureTransfer, fixture, "to" => from, crate::ErrorCode::SelfTransfer);
+}
+
+#[test]
+fn secure_transfer_requires_the_relayers_own_balance() {
+    assert_constraint_violation!(
+        SecureTransfer,
+        transfer_fixture(),
+        "relayer_balance" => balance_account(Pubkey::new_unique()),
+        crate::ErrorCode::NotRelayerBalance
+    );
+    assert_signer_violation!(SecureTransfer, transfer_fixture(), "relayer");
+}
+
+#[test]
+fn secure_transfer_rejects_a_relayer_balance_aliasing_either_party() {
+    for party in ["from", "to"] {
+        let fixture = relayed_by(party);
+        let aliased = fixture.account(party).clone();
+        assert_constraint_violation!(
+            SecureTransfer,
+            fixture,
+            "relayer_balance" => aliased,
+            crate::ErrorCode::DuplicateAccount
+        );
+    }
+}
+
+#[test]
+fn secure_transfer_requires_a_second_approver() {
+    let fixture = transfer_fixture();
+    let authority = fixture.account("authority").clone();
+    assert_constraint_violation!(
+        SecureTransfer,
+        fixture,
+        "approver" => authority,
+        crate::ErrorCode::SelfApproval
+    );
+    assert_signer_violation!(SecureTransfer, transfer_fixture(), "approver");
+}
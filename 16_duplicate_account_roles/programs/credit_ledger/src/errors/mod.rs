@@ -0,0 +1,21 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Approver is not on the council")]
+    NotCouncilMember,
+    #[msg("Relayer balance does not belong to the relayer")]
+    NotRelayerBalance,
+    #[msg("Sender and recipient must be different accounts")]
+    SelfTransfer,
+    #[msg("The same account was passed in two roles")]
+    DuplicateAccount,
+    #[msg("Transfers must be approved by someone other than the sender")]
+    SelfApproval,
+    #[msg("Insufficient credits")]
+    InsufficientCredits,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
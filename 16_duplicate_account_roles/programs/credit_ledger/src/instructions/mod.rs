@@ -0,0 +1,58 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializeLedger<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 32 * COUNCIL_SIZE + 1, // discriminator + admin + council + bump
+        seeds = [b"ledger"],
+        bump
+    )]
+    pub ledger: Account<'info, Ledger>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenBalance<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 8 + 1, // discriminator + authority + amount + bump
+        seeds = [b"balance", authority.key().as_ref()],
+        bump
+    )]
+    pub balance: Account<'info, Balance>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MintCredits<'info> {
+    #[account(has_one = admin, seeds = [b"ledger"], bump = ledger.bump)]
+    pub ledger: Account<'info, Ledger>,
+
+    #[account(mut, seeds = [b"balance", balance.authority.as_ref()], bump = balance.bump)]
+    pub balance: Account<'info, Balance>,
+
+    pub admin: Signer<'info>,
+}
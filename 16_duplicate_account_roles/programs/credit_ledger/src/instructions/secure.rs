@@ -0,0 +1,40 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureTransfer<'info> {
+    #[account(seeds = [b"ledger"], bump = ledger.bump)]
+    pub ledger: Account<'info, Ledger>,
+
+    #[account(mut, has_one = authority, seeds = [b"balance", authority.key().as_ref()], bump = from.bump)]
+    pub from: Account<'info, Balance>,
+
+    // SECURITY: Sender and recipient must be different balances
+    #[account(
+        mut,
+        seeds = [b"balance", to.authority.as_ref()],
+        bump = to.bump,
+        constraint = to.key() != from.key() @ ErrorCode::SelfTransfer
+    )]
+    pub to: Account<'info, Balance>,
+
+    // SECURITY: The relayer's balance must not alias either party
+    #[account(
+        mut,
+        constraint = relayer_balance.authority == relayer.key() @ ErrorCode::NotRelayerBalance,
+        constraint = relayer_balance.key() != from.key() @ ErrorCode::DuplicateAccount,
+        constraint = relayer_balance.key() != to.key() @ ErrorCode::DuplicateAccount
+    )]
+    pub relayer_balance: Account<'info, Balance>,
+
+    pub authority: Signer<'info>,
+
+    /// Pays the transaction fee and earns the relay fee
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    // SECURITY: A second council member must approve
+    #[account(constraint = approver.key() != authority.key() @ ErrorCode::SelfApproval)]
+    pub approver: Signer<'info>,
+}
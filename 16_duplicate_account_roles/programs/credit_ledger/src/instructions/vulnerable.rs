@@ -0,0 +1,32 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableTransfer<'info> {
+    #[account(seeds = [b"ledger"], bump = ledger.bump)]
+    pub ledger: Account<'info, Ledger>,
+
+    #[account(mut, has_one = authority, seeds = [b"balance", authority.key().as_ref()], bump = from.bump)]
+    pub from: Account<'info, Balance>,
+
+    // VULNERABILITY: May be the same account as `from`
+    #[account(mut, seeds = [b"balance", to.authority.as_ref()], bump = to.bump)]
+    pub to: Account<'info, Balance>,
+
+    // VULNERABILITY: May alias `from` or `to`
+    #[account(
+        mut,
+        constraint = relayer_balance.authority == relayer.key() @ ErrorCode::NotRelayerBalance
+    )]
+    pub relayer_balance: Account<'info, Balance>,
+
+    pub authority: Signer<'info>,
+
+    /// Pays the transaction fee and earns the relay fee
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    // VULNERABILITY: May be the same signer as `authority`
+    pub approver: Signer<'info>,
+}
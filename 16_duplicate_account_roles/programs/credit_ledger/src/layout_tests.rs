@@ -0,0 +1,26 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Balance, Ledger, COUNCIL_SIZE};
+
+#[test]
+fn ledger_layout_is_stable() {
+    let ledger = Ledger {
+        admin: Pubkey::new_from_array([1; 32]),
+        council: [Pubkey::new_from_array([2; 32]); COUNCIL_SIZE],
+        bump: 49,
+    };
+    assert_golden!("Ledger", golden::anchor(&ledger));
+}
+
+#[test]
+fn balance_layout_is_stable() {
+    let balance = Balance {
+        authority: Pubkey::new_from_array([4; 32]),
+        amount: 0x5152535455565758,
+        bump: 97,
+    };
+    assert_golden!("Balance", golden::anchor(&balance));
+}
@@ -0,0 +1,141 @@
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Number of council members who may approve transfers
+pub const COUNCIL_SIZE: usize = 3;
+
+#[feature_gated]
+#[program]
+pub mod credit_ledger {
+    use super::*;
+
+    /// Create the ledger with its approval council
+    pub fn initialize_ledger(ctx: Context<InitializeLedger>, council: [Pubkey; COUNCIL_SIZE]) -> Result<()> {
+        let ledger = &mut ctx.accounts.ledger;
+        ledger.admin = ctx.accounts.admin.key();
+        ledger.council = council;
+        ledger.bump = ctx.bumps.ledger;
+
+        msg!("Ledger initialized with a {}-member council", COUNCIL_SIZE);
+        Ok(())
+    }
+
+    /// Open an empty credit balance for the signer
+    pub fn open_balance(ctx: Context<OpenBalance>) -> Result<()> {
+        let balance = &mut ctx.accounts.balance;
+        balance.authority = ctx.accounts.authority.key();
+        balance.amount = 0;
+        balance.bump = ctx.bumps.balance;
+
+        msg!("Balance opened for {}", balance.authority);
+        Ok(())
+    }
+
+    /// Issue credits to a balance (admin only)
+    pub fn mint_credits(ctx: Context<MintCredits>, amount: u64) -> Result<()> {
+        let balance = &mut ctx.accounts.balance;
+        balance.amount = balance.amount.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Minted {} credits to {}", amount, balance.authority);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Relayed, council-approved transfer that assumes distinct roles
+    ///
+    /// Security Issue: `from`, `to` and `relayer_balance` are deserialized
+    /// into separate copies even when they are the same account, and Anchor
+    /// writes each copy back on exit in field order. Passing `to == from`
+    /// debits one copy, credits the other, and the credited copy is written
+    /// last - the sender gains `amount` from nothing. Likewise, nothing stops
+    /// a council member from passing themselves as both `authority` and
+    /// `approver`, so the two-person rule approves itself.
+    #[vulnerable]
+    pub fn vulnerable_transfer(ctx: Context<VulnerableTransfer>, amount: u64, fee: u64) -> Result<()> {
+        // VULNERABILITY: The approver may be the authority itself
+        require_council_member(&ctx.accounts.ledger, &ctx.accounts.approver.key())?;
+
+        // VULNERABILITY: from, to and relayer_balance may all be one account
+        apply_transfer(
+            &mut ctx.accounts.from,
+            &mut ctx.accounts.to,
+            &mut ctx.accounts.relayer_balance,
+            amount,
+            fee,
+        )
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version of the same instruction
+    // with every pair of roles that must differ checked explicitly.
+
+    /// SECURE: Relayed, council-approved transfer with distinct roles
+    ///
+    /// Security Fix: The account constraints reject `to == from`, a relayer
+    /// balance that aliases the sender or recipient, and an approver who is
+    /// also the authority. The transfer logic is unchanged - it is only safe
+    /// once its assumption that the accounts are distinct is enforced.
+    #[secure]
+    pub fn secure_transfer(ctx: Context<SecureTransfer>, amount: u64, fee: u64) -> Result<()> {
+        require_council_member(&ctx.accounts.ledger, &ctx.accounts.approver.key())?;
+
+        apply_transfer(
+            &mut ctx.accounts.from,
+            &mut ctx.accounts.to,
+            &mut ctx.accounts.relayer_balance,
+            amount,
+            fee,
+        )
+    }
+}
+
+/// Require `approver` to sit on the ledger's council
+fn require_council_member(ledger: &Ledger, approver: &Pubkey) -> Result<()> {
+    require!(ledger.council.contains(approver), ErrorCode::NotCouncilMember);
+    Ok(())
+}
+
+/// Debit `amount + fee` from `from`, credit `amount` to `to` and `fee` to the relayer
+///
+/// Assumes the three balances are different accounts.
+fn apply_transfer(
+    from: &mut Balance,
+    to: &mut Balance,
+    relayer_balance: &mut Balance,
+    amount: u64,
+    fee: u64,
+) -> Result<()> {
+    let total = amount.checked_add(fee).ok_or(ErrorCode::ArithmeticOverflow)?;
+    from.amount = from.amount.checked_sub(total).ok_or(ErrorCode::InsufficientCredits)?;
+    to.amount = to.amount.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+    relayer_balance.amount = relayer_balance
+        .amount
+        .checked_add(fee)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    msg!("Transferred {} credits to {} with a {} credit relay fee", amount, to.authority, fee);
+    Ok(())
+}
@@ -0,0 +1,23 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Ledger {
+    /// The authority allowed to mint credits (32 bytes)
+    pub admin: Pubkey,
+    /// Members allowed to approve transfers (32 * COUNCIL_SIZE bytes)
+    pub council: [Pubkey; COUNCIL_SIZE],
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Balance {
+    /// The wallet that owns these credits (32 bytes)
+    pub authority: Pubkey,
+    /// Credits held (8 bytes)
+    pub amount: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
@@ -0,0 +1,214 @@
+//! A stolen admin key against the treasury sweep: paid out at once without
+//! an allowlist, held back for `DESTINATION_DELAY` with one.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use secref_testkit::runtime::{take_events, warp_by, warp_to};
+use secref_testkit::{execute, Fixture, TestAccount};
+
+use crate::{instruction, Config, DestinationAdded, ErrorCode, Vault, DESTINATION_DELAY};
+
+const START: i64 = 1_700_000_000;
+const TREASURY: u64 = 1_000;
+const ALLOWED_DESTINATION_SPACE: usize = 8 + 32 + 32 + 8 + 1;
+
+fn pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &crate::ID)
+}
+
+/// The bank's config and secure treasury, and the admin key the attacker holds
+struct Bank {
+    admin: TestAccount,
+    config: TestAccount,
+    treasury: TestAccount,
+    treasury_tokens: TestAccount,
+    treasury_authority: TestAccount,
+}
+
+impl Bank {
+    fn new() -> Self {
+        warp_to(START);
+        let admin = TestAccount::signer();
+        let mint = Pubkey::new_unique();
+        let (config, bump) = pda(&[b"config"]);
+        let (treasury, treasury_bump) = pda(&[b"secure_vault", admin.key.as_ref(), mint.as_ref()]);
+        let (authority, authority_bump) = pda(&[b"vault_authority", treasury.as_ref()]);
+        let (tokens, _) = pda(&[b"vault_tokens", treasury.as_ref()]);
+        Self {
+            config: TestAccount::anchor(&Config { admin: admin.key, bump }).at(config),
+            treasury: TestAccount::anchor(&Vault {
+                owner: admin.key,
+                mint,
+                authority_bump,
+                bump: treasury_bump,
+            })
+            .at(treasury),
+            treasury_tokens: TestAccount::token_account(mint, authority, TREASURY).at(tokens),
+            treasury_authority: TestAccount::system(0).at(authority),
+            admin,
+        }
+    }
+
+    /// A token account of the treasury's mint, owned by someone new
+    fn destination(&self) -> TestAccount {
+        TestAccount::token_account(self.treasury.state::<Vault>().mint, Pubkey::new_unique(), 0)
+    }
+
+    fn allowed_address(&self, destination: Pubkey) -> Pubkey {
+        pda(&[b"destination", self.config.key.as_ref(), destination.as_ref()]).0
+    }
+
+    /// Allowlist `destination` with the admin key, returning its entry
+    fn add(&self, destination: &TestAccount) -> TestAccount {
+        let mut fixture = Fixture::new()
+            .with("config", self.config.clone())
+            .with(
+                "allowed_destination",
+                TestAccount::uninitialized(ALLOWED_DESTINATION_SPACE).at(self.allowed_address(destination.key)),
+            )
+            .with("destination", destination.clone())
+            .with("admin", self.admin.clone())
+            .with("system_program", TestAccount::system_program());
+        execute!(&mut fixture, instruction::AddDestination {}).unwrap();
+        fixture.account("allowed_destination").clone()
+    }
+
+    /// Remove `allowed` from the allowlist, returning the closed account
+    fn remove(&self, allowed: TestAccount) -> TestAccount {
+        let mut fixture = Fixture::new()
+            .with("config", self.config.clone())
+            .with("allowed_destination", allowed)
+            .with("admin", self.admin.clone());
+        execute!(&mut fixture, instruction::RemoveDestination {}).unwrap();
+        fixture.account("allowed_destination").clone()
+    }
+
+    /// Sweep the whole treasury to `destination`, vouched for by `allowed`
+    fn sweep(&mut self, destination: &mut TestAccount, allowed: TestAccount) -> std::result::Result<(), ProgramError> {
+        let mut fixture = Fixture::new()
+            .with("config", self.config.clone())
+            .with("treasury", self.treasury.clone())
+            .with("source", self.treasury_tokens.clone())
+            .with("destination", destination.clone())
+            .with("treasury_authority", self.treasury_authority.clone())
+            .with("allowed_destination", allowed)
+            .with("admin", self.admin.clone())
+            .with("token_program", TestAccount::token_program());
+        let result = execute!(&mut fixture, instruction::SecureSweep { amount: TREASURY });
+        self.treasury_tokens = fixture.account("source").clone();
+        *destination = fixture.account("destination").clone();
+        result
+    }
+
+    fn treasury_balance(&self) -> u64 {
+        self.treasury_tokens.state::<TokenAccount>().amount
+    }
+}
+
+fn balance(account: &TestAccount) -> u64 {
+    account.state::<TokenAccount>().amount
+}
+
+#[test]
+fn vulnerable_sweep_pays_a_new_destination_at_once() {
+    let admin = TestAccount::signer();
+    let mint = Pubkey::new_unique();
+    let (config, bump) = pda(&[b"config"]);
+    let (treasury, treasury_bump) = pda(&[b"vault", admin.key.as_ref(), mint.as_ref()]);
+    let (authority, authority_bump) = pda(&[b"authority"]);
+    let (tokens, _) = pda(&[b"vault_tokens", treasury.as_ref()]);
+    let mut fixture = Fixture::new()
+        .with("config", TestAccount::anchor(&Config { admin: admin.key, bump }).at(config))
+        .with(
+            "treasury",
+            TestAccount::anchor(&Vault {
+                owner: admin.key,
+                mint,
+                authority_bump,
+                bump: treasury_bump,
+            })
+            .at(treasury),
+        )
+        .with("source", TestAccount::token_account(mint, authority, TREASURY).at(tokens))
+        .with("destination", TestAccount::token_account(mint, Pubkey::new_unique(), 0))
+        .with("global_authority", TestAccount::system(0).at(authority))
+        .with("admin", admin)
+        .with("token_program", TestAccount::token_program());
+
+    execute!(&mut fixture, instruction::VulnerableSweep { amount: TREASURY }).unwrap();
+
+    // The attacker's first transaction empties the treasury
+    assert_eq!(fixture.state::<TokenAccount>("destination").amount, TREASURY);
+}
+
+#[test]
+fn secure_sweep_refuses_a_destination_never_allowlisted() {
+    let mut bank = Bank::new();
+    let mut attacker = bank.destination();
+    let unlisted = TestAccount::system(0).at(bank.allowed_address(attacker.key));
+
+    assert_eq!(
+        bank.sweep(&mut attacker, unlisted),
+        Err(Error::from(anchor_lang::error::ErrorCode::AccountNotInitialized).into())
+    );
+    assert_eq!(bank.treasury_balance(), TREASURY);
+}
+
+#[test]
+fn add_destination_announces_a_destination_it_cannot_pay_yet() {
+    let mut bank = Bank::new();
+    let mut attacker = bank.destination();
+    let allowed = bank.add(&attacker);
+
+    assert_eq!(
+        take_events::<DestinationAdded>()
+            .iter()
+            .map(|event| (event.config, event.destination, event.active_at))
+            .collect::<Vec<_>>(),
+        [(bank.config.key, attacker.key, START + DESTINATION_DELAY)]
+    );
+    assert_eq!(
+        bank.sweep(&mut attacker, allowed.clone()),
+        Err(Error::from(ErrorCode::DestinationNotActive).into())
+    );
+
+    // One second short of the delay is still too early
+    warp_by(DESTINATION_DELAY - 1);
+    assert_eq!(
+        bank.sweep(&mut attacker, allowed),
+        Err(Error::from(ErrorCode::DestinationNotActive).into())
+    );
+    assert_eq!(bank.treasury_balance(), TREASURY);
+}
+
+#[test]
+fn removing_a_stolen_addition_within_the_delay_keeps_the_treasury() {
+    let mut bank = Bank::new();
+    let mut attacker = bank.destination();
+    let allowed = bank.add(&attacker);
+
+    // The team sees the event and removes the entry before it activates
+    warp_by(DESTINATION_DELAY / 2);
+    let removed = bank.remove(allowed);
+    warp_by(DESTINATION_DELAY);
+
+    assert_eq!(
+        bank.sweep(&mut attacker, removed),
+        Err(Error::from(anchor_lang::error::ErrorCode::AccountNotInitialized).into())
+    );
+    assert_eq!(bank.treasury_balance(), TREASURY);
+    assert_eq!(balance(&attacker), 0);
+}
+
+#[test]
+fn secure_sweep_pays_an_allowlisted_destination_after_the_delay() {
+    let mut bank = Bank::new();
+    let mut team = bank.destination();
+    let allowed = bank.add(&team);
+
+    warp_by(DESTINATION_DELAY);
+    bank.sweep(&mut team, allowed).unwrap();
+
+    assert_eq!(bank.treasury_balance(), 0);
+    assert_eq!(balance(&team), TREASURY);
+}
@@ -0,0 +1,247 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::runtime::account_already_in_use;
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_owner_violation, assert_seeds_violation,
+    assert_signer_violation, Fixture, TestAccount,
+};
+
+use crate::{
+    AddDestination, AllowedDestination, Config, RemoveDestination, SecureOpenVault, SecureSweep, SecureWithdraw, Vault,
+};
+
+const VAULT_SPACE: usize = 8 + 32 + 32 + 1 + 1;
+const ALLOWED_DESTINATION_SPACE: usize = 8 + 32 + 32 + 8 + 1;
+const TOKEN_ACCOUNT_SPACE: usize = 165;
+
+fn pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &crate::ID)
+}
+
+/// A vault at its PDA, with its token account and signing authority
+fn vault_accounts(owner: Pubkey, mint: Pubkey) -> (TestAccount, TestAccount, TestAccount) {
+    let (address, bump) = pda(&[b"secure_vault", owner.as_ref(), mint.as_ref()]);
+    let (authority, authority_bump) = pda(&[b"vault_authority", address.as_ref()]);
+    let (tokens, _) = pda(&[b"vault_tokens", address.as_ref()]);
+    let vault = TestAccount::anchor(&Vault {
+        owner,
+        mint,
+        authority_bump,
+        bump,
+    })
+    .at(address);
+    (
+        vault,
+        TestAccount::token_account(mint, authority, 1_000).at(tokens),
+        TestAccount::system(0).at(authority),
+    )
+}
+
+fn open_fixture() -> Fixture {
+    let owner = TestAccount::signer();
+    let mint = TestAccount::mint(Pubkey::new_unique(), 6, 1_000_000);
+    let (vault, _) = pda(&[b"secure_vault", owner.key.as_ref(), mint.key.as_ref()]);
+    let (tokens, _) = pda(&[b"vault_tokens", vault.as_ref()]);
+    let (authority, _) = pda(&[b"vault_authority", vault.as_ref()]);
+    Fixture::new()
+        .with("vault", TestAccount::uninitialized(VAULT_SPACE).at(vault))
+        .with(
+            "vault_tokens",
+            TestAccount::uninitialized(TOKEN_ACCOUNT_SPACE).at(tokens),
+        )
+        .with("vault_authority", TestAccount::system(0).at(authority))
+        .with("mint", mint)
+        .with("owner", owner)
+        .with("token_program", TestAccount::token_program())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn withdraw_fixture() -> Fixture {
+    let owner = TestAccount::signer();
+    let mint = Pubkey::new_unique();
+    let (vault, vault_tokens, vault_authority) = vault_accounts(owner.key, mint);
+    Fixture::new()
+        .with("vault", vault)
+        .with("vault_tokens", vault_tokens)
+        .with("vault_authority", vault_authority)
+        .with("destination", TestAccount::token_account(mint, owner.key, 0))
+        .with("owner", owner)
+        .with("token_program", TestAccount::token_program())
+}
+
+/// The config at its PDA, naming `admin`
+fn config_account(admin: Pubkey) -> TestAccount {
+    let (address, bump) = pda(&[b"config"]);
+    TestAccount::anchor(&Config { admin, bump }).at(address)
+}
+
+/// `destination` on `config`'s allowlist, payable from `active_at`
+fn allowed_destination(config: Pubkey, destination: Pubkey, active_at: i64) -> TestAccount {
+    let (address, bump) = pda(&[b"destination", config.as_ref(), destination.as_ref()]);
+    TestAccount::anchor(&AllowedDestination {
+        config,
+        destination,
+        active_at,
+        bump,
+    })
+    .at(address)
+}
+
+fn sweep_fixture() -> Fixture {
+    let admin = TestAccount::signer();
+    let mint = Pubkey::new_unique();
+    let config = config_account(admin.key);
+    let (treasury, treasury_tokens, treasury_authority) = vault_accounts(admin.key, mint);
+    let destination = TestAccount::token_account(mint, Pubkey::new_unique(), 0);
+    let allowed = allowed_destination(config.key, destination.key, 0);
+    Fixture::new()
+        .with("config", config)
+        .with("treasury", treasury)
+        .with("source", treasury_tokens)
+        .with("destination", destination)
+        .with("treasury_authority", treasury_authority)
+        .with("allowed_destination", allowed)
+        .with("admin", admin)
+        .with("token_program", TestAccount::token_program())
+}
+
+fn add_destination_fixture() -> Fixture {
+    let admin = TestAccount::signer();
+    let config = config_account(admin.key);
+    let destination = TestAccount::token_account(Pubkey::new_unique(), Pubkey::new_unique(), 0);
+    let (allowed, _) = pda(&[b"destination", config.key.as_ref(), destination.key.as_ref()]);
+    Fixture::new()
+        .with("config", config)
+        .with("allowed_destination", TestAccount::uninitialized(ALLOWED_DESTINATION_SPACE).at(allowed))
+        .with("destination", destination)
+        .with("admin", admin)
+        .with("system_program", TestAccount::system_program())
+}
+
+fn remove_destination_fixture() -> Fixture {
+    let admin = TestAccount::signer();
+    let config = config_account(admin.key);
+    let allowed = allowed_destination(config.key, Pubkey::new_unique(), 0);
+    Fixture::new()
+        .with("config", config)
+        .with("allowed_destination", allowed)
+        .with("admin", admin)
+}
+
+#[test]
+fn secure_open_vault_derives_every_account_from_owner_and_mint() {
+    assert_seeds_violation!(SecureOpenVault, open_fixture(), "vault");
+    assert_seeds_violation!(SecureOpenVault, open_fixture(), "vault_tokens");
+    assert_seeds_violation!(SecureOpenVault, open_fixture(), "vault_authority");
+}
+
+#[test]
+fn secure_open_vault_rejects_existing_token_accounts() {
+    assert_constraint_violation!(
+        SecureOpenVault,
+        open_fixture(),
+        "vault_tokens" => |tokens| {
+            *tokens = TestAccount::token_account(Pubkey::new_unique(), Pubkey::new_unique(), 0).at(tokens.key)
+        },
+        account_already_in_use()
+    );
+}
+
+#[test]
+fn secure_open_vault_requires_a_real_mint_and_owner_signature() {
+    assert_owner_violation!(SecureOpenVault, open_fixture(), "mint");
+    assert_signer_violation!(SecureOpenVault, open_fixture(), "owner");
+    assert_constraint_violation!(
+        SecureOpenVault,
+        open_fixture(),
+        "token_program" => TestAccount::program(Pubkey::new_unique()),
+        ErrorCode::InvalidProgramId
+    );
+}
+
+#[test]
+fn secure_withdraw_requires_the_vault_owner() {
+    assert_constraint_violation!(
+        SecureWithdraw,
+        withdraw_fixture(),
+        "vault" => |vault| vault.edit(|stored: &mut Vault| stored.owner = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_seeds_violation!(SecureWithdraw, withdraw_fixture(), "owner");
+    assert_signer_violation!(SecureWithdraw, withdraw_fixture(), "owner");
+}
+
+#[test]
+fn secure_withdraw_uses_only_the_vaults_own_accounts() {
+    assert_seeds_violation!(SecureWithdraw, withdraw_fixture(), "vault");
+    assert_seeds_violation!(SecureWithdraw, withdraw_fixture(), "vault_tokens");
+    assert_seeds_violation!(SecureWithdraw, withdraw_fixture(), "vault_authority");
+}
+
+#[test]
+fn secure_withdraw_pays_out_the_vaults_mint() {
+    assert_constraint_violation!(
+        SecureWithdraw,
+        withdraw_fixture(),
+        "destination" => |destination| {
+            *destination = TestAccount::token_account(Pubkey::new_unique(), Pubkey::new_unique(), 0)
+        },
+        ErrorCode::ConstraintTokenMint
+    );
+}
+
+#[test]
+fn secure_sweep_requires_the_config_admin() {
+    assert_has_one_violation!(SecureSweep, sweep_fixture(), "admin");
+    assert_signer_violation!(SecureSweep, sweep_fixture(), "admin");
+    assert_seeds_violation!(SecureSweep, sweep_fixture(), "config");
+}
+
+#[test]
+fn secure_sweep_signs_only_for_the_admins_treasury() {
+    assert_seeds_violation!(SecureSweep, sweep_fixture(), "treasury");
+    assert_seeds_violation!(SecureSweep, sweep_fixture(), "treasury_authority");
+    assert_constraint_violation!(
+        SecureSweep,
+        sweep_fixture(),
+        "treasury" => |treasury| treasury.edit(|stored: &mut Vault| stored.authority_bump = stored.authority_bump.wrapping_sub(1)),
+        ErrorCode::ConstraintSeeds
+    );
+}
+
+#[test]
+fn secure_sweep_pays_only_an_allowlisted_destination() {
+    assert_seeds_violation!(SecureSweep, sweep_fixture(), "allowed_destination");
+    assert_seeds_violation!(SecureSweep, sweep_fixture(), "destination");
+    assert_owner_violation!(SecureSweep, sweep_fixture(), "allowed_destination");
+}
+
+#[test]
+fn add_destination_requires_the_config_admin() {
+    assert_has_one_violation!(AddDestination, add_destination_fixture(), "admin");
+    assert_signer_violation!(AddDestination, add_destination_fixture(), "admin");
+    assert_seeds_violation!(AddDestination, add_destination_fixture(), "config");
+}
+
+#[test]
+fn add_destination_allowlists_a_token_account_at_its_pda() {
+    assert_seeds_violation!(AddDestination, add_destination_fixture(), "allowed_destination");
+    assert_owner_violation!(AddDestination, add_destination_fixture(), "destination");
+    assert_constraint_violation!(
+        AddDestination,
+        add_destination_fixture(),
+        "allowed_destination" => |allowed| {
+            let stored = allowed_destination(Pubkey::new_unique(), Pubkey::new_unique(), 0);
+            *allowed = stored.at(allowed.key)
+        },
+        account_already_in_use()
+    );
+}
+
+#[test]
+fn remove_destination_requires_the_config_admin() {
+    assert_has_one_violation!(RemoveDestination, remove_destination_fixture(), "admin");
+    assert_signer_violation!(RemoveDestination, remove_destination_fixture(), "admin");
+    assert_seeds_violation!(RemoveDestination, remove_destination_fixture(), "allowed_destination");
+}
@@ -0,0 +1,11 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Destination is allowlisted but its delay has not passed")]
+    DestinationNotActive,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
@@ -0,0 +1,45 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 1, // discriminator + admin + bump
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut, seeds = [b"vault_tokens", vault.key().as_ref()], bump)]
+    pub vault_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = vault.mint, token::authority = depositor)]
+    pub depositor_tokens: Account<'info, TokenAccount>,
+
+    pub depositor: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,128 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureOpenVault<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 32 + 32 + 1 + 1, // discriminator + owner + mint + authority_bump + bump
+        seeds = [b"secure_vault", owner.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    // SECURITY: Each vault's tokens are owned by an authority derived from the vault
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"vault_tokens", vault.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority
+    )]
+    pub vault_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: Per-vault signing PDA; holds no data
+    #[account(seeds = [b"vault_authority", vault.key().as_ref()], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SecureWithdraw<'info> {
+    #[account(
+        has_one = owner,
+        seeds = [b"secure_vault", owner.key().as_ref(), vault.mint.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut, seeds = [b"vault_tokens", vault.key().as_ref()], bump)]
+    pub vault_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: Per-vault signing PDA; holds no data
+    #[account(seeds = [b"vault_authority", vault.key().as_ref()], bump = vault.authority_bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut, token::mint = vault.mint)]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SecureSweep<'info> {
+    #[account(has_one = admin, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(seeds = [b"secure_vault", admin.key().as_ref(), treasury.mint.as_ref()], bump = treasury.bump)]
+    pub treasury: Account<'info, Vault>,
+
+    // Left as loose as the vulnerable sweep to demonstrate containment
+    #[account(mut)]
+    pub source: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+
+    /// CHECK: The treasury's own signing PDA; holds no data
+    #[account(seeds = [b"vault_authority", treasury.key().as_ref()], bump = treasury.authority_bump)]
+    pub treasury_authority: UncheckedAccount<'info>,
+
+    // SECURITY: The destination must be on the config's allowlist
+    #[account(
+        seeds = [b"destination", config.key().as_ref(), destination.key().as_ref()],
+        bump = allowed_destination.bump
+    )]
+    pub allowed_destination: Account<'info, AllowedDestination>,
+
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct AddDestination<'info> {
+    #[account(has_one = admin, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 32 + 8 + 1, // discriminator + config + destination + active_at + bump
+        seeds = [b"destination", config.key().as_ref(), destination.key().as_ref()],
+        bump
+    )]
+    pub allowed_destination: Account<'info, AllowedDestination>,
+
+    pub destination: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveDestination<'info> {
+    #[account(has_one = admin, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"destination", config.key().as_ref(), allowed_destination.destination.as_ref()],
+        bump = allowed_destination.bump
+    )]
+    pub allowed_destination: Account<'info, AllowedDestination>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
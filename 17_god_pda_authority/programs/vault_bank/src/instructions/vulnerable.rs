@@ -0,0 +1,83 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableOpenVault<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 32 + 32 + 1 + 1, // discriminator + owner + mint + authority_bump + bump
+        seeds = [b"vault", owner.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    // VULNERABILITY: Every vault's tokens are owned by the same PDA
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"vault_tokens", vault.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = global_authority
+    )]
+    pub vault_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: Program-wide signing PDA; holds no data
+    #[account(seeds = [b"authority"], bump)]
+    pub global_authority: UncheckedAccount<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableWithdraw<'info> {
+    #[account(
+        has_one = owner,
+        seeds = [b"vault", owner.key().as_ref(), vault.mint.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut, seeds = [b"vault_tokens", vault.key().as_ref()], bump)]
+    pub vault_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: Program-wide signing PDA; holds no data
+    #[account(seeds = [b"authority"], bump = vault.authority_bump)]
+    pub global_authority: UncheckedAccount<'info>,
+
+    #[account(mut, token::mint = vault.mint)]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableSweep<'info> {
+    #[account(has_one = admin, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(seeds = [b"vault", admin.key().as_ref(), treasury.mint.as_ref()], bump = treasury.bump)]
+    pub treasury: Account<'info, Vault>,
+
+    // VULNERABILITY: Not tied to the treasury's token account
+    #[account(mut)]
+    pub source: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub destination: Account<'info, TokenAccount>,
+
+    /// CHECK: Program-wide signing PDA; holds no data
+    #[account(seeds = [b"authority"], bump)]
+    pub global_authority: UncheckedAccount<'info>,
+
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,37 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{AllowedDestination, Config, Vault};
+
+#[test]
+fn config_layout_is_stable() {
+    let config = Config {
+        admin: Pubkey::new_from_array([1; 32]),
+        bump: 33,
+    };
+    assert_golden!("Config", golden::anchor(&config));
+}
+
+#[test]
+fn vault_layout_is_stable() {
+    let vault = Vault {
+        owner: Pubkey::new_from_array([3; 32]),
+        mint: Pubkey::new_from_array([4; 32]),
+        authority_bump: 81,
+        bump: 97,
+    };
+    assert_golden!("Vault", golden::anchor(&vault));
+}
+
+#[test]
+fn allowed_destination_layout_is_stable() {
+    let allowed = AllowedDestination {
+        config: Pubkey::new_from_array([5; 32]),
+        destination: Pubkey::new_from_array([6; 32]),
+        active_at: 1_700_000_000,
+        bump: 89,
+    };
+    assert_golden!("AllowedDestination", golden::anchor(&allowed));
+}
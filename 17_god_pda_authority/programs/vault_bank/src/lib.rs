@@ -0,0 +1,272 @@
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod allowlist_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Seconds between allowlisting a sweep destination and paying it: two days
+pub const DESTINATION_DELAY: i64 = 2 * 86_400;
+
+#[feature_gated]
+#[program]
+pub mod vault_bank {
+    use super::*;
+
+    /// Record the admin allowed to sweep the treasury
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.bump = ctx.bumps.config;
+
+        msg!("Config initialized with admin: {}", config.admin);
+        Ok(())
+    }
+
+    /// Deposit tokens into any vault's token account
+    ///
+    /// Deposits need no program signature, so both designs share this.
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.depositor_tokens.to_account_info(),
+                    to: ctx.accounts.vault_tokens.to_account_info(),
+                    authority: ctx.accounts.depositor.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Deposited {} tokens into vault {}", amount, ctx.accounts.vault.key());
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Open a vault whose tokens are owned by the global authority
+    ///
+    /// Security Issue: Every vault token account - and the admin's treasury -
+    /// names the same PDA, `[b"authority"]`, as its owner. The program can
+    /// produce that signature in any instruction, so the safety of every
+    /// depositor's funds depends on every instruction being bug-free.
+    #[vulnerable]
+    pub fn vulnerable_open_vault(ctx: Context<VulnerableOpenVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.owner = ctx.accounts.owner.key();
+        vault.mint = ctx.accounts.mint.key();
+        vault.authority_bump = ctx.bumps.global_authority;
+        vault.bump = ctx.bumps.vault;
+
+        msg!("Vault opened for {} under the global authority", vault.owner);
+        Ok(())
+    }
+
+    /// VULNERABLE: Withdraw from a vault, signed by the global authority
+    ///
+    /// This instruction is correct on its own - the owner check is in place.
+    /// The problem is that the signature it produces is the same one that
+    /// guards every other vault.
+    #[vulnerable]
+    pub fn vulnerable_withdraw(ctx: Context<VulnerableWithdraw>, amount: u64) -> Result<()> {
+        let seeds: &[&[u8]] = &[b"authority", &[ctx.accounts.vault.authority_bump]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_tokens.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.global_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        msg!("Withdrew {} tokens from vault {}", amount, ctx.accounts.vault.key());
+        Ok(())
+    }
+
+    /// VULNERABLE: Admin sweep of the treasury, signed by the global authority
+    ///
+    /// Security Issue: `source` should be the treasury's token account but is
+    /// never tied to it. Because the global authority owns every vault, the
+    /// signature this instruction produces is accepted by the token program
+    /// for any depositor's vault. One missing constraint - or one leaked
+    /// admin key - exposes every vault the program has ever opened.
+    #[vulnerable]
+    pub fn vulnerable_sweep(ctx: Context<VulnerableSweep>, amount: u64) -> Result<()> {
+        let seeds: &[&[u8]] = &[b"authority", &[ctx.bumps.global_authority]];
+
+        // VULNERABILITY: The god PDA signs for whatever `source` turns out to be
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.source.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.global_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        msg!("Swept {} tokens from {}", amount, ctx.accounts.source.key());
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version of the same instructions
+    // with a separate authority PDA derived from each vault's key.
+
+    /// SECURE: Open a vault whose tokens are owned by its own authority PDA
+    ///
+    /// Security Fix: The token account's owner is `[b"vault_authority",
+    /// vault]`. The program can only sign for a vault by naming that vault,
+    /// so no instruction can produce a signature that covers two vaults.
+    #[secure]
+    pub fn secure_open_vault(ctx: Context<SecureOpenVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.owner = ctx.accounts.owner.key();
+        vault.mint = ctx.accounts.mint.key();
+        vault.authority_bump = ctx.bumps.vault_authority;
+        vault.bump = ctx.bumps.vault;
+
+        msg!("Vault opened for {} with a per-vault authority", vault.owner);
+        Ok(())
+    }
+
+    /// SECURE: Withdraw from a vault, signed by that vault's authority
+    #[secure]
+    pub fn secure_withdraw(ctx: Context<SecureWithdraw>, amount: u64) -> Result<()> {
+        let vault_key = ctx.accounts.vault.key();
+        // SECURITY: The signature only covers this vault's token account
+        let seeds: &[&[u8]] = &[b"vault_authority", vault_key.as_ref(), &[ctx.accounts.vault.authority_bump]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_tokens.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        msg!("Withdrew {} tokens from vault {}", amount, vault_key);
+        Ok(())
+    }
+
+    /// SECURE: Allowlist a sweep destination, payable after DESTINATION_DELAY
+    ///
+    /// Security Fix: An admin key is all it takes to add a destination, so
+    /// the addition is announced as `DestinationAdded` and the destination
+    /// cannot be paid for two days. A stolen key cannot exfiltrate at once:
+    /// the team sees the event and removes the destination before it can
+    /// be used.
+    #[secure]
+    pub fn add_destination(ctx: Context<AddDestination>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let allowed = &mut ctx.accounts.allowed_destination;
+        allowed.config = ctx.accounts.config.key();
+        allowed.destination = ctx.accounts.destination.key();
+        allowed.active_at = now.checked_add(DESTINATION_DELAY).ok_or(ErrorCode::ArithmeticOverflow)?;
+        allowed.bump = ctx.bumps.allowed_destination;
+
+        // SECURITY: Announced two days before the destination can be paid
+        emit!(DestinationAdded {
+            config: allowed.config,
+            destination: allowed.destination,
+            active_at: allowed.active_at,
+        });
+
+        msg!("Destination {} allowlisted from {}", allowed.destination, allowed.active_at);
+        Ok(())
+    }
+
+    /// SECURE: Remove a sweep destination from the allowlist, at once
+    ///
+    /// Removal only narrows where the treasury can go, so it needs no delay:
+    /// the defence against a destination added with a stolen key.
+    #[secure]
+    pub fn remove_destination(ctx: Context<RemoveDestination>) -> Result<()> {
+        msg!("Destination {} removed", ctx.accounts.allowed_destination.destination);
+        Ok(())
+    }
+
+    /// SECURE: Admin sweep of the treasury, signed by the treasury's authority
+    ///
+    /// Security Fix: The signature is derived from the treasury vault, so the
+    /// token program rejects it for any other vault's token account. `source`
+    /// is deliberately left as loosely checked as in the vulnerable sweep to
+    /// show the containment: the same bug can now reach the treasury and
+    /// nothing else. Production code should also constrain `source`.
+    ///
+    /// The destination must have been allowlisted at least DESTINATION_DELAY
+    /// ago, so an admin key alone cannot send the treasury anywhere new.
+    #[secure]
+    pub fn secure_sweep(ctx: Context<SecureSweep>, amount: u64) -> Result<()> {
+        // SECURITY: Allowlisted destinations only, once their delay is over
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= ctx.accounts.allowed_destination.active_at, ErrorCode::DestinationNotActive);
+
+        let treasury_key = ctx.accounts.treasury.key();
+        // SECURITY: The blast radius of this instruction is the treasury alone
+        let seeds: &[&[u8]] = &[b"vault_authority", treasury_key.as_ref(), &[ctx.accounts.treasury.authority_bump]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.source.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.treasury_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        msg!("Swept {} tokens from {}", amount, ctx.accounts.source.key());
+        Ok(())
+    }
+}
+
+// ========================================
+// EVENTS
+// ========================================
+
+/// A sweep destination was allowlisted, and can be paid from `active_at`
+#[event]
+pub struct DestinationAdded {
+    pub config: Pubkey,
+    pub destination: Pubkey,
+    pub active_at: i64,
+}
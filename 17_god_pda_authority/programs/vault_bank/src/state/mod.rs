@@ -0,0 +1,35 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Config {
+    /// The authority allowed to sweep the treasury (32 bytes)
+    pub admin: Pubkey,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Vault {
+    /// The wallet allowed to withdraw (32 bytes)
+    pub owner: Pubkey,
+    /// The mint this vault holds (32 bytes)
+    pub mint: Pubkey,
+    /// Bump of the PDA that signs for the vault's tokens (1 byte)
+    pub authority_bump: u8,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct AllowedDestination {
+    /// The config whose admin allowlisted this destination (32 bytes)
+    pub config: Pubkey,
+    /// The token account the treasury may be swept to (32 bytes)
+    pub destination: Pubkey,
+    /// Unix timestamp from which sweeps may pay the destination (8 bytes)
+    pub active_at: i64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
@@ -0,0 +1,13 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("A flash loan is already outstanding")]
+    LoanOutstanding,
+    #[msg("No matching flash_repay later in the transaction")]
+    MissingRepayment,
+    #[msg("Repayment is smaller than the loan")]
+    InsufficientRepayment,
+}
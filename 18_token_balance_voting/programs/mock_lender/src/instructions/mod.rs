@@ -0,0 +1,80 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 8 + 1, // discriminator + mint + outstanding + bump
+        seeds = [b"pool", mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"pool_tokens", pool.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = pool
+    )]
+    pub pool_tokens: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundPool<'info> {
+    #[account(seeds = [b"pool", pool.mint.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut, seeds = [b"pool_tokens", pool.key().as_ref()], bump)]
+    pub pool_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = pool.mint, token::authority = funder)]
+    pub funder_tokens: Account<'info, TokenAccount>,
+
+    pub funder: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct FlashBorrow<'info> {
+    #[account(mut, seeds = [b"pool", pool.mint.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut, seeds = [b"pool_tokens", pool.key().as_ref()], bump)]
+    pub pool_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = pool.mint)]
+    pub borrower_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: Address is checked against the instructions sysvar id
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct FlashRepay<'info> {
+    #[account(mut, seeds = [b"pool", pool.mint.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut, seeds = [b"pool_tokens", pool.key().as_ref()], bump)]
+    pub pool_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = pool.mint, token::authority = borrower)]
+    pub borrower_tokens: Account<'info, TokenAccount>,
+
+    pub borrower: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,16 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::Pool;
+
+#[test]
+fn pool_layout_is_stable() {
+    let pool = Pool {
+        mint: Pubkey::new_from_array([1; 32]),
+        outstanding: 0x2122232425262728,
+        bump: 49,
+    };
+    assert_golden!("Pool", golden::anchor(&pool));
+}
@@ -0,0 +1,126 @@
+//! A minimal flash-loan pool used by the exploit tests.
+//!
+//! `flash_borrow` hands out tokens as long as a matching `flash_repay` for
+//! the same pool appears later in the same transaction. There are no fees
+//! and no interest - just enough to show that anything a program reads from
+//! a token balance mid-transaction can be borrowed.
+
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_lang::Discriminator;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use secref_prelude::*;
+
+declare_id!("MockLender111111111111111111111111111111111");
+
+pub mod errors;
+pub mod instructions;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+#[program]
+pub mod mock_lender {
+    use super::*;
+
+    /// Create a lending pool for one mint
+    pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        pool.mint = ctx.accounts.mint.key();
+        pool.outstanding = 0;
+        pool.bump = ctx.bumps.pool;
+
+        msg!("Pool initialized for mint: {}", pool.mint);
+        Ok(())
+    }
+
+    /// Add liquidity to the pool
+    pub fn fund_pool(ctx: Context<FundPool>, amount: u64) -> Result<()> {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.funder_tokens.to_account_info(),
+                    to: ctx.accounts.pool_tokens.to_account_info(),
+                    authority: ctx.accounts.funder.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Pool funded with {} tokens", amount);
+        Ok(())
+    }
+
+    /// Lend `amount` tokens that must be repaid later in this transaction
+    pub fn flash_borrow(ctx: Context<FlashBorrow>, amount: u64) -> Result<()> {
+        require!(ctx.accounts.pool.outstanding == 0, ErrorCode::LoanOutstanding);
+        require_repayment(&ctx.accounts.instructions, &ctx.accounts.pool.key(), amount)?;
+
+        let mint = ctx.accounts.pool.mint;
+        let seeds: &[&[u8]] = &[b"pool", mint.as_ref(), &[ctx.accounts.pool.bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.pool_tokens.to_account_info(),
+                    to: ctx.accounts.borrower_tokens.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        ctx.accounts.pool.outstanding = amount;
+        msg!("Flash loan of {} tokens issued", amount);
+        Ok(())
+    }
+
+    /// Repay the outstanding flash loan
+    pub fn flash_repay(ctx: Context<FlashRepay>, amount: u64) -> Result<()> {
+        require!(amount >= ctx.accounts.pool.outstanding, ErrorCode::InsufficientRepayment);
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.borrower_tokens.to_account_info(),
+                    to: ctx.accounts.pool_tokens.to_account_info(),
+                    authority: ctx.accounts.borrower.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        ctx.accounts.pool.outstanding = 0;
+        msg!("Flash loan of {} tokens repaid", amount);
+        Ok(())
+    }
+}
+
+/// Require a later `flash_repay` of at least `amount` to the same pool
+fn require_repayment(instructions: &AccountInfo, pool: &Pubkey, amount: u64) -> Result<()> {
+    let mut index = load_current_index_checked(instructions)? as usize + 1;
+
+    while let Ok(ix) = load_instruction_at_checked(index, instructions) {
+        let is_repay = ix.program_id == crate::ID
+            && ix.data.len() >= 16
+            && ix.data[..8] == instruction::FlashRepay::DISCRIMINATOR
+            && ix.accounts.first().map(|meta| meta.pubkey) == Some(*pool);
+
+        if is_repay {
+            let mut repaid = [0u8; 8];
+            repaid.copy_from_slice(&ix.data[8..16]);
+            require!(u64::from_le_bytes(repaid) >= amount, ErrorCode::InsufficientRepayment);
+            return Ok(());
+        }
+        index += 1;
+    }
+
+    err!(ErrorCode::MissingRepayment)
+}
@@ -0,0 +1,13 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Pool {
+    /// The mint this pool lends (32 bytes)
+    pub mint: Pubkey,
+    /// Amount lent by the flash loan in progress, if any (8 bytes)
+    pub outstanding: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
@@ -0,0 +1,196 @@
+//! Every constraint on the escrow-based contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::runtime::account_already_in_use;
+use secref_testkit::{
+    assert_constraint_violation, assert_seeds_violation, assert_signer_violation, Fixture, TestAccount,
+};
+
+use crate::{Escrow, LockTokens, Proposal, SecureCastVote, UnlockTokens, VoteRecord};
+
+const PROPOSAL_ID: u64 = 7;
+
+fn pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &crate::ID)
+}
+
+fn proposal_account(mint: Pubkey) -> TestAccount {
+    let (address, bump) = pda(&[b"proposal", PROPOSAL_ID.to_le_bytes().as_ref()]);
+    TestAccount::anchor(&Proposal {
+        id: PROPOSAL_ID,
+        mint,
+        yes_votes: 0,
+        no_votes: 0,
+        voting_ends_at: 0,
+        bump,
+    })
+    .at(address)
+}
+
+fn escrow_address(proposal: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+    pda(&[b"escrow", proposal.as_ref(), voter.as_ref()])
+}
+
+fn escrow_account(proposal: Pubkey, voter: Pubkey) -> TestAccount {
+    let (address, bump) = escrow_address(&proposal, &voter);
+    TestAccount::anchor(&Escrow {
+        proposal,
+        voter,
+        amount: 1_000,
+        bump,
+    })
+    .at(address)
+}
+
+fn lock_fixture() -> Fixture {
+    let voter = TestAccount::signer();
+    let mint = TestAccount::mint(Pubkey::new_unique(), 6, 1_000_000);
+    let proposal = proposal_account(mint.key);
+    let (escrow, _) = escrow_address(&proposal.key, &voter.key);
+    let (escrow_tokens, _) = pda(&[b"escrow_tokens", escrow.as_ref()]);
+    Fixture::new()
+        .with("proposal", proposal)
+        .with("escrow", TestAccount::uninitialized(8 + 32 + 32 + 8 + 1).at(escrow))
+        .with("escrow_tokens", TestAccount::uninitialized(165).at(escrow_tokens))
+        .with("voter_tokens", TestAccount::token_account(mint.key, voter.key, 1_000))
+        .with("mint", mint)
+        .with("voter", voter)
+        .with("token_program", TestAccount::token_program())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn cast_fixture() -> Fixture {
+    let voter = TestAccount::signer();
+    let proposal = proposal_account(Pubkey::new_unique());
+    let (vote_record, _) = pda(&[b"vote", proposal.key.as_ref(), voter.key.as_ref()]);
+    let escrow = escrow_account(proposal.key, voter.key);
+    Fixture::new()
+        .with("proposal", proposal)
+        .with(
+            "vote_record",
+            TestAccount::uninitialized(8 + 32 + 8 + 1 + 1).at(vote_record),
+        )
+        .with("escrow", escrow)
+        .with("voter", voter)
+        .with("system_program", TestAccount::system_program())
+}
+
+fn unlock_fixture() -> Fixture {
+    let voter = TestAccount::signer();
+    let mint = Pubkey::new_unique();
+    let proposal = proposal_account(mint);
+    let escrow = escrow_account(proposal.key, voter.key);
+    let (escrow_tokens, _) = pda(&[b"escrow_tokens", escrow.key.as_ref()]);
+    Fixture::new()
+        .with("proposal", proposal)
+        .with("escrow", escrow.clone())
+        .with(
+            "escrow_tokens",
+            TestAccount::token_account(mint, escrow.key, 1_000).at(escrow_tokens),
+        )
+        .with("voter_tokens", TestAccount::token_account(mint, voter.key, 0))
+        .with("voter", voter)
+        .with("token_program", TestAccount::token_program())
+}
+
+/// The voter's own tokens, but of another mint
+fn other_mint_tokens(fixture: &Fixture) -> TestAccount {
+    TestAccount::token_account(Pubkey::new_unique(), fixture.key("voter"), 1_000)
+}
+
+#[test]
+fn lock_tokens_escrows_under_the_voters_pdas() {
+    assert_seeds_violation!(LockTokens, lock_fixture(), "proposal");
+    assert_seeds_violation!(LockTokens, lock_fixture(), "escrow");
+    assert_seeds_violation!(LockTokens, lock_fixture(), "escrow_tokens");
+    assert_signer_violation!(LockTokens, lock_fixture(), "voter");
+}
+
+#[test]
+fn lock_tokens_takes_the_proposals_token_from_the_voter() {
+    let fixture = lock_fixture();
+    let other_mint = other_mint_tokens(&fixture);
+    assert_constraint_violation!(LockTokens, fixture, "voter_tokens" => other_mint, ErrorCode::ConstraintTokenMint);
+    let fixture = lock_fixture();
+    let borrowed = TestAccount::token_account(fixture.key("mint"), Pubkey::new_unique(), 1_000);
+    assert_constraint_violation!(LockTokens, fixture, "voter_tokens" => borrowed, ErrorCode::ConstraintTokenOwner);
+    assert_constraint_violation!(
+        LockTokens,
+        lock_fixture(),
+        "mint" => |mint| *mint = mint.clone().rekeyed(),
+        ErrorCode::ConstraintAddress
+    );
+}
+
+#[test]
+fn lock_tokens_rejects_an_existing_escrow() {
+    assert_constraint_violation!(
+        LockTokens,
+        lock_fixture(),
+        "escrow" => |escrow| *escrow = TestAccount::anchor(&Escrow {
+            proposal: Pubkey::new_unique(),
+            voter: Pubkey::new_unique(),
+            amount: 0,
+            bump: 0,
+        })
+        .at(escrow.key),
+        account_already_in_use()
+    );
+}
+
+#[test]
+fn secure_cast_vote_records_one_vote_per_voter() {
+    assert_seeds_violation!(SecureCastVote, cast_fixture(), "proposal");
+    assert_seeds_violation!(SecureCastVote, cast_fixture(), "vote_record");
+    assert_constraint_violation!(
+        SecureCastVote,
+        cast_fixture(),
+        "vote_record" => |record| *record = TestAccount::anchor(&VoteRecord {
+            voter: Pubkey::new_unique(),
+            weight: 1_000,
+            support: true,
+            bump: 0,
+        })
+        .at(record.key),
+        account_already_in_use()
+    );
+    assert_signer_violation!(SecureCastVote, cast_fixture(), "voter");
+}
+
+#[test]
+fn secure_cast_vote_weighs_the_voters_escrow_for_this_proposal() {
+    assert_seeds_violation!(SecureCastVote, cast_fixture(), "escrow");
+    assert_constraint_violation!(
+        SecureCastVote,
+        cast_fixture(),
+        "escrow" => |escrow| escrow.edit(|stored: &mut Escrow| stored.proposal = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_constraint_violation!(
+        SecureCastVote,
+        cast_fixture(),
+        "escrow" => |escrow| escrow.edit(|stored: &mut Escrow| stored.voter = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+}
+
+#[test]
+fn unlock_tokens_releases_only_the_voters_escrow() {
+    assert_seeds_violation!(UnlockTokens, unlock_fixture(), "proposal");
+    assert_seeds_violation!(UnlockTokens, unlock_fixture(), "escrow");
+    assert_seeds_violation!(UnlockTokens, unlock_fixture(), "escrow_tokens");
+    assert_constraint_violation!(
+        UnlockTokens,
+        unlock_fixture(),
+        "escrow" => |escrow| escrow.edit(|stored: &mut Escrow| stored.voter = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_signer_violation!(UnlockTokens, unlock_fixture(), "voter");
+}
+
+#[test]
+fn unlock_tokens_returns_the_proposals_token_to_the_voter() {
+    let fixture = unlock_fixture();
+    let other_mint = other_mint_tokens(&fixture);
+    assert_constraint_violation!(UnlockTokens, fixture, "voter_tokens" => other_mint, ErrorCode::ConstraintTokenMint);
+}
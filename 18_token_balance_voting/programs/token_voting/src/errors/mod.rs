@@ -0,0 +1,19 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Voting period must be positive")]
+    InvalidVotingPeriod,
+    #[msg("Voting has closed for this proposal")]
+    VotingClosed,
+    #[msg("Voting is still open; escrowed tokens are locked")]
+    VotingStillOpen,
+    #[msg("Voter has no voting power")]
+    NoVotingPower,
+    #[msg("Cannot lock zero tokens")]
+    NothingToLock,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
@@ -0,0 +1,36 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use anchor_spl::token::{Mint, TokenAccount};
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CreateProposal<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + 8 + 32 + 8 + 8 + 8 + 1, // discriminator + id + mint + yes_votes + no_votes + voting_ends_at + bump
+        seeds = [b"proposal", id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
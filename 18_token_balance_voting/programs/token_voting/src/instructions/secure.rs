@@ -0,0 +1,96 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use anchor_spl::token::Token;
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct LockTokens<'info> {
+    #[account(seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + 32 + 32 + 8 + 1, // discriminator + proposal + voter + amount + bump
+        seeds = [b"escrow", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    // SECURITY: Tokens are held by the escrow PDA, not the voter
+    #[account(
+        init,
+        payer = voter,
+        seeds = [b"escrow_tokens", escrow.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = escrow
+    )]
+    pub escrow_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = proposal.mint, token::authority = voter)]
+    pub voter_tokens: Account<'info, TokenAccount>,
+
+    #[account(address = proposal.mint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SecureCastVote<'info> {
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + 32 + 8 + 1 + 1, // discriminator + voter + weight + support + bump
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    // SECURITY: Weight comes from this voter's escrow for this proposal
+    #[account(
+        has_one = proposal,
+        has_one = voter,
+        seeds = [b"escrow", proposal.key().as_ref(), voter.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnlockTokens<'info> {
+    #[account(seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        close = voter,
+        has_one = proposal,
+        has_one = voter,
+        seeds = [b"escrow", proposal.key().as_ref(), voter.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut, seeds = [b"escrow_tokens", escrow.key().as_ref()], bump)]
+    pub escrow_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = proposal.mint, token::authority = voter)]
+    pub voter_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
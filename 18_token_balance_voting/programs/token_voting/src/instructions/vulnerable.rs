@@ -0,0 +1,26 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableCastVote<'info> {
+    #[account(mut, seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + 32 + 8 + 1 + 1, // discriminator + voter + weight + support + bump
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    // VULNERABILITY: The live balance of this account is the voting weight
+    #[account(token::mint = proposal.mint, token::authority = voter)]
+    pub voter_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
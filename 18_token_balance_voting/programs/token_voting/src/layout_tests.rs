@@ -0,0 +1,41 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Escrow, Proposal, VoteRecord};
+
+#[test]
+fn proposal_layout_is_stable() {
+    let proposal = Proposal {
+        id: 0x1112131415161718,
+        mint: Pubkey::new_from_array([2; 32]),
+        yes_votes: 0x3132333435363738,
+        no_votes: 0x4142434445464748,
+        voting_ends_at: 0x5152535455565758,
+        bump: 97,
+    };
+    assert_golden!("Proposal", golden::anchor(&proposal));
+}
+
+#[test]
+fn vote_record_layout_is_stable() {
+    let vote_record = VoteRecord {
+        voter: Pubkey::new_from_array([7; 32]),
+        weight: 0x8182838485868788,
+        support: true,
+        bump: 145,
+    };
+    assert_golden!("VoteRecord", golden::anchor(&vote_record));
+}
+
+#[test]
+fn escrow_layout_is_stable() {
+    let escrow = Escrow {
+        proposal: Pubkey::new_from_array([10; 32]),
+        voter: Pubkey::new_from_array([11; 32]),
+        amount: 0xc1c2c3c4c5c6c7c8,
+        bump: 209,
+    };
+    assert_golden!("Escrow", golden::anchor(&escrow));
+}
@@ -0,0 +1,200 @@
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+#[feature_gated]
+#[program]
+pub mod token_voting {
+    use super::*;
+    #[secure]
+    use anchor_spl::token::{self, CloseAccount, Transfer};
+
+    /// Open a proposal weighted by holdings of `mint`
+    pub fn create_proposal(ctx: Context<CreateProposal>, id: u64, voting_period: i64) -> Result<()> {
+        require!(voting_period > 0, ErrorCode::InvalidVotingPeriod);
+
+        let now = Clock::get()?.unix_timestamp;
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.id = id;
+        proposal.mint = ctx.accounts.mint.key();
+        proposal.yes_votes = 0;
+        proposal.no_votes = 0;
+        proposal.voting_ends_at = now.checked_add(voting_period).ok_or(ErrorCode::ArithmeticOverflow)?;
+        proposal.bump = ctx.bumps.proposal;
+
+        msg!("Proposal {} open until {}", id, proposal.voting_ends_at);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Weigh a vote by the voter's live token balance
+    ///
+    /// Security Issue: `voter_tokens.amount` is whatever the account holds at
+    /// this instruction - not what the voter owns, and not what they will
+    /// hold when the vote is over. An attacker can flash-borrow the supply,
+    /// vote, and repay in one transaction, or vote from one wallet, move the
+    /// tokens to a second wallet and vote again. The vote record only stops
+    /// the same wallet voting twice.
+    #[vulnerable]
+    pub fn vulnerable_cast_vote(ctx: Context<VulnerableCastVote>, support: bool) -> Result<()> {
+        require_voting_open(&ctx.accounts.proposal)?;
+
+        // VULNERABILITY: Weight is a balance that can be borrowed or recycled
+        let weight = ctx.accounts.voter_tokens.amount;
+
+        record_vote(
+            &mut ctx.accounts.proposal,
+            &mut ctx.accounts.vote_record,
+            ctx.accounts.voter.key(),
+            weight,
+            support,
+            ctx.bumps.vote_record,
+        )
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version of the same instruction
+    // with voting weight backed by tokens escrowed for the voting period.
+
+    /// SECURE: Lock tokens in an escrow PDA until voting ends
+    ///
+    /// Security Fix: Tokens leave the voter's wallet for an account only
+    /// this program can move, and `unlock_tokens` refuses to return them
+    /// before `voting_ends_at`. Borrowed tokens cannot be repaid in the
+    /// same transaction, so a flash loan that locks them always fails.
+    #[secure]
+    pub fn lock_tokens(ctx: Context<LockTokens>, amount: u64) -> Result<()> {
+        require_voting_open(&ctx.accounts.proposal)?;
+        require!(amount > 0, ErrorCode::NothingToLock);
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.voter_tokens.to_account_info(),
+                    to: ctx.accounts.escrow_tokens.to_account_info(),
+                    authority: ctx.accounts.voter.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.proposal = ctx.accounts.proposal.key();
+        escrow.voter = ctx.accounts.voter.key();
+        escrow.amount = amount;
+        escrow.bump = ctx.bumps.escrow;
+
+        msg!("Locked {} tokens until {}", amount, ctx.accounts.proposal.voting_ends_at);
+        Ok(())
+    }
+
+    /// SECURE: Weigh a vote by the voter's escrowed tokens
+    #[secure]
+    pub fn secure_cast_vote(ctx: Context<SecureCastVote>, support: bool) -> Result<()> {
+        require_voting_open(&ctx.accounts.proposal)?;
+
+        // SECURITY: Weight is capital locked for the rest of the vote
+        let weight = ctx.accounts.escrow.amount;
+
+        record_vote(
+            &mut ctx.accounts.proposal,
+            &mut ctx.accounts.vote_record,
+            ctx.accounts.voter.key(),
+            weight,
+            support,
+            ctx.bumps.vote_record,
+        )
+    }
+
+    /// SECURE: Return escrowed tokens once voting has ended
+    #[secure]
+    pub fn unlock_tokens(ctx: Context<UnlockTokens>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        // SECURITY: Tokens stay locked for the whole voting period
+        require!(now >= ctx.accounts.proposal.voting_ends_at, ErrorCode::VotingStillOpen);
+
+        let escrow = &ctx.accounts.escrow;
+        let proposal_key = ctx.accounts.proposal.key();
+        let voter_key = ctx.accounts.voter.key();
+        let seeds: &[&[u8]] = &[b"escrow", proposal_key.as_ref(), voter_key.as_ref(), &[escrow.bump]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.escrow_tokens.to_account_info(),
+                    to: ctx.accounts.voter_tokens.to_account_info(),
+                    authority: ctx.accounts.escrow.to_account_info(),
+                },
+                &[seeds],
+            ),
+            escrow.amount,
+        )?;
+
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow_tokens.to_account_info(),
+                destination: ctx.accounts.voter.to_account_info(),
+                authority: ctx.accounts.escrow.to_account_info(),
+            },
+            &[seeds],
+        ))?;
+
+        msg!("Unlocked {} tokens", escrow.amount);
+        Ok(())
+    }
+}
+
+/// Require the proposal's voting period to still be running
+fn require_voting_open(proposal: &Proposal) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < proposal.voting_ends_at, ErrorCode::VotingClosed);
+    Ok(())
+}
+
+/// Add `weight` to the chosen side and fill in the voter's record
+fn record_vote(
+    proposal: &mut Proposal,
+    vote_record: &mut VoteRecord,
+    voter: Pubkey,
+    weight: u64,
+    support: bool,
+    bump: u8,
+) -> Result<()> {
+    require!(weight > 0, ErrorCode::NoVotingPower);
+
+    let tally = if support { &mut proposal.yes_votes } else { &mut proposal.no_votes };
+    *tally = tally.checked_add(weight).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    vote_record.voter = voter;
+    vote_record.weight = weight;
+    vote_record.support = support;
+    vote_record.bump = bump;
+
+    msg!("Vote recorded: {} with weight {}", if support { "yes" } else { "no" }, weight);
+    Ok(())
+}
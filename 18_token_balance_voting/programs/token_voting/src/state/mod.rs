@@ -0,0 +1,43 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Proposal {
+    /// Caller-chosen proposal number (8 bytes)
+    pub id: u64,
+    /// The governance token whose holders vote (32 bytes)
+    pub mint: Pubkey,
+    /// Weight voting in favour (8 bytes)
+    pub yes_votes: u64,
+    /// Weight voting against (8 bytes)
+    pub no_votes: u64,
+    /// Unix timestamp at which voting closes (8 bytes)
+    pub voting_ends_at: i64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct VoteRecord {
+    /// The wallet that voted (32 bytes)
+    pub voter: Pubkey,
+    /// Weight counted for this vote (8 bytes)
+    pub weight: u64,
+    /// Whether the vote was in favour (1 byte)
+    pub support: bool,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Escrow {
+    /// The proposal these tokens are locked for (32 bytes)
+    pub proposal: Pubkey,
+    /// The wallet the tokens are returned to (32 bytes)
+    pub voter: Pubkey,
+    /// Tokens locked, and the voter's weight (8 bytes)
+    pub amount: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
@@ -0,0 +1,107 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_constraint_violation, assert_seeds_violation, assert_signer_violation, Fixture, TestAccount,
+};
+
+use crate::{Pool, SecureAddLiquidity, SecureRemoveLiquidity};
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &crate::ID).0
+}
+
+fn liquidity_fixture(remove: bool) -> Fixture {
+    let provider = TestAccount::signer();
+    let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let (address, bump) = Pubkey::find_program_address(&[b"pool", mint_a.as_ref(), mint_b.as_ref()], &crate::ID);
+    let pool = TestAccount::anchor(&Pool {
+        mint_a,
+        mint_b,
+        reserve_a: 1_000,
+        reserve_b: 1_000,
+        bump,
+    })
+    .at(address);
+    let lp_mint = TestAccount::mint(address, 6, 1_000).at(pda(&[b"lp_mint", address.as_ref()]));
+    let fixture = Fixture::new()
+        .with("pool", pool)
+        .with(
+            "vault_a",
+            TestAccount::token_account(mint_a, address, 1_000).at(pda(&[b"vault_a", address.as_ref()])),
+        )
+        .with(
+            "vault_b",
+            TestAccount::token_account(mint_b, address, 1_000).at(pda(&[b"vault_b", address.as_ref()])),
+        )
+        .with("lp_mint", lp_mint.clone())
+        .with(
+            "provider_lp",
+            TestAccount::token_account(lp_mint.key, provider.key, 100),
+        );
+    let fixture = if remove {
+        fixture
+            .with("provider_a", TestAccount::token_account(mint_a, provider.key, 0))
+            .with("provider_b", TestAccount::token_account(mint_b, provider.key, 0))
+    } else {
+        fixture
+    };
+    fixture
+        .with("provider", provider)
+        .with("token_program", TestAccount::token_program())
+}
+
+/// The provider's own tokens, but of another mint
+fn other_mint_tokens(fixture: &Fixture) -> TestAccount {
+    TestAccount::token_account(Pubkey::new_unique(), fixture.key("provider"), 0)
+}
+
+#[test]
+fn secure_add_liquidity_measures_the_pools_own_vaults() {
+    assert_seeds_violation!(SecureAddLiquidity, liquidity_fixture(false), "pool");
+    assert_seeds_violation!(SecureAddLiquidity, liquidity_fixture(false), "vault_a");
+    assert_seeds_violation!(SecureAddLiquidity, liquidity_fixture(false), "vault_b");
+    assert_seeds_violation!(SecureAddLiquidity, liquidity_fixture(false), "lp_mint");
+}
+
+#[test]
+fn secure_add_liquidity_mints_the_pools_lp_token() {
+    let fixture = liquidity_fixture(false);
+    let other_mint = other_mint_tokens(&fixture);
+    assert_constraint_violation!(SecureAddLiquidity, fixture, "provider_lp" => other_mint, ErrorCode::ConstraintTokenMint);
+    assert_signer_violation!(SecureAddLiquidity, liquidity_fixture(false), "provider");
+}
+
+#[test]
+fn secure_remove_liquidity_pays_out_of_the_pools_own_vaults() {
+    assert_seeds_violation!(SecureRemoveLiquidity, liquidity_fixture(true), "pool");
+    assert_seeds_violation!(SecureRemoveLiquidity, liquidity_fixture(true), "vault_a");
+    assert_seeds_violation!(SecureRemoveLiquidity, liquidity_fixture(true), "vault_b");
+    assert_seeds_violation!(SecureRemoveLiquidity, liquidity_fixture(true), "lp_mint");
+}
+
+#[test]
+fn secure_remove_liquidity_burns_the_providers_lp_tokens() {
+    let fixture = liquidity_fixture(true);
+    let lp_mint = fixture.key("lp_mint");
+    assert_constraint_violation!(
+        SecureRemoveLiquidity,
+        fixture,
+        "provider_lp" => TestAccount::token_account(lp_mint, Pubkey::new_unique(), 100),
+        ErrorCode::ConstraintTokenOwner
+    );
+    let fixture = liquidity_fixture(true);
+    let other_mint = other_mint_tokens(&fixture);
+    assert_constraint_violation!(SecureRemoveLiquidity, fixture, "provider_lp" => other_mint, ErrorCode::ConstraintTokenMint);
+    assert_signer_violation!(SecureRemoveLiquidity, liquidity_fixture(true), "provider");
+}
+
+#[test]
+fn secure_remove_liquidity_pays_out_the_pools_mints() {
+    let fixture = liquidity_fixture(true);
+    let other_mint = other_mint_tokens(&fixture);
+    assert_constraint_violation!(SecureRemoveLiquidity, fixture, "provider_a" => other_mint, ErrorCode::ConstraintTokenMint);
+    let fixture = liquidity_fixture(true);
+    let other_mint = other_mint_tokens(&fixture);
+    assert_constraint_violation!(SecureRemoveLiquidity, fixture, "provider_b" => other_mint, ErrorCode::ConstraintTokenMint);
+}
@@ -0,0 +1,17 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Vault balance is below the recorded reserve")]
+    ReserveMismatch,
+    #[msg("Deposit is too small to mint any LP tokens")]
+    ZeroLiquidity,
+    #[msg("Burn exceeds the LP supply")]
+    InsufficientLiquidity,
+    #[msg("Output is below the caller's minimum")]
+    SlippageExceeded,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
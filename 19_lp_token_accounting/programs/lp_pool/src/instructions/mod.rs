@@ -0,0 +1,65 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 32 + 8 + 8 + 1, // discriminator + mint_a + mint_b + reserve_a + reserve_b + bump
+        seeds = [b"pool", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"vault_a", pool.key().as_ref()],
+        bump,
+        token::mint = mint_a,
+        token::authority = pool
+    )]
+    pub vault_a: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"vault_b", pool.key().as_ref()],
+        bump,
+        token::mint = mint_b,
+        token::authority = pool
+    )]
+    pub vault_b: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"lp_mint", pool.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = pool
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    pub mint_a: Account<'info, Mint>,
+    pub mint_b: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
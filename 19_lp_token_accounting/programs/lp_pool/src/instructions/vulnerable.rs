@@ -0,0 +1,46 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableAddLiquidity<'info> {
+    #[account(mut, seeds = [b"pool", pool.mint_a.as_ref(), pool.mint_b.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut, seeds = [b"lp_mint", pool.key().as_ref()], bump)]
+    pub lp_mint: Account<'info, Mint>,
+
+    #[account(mut, token::mint = lp_mint)]
+    pub provider_lp: Account<'info, TokenAccount>,
+
+    // VULNERABILITY: Neither vault is even read to confirm the deposit
+    pub provider: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableRemoveLiquidity<'info> {
+    #[account(mut, seeds = [b"pool", pool.mint_a.as_ref(), pool.mint_b.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut, seeds = [b"vault_a", pool.key().as_ref()], bump)]
+    pub vault_a: Account<'info, TokenAccount>,
+
+    #[account(mut, seeds = [b"vault_b", pool.key().as_ref()], bump)]
+    pub vault_b: Account<'info, TokenAccount>,
+
+    #[account(mut, seeds = [b"lp_mint", pool.key().as_ref()], bump)]
+    pub lp_mint: Account<'info, Mint>,
+
+    #[account(mut, token::mint = lp_mint, token::authority = provider)]
+    pub provider_lp: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = pool.mint_a)]
+    pub provider_a: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = pool.mint_b)]
+    pub provider_b: Account<'info, TokenAccount>,
+
+    pub provider: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,18 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::Pool;
+
+#[test]
+fn pool_layout_is_stable() {
+    let pool = Pool {
+        mint_a: Pubkey::new_from_array([1; 32]),
+        mint_b: Pubkey::new_from_array([2; 32]),
+        reserve_a: 0x3132333435363738,
+        reserve_b: 0x4142434445464748,
+        bump: 81,
+    };
+    assert_golden!("Pool", golden::anchor(&pool));
+}
@@ -0,0 +1,321 @@
+use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount, Transfer};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+#[feature_gated]
+#[program]
+pub mod lp_pool {
+    use super::*;
+
+    /// Create an empty constant-product pool for a token pair
+    ///
+    /// Liquidity is added in the "transfer, then sync" style: a provider
+    /// transfers tokens into the vaults and then calls an add-liquidity
+    /// instruction in the same transaction to be issued LP tokens.
+    pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        pool.mint_a = ctx.accounts.mint_a.key();
+        pool.mint_b = ctx.accounts.mint_b.key();
+        pool.reserve_a = 0;
+        pool.reserve_b = 0;
+        pool.bump = ctx.bumps.pool;
+
+        msg!("Pool initialized for {} / {}", pool.mint_a, pool.mint_b);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Mint LP tokens for the amounts the caller says they deposited
+    ///
+    /// Security Issue: `amount_a` and `amount_b` are never compared with what
+    /// actually arrived in the vaults. A caller can claim any deposit, be
+    /// minted the matching share of the pool, and have the claimed amounts
+    /// added to the recorded reserves. The share math is also done in u64,
+    /// so `amount * supply` overflows for large pools.
+    #[vulnerable]
+    pub fn vulnerable_add_liquidity(ctx: Context<VulnerableAddLiquidity>, amount_a: u64, amount_b: u64) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+        let supply = ctx.accounts.lp_mint.supply;
+
+        // VULNERABILITY: Shares come from instruction arguments, not measured deposits
+        let lp_amount = if supply == 0 {
+            amount_a.min(amount_b)
+        } else {
+            let share_a = amount_a.checked_mul(supply).ok_or(ErrorCode::ArithmeticOverflow)? / pool.reserve_a;
+            let share_b = amount_b.checked_mul(supply).ok_or(ErrorCode::ArithmeticOverflow)? / pool.reserve_b;
+            share_a.min(share_b)
+        };
+
+        mint_lp(&ctx.accounts.pool, &ctx.accounts.lp_mint, &ctx.accounts.provider_lp, &ctx.accounts.token_program, lp_amount)?;
+
+        let pool = &mut ctx.accounts.pool;
+        pool.reserve_a = pool.reserve_a.checked_add(amount_a).ok_or(ErrorCode::ArithmeticOverflow)?;
+        pool.reserve_b = pool.reserve_b.checked_add(amount_b).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Minted {} LP for claimed deposit {} / {}", lp_amount, amount_a, amount_b);
+        Ok(())
+    }
+
+    /// VULNERABLE: Burn LP tokens and pay out the amounts the caller asks for
+    ///
+    /// Security Issue: The payout is never tied to the share of supply being
+    /// burned. Burning a single LP token can withdraw the entire reserves,
+    /// leaving every other provider's LP tokens backed by nothing.
+    #[vulnerable]
+    pub fn vulnerable_remove_liquidity(
+        ctx: Context<VulnerableRemoveLiquidity>,
+        lp_amount: u64,
+        amount_a: u64,
+        amount_b: u64,
+    ) -> Result<()> {
+        burn_lp(&ctx.accounts.lp_mint, &ctx.accounts.provider_lp, &ctx.accounts.provider, &ctx.accounts.token_program, lp_amount)?;
+
+        // VULNERABILITY: Payout amounts are trusted, not derived from lp_amount / supply
+        pay_out(
+            &ctx.accounts.pool,
+            &ctx.accounts.vault_a,
+            &ctx.accounts.provider_a,
+            &ctx.accounts.token_program,
+            amount_a,
+        )?;
+        pay_out(
+            &ctx.accounts.pool,
+            &ctx.accounts.vault_b,
+            &ctx.accounts.provider_b,
+            &ctx.accounts.token_program,
+            amount_b,
+        )?;
+
+        let pool = &mut ctx.accounts.pool;
+        pool.reserve_a = pool.reserve_a.saturating_sub(amount_a);
+        pool.reserve_b = pool.reserve_b.saturating_sub(amount_b);
+
+        msg!("Burned {} LP for {} / {}", lp_amount, amount_a, amount_b);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version of the same instructions
+    // with shares derived from measured reserve deltas in u128 math.
+
+    /// SECURE: Mint LP tokens for the tokens that actually arrived
+    ///
+    /// Security Fix: The deposit is measured as `vault balance - recorded
+    /// reserve` for each side, so only tokens that really reached the vaults
+    /// count. The provider receives the smaller of the two proportional
+    /// shares (or the geometric mean for the first deposit), computed in
+    /// u128 so the intermediate product cannot overflow.
+    #[secure]
+    pub fn secure_add_liquidity(ctx: Context<SecureAddLiquidity>, min_lp: u64) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+
+        // SECURITY: Measure deposits from the vaults, never from arguments
+        let deposit_a = ctx
+            .accounts
+            .vault_a
+            .amount
+            .checked_sub(pool.reserve_a)
+            .ok_or(ErrorCode::ReserveMismatch)?;
+        let deposit_b = ctx
+            .accounts
+            .vault_b
+            .amount
+            .checked_sub(pool.reserve_b)
+            .ok_or(ErrorCode::ReserveMismatch)?;
+
+        let lp_amount = shares_for_deposit(
+            deposit_a,
+            deposit_b,
+            pool.reserve_a,
+            pool.reserve_b,
+            ctx.accounts.lp_mint.supply,
+        )?;
+        require!(lp_amount > 0, ErrorCode::ZeroLiquidity);
+        require!(lp_amount >= min_lp, ErrorCode::SlippageExceeded);
+
+        mint_lp(&ctx.accounts.pool, &ctx.accounts.lp_mint, &ctx.accounts.provider_lp, &ctx.accounts.token_program, lp_amount)?;
+
+        // SECURITY: Recorded reserves always match the vaults after a sync
+        let pool = &mut ctx.accounts.pool;
+        pool.reserve_a = ctx.accounts.vault_a.amount;
+        pool.reserve_b = ctx.accounts.vault_b.amount;
+
+        msg!("Minted {} LP for measured deposit {} / {}", lp_amount, deposit_a, deposit_b);
+        Ok(())
+    }
+
+    /// SECURE: Burn LP tokens for exactly their share of the reserves
+    ///
+    /// Security Fix: Each side pays `lp_amount * reserve / supply`, rounded
+    /// down in the pool's favour, and the caller can only set minimums for
+    /// slippage protection - never the amounts themselves.
+    #[secure]
+    pub fn secure_remove_liquidity(
+        ctx: Context<SecureRemoveLiquidity>,
+        lp_amount: u64,
+        min_a: u64,
+        min_b: u64,
+    ) -> Result<()> {
+        require!(lp_amount > 0, ErrorCode::ZeroLiquidity);
+
+        let supply = ctx.accounts.lp_mint.supply;
+        let pool = &ctx.accounts.pool;
+
+        // SECURITY: Payouts are the burned share of each reserve
+        let amount_a = share_of(lp_amount, pool.reserve_a, supply)?;
+        let amount_b = share_of(lp_amount, pool.reserve_b, supply)?;
+        require!(amount_a >= min_a, ErrorCode::SlippageExceeded);
+        require!(amount_b >= min_b, ErrorCode::SlippageExceeded);
+
+        burn_lp(&ctx.accounts.lp_mint, &ctx.accounts.provider_lp, &ctx.accounts.provider, &ctx.accounts.token_program, lp_amount)?;
+        pay_out(
+            &ctx.accounts.pool,
+            &ctx.accounts.vault_a,
+            &ctx.accounts.provider_a,
+            &ctx.accounts.token_program,
+            amount_a,
+        )?;
+        pay_out(
+            &ctx.accounts.pool,
+            &ctx.accounts.vault_b,
+            &ctx.accounts.provider_b,
+            &ctx.accounts.token_program,
+            amount_b,
+        )?;
+
+        let pool = &mut ctx.accounts.pool;
+        pool.reserve_a = pool.reserve_a.checked_sub(amount_a).ok_or(ErrorCode::ArithmeticOverflow)?;
+        pool.reserve_b = pool.reserve_b.checked_sub(amount_b).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Burned {} LP for {} / {}", lp_amount, amount_a, amount_b);
+        Ok(())
+    }
+}
+
+/// LP tokens owed for a measured deposit of `deposit_a` / `deposit_b`
+#[cfg(feature = "secure")]
+fn shares_for_deposit(deposit_a: u64, deposit_b: u64, reserve_a: u64, reserve_b: u64, supply: u64) -> Result<u64> {
+    let shares = if supply == 0 {
+        integer_sqrt(u128::from(deposit_a) * u128::from(deposit_b))
+    } else {
+        require!(reserve_a > 0, ErrorCode::ReserveMismatch);
+        require!(reserve_b > 0, ErrorCode::ReserveMismatch);
+        let share_a = u128::from(deposit_a) * u128::from(supply) / u128::from(reserve_a);
+        let share_b = u128::from(deposit_b) * u128::from(supply) / u128::from(reserve_b);
+        share_a.min(share_b)
+    };
+    u64::try_from(shares).map_err(|_| error!(ErrorCode::ArithmeticOverflow))
+}
+
+/// `lp_amount / supply` of `reserve`, rounded down
+#[cfg(feature = "secure")]
+fn share_of(lp_amount: u64, reserve: u64, supply: u64) -> Result<u64> {
+    require!(lp_amount <= supply, ErrorCode::InsufficientLiquidity);
+    let amount = u128::from(lp_amount) * u128::from(reserve) / u128::from(supply);
+    u64::try_from(amount).map_err(|_| error!(ErrorCode::ArithmeticOverflow))
+}
+
+/// Largest integer whose square does not exceed `value`
+#[cfg(feature = "secure")]
+fn integer_sqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// Mint `amount` LP tokens, signed by the pool
+fn mint_lp<'info>(
+    pool: &Account<'info, Pool>,
+    lp_mint: &Account<'info, Mint>,
+    destination: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[b"pool", pool.mint_a.as_ref(), pool.mint_b.as_ref(), &[pool.bump]];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: lp_mint.to_account_info(),
+                to: destination.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )
+}
+
+/// Burn `amount` LP tokens from the provider
+fn burn_lp<'info>(
+    lp_mint: &Account<'info, Mint>,
+    source: &Account<'info, TokenAccount>,
+    provider: &Signer<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    token::burn(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Burn {
+                mint: lp_mint.to_account_info(),
+                from: source.to_account_info(),
+                authority: provider.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
+
+/// Transfer `amount` out of a pool vault, signed by the pool
+fn pay_out<'info>(
+    pool: &Account<'info, Pool>,
+    vault: &Account<'info, TokenAccount>,
+    destination: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[b"pool", pool.mint_a.as_ref(), pool.mint_b.as_ref(), &[pool.bump]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to: destination.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )
+}
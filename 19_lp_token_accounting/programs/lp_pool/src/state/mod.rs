@@ -0,0 +1,17 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Pool {
+    /// First token of the pair (32 bytes)
+    pub mint_a: Pubkey,
+    /// Second token of the pair (32 bytes)
+    pub mint_b: Pubkey,
+    /// Token A the pool has accounted for (8 bytes)
+    pub reserve_a: u64,
+    /// Token B the pool has accounted for (8 bytes)
+    pub reserve_b: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
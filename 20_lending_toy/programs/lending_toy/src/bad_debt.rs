@@ -0,0 +1,78 @@
+//! Bad debt, and how lenders share it.
+//!
+//! A position whose collateral has all been seized, and which still owes,
+//! will never be repaid: its debt is bad, and the lenders who funded the
+//! loan have lost it. Writing the debt off takes it out of the market's
+//! loans, and the loss has to come out of the deposits too. Marking down
+//! every lender's position would touch every account in the market, so the
+//! market keeps one loss index - the share of each deposit still backed -
+//! and each position records the index its deposit was last settled at.
+
+/// Loss index of a market that has lost nothing
+pub const LOSS_INDEX_ONE: u128 = 1_000_000_000_000_000_000;
+
+/// What `deposited`, settled at `deposit_index`, is worth at `loss_index`, rounded down
+///
+/// The index only falls, so a deposit is never worth more than it was
+/// when it was settled.
+pub fn claim(deposited: u64, deposit_index: u128, loss_index: u128) -> u64 {
+    if deposit_index == 0 {
+        return 0;
+    }
+    (u128::from(deposited) * loss_index / deposit_index) as u64
+}
+
+// ========================================
+// SECURE IMPLEMENTATION
+// ========================================
+// This section contains the SECURE version: a write-off lowers the loss
+// index by the share of all deposits it is.
+
+/// SECURE: The loss index once `loss` of `total_deposits` is written off
+///
+/// Security Fix: Every deposit loses the same fraction, `loss /
+/// total_deposits`, however early or late its owner withdraws. The index
+/// is rounded down, so every claim rounds down with it and together they
+/// never come to more than the deposits left. `loss` must be less than
+/// `total_deposits`: an index of zero could never be written down again.
+pub fn written_down_index(loss_index: u128, total_deposits: u64, loss: u64) -> u128 {
+    loss_index * u128::from(total_deposits - loss) / u128::from(total_deposits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn a_write_off_marks_every_deposit_down_by_the_same_share() {
+        // 4,000 of 10,000 lost: each deposit keeps 60%
+        let index = written_down_index(LOSS_INDEX_ONE, 10_000, 4_000);
+        assert_eq!(index, LOSS_INDEX_ONE * 6 / 10);
+        assert_eq!(claim(5_000, LOSS_INDEX_ONE, index), 3_000);
+        assert_eq!(claim(1, LOSS_INDEX_ONE, index), 0);
+
+        // A deposit settled after the loss does not bear it
+        assert_eq!(claim(2_000, index, index), 2_000);
+        let later = written_down_index(index, 8_000, 2_000);
+        assert_eq!(claim(2_000, index, later), 1_500);
+        assert_eq!(claim(5_000, LOSS_INDEX_ONE, later), 2_250);
+    }
+
+    proptest! {
+        #[test]
+        fn claims_never_outrun_the_deposits_left(a in 1..u64::MAX / 2, b in 1..u64::MAX / 2, loss: u64) {
+            let total = a + b;
+            let loss = loss % total;
+            let index = written_down_index(LOSS_INDEX_ONE, total, loss);
+            let claims = u128::from(claim(a, LOSS_INDEX_ONE, index)) + u128::from(claim(b, LOSS_INDEX_ONE, index));
+            prop_assert!(claims <= u128::from(total - loss));
+        }
+
+        #[test]
+        fn claims_never_grow(deposited: u64, loss_index in 1..=LOSS_INDEX_ONE, fall in 0..=LOSS_INDEX_ONE) {
+            let later = loss_index * (LOSS_INDEX_ONE - fall) / LOSS_INDEX_ONE;
+            prop_assert!(claim(deposited, loss_index, later) <= deposited);
+        }
+    }
+}
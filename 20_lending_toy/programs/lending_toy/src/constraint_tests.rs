@@ -0,0 +1,140 @@
+//! Every constraint on the contexts the secure instructions use, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, Fixture,
+    TestAccount,
+};
+
+use crate::bad_debt::LOSS_INDEX_ONE;
+use crate::{AccrueInterest, Liquidate, Market, Position, UpdatePosition, WriteOffBadDebt};
+
+fn market_account() -> TestAccount {
+    TestAccount::anchor(&Market {
+        admin: Pubkey::new_unique(),
+        total_deposits: 1_000,
+        total_borrows: 500,
+        borrow_rate_bps: 500,
+        last_update_slot: 0,
+        loss_index: LOSS_INDEX_ONE,
+        bump: 0,
+    })
+}
+
+/// `owner`'s position in `market`
+fn position_account(market: Pubkey, owner: Pubkey, collateral: u64, borrowed: u64) -> TestAccount {
+    let (address, bump) = Pubkey::find_program_address(&[b"position", market.as_ref(), owner.as_ref()], &crate::ID);
+    TestAccount::anchor(&Position {
+        market,
+        owner,
+        deposited: 0,
+        collateral,
+        borrowed,
+        deposit_index: LOSS_INDEX_ONE,
+        bump,
+    })
+    .at(address)
+}
+
+fn update_fixture() -> Fixture {
+    let market = market_account();
+    let owner = TestAccount::signer();
+    let position = position_account(market.key, owner.key, 1_000, 500);
+    Fixture::new()
+        .with("market", market)
+        .with("position", position)
+        .with("owner", owner)
+}
+
+/// A liquidation of a position borrowing 900 against 1,000
+fn liquidate_fixture() -> Fixture {
+    let market = market_account();
+    let liquidator = TestAccount::signer();
+    let borrower_position = position_account(market.key, Pubkey::new_unique(), 1_000, 900);
+    let liquidator_position = position_account(market.key, liquidator.key, 0, 0);
+    Fixture::new()
+        .with("market", market)
+        .with("borrower_position", borrower_position)
+        .with("liquidator_position", liquidator_position)
+        .with("liquidator", liquidator)
+}
+
+/// A write-off by the market's admin of a position owing 300 with no collateral
+fn write_off_fixture() -> Fixture {
+    let admin = TestAccount::signer();
+    let mut market = market_account();
+    market.edit(|stored: &mut Market| stored.admin = admin.key);
+    let borrower_position = position_account(market.key, Pubkey::new_unique(), 0, 300);
+    Fixture::new()
+        .with("market", market)
+        .with("borrower_position", borrower_position)
+        .with("admin", admin)
+}
+
+#[test]
+fn accrue_interest_rejects_foreign_markets() {
+    assert_owner_violation!(
+        AccrueInterest,
+        Fixture::new().with("market", market_account()),
+        "market"
+    );
+}
+
+#[test]
+fn update_position_reads_the_owners_position_in_this_market() {
+    assert_owner_violation!(UpdatePosition, update_fixture(), "market");
+    assert_seeds_violation!(UpdatePosition, update_fixture(), "market");
+    assert_seeds_violation!(UpdatePosition, update_fixture(), "position");
+}
+
+#[test]
+fn update_position_requires_the_position_owner() {
+    assert_seeds_violation!(UpdatePosition, update_fixture(), "owner");
+    assert_signer_violation!(UpdatePosition, update_fixture(), "owner");
+    assert_constraint_violation!(
+        UpdatePosition,
+        update_fixture(),
+        "position" => |position| position.edit(|stored: &mut Position| stored.owner = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+}
+
+#[test]
+fn liquidate_reads_both_positions_in_this_market() {
+    assert_owner_violation!(Liquidate, liquidate_fixture(), "market");
+    assert_seeds_violation!(Liquidate, liquidate_fixture(), "market");
+    assert_seeds_violation!(Liquidate, liquidate_fixture(), "borrower_position");
+    assert_seeds_violation!(Liquidate, liquidate_fixture(), "liquidator_position");
+}
+
+#[test]
+fn liquidate_credits_the_signers_own_position() {
+    assert_seeds_violation!(Liquidate, liquidate_fixture(), "liquidator");
+    assert_signer_violation!(Liquidate, liquidate_fixture(), "liquidator");
+}
+
+#[test]
+fn liquidate_rejects_a_position_liquidated_into_itself() {
+    let fixture = liquidate_fixture();
+    let borrower_position = fixture.account("borrower_position").clone();
+    assert_constraint_violation!(
+        Liquidate,
+        fixture,
+        "liquidator_position" => borrower_position,
+        crate::ErrorCode::SelfLiquidation
+    );
+}
+
+#[test]
+fn only_the_markets_admin_writes_off_its_positions() {
+    assert_owner_violation!(WriteOffBadDebt, write_off_fixture(), "market");
+    assert_seeds_violation!(WriteOffBadDebt, write_off_fixture(), "market");
+    assert_seeds_violation!(WriteOffBadDebt, write_off_fixture(), "borrower_position");
+    assert_signer_violation!(WriteOffBadDebt, write_off_fixture(), "admin");
+    assert_constraint_violation!(
+        WriteOffBadDebt,
+        write_off_fixture(),
+        "market" => |market| market.edit(|stored: &mut Market| stored.admin = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+}
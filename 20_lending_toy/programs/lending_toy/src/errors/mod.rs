@@ -0,0 +1,29 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Insufficient balance in position")]
+    InsufficientBalance,
+    #[msg("Insufficient liquidity in market")]
+    InsufficientLiquidity,
+    #[msg("Loan would exceed the maximum loan-to-value")]
+    ExceedsLtv,
+    #[msg("Position would be undercollateralized")]
+    Unhealthy,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+    #[msg("Position is healthy and cannot be liquidated")]
+    Healthy,
+    #[msg("Repayment exceeds what one liquidation may repay")]
+    ExceedsCloseFactor,
+    #[msg("Liquidation would repay nothing")]
+    NothingToLiquidate,
+    #[msg("A position cannot be liquidated into itself")]
+    SelfLiquidation,
+    #[msg("Only a position with debt and no collateral can be written off")]
+    NotBadDebt,
+    #[msg("Bad debt would wipe out every deposit")]
+    LossExceedsDeposits,
+}
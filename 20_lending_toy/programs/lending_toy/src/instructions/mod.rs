@@ -0,0 +1,105 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct InitializeMarket<'info> {
+    #[account(
+        init,
+        payer = admin,
+        // discriminator + admin + total_deposits + total_borrows + borrow_rate_bps + last_update_slot + loss_index + bump
+        space = 8 + 32 + 8 + 8 + 8 + 8 + 16 + 1,
+        seeds = [b"market", admin.key().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = owner,
+        // discriminator + market + owner + deposited + collateral + borrowed + deposit_index + bump
+        space = 8 + 32 + 32 + 8 + 8 + 8 + 16 + 1,
+        seeds = [b"position", market.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePosition<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner
+    )]
+    pub position: Account<'info, Position>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AccrueInterest<'info> {
+    /// Anyone may crank interest accrual
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+}
+
+#[derive(Accounts)]
+pub struct Liquidate<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// Anyone may liquidate any unhealthy position in the market
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), borrower_position.owner.as_ref()],
+        bump = borrower_position.bump,
+        constraint = borrower_position.key() != liquidator_position.key() @ ErrorCode::SelfLiquidation
+    )]
+    pub borrower_position: Account<'info, Position>,
+
+    /// Receives the seized collateral
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), liquidator.key().as_ref()],
+        bump = liquidator_position.bump
+    )]
+    pub liquidator_position: Account<'info, Position>,
+
+    pub liquidator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WriteOffBadDebt<'info> {
+    #[account(mut, has_one = admin)]
+    pub market: Account<'info, Market>,
+
+    /// A position in the market with debt and no collateral left
+    #[account(
+        mut,
+        seeds = [b"position", market.key().as_ref(), borrower_position.owner.as_ref()],
+        bump = borrower_position.bump
+    )]
+    pub borrower_position: Account<'info, Position>,
+
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,200 @@
+//! Utilization-based borrow rate model.
+//!
+//! The rate follows the usual kinked curve: it rises gently up to
+//! `OPTIMAL_UTILIZATION_BPS`, then steeply up to 100% so that lenders can
+//! always withdraw. The two boundaries of that curve - an empty market and
+//! a fully borrowed one - are where naive implementations break.
+
+/// Basis points in 100%
+pub const BPS: u64 = 10_000;
+/// Borrow rate at 0% utilization
+pub const BASE_RATE_BPS: u64 = 150;
+/// Rate added between 0% and the optimal utilization
+pub const SLOPE_1_BPS: u64 = 400;
+/// Rate added between the optimal utilization and 100% (the "jump")
+pub const SLOPE_2_BPS: u64 = 65_000;
+/// Utilization at which the jump slope starts
+pub const OPTIMAL_UTILIZATION_BPS: u64 = 8_000;
+/// Borrow rate at 100% utilization; no input can produce more
+pub const MAX_BORROW_RATE_BPS: u64 = BASE_RATE_BPS + SLOPE_1_BPS + SLOPE_2_BPS;
+
+// ========================================
+// VULNERABLE IMPLEMENTATION
+// ========================================
+// This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+// DO NOT use this code in production environments.
+
+/// VULNERABLE: Utilization as `borrowed / deposited`, in basis points
+///
+/// Security Issue: A market with nothing deposited is at 0% utilization,
+/// but this divides by zero and panics. The first accrual on every new
+/// market fails, as does any accrual after the last lender withdraws.
+pub fn vulnerable_utilization_bps(borrowed: u64, deposited: u64) -> u64 {
+    // VULNERABILITY: No empty-market case
+    borrowed * BPS / deposited
+}
+
+/// VULNERABLE: Kinked borrow rate stored as a u16 of basis points
+///
+/// Security Issue: `u16` holds rates up to 655.35%, which covers every
+/// utilization except the last basis point. At 100% the rate is 655.50%
+/// and the addition overflows - with overflow checks on, the accrual
+/// panics; without them, it wraps to a near-zero rate. Either way
+/// borrowers stop paying the jump rate exactly when it is meant to bite.
+/// Utilization above 100% is not clamped and fails the same way.
+pub fn vulnerable_borrow_rate_bps(utilization_bps: u64) -> u16 {
+    let base = BASE_RATE_BPS as u16;
+    let slope_1 = SLOPE_1_BPS as u16;
+
+    if utilization_bps <= OPTIMAL_UTILIZATION_BPS {
+        base + (SLOPE_1_BPS * utilization_bps / OPTIMAL_UTILIZATION_BPS) as u16
+    } else {
+        let excess = utilization_bps - OPTIMAL_UTILIZATION_BPS;
+        let jump = (SLOPE_2_BPS * excess / (BPS - OPTIMAL_UTILIZATION_BPS)) as u16;
+        // VULNERABILITY: 150 + 400 + 65,000 does not fit in a u16
+        base + slope_1 + jump
+    }
+}
+
+// ========================================
+// SECURE IMPLEMENTATION
+// ========================================
+// This section contains the SECURE version of the same model with both
+// boundaries defined and every intermediate value bounded.
+
+/// SECURE: Utilization in basis points, defined for every input
+///
+/// Security Fix: An empty market is 0% utilized. Borrows can exceed
+/// deposits after interest accrues or bad debt is written off, so the
+/// result saturates at 100% rather than growing without bound. The
+/// product is taken in u128 so large markets cannot overflow.
+pub fn utilization_bps(borrowed: u64, deposited: u64) -> u64 {
+    if deposited == 0 {
+        return 0;
+    }
+    let utilization = u128::from(borrowed) * u128::from(BPS) / u128::from(deposited);
+    // SECURITY: Never above 100%; the cast cannot truncate after `min`
+    utilization.min(u128::from(BPS)) as u64
+}
+
+/// SECURE: Piecewise borrow rate for any utilization, capped at the maximum
+///
+/// Security Fix: The rate is computed in u64, whose range is far larger
+/// than `MAX_BORROW_RATE_BPS`, utilization is clamped to 100% first, and
+/// the result is capped so no input can exceed the curve's top.
+pub fn borrow_rate_bps(utilization_bps: u64) -> u64 {
+    let utilization = utilization_bps.min(BPS);
+
+    let rate = if utilization <= OPTIMAL_UTILIZATION_BPS {
+        BASE_RATE_BPS + SLOPE_1_BPS * utilization / OPTIMAL_UTILIZATION_BPS
+    } else {
+        let excess = utilization - OPTIMAL_UTILIZATION_BPS;
+        BASE_RATE_BPS + SLOPE_1_BPS + SLOPE_2_BPS * excess / (BPS - OPTIMAL_UTILIZATION_BPS)
+    };
+    rate.min(MAX_BORROW_RATE_BPS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::panic::catch_unwind;
+
+    #[test]
+    #[should_panic]
+    fn vulnerable_utilization_divides_by_zero_in_an_empty_market() {
+        vulnerable_utilization_bps(0, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn vulnerable_rate_overflows_at_full_utilization() {
+        vulnerable_borrow_rate_bps(BPS);
+    }
+
+    #[test]
+    fn vulnerable_rate_fails_only_at_the_last_basis_point() {
+        // Every utilization below 100% works, which is why tests that stop
+        // at 99% never find the overflow
+        for utilization in 0..BPS {
+            assert!(catch_unwind(|| vulnerable_borrow_rate_bps(utilization)).is_ok(), "{utilization}");
+        }
+        assert!(catch_unwind(|| vulnerable_borrow_rate_bps(BPS)).is_err());
+    }
+
+    #[test]
+    fn empty_market_is_zero_utilization() {
+        assert_eq!(utilization_bps(0, 0), 0);
+        assert_eq!(utilization_bps(1_000, 0), 0);
+        assert_eq!(borrow_rate_bps(utilization_bps(0, 0)), BASE_RATE_BPS);
+    }
+
+    #[test]
+    fn utilization_saturates_at_one_hundred_percent() {
+        assert_eq!(utilization_bps(100, 100), BPS);
+        assert_eq!(utilization_bps(u64::MAX, 1), BPS);
+        assert_eq!(utilization_bps(u64::MAX, u64::MAX), BPS);
+    }
+
+    #[test]
+    fn rate_hits_each_breakpoint_exactly() {
+        assert_eq!(borrow_rate_bps(0), BASE_RATE_BPS);
+        assert_eq!(borrow_rate_bps(OPTIMAL_UTILIZATION_BPS), BASE_RATE_BPS + SLOPE_1_BPS);
+        assert_eq!(borrow_rate_bps(BPS), MAX_BORROW_RATE_BPS);
+        assert_eq!(borrow_rate_bps(u64::MAX), MAX_BORROW_RATE_BPS);
+    }
+
+    #[test]
+    fn rate_is_monotonic_and_bounded_over_every_utilization() {
+        // Exhaustive: there are only 10,001 distinct utilizations
+        let mut previous = borrow_rate_bps(0);
+        for utilization in 1..=BPS {
+            let rate = borrow_rate_bps(utilization);
+            assert!(rate >= previous, "rate decreased at {utilization} bps");
+            assert!(rate <= MAX_BORROW_RATE_BPS);
+            previous = rate;
+        }
+    }
+
+    #[test]
+    fn secure_rate_matches_vulnerable_rate_wherever_the_latter_works() {
+        for utilization in 0..BPS {
+            assert_eq!(borrow_rate_bps(utilization), u64::from(vulnerable_borrow_rate_bps(utilization)));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn utilization_never_exceeds_one_hundred_percent(borrowed in any::<u64>(), deposited in any::<u64>()) {
+            prop_assert!(utilization_bps(borrowed, deposited) <= BPS);
+        }
+
+        #[test]
+        fn rate_is_bounded_for_any_market(borrowed in any::<u64>(), deposited in any::<u64>()) {
+            let rate = borrow_rate_bps(utilization_bps(borrowed, deposited));
+            prop_assert!((BASE_RATE_BPS..=MAX_BORROW_RATE_BPS).contains(&rate));
+        }
+
+        #[test]
+        fn more_borrowing_never_lowers_the_rate(
+            borrowed in any::<u64>(),
+            extra in any::<u64>(),
+            deposited in 1..=u64::MAX,
+        ) {
+            let before = borrow_rate_bps(utilization_bps(borrowed, deposited));
+            let after = borrow_rate_bps(utilization_bps(borrowed.saturating_add(extra), deposited));
+            prop_assert!(after >= before);
+        }
+
+        #[test]
+        fn more_deposits_never_raise_the_rate(
+            borrowed in any::<u64>(),
+            deposited in 1..=u64::MAX,
+            extra in any::<u64>(),
+        ) {
+            let before = borrow_rate_bps(utilization_bps(borrowed, deposited));
+            let after = borrow_rate_bps(utilization_bps(borrowed, deposited.saturating_add(extra)));
+            prop_assert!(after <= before);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Market, Position};
+
+#[test]
+fn market_layout_is_stable() {
+    let market = Market {
+        admin: Pubkey::new_from_array([1; 32]),
+        total_deposits: 0x2122232425262728,
+        total_borrows: 0x3132333435363738,
+        borrow_rate_bps: 0x4142434445464748,
+        last_update_slot: 0x5152535455565758,
+        loss_index: 0x6162636465666768696a6b6c6d6e6f70,
+        bump: 97,
+    };
+    assert_golden!("Market", golden::anchor(&market));
+}
+
+#[test]
+fn position_layout_is_stable() {
+    let position = Position {
+        market: Pubkey::new_from_array([7; 32]),
+        owner: Pubkey::new_from_array([8; 32]),
+        deposited: 0x9192939495969798,
+        collateral: 0xa1a2a3a4a5a6a7a8,
+        borrowed: 0xb1b2b3b4b5b6b7b8,
+        deposit_index: 0xc1c2c3c4c5c6c7c8c9cacbcccdcecfd0,
+        bump: 193,
+    };
+    assert_golden!("Position", golden::anchor(&position));
+}
@@ -0,0 +1,403 @@
+use secref_prelude::*;
+
+pub mod bad_debt;
+pub mod interest_rate;
+pub mod liquidation;
+
+use bad_debt::LOSS_INDEX_ONE;
+use interest_rate::BPS;
+use liquidation::Liquidation;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Slots per year at 400ms slots, used to turn an annual rate into per-slot interest
+pub const SLOTS_PER_YEAR: u64 = 63_072_000;
+
+/// Largest loan a position may take, as a share of its collateral
+pub const MAX_LTV_BPS: u64 = 7_500;
+
+/// Share of collateral a loan may reach before the position is unhealthy
+pub const LIQUIDATION_THRESHOLD_BPS: u64 = 8_000;
+
+/// A minimal single-asset lending market.
+///
+/// Lenders `deposit` into the market and earn the borrow rate; borrowers
+/// post collateral and `borrow` against it. The toy tracks balances as
+/// numbers instead of moving tokens, so each example built on it can focus
+/// on the accounting it demonstrates. Interest is applied at market level
+/// only. Each vulnerable / secure pair below demonstrates one lending bug
+/// against the same market.
+#[feature_gated]
+#[program]
+pub mod lending_toy {
+    use super::*;
+
+    /// Create a market administered by the signer
+    pub fn initialize_market(ctx: Context<InitializeMarket>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        market.admin = ctx.accounts.admin.key();
+        market.total_deposits = 0;
+        market.total_borrows = 0;
+        market.borrow_rate_bps = 0;
+        market.last_update_slot = Clock::get()?.slot;
+        market.loss_index = LOSS_INDEX_ONE;
+        market.bump = ctx.bumps.market;
+
+        msg!("Market initialized by {}", market.admin);
+        Ok(())
+    }
+
+    /// Open a lending / borrowing position in a market
+    pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.market = ctx.accounts.market.key();
+        position.owner = ctx.accounts.owner.key();
+        position.deposited = 0;
+        position.collateral = 0;
+        position.borrowed = 0;
+        position.deposit_index = ctx.accounts.market.loss_index;
+        position.bump = ctx.bumps.position;
+        Ok(())
+    }
+
+    /// Supply liquidity for borrowers
+    ///
+    /// The deposit is settled at the market's loss index first, so the new
+    /// liquidity bears only losses written off from now on.
+    pub fn deposit(ctx: Context<UpdatePosition>, amount: u64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let position = &mut ctx.accounts.position;
+
+        let deposited = bad_debt::claim(position.deposited, position.deposit_index, market.loss_index);
+        position.deposited = deposited.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+        position.deposit_index = market.loss_index;
+        market.total_deposits = market.total_deposits.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Deposited {}", amount);
+        Ok(())
+    }
+
+    /// Withdraw supplied liquidity that is not currently lent out, up to what the deposit is worth
+    pub fn withdraw(ctx: Context<UpdatePosition>, amount: u64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let position = &mut ctx.accounts.position;
+
+        let deposited = bad_debt::claim(position.deposited, position.deposit_index, market.loss_index);
+        require!(amount <= deposited, ErrorCode::InsufficientBalance);
+        require!(amount <= available_liquidity(market), ErrorCode::InsufficientLiquidity);
+
+        position.deposited = deposited - amount;
+        position.deposit_index = market.loss_index;
+        market.total_deposits -= amount;
+
+        msg!("Withdrew {}", amount);
+        Ok(())
+    }
+
+    /// Post collateral against future borrows
+    pub fn deposit_collateral(ctx: Context<UpdatePosition>, amount: u64) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.collateral = position.collateral.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Posted {} collateral", amount);
+        Ok(())
+    }
+
+    /// Borrow up to `MAX_LTV_BPS` of the position's collateral
+    pub fn borrow(ctx: Context<UpdatePosition>, amount: u64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let position = &mut ctx.accounts.position;
+
+        require!(amount <= available_liquidity(market), ErrorCode::InsufficientLiquidity);
+
+        let borrowed = position.borrowed.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(within_ltv(position.collateral, borrowed), ErrorCode::ExceedsLtv);
+
+        position.borrowed = borrowed;
+        market.total_borrows = market.total_borrows.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Borrowed {}", amount);
+        Ok(())
+    }
+
+    /// Repay part or all of a position's loan
+    pub fn repay(ctx: Context<UpdatePosition>, amount: u64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let position = &mut ctx.accounts.position;
+
+        let amount = amount.min(position.borrowed);
+        position.borrowed -= amount;
+        market.total_borrows = market.total_borrows.saturating_sub(amount);
+
+        msg!("Repaid {}", amount);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Accrue interest using the unbounded rate model
+    ///
+    /// Security Issue: `vulnerable_utilization_bps` divides by zero when the
+    /// market has no deposits, and `vulnerable_borrow_rate_bps` overflows its
+    /// u16 at exactly 100% utilization. Both panic, so the crank cannot run
+    /// on a new market, and - worse - cannot run on a fully borrowed one,
+    /// which is exactly when the jump rate is needed to pull liquidity back.
+    #[vulnerable]
+    pub fn vulnerable_accrue_interest(ctx: Context<AccrueInterest>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        // VULNERABILITY: Both boundaries of the rate curve panic
+        let utilization = interest_rate::vulnerable_utilization_bps(market.total_borrows, market.total_deposits);
+        let rate = u64::from(interest_rate::vulnerable_borrow_rate_bps(utilization));
+
+        apply_interest(market, rate, Clock::get()?.slot)
+    }
+
+    /// VULNERABLE: Check position health, then withdraw collateral
+    ///
+    /// Security Issue: The health check runs against the position as it was
+    /// before the withdrawal. Any position that is healthy now passes, and
+    /// the collateral is debited afterwards, so a borrower at the maximum
+    /// loan-to-value can take back almost all of their collateral and walk
+    /// away with the loan. The check is correct; it is just answering a
+    /// question about state the handler is about to change.
+    #[vulnerable]
+    pub fn vulnerable_withdraw_collateral(ctx: Context<UpdatePosition>, amount: u64) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+
+        // VULNERABILITY: Health of the pre-withdrawal state
+        require!(is_healthy(position.collateral, position.borrowed), ErrorCode::Unhealthy);
+        require!(amount <= position.collateral, ErrorCode::InsufficientBalance);
+
+        position.collateral -= amount;
+
+        msg!("Withdrew {} collateral, {} remaining against {} borrowed", amount, position.collateral, position.borrowed);
+        Ok(())
+    }
+
+    /// VULNERABLE: Repay an unhealthy position's debt for its collateral plus a bounty
+    ///
+    /// Security Issue: `vulnerable_liquidation` pays the bonus and a flat
+    /// bounty whatever the position holds, and the borrower's collateral is
+    /// debited with a saturating subtraction. Collateral the position never
+    /// had is credited to the liquidator: a dust position pays out 1,000 on
+    /// a repayment of nothing, as often as a bot cares to ask, and an
+    /// underwater one pays the bonus on collateral that is not there.
+    #[vulnerable]
+    pub fn vulnerable_liquidate(ctx: Context<Liquidate>, repay: u64) -> Result<()> {
+        let borrower = &mut ctx.accounts.borrower_position;
+        require!(!is_healthy(borrower.collateral, borrower.borrowed), ErrorCode::Healthy);
+        require!(repay <= borrower.borrowed, ErrorCode::InsufficientBalance);
+
+        // VULNERABILITY: The bounty is set by the repayment alone
+        let Liquidation { repaid, seized } = liquidation::vulnerable_liquidation(repay);
+        // VULNERABILITY: Saturates at zero; the liquidator is credited the full amount anyway
+        borrower.collateral = borrower.collateral.saturating_sub(seized);
+
+        settle_liquidation(ctx.accounts, repaid, seized)
+    }
+
+    /// VULNERABLE: Clear the debt of a position with no collateral left; admin only
+    ///
+    /// Security Issue: The position's debt is cleared, but the market still
+    /// counts the loan as lent and every deposit as whole. The lenders have
+    /// lost the loan between them, and nothing says so: each withdrawal is
+    /// paid in full while liquidity lasts, so the first lenders out are
+    /// made whole and the last find the market empty, their deposits still
+    /// showing every unit. Interest keeps accruing on the lost loan, too,
+    /// and is credited to deposits that will never see it.
+    #[vulnerable]
+    pub fn vulnerable_write_off_bad_debt(ctx: Context<WriteOffBadDebt>) -> Result<()> {
+        let position = &mut ctx.accounts.borrower_position;
+        require!(position.collateral == 0 && position.borrowed > 0, ErrorCode::NotBadDebt);
+
+        // VULNERABILITY: The loss is not recorded against the deposits
+        let loss = position.borrowed;
+        position.borrowed = 0;
+
+        msg!("Wrote off {} of bad debt", loss);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE versions of the same instructions.
+
+    /// SECURE: Accrue interest using the bounded piecewise rate model
+    ///
+    /// Security Fix: `utilization_bps` treats an empty market as 0% and
+    /// saturates at 100%, and `borrow_rate_bps` is computed in u64 and
+    /// capped at `MAX_BORROW_RATE_BPS`. The crank succeeds for every
+    /// market state.
+    #[secure]
+    pub fn secure_accrue_interest(ctx: Context<AccrueInterest>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        // SECURITY: Defined and bounded for every (borrowed, deposited) pair
+        let utilization = interest_rate::utilization_bps(market.total_borrows, market.total_deposits);
+        let rate = interest_rate::borrow_rate_bps(utilization);
+
+        apply_interest(market, rate, Clock::get()?.slot)
+    }
+
+    /// SECURE: Withdraw collateral only if the position stays healthy afterwards
+    ///
+    /// Security Fix: The remaining collateral is computed first and the
+    /// health check runs against that post-withdrawal state, so the
+    /// instruction can only succeed if it leaves the position healthy.
+    #[secure]
+    pub fn secure_withdraw_collateral(ctx: Context<UpdatePosition>, amount: u64) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+
+        let remaining = position.collateral.checked_sub(amount).ok_or(ErrorCode::InsufficientBalance)?;
+        // SECURITY: Health of the state the instruction will leave behind
+        require!(is_healthy(remaining, position.borrowed), ErrorCode::Unhealthy);
+
+        position.collateral = remaining;
+
+        msg!("Withdrew {} collateral, {} remaining against {} borrowed", amount, position.collateral, position.borrowed);
+        Ok(())
+    }
+
+    /// SECURE: Repay part of an unhealthy position's debt for its collateral plus a capped bonus
+    ///
+    /// Security Fix: A liquidation repays at most half the debt, or all of
+    /// a dust debt, and `liquidation::liquidation` seizes the repayment and
+    /// its bonus only up to the collateral the position holds, cutting the
+    /// repayment to match. There is no flat bounty, so a liquidation that
+    /// repays nothing seizes nothing and is rejected. Collateral only ever
+    /// moves from the borrower to the liquidator.
+    #[secure]
+    pub fn secure_liquidate(ctx: Context<Liquidate>, repay: u64) -> Result<()> {
+        let borrower = &mut ctx.accounts.borrower_position;
+        require!(!is_healthy(borrower.collateral, borrower.borrowed), ErrorCode::Healthy);
+        // SECURITY: Half the debt at a time, or all of a dust debt
+        require!(repay <= liquidation::max_repay(borrower.borrowed), ErrorCode::ExceedsCloseFactor);
+
+        // SECURITY: Seized from what the position holds, in proportion to what is repaid
+        let Liquidation { repaid, seized } = liquidation::liquidation(repay, borrower.collateral);
+        require!(repaid > 0, ErrorCode::NothingToLiquidate);
+        borrower.collateral -= seized;
+
+        settle_liquidation(ctx.accounts, repaid, seized)
+    }
+
+    /// SECURE: Write off the debt of a position with no collateral left, shared by every lender; admin only
+    ///
+    /// Security Fix: The debt leaves the market's loans and its deposits
+    /// together, and `bad_debt::written_down_index` lowers the loss index
+    /// by the share of all deposits it is. Every deposit is worth the same
+    /// fraction less, whenever its owner withdraws, and the deposits never
+    /// add up to more than the market holds and is owed. The loss is
+    /// emitted as `BadDebtSocialized`.
+    #[secure]
+    pub fn secure_write_off_bad_debt(ctx: Context<WriteOffBadDebt>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let position = &mut ctx.accounts.borrower_position;
+        require!(position.collateral == 0 && position.borrowed > 0, ErrorCode::NotBadDebt);
+        let loss = position.borrowed;
+        require!(loss < market.total_deposits, ErrorCode::LossExceedsDeposits);
+
+        // SECURITY: Every deposit bears the same share of the loss
+        market.loss_index = bad_debt::written_down_index(market.loss_index, market.total_deposits, loss);
+        market.total_deposits -= loss;
+        market.total_borrows = market.total_borrows.saturating_sub(loss);
+        position.borrowed = 0;
+
+        emit!(BadDebtSocialized {
+            market: market.key(),
+            position: position.key(),
+            loss,
+            loss_index: market.loss_index,
+        });
+
+        msg!("Wrote off {} of bad debt; loss index now {}", loss, market.loss_index);
+        Ok(())
+    }
+}
+
+// ========================================
+// SHARED HELPERS
+// ========================================
+
+/// Liquidity that has been deposited and not lent out
+fn available_liquidity(market: &Market) -> u64 {
+    market.total_deposits.saturating_sub(market.total_borrows)
+}
+
+/// Whether `borrowed` is within `MAX_LTV_BPS` of `collateral`
+fn within_ltv(collateral: u64, borrowed: u64) -> bool {
+    u128::from(borrowed) * u128::from(BPS) <= u128::from(collateral) * u128::from(MAX_LTV_BPS)
+}
+
+/// Whether `borrowed` is within `LIQUIDATION_THRESHOLD_BPS` of `collateral`
+fn is_healthy(collateral: u64, borrowed: u64) -> bool {
+    u128::from(borrowed) * u128::from(BPS) <= u128::from(collateral) * u128::from(LIQUIDATION_THRESHOLD_BPS)
+}
+
+/// Count `repaid` of the borrower's debt as repaid, and credit `seized` collateral to the liquidator
+///
+/// The handler has already debited the borrower's collateral.
+fn settle_liquidation(accounts: &mut Liquidate, repaid: u64, seized: u64) -> Result<()> {
+    let borrower = &mut accounts.borrower_position;
+    borrower.borrowed = borrower.borrowed.checked_sub(repaid).ok_or(ErrorCode::InsufficientBalance)?;
+    accounts.market.total_borrows = accounts.market.total_borrows.saturating_sub(repaid);
+
+    let liquidator = &mut accounts.liquidator_position;
+    liquidator.collateral = liquidator.collateral.checked_add(seized).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    msg!("Liquidated {} of debt for {} collateral", repaid, seized);
+    Ok(())
+}
+
+/// Charge `rate_bps` per year on outstanding borrows for the slots since the last update
+///
+/// Interest is owed by borrowers and earned by lenders, so it is added to
+/// both totals.
+fn apply_interest(market: &mut Market, rate_bps: u64, current_slot: u64) -> Result<()> {
+    let elapsed = current_slot.saturating_sub(market.last_update_slot);
+    let interest = u128::from(market.total_borrows) * u128::from(rate_bps) * u128::from(elapsed)
+        / (u128::from(BPS) * u128::from(SLOTS_PER_YEAR));
+    let interest = u64::try_from(interest).map_err(|_| error!(ErrorCode::ArithmeticOverflow))?;
+
+    market.total_borrows = market.total_borrows.checked_add(interest).ok_or(ErrorCode::ArithmeticOverflow)?;
+    market.total_deposits = market.total_deposits.checked_add(interest).ok_or(ErrorCode::ArithmeticOverflow)?;
+    market.borrow_rate_bps = rate_bps;
+    market.last_update_slot = current_slot;
+
+    msg!("Accrued {} interest over {} slots at {} bps", interest, elapsed, rate_bps);
+    Ok(())
+}
+
+// ========================================
+// EVENTS
+// ========================================
+
+/// Bad debt written off against every deposit: deposits now scale by `loss_index`
+#[event]
+pub struct BadDebtSocialized {
+    pub market: Pubkey,
+    pub position: Pubkey,
+    pub loss: u64,
+    pub loss_index: u128,
+}
@@ -0,0 +1,160 @@
+//! Liquidation bounties.
+//!
+//! Anyone may repay part of an unhealthy position's debt and take its
+//! collateral at a discount: the repaid amount plus a bonus. The bonus is
+//! what pays liquidation bots to watch the market, so it has to be worth
+//! their transaction fees - which is hard on a dust position, whose bonus
+//! rounds to nothing. Whatever the bot is paid comes out of the borrower's
+//! collateral, and a position cannot give up more collateral than it has.
+
+use crate::interest_rate::BPS;
+
+/// Collateral a liquidator receives on top of the debt they repay
+pub const LIQUIDATION_BONUS_BPS: u64 = 500;
+/// Share of a position's debt one liquidation may repay
+pub const CLOSE_FACTOR_BPS: u64 = 5_000;
+/// Flat bounty the vulnerable formula adds, so liquidating a dust position still pays
+pub const MIN_LIQUIDATION_BOUNTY: u64 = 1_000;
+/// Debt at or below which a position may be liquidated in full
+pub const DUST_DEBT: u64 = 1_000;
+
+/// What one liquidation moves: debt the liquidator repays, and collateral they take for it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Liquidation {
+    pub repaid: u64,
+    pub seized: u64,
+}
+
+// ========================================
+// VULNERABLE IMPLEMENTATION
+// ========================================
+// This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+// DO NOT use this code in production environments.
+
+/// VULNERABLE: Repaying `repay` seizes it, the bonus on it, and a flat bounty
+///
+/// Security Issue: The seized amount is a function of the repayment alone.
+/// The flat bounty keeps dust liquidations worth a bot's while, but it is
+/// paid in full on a position holding less collateral than it - and on a
+/// repayment of zero - and the bonus is paid even when the position is
+/// underwater and its collateral cannot cover the repayment itself.
+pub fn vulnerable_liquidation(repay: u64) -> Liquidation {
+    // VULNERABILITY: Nothing here knows how much collateral there is
+    let bonus = (u128::from(repay) * u128::from(LIQUIDATION_BONUS_BPS) / u128::from(BPS)) as u64;
+    let bounty = bonus.saturating_add(MIN_LIQUIDATION_BOUNTY);
+    Liquidation {
+        repaid: repay,
+        seized: repay.saturating_add(bounty),
+    }
+}
+
+// ========================================
+// SECURE IMPLEMENTATION
+// ========================================
+// This section contains the SECURE version: the bounty is a share of the
+// repayment and never more than the position holds.
+
+/// SECURE: Most of a `borrowed` debt one liquidation may repay
+///
+/// Half the debt, so a position is brought back to health a piece at a
+/// time rather than sold off whole. A dust debt may be repaid in full:
+/// halving it would only leave a smaller remainder nobody is paid enough
+/// to liquidate.
+pub fn max_repay(borrowed: u64) -> u64 {
+    if borrowed <= DUST_DEBT {
+        return borrowed;
+    }
+    // At most `borrowed`, since the close factor is below the whole
+    (u128::from(borrowed) * u128::from(CLOSE_FACTOR_BPS) / u128::from(BPS)) as u64
+}
+
+/// SECURE: Repaying up to `repay` against `collateral` seizes the repayment and its bonus, capped
+///
+/// Security Fix: No flat bounty; the bonus is a share of the repayment,
+/// rounded down. When the repayment and bonus come to more than the
+/// collateral, the collateral is all that is seized, and the repayment is
+/// cut to what that collateral buys at the bonus, rounded up against the
+/// liquidator. The seized amount is never more than `collateral`, and
+/// never more than the repayment plus its bonus.
+pub fn liquidation(repay: u64, collateral: u64) -> Liquidation {
+    let bonus = u128::from(repay) * u128::from(LIQUIDATION_BONUS_BPS) / u128::from(BPS);
+    let seized = u128::from(repay) + bonus;
+    if seized <= u128::from(collateral) {
+        return Liquidation {
+            repaid: repay,
+            seized: seized as u64,
+        };
+    }
+
+    // SECURITY: Never more than the position holds
+    let repaid = (u128::from(collateral) * u128::from(BPS)).div_ceil(u128::from(BPS + LIQUIDATION_BONUS_BPS));
+    // At most `repay`: `repay` with its bonus already came to more than `collateral`
+    Liquidation {
+        repaid: repaid as u64,
+        seized: collateral,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn vulnerable_liquidation_pays_the_flat_bounty_on_dust() {
+        // A 10-unit debt against 12 of collateral: the bot is owed 1,010
+        assert_eq!(vulnerable_liquidation(10), Liquidation { repaid: 10, seized: 1_010 });
+        assert_eq!(liquidation(10, 12), Liquidation { repaid: 10, seized: 10 });
+
+        // Repaying nothing still earns the bounty
+        assert_eq!(vulnerable_liquidation(0).seized, MIN_LIQUIDATION_BOUNTY);
+        assert_eq!(liquidation(0, 12), Liquidation { repaid: 0, seized: 0 });
+    }
+
+    #[test]
+    fn liquidation_pays_the_bonus_while_the_collateral_covers_it() {
+        assert_eq!(liquidation(1_000, 2_000), Liquidation { repaid: 1_000, seized: 1_050 });
+        assert_eq!(liquidation(1_000, 1_050), Liquidation { repaid: 1_000, seized: 1_050 });
+        // 5% of 19 is 0.95, rounded down
+        assert_eq!(liquidation(19, 1_000), Liquidation { repaid: 19, seized: 19 });
+    }
+
+    #[test]
+    fn liquidation_of_an_underwater_position_takes_what_there_is() {
+        // 1,000 of debt against 840 of collateral buys 800 of debt at the bonus
+        assert_eq!(liquidation(1_000, 840), Liquidation { repaid: 800, seized: 840 });
+        // 1,049 / 1.05 is 999.05, rounded up
+        assert_eq!(liquidation(1_000, 1_049), Liquidation { repaid: 1_000, seized: 1_049 });
+        assert_eq!(liquidation(u64::MAX, u64::MAX - 1).seized, u64::MAX - 1);
+        assert_eq!(liquidation(1_000, 0), Liquidation { repaid: 0, seized: 0 });
+    }
+
+    #[test]
+    fn dust_debts_are_repaid_in_full() {
+        assert_eq!(max_repay(DUST_DEBT), DUST_DEBT);
+        assert_eq!(max_repay(1), 1);
+        assert_eq!(max_repay(DUST_DEBT + 2), DUST_DEBT / 2 + 1);
+        assert_eq!(max_repay(u64::MAX), u64::MAX / 2);
+    }
+
+    proptest! {
+        #[test]
+        fn liquidation_never_seizes_more_than_the_collateral(repay: u64, collateral: u64) {
+            let Liquidation { repaid, seized } = liquidation(repay, collateral);
+            prop_assert!(seized <= collateral);
+            prop_assert!(repaid <= repay);
+        }
+
+        #[test]
+        fn liquidation_never_pays_more_than_the_bonus(repay: u64, collateral: u64) {
+            let Liquidation { repaid, seized } = liquidation(repay, collateral);
+            prop_assert!(u128::from(seized) * u128::from(BPS)
+                <= u128::from(repaid) * u128::from(BPS + LIQUIDATION_BONUS_BPS));
+        }
+
+        #[test]
+        fn debts_above_dust_are_repaid_at_most_half_at_a_time(borrowed in DUST_DEBT + 1..) {
+            prop_assert!(u128::from(max_repay(borrowed)) * 2 <= u128::from(borrowed));
+        }
+    }
+}
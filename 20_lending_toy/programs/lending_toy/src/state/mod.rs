@@ -0,0 +1,39 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Market {
+    /// Creator of the market (32 bytes)
+    pub admin: Pubkey,
+    /// Liquidity supplied by lenders, including earned interest (8 bytes)
+    pub total_deposits: u64,
+    /// Outstanding loans, including accrued interest (8 bytes)
+    pub total_borrows: u64,
+    /// Annual borrow rate applied at the last accrual (8 bytes)
+    pub borrow_rate_bps: u64,
+    /// Slot of the last accrual (8 bytes)
+    pub last_update_slot: u64,
+    /// Share of each deposit still backed, out of LOSS_INDEX_ONE (16 bytes)
+    pub loss_index: u128,
+    /// PDA bump (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Position {
+    /// Market this position belongs to (32 bytes)
+    pub market: Pubkey,
+    /// Position owner (32 bytes)
+    pub owner: Pubkey,
+    /// Liquidity supplied to the market (8 bytes)
+    pub deposited: u64,
+    /// Collateral backing loans (8 bytes)
+    pub collateral: u64,
+    /// Outstanding loan principal (8 bytes)
+    pub borrowed: u64,
+    /// Market's loss index when `deposited` was last settled (16 bytes)
+    pub deposit_index: u128,
+    /// PDA bump (1 byte)
+    pub bump: u8,
+}
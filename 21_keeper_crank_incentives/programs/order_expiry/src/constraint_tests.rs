@@ -0,0 +1,74 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, Fixture,
+    TestAccount,
+};
+
+use crate::{Order, SecureExpireOrder, SecureReclaimOrder};
+
+fn expire_fixture() -> Fixture {
+    reclaim_fixture().with("keeper", TestAccount::signer())
+}
+
+fn reclaim_fixture() -> Fixture {
+    let maker = TestAccount::system(0);
+    let id = 3u64;
+    let (address, bump) = Pubkey::find_program_address(&[b"order", maker.key.as_ref(), &id.to_le_bytes()], &crate::ID);
+    let order = TestAccount::anchor(&Order {
+        maker: maker.key,
+        id,
+        amount: 1_000,
+        expires_at: 0,
+        bump,
+    })
+    .at(address);
+    Fixture::new().with("order", order).with("maker", maker)
+}
+
+#[test]
+fn secure_expire_order_refunds_the_stored_maker() {
+    // Rekeying `maker` moves the PDA, so edit the stored maker instead
+    assert_seeds_violation!(SecureExpireOrder, expire_fixture(), "maker");
+    assert_constraint_violation!(
+        SecureExpireOrder,
+        expire_fixture(),
+        "order" => |order| order.edit(|stored: &mut Order| stored.maker = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+}
+
+#[test]
+fn secure_expire_order_closes_only_the_order_pda() {
+    assert_seeds_violation!(SecureExpireOrder, expire_fixture(), "order");
+    assert_owner_violation!(SecureExpireOrder, expire_fixture(), "order");
+    assert_constraint_violation!(
+        SecureExpireOrder,
+        expire_fixture(),
+        "order" => |order| order.edit(|stored: &mut Order| stored.id += 1),
+        ErrorCode::ConstraintSeeds
+    );
+}
+
+#[test]
+fn secure_expire_order_requires_a_keeper_signature() {
+    assert_signer_violation!(SecureExpireOrder, expire_fixture(), "keeper");
+}
+
+#[test]
+fn secure_reclaim_order_refunds_the_stored_maker() {
+    assert_seeds_violation!(SecureReclaimOrder, reclaim_fixture(), "maker");
+    assert_constraint_violation!(
+        SecureReclaimOrder,
+        reclaim_fixture(),
+        "order" => |order| order.edit(|stored: &mut Order| stored.maker = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+}
+
+#[test]
+fn secure_reclaim_order_closes_only_the_order_pda() {
+    assert_seeds_violation!(SecureReclaimOrder, reclaim_fixture(), "order");
+    assert_owner_violation!(SecureReclaimOrder, reclaim_fixture(), "order");
+}
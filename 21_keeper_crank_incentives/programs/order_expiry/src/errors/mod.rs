@@ -0,0 +1,17 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Order must escrow more than the keeper reward")]
+    OrderTooSmall,
+    #[msg("Time-to-live must be non-negative")]
+    InvalidTtl,
+    #[msg("Order has not expired yet")]
+    OrderNotExpired,
+    #[msg("Reward exceeds the escrowed amount")]
+    RewardExceedsEscrow,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
@@ -0,0 +1,33 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct PlaceOrder<'info> {
+    #[account(
+        init,
+        payer = maker,
+        space = 8 + 32 + 8 + 8 + 8 + 1, // discriminator + maker + id + amount + expires_at + bump
+        seeds = [b"order", maker.key().as_ref(), &id.to_le_bytes()],
+        bump
+    )]
+    pub order: Account<'info, Order>,
+
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,39 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureExpireOrder<'info> {
+    #[account(
+        mut,
+        seeds = [b"order", maker.key().as_ref(), &order.id.to_le_bytes()],
+        bump = order.bump,
+        has_one = maker,
+        close = maker
+    )]
+    pub order: Account<'info, Order>,
+
+    /// CHECK: SECURITY - must be the order's stored maker (`has_one`); only receives lamports
+    #[account(mut)]
+    pub maker: UncheckedAccount<'info>,
+
+    /// Anyone may crank
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SecureReclaimOrder<'info> {
+    // SECURITY: The handler checks expiry before closing
+    #[account(
+        mut,
+        seeds = [b"order", maker.key().as_ref(), &order.id.to_le_bytes()],
+        bump = order.bump,
+        has_one = maker
+    )]
+    pub order: Account<'info, Order>,
+
+    /// CHECK: SECURITY - must be the order's stored maker (`has_one`); only receives lamports
+    #[account(mut)]
+    pub maker: UncheckedAccount<'info>,
+}
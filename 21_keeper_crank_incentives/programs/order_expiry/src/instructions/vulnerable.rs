@@ -0,0 +1,28 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableExpireOrder<'info> {
+    #[account(mut, close = refund_destination)]
+    pub order: Account<'info, Order>,
+
+    /// CHECK: VULNERABILITY - any writable account the keeper chooses
+    #[account(mut)]
+    pub refund_destination: UncheckedAccount<'info>,
+
+    /// Anyone may crank
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableReclaimOrder<'info> {
+    // VULNERABILITY: Nothing here or in the handler asks whether the order has expired
+    #[account(mut, has_one = maker)]
+    pub order: Account<'info, Order>,
+
+    /// CHECK: The order's stored maker (`has_one`); only receives lamports
+    #[account(mut)]
+    pub maker: UncheckedAccount<'info>,
+}
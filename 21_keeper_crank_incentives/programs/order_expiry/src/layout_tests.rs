@@ -0,0 +1,18 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::Order;
+
+#[test]
+fn order_layout_is_stable() {
+    let order = Order {
+        maker: Pubkey::new_from_array([1; 32]),
+        id: 0x2122232425262728,
+        amount: 0x3132333435363738,
+        expires_at: 0x4142434445464748,
+        bump: 81,
+    };
+    assert_golden!("Order", golden::anchor(&order));
+}
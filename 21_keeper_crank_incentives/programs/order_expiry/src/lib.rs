@@ -0,0 +1,167 @@
+use anchor_lang::system_program::{self, Transfer};
+use secref_guards::rent::reclaim_rent;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Lamports paid to whoever expires an order
+///
+/// Fixed by the program: enough to cover a keeper's transaction fee with a
+/// margin, and independent of anything the keeper passes in.
+pub const KEEPER_REWARD_LAMPORTS: u64 = 10_000;
+
+#[feature_gated]
+#[program]
+pub mod order_expiry {
+    use super::*;
+    #[secure]
+    use secref_guards::rent::assert_reclaimable;
+
+    /// Escrow `amount` lamports in an order that expires after `ttl_seconds`
+    ///
+    /// Expired orders are cleaned up by keepers: anyone may call an expire
+    /// instruction once the order has expired, which refunds the maker and
+    /// pays the keeper for the transaction.
+    pub fn place_order(ctx: Context<PlaceOrder>, id: u64, amount: u64, ttl_seconds: i64) -> Result<()> {
+        require!(amount > KEEPER_REWARD_LAMPORTS, ErrorCode::OrderTooSmall);
+        require!(ttl_seconds >= 0, ErrorCode::InvalidTtl);
+
+        let expires_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(ttl_seconds)
+            .ok_or(ErrorCode::InvalidTtl)?;
+
+        let order = &mut ctx.accounts.order;
+        order.maker = ctx.accounts.maker.key();
+        order.id = id;
+        order.amount = amount;
+        order.expires_at = expires_at;
+        order.bump = ctx.bumps.order;
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.maker.to_account_info(),
+                    to: ctx.accounts.order.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Order {} escrowed {} lamports until {}", id, amount, expires_at);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Expire an order with keeper-chosen payout parameters
+    ///
+    /// Security Issue: The instruction is permissionless, but the keeper
+    /// supplies both the reward it is paid and the account that receives
+    /// the maker's refund. Nothing ties either to the order: a keeper can
+    /// set `reward` to the whole escrow, or pass its own account as
+    /// `refund_destination`, and take the maker's funds for the price of
+    /// a transaction fee. The expiry check is correct - the keeper cannot
+    /// act early - but timing was never the dangerous input.
+    #[vulnerable]
+    pub fn vulnerable_expire_order(ctx: Context<VulnerableExpireOrder>, reward: u64) -> Result<()> {
+        let order = &ctx.accounts.order;
+        require!(Clock::get()?.unix_timestamp >= order.expires_at, ErrorCode::OrderNotExpired);
+
+        // VULNERABILITY: Reward chosen by the keeper; only bounded by the escrow
+        require!(reward <= order.amount, ErrorCode::RewardExceedsEscrow);
+        pay_keeper(&ctx.accounts.order.to_account_info(), &ctx.accounts.keeper.to_account_info(), reward)?;
+
+        // VULNERABILITY: Remaining lamports go to `refund_destination` via `close`,
+        // which the keeper picks
+        msg!("Order {} expired; keeper paid {}", order.id, reward);
+        Ok(())
+    }
+
+    /// VULNERABLE: Reclaim an order's rent and escrow for its maker
+    ///
+    /// Security Issue: Reclaiming is the reward-free way to clean up an
+    /// expired order, so it is permissionless and refunds the stored
+    /// maker. It never checks that the order has expired. Anyone can close
+    /// any resting order the moment it is placed: a competing maker clears
+    /// the book of everyone else's orders for the price of a transaction
+    /// fee, and no order stays live long enough to be filled.
+    #[vulnerable]
+    pub fn vulnerable_reclaim_order(ctx: Context<VulnerableReclaimOrder>) -> Result<()> {
+        // VULNERABILITY: No expiry check; live orders are deleted too
+        let refunded = reclaim_rent(&ctx.accounts.order.to_account_info(), &ctx.accounts.maker.to_account_info())?;
+        msg!("Order {} reclaimed; {} lamports refunded", ctx.accounts.order.id, refunded);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version with keeper influence
+    // limited to timing.
+
+    /// SECURE: Expire an order with fixed, program-defined payouts
+    ///
+    /// Security Fix: The keeper takes no payout arguments. The reward is
+    /// `KEEPER_REWARD_LAMPORTS`, a constant that `place_order` guarantees
+    /// the escrow can cover, and the refund is closed to the order's
+    /// stored maker. The only thing the keeper controls is when the
+    /// instruction runs, and the expiry check makes early calls fail.
+    #[secure]
+    pub fn secure_expire_order(ctx: Context<SecureExpireOrder>) -> Result<()> {
+        let order = &ctx.accounts.order;
+        // SECURITY: Timing is the keeper's only input, and it is checked
+        require!(Clock::get()?.unix_timestamp >= order.expires_at, ErrorCode::OrderNotExpired);
+
+        // SECURITY: Fixed incentive, not a parameter
+        pay_keeper(&ctx.accounts.order.to_account_info(), &ctx.accounts.keeper.to_account_info(), KEEPER_REWARD_LAMPORTS)?;
+
+        // SECURITY: `close = maker` with `has_one = maker` refunds the stored maker
+        msg!("Order {} expired; keeper paid {}", order.id, KEEPER_REWARD_LAMPORTS);
+        Ok(())
+    }
+
+    /// SECURE: Reclaim an expired order's rent and escrow for its maker
+    ///
+    /// Security Fix: The same reward-free cleanup, but only once the order
+    /// has expired (`secref_guards::rent::assert_reclaimable`). Until then
+    /// the order is live state, and deleting it is not cleanup. Everything
+    /// the order holds goes to the stored maker, who paid its rent.
+    #[secure]
+    pub fn secure_reclaim_order(ctx: Context<SecureReclaimOrder>) -> Result<()> {
+        // SECURITY: Only expired orders give their rent back
+        assert_reclaimable(ctx.accounts.order.expires_at, Clock::get()?.unix_timestamp)?;
+
+        let refunded = reclaim_rent(&ctx.accounts.order.to_account_info(), &ctx.accounts.maker.to_account_info())?;
+        msg!("Order {} reclaimed; {} lamports refunded", ctx.accounts.order.id, refunded);
+        Ok(())
+    }
+}
+
+/// Move `reward` lamports from a program-owned order to the keeper
+fn pay_keeper<'info>(order: &AccountInfo<'info>, keeper: &AccountInfo<'info>, reward: u64) -> Result<()> {
+    let remaining = order.lamports().checked_sub(reward).ok_or(ErrorCode::RewardExceedsEscrow)?;
+    **order.try_borrow_mut_lamports()? = remaining;
+    **keeper.try_borrow_mut_lamports()? = keeper.lamports().checked_add(reward).ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
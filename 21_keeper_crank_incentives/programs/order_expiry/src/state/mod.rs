@@ -0,0 +1,17 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Order {
+    /// Owner of the escrowed lamports (32 bytes)
+    pub maker: Pubkey,
+    /// Maker-chosen order id (8 bytes)
+    pub id: u64,
+    /// Lamports escrowed on top of rent (8 bytes)
+    pub amount: u64,
+    /// Unix timestamp after which anyone may expire the order (8 bytes)
+    pub expires_at: i64,
+    /// PDA bump (1 byte)
+    pub bump: u8,
+}
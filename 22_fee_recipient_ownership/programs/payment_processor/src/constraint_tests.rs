@@ -0,0 +1,126 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, Fixture,
+    TestAccount,
+};
+
+use crate::{Config, SecurePay, SecurePayOptionalFee};
+
+fn pay_fixture() -> Fixture {
+    let payer = TestAccount::signer();
+    let mint = Pubkey::new_unique();
+    let config = TestAccount::anchor(&Config {
+        admin: Pubkey::new_unique(),
+        mint,
+        fee_bps: 100,
+        fee_authority_bump: 0,
+        bump: 0,
+        fee_waivable: false,
+    });
+    let (fee_authority, fee_authority_bump) =
+        Pubkey::find_program_address(&[b"fee_authority", config.key.as_ref()], &crate::ID);
+    let mut config = config;
+    config.edit(|stored: &mut Config| stored.fee_authority_bump = fee_authority_bump);
+    Fixture::new()
+        .with("config", config)
+        .with("fee_authority", TestAccount::system(0).at(fee_authority))
+        .with("payer_tokens", TestAccount::token_account(mint, payer.key, 1_000))
+        .with(
+            "merchant_tokens",
+            TestAccount::token_account(mint, Pubkey::new_unique(), 0),
+        )
+        .with("fee_recipient", TestAccount::token_account(mint, fee_authority, 0))
+        .with("payer", payer)
+        .with("token_program", TestAccount::token_program())
+}
+
+fn configured_mint(fixture: &Fixture) -> Pubkey {
+    Config::try_deserialize(&mut &fixture.account("config").data[..])
+        .unwrap()
+        .mint
+}
+
+fn other_mint(authority: Pubkey) -> TestAccount {
+    TestAccount::token_account(Pubkey::new_unique(), authority, 0)
+}
+
+#[test]
+fn secure_pay_reads_a_program_owned_config() {
+    assert_owner_violation!(SecurePay, pay_fixture(), "config");
+}
+
+#[test]
+fn secure_pay_derives_the_fee_authority_from_the_config() {
+    assert_seeds_violation!(SecurePay, pay_fixture(), "fee_authority");
+    assert_constraint_violation!(
+        SecurePay,
+        pay_fixture(),
+        "config" => |config| config.edit(|stored: &mut Config| stored.fee_authority_bump ^= 1),
+        ErrorCode::ConstraintSeeds
+    );
+}
+
+#[test]
+fn secure_pay_debits_the_payers_tokens() {
+    let fixture = pay_fixture();
+    let payer = fixture.key("payer");
+    assert_constraint_violation!(SecurePay, fixture, "payer_tokens" => other_mint(payer), ErrorCode::ConstraintTokenMint);
+    let fixture = pay_fixture();
+    let someone_else = TestAccount::token_account(configured_mint(&fixture), Pubkey::new_unique(), 1_000);
+    assert_constraint_violation!(SecurePay, fixture, "payer_tokens" => someone_else, ErrorCode::ConstraintTokenOwner);
+    assert_signer_violation!(SecurePay, pay_fixture(), "payer");
+}
+
+#[test]
+fn secure_pay_credits_the_merchant_in_the_configured_mint() {
+    assert_constraint_violation!(
+        SecurePay,
+        pay_fixture(),
+        "merchant_tokens" => other_mint(Pubkey::new_unique()),
+        ErrorCode::ConstraintTokenMint
+    );
+}
+
+#[test]
+fn secure_pay_sends_fees_only_to_the_fee_authority() {
+    let fixture = pay_fixture();
+    let fee_authority = fixture.key("fee_authority");
+    assert_constraint_violation!(
+        SecurePay,
+        fixture,
+        "fee_recipient" => other_mint(fee_authority),
+        ErrorCode::ConstraintTokenMint
+    );
+    let fixture = pay_fixture();
+    let attacker_tokens = TestAccount::token_account(configured_mint(&fixture), Pubkey::new_unique(), 0);
+    assert_constraint_violation!(
+        SecurePay,
+        fixture,
+        "fee_recipient" => attacker_tokens,
+        crate::ErrorCode::InvalidFeeRecipient
+    );
+}
+
+#[test]
+fn secure_pay_optional_fee_checks_a_present_fee_account_like_secure_pay() {
+    let fixture = pay_fixture();
+    let attacker_tokens = TestAccount::token_account(configured_mint(&fixture), Pubkey::new_unique(), 0);
+    assert_constraint_violation!(
+        SecurePayOptionalFee,
+        fixture,
+        "fee_recipient" => attacker_tokens,
+        crate::ErrorCode::InvalidFeeRecipient
+    );
+}
+
+#[test]
+fn secure_pay_requires_the_token_program() {
+    assert_constraint_violation!(
+        SecurePay,
+        pay_fixture(),
+        "token_program" => TestAccount::program(Pubkey::new_unique()),
+        ErrorCode::InvalidProgramId
+    );
+}
@@ -0,0 +1,15 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Fee must not exceed 100%")]
+    InvalidFee,
+    #[msg("Fee recipient is not owned by the protocol fee authority")]
+    InvalidFeeRecipient,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+    #[msg("Fee account is required: this config does not waive fees")]
+    FeeRecipientRequired,
+}
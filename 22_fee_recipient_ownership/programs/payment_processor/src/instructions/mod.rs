@@ -0,0 +1,78 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        // discriminator + admin + mint + fee_bps + fee_authority_bump + bump + fee_waivable
+        space = 8 + 32 + 32 + 2 + 1 + 1 + 1,
+        seeds = [b"config", mint.key().as_ref()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: PDA that owns every protocol fee account; holds no data
+    #[account(seeds = [b"fee_authority", config.key().as_ref()], bump)]
+    pub fee_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"fee_vault", config.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = fee_authority
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(has_one = admin)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Fee authority PDA; signs the transfer
+    #[account(seeds = [b"fee_authority", config.key().as_ref()], bump = config.fee_authority_bump)]
+    pub fee_authority: UncheckedAccount<'info>,
+
+    #[account(mut, token::authority = fee_authority)]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = config.mint)]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeWaiver<'info> {
+    #[account(mut, has_one = admin)]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
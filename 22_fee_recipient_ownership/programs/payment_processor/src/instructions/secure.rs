@@ -0,0 +1,58 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecurePay<'info> {
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Fee authority PDA for this config; only its address is used
+    #[account(seeds = [b"fee_authority", config.key().as_ref()], bump = config.fee_authority_bump)]
+    pub fee_authority: UncheckedAccount<'info>,
+
+    #[account(mut, token::mint = config.mint, token::authority = payer)]
+    pub payer_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = config.mint)]
+    pub merchant_tokens: Account<'info, TokenAccount>,
+
+    // SECURITY: Fees may only land in accounts the protocol PDA controls
+    #[account(
+        mut,
+        token::mint = config.mint,
+        constraint = fee_recipient.owner == fee_authority.key() @ ErrorCode::InvalidFeeRecipient
+    )]
+    pub fee_recipient: Account<'info, TokenAccount>,
+
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SecurePayOptionalFee<'info> {
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Fee authority PDA for this config; only its address is used
+    #[account(seeds = [b"fee_authority", config.key().as_ref()], bump = config.fee_authority_bump)]
+    pub fee_authority: UncheckedAccount<'info>,
+
+    #[account(mut, token::mint = config.mint, token::authority = payer)]
+    pub payer_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = config.mint)]
+    pub merchant_tokens: Account<'info, TokenAccount>,
+
+    /// Checked like `SecurePay`'s when present. SECURITY: Absent only if
+    /// `config.fee_waivable`, which the handler checks
+    #[account(
+        mut,
+        token::mint = config.mint,
+        constraint = fee_recipient.owner == fee_authority.key() @ ErrorCode::InvalidFeeRecipient
+    )]
+    pub fee_recipient: Option<Account<'info, TokenAccount>>,
+
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,50 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerablePay<'info> {
+    pub config: Account<'info, Config>,
+
+    #[account(mut, token::mint = config.mint, token::authority = payer)]
+    pub payer_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = config.mint)]
+    pub merchant_tokens: Account<'info, TokenAccount>,
+
+    // VULNERABILITY: Mint checked, owner authority never checked
+    #[account(mut, token::mint = config.mint)]
+    pub fee_recipient: Account<'info, TokenAccount>,
+
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerablePayOptionalFee<'info> {
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Fee authority PDA for this config; only its address is used
+    #[account(seeds = [b"fee_authority", config.key().as_ref()], bump = config.fee_authority_bump)]
+    pub fee_authority: UncheckedAccount<'info>,
+
+    #[account(mut, token::mint = config.mint, token::authority = payer)]
+    pub payer_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = config.mint)]
+    pub merchant_tokens: Account<'info, TokenAccount>,
+
+    /// Checked like `SecurePay`'s when present. VULNERABILITY: The payer
+    /// decides whether it is present, and absent means no fee
+    #[account(
+        mut,
+        token::mint = config.mint,
+        constraint = fee_recipient.owner == fee_authority.key() @ ErrorCode::InvalidFeeRecipient
+    )]
+    pub fee_recipient: Option<Account<'info, TokenAccount>>,
+
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
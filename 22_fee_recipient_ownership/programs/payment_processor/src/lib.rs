@@ -0,0 +1,260 @@
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod optional_fee_tests;
+
+/// Basis points in 100%
+pub const BPS: u64 = 10_000;
+
+#[feature_gated]
+#[program]
+pub mod payment_processor {
+    use super::*;
+
+    /// Configure a payment processor for one mint
+    ///
+    /// Creates the protocol fee authority PDA's fee vault. Fees may only be
+    /// moved out of accounts that authority owns by `withdraw_fees`, which
+    /// the admin must sign.
+    pub fn initialize_config(ctx: Context<InitializeConfig>, fee_bps: u16) -> Result<()> {
+        require!(u64::from(fee_bps) <= BPS, ErrorCode::InvalidFee);
+
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.mint = ctx.accounts.mint.key();
+        config.fee_bps = fee_bps;
+        config.fee_authority_bump = ctx.bumps.fee_authority;
+        config.bump = ctx.bumps.config;
+        config.fee_waivable = false;
+
+        msg!("Payment processor configured with a {} bps fee", fee_bps);
+        Ok(())
+    }
+
+    /// Withdraw accumulated fees; admin only, signed by the fee authority PDA
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        let config_key = ctx.accounts.config.key();
+        let seeds: &[&[u8]] = &[b"fee_authority", config_key.as_ref(), &[ctx.accounts.config.fee_authority_bump]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.fee_vault.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.fee_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        msg!("Admin withdrew {} in fees", amount);
+        Ok(())
+    }
+
+    /// Allow or forbid payments that omit the fee account; admin only
+    ///
+    /// Waived payments pay no fee at all, so this is the protocol's choice
+    /// to make - for a promotion, or a fee-free config - never the payer's.
+    pub fn set_fee_waiver(ctx: Context<SetFeeWaiver>, fee_waivable: bool) -> Result<()> {
+        ctx.accounts.config.fee_waivable = fee_waivable;
+
+        msg!("Fee waiver {}", if fee_waivable { "allowed" } else { "forbidden" });
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Pay a merchant, sending the protocol fee to any token account of the mint
+    ///
+    /// Security Issue: `fee_recipient` is only checked to hold the right
+    /// mint. Its owner authority is never compared with the protocol's fee
+    /// authority PDA, so a payer can name their own token account and keep
+    /// the fee, and a fee account owned by a deployer's key rather than the
+    /// PDA can be drained or reassigned without going through
+    /// `withdraw_fees`.
+    #[vulnerable]
+    pub fn vulnerable_pay(ctx: Context<VulnerablePay>, amount: u64) -> Result<()> {
+        let fee = fee_for(amount, ctx.accounts.config.fee_bps)?;
+
+        transfer_from_payer(
+            &ctx.accounts.payer_tokens,
+            &ctx.accounts.merchant_tokens,
+            &ctx.accounts.payer,
+            &ctx.accounts.token_program,
+            amount - fee,
+        )?;
+        // VULNERABILITY: Fee goes wherever the payer points it
+        transfer_from_payer(
+            &ctx.accounts.payer_tokens,
+            &ctx.accounts.fee_recipient,
+            &ctx.accounts.payer,
+            &ctx.accounts.token_program,
+            fee,
+        )?;
+
+        msg!("Paid {} to merchant and {} in fees to {}", amount - fee, fee, ctx.accounts.fee_recipient.owner);
+        Ok(())
+    }
+
+    /// VULNERABLE: Pay a merchant, with the fee account optional
+    ///
+    /// Security Issue: `fee_recipient` is an optional account, and the
+    /// handler reads its absence as "no fee to collect". Whether an optional
+    /// account is present is up to whoever builds the transaction, so every
+    /// payer can omit it and pay the merchant the whole amount, fee-free.
+    /// The constraints on `fee_recipient` only run when it is present.
+    #[vulnerable]
+    pub fn vulnerable_pay_optional_fee(ctx: Context<VulnerablePayOptionalFee>, amount: u64) -> Result<()> {
+        // VULNERABILITY: No fee account means no fee
+        let fee = match ctx.accounts.fee_recipient {
+            Some(_) => fee_for(amount, ctx.accounts.config.fee_bps)?,
+            None => 0,
+        };
+
+        transfer_from_payer(
+            &ctx.accounts.payer_tokens,
+            &ctx.accounts.merchant_tokens,
+            &ctx.accounts.payer,
+            &ctx.accounts.token_program,
+            amount - fee,
+        )?;
+        if let Some(fee_recipient) = &ctx.accounts.fee_recipient {
+            transfer_from_payer(
+                &ctx.accounts.payer_tokens,
+                fee_recipient,
+                &ctx.accounts.payer,
+                &ctx.accounts.token_program,
+                fee,
+            )?;
+        }
+
+        msg!("Paid {} to merchant and {} in fees", amount - fee, fee);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that validates the fee
+    // recipient's owner on every payout.
+
+    /// SECURE: Pay a merchant, sending the protocol fee to an account the fee authority owns
+    ///
+    /// Security Fix: `fee_recipient.owner` must equal the fee authority PDA,
+    /// checked on every payout. Only this program can sign for that PDA,
+    /// and it only does so in the admin-gated `withdraw_fees`, so fees can
+    /// neither be diverted at payment time nor moved afterwards by anyone
+    /// holding an outside key.
+    #[secure]
+    pub fn secure_pay(ctx: Context<SecurePay>, amount: u64) -> Result<()> {
+        let fee = fee_for(amount, ctx.accounts.config.fee_bps)?;
+
+        transfer_from_payer(
+            &ctx.accounts.payer_tokens,
+            &ctx.accounts.merchant_tokens,
+            &ctx.accounts.payer,
+            &ctx.accounts.token_program,
+            amount - fee,
+        )?;
+        // SECURITY: `fee_recipient` is owned by the fee authority PDA (account constraint)
+        transfer_from_payer(
+            &ctx.accounts.payer_tokens,
+            &ctx.accounts.fee_recipient,
+            &ctx.accounts.payer,
+            &ctx.accounts.token_program,
+            fee,
+        )?;
+
+        msg!("Paid {} to merchant and {} in fees", amount - fee, fee);
+        Ok(())
+    }
+
+    /// SECURE: Pay a merchant, with the fee account optional only where the config says so
+    ///
+    /// Security Fix: An absent `fee_recipient` waives the fee only when the
+    /// admin has set `config.fee_waivable`; otherwise the payment fails.
+    /// Leaving an account out is something any payer can do, so it can only
+    /// select behaviour the protocol has already allowed.
+    #[secure]
+    pub fn secure_pay_optional_fee(ctx: Context<SecurePayOptionalFee>, amount: u64) -> Result<()> {
+        let fee = match ctx.accounts.fee_recipient {
+            Some(_) => fee_for(amount, ctx.accounts.config.fee_bps)?,
+            // SECURITY: Omission is a waiver only if the config allows waivers
+            None => {
+                require!(ctx.accounts.config.fee_waivable, ErrorCode::FeeRecipientRequired);
+                0
+            }
+        };
+
+        transfer_from_payer(
+            &ctx.accounts.payer_tokens,
+            &ctx.accounts.merchant_tokens,
+            &ctx.accounts.payer,
+            &ctx.accounts.token_program,
+            amount - fee,
+        )?;
+        // SECURITY: `fee_recipient`, when present, is owned by the fee authority PDA (account constraint)
+        if let Some(fee_recipient) = &ctx.accounts.fee_recipient {
+            transfer_from_payer(
+                &ctx.accounts.payer_tokens,
+                fee_recipient,
+                &ctx.accounts.payer,
+                &ctx.accounts.token_program,
+                fee,
+            )?;
+        }
+
+        msg!("Paid {} to merchant and {} in fees", amount - fee, fee);
+        Ok(())
+    }
+}
+
+/// Protocol fee on `amount`, rounded down; never more than `amount`
+fn fee_for(amount: u64, fee_bps: u16) -> Result<u64> {
+    let fee = u128::from(amount) * u128::from(fee_bps) / u128::from(BPS);
+    u64::try_from(fee).map_err(|_| error!(ErrorCode::ArithmeticOverflow))
+}
+
+/// Transfer `amount` from the payer's token account
+fn transfer_from_payer<'info>(
+    from: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    payer: &Signer<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    token::transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Transfer {
+                from: from.to_account_info(),
+                to: to.to_account_info(),
+                authority: payer.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
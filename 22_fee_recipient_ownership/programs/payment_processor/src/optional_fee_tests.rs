@@ -0,0 +1,125 @@
+//! Paying with the fee account left out: a waiver the vulnerable handler
+//! grants every payer, and the secure handler only where the config does.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use secref_testkit::{execute, Fixture, TestAccount};
+
+use crate::{instruction, Config, ErrorCode};
+
+const AMOUNT: u64 = 1_000;
+/// 1% of `AMOUNT`, at the config's 100 bps
+const FEE: u64 = 10;
+
+/// A payer holding `AMOUNT` under a 1% config, with the fee account present
+fn payment(fee_waivable: bool) -> Fixture {
+    let payer = TestAccount::signer();
+    let mint = Pubkey::new_unique();
+    let config_key = Pubkey::new_unique();
+    let (fee_authority, fee_authority_bump) =
+        Pubkey::find_program_address(&[b"fee_authority", config_key.as_ref()], &crate::ID);
+    let config = TestAccount::anchor(&Config {
+        admin: Pubkey::new_unique(),
+        mint,
+        fee_bps: 100,
+        fee_authority_bump,
+        bump: 0,
+        fee_waivable,
+    })
+    .at(config_key);
+    Fixture::new()
+        .with("config", config)
+        .with("fee_authority", TestAccount::system(0).at(fee_authority))
+        .with("payer_tokens", TestAccount::token_account(mint, payer.key, AMOUNT))
+        .with("merchant_tokens", TestAccount::token_account(mint, Pubkey::new_unique(), 0))
+        .with("fee_recipient", TestAccount::token_account(mint, fee_authority, 0))
+        .with("payer", payer)
+        .with("token_program", TestAccount::token_program())
+}
+
+/// Leave the fee account out, as a client passing `None` would
+fn without_fee_account(mut fixture: Fixture) -> Fixture {
+    *fixture.account_mut("fee_recipient") = TestAccount::program(crate::ID);
+    fixture
+}
+
+fn tokens(fixture: &Fixture, name: &str) -> u64 {
+    fixture.state::<TokenAccount>(name).amount
+}
+
+#[test]
+fn vulnerable_payment_without_a_fee_account_pays_no_fee() {
+    let mut fixture = without_fee_account(payment(false));
+
+    execute!(&mut fixture, instruction::VulnerablePayOptionalFee { amount: AMOUNT }).unwrap();
+
+    // The config never waived fees, yet the merchant received the whole amount
+    assert_eq!(tokens(&fixture, "merchant_tokens"), AMOUNT);
+}
+
+#[test]
+fn both_payments_with_a_fee_account_charge_the_fee() {
+    let mut vulnerable = payment(false);
+    execute!(&mut vulnerable, instruction::VulnerablePayOptionalFee { amount: AMOUNT }).unwrap();
+    let mut secure = payment(false);
+    execute!(&mut secure, instruction::SecurePayOptionalFee { amount: AMOUNT }).unwrap();
+
+    for fixture in [vulnerable, secure] {
+        assert_eq!(tokens(&fixture, "merchant_tokens"), AMOUNT - FEE);
+        assert_eq!(tokens(&fixture, "fee_recipient"), FEE);
+        assert_eq!(tokens(&fixture, "payer_tokens"), 0);
+    }
+}
+
+#[test]
+fn secure_payment_without_a_fee_account_requires_a_waiver() {
+    let mut fixture = without_fee_account(payment(false));
+
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecurePayOptionalFee { amount: AMOUNT }),
+        Err(Error::from(ErrorCode::FeeRecipientRequired).into())
+    );
+    assert_eq!(tokens(&fixture, "payer_tokens"), AMOUNT);
+}
+
+#[test]
+fn secure_payment_without_a_fee_account_is_fee_free_where_waived() {
+    let mut fixture = without_fee_account(payment(true));
+
+    execute!(&mut fixture, instruction::SecurePayOptionalFee { amount: AMOUNT }).unwrap();
+
+    assert_eq!(tokens(&fixture, "merchant_tokens"), AMOUNT);
+}
+
+#[test]
+fn a_waiver_does_not_stop_payers_who_include_the_fee_account_paying_it() {
+    let mut fixture = payment(true);
+
+    execute!(&mut fixture, instruction::SecurePayOptionalFee { amount: AMOUNT }).unwrap();
+
+    assert_eq!(tokens(&fixture, "fee_recipient"), FEE);
+}
+
+#[test]
+fn only_the_admin_sets_the_fee_waiver() {
+    let admin = TestAccount::signer();
+    let config = TestAccount::anchor(&Config {
+        admin: admin.key,
+        mint: Pubkey::new_unique(),
+        fee_bps: 100,
+        fee_authority_bump: 0,
+        bump: 0,
+        fee_waivable: false,
+    });
+    let mut fixture = Fixture::new().with("config", config).with("admin", admin);
+
+    execute!(&mut fixture, instruction::SetFeeWaiver { fee_waivable: true }).unwrap();
+    assert!(fixture.state::<Config>("config").fee_waivable);
+
+    *fixture.account_mut("admin") = TestAccount::signer();
+    assert_eq!(
+        execute!(&mut fixture, instruction::SetFeeWaiver { fee_waivable: false }),
+        Err(Error::from(anchor_lang::error::ErrorCode::ConstraintHasOne).into())
+    );
+    assert!(fixture.state::<Config>("config").fee_waivable);
+}
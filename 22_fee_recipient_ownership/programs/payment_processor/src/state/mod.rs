@@ -0,0 +1,19 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Config {
+    /// Admin allowed to withdraw fees (32 bytes)
+    pub admin: Pubkey,
+    /// Mint payments are made in (32 bytes)
+    pub mint: Pubkey,
+    /// Protocol fee in basis points (2 bytes)
+    pub fee_bps: u16,
+    /// Bump of the fee authority PDA (1 byte)
+    pub fee_authority_bump: u8,
+    /// PDA bump (1 byte)
+    pub bump: u8,
+    /// Whether a payment may omit its fee account and pay no fee (1 byte)
+    pub fee_waivable: bool,
+}
@@ -0,0 +1,48 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+//!
+//! The route's pools arrive as `remaining_accounts`, outside the context;
+//! the handler's registry check covers them.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_owner_violation, assert_seeds_violation, assert_signer_violation, Fixture, TestAccount};
+
+use crate::{Registry, SecureSwapRoute, Trader, ASSET_COUNT};
+
+fn swap_fixture() -> Fixture {
+    let owner = TestAccount::signer();
+    let (registry, registry_bump) = Pubkey::find_program_address(&[b"registry"], &crate::ID);
+    let (trader, trader_bump) = Pubkey::find_program_address(&[b"trader", owner.key.as_ref()], &crate::ID);
+    Fixture::new()
+        .with(
+            "registry",
+            TestAccount::anchor(&Registry {
+                admin: Pubkey::new_unique(),
+                pools: vec![Pubkey::new_unique()],
+                bump: registry_bump,
+            })
+            .at(registry),
+        )
+        .with(
+            "trader",
+            TestAccount::anchor(&Trader {
+                owner: owner.key,
+                balances: [1_000; ASSET_COUNT],
+                bump: trader_bump,
+            })
+            .at(trader),
+        )
+        .with("owner", owner)
+}
+
+#[test]
+fn secure_swap_route_checks_the_canonical_registry() {
+    assert_seeds_violation!(SecureSwapRoute, swap_fixture(), "registry");
+    assert_owner_violation!(SecureSwapRoute, swap_fixture(), "registry");
+}
+
+#[test]
+fn secure_swap_route_trades_the_owners_balances() {
+    assert_seeds_violation!(SecureSwapRoute, swap_fixture(), "trader");
+    assert_seeds_violation!(SecureSwapRoute, swap_fixture(), "owner");
+    assert_signer_violation!(SecureSwapRoute, swap_fixture(), "owner");
+}
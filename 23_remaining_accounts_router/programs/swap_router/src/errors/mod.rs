@@ -0,0 +1,27 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Pool is already registered")]
+    PoolAlreadyRegistered,
+    #[msg("Registry is full")]
+    RegistryFull,
+    #[msg("Pool is not in the registry")]
+    PoolNotRegistered,
+    #[msg("Route is empty or its hops do not connect")]
+    InvalidRoute,
+    #[msg("Unknown asset")]
+    InvalidAsset,
+    #[msg("Fee must not exceed 100%")]
+    InvalidFee,
+    #[msg("Pool reserves must be non-zero")]
+    EmptyPool,
+    #[msg("Insufficient balance")]
+    InsufficientBalance,
+    #[msg("Output below the minimum")]
+    SlippageExceeded,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
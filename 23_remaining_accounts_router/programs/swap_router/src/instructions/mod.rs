@@ -0,0 +1,102 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializeRegistry<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 4 + 32 * MAX_REGISTERED_POOLS + 1, // discriminator + admin + pools + bump
+        seeds = [b"registry"],
+        bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterPool<'info> {
+    #[account(mut, seeds = [b"registry"], bump = registry.bump, has_one = admin)]
+    pub registry: Account<'info, Registry>,
+
+    pub pool: Account<'info, Pool>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OpenTrader<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 32 + 8 * ASSET_COUNT + 1, // discriminator + owner + balances + bump
+        seeds = [b"trader", owner.key().as_ref()],
+        bump
+    )]
+    pub trader: Account<'info, Trader>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundTrader<'info> {
+    #[account(seeds = [b"registry"], bump = registry.bump, has_one = admin)]
+    pub registry: Account<'info, Registry>,
+
+    #[account(mut)]
+    pub trader: Account<'info, Trader>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CreatePool<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + 32 + 8 + 1 + 1 + 8 + 8 + 2 + 8 + 1, // discriminator + creator + id + asset_in + asset_out + reserve_in + reserve_out + fee_bps + fees_owed + bump
+        seeds = [b"pool", creator.key().as_ref(), &id.to_le_bytes()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut, seeds = [b"trader", creator.key().as_ref()], bump = creator_trader.bump)]
+    pub creator_trader: Account<'info, Trader>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFees<'info> {
+    #[account(mut, has_one = creator)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut, seeds = [b"trader", creator.key().as_ref()], bump = creator_trader.bump)]
+    pub creator_trader: Account<'info, Trader>,
+
+    pub creator: Signer<'info>,
+}
@@ -0,0 +1,15 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+/// Route pools are passed as writable `remaining_accounts`, in hop order
+#[derive(Accounts)]
+pub struct SecureSwapRoute<'info> {
+    #[account(seeds = [b"registry"], bump = registry.bump)]
+    pub registry: Account<'info, Registry>,
+
+    #[account(mut, seeds = [b"trader", owner.key().as_ref()], bump = trader.bump)]
+    pub trader: Account<'info, Trader>,
+
+    pub owner: Signer<'info>,
+}
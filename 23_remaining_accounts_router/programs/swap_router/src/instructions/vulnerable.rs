@@ -0,0 +1,12 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+/// Route pools are passed as writable `remaining_accounts`, in hop order
+#[derive(Accounts)]
+pub struct SwapRoute<'info> {
+    #[account(mut, seeds = [b"trader", owner.key().as_ref()], bump = trader.bump)]
+    pub trader: Account<'info, Trader>,
+
+    pub owner: Signer<'info>,
+}
@@ -0,0 +1,42 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Pool, Registry, Trader, ASSET_COUNT};
+
+#[test]
+fn registry_layout_is_stable() {
+    let registry = Registry {
+        admin: Pubkey::new_from_array([1; 32]),
+        pools: vec![Pubkey::new_from_array([2; 32]), Pubkey::new_from_array([3; 32])],
+        bump: 65,
+    };
+    assert_golden!("Registry", golden::anchor(&registry));
+}
+
+#[test]
+fn trader_layout_is_stable() {
+    let trader = Trader {
+        owner: Pubkey::new_from_array([5; 32]),
+        balances: [0x6162636465666768; ASSET_COUNT],
+        bump: 113,
+    };
+    assert_golden!("Trader", golden::anchor(&trader));
+}
+
+#[test]
+fn pool_layout_is_stable() {
+    let pool = Pool {
+        creator: Pubkey::new_from_array([8; 32]),
+        id: 0x9192939495969798,
+        asset_in: 161,
+        asset_out: 177,
+        reserve_in: 0xc1c2c3c4c5c6c7c8,
+        reserve_out: 0xd1d2d3d4d5d6d7d8,
+        fee_bps: 0xe1e2,
+        fees_owed: 0xf1f2f3f4f5f6f7f8,
+        bump: 1,
+    };
+    assert_golden!("Pool", golden::anchor(&pool));
+}
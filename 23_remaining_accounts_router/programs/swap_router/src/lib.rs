@@ -0,0 +1,244 @@
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Basis points in 100%
+pub const BPS: u64 = 10_000;
+
+/// Number of assets traders can hold
+pub const ASSET_COUNT: usize = 4;
+
+/// Maximum number of pools the registry can list
+pub const MAX_REGISTERED_POOLS: usize = 16;
+
+/// A router that swaps through a sequence of constant-product pools.
+///
+/// Pools are permissionless: anyone can create one from their own
+/// balances and set its fee. The router takes the pools for a route in
+/// `remaining_accounts`, so a single instruction can serve routes of any
+/// length. Balances are tracked as numbers in `Trader` accounts rather
+/// than as tokens, so the example can focus on route validation.
+#[feature_gated]
+#[program]
+pub mod swap_router {
+    use super::*;
+
+    /// Create the pool registry; the signer becomes its admin
+    pub fn initialize_registry(ctx: Context<InitializeRegistry>) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        registry.admin = ctx.accounts.admin.key();
+        registry.pools = Vec::new();
+        registry.bump = ctx.bumps.registry;
+
+        msg!("Registry initialized with admin: {}", registry.admin);
+        Ok(())
+    }
+
+    /// Add a reviewed pool to the registry; admin only
+    pub fn register_pool(ctx: Context<RegisterPool>) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        let pool = ctx.accounts.pool.key();
+
+        require!(!registry.pools.contains(&pool), ErrorCode::PoolAlreadyRegistered);
+        require!(registry.pools.len() < MAX_REGISTERED_POOLS, ErrorCode::RegistryFull);
+        registry.pools.push(pool);
+
+        msg!("Registered pool {}", pool);
+        Ok(())
+    }
+
+    /// Open a balance account for the signer
+    pub fn open_trader(ctx: Context<OpenTrader>) -> Result<()> {
+        let trader = &mut ctx.accounts.trader;
+        trader.owner = ctx.accounts.owner.key();
+        trader.balances = [0; ASSET_COUNT];
+        trader.bump = ctx.bumps.trader;
+        Ok(())
+    }
+
+    /// Credit a trader with `amount` of `asset`; admin only
+    pub fn fund_trader(ctx: Context<FundTrader>, asset: u8, amount: u64) -> Result<()> {
+        let balance = balance_mut(&mut ctx.accounts.trader, asset)?;
+        *balance = balance.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Create a pool that swaps `asset_in` for `asset_out`, seeded from the creator's balances
+    ///
+    /// `fee_bps` of every input is kept for the creator, who can claim it
+    /// with `claim_fees`.
+    pub fn create_pool(
+        ctx: Context<CreatePool>,
+        id: u64,
+        asset_in: u8,
+        asset_out: u8,
+        reserve_in: u64,
+        reserve_out: u64,
+        fee_bps: u16,
+    ) -> Result<()> {
+        require!(u64::from(fee_bps) <= BPS, ErrorCode::InvalidFee);
+        require!(reserve_in > 0, ErrorCode::EmptyPool);
+        require!(reserve_out > 0, ErrorCode::EmptyPool);
+
+        let trader = &mut ctx.accounts.creator_trader;
+        let balance = balance_mut(trader, asset_in)?;
+        *balance = balance.checked_sub(reserve_in).ok_or(ErrorCode::InsufficientBalance)?;
+        let balance = balance_mut(trader, asset_out)?;
+        *balance = balance.checked_sub(reserve_out).ok_or(ErrorCode::InsufficientBalance)?;
+
+        let pool = &mut ctx.accounts.pool;
+        pool.creator = ctx.accounts.creator.key();
+        pool.id = id;
+        pool.asset_in = asset_in;
+        pool.asset_out = asset_out;
+        pool.reserve_in = reserve_in;
+        pool.reserve_out = reserve_out;
+        pool.fee_bps = fee_bps;
+        pool.fees_owed = 0;
+        pool.bump = ctx.bumps.pool;
+
+        msg!("Pool {} created: asset {} → asset {} at {} bps", pool.key(), asset_in, asset_out, fee_bps);
+        Ok(())
+    }
+
+    /// Move a pool's accumulated fees to its creator's balance
+    pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        let fees = pool.fees_owed;
+        pool.fees_owed = 0;
+
+        let balance = balance_mut(&mut ctx.accounts.creator_trader, pool.asset_in)?;
+        *balance = balance.checked_add(fees).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Creator claimed {} in fees", fees);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Swap through every pool in `remaining_accounts`
+    ///
+    /// Security Issue: Each hop is deserialized with `Account::try_from`,
+    /// which checks the owner and discriminator - so every hop really is
+    /// one of this program's pools - but pools are permissionless. Whoever
+    /// builds the route (an aggregator API, a frontend, a compromised
+    /// dependency) can insert a pool they created with a 99% fee, and the
+    /// router swaps through it. `min_amount_out` does not help when it is
+    /// computed from a quote over the same poisoned route.
+    #[vulnerable]
+    pub fn vulnerable_swap_route<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapRoute<'info>>,
+        asset_in: u8,
+        amount_in: u64,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        require!(!ctx.remaining_accounts.is_empty(), ErrorCode::InvalidRoute);
+
+        let balance = balance_mut(&mut ctx.accounts.trader, asset_in)?;
+        *balance = balance.checked_sub(amount_in).ok_or(ErrorCode::InsufficientBalance)?;
+
+        let mut asset = asset_in;
+        let mut amount = amount_in;
+        for pool_info in ctx.remaining_accounts.iter() {
+            // VULNERABILITY: Any pool of this program is accepted as a hop
+            let mut pool = Account::<Pool>::try_from(pool_info)?;
+            require!(pool.asset_in == asset, ErrorCode::InvalidRoute);
+
+            amount = execute_hop(&mut pool, amount)?;
+            asset = pool.asset_out;
+            pool.exit(&crate::ID)?;
+        }
+
+        require!(amount >= min_amount_out, ErrorCode::SlippageExceeded);
+        let balance = balance_mut(&mut ctx.accounts.trader, asset)?;
+        *balance = balance.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Swapped {} of asset {} for {} of asset {}", amount_in, asset_in, amount, asset);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that validates every hop.
+
+    /// SECURE: Swap through registered pools only
+    ///
+    /// Security Fix: Every hop must appear in the registry PDA, which only
+    /// the admin can add to. An attacker can still create pools, and can
+    /// still put them in a route, but the router refuses to trade through
+    /// them. Route continuity is checked the same way as before.
+    #[secure]
+    pub fn secure_swap_route<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SecureSwapRoute<'info>>,
+        asset_in: u8,
+        amount_in: u64,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        require!(!ctx.remaining_accounts.is_empty(), ErrorCode::InvalidRoute);
+
+        let balance = balance_mut(&mut ctx.accounts.trader, asset_in)?;
+        *balance = balance.checked_sub(amount_in).ok_or(ErrorCode::InsufficientBalance)?;
+
+        let registry = &ctx.accounts.registry;
+        let mut asset = asset_in;
+        let mut amount = amount_in;
+        for pool_info in ctx.remaining_accounts.iter() {
+            // SECURITY: Remaining accounts get the same scrutiny as declared ones
+            require!(registry.pools.contains(pool_info.key), ErrorCode::PoolNotRegistered);
+            let mut pool = Account::<Pool>::try_from(pool_info)?;
+            require!(pool.asset_in == asset, ErrorCode::InvalidRoute);
+
+            amount = execute_hop(&mut pool, amount)?;
+            asset = pool.asset_out;
+            pool.exit(&crate::ID)?;
+        }
+
+        require!(amount >= min_amount_out, ErrorCode::SlippageExceeded);
+        let balance = balance_mut(&mut ctx.accounts.trader, asset)?;
+        *balance = balance.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Swapped {} of asset {} for {} of asset {}", amount_in, asset_in, amount, asset);
+        Ok(())
+    }
+}
+
+/// Mutable balance of `asset`, or `InvalidAsset`
+fn balance_mut(trader: &mut Trader, asset: u8) -> Result<&mut u64> {
+    trader.balances.get_mut(usize::from(asset)).ok_or(error!(ErrorCode::InvalidAsset))
+}
+
+/// Swap `amount_in` through one constant-product pool, keeping the fee for its creator
+fn execute_hop(pool: &mut Pool, amount_in: u64) -> Result<u64> {
+    let fee = u64::try_from(u128::from(amount_in) * u128::from(pool.fee_bps) / u128::from(BPS))
+        .map_err(|_| error!(ErrorCode::ArithmeticOverflow))?;
+    let traded = amount_in - fee;
+
+    let amount_out = u128::from(pool.reserve_out) * u128::from(traded) / (u128::from(pool.reserve_in) + u128::from(traded));
+    // Strictly less than reserve_out, so the cast and subtraction cannot fail
+    let amount_out = amount_out as u64;
+
+    pool.reserve_in = pool.reserve_in.checked_add(traded).ok_or(ErrorCode::ArithmeticOverflow)?;
+    pool.reserve_out -= amount_out;
+    pool.fees_owed = pool.fees_owed.checked_add(fee).ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(amount_out)
+}
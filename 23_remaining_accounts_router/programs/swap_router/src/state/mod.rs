@@ -0,0 +1,45 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Registry {
+    /// Admin who reviews and registers pools (32 bytes)
+    pub admin: Pubkey,
+    /// Pools the secure router may trade through (4 + 32 * MAX_REGISTERED_POOLS bytes)
+    pub pools: Vec<Pubkey>,
+    /// PDA bump (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Trader {
+    /// Balance owner (32 bytes)
+    pub owner: Pubkey,
+    /// Balance of each asset (8 * ASSET_COUNT bytes)
+    pub balances: [u64; ASSET_COUNT],
+    /// PDA bump (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Pool {
+    /// Pool creator, who earns its fees (32 bytes)
+    pub creator: Pubkey,
+    /// Creator-chosen pool id (8 bytes)
+    pub id: u64,
+    /// Asset the pool accepts (1 byte)
+    pub asset_in: u8,
+    /// Asset the pool pays out (1 byte)
+    pub asset_out: u8,
+    /// Reserve of `asset_in` (8 bytes)
+    pub reserve_in: u64,
+    /// Reserve of `asset_out` (8 bytes)
+    pub reserve_out: u64,
+    /// Fee on input, in basis points (2 bytes)
+    pub fee_bps: u16,
+    /// Fees in `asset_in` awaiting `claim_fees` (8 bytes)
+    pub fees_owed: u64,
+    /// PDA bump (1 byte)
+    pub bump: u8,
+}
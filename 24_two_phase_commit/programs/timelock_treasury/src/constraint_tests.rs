@@ -0,0 +1,133 @@
+//! Every constraint on the secure contexts, tripped one account at a time,
+//! and the delay run down with the clock warped.
+//!
+//! The destination is bound by the intent hash in the handler, not by a
+//! constraint.
+
+use anchor_lang::prelude::*;
+use secref_guards::intent::Intent;
+use secref_guards::GuardError;
+use secref_testkit::runtime::{warp_by, warp_to};
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, execute,
+    Fixture, TestAccount,
+};
+
+use crate::{instruction, withdrawal_params, PendingWithdrawal, SecureExecuteWithdrawal, Treasury};
+
+const START: i64 = 1_700_000_000;
+const DELAY: i64 = 3_600;
+const AMOUNT: u64 = 1_000_000;
+const BALANCE: u64 = 10 * AMOUNT;
+
+fn execute_fixture() -> Fixture {
+    let admin = TestAccount::system(0);
+    let (treasury, treasury_bump) = Pubkey::find_program_address(&[b"treasury", admin.key.as_ref()], &crate::ID);
+    let (pending, pending_bump) = Pubkey::find_program_address(&[b"pending", treasury.as_ref()], &crate::ID);
+    Fixture::new()
+        .with(
+            "treasury",
+            TestAccount::anchor(&Treasury {
+                admin: admin.key,
+                delay_seconds: DELAY,
+                bump: treasury_bump,
+            })
+            .at(treasury),
+        )
+        .with(
+            "pending",
+            TestAccount::anchor(&PendingWithdrawal {
+                treasury,
+                intent: Intent::default(),
+                bump: pending_bump,
+            })
+            .at(pending),
+        )
+        .with("admin", admin)
+        .with("destination", TestAccount::system(0))
+        .with("executor", TestAccount::signer())
+}
+
+#[test]
+fn secure_execute_withdrawal_pays_from_the_admins_treasury() {
+    assert_seeds_violation!(SecureExecuteWithdrawal, execute_fixture(), "treasury");
+    assert_owner_violation!(SecureExecuteWithdrawal, execute_fixture(), "treasury");
+    assert_seeds_violation!(SecureExecuteWithdrawal, execute_fixture(), "admin");
+    assert_constraint_violation!(
+        SecureExecuteWithdrawal,
+        execute_fixture(),
+        "treasury" => |treasury| treasury.edit(|stored: &mut Treasury| stored.admin = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+}
+
+#[test]
+fn secure_execute_withdrawal_executes_the_treasurys_own_intent() {
+    assert_seeds_violation!(SecureExecuteWithdrawal, execute_fixture(), "pending");
+    assert_owner_violation!(SecureExecuteWithdrawal, execute_fixture(), "pending");
+    assert_constraint_violation!(
+        SecureExecuteWithdrawal,
+        execute_fixture(),
+        "pending" => |pending| pending.edit(|stored: &mut PendingWithdrawal| stored.treasury = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+}
+
+#[test]
+fn secure_execute_withdrawal_requires_an_executor_signature() {
+    assert_signer_violation!(SecureExecuteWithdrawal, execute_fixture(), "executor");
+}
+
+/// `execute_fixture` with BALANCE spare in the treasury and a withdrawal of AMOUNT to "destination" prepared at START
+fn prepared() -> Fixture {
+    warp_to(START);
+    let mut fixture = execute_fixture();
+    let admin = fixture.key("admin");
+    let params = withdrawal_params(AMOUNT, &fixture.key("destination"));
+    fixture.account_mut("pending").edit(|stored: &mut PendingWithdrawal| {
+        stored.intent = Intent::prepare(admin, params, START, DELAY).unwrap();
+    });
+    fixture.account_mut("treasury").lamports += BALANCE;
+    fixture
+}
+
+#[test]
+fn secure_execute_withdrawal_waits_out_the_delay() {
+    let mut fixture = prepared();
+    warp_by(DELAY - 1);
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureExecuteWithdrawal { amount: AMOUNT }),
+        Err(Error::from(GuardError::IntentNotReady).into())
+    );
+
+    warp_by(1);
+    execute!(&mut fixture, instruction::SecureExecuteWithdrawal { amount: AMOUNT }).unwrap();
+    assert_eq!(fixture.account("destination").lamports, AMOUNT);
+}
+
+#[test]
+fn vulnerable_execute_withdrawal_drains_the_treasury_once_any_delay_has_passed() {
+    let mut fixture = prepared();
+    *fixture.account_mut("destination") = TestAccount::system(0);
+    warp_by(DELAY - 1);
+    assert_eq!(
+        execute!(&mut fixture, instruction::VulnerableExecuteWithdrawal { amount: BALANCE }),
+        Err(Error::from(crate::ErrorCode::TimelockActive).into())
+    );
+
+    // The delay only held back the announced withdrawal; the executor's own one goes through
+    warp_by(1);
+    execute!(&mut fixture, instruction::VulnerableExecuteWithdrawal { amount: BALANCE }).unwrap();
+    assert_eq!(fixture.account("destination").lamports, BALANCE);
+}
+
+#[test]
+fn secure_execute_withdrawal_only_executes_the_announced_withdrawal() {
+    let mut fixture = prepared();
+    *fixture.account_mut("destination") = TestAccount::system(0);
+    warp_by(DELAY);
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureExecuteWithdrawal { amount: BALANCE }),
+        Err(Error::from(GuardError::IntentParamsMismatch).into())
+    );
+}
@@ -0,0 +1,15 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Delay must be non-negative")]
+    InvalidDelay,
+    #[msg("Withdrawal delay has not passed")]
+    TimelockActive,
+    #[msg("Treasury does not hold enough lamports")]
+    InsufficientFunds,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
@@ -0,0 +1,82 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 8 + 1, // discriminator + admin + delay_seconds + bump
+        seeds = [b"treasury", admin.key().as_ref()],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut, seeds = [b"treasury", treasury.admin.as_ref()], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PrepareWithdrawal<'info> {
+    #[account(has_one = admin, seeds = [b"treasury", admin.key().as_ref()], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+
+    // One pending withdrawal per treasury; cancel or execute it before preparing another
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + Intent::SPACE + 1, // discriminator + treasury + intent + bump
+        seeds = [b"pending", treasury.key().as_ref()],
+        bump
+    )]
+    pub pending: Account<'info, PendingWithdrawal>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelWithdrawal<'info> {
+    #[account(has_one = admin)]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        has_one = treasury,
+        seeds = [b"pending", treasury.key().as_ref()],
+        bump = pending.bump,
+        close = admin
+    )]
+    pub pending: Account<'info, PendingWithdrawal>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,28 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureExecuteWithdrawal<'info> {
+    #[account(mut, has_one = admin, seeds = [b"treasury", admin.key().as_ref()], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        has_one = treasury,
+        seeds = [b"pending", treasury.key().as_ref()],
+        bump = pending.bump,
+        close = admin
+    )]
+    pub pending: Account<'info, PendingWithdrawal>,
+
+    /// CHECK: Receives the pending account's rent; must be the treasury admin
+    #[account(mut)]
+    pub admin: UncheckedAccount<'info>,
+
+    /// CHECK: Bound to the prepared intent by the handler's hash check
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    pub executor: Signer<'info>,
+}
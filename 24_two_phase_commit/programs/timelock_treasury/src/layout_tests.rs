@@ -0,0 +1,32 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_guards::intent::Intent;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{PendingWithdrawal, Treasury};
+
+#[test]
+fn treasury_layout_is_stable() {
+    let treasury = Treasury {
+        admin: Pubkey::new_from_array([1; 32]),
+        delay_seconds: 0x2122232425262728,
+        bump: 49,
+    };
+    assert_golden!("Treasury", golden::anchor(&treasury));
+}
+
+#[test]
+fn pending_withdrawal_layout_is_stable() {
+    let pending_withdrawal = PendingWithdrawal {
+        treasury: Pubkey::new_from_array([4; 32]),
+        intent: Intent {
+            proposer: Pubkey::new_from_array([40; 32]),
+            params_hash: [41; 32],
+            prepared_at: 0x6162636465666768,
+            executable_at: 0x7172737475767778,
+        },
+        bump: 81,
+    };
+    assert_golden!("PendingWithdrawal", golden::anchor(&pending_withdrawal));
+}
@@ -0,0 +1,156 @@
+use anchor_lang::system_program::{self, Transfer};
+use secref_guards::intent::{hash_params, Intent};
+use secref_guards::rent::minimum_balance;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+#[feature_gated]
+#[program]
+pub mod timelock_treasury {
+    use super::*;
+
+    /// Create a treasury whose withdrawals wait `delay_seconds` after being prepared
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>, delay_seconds: i64) -> Result<()> {
+        require!(delay_seconds >= 0, ErrorCode::InvalidDelay);
+
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.admin = ctx.accounts.admin.key();
+        treasury.delay_seconds = delay_seconds;
+        treasury.bump = ctx.bumps.treasury;
+
+        msg!("Treasury initialized with a {}s withdrawal delay", delay_seconds);
+        Ok(())
+    }
+
+    /// Deposit `amount` lamports into the treasury
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.depositor.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    /// Phase 1: announce a withdrawal of `amount` lamports to `destination`
+    ///
+    /// Only the hash of the parameters is stored; the log announces them so
+    /// depositors and monitors can react during the delay. Anyone may
+    /// execute the withdrawal once the delay has passed.
+    pub fn prepare_withdrawal(ctx: Context<PrepareWithdrawal>, amount: u64, destination: Pubkey) -> Result<()> {
+        let treasury = &ctx.accounts.treasury;
+        let now = Clock::get()?.unix_timestamp;
+
+        let pending = &mut ctx.accounts.pending;
+        pending.treasury = treasury.key();
+        pending.intent = Intent::prepare(treasury.admin, withdrawal_params(amount, &destination), now, treasury.delay_seconds)?;
+        pending.bump = ctx.bumps.pending;
+
+        msg!(
+            "Prepared withdrawal of {} lamports to {}, executable at {}",
+            amount,
+            destination,
+            pending.intent.executable_at
+        );
+        Ok(())
+    }
+
+    /// Drop the pending withdrawal; admin only
+    pub fn cancel_withdrawal(_ctx: Context<CancelWithdrawal>) -> Result<()> {
+        msg!("Pending withdrawal cancelled");
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Phase 2 that enforces the delay but not the prepared parameters
+    ///
+    /// Security Issue: `amount` and `destination` come from the executor, and
+    /// nothing compares them with the prepared intent. Once any withdrawal
+    /// has waited out its delay, anyone can execute it for the whole
+    /// treasury to their own address. The delay still "works" - it just
+    /// delays a different operation from the one everyone saw announced.
+    #[vulnerable]
+    pub fn vulnerable_execute_withdrawal(ctx: Context<VulnerableExecuteWithdrawal>, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= ctx.accounts.pending.intent.executable_at, ErrorCode::TimelockActive);
+
+        // VULNERABILITY: `amount` and `destination` are never hashed and
+        // compared with `pending.intent.params_hash`
+        pay_out(&ctx.accounts.treasury.to_account_info(), &ctx.accounts.destination, amount)?;
+
+        msg!("Executed withdrawal of {} lamports to {}", amount, ctx.accounts.destination.key());
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that executes only the
+    // parameters recorded at prepare time.
+
+    /// SECURE: Phase 2 bound to the prepared parameters
+    ///
+    /// Security Fix: The executor's `amount` and `destination` are hashed the
+    /// same way as at prepare time and must match the stored intent, so the
+    /// only operation that can run is the one that was announced.
+    #[secure]
+    pub fn secure_execute_withdrawal(ctx: Context<SecureExecuteWithdrawal>, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let destination = &ctx.accounts.destination;
+
+        // SECURITY: Delay and parameters are both checked against the intent
+        ctx.accounts
+            .pending
+            .intent
+            .assert_executable(&withdrawal_params(amount, destination.key), now)?;
+
+        pay_out(&ctx.accounts.treasury.to_account_info(), destination, amount)?;
+
+        msg!("Executed withdrawal of {} lamports to {}", amount, destination.key());
+        Ok(())
+    }
+}
+
+/// Parameters of a withdrawal, as recorded in its intent
+fn withdrawal_params(amount: u64, destination: &Pubkey) -> [u8; 32] {
+    hash_params(&[&amount.to_le_bytes(), destination.as_ref()])
+}
+
+/// Move `amount` lamports out of the treasury, keeping it rent exempt
+fn pay_out<'info>(treasury: &AccountInfo<'info>, destination: &AccountInfo<'info>, amount: u64) -> Result<()> {
+    let reserve = minimum_balance(treasury.data_len())?;
+    let available = treasury.lamports().saturating_sub(reserve);
+    require!(amount <= available, ErrorCode::InsufficientFunds);
+
+    **treasury.try_borrow_mut_lamports()? = treasury.lamports() - amount;
+    **destination.try_borrow_mut_lamports()? = destination.lamports().checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
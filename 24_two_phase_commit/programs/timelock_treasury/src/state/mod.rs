@@ -0,0 +1,23 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Treasury {
+    /// Key allowed to prepare and cancel withdrawals (32 bytes)
+    pub admin: Pubkey,
+    /// Seconds between prepare and the earliest execute (8 bytes)
+    pub delay_seconds: i64,
+    /// PDA bump (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct PendingWithdrawal {
+    /// Treasury the withdrawal is drawn from (32 bytes)
+    pub treasury: Pubkey,
+    /// Hash of the prepared parameters and the earliest execution time (Intent::SPACE bytes)
+    pub intent: Intent,
+    /// PDA bump (1 byte)
+    pub bump: u8,
+}
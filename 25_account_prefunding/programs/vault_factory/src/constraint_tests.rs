@@ -0,0 +1,43 @@
+//! Every constraint on the context the secure instruction uses, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_constraint_violation, assert_seeds_violation, assert_signer_violation, try_accounts, Fixture, TestAccount,
+};
+
+use crate::CreateVault;
+
+fn create_fixture() -> Fixture {
+    let owner = TestAccount::signer();
+    let (vault, _) = Pubkey::find_program_address(&[b"vault", owner.key.as_ref()], &crate::ID);
+    Fixture::new()
+        .with("vault", TestAccount::system(0).at(vault))
+        .with("owner", owner)
+        .with("system_program", TestAccount::system_program())
+}
+
+#[test]
+fn create_vault_derives_the_vault_from_its_owner() {
+    assert_seeds_violation!(CreateVault, create_fixture(), "vault");
+    assert_seeds_violation!(CreateVault, create_fixture(), "owner");
+    assert_signer_violation!(CreateVault, create_fixture(), "owner");
+}
+
+#[test]
+fn create_vault_leaves_prefunded_addresses_to_the_handler() {
+    // The context cannot tell a griefer's lamports from none at all;
+    // `create_pda_account` in the handler is what copes with them
+    let mut fixture = create_fixture();
+    fixture.account_mut("vault").lamports = 1;
+    assert_eq!(try_accounts!(CreateVault, fixture), Ok(()));
+}
+
+#[test]
+fn create_vault_requires_the_system_program() {
+    assert_constraint_violation!(
+        CreateVault,
+        create_fixture(),
+        "system_program" => TestAccount::program(Pubkey::new_unique()),
+        ErrorCode::InvalidProgramId
+    );
+}
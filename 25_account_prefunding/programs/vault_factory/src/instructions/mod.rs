@@ -0,0 +1,15 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct CreateVault<'info> {
+    /// CHECK: Created by the handler; address fixed by the seeds
+    #[account(mut, seeds = [b"vault", owner.key().as_ref()], bump)]
+    pub vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,102 @@
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Size of a vault account
+pub const VAULT_SPACE: usize = 8 + 32 + 1; // discriminator + owner + bump
+
+#[feature_gated]
+#[program]
+pub mod vault_factory {
+    use super::*;
+    #[vulnerable]
+    use anchor_lang::system_program::{self, CreateAccount};
+    #[secure]
+    use secref_guards::account_creation::create_pda_account;
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Create the signer's vault with a bare `create_account`
+    ///
+    /// Security Issue: `create_account` fails with "account already in use"
+    /// if the address holds any lamports. The vault address is a PDA of the
+    /// owner's key, so anyone can compute it and send it one lamport first.
+    /// The lamports cannot be withdrawn without the program's signature, so
+    /// the owner can never create their vault.
+    #[vulnerable]
+    pub fn vulnerable_create_vault(ctx: Context<CreateVault>) -> Result<()> {
+        let owner = ctx.accounts.owner.key();
+        let bump = ctx.bumps.vault;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"vault", owner.as_ref(), &[bump]]];
+
+        // VULNERABILITY: Fails on any pre-funded address
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            Rent::get()?.minimum_balance(VAULT_SPACE),
+            VAULT_SPACE as u64,
+            &crate::ID,
+        )?;
+
+        write_vault(&ctx.accounts.vault, owner, bump)
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that creates the vault
+    // whether or not its address was funded first.
+
+    /// SECURE: Create the signer's vault, tolerating pre-funded addresses
+    ///
+    /// Security Fix: `create_pda_account` from `secref-guards` uses
+    /// `create_account` for empty addresses and transfer + allocate + assign
+    /// for funded ones. Pre-funding now only donates lamports to the owner.
+    #[secure]
+    pub fn secure_create_vault(ctx: Context<CreateVault>) -> Result<()> {
+        let owner = ctx.accounts.owner.key();
+        let bump = ctx.bumps.vault;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"vault", owner.as_ref(), &[bump]]];
+
+        // SECURITY: Falls back to allocate + assign when the address is funded
+        create_pda_account(
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &crate::ID,
+            VAULT_SPACE,
+            signer_seeds,
+        )?;
+
+        write_vault(&ctx.accounts.vault, owner, bump)
+    }
+}
+
+/// Write a fresh `Vault`, discriminator included, into a newly created account
+fn write_vault(vault: &AccountInfo, owner: Pubkey, bump: u8) -> Result<()> {
+    let mut data = vault.try_borrow_mut_data()?;
+    Vault { owner, bump }.try_serialize(&mut &mut data[..])
+}
@@ -0,0 +1,11 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Vault {
+    /// User the vault belongs to (32 bytes)
+    pub owner: Pubkey,
+    /// PDA bump (1 byte)
+    pub bump: u8,
+}
@@ -0,0 +1,40 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, Fixture,
+    TestAccount,
+};
+
+use crate::{SecureRecordPoints, Shard};
+
+fn record_fixture() -> Fixture {
+    let owner = TestAccount::signer();
+    let (shard, bump) = Pubkey::find_program_address(&[b"shard", owner.key.as_ref()], &crate::ID);
+    let shard = TestAccount::anchor(&Shard {
+        owner: owner.key,
+        pending_points: 0,
+        lifetime_points: 0,
+        bump,
+    })
+    .at(shard);
+    Fixture::new().with("shard", shard).with("owner", owner)
+}
+
+#[test]
+fn secure_record_points_writes_only_the_owners_shard() {
+    assert_seeds_violation!(SecureRecordPoints, record_fixture(), "shard");
+    assert_owner_violation!(SecureRecordPoints, record_fixture(), "shard");
+    assert_constraint_violation!(
+        SecureRecordPoints,
+        record_fixture(),
+        "shard" => |shard| shard.edit(|stored: &mut Shard| stored.owner = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+}
+
+#[test]
+fn secure_record_points_requires_the_shard_owner() {
+    assert_seeds_violation!(SecureRecordPoints, record_fixture(), "owner");
+    assert_signer_violation!(SecureRecordPoints, record_fixture(), "owner");
+}
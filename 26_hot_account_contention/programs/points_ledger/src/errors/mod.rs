@@ -0,0 +1,11 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Aggregation needs at least one shard")]
+    NoShards,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
@@ -0,0 +1,57 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializeLedger<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 8 + 8 + 1, // discriminator + total_points + last_aggregated_slot + bump
+        seeds = [b"ledger"],
+        bump
+    )]
+    pub ledger: Account<'info, Ledger>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenShard<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 32 + 8 + 8 + 1, // discriminator + owner + pending_points + lifetime_points + bump
+        seeds = [b"shard", owner.key().as_ref()],
+        bump
+    )]
+    pub shard: Account<'info, Shard>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Aggregate<'info> {
+    #[account(mut, seeds = [b"ledger"], bump = ledger.bump)]
+    pub ledger: Account<'info, Ledger>,
+
+    pub cranker: Signer<'info>,
+}
@@ -0,0 +1,11 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureRecordPoints<'info> {
+    #[account(mut, has_one = owner, seeds = [b"shard", owner.key().as_ref()], bump = shard.bump)]
+    pub shard: Account<'info, Shard>,
+
+    pub owner: Signer<'info>,
+}
@@ -0,0 +1,12 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableRecordPoints<'info> {
+    // VULNERABILITY: Every user writes the same account
+    #[account(mut, seeds = [b"ledger"], bump = ledger.bump)]
+    pub ledger: Account<'info, Ledger>,
+
+    pub user: Signer<'info>,
+}
@@ -0,0 +1,27 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Ledger, Shard};
+
+#[test]
+fn ledger_layout_is_stable() {
+    let ledger = Ledger {
+        total_points: 0x1112131415161718,
+        last_aggregated_slot: 0x2122232425262728,
+        bump: 49,
+    };
+    assert_golden!("Ledger", golden::anchor(&ledger));
+}
+
+#[test]
+fn shard_layout_is_stable() {
+    let shard = Shard {
+        owner: Pubkey::new_from_array([4; 32]),
+        pending_points: 0x5152535455565758,
+        lifetime_points: 0x6162636465666768,
+        bump: 113,
+    };
+    assert_golden!("Shard", golden::anchor(&shard));
+}
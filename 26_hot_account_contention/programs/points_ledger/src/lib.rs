@@ -0,0 +1,119 @@
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+#[feature_gated]
+#[program]
+pub mod points_ledger {
+    use super::*;
+
+    /// Create the global points ledger
+    pub fn initialize_ledger(ctx: Context<InitializeLedger>) -> Result<()> {
+        let ledger = &mut ctx.accounts.ledger;
+        ledger.total_points = 0;
+        ledger.last_aggregated_slot = 0;
+        ledger.bump = ctx.bumps.ledger;
+
+        msg!("Ledger initialized");
+        Ok(())
+    }
+
+    /// Create the signer's own points shard
+    pub fn open_shard(ctx: Context<OpenShard>) -> Result<()> {
+        let shard = &mut ctx.accounts.shard;
+        shard.owner = ctx.accounts.owner.key();
+        shard.pending_points = 0;
+        shard.lifetime_points = 0;
+        shard.bump = ctx.bumps.shard;
+
+        msg!("Shard opened for {}", shard.owner);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Record points straight into the global ledger
+    ///
+    /// Security Issue: Every call write-locks the one `ledger` account, so
+    /// the runtime runs all users' transactions one after another, and the
+    /// program's throughput is that of a single account. Write locks are
+    /// taken for any transaction that lists the account as writable - the
+    /// attacker does not even need to call this program - so spamming such
+    /// transactions delays or prices out every honest user.
+    #[vulnerable]
+    pub fn vulnerable_record_points(ctx: Context<VulnerableRecordPoints>, points: u64) -> Result<()> {
+        // VULNERABILITY: Shared write lock on every user transaction
+        let ledger = &mut ctx.accounts.ledger;
+        ledger.total_points = ledger.total_points.checked_add(points).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Recorded {} points for {}", points, ctx.accounts.user.key());
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that shards writes into
+    // per-user PDAs and aggregates them with a crank.
+
+    /// SECURE: Record points into the signer's own shard
+    ///
+    /// Security Fix: Only the user's shard is written, so users never share
+    /// a write lock and their transactions run in parallel. Spamming one
+    /// user's shard requires that user's signature.
+    #[secure]
+    pub fn secure_record_points(ctx: Context<SecureRecordPoints>, points: u64) -> Result<()> {
+        // SECURITY: The only writable account is owned by this user
+        let shard = &mut ctx.accounts.shard;
+        shard.pending_points = shard.pending_points.checked_add(points).ok_or(ErrorCode::ArithmeticOverflow)?;
+        shard.lifetime_points = shard.lifetime_points.checked_add(points).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Recorded {} points for {}", points, shard.owner);
+        Ok(())
+    }
+
+    /// Fold the pending points of the shards in `remaining_accounts` into the ledger
+    ///
+    /// Permissionless crank: the only transaction that write-locks the
+    /// ledger. Each shard is type-checked and its pending points zeroed, so
+    /// passing a shard twice or re-running the crank cannot double count.
+    #[secure]
+    pub fn aggregate<'info>(ctx: Context<'_, '_, 'info, 'info, Aggregate<'info>>) -> Result<()> {
+        require!(!ctx.remaining_accounts.is_empty(), ErrorCode::NoShards);
+
+        let mut collected: u64 = 0;
+        for shard_info in ctx.remaining_accounts.iter() {
+            // SECURITY: Owner and discriminator checked; any shard of this program is genuine
+            let mut shard = Account::<Shard>::try_from(shard_info)?;
+            collected = collected.checked_add(shard.pending_points).ok_or(ErrorCode::ArithmeticOverflow)?;
+            shard.pending_points = 0;
+            shard.exit(&crate::ID)?;
+        }
+
+        let ledger = &mut ctx.accounts.ledger;
+        ledger.total_points = ledger.total_points.checked_add(collected).ok_or(ErrorCode::ArithmeticOverflow)?;
+        ledger.last_aggregated_slot = Clock::get()?.slot;
+
+        msg!("Aggregated {} points from {} shards", collected, ctx.remaining_accounts.len());
+        Ok(())
+    }
+}
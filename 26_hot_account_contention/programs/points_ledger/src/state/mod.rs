@@ -0,0 +1,25 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Ledger {
+    /// Points folded in by the crank (8 bytes)
+    pub total_points: u64,
+    /// Slot of the last aggregation (8 bytes)
+    pub last_aggregated_slot: u64,
+    /// PDA bump (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Shard {
+    /// User the shard belongs to (32 bytes)
+    pub owner: Pubkey,
+    /// Points recorded since the last aggregation (8 bytes)
+    pub pending_points: u64,
+    /// All points ever recorded by this user (8 bytes)
+    pub lifetime_points: u64,
+    /// PDA bump (1 byte)
+    pub bump: u8,
+}
@@ -0,0 +1,21 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SpoofLogs<'info> {
+    pub attacker: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SpoofCpiEvent<'info> {
+    /// CHECK: The rewards program's event authority PDA
+    #[account(seeds = [b"__event_authority"], bump, seeds::program = rewards_distributor::ID)]
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: The rewards program, invoked directly
+    #[account(address = rewards_distributor::ID)]
+    pub rewards_program: UncheckedAccount<'info>,
+
+    pub attacker: Signer<'info>,
+}
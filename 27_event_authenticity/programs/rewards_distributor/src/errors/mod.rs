@@ -0,0 +1,9 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
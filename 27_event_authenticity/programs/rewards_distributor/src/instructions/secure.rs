@@ -0,0 +1,13 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+// `#[event_cpi]` adds the `event_authority` PDA and this program as accounts
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SecureGrantReward<'info> {
+    #[account(mut, has_one = admin, seeds = [b"rewards"], bump = pool.bump)]
+    pub pool: Account<'info, RewardsPool>,
+
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,11 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableGrantReward<'info> {
+    #[account(mut, has_one = admin, seeds = [b"rewards"], bump = pool.bump)]
+    pub pool: Account<'info, RewardsPool>,
+
+    pub admin: Signer<'info>,
+}
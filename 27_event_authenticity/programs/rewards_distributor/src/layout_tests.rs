@@ -0,0 +1,17 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::RewardsPool;
+
+#[test]
+fn rewards_pool_layout_is_stable() {
+    let rewards_pool = RewardsPool {
+        admin: Pubkey::new_from_array([1; 32]),
+        grants_issued: 0x2122232425262728,
+        total_granted: 0x3132333435363738,
+        bump: 65,
+    };
+    assert_golden!("RewardsPool", golden::anchor(&rewards_pool));
+}
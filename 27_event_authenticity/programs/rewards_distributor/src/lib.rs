@@ -0,0 +1,113 @@
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+#[feature_gated]
+#[program]
+pub mod rewards_distributor {
+    use super::*;
+
+    /// Create the rewards pool; the signer becomes the only granter
+    ///
+    /// Rewards are settled off-chain: a points backend indexes grant events
+    /// and credits each recipient. The events are the whole interface.
+    pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        pool.admin = ctx.accounts.admin.key();
+        pool.grants_issued = 0;
+        pool.total_granted = 0;
+        pool.bump = ctx.bumps.pool;
+
+        msg!("Rewards pool initialized with admin: {}", pool.admin);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Announce a grant through `msg!` and `emit!`
+    ///
+    /// Security Issue: Both end up as plain log lines - `Program log:` and
+    /// `Program data:` - and any program in any transaction can print the
+    /// same lines. The admin check here is real, but an indexer reading the
+    /// logs cannot tell a grant from this program apart from a copy printed
+    /// by `log_spoofer`, so the check protects nothing off-chain.
+    #[vulnerable]
+    pub fn vulnerable_grant_reward(ctx: Context<VulnerableGrantReward>, recipient: Pubkey, amount: u64) -> Result<()> {
+        let grant_id = record_grant(&mut ctx.accounts.pool, amount)?;
+
+        // VULNERABILITY: Log output is not authenticated
+        msg!("Reward granted: {} to {}", amount, recipient);
+        emit!(RewardGranted {
+            recipient,
+            amount,
+            grant_id,
+        });
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that emits events as a
+    // self-CPI signed by the program's event authority.
+
+    /// SECURE: Announce a grant with `emit_cpi!`
+    ///
+    /// Security Fix: `emit_cpi!` invokes this program with the event as
+    /// instruction data and the `[b"__event_authority"]` PDA as a signer.
+    /// Only this program can sign for that PDA, and Anchor's event handler
+    /// rejects the instruction without the signature, so an inner
+    /// instruction to this program carrying the event is proof the program
+    /// emitted it. Indexers read inner instructions, not logs.
+    #[secure]
+    pub fn secure_grant_reward(ctx: Context<SecureGrantReward>, recipient: Pubkey, amount: u64) -> Result<()> {
+        let grant_id = record_grant(&mut ctx.accounts.pool, amount)?;
+
+        // SECURITY: Verifiable from the transaction's inner instructions
+        emit_cpi!(RewardGranted {
+            recipient,
+            amount,
+            grant_id,
+        });
+        Ok(())
+    }
+}
+
+/// Count a grant against the pool and return its id
+fn record_grant(pool: &mut Account<RewardsPool>, amount: u64) -> Result<u64> {
+    let grant_id = pool.grants_issued;
+    pool.grants_issued = grant_id.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+    pool.total_granted = pool.total_granted.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(grant_id)
+}
+
+// ========================================
+// EVENTS
+// ========================================
+
+/// A reward the off-chain backend should credit to `recipient`
+#[event]
+pub struct RewardGranted {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub grant_id: u64,
+}
@@ -0,0 +1,15 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct RewardsPool {
+    /// Only key allowed to grant rewards (32 bytes)
+    pub admin: Pubkey,
+    /// Number of grants issued, also the next grant id (8 bytes)
+    pub grants_issued: u64,
+    /// Sum of all granted amounts (8 bytes)
+    pub total_granted: u64,
+    /// PDA bump (1 byte)
+    pub bump: u8,
+}
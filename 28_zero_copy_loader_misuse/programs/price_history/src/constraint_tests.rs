@@ -0,0 +1,41 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_signer_violation, Fixture, TestAccount,
+};
+
+use crate::{PriceHistory, SecureInitHistory, HISTORY_SPACE};
+
+fn init_fixture() -> Fixture {
+    Fixture::new()
+        .with("history", TestAccount::new(crate::ID, vec![0; HISTORY_SPACE]))
+        .with("authority", TestAccount::signer())
+}
+
+#[test]
+fn secure_init_history_rejects_initialized_histories() {
+    assert_constraint_violation!(
+        SecureInitHistory,
+        init_fixture(),
+        "history" => |history| history.data[..8].copy_from_slice(&PriceHistory::DISCRIMINATOR),
+        ErrorCode::ConstraintZero
+    );
+}
+
+#[test]
+fn secure_init_history_requires_a_rent_exempt_account_of_this_program() {
+    assert_owner_violation!(SecureInitHistory, init_fixture(), "history");
+    assert_constraint_violation!(
+        SecureInitHistory,
+        init_fixture(),
+        "history" => |history| history.lamports = 1,
+        ErrorCode::ConstraintRentExempt
+    );
+}
+
+#[test]
+fn secure_init_history_requires_an_authority_signature() {
+    assert_signer_violation!(SecureInitHistory, init_fixture(), "authority");
+}
@@ -0,0 +1,11 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Account is already initialized")]
+    AlreadyInitialized,
+    #[msg("Account is too small for the price history")]
+    AccountTooSmall,
+}
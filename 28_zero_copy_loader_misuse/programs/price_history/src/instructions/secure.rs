@@ -0,0 +1,12 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureInitHistory<'info> {
+    // Created by the client in the same transaction (too large for CPI creation)
+    #[account(zero)]
+    pub history: AccountLoader<'info, PriceHistory>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,12 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableInitHistory<'info> {
+    /// CHECK: VULNERABILITY - Raw bytes handled by the hand-rolled loaders
+    #[account(mut, owner = crate::ID)]
+    pub history: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,17 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{PriceHistory, HISTORY_LEN};
+
+#[test]
+fn price_history_layout_is_stable() {
+    let price_history = PriceHistory {
+        authority: Pubkey::new_from_array([1; 32]),
+        head: 0x2122232425262728,
+        count: 0x3132333435363738,
+        prices: [0x4142434445464748; HISTORY_LEN],
+    };
+    assert_golden!("PriceHistory", golden::zero_copy(&price_history));
+}
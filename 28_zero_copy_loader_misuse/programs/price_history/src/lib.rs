@@ -0,0 +1,115 @@
+use secref_prelude::*;
+
+pub mod raw_loader;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Prices kept in the ring buffer
+pub const HISTORY_LEN: usize = 64;
+
+/// Size of a price history account
+pub const HISTORY_SPACE: usize = 8 + 32 + 8 + 8 + 8 * HISTORY_LEN; // discriminator + authority + head + count + prices
+
+#[feature_gated]
+#[program]
+pub mod price_history {
+    use super::*;
+    #[vulnerable]
+    use crate::raw_loader::{init_without_zero_check, is_uninitialized, view_mut_skipping_discriminator};
+
+    /// Append `price` to the history, overwriting the oldest entry when full
+    ///
+    /// `AccountLoader` has already checked owner and discriminator, and
+    /// `has_one` the authority, so this only ever runs on initialized
+    /// histories.
+    pub fn push_price(ctx: Context<PushPrice>, price: u64) -> Result<()> {
+        let mut history = ctx.accounts.history.load_mut()?;
+        let head = history.head as usize;
+        history.prices[head] = price;
+        history.head = ((head + 1) % HISTORY_LEN) as u64;
+        history.count = (history.count + 1).min(HISTORY_LEN as u64);
+
+        msg!("Recorded price {} ({} stored)", price, history.count);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Initialize through a `load_mut`-style view
+    ///
+    /// Security Issue: The uninitialized check is right, but the fields are
+    /// written without the discriminator, so the account passes that same
+    /// check forever. Anyone can call this again on the victim's history
+    /// and become its authority, and `push_price` rejects it in the
+    /// meantime because `AccountLoader` finds no discriminator.
+    #[vulnerable]
+    pub fn vulnerable_init_skip_discriminator(ctx: Context<VulnerableInitHistory>) -> Result<()> {
+        let mut data = ctx.accounts.history.try_borrow_mut_data()?;
+        require!(is_uninitialized(&data), ErrorCode::AlreadyInitialized);
+
+        // VULNERABILITY: Writes the body, never the discriminator
+        let history = view_mut_skipping_discriminator::<PriceHistory>(&mut data)?;
+        history.authority = ctx.accounts.authority.key();
+
+        msg!("History initialized for {}", history.authority);
+        Ok(())
+    }
+
+    /// VULNERABLE: Initialize through a `load_init`-style writer without the zero check
+    ///
+    /// Security Issue: The discriminator is written, so the account is
+    /// usable, but nothing checks that it was zero first. Calling this on a
+    /// live history resets its prices and hands the attacker its authority.
+    #[vulnerable]
+    pub fn vulnerable_init_no_zero_check(ctx: Context<VulnerableInitHistory>) -> Result<()> {
+        let mut data = ctx.accounts.history.try_borrow_mut_data()?;
+
+        // VULNERABILITY: Overwrites whatever the account held
+        let history = init_without_zero_check::<PriceHistory>(&mut data)?;
+        history.authority = ctx.accounts.authority.key();
+
+        msg!("History initialized for {}", history.authority);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that uses Anchor's loaders
+    // for what each is for.
+
+    /// SECURE: Initialize with `#[account(zero)]` and `load_init`
+    ///
+    /// Security Fix: `#[account(zero)]` and `load_init` both require a zero
+    /// discriminator, so live accounts are rejected, and the loader writes
+    /// the discriminator on exit, so the account can never be initialized
+    /// again. Later updates go through `load_mut`, which requires it.
+    #[secure]
+    pub fn secure_init_history(ctx: Context<SecureInitHistory>) -> Result<()> {
+        // SECURITY: Fails with AccountDiscriminatorAlreadySet on live accounts
+        let mut history = ctx.accounts.history.load_init()?;
+        history.authority = ctx.accounts.authority.key();
+
+        msg!("History initialized for {}", history.authority);
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+//! Hand-rolled zero-copy loaders.
+//!
+//! Programs bypass `AccountLoader` when they want variable-length data, a
+//! header in front of the struct, or fewer compute units. Each function here
+//! reproduces one half of what Anchor's loaders do and forgets the other
+//! half:
+//!
+//! | Loader                              | Checks discriminator is | Writes discriminator |
+//! |-------------------------------------|-------------------------|----------------------|
+//! | `AccountLoader::load_init`          | zero                    | yes (on exit)        |
+//! | `AccountLoader::load_mut`           | `T::DISCRIMINATOR`      | yes (on exit)        |
+//! | [`view_mut_skipping_discriminator`] | nothing                 | no                   |
+//! | [`init_without_zero_check`]         | nothing                 | yes                  |
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use bytemuck::Pod;
+
+use crate::ErrorCode;
+
+/// Length of Anchor's account discriminator
+pub const DISCRIMINATOR_LEN: usize = 8;
+
+/// VULNERABLE: `load_mut`-style view used to initialize a brand-new account
+///
+/// Security Issue: The fields are written but the discriminator never is,
+/// so the account still reads as uninitialized. Any initializer accepts it
+/// again, and every `AccountLoader` rejects it.
+pub fn view_mut_skipping_discriminator<T: Pod>(data: &mut [u8]) -> Result<&mut T> {
+    let body = data
+        .get_mut(DISCRIMINATOR_LEN..DISCRIMINATOR_LEN + std::mem::size_of::<T>())
+        .ok_or(ErrorCode::AccountTooSmall)?;
+    Ok(bytemuck::from_bytes_mut(body))
+}
+
+/// VULNERABLE: `load_init`-style initialization without the zero check
+///
+/// Security Issue: The discriminator is written, but nothing checks it was
+/// zero first, so calling this on a live account resets it.
+pub fn init_without_zero_check<T: Pod + Discriminator>(data: &mut [u8]) -> Result<&mut T> {
+    let (discriminator, rest) = data.split_at_mut(DISCRIMINATOR_LEN.min(data.len()));
+    require!(discriminator.len() == DISCRIMINATOR_LEN, ErrorCode::AccountTooSmall);
+    discriminator.copy_from_slice(&T::DISCRIMINATOR);
+
+    let body = rest.get_mut(..std::mem::size_of::<T>()).ok_or(ErrorCode::AccountTooSmall)?;
+    let state: &mut T = bytemuck::from_bytes_mut(body);
+    *state = T::zeroed();
+    Ok(state)
+}
+
+/// `true` if the account's discriminator bytes are all zero
+pub fn is_uninitialized(data: &[u8]) -> bool {
+    data.get(..DISCRIMINATOR_LEN).is_some_and(|discriminator| discriminator.iter().all(|byte| *byte == 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PriceHistory, HISTORY_SPACE};
+
+    fn fresh_account() -> Vec<u8> {
+        vec![0; HISTORY_SPACE]
+    }
+
+    #[test]
+    fn skipping_the_discriminator_leaves_the_account_reinitializable() {
+        let mut data = fresh_account();
+        let owner = Pubkey::new_unique();
+        view_mut_skipping_discriminator::<PriceHistory>(&mut data).unwrap().authority = owner;
+
+        assert_eq!(&data[..DISCRIMINATOR_LEN], &[0; DISCRIMINATOR_LEN]);
+        assert!(is_uninitialized(&data));
+        assert_eq!(&data[DISCRIMINATOR_LEN..DISCRIMINATOR_LEN + 32], owner.as_ref());
+    }
+
+    #[test]
+    fn initializing_without_the_zero_check_resets_live_accounts() {
+        let mut data = fresh_account();
+        let owner = Pubkey::new_unique();
+        {
+            let history = init_without_zero_check::<PriceHistory>(&mut data).unwrap();
+            history.authority = owner;
+            history.count = 5;
+        }
+        assert_eq!(&data[..DISCRIMINATOR_LEN], &PriceHistory::DISCRIMINATOR);
+        assert!(!is_uninitialized(&data));
+
+        // A second call on the live account succeeds and wipes it
+        let attacker = Pubkey::new_unique();
+        let history = init_without_zero_check::<PriceHistory>(&mut data).unwrap();
+        assert_eq!(history.count, 0);
+        history.authority = attacker;
+        assert_eq!(&data[DISCRIMINATOR_LEN..DISCRIMINATOR_LEN + 32], attacker.as_ref());
+    }
+
+    #[test]
+    fn rejects_accounts_too_small_for_the_struct() {
+        let mut data = vec![0; HISTORY_SPACE - 1];
+        assert!(view_mut_skipping_discriminator::<PriceHistory>(&mut data).is_err());
+        assert!(init_without_zero_check::<PriceHistory>(&mut data).is_err());
+    }
+}
@@ -0,0 +1,15 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account(zero_copy)]
+pub struct PriceHistory {
+    /// Key allowed to push prices (32 bytes)
+    pub authority: Pubkey,
+    /// Index the next price is written to (8 bytes)
+    pub head: u64,
+    /// Number of prices stored, up to HISTORY_LEN (8 bytes)
+    pub count: u64,
+    /// Ring buffer of prices (8 * HISTORY_LEN bytes)
+    pub prices: [u64; HISTORY_LEN],
+}
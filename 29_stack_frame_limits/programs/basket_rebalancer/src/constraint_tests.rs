@@ -0,0 +1,61 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_owner_violation, assert_signer_violation, Fixture,
+    TestAccount,
+};
+
+use crate::{Basket, Index, SecureRebalance, BASKET_ASSETS};
+
+fn basket_account(authority: Pubkey, id: u8) -> TestAccount {
+    TestAccount::anchor(&Basket {
+        authority,
+        id,
+        bump: 0,
+        weights: [1; BASKET_ASSETS],
+    })
+}
+
+/// Zero-copy accounts are their discriminator followed by the raw struct
+fn index_account(authority: Pubkey) -> TestAccount {
+    let index = Index {
+        authority,
+        rebalances: 0,
+        weights: [0; BASKET_ASSETS],
+    };
+    let data = [&Index::DISCRIMINATOR[..], bytemuck::bytes_of(&index)].concat();
+    TestAccount::new(crate::ID, data)
+}
+
+fn rebalance_fixture() -> Fixture {
+    let authority = TestAccount::signer();
+    Fixture::new()
+        .with("index", index_account(authority.key))
+        .with("basket_a", basket_account(authority.key, 0))
+        .with("basket_b", basket_account(authority.key, 1))
+        .with("basket_c", basket_account(authority.key, 2))
+        .with("basket_d", basket_account(authority.key, 3))
+        .with("authority", authority)
+}
+
+fn foreign_basket(id: u8) -> TestAccount {
+    basket_account(Pubkey::new_unique(), id)
+}
+
+#[test]
+fn secure_rebalance_requires_the_index_authority() {
+    assert_has_one_violation!(SecureRebalance, rebalance_fixture(), "authority");
+    assert_signer_violation!(SecureRebalance, rebalance_fixture(), "authority");
+    assert_owner_violation!(SecureRebalance, rebalance_fixture(), "index");
+}
+
+#[test]
+fn secure_rebalance_reads_only_the_authoritys_baskets() {
+    assert_constraint_violation!(SecureRebalance, rebalance_fixture(), "basket_a" => foreign_basket(0), ErrorCode::ConstraintHasOne);
+    assert_constraint_violation!(SecureRebalance, rebalance_fixture(), "basket_b" => foreign_basket(1), ErrorCode::ConstraintHasOne);
+    assert_constraint_violation!(SecureRebalance, rebalance_fixture(), "basket_c" => foreign_basket(2), ErrorCode::ConstraintHasOne);
+    assert_constraint_violation!(SecureRebalance, rebalance_fixture(), "basket_d" => foreign_basket(3), ErrorCode::ConstraintHasOne);
+    assert_owner_violation!(SecureRebalance, rebalance_fixture(), "basket_a");
+}
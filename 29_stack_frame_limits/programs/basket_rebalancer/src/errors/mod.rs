@@ -0,0 +1,35 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Weight arithmetic overflowed")]
+    WeightOverflow,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The contexts are what `try_accounts` builds on the stack; their size
+    // on the host matches SBF closely because both are 64-bit.
+
+    #[test]
+    fn unboxed_context_exceeds_the_stack_frame() {
+        let size = std::mem::size_of::<VulnerableRebalance>();
+        assert!(size > SBF_STACK_FRAME_SIZE, "VulnerableRebalance is {size} bytes");
+    }
+
+    #[test]
+    fn boxed_context_fits_easily() {
+        let size = std::mem::size_of::<SecureRebalance>();
+        assert!(size < SBF_STACK_FRAME_SIZE / 8, "SecureRebalance is {size} bytes");
+    }
+
+    #[test]
+    fn boxed_baskets_fit_on_the_heap() {
+        let heap_per_basket = std::mem::size_of::<Account<Basket>>();
+        assert!(4 * heap_per_basket < SBF_HEAP_SIZE);
+    }
+}
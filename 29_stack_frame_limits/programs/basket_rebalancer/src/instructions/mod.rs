@@ -0,0 +1,55 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+#[instruction(id: u8)]
+pub struct InitializeBasket<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = BASKET_SPACE,
+        seeds = [b"basket", authority.key().as_ref(), &[id]],
+        bump
+    )]
+    pub basket: Box<Account<'info, Basket>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeIndex<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = INDEX_SPACE,
+        seeds = [b"index", authority.key().as_ref()],
+        bump
+    )]
+    pub index: AccountLoader<'info, Index>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProbeHeap<'info> {
+    pub payer: Signer<'info>,
+}
@@ -0,0 +1,22 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureRebalance<'info> {
+    // SECURITY: Zero-copy, read in place from the account data
+    #[account(mut, has_one = authority)]
+    pub index: AccountLoader<'info, Index>,
+
+    // SECURITY: Deserialized onto the heap
+    #[account(has_one = authority)]
+    pub basket_a: Box<Account<'info, Basket>>,
+    #[account(has_one = authority)]
+    pub basket_b: Box<Account<'info, Basket>>,
+    #[account(has_one = authority)]
+    pub basket_c: Box<Account<'info, Basket>>,
+    #[account(has_one = authority)]
+    pub basket_d: Box<Account<'info, Basket>>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,21 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableRebalance<'info> {
+    #[account(mut, has_one = authority)]
+    pub index: AccountLoader<'info, Index>,
+
+    // VULNERABILITY: Deserialized onto the stack, ~1KB each
+    #[account(has_one = authority)]
+    pub basket_a: Account<'info, Basket>,
+    #[account(has_one = authority)]
+    pub basket_b: Account<'info, Basket>,
+    #[account(has_one = authority)]
+    pub basket_c: Account<'info, Basket>,
+    #[account(has_one = authority)]
+    pub basket_d: Account<'info, Basket>,
+
+    pub authority: Signer<'info>,
+}
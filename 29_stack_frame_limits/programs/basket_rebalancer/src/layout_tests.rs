@@ -0,0 +1,27 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Basket, Index, BASKET_ASSETS};
+
+#[test]
+fn basket_layout_is_stable() {
+    let basket = Basket {
+        authority: Pubkey::new_from_array([1; 32]),
+        id: 33,
+        bump: 49,
+        weights: [0x4142434445464748; BASKET_ASSETS],
+    };
+    assert_golden!("Basket", golden::anchor(&basket));
+}
+
+#[test]
+fn index_layout_is_stable() {
+    let index = Index {
+        authority: Pubkey::new_from_array([5; 32]),
+        rebalances: 0x6162636465666768,
+        weights: [0x7172737475767778; BASKET_ASSETS],
+    };
+    assert_golden!("Index", golden::zero_copy(&index));
+}
@@ -0,0 +1,128 @@
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Assets tracked by every basket and by the index
+pub const BASKET_ASSETS: usize = 128;
+
+/// Size of a basket account
+pub const BASKET_SPACE: usize = 8 + 32 + 1 + 1 + 8 * BASKET_ASSETS; // discriminator + authority + id + bump + weights
+
+/// Size of the index account
+pub const INDEX_SPACE: usize = 8 + 32 + 8 + 8 * BASKET_ASSETS; // discriminator + authority + rebalances + weights
+
+/// Stack frame available to each SBF function
+pub const SBF_STACK_FRAME_SIZE: usize = 4 * 1024;
+
+/// Default heap available to an SBF program
+pub const SBF_HEAP_SIZE: usize = 32 * 1024;
+
+#[feature_gated]
+#[program]
+pub mod basket_rebalancer {
+    use super::*;
+
+    /// Create basket `id` for the signer with every asset weighted `weight`
+    pub fn initialize_basket(ctx: Context<InitializeBasket>, id: u8, weight: u64) -> Result<()> {
+        let basket = &mut ctx.accounts.basket;
+        basket.authority = ctx.accounts.authority.key();
+        basket.id = id;
+        basket.bump = ctx.bumps.basket;
+        basket.weights = [weight; BASKET_ASSETS];
+
+        msg!("Basket {} created with weight {}", id, weight);
+        Ok(())
+    }
+
+    /// Create the signer's index, which holds the averaged weights
+    pub fn initialize_index(ctx: Context<InitializeIndex>) -> Result<()> {
+        let mut index = ctx.accounts.index.load_init()?;
+        index.authority = ctx.accounts.authority.key();
+
+        msg!("Index created for {}", index.authority);
+        Ok(())
+    }
+
+    /// Allocate `bytes` on the heap
+    ///
+    /// Used by the limits harness to find the heap ceiling empirically,
+    /// which bounds how many boxed accounts one instruction can hold.
+    pub fn probe_heap(_ctx: Context<ProbeHeap>, bytes: u32) -> Result<()> {
+        let buffer = vec![1u8; bytes as usize];
+        msg!("Allocated {} bytes", buffer.len());
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Rebalance from four unboxed baskets
+    ///
+    /// Security Issue: Each `Account<'info, Basket>` holds the deserialized
+    /// basket inline, so the context alone needs more than the 4KB SBF
+    /// stack frame. `anchor build` only warns about it, and the instruction
+    /// then fails at runtime with `Access violation in stack frame` - or,
+    /// worse, overwrites a neighbouring frame - as soon as the baskets grow
+    /// past what the original tests used.
+    #[vulnerable]
+    pub fn vulnerable_rebalance(ctx: Context<VulnerableRebalance>) -> Result<()> {
+        let accounts = &ctx.accounts;
+        // VULNERABILITY: Four ~1KB baskets live on the stack
+        let baskets: [&Basket; 4] = [&accounts.basket_a, &accounts.basket_b, &accounts.basket_c, &accounts.basket_d];
+        write_average(&accounts.index, &baskets)
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that keeps large accounts
+    // off the stack.
+
+    /// SECURE: Rebalance from four boxed baskets into a zero-copy index
+    ///
+    /// Security Fix: `Box<Account<'info, Basket>>` deserializes each basket
+    /// onto the 32KB heap, leaving a pointer on the stack, and the index is
+    /// zero-copy, so it is never copied at all. The context stays a few
+    /// hundred bytes however large the accounts get.
+    #[secure]
+    pub fn secure_rebalance(ctx: Context<SecureRebalance>) -> Result<()> {
+        let accounts = &ctx.accounts;
+        // SECURITY: Only pointers to the baskets are on the stack
+        let baskets: [&Basket; 4] = [&accounts.basket_a, &accounts.basket_b, &accounts.basket_c, &accounts.basket_d];
+        write_average(&accounts.index, &baskets)
+    }
+}
+
+/// Store the average weight of every asset across `baskets` in the index
+fn write_average(index: &AccountLoader<Index>, baskets: &[&Basket]) -> Result<()> {
+    let mut index = index.load_mut()?;
+    for asset in 0..BASKET_ASSETS {
+        let total = baskets
+            .iter()
+            .try_fold(0u64, |total, basket| total.checked_add(basket.weights[asset]))
+            .ok_or(ErrorCode::WeightOverflow)?;
+        index.weights[asset] = total / baskets.len() as u64;
+    }
+    index.rebalances = index.rebalances.checked_add(1).ok_or(ErrorCode::WeightOverflow)?;
+
+    msg!("Index rebalanced from {} baskets", baskets.len());
+    Ok(())
+}
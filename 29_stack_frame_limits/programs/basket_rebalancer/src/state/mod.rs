@@ -0,0 +1,25 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Basket {
+    /// Owner of the basket (32 bytes)
+    pub authority: Pubkey,
+    /// Basket number within the owner's set (1 byte)
+    pub id: u8,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+    /// Target weight per asset (8 * BASKET_ASSETS bytes)
+    pub weights: [u64; BASKET_ASSETS],
+}
+
+#[account(zero_copy)]
+pub struct Index {
+    /// Owner of the index (32 bytes)
+    pub authority: Pubkey,
+    /// Number of rebalances applied (8 bytes)
+    pub rebalances: u64,
+    /// Average weight per asset (8 * BASKET_ASSETS bytes)
+    pub weights: [u64; BASKET_ASSETS],
+}
@@ -0,0 +1,56 @@
+//! Every constraint on the context the secure instruction uses, tripped one account at a time.
+//!
+//! Which quoter answered is checked on the return data by `read_quote`; the
+//! context only insists the borrower named a program.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, Fixture,
+    TestAccount,
+};
+
+use crate::{Position, PricePosition};
+
+fn price_fixture() -> Fixture {
+    let borrower = TestAccount::signer();
+    let (position, bump) = Pubkey::find_program_address(&[b"position", borrower.key.as_ref()], &crate::ID);
+    let position = TestAccount::anchor(&Position {
+        borrower: borrower.key,
+        collateral: 1_000,
+        borrow_limit: 0,
+        bump,
+    })
+    .at(position);
+    Fixture::new()
+        .with("position", position)
+        .with("borrower", borrower)
+        .with("quoter", TestAccount::program(price_quoter::ID))
+}
+
+#[test]
+fn price_position_prices_the_borrowers_own_position() {
+    assert_seeds_violation!(PricePosition, price_fixture(), "position");
+    assert_owner_violation!(PricePosition, price_fixture(), "position");
+    assert_constraint_violation!(
+        PricePosition,
+        price_fixture(),
+        "position" => |position| position.edit(|stored: &mut Position| stored.borrower = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+}
+
+#[test]
+fn price_position_requires_the_borrower() {
+    assert_seeds_violation!(PricePosition, price_fixture(), "borrower");
+    assert_signer_violation!(PricePosition, price_fixture(), "borrower");
+}
+
+#[test]
+fn price_position_asks_a_program_for_the_quote() {
+    assert_constraint_violation!(
+        PricePosition,
+        price_fixture(),
+        "quoter" => TestAccount::system(0),
+        ErrorCode::ConstraintExecutable
+    );
+}
@@ -0,0 +1,17 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("The quoter set no return data")]
+    MissingQuote,
+    #[msg("Return data was not set by the price quoter")]
+    UntrustedQuoter,
+    #[msg("Return data is not a single quote")]
+    MalformedQuote,
+    #[msg("Quote is for a different amount than was requested")]
+    QuoteMismatch,
+    #[msg("Borrow limit overflowed")]
+    LimitOverflow,
+}
@@ -0,0 +1,38 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    #[account(
+        init,
+        payer = borrower,
+        space = 8 + 32 + 8 + 8 + 1, // discriminator + borrower + collateral + borrow_limit + bump
+        seeds = [b"position", borrower.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PricePosition<'info> {
+    #[account(
+        mut,
+        has_one = borrower,
+        seeds = [b"position", borrower.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+
+    pub borrower: Signer<'info>,
+
+    /// CHECK: Quoting venue chosen by the borrower, like an aggregator route;
+    /// its answer is what has to be validated
+    #[account(executable)]
+    pub quoter: UncheckedAccount<'info>,
+}
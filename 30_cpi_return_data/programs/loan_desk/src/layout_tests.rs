@@ -0,0 +1,17 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::Position;
+
+#[test]
+fn position_layout_is_stable() {
+    let position = Position {
+        borrower: Pubkey::new_from_array([1; 32]),
+        collateral: 0x2122232425262728,
+        borrow_limit: 0x3132333435363738,
+        bump: 65,
+    };
+    assert_golden!("Position", golden::anchor(&position));
+}
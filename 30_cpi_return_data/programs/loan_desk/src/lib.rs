@@ -0,0 +1,132 @@
+#[cfg(feature = "secure")]
+use anchor_lang::solana_program::program::get_return_data;
+use price_quoter::Quote;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Share of the collateral's value that may be borrowed (50%)
+pub const LTV_BPS: u64 = 5_000;
+
+/// Basis point denominator
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+#[feature_gated]
+#[program]
+pub mod loan_desk {
+    use super::*;
+
+    /// Open a position backed by `collateral` units
+    pub fn open_position(ctx: Context<OpenPosition>, collateral: u64) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.borrower = ctx.accounts.borrower.key();
+        position.collateral = collateral;
+        position.borrow_limit = 0;
+        position.bump = ctx.bumps.position;
+
+        msg!("Position opened with {} collateral", collateral);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Price a position from whatever return data is left after the CPI
+    ///
+    /// Security Issue: `get_return_data` returns the last return data set
+    /// by *any* program during the CPI, tagged with that program's id.
+    /// Anchor's generated `Return::get()` discards the id, so a quoter that
+    /// forges its answer - or relays a genuine `price_quoter` quote for a
+    /// different amount - sets the borrower's limit.
+    #[vulnerable]
+    pub fn vulnerable_price_position<'info>(ctx: Context<'_, '_, 'info, 'info, PricePosition<'info>>) -> Result<()> {
+        let collateral = ctx.accounts.position.collateral;
+
+        // VULNERABILITY: Return::get() ignores who set the return data and what it answers
+        let quote = request_quote(&ctx, collateral)?.get();
+
+        apply_quote(&mut ctx.accounts.position, &quote)
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that validates the return
+    // data before using it.
+
+    /// SECURE: Accept return data only from `price_quoter`, for this request
+    ///
+    /// Security Fix: The `(program_id, data)` tuple must come from
+    /// `price_quoter`, the data must be exactly one `Quote`, and the quote
+    /// must echo the collateral amount that was asked about. Forged and
+    /// relayed quotes both fail one of these checks.
+    #[secure]
+    pub fn secure_price_position<'info>(ctx: Context<'_, '_, 'info, 'info, PricePosition<'info>>) -> Result<()> {
+        let collateral = ctx.accounts.position.collateral;
+        request_quote(&ctx, collateral)?;
+
+        // SECURITY: Validate the returning program, the length and the echoed request
+        let quote = read_quote(collateral)?;
+
+        apply_quote(&mut ctx.accounts.position, &quote)
+    }
+}
+
+/// Ask the borrower's chosen quoter to value `collateral`
+///
+/// Remaining accounts are forwarded to the quoter.
+fn request_quote<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, PricePosition<'info>>,
+    collateral: u64,
+) -> Result<price_quoter::cpi::Return<Quote>> {
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.quoter.to_account_info(),
+        price_quoter::cpi::accounts::GetQuote {
+            requester: ctx.accounts.borrower.to_account_info(),
+        },
+    )
+    .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    price_quoter::cpi::quote(cpi_ctx, collateral)
+}
+
+/// Read a quote for `amount_in` from `price_quoter`'s return data
+#[cfg(feature = "secure")]
+fn read_quote(amount_in: u64) -> Result<Quote> {
+    let (program_id, data) = get_return_data().ok_or(ErrorCode::MissingQuote)?;
+    require_keys_eq!(program_id, price_quoter::ID, ErrorCode::UntrustedQuoter);
+    require!(data.len() == Quote::LEN, ErrorCode::MalformedQuote);
+
+    let quote = Quote::try_from_slice(&data).map_err(|_| ErrorCode::MalformedQuote)?;
+    require!(quote.amount_in == amount_in, ErrorCode::QuoteMismatch);
+    Ok(quote)
+}
+
+/// Set the position's borrow limit from a quote of its collateral
+fn apply_quote(position: &mut Position, quote: &Quote) -> Result<()> {
+    position.borrow_limit = (quote.amount_out as u128)
+        .checked_mul(LTV_BPS as u128)
+        .map(|limit| limit / BPS_DENOMINATOR as u128)
+        .and_then(|limit| u64::try_from(limit).ok())
+        .ok_or(ErrorCode::LimitOverflow)?;
+
+    msg!("Borrow limit set to {}", position.borrow_limit);
+    Ok(())
+}
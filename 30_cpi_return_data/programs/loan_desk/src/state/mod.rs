@@ -0,0 +1,15 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Position {
+    /// Owner of the position (32 bytes)
+    pub borrower: Pubkey,
+    /// Collateral units deposited (8 bytes)
+    pub collateral: u64,
+    /// Amount the borrower may draw, from the last quote (8 bytes)
+    pub borrow_limit: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
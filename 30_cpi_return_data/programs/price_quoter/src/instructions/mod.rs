@@ -0,0 +1,9 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct GetQuote<'info> {
+    /// Account the quote is for
+    pub requester: Signer<'info>,
+}
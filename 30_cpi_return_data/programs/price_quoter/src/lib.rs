@@ -0,0 +1,48 @@
+//! Quoting venue called by the loan desk over CPI.
+//!
+//! `quote` answers through return data. Anchor serializes the returned
+//! `Quote` with Borsh and sets it as this program's return data, which the
+//! caller reads with `get_return_data` after the CPI.
+
+use secref_prelude::*;
+
+declare_id!("PriceQuoter11111111111111111111111111111111");
+
+pub mod errors;
+pub mod instructions;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+
+/// Collateral value per unit, in the desk's quote currency
+pub const PRICE: u64 = 25;
+
+#[program]
+pub mod price_quoter {
+    use super::*;
+
+    /// Value `amount_in` units of collateral
+    ///
+    /// The quote echoes `amount_in` so callers can check the answer is for
+    /// the question they asked.
+    pub fn quote(_ctx: Context<GetQuote>, amount_in: u64) -> Result<Quote> {
+        let amount_out = amount_in.checked_mul(PRICE).ok_or(ErrorCode::QuoteOverflow)?;
+
+        msg!("Quoted {} for {}", amount_out, amount_in);
+        Ok(Quote { amount_in, amount_out })
+    }
+}
+
+/// Answer to a `quote` call, returned through return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quote {
+    /// Amount that was priced (8 bytes)
+    pub amount_in: u64,
+    /// Its value (8 bytes)
+    pub amount_out: u64,
+}
+
+impl Quote {
+    /// Serialized size of a quote
+    pub const LEN: usize = 8 + 8; // amount_in + amount_out
+}
@@ -0,0 +1,8 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SpoofQuote<'info> {
+    pub requester: Signer<'info>,
+}
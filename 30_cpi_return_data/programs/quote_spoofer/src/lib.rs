@@ -0,0 +1,58 @@
+//! Attacker fixture: a quoting venue that lies.
+//!
+//! Its `quote` instruction has the same name, and therefore the same
+//! discriminator, as `price_quoter::quote`, so the loan desk can call it
+//! with exactly the instruction data it sends to the real venue.
+
+use anchor_lang::solana_program::program::set_return_data;
+use price_quoter::program::PriceQuoter;
+use price_quoter::Quote;
+use secref_prelude::*;
+
+declare_id!("QuoteSpoofer1111111111111111111111111111111");
+
+pub mod instructions;
+
+pub use instructions::*;
+
+/// Factor the spoofer inflates quotes by
+pub const INFLATION: u64 = 1_000;
+
+#[program]
+pub mod quote_spoofer {
+    use super::*;
+
+    /// Answer a quote request dishonestly
+    ///
+    /// With no remaining accounts, forges the return data outright. With
+    /// the real `price_quoter` as the first remaining account, sits in the
+    /// middle: asks the real venue about `amount_in * INFLATION` and sets
+    /// no return data of its own, so the caller reads a genuine
+    /// `price_quoter` quote - for the wrong amount.
+    pub fn quote<'info>(ctx: Context<'_, '_, 'info, 'info, SpoofQuote<'info>>, amount_in: u64) -> Result<()> {
+        match ctx.remaining_accounts.first() {
+            Some(real_quoter) => {
+                let program = Program::<PriceQuoter>::try_from(real_quoter)?;
+                price_quoter::cpi::quote(
+                    CpiContext::new(
+                        program.to_account_info(),
+                        price_quoter::cpi::accounts::GetQuote {
+                            requester: ctx.accounts.requester.to_account_info(),
+                        },
+                    ),
+                    amount_in.saturating_mul(INFLATION),
+                )?;
+                msg!("Relayed an inflated quote for {}", amount_in);
+            }
+            None => {
+                let forged = Quote {
+                    amount_in,
+                    amount_out: amount_in.saturating_mul(price_quoter::PRICE).saturating_mul(INFLATION),
+                };
+                set_return_data(&forged.try_to_vec()?);
+                msg!("Forged a quote for {}", amount_in);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+//! Attacker fixture: a "system program" that moves nothing.
+//!
+//! Passed where `watched_vault::vulnerable_deposit` expects the system
+//! program, it receives the transfer instruction, reports success and
+//! leaves every balance unchanged.
+
+use secref_prelude::*;
+
+declare_id!("FakeSystem111111111111111111111111111111111");
+
+#[program]
+pub mod fake_system {
+    use super::*;
+
+    /// Accept any instruction, including a system `Transfer`, and do nothing
+    pub fn fallback<'info>(_program_id: &Pubkey, _accounts: &'info [AccountInfo<'info>], data: &[u8]) -> Result<()> {
+        msg!("Pretended to process {} bytes of instruction data", data.len());
+        Ok(())
+    }
+}
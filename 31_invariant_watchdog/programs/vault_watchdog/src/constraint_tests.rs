@@ -0,0 +1,33 @@
+//! Every constraint on the watchdog's contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_owner_violation, assert_seeds_violation, Fixture, TestAccount};
+use watched_vault::VaultState;
+
+use crate::AssertVaultSolvent;
+
+fn solvent_fixture() -> Fixture {
+    let state = TestAccount::anchor(&VaultState {
+        admin: Pubkey::new_unique(),
+        total_liabilities: 1_000,
+        vault_bump: 0,
+        bump: 0,
+    });
+    let (vault, vault_bump) = Pubkey::find_program_address(&[b"vault", state.key.as_ref()], &watched_vault::ID);
+    let mut state = state;
+    state.edit(|stored: &mut VaultState| stored.vault_bump = vault_bump);
+    Fixture::new()
+        .with("state", state)
+        .with("vault", TestAccount::system(1_000_000).at(vault))
+}
+
+#[test]
+fn assert_vault_solvent_reads_watched_vault_state() {
+    assert_owner_violation!(AssertVaultSolvent, solvent_fixture(), "state");
+}
+
+#[test]
+fn assert_vault_solvent_measures_the_states_own_vault() {
+    assert_seeds_violation!(AssertVaultSolvent, solvent_fixture(), "vault");
+    assert_seeds_violation!(AssertVaultSolvent, solvent_fixture(), "state");
+}
@@ -0,0 +1,13 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Vault holds less than it owes")]
+    VaultInsolvent,
+    #[msg("A deposit claims more than the vault owes in total")]
+    DepositExceedsLiabilities,
+    #[msg("Deposit belongs to a different vault")]
+    ForeignDeposit,
+}
@@ -0,0 +1,16 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct AssertVaultSolvent<'info> {
+    // Owner checked by `Account`: must belong to watched_vault
+    pub state: Account<'info, VaultState>,
+
+    #[account(
+        seeds = [b"vault", state.key().as_ref()],
+        bump = state.vault_bump,
+        seeds::program = watched_vault::ID
+    )]
+    pub vault: SystemAccount<'info>,
+}
@@ -0,0 +1,66 @@
+//! Invariant assertions for `watched_vault`.
+//!
+//! Nothing here changes state. Each instruction reads the vault's accounts
+//! and fails if an invariant does not hold, so appending it to the end of a
+//! transaction reverts every earlier instruction that broke one - whatever
+//! the bug was.
+
+use secref_guards::rent::minimum_balance;
+use secref_prelude::*;
+use watched_vault::{Deposit, VaultState};
+
+declare_id!("Watchdog11111111111111111111111111111111111");
+
+pub mod errors;
+pub mod instructions;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+
+#[cfg(test)]
+mod constraint_tests;
+
+#[program]
+pub mod vault_watchdog {
+    use super::*;
+
+    /// Assert the vault can pay everything it owes
+    ///
+    /// Invariants:
+    /// 1. The vault PDA holds at least its rent reserve plus
+    ///    `total_liabilities`.
+    /// 2. No deposit passed in `remaining_accounts` claims more than
+    ///    `total_liabilities`; one that does means an update was lost or
+    ///    wrapped, even if the lamport totals still match.
+    pub fn assert_vault_solvent<'info>(ctx: Context<'_, '_, 'info, 'info, AssertVaultSolvent<'info>>) -> Result<()> {
+        let state = &ctx.accounts.state;
+        let assets = ctx.accounts.vault.lamports();
+        let required = minimum_balance(0)?
+            .checked_add(state.total_liabilities)
+            .ok_or(ErrorCode::VaultInsolvent)?;
+        require!(assets >= required, ErrorCode::VaultInsolvent);
+
+        for info in ctx.remaining_accounts {
+            let deposit = Account::<Deposit>::try_from(info)?;
+            require_keys_eq!(deposit.state, state.key(), ErrorCode::ForeignDeposit);
+            require!(deposit.amount <= state.total_liabilities, ErrorCode::DepositExceedsLiabilities);
+        }
+
+        msg!("Vault solvent: {} lamports for {} owed", assets, state.total_liabilities);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::Discriminator;
+
+    #[test]
+    fn watched_vault_knows_this_program() {
+        assert_eq!(watched_vault::WATCHDOG_ID, crate::ID);
+        assert_eq!(
+            watched_vault::ASSERT_SOLVENT_DISCRIMINATOR,
+            crate::instruction::AssertVaultSolvent::DISCRIMINATOR
+        );
+    }
+}
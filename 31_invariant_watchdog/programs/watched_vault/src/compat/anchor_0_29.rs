@@ -0,0 +1,12 @@
+//! Anchor 0.29 spellings of the code that differs between releases.
+
+use anchor_lang::prelude::*;
+
+/// Program id of `vault_watchdog`
+///
+/// 0.29 has no `pubkey!` macro, so the base58 id
+/// `Watchdog11111111111111111111111111111111111` is spelled out as bytes.
+pub const WATCHDOG_ID: Pubkey = Pubkey::new_from_array([
+    7, 148, 41, 181, 51, 84, 109, 60, 50, 72, 87, 154, 173, 101, 87, 235, 147, 28, 94, 111, 102, 228, 2, 223, 141, 201,
+    213, 248, 0, 0, 0, 0,
+]);
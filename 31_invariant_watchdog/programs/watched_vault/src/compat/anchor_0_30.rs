@@ -0,0 +1,6 @@
+//! Anchor 0.30 spellings of the code that differs between releases.
+
+use anchor_lang::prelude::*;
+
+/// Program id of `vault_watchdog`
+pub const WATCHDOG_ID: Pubkey = pubkey!("Watchdog11111111111111111111111111111111111");
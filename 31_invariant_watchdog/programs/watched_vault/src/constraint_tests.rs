@@ -0,0 +1,98 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_owner_violation, assert_seeds_violation,
+    assert_signer_violation, Fixture, TestAccount,
+};
+
+use crate::{Deposit, SecureDeposit, SecureWithdraw, VaultState};
+
+fn transfer_fixture() -> Fixture {
+    let owner = TestAccount::signer();
+    let state = TestAccount::anchor(&VaultState {
+        admin: Pubkey::new_unique(),
+        total_liabilities: 1_000,
+        vault_bump: 0,
+        bump: 0,
+    });
+    let (vault, vault_bump) = Pubkey::find_program_address(&[b"vault", state.key.as_ref()], &crate::ID);
+    let mut state = state;
+    state.edit(|stored: &mut VaultState| stored.vault_bump = vault_bump);
+    let deposit = TestAccount::anchor(&Deposit {
+        state: state.key,
+        owner: owner.key,
+        amount: 1_000,
+        bump: 0,
+    });
+    Fixture::new()
+        .with("state", state)
+        .with("vault", TestAccount::system(1_000_000).at(vault))
+        .with("deposit", deposit)
+        .with("owner", owner)
+        .with("instructions", TestAccount::instructions_sysvar())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn spoofed_instructions() -> TestAccount {
+    TestAccount::new(sysvar::ID, vec![0; 64])
+}
+
+#[test]
+fn secure_deposit_moves_lamports_into_the_states_vault() {
+    assert_owner_violation!(SecureDeposit, transfer_fixture(), "state");
+    assert_seeds_violation!(SecureDeposit, transfer_fixture(), "vault");
+    assert_seeds_violation!(SecureDeposit, transfer_fixture(), "state");
+}
+
+#[test]
+fn secure_deposit_credits_the_owners_deposit_in_this_vault() {
+    assert_has_one_violation!(SecureDeposit, transfer_fixture(), "owner");
+    assert_signer_violation!(SecureDeposit, transfer_fixture(), "owner");
+    assert_constraint_violation!(
+        SecureDeposit,
+        transfer_fixture(),
+        "deposit" => |deposit| deposit.edit(|stored: &mut Deposit| stored.state = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+}
+
+#[test]
+fn secure_deposit_reads_the_real_instructions_sysvar() {
+    assert_constraint_violation!(
+        SecureDeposit,
+        transfer_fixture(),
+        "instructions" => spoofed_instructions(),
+        ErrorCode::ConstraintAddress
+    );
+}
+
+#[test]
+fn secure_withdraw_pays_out_of_the_states_vault() {
+    assert_owner_violation!(SecureWithdraw, transfer_fixture(), "state");
+    assert_seeds_violation!(SecureWithdraw, transfer_fixture(), "vault");
+    assert_seeds_violation!(SecureWithdraw, transfer_fixture(), "state");
+}
+
+#[test]
+fn secure_withdraw_debits_the_owners_deposit_in_this_vault() {
+    assert_has_one_violation!(SecureWithdraw, transfer_fixture(), "owner");
+    assert_signer_violation!(SecureWithdraw, transfer_fixture(), "owner");
+    assert_constraint_violation!(
+        SecureWithdraw,
+        transfer_fixture(),
+        "deposit" => |deposit| deposit.edit(|stored: &mut Deposit| stored.state = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+}
+
+#[test]
+fn secure_withdraw_reads_the_real_instructions_sysvar() {
+    assert_constraint_violation!(
+        SecureWithdraw,
+        transfer_fixture(),
+        "instructions" => spoofed_instructions(),
+        ErrorCode::ConstraintAddress
+    );
+}
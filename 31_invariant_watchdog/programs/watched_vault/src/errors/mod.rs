@@ -0,0 +1,13 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Withdrawal exceeds the deposit")]
+    InsufficientDeposit,
+    #[msg("Amount overflowed")]
+    AmountOverflow,
+    #[msg("Transaction does not end with vault_watchdog::assert_vault_solvent for this vault")]
+    WatchdogMissing,
+}
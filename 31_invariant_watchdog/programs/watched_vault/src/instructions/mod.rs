@@ -0,0 +1,54 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializeVault<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 8 + 1 + 1, // discriminator + admin + total_liabilities + vault_bump + bump
+        seeds = [b"state", admin.key().as_ref()],
+        bump
+    )]
+    pub state: Account<'info, VaultState>,
+
+    #[account(mut, seeds = [b"vault", state.key().as_ref()], bump)]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenDeposit<'info> {
+    pub state: Account<'info, VaultState>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 32 + 32 + 8 + 1, // discriminator + state + owner + amount + bump
+        seeds = [b"deposit", state.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub deposit: Account<'info, Deposit>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
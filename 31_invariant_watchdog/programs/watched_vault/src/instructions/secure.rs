@@ -0,0 +1,45 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureDeposit<'info> {
+    #[account(mut)]
+    pub state: Account<'info, VaultState>,
+
+    #[account(mut, seeds = [b"vault", state.key().as_ref()], bump = state.vault_bump)]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mut, has_one = owner, has_one = state)]
+    pub deposit: Account<'info, Deposit>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Address is checked against the instructions sysvar id
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SecureWithdraw<'info> {
+    #[account(mut)]
+    pub state: Account<'info, VaultState>,
+
+    #[account(mut, seeds = [b"vault", state.key().as_ref()], bump = state.vault_bump)]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mut, has_one = owner, has_one = state)]
+    pub deposit: Account<'info, Deposit>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Address is checked against the instructions sysvar id
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
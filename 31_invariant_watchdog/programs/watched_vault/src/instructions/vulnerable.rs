@@ -0,0 +1,38 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableDeposit<'info> {
+    #[account(mut)]
+    pub state: Account<'info, VaultState>,
+
+    #[account(mut, seeds = [b"vault", state.key().as_ref()], bump = state.vault_bump)]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mut, has_one = owner, has_one = state)]
+    pub deposit: Account<'info, Deposit>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: VULNERABILITY - Not checked to be the system program
+    pub system_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableWithdraw<'info> {
+    #[account(mut)]
+    pub state: Account<'info, VaultState>,
+
+    #[account(mut, seeds = [b"vault", state.key().as_ref()], bump = state.vault_bump)]
+    pub vault: SystemAccount<'info>,
+
+    #[account(mut, has_one = owner, has_one = state)]
+    pub deposit: Account<'info, Deposit>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,28 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Deposit, VaultState};
+
+#[test]
+fn vault_state_layout_is_stable() {
+    let vault_state = VaultState {
+        admin: Pubkey::new_from_array([1; 32]),
+        total_liabilities: 0x2122232425262728,
+        vault_bump: 49,
+        bump: 65,
+    };
+    assert_golden!("VaultState", golden::anchor(&vault_state));
+}
+
+#[test]
+fn deposit_layout_is_stable() {
+    let deposit = Deposit {
+        state: Pubkey::new_from_array([5; 32]),
+        owner: Pubkey::new_from_array([6; 32]),
+        amount: 0x7172737475767778,
+        bump: 129,
+    };
+    assert_golden!("Deposit", golden::anchor(&deposit));
+}
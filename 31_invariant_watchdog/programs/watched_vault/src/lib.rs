@@ -0,0 +1,249 @@
+#[cfg(feature = "secure")]
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_lang::system_program::{self, Transfer};
+use secref_guards::rent::minimum_balance;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+// Code that differs between Anchor releases, one file per release
+#[cfg_attr(feature = "anchor-0_29", path = "compat/anchor_0_29.rs")]
+#[cfg_attr(not(feature = "anchor-0_29"), path = "compat/anchor_0_30.rs")]
+mod compat;
+
+/// Program id of `vault_watchdog`
+///
+/// Kept here rather than imported: the watchdog depends on this crate to
+/// read its accounts, so this crate cannot depend on the watchdog.
+pub const WATCHDOG_ID: Pubkey = compat::WATCHDOG_ID;
+
+/// Anchor discriminator of `vault_watchdog::assert_vault_solvent`
+pub const ASSERT_SOLVENT_DISCRIMINATOR: [u8; 8] = [142, 40, 37, 36, 119, 175, 203, 248];
+
+#[feature_gated]
+#[program]
+pub mod watched_vault {
+    use super::*;
+
+    /// Create a vault administered by the signer
+    ///
+    /// The vault PDA is a system account holding the deposited lamports. It
+    /// is funded with its rent-exempt minimum here so withdrawals never have
+    /// to reason about it.
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        let state = &mut ctx.accounts.state;
+        state.admin = ctx.accounts.admin.key();
+        state.total_liabilities = 0;
+        state.vault_bump = ctx.bumps.vault;
+        state.bump = ctx.bumps.state;
+
+        let reserve = minimum_balance(0)?;
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            reserve,
+        )?;
+
+        msg!("Vault initialized with a {} lamport reserve", reserve);
+        Ok(())
+    }
+
+    /// Open the signer's deposit record in a vault
+    pub fn open_deposit(ctx: Context<OpenDeposit>) -> Result<()> {
+        let deposit = &mut ctx.accounts.deposit;
+        deposit.state = ctx.accounts.state.key();
+        deposit.owner = ctx.accounts.owner.key();
+        deposit.amount = 0;
+        deposit.bump = ctx.bumps.deposit;
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Deposit through an unchecked "system program"
+    ///
+    /// Security Issue: The transfer CPI goes to whatever program is passed
+    /// as `system_program` (see `03_unsafe_cpi`). A program that accepts the
+    /// transfer and moves nothing leaves the deposit credited with no
+    /// lamports behind it, and the vault is insolvent.
+    #[vulnerable]
+    pub fn vulnerable_deposit(ctx: Context<VulnerableDeposit>, amount: u64) -> Result<()> {
+        // VULNERABILITY: Any program can stand in for the system program
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        credit(&mut ctx.accounts.state, &mut ctx.accounts.deposit, amount)
+    }
+
+    /// VULNERABLE: Withdraw with wrapping arithmetic
+    ///
+    /// Security Issue: Nothing checks that `amount` is within the owner's
+    /// deposit (see `04_arithmetic_overflow`). The payout comes out of
+    /// other depositors' lamports and the owner's balance wraps to almost
+    /// `u64::MAX`, a claim on more than the vault will ever hold.
+    #[vulnerable]
+    pub fn vulnerable_withdraw(ctx: Context<VulnerableWithdraw>, amount: u64) -> Result<()> {
+        // VULNERABILITY: Wraps instead of failing when amount > deposit
+        ctx.accounts.deposit.amount = ctx.accounts.deposit.amount.wrapping_sub(amount);
+        ctx.accounts.state.total_liabilities = ctx.accounts.state.total_liabilities.wrapping_sub(amount);
+
+        pay_out(
+            &ctx.accounts.system_program,
+            &ctx.accounts.state,
+            &ctx.accounts.vault,
+            &ctx.accounts.owner,
+            amount,
+        )
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE versions, which fix both bugs and
+    // also refuse to run unless the transaction ends by asserting the
+    // vault's invariants.
+
+    /// SECURE: Deposit through the real system program, under the watchdog
+    ///
+    /// Security Fix: `Program<'info, System>` pins the CPI target, and
+    /// `require_watchdog` refuses the deposit unless a later instruction
+    /// calls `vault_watchdog::assert_vault_solvent` on this vault, so a
+    /// future bug that breaks solvency reverts instead of persisting.
+    #[secure]
+    pub fn secure_deposit(ctx: Context<SecureDeposit>, amount: u64) -> Result<()> {
+        // SECURITY: The transaction must end by asserting solvency
+        require_watchdog(&ctx.accounts.instructions, &ctx.accounts.state.key())?;
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        credit(&mut ctx.accounts.state, &mut ctx.accounts.deposit, amount)
+    }
+
+    /// SECURE: Withdraw with checked arithmetic, under the watchdog
+    ///
+    /// Security Fix: Withdrawals above the deposit fail with
+    /// `InsufficientDeposit`, and the watchdog requirement backs that check
+    /// with the vault-wide invariant.
+    #[secure]
+    pub fn secure_withdraw(ctx: Context<SecureWithdraw>, amount: u64) -> Result<()> {
+        // SECURITY: The transaction must end by asserting solvency
+        require_watchdog(&ctx.accounts.instructions, &ctx.accounts.state.key())?;
+
+        // SECURITY: Checked arithmetic
+        ctx.accounts.deposit.amount = ctx
+            .accounts
+            .deposit
+            .amount
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientDeposit)?;
+        ctx.accounts.state.total_liabilities = ctx
+            .accounts
+            .state
+            .total_liabilities
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientDeposit)?;
+
+        pay_out(
+            &ctx.accounts.system_program,
+            &ctx.accounts.state,
+            &ctx.accounts.vault,
+            &ctx.accounts.owner,
+            amount,
+        )
+    }
+}
+
+/// Record `amount` as owed to the depositor
+fn credit(state: &mut VaultState, deposit: &mut Deposit, amount: u64) -> Result<()> {
+    deposit.amount = deposit.amount.checked_add(amount).ok_or(ErrorCode::AmountOverflow)?;
+    state.total_liabilities = state.total_liabilities.checked_add(amount).ok_or(ErrorCode::AmountOverflow)?;
+
+    msg!("Deposited {}. Vault owes {}", amount, state.total_liabilities);
+    Ok(())
+}
+
+/// Send `amount` lamports from the vault PDA to `recipient`
+fn pay_out<'info>(
+    system_program: &Program<'info, System>,
+    state: &Account<'info, VaultState>,
+    vault: &SystemAccount<'info>,
+    recipient: &Signer<'info>,
+    amount: u64,
+) -> Result<()> {
+    let state_key = state.key();
+    let seeds: &[&[u8]] = &[b"vault", state_key.as_ref(), &[state.vault_bump]];
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to: recipient.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )?;
+
+    msg!("Withdrew {}. Vault owes {}", amount, state.total_liabilities);
+    Ok(())
+}
+
+/// Require a later `vault_watchdog::assert_vault_solvent` on `state`
+#[cfg(feature = "secure")]
+fn require_watchdog(instructions: &AccountInfo, state: &Pubkey) -> Result<()> {
+    let mut index = load_current_index_checked(instructions)? as usize + 1;
+
+    while let Ok(ix) = load_instruction_at_checked(index, instructions) {
+        let is_assertion = ix.program_id == WATCHDOG_ID
+            && ix.data.get(..8) == Some(&ASSERT_SOLVENT_DISCRIMINATOR[..])
+            && ix.accounts.first().map(|meta| meta.pubkey) == Some(*state);
+
+        if is_assertion {
+            return Ok(());
+        }
+        index += 1;
+    }
+
+    err!(ErrorCode::WatchdogMissing)
+}
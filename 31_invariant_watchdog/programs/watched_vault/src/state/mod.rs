@@ -0,0 +1,27 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct VaultState {
+    /// Creator of the vault (32 bytes)
+    pub admin: Pubkey,
+    /// Sum of all deposits owed to depositors (8 bytes)
+    pub total_liabilities: u64,
+    /// Bump of the lamport-holding vault PDA (1 byte)
+    pub vault_bump: u8,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Deposit {
+    /// Vault this deposit belongs to (32 bytes)
+    pub state: Pubkey,
+    /// Depositor (32 bytes)
+    pub owner: Pubkey,
+    /// Lamports owed to the depositor (8 bytes)
+    pub amount: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
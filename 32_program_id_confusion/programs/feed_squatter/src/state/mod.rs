@@ -0,0 +1,12 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+/// Byte-for-byte copy of `price_feed::PriceFeed`
+#[account]
+pub struct PriceFeed {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub price: u64,
+    pub bump: u8,
+}
@@ -0,0 +1,118 @@
+//! Every constraint on the contexts that price a position, tripped one account at a time.
+//!
+//! `price_feed::ID` here is the devnet id this crate was built with; the
+//! feed program is deployed at `deployed_feed_program()`. The feed's owner
+//! in the secure path is checked by `read_feed`, not by the context.
+
+use anchor_lang::prelude::*;
+use price_feed::PriceFeed;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, Fixture,
+    TestAccount,
+};
+
+use crate::{Market, Position, SecurePricePosition, VulnerablePricePosition};
+
+fn deployed_feed_program() -> Pubkey {
+    "PriceFeed1111111111111111111111111111111111".parse().unwrap()
+}
+
+/// A feed for `market`'s oracle and mint, created by `program`
+fn feed(market: &Market, program: Pubkey) -> TestAccount {
+    let (address, bump) =
+        Pubkey::find_program_address(&[b"feed", market.oracle.as_ref(), market.mint.as_ref()], &program);
+    TestAccount::anchor(&PriceFeed {
+        authority: market.oracle,
+        mint: market.mint,
+        price: 25,
+        bump,
+    })
+    .owned_by(program)
+    .at(address)
+}
+
+/// A market set up with the deployed feed program
+fn market() -> Market {
+    Market {
+        admin: Pubkey::new_from_array([1; 32]),
+        mint: Pubkey::new_from_array([2; 32]),
+        oracle: Pubkey::new_from_array([3; 32]),
+        price_feed_program: deployed_feed_program(),
+        bump: 255,
+    }
+}
+
+/// A market, a position in it, and the feed created by `feed_program`
+fn price_fixture(feed_program: Pubkey) -> Fixture {
+    let market = market();
+    let market_account = TestAccount::anchor(&market);
+    let borrower = TestAccount::signer();
+    let (position, bump) = Pubkey::find_program_address(
+        &[b"position", market_account.key.as_ref(), borrower.key.as_ref()],
+        &crate::ID,
+    );
+    let position = TestAccount::anchor(&Position {
+        market: market_account.key,
+        borrower: borrower.key,
+        collateral: 1_000,
+        borrow_limit: 0,
+        bump,
+    })
+    .at(position);
+    Fixture::new()
+        .with("market", market_account)
+        .with("position", position)
+        .with("borrower", borrower)
+        .with("feed", feed(&market, feed_program))
+}
+
+fn secure_fixture() -> Fixture {
+    price_fixture(deployed_feed_program())
+}
+
+#[test]
+fn secure_price_position_prices_the_borrowers_own_position() {
+    assert_owner_violation!(SecurePricePosition, secure_fixture(), "market");
+    assert_seeds_violation!(SecurePricePosition, secure_fixture(), "position");
+    assert_owner_violation!(SecurePricePosition, secure_fixture(), "position");
+    assert_constraint_violation!(
+        SecurePricePosition,
+        secure_fixture(),
+        "position" => |position| position.edit(|stored: &mut Position| stored.market = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_constraint_violation!(
+        SecurePricePosition,
+        secure_fixture(),
+        "position" => |position| position.edit(|stored: &mut Position| stored.borrower = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+}
+
+#[test]
+fn secure_price_position_requires_the_borrower() {
+    assert_seeds_violation!(SecurePricePosition, secure_fixture(), "borrower");
+    assert_signer_violation!(SecurePricePosition, secure_fixture(), "borrower");
+}
+
+#[test]
+fn secure_price_position_derives_the_feed_under_the_recorded_program() {
+    assert_seeds_violation!(SecurePricePosition, secure_fixture(), "feed");
+    assert_constraint_violation!(
+        SecurePricePosition,
+        secure_fixture(),
+        "feed" => feed(&market(), price_feed::ID),
+        ErrorCode::ConstraintSeeds
+    );
+}
+
+#[test]
+fn vulnerable_price_position_rejects_the_deployed_feed_and_accepts_a_squatted_one() {
+    // The baseline is a feed planted at the devnet id; the oracle's real feed is what fails
+    assert_constraint_violation!(
+        VulnerablePricePosition,
+        price_fixture(price_feed::ID),
+        "feed" => feed(&market(), deployed_feed_program()),
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+}
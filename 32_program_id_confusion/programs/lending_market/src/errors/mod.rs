@@ -0,0 +1,13 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Feed is not owned by the market's feed program")]
+    UntrustedFeed,
+    #[msg("Feed account is not a price feed")]
+    MalformedFeed,
+    #[msg("Borrow limit overflowed")]
+    LimitOverflow,
+}
@@ -0,0 +1,58 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializeMarket<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 32 + 32 + 32 + 1, // discriminator + admin + mint + oracle + price_feed_program + bump
+        seeds = [b"market", mint.key().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Only its address is recorded; the collateral asset
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: The deployed feed program; its address is what the market trusts
+    #[account(executable)]
+    pub price_feed_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = borrower,
+        space = 8 + 32 + 32 + 8 + 8 + 1, // discriminator + market + borrower + collateral + borrow_limit + bump
+        seeds = [b"position", market.key().as_ref(), borrower.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub borrower: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,27 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecurePricePosition<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        has_one = market,
+        has_one = borrower,
+        seeds = [b"position", market.key().as_ref(), borrower.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+
+    pub borrower: Signer<'info>,
+
+    /// CHECK: Owner and layout are checked by `read_feed`
+    #[account(
+        seeds = [b"feed", market.oracle.as_ref(), market.mint.as_ref()],
+        bump,
+        seeds::program = market.price_feed_program
+    )]
+    pub feed: UncheckedAccount<'info>,
+}
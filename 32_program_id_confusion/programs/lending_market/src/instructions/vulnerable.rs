@@ -0,0 +1,27 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerablePricePosition<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        has_one = market,
+        has_one = borrower,
+        seeds = [b"position", market.key().as_ref(), borrower.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+
+    pub borrower: Signer<'info>,
+
+    // VULNERABILITY: Owner and PDA are both checked against price_feed::ID
+    #[account(
+        seeds = [b"feed", market.oracle.as_ref(), market.mint.as_ref()],
+        bump = feed.bump,
+        seeds::program = price_feed::ID
+    )]
+    pub feed: Account<'info, PriceFeed>,
+}
@@ -0,0 +1,30 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Market, Position};
+
+#[test]
+fn market_layout_is_stable() {
+    let market = Market {
+        admin: Pubkey::new_from_array([1; 32]),
+        mint: Pubkey::new_from_array([2; 32]),
+        oracle: Pubkey::new_from_array([3; 32]),
+        price_feed_program: Pubkey::new_from_array([4; 32]),
+        bump: 81,
+    };
+    assert_golden!("Market", golden::anchor(&market));
+}
+
+#[test]
+fn position_layout_is_stable() {
+    let position = Position {
+        market: Pubkey::new_from_array([6; 32]),
+        borrower: Pubkey::new_from_array([7; 32]),
+        collateral: 0x8182838485868788,
+        borrow_limit: 0x9192939495969798,
+        bump: 161,
+    };
+    assert_golden!("Position", golden::anchor(&position));
+}
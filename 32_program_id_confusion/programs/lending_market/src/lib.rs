@@ -0,0 +1,125 @@
+use price_feed::PriceFeed;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Share of the collateral's value that may be borrowed (50%)
+pub const LTV_BPS: u64 = 5_000;
+
+/// Basis point denominator
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+#[feature_gated]
+#[program]
+pub mod lending_market {
+    use super::*;
+
+    /// Create a market for `mint`, priced by `oracle`'s feed
+    ///
+    /// The feed program is passed as its executable account and recorded,
+    /// so the market trusts the program that is actually deployed rather
+    /// than the id `price_feed` was compiled with.
+    pub fn initialize_market(ctx: Context<InitializeMarket>, oracle: Pubkey) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        market.admin = ctx.accounts.admin.key();
+        market.mint = ctx.accounts.mint.key();
+        market.oracle = oracle;
+        market.price_feed_program = ctx.accounts.price_feed_program.key();
+        market.bump = ctx.bumps.market;
+
+        msg!("Market priced by {} through {}", oracle, market.price_feed_program);
+        Ok(())
+    }
+
+    /// Open a position backed by `collateral` units of the market's mint
+    pub fn open_position(ctx: Context<OpenPosition>, collateral: u64) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.market = ctx.accounts.market.key();
+        position.borrower = ctx.accounts.borrower.key();
+        position.collateral = collateral;
+        position.borrow_limit = 0;
+        position.bump = ctx.bumps.position;
+
+        msg!("Position opened with {} collateral", collateral);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Price a position from a feed owned by `price_feed::ID`
+    ///
+    /// Security Issue: This crate links `price_feed` with its `devnet`
+    /// feature, so `price_feed::ID` is the stale devnet id, not the
+    /// deployed feed program. `Account<PriceFeed>` checks the owner against
+    /// that id and `seeds::program` derives the PDA under it: the oracle's
+    /// real feed is rejected, and a feed planted by whoever deploys to the
+    /// devnet id is accepted at any price.
+    #[vulnerable]
+    pub fn vulnerable_price_position(ctx: Context<VulnerablePricePosition>) -> Result<()> {
+        // VULNERABILITY: The feed was validated against a compiled-in id that is not the deployed one
+        let price = ctx.accounts.feed.price;
+
+        apply_price(&mut ctx.accounts.position, price)
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that validates the feed
+    // against the program recorded in the market.
+
+    /// SECURE: Price a position from the feed program recorded at setup
+    ///
+    /// Security Fix: The feed's PDA is derived under, and its owner checked
+    /// against, `market.price_feed_program`, which was read from the
+    /// deployed program's account. Which id `price_feed` was compiled with
+    /// no longer matters.
+    #[secure]
+    pub fn secure_price_position(ctx: Context<SecurePricePosition>) -> Result<()> {
+        // SECURITY: Owner and layout are checked against the deployed feed program
+        let feed = read_feed(&ctx.accounts.feed, &ctx.accounts.market.price_feed_program)?;
+
+        apply_price(&mut ctx.accounts.position, feed.price)
+    }
+}
+
+/// Deserialize a `PriceFeed` owned by `program`
+#[cfg(feature = "secure")]
+fn read_feed(feed: &AccountInfo, program: &Pubkey) -> Result<PriceFeed> {
+    require_keys_eq!(*feed.owner, *program, ErrorCode::UntrustedFeed);
+
+    let data = feed.try_borrow_data()?;
+    PriceFeed::try_deserialize(&mut &data[..]).map_err(|_| error!(ErrorCode::MalformedFeed))
+}
+
+/// Set the position's borrow limit from the collateral's unit price
+fn apply_price(position: &mut Position, price: u64) -> Result<()> {
+    position.borrow_limit = (position.collateral as u128)
+        .checked_mul(price as u128)
+        .and_then(|value| value.checked_mul(LTV_BPS as u128))
+        .map(|limit| limit / BPS_DENOMINATOR as u128)
+        .and_then(|limit| u64::try_from(limit).ok())
+        .ok_or(ErrorCode::LimitOverflow)?;
+
+    msg!("Borrow limit set to {}", position.borrow_limit);
+    Ok(())
+}
@@ -0,0 +1,31 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Market {
+    /// Creator of the market (32 bytes)
+    pub admin: Pubkey,
+    /// Collateral asset (32 bytes)
+    pub mint: Pubkey,
+    /// Oracle whose feed prices the collateral (32 bytes)
+    pub oracle: Pubkey,
+    /// Feed program as deployed, read from its program account (32 bytes)
+    pub price_feed_program: Pubkey,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Position {
+    /// Market the position belongs to (32 bytes)
+    pub market: Pubkey,
+    /// Owner of the position (32 bytes)
+    pub borrower: Pubkey,
+    /// Collateral units deposited (8 bytes)
+    pub collateral: u64,
+    /// Amount the borrower may draw, from the last price (8 bytes)
+    pub borrow_limit: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
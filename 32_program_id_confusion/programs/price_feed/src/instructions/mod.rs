@@ -0,0 +1,36 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct InitializeFeed<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PriceFeed::LEN,
+        seeds = [b"feed", authority.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub feed: Account<'info, PriceFeed>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Only its address is recorded; the asset being priced
+    pub mint: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePrice<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [b"feed", authority.key().as_ref(), feed.mint.as_ref()],
+        bump = feed.bump
+    )]
+    pub feed: Account<'info, PriceFeed>,
+
+    pub authority: Signer<'info>,
+}
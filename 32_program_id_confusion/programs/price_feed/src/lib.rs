@@ -0,0 +1,47 @@
+//! Oracle whose feeds the lending market prices collateral with.
+//!
+//! Like many published program crates, it declares a different id per
+//! cluster behind a feature. The `devnet` id belongs to an old devnet
+//! deployment whose keypair was committed with the repository; the default
+//! is where the program actually runs.
+
+use secref_prelude::*;
+
+#[cfg(feature = "devnet")]
+declare_id!("DevnetFeed1111111111111111111111111111111111");
+#[cfg(not(feature = "devnet"))]
+declare_id!("PriceFeed1111111111111111111111111111111111");
+
+pub mod instructions;
+pub mod state;
+
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+#[program]
+pub mod price_feed {
+    use super::*;
+
+    /// Publish a price for `mint`, signed by the oracle `authority`
+    pub fn initialize_feed(ctx: Context<InitializeFeed>, price: u64) -> Result<()> {
+        let feed = &mut ctx.accounts.feed;
+        feed.authority = ctx.accounts.authority.key();
+        feed.mint = ctx.accounts.mint.key();
+        feed.price = price;
+        feed.bump = ctx.bumps.feed;
+
+        msg!("Feed initialized at {}", price);
+        Ok(())
+    }
+
+    /// Move the price; only the feed's authority may
+    pub fn update_price(ctx: Context<UpdatePrice>, price: u64) -> Result<()> {
+        ctx.accounts.feed.price = price;
+
+        msg!("Price updated to {}", price);
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct PriceFeed {
+    /// Oracle that publishes this feed (32 bytes)
+    pub authority: Pubkey,
+    /// Asset being priced (32 bytes)
+    pub mint: Pubkey,
+    /// Value of one unit (8 bytes)
+    pub price: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl PriceFeed {
+    /// Serialized size without the discriminator
+    pub const LEN: usize = 32 + 32 + 8 + 1; // authority + mint + price + bump
+}
@@ -0,0 +1,183 @@
+//! Every constraint on the renting and claiming contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::runtime::account_already_in_use;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, Fixture,
+    TestAccount,
+};
+
+use crate::{instruction, Claim, Listing, OwnerClaimPerk, RentNft, Rental, SecureClaimPerk};
+
+const DAY: i64 = 0;
+
+fn pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &crate::ID)
+}
+
+fn listing_account(owner: Pubkey) -> TestAccount {
+    let nft_mint = Pubkey::new_unique();
+    let (address, bump) = pda(&[b"listing", nft_mint.as_ref()]);
+    TestAccount::anchor(&Listing {
+        owner,
+        nft_mint,
+        daily_fee: 1_000_000,
+        rented_until: 0,
+        rentals: 0,
+        bump,
+    })
+    .at(address)
+}
+
+fn claim_address(listing: &TestAccount) -> Pubkey {
+    let nft_mint = listing.state::<Listing>().nft_mint;
+    pda(&[b"claim", nft_mint.as_ref(), DAY.to_le_bytes().as_ref()]).0
+}
+
+fn rent_fixture() -> Fixture {
+    let owner = TestAccount::signer();
+    let listing = listing_account(owner.key);
+    let (rental, _) = pda(&[b"rental", listing.key.as_ref(), 0u64.to_le_bytes().as_ref()]);
+    let (receipt_mint, _) = pda(&[b"receipt_mint", rental.as_ref()]);
+    let (receipt, _) = pda(&[b"receipt", rental.as_ref()]);
+    Fixture::new()
+        .with("listing", listing)
+        .with(
+            "rental",
+            TestAccount::uninitialized(8 + 32 + 32 + 32 + 8 + 8 + 1).at(rental),
+        )
+        .with("receipt_mint", TestAccount::uninitialized(0).at(receipt_mint))
+        .with("receipt", TestAccount::uninitialized(0).at(receipt))
+        .with("renter", TestAccount::signer())
+        .with("owner", owner)
+        .with("token_2022_program", TestAccount::program(anchor_spl::token_2022::ID))
+        .with("system_program", TestAccount::system_program())
+}
+
+fn secure_claim_fixture() -> Fixture {
+    let renter = TestAccount::signer();
+    let listing = listing_account(Pubkey::new_unique());
+    let (rental, bump) = pda(&[b"rental", listing.key.as_ref(), 0u64.to_le_bytes().as_ref()]);
+    let (receipt_mint, _) = pda(&[b"receipt_mint", rental.as_ref()]);
+    let (receipt, _) = pda(&[b"receipt", rental.as_ref()]);
+    let claim = claim_address(&listing);
+    let rental_account = TestAccount::anchor(&Rental {
+        listing: listing.key,
+        renter: renter.key,
+        receipt_mint,
+        index: 0,
+        expires_at: i64::MAX,
+        bump,
+    })
+    .at(rental);
+    Fixture::new()
+        .with("claim", TestAccount::uninitialized(8 + Claim::LEN).at(claim))
+        .with("listing", listing)
+        .with("rental", rental_account)
+        .with(
+            "receipt",
+            TestAccount::token_account(receipt_mint, renter.key, 1)
+                .owned_by(anchor_spl::token_2022::ID)
+                .at(receipt),
+        )
+        .with("renter", renter)
+        .with("system_program", TestAccount::system_program())
+        .args(instruction::SecureClaimPerk { day: DAY })
+}
+
+fn owner_claim_fixture() -> Fixture {
+    let owner = TestAccount::signer();
+    let listing = listing_account(owner.key);
+    let claim = claim_address(&listing);
+    Fixture::new()
+        .with("claim", TestAccount::uninitialized(8 + Claim::LEN).at(claim))
+        .with("listing", listing)
+        .with("owner", owner)
+        .with("system_program", TestAccount::system_program())
+        .args(instruction::OwnerClaimPerk { day: DAY })
+}
+
+fn existing_claim(claim: &TestAccount) -> TestAccount {
+    TestAccount::anchor(&Claim {
+        nft_mint: Pubkey::new_unique(),
+        day: DAY,
+        claimant: Pubkey::new_unique(),
+        bump: 0,
+    })
+    .at(claim.key)
+}
+
+#[test]
+fn rent_pays_the_listing_owner_for_the_next_rental() {
+    assert_seeds_violation!(RentNft, rent_fixture(), "listing");
+    assert_constraint_violation!(RentNft, rent_fixture(), "owner" => TestAccount::signer(), ErrorCode::ConstraintHasOne);
+    assert_seeds_violation!(RentNft, rent_fixture(), "rental");
+    assert_seeds_violation!(RentNft, rent_fixture(), "receipt_mint");
+    assert_seeds_violation!(RentNft, rent_fixture(), "receipt");
+    assert_signer_violation!(RentNft, rent_fixture(), "renter");
+}
+
+#[test]
+fn secure_claim_perk_takes_the_rental_of_this_listing() {
+    assert_seeds_violation!(SecureClaimPerk, secure_claim_fixture(), "listing");
+    assert_seeds_violation!(SecureClaimPerk, secure_claim_fixture(), "rental");
+    assert_owner_violation!(SecureClaimPerk, secure_claim_fixture(), "rental");
+    assert_constraint_violation!(
+        SecureClaimPerk,
+        secure_claim_fixture(),
+        "rental" => |rental| rental.edit(|stored: &mut Rental| stored.listing = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+}
+
+#[test]
+fn secure_claim_perk_pays_only_the_current_renter() {
+    assert_constraint_violation!(
+        SecureClaimPerk,
+        secure_claim_fixture(),
+        "rental" => |rental| rental.edit(|stored: &mut Rental| stored.renter = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_signer_violation!(SecureClaimPerk, secure_claim_fixture(), "renter");
+}
+
+#[test]
+fn secure_claim_perk_requires_the_renters_receipt() {
+    assert_seeds_violation!(SecureClaimPerk, secure_claim_fixture(), "receipt");
+    let fixture = secure_claim_fixture();
+    let renter = fixture.key("renter");
+    let other_mint = TestAccount::token_account(Pubkey::new_unique(), renter, 1)
+        .owned_by(anchor_spl::token_2022::ID)
+        .at(fixture.key("receipt"));
+    assert_constraint_violation!(SecureClaimPerk, fixture, "receipt" => other_mint, ErrorCode::ConstraintTokenMint);
+    let fixture = secure_claim_fixture();
+    let receipt_mint = fixture.state::<Rental>("rental").receipt_mint;
+    let someone_elses = TestAccount::token_account(receipt_mint, Pubkey::new_unique(), 1)
+        .owned_by(anchor_spl::token_2022::ID)
+        .at(fixture.key("receipt"));
+    assert_constraint_violation!(SecureClaimPerk, fixture, "receipt" => someone_elses, ErrorCode::ConstraintTokenOwner);
+}
+
+#[test]
+fn secure_claim_perk_records_one_claim_per_nft_and_day() {
+    assert_seeds_violation!(SecureClaimPerk, secure_claim_fixture(), "claim");
+    assert_constraint_violation!(
+        SecureClaimPerk,
+        secure_claim_fixture(),
+        "claim" => |claim| *claim = existing_claim(claim),
+        account_already_in_use()
+    );
+}
+
+#[test]
+fn owner_claim_perk_requires_the_listing_owner() {
+    assert_seeds_violation!(OwnerClaimPerk, owner_claim_fixture(), "claim");
+    assert_seeds_violation!(OwnerClaimPerk, owner_claim_fixture(), "listing");
+    assert_constraint_violation!(
+        OwnerClaimPerk,
+        owner_claim_fixture(),
+        "owner" => TestAccount::signer(),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_signer_violation!(OwnerClaimPerk, owner_claim_fixture(), "owner");
+}
@@ -0,0 +1,23 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Mint is not an NFT: it needs 0 decimals and a supply of 1")]
+    NotAnNft,
+    #[msg("Rentals last between 1 and 30 days")]
+    InvalidDuration,
+    #[msg("The NFT is rented")]
+    AlreadyRented,
+    #[msg("Rental fee overflowed")]
+    FeeOverflow,
+    #[msg("Perks can only be claimed for the current day")]
+    WrongDay,
+    #[msg("Signer does not hold the NFT")]
+    NotHolder,
+    #[msg("The rental has expired")]
+    RentalExpired,
+    #[msg("Renter does not hold the rental's receipt")]
+    NoReceipt,
+}
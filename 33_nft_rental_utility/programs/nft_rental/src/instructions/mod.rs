@@ -0,0 +1,109 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct List<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 32 + 32 + 8 + 8 + 8 + 1, // discriminator + owner + nft_mint + daily_fee + rented_until + rentals + bump
+        seeds = [b"listing", nft_mint.key().as_ref()],
+        bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        init,
+        payer = owner,
+        token::mint = nft_mint,
+        token::authority = listing,
+        seeds = [b"vault", nft_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = nft_mint, token::authority = owner)]
+    pub owner_nft: Account<'info, TokenAccount>,
+
+    pub nft_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RentNft<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [b"listing", listing.nft_mint.as_ref()],
+        bump = listing.bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        init,
+        payer = renter,
+        space = 8 + 32 + 32 + 32 + 8 + 8 + 1, // discriminator + listing + renter + receipt_mint + index + expires_at + bump
+        seeds = [b"rental", listing.key().as_ref(), listing.rentals.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub rental: Account<'info, Rental>,
+
+    /// CHECK: Created and initialized as a non-transferable Token-2022 mint by `rent`
+    #[account(mut, seeds = [b"receipt_mint", rental.key().as_ref()], bump)]
+    pub receipt_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Created and initialized as the renter's receipt account by `rent`
+    #[account(mut, seeds = [b"receipt", rental.key().as_ref()], bump)]
+    pub receipt: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub renter: Signer<'info>,
+
+    /// CHECK: Receives the fee; checked against the listing by `has_one`
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Delist<'info> {
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner,
+        seeds = [b"listing", listing.nft_mint.as_ref()],
+        bump = listing.bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(mut, seeds = [b"vault", listing.nft_mint.as_ref()], bump)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = listing.nft_mint, token::authority = owner)]
+    pub owner_nft: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,63 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use anchor_spl::token_interface;
+
+use super::*;
+
+#[derive(Accounts)]
+#[instruction(day: i64)]
+pub struct SecureClaimPerk<'info> {
+    #[account(
+        init,
+        payer = renter,
+        space = 8 + Claim::LEN,
+        seeds = [b"claim", listing.nft_mint.as_ref(), day.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub claim: Account<'info, Claim>,
+
+    #[account(seeds = [b"listing", listing.nft_mint.as_ref()], bump = listing.bump)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        has_one = listing,
+        has_one = renter,
+        seeds = [b"rental", listing.key().as_ref(), rental.index.to_le_bytes().as_ref()],
+        bump = rental.bump
+    )]
+    pub rental: Account<'info, Rental>,
+
+    #[account(
+        seeds = [b"receipt", rental.key().as_ref()],
+        bump,
+        token::mint = rental.receipt_mint,
+        token::authority = renter
+    )]
+    pub receipt: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    #[account(mut)]
+    pub renter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(day: i64)]
+pub struct OwnerClaimPerk<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Claim::LEN,
+        seeds = [b"claim", listing.nft_mint.as_ref(), day.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub claim: Account<'info, Claim>,
+
+    #[account(has_one = owner, seeds = [b"listing", listing.nft_mint.as_ref()], bump = listing.bump)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
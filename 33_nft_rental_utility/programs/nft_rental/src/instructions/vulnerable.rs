@@ -0,0 +1,27 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+#[instruction(day: i64)]
+pub struct VulnerableClaimPerk<'info> {
+    #[account(
+        init,
+        payer = holder,
+        space = 8 + Claim::LEN,
+        seeds = [b"claim", nft_mint.key().as_ref(), day.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub claim: Account<'info, Claim>,
+
+    // VULNERABILITY: Only proves the signer holds the NFT during this instruction
+    #[account(token::mint = nft_mint, token::authority = holder)]
+    pub holder_nft: Account<'info, TokenAccount>,
+
+    pub nft_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,43 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Claim, Listing, Rental};
+
+#[test]
+fn listing_layout_is_stable() {
+    let listing = Listing {
+        owner: Pubkey::new_from_array([1; 32]),
+        nft_mint: Pubkey::new_from_array([2; 32]),
+        daily_fee: 0x3132333435363738,
+        rented_until: 0x4142434445464748,
+        rentals: 0x5152535455565758,
+        bump: 97,
+    };
+    assert_golden!("Listing", golden::anchor(&listing));
+}
+
+#[test]
+fn rental_layout_is_stable() {
+    let rental = Rental {
+        listing: Pubkey::new_from_array([7; 32]),
+        renter: Pubkey::new_from_array([8; 32]),
+        receipt_mint: Pubkey::new_from_array([9; 32]),
+        index: 0xa1a2a3a4a5a6a7a8,
+        expires_at: 0x32b2b3b4b5b6b7b8,
+        bump: 193,
+    };
+    assert_golden!("Rental", golden::anchor(&rental));
+}
+
+#[test]
+fn claim_layout_is_stable() {
+    let claim = Claim {
+        nft_mint: Pubkey::new_from_array([13; 32]),
+        day: 0x62e2e3e4e5e6e7e8,
+        claimant: Pubkey::new_from_array([15; 32]),
+        bump: 1,
+    };
+    assert_golden!("Claim", golden::anchor(&claim));
+}
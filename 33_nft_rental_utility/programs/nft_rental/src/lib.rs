@@ -0,0 +1,309 @@
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::system_program::{self, CreateAccount, Transfer};
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use anchor_spl::token_2022::spl_token_2022::instruction::initialize_non_transferable_mint;
+use anchor_spl::token_2022::spl_token_2022::state::{Account as Token2022Account, Mint as Token2022Mint};
+use anchor_spl::token_2022::{self, InitializeAccount3, InitializeMint2, MintTo, Token2022};
+use secref_guards::rent::minimum_balance;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Length of a rental day and of a perk period, in seconds
+pub const DAY: i64 = 86_400;
+
+/// Longest rental, in days
+pub const MAX_RENTAL_DAYS: u64 = 30;
+
+#[feature_gated]
+#[program]
+pub mod nft_rental {
+    use super::*;
+
+    /// Escrow an NFT and offer it for rent at `daily_fee` lamports a day
+    ///
+    /// The NFT stays in the listing's vault until it is delisted, so no
+    /// renter ever holds it and nobody can lend it out from under the owner.
+    pub fn list(ctx: Context<List>, daily_fee: u64) -> Result<()> {
+        let nft_mint = &ctx.accounts.nft_mint;
+        require!(nft_mint.decimals == 0 && nft_mint.supply == 1, ErrorCode::NotAnNft);
+
+        let listing = &mut ctx.accounts.listing;
+        listing.owner = ctx.accounts.owner.key();
+        listing.nft_mint = nft_mint.key();
+        listing.daily_fee = daily_fee;
+        listing.rented_until = 0;
+        listing.rentals = 0;
+        listing.bump = ctx.bumps.listing;
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.owner_nft.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        msg!("Listed {} at {} lamports a day", listing.nft_mint, daily_fee);
+        Ok(())
+    }
+
+    /// Rent a listed NFT for `days`, receiving a non-transferable receipt
+    ///
+    /// Creates the rental PDA, a Token-2022 receipt mint with the
+    /// `NonTransferable` extension (the rental is its mint authority), and
+    /// the renter's receipt account holding one receipt.
+    pub fn rent(ctx: Context<RentNft>, days: u64) -> Result<()> {
+        require!(days > 0 && days <= MAX_RENTAL_DAYS, ErrorCode::InvalidDuration);
+        let now = Clock::get()?.unix_timestamp;
+        let listing = &mut ctx.accounts.listing;
+        require!(listing.rented_until <= now, ErrorCode::AlreadyRented);
+
+        let fee = listing.daily_fee.checked_mul(days).ok_or(ErrorCode::FeeOverflow)?;
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.renter.to_account_info(),
+                    to: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+
+        let expires_at = now + days as i64 * DAY;
+        let rental = &mut ctx.accounts.rental;
+        rental.listing = listing.key();
+        rental.renter = ctx.accounts.renter.key();
+        rental.receipt_mint = ctx.accounts.receipt_mint.key();
+        rental.index = listing.rentals;
+        rental.expires_at = expires_at;
+        rental.bump = ctx.bumps.rental;
+
+        listing.rented_until = expires_at;
+        listing.rentals += 1;
+        msg!("Rented {} until {}", listing.nft_mint, expires_at);
+
+        issue_receipt(&ctx)
+    }
+
+    /// Return the NFT to its owner and close the listing, once no rental is running
+    pub fn delist(ctx: Context<Delist>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(ctx.accounts.listing.rented_until <= now, ErrorCode::AlreadyRented);
+
+        let nft_mint = ctx.accounts.listing.nft_mint;
+        let seeds: &[&[u8]] = &[b"listing", nft_mint.as_ref(), &[ctx.accounts.listing.bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.owner_nft.to_account_info(),
+                    authority: ctx.accounts.listing.to_account_info(),
+                },
+                &[seeds],
+            ),
+            1,
+        )?;
+
+        msg!("Delisted {}", nft_mint);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Grant the day's perk to whoever holds the NFT right now
+    ///
+    /// Security Issue: Holding the token account for the length of one
+    /// instruction is all this checks. The NFT can be transferred in before
+    /// this instruction and out after it in the same transaction - from a
+    /// flash-lending pool, a marketplace escrow or a renter who was handed
+    /// the NFT - and the perk goes to the borrower, not the owner or renter.
+    #[vulnerable]
+    pub fn vulnerable_claim_perk(ctx: Context<VulnerableClaimPerk>, day: i64) -> Result<()> {
+        require!(day == current_day()?, ErrorCode::WrongDay);
+
+        // VULNERABILITY: Momentary possession is treated as entitlement
+        let holder = &ctx.accounts.holder_nft;
+        require!(holder.amount == 1, ErrorCode::NotHolder);
+
+        record_claim(
+            &mut ctx.accounts.claim,
+            ctx.accounts.nft_mint.key(),
+            day,
+            ctx.accounts.holder.key(),
+            ctx.bumps.claim,
+        )
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that grants utility from
+    // records of entitlement instead of from possession.
+
+    /// SECURE: Grant the day's perk to the renter of an unexpired rental
+    ///
+    /// Security Fix: The NFT never leaves escrow. The claimant must be the
+    /// rental's renter, the rental must not have expired, and the renter
+    /// must hold its receipt. Receipts come from a `NonTransferable`
+    /// Token-2022 mint, so they cannot be lent for a transaction either.
+    #[secure]
+    pub fn secure_claim_perk(ctx: Context<SecureClaimPerk>, day: i64) -> Result<()> {
+        require!(day == current_day()?, ErrorCode::WrongDay);
+
+        // SECURITY: Entitlement is a time-bound record, proven by a receipt that cannot move
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < ctx.accounts.rental.expires_at, ErrorCode::RentalExpired);
+        require!(ctx.accounts.receipt.amount == 1, ErrorCode::NoReceipt);
+
+        record_claim(
+            &mut ctx.accounts.claim,
+            ctx.accounts.listing.nft_mint,
+            day,
+            ctx.accounts.renter.key(),
+            ctx.bumps.claim,
+        )
+    }
+
+    /// SECURE: Grant the day's perk to the listing's owner while it is not rented
+    #[secure]
+    pub fn owner_claim_perk(ctx: Context<OwnerClaimPerk>, day: i64) -> Result<()> {
+        require!(day == current_day()?, ErrorCode::WrongDay);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(ctx.accounts.listing.rented_until <= now, ErrorCode::AlreadyRented);
+
+        record_claim(
+            &mut ctx.accounts.claim,
+            ctx.accounts.listing.nft_mint,
+            day,
+            ctx.accounts.owner.key(),
+            ctx.bumps.claim,
+        )
+    }
+}
+
+/// Index of the current perk period
+fn current_day() -> Result<i64> {
+    Ok(Clock::get()?.unix_timestamp / DAY)
+}
+
+fn record_claim(claim: &mut Claim, nft_mint: Pubkey, day: i64, claimant: Pubkey, bump: u8) -> Result<()> {
+    claim.nft_mint = nft_mint;
+    claim.day = day;
+    claim.claimant = claimant;
+    claim.bump = bump;
+
+    msg!("Perk for {} on day {} claimed by {}", nft_mint, day, claimant);
+    Ok(())
+}
+
+/// Create the rental's non-transferable receipt mint and mint one receipt to the renter
+///
+/// Anchor's `init` cannot size accounts for Token-2022 extensions on every
+/// release this repository builds against, so the accounts are created
+/// directly: the mint with room for `NonTransferable`, and the account with
+/// room for the extensions Token-2022 requires for such a mint.
+fn issue_receipt(ctx: &Context<RentNft>) -> Result<()> {
+    let accounts = &*ctx.accounts;
+    let rental = accounts.rental.key();
+    let rental_seeds: &[&[u8]] = &[
+        b"rental",
+        accounts.rental.listing.as_ref(),
+        &accounts.rental.index.to_le_bytes(),
+        &[accounts.rental.bump],
+    ];
+    let mint_seeds: &[&[u8]] = &[b"receipt_mint", rental.as_ref(), &[ctx.bumps.receipt_mint]];
+    let receipt_seeds: &[&[u8]] = &[b"receipt", rental.as_ref(), &[ctx.bumps.receipt]];
+
+    let mint_space = ExtensionType::try_calculate_account_len::<Token2022Mint>(&[ExtensionType::NonTransferable])?;
+    create_token_2022_account(accounts, &accounts.receipt_mint, mint_seeds, mint_space)?;
+    invoke(
+        &initialize_non_transferable_mint(&token_2022::ID, &accounts.receipt_mint.key())?,
+        &[accounts.receipt_mint.to_account_info()],
+    )?;
+    token_2022::initialize_mint2(
+        CpiContext::new(
+            accounts.token_2022_program.to_account_info(),
+            InitializeMint2 {
+                mint: accounts.receipt_mint.to_account_info(),
+            },
+        ),
+        0,
+        &rental,
+        None,
+    )?;
+
+    let account_extensions = ExtensionType::get_required_init_account_extensions(&[ExtensionType::NonTransferable]);
+    let receipt_space = ExtensionType::try_calculate_account_len::<Token2022Account>(&account_extensions)?;
+    create_token_2022_account(accounts, &accounts.receipt, receipt_seeds, receipt_space)?;
+    token_2022::initialize_account3(CpiContext::new(
+        accounts.token_2022_program.to_account_info(),
+        InitializeAccount3 {
+            account: accounts.receipt.to_account_info(),
+            mint: accounts.receipt_mint.to_account_info(),
+            authority: accounts.renter.to_account_info(),
+        },
+    ))?;
+
+    token_2022::mint_to(
+        CpiContext::new_with_signer(
+            accounts.token_2022_program.to_account_info(),
+            MintTo {
+                mint: accounts.receipt_mint.to_account_info(),
+                to: accounts.receipt.to_account_info(),
+                authority: accounts.rental.to_account_info(),
+            },
+            &[rental_seeds],
+        ),
+        1,
+    )
+}
+
+/// Create a Token-2022-owned account of `space` bytes at a PDA, paid by the renter
+fn create_token_2022_account<'info>(
+    accounts: &RentNft<'info>,
+    account: &UncheckedAccount<'info>,
+    seeds: &[&[u8]],
+    space: usize,
+) -> Result<()> {
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            accounts.system_program.to_account_info(),
+            CreateAccount {
+                from: accounts.renter.to_account_info(),
+                to: account.to_account_info(),
+            },
+            &[seeds],
+        ),
+        minimum_balance(space)?,
+        space as u64,
+        &token_2022::ID,
+    )
+}
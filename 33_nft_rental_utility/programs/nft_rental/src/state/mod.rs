@@ -0,0 +1,52 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Listing {
+    /// Owner of the escrowed NFT (32 bytes)
+    pub owner: Pubkey,
+    /// The NFT (32 bytes)
+    pub nft_mint: Pubkey,
+    /// Rent per day, in lamports (8 bytes)
+    pub daily_fee: u64,
+    /// End of the current or last rental (8 bytes)
+    pub rented_until: i64,
+    /// Rentals so far; seeds the next rental PDA (8 bytes)
+    pub rentals: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Rental {
+    /// Listing rented (32 bytes)
+    pub listing: Pubkey,
+    /// Who is entitled to the NFT's utility until expiry (32 bytes)
+    pub renter: Pubkey,
+    /// Non-transferable Token-2022 mint of this rental's receipt (32 bytes)
+    pub receipt_mint: Pubkey,
+    /// Position among the listing's rentals (8 bytes)
+    pub index: u64,
+    /// Unix time the rental ends (8 bytes)
+    pub expires_at: i64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Claim {
+    /// NFT whose perk was claimed (32 bytes)
+    pub nft_mint: Pubkey,
+    /// Perk period (8 bytes)
+    pub day: i64,
+    /// Who received it (32 bytes)
+    pub claimant: Pubkey,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Claim {
+    /// Serialized size without the discriminator
+    pub const LEN: usize = 32 + 8 + 32 + 1; // nft_mint + day + claimant + bump
+}
@@ -0,0 +1,172 @@
+//! Every constraint on the listing and purchase contexts, tripped one account at a time,
+//! and the royalty checks `secure_buy` makes on its remaining accounts.
+
+use anchor_lang::prelude::*;
+use secref_testkit::runtime::account_already_in_use;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, execute,
+    Fixture, TestAccount,
+};
+
+use crate::{instruction, ErrorCode as MarketError, List, Listing, SecureBuy, VulnerableBuy};
+
+const PRICE: u64 = 1_000_000;
+const SELLER_FEE_BASIS_POINTS: u16 = 500;
+
+fn pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &crate::ID)
+}
+
+fn listing_account(seller: Pubkey, mint: Pubkey) -> TestAccount {
+    let (address, bump) = pda(&[b"listing", mint.as_ref()]);
+    TestAccount::anchor(&Listing {
+        seller,
+        mint,
+        price: PRICE,
+        bump,
+    })
+    .at(address)
+}
+
+fn list_fixture() -> Fixture {
+    let seller = TestAccount::signer();
+    let mint = TestAccount::mint(Pubkey::new_unique(), 0, 1);
+    let (listing, _) = pda(&[b"listing", mint.key.as_ref()]);
+    let (vault, _) = pda(&[b"vault", mint.key.as_ref()]);
+    Fixture::new()
+        .with("listing", TestAccount::uninitialized(8 + 32 + 32 + 8 + 1).at(listing))
+        .with("vault", TestAccount::uninitialized(165).at(vault))
+        .with("seller_nft", TestAccount::token_account(mint.key, seller.key, 1))
+        .with("mint", mint)
+        .with("seller", seller)
+        .with("token_program", TestAccount::token_program())
+        .with("system_program", TestAccount::system_program())
+}
+
+/// The accounts both purchases start with, up to and including `buyer_nft`
+fn purchase(seller: &TestAccount, buyer: &TestAccount) -> Fixture {
+    let mint = Pubkey::new_unique();
+    let listing = listing_account(seller.key, mint);
+    let (vault, _) = pda(&[b"vault", mint.as_ref()]);
+    Fixture::new()
+        .with("listing", listing.clone())
+        .with("vault", TestAccount::token_account(mint, listing.key, 1).at(vault))
+        .with("buyer_nft", TestAccount::token_account(mint, buyer.key, 0))
+}
+
+fn vulnerable_buy_fixture() -> Fixture {
+    let (seller, buyer) = (TestAccount::system(0), TestAccount::signer());
+    purchase(&seller, &buyer)
+        .with("seller", seller)
+        .with("buyer", buyer)
+        .with("token_program", TestAccount::token_program())
+        .with("system_program", TestAccount::system_program())
+}
+
+/// A purchase of an NFT whose metadata pays `creators`, with no recipients passed yet
+fn secure_buy_fixture_for(creators: &[(Pubkey, u8)]) -> Fixture {
+    let (seller, buyer) = (TestAccount::system(0), TestAccount::signer());
+    let fixture = purchase(&seller, &buyer);
+    let mint = fixture.state::<Listing>("listing").mint;
+    fixture
+        .with(
+            "metadata",
+            TestAccount::metadata(mint, SELLER_FEE_BASIS_POINTS, creators),
+        )
+        .with("seller", seller)
+        .with("buyer", buyer)
+        .with("metadata_program", TestAccount::metadata_program())
+        .with("token_program", TestAccount::token_program())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn secure_buy_fixture() -> Fixture {
+    secure_buy_fixture_for(&[(Pubkey::new_unique(), 100)])
+}
+
+#[test]
+fn list_escrows_the_nft_under_the_listing() {
+    assert_seeds_violation!(List, list_fixture(), "listing");
+    assert_seeds_violation!(List, list_fixture(), "vault");
+    assert_constraint_violation!(
+        List,
+        list_fixture(),
+        "listing" => |listing| *listing = listing_account(Pubkey::new_unique(), Pubkey::new_unique()).at(listing.key),
+        account_already_in_use()
+    );
+    let fixture = list_fixture();
+    let borrowed = TestAccount::token_account(fixture.key("mint"), Pubkey::new_unique(), 1);
+    assert_constraint_violation!(List, fixture, "seller_nft" => borrowed, ErrorCode::ConstraintTokenOwner);
+    assert_signer_violation!(List, list_fixture(), "seller");
+}
+
+#[test]
+fn vulnerable_buy_pays_the_listing_seller() {
+    assert_seeds_violation!(VulnerableBuy, vulnerable_buy_fixture(), "listing");
+    assert_seeds_violation!(VulnerableBuy, vulnerable_buy_fixture(), "vault");
+    assert_constraint_violation!(
+        VulnerableBuy,
+        vulnerable_buy_fixture(),
+        "seller" => TestAccount::system(0),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_signer_violation!(VulnerableBuy, vulnerable_buy_fixture(), "buyer");
+}
+
+#[test]
+fn secure_buy_pays_the_listing_seller() {
+    assert_seeds_violation!(SecureBuy, secure_buy_fixture(), "listing");
+    assert_seeds_violation!(SecureBuy, secure_buy_fixture(), "vault");
+    assert_constraint_violation!(
+        SecureBuy,
+        secure_buy_fixture(),
+        "seller" => TestAccount::system(0),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_signer_violation!(SecureBuy, secure_buy_fixture(), "buyer");
+}
+
+#[test]
+fn secure_buy_delivers_the_nft_to_the_buyer() {
+    let fixture = secure_buy_fixture();
+    let other_mint = TestAccount::token_account(Pubkey::new_unique(), fixture.key("buyer"), 0);
+    assert_constraint_violation!(SecureBuy, fixture, "buyer_nft" => other_mint, ErrorCode::ConstraintTokenMint);
+    let fixture = secure_buy_fixture();
+    let mint = fixture.state::<Listing>("listing").mint;
+    let someone_elses = TestAccount::token_account(mint, Pubkey::new_unique(), 0);
+    assert_constraint_violation!(SecureBuy, fixture, "buyer_nft" => someone_elses, ErrorCode::ConstraintTokenOwner);
+}
+
+#[test]
+fn secure_buy_reads_the_listed_mints_metadata() {
+    assert_seeds_violation!(SecureBuy, secure_buy_fixture(), "metadata");
+    assert_owner_violation!(SecureBuy, secure_buy_fixture(), "metadata");
+    let creator = Pubkey::new_unique();
+    assert_constraint_violation!(
+        SecureBuy,
+        secure_buy_fixture(),
+        "metadata" => TestAccount::metadata(Pubkey::new_unique(), 0, &[(creator, 100)]),
+        ErrorCode::ConstraintSeeds
+    );
+    assert_constraint_violation!(
+        SecureBuy,
+        secure_buy_fixture(),
+        "metadata_program" => |program| *program = TestAccount::program(Pubkey::new_unique()),
+        ErrorCode::InvalidProgramId
+    );
+}
+
+#[test]
+fn secure_buy_requires_one_recipient_per_creator() {
+    let mut fixture = secure_buy_fixture();
+    let result = execute!(&mut fixture, instruction::SecureBuy {});
+    assert_eq!(result, Err(Error::from(MarketError::RoyaltyRecipientsMismatch).into()));
+}
+
+#[test]
+fn secure_buy_pays_royalties_only_to_the_metadata_creators() {
+    let creator = Pubkey::new_unique();
+    let mut fixture = secure_buy_fixture_for(&[(creator, 100)]).with("impostor", TestAccount::system(0));
+    let result = execute!(&mut fixture, instruction::SecureBuy {});
+    assert_eq!(result, Err(Error::from(MarketError::WrongRoyaltyRecipient).into()));
+}
@@ -0,0 +1,15 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Mint is not an NFT: it needs 0 decimals and a supply of 1")]
+    NotAnNft,
+    #[msg("Expected one royalty recipient account per royalty")]
+    RoyaltyRecipientsMismatch,
+    #[msg("Royalty recipient is not the creator the metadata lists")]
+    WrongRoyaltyRecipient,
+    #[msg("Royalty calculation overflowed")]
+    RoyaltyOverflow,
+}
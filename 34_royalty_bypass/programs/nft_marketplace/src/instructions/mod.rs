@@ -0,0 +1,48 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct List<'info> {
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + 32 + 32 + 8 + 1, // discriminator + seller + mint + price + bump
+        seeds = [b"listing", mint.key().as_ref()],
+        bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        init,
+        payer = seller,
+        token::mint = mint,
+        token::authority = listing,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint, token::authority = seller)]
+    pub seller_nft: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,43 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use anchor_spl::metadata::{Metadata, MetadataAccount};
+
+use super::*;
+
+/// The metadata's creators follow as writable `remaining_accounts`, in order
+#[derive(Accounts)]
+pub struct SecureBuy<'info> {
+    #[account(
+        mut,
+        close = seller,
+        has_one = seller,
+        seeds = [b"listing", listing.mint.as_ref()],
+        bump = listing.bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(mut, seeds = [b"vault", listing.mint.as_ref()], bump)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = listing.mint, token::authority = buyer)]
+    pub buyer_nft: Account<'info, TokenAccount>,
+
+    // SECURITY: The listed mint's own metadata, owned by Token Metadata
+    #[account(
+        seeds = [b"metadata", metadata_program.key().as_ref(), listing.mint.as_ref()],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub metadata: Box<Account<'info, MetadataAccount>>,
+
+    /// CHECK: Receives the price; checked against the listing by `has_one`
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub metadata_program: Program<'info, Metadata>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,32 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+/// Royalty recipients follow as writable `remaining_accounts`, one per entry of `royalties`
+#[derive(Accounts)]
+pub struct VulnerableBuy<'info> {
+    #[account(
+        mut,
+        close = seller,
+        has_one = seller,
+        seeds = [b"listing", listing.mint.as_ref()],
+        bump = listing.bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(mut, seeds = [b"vault", listing.mint.as_ref()], bump)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = listing.mint, token::authority = buyer)]
+    pub buyer_nft: Account<'info, TokenAccount>,
+
+    /// CHECK: Receives the price; checked against the listing by `has_one`
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
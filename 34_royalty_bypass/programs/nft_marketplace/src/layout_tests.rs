@@ -0,0 +1,17 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::Listing;
+
+#[test]
+fn listing_layout_is_stable() {
+    let listing = Listing {
+        seller: Pubkey::new_from_array([1; 32]),
+        mint: Pubkey::new_from_array([2; 32]),
+        price: 0x3132333435363738,
+        bump: 65,
+    };
+    assert_golden!("Listing", golden::anchor(&listing));
+}
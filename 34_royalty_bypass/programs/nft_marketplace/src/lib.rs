@@ -0,0 +1,213 @@
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Basis points in 100%
+pub const BPS: u64 = 10_000;
+
+/// A fixed-price NFT marketplace.
+///
+/// Sellers escrow an NFT in a vault owned by its listing; a purchase pays
+/// the seller the listed price, pays royalties on top to the NFT's
+/// creators, and releases the NFT to the buyer. Royalty recipients are
+/// passed as `remaining_accounts`, one per creator.
+#[feature_gated]
+#[program]
+pub mod nft_marketplace {
+    use super::*;
+
+    /// Escrow an NFT and list it for `price` lamports
+    pub fn list(ctx: Context<List>, price: u64) -> Result<()> {
+        let mint = &ctx.accounts.mint;
+        require!(mint.decimals == 0 && mint.supply == 1, ErrorCode::NotAnNft);
+
+        let listing = &mut ctx.accounts.listing;
+        listing.seller = ctx.accounts.seller.key();
+        listing.mint = mint.key();
+        listing.price = price;
+        listing.bump = ctx.bumps.listing;
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.seller_nft.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.seller.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        msg!("Listed {} for {} lamports", listing.mint, price);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Buy a listed NFT, paying the royalties the buyer lists
+    ///
+    /// Security Issue: `royalties[i]` lamports go to `remaining_accounts[i]`,
+    /// and both come from the buyer. Nothing ties them to the NFT's
+    /// creators or its royalty rate, so a buyer passes no recipients and
+    /// pays no royalty, or lists their own wallet and pays it to themselves.
+    #[vulnerable]
+    pub fn vulnerable_buy<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VulnerableBuy<'info>>,
+        royalties: Vec<u64>,
+    ) -> Result<()> {
+        require!(
+            royalties.len() == ctx.remaining_accounts.len(),
+            ErrorCode::RoyaltyRecipientsMismatch
+        );
+
+        let buyer = ctx.accounts.buyer.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        pay(
+            &system_program,
+            &buyer,
+            &ctx.accounts.seller,
+            ctx.accounts.listing.price,
+        )?;
+
+        // VULNERABILITY: Recipients and amounts are whatever the buyer supplied
+        for (recipient, amount) in ctx.remaining_accounts.iter().zip(royalties) {
+            pay(&system_program, &buyer, recipient, amount)?;
+        }
+
+        release(
+            &ctx.accounts.listing,
+            &ctx.accounts.vault,
+            &ctx.accounts.buyer_nft,
+            &ctx.accounts.seller,
+            &ctx.accounts.token_program,
+        )
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that takes royalties from
+    // the NFT's metadata.
+
+    /// SECURE: Buy a listed NFT, paying the royalties its metadata sets
+    ///
+    /// Security Fix: The metadata account is derived from the listing's
+    /// mint under the Token Metadata program, so the buyer cannot swap in
+    /// another NFT's. Its `seller_fee_basis_points` sets the royalty, and
+    /// `remaining_accounts` must be exactly its creators, in order; each is
+    /// paid its share.
+    #[secure]
+    pub fn secure_buy<'info>(ctx: Context<'_, '_, 'info, 'info, SecureBuy<'info>>) -> Result<()> {
+        let metadata = &ctx.accounts.metadata;
+        let creators = metadata.creators.as_deref().unwrap_or_default();
+        require!(
+            creators.len() == ctx.remaining_accounts.len(),
+            ErrorCode::RoyaltyRecipientsMismatch
+        );
+
+        let price = ctx.accounts.listing.price;
+        let royalty = price
+            .checked_mul(u64::from(metadata.seller_fee_basis_points))
+            .ok_or(ErrorCode::RoyaltyOverflow)?
+            / BPS;
+
+        let buyer = ctx.accounts.buyer.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        pay(&system_program, &buyer, &ctx.accounts.seller, price)?;
+
+        // SECURITY: Every recipient is the creator the metadata names, paid the metadata's share
+        for (recipient, creator) in ctx.remaining_accounts.iter().zip(creators) {
+            require_keys_eq!(*recipient.key, creator.address, ErrorCode::WrongRoyaltyRecipient);
+            let share = royalty * u64::from(creator.share) / 100;
+            pay(&system_program, &buyer, recipient, share)?;
+        }
+
+        msg!("Paid {} lamports of royalties to {} creators", royalty, creators.len());
+        release(
+            &ctx.accounts.listing,
+            &ctx.accounts.vault,
+            &ctx.accounts.buyer_nft,
+            &ctx.accounts.seller,
+            &ctx.accounts.token_program,
+        )
+    }
+}
+
+/// Move `amount` lamports from the buyer, skipping zero amounts
+fn pay<'info>(
+    system_program: &AccountInfo<'info>,
+    buyer: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    system_program::transfer(
+        CpiContext::new(
+            system_program.clone(),
+            Transfer {
+                from: buyer.clone(),
+                to: recipient.clone(),
+            },
+        ),
+        amount,
+    )
+}
+
+/// Send the escrowed NFT to the buyer and return the vault's rent to the seller
+fn release<'info>(
+    listing: &Account<'info, Listing>,
+    vault: &Account<'info, TokenAccount>,
+    buyer_nft: &Account<'info, TokenAccount>,
+    seller: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[b"listing", listing.mint.as_ref(), &[listing.bump]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token::Transfer {
+                from: vault.to_account_info(),
+                to: buyer_nft.to_account_info(),
+                authority: listing.to_account_info(),
+            },
+            &[seeds],
+        ),
+        1,
+    )?;
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: vault.to_account_info(),
+            destination: seller.clone(),
+            authority: listing.to_account_info(),
+        },
+        &[seeds],
+    ))?;
+
+    msg!("Sold {} for {} lamports", listing.mint, listing.price);
+    Ok(())
+}
@@ -0,0 +1,15 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Listing {
+    /// Who receives the price (32 bytes)
+    pub seller: Pubkey,
+    /// The NFT for sale (32 bytes)
+    pub mint: Pubkey,
+    /// Price in lamports, before royalties (8 bytes)
+    pub price: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
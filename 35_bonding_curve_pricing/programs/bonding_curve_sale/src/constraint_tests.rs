@@ -0,0 +1,156 @@
+//! Every constraint on the sale contexts, tripped one account at a time,
+//! and the checks `secure_buy` makes before it prices the curve.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_seeds_violation, assert_signer_violation, execute,
+    Fixture, TestAccount,
+};
+
+use crate::{instruction, Buy, Curve, ErrorCode as SaleError, InitializeSale, Sale};
+
+const LINEAR: Curve = Curve::Linear {
+    base_price: 1_000_000,
+    slope: 1_000,
+};
+const SUPPLY_CAP: u64 = 1_000_000;
+
+fn sale_address(mint: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"sale", mint.as_ref()], &crate::ID)
+}
+
+fn initialize_fixture() -> Fixture {
+    let mint = Pubkey::new_unique();
+    let (sale, _) = sale_address(mint);
+    Fixture::new()
+        .with(
+            "sale",
+            TestAccount::uninitialized(8 + 32 + Curve::SPACE + 8 + 1).at(sale),
+        )
+        .with("mint", TestAccount::mint(sale, 0, 0).at(mint))
+        .with("creator", TestAccount::signer())
+        .with("system_program", TestAccount::system_program())
+}
+
+/// A sale on `curve` that has already sold `supply` tokens
+fn buy_fixture_for(curve: Curve, supply: u64) -> Fixture {
+    let mint = Pubkey::new_unique();
+    let (sale, bump) = sale_address(mint);
+    let buyer = TestAccount::signer();
+    Fixture::new()
+        .with(
+            "sale",
+            TestAccount::anchor(&Sale {
+                mint,
+                curve,
+                supply_cap: SUPPLY_CAP,
+                bump,
+            })
+            .at(sale),
+        )
+        .with("mint", TestAccount::mint(sale, 0, supply).at(mint))
+        .with("buyer_tokens", TestAccount::token_account(mint, buyer.key, 0))
+        .with("buyer", buyer)
+        .with("token_program", TestAccount::token_program())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn buy_fixture() -> Fixture {
+    buy_fixture_for(LINEAR, 0)
+}
+
+#[test]
+fn initialize_sale_mints_through_the_sale_pda() {
+    assert_seeds_violation!(InitializeSale, initialize_fixture(), "sale");
+    assert_constraint_violation!(
+        InitializeSale,
+        initialize_fixture(),
+        "mint" => |mint| *mint = TestAccount::mint(Pubkey::new_unique(), 0, 0).at(mint.key),
+        ErrorCode::ConstraintMintMintAuthority
+    );
+    assert_signer_violation!(InitializeSale, initialize_fixture(), "creator");
+}
+
+#[test]
+fn initialize_sale_rejects_flat_curves() {
+    let flat = Curve::Linear {
+        base_price: 1_000_000,
+        slope: 0,
+    };
+    let mut fixture = initialize_fixture();
+    let result = execute!(
+        &mut fixture,
+        instruction::InitializeSale {
+            curve: flat,
+            supply_cap: SUPPLY_CAP
+        }
+    );
+    assert_eq!(result, Err(Error::from(SaleError::InvalidCurve).into()));
+}
+
+#[test]
+fn buy_mints_the_sales_own_token() {
+    assert_seeds_violation!(Buy, buy_fixture(), "sale");
+    assert_has_one_violation!(Buy, buy_fixture(), "mint");
+    let fixture = buy_fixture();
+    let other_mint = TestAccount::token_account(Pubkey::new_unique(), fixture.key("buyer"), 0);
+    assert_constraint_violation!(Buy, fixture, "buyer_tokens" => other_mint, ErrorCode::ConstraintTokenMint);
+    let fixture = buy_fixture();
+    let someone_elses = TestAccount::token_account(fixture.key("mint"), Pubkey::new_unique(), 0);
+    assert_constraint_violation!(Buy, fixture, "buyer_tokens" => someone_elses, ErrorCode::ConstraintTokenOwner);
+    assert_signer_violation!(Buy, buy_fixture(), "buyer");
+}
+
+#[test]
+fn secure_buy_stops_at_the_supply_cap() {
+    let mut fixture = buy_fixture_for(LINEAR, SUPPLY_CAP - 1);
+    let result = execute!(
+        &mut fixture,
+        instruction::SecureBuy {
+            amount: 2,
+            max_cost: u64::MAX
+        }
+    );
+    assert_eq!(result, Err(Error::from(SaleError::SupplyCapExceeded).into()));
+
+    // The purchase the vulnerable sale prices at a single token
+    let mut fixture = buy_fixture();
+    let result = execute!(
+        &mut fixture,
+        instruction::SecureBuy {
+            amount: (1 << 62) + 1,
+            max_cost: u64::MAX
+        }
+    );
+    assert_eq!(result, Err(Error::from(SaleError::SupplyCapExceeded).into()));
+}
+
+#[test]
+fn secure_buy_rejects_costs_past_u64() {
+    let steep = Curve::Exponential {
+        base_price: 1_000,
+        doubling_supply: 10_000,
+    };
+    let mut fixture = buy_fixture_for(steep, 0);
+    let result = execute!(
+        &mut fixture,
+        instruction::SecureBuy {
+            amount: SUPPLY_CAP,
+            max_cost: u64::MAX
+        }
+    );
+    assert_eq!(result, Err(Error::from(SaleError::PriceOverflow).into()));
+}
+
+#[test]
+fn secure_buy_honors_the_buyers_maximum() {
+    let mut fixture = buy_fixture();
+    let result = execute!(
+        &mut fixture,
+        instruction::SecureBuy {
+            amount: 10,
+            max_cost: 10_000_000
+        }
+    );
+    assert_eq!(result, Err(Error::from(SaleError::SlippageExceeded).into()));
+}
@@ -0,0 +1,309 @@
+//! Bonding curve pricing.
+//!
+//! A token's price is a function of how many tokens have already been
+//! sold, and buying `amount` tokens costs the sum of the price at every
+//! supply the purchase passes through. Prices stay small for the whole
+//! intended sale, but that sum is quadratic (linear curve) or exponential
+//! in the supply, so it leaves u64 range long before the supply does.
+
+use anchor_lang::prelude::*;
+
+/// The shape of a sale's price curve; tokens have no decimals, so supply counts whole tokens
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Curve {
+    /// `base_price + slope × supply` lamports per token
+    Linear { base_price: u64, slope: u64 },
+    /// `base_price` lamports per token, doubling every `doubling_supply` tokens
+    Exponential { base_price: u64, doubling_supply: u64 },
+}
+
+impl Curve {
+    /// Serialized size: variant tag plus two u64 parameters
+    pub const SPACE: usize = 1 + 8 + 8;
+
+    /// Every price is positive and the curve rises
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            Curve::Linear { base_price, slope } => base_price > 0 && slope > 0,
+            Curve::Exponential {
+                base_price,
+                doubling_supply,
+            } => base_price > 0 && doubling_supply > 0,
+        }
+    }
+}
+
+// ========================================
+// VULNERABLE IMPLEMENTATION
+// ========================================
+// This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+// DO NOT use this code in production environments.
+
+/// VULNERABLE: Cost of `amount` tokens from `supply`, summed in u64
+///
+/// Security Issue: Wrapping arithmetic keeps large purchases from
+/// panicking, so it also keeps them from failing. On the linear curve the
+/// sum of supplies and its product with the slope wrap modulo 2^64; on a
+/// fresh sale with a base price of 1,000,000 and a slope of 1,000,
+/// `2^62 + 1` tokens cost exactly 1,000,000 lamports. On the exponential
+/// curve `wrapping_shl` takes the number of doublings modulo 64, so after
+/// 64 doublings the price drops back to `base_price`.
+pub fn vulnerable_cost(curve: &Curve, supply: u64, amount: u64) -> u64 {
+    match *curve {
+        Curve::Linear { base_price, slope } => {
+            // VULNERABILITY: Gauss's sum of supply..supply + amount, wrapped
+            let first_plus_last = supply.wrapping_mul(2).wrapping_add(amount).wrapping_sub(1);
+            let supplies = amount.wrapping_mul(first_plus_last) / 2;
+            amount
+                .wrapping_mul(base_price)
+                .wrapping_add(slope.wrapping_mul(supplies))
+        }
+        Curve::Exponential {
+            base_price,
+            doubling_supply,
+        } => {
+            let (mut supply, mut remaining, mut total) = (supply, amount, 0u64);
+            while remaining > 0 {
+                let doublings = supply / doubling_supply;
+                let in_segment = (doubling_supply - supply % doubling_supply).min(remaining);
+                // VULNERABILITY: The shift wraps at 64 doublings and the products wrap
+                let price = base_price.wrapping_shl(doublings as u32);
+                total = total.wrapping_add(in_segment.wrapping_mul(price));
+                supply = supply.wrapping_add(in_segment);
+                remaining -= in_segment;
+            }
+            total
+        }
+    }
+}
+
+// ========================================
+// SECURE IMPLEMENTATION
+// ========================================
+// This section contains the SECURE version of the same curves, integrated
+// in u128 one piece at a time and rejected when the result leaves u64.
+
+/// SECURE: Price of the token sold at `supply`, saturating at `u128::MAX`
+///
+/// Exact for any price that fits in a u64, which covers every price a
+/// purchase can pay.
+pub fn price(curve: &Curve, supply: u64) -> u128 {
+    match *curve {
+        Curve::Linear { base_price, slope } => {
+            u128::from(base_price).saturating_add(u128::from(slope) * u128::from(supply))
+        }
+        Curve::Exponential {
+            base_price,
+            doubling_supply,
+        } => {
+            let doublings = supply / doubling_supply;
+            if doublings >= 64 {
+                return u128::MAX;
+            }
+            u128::from(base_price) << doublings
+        }
+    }
+}
+
+/// SECURE: Cost of `amount` tokens from `supply`, or `None` if it exceeds a u64
+///
+/// Security Fix: The curve is integrated piece by piece, each piece in
+/// closed form: the whole linear curve is one piece, and each doubling of
+/// the exponential curve is a flat one. Every sum is taken in u128 with
+/// checked operations and the total must fit in a u64, so a purchase
+/// either pays its exact cost or fails. A valid curve's prices are at
+/// least 1, so the exponential loop gives up within 64 doublings.
+pub fn cost(curve: &Curve, supply: u64, amount: u64) -> Option<u64> {
+    // SECURITY: The last token sold must itself be a u64 supply
+    supply.checked_add(amount)?;
+
+    let total = match *curve {
+        Curve::Linear { base_price, slope } => {
+            // Σ (base_price + slope × s) = amount × base_price + slope × Σ s
+            let n = u128::from(amount);
+            let supplies = (n * u128::from(supply)).checked_add(n * n.saturating_sub(1) / 2)?;
+            (n * u128::from(base_price)).checked_add(u128::from(slope).checked_mul(supplies)?)?
+        }
+        Curve::Exponential {
+            base_price,
+            doubling_supply,
+        } => {
+            let (mut supply, mut remaining, mut total) = (supply, amount, 0u128);
+            while remaining > 0 {
+                let doublings = supply / doubling_supply;
+                if doublings >= 64 {
+                    return None;
+                }
+                let in_segment = (doubling_supply - supply % doubling_supply).min(remaining);
+                let price = u128::from(base_price) << doublings;
+                total = total.checked_add(u128::from(in_segment).checked_mul(price)?)?;
+                if total > u128::from(u64::MAX) {
+                    return None;
+                }
+                supply += in_segment;
+                remaining -= in_segment;
+            }
+            total
+        }
+    };
+    u64::try_from(total).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const LINEAR: Curve = Curve::Linear {
+        base_price: 1_000_000,
+        slope: 1_000,
+    };
+    const EXPONENTIAL: Curve = Curve::Exponential {
+        base_price: 1_000,
+        doubling_supply: 1_000,
+    };
+
+    /// Sum of `price` over every token, the definition `cost` integrates in closed form
+    fn summed(curve: &Curve, supply: u64, amount: u64) -> Option<u64> {
+        let total = (supply..supply + amount).fold(0u128, |total, s| total.saturating_add(price(curve, s)));
+        u64::try_from(total).ok()
+    }
+
+    #[test]
+    fn vulnerable_linear_cost_wraps_to_a_single_token() {
+        let amount = (1 << 62) + 1;
+        assert_eq!(vulnerable_cost(&LINEAR, 0, amount), 1_000_000);
+        assert_eq!(vulnerable_cost(&LINEAR, 0, 1), 1_000_000);
+        assert_eq!(cost(&LINEAR, 0, amount), None);
+    }
+
+    #[test]
+    fn vulnerable_exponential_price_resets_after_64_doublings() {
+        assert_eq!(vulnerable_cost(&EXPONENTIAL, 64 * 1_000, 1), 1_000);
+        assert_eq!(vulnerable_cost(&EXPONENTIAL, 0, 1), 1_000);
+        assert_eq!(price(&EXPONENTIAL, 64 * 1_000), u128::MAX);
+        assert_eq!(cost(&EXPONENTIAL, 64 * 1_000, 1), None);
+    }
+
+    #[test]
+    fn vulnerable_linear_cost_wraps_before_the_cost_does() {
+        // amount × (2 × supply + amount - 1) passes 2^64 while the cost itself still fits
+        let (curve, supply, amount) = (
+            Curve::Linear {
+                base_price: 1,
+                slope: 1,
+            },
+            0,
+            5_000_000_000,
+        );
+        let exact = cost(&curve, supply, amount).unwrap();
+        assert_ne!(vulnerable_cost(&curve, supply, amount), exact);
+    }
+
+    #[test]
+    fn secure_cost_matches_vulnerable_cost_for_small_purchases() {
+        for curve in [LINEAR, EXPONENTIAL] {
+            for supply in [0, 1, 999, 1_000, 20_000] {
+                for amount in [0, 1, 2, 1_000, 5_000] {
+                    assert_eq!(
+                        cost(&curve, supply, amount),
+                        Some(vulnerable_cost(&curve, supply, amount))
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cost_is_the_sum_of_prices() {
+        for curve in [LINEAR, EXPONENTIAL] {
+            for supply in [0, 1, 999, 1_000, 62_999] {
+                for amount in [0, 1, 2, 1_001, 3_000] {
+                    assert_eq!(cost(&curve, supply, amount), summed(&curve, supply, amount));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cost_fails_past_u64_supply() {
+        assert_eq!(cost(&LINEAR, u64::MAX, 1), None);
+        assert_eq!(cost(&LINEAR, u64::MAX, 0), Some(0));
+    }
+
+    #[test]
+    fn invalid_curves_are_flat_or_free() {
+        assert!(LINEAR.is_valid() && EXPONENTIAL.is_valid());
+        assert!(!Curve::Linear {
+            base_price: 0,
+            slope: 1
+        }
+        .is_valid());
+        assert!(!Curve::Linear {
+            base_price: 1,
+            slope: 0
+        }
+        .is_valid());
+        assert!(!Curve::Exponential {
+            base_price: 1,
+            doubling_supply: 0
+        }
+        .is_valid());
+    }
+
+    /// Any valid curve, with parameters small enough that most purchases are affordable
+    fn curves() -> impl Strategy<Value = Curve> {
+        prop_oneof![
+            (1..=1_000_000_000u64, 1..=1_000_000u64)
+                .prop_map(|(base_price, slope)| Curve::Linear { base_price, slope }),
+            (1..=1_000_000_000u64, 1..=100_000u64).prop_map(|(base_price, doubling_supply)| Curve::Exponential {
+                base_price,
+                doubling_supply
+            }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn price_never_decreases(curve in curves(), supply in 0..u64::MAX) {
+            prop_assert!(price(&curve, supply) <= price(&curve, supply + 1));
+        }
+
+        #[test]
+        fn buying_more_never_costs_less(curve in curves(), supply in 0..10_000_000u64, amount in 0..10_000_000u64) {
+            if let Some(more) = cost(&curve, supply, amount + 1) {
+                let fewer = cost(&curve, supply, amount);
+                prop_assert!(fewer.is_some_and(|fewer| fewer < more));
+            }
+        }
+
+        #[test]
+        fn buying_later_never_costs_less(curve in curves(), supply in 0..10_000_000u64, amount in 0..10_000_000u64) {
+            if let Some(later) = cost(&curve, supply + 1, amount) {
+                let earlier = cost(&curve, supply, amount);
+                prop_assert!(earlier.is_some_and(|earlier| earlier <= later));
+            }
+        }
+
+        #[test]
+        fn splitting_a_purchase_never_changes_its_cost(
+            curve in curves(),
+            supply in 0..10_000_000u64,
+            first in 0..10_000_000u64,
+            second in 0..10_000_000u64,
+        ) {
+            let whole = cost(&curve, supply, first + second);
+            let split = cost(&curve, supply, first)
+                .zip(cost(&curve, supply + first, second))
+                .and_then(|(first, second)| first.checked_add(second));
+            prop_assert_eq!(whole, split);
+        }
+
+        #[test]
+        fn cost_is_at_least_every_price_paid(curve in curves(), supply in 0..u64::MAX / 2, amount in 1..u64::MAX / 2) {
+            if let Some(total) = cost(&curve, supply, amount) {
+                prop_assert!(u128::from(total) >= price(&curve, supply + amount - 1));
+            }
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Curve prices must start above zero and rise, and the cap must be positive")]
+    InvalidCurve,
+    #[msg("Purchase would take the supply past the sale's cap")]
+    SupplyCapExceeded,
+    #[msg("Purchase costs more lamports than a u64 can hold")]
+    PriceOverflow,
+    #[msg("Purchase costs more than the buyer's maximum")]
+    SlippageExceeded,
+}
@@ -0,0 +1,47 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct InitializeSale<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + 32 + Curve::SPACE + 8 + 1, // discriminator + mint + curve + supply_cap + bump
+        seeds = [b"sale", mint.key().as_ref()],
+        bump
+    )]
+    pub sale: Account<'info, Sale>,
+
+    #[account(mint::authority = sale)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Both purchases take the same accounts; they differ only in how they price the curve
+#[derive(Accounts)]
+pub struct Buy<'info> {
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [b"sale", sale.mint.as_ref()],
+        bump = sale.bump
+    )]
+    pub sale: Account<'info, Sale>,
+
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut, token::mint = mint, token::authority = buyer)]
+    pub buyer_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
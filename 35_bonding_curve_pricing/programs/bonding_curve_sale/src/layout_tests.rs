@@ -0,0 +1,21 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::curve::Curve;
+use crate::Sale;
+
+#[test]
+fn sale_layout_is_stable() {
+    let sale = Sale {
+        mint: Pubkey::new_from_array([1; 32]),
+        curve: Curve::Exponential {
+            base_price: 0x6162636465666768,
+            doubling_supply: 0x7172737475767778,
+        },
+        supply_cap: 0x2122232425262728,
+        bump: 49,
+    };
+    assert_golden!("Sale", golden::anchor(&sale));
+}
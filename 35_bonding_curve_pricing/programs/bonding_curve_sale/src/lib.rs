@@ -0,0 +1,132 @@
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+
+pub mod curve;
+
+use curve::Curve;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// A token sale priced by a bonding curve.
+///
+/// The sale PDA is the mint authority of a token with no decimals. Each
+/// purchase pays the curve's price for every token it mints, in lamports,
+/// into the sale account, and the mint's supply is the sale's position on
+/// the curve. Sales stop at `supply_cap` tokens.
+#[feature_gated]
+#[program]
+pub mod bonding_curve_sale {
+    use super::*;
+
+    /// Open a sale for a mint whose authority is already the sale PDA
+    pub fn initialize_sale(ctx: Context<InitializeSale>, curve: Curve, supply_cap: u64) -> Result<()> {
+        require!(curve.is_valid() && supply_cap > 0, ErrorCode::InvalidCurve);
+
+        let sale = &mut ctx.accounts.sale;
+        sale.mint = ctx.accounts.mint.key();
+        sale.curve = curve;
+        sale.supply_cap = supply_cap;
+        sale.bump = ctx.bumps.sale;
+
+        msg!("Sale of {} opened, capped at {} tokens", sale.mint, supply_cap);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Buy `amount` tokens at the u64 curve price
+    ///
+    /// Security Issue: `vulnerable_cost` wraps instead of failing, and the
+    /// cap is never checked because the rising price is assumed to make
+    /// supply past it unaffordable. A buyer chooses an amount whose cost
+    /// wraps to almost nothing - 2^62 + 1 tokens for the price of one on
+    /// the linear curve in the tests - and mints it far past the cap.
+    #[vulnerable]
+    pub fn vulnerable_buy(ctx: Context<Buy>, amount: u64, max_cost: u64) -> Result<()> {
+        let sale = &ctx.accounts.sale;
+        let cost = curve::vulnerable_cost(&sale.curve, ctx.accounts.mint.supply, amount);
+        require!(cost <= max_cost, ErrorCode::SlippageExceeded);
+
+        // VULNERABILITY: No supply cap; the wrapped cost is all that stands in the way
+        sell(ctx.accounts, amount, cost)
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that enforces the cap and
+    // prices the curve without overflow.
+
+    /// SECURE: Buy `amount` tokens at the exact curve price, up to the cap
+    ///
+    /// Security Fix: The cap is checked before anything is priced, so the
+    /// curve is only ever integrated over supplies the sale allows. The
+    /// cost is integrated in u128 and a cost that does not fit in a u64 is
+    /// an error, not a small number.
+    #[secure]
+    pub fn secure_buy(ctx: Context<Buy>, amount: u64, max_cost: u64) -> Result<()> {
+        let sale = &ctx.accounts.sale;
+        let supply = ctx.accounts.mint.supply;
+
+        // SECURITY: No purchase may take the supply past the cap
+        let new_supply = supply.checked_add(amount).ok_or(ErrorCode::SupplyCapExceeded)?;
+        require!(new_supply <= sale.supply_cap, ErrorCode::SupplyCapExceeded);
+
+        let cost = curve::cost(&sale.curve, supply, amount).ok_or(ErrorCode::PriceOverflow)?;
+        require!(cost <= max_cost, ErrorCode::SlippageExceeded);
+
+        sell(ctx.accounts, amount, cost)
+    }
+}
+
+/// Take `cost` lamports from the buyer into the sale and mint `amount` tokens to them
+fn sell(accounts: &Buy, amount: u64, cost: u64) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            accounts.system_program.to_account_info(),
+            Transfer {
+                from: accounts.buyer.to_account_info(),
+                to: accounts.sale.to_account_info(),
+            },
+        ),
+        cost,
+    )?;
+
+    let sale = &accounts.sale;
+    let seeds: &[&[u8]] = &[b"sale", sale.mint.as_ref(), &[sale.bump]];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            MintTo {
+                mint: accounts.mint.to_account_info(),
+                to: accounts.buyer_tokens.to_account_info(),
+                authority: sale.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )?;
+
+    msg!("Sold {} tokens for {} lamports", amount, cost);
+    Ok(())
+}
@@ -0,0 +1,15 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Sale {
+    /// The token sold; the sale PDA is its mint authority (32 bytes)
+    pub mint: Pubkey,
+    /// How the price rises with supply (17 bytes)
+    pub curve: Curve,
+    /// Most tokens the sale will ever mint (8 bytes)
+    pub supply_cap: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
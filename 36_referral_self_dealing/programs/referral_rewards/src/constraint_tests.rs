@@ -0,0 +1,232 @@
+//! Every constraint on the campaign contexts, tripped one account at a time,
+//! and deposit cycles that show who earns a bonus and how much.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_seeds_violation, assert_signer_violation, execute,
+    Fixture, TestAccount,
+};
+
+use crate::{
+    instruction, Campaign, ErrorCode as ReferralError, InitializeCampaign, Member, SecureDeposit, SecureRegister,
+    VulnerableDeposit, VulnerableRegister,
+};
+
+const BUDGET: u64 = 1_000;
+const DEPOSIT: u64 = 1_000;
+
+fn pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &crate::ID)
+}
+
+fn campaign_account(authority: Pubkey) -> TestAccount {
+    let (address, bump) = pda(&[b"campaign", authority.as_ref()]);
+    TestAccount::anchor(&Campaign {
+        authority,
+        budget: BUDGET,
+        bump,
+    })
+    .at(address)
+}
+
+fn member(campaign: Pubkey, owner: Pubkey, referrer: Pubkey, bump: u8) -> Member {
+    Member {
+        campaign,
+        owner,
+        referrer,
+        deposited: 0,
+        peak_deposited: 0,
+        rewards: 0,
+        bump,
+    }
+}
+
+/// `owner`'s member PDA in `campaign`
+fn member_pda(campaign: Pubkey, owner: Pubkey, referrer: Pubkey) -> TestAccount {
+    let (address, bump) = pda(&[b"member", campaign.as_ref(), owner.as_ref()]);
+    TestAccount::anchor(&member(campaign, owner, referrer, bump)).at(address)
+}
+
+/// A member created by `vulnerable_register`, at a fresh keypair
+fn keypair_member(campaign: Pubkey, owner: Pubkey, referrer: Pubkey) -> TestAccount {
+    TestAccount::anchor(&member(campaign, owner, referrer, 0))
+}
+
+fn initialize_fixture() -> Fixture {
+    let authority = TestAccount::signer();
+    let (campaign, _) = pda(&[b"campaign", authority.key.as_ref()]);
+    let (root, _) = pda(&[b"member", campaign.as_ref(), authority.key.as_ref()]);
+    Fixture::new()
+        .with("campaign", TestAccount::uninitialized(8 + 32 + 8 + 1).at(campaign))
+        .with("root", TestAccount::uninitialized(Member::SPACE).at(root))
+        .with("authority", authority)
+        .with("system_program", TestAccount::system_program())
+}
+
+fn vulnerable_register_fixture() -> Fixture {
+    Fixture::new()
+        .with("campaign", campaign_account(Pubkey::new_unique()))
+        .with("member", TestAccount::uninitialized(Member::SPACE).signed())
+        .with("owner", TestAccount::signer())
+        .with("system_program", TestAccount::system_program())
+}
+
+/// A wallet joining a campaign through its root member
+fn secure_register_fixture() -> Fixture {
+    let campaign = campaign_account(Pubkey::new_unique());
+    let authority = campaign.state::<Campaign>().authority;
+    let owner = TestAccount::signer();
+    let (address, _) = pda(&[b"member", campaign.key.as_ref(), owner.key.as_ref()]);
+    Fixture::new()
+        .with("campaign", campaign.clone())
+        .with("member", TestAccount::uninitialized(Member::SPACE).at(address))
+        .with(
+            "referrer_member",
+            member_pda(campaign.key, authority, Pubkey::default()),
+        )
+        .with("owner", owner)
+        .with("system_program", TestAccount::system_program())
+}
+
+/// A keypair member whose wallet is its own referrer, with two more of its members as uplines
+fn vulnerable_deposit_fixture() -> Fixture {
+    let (campaign, owner) = (Pubkey::new_unique(), TestAccount::signer());
+    Fixture::new()
+        .with("member", keypair_member(campaign, owner.key, owner.key))
+        .with("owner", owner.clone())
+        .with("upline", keypair_member(campaign, owner.key, owner.key))
+        .with("second_upline", keypair_member(campaign, owner.key, owner.key))
+}
+
+/// A member referred by `referrer`, who was referred by the root, with both as uplines
+fn secure_deposit_fixture() -> Fixture {
+    let campaign = campaign_account(Pubkey::new_unique());
+    let authority = campaign.state::<Campaign>().authority;
+    let (owner, referrer) = (TestAccount::signer(), Pubkey::new_unique());
+    Fixture::new()
+        .with("campaign", campaign.clone())
+        .with("member", member_pda(campaign.key, owner.key, referrer))
+        .with("owner", owner)
+        .with("upline", member_pda(campaign.key, referrer, authority))
+        .with("second_upline", member_pda(campaign.key, authority, Pubkey::default()))
+}
+
+/// Withdraw everything, as `withdraw` would
+fn withdraw_all(fixture: &mut Fixture) {
+    fixture
+        .account_mut("member")
+        .edit(|member: &mut Member| member.deposited = 0);
+}
+
+#[test]
+fn initialize_campaign_roots_the_tree_at_the_authority() {
+    assert_seeds_violation!(InitializeCampaign, initialize_fixture(), "campaign");
+    assert_seeds_violation!(InitializeCampaign, initialize_fixture(), "root");
+    assert_signer_violation!(InitializeCampaign, initialize_fixture(), "authority");
+}
+
+#[test]
+fn vulnerable_register_accepts_the_wallet_as_its_own_referrer() {
+    assert_signer_violation!(VulnerableRegister, vulnerable_register_fixture(), "owner");
+
+    let mut fixture = vulnerable_register_fixture();
+    let owner = fixture.key("owner");
+    execute!(&mut fixture, instruction::VulnerableRegister { referrer: owner }).unwrap();
+    assert_eq!(fixture.state::<Member>("member").referrer, owner);
+}
+
+#[test]
+fn secure_register_requires_an_existing_distinct_member() {
+    assert_seeds_violation!(SecureRegister, secure_register_fixture(), "member");
+    assert_seeds_violation!(SecureRegister, secure_register_fixture(), "referrer_member");
+    assert_constraint_violation!(
+        SecureRegister,
+        secure_register_fixture(),
+        "referrer_member" => |referrer| referrer.edit(|stored: &mut Member| stored.campaign = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    let fixture = secure_register_fixture();
+    let sybil = keypair_member(fixture.key("campaign"), Pubkey::new_unique(), Pubkey::default());
+    assert_constraint_violation!(SecureRegister, fixture, "referrer_member" => sybil, ErrorCode::ConstraintSeeds);
+    let fixture = secure_register_fixture();
+    let own = member_pda(fixture.key("campaign"), fixture.key("owner"), Pubkey::default());
+    assert_constraint_violation!(SecureRegister, fixture, "referrer_member" => own, ReferralError::SelfReferral);
+    assert_signer_violation!(SecureRegister, secure_register_fixture(), "owner");
+}
+
+#[test]
+fn vulnerable_deposit_pays_a_self_referrer_on_every_cycle() {
+    assert_has_one_violation!(VulnerableDeposit, vulnerable_deposit_fixture(), "owner");
+    assert_signer_violation!(VulnerableDeposit, vulnerable_deposit_fixture(), "owner");
+
+    let mut fixture = vulnerable_deposit_fixture();
+    for _ in 0..3 {
+        execute!(&mut fixture, instruction::VulnerableDeposit { amount: DEPOSIT }).unwrap();
+        withdraw_all(&mut fixture);
+    }
+    // 10% and 5% of the same 1,000 deposited, three times over, all to the depositor's own wallet
+    assert_eq!(fixture.state::<Member>("upline").rewards, 300);
+    assert_eq!(fixture.state::<Member>("second_upline").rewards, 150);
+}
+
+#[test]
+fn secure_deposit_checks_the_campaign_and_member() {
+    assert_seeds_violation!(SecureDeposit, secure_deposit_fixture(), "campaign");
+    assert_seeds_violation!(SecureDeposit, secure_deposit_fixture(), "member");
+    assert_constraint_violation!(
+        SecureDeposit,
+        secure_deposit_fixture(),
+        "member" => |member| member.edit(|stored: &mut Member| stored.campaign = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_signer_violation!(SecureDeposit, secure_deposit_fixture(), "owner");
+}
+
+#[test]
+fn secure_deposit_pays_once_per_new_high() {
+    let mut fixture = secure_deposit_fixture();
+    for _ in 0..3 {
+        execute!(&mut fixture, instruction::SecureDeposit { amount: DEPOSIT }).unwrap();
+        withdraw_all(&mut fixture);
+    }
+    assert_eq!(fixture.state::<Member>("upline").rewards, 100);
+    assert_eq!(fixture.state::<Member>("second_upline").rewards, 50);
+    assert_eq!(fixture.state::<Campaign>("campaign").budget, BUDGET - 150);
+}
+
+#[test]
+fn secure_deposit_never_pays_past_the_budget() {
+    let mut fixture = secure_deposit_fixture();
+    execute!(&mut fixture, instruction::SecureDeposit { amount: 100 * DEPOSIT }).unwrap();
+    assert_eq!(fixture.state::<Member>("upline").rewards, BUDGET);
+    assert_eq!(fixture.state::<Member>("second_upline").rewards, 0);
+    assert_eq!(fixture.state::<Campaign>("campaign").budget, 0);
+}
+
+#[test]
+fn secure_deposit_walks_only_the_real_referral_chain() {
+    // A keypair member of the referrer's wallet is not its member PDA
+    let mut fixture = secure_deposit_fixture();
+    let upline = fixture.state::<Member>("upline");
+    *fixture.account_mut("upline") = keypair_member(upline.campaign, upline.owner, upline.referrer);
+    let result = execute!(&mut fixture, instruction::SecureDeposit { amount: DEPOSIT });
+    assert_eq!(result, Err(Error::from(ReferralError::BrokenReferralChain).into()));
+
+    // Nor can the first level collect the second level's bonus too
+    let mut fixture = secure_deposit_fixture();
+    *fixture.account_mut("second_upline") = fixture.account("upline").clone();
+    let result = execute!(&mut fixture, instruction::SecureDeposit { amount: DEPOSIT });
+    assert_eq!(result, Err(Error::from(ReferralError::BrokenReferralChain).into()));
+}
+
+#[test]
+fn secure_deposit_requires_every_upline() {
+    let campaign = campaign_account(Pubkey::new_unique());
+    let owner = TestAccount::signer();
+    let mut fixture = Fixture::new()
+        .with("campaign", campaign.clone())
+        .with("member", member_pda(campaign.key, owner.key, Pubkey::new_unique()))
+        .with("owner", owner);
+    let result = execute!(&mut fixture, instruction::SecureDeposit { amount: DEPOSIT });
+    assert_eq!(result, Err(Error::from(ReferralError::MissingUpline).into()));
+}
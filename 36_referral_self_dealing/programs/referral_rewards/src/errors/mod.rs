@@ -0,0 +1,17 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Cannot withdraw more than is deposited")]
+    InsufficientDeposit,
+    #[msg("A member cannot be their own referrer or upline")]
+    SelfReferral,
+    #[msg("Upline account is not the previous member's referrer")]
+    BrokenReferralChain,
+    #[msg("Every upline up to the root or the last bonus level must be passed")]
+    MissingUpline,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
@@ -0,0 +1,49 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializeCampaign<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 8 + 1, // discriminator + authority + budget + bump
+        seeds = [b"campaign", authority.key().as_ref()],
+        bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Member::SPACE,
+        seeds = [b"member", campaign.key().as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    pub root: Account<'info, Member>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut, has_one = owner)]
+    pub member: Account<'info, Member>,
+
+    pub owner: Signer<'info>,
+}
@@ -0,0 +1,54 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureRegister<'info> {
+    pub campaign: Account<'info, Campaign>,
+
+    // SECURITY: One member per wallet per campaign
+    #[account(
+        init,
+        payer = owner,
+        space = Member::SPACE,
+        seeds = [b"member", campaign.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub member: Account<'info, Member>,
+
+    // SECURITY: An existing member PDA of this campaign, owned by someone else
+    #[account(
+        has_one = campaign,
+        seeds = [b"member", campaign.key().as_ref(), referrer_member.owner.as_ref()],
+        bump = referrer_member.bump,
+        constraint = referrer_member.owner != owner.key() @ ErrorCode::SelfReferral
+    )]
+    pub referrer_member: Account<'info, Member>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upline member PDAs follow as writable `remaining_accounts`, nearest first
+#[derive(Accounts)]
+pub struct SecureDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"campaign", campaign.authority.as_ref()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    #[account(
+        mut,
+        has_one = campaign,
+        has_one = owner,
+        seeds = [b"member", campaign.key().as_ref(), owner.key().as_ref()],
+        bump = member.bump
+    )]
+    pub member: Account<'info, Member>,
+
+    pub owner: Signer<'info>,
+}
@@ -0,0 +1,26 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableRegister<'info> {
+    pub campaign: Account<'info, Campaign>,
+
+    // VULNERABILITY: Any new keypair; nothing limits a wallet to one member
+    #[account(init, payer = owner, space = Member::SPACE)]
+    pub member: Account<'info, Member>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upline members follow as writable `remaining_accounts`, nearest first
+#[derive(Accounts)]
+pub struct VulnerableDeposit<'info> {
+    #[account(mut, has_one = owner)]
+    pub member: Account<'info, Member>,
+
+    pub owner: Signer<'info>,
+}
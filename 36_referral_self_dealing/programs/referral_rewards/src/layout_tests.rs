@@ -0,0 +1,30 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Campaign, Member};
+
+#[test]
+fn campaign_layout_is_stable() {
+    let campaign = Campaign {
+        authority: Pubkey::new_from_array([1; 32]),
+        budget: 0x2122232425262728,
+        bump: 49,
+    };
+    assert_golden!("Campaign", golden::anchor(&campaign));
+}
+
+#[test]
+fn member_layout_is_stable() {
+    let member = Member {
+        campaign: Pubkey::new_from_array([4; 32]),
+        owner: Pubkey::new_from_array([5; 32]),
+        referrer: Pubkey::new_from_array([6; 32]),
+        deposited: 0x7172737475767778,
+        peak_deposited: 0x8182838485868788,
+        rewards: 0x9192939495969798,
+        bump: 161,
+    };
+    assert_golden!("Member", golden::anchor(&member));
+}
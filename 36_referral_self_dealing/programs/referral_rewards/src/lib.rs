@@ -0,0 +1,207 @@
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Basis points in 100%
+pub const BPS: u64 = 10_000;
+
+/// Bonus paid on each deposit to the depositor's referrer, then to that referrer's referrer
+pub const LEVEL_BONUS_BPS: [u64; 2] = [1_000, 500];
+
+/// A deposit campaign that pays referral bonuses.
+///
+/// Every member joins through a referral, and the campaign authority's
+/// member is the root of the referral tree. When a member deposits, their
+/// referrer earns `LEVEL_BONUS_BPS[0]` of the deposit and the referrer's
+/// own referrer earns `LEVEL_BONUS_BPS[1]`. Like the lending toy, the
+/// campaign tracks deposits and rewards as numbers instead of moving
+/// tokens; rewards are what the campaign owes, paid from its `budget`.
+/// The upline members to credit follow as writable `remaining_accounts`,
+/// nearest first.
+#[feature_gated]
+#[program]
+pub mod referral_rewards {
+    use super::*;
+
+    /// Open a campaign with `budget` to pay bonuses from; the authority becomes the root member
+    pub fn initialize_campaign(ctx: Context<InitializeCampaign>, budget: u64) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.authority = ctx.accounts.authority.key();
+        campaign.budget = budget;
+        campaign.bump = ctx.bumps.campaign;
+
+        let root = &mut ctx.accounts.root;
+        root.campaign = campaign.key();
+        root.owner = campaign.authority;
+        root.referrer = Pubkey::default();
+        root.bump = ctx.bumps.root;
+
+        msg!("Campaign opened with a budget of {}", budget);
+        Ok(())
+    }
+
+    /// Withdraw deposited funds; earned rewards stay earned
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let member = &mut ctx.accounts.member;
+        require!(amount <= member.deposited, ErrorCode::InsufficientDeposit);
+        member.deposited -= amount;
+
+        msg!("Withdrew {}", amount);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Join the campaign at a fresh keypair, referred by any key
+    ///
+    /// Security Issue: `referrer` is not checked against anything. A wallet
+    /// names itself, or a wallet that will only register later, so two
+    /// members can refer each other. The member is a keypair account, so
+    /// the same wallet can join as many times as it likes and act as its
+    /// own upline at every level.
+    #[vulnerable]
+    pub fn vulnerable_register(ctx: Context<VulnerableRegister>, referrer: Pubkey) -> Result<()> {
+        let member = &mut ctx.accounts.member;
+        member.campaign = ctx.accounts.campaign.key();
+        member.owner = ctx.accounts.owner.key();
+        // VULNERABILITY: Self-referral and referrers that are not members are both accepted
+        member.referrer = referrer;
+
+        msg!("{} joined, referred by {}", member.owner, referrer);
+        Ok(())
+    }
+
+    /// VULNERABLE: Deposit and pay a bonus on the whole amount to each upline
+    ///
+    /// Security Issue: Bonuses are credited on every deposit, not on new
+    /// money, and nothing takes them out of the budget. A wallet that is
+    /// its own referrer - or sits in a loop with an accomplice - deposits,
+    /// withdraws and deposits the same funds again, collecting 15% each
+    /// time.
+    #[vulnerable]
+    pub fn vulnerable_deposit<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VulnerableDeposit<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let member = &mut ctx.accounts.member;
+        member.deposited = member
+            .deposited
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let mut referrer = member.referrer;
+        for (info, level_bps) in ctx.remaining_accounts.iter().zip(LEVEL_BONUS_BPS) {
+            let mut upline = Account::<Member>::try_from(info)?;
+            require_keys_eq!(upline.owner, referrer, ErrorCode::BrokenReferralChain);
+
+            // VULNERABILITY: Paid on gross deposits, from no budget, to whoever the chain names
+            let bonus = (u128::from(amount) * u128::from(level_bps) / u128::from(BPS)) as u64;
+            upline.rewards = upline.rewards.checked_add(bonus).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+            referrer = upline.referrer;
+            upline.exit(&crate::ID)?;
+        }
+
+        msg!("Deposited {}", amount);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version with one member per wallet,
+    // an acyclic referral tree, and bonuses bounded by new deposits and the
+    // campaign's budget.
+
+    /// SECURE: Join the campaign once, referred by an existing member
+    ///
+    /// Security Fix: The member is a PDA of the campaign and the wallet, so
+    /// each wallet joins once. The referrer must already be a member PDA of
+    /// the same campaign with a different owner. Since a referrer always
+    /// joined first and referrers never change, the referral graph is a
+    /// tree rooted at the campaign authority and cannot contain a loop.
+    #[secure]
+    pub fn secure_register(ctx: Context<SecureRegister>) -> Result<()> {
+        let member = &mut ctx.accounts.member;
+        member.campaign = ctx.accounts.campaign.key();
+        member.owner = ctx.accounts.owner.key();
+        member.referrer = ctx.accounts.referrer_member.owner;
+        member.bump = ctx.bumps.member;
+
+        msg!("{} joined, referred by {}", member.owner, member.referrer);
+        Ok(())
+    }
+
+    /// SECURE: Deposit and pay bonuses on new money only, out of the budget
+    ///
+    /// Security Fix: Only the part of a deposit that takes the member above
+    /// their previous high earns a bonus, so redepositing withdrawn funds
+    /// earns nothing. Each upline must be the member PDA of the previous
+    /// member's referrer, every upline up to the root or the last level must
+    /// be passed, no upline may be the depositor, and every bonus is taken
+    /// out of the budget, capped at what remains.
+    #[secure]
+    pub fn secure_deposit<'info>(ctx: Context<'_, '_, 'info, 'info, SecureDeposit<'info>>, amount: u64) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let member = &mut ctx.accounts.member;
+        member.deposited = member
+            .deposited
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // SECURITY: Bonuses accrue on new highs, not on funds cycled in and out
+        let eligible = member.deposited.saturating_sub(member.peak_deposited);
+        member.peak_deposited = member.peak_deposited.max(member.deposited);
+
+        let mut referrer = member.referrer;
+        let mut uplines = ctx.remaining_accounts.iter();
+        for level_bps in LEVEL_BONUS_BPS {
+            if referrer == Pubkey::default() {
+                break;
+            }
+            let info = uplines.next().ok_or(ErrorCode::MissingUpline)?;
+            let mut upline = Account::<Member>::try_from(info)?;
+
+            // SECURITY: The upline is the referrer's own member PDA, and never the depositor
+            let expected = Pubkey::create_program_address(
+                &[b"member", campaign.key().as_ref(), referrer.as_ref(), &[upline.bump]],
+                &crate::ID,
+            )
+            .map_err(|_| ErrorCode::BrokenReferralChain)?;
+            require_keys_eq!(info.key(), expected, ErrorCode::BrokenReferralChain);
+            require_keys_eq!(upline.owner, referrer, ErrorCode::BrokenReferralChain);
+            require_keys_neq!(upline.owner, member.owner, ErrorCode::SelfReferral);
+
+            // SECURITY: Bounded by the deposit's new money and by the remaining budget
+            let bonus = (u128::from(eligible) * u128::from(level_bps) / u128::from(BPS)) as u64;
+            let bonus = bonus.min(campaign.budget);
+            campaign.budget -= bonus;
+            upline.rewards = upline.rewards.checked_add(bonus).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+            referrer = upline.referrer;
+            upline.exit(&crate::ID)?;
+        }
+
+        msg!("Deposited {}, {} eligible for referral bonuses", amount, eligible);
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Campaign {
+    /// Root of the referral tree (32 bytes)
+    pub authority: Pubkey,
+    /// Bonuses the campaign can still pay (8 bytes)
+    pub budget: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Member {
+    /// Campaign joined (32 bytes)
+    pub campaign: Pubkey,
+    /// Wallet that deposits and earns (32 bytes)
+    pub owner: Pubkey,
+    /// Wallet that referred this member; the default key for the root (32 bytes)
+    pub referrer: Pubkey,
+    /// Funds currently deposited (8 bytes)
+    pub deposited: u64,
+    /// Highest `deposited` has been; bonuses accrue above it (8 bytes)
+    pub peak_deposited: u64,
+    /// Referral bonuses earned (8 bytes)
+    pub rewards: u64,
+    /// PDA bump seed; 0 for keypair members (1 byte)
+    pub bump: u8,
+}
+
+impl Member {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 1;
+}
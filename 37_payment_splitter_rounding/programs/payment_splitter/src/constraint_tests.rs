@@ -0,0 +1,218 @@
+//! The splitter contexts' constraints, and distributions that show who is
+//! paid how much depending on where they sit in the list.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_has_one_violation, assert_seeds_violation, assert_signer_violation, execute, Fixture, TestAccount,
+};
+
+use crate::{
+    instruction, AddRecipient, CreateSplitter, Distribute, ErrorCode as SplitterError, Recipient, Splitter,
+    MAX_RECIPIENTS,
+};
+
+fn recipients(shares: &[u16]) -> Vec<Recipient> {
+    shares
+        .iter()
+        .map(|&shares| Recipient {
+            address: Pubkey::new_unique(),
+            shares,
+        })
+        .collect()
+}
+
+fn create_fixture(capacity: u16) -> Fixture {
+    let authority = TestAccount::signer();
+    let (splitter, _) = Pubkey::find_program_address(&[b"splitter", authority.key.as_ref()], &crate::ID);
+    Fixture::new()
+        .with(
+            "splitter",
+            TestAccount::uninitialized(Splitter::space(usize::from(capacity))).at(splitter),
+        )
+        .with("authority", authority)
+        .with("system_program", TestAccount::system_program())
+        .args(instruction::SecureCreateSplitter { capacity })
+}
+
+/// An empty splitter with room for `capacity` recipients
+fn add_recipient_fixture(capacity: u16) -> Fixture {
+    let authority = TestAccount::signer();
+    let (address, bump) = Pubkey::find_program_address(&[b"splitter", authority.key.as_ref()], &crate::ID);
+    let mut splitter = TestAccount::anchor(&Splitter {
+        authority: authority.key,
+        recipients: Vec::new(),
+        total_shares: 0,
+        bump,
+    })
+    .at(address);
+    splitter.data.resize(Splitter::space(usize::from(capacity)), 0);
+    Fixture::new().with("splitter", splitter).with("authority", authority)
+}
+
+/// A splitter holding `funds` lamports above its reserve, with its recipients after it in list order
+fn distribute_fixture(shares: &[u16], funds: u64) -> Fixture {
+    let authority = Pubkey::new_unique();
+    let (address, bump) = Pubkey::find_program_address(&[b"splitter", authority.as_ref()], &crate::ID);
+    let recipients = recipients(shares);
+    let reserve = Rent::default().minimum_balance(Splitter::space(recipients.len()));
+    let splitter = TestAccount::anchor(&Splitter {
+        authority,
+        recipients: recipients.clone(),
+        total_shares: shares.iter().map(|&s| u32::from(s)).sum(),
+        bump,
+    })
+    .at(address)
+    .with_lamports(reserve + funds);
+
+    let names = ["first", "second", "third"];
+    recipients.iter().zip(names).fold(
+        Fixture::new().with("splitter", splitter),
+        |fixture, (recipient, name)| fixture.with(name, TestAccount::system(0).at(recipient.address)),
+    )
+}
+
+/// Lamports each recipient holds, in list order
+fn paid(fixture: &Fixture, count: usize) -> Vec<u64> {
+    ["first", "second", "third"][..count]
+        .iter()
+        .map(|name| fixture.account(name).lamports)
+        .collect()
+}
+
+/// Lamports the splitter holds above its reserve
+fn left(fixture: &Fixture) -> u64 {
+    let splitter = fixture.account("splitter");
+    splitter.lamports - Rent::default().minimum_balance(splitter.data.len())
+}
+
+#[test]
+fn create_splitter_is_a_pda_of_the_authority() {
+    assert_seeds_violation!(CreateSplitter, create_fixture(2), "splitter");
+    assert_signer_violation!(CreateSplitter, create_fixture(2), "authority");
+}
+
+#[test]
+fn vulnerable_create_reserves_room_for_any_number_of_recipients() {
+    let mut fixture = create_fixture(200);
+    execute!(&mut fixture, instruction::VulnerableCreateSplitter { capacity: 200 }).unwrap();
+    assert_eq!(fixture.account("splitter").data.len(), Splitter::space(200));
+}
+
+#[test]
+fn secure_create_bounds_the_recipients() {
+    let capacity = MAX_RECIPIENTS as u16 + 1;
+    let mut fixture = create_fixture(capacity);
+    let result = execute!(&mut fixture, instruction::SecureCreateSplitter { capacity });
+    assert_eq!(result, Err(Error::from(SplitterError::TooManyRecipients).into()));
+
+    let capacity = MAX_RECIPIENTS as u16;
+    let mut fixture = create_fixture(capacity);
+    execute!(&mut fixture, instruction::SecureCreateSplitter { capacity }).unwrap();
+    assert_eq!(
+        fixture.state::<Splitter>("splitter").authority,
+        fixture.key("authority")
+    );
+}
+
+#[test]
+fn add_recipient_is_the_authoritys() {
+    assert_seeds_violation!(AddRecipient, add_recipient_fixture(2), "splitter");
+    assert_has_one_violation!(AddRecipient, add_recipient_fixture(2), "authority");
+    assert_signer_violation!(AddRecipient, add_recipient_fixture(2), "authority");
+}
+
+#[test]
+fn add_recipient_fills_the_reserved_room() {
+    let mut fixture = add_recipient_fixture(2);
+    for recipient in recipients(&[1, 3]) {
+        execute!(&mut fixture, instruction::AddRecipient { recipient }).unwrap();
+    }
+    assert_eq!(fixture.state::<Splitter>("splitter").total_shares, 4);
+
+    let recipient = recipients(&[1])[0];
+    let result = execute!(&mut fixture, instruction::AddRecipient { recipient });
+    assert_eq!(result, Err(Error::from(SplitterError::SplitterFull).into()));
+}
+
+#[test]
+fn distribute_requires_a_share() {
+    let mut fixture = distribute_fixture(&[0, 0], 1_000);
+    let result = execute!(&mut fixture, instruction::SecureDistribute {});
+    assert_eq!(result, Err(Error::from(SplitterError::NoShares).into()));
+    let result = execute!(&mut fixture, instruction::VulnerableDistribute {});
+    assert_eq!(result, Err(Error::from(SplitterError::NoShares).into()));
+}
+
+#[test]
+fn distribute_checks_the_splitter() {
+    assert_seeds_violation!(Distribute, distribute_fixture(&[1, 1], 1_000), "splitter");
+}
+
+#[test]
+fn vulnerable_distribute_pays_by_position() {
+    let mut fixture = distribute_fixture(&[1, 1], 1_000);
+    execute!(&mut fixture, instruction::VulnerableDistribute {}).unwrap();
+    // Half of 1,000, then half of the 500 left
+    assert_eq!(paid(&fixture, 2), vec![500, 250]);
+    assert_eq!(left(&fixture), 250);
+
+    // The leftover is split the same way, so equal shares converge on two to one
+    execute!(&mut fixture, instruction::VulnerableDistribute {}).unwrap();
+    assert_eq!(paid(&fixture, 2), vec![625, 312]);
+    assert_eq!(left(&fixture), 63);
+}
+
+#[test]
+fn vulnerable_distribute_strands_dust() {
+    let mut fixture = distribute_fixture(&[1, 1, 1], 2);
+    for _ in 0..3 {
+        execute!(&mut fixture, instruction::VulnerableDistribute {}).unwrap();
+    }
+    assert_eq!(paid(&fixture, 3), vec![0, 0, 0]);
+    assert_eq!(left(&fixture), 2);
+}
+
+#[test]
+fn secure_distribute_pays_by_shares() {
+    let mut fixture = distribute_fixture(&[1, 1], 1_000);
+    execute!(&mut fixture, instruction::SecureDistribute {}).unwrap();
+    assert_eq!(paid(&fixture, 2), vec![500, 500]);
+    assert_eq!(left(&fixture), 0);
+
+    let mut fixture = distribute_fixture(&[1, 2, 1], 1_001);
+    execute!(&mut fixture, instruction::SecureDistribute {}).unwrap();
+    assert_eq!(paid(&fixture, 3), vec![251, 500, 250]);
+    assert_eq!(left(&fixture), 0);
+}
+
+#[test]
+fn secure_distribute_assigns_dust_to_the_first_recipient() {
+    let mut fixture = distribute_fixture(&[1, 1, 1], 2);
+    execute!(&mut fixture, instruction::SecureDistribute {}).unwrap();
+    assert_eq!(paid(&fixture, 3), vec![2, 0, 0]);
+    assert_eq!(left(&fixture), 0);
+}
+
+#[test]
+fn distribute_requires_every_recipient_in_order() {
+    let mut fixture = distribute_fixture(&[1, 1], 1_000);
+    let (first, second) = (fixture.key("first"), fixture.key("second"));
+    fixture.account_mut("first").key = second;
+    fixture.account_mut("second").key = first;
+    let result = execute!(&mut fixture, instruction::SecureDistribute {});
+    assert_eq!(result, Err(Error::from(SplitterError::RecipientMismatch).into()));
+    let result = execute!(&mut fixture, instruction::VulnerableDistribute {});
+    assert_eq!(result, Err(Error::from(SplitterError::RecipientMismatch).into()));
+
+    let shares = [1, 1, 1];
+    let mut fixture = distribute_fixture(&shares, 1_000);
+    let splitter = fixture.account("splitter").clone();
+    let mut missing = Fixture::new()
+        .with("splitter", splitter)
+        .with("first", fixture.account("first").clone())
+        .with("second", fixture.account("second").clone());
+    let result = execute!(&mut missing, instruction::SecureDistribute {});
+    assert_eq!(result, Err(Error::from(SplitterError::RecipientMismatch).into()));
+    execute!(&mut fixture, instruction::SecureDistribute {}).unwrap();
+    assert_eq!(paid(&fixture, shares.len()), vec![334, 333, 333]);
+}
@@ -0,0 +1,17 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("A splitter needs at least one share to distribute")]
+    NoShares,
+    #[msg("Too many recipients to distribute in one transaction")]
+    TooManyRecipients,
+    #[msg("The splitter has no room for another recipient")]
+    SplitterFull,
+    #[msg("Recipients must be passed in the splitter's order")]
+    RecipientMismatch,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
@@ -0,0 +1,45 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+#[instruction(capacity: u16)]
+pub struct CreateSplitter<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Splitter::space(usize::from(capacity)),
+        seeds = [b"splitter", authority.key().as_ref()],
+        bump
+    )]
+    pub splitter: Account<'info, Splitter>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddRecipient<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [b"splitter", splitter.authority.as_ref()],
+        bump = splitter.bump
+    )]
+    pub splitter: Account<'info, Splitter>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Recipients follow as writable `remaining_accounts`, in list order
+#[derive(Accounts)]
+pub struct Distribute<'info> {
+    #[account(
+        mut,
+        seeds = [b"splitter", splitter.authority.as_ref()],
+        bump = splitter.bump
+    )]
+    pub splitter: Account<'info, Splitter>,
+}
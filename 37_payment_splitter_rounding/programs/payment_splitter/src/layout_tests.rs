@@ -0,0 +1,23 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Recipient, Splitter};
+
+#[test]
+fn splitter_layout_is_stable() {
+    let splitter = Splitter {
+        authority: Pubkey::new_from_array([1; 32]),
+        recipients: vec![Recipient {
+            address: Pubkey::new_from_array([2; 32]),
+            shares: 0x3132,
+        }, Recipient {
+            address: Pubkey::new_from_array([4; 32]),
+            shares: 0x5152,
+        }],
+        total_shares: 0x61626364,
+        bump: 113,
+    };
+    assert_golden!("Splitter", golden::anchor(&splitter));
+}
@@ -0,0 +1,189 @@
+#[cfg(feature = "secure")]
+use secref_guards::rent::minimum_balance;
+use secref_prelude::*;
+
+pub mod split;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Most recipients a secure splitter accepts; every distribution touches each of them
+pub const MAX_RECIPIENTS: usize = 10;
+
+/// A lamport splitter that pays its balance out to a list of recipients.
+///
+/// The authority creates the splitter with room for `capacity` recipients
+/// and adds them one instruction at a time, each with a number of shares.
+/// Anyone can send lamports to the splitter PDA. Distributing is
+/// permissionless: it pays everything above the splitter's rent-exempt
+/// reserve to the recipients in proportion to their shares. Every
+/// recipient follows as a writable `remaining_account`, in the order they
+/// were added.
+#[feature_gated]
+#[program]
+pub mod payment_splitter {
+    use super::*;
+
+    /// Add a recipient with `shares` of every future distribution
+    pub fn add_recipient(ctx: Context<AddRecipient>, recipient: Recipient) -> Result<()> {
+        let splitter = &mut ctx.accounts.splitter;
+        let data_len = splitter.to_account_info().data_len();
+        require!(
+            Splitter::space(splitter.recipients.len() + 1) <= data_len,
+            ErrorCode::SplitterFull
+        );
+
+        splitter.total_shares = splitter
+            .total_shares
+            .checked_add(u32::from(recipient.shares))
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        splitter.recipients.push(recipient);
+
+        msg!("Added {} with {} shares", recipient.address, recipient.shares);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Create a splitter with room for any number of recipients
+    ///
+    /// Security Issue: Recipients are added one instruction at a time, but
+    /// every distribution must pass and pay all of them at once. A splitter
+    /// created with room for a few hundred recipients fills up in as many
+    /// small transactions, and then no single transaction can carry every
+    /// recipient account - or pay them all within its compute budget - so
+    /// everything sent to the splitter is locked in it.
+    #[vulnerable]
+    pub fn vulnerable_create_splitter(ctx: Context<CreateSplitter>, capacity: u16) -> Result<()> {
+        // VULNERABILITY: No limit on how many recipients each distribution has to pay
+        create(ctx, capacity)
+    }
+
+    /// VULNERABLE: Pay each recipient its share of whatever is left when its turn comes
+    ///
+    /// Security Issue: The distributable balance is read again after every
+    /// payment, so each share is taken from what the recipients before it
+    /// left behind. With two equal shares the first recipient is paid half
+    /// the balance and the second a quarter; the rest waits for the next
+    /// distribution, which splits it the same way, so over time the first
+    /// recipient receives twice as much. Each share is also floored and the
+    /// remainder is never assigned: once the balance is smaller than the
+    /// total shares, every share rounds to zero and those lamports can
+    /// never be paid out.
+    #[vulnerable]
+    pub fn vulnerable_distribute<'info>(ctx: Context<'_, '_, 'info, 'info, Distribute<'info>>) -> Result<()> {
+        let splitter = &ctx.accounts.splitter;
+        let splitter_info = splitter.to_account_info();
+        let reserve = Rent::get()?.minimum_balance(splitter_info.data_len());
+        require!(splitter.total_shares > 0, ErrorCode::NoShares);
+        require!(
+            ctx.remaining_accounts.len() == splitter.recipients.len(),
+            ErrorCode::RecipientMismatch
+        );
+
+        for (info, recipient) in ctx.remaining_accounts.iter().zip(&splitter.recipients) {
+            require_keys_eq!(info.key(), recipient.address, ErrorCode::RecipientMismatch);
+
+            // VULNERABILITY: Re-read after every payment, so earlier recipients shrink later shares
+            let available = splitter_info.lamports().saturating_sub(reserve);
+            // VULNERABILITY: Floored, and the remainder is left for no one
+            let amount =
+                (u128::from(available) * u128::from(recipient.shares) / u128::from(splitter.total_shares)) as u64;
+            pay(&splitter_info, info, amount)?;
+        }
+
+        msg!("Distributed to {} recipients", splitter.recipients.len());
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version with a bounded recipient
+    // list and every share computed up front from one balance snapshot,
+    // with the rounding remainder assigned.
+
+    /// SECURE: Create a splitter with room for at most `MAX_RECIPIENTS` recipients
+    ///
+    /// Security Fix: Bounding the list bounds the accounts and compute
+    /// every distribution needs, so a splitter can always be paid out in a
+    /// single transaction.
+    #[secure]
+    pub fn secure_create_splitter(ctx: Context<CreateSplitter>, capacity: u16) -> Result<()> {
+        // SECURITY: Every distribution fits in one transaction
+        require!(usize::from(capacity) <= MAX_RECIPIENTS, ErrorCode::TooManyRecipients);
+        create(ctx, capacity)
+    }
+
+    /// SECURE: Pay each recipient its share of one snapshot of the balance
+    ///
+    /// Security Fix: The distributable balance is read once and every
+    /// share is computed from it before any lamports move, so a
+    /// recipient's payout depends on its shares and not on its place in
+    /// the list. The rounding remainder, less than one lamport per
+    /// recipient, goes to the first recipient, so every distribution pays
+    /// out the whole balance.
+    #[secure]
+    pub fn secure_distribute<'info>(ctx: Context<'_, '_, 'info, 'info, Distribute<'info>>) -> Result<()> {
+        let splitter = &ctx.accounts.splitter;
+        let splitter_info = splitter.to_account_info();
+        let reserve = minimum_balance(splitter_info.data_len())?;
+        require!(splitter.total_shares > 0, ErrorCode::NoShares);
+        require!(
+            ctx.remaining_accounts.len() == splitter.recipients.len(),
+            ErrorCode::RecipientMismatch
+        );
+
+        // SECURITY: One snapshot, and every share computed from it before anything is paid
+        let available = splitter_info.lamports().saturating_sub(reserve);
+        let shares: Vec<u16> = splitter.recipients.iter().map(|recipient| recipient.shares).collect();
+        let amounts = split::split(available, &shares);
+
+        for ((info, recipient), amount) in ctx.remaining_accounts.iter().zip(&splitter.recipients).zip(amounts) {
+            require_keys_eq!(info.key(), recipient.address, ErrorCode::RecipientMismatch);
+            pay(&splitter_info, info, amount)?;
+        }
+
+        msg!("Distributed {} to {} recipients", available, splitter.recipients.len());
+        Ok(())
+    }
+}
+
+/// Start an empty splitter with room for `capacity` recipients
+fn create(ctx: Context<CreateSplitter>, capacity: u16) -> Result<()> {
+    let splitter = &mut ctx.accounts.splitter;
+    splitter.authority = ctx.accounts.authority.key();
+    splitter.bump = ctx.bumps.splitter;
+
+    msg!("Splitter created with room for {} recipients", capacity);
+    Ok(())
+}
+
+/// Move `amount` lamports out of the program-owned splitter
+fn pay(splitter: &AccountInfo, recipient: &AccountInfo, amount: u64) -> Result<()> {
+    **splitter.try_borrow_mut_lamports()? -= amount;
+    let balance = recipient
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    **recipient.try_borrow_mut_lamports()? = balance;
+    Ok(())
+}
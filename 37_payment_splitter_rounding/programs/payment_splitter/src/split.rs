@@ -0,0 +1,90 @@
+//! Share arithmetic for the secure splitter.
+//!
+//! Every share is computed from one snapshot of the distributable balance
+//! before anything is paid. Flooring each share leaves a remainder smaller
+//! than the number of recipients; it goes to the first recipient, so the
+//! payouts always add up to exactly the balance.
+
+/// Split `available` lamports in proportion to `shares`, with the rounding remainder to the first recipient
+///
+/// `shares` must not be empty or all zero; distributions check the
+/// splitter's total before splitting.
+pub fn split(available: u64, shares: &[u16]) -> Vec<u64> {
+    let total: u128 = shares.iter().map(|&share| u128::from(share)).sum();
+    let mut amounts: Vec<u64> = shares
+        .iter()
+        .map(|&share| (u128::from(available) * u128::from(share) / total) as u64)
+        .collect();
+
+    // Each floored share is at most `available`'s proportion, so the sum cannot exceed it
+    let dust = available - amounts.iter().sum::<u64>();
+    amounts[0] += dust;
+    amounts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn remainder_goes_to_the_first_recipient() {
+        assert_eq!(split(1_000, &[1, 1, 1]), vec![334, 333, 333]);
+        assert_eq!(split(1_000, &[1, 2, 1]), vec![250, 500, 250]);
+        assert_eq!(split(2, &[1, 1, 1]), vec![2, 0, 0]);
+    }
+
+    #[test]
+    fn zero_shares_are_paid_nothing() {
+        assert_eq!(split(999, &[0, 3]), vec![0, 999]);
+        assert_eq!(split(1_000, &[3, 0, 1]), vec![750, 0, 250]);
+    }
+
+    #[test]
+    fn whole_balance_is_split_without_overflow() {
+        assert_eq!(
+            split(u64::MAX, &[u16::MAX, u16::MAX]),
+            vec![u64::MAX / 2 + 1, u64::MAX / 2]
+        );
+    }
+
+    fn shares() -> impl Strategy<Value = Vec<u16>> {
+        prop::collection::vec(any::<u16>(), 1..=10).prop_filter("some share", |shares| shares.iter().any(|&s| s > 0))
+    }
+
+    proptest! {
+        #[test]
+        fn payouts_add_up_to_the_balance(available in any::<u64>(), shares in shares()) {
+            let amounts = split(available, &shares);
+            prop_assert_eq!(amounts.len(), shares.len());
+            prop_assert_eq!(amounts.iter().map(|&a| u128::from(a)).sum::<u128>(), u128::from(available));
+        }
+
+        #[test]
+        fn every_payout_is_within_the_remainder_of_its_exact_share(available in any::<u64>(), shares in shares()) {
+            let total: u128 = shares.iter().map(|&s| u128::from(s)).sum();
+            let amounts = split(available, &shares);
+            for (i, (&amount, &share)) in amounts.iter().zip(&shares).enumerate() {
+                let exact = u128::from(available) * u128::from(share);
+                let floor = exact / total;
+                let slack = if i == 0 { shares.len() as u128 } else { 1 };
+                prop_assert!(floor <= u128::from(amount) && u128::from(amount) < floor + slack);
+            }
+        }
+
+        #[test]
+        fn payout_does_not_depend_on_position(available in any::<u64>(), shares in shares(), first in any::<u16>()) {
+            // Only the first recipient's payout changes with the rest of the list; everyone
+            // after it is paid the same whichever order they are listed in
+            let mut forward = vec![first.max(1)];
+            forward.extend(&shares);
+            let mut backward = vec![first.max(1)];
+            backward.extend(shares.iter().rev());
+
+            let paid = split(available, &forward)[1..].to_vec();
+            let mut reversed = split(available, &backward)[1..].to_vec();
+            reversed.reverse();
+            prop_assert_eq!(paid, reversed);
+        }
+    }
+}
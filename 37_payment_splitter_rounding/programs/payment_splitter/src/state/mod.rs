@@ -0,0 +1,34 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Recipient {
+    /// Account paid (32 bytes)
+    pub address: Pubkey,
+    /// Share of each distribution, out of the splitter's total (2 bytes)
+    pub shares: u16,
+}
+
+impl Recipient {
+    pub const SPACE: usize = 32 + 2;
+}
+
+#[account]
+pub struct Splitter {
+    /// Wallet that created the splitter and adds its recipients (32 bytes)
+    pub authority: Pubkey,
+    /// Recipients in the order they were added and are paid (4 + 34 bytes each)
+    pub recipients: Vec<Recipient>,
+    /// Sum of every recipient's shares (4 bytes)
+    pub total_shares: u32,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Splitter {
+    /// Account size with room for `recipients`
+    pub const fn space(recipients: usize) -> usize {
+        8 + 32 + 4 + recipients * Recipient::SPACE + 4 + 1
+    }
+}
@@ -0,0 +1,325 @@
+//! Every constraint on the subscription contexts, tripped one account at a
+//! time, and pulls over time that show what each subscription lets a
+//! service take.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::TokenAccount;
+use secref_testkit::runtime::warp_to;
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_seeds_violation, assert_signer_violation, execute,
+    Fixture, TestAccount,
+};
+
+use crate::{instruction, Cancel, ErrorCode as SubscriptionError, Pull, Subscribe, Subscription};
+
+const BALANCE: u64 = 1_000;
+const AMOUNT: u64 = 10;
+const DAY: i64 = 86_400;
+const START: i64 = 1_700_000_000;
+/// Thirty days of daily pulls
+const EXPIRES_AT: i64 = START + 30 * DAY;
+
+fn subscription_pda(subscriber: Pubkey, service: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"subscription", subscriber.as_ref(), service.as_ref()], &crate::ID)
+}
+
+fn subscribe_fixture() -> Fixture {
+    let (subscriber, service) = (TestAccount::signer(), TestAccount::system(0));
+    let (subscription, _) = subscription_pda(subscriber.key, service.key);
+    Fixture::new()
+        .with(
+            "subscription",
+            TestAccount::uninitialized(Subscription::SPACE).at(subscription),
+        )
+        .with(
+            "subscriber_tokens",
+            TestAccount::token_account(Pubkey::new_unique(), subscriber.key, BALANCE),
+        )
+        .with("service", service)
+        .with("subscriber", subscriber)
+        .with("token_program", TestAccount::token_program())
+        .with("system_program", TestAccount::system_program())
+}
+
+/// Daily pulls of `AMOUNT` for thirty days from `START`, delegated `allowance`
+fn pull_fixture(allowance: u64) -> Fixture {
+    let (subscriber, service) = (Pubkey::new_unique(), TestAccount::signer());
+    let (address, bump) = subscription_pda(subscriber, service.key);
+    let mint = Pubkey::new_unique();
+    let subscriber_tokens = TestAccount::token_account(mint, subscriber, BALANCE).with_delegate(address, allowance);
+    let subscription = TestAccount::anchor(&Subscription {
+        subscriber,
+        service: service.key,
+        subscriber_tokens: subscriber_tokens.key,
+        amount: AMOUNT,
+        interval: DAY,
+        next_pull_at: START,
+        expires_at: EXPIRES_AT,
+        bump,
+    })
+    .at(address);
+    Fixture::new()
+        .with("subscription", subscription)
+        .with("subscriber_tokens", subscriber_tokens)
+        .with("service_tokens", TestAccount::token_account(mint, service.key, 0))
+        .with("service", service)
+        .with("token_program", TestAccount::token_program())
+}
+
+fn cancel_fixture() -> Fixture {
+    let pull = pull_fixture(30 * AMOUNT);
+    let subscriber = pull.state::<Subscription>("subscription").subscriber;
+    Fixture::new()
+        .with("subscription", pull.account("subscription").clone())
+        .with("subscriber_tokens", pull.account("subscriber_tokens").clone())
+        .with("subscriber", TestAccount::signer().at(subscriber))
+        .with("token_program", TestAccount::token_program())
+}
+
+fn tokens(fixture: &Fixture, name: &str) -> TokenAccount {
+    fixture.state::<TokenAccount>(name)
+}
+
+#[test]
+fn subscribe_is_a_pda_of_the_subscriber_and_service() {
+    assert_seeds_violation!(Subscribe, subscribe_fixture(), "subscription");
+    assert_constraint_violation!(
+        Subscribe,
+        subscribe_fixture(),
+        "subscriber_tokens" => TestAccount::token_account(Pubkey::new_unique(), Pubkey::new_unique(), BALANCE),
+        ErrorCode::ConstraintTokenOwner
+    );
+    assert_signer_violation!(Subscribe, subscribe_fixture(), "subscriber");
+}
+
+#[test]
+fn pull_is_signed_by_the_subscriptions_service() {
+    assert_seeds_violation!(Pull, pull_fixture(u64::MAX), "subscription");
+    assert_has_one_violation!(Pull, pull_fixture(u64::MAX), "service");
+    assert_has_one_violation!(Pull, pull_fixture(u64::MAX), "subscriber_tokens");
+    assert_constraint_violation!(
+        Pull,
+        pull_fixture(u64::MAX),
+        "service_tokens" => TestAccount::token_account(Pubkey::new_unique(), Pubkey::new_unique(), 0),
+        ErrorCode::ConstraintTokenMint
+    );
+    assert_signer_violation!(Pull, pull_fixture(u64::MAX), "service");
+}
+
+#[test]
+fn cancel_is_the_subscribers() {
+    assert_seeds_violation!(Cancel, cancel_fixture(), "subscription");
+    assert_has_one_violation!(Cancel, cancel_fixture(), "subscriber");
+    assert_has_one_violation!(Cancel, cancel_fixture(), "subscriber_tokens");
+    assert_signer_violation!(Cancel, cancel_fixture(), "subscriber");
+}
+
+#[test]
+fn vulnerable_subscribe_delegates_the_whole_balance() {
+    warp_to(START);
+    let mut fixture = subscribe_fixture();
+    execute!(
+        &mut fixture,
+        instruction::VulnerableSubscribe {
+            amount: AMOUNT,
+            interval: DAY,
+            expires_at: EXPIRES_AT
+        }
+    )
+    .unwrap();
+    let subscriber_tokens = tokens(&fixture, "subscriber_tokens");
+    assert_eq!(subscriber_tokens.delegate, COption::Some(fixture.key("subscription")));
+    assert_eq!(subscriber_tokens.delegated_amount, u64::MAX);
+}
+
+#[test]
+fn vulnerable_subscribe_replaces_another_delegation() {
+    warp_to(START);
+    let mut fixture = subscribe_fixture();
+    let other = Pubkey::new_unique();
+    let existing = fixture.account("subscriber_tokens").clone().with_delegate(other, 50);
+    *fixture.account_mut("subscriber_tokens") = existing;
+    execute!(
+        &mut fixture,
+        instruction::VulnerableSubscribe {
+            amount: AMOUNT,
+            interval: DAY,
+            expires_at: EXPIRES_AT
+        }
+    )
+    .unwrap();
+    assert_eq!(
+        tokens(&fixture, "subscriber_tokens").delegate,
+        COption::Some(fixture.key("subscription"))
+    );
+}
+
+#[test]
+fn vulnerable_pull_takes_everything_at_once() {
+    warp_to(START);
+    let mut fixture = pull_fixture(u64::MAX);
+    execute!(&mut fixture, instruction::VulnerablePull { amount: BALANCE }).unwrap();
+    assert_eq!(tokens(&fixture, "subscriber_tokens").amount, 0);
+    assert_eq!(tokens(&fixture, "service_tokens").amount, BALANCE);
+}
+
+#[test]
+fn vulnerable_pull_keeps_working_after_expiry() {
+    warp_to(EXPIRES_AT + 365 * DAY);
+    let mut fixture = pull_fixture(u64::MAX);
+    execute!(&mut fixture, instruction::VulnerablePull { amount: AMOUNT }).unwrap();
+    execute!(&mut fixture, instruction::VulnerablePull { amount: AMOUNT }).unwrap();
+    assert_eq!(tokens(&fixture, "service_tokens").amount, 2 * AMOUNT);
+}
+
+#[test]
+fn secure_subscribe_delegates_only_the_terms() {
+    warp_to(START);
+    let mut fixture = subscribe_fixture();
+    execute!(
+        &mut fixture,
+        instruction::SecureSubscribe {
+            amount: AMOUNT,
+            interval: DAY,
+            expires_at: EXPIRES_AT
+        }
+    )
+    .unwrap();
+    let subscriber_tokens = tokens(&fixture, "subscriber_tokens");
+    assert_eq!(subscriber_tokens.delegate, COption::Some(fixture.key("subscription")));
+    assert_eq!(subscriber_tokens.delegated_amount, 30 * AMOUNT);
+    assert_eq!(fixture.state::<Subscription>("subscription").next_pull_at, START);
+
+    // A partial last period still gets its pull
+    let mut fixture = subscribe_fixture();
+    execute!(
+        &mut fixture,
+        instruction::SecureSubscribe {
+            amount: AMOUNT,
+            interval: DAY,
+            expires_at: START + DAY + 1
+        }
+    )
+    .unwrap();
+    assert_eq!(tokens(&fixture, "subscriber_tokens").delegated_amount, 2 * AMOUNT);
+}
+
+#[test]
+fn secure_subscribe_rejects_invalid_terms() {
+    warp_to(START);
+    for (amount, interval, expires_at) in [(0, DAY, EXPIRES_AT), (AMOUNT, 0, EXPIRES_AT), (AMOUNT, DAY, START)] {
+        let mut fixture = subscribe_fixture();
+        let result = execute!(
+            &mut fixture,
+            instruction::SecureSubscribe {
+                amount,
+                interval,
+                expires_at
+            }
+        );
+        assert_eq!(result, Err(Error::from(SubscriptionError::InvalidTerms).into()));
+    }
+
+    let mut fixture = subscribe_fixture();
+    let result = execute!(
+        &mut fixture,
+        instruction::SecureSubscribe {
+            amount: u64::MAX,
+            interval: DAY,
+            expires_at: EXPIRES_AT
+        }
+    );
+    assert_eq!(result, Err(Error::from(SubscriptionError::ArithmeticOverflow).into()));
+}
+
+#[test]
+fn secure_subscribe_leaves_another_delegation_alone() {
+    warp_to(START);
+    let mut fixture = subscribe_fixture();
+    let existing = fixture
+        .account("subscriber_tokens")
+        .clone()
+        .with_delegate(Pubkey::new_unique(), 50);
+    *fixture.account_mut("subscriber_tokens") = existing;
+    let result = execute!(
+        &mut fixture,
+        instruction::SecureSubscribe {
+            amount: AMOUNT,
+            interval: DAY,
+            expires_at: EXPIRES_AT
+        }
+    );
+    assert_eq!(result, Err(Error::from(SubscriptionError::DelegateInUse).into()));
+}
+
+#[test]
+fn secure_pull_takes_one_amount_per_interval() {
+    warp_to(START);
+    let mut fixture = pull_fixture(30 * AMOUNT);
+    execute!(&mut fixture, instruction::SecurePull {}).unwrap();
+    let result = execute!(&mut fixture, instruction::SecurePull {});
+    assert_eq!(result, Err(Error::from(SubscriptionError::PullTooEarly).into()));
+
+    warp_to(START + DAY);
+    execute!(&mut fixture, instruction::SecurePull {}).unwrap();
+    assert_eq!(tokens(&fixture, "service_tokens").amount, 2 * AMOUNT);
+    assert_eq!(tokens(&fixture, "subscriber_tokens").delegated_amount, 28 * AMOUNT);
+}
+
+#[test]
+fn secure_pull_forfeits_missed_periods() {
+    warp_to(START + 5 * DAY + 1);
+    let mut fixture = pull_fixture(30 * AMOUNT);
+    execute!(&mut fixture, instruction::SecurePull {}).unwrap();
+    let result = execute!(&mut fixture, instruction::SecurePull {});
+    assert_eq!(result, Err(Error::from(SubscriptionError::PullTooEarly).into()));
+    assert_eq!(
+        fixture.state::<Subscription>("subscription").next_pull_at,
+        START + 6 * DAY
+    );
+    assert_eq!(tokens(&fixture, "service_tokens").amount, AMOUNT);
+}
+
+#[test]
+fn secure_pull_stops_at_expiry() {
+    warp_to(EXPIRES_AT);
+    let mut fixture = pull_fixture(30 * AMOUNT);
+    let result = execute!(&mut fixture, instruction::SecurePull {});
+    assert_eq!(result, Err(Error::from(SubscriptionError::SubscriptionExpired).into()));
+}
+
+#[test]
+fn secure_pull_reports_a_revoked_delegation() {
+    warp_to(START);
+    let mut fixture = pull_fixture(30 * AMOUNT);
+    let replaced = fixture
+        .account("subscriber_tokens")
+        .clone()
+        .with_delegate(Pubkey::new_unique(), u64::MAX);
+    *fixture.account_mut("subscriber_tokens") = replaced;
+    let result = execute!(&mut fixture, instruction::SecurePull {});
+    assert_eq!(result, Err(Error::from(SubscriptionError::DelegateRevoked).into()));
+
+    // An allowance spent down below one pull has lapsed as well
+    let mut fixture = pull_fixture(AMOUNT - 1);
+    let result = execute!(&mut fixture, instruction::SecurePull {});
+    assert_eq!(result, Err(Error::from(SubscriptionError::DelegateRevoked).into()));
+}
+
+#[test]
+fn cancel_revokes_only_its_own_delegation() {
+    let mut fixture = cancel_fixture();
+    execute!(&mut fixture, instruction::Cancel {}).unwrap();
+    let subscriber_tokens = tokens(&fixture, "subscriber_tokens");
+    assert_eq!(subscriber_tokens.delegate, COption::None);
+    assert_eq!(subscriber_tokens.delegated_amount, 0);
+
+    // The subscriber already moved the delegation elsewhere; cancelling must not undo that
+    let mut fixture = cancel_fixture();
+    let other = Pubkey::new_unique();
+    let replaced = fixture.account("subscriber_tokens").clone().with_delegate(other, 50);
+    *fixture.account_mut("subscriber_tokens") = replaced;
+    execute!(&mut fixture, instruction::Cancel {}).unwrap();
+    assert_eq!(tokens(&fixture, "subscriber_tokens").delegate, COption::Some(other));
+}
@@ -0,0 +1,19 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("A subscription needs a positive amount and interval and an expiry in the future")]
+    InvalidTerms,
+    #[msg("The token account already has a delegate; revoke it first")]
+    DelegateInUse,
+    #[msg("The subscription has expired")]
+    SubscriptionExpired,
+    #[msg("The next pull is not due yet")]
+    PullTooEarly,
+    #[msg("The subscriber revoked or replaced this subscription's delegation")]
+    DelegateRevoked,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
@@ -0,0 +1,70 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct Subscribe<'info> {
+    #[account(
+        init,
+        payer = subscriber,
+        space = Subscription::SPACE,
+        seeds = [b"subscription", subscriber.key().as_ref(), service.key().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut, token::authority = subscriber)]
+    pub subscriber_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: Only its key is recorded, as the wallet allowed to pull
+    pub service: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub subscriber: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Pull<'info> {
+    #[account(
+        mut,
+        has_one = service,
+        has_one = subscriber_tokens,
+        seeds = [b"subscription", subscription.subscriber.as_ref(), subscription.service.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut)]
+    pub subscriber_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = subscriber_tokens.mint)]
+    pub service_tokens: Account<'info, TokenAccount>,
+
+    pub service: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Cancel<'info> {
+    #[account(
+        mut,
+        close = subscriber,
+        has_one = subscriber,
+        has_one = subscriber_tokens,
+        seeds = [b"subscription", subscription.subscriber.as_ref(), subscription.service.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut)]
+    pub subscriber_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub subscriber: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
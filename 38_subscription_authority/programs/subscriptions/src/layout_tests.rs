@@ -0,0 +1,21 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::Subscription;
+
+#[test]
+fn subscription_layout_is_stable() {
+    let subscription = Subscription {
+        subscriber: Pubkey::new_from_array([1; 32]),
+        service: Pubkey::new_from_array([2; 32]),
+        subscriber_tokens: Pubkey::new_from_array([3; 32]),
+        amount: 0x4142434445464748,
+        interval: 0x5152535455565758,
+        next_pull_at: 0x6162636465666768,
+        expires_at: 0x7172737475767778,
+        bump: 129,
+    };
+    assert_golden!("Subscription", golden::anchor(&subscription));
+}
@@ -0,0 +1,221 @@
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Token, TokenAccount};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Recurring token payments pulled by a service.
+///
+/// A subscriber authorizes a service to pull `amount` tokens every
+/// `interval` seconds until `expires_at`. The authorization is a PDA of the
+/// subscriber and the service, approved as the SPL Token delegate of the
+/// subscriber's token account, so the service never holds the tokens or
+/// the subscriber's signature; each pull is a transfer the PDA signs for.
+/// A token account has a single delegate, so one account backs one
+/// subscription at a time.
+#[feature_gated]
+#[program]
+pub mod subscriptions {
+    use super::*;
+
+    /// End a subscription: revoke its delegation and return the rent to the subscriber
+    pub fn cancel(ctx: Context<Cancel>) -> Result<()> {
+        // The subscriber may have revoked or replaced the delegation already;
+        // revoking unconditionally would also end whatever delegation replaced it
+        let subscription = ctx.accounts.subscription.key();
+        if ctx.accounts.subscriber_tokens.delegate == COption::Some(subscription) {
+            token::revoke(CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Revoke {
+                    source: ctx.accounts.subscriber_tokens.to_account_info(),
+                    authority: ctx.accounts.subscriber.to_account_info(),
+                },
+            ))?;
+        }
+
+        msg!("Subscription {} cancelled", subscription);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Subscribe, approving the subscription for the whole balance
+    ///
+    /// Security Issue: The terms are recorded but the delegation ignores
+    /// them: the subscription PDA may spend every token the account holds
+    /// now or receives later. Approving also silently replaces any
+    /// delegation the account already had, ending another subscription
+    /// without telling it.
+    #[vulnerable]
+    pub fn vulnerable_subscribe(ctx: Context<Subscribe>, amount: u64, interval: i64, expires_at: i64) -> Result<()> {
+        // VULNERABILITY: Unlimited allowance, whatever the terms say
+        subscribe(ctx, amount, interval, expires_at, u64::MAX)
+    }
+
+    /// VULNERABLE: Pull whatever the service asks for
+    ///
+    /// Security Issue: Only the service's signature is checked. The amount
+    /// is the service's argument and nothing looks at the interval or the
+    /// expiry, so one pull can take the subscriber's whole balance - before
+    /// the first period is due, or long after the subscription has ended.
+    #[vulnerable]
+    pub fn vulnerable_pull(ctx: Context<Pull>, amount: u64) -> Result<()> {
+        // VULNERABILITY: Any amount, at any time, for as long as the delegation lasts
+        pull(ctx.accounts, amount)?;
+
+        msg!("Pulled {}", amount);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version with the amount, interval
+    // and expiry enforced on every pull, an allowance sized to the terms,
+    // and the delegation checked before it is used or replaced.
+
+    /// SECURE: Subscribe with an allowance covering exactly the terms
+    ///
+    /// Security Fix: The terms must describe a positive amount, a positive
+    /// interval and an expiry in the future. The delegation is sized to one
+    /// `amount` per interval until expiry, so even a flaw in pulling could
+    /// not take more than the subscriber agreed to. A token account that
+    /// already has a delegate is rejected instead of silently taking the
+    /// delegation from whatever holds it.
+    #[secure]
+    pub fn secure_subscribe(ctx: Context<Subscribe>, amount: u64, interval: i64, expires_at: i64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(amount > 0 && interval > 0 && expires_at > now, ErrorCode::InvalidTerms);
+
+        // SECURITY: Never take over a delegation another subscription or program relies on
+        require!(
+            ctx.accounts.subscriber_tokens.delegate.is_none(),
+            ErrorCode::DelegateInUse
+        );
+
+        // SECURITY: Allow one amount per period until expiry, and no more
+        let periods = ((expires_at - now) as u64).div_ceil(interval as u64);
+        let allowance = amount.checked_mul(periods).ok_or(ErrorCode::ArithmeticOverflow)?;
+        subscribe(ctx, amount, interval, expires_at, allowance)
+    }
+
+    /// SECURE: Pull the subscription's amount, once per interval, until expiry
+    ///
+    /// Security Fix: The amount comes from the subscription, not from the
+    /// service. A pull before `next_pull_at` or after `expires_at` fails,
+    /// and a period the service did not pull in is forfeited rather than
+    /// collected in a burst later. If the subscriber revoked the
+    /// delegation, or replaced it, the pull fails with `DelegateRevoked`
+    /// so the service can tell a lapsed subscription from an error.
+    #[secure]
+    pub fn secure_pull(ctx: Context<Pull>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let subscription = &mut ctx.accounts.subscription;
+
+        // SECURITY: Within the term, and no sooner than the next period
+        require!(now < subscription.expires_at, ErrorCode::SubscriptionExpired);
+        require!(now >= subscription.next_pull_at, ErrorCode::PullTooEarly);
+
+        // SECURITY: The subscriber can end the authority at any time by revoking it
+        let tokens = &ctx.accounts.subscriber_tokens;
+        require!(
+            tokens.delegate == COption::Some(subscription.key()) && tokens.delegated_amount >= subscription.amount,
+            ErrorCode::DelegateRevoked
+        );
+
+        // SECURITY: One pull per period; periods missed are skipped, not accumulated
+        let missed = (now - subscription.next_pull_at) / subscription.interval;
+        subscription.next_pull_at = (missed + 1)
+            .checked_mul(subscription.interval)
+            .and_then(|skip| subscription.next_pull_at.checked_add(skip))
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let amount = subscription.amount;
+        pull(ctx.accounts, amount)?;
+
+        msg!(
+            "Pulled {}; next pull at {}",
+            amount,
+            ctx.accounts.subscription.next_pull_at
+        );
+        Ok(())
+    }
+}
+
+/// Record the terms and approve the subscription as delegate for `allowance`
+fn subscribe(ctx: Context<Subscribe>, amount: u64, interval: i64, expires_at: i64, allowance: u64) -> Result<()> {
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.subscriber = ctx.accounts.subscriber.key();
+    subscription.service = ctx.accounts.service.key();
+    subscription.subscriber_tokens = ctx.accounts.subscriber_tokens.key();
+    subscription.amount = amount;
+    subscription.interval = interval;
+    subscription.next_pull_at = Clock::get()?.unix_timestamp;
+    subscription.expires_at = expires_at;
+    subscription.bump = ctx.bumps.subscription;
+
+    token::approve(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Approve {
+                to: ctx.accounts.subscriber_tokens.to_account_info(),
+                delegate: ctx.accounts.subscription.to_account_info(),
+                authority: ctx.accounts.subscriber.to_account_info(),
+            },
+        ),
+        allowance,
+    )?;
+
+    msg!(
+        "Subscribed {} to {}: {} every {}s until {}, allowance {}",
+        ctx.accounts.subscriber.key(),
+        ctx.accounts.service.key(),
+        amount,
+        interval,
+        expires_at,
+        allowance
+    );
+    Ok(())
+}
+
+/// Transfer `amount` from the subscriber to the service, signed by the subscription PDA
+fn pull(accounts: &Pull, amount: u64) -> Result<()> {
+    let subscription = &accounts.subscription;
+    let seeds: &[&[u8]] = &[
+        b"subscription",
+        subscription.subscriber.as_ref(),
+        subscription.service.as_ref(),
+        &[subscription.bump],
+    ];
+    token::transfer(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: accounts.subscriber_tokens.to_account_info(),
+                to: accounts.service_tokens.to_account_info(),
+                authority: subscription.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )
+}
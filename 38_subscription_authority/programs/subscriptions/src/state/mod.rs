@@ -0,0 +1,27 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Subscription {
+    /// Wallet paying (32 bytes)
+    pub subscriber: Pubkey,
+    /// Wallet allowed to pull (32 bytes)
+    pub service: Pubkey,
+    /// Token account pulled from; the subscription is its delegate (32 bytes)
+    pub subscriber_tokens: Pubkey,
+    /// Tokens per pull (8 bytes)
+    pub amount: u64,
+    /// Seconds between pulls (8 bytes)
+    pub interval: i64,
+    /// Earliest time of the next pull (8 bytes)
+    pub next_pull_at: i64,
+    /// No pulls from this time on (8 bytes)
+    pub expires_at: i64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Subscription {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 1;
+}
@@ -0,0 +1,318 @@
+//! The inbox contexts' constraints, and messages delivered with and without
+//! the guardians' attestation.
+//!
+//! Ed25519 instructions here carry placeholder signatures. On chain the
+//! precompile rejects the transaction before the inbox runs unless every
+//! signature is valid, so the program only reads who signed which bytes,
+//! and that is what these tests exercise.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::Instruction;
+use secref_testkit::runtime::account_already_in_use;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, execute,
+    Fixture, TestAccount,
+};
+
+use crate::{
+    instruction, Bridge, BridgeMessage, ConsumedMessage, ErrorCode as BridgeError, InitializeBridge, SecureReceive,
+    MAX_GUARDIANS,
+};
+
+const ADMIN: Pubkey = Pubkey::new_from_array([9; 32]);
+const CUSTODY: u64 = 10_000_000_000;
+const AMOUNT: u64 = 1_000_000_000;
+const THRESHOLD: u8 = 2;
+
+fn guardians() -> Vec<Pubkey> {
+    (1..=3).map(|seed| Pubkey::new_from_array([seed; 32])).collect()
+}
+
+fn bridge_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"bridge", ADMIN.as_ref()], &crate::ID)
+}
+
+fn message(sequence: u64) -> BridgeMessage {
+    BridgeMessage {
+        emitter_chain: 2,
+        emitter: [7; 32],
+        sequence,
+        recipient: Pubkey::new_from_array([8; 32]),
+        amount: AMOUNT,
+    }
+}
+
+/// An Ed25519 program instruction with one placeholder signature per `(signer, message)`
+fn ed25519_instruction(signatures: &[(Pubkey, Vec<u8>)]) -> Instruction {
+    const CURRENT: usize = u16::MAX as usize;
+    let header = 2 + signatures.len() * 14;
+    let mut data = vec![signatures.len() as u8, 0];
+    let mut body = Vec::new();
+    for (signer, message) in signatures {
+        let public_key_offset = header + body.len();
+        body.extend_from_slice(signer.as_ref());
+        let signature_offset = header + body.len();
+        body.extend_from_slice(&[0; 64]);
+        let message_offset = header + body.len();
+        body.extend_from_slice(message);
+        for value in [
+            signature_offset,
+            CURRENT,
+            public_key_offset,
+            CURRENT,
+            message_offset,
+            message.len(),
+            CURRENT,
+        ] {
+            data.extend_from_slice(&(value as u16).to_le_bytes());
+        }
+    }
+    data.extend_from_slice(&body);
+    Instruction::new_with_bytes(ed25519_program::ID, &data, vec![])
+}
+
+/// One Ed25519 instruction per signer, each over `message` as this bridge's guardians sign it
+fn attestation(signers: &[Pubkey], message: &BridgeMessage) -> Vec<Instruction> {
+    let bytes = message.signing_bytes(&bridge_pda().0).unwrap();
+    signers
+        .iter()
+        .map(|signer| ed25519_instruction(&[(*signer, bytes.clone())]))
+        .collect()
+}
+
+/// The bridge, holding CUSTODY lamports above its reserve
+fn bridge() -> TestAccount {
+    let (address, bump) = bridge_pda();
+    TestAccount::anchor(&Bridge {
+        admin: ADMIN,
+        guardians: guardians(),
+        threshold: THRESHOLD,
+        bump,
+    })
+    .at(address)
+    .with_lamports(Rent::default().minimum_balance(Bridge::SPACE) + CUSTODY)
+}
+
+fn initialize_fixture(guardians: Vec<Pubkey>, threshold: u8) -> Fixture {
+    let admin = TestAccount::signer().at(ADMIN);
+    Fixture::new()
+        .with("bridge", TestAccount::uninitialized(Bridge::SPACE).at(bridge_pda().0))
+        .with("admin", admin)
+        .with("system_program", TestAccount::system_program())
+        .args(instruction::InitializeBridge { guardians, threshold })
+}
+
+fn vulnerable_fixture(message: &BridgeMessage) -> Fixture {
+    Fixture::new()
+        .with("bridge", bridge())
+        .with("recipient", TestAccount::system(0).at(message.recipient))
+        .with("relayer", TestAccount::signer())
+        .args(instruction::VulnerableReceive {
+            message: message.clone(),
+        })
+}
+
+/// Delivery of `message` in a transaction where `preceding` run before the inbox
+fn secure_fixture(message: &BridgeMessage, mut preceding: Vec<Instruction>) -> Fixture {
+    let (bridge_address, _) = bridge_pda();
+    let (consumed, _) = Pubkey::find_program_address(
+        &[
+            b"consumed",
+            bridge_address.as_ref(),
+            &message.emitter_chain.to_le_bytes(),
+            &message.emitter,
+            &message.sequence.to_le_bytes(),
+        ],
+        &crate::ID,
+    );
+    let current = preceding.len() as u16;
+    preceding.push(Instruction::new_with_bytes(crate::ID, &[], vec![]));
+
+    Fixture::new()
+        .with("bridge", bridge())
+        .with(
+            "consumed",
+            TestAccount::uninitialized(ConsumedMessage::SPACE).at(consumed),
+        )
+        .with("recipient", TestAccount::system(0).at(message.recipient))
+        .with("instructions", TestAccount::instructions_sysvar_of(&preceding, current))
+        .with("relayer", TestAccount::signer())
+        .with("system_program", TestAccount::system_program())
+        .args(instruction::SecureReceive {
+            message: message.clone(),
+        })
+}
+
+fn secure_receive(fixture: &mut Fixture, message: &BridgeMessage) -> ProgramResult {
+    execute!(
+        fixture,
+        instruction::SecureReceive {
+            message: message.clone(),
+        }
+    )
+}
+
+fn not_enough_signatures() -> ProgramResult {
+    Err(Error::from(BridgeError::NotEnoughSignatures).into())
+}
+
+#[test]
+fn initialize_bridge_is_a_pda_of_the_admin() {
+    assert_seeds_violation!(InitializeBridge, initialize_fixture(guardians(), THRESHOLD), "bridge");
+    assert_signer_violation!(InitializeBridge, initialize_fixture(guardians(), THRESHOLD), "admin");
+}
+
+#[test]
+fn initialize_bridge_rejects_unusable_guardian_sets() {
+    let cases = [
+        (guardians(), 0, BridgeError::InvalidThreshold),
+        (guardians(), 4, BridgeError::InvalidThreshold),
+        (vec![guardians()[0]; 2], 1, BridgeError::DuplicateGuardian),
+        (
+            (0..=MAX_GUARDIANS).map(|_| Pubkey::new_unique()).collect(),
+            1,
+            BridgeError::TooManyGuardians,
+        ),
+    ];
+    for (guardians, threshold, error) in cases {
+        let mut fixture = initialize_fixture(guardians.clone(), threshold);
+        let result = execute!(&mut fixture, instruction::InitializeBridge { guardians, threshold });
+        assert_eq!(result, Err(Error::from(error).into()));
+    }
+
+    let mut fixture = initialize_fixture(guardians(), THRESHOLD);
+    execute!(
+        &mut fixture,
+        instruction::InitializeBridge {
+            guardians: guardians(),
+            threshold: THRESHOLD
+        }
+    )
+    .unwrap();
+    assert_eq!(fixture.state::<Bridge>("bridge").guardians, guardians());
+}
+
+#[test]
+fn secure_receive_checks_every_account() {
+    let fixture = || secure_fixture(&message(1), attestation(&guardians()[..2], &message(1)));
+    assert_seeds_violation!(SecureReceive, fixture(), "bridge");
+    assert_owner_violation!(SecureReceive, fixture(), "bridge");
+    assert_seeds_violation!(SecureReceive, fixture(), "consumed");
+    assert_constraint_violation!(
+        SecureReceive,
+        fixture(),
+        "recipient" => |recipient| recipient.key = Pubkey::new_unique(),
+        BridgeError::RecipientMismatch
+    );
+    assert_constraint_violation!(
+        SecureReceive,
+        fixture(),
+        "instructions" => TestAccount::new(Pubkey::new_unique(), vec![0; 2]),
+        ErrorCode::ConstraintAddress
+    );
+    assert_signer_violation!(SecureReceive, fixture(), "relayer");
+}
+
+#[test]
+fn vulnerable_receive_pays_a_message_nobody_attested() {
+    let forged = BridgeMessage {
+        amount: CUSTODY,
+        ..message(1)
+    };
+    let mut fixture = vulnerable_fixture(&forged);
+    execute!(&mut fixture, instruction::VulnerableReceive { message: forged }).unwrap();
+    assert_eq!(fixture.account("recipient").lamports, CUSTODY);
+}
+
+#[test]
+fn vulnerable_receive_pays_the_same_message_again_and_again() {
+    let mut fixture = vulnerable_fixture(&message(1));
+    for _ in 0..5 {
+        execute!(&mut fixture, instruction::VulnerableReceive { message: message(1) }).unwrap();
+    }
+    assert_eq!(fixture.account("recipient").lamports, 5 * AMOUNT);
+}
+
+#[test]
+fn secure_receive_pays_a_message_the_threshold_attested() {
+    let mut fixture = secure_fixture(&message(1), attestation(&guardians()[1..], &message(1)));
+    secure_receive(&mut fixture, &message(1)).unwrap();
+    assert_eq!(fixture.account("recipient").lamports, AMOUNT);
+    assert_eq!(fixture.account("consumed").owner, crate::ID);
+}
+
+#[test]
+fn secure_receive_executes_each_message_once() {
+    let mut fixture = secure_fixture(&message(1), attestation(&guardians(), &message(1)));
+    secure_receive(&mut fixture, &message(1)).unwrap();
+    assert_eq!(secure_receive(&mut fixture, &message(1)), Err(account_already_in_use()));
+    assert_eq!(fixture.account("recipient").lamports, AMOUNT);
+
+    // The next sequence number is a different message, with its own nullifier
+    let mut fixture = secure_fixture(&message(2), attestation(&guardians(), &message(2)));
+    secure_receive(&mut fixture, &message(2)).unwrap();
+}
+
+#[test]
+fn secure_receive_needs_the_threshold() {
+    let mut fixture = secure_fixture(&message(1), attestation(&guardians()[..1], &message(1)));
+    assert_eq!(secure_receive(&mut fixture, &message(1)), not_enough_signatures());
+
+    let mut fixture = secure_fixture(&message(1), vec![]);
+    assert_eq!(secure_receive(&mut fixture, &message(1)), not_enough_signatures());
+}
+
+#[test]
+fn secure_receive_counts_each_guardian_once() {
+    // Twice in separate instructions
+    let guardian = guardians()[0];
+    let mut fixture = secure_fixture(&message(1), attestation(&[guardian, guardian], &message(1)));
+    assert_eq!(secure_receive(&mut fixture, &message(1)), not_enough_signatures());
+
+    // Twice in one instruction
+    let bytes = message(1).signing_bytes(&bridge_pda().0).unwrap();
+    let twice = ed25519_instruction(&[(guardian, bytes.clone()), (guardian, bytes)]);
+    let mut fixture = secure_fixture(&message(1), vec![twice]);
+    assert_eq!(secure_receive(&mut fixture, &message(1)), not_enough_signatures());
+}
+
+#[test]
+fn secure_receive_ignores_signers_outside_the_guardian_set() {
+    let signers = [guardians()[0], Pubkey::new_unique(), Pubkey::new_unique()];
+    let mut fixture = secure_fixture(&message(1), attestation(&signers, &message(1)));
+    assert_eq!(secure_receive(&mut fixture, &message(1)), not_enough_signatures());
+}
+
+#[test]
+fn secure_receive_rejects_signatures_over_other_bytes() {
+    // Attested for a smaller amount
+    let attested = BridgeMessage {
+        amount: 1,
+        ..message(1)
+    };
+    let mut fixture = secure_fixture(&message(1), attestation(&guardians(), &attested));
+    assert_eq!(secure_receive(&mut fixture, &message(1)), not_enough_signatures());
+
+    // Attested for another bridge the same guardians serve
+    let other_bridge = message(1).signing_bytes(&Pubkey::new_unique()).unwrap();
+    let preceding = guardians()
+        .into_iter()
+        .map(|guardian| ed25519_instruction(&[(guardian, other_bridge.clone())]))
+        .collect();
+    let mut fixture = secure_fixture(&message(1), preceding);
+    assert_eq!(secure_receive(&mut fixture, &message(1)), not_enough_signatures());
+}
+
+#[test]
+fn secure_receive_rejects_offsets_into_other_instructions() {
+    let mut preceding = attestation(&guardians(), &message(1));
+    // The first entry's message instruction index, the last of its seven offsets
+    preceding[0].data[14..16].copy_from_slice(&1u16.to_le_bytes());
+    let mut fixture = secure_fixture(&message(1), preceding);
+    assert_eq!(
+        secure_receive(&mut fixture, &message(1)),
+        Err(Error::from(BridgeError::InvalidSignatureInstruction).into())
+    );
+}
@@ -0,0 +1,23 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("A bridge can have at most MAX_GUARDIANS guardians")]
+    TooManyGuardians,
+    #[msg("The threshold must be between one and the number of guardians")]
+    InvalidThreshold,
+    #[msg("A guardian is listed twice")]
+    DuplicateGuardian,
+    #[msg("The recipient account is not the one the message names")]
+    RecipientMismatch,
+    #[msg("Ed25519 instruction is malformed or references other instructions")]
+    InvalidSignatureInstruction,
+    #[msg("Too few distinct guardians signed this message for this bridge")]
+    NotEnoughSignatures,
+    #[msg("Bridge custody has insufficient funds")]
+    InsufficientFunds,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
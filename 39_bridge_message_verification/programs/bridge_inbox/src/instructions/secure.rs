@@ -0,0 +1,39 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+#[instruction(message: BridgeMessage)]
+pub struct SecureReceive<'info> {
+    #[account(mut, seeds = [b"bridge", bridge.admin.as_ref()], bump = bridge.bump)]
+    pub bridge: Account<'info, Bridge>,
+
+    // SECURITY: One nullifier per message; `init` fails once it exists
+    #[account(
+        init,
+        payer = relayer,
+        space = ConsumedMessage::SPACE,
+        seeds = [
+            b"consumed",
+            bridge.key().as_ref(),
+            message.emitter_chain.to_le_bytes().as_ref(),
+            message.emitter.as_ref(),
+            message.sequence.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub consumed: Account<'info, ConsumedMessage>,
+
+    /// CHECK: The recipient the message names
+    #[account(mut, address = message.recipient @ ErrorCode::RecipientMismatch)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Address constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// Anyone may relay; the guardians' signatures are what is trusted
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
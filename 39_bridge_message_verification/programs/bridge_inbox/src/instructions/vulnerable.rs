@@ -0,0 +1,17 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+#[instruction(message: BridgeMessage)]
+pub struct VulnerableReceive<'info> {
+    #[account(mut, seeds = [b"bridge", bridge.admin.as_ref()], bump = bridge.bump)]
+    pub bridge: Account<'info, Bridge>,
+
+    /// CHECK: The recipient the message names
+    #[account(mut, address = message.recipient @ ErrorCode::RecipientMismatch)]
+    pub recipient: UncheckedAccount<'info>,
+
+    // VULNERABILITY: Any wallet can relay, and its signature is all that is checked
+    pub relayer: Signer<'info>,
+}
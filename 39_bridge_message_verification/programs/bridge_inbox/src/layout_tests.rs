@@ -0,0 +1,25 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Bridge, ConsumedMessage};
+
+#[test]
+fn bridge_layout_is_stable() {
+    let bridge = Bridge {
+        admin: Pubkey::new_from_array([1; 32]),
+        guardians: vec![Pubkey::new_from_array([2; 32]), Pubkey::new_from_array([3; 32])],
+        threshold: 65,
+        bump: 81,
+    };
+    assert_golden!("Bridge", golden::anchor(&bridge));
+}
+
+#[test]
+fn consumed_message_layout_is_stable() {
+    let consumed_message = ConsumedMessage {
+        bump: 97,
+    };
+    assert_golden!("ConsumedMessage", golden::anchor(&consumed_message));
+}
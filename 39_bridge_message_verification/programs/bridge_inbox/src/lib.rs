@@ -0,0 +1,252 @@
+#[cfg(feature = "secure")]
+use anchor_lang::solana_program::ed25519_program;
+#[cfg(feature = "secure")]
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use secref_guards::rent::minimum_balance;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Most guardians a bridge can have
+pub const MAX_GUARDIANS: usize = 5;
+
+/// Offset of the first signature-offsets entry in Ed25519 program instruction data
+#[cfg(feature = "secure")]
+const ED25519_OFFSETS_START: usize = 2;
+
+/// Size of one signature-offsets entry (seven little-endian u16 values)
+#[cfg(feature = "secure")]
+const ED25519_OFFSETS_SIZE: usize = 14;
+
+/// Instruction index the Ed25519 program uses for "this instruction"
+#[cfg(feature = "secure")]
+const ED25519_CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// The receiving end of a toy lamport bridge.
+///
+/// Funds locked on a source chain are released here when a relayer
+/// delivers the source chain's message. Relayers are untrusted couriers:
+/// what makes a message genuine is a threshold of the bridge's guardians
+/// observing it on the source chain and signing it, and what makes its
+/// execution safe is that each message runs at most once.
+#[feature_gated]
+#[program]
+pub mod bridge_inbox {
+    use super::*;
+
+    /// Create a bridge trusting `guardians`, any `threshold` of whom can attest a message
+    pub fn initialize_bridge(ctx: Context<InitializeBridge>, guardians: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        require!(guardians.len() <= MAX_GUARDIANS, ErrorCode::TooManyGuardians);
+        require!(
+            threshold > 0 && usize::from(threshold) <= guardians.len(),
+            ErrorCode::InvalidThreshold
+        );
+        for (index, guardian) in guardians.iter().enumerate() {
+            require!(!guardians[..index].contains(guardian), ErrorCode::DuplicateGuardian);
+        }
+
+        let bridge = &mut ctx.accounts.bridge;
+        bridge.admin = ctx.accounts.admin.key();
+        bridge.guardians = guardians;
+        bridge.threshold = threshold;
+        bridge.bump = ctx.bumps.bridge;
+
+        msg!(
+            "Bridge initialized: {} of {} guardians",
+            threshold,
+            ctx.accounts.bridge.guardians.len()
+        );
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Execute whatever message a relayer delivers
+    ///
+    /// Security Issue: The only signature on the transaction is the
+    /// relayer's, which proves who paid the fee and nothing about the
+    /// source chain. Anyone can relay a message that was never emitted.
+    /// Nothing records which messages have run either, so a genuine
+    /// message can be delivered again and again until custody is empty.
+    #[vulnerable]
+    pub fn vulnerable_receive(ctx: Context<VulnerableReceive>, message: BridgeMessage) -> Result<()> {
+        // VULNERABILITY: No guardian attestation, and no record that this message ran
+        release(&ctx.accounts.bridge, &ctx.accounts.recipient, message.amount)?;
+
+        msg!(
+            "Released {} lamports for chain {} sequence {}",
+            message.amount,
+            message.emitter_chain,
+            message.sequence
+        );
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version with guardian signatures
+    // verified through Ed25519 instruction introspection and every message
+    // consumed by a nullifier PDA.
+
+    /// SECURE: Execute a message attested by a threshold of guardians, once
+    ///
+    /// Security Fix: The transaction must carry Ed25519 instructions in
+    /// which at least `threshold` distinct guardians signed this bridge's
+    /// address followed by the message. The `consumed` nullifier PDA is
+    /// derived from the message's emitter and sequence, so `init` fails
+    /// with the System Program's `AccountAlreadyInUse` if the message has
+    /// already been executed.
+    #[secure]
+    pub fn secure_receive(ctx: Context<SecureReceive>, message: BridgeMessage) -> Result<()> {
+        let bridge = &ctx.accounts.bridge;
+
+        // SECURITY: Signatures must cover this bridge, not just the message
+        let expected = message.signing_bytes(&bridge.key())?;
+        let approvals = guardian_approvals(&ctx.accounts.instructions, &bridge.guardians, &expected)?;
+        require!(
+            approvals >= usize::from(bridge.threshold),
+            ErrorCode::NotEnoughSignatures
+        );
+
+        // SECURITY: The nullifier now exists, so this message can never run again
+        ctx.accounts.consumed.bump = ctx.bumps.consumed;
+
+        release(&ctx.accounts.bridge, &ctx.accounts.recipient, message.amount)?;
+
+        msg!(
+            "Released {} lamports for chain {} sequence {} with {} guardian signatures",
+            message.amount,
+            message.emitter_chain,
+            message.sequence,
+            approvals
+        );
+        Ok(())
+    }
+}
+
+// ========================================
+// MESSAGE FORMAT
+// ========================================
+
+/// A transfer emitted on the source chain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BridgeMessage {
+    /// Chain the message was emitted on
+    pub emitter_chain: u16,
+    /// Contract that emitted it, in the source chain's address format
+    pub emitter: [u8; 32],
+    /// The emitter's counter; with the chain and emitter, names the message uniquely
+    pub sequence: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+impl BridgeMessage {
+    /// SECURE: The bytes guardians sign: the bridge's address, then the Borsh-encoded message
+    pub fn signing_bytes(&self, bridge: &Pubkey) -> Result<Vec<u8>> {
+        let mut bytes = bridge.to_bytes().to_vec();
+        self.serialize(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+// ========================================
+// SIGNATURE VERIFICATION
+// ========================================
+
+/// Count the distinct guardians that signed `expected` in Ed25519 instructions before this one
+///
+/// The Ed25519 program checks every signature in its instructions before
+/// the transaction runs, so any signature found here is valid; this only
+/// decides whose it is and what it covers. Offsets must point into the
+/// Ed25519 instruction itself, or the bytes read here could differ from
+/// the bytes that were verified.
+#[cfg(feature = "secure")]
+fn guardian_approvals(instructions: &AccountInfo, guardians: &[Pubkey], expected: &[u8]) -> Result<usize> {
+    let current = load_current_index_checked(instructions)?;
+    let mut approvals: Vec<Pubkey> = Vec::new();
+
+    for index in 0..current {
+        let instruction = load_instruction_at_checked(usize::from(index), instructions)?;
+        if instruction.program_id != ed25519_program::ID {
+            continue;
+        }
+
+        let data = &instruction.data;
+        let count = usize::from(*data.first().ok_or(ErrorCode::InvalidSignatureInstruction)?);
+        require!(
+            data.len() >= ED25519_OFFSETS_START + count * ED25519_OFFSETS_SIZE,
+            ErrorCode::InvalidSignatureInstruction
+        );
+
+        for entry in 0..count {
+            let read_u16 = |field: usize| {
+                let at = ED25519_OFFSETS_START + entry * ED25519_OFFSETS_SIZE + field * 2;
+                u16::from_le_bytes([data[at], data[at + 1]])
+            };
+            let signature_instruction = read_u16(1);
+            let public_key_offset = usize::from(read_u16(2));
+            let public_key_instruction = read_u16(3);
+            let message_offset = usize::from(read_u16(4));
+            let message_size = usize::from(read_u16(5));
+            let message_instruction = read_u16(6);
+            require!(
+                signature_instruction == ED25519_CURRENT_INSTRUCTION
+                    && public_key_instruction == ED25519_CURRENT_INSTRUCTION
+                    && message_instruction == ED25519_CURRENT_INSTRUCTION,
+                ErrorCode::InvalidSignatureInstruction
+            );
+
+            let signer = data
+                .get(public_key_offset..public_key_offset + 32)
+                .and_then(|bytes| Pubkey::try_from(bytes).ok())
+                .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+            let message = data
+                .get(message_offset..message_offset + message_size)
+                .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+
+            // SECURITY: Only guardians count, each of them once, and only for this exact message
+            if message == expected && guardians.contains(&signer) && !approvals.contains(&signer) {
+                approvals.push(signer);
+            }
+        }
+    }
+    Ok(approvals.len())
+}
+
+/// Pay `amount` lamports from the bridge's custody to `recipient`, keeping the bridge rent-exempt
+fn release<'info>(bridge: &Account<'info, Bridge>, recipient: &AccountInfo<'info>, amount: u64) -> Result<()> {
+    let bridge_info = bridge.to_account_info();
+    let reserve = minimum_balance(bridge_info.data_len())?;
+    require!(
+        amount <= bridge_info.lamports().saturating_sub(reserve),
+        ErrorCode::InsufficientFunds
+    );
+
+    **bridge_info.try_borrow_mut_lamports()? -= amount;
+    **recipient.try_borrow_mut_lamports()? = recipient
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
@@ -0,0 +1,30 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Bridge {
+    /// Wallet that created the bridge; seeds its address (32 bytes)
+    pub admin: Pubkey,
+    /// Keys that observe the source chain and sign its messages (4 + 32 * MAX_GUARDIANS bytes)
+    pub guardians: Vec<Pubkey>,
+    /// Distinct guardian signatures a message needs (1 byte)
+    pub threshold: u8,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Bridge {
+    pub const SPACE: usize = 8 + 32 + 4 + 32 * MAX_GUARDIANS + 1 + 1;
+}
+
+/// Nullifier for one executed message; its existence is the record
+#[account]
+pub struct ConsumedMessage {
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl ConsumedMessage {
+    pub const SPACE: usize = 8 + 1;
+}
@@ -0,0 +1,120 @@
+//! Claim records at a bump the client sends, and the claim that derives
+//! the distributor's address on every call.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use secref_guards::GuardError;
+use secref_testkit::runtime::account_already_in_use;
+use secref_testkit::{execute, Fixture, TestAccount};
+
+use crate::{instruction, ClaimRecord, Distributor};
+
+const AMOUNT_PER_CLAIM: u64 = 100;
+const VAULT_BALANCE: u64 = 10_000;
+
+/// A funded distributor and a claimant with an empty token account; `claim` is the canonical record's address
+fn claim_fixture() -> Fixture {
+    let mint = Pubkey::new_unique();
+    let (address, bump) = Pubkey::find_program_address(&[b"distributor", mint.as_ref()], &crate::ID);
+    let vault = TestAccount::token_account(mint, address, VAULT_BALANCE);
+    let distributor = TestAccount::anchor(&Distributor {
+        operator: Pubkey::new_unique(),
+        mint,
+        vault: vault.key,
+        amount_per_claim: AMOUNT_PER_CLAIM,
+        bump,
+    })
+    .at(address);
+
+    let claimant = TestAccount::signer();
+    let (claim, _) = Pubkey::find_program_address(&[b"claim", address.as_ref(), claimant.key.as_ref()], &crate::ID);
+    Fixture::new()
+        .with("distributor", distributor)
+        .with("vault", vault)
+        .with("claim", TestAccount::system(0).at(claim))
+        .with("claimant_tokens", TestAccount::token_account(mint, claimant.key, 0))
+        .with("claimant", claimant)
+        .with("token_program", TestAccount::program(anchor_spl::token::ID))
+        .with("system_program", TestAccount::system_program())
+}
+
+/// Every bump the claimant's record seeds have an address at, canonical first
+fn record_addresses(fixture: &Fixture) -> Vec<(Pubkey, u8)> {
+    let (distributor, claimant) = (fixture.key("distributor"), fixture.key("claimant"));
+    (0..=u8::MAX)
+        .rev()
+        .filter_map(|bump| {
+            Pubkey::create_program_address(&[b"claim", distributor.as_ref(), claimant.as_ref(), &[bump]], &crate::ID)
+                .ok()
+                .map(|address| (address, bump))
+        })
+        .collect()
+}
+
+/// Point the `claim` account at `address`, as an empty system account
+fn claim_at(fixture: &mut Fixture, address: Pubkey) {
+    *fixture.account_mut("claim") = TestAccount::system(0).at(address);
+}
+
+fn claimed(fixture: &Fixture) -> u64 {
+    fixture.state::<TokenAccount>("claimant_tokens").amount
+}
+
+#[test]
+fn vulnerable_claim_with_bump_pays_once_per_off_curve_bump() {
+    let mut fixture = claim_fixture();
+    let addresses = record_addresses(&fixture);
+    assert!(addresses.len() > 64, "about half of all bumps land off the curve");
+
+    for &(address, bump) in &addresses[..5] {
+        claim_at(&mut fixture, address);
+        execute!(&mut fixture, instruction::VulnerableClaimWithBump { claim_bump: bump }).unwrap();
+        assert_eq!(fixture.state::<ClaimRecord>("claim").bump, bump);
+    }
+
+    // Five records, each "the" claimant's one claim
+    assert_eq!(claimed(&fixture), 5 * AMOUNT_PER_CLAIM);
+}
+
+#[test]
+fn secure_claim_with_bump_only_creates_the_canonical_record() {
+    let mut fixture = claim_fixture();
+    let addresses = record_addresses(&fixture);
+    let (canonical, canonical_bump) = addresses[0];
+    let (other, other_bump) = addresses[1];
+
+    claim_at(&mut fixture, other);
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureClaimWithBump { claim_bump: other_bump }),
+        Err(Error::from(GuardError::NonCanonicalBump).into())
+    );
+    // A canonical bump sent with another address is refused too
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureClaimWithBump { claim_bump: canonical_bump }),
+        Err(Error::from(GuardError::PdaMismatch).into())
+    );
+
+    claim_at(&mut fixture, canonical);
+    execute!(&mut fixture, instruction::SecureClaimWithBump { claim_bump: canonical_bump }).unwrap();
+    assert_eq!(claimed(&fixture), AMOUNT_PER_CLAIM);
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureClaimWithBump { claim_bump: canonical_bump }),
+        Err(account_already_in_use())
+    );
+}
+
+#[test]
+fn recomputed_and_stored_bump_claims_pay_the_same_single_claim() {
+    let mut stored = claim_fixture();
+    execute!(&mut stored, instruction::SecureClaim {}).unwrap();
+    let mut recomputed = claim_fixture();
+    execute!(&mut recomputed, instruction::RecomputedBumpClaim {}).unwrap();
+
+    for mut fixture in [stored, recomputed] {
+        assert_eq!(claimed(&fixture), AMOUNT_PER_CLAIM);
+        assert_eq!(
+            execute!(&mut fixture, instruction::RecomputedBumpClaim {}),
+            Err(account_already_in_use())
+        );
+    }
+}
@@ -0,0 +1,183 @@
+//! The claim contexts' constraints, and what each claim leaves the
+//! claimant's token account looking like.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::TokenAccount;
+use secref_testkit::runtime::account_already_in_use;
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_seeds_violation, assert_signer_violation, execute,
+    Fixture, TestAccount,
+};
+
+use crate::{instruction, ClaimRecord, Distributor, InitializeDistributor, SecureClaim};
+
+const AMOUNT_PER_CLAIM: u64 = 100;
+const VAULT_BALANCE: u64 = 10_000;
+const CLAIMANT_BALANCE: u64 = 5_000;
+
+fn initialize_fixture() -> Fixture {
+    let mint = Pubkey::new_unique();
+    let (distributor, _) = Pubkey::find_program_address(&[b"distributor", mint.as_ref()], &crate::ID);
+    let (vault, _) = Pubkey::find_program_address(&[b"vault", distributor.as_ref()], &crate::ID);
+    Fixture::new()
+        .with(
+            "distributor",
+            TestAccount::uninitialized(Distributor::SPACE).at(distributor),
+        )
+        .with("vault", TestAccount::uninitialized(165).at(vault))
+        .with("mint", TestAccount::mint(Pubkey::new_unique(), 6, 0).at(mint))
+        .with("operator", TestAccount::signer())
+        .with("token_program", TestAccount::program(anchor_spl::token::ID))
+        .with("system_program", TestAccount::system_program())
+}
+
+/// A funded distributor and a claimant whose token account already holds CLAIMANT_BALANCE
+///
+/// The vulnerable context takes the operator after the claimant's token
+/// account; the secure one has no such account.
+fn claim_fixture(with_operator: bool) -> Fixture {
+    let mint = Pubkey::new_unique();
+    let operator = Pubkey::new_unique();
+    let (address, bump) = Pubkey::find_program_address(&[b"distributor", mint.as_ref()], &crate::ID);
+    let vault = TestAccount::token_account(mint, address, VAULT_BALANCE);
+    let distributor = TestAccount::anchor(&Distributor {
+        operator,
+        mint,
+        vault: vault.key,
+        amount_per_claim: AMOUNT_PER_CLAIM,
+        bump,
+    })
+    .at(address);
+
+    let claimant = TestAccount::signer();
+    let (claim, _) = Pubkey::find_program_address(&[b"claim", address.as_ref(), claimant.key.as_ref()], &crate::ID);
+    let fixture = Fixture::new()
+        .with("distributor", distributor)
+        .with("vault", vault)
+        .with("claim", TestAccount::uninitialized(ClaimRecord::SPACE).at(claim))
+        .with(
+            "claimant_tokens",
+            TestAccount::token_account(mint, claimant.key, CLAIMANT_BALANCE),
+        );
+    let fixture = if with_operator {
+        fixture.with("operator", TestAccount::system(0).at(operator))
+    } else {
+        fixture
+    };
+    fixture
+        .with("claimant", claimant)
+        .with("token_program", TestAccount::program(anchor_spl::token::ID))
+        .with("system_program", TestAccount::system_program())
+}
+
+fn tokens(fixture: &Fixture) -> TokenAccount {
+    fixture.state::<TokenAccount>("claimant_tokens")
+}
+
+#[test]
+fn initialize_distributor_owns_its_vault() {
+    assert_seeds_violation!(InitializeDistributor, initialize_fixture(), "distributor");
+    assert_seeds_violation!(InitializeDistributor, initialize_fixture(), "vault");
+    assert_signer_violation!(InitializeDistributor, initialize_fixture(), "operator");
+
+    let mut fixture = initialize_fixture();
+    execute!(
+        &mut fixture,
+        instruction::InitializeDistributor {
+            amount_per_claim: AMOUNT_PER_CLAIM
+        }
+    )
+    .unwrap();
+    let vault = fixture.state::<TokenAccount>("vault");
+    assert_eq!(vault.owner, fixture.key("distributor"));
+    assert_eq!(fixture.state::<Distributor>("distributor").vault, fixture.key("vault"));
+}
+
+#[test]
+fn secure_claim_pays_the_claimants_own_account_from_the_distributors_vault() {
+    assert_seeds_violation!(SecureClaim, claim_fixture(false), "distributor");
+    assert_has_one_violation!(SecureClaim, claim_fixture(false), "vault");
+    assert_seeds_violation!(SecureClaim, claim_fixture(false), "claim");
+
+    let fixture = claim_fixture(false);
+    let mint = fixture.state::<Distributor>("distributor").mint;
+    let claimant = fixture.key("claimant");
+    assert_constraint_violation!(
+        SecureClaim,
+        fixture.clone(),
+        "claimant_tokens" => TestAccount::token_account(mint, Pubkey::new_unique(), 0),
+        ErrorCode::ConstraintTokenOwner
+    );
+    assert_constraint_violation!(
+        SecureClaim,
+        fixture,
+        "claimant_tokens" => TestAccount::token_account(Pubkey::new_unique(), claimant, 0),
+        ErrorCode::ConstraintTokenMint
+    );
+    assert_signer_violation!(SecureClaim, claim_fixture(false), "claimant");
+}
+
+#[test]
+fn vulnerable_claim_with_approve_delegates_the_whole_account_to_the_operator() {
+    let mut fixture = claim_fixture(true);
+    execute!(&mut fixture, instruction::VulnerableClaimWithApprove {}).unwrap();
+
+    let tokens = tokens(&fixture);
+    assert_eq!(tokens.amount, CLAIMANT_BALANCE + AMOUNT_PER_CLAIM);
+    // Still the claimant's account, so nothing looks wrong in the wallet
+    assert_eq!(tokens.owner, fixture.key("claimant"));
+    assert_eq!(tokens.delegate, COption::Some(fixture.key("operator")));
+    assert_eq!(tokens.delegated_amount, u64::MAX);
+}
+
+#[test]
+fn vulnerable_claim_with_owner_change_gives_the_account_to_the_operator() {
+    let mut fixture = claim_fixture(true);
+    execute!(&mut fixture, instruction::VulnerableClaimWithOwnerChange {}).unwrap();
+
+    let tokens = tokens(&fixture);
+    assert_eq!(tokens.amount, CLAIMANT_BALANCE + AMOUNT_PER_CLAIM);
+    assert_eq!(tokens.owner, fixture.key("operator"));
+}
+
+#[test]
+fn secure_claim_leaves_every_authority_untouched() {
+    let mut fixture = claim_fixture(false);
+    execute!(&mut fixture, instruction::SecureClaim {}).unwrap();
+
+    let tokens = tokens(&fixture);
+    assert_eq!(tokens.amount, CLAIMANT_BALANCE + AMOUNT_PER_CLAIM);
+    assert_eq!(tokens.owner, fixture.key("claimant"));
+    assert_eq!(tokens.delegate, COption::None);
+    assert_eq!(tokens.delegated_amount, 0);
+    assert_eq!(tokens.close_authority, COption::None);
+    assert_eq!(
+        fixture.state::<TokenAccount>("vault").amount,
+        VAULT_BALANCE - AMOUNT_PER_CLAIM
+    );
+}
+
+#[test]
+fn secure_claim_keeps_a_delegation_the_claimant_chose() {
+    let delegate = Pubkey::new_unique();
+    let mut fixture = claim_fixture(false);
+    let edited = fixture.account("claimant_tokens").clone().with_delegate(delegate, 42);
+    *fixture.account_mut("claimant_tokens") = edited;
+    execute!(&mut fixture, instruction::SecureClaim {}).unwrap();
+
+    let tokens = tokens(&fixture);
+    assert_eq!(tokens.delegate, COption::Some(delegate));
+    assert_eq!(tokens.delegated_amount, 42);
+}
+
+#[test]
+fn each_wallet_claims_once() {
+    let mut fixture = claim_fixture(false);
+    execute!(&mut fixture, instruction::SecureClaim {}).unwrap();
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureClaim {}),
+        Err(account_already_in_use())
+    );
+    assert_eq!(tokens(&fixture).amount, CLAIMANT_BALANCE + AMOUNT_PER_CLAIM);
+}
@@ -0,0 +1,9 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("The claim changed who controls the claimant's token account")]
+    AuthorityChanged,
+}
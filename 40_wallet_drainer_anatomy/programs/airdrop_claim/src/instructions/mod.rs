@@ -0,0 +1,97 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializeDistributor<'info> {
+    #[account(
+        init,
+        payer = operator,
+        space = Distributor::SPACE,
+        seeds = [b"distributor", mint.key().as_ref()],
+        bump
+    )]
+    pub distributor: Account<'info, Distributor>,
+
+    #[account(
+        init,
+        payer = operator,
+        seeds = [b"vault", distributor.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = distributor
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// `SecureClaim`, with the distributor's address derived on every claim
+/// rather than checked at its stored bump
+#[derive(Accounts)]
+pub struct RecomputedBumpClaim<'info> {
+    #[account(seeds = [b"distributor", distributor.mint.as_ref()], bump, has_one = vault)]
+    pub distributor: Account<'info, Distributor>,
+
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = claimant,
+        space = ClaimRecord::SPACE,
+        seeds = [b"claim", distributor.key().as_ref(), claimant.key().as_ref()],
+        bump
+    )]
+    pub claim: Account<'info, ClaimRecord>,
+
+    #[account(mut, token::mint = distributor.mint, token::authority = claimant)]
+    pub claimant_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// A claim whose record the handler creates at a bump the client sends
+#[derive(Accounts)]
+pub struct ClaimWithBump<'info> {
+    #[account(seeds = [b"distributor", distributor.mint.as_ref()], bump = distributor.bump, has_one = vault)]
+    pub distributor: Account<'info, Distributor>,
+
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Created by the handler, at the address its seeds and bump check names
+    #[account(mut)]
+    pub claim: UncheckedAccount<'info>,
+
+    #[account(mut, token::mint = distributor.mint, token::authority = claimant)]
+    pub claimant_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
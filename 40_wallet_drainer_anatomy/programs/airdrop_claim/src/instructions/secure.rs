@@ -0,0 +1,32 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureClaim<'info> {
+    #[account(seeds = [b"distributor", distributor.mint.as_ref()], bump = distributor.bump, has_one = vault)]
+    pub distributor: Account<'info, Distributor>,
+
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+
+    // SECURITY: One claim per claimant; `init` fails once the record exists
+    #[account(
+        init,
+        payer = claimant,
+        space = ClaimRecord::SPACE,
+        seeds = [b"claim", distributor.key().as_ref(), claimant.key().as_ref()],
+        bump
+    )]
+    pub claim: Account<'info, ClaimRecord>,
+
+    #[account(mut, token::mint = distributor.mint, token::authority = claimant)]
+    pub claimant_tokens: Account<'info, TokenAccount>,
+
+    /// Signs only to pay for the claim record
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
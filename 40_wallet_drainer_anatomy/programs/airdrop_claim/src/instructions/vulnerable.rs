@@ -0,0 +1,34 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableClaim<'info> {
+    #[account(seeds = [b"distributor", distributor.mint.as_ref()], bump = distributor.bump, has_one = vault, has_one = operator)]
+    pub distributor: Account<'info, Distributor>,
+
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = claimant,
+        space = ClaimRecord::SPACE,
+        seeds = [b"claim", distributor.key().as_ref(), claimant.key().as_ref()],
+        bump
+    )]
+    pub claim: Account<'info, ClaimRecord>,
+
+    #[account(mut, token::mint = distributor.mint, token::authority = claimant)]
+    pub claimant_tokens: Account<'info, TokenAccount>,
+
+    // VULNERABILITY: An account nothing in a claim needs, there to receive the claimant's authority
+    /// CHECK: The distributor's operator
+    pub operator: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,27 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{ClaimRecord, Distributor};
+
+#[test]
+fn distributor_layout_is_stable() {
+    let distributor = Distributor {
+        operator: Pubkey::new_from_array([1; 32]),
+        mint: Pubkey::new_from_array([2; 32]),
+        vault: Pubkey::new_from_array([3; 32]),
+        amount_per_claim: 0x4142434445464748,
+        bump: 81,
+    };
+    assert_golden!("Distributor", golden::anchor(&distributor));
+}
+
+#[test]
+fn claim_record_layout_is_stable() {
+    let claim_record = ClaimRecord {
+        claimant: Pubkey::new_from_array([6; 32]),
+        bump: 113,
+    };
+    assert_golden!("ClaimRecord", golden::anchor(&claim_record));
+}
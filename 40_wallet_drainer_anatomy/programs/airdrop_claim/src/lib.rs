@@ -0,0 +1,324 @@
+#[cfg(feature = "secure")]
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use secref_guards::account_creation::create_pda_account;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod bump_storage_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// A token airdrop anyone can claim once.
+///
+/// Claiming pays a fixed amount from the distributor's vault into the
+/// claimant's token account. The claimant signs the transaction because
+/// they pay for their claim record, and that signature is the dangerous
+/// part: for the whole instruction, the program may invoke the Token
+/// program as the claimant, on any account the claimant owns.
+#[feature_gated]
+#[program]
+pub mod airdrop_claim {
+    use super::*;
+    #[vulnerable]
+    use anchor_spl::token::spl_token::instruction::AuthorityType;
+    #[vulnerable]
+    use secref_guards::pda::assert_stored_bump;
+    #[secure]
+    use secref_guards::pda::assert_canonical_bump;
+
+    /// Create a distributor paying `amount_per_claim` of `mint` from a vault the operator funds
+    pub fn initialize_distributor(ctx: Context<InitializeDistributor>, amount_per_claim: u64) -> Result<()> {
+        let distributor = &mut ctx.accounts.distributor;
+        distributor.operator = ctx.accounts.operator.key();
+        distributor.mint = ctx.accounts.mint.key();
+        distributor.vault = ctx.accounts.vault.key();
+        distributor.amount_per_claim = amount_per_claim;
+        distributor.bump = ctx.bumps.distributor;
+
+        msg!(
+            "Distributor for {} paying {} per claim",
+            distributor.mint,
+            amount_per_claim
+        );
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Claim the airdrop, and approve the operator to spend everything
+    ///
+    /// Security Issue: After paying the airdrop, the instruction reuses the
+    /// claimant's signature to approve the operator as delegate of the
+    /// claimant's token account for `u64::MAX`. The claimant keeps the
+    /// account and sees the airdrop arrive; the operator can transfer the
+    /// whole balance, including anything received later, at any time.
+    #[vulnerable]
+    pub fn vulnerable_claim_with_approve(ctx: Context<VulnerableClaim>) -> Result<()> {
+        record_claim(&mut ctx.accounts.claim, ctx.accounts.claimant.key(), ctx.bumps.claim);
+        pay_claim(
+            &ctx.accounts.distributor,
+            &ctx.accounts.vault,
+            &ctx.accounts.claimant_tokens,
+            &ctx.accounts.token_program,
+        )?;
+
+        // VULNERABILITY: The claimant signed to claim; the same signature authorizes this
+        token::approve(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Approve {
+                    to: ctx.accounts.claimant_tokens.to_account_info(),
+                    delegate: ctx.accounts.operator.to_account_info(),
+                    authority: ctx.accounts.claimant.to_account_info(),
+                },
+            ),
+            u64::MAX,
+        )?;
+
+        msg!("Claimed {}", ctx.accounts.distributor.amount_per_claim);
+        Ok(())
+    }
+
+    /// VULNERABLE: Claim the airdrop, and hand the token account to the operator
+    ///
+    /// Security Issue: The instruction reuses the claimant's signature to
+    /// make the operator the owner of the claimant's token account. Classic
+    /// SPL Token allows this even for associated token accounts. The
+    /// account keeps its address, so the wallet may keep showing it, but
+    /// every token in it now belongs to the operator.
+    #[vulnerable]
+    pub fn vulnerable_claim_with_owner_change(ctx: Context<VulnerableClaim>) -> Result<()> {
+        record_claim(&mut ctx.accounts.claim, ctx.accounts.claimant.key(), ctx.bumps.claim);
+        pay_claim(
+            &ctx.accounts.distributor,
+            &ctx.accounts.vault,
+            &ctx.accounts.claimant_tokens,
+            &ctx.accounts.token_program,
+        )?;
+
+        // VULNERABILITY: The claimant signed to claim; the same signature gives the account away
+        token::set_authority(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::SetAuthority {
+                    current_authority: ctx.accounts.claimant.to_account_info(),
+                    account_or_mint: ctx.accounts.claimant_tokens.to_account_info(),
+                },
+            ),
+            AuthorityType::AccountOwner,
+            Some(ctx.accounts.operator.key()),
+        )?;
+
+        msg!("Claimed {}", ctx.accounts.distributor.amount_per_claim);
+        Ok(())
+    }
+
+    /// VULNERABLE: Claim the airdrop, with the claim record at the client's bump
+    ///
+    /// Security Issue: Finding the record's canonical bump costs a
+    /// `find_program_address` on every claim, so this instruction takes the
+    /// bump from the client and checks the record's address with a single
+    /// `create_program_address`. That check passes at any bump whose address
+    /// lands off the curve - about half of them - and each is a different
+    /// account. The record exists to stop a second claim; a claimant who
+    /// sends another bump gets another record and another payout, some 128
+    /// in all.
+    #[vulnerable]
+    pub fn vulnerable_claim_with_bump(ctx: Context<ClaimWithBump>, claim_bump: u8) -> Result<()> {
+        let distributor = ctx.accounts.distributor.key();
+        let claimant = ctx.accounts.claimant.key();
+        let seeds: &[&[u8]] = &[b"claim", distributor.as_ref(), claimant.as_ref()];
+
+        // VULNERABILITY: A stored-bump check, on a bump this program never stored
+        assert_stored_bump(ctx.accounts.claim.key, seeds, claim_bump, &crate::ID)?;
+        create_claim_record(ctx.accounts, seeds, claim_bump)?;
+        pay_claim(
+            &ctx.accounts.distributor,
+            &ctx.accounts.vault,
+            &ctx.accounts.claimant_tokens,
+            &ctx.accounts.token_program,
+        )?;
+
+        msg!("Claimed {}", ctx.accounts.distributor.amount_per_claim);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version, whose only token CPI is
+    // signed by its own vault and which checks the claimant's token
+    // account comes out with the authorities it went in with.
+
+    /// SECURE: Claim the airdrop and nothing else
+    ///
+    /// Security Fix: The context has no account the claimant's authority
+    /// could be granted to, and the claimant's signature is never passed to
+    /// a token CPI: the one transfer is signed by the distributor PDA, as
+    /// the vault's owner. The result depends only on stored state, so a
+    /// wallet's simulation shows exactly what execution does. After paying,
+    /// the claimant's owner, delegate and close authority must be exactly
+    /// as they were, so a later change cannot reintroduce a hidden
+    /// authority mutation without failing with `AuthorityChanged`.
+    #[secure]
+    pub fn secure_claim(ctx: Context<SecureClaim>) -> Result<()> {
+        let before = Authorities::of(&ctx.accounts.claimant_tokens);
+
+        record_claim(&mut ctx.accounts.claim, ctx.accounts.claimant.key(), ctx.bumps.claim);
+        pay_claim(
+            &ctx.accounts.distributor,
+            &ctx.accounts.vault,
+            &ctx.accounts.claimant_tokens,
+            &ctx.accounts.token_program,
+        )?;
+
+        // SECURITY: The claimant's account gained tokens and nothing else
+        ctx.accounts.claimant_tokens.reload()?;
+        require!(
+            Authorities::of(&ctx.accounts.claimant_tokens) == before,
+            ErrorCode::AuthorityChanged
+        );
+
+        msg!("Claimed {}", ctx.accounts.distributor.amount_per_claim);
+        Ok(())
+    }
+
+    /// SECURE: Claim the airdrop, with the claim record only at its canonical bump
+    ///
+    /// Security Fix: The client's bump must be the canonical one, which
+    /// `assert_canonical_bump` checks with the same `find_program_address`
+    /// the bump was sent to save. Each claimant has one claim record again,
+    /// and the instruction costs what `secure_claim` does - which is why
+    /// `secure_claim` takes no bump at all.
+    #[secure]
+    pub fn secure_claim_with_bump(ctx: Context<ClaimWithBump>, claim_bump: u8) -> Result<()> {
+        let distributor = ctx.accounts.distributor.key();
+        let claimant = ctx.accounts.claimant.key();
+        let seeds: &[&[u8]] = &[b"claim", distributor.as_ref(), claimant.as_ref()];
+
+        // SECURITY: One address per claimant, whatever bump the client sends
+        assert_canonical_bump(ctx.accounts.claim.key, seeds, claim_bump, &crate::ID)?;
+        create_claim_record(ctx.accounts, seeds, claim_bump)?;
+        pay_claim(
+            &ctx.accounts.distributor,
+            &ctx.accounts.vault,
+            &ctx.accounts.claimant_tokens,
+            &ctx.accounts.token_program,
+        )?;
+
+        msg!("Claimed {}", ctx.accounts.distributor.amount_per_claim);
+        Ok(())
+    }
+
+    // ========================================
+    // BUMP STORAGE COMPARISON
+    // ========================================
+    // `secure_claim` checks the distributor at the bump stored when it was
+    // created, with one `create_program_address`. This claim derives the
+    // distributor's address again on every call, so the compute units each
+    // costs can be compared.
+
+    /// Claim the airdrop, re-deriving the distributor's address with `find_program_address`
+    pub fn recomputed_bump_claim(ctx: Context<RecomputedBumpClaim>) -> Result<()> {
+        record_claim(&mut ctx.accounts.claim, ctx.accounts.claimant.key(), ctx.bumps.claim);
+        pay_claim(
+            &ctx.accounts.distributor,
+            &ctx.accounts.vault,
+            &ctx.accounts.claimant_tokens,
+            &ctx.accounts.token_program,
+        )?;
+
+        msg!("Claimed {}", ctx.accounts.distributor.amount_per_claim);
+        Ok(())
+    }
+}
+
+/// Everything that decides who can move or close a token account's tokens
+#[cfg(feature = "secure")]
+#[derive(PartialEq)]
+struct Authorities {
+    owner: Pubkey,
+    delegate: COption<Pubkey>,
+    delegated_amount: u64,
+    close_authority: COption<Pubkey>,
+}
+
+#[cfg(feature = "secure")]
+impl Authorities {
+    fn of(account: &TokenAccount) -> Self {
+        Self {
+            owner: account.owner,
+            delegate: account.delegate,
+            delegated_amount: account.delegated_amount,
+            close_authority: account.close_authority,
+        }
+    }
+}
+
+fn record_claim(claim: &mut ClaimRecord, claimant: Pubkey, bump: u8) {
+    claim.claimant = claimant;
+    claim.bump = bump;
+}
+
+/// Create the claim record at `seeds` and `bump`, which the handler has checked
+fn create_claim_record(accounts: &ClaimWithBump, seeds: &[&[u8]], bump: u8) -> Result<()> {
+    let bump_seed = [bump];
+    let mut signer_seeds = seeds.to_vec();
+    signer_seeds.push(&bump_seed);
+    create_pda_account(
+        &accounts.claimant.to_account_info(),
+        &accounts.claim.to_account_info(),
+        &accounts.system_program.to_account_info(),
+        &crate::ID,
+        ClaimRecord::SPACE,
+        &[&signer_seeds],
+    )?;
+
+    let mut data = accounts.claim.try_borrow_mut_data()?;
+    ClaimRecord {
+        claimant: accounts.claimant.key(),
+        bump,
+    }
+    .try_serialize(&mut &mut data[..])
+}
+
+/// Transfer one claim from the vault to `claimant_tokens`, signed by the distributor PDA
+fn pay_claim<'info>(
+    distributor: &Account<'info, Distributor>,
+    vault: &Account<'info, TokenAccount>,
+    claimant_tokens: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[b"distributor", distributor.mint.as_ref(), &[distributor.bump]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token::Transfer {
+                from: vault.to_account_info(),
+                to: claimant_tokens.to_account_info(),
+                authority: distributor.to_account_info(),
+            },
+            &[seeds],
+        ),
+        distributor.amount_per_claim,
+    )
+}
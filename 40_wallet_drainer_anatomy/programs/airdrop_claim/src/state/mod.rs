@@ -0,0 +1,34 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Distributor {
+    /// Wallet that created and funds the airdrop (32 bytes)
+    pub operator: Pubkey,
+    /// Token being distributed (32 bytes)
+    pub mint: Pubkey,
+    /// Token account claims are paid from, owned by this PDA (32 bytes)
+    pub vault: Pubkey,
+    /// Tokens each claimant receives (8 bytes)
+    pub amount_per_claim: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Distributor {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 1;
+}
+
+/// Proof a wallet has claimed; its existence blocks a second claim
+#[account]
+pub struct ClaimRecord {
+    /// Wallet that claimed (32 bytes)
+    pub claimant: Pubkey,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl ClaimRecord {
+    pub const SPACE: usize = 8 + 32 + 1;
+}
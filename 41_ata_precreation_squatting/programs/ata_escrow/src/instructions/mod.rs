@@ -0,0 +1,62 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use anchor_spl::associated_token::AssociatedToken;
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct CancelEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [b"escrow", maker.key().as_ref(), &escrow.seed.to_le_bytes()],
+        bump = escrow.bump,
+        has_one = maker,
+        has_one = vault,
+        close = maker
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = escrow.mint, token::authority = maker)]
+    pub maker_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct ReclaimVaultRent<'info> {
+    /// CHECK: The maker's escrow PDA for `seed`; the handler requires it to be closed, and it signs for its vault
+    #[account(seeds = [b"escrow", maker.key().as_ref(), &seed.to_le_bytes()], bump)]
+    pub escrow: UncheckedAccount<'info>,
+
+    #[account(mut, associated_token::mint = mint, associated_token::authority = escrow)]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut, token::mint = mint, token::authority = maker)]
+    pub maker_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
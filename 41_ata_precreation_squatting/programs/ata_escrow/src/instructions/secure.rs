@@ -0,0 +1,33 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct SecureOpenEscrow<'info> {
+    #[account(
+        init,
+        payer = maker,
+        space = Escrow::SPACE,
+        seeds = [b"escrow", maker.key().as_ref(), &seed.to_le_bytes()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    // SECURITY: Created idempotently in the handler, then checked like a new account
+    /// CHECK: `create_associated_token_account` checks the address, owner, mint and authorities
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut, token::mint = mint, token::authority = maker)]
+    pub maker_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,71 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct VulnerableOpenEscrow<'info> {
+    #[account(
+        init,
+        payer = maker,
+        space = Escrow::SPACE,
+        seeds = [b"escrow", maker.key().as_ref(), &seed.to_le_bytes()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    // VULNERABILITY: Fails for good once anyone has created this address
+    #[account(
+        init,
+        payer = maker,
+        associated_token::mint = mint,
+        associated_token::authority = escrow
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut, token::mint = mint, token::authority = maker)]
+    pub maker_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct VulnerableOpenEscrowIfNeeded<'info> {
+    #[account(
+        init,
+        payer = maker,
+        space = Escrow::SPACE,
+        seeds = [b"escrow", maker.key().as_ref(), &seed.to_le_bytes()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    // VULNERABILITY: An existing vault is accepted with whatever delegate, close authority or frozen state it has
+    #[account(
+        init_if_needed,
+        payer = maker,
+        associated_token::mint = mint,
+        associated_token::authority = escrow
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut, token::mint = mint, token::authority = maker)]
+    pub maker_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,19 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::Escrow;
+
+#[test]
+fn escrow_layout_is_stable() {
+    let escrow = Escrow {
+        maker: Pubkey::new_from_array([1; 32]),
+        mint: Pubkey::new_from_array([2; 32]),
+        vault: Pubkey::new_from_array([3; 32]),
+        seed: 0x4142434445464748,
+        amount: 0x5152535455565758,
+        bump: 97,
+    };
+    assert_golden!("Escrow", golden::anchor(&escrow));
+}
@@ -0,0 +1,263 @@
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// A token escrow: a maker deposits tokens under a numbered escrow and can
+/// take them back by cancelling it.
+///
+/// Each deposit sits in the associated token account (ATA) of the escrow
+/// PDA. The PDA's address comes from the maker and seed, and the vault's
+/// address from the PDA and mint, so anyone can compute the vault before
+/// the escrow exists - and the Associated Token program will create it for
+/// them, for any wallet.
+#[feature_gated]
+#[program]
+pub mod ata_escrow {
+    use super::*;
+    #[secure]
+    use secref_guards::rent::assert_closed;
+    #[secure]
+    use secref_guards::token_account::create_associated_token_account;
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Open an escrow whose vault must not exist yet
+    ///
+    /// Security Issue: `init` on the vault asks the Associated Token program
+    /// to `Create` it, which fails if the account already exists. Anyone can
+    /// create the vault first, at the cost of its rent, and this escrow can
+    /// then never be opened; cancelling an escrow leaves its vault behind,
+    /// so even the maker cannot reuse a seed.
+    #[vulnerable]
+    pub fn vulnerable_open_escrow(ctx: Context<VulnerableOpenEscrow>, seed: u64, amount: u64) -> Result<()> {
+        deposit(
+            &ctx.accounts.maker_tokens,
+            &ctx.accounts.vault,
+            &ctx.accounts.maker,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
+        record_escrow(
+            &mut ctx.accounts.escrow,
+            &ctx.accounts.maker,
+            &ctx.accounts.mint,
+            ctx.accounts.vault.key(),
+            seed,
+            amount,
+            ctx.bumps.escrow,
+        );
+        Ok(())
+    }
+
+    /// VULNERABLE: Open an escrow, taking over its vault if it exists
+    ///
+    /// Security Issue: `init_if_needed` skips creation for an existing
+    /// vault and checks only its address, mint and owner. The account's
+    /// other state comes along unchecked: a frozen vault traps the deposit,
+    /// and a delegate or close authority - anything approved on the
+    /// address before this escrow was opened - keeps its power over the
+    /// deposit made into it.
+    #[vulnerable]
+    pub fn vulnerable_open_escrow_if_needed(
+        ctx: Context<VulnerableOpenEscrowIfNeeded>,
+        seed: u64,
+        amount: u64,
+    ) -> Result<()> {
+        deposit(
+            &ctx.accounts.maker_tokens,
+            &ctx.accounts.vault,
+            &ctx.accounts.maker,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
+        record_escrow(
+            &mut ctx.accounts.escrow,
+            &ctx.accounts.maker,
+            &ctx.accounts.mint,
+            ctx.accounts.vault.key(),
+            seed,
+            amount,
+            ctx.bumps.escrow,
+        );
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version, which creates the vault
+    // idempotently and then checks it as if it had just been created.
+
+    /// SECURE: Open an escrow whether or not its vault already exists
+    ///
+    /// Security Fix: The vault is created with `CreateIdempotent`, which
+    /// succeeds if someone created it first, so squatting blocks nothing.
+    /// The resulting account must then be the escrow's canonical ATA for the
+    /// mint, not frozen, with no delegate and no close authority
+    /// (`secref_guards::token_account`). Tokens someone sent to the vault
+    /// beforehand are accepted but not counted: the escrow records only the
+    /// amount the maker deposited.
+    #[secure]
+    pub fn secure_open_escrow(ctx: Context<SecureOpenEscrow>, seed: u64, amount: u64) -> Result<()> {
+        // SECURITY: Works over a squatted vault, and rejects one carrying authority the escrow never granted
+        create_associated_token_account(
+            &ctx.accounts.maker.to_account_info(),
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.escrow.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.associated_token_program.to_account_info(),
+        )?;
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.maker_tokens.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.maker.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        record_escrow(
+            &mut ctx.accounts.escrow,
+            &ctx.accounts.maker,
+            &ctx.accounts.mint,
+            ctx.accounts.vault.key(),
+            seed,
+            amount,
+            ctx.bumps.escrow,
+        );
+        Ok(())
+    }
+
+    /// Return the deposit to the maker and close the escrow
+    ///
+    /// The vault stays open, so a later escrow with the same seed finds it
+    /// already created, until the maker reclaims its rent with
+    /// `reclaim_vault_rent`.
+    #[secure]
+    pub fn cancel_escrow(ctx: Context<CancelEscrow>) -> Result<()> {
+        let escrow = &ctx.accounts.escrow;
+        let seed = escrow.seed.to_le_bytes();
+        let seeds: &[&[u8]] = &[b"escrow", escrow.maker.as_ref(), &seed, &[escrow.bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.maker_tokens.to_account_info(),
+                    authority: ctx.accounts.escrow.to_account_info(),
+                },
+                &[seeds],
+            ),
+            escrow.amount,
+        )?;
+
+        msg!("Escrow {} cancelled, {} returned", escrow.seed, escrow.amount);
+        Ok(())
+    }
+
+    /// Close the vault a cancelled escrow left behind, returning its rent to the maker
+    ///
+    /// The vault's rent is stranded once its escrow is gone: only the
+    /// escrow PDA can close it. The escrow must already be closed
+    /// (`secref_guards::rent::assert_closed`), so a live escrow's vault is
+    /// never touched. Tokens sent to the vault after the escrow was
+    /// cancelled would block the close, and no one else can withdraw them,
+    /// so they go to the maker first.
+    #[secure]
+    pub fn reclaim_vault_rent(ctx: Context<ReclaimVaultRent>, seed: u64) -> Result<()> {
+        assert_closed(&ctx.accounts.escrow)?;
+
+        let maker = ctx.accounts.maker.key();
+        let seed_bytes = seed.to_le_bytes();
+        let seeds: &[&[u8]] = &[b"escrow", maker.as_ref(), &seed_bytes, &[ctx.bumps.escrow]];
+        let stray = ctx.accounts.vault.amount;
+        if stray > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Transfer {
+                        from: ctx.accounts.vault.to_account_info(),
+                        to: ctx.accounts.maker_tokens.to_account_info(),
+                        authority: ctx.accounts.escrow.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                stray,
+            )?;
+        }
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::CloseAccount {
+                account: ctx.accounts.vault.to_account_info(),
+                destination: ctx.accounts.maker.to_account_info(),
+                authority: ctx.accounts.escrow.to_account_info(),
+            },
+            &[seeds],
+        ))?;
+
+        msg!("Vault of escrow {} closed, {} stray tokens returned", seed, stray);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "vulnerable")]
+fn deposit<'info>(
+    maker_tokens: &Account<'info, TokenAccount>,
+    vault: &Account<'info, TokenAccount>,
+    maker: &Signer<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    token::transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            token::Transfer {
+                from: maker_tokens.to_account_info(),
+                to: vault.to_account_info(),
+                authority: maker.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
+
+fn record_escrow(
+    escrow: &mut Escrow,
+    maker: &Signer,
+    mint: &Account<Mint>,
+    vault: Pubkey,
+    seed: u64,
+    amount: u64,
+    bump: u8,
+) {
+    escrow.maker = maker.key();
+    escrow.mint = mint.key();
+    escrow.vault = vault;
+    escrow.seed = seed;
+    escrow.amount = amount;
+    escrow.bump = bump;
+    msg!("Escrow {} holds {} of {}", seed, amount, escrow.mint);
+}
@@ -0,0 +1,23 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Escrow {
+    /// Wallet that deposited and may cancel (32 bytes)
+    pub maker: Pubkey,
+    /// Token held in escrow (32 bytes)
+    pub mint: Pubkey,
+    /// The escrow PDA's associated token account for `mint` (32 bytes)
+    pub vault: Pubkey,
+    /// Maker-chosen number distinguishing the maker's escrows (8 bytes)
+    pub seed: u64,
+    /// Tokens the maker deposited; anything else in the vault is not theirs to withdraw (8 bytes)
+    pub amount: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Escrow {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1;
+}
@@ -0,0 +1,345 @@
+//! Who may do what to a position: the role checks on each context, and the
+//! seizure lifecycle from freeze through the timelock to execution.
+
+use anchor_lang::prelude::*;
+use secref_guards::GuardError;
+use secref_testkit::runtime::warp_to;
+use secref_testkit::{
+    assert_has_one_violation, assert_seeds_violation, assert_signer_violation, execute, Fixture, TestAccount,
+};
+
+use crate::{
+    instruction, AssignRole, ErrorCode as VaultError, ExecuteSeizure, Position, ProposeSeizure, Role, SetFrozen, Vault,
+    VulnerableSeize, Withdraw,
+};
+
+const BALANCE: u64 = 1_000;
+const AMOUNT: u64 = 400;
+const DELAY: i64 = 86_400;
+const START: i64 = 1_700_000_000;
+
+/// A vault holding one owner's BALANCE, and a signer for every role
+struct World {
+    vault: TestAccount,
+    position: TestAccount,
+    treasury: TestAccount,
+    admin: TestAccount,
+    owner: TestAccount,
+    freezer: TestAccount,
+    seizer: TestAccount,
+}
+
+impl World {
+    fn new(frozen: bool) -> Self {
+        let (admin, owner, freezer, seizer) = (
+            TestAccount::signer(),
+            TestAccount::signer(),
+            TestAccount::signer(),
+            TestAccount::signer(),
+        );
+        let treasury = TestAccount::system(0);
+        let (vault, vault_bump) = Pubkey::find_program_address(&[b"vault", admin.key.as_ref()], &crate::ID);
+        let (position, position_bump) =
+            Pubkey::find_program_address(&[b"position", vault.as_ref(), owner.key.as_ref()], &crate::ID);
+
+        let vault_account = TestAccount::anchor(&Vault {
+            admin: admin.key,
+            freezer: freezer.key,
+            seizer: seizer.key,
+            treasury: treasury.key,
+            seize_delay: DELAY,
+            bump: vault_bump,
+        })
+        .at(vault);
+        let lamports = vault_account.lamports + BALANCE;
+        // Room for the seizure a test may propose
+        let mut position_account = TestAccount::anchor(&Position {
+            vault,
+            owner: owner.key,
+            balance: BALANCE,
+            frozen,
+            seizure: None,
+            bump: position_bump,
+        })
+        .at(position);
+        position_account.data.resize(Position::SPACE, 0);
+        Self {
+            vault: vault_account.with_lamports(lamports),
+            position: position_account,
+            treasury,
+            admin,
+            owner,
+            freezer,
+            seizer,
+        }
+    }
+
+    fn assign_role(&self) -> Fixture {
+        Fixture::new()
+            .with("vault", self.vault.clone())
+            .with("admin", self.admin.clone())
+    }
+
+    fn withdraw(&self) -> Fixture {
+        Fixture::new()
+            .with("vault", self.vault.clone())
+            .with("position", self.position.clone())
+            .with("owner", self.owner.clone())
+    }
+
+    fn set_frozen(&self) -> Fixture {
+        Fixture::new()
+            .with("vault", self.vault.clone())
+            .with("position", self.position.clone())
+            .with("freezer", self.freezer.clone())
+    }
+
+    fn vulnerable_seize(&self) -> Fixture {
+        Fixture::new()
+            .with("vault", self.vault.clone())
+            .with("position", self.position.clone())
+            .with("treasury", self.treasury.clone())
+            .with("freezer", self.freezer.clone())
+    }
+
+    fn propose(&self) -> Fixture {
+        Fixture::new()
+            .with("vault", self.vault.clone())
+            .with("position", self.position.clone())
+            .with("seizer", self.seizer.clone())
+    }
+
+    fn execute(&self) -> Fixture {
+        Fixture::new()
+            .with("vault", self.vault.clone())
+            .with("position", self.position.clone())
+            .with("treasury", self.treasury.clone())
+            .with("seizer", self.seizer.clone())
+    }
+
+    /// Carry what an instruction wrote into the next one's fixture
+    fn keep(&mut self, fixture: &Fixture) {
+        self.vault = fixture.account("vault").clone();
+        self.position = fixture.account("position").clone();
+    }
+
+    fn position(&self) -> Position {
+        self.position.state()
+    }
+}
+
+fn initialize_fixture() -> Fixture {
+    let admin = TestAccount::signer();
+    let (vault, _) = Pubkey::find_program_address(&[b"vault", admin.key.as_ref()], &crate::ID);
+    Fixture::new()
+        .with("vault", TestAccount::uninitialized(Vault::SPACE).at(vault))
+        .with("treasury", TestAccount::system(0))
+        .with("admin", admin)
+        .with("system_program", TestAccount::system_program())
+}
+
+fn initialize(freezer: Pubkey, seizer: Pubkey) -> instruction::InitializeVault {
+    instruction::InitializeVault {
+        freezer,
+        seizer,
+        seize_delay: DELAY,
+    }
+}
+
+/// World `frozen`, with a seizure of AMOUNT proposed at START
+fn proposed() -> World {
+    warp_to(START);
+    let mut world = World::new(true);
+    let mut fixture = world.propose();
+    execute!(&mut fixture, instruction::ProposeSeizure { amount: AMOUNT }).unwrap();
+    world.keep(&fixture);
+    world
+}
+
+#[test]
+fn initialize_vault_requires_three_different_keys() {
+    let mut fixture = initialize_fixture();
+    let admin = fixture.key("admin");
+    let (freezer, seizer) = (Pubkey::new_unique(), Pubkey::new_unique());
+    for (freezer, seizer) in [(admin, seizer), (freezer, admin), (freezer, freezer)] {
+        assert_eq!(
+            execute!(&mut fixture.clone(), initialize(freezer, seizer)),
+            Err(Error::from(VaultError::RolesNotSeparated).into())
+        );
+    }
+
+    execute!(&mut fixture, initialize(freezer, seizer)).unwrap();
+    let vault = fixture.state::<Vault>("vault");
+    assert_eq!((vault.freezer, vault.seizer), (freezer, seizer));
+    assert_eq!(vault.treasury, fixture.key("treasury"));
+}
+
+#[test]
+fn assign_role_is_the_admins() {
+    let world = World::new(false);
+    assert_seeds_violation!(AssignRole, world.assign_role(), "vault");
+    assert_seeds_violation!(AssignRole, world.assign_role(), "admin");
+    assert_signer_violation!(AssignRole, world.assign_role(), "admin");
+}
+
+#[test]
+fn assign_role_keeps_the_roles_separated() {
+    let world = World::new(false);
+    let assign = |role, holder| execute!(&mut world.assign_role(), instruction::AssignRole { role, holder });
+    let separation = Err(Error::from(VaultError::RolesNotSeparated).into());
+    assert_eq!(assign(Role::Seizer, world.freezer.key), separation);
+    assert_eq!(assign(Role::Freezer, world.seizer.key), separation);
+    assert_eq!(assign(Role::Freezer, world.admin.key), separation);
+
+    let replacement = Pubkey::new_unique();
+    let mut fixture = world.assign_role();
+    execute!(
+        &mut fixture,
+        instruction::AssignRole {
+            role: Role::Seizer,
+            holder: replacement
+        }
+    )
+    .unwrap();
+    assert_eq!(fixture.state::<Vault>("vault").seizer, replacement);
+}
+
+#[test]
+fn withdraw_is_the_owners_until_the_position_is_frozen() {
+    let world = World::new(false);
+    assert_seeds_violation!(Withdraw, world.withdraw(), "position");
+    assert_signer_violation!(Withdraw, world.withdraw(), "owner");
+
+    let mut fixture = world.withdraw();
+    execute!(&mut fixture, instruction::Withdraw { amount: AMOUNT }).unwrap();
+    assert_eq!(fixture.state::<Position>("position").balance, BALANCE - AMOUNT);
+    assert_eq!(fixture.account("owner").lamports, world.owner.lamports + AMOUNT);
+
+    assert_eq!(
+        execute!(
+            &mut World::new(true).withdraw(),
+            instruction::Withdraw { amount: AMOUNT }
+        ),
+        Err(Error::from(VaultError::PositionFrozen).into())
+    );
+}
+
+#[test]
+fn freezing_is_the_freezers() {
+    let world = World::new(false);
+    assert_seeds_violation!(SetFrozen, world.set_frozen(), "position");
+    assert_has_one_violation!(SetFrozen, world.set_frozen(), "freezer");
+    assert_signer_violation!(SetFrozen, world.set_frozen(), "freezer");
+
+    let mut fixture = world.set_frozen();
+    execute!(&mut fixture, instruction::FreezePosition {}).unwrap();
+    assert!(fixture.state::<Position>("position").frozen);
+    execute!(&mut fixture, instruction::ThawPosition {}).unwrap();
+    assert!(!fixture.state::<Position>("position").frozen);
+}
+
+#[test]
+fn vulnerable_seize_lets_the_freezer_empty_any_position_at_once() {
+    assert_has_one_violation!(VulnerableSeize, World::new(false).vulnerable_seize(), "treasury");
+
+    // Not frozen, no proposal, no delay: the freezer's key alone takes it all
+    let world = World::new(false);
+    let mut fixture = world.vulnerable_seize();
+    execute!(&mut fixture, instruction::VulnerableSeize { amount: BALANCE }).unwrap();
+    assert_eq!(fixture.state::<Position>("position").balance, 0);
+    assert_eq!(fixture.account("treasury").lamports, BALANCE);
+}
+
+#[test]
+fn propose_seizure_is_the_seizers_and_needs_a_frozen_position() {
+    let world = World::new(true);
+    assert_seeds_violation!(ProposeSeizure, world.propose(), "position");
+    assert_has_one_violation!(ProposeSeizure, world.propose(), "seizer");
+    assert_signer_violation!(ProposeSeizure, world.propose(), "seizer");
+
+    // The freezer cannot stand in for the seizer
+    let mut fixture = world.propose();
+    *fixture.account_mut("seizer") = world.freezer.clone();
+    assert_eq!(
+        execute!(&mut fixture, instruction::ProposeSeizure { amount: AMOUNT }),
+        Err(Error::from(ErrorCode::ConstraintHasOne).into())
+    );
+
+    assert_eq!(
+        execute!(
+            &mut World::new(false).propose(),
+            instruction::ProposeSeizure { amount: AMOUNT }
+        ),
+        Err(Error::from(VaultError::PositionNotFrozen).into())
+    );
+    assert_eq!(
+        execute!(
+            &mut world.propose(),
+            instruction::ProposeSeizure { amount: BALANCE + 1 }
+        ),
+        Err(Error::from(VaultError::InsufficientBalance).into())
+    );
+}
+
+#[test]
+fn execute_seizure_is_the_seizers_and_pays_only_the_treasury() {
+    let world = proposed();
+    assert_seeds_violation!(ExecuteSeizure, world.execute(), "position");
+    assert_has_one_violation!(ExecuteSeizure, world.execute(), "seizer");
+    assert_has_one_violation!(ExecuteSeizure, world.execute(), "treasury");
+    assert_signer_violation!(ExecuteSeizure, world.execute(), "seizer");
+}
+
+#[test]
+fn execute_seizure_waits_out_the_delay() {
+    let mut world = proposed();
+    warp_to(START + DELAY - 1);
+    assert_eq!(
+        execute!(&mut world.execute(), instruction::ExecuteSeizure { amount: AMOUNT }),
+        Err(Error::from(GuardError::IntentNotReady).into())
+    );
+
+    warp_to(START + DELAY);
+    let mut fixture = world.execute();
+    execute!(&mut fixture, instruction::ExecuteSeizure { amount: AMOUNT }).unwrap();
+    assert_eq!(fixture.account("treasury").lamports, AMOUNT);
+    world.keep(&fixture);
+    let position = world.position();
+    assert_eq!(position.balance, BALANCE - AMOUNT);
+    assert!(position.seizure.is_none());
+
+    // The intent is spent; a second seizure needs a second proposal
+    assert_eq!(
+        execute!(&mut world.execute(), instruction::ExecuteSeizure { amount: AMOUNT }),
+        Err(Error::from(VaultError::NoSeizureProposed).into())
+    );
+}
+
+#[test]
+fn execute_seizure_takes_only_the_announced_amount() {
+    let world = proposed();
+    warp_to(START + DELAY);
+    assert_eq!(
+        execute!(&mut world.execute(), instruction::ExecuteSeizure { amount: BALANCE }),
+        Err(Error::from(GuardError::IntentParamsMismatch).into())
+    );
+}
+
+#[test]
+fn thawing_cancels_a_proposed_seizure() {
+    let mut world = proposed();
+    let mut fixture = world.set_frozen();
+    execute!(&mut fixture, instruction::ThawPosition {}).unwrap();
+    world.keep(&fixture);
+    assert!(world.position().seizure.is_none());
+
+    // Freezing again does not bring the old proposal back
+    let mut fixture = world.set_frozen();
+    execute!(&mut fixture, instruction::FreezePosition {}).unwrap();
+    world.keep(&fixture);
+    warp_to(START + DELAY);
+    assert_eq!(
+        execute!(&mut world.execute(), instruction::ExecuteSeizure { amount: AMOUNT }),
+        Err(Error::from(VaultError::NoSeizureProposed).into())
+    );
+}
@@ -0,0 +1,21 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("The admin, freezer and seizer must be different keys")]
+    RolesNotSeparated,
+    #[msg("Seizure delay must be non-negative")]
+    InvalidDelay,
+    #[msg("Position is frozen")]
+    PositionFrozen,
+    #[msg("Only a frozen position can be seized")]
+    PositionNotFrozen,
+    #[msg("No seizure has been proposed for this position")]
+    NoSeizureProposed,
+    #[msg("Position balance is too low")]
+    InsufficientBalance,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
@@ -0,0 +1,43 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct ProposeSeizure<'info> {
+    // SECURITY: Only the seizer, a key distinct from the admin and freezer
+    #[account(seeds = [b"vault", vault.admin.as_ref()], bump = vault.bump, has_one = seizer)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"position", vault.key().as_ref(), position.owner.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+
+    pub seizer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteSeizure<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.admin.as_ref()],
+        bump = vault.bump,
+        has_one = seizer,
+        has_one = treasury
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"position", vault.key().as_ref(), position.owner.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+
+    pub seizer: Signer<'info>,
+}
@@ -0,0 +1,28 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableSeize<'info> {
+    // VULNERABILITY: The freezer, not a separate seizer, authorizes taking funds
+    #[account(
+        mut,
+        seeds = [b"vault", vault.admin.as_ref()],
+        bump = vault.bump,
+        has_one = freezer,
+        has_one = treasury
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"position", vault.key().as_ref(), position.owner.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+
+    pub freezer: Signer<'info>,
+}
@@ -0,0 +1,38 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_guards::intent::Intent;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Position, Vault};
+
+#[test]
+fn vault_layout_is_stable() {
+    let vault = Vault {
+        admin: Pubkey::new_from_array([1; 32]),
+        freezer: Pubkey::new_from_array([2; 32]),
+        seizer: Pubkey::new_from_array([3; 32]),
+        treasury: Pubkey::new_from_array([4; 32]),
+        seize_delay: 0x5152535455565758,
+        bump: 97,
+    };
+    assert_golden!("Vault", golden::anchor(&vault));
+}
+
+#[test]
+fn position_layout_is_stable() {
+    let position = Position {
+        vault: Pubkey::new_from_array([7; 32]),
+        owner: Pubkey::new_from_array([8; 32]),
+        balance: 0x9192939495969798,
+        frozen: true,
+        seizure: Some(Intent {
+            proposer: Pubkey::new_from_array([40; 32]),
+            params_hash: [41; 32],
+            prepared_at: 0x6162636465666768,
+            executable_at: 0x7172737475767778,
+        }),
+        bump: 161,
+    };
+    assert_golden!("Position", golden::anchor(&position));
+}
@@ -0,0 +1,258 @@
+use anchor_lang::system_program::{self, Transfer};
+#[cfg(feature = "secure")]
+use secref_guards::intent::hash_params;
+use secref_guards::intent::Intent;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// A lamport vault with per-user positions under compliance controls.
+///
+/// Users deposit into and withdraw from their own position. The vault's
+/// admin assigns two roles: a freezer, who can freeze a position (a
+/// sanctions hold: no withdrawals) and thaw it again, and a seizer, who can
+/// move a frozen position's funds to the vault's treasury.
+#[feature_gated]
+#[program]
+pub mod compliance_vault {
+    use super::*;
+
+    /// Create a vault whose seizures wait `seize_delay` seconds
+    ///
+    /// The admin, freezer and seizer must be three different keys.
+    pub fn initialize_vault(
+        ctx: Context<InitializeVault>,
+        freezer: Pubkey,
+        seizer: Pubkey,
+        seize_delay: i64,
+    ) -> Result<()> {
+        require!(seize_delay >= 0, ErrorCode::InvalidDelay);
+
+        let vault = &mut ctx.accounts.vault;
+        vault.admin = ctx.accounts.admin.key();
+        vault.freezer = freezer;
+        vault.seizer = seizer;
+        vault.treasury = ctx.accounts.treasury.key();
+        vault.seize_delay = seize_delay;
+        vault.bump = ctx.bumps.vault;
+        vault.assert_roles_separated()?;
+
+        msg!("Vault initialized with a {}s seizure delay", seize_delay);
+        Ok(())
+    }
+
+    /// Hand `role` to `holder`; admin only
+    ///
+    /// The role assignment is checked the same way as at initialization, so
+    /// the admin can never end up holding a role, or one key both.
+    pub fn assign_role(ctx: Context<AssignRole>, role: Role, holder: Pubkey) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        match role {
+            Role::Freezer => vault.freezer = holder,
+            Role::Seizer => vault.seizer = holder,
+        }
+        vault.assert_roles_separated()?;
+
+        msg!("{:?} is now {}", role, holder);
+        Ok(())
+    }
+
+    /// Open the signer's position in the vault
+    pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.vault = ctx.accounts.vault.key();
+        position.owner = ctx.accounts.owner.key();
+        position.bump = ctx.bumps.position;
+        Ok(())
+    }
+
+    /// Deposit `amount` lamports into the signer's position
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        let position = &mut ctx.accounts.position;
+        position.balance = position
+            .balance
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    /// Withdraw `amount` lamports from the signer's position, unless it is frozen
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        require!(!ctx.accounts.position.frozen, ErrorCode::PositionFrozen);
+        take(
+            &mut ctx.accounts.position,
+            &ctx.accounts.vault,
+            &ctx.accounts.owner.to_account_info(),
+            amount,
+        )?;
+
+        msg!("Withdrew {} lamports", amount);
+        Ok(())
+    }
+
+    /// Freeze a position: its owner can no longer withdraw; freezer only
+    pub fn freeze_position(ctx: Context<SetFrozen>) -> Result<()> {
+        ctx.accounts.position.frozen = true;
+        msg!("Position of {} frozen", ctx.accounts.position.owner);
+        Ok(())
+    }
+
+    /// Thaw a position, cancelling any seizure proposed against it; freezer only
+    pub fn thaw_position(ctx: Context<SetFrozen>) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.frozen = false;
+        position.seizure = None;
+        msg!("Position of {} thawed", position.owner);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Seize `amount` from a position, as the freezer, at once
+    ///
+    /// Security Issue: The key trusted to pause a position can also take
+    /// it, so a hold meant to be reversible is as dangerous as a withdrawal
+    /// key. Nothing requires the position to be frozen or waits for anyone
+    /// to notice: one compromised or rogue freezer key empties every
+    /// position in the vault, one transaction each, before a thaw or a key
+    /// rotation can happen.
+    #[vulnerable]
+    pub fn vulnerable_seize(ctx: Context<VulnerableSeize>, amount: u64) -> Result<()> {
+        // VULNERABILITY: No frozen check, no delay, and the freezer signs
+        take(
+            &mut ctx.accounts.position,
+            &ctx.accounts.vault,
+            &ctx.accounts.treasury.to_account_info(),
+            amount,
+        )?;
+
+        msg!("Seized {} lamports from {}", amount, ctx.accounts.position.owner);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version: a separate seizer role,
+    // only for frozen positions, through a timelocked two-phase seizure.
+
+    /// SECURE: Phase 1, announce a seizure of `amount` from a frozen position; seizer only
+    ///
+    /// Security Fix: Seizing needs the seizer, a key the admin and freezer
+    /// cannot hold, and a position the freezer has already frozen, so no
+    /// single key can both hold and take funds. The intent records the
+    /// amount and treasury and becomes executable only after the vault's
+    /// delay, which gives the owner time to contest and the freezer time
+    /// to thaw.
+    #[secure]
+    pub fn propose_seizure(ctx: Context<ProposeSeizure>, amount: u64) -> Result<()> {
+        let vault = &ctx.accounts.vault;
+        let position = &mut ctx.accounts.position;
+        // SECURITY: Only a position the freezer has frozen can be seized
+        require!(position.frozen, ErrorCode::PositionNotFrozen);
+        require!(amount <= position.balance, ErrorCode::InsufficientBalance);
+
+        let now = Clock::get()?.unix_timestamp;
+        let intent = Intent::prepare(
+            vault.seizer,
+            seizure_params(amount, &vault.treasury),
+            now,
+            vault.seize_delay,
+        )?;
+        msg!(
+            "Proposed seizure of {} lamports from {}, executable at {}",
+            amount,
+            position.owner,
+            intent.executable_at
+        );
+        position.seizure = Some(intent);
+        Ok(())
+    }
+
+    /// SECURE: Phase 2, carry out the announced seizure; seizer only
+    ///
+    /// Security Fix: The position must still be frozen - thawing it
+    /// cancels the seizure - and `amount` must be the announced one, after
+    /// the delay has passed.
+    #[secure]
+    pub fn execute_seizure(ctx: Context<ExecuteSeizure>, amount: u64) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        require!(position.frozen, ErrorCode::PositionNotFrozen);
+        let intent = position.seizure.take().ok_or(ErrorCode::NoSeizureProposed)?;
+
+        // SECURITY: Delay and parameters are both checked against the intent
+        let now = Clock::get()?.unix_timestamp;
+        intent.assert_executable(&seizure_params(amount, &ctx.accounts.vault.treasury), now)?;
+
+        take(
+            position,
+            &ctx.accounts.vault,
+            &ctx.accounts.treasury.to_account_info(),
+            amount,
+        )?;
+
+        msg!("Seized {} lamports from {}", amount, position.owner);
+        Ok(())
+    }
+}
+
+/// Parameters of a seizure, as recorded in its intent
+#[cfg(feature = "secure")]
+fn seizure_params(amount: u64, treasury: &Pubkey) -> [u8; 32] {
+    hash_params(&[&amount.to_le_bytes(), treasury.as_ref()])
+}
+
+/// Debit `amount` from `position` and pay it out of the vault to `destination`
+fn take<'info>(
+    position: &mut Position,
+    vault: &Account<'info, Vault>,
+    destination: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    position.balance = position
+        .balance
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientBalance)?;
+
+    let vault = vault.to_account_info();
+    **vault.try_borrow_mut_lamports()? = vault
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientBalance)?;
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
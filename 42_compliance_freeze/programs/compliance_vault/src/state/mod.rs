@@ -0,0 +1,59 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+/// A compliance role the admin can assign
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    Freezer,
+    Seizer,
+}
+
+#[account]
+pub struct Vault {
+    /// Assigns roles; holds neither (32 bytes)
+    pub admin: Pubkey,
+    /// Freezes and thaws positions (32 bytes)
+    pub freezer: Pubkey,
+    /// Proposes and executes seizures of frozen positions (32 bytes)
+    pub seizer: Pubkey,
+    /// Receives seized funds (32 bytes)
+    pub treasury: Pubkey,
+    /// Seconds between proposing and executing a seizure (8 bytes)
+    pub seize_delay: i64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Vault {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 32 + 8 + 1;
+
+    /// Require the admin, freezer and seizer to be three different keys
+    pub(crate) fn assert_roles_separated(&self) -> Result<()> {
+        require!(
+            self.admin != self.freezer && self.admin != self.seizer && self.freezer != self.seizer,
+            ErrorCode::RolesNotSeparated
+        );
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Position {
+    /// Vault the position belongs to (32 bytes)
+    pub vault: Pubkey,
+    /// Depositor who may withdraw while the position is not frozen (32 bytes)
+    pub owner: Pubkey,
+    /// Lamports deposited and not yet withdrawn or seized (8 bytes)
+    pub balance: u64,
+    /// Whether the freezer has put the position on hold (1 byte)
+    pub frozen: bool,
+    /// Seizure proposed by the seizer, cleared by a thaw (1 + 80 bytes)
+    pub seizure: Option<Intent>,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Position {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1 + (1 + Intent::SPACE) + 1;
+}
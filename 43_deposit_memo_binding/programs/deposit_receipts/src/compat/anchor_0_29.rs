@@ -0,0 +1,12 @@
+//! Anchor 0.29 spellings of the code that differs between releases.
+
+use anchor_lang::prelude::*;
+
+/// Program id of the SPL Memo program (v2)
+///
+/// 0.29 has no `pubkey!` macro, so the base58 id
+/// `MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr` is spelled out as bytes.
+pub const MEMO_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    5, 74, 83, 90, 153, 41, 33, 6, 77, 36, 232, 113, 96, 218, 56, 124, 124, 53, 181, 221, 188, 146, 187, 129, 228, 31,
+    168, 64, 65, 5, 68, 141,
+]);
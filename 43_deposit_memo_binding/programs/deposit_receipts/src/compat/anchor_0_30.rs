@@ -0,0 +1,6 @@
+//! Anchor 0.30 spellings of the code that differs between releases.
+
+use anchor_lang::prelude::*;
+
+/// Program id of the SPL Memo program (v2)
+pub const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
@@ -0,0 +1,117 @@
+//! The deposit contexts' constraints, and what each deposit leaves behind
+//! for the indexer.
+
+use anchor_lang::prelude::*;
+use secref_testkit::runtime::{account_already_in_use, warp_to};
+use secref_testkit::{assert_seeds_violation, assert_signer_violation, execute, Fixture, TestAccount};
+
+use crate::indexer::{receipt_address, verify_receipt, Credit};
+use crate::{instruction, ErrorCode as DepositError, Receipt, SecureDeposit, Vault, VulnerableDeposit};
+
+const AMOUNT: u64 = 1_000_000;
+const NOW: i64 = 1_700_000_000;
+
+fn vault() -> TestAccount {
+    let authority = Pubkey::new_unique();
+    let (address, bump) = Pubkey::find_program_address(&[b"vault", authority.as_ref()], &crate::ID);
+    TestAccount::anchor(&Vault { authority, bump }).at(address)
+}
+
+fn vulnerable_fixture() -> Fixture {
+    Fixture::new()
+        .with("vault", vault())
+        .with("depositor", TestAccount::signer())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn secure_deposit_fixture(reference: Pubkey) -> Fixture {
+    let vault = vault();
+    let receipt = TestAccount::uninitialized(Receipt::SPACE).at(receipt_address(&vault.key, &reference));
+    Fixture::new()
+        .args((AMOUNT, reference))
+        .with("vault", vault)
+        .with("receipt", receipt)
+        .with("depositor", TestAccount::signer())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn secure_deposit(reference: Pubkey) -> instruction::SecureDeposit {
+    instruction::SecureDeposit {
+        amount: AMOUNT,
+        reference,
+    }
+}
+
+#[test]
+fn deposits_go_into_a_vault_of_this_program() {
+    assert_seeds_violation!(VulnerableDeposit, vulnerable_fixture(), "vault");
+    assert_signer_violation!(VulnerableDeposit, vulnerable_fixture(), "depositor");
+
+    let reference = Pubkey::new_unique();
+    assert_seeds_violation!(SecureDeposit, secure_deposit_fixture(reference), "vault");
+    assert_signer_violation!(SecureDeposit, secure_deposit_fixture(reference), "depositor");
+}
+
+#[test]
+fn secure_deposit_receipt_lives_at_the_vault_and_reference_address() {
+    assert_seeds_violation!(SecureDeposit, secure_deposit_fixture(Pubkey::new_unique()), "receipt");
+}
+
+#[test]
+fn vulnerable_deposit_leaves_nothing_to_credit_by() {
+    let mut fixture = vulnerable_fixture();
+    let before = fixture.account("vault").lamports;
+    execute!(&mut fixture, instruction::VulnerableDeposit { amount: AMOUNT }).unwrap();
+
+    // The vault is richer, and its data says nothing about whom for
+    let vault = fixture.account("vault");
+    assert_eq!(vault.lamports, before + AMOUNT);
+    assert_eq!(vault.data.len(), Vault::SPACE);
+}
+
+#[test]
+fn secure_deposit_records_what_the_vault_received_for_the_reference() {
+    warp_to(NOW);
+    let reference = Pubkey::new_unique();
+    let mut fixture = secure_deposit_fixture(reference);
+    let before = fixture.account("vault").lamports;
+    execute!(&mut fixture, secure_deposit(reference)).unwrap();
+    assert_eq!(fixture.account("vault").lamports, before + AMOUNT);
+
+    let stored = fixture.state::<Receipt>("receipt");
+    assert_eq!(stored.depositor, fixture.key("depositor"));
+    assert_eq!(stored.deposited_at, NOW);
+
+    // What the indexer reads back is exactly the deposit
+    let (vault, receipt) = (fixture.key("vault"), fixture.account("receipt"));
+    assert_eq!(
+        verify_receipt(&vault, &reference, &receipt.key, &receipt.owner, &receipt.data),
+        Ok(Credit {
+            reference,
+            amount: AMOUNT
+        })
+    );
+}
+
+#[test]
+fn secure_deposit_credits_each_reference_once() {
+    let reference = Pubkey::new_unique();
+    let mut fixture = secure_deposit_fixture(reference);
+    execute!(&mut fixture, secure_deposit(reference)).unwrap();
+    assert_eq!(
+        execute!(&mut fixture, secure_deposit(reference)),
+        Err(account_already_in_use())
+    );
+}
+
+#[test]
+fn secure_deposit_rejects_an_empty_deposit() {
+    let reference = Pubkey::new_unique();
+    assert_eq!(
+        execute!(
+            &mut secure_deposit_fixture(reference),
+            instruction::SecureDeposit { amount: 0, reference }
+        ),
+        Err(Error::from(DepositError::ZeroDeposit).into())
+    );
+}
@@ -0,0 +1,11 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Deposit amount must be greater than zero")]
+    ZeroDeposit,
+    #[msg("Receipt is not this vault's receipt for the reference")]
+    ReceiptMismatch,
+}
@@ -0,0 +1,249 @@
+//! What the exchange's indexer runs off-chain to decide whom a deposit is for.
+//!
+//! Nothing here executes on-chain. The vulnerable indexer reads fetched
+//! transactions and pairs memos with deposits; the secure one reads
+//! receipts and checks each is the program's own, at the address derived
+//! from the vault and reference it is credited to.
+
+#[cfg(feature = "vulnerable")]
+use std::str::FromStr;
+
+use anchor_lang::error::ErrorCode as AnchorError;
+use anchor_lang::prelude::*;
+#[cfg(feature = "vulnerable")]
+use anchor_lang::solana_program::instruction::Instruction;
+#[cfg(feature = "vulnerable")]
+use anchor_lang::Discriminator;
+
+#[cfg(feature = "vulnerable")]
+use crate::instruction;
+use crate::{ErrorCode, Receipt};
+
+/// The SPL Memo program (v2), whose instruction data is the memo text
+pub const MEMO_PROGRAM_ID: Pubkey = crate::compat::MEMO_PROGRAM_ID;
+
+/// An amount to add to the account a reference names
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Credit {
+    pub reference: Pubkey,
+    pub amount: u64,
+}
+
+// ========================================
+// VULNERABLE INDEXER
+// ========================================
+
+/// VULNERABLE: Credit the memos of a transaction that mentions `vault`
+///
+/// Security Issue: The transaction was found by the vault's address, so it
+/// is assumed to be a deposit into the vault, and every memo in it is
+/// assumed to describe every `vulnerable_deposit` beside it. Neither holds:
+/// a 1-lamport transfer is enough to mention the vault, the deposits can
+/// go into any vault of the program, and two memos get the same deposit
+/// credited twice.
+#[cfg(feature = "vulnerable")]
+pub fn vulnerable_credit(vault: &Pubkey, transaction: &[Instruction]) -> Vec<Credit> {
+    let mentions_vault = transaction
+        .iter()
+        .any(|ix| ix.accounts.iter().any(|meta| meta.pubkey == *vault));
+    if !mentions_vault {
+        return Vec::new();
+    }
+
+    // VULNERABILITY: Deposits are summed without looking at which vault they paid
+    let amount = transaction
+        .iter()
+        .filter(|ix| ix.program_id == crate::ID && ix.data.starts_with(&instruction::VulnerableDeposit::DISCRIMINATOR))
+        .filter_map(|ix| instruction::VulnerableDeposit::try_from_slice(&ix.data[8..]).ok())
+        .fold(0u64, |total, deposit| total.saturating_add(deposit.amount));
+
+    // VULNERABILITY: Every memo is credited with the whole amount
+    transaction
+        .iter()
+        .filter(|ix| ix.program_id == MEMO_PROGRAM_ID)
+        .filter_map(|ix| std::str::from_utf8(&ix.data).ok())
+        .filter_map(|memo| Pubkey::from_str(memo).ok())
+        .map(|reference| Credit { reference, amount })
+        .collect()
+}
+
+// ========================================
+// SECURE INDEXER
+// ========================================
+
+/// Where the vault's receipt for `reference` lives
+pub fn receipt_address(vault: &Pubkey, reference: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"receipt", vault.as_ref(), reference.as_ref()], &crate::ID).0
+}
+
+/// SECURE: Check a fetched account is `vault`'s receipt for `reference` and read it
+///
+/// Security Fix: The account must be owned by this program, which only
+/// writes receipts from `secure_deposit`, and sit at the address derived
+/// from the vault and reference, which `init` lets exist only once. Its
+/// `amount` is then exactly what the vault received for the reference.
+pub fn verify_receipt(
+    vault: &Pubkey,
+    reference: &Pubkey,
+    address: &Pubkey,
+    owner: &Pubkey,
+    data: &[u8],
+) -> Result<Credit> {
+    // SECURITY: A look-alike program can write the same layout anywhere else
+    require_keys_eq!(*owner, crate::ID, AnchorError::AccountOwnedByWrongProgram);
+    // SECURITY: A receipt for another reference or vault is not this one
+    require_keys_eq!(
+        *address,
+        receipt_address(vault, reference),
+        AnchorError::ConstraintSeeds
+    );
+
+    let receipt = Receipt::try_deserialize(&mut &data[..])?;
+    require!(
+        receipt.vault == *vault && receipt.reference == *reference,
+        ErrorCode::ReceiptMismatch
+    );
+    Ok(Credit {
+        reference: receipt.reference,
+        amount: receipt.amount,
+    })
+}
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod tests {
+    use anchor_lang::solana_program::system_instruction;
+    use anchor_lang::{InstructionData, ToAccountMetas};
+    use secref_testkit::TestAccount;
+
+    use super::*;
+    use crate::Vault;
+
+    const AMOUNT: u64 = 1_000_000;
+
+    fn memo(reference: &Pubkey) -> Instruction {
+        Instruction::new_with_bytes(MEMO_PROGRAM_ID, reference.to_string().as_bytes(), vec![])
+    }
+
+    fn deposit(vault: Pubkey, depositor: Pubkey, amount: u64) -> Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: crate::accounts::VulnerableDeposit {
+                vault,
+                depositor,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::VulnerableDeposit { amount }.data(),
+        }
+    }
+
+    fn receipt(vault: Pubkey, reference: Pubkey) -> TestAccount {
+        TestAccount::anchor(&Receipt {
+            vault,
+            reference,
+            depositor: Pubkey::new_unique(),
+            amount: AMOUNT,
+            deposited_at: 0,
+            bump: 255,
+        })
+        .at(receipt_address(&vault, &reference))
+    }
+
+    fn verify(vault: &Pubkey, reference: &Pubkey, account: &TestAccount) -> Result<Credit> {
+        verify_receipt(vault, reference, &account.key, &account.owner, &account.data)
+    }
+
+    #[test]
+    fn vulnerable_credit_pairs_an_honest_memo_and_deposit() {
+        let (vault, user) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let transaction = [memo(&user), deposit(vault, user, AMOUNT)];
+        assert_eq!(
+            vulnerable_credit(&vault, &transaction),
+            vec![Credit {
+                reference: user,
+                amount: AMOUNT
+            }]
+        );
+    }
+
+    #[test]
+    fn vulnerable_credit_counts_a_deposit_into_another_vault() {
+        let (exchange_vault, attacker_vault, attacker) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        // One lamport mentions the exchange's vault; the real deposit goes to the attacker's own
+        let transaction = [
+            system_instruction::transfer(&attacker, &exchange_vault, 1),
+            memo(&attacker),
+            deposit(attacker_vault, attacker, AMOUNT),
+        ];
+        assert_eq!(
+            vulnerable_credit(&exchange_vault, &transaction),
+            vec![Credit {
+                reference: attacker,
+                amount: AMOUNT
+            }]
+        );
+    }
+
+    #[test]
+    fn vulnerable_credit_credits_one_deposit_to_every_memo() {
+        let (vault, attacker, accomplice) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let transaction = [memo(&attacker), memo(&accomplice), deposit(vault, attacker, AMOUNT)];
+        let credited: u64 = vulnerable_credit(&vault, &transaction)
+            .iter()
+            .map(|credit| credit.amount)
+            .sum();
+        assert_eq!(credited, 2 * AMOUNT);
+    }
+
+    #[test]
+    fn verify_receipt_reads_the_vaults_receipt_for_the_reference() {
+        let (vault, reference) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert_eq!(
+            verify(&vault, &reference, &receipt(vault, reference)),
+            Ok(Credit {
+                reference,
+                amount: AMOUNT
+            })
+        );
+    }
+
+    #[test]
+    fn verify_receipt_rejects_a_look_alike_programs_account() {
+        let (vault, reference) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let forged = receipt(vault, reference).owned_by(Pubkey::new_unique());
+        assert_eq!(
+            verify(&vault, &reference, &forged),
+            Err(AnchorError::AccountOwnedByWrongProgram.into())
+        );
+    }
+
+    #[test]
+    fn verify_receipt_rejects_a_receipt_for_another_reference_or_vault() {
+        let (vault, reference) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let other_reference = receipt(vault, Pubkey::new_unique());
+        assert_eq!(
+            verify(&vault, &reference, &other_reference),
+            Err(AnchorError::ConstraintSeeds.into())
+        );
+        let other_vault = receipt(Pubkey::new_unique(), reference);
+        assert_eq!(
+            verify(&vault, &reference, &other_vault),
+            Err(AnchorError::ConstraintSeeds.into())
+        );
+    }
+
+    #[test]
+    fn verify_receipt_rejects_other_program_accounts_at_the_address() {
+        let (vault, reference) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let not_a_receipt = TestAccount::anchor(&Vault {
+            authority: Pubkey::new_unique(),
+            bump: 255,
+        })
+        .at(receipt_address(&vault, &reference));
+        assert_eq!(
+            verify(&vault, &reference, &not_a_receipt),
+            Err(AnchorError::AccountDiscriminatorMismatch.into())
+        );
+    }
+}
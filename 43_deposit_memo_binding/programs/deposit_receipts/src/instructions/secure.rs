@@ -0,0 +1,25 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+#[instruction(amount: u64, reference: Pubkey)]
+pub struct SecureDeposit<'info> {
+    #[account(mut, seeds = [b"vault", vault.authority.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    // SECURITY: One receipt per reference, at an address the indexer can derive
+    #[account(
+        init,
+        payer = depositor,
+        space = Receipt::SPACE,
+        seeds = [b"receipt", vault.key().as_ref(), reference.as_ref()],
+        bump
+    )]
+    pub receipt: Account<'info, Receipt>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
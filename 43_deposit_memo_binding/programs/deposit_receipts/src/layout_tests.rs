@@ -0,0 +1,28 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Receipt, Vault};
+
+#[test]
+fn vault_layout_is_stable() {
+    let vault = Vault {
+        authority: Pubkey::new_from_array([1; 32]),
+        bump: 33,
+    };
+    assert_golden!("Vault", golden::anchor(&vault));
+}
+
+#[test]
+fn receipt_layout_is_stable() {
+    let receipt = Receipt {
+        vault: Pubkey::new_from_array([3; 32]),
+        reference: Pubkey::new_from_array([4; 32]),
+        depositor: Pubkey::new_from_array([5; 32]),
+        amount: 0x6162636465666768,
+        deposited_at: 0x7172737475767778,
+        bump: 129,
+    };
+    assert_golden!("Receipt", golden::anchor(&receipt));
+}
@@ -0,0 +1,118 @@
+use anchor_lang::system_program::{self, Transfer};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+pub mod indexer;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+// Code that differs between Anchor releases, one file per release
+#[cfg_attr(feature = "anchor-0_29", path = "compat/anchor_0_29.rs")]
+#[cfg_attr(not(feature = "anchor-0_29"), path = "compat/anchor_0_30.rs")]
+mod compat;
+
+/// An exchange's deposit vault.
+///
+/// Users deposit lamports into the vault, and the exchange's off-chain
+/// indexer credits each deposit to the account its reference names. The
+/// program itself keeps no balances; whatever the indexer believes is what
+/// users can later withdraw off-chain.
+#[feature_gated]
+#[program]
+pub mod deposit_receipts {
+    use super::*;
+
+    /// Create the exchange's vault
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.authority = ctx.accounts.authority.key();
+        vault.bump = ctx.bumps.vault;
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Deposit `amount` lamports; the reference travels in a memo
+    ///
+    /// Security Issue: The user's reference is a separate Memo program
+    /// instruction in the same transaction. Nothing on-chain ties the two
+    /// together: the memo can sit beside a deposit into another vault, beside
+    /// two deposits, or beside none, and the indexer has to guess which
+    /// transfer it describes. See `indexer::vulnerable_credit`.
+    #[vulnerable]
+    pub fn vulnerable_deposit(ctx: Context<VulnerableDeposit>, amount: u64) -> Result<()> {
+        // VULNERABILITY: The transfer leaves no record of who it is for
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.depositor.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version: the reference is an argument
+    // of the deposit, recorded on-chain with the amount it received.
+
+    /// SECURE: Deposit `amount` lamports for `reference`, recording a receipt
+    ///
+    /// Security Fix: The same instruction that moves the funds writes the
+    /// reference, depositor and amount into a receipt at
+    /// `["receipt", vault, reference]`. The indexer reads receipts, not
+    /// transactions, so there is nothing left to pair up, and `init` makes
+    /// each reference creditable exactly once.
+    #[secure]
+    pub fn secure_deposit(ctx: Context<SecureDeposit>, amount: u64, reference: Pubkey) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroDeposit);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.depositor.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        // SECURITY: The receipt is written by the instruction that moved the funds
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.vault = ctx.accounts.vault.key();
+        receipt.reference = reference;
+        receipt.depositor = ctx.accounts.depositor.key();
+        receipt.amount = amount;
+        receipt.deposited_at = Clock::get()?.unix_timestamp;
+        receipt.bump = ctx.bumps.receipt;
+
+        msg!("Deposited {} lamports for {}", amount, reference);
+        Ok(())
+    }
+}
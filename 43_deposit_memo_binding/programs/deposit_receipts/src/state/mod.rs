@@ -0,0 +1,35 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Vault {
+    /// Exchange that runs the vault and its indexer (32 bytes)
+    pub authority: Pubkey,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Vault {
+    pub const SPACE: usize = 8 + 32 + 1;
+}
+
+#[account]
+pub struct Receipt {
+    /// Vault that received the deposit (32 bytes)
+    pub vault: Pubkey,
+    /// Account the deposit is to be credited to off-chain (32 bytes)
+    pub reference: Pubkey,
+    /// Signer the lamports came from (32 bytes)
+    pub depositor: Pubkey,
+    /// Lamports the vault received (8 bytes)
+    pub amount: u64,
+    /// Unix timestamp of the deposit (8 bytes)
+    pub deposited_at: i64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Receipt {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1;
+}
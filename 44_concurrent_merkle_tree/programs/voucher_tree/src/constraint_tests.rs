@@ -0,0 +1,283 @@
+//! Which proofs each redemption accepts: against which root, at which
+//! index and of which length - and who may change the tree.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_seeds_violation, assert_signer_violation, execute, Fixture, TestAccount};
+
+use crate::merkle::{empty_root, voucher_leaf, OffChainTree, EMPTY_LEAF};
+use crate::{instruction, ErrorCode as TreeError, Redemption, Tree, UpdateTree, ROOT_HISTORY};
+
+const DEPTH: u8 = 3;
+const AMOUNT: u64 = 1_000;
+const FUNDS: u64 = 10_000;
+
+/// A funded depth-3 tree holding one voucher per owner, and the indexer's copy of it
+struct World {
+    tree: TestAccount,
+    authority: TestAccount,
+    owners: Vec<TestAccount>,
+    off_chain: OffChainTree,
+}
+
+impl World {
+    fn new(vouchers: usize) -> Self {
+        let authority = TestAccount::signer();
+        let owners: Vec<TestAccount> = (0..vouchers).map(|_| TestAccount::signer()).collect();
+        let mut off_chain = OffChainTree::new(DEPTH);
+        for (index, owner) in owners.iter().enumerate() {
+            off_chain.set(index as u64, voucher_leaf(&owner.key, AMOUNT));
+        }
+
+        let (tree, bump) = Pubkey::find_program_address(&[b"tree", authority.key.as_ref()], &crate::ID);
+        let mut root_history = [[0; 32]; ROOT_HISTORY];
+        root_history[0] = off_chain.root();
+        let tree_account = TestAccount::anchor(&Tree {
+            authority: authority.key,
+            depth: DEPTH,
+            next_index: vouchers as u64,
+            root: off_chain.root(),
+            root_history,
+            history_cursor: 0,
+            bump,
+        })
+        .at(tree);
+        let lamports = tree_account.lamports + FUNDS;
+        Self {
+            tree: tree_account.with_lamports(lamports),
+            authority,
+            owners,
+            off_chain,
+        }
+    }
+
+    fn update(&self) -> Fixture {
+        Fixture::new()
+            .with("tree", self.tree.clone())
+            .with("authority", self.authority.clone())
+    }
+
+    fn vulnerable_redeem(&self, owner: usize, index: u64) -> Fixture {
+        let (redemption, _) = Pubkey::find_program_address(
+            &[b"redemption", self.tree.key.as_ref(), &index.to_le_bytes()],
+            &crate::ID,
+        );
+        Fixture::new()
+            .with("tree", self.tree.clone())
+            .with(
+                "redemption",
+                TestAccount::uninitialized(Redemption::SPACE).at(redemption),
+            )
+            .with("owner", self.owners[owner].clone())
+            .with("system_program", TestAccount::system_program())
+    }
+
+    fn secure_redeem(&self, owner: usize) -> Fixture {
+        Fixture::new()
+            .with("tree", self.tree.clone())
+            .with("owner", self.owners[owner].clone())
+    }
+
+    /// The authority revokes the voucher at `index`, on-chain and in the indexer
+    fn revoke(&mut self, index: u64) {
+        let mut fixture = self.update();
+        execute!(
+            &mut fixture,
+            instruction::RevokeVoucher {
+                owner: self.owners[index as usize].key,
+                amount: AMOUNT,
+                index,
+                proof: self.off_chain.proof(index),
+            }
+        )
+        .unwrap();
+        self.tree = fixture.account("tree").clone();
+        self.off_chain.set(index, EMPTY_LEAF);
+    }
+
+    fn root(&self) -> [u8; 32] {
+        self.tree.state::<Tree>().root
+    }
+}
+
+fn vulnerable(index: u64, root: [u8; 32], proof: Vec<[u8; 32]>) -> instruction::VulnerableRedeem {
+    instruction::VulnerableRedeem {
+        amount: AMOUNT,
+        index,
+        root,
+        proof,
+    }
+}
+
+fn secure(index: u64, proof: Vec<[u8; 32]>) -> instruction::SecureRedeem {
+    instruction::SecureRedeem {
+        amount: AMOUNT,
+        index,
+        proof,
+    }
+}
+
+#[test]
+fn initialize_tree_starts_empty_at_a_bounded_depth() {
+    let authority = TestAccount::signer();
+    let (tree, _) = Pubkey::find_program_address(&[b"tree", authority.key.as_ref()], &crate::ID);
+    let fixture = Fixture::new()
+        .with("tree", TestAccount::uninitialized(Tree::SPACE).at(tree))
+        .with("authority", authority)
+        .with("system_program", TestAccount::system_program());
+
+    for depth in [0, crate::merkle::MAX_DEPTH + 1] {
+        assert_eq!(
+            execute!(&mut fixture.clone(), instruction::InitializeTree { depth }),
+            Err(Error::from(TreeError::InvalidDepth).into())
+        );
+    }
+
+    let mut fixture = fixture;
+    execute!(&mut fixture, instruction::InitializeTree { depth: DEPTH }).unwrap();
+    let tree = fixture.state::<Tree>("tree");
+    assert_eq!(tree.root, empty_root(DEPTH));
+    assert!(tree.root_history.contains(&tree.root));
+    assert_eq!(tree.next_index, 0);
+}
+
+#[test]
+fn issuing_and_revoking_are_the_authoritys() {
+    let world = World::new(0);
+    assert_seeds_violation!(UpdateTree, world.update(), "authority");
+    assert_signer_violation!(UpdateTree, world.update(), "authority");
+}
+
+#[test]
+fn issue_voucher_fills_the_next_empty_leaf_until_the_tree_is_full() {
+    let mut world = World::new(0);
+    let owner = Pubkey::new_unique();
+    for index in 0..(1u64 << DEPTH) {
+        let mut fixture = world.update();
+        execute!(
+            &mut fixture,
+            instruction::IssueVoucher {
+                owner,
+                amount: AMOUNT,
+                proof: world.off_chain.proof(index),
+            }
+        )
+        .unwrap();
+        world.tree = fixture.account("tree").clone();
+        world.off_chain.set(index, voucher_leaf(&owner, AMOUNT));
+        assert_eq!(world.root(), world.off_chain.root());
+    }
+
+    assert_eq!(
+        execute!(
+            &mut world.update(),
+            instruction::IssueVoucher {
+                owner,
+                amount: AMOUNT,
+                proof: world.off_chain.proof(0),
+            }
+        ),
+        Err(Error::from(TreeError::TreeFull).into())
+    );
+}
+
+#[test]
+fn vulnerable_redeem_pays_a_revoked_voucher_against_a_stale_root() {
+    let mut world = World::new(2);
+    let (stale_root, stale_proof) = (world.root(), world.off_chain.proof(1));
+    world.revoke(1);
+    assert_ne!(world.root(), stale_root);
+
+    let mut fixture = world.vulnerable_redeem(1, 1);
+    execute!(&mut fixture, vulnerable(1, stale_root, stale_proof)).unwrap();
+    assert_eq!(fixture.account("tree").lamports, world.tree.lamports - AMOUNT);
+}
+
+#[test]
+fn vulnerable_redeem_pays_one_voucher_under_every_alias_of_its_index() {
+    let mut world = World::new(1);
+    let (root, proof) = (world.root(), world.off_chain.proof(0));
+
+    // 0, 8 and 16 all take the same path through a depth-3 tree
+    for alias in [0, 1 << DEPTH, 2 << DEPTH] {
+        let mut fixture = world.vulnerable_redeem(0, alias);
+        execute!(&mut fixture, vulnerable(alias, root, proof.clone())).unwrap();
+        world.tree = fixture.account("tree").clone();
+        world.owners[0] = fixture.account("owner").clone();
+    }
+    assert_eq!(world.tree.lamports, World::new(1).tree.lamports - 3 * AMOUNT);
+}
+
+#[test]
+fn vulnerable_redeem_still_wants_a_root_the_tree_has_had() {
+    let world = World::new(1);
+    let forged = OffChainTree::new(DEPTH);
+    assert_eq!(
+        execute!(
+            &mut world.vulnerable_redeem(0, 0),
+            vulnerable(0, forged.root(), forged.proof(0))
+        ),
+        Err(Error::from(TreeError::UnknownRoot).into())
+    );
+}
+
+#[test]
+fn secure_redeem_rejects_proofs_against_a_stale_root() {
+    let mut world = World::new(2);
+    let stale_proof = world.off_chain.proof(1);
+    world.revoke(1);
+
+    assert_eq!(
+        execute!(&mut world.secure_redeem(1), secure(1, stale_proof)),
+        Err(Error::from(TreeError::InvalidProof).into())
+    );
+}
+
+#[test]
+fn secure_redeem_bounds_the_index_and_the_proof_length() {
+    let world = World::new(1);
+    let proof = world.off_chain.proof(0);
+    assert_eq!(
+        execute!(&mut world.secure_redeem(0), secure(1 << DEPTH, proof.clone())),
+        Err(Error::from(TreeError::LeafIndexOutOfBounds).into())
+    );
+
+    let mismatch = Err(Error::from(TreeError::ProofLengthMismatch).into());
+    assert_eq!(
+        execute!(&mut world.secure_redeem(0), secure(0, proof[..2].to_vec())),
+        mismatch
+    );
+    let mut long = proof;
+    long.push([7; 32]);
+    assert_eq!(execute!(&mut world.secure_redeem(0), secure(0, long)), mismatch);
+}
+
+#[test]
+fn secure_redeem_pays_once_and_burns_the_leaf() {
+    let mut world = World::new(2);
+    let proof = world.off_chain.proof(0);
+
+    let mut fixture = world.secure_redeem(0);
+    execute!(&mut fixture, secure(0, proof.clone())).unwrap();
+    assert_eq!(fixture.account("owner").lamports, world.owners[0].lamports + AMOUNT);
+    world.tree = fixture.account("tree").clone();
+    world.off_chain.set(0, EMPTY_LEAF);
+    assert_eq!(world.root(), world.off_chain.root());
+
+    // Neither the old proof nor a fresh one proves the voucher any more
+    for proof in [proof, world.off_chain.proof(0)] {
+        assert_eq!(
+            execute!(&mut world.secure_redeem(0), secure(0, proof)),
+            Err(Error::from(TreeError::InvalidProof).into())
+        );
+    }
+}
+
+#[test]
+fn secure_redeem_keeps_the_tree_rent_exempt() {
+    let mut world = World::new(1);
+    world.tree.lamports -= FUNDS;
+    assert_eq!(
+        execute!(&mut world.secure_redeem(0), secure(0, world.off_chain.proof(0))),
+        Err(Error::from(TreeError::InsufficientFunds).into())
+    );
+}
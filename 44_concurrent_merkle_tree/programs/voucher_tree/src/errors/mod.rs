@@ -0,0 +1,21 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Tree depth must be between 1 and 14")]
+    InvalidDepth,
+    #[msg("Every leaf of the tree has been issued")]
+    TreeFull,
+    #[msg("Proof does not hash up to the expected root")]
+    InvalidProof,
+    #[msg("Root is not one the tree has had recently")]
+    UnknownRoot,
+    #[msg("Proof must have exactly one node per level of the tree")]
+    ProofLengthMismatch,
+    #[msg("Leaf index is outside the tree")]
+    LeafIndexOutOfBounds,
+    #[msg("Tree does not hold enough lamports for the voucher")]
+    InsufficientFunds,
+}
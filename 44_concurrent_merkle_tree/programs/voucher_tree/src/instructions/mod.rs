@@ -0,0 +1,41 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializeTree<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Tree::SPACE,
+        seeds = [b"tree", authority.key().as_ref()],
+        bump
+    )]
+    pub tree: Account<'info, Tree>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Issuing and revoking are the authority's
+#[derive(Accounts)]
+pub struct UpdateTree<'info> {
+    #[account(mut, has_one = authority, seeds = [b"tree", authority.key().as_ref()], bump = tree.bump)]
+    pub tree: Account<'info, Tree>,
+
+    pub authority: Signer<'info>,
+}
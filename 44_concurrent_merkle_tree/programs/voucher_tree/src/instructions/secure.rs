@@ -0,0 +1,12 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureRedeem<'info> {
+    #[account(mut, seeds = [b"tree", tree.authority.as_ref()], bump = tree.bump)]
+    pub tree: Account<'info, Tree>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
@@ -0,0 +1,25 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+#[instruction(amount: u64, index: u64)]
+pub struct VulnerableRedeem<'info> {
+    #[account(mut, seeds = [b"tree", tree.authority.as_ref()], bump = tree.bump)]
+    pub tree: Account<'info, Tree>,
+
+    // VULNERABILITY: Redemptions are tracked by index, and an index has many aliases
+    #[account(
+        init,
+        payer = owner,
+        space = Redemption::SPACE,
+        seeds = [b"redemption", tree.key().as_ref(), &index.to_le_bytes()],
+        bump
+    )]
+    pub redemption: Account<'info, Redemption>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
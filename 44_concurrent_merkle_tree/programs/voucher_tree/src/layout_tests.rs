@@ -0,0 +1,30 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Redemption, Tree, ROOT_HISTORY};
+
+#[test]
+fn tree_layout_is_stable() {
+    let tree = Tree {
+        authority: Pubkey::new_from_array([1; 32]),
+        depth: 33,
+        next_index: 0x3132333435363738,
+        root: [65; 32],
+        root_history: [[81; 32]; ROOT_HISTORY],
+        history_cursor: 97,
+        bump: 113,
+    };
+    assert_golden!("Tree", golden::anchor(&tree));
+}
+
+#[test]
+fn redemption_layout_is_stable() {
+    let redemption = Redemption {
+        tree: Pubkey::new_from_array([8; 32]),
+        index: 0x9192939495969798,
+        bump: 161,
+    };
+    assert_golden!("Redemption", golden::anchor(&redemption));
+}
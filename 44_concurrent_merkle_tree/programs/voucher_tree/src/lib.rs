@@ -0,0 +1,175 @@
+use secref_guards::rent::minimum_balance;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+pub mod merkle;
+
+use merkle::{compute_root, empty_root, voucher_leaf, EMPTY_LEAF, MAX_DEPTH};
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Lamport vouchers stored as leaves of a concurrent Merkle tree.
+///
+/// Only the tree's root lives on-chain; the leaves are kept off-chain by an
+/// indexer that serves proofs. The authority issues vouchers into empty
+/// leaves and can revoke them. A voucher's owner redeems it for lamports
+/// held by the tree account.
+///
+/// Like spl-account-compression, the tree remembers its last
+/// `ROOT_HISTORY` roots so that transactions built against a root that
+/// changed in the meantime need not fail.
+#[feature_gated]
+#[program]
+pub mod voucher_tree {
+    use super::*;
+
+    /// Create an empty tree of `depth`; fund it by transferring lamports to it
+    pub fn initialize_tree(ctx: Context<InitializeTree>, depth: u8) -> Result<()> {
+        require!(depth > 0 && depth <= MAX_DEPTH, ErrorCode::InvalidDepth);
+
+        let tree = &mut ctx.accounts.tree;
+        tree.authority = ctx.accounts.authority.key();
+        tree.depth = depth;
+        tree.next_index = 0;
+        tree.root = [0; 32];
+        tree.root_history = [[0; 32]; ROOT_HISTORY];
+        tree.history_cursor = 0;
+        tree.bump = ctx.bumps.tree;
+        tree.set_root(empty_root(depth));
+
+        msg!("Tree of depth {} created", depth);
+        Ok(())
+    }
+
+    /// Issue a voucher into the next empty leaf, proven empty by `proof`
+    pub fn issue_voucher(ctx: Context<UpdateTree>, owner: Pubkey, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        let tree = &mut ctx.accounts.tree;
+        let index = tree.next_index;
+        require!(index < tree.capacity(), ErrorCode::TreeFull);
+
+        tree.replace_leaf(&EMPTY_LEAF, &voucher_leaf(&owner, amount), index, &proof)?;
+        tree.next_index = index + 1;
+
+        msg!("Voucher {} of {} lamports issued to {}", index, amount, owner);
+        Ok(())
+    }
+
+    /// Revoke the voucher at `index` by emptying its leaf
+    pub fn revoke_voucher(
+        ctx: Context<UpdateTree>,
+        owner: Pubkey,
+        amount: u64,
+        index: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts
+            .tree
+            .replace_leaf(&voucher_leaf(&owner, amount), &EMPTY_LEAF, index, &proof)?;
+
+        msg!("Voucher {} revoked", index);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Redeem the signer's voucher, proven against any recent root
+    ///
+    /// Security Issue: Accepting any root in the history keeps redemptions
+    /// working while other transactions change the tree, but an old root
+    /// only proves the voucher existed then. A voucher revoked since is
+    /// still paid out. The proof's length is never compared with the tree's
+    /// depth either, so index bits above the depth are ignored: the same
+    /// voucher redeems again as `index + 2^depth`, under a different
+    /// redemption record.
+    #[vulnerable]
+    pub fn vulnerable_redeem(
+        ctx: Context<VulnerableRedeem>,
+        amount: u64,
+        index: u64,
+        root: [u8; 32],
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let tree = &ctx.accounts.tree;
+
+        // VULNERABILITY: Any root the tree has had recently is good enough
+        require!(tree.root_history.contains(&root), ErrorCode::UnknownRoot);
+
+        // VULNERABILITY: Neither the proof length nor the index is bounded by the depth
+        let leaf = voucher_leaf(&ctx.accounts.owner.key(), amount);
+        require!(compute_root(&leaf, index, &proof) == root, ErrorCode::InvalidProof);
+
+        // VULNERABILITY: The voucher stays in the tree; only this index is marked redeemed
+        let redemption = &mut ctx.accounts.redemption;
+        redemption.tree = tree.key();
+        redemption.index = index;
+        redemption.bump = ctx.bumps.redemption;
+
+        pay(
+            &ctx.accounts.tree.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            amount,
+        )?;
+
+        msg!("Voucher {} redeemed for {} lamports", index, amount);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that proves the voucher
+    // against the canonical root and consumes it in the tree.
+
+    /// SECURE: Redeem the signer's voucher, burning its leaf
+    ///
+    /// Security Fix: The proof must be exactly `depth` long, the index
+    /// inside the tree, and the leaf must hash up to the current root - the
+    /// only root that says what the tree holds now. Redeeming replaces the
+    /// leaf with an empty one, so the tree itself records that the voucher
+    /// is gone and no separate record can be sidestepped.
+    #[secure]
+    pub fn secure_redeem(ctx: Context<SecureRedeem>, amount: u64, index: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        let leaf = voucher_leaf(&ctx.accounts.owner.key(), amount);
+
+        // SECURITY: Canonical root, bounded proof and index; the leaf is consumed
+        ctx.accounts.tree.replace_leaf(&leaf, &EMPTY_LEAF, index, &proof)?;
+
+        pay(
+            &ctx.accounts.tree.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            amount,
+        )?;
+
+        msg!("Voucher {} redeemed for {} lamports", index, amount);
+        Ok(())
+    }
+}
+
+/// Move `amount` lamports out of the program-owned tree, keeping it rent-exempt
+fn pay<'info>(tree: &AccountInfo<'info>, owner: &AccountInfo<'info>, amount: u64) -> Result<()> {
+    let reserve = minimum_balance(tree.data_len())?;
+    let available = tree.lamports().saturating_sub(reserve);
+    require!(amount <= available, ErrorCode::InsufficientFunds);
+
+    **tree.try_borrow_mut_lamports()? -= amount;
+    **owner.try_borrow_mut_lamports()? += amount;
+    Ok(())
+}
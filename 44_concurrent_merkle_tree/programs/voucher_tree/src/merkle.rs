@@ -0,0 +1,135 @@
+//! Hashing for the voucher tree: leaves, nodes, and roots recomputed from a
+//! leaf and its proof.
+//!
+//! A tree of depth `d` has `2^d` leaves. Empty leaves are all zeroes, and a
+//! node is the SHA-256 of its two children. A proof is the sibling of each
+//! node on the path from a leaf to the root, lowest first; bit `i` of the
+//! leaf's index says whether the node at height `i` is a right child.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+/// Deepest tree the program creates: 16,384 vouchers
+pub const MAX_DEPTH: u8 = 14;
+
+/// Leaf of an unused or consumed slot
+pub const EMPTY_LEAF: [u8; 32] = [0; 32];
+
+/// The leaf for a voucher worth `amount` lamports to `owner`
+pub fn voucher_leaf(owner: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[owner.as_ref(), &amount.to_le_bytes()]).to_bytes()
+}
+
+pub fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hashv(&[left, right]).to_bytes()
+}
+
+/// Root of a tree of `depth` whose leaves are all empty
+pub fn empty_root(depth: u8) -> [u8; 32] {
+    (0..depth).fold(EMPTY_LEAF, |node, _| hash_pair(&node, &node))
+}
+
+/// The root `leaf` hashes up to at `index` along `proof`
+///
+/// One level per proof element: bits of `index` above `proof.len()` are never
+/// read, and nothing here knows how deep the tree is. Callers bound both.
+pub fn compute_root(leaf: &[u8; 32], index: u64, proof: &[[u8; 32]]) -> [u8; 32] {
+    proof.iter().enumerate().fold(*leaf, |node, (height, sibling)| {
+        if (index >> height) & 1 == 0 {
+            hash_pair(&node, sibling)
+        } else {
+            hash_pair(sibling, &node)
+        }
+    })
+}
+
+/// A whole tree held off-chain, the way an indexer keeps one to serve proofs
+#[cfg(test)]
+pub struct OffChainTree {
+    depth: u8,
+    leaves: Vec<[u8; 32]>,
+}
+
+#[cfg(test)]
+impl OffChainTree {
+    pub fn new(depth: u8) -> Self {
+        Self {
+            depth,
+            leaves: vec![EMPTY_LEAF; 1 << depth],
+        }
+    }
+
+    pub fn set(&mut self, index: u64, leaf: [u8; 32]) {
+        self.leaves[index as usize] = leaf;
+    }
+
+    /// Every level from the leaves (level 0) up to the root
+    fn levels(&self) -> Vec<Vec<[u8; 32]>> {
+        let mut levels = vec![self.leaves.clone()];
+        for _ in 0..self.depth {
+            let below = levels.last().unwrap();
+            levels.push(below.chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect());
+        }
+        levels
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels()[usize::from(self.depth)][0]
+    }
+
+    pub fn proof(&self, index: u64) -> Vec<[u8; 32]> {
+        let levels = self.levels();
+        (0..usize::from(self.depth))
+            .map(|height| levels[height][((index >> height) ^ 1) as usize])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_root_matches_a_tree_of_empty_leaves() {
+        for depth in 0..=4 {
+            assert_eq!(empty_root(depth), OffChainTree::new(depth).root());
+        }
+    }
+
+    #[test]
+    fn every_leaf_proves_up_to_the_root() {
+        let mut tree = OffChainTree::new(3);
+        for index in 0..8 {
+            tree.set(index, voucher_leaf(&Pubkey::new_unique(), index));
+        }
+        for index in 0..8 {
+            let leaf = tree.leaves[index as usize];
+            assert_eq!(compute_root(&leaf, index, &tree.proof(index)), tree.root());
+        }
+    }
+
+    #[test]
+    fn a_proof_is_only_good_for_its_own_leaf_and_index() {
+        let mut tree = OffChainTree::new(3);
+        let (owner, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        tree.set(5, voucher_leaf(&owner, 100));
+        let proof = tree.proof(5);
+
+        assert_ne!(compute_root(&voucher_leaf(&owner, 101), 5, &proof), tree.root());
+        assert_ne!(compute_root(&voucher_leaf(&other, 100), 5, &proof), tree.root());
+        assert_ne!(compute_root(&voucher_leaf(&owner, 100), 4, &proof), tree.root());
+    }
+
+    #[test]
+    fn index_bits_past_the_proof_are_ignored() {
+        let mut tree = OffChainTree::new(3);
+        let leaf = voucher_leaf(&Pubkey::new_unique(), 100);
+        tree.set(5, leaf);
+        let proof = tree.proof(5);
+
+        // 5, 13 and 5 + 2^40 all take the same path through a depth-3 tree
+        for alias in [5, 5 + 8, 5 + (1 << 40)] {
+            assert_eq!(compute_root(&leaf, alias, &proof), tree.root());
+        }
+    }
+}
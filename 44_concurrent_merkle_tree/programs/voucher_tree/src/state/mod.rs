@@ -0,0 +1,66 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+/// Roots the tree remembers, current one included
+pub const ROOT_HISTORY: usize = 8;
+
+#[account]
+pub struct Tree {
+    /// Issues and revokes vouchers (32 bytes)
+    pub authority: Pubkey,
+    /// Levels below the root; the tree holds 2^depth leaves (1 byte)
+    pub depth: u8,
+    /// Next empty leaf to issue into (8 bytes)
+    pub next_index: u64,
+    /// The canonical root: what the tree holds now (32 bytes)
+    pub root: [u8; 32],
+    /// The last ROOT_HISTORY roots, for writers racing each other (32 * ROOT_HISTORY bytes)
+    pub root_history: [[u8; 32]; ROOT_HISTORY],
+    /// Slot in root_history the current root occupies (1 byte)
+    pub history_cursor: u8,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Tree {
+    pub const SPACE: usize = 8 + 32 + 1 + 8 + 32 + 32 * ROOT_HISTORY + 1 + 1;
+
+    pub fn capacity(&self) -> u64 {
+        1 << self.depth
+    }
+
+    /// Make `root` current, remembering it in the history
+    pub(crate) fn set_root(&mut self, root: [u8; 32]) {
+        self.history_cursor = ((usize::from(self.history_cursor) + 1) % ROOT_HISTORY) as u8;
+        self.root_history[usize::from(self.history_cursor)] = root;
+        self.root = root;
+    }
+
+    /// Swap `old` for `new` at `index`, with `proof` checked against the current root
+    pub fn replace_leaf(&mut self, old: &[u8; 32], new: &[u8; 32], index: u64, proof: &[[u8; 32]]) -> Result<()> {
+        // SECURITY: One sibling per level, no more and no fewer
+        require!(proof.len() == usize::from(self.depth), ErrorCode::ProofLengthMismatch);
+        // SECURITY: Each leaf has exactly one index
+        require!(index < self.capacity(), ErrorCode::LeafIndexOutOfBounds);
+        // SECURITY: Only the current root describes the tree as it is
+        require!(compute_root(old, index, proof) == self.root, ErrorCode::InvalidProof);
+
+        self.set_root(compute_root(new, index, proof));
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Redemption {
+    /// Tree the voucher was redeemed from (32 bytes)
+    pub tree: Pubkey,
+    /// Index the voucher was redeemed under (8 bytes)
+    pub index: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Redemption {
+    pub const SPACE: usize = 8 + 32 + 8 + 1;
+}
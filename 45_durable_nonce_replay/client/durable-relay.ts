@@ -0,0 +1,136 @@
+/**
+ * durable-relay: the owner and relayer sides of relayed_payments.
+ *
+ * The owner signs payment authorizations off-chain and hands them to a
+ * relayer. The relayer places each one, as an Ed25519 instruction, in a
+ * transaction built on a durable nonce account: instead of a recent
+ * blockhash the transaction carries the nonce account's stored value, and
+ * advances it as its first instruction. Such a transaction never expires;
+ * it stays valid until it lands or someone advances the nonce.
+ *
+ * Once the nonce has advanced, the next transaction built on it has a new
+ * "blockhash" and therefore a new signature. The runtime deduplicates
+ * transactions by signature, so it has no way to know that the Ed25519
+ * instruction inside is one it has executed before. Only the program can.
+ */
+
+import { BN } from "@coral-xyz/anchor";
+import {
+  Connection,
+  Ed25519Program,
+  Keypair,
+  NONCE_ACCOUNT_LENGTH,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+
+/** `PaymentAuthorization` in relayed_payments: no nonce, no expiry */
+export interface PaymentAuthorization {
+  wallet: PublicKey;
+  recipient: PublicKey;
+  amount: number;
+}
+
+/** `NoncedPaymentAuthorization` in relayed_payments */
+export interface NoncedPaymentAuthorization extends PaymentAuthorization {
+  /** Chosen by the owner; each value authorizes at most one payment */
+  nonce: number;
+  /** Unix timestamp after which the program refuses the authorization */
+  expiresAt: number;
+}
+
+/** Borsh encoding of a `PaymentAuthorization`: the bytes the vulnerable wallet's owner signs */
+export function paymentBytes(payment: PaymentAuthorization): Buffer {
+  return Buffer.concat([
+    payment.wallet.toBuffer(),
+    payment.recipient.toBuffer(),
+    new BN(payment.amount).toArrayLike(Buffer, "le", 8),
+  ]);
+}
+
+/** Borsh encoding of a `NoncedPaymentAuthorization`: the bytes the secure wallet's owner signs */
+export function noncedPaymentBytes(payment: NoncedPaymentAuthorization): Buffer {
+  return Buffer.concat([
+    paymentBytes(payment),
+    new BN(payment.nonce).toArrayLike(Buffer, "le", 8),
+    new BN(payment.expiresAt).toTwos(64).toArrayLike(Buffer, "le", 8),
+  ]);
+}
+
+/**
+ * The owner's side: sign `message` off-chain, as the Ed25519 instruction a relayer submits
+ *
+ * This instruction is the whole authorization. Whoever holds it - the
+ * relayer, or anyone who saw it in a landed transaction - can place it in
+ * a transaction of their own.
+ */
+export function signAuthorization(owner: Keypair, message: Buffer): TransactionInstruction {
+  return Ed25519Program.createInstructionWithPrivateKey({ privateKey: owner.secretKey, message });
+}
+
+/** The PDA relayed_payments creates to spend `nonce` of `wallet` */
+export function usedNonceAddress(programId: PublicKey, wallet: PublicKey, nonce: number): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("nonce"), wallet.toBuffer(), new BN(nonce).toArrayLike(Buffer, "le", 8)],
+    programId
+  )[0];
+}
+
+/** Create a durable nonce account that `authority` advances */
+export async function createNonceAccount(connection: Connection, authority: Keypair): Promise<PublicKey> {
+  const nonceAccount = Keypair.generate();
+  const lamports = await connection.getMinimumBalanceForRentExemption(NONCE_ACCOUNT_LENGTH);
+  const transaction = SystemProgram.createNonceAccount({
+    fromPubkey: authority.publicKey,
+    noncePubkey: nonceAccount.publicKey,
+    authorizedPubkey: authority.publicKey,
+    lamports,
+  });
+  transaction.feePayer = authority.publicKey;
+  transaction.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+  transaction.sign(authority, nonceAccount);
+  const signature = await connection.sendRawTransaction(transaction.serialize());
+  await connection.confirmTransaction(signature);
+  return nonceAccount.publicKey;
+}
+
+/**
+ * The relayer's side: build and sign a transaction on the nonce account's current value
+ *
+ * The result can be held for as long as the relayer likes - minutes or
+ * months - and submitted with `submit`. Its only deadline is the next
+ * advance of the nonce.
+ */
+export async function prepareDurableTransaction(
+  connection: Connection,
+  relayer: Keypair,
+  nonceAccount: PublicKey,
+  instructions: TransactionInstruction[]
+): Promise<Transaction> {
+  const stored = await connection.getNonce(nonceAccount);
+  if (!stored) {
+    throw new Error(`${nonceAccount.toBase58()} is not an initialized nonce account`);
+  }
+
+  // The advance must come first; the runtime then accepts the stored value in place of a blockhash
+  const transaction = new Transaction().add(
+    SystemProgram.nonceAdvance({ noncePubkey: nonceAccount, authorizedPubkey: relayer.publicKey }),
+    ...instructions
+  );
+  transaction.feePayer = relayer.publicKey;
+  transaction.recentBlockhash = stored.nonce;
+  transaction.sign(relayer);
+  return transaction;
+}
+
+/** Send a prepared transaction and wait for it to land */
+export async function submit(connection: Connection, transaction: Transaction): Promise<string> {
+  const signature = await connection.sendRawTransaction(transaction.serialize());
+  const { value } = await connection.confirmTransaction(signature);
+  if (value.err) {
+    throw new Error(`${signature} failed: ${JSON.stringify(value.err)}`);
+  }
+  return signature;
+}
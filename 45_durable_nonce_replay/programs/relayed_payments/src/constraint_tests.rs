@@ -0,0 +1,331 @@
+//! Relayed payments, replayed: the same signed payload submitted again in
+//! a new transaction, as a relayer holding it on a durable nonce would.
+//!
+//! Ed25519 instructions here carry placeholder signatures. On chain the
+//! precompile rejects the transaction before the wallet runs unless the
+//! signature is valid, so the program only reads who signed which bytes,
+//! and that is what these tests exercise. Executing the same fixture twice
+//! is submitting the same instructions in two transactions; the runtime
+//! has nothing to deduplicate when their blockhashes - or durable nonce
+//! values - differ.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::Instruction;
+use secref_testkit::runtime::{account_already_in_use, warp_to};
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, execute,
+    Fixture, TestAccount,
+};
+
+use crate::{
+    instruction, CancelAuthorization, ErrorCode as WalletError, InitializeWallet, NoncedPaymentAuthorization,
+    PaymentAuthorization, SecureRelayPayment, UsedNonce, Wallet,
+};
+
+const FUNDS: u64 = 10_000_000;
+const AMOUNT: u64 = 1_000_000;
+const NOW: i64 = 1_700_000_000;
+const EXPIRES_AT: i64 = NOW + 600;
+
+/// An Ed25519 program instruction with a placeholder signature by `signer` over `message`
+fn ed25519_instruction(signer: &Pubkey, message: &[u8]) -> Instruction {
+    const CURRENT: usize = u16::MAX as usize;
+    const HEADER: usize = 2 + 14;
+    let (public_key_offset, signature_offset, message_offset) = (HEADER, HEADER + 32, HEADER + 32 + 64);
+
+    let mut data = vec![1, 0];
+    for value in [
+        signature_offset,
+        CURRENT,
+        public_key_offset,
+        CURRENT,
+        message_offset,
+        message.len(),
+        CURRENT,
+    ] {
+        data.extend_from_slice(&(value as u16).to_le_bytes());
+    }
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(&[0; 64]);
+    data.extend_from_slice(message);
+    Instruction::new_with_bytes(ed25519_program::ID, &data, vec![])
+}
+
+/// The instructions sysvar of `[signature, relay]`, executing the relay
+fn relayed(signature: Instruction) -> TestAccount {
+    TestAccount::instructions_sysvar_of(&[signature, Instruction::new_with_bytes(crate::ID, &[], vec![])], 1)
+}
+
+/// A funded wallet, its owner and one recipient
+struct World {
+    owner: TestAccount,
+    wallet: TestAccount,
+    recipient: TestAccount,
+}
+
+impl World {
+    fn new() -> Self {
+        warp_to(NOW);
+        let owner = TestAccount::signer();
+        let (address, bump) = Pubkey::find_program_address(&[b"wallet", owner.key.as_ref()], &crate::ID);
+        let wallet = TestAccount::anchor(&Wallet { owner: owner.key, bump })
+            .at(address)
+            .with_lamports(Rent::default().minimum_balance(Wallet::SPACE) + FUNDS);
+        Self {
+            owner,
+            wallet,
+            recipient: TestAccount::system(0),
+        }
+    }
+
+    fn used_nonce(&self, nonce: u64) -> TestAccount {
+        let (address, _) =
+            Pubkey::find_program_address(&[b"nonce", self.wallet.key.as_ref(), &nonce.to_le_bytes()], &crate::ID);
+        TestAccount::uninitialized(UsedNonce::SPACE).at(address)
+    }
+
+    /// What the vulnerable wallet's owner signs to pay AMOUNT to the recipient
+    fn payment(&self) -> Vec<u8> {
+        PaymentAuthorization {
+            wallet: self.wallet.key,
+            recipient: self.recipient.key,
+            amount: AMOUNT,
+        }
+        .signing_bytes()
+        .unwrap()
+    }
+
+    /// What the secure wallet's owner signs to pay AMOUNT to the recipient under `nonce`
+    fn nonced_payment(&self, nonce: u64) -> Vec<u8> {
+        NoncedPaymentAuthorization {
+            wallet: self.wallet.key,
+            recipient: self.recipient.key,
+            amount: AMOUNT,
+            nonce,
+            expires_at: EXPIRES_AT,
+        }
+        .signing_bytes()
+        .unwrap()
+    }
+
+    fn vulnerable_relay(&self, signer: &Pubkey, message: &[u8]) -> Fixture {
+        Fixture::new()
+            .with("wallet", self.wallet.clone())
+            .with("recipient", self.recipient.clone())
+            .with("instructions", relayed(ed25519_instruction(signer, message)))
+            .with("relayer", TestAccount::signer())
+    }
+
+    /// The owner's authorization under `nonce`, relayed in a transaction of its own
+    fn secure_relay(&self, nonce: u64) -> Fixture {
+        self.secure_relay_signed(nonce, &self.owner.key, &self.nonced_payment(nonce))
+    }
+
+    fn secure_relay_signed(&self, nonce: u64, signer: &Pubkey, message: &[u8]) -> Fixture {
+        Fixture::new()
+            .with("wallet", self.wallet.clone())
+            .with("used_nonce", self.used_nonce(nonce))
+            .with("recipient", self.recipient.clone())
+            .with("instructions", relayed(ed25519_instruction(signer, message)))
+            .with("relayer", TestAccount::signer())
+            .with("system_program", TestAccount::system_program())
+            .args(instruction::SecureRelayPayment {
+                amount: AMOUNT,
+                nonce,
+                expires_at: EXPIRES_AT,
+            })
+    }
+
+    fn cancel(&self, nonce: u64) -> Fixture {
+        Fixture::new()
+            .with("wallet", self.wallet.clone())
+            .with("used_nonce", self.used_nonce(nonce))
+            .with("owner", self.owner.clone())
+            .with("system_program", TestAccount::system_program())
+            .args(instruction::CancelAuthorization { nonce })
+    }
+
+    /// Carry the wallet and recipient of a finished transaction into the next one
+    fn settle(&mut self, fixture: &Fixture) {
+        self.wallet = fixture.account("wallet").clone();
+        self.recipient = fixture.account("recipient").clone();
+    }
+}
+
+fn vulnerable_relay(fixture: &mut Fixture) -> ProgramResult {
+    execute!(fixture, instruction::VulnerableRelayPayment { amount: AMOUNT })
+}
+
+fn secure_relay(fixture: &mut Fixture, nonce: u64) -> ProgramResult {
+    execute!(
+        fixture,
+        instruction::SecureRelayPayment {
+            amount: AMOUNT,
+            nonce,
+            expires_at: EXPIRES_AT,
+        }
+    )
+}
+
+#[test]
+fn initialize_wallet_is_a_pda_of_its_owner() {
+    let owner = TestAccount::signer();
+    let (address, _) = Pubkey::find_program_address(&[b"wallet", owner.key.as_ref()], &crate::ID);
+    let fixture = Fixture::new()
+        .with("wallet", TestAccount::uninitialized(Wallet::SPACE).at(address))
+        .with("owner", owner.clone())
+        .with("system_program", TestAccount::system_program());
+    assert_seeds_violation!(InitializeWallet, fixture.clone(), "wallet");
+    assert_signer_violation!(InitializeWallet, fixture.clone(), "owner");
+
+    let mut fixture = fixture;
+    execute!(&mut fixture, instruction::InitializeWallet {}).unwrap();
+    assert_eq!(fixture.state::<Wallet>("wallet").owner, owner.key);
+}
+
+#[test]
+fn secure_relay_payment_checks_every_account() {
+    let world = World::new();
+    assert_seeds_violation!(SecureRelayPayment, world.secure_relay(0), "wallet");
+    assert_owner_violation!(SecureRelayPayment, world.secure_relay(0), "wallet");
+    assert_seeds_violation!(SecureRelayPayment, world.secure_relay(0), "used_nonce");
+    assert_constraint_violation!(
+        SecureRelayPayment,
+        world.secure_relay(0),
+        "instructions" => TestAccount::new(Pubkey::new_unique(), vec![0; 2]),
+        ErrorCode::ConstraintAddress
+    );
+    assert_signer_violation!(SecureRelayPayment, world.secure_relay(0), "relayer");
+}
+
+#[test]
+fn only_the_owner_cancels_authorizations() {
+    let world = World::new();
+    assert_signer_violation!(CancelAuthorization, world.cancel(0), "owner");
+    assert_seeds_violation!(CancelAuthorization, world.cancel(0), "used_nonce");
+    assert_constraint_violation!(
+        CancelAuthorization,
+        world.cancel(0),
+        "wallet" => |wallet| wallet.edit::<Wallet>(|wallet| wallet.owner = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+}
+
+#[test]
+fn vulnerable_relay_payment_pays_the_same_payload_in_every_transaction() {
+    let mut world = World::new();
+    let payload = world.payment();
+
+    // A new durable nonce value each time: a new transaction, the same payload
+    for _ in 0..3 {
+        let mut fixture = world.vulnerable_relay(&world.owner.key, &payload);
+        vulnerable_relay(&mut fixture).unwrap();
+        world.settle(&fixture);
+    }
+    assert_eq!(world.recipient.lamports, 3 * AMOUNT);
+}
+
+#[test]
+fn vulnerable_relay_payment_pays_a_payload_signed_long_ago() {
+    let world = World::new();
+    let mut fixture = world.vulnerable_relay(&world.owner.key, &world.payment());
+
+    // A year on, nothing about the payload has gone stale
+    warp_to(NOW + 365 * 24 * 60 * 60);
+    vulnerable_relay(&mut fixture).unwrap();
+    assert_eq!(fixture.account("recipient").lamports, AMOUNT);
+}
+
+#[test]
+fn vulnerable_relay_payment_still_checks_who_signed_what() {
+    let world = World::new();
+    let mut fixture = world.vulnerable_relay(&Pubkey::new_unique(), &world.payment());
+    assert_eq!(
+        vulnerable_relay(&mut fixture),
+        Err(Error::from(WalletError::WrongSigner).into())
+    );
+
+    let smaller = PaymentAuthorization {
+        wallet: world.wallet.key,
+        recipient: world.recipient.key,
+        amount: 1,
+    };
+    let mut fixture = world.vulnerable_relay(&world.owner.key, &smaller.signing_bytes().unwrap());
+    assert_eq!(
+        vulnerable_relay(&mut fixture),
+        Err(Error::from(WalletError::MessageMismatch).into())
+    );
+}
+
+#[test]
+fn secure_relay_payment_pays_each_nonce_once() {
+    let mut world = World::new();
+    let mut fixture = world.secure_relay(7);
+    secure_relay(&mut fixture, 7).unwrap();
+    assert_eq!(fixture.state::<UsedNonce>("used_nonce").nonce, 7);
+    world.settle(&fixture);
+
+    // The same payload in a new transaction finds its nonce spent
+    let mut replay = world.secure_relay(7);
+    replay
+        .account_mut("used_nonce")
+        .clone_from(fixture.account("used_nonce"));
+    assert_eq!(secure_relay(&mut replay, 7), Err(account_already_in_use()));
+    assert_eq!(replay.account("recipient").lamports, AMOUNT);
+}
+
+#[test]
+fn secure_relay_payment_accepts_nonces_in_any_order() {
+    let mut world = World::new();
+    for nonce in [5, 2, 9] {
+        let mut fixture = world.secure_relay(nonce);
+        secure_relay(&mut fixture, nonce).unwrap();
+        world.settle(&fixture);
+    }
+    assert_eq!(world.recipient.lamports, 3 * AMOUNT);
+}
+
+#[test]
+fn secure_relay_payment_rejects_an_expired_authorization() {
+    let world = World::new();
+    let mut fixture = world.secure_relay(0);
+    warp_to(EXPIRES_AT + 1);
+    assert_eq!(
+        secure_relay(&mut fixture, 0),
+        Err(Error::from(WalletError::AuthorizationExpired).into())
+    );
+}
+
+#[test]
+fn secure_relay_payment_rejects_a_payload_without_the_nonce_and_expiry() {
+    // The vulnerable wallet's payload, replayed against the secure instruction
+    let world = World::new();
+    let mut fixture = world.secure_relay_signed(0, &world.owner.key, &world.payment());
+    assert_eq!(
+        secure_relay(&mut fixture, 0),
+        Err(Error::from(WalletError::MessageMismatch).into())
+    );
+
+    // Signed for nonce 1, relayed as nonce 0
+    let mut fixture = world.secure_relay_signed(0, &world.owner.key, &world.nonced_payment(1));
+    assert_eq!(
+        secure_relay(&mut fixture, 0),
+        Err(Error::from(WalletError::MessageMismatch).into())
+    );
+}
+
+#[test]
+fn cancel_authorization_voids_a_payload_already_handed_out() {
+    let world = World::new();
+    let mut cancel = world.cancel(3);
+    execute!(&mut cancel, instruction::CancelAuthorization { nonce: 3 }).unwrap();
+
+    let mut fixture = world.secure_relay(3);
+    fixture
+        .account_mut("used_nonce")
+        .clone_from(cancel.account("used_nonce"));
+    assert_eq!(secure_relay(&mut fixture, 3), Err(account_already_in_use()));
+    assert_eq!(fixture.account("recipient").lamports, 0);
+}
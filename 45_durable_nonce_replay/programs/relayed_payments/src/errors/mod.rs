@@ -0,0 +1,21 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Expected an Ed25519 signature instruction before this instruction")]
+    MissingSignatureInstruction,
+    #[msg("Ed25519 instruction is malformed or references other instructions")]
+    InvalidSignatureInstruction,
+    #[msg("Authorization was not signed by the wallet's owner")]
+    WrongSigner,
+    #[msg("Signed message does not match this payment")]
+    MessageMismatch,
+    #[msg("Authorization has expired")]
+    AuthorizationExpired,
+    #[msg("Wallet has insufficient funds")]
+    InsufficientFunds,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
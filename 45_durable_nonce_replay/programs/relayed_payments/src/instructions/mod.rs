@@ -0,0 +1,54 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializeWallet<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = Wallet::SPACE,
+        seeds = [b"wallet", owner.key().as_ref()],
+        bump
+    )]
+    pub wallet: Account<'info, Wallet>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Only the owner cancels their own authorizations
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CancelAuthorization<'info> {
+    #[account(has_one = owner, seeds = [b"wallet", owner.key().as_ref()], bump = wallet.bump)]
+    pub wallet: Account<'info, Wallet>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = UsedNonce::SPACE,
+        seeds = [b"nonce", wallet.key().as_ref(), &nonce.to_le_bytes()],
+        bump
+    )]
+    pub used_nonce: Account<'info, UsedNonce>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,33 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+#[instruction(amount: u64, nonce: u64)]
+pub struct SecureRelayPayment<'info> {
+    #[account(mut, seeds = [b"wallet", wallet.owner.as_ref()], bump = wallet.bump)]
+    pub wallet: Account<'info, Wallet>,
+
+    // SECURITY: One account per (wallet, nonce); creating it is what spends the nonce
+    #[account(
+        init,
+        payer = relayer,
+        space = UsedNonce::SPACE,
+        seeds = [b"nonce", wallet.key().as_ref(), &nonce.to_le_bytes()],
+        bump
+    )]
+    pub used_nonce: Account<'info, UsedNonce>,
+
+    /// CHECK: Any recipient named in the signed authorization
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Address constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
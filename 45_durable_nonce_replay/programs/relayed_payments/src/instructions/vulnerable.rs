@@ -0,0 +1,20 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableRelayPayment<'info> {
+    #[account(mut, seeds = [b"wallet", wallet.owner.as_ref()], bump = wallet.bump)]
+    pub wallet: Account<'info, Wallet>,
+
+    /// CHECK: Any recipient named in the signed authorization
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Address constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    // VULNERABILITY: Nothing here, or anywhere else, records that the payload was used
+    pub relayer: Signer<'info>,
+}
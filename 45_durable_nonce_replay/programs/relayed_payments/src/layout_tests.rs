@@ -0,0 +1,25 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{UsedNonce, Wallet};
+
+#[test]
+fn wallet_layout_is_stable() {
+    let wallet = Wallet {
+        owner: Pubkey::new_from_array([1; 32]),
+        bump: 33,
+    };
+    assert_golden!("Wallet", golden::anchor(&wallet));
+}
+
+#[test]
+fn used_nonce_layout_is_stable() {
+    let used_nonce = UsedNonce {
+        wallet: Pubkey::new_from_array([3; 32]),
+        nonce: 0x4142434445464748,
+        bump: 81,
+    };
+    assert_golden!("UsedNonce", golden::anchor(&used_nonce));
+}
@@ -0,0 +1,276 @@
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use secref_guards::rent::minimum_balance;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Offset of the first signature-offsets entry in Ed25519 program instruction data
+const ED25519_OFFSETS_START: usize = 2;
+
+/// Size of one signature-offsets entry (seven little-endian u16 values)
+const ED25519_OFFSETS_SIZE: usize = 14;
+
+/// Instruction index the Ed25519 program uses for "this instruction"
+const ED25519_CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// A gasless payments wallet.
+///
+/// The owner never sends transactions. They sign payment authorizations
+/// off-chain, and a relayer submits each one, Ed25519 instruction first,
+/// paying the fee. Relayers commonly build those transactions on durable
+/// nonces, so a transaction can be prepared once and submitted whenever
+/// the relayer is ready rather than within the ~150 slots a recent
+/// blockhash lasts.
+#[feature_gated]
+#[program]
+pub mod relayed_payments {
+    use super::*;
+
+    /// Open the owner's wallet; fund it by transferring lamports to it
+    pub fn initialize_wallet(ctx: Context<InitializeWallet>) -> Result<()> {
+        let wallet = &mut ctx.accounts.wallet;
+        wallet.owner = ctx.accounts.owner.key();
+        wallet.bump = ctx.bumps.wallet;
+
+        msg!("Wallet opened for {}", wallet.owner);
+        Ok(())
+    }
+
+    /// Burn `nonce` before any relayer uses it, cancelling the authorization that carries it
+    ///
+    /// The on-chain counterpart of advancing a durable nonce account: a
+    /// payload that has already been signed and handed out can no longer
+    /// be executed.
+    pub fn cancel_authorization(ctx: Context<CancelAuthorization>, nonce: u64) -> Result<()> {
+        let used = &mut ctx.accounts.used_nonce;
+        used.wallet = ctx.accounts.wallet.key();
+        used.nonce = nonce;
+        used.bump = ctx.bumps.used_nonce;
+
+        msg!("Authorization {} cancelled", nonce);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Pay out an authorization the owner signed off-chain
+    ///
+    /// Security Issue: The signed payload names the wallet, the recipient
+    /// and the amount, and nothing that makes it single-use or short-lived.
+    /// That was left to the runtime: the relayer's transaction expires with
+    /// its blockhash, and the runtime never processes the same transaction
+    /// twice. Neither holds for the payload. A durable nonce never expires,
+    /// and after each use it advances to a new value, so the same Ed25519
+    /// instruction can be wrapped in a new durable-nonce transaction - with
+    /// a new signature the runtime has never seen - and paid again, by
+    /// anyone who has seen it, for as long as the wallet holds funds.
+    #[vulnerable]
+    pub fn vulnerable_relay_payment(ctx: Context<VulnerableRelayPayment>, amount: u64) -> Result<()> {
+        let wallet = &ctx.accounts.wallet;
+        let message = verified_ed25519_message(&ctx.accounts.instructions, &wallet.owner)?;
+
+        // VULNERABILITY: No nonce and no expiry - the same bytes authorize
+        // this payment in every transaction they are ever placed in
+        let authorization = PaymentAuthorization {
+            wallet: wallet.key(),
+            recipient: ctx.accounts.recipient.key(),
+            amount,
+        };
+        require!(message == authorization.signing_bytes()?, ErrorCode::MessageMismatch);
+
+        pay(&ctx.accounts.wallet, &ctx.accounts.recipient, amount)?;
+
+        msg!("Relayed {} lamports to {}", amount, authorization.recipient);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version, whose authorizations carry
+    // their own expiry and a nonce consumed on-chain.
+
+    /// SECURE: Pay out an authorization once, before it expires
+    ///
+    /// Security Fix: The owner signs an expiry and a nonce into every
+    /// authorization. The expiry is checked against the Clock, because a
+    /// durable-nonce transaction carrying the payload has no deadline of
+    /// its own. The `used_nonce` PDA is derived from the wallet and the
+    /// nonce, so `init` fails with the System Program's `AccountAlreadyInUse`
+    /// once the authorization has been paid or cancelled, whichever
+    /// transaction it arrives in. Nonces are independent of each other, so
+    /// relayers may submit authorizations in any order.
+    #[secure]
+    pub fn secure_relay_payment(
+        ctx: Context<SecureRelayPayment>,
+        amount: u64,
+        nonce: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        let wallet = &ctx.accounts.wallet;
+        let message = verified_ed25519_message(&ctx.accounts.instructions, &wallet.owner)?;
+
+        let authorization = NoncedPaymentAuthorization {
+            wallet: wallet.key(),
+            recipient: ctx.accounts.recipient.key(),
+            amount,
+            nonce,
+            expires_at,
+        };
+        require!(message == authorization.signing_bytes()?, ErrorCode::MessageMismatch);
+
+        // SECURITY: The payload's lifetime is the one its signer chose, not the transaction's
+        require!(
+            Clock::get()?.unix_timestamp <= expires_at,
+            ErrorCode::AuthorizationExpired
+        );
+
+        // SECURITY: The nonce PDA now exists, so this payload can never be paid again
+        let used = &mut ctx.accounts.used_nonce;
+        used.wallet = authorization.wallet;
+        used.nonce = nonce;
+        used.bump = ctx.bumps.used_nonce;
+
+        pay(&ctx.accounts.wallet, &ctx.accounts.recipient, amount)?;
+
+        msg!(
+            "Relayed {} lamports to {} under nonce {}",
+            amount,
+            authorization.recipient,
+            nonce
+        );
+        Ok(())
+    }
+}
+
+// ========================================
+// SIGNED MESSAGE FORMATS
+// ========================================
+
+/// What the vulnerable wallet's owner signs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PaymentAuthorization {
+    pub wallet: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+impl PaymentAuthorization {
+    /// VULNERABLE: The bytes signed for `vulnerable_relay_payment`
+    pub fn signing_bytes(&self) -> Result<Vec<u8>> {
+        Ok(self.try_to_vec()?)
+    }
+}
+
+/// What the secure wallet's owner signs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct NoncedPaymentAuthorization {
+    pub wallet: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    /// Chosen by the owner; each value authorizes at most one payment
+    pub nonce: u64,
+    /// Unix timestamp after which the authorization is void
+    pub expires_at: i64,
+}
+
+impl NoncedPaymentAuthorization {
+    /// SECURE: The bytes signed for `secure_relay_payment`
+    pub fn signing_bytes(&self) -> Result<Vec<u8>> {
+        Ok(self.try_to_vec()?)
+    }
+}
+
+// ========================================
+// SIGNATURE VERIFICATION
+// ========================================
+// Shared by both implementations; the vulnerability is entirely in what the
+// signed message leaves out, not in how the signature is checked.
+
+/// Return the message verified by the Ed25519 instruction preceding this one
+///
+/// The Ed25519 program has already checked the signature when this runs; this
+/// helper only confirms that the verified instruction exists, that it signed
+/// with `expected_signer`, and that every offset points into that instruction.
+fn verified_ed25519_message(instructions: &AccountInfo, expected_signer: &Pubkey) -> Result<Vec<u8>> {
+    let current = load_current_index_checked(instructions)?;
+    require!(current > 0, ErrorCode::MissingSignatureInstruction);
+    let instruction = load_instruction_at_checked(usize::from(current - 1), instructions)?;
+    require_keys_eq!(
+        instruction.program_id,
+        ed25519_program::ID,
+        ErrorCode::MissingSignatureInstruction
+    );
+
+    let data = &instruction.data;
+    require!(
+        data.len() >= ED25519_OFFSETS_START + ED25519_OFFSETS_SIZE && data[0] == 1,
+        ErrorCode::InvalidSignatureInstruction
+    );
+    let read_u16 = |index: usize| {
+        let at = ED25519_OFFSETS_START + index * 2;
+        u16::from_le_bytes([data[at], data[at + 1]])
+    };
+    let signature_instruction = read_u16(1);
+    let public_key_offset = usize::from(read_u16(2));
+    let public_key_instruction = read_u16(3);
+    let message_offset = usize::from(read_u16(4));
+    let message_size = usize::from(read_u16(5));
+    let message_instruction = read_u16(6);
+
+    // Offsets that point into other instructions would let the verified
+    // signature cover different bytes than the ones read here
+    require!(
+        signature_instruction == ED25519_CURRENT_INSTRUCTION
+            && public_key_instruction == ED25519_CURRENT_INSTRUCTION
+            && message_instruction == ED25519_CURRENT_INSTRUCTION,
+        ErrorCode::InvalidSignatureInstruction
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+    require!(public_key == expected_signer.as_ref(), ErrorCode::WrongSigner);
+
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+    Ok(message.to_vec())
+}
+
+/// Pay `amount` lamports from the wallet to `recipient`, keeping the wallet rent-exempt
+fn pay<'info>(wallet: &Account<'info, Wallet>, recipient: &AccountInfo<'info>, amount: u64) -> Result<()> {
+    let wallet_info = wallet.to_account_info();
+    let reserve = minimum_balance(wallet_info.data_len())?;
+    require!(
+        amount <= wallet_info.lamports().saturating_sub(reserve),
+        ErrorCode::InsufficientFunds
+    );
+
+    **wallet_info.try_borrow_mut_lamports()? -= amount;
+    **recipient.try_borrow_mut_lamports()? = recipient
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
@@ -0,0 +1,30 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Wallet {
+    /// Signs payment authorizations off-chain (32 bytes)
+    pub owner: Pubkey,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Wallet {
+    pub const SPACE: usize = 8 + 32 + 1;
+}
+
+/// Exists once the authorization carrying `nonce` has been paid or cancelled
+#[account]
+pub struct UsedNonce {
+    /// Wallet the nonce belongs to (32 bytes)
+    pub wallet: Pubkey,
+    /// The nonce itself (8 bytes)
+    pub nonce: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl UsedNonce {
+    pub const SPACE: usize = 8 + 32 + 8 + 1;
+}
@@ -0,0 +1,301 @@
+//! What each claim accepts as proof of holding the threshold, and the
+//! constraints that tie a stake to its club and member.
+//!
+//! A transaction that funds the member, claims and drains the member again
+//! is modelled by changing the member's lamports around a single
+//! `execute!`: the claim sees exactly what an instruction between those
+//! two transfers would.
+
+use anchor_lang::prelude::*;
+use secref_testkit::runtime::{account_already_in_use, warp_to};
+use secref_testkit::{
+    assert_constraint_violation, assert_seeds_violation, assert_signer_violation, execute, Fixture, TestAccount,
+};
+
+use crate::{instruction, Claim, Club, ErrorCode as ClubError, SecureClaimBonus, StakeRecord, Unstake};
+
+const THRESHOLD: u64 = 100_000_000_000;
+const BONUS: u64 = 1_000_000;
+const LOCK_PERIOD: i64 = 7 * 24 * 60 * 60;
+const NOW: i64 = 1_700_000_000;
+/// What a member's wallet holds of its own
+const POCKET_MONEY: u64 = 10_000_000;
+
+/// A club funded for ten bonuses
+struct World {
+    club: TestAccount,
+}
+
+impl World {
+    fn new() -> Self {
+        warp_to(NOW);
+        let authority = Pubkey::new_unique();
+        let (address, bump) = Pubkey::find_program_address(&[b"club", authority.as_ref()], &crate::ID);
+        let club = TestAccount::anchor(&Club {
+            authority,
+            threshold: THRESHOLD,
+            bonus: BONUS,
+            lock_period: LOCK_PERIOD,
+            bump,
+        })
+        .at(address)
+        .with_lamports(Rent::default().minimum_balance(Club::SPACE) + 10 * BONUS);
+        Self { club }
+    }
+
+    fn member() -> TestAccount {
+        TestAccount::signer().with_lamports(POCKET_MONEY)
+    }
+
+    fn pda(&self, seed: &[u8], member: &TestAccount) -> Pubkey {
+        Pubkey::find_program_address(&[seed, self.club.key.as_ref(), member.key.as_ref()], &crate::ID).0
+    }
+
+    fn vulnerable_claim(&self, member: &TestAccount) -> Fixture {
+        Fixture::new()
+            .with("club", self.club.clone())
+            .with(
+                "claim",
+                TestAccount::uninitialized(Claim::SPACE).at(self.pda(b"claim", member)),
+            )
+            .with("member", member.clone())
+            .with("system_program", TestAccount::system_program())
+    }
+
+    fn stake(&self, member: &TestAccount) -> Fixture {
+        Fixture::new()
+            .with("club", self.club.clone())
+            .with(
+                "stake",
+                TestAccount::uninitialized(StakeRecord::SPACE).at(self.pda(b"stake", member)),
+            )
+            .with("member", member.clone())
+            .with("system_program", TestAccount::system_program())
+    }
+
+    /// A stake of `amount` by `member`, as `stake` leaves it
+    fn staked(&self, member: &TestAccount, amount: u64) -> TestAccount {
+        let (address, bump) =
+            Pubkey::find_program_address(&[b"stake", self.club.key.as_ref(), member.key.as_ref()], &crate::ID);
+        let record = TestAccount::anchor(&StakeRecord {
+            club: self.club.key,
+            member: member.key,
+            amount,
+            locked_until: NOW + LOCK_PERIOD,
+            bump,
+        })
+        .at(address);
+        let lamports = record.lamports + amount;
+        record.with_lamports(lamports)
+    }
+
+    fn secure_claim(&self, member: &TestAccount, stake: TestAccount) -> Fixture {
+        Fixture::new()
+            .with("club", self.club.clone())
+            .with("stake", stake)
+            .with(
+                "claim",
+                TestAccount::uninitialized(Claim::SPACE).at(self.pda(b"claim", member)),
+            )
+            .with("member", member.clone())
+            .with("system_program", TestAccount::system_program())
+    }
+
+    fn unstake(member: &TestAccount, stake: TestAccount) -> Fixture {
+        Fixture::new().with("stake", stake).with("member", member.clone())
+    }
+}
+
+#[test]
+fn initialize_club_rejects_an_unusable_configuration() {
+    let authority = TestAccount::signer();
+    let (address, _) = Pubkey::find_program_address(&[b"club", authority.key.as_ref()], &crate::ID);
+    let fixture = Fixture::new()
+        .with("club", TestAccount::uninitialized(Club::SPACE).at(address))
+        .with("authority", authority)
+        .with("system_program", TestAccount::system_program());
+
+    for (threshold, bonus, lock_period) in [
+        (0, BONUS, LOCK_PERIOD),
+        (THRESHOLD, 0, LOCK_PERIOD),
+        (THRESHOLD, BONUS, 0),
+    ] {
+        assert_eq!(
+            execute!(
+                &mut fixture.clone(),
+                instruction::InitializeClub {
+                    threshold,
+                    bonus,
+                    lock_period
+                }
+            ),
+            Err(Error::from(ClubError::InvalidConfiguration).into())
+        );
+    }
+
+    let mut fixture = fixture;
+    execute!(
+        &mut fixture,
+        instruction::InitializeClub {
+            threshold: THRESHOLD,
+            bonus: BONUS,
+            lock_period: LOCK_PERIOD
+        }
+    )
+    .unwrap();
+    assert_eq!(fixture.state::<Club>("club").threshold, THRESHOLD);
+}
+
+#[test]
+fn vulnerable_claim_bonus_pays_on_lamports_held_for_one_instruction() {
+    let world = World::new();
+    let member = World::member();
+    assert_eq!(
+        execute!(
+            &mut world.vulnerable_claim(&member),
+            instruction::VulnerableClaimBonus {}
+        ),
+        Err(Error::from(ClubError::InsufficientBalance).into())
+    );
+
+    // Funded by the instruction before, drained by the instruction after
+    let mut fixture = world.vulnerable_claim(&member);
+    fixture.account_mut("member").lamports += THRESHOLD;
+    execute!(&mut fixture, instruction::VulnerableClaimBonus {}).unwrap();
+    fixture.account_mut("member").lamports -= THRESHOLD;
+
+    let claim_rent = Rent::default().minimum_balance(Claim::SPACE);
+    assert_eq!(fixture.account("member").lamports, POCKET_MONEY + BONUS - claim_rent);
+}
+
+#[test]
+fn vulnerable_claim_bonus_qualifies_every_sybil_with_the_same_lamports() {
+    let mut world = World::new();
+    let before = world.club.lamports;
+
+    // One pot of THRESHOLD lamports, passed through five fresh keypairs
+    for _ in 0..5 {
+        let mut fixture = world.vulnerable_claim(&World::member());
+        fixture.account_mut("member").lamports += THRESHOLD;
+        execute!(&mut fixture, instruction::VulnerableClaimBonus {}).unwrap();
+        fixture.account_mut("member").lamports -= THRESHOLD;
+        world.club = fixture.account("club").clone();
+    }
+    assert_eq!(world.club.lamports, before - 5 * BONUS);
+}
+
+#[test]
+fn stake_moves_the_lamports_out_of_the_members_hands_and_locks_them() {
+    let world = World::new();
+    let member = World::member().with_lamports(THRESHOLD + POCKET_MONEY);
+    let mut fixture = world.stake(&member);
+    execute!(&mut fixture, instruction::Stake { amount: THRESHOLD }).unwrap();
+
+    let stake = fixture.state::<StakeRecord>("stake");
+    assert_eq!(stake.amount, THRESHOLD);
+    assert_eq!(stake.locked_until, NOW + LOCK_PERIOD);
+    let rent = Rent::default().minimum_balance(StakeRecord::SPACE);
+    assert_eq!(fixture.account("stake").lamports, rent + THRESHOLD);
+    assert_eq!(fixture.account("member").lamports, POCKET_MONEY - rent);
+
+    assert_eq!(
+        execute!(&mut world.stake(&member), instruction::Stake { amount: 0 }),
+        Err(Error::from(ClubError::InvalidConfiguration).into())
+    );
+}
+
+#[test]
+fn secure_claim_bonus_ignores_the_members_balance() {
+    // A member flush with borrowed lamports, but no stake
+    let world = World::new();
+    let member = World::member().with_lamports(THRESHOLD + POCKET_MONEY);
+    let fixture = world.secure_claim(&member, TestAccount::system(0).at(world.pda(b"stake", &member)));
+    assert_eq!(
+        execute!(&mut fixture.clone(), instruction::SecureClaimBonus {}),
+        Err(Error::from(ErrorCode::AccountNotInitialized).into())
+    );
+
+    // A stake just short of the threshold, however much else the member holds
+    let mut fixture = world.secure_claim(&member, world.staked(&member, THRESHOLD - 1));
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureClaimBonus {}),
+        Err(Error::from(ClubError::InsufficientStake).into())
+    );
+}
+
+#[test]
+fn secure_claim_bonus_pays_a_staked_member_once() {
+    let world = World::new();
+    let member = World::member();
+    let mut fixture = world.secure_claim(&member, world.staked(&member, THRESHOLD));
+    execute!(&mut fixture, instruction::SecureClaimBonus {}).unwrap();
+    assert_eq!(fixture.account("club").lamports, world.club.lamports - BONUS);
+
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureClaimBonus {}),
+        Err(account_already_in_use())
+    );
+}
+
+#[test]
+fn secure_claim_bonus_only_counts_the_members_own_stake() {
+    let world = World::new();
+    let member = World::member();
+    let fixture = || world.secure_claim(&member, world.staked(&member, THRESHOLD));
+
+    // Someone else's stake, moved or at its own address
+    assert_seeds_violation!(SecureClaimBonus, fixture(), "stake");
+    let whale = World::member();
+    assert_constraint_violation!(
+        SecureClaimBonus,
+        fixture(),
+        "stake" => world.staked(&whale, THRESHOLD),
+        ErrorCode::ConstraintSeeds
+    );
+    assert_constraint_violation!(
+        SecureClaimBonus,
+        fixture(),
+        "stake" => |stake| stake.edit::<StakeRecord>(|stake| stake.club = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_seeds_violation!(SecureClaimBonus, fixture(), "claim");
+    assert_signer_violation!(SecureClaimBonus, fixture(), "member");
+}
+
+#[test]
+fn unstake_waits_out_the_lock_then_returns_everything() {
+    let world = World::new();
+    let member = World::member();
+    let stake = world.staked(&member, THRESHOLD);
+    let held = stake.lamports;
+
+    // In the same transaction as the stake, or any time before the lock ends
+    for now in [NOW, NOW + LOCK_PERIOD - 1] {
+        warp_to(now);
+        assert_eq!(
+            execute!(&mut World::unstake(&member, stake.clone()), instruction::Unstake {}),
+            Err(Error::from(ClubError::StakeLocked).into())
+        );
+    }
+
+    warp_to(NOW + LOCK_PERIOD);
+    let mut fixture = World::unstake(&member, stake);
+    execute!(&mut fixture, instruction::Unstake {}).unwrap();
+    assert_eq!(fixture.account("member").lamports, POCKET_MONEY + held);
+    assert_eq!(fixture.account("stake").lamports, 0);
+}
+
+#[test]
+fn only_the_staker_unstakes() {
+    let world = World::new();
+    let member = World::member();
+    let fixture = || World::unstake(&member, world.staked(&member, THRESHOLD));
+    assert_signer_violation!(Unstake, fixture(), "member");
+    assert_seeds_violation!(Unstake, fixture(), "stake");
+    assert_constraint_violation!(
+        Unstake,
+        fixture(),
+        "member" => World::member(),
+        ErrorCode::ConstraintSeeds
+    );
+}
@@ -0,0 +1,19 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Threshold, bonus, lock period and stake must all be positive")]
+    InvalidConfiguration,
+    #[msg("Member's balance is below the club's threshold")]
+    InsufficientBalance,
+    #[msg("Member's stake is below the club's threshold")]
+    InsufficientStake,
+    #[msg("Stake is still locked")]
+    StakeLocked,
+    #[msg("Club does not hold enough lamports for the bonus")]
+    InsufficientFunds,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
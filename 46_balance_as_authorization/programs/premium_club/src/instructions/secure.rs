@@ -0,0 +1,67 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(seeds = [b"club", club.authority.as_ref()], bump = club.bump)]
+    pub club: Account<'info, Club>,
+
+    #[account(
+        init,
+        payer = member,
+        space = StakeRecord::SPACE,
+        seeds = [b"stake", club.key().as_ref(), member.key().as_ref()],
+        bump
+    )]
+    pub stake: Account<'info, StakeRecord>,
+
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SecureClaimBonus<'info> {
+    #[account(mut, seeds = [b"club", club.authority.as_ref()], bump = club.bump)]
+    pub club: Account<'info, Club>,
+
+    // SECURITY: The member's own stake in this club, which only the program can release
+    #[account(
+        seeds = [b"stake", club.key().as_ref(), member.key().as_ref()],
+        bump = stake.bump,
+        has_one = club,
+        has_one = member
+    )]
+    pub stake: Account<'info, StakeRecord>,
+
+    #[account(
+        init,
+        payer = member,
+        space = Claim::SPACE,
+        seeds = [b"claim", club.key().as_ref(), member.key().as_ref()],
+        bump
+    )]
+    pub claim: Account<'info, Claim>,
+
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(
+        mut,
+        close = member,
+        seeds = [b"stake", stake.club.as_ref(), member.key().as_ref()],
+        bump = stake.bump,
+        has_one = member
+    )]
+    pub stake: Account<'info, StakeRecord>,
+
+    #[account(mut)]
+    pub member: Signer<'info>,
+}
@@ -0,0 +1,24 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableClaimBonus<'info> {
+    #[account(mut, seeds = [b"club", club.authority.as_ref()], bump = club.bump)]
+    pub club: Account<'info, Club>,
+
+    #[account(
+        init,
+        payer = member,
+        space = Claim::SPACE,
+        seeds = [b"claim", club.key().as_ref(), member.key().as_ref()],
+        bump
+    )]
+    pub claim: Account<'info, Claim>,
+
+    // VULNERABILITY: Only the balance of this account is checked, and only right now
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,40 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Claim, Club, StakeRecord};
+
+#[test]
+fn club_layout_is_stable() {
+    let club = Club {
+        authority: Pubkey::new_from_array([1; 32]),
+        threshold: 0x2122232425262728,
+        bonus: 0x3132333435363738,
+        lock_period: 0x4142434445464748,
+        bump: 81,
+    };
+    assert_golden!("Club", golden::anchor(&club));
+}
+
+#[test]
+fn stake_record_layout_is_stable() {
+    let stake_record = StakeRecord {
+        club: Pubkey::new_from_array([6; 32]),
+        member: Pubkey::new_from_array([7; 32]),
+        amount: 0x8182838485868788,
+        locked_until: 0x1292939495969798,
+        bump: 161,
+    };
+    assert_golden!("StakeRecord", golden::anchor(&stake_record));
+}
+
+#[test]
+fn claim_layout_is_stable() {
+    let claim = Claim {
+        club: Pubkey::new_from_array([11; 32]),
+        member: Pubkey::new_from_array([12; 32]),
+        bump: 209,
+    };
+    assert_golden!("Claim", golden::anchor(&claim));
+}
@@ -0,0 +1,204 @@
+use secref_guards::rent::minimum_balance;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// A members' club paying a one-time bonus, from lamports held by the club
+/// account, to members who show they have at least `threshold` lamports
+/// of skin in the game.
+#[feature_gated]
+#[program]
+pub mod premium_club {
+    use super::*;
+    #[secure]
+    use anchor_lang::system_program::{transfer, Transfer};
+
+    /// Open a club; fund its bonuses by transferring lamports to it
+    pub fn initialize_club(ctx: Context<InitializeClub>, threshold: u64, bonus: u64, lock_period: i64) -> Result<()> {
+        require!(threshold > 0 && bonus > 0, ErrorCode::InvalidConfiguration);
+        require!(lock_period > 0, ErrorCode::InvalidConfiguration);
+
+        let club = &mut ctx.accounts.club;
+        club.authority = ctx.accounts.authority.key();
+        club.threshold = threshold;
+        club.bonus = bonus;
+        club.lock_period = lock_period;
+        club.bump = ctx.bumps.club;
+
+        msg!(
+            "Club opened: {} lamports to qualify, {} lamport bonus",
+            threshold,
+            bonus
+        );
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Pay the bonus to a member whose account holds the threshold
+    ///
+    /// Security Issue: `member.lamports()` is what the account holds while
+    /// this instruction runs, nothing more. An instruction earlier in the
+    /// same transaction can move the threshold in - from another wallet of
+    /// the attacker's, or a flash loan - and one later in it can move the
+    /// lamports straight back out. The claim record stops the same key
+    /// claiming twice, but the same lamports can pass through a fresh
+    /// keypair in every transaction, so one pot of capital, held for no
+    /// time at all, qualifies any number of members. Checking a token
+    /// account's `amount` instead has exactly the same problem.
+    #[vulnerable]
+    pub fn vulnerable_claim_bonus(ctx: Context<VulnerableClaimBonus>) -> Result<()> {
+        let club = &ctx.accounts.club;
+
+        // VULNERABILITY: A balance at this instant proves nothing about who holds what
+        require!(
+            ctx.accounts.member.lamports() >= club.threshold,
+            ErrorCode::InsufficientBalance
+        );
+
+        record_claim(
+            &mut ctx.accounts.claim,
+            club.key(),
+            ctx.accounts.member.key(),
+            ctx.bumps.claim,
+        );
+        pay_bonus(&ctx.accounts.club, &ctx.accounts.member)?;
+
+        msg!(
+            "Bonus of {} lamports paid to {} on its balance",
+            club.bonus,
+            ctx.accounts.member.key()
+        );
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version, which qualifies members by
+    // a stake the program holds and locks rather than by a balance.
+
+    /// SECURE: Deposit `amount` lamports into the member's stake PDA, locked for the club's lock period
+    ///
+    /// The lamports leave the member's control for `lock_period` seconds,
+    /// which no instruction later in the same transaction can undo.
+    #[secure]
+    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidConfiguration);
+
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.member.to_account_info(),
+                    to: ctx.accounts.stake.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let stake = &mut ctx.accounts.stake;
+        stake.club = ctx.accounts.club.key();
+        stake.member = ctx.accounts.member.key();
+        stake.amount = amount;
+        stake.locked_until = now
+            .checked_add(ctx.accounts.club.lock_period)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        stake.bump = ctx.bumps.stake;
+
+        msg!("{} lamports staked until {}", amount, stake.locked_until);
+        Ok(())
+    }
+
+    /// SECURE: Pay the bonus to a member whose locked stake meets the threshold
+    ///
+    /// Security Fix: The stake PDA is derived from the club and the member,
+    /// and the program only lets lamports out of it once `locked_until` has
+    /// passed. Meeting the threshold therefore costs `threshold` lamports
+    /// held for the whole lock period, per member - funds moved in for one
+    /// transaction never reach the stake, and a stake made in this
+    /// transaction cannot be withdrawn in it.
+    #[secure]
+    pub fn secure_claim_bonus(ctx: Context<SecureClaimBonus>) -> Result<()> {
+        let club = &ctx.accounts.club;
+
+        // SECURITY: Qualification comes from a recorded, locked stake
+        require!(
+            ctx.accounts.stake.amount >= club.threshold,
+            ErrorCode::InsufficientStake
+        );
+
+        record_claim(
+            &mut ctx.accounts.claim,
+            club.key(),
+            ctx.accounts.member.key(),
+            ctx.bumps.claim,
+        );
+        pay_bonus(&ctx.accounts.club, &ctx.accounts.member)?;
+
+        msg!(
+            "Bonus of {} lamports paid to {} on its stake",
+            club.bonus,
+            ctx.accounts.member.key()
+        );
+        Ok(())
+    }
+
+    /// SECURE: Return the stake, and the PDA's rent, once the lock has passed
+    #[secure]
+    pub fn unstake(ctx: Context<Unstake>) -> Result<()> {
+        // SECURITY: The lock is what makes the stake cost something
+        require!(
+            Clock::get()?.unix_timestamp >= ctx.accounts.stake.locked_until,
+            ErrorCode::StakeLocked
+        );
+
+        // Anchor's `close = member` returns every lamport in the PDA on exit
+        msg!("{} lamports unstaked", ctx.accounts.stake.amount);
+        Ok(())
+    }
+}
+
+/// Record that `member` has had the club's bonus
+fn record_claim(claim: &mut Account<Claim>, club: Pubkey, member: Pubkey, bump: u8) {
+    claim.club = club;
+    claim.member = member;
+    claim.bump = bump;
+}
+
+/// Pay the bonus out of the program-owned club account, keeping it rent-exempt
+fn pay_bonus<'info>(club: &Account<'info, Club>, member: &AccountInfo<'info>) -> Result<()> {
+    let club_info = club.to_account_info();
+    let reserve = minimum_balance(club_info.data_len())?;
+    require!(
+        club.bonus <= club_info.lamports().saturating_sub(reserve),
+        ErrorCode::InsufficientFunds
+    );
+
+    **club_info.try_borrow_mut_lamports()? -= club.bonus;
+    **member.try_borrow_mut_lamports()? = member
+        .lamports()
+        .checked_add(club.bonus)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
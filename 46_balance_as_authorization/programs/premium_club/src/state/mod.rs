@@ -0,0 +1,55 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Club {
+    /// Opened the club (32 bytes)
+    pub authority: Pubkey,
+    /// Lamports a member must show to qualify (8 bytes)
+    pub threshold: u64,
+    /// Lamports paid once to each qualifying member (8 bytes)
+    pub bonus: u64,
+    /// Seconds a stake stays locked (8 bytes)
+    pub lock_period: i64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Club {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 8 + 1;
+}
+
+/// A member's stake; the PDA holds the staked lamports on top of its rent
+#[account]
+pub struct StakeRecord {
+    /// Club the stake qualifies for (32 bytes)
+    pub club: Pubkey,
+    /// Who staked, and who gets it back (32 bytes)
+    pub member: Pubkey,
+    /// Lamports staked (8 bytes)
+    pub amount: u64,
+    /// Unix timestamp before which the stake cannot be withdrawn (8 bytes)
+    pub locked_until: i64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl StakeRecord {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8 + 1;
+}
+
+/// Exists once a member has had the bonus
+#[account]
+pub struct Claim {
+    /// Club that paid (32 bytes)
+    pub club: Pubkey,
+    /// Member paid (32 bytes)
+    pub member: Pubkey,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Claim {
+    pub const SPACE: usize = 8 + 32 + 32 + 1;
+}
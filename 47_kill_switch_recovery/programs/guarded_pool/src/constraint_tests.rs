@@ -0,0 +1,430 @@
+//! Every mode transition, who may make it, and what each mode lets users
+//! do: the single-guardian halt that traps exits, and the quorum halt that
+//! lapses into withdraw-only mode until a timelocked resumption.
+
+use anchor_lang::prelude::*;
+use secref_guards::GuardError;
+use secref_testkit::runtime::warp_to;
+use secref_testkit::{
+    assert_has_one_violation, assert_seeds_violation, assert_signer_violation, execute, Fixture, TestAccount,
+};
+
+use crate::{
+    instruction, ErrorCode as PoolError, Mode, Pool, Position, Resume, VulnerableEmergencyWithdraw, Withdraw,
+    MAX_GUARDIANS,
+};
+
+const BALANCE: u64 = 1_000;
+const THRESHOLD: u8 = 2;
+const MAX_HALT: i64 = 3 * 86_400;
+const RESUME_DELAY: i64 = 2 * 86_400;
+const START: i64 = 1_700_000_000;
+
+/// An active pool holding one owner's BALANCE, behind 2 of 3 guardians
+struct World {
+    pool: TestAccount,
+    position: TestAccount,
+    governance: TestAccount,
+    owner: TestAccount,
+    guardians: [TestAccount; 3],
+}
+
+impl World {
+    fn new() -> Self {
+        warp_to(START);
+        let governance = TestAccount::signer();
+        let owner = TestAccount::signer();
+        let guardians = [TestAccount::signer(), TestAccount::signer(), TestAccount::signer()];
+        let (pool, pool_bump) = Pubkey::find_program_address(&[b"pool", governance.key.as_ref()], &crate::ID);
+        let (position, position_bump) =
+            Pubkey::find_program_address(&[b"position", pool.as_ref(), owner.key.as_ref()], &crate::ID);
+
+        // Room for the resumption a test may propose
+        let mut pool_account = TestAccount::anchor(&Pool {
+            governance: governance.key,
+            guardians: guardians.iter().map(|guardian| guardian.key).collect(),
+            threshold: THRESHOLD,
+            mode: Mode::Active,
+            halt_ends_at: 0,
+            max_halt: MAX_HALT,
+            resume_delay: RESUME_DELAY,
+            resume: None,
+            bump: pool_bump,
+        })
+        .at(pool);
+        pool_account.data.resize(Pool::SPACE, 0);
+        let lamports = pool_account.lamports + BALANCE;
+        Self {
+            pool: pool_account.with_lamports(lamports),
+            position: TestAccount::anchor(&Position {
+                pool,
+                owner: owner.key,
+                balance: BALANCE,
+                bump: position_bump,
+            })
+            .at(position),
+            governance,
+            owner: owner.with_lamports(BALANCE),
+            guardians,
+        }
+    }
+
+    fn deposit(&self) -> Fixture {
+        Fixture::new()
+            .with("pool", self.pool.clone())
+            .with("position", self.position.clone())
+            .with("owner", self.owner.clone())
+            .with("system_program", TestAccount::system_program())
+    }
+
+    fn withdraw(&self) -> Fixture {
+        Fixture::new()
+            .with("pool", self.pool.clone())
+            .with("position", self.position.clone())
+            .with("owner", self.owner.clone())
+    }
+
+    fn vulnerable_halt(&self, guardian: &TestAccount) -> Fixture {
+        Fixture::new()
+            .with("pool", self.pool.clone())
+            .with("guardian", guardian.clone())
+    }
+
+    fn emergency_withdraw(&self) -> Fixture {
+        Fixture::new()
+            .with("pool", self.pool.clone())
+            .with("destination", TestAccount::system(0))
+            .with("governance", self.governance.clone())
+    }
+
+    /// The pool, followed by `signers` as the signing guardians
+    fn guardians(&self, signers: &[&TestAccount]) -> Fixture {
+        const NAMES: [&str; 4] = ["first", "second", "third", "fourth"];
+        signers
+            .iter()
+            .zip(NAMES)
+            .fold(Fixture::new().with("pool", self.pool.clone()), |fixture, (signer, name)| {
+                fixture.with(name, (*signer).clone())
+            })
+    }
+
+    /// The pool with a quorum of its guardians
+    fn quorum(&self) -> Fixture {
+        self.guardians(&[&self.guardians[0], &self.guardians[1]])
+    }
+
+    fn resume(&self) -> Fixture {
+        Fixture::new()
+            .with("pool", self.pool.clone())
+            .with("governance", self.governance.clone())
+    }
+
+    /// Carry the pool an instruction wrote into the next one's fixture
+    fn keep(&mut self, fixture: &Fixture) {
+        self.pool = fixture.account("pool").clone();
+    }
+
+    fn pool(&self) -> Pool {
+        self.pool.state()
+    }
+
+    /// Halt the pool with a quorum at the current time
+    fn halted() -> Self {
+        let mut world = Self::new();
+        let mut fixture = world.quorum();
+        execute!(&mut fixture, instruction::SecureHalt {}).unwrap();
+        world.keep(&fixture);
+        world
+    }
+}
+
+fn initialize_fixture() -> Fixture {
+    let governance = TestAccount::signer();
+    let (pool, _) = Pubkey::find_program_address(&[b"pool", governance.key.as_ref()], &crate::ID);
+    Fixture::new()
+        .with("pool", TestAccount::uninitialized(Pool::SPACE).at(pool))
+        .with("governance", governance)
+        .with("system_program", TestAccount::system_program())
+}
+
+fn initialize(guardians: Vec<Pubkey>, threshold: u8, max_halt: i64) -> instruction::InitializePool {
+    instruction::InitializePool {
+        guardians,
+        threshold,
+        max_halt,
+        resume_delay: RESUME_DELAY,
+    }
+}
+
+#[test]
+fn initialize_pool_rejects_an_unusable_guardian_set() {
+    let keys = |count: usize| (0..count).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    let twice = Pubkey::new_unique();
+    for (guardians, threshold, max_halt, error) in [
+        (keys(MAX_GUARDIANS + 1), THRESHOLD, MAX_HALT, PoolError::TooManyGuardians),
+        (keys(3), 0, MAX_HALT, PoolError::InvalidThreshold),
+        (keys(3), 4, MAX_HALT, PoolError::InvalidThreshold),
+        (vec![twice, twice, Pubkey::new_unique()], THRESHOLD, MAX_HALT, PoolError::DuplicateGuardian),
+        (keys(3), THRESHOLD, 0, PoolError::InvalidDelay),
+    ] {
+        assert_eq!(
+            execute!(&mut initialize_fixture(), initialize(guardians, threshold, max_halt)),
+            Err(Error::from(error).into())
+        );
+    }
+
+    let mut fixture = initialize_fixture();
+    execute!(&mut fixture, initialize(keys(3), THRESHOLD, MAX_HALT)).unwrap();
+    assert_eq!(fixture.state::<Pool>("pool").mode, Mode::Active);
+}
+
+#[test]
+fn an_active_pool_takes_deposits_and_pays_withdrawals() {
+    let world = World::new();
+    let mut fixture = world.deposit();
+    execute!(&mut fixture, instruction::Deposit { amount: BALANCE }).unwrap();
+    assert_eq!(fixture.state::<Position>("position").balance, 2 * BALANCE);
+
+    let mut fixture = world.withdraw();
+    execute!(&mut fixture, instruction::Withdraw { amount: BALANCE }).unwrap();
+    assert_eq!(fixture.account("owner").lamports, 2 * BALANCE);
+}
+
+#[test]
+fn vulnerable_halt_lets_one_guardian_trap_every_exit() {
+    let mut world = World::new();
+    assert_eq!(
+        execute!(&mut world.vulnerable_halt(&TestAccount::signer()), instruction::VulnerableHalt {}),
+        Err(Error::from(PoolError::NotAGuardian).into())
+    );
+
+    let mut fixture = world.vulnerable_halt(&world.guardians[0]);
+    execute!(&mut fixture, instruction::VulnerableHalt {}).unwrap();
+    world.keep(&fixture);
+
+    // Years later, the owner still cannot leave
+    warp_to(START + 10 * 365 * 86_400);
+    assert_eq!(
+        execute!(&mut world.withdraw(), instruction::Withdraw { amount: BALANCE }),
+        Err(Error::from(PoolError::PoolHalted).into())
+    );
+}
+
+#[test]
+fn vulnerable_emergency_withdraw_empties_a_halted_pool_to_any_address() {
+    let mut world = World::new();
+    assert_eq!(
+        execute!(
+            &mut world.emergency_withdraw(),
+            instruction::VulnerableEmergencyWithdraw { amount: BALANCE }
+        ),
+        Err(Error::from(PoolError::PoolNotHalted).into())
+    );
+
+    let mut fixture = world.vulnerable_halt(&world.guardians[0]);
+    execute!(&mut fixture, instruction::VulnerableHalt {}).unwrap();
+    world.keep(&fixture);
+
+    let mut fixture = world.emergency_withdraw();
+    execute!(
+        &mut fixture,
+        instruction::VulnerableEmergencyWithdraw { amount: BALANCE }
+    )
+    .unwrap();
+    assert_eq!(fixture.account("destination").lamports, BALANCE);
+    // The owner's position still says BALANCE; the pool no longer holds it
+    assert_eq!(world.position.state::<Position>().balance, BALANCE);
+}
+
+#[test]
+fn vulnerable_resume_ends_a_halt_without_notice() {
+    let mut world = World::new();
+    let mut fixture = world.vulnerable_halt(&world.guardians[0]);
+    execute!(&mut fixture, instruction::VulnerableHalt {}).unwrap();
+    world.keep(&fixture);
+
+    let mut fixture = world.resume();
+    execute!(&mut fixture, instruction::VulnerableResume {}).unwrap();
+    assert_eq!(fixture.state::<Pool>("pool").mode, Mode::Active);
+}
+
+#[test]
+fn emergency_withdraw_and_resume_are_governances() {
+    let world = World::new();
+    // The pool's address is derived from its governance, so another key fails the seeds first
+    assert_has_one_violation!(
+        VulnerableEmergencyWithdraw,
+        world.emergency_withdraw(),
+        "governance",
+        ErrorCode::ConstraintSeeds
+    );
+    assert_signer_violation!(VulnerableEmergencyWithdraw, world.emergency_withdraw(), "governance");
+    assert_has_one_violation!(Resume, world.resume(), "governance", ErrorCode::ConstraintSeeds);
+    assert_signer_violation!(Resume, world.resume(), "governance");
+    assert_seeds_violation!(Resume, world.resume(), "pool");
+}
+
+#[test]
+fn secure_halt_needs_a_quorum_of_distinct_signing_guardians() {
+    let world = World::new();
+    let [first, second, _] = &world.guardians;
+    let outsider = TestAccount::signer();
+    let unsigned = second.clone().unsigned();
+    for signers in [
+        vec![first],
+        vec![first, first],
+        vec![first, &outsider],
+        vec![first, &unsigned],
+    ] {
+        assert_eq!(
+            execute!(&mut world.guardians(&signers), instruction::SecureHalt {}),
+            Err(Error::from(PoolError::NotEnoughGuardians).into())
+        );
+    }
+
+    let mut fixture = world.guardians(&[first, &outsider, second]);
+    execute!(&mut fixture, instruction::SecureHalt {}).unwrap();
+    let pool = fixture.state::<Pool>("pool");
+    assert_eq!(pool.mode, Mode::Halted);
+    assert_eq!(pool.halt_ends_at, START + MAX_HALT);
+}
+
+#[test]
+fn secure_halt_stops_everything_until_it_lapses_into_withdrawals() {
+    let world = World::halted();
+    assert_eq!(
+        execute!(&mut world.deposit(), instruction::Deposit { amount: BALANCE }),
+        Err(Error::from(PoolError::PoolNotActive).into())
+    );
+    warp_to(START + MAX_HALT - 1);
+    assert_eq!(
+        execute!(&mut world.withdraw(), instruction::Withdraw { amount: BALANCE }),
+        Err(Error::from(PoolError::PoolHalted).into())
+    );
+
+    // Nobody has to act for exits to reopen
+    warp_to(START + MAX_HALT);
+    assert_eq!(world.pool().mode_at(START + MAX_HALT), Mode::WithdrawOnly);
+    let mut fixture = world.withdraw();
+    execute!(&mut fixture, instruction::Withdraw { amount: BALANCE }).unwrap();
+    assert_eq!(fixture.account("owner").lamports, 2 * BALANCE);
+    assert_eq!(
+        execute!(&mut world.deposit(), instruction::Deposit { amount: BALANCE }),
+        Err(Error::from(PoolError::PoolNotActive).into())
+    );
+}
+
+#[test]
+fn secure_halt_cannot_be_renewed_to_keep_exits_closed() {
+    let world = World::halted();
+    assert_eq!(
+        execute!(&mut world.quorum(), instruction::SecureHalt {}),
+        Err(Error::from(PoolError::PoolNotActive).into())
+    );
+    warp_to(START + MAX_HALT);
+    assert_eq!(
+        execute!(&mut world.quorum(), instruction::SecureHalt {}),
+        Err(Error::from(PoolError::PoolNotActive).into())
+    );
+}
+
+#[test]
+fn secure_restrict_to_withdrawals_steps_down_or_ends_a_halt_early() {
+    let world = World::new();
+    let mut fixture = world.guardians(&[&world.guardians[2]]);
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureRestrictToWithdrawals {}),
+        Err(Error::from(PoolError::NotEnoughGuardians).into())
+    );
+
+    for mut world in [World::new(), World::halted()] {
+        let mut fixture = world.quorum();
+        execute!(&mut fixture, instruction::SecureRestrictToWithdrawals {}).unwrap();
+        world.keep(&fixture);
+        assert_eq!(world.pool().mode_at(START), Mode::WithdrawOnly);
+        execute!(&mut world.withdraw(), instruction::Withdraw { amount: BALANCE }).unwrap();
+    }
+}
+
+#[test]
+fn resume_is_proposed_then_executed_from_withdraw_only_after_the_delay() {
+    let mut world = World::halted();
+    assert_eq!(
+        execute!(&mut world.resume(), instruction::ExecuteResume {}),
+        Err(Error::from(PoolError::PoolHalted).into())
+    );
+    let mut fixture = world.resume();
+    execute!(&mut fixture, instruction::ProposeResume {}).unwrap();
+    world.keep(&fixture);
+
+    // The delay has passed, but the halt has not lapsed yet
+    warp_to(START + RESUME_DELAY);
+    assert_eq!(
+        execute!(&mut world.resume(), instruction::ExecuteResume {}),
+        Err(Error::from(PoolError::PoolHalted).into())
+    );
+
+    warp_to(START + MAX_HALT);
+    let mut fixture = world.resume();
+    execute!(&mut fixture, instruction::ExecuteResume {}).unwrap();
+    world.keep(&fixture);
+    assert_eq!(world.pool().mode_at(START + MAX_HALT), Mode::Active);
+    assert_eq!(world.pool().resume, None);
+    execute!(&mut world.deposit(), instruction::Deposit { amount: BALANCE }).unwrap();
+}
+
+#[test]
+fn resume_waits_out_the_announced_delay() {
+    let mut world = World::new();
+    assert_eq!(
+        execute!(&mut world.resume(), instruction::ProposeResume {}),
+        Err(Error::from(PoolError::PoolAlreadyActive).into())
+    );
+
+    let mut fixture = world.quorum();
+    execute!(&mut fixture, instruction::SecureRestrictToWithdrawals {}).unwrap();
+    world.keep(&fixture);
+    assert_eq!(
+        execute!(&mut world.resume(), instruction::ExecuteResume {}),
+        Err(Error::from(PoolError::NoResumeProposed).into())
+    );
+    let mut fixture = world.resume();
+    execute!(&mut fixture, instruction::ProposeResume {}).unwrap();
+    world.keep(&fixture);
+
+    warp_to(START + RESUME_DELAY - 1);
+    assert_eq!(
+        execute!(&mut world.resume(), instruction::ExecuteResume {}),
+        Err(Error::from(GuardError::IntentNotReady).into())
+    );
+    warp_to(START + RESUME_DELAY);
+    execute!(&mut world.resume(), instruction::ExecuteResume {}).unwrap();
+}
+
+#[test]
+fn guardians_veto_a_pending_resumption_by_restricting_again() {
+    let mut world = World::new();
+    let mut fixture = world.quorum();
+    execute!(&mut fixture, instruction::SecureRestrictToWithdrawals {}).unwrap();
+    world.keep(&fixture);
+    let mut fixture = world.resume();
+    execute!(&mut fixture, instruction::ProposeResume {}).unwrap();
+    world.keep(&fixture);
+
+    let mut fixture = world.quorum();
+    execute!(&mut fixture, instruction::SecureRestrictToWithdrawals {}).unwrap();
+    world.keep(&fixture);
+    warp_to(START + RESUME_DELAY);
+    assert_eq!(
+        execute!(&mut world.resume(), instruction::ExecuteResume {}),
+        Err(Error::from(PoolError::NoResumeProposed).into())
+    );
+}
+
+#[test]
+fn withdraw_is_the_owners() {
+    let world = World::new();
+    assert_has_one_violation!(Withdraw, world.withdraw(), "owner", ErrorCode::ConstraintSeeds);
+    assert_seeds_violation!(Withdraw, world.withdraw(), "position");
+    assert_signer_violation!(Withdraw, world.withdraw(), "owner");
+}
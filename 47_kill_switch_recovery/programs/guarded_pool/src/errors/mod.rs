@@ -0,0 +1,33 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("A pool can have at most MAX_GUARDIANS guardians")]
+    TooManyGuardians,
+    #[msg("Threshold must be between 1 and the number of guardians")]
+    InvalidThreshold,
+    #[msg("Guardians must be distinct")]
+    DuplicateGuardian,
+    #[msg("The halt must be positive and the resume delay non-negative")]
+    InvalidDelay,
+    #[msg("Signer is not one of the pool's guardians")]
+    NotAGuardian,
+    #[msg("Not enough guardians signed")]
+    NotEnoughGuardians,
+    #[msg("Pool is not active")]
+    PoolNotActive,
+    #[msg("Pool is halted")]
+    PoolHalted,
+    #[msg("Pool is not halted")]
+    PoolNotHalted,
+    #[msg("Pool is already active")]
+    PoolAlreadyActive,
+    #[msg("No resumption has been proposed")]
+    NoResumeProposed,
+    #[msg("Position or pool balance is too low")]
+    InsufficientBalance,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
@@ -0,0 +1,18 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureHalt<'info> {
+    // SECURITY: The signing guardians follow as `remaining_accounts`, counted by the handler
+    #[account(mut, seeds = [b"pool", pool.governance.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+}
+
+#[derive(Accounts)]
+pub struct Resume<'info> {
+    #[account(mut, seeds = [b"pool", governance.key().as_ref()], bump = pool.bump, has_one = governance)]
+    pub pool: Account<'info, Pool>,
+
+    pub governance: Signer<'info>,
+}
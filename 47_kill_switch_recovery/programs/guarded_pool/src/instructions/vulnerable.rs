@@ -0,0 +1,32 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableHalt<'info> {
+    #[account(mut, seeds = [b"pool", pool.governance.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    // VULNERABILITY: One guardian's signature is enough
+    pub guardian: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableEmergencyWithdraw<'info> {
+    #[account(mut, seeds = [b"pool", governance.key().as_ref()], bump = pool.bump, has_one = governance)]
+    pub pool: Account<'info, Pool>,
+
+    /// CHECK: VULNERABILITY - Any account governance chooses
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    pub governance: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableResume<'info> {
+    #[account(mut, seeds = [b"pool", governance.key().as_ref()], bump = pool.bump, has_one = governance)]
+    pub pool: Account<'info, Pool>,
+
+    pub governance: Signer<'info>,
+}
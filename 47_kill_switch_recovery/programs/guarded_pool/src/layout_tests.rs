@@ -0,0 +1,39 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_guards::intent::Intent;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Mode, Pool, Position};
+
+#[test]
+fn pool_layout_is_stable() {
+    let pool = Pool {
+        governance: Pubkey::new_from_array([1; 32]),
+        guardians: vec![Pubkey::new_from_array([2; 32]), Pubkey::new_from_array([3; 32])],
+        threshold: 65,
+        mode: Mode::Halted,
+        halt_ends_at: 0x5152535455565758,
+        max_halt: 0x6162636465666768,
+        resume_delay: 0x7172737475767778,
+        resume: Some(Intent {
+            proposer: Pubkey::new_from_array([40; 32]),
+            params_hash: [41; 32],
+            prepared_at: 0x6162636465666768,
+            executable_at: 0x7172737475767778,
+        }),
+        bump: 129,
+    };
+    assert_golden!("Pool", golden::anchor(&pool));
+}
+
+#[test]
+fn position_layout_is_stable() {
+    let position = Position {
+        pool: Pubkey::new_from_array([9; 32]),
+        owner: Pubkey::new_from_array([10; 32]),
+        balance: 0xb1b2b3b4b5b6b7b8,
+        bump: 193,
+    };
+    assert_golden!("Position", golden::anchor(&position));
+}
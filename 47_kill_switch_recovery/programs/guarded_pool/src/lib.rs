@@ -0,0 +1,314 @@
+use anchor_lang::system_program::{self, Transfer};
+#[cfg(feature = "secure")]
+use secref_guards::intent::hash_params;
+use secref_guards::intent::Intent;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Most guardians a pool can have
+pub const MAX_GUARDIANS: usize = 5;
+
+/// A lamport pool with per-user positions and an incident-response kill switch.
+///
+/// Users deposit into and withdraw from their own position. Guardians -
+/// keys watching the pool for exploits - can stop it when something goes
+/// wrong, and governance brings it back. The pool's mode says what is
+/// allowed: everything, withdrawals only, or nothing.
+#[feature_gated]
+#[program]
+pub mod guarded_pool {
+    use super::*;
+
+    /// Create a pool stopped by `threshold` of `guardians`
+    ///
+    /// A halt lasts at most `max_halt` seconds, and governance's resumption
+    /// waits `resume_delay` seconds after being proposed.
+    pub fn initialize_pool(
+        ctx: Context<InitializePool>,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+        max_halt: i64,
+        resume_delay: i64,
+    ) -> Result<()> {
+        require!(guardians.len() <= MAX_GUARDIANS, ErrorCode::TooManyGuardians);
+        require!(
+            threshold > 0 && usize::from(threshold) <= guardians.len(),
+            ErrorCode::InvalidThreshold
+        );
+        for (index, guardian) in guardians.iter().enumerate() {
+            require!(!guardians[..index].contains(guardian), ErrorCode::DuplicateGuardian);
+        }
+        require!(max_halt > 0 && resume_delay >= 0, ErrorCode::InvalidDelay);
+
+        let pool = &mut ctx.accounts.pool;
+        pool.governance = ctx.accounts.governance.key();
+        pool.guardians = guardians;
+        pool.threshold = threshold;
+        pool.mode = Mode::Active;
+        pool.halt_ends_at = 0;
+        pool.max_halt = max_halt;
+        pool.resume_delay = resume_delay;
+        pool.resume = None;
+        pool.bump = ctx.bumps.pool;
+
+        msg!(
+            "Pool initialized: {} of {} guardians halt it for at most {}s",
+            threshold,
+            ctx.accounts.pool.guardians.len(),
+            max_halt
+        );
+        Ok(())
+    }
+
+    /// Open the signer's position in the pool
+    pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.pool = ctx.accounts.pool.key();
+        position.owner = ctx.accounts.owner.key();
+        position.bump = ctx.bumps.position;
+        Ok(())
+    }
+
+    /// Deposit `amount` lamports into the signer's position; only while the pool is active
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(ctx.accounts.pool.mode_at(now) == Mode::Active, ErrorCode::PoolNotActive);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.pool.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        let position = &mut ctx.accounts.position;
+        position.balance = position
+            .balance
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    /// Withdraw `amount` lamports from the signer's position; in any mode but a halt
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(ctx.accounts.pool.mode_at(now) != Mode::Halted, ErrorCode::PoolHalted);
+
+        let position = &mut ctx.accounts.position;
+        position.balance = position
+            .balance
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientBalance)?;
+        pay_out(&ctx.accounts.pool, &ctx.accounts.owner.to_account_info(), amount)?;
+
+        msg!("Withdrew {} lamports", amount);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Halt the pool, as any one guardian, until governance resumes it
+    ///
+    /// Security Issue: One guardian key stops everything, withdrawals
+    /// included, and the halt has no end. Users' funds stay in the pool for
+    /// as long as governance chooses, and while they do, governance's
+    /// emergency withdrawal can move them anywhere. A kill switch that
+    /// traps exits is a rug: a compromised guardian and governance key, or
+    /// one insider holding both, take the pool with a halt and a "rescue".
+    #[vulnerable]
+    pub fn vulnerable_halt(ctx: Context<VulnerableHalt>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        // VULNERABILITY: A single guardian, not a quorum
+        require!(
+            pool.guardians.contains(ctx.accounts.guardian.key),
+            ErrorCode::NotAGuardian
+        );
+
+        // VULNERABILITY: The halt never lapses, so withdrawals stay blocked
+        pool.mode = Mode::Halted;
+        pool.halt_ends_at = i64::MAX;
+
+        msg!("Pool halted by {}", ctx.accounts.guardian.key());
+        Ok(())
+    }
+
+    /// VULNERABLE: Move `amount` out of a halted pool to `destination`; governance only
+    ///
+    /// Security Issue: Meant to rescue funds from an exploit in progress,
+    /// this is a withdrawal of everyone's deposits to an address
+    /// governance picks, available exactly when users cannot withdraw
+    /// their own.
+    #[vulnerable]
+    pub fn vulnerable_emergency_withdraw(ctx: Context<VulnerableEmergencyWithdraw>, amount: u64) -> Result<()> {
+        require!(ctx.accounts.pool.mode == Mode::Halted, ErrorCode::PoolNotHalted);
+
+        // VULNERABILITY: No position is debited; the funds are the users'
+        pay_out(&ctx.accounts.pool, &ctx.accounts.destination, amount)?;
+
+        msg!("Emergency withdrawal of {} lamports to {}", amount, ctx.accounts.destination.key());
+        Ok(())
+    }
+
+    /// VULNERABLE: Resume the pool at once; governance only
+    ///
+    /// Security Issue: Governance can end an incident the moment it
+    /// likes, with nobody warned first - including straight after a
+    /// rescue, or before the bug the guardians halted for is fixed.
+    #[vulnerable]
+    pub fn vulnerable_resume(ctx: Context<VulnerableResume>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        // VULNERABILITY: No timelock between deciding to resume and resuming
+        pool.mode = Mode::Active;
+        pool.halt_ends_at = 0;
+
+        msg!("Pool resumed");
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version: a quorum of guardians
+    // halts the pool for a bounded time, exits reopen when the halt lapses,
+    // and governance resumes full service only through a timelock.
+
+    /// SECURE: Halt an active pool for at most `max_halt` seconds; a quorum of guardians
+    ///
+    /// Security Fix: `threshold` distinct guardians must sign, passed as
+    /// `remaining_accounts`. A halt stops withdrawals too - the exploit may
+    /// be in them - but it lapses into withdraw-only mode by itself, and
+    /// only an active pool can be halted, so guardians cannot chain halts
+    /// to keep exits closed. There is no emergency withdrawal at all.
+    #[secure]
+    pub fn secure_halt(ctx: Context<SecureHalt>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let pool = &mut ctx.accounts.pool;
+        // SECURITY: A quorum, not one key
+        let signers = guardian_signers(pool, ctx.remaining_accounts);
+        require!(signers >= usize::from(pool.threshold), ErrorCode::NotEnoughGuardians);
+
+        // SECURITY: One bounded halt per incident
+        require!(pool.mode_at(now) == Mode::Active, ErrorCode::PoolNotActive);
+        pool.mode = Mode::Halted;
+        pool.halt_ends_at = now.checked_add(pool.max_halt).ok_or(ErrorCode::ArithmeticOverflow)?;
+        pool.resume = None;
+
+        msg!("Pool halted by {} guardians until {}", signers, pool.halt_ends_at);
+        Ok(())
+    }
+
+    /// SECURE: Restrict the pool to withdrawals; a quorum of guardians
+    ///
+    /// Security Fix: Guardians can step an active pool down, or end their
+    /// own halt early, but only toward users getting their funds out.
+    /// Restricting the pool also cancels a pending resumption, so
+    /// guardians can veto governance while exits stay open.
+    #[secure]
+    pub fn secure_restrict_to_withdrawals(ctx: Context<SecureHalt>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        let signers = guardian_signers(pool, ctx.remaining_accounts);
+        require!(signers >= usize::from(pool.threshold), ErrorCode::NotEnoughGuardians);
+
+        pool.mode = Mode::WithdrawOnly;
+        pool.halt_ends_at = 0;
+        pool.resume = None;
+
+        msg!("Pool restricted to withdrawals by {} guardians", signers);
+        Ok(())
+    }
+
+    /// SECURE: Phase 1, announce that the pool will resume; governance only
+    ///
+    /// Security Fix: Resuming is announced `resume_delay` seconds ahead,
+    /// so users who distrust the fix can leave while the pool is still
+    /// withdraw-only.
+    #[secure]
+    pub fn propose_resume(ctx: Context<Resume>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let pool = &mut ctx.accounts.pool;
+        require!(pool.mode_at(now) != Mode::Active, ErrorCode::PoolAlreadyActive);
+
+        let intent = Intent::prepare(pool.governance, resume_params(&pool.key()), now, pool.resume_delay)?;
+        msg!("Resumption proposed, executable at {}", intent.executable_at);
+        pool.resume = Some(intent);
+        Ok(())
+    }
+
+    /// SECURE: Phase 2, resume the pool once the delay and any halt are over; governance only
+    ///
+    /// Security Fix: Recovery is staged. A halted pool first lapses into
+    /// withdraw-only mode, and full service returns only from there, after
+    /// the announced delay.
+    #[secure]
+    pub fn execute_resume(ctx: Context<Resume>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let pool = &mut ctx.accounts.pool;
+        // SECURITY: Halted -> withdraw-only -> active, never straight from a halt
+        require!(pool.mode_at(now) == Mode::WithdrawOnly, ErrorCode::PoolHalted);
+        let intent = pool.resume.take().ok_or(ErrorCode::NoResumeProposed)?;
+        intent.assert_executable(&resume_params(&pool.key()), now)?;
+
+        pool.mode = Mode::Active;
+        pool.halt_ends_at = 0;
+
+        msg!("Pool resumed");
+        Ok(())
+    }
+}
+
+/// Parameters of a resumption, as recorded in its intent
+#[cfg(feature = "secure")]
+fn resume_params(pool: &Pubkey) -> [u8; 32] {
+    hash_params(&[b"resume", pool.as_ref()])
+}
+
+/// How many distinct guardians of `pool` signed, among `accounts`
+#[cfg(feature = "secure")]
+fn guardian_signers(pool: &Pool, accounts: &[AccountInfo]) -> usize {
+    let mut signed: Vec<&Pubkey> = Vec::new();
+    for account in accounts {
+        if account.is_signer && pool.guardians.contains(account.key) && !signed.contains(&account.key) {
+            signed.push(account.key);
+        }
+    }
+    signed.len()
+}
+
+/// Pay `amount` lamports out of the pool to `destination`
+fn pay_out<'info>(pool: &Account<'info, Pool>, destination: &AccountInfo<'info>, amount: u64) -> Result<()> {
+    let pool = pool.to_account_info();
+    **pool.try_borrow_mut_lamports()? = pool
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientBalance)?;
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
@@ -0,0 +1,65 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+/// What a pool allows
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Deposits and withdrawals
+    Active,
+    /// Withdrawals only
+    WithdrawOnly,
+    /// Nothing, until `halt_ends_at`
+    Halted,
+}
+
+#[account]
+pub struct Pool {
+    /// Sets the pool up, resumes it, and in the vulnerable version rescues its funds (32 bytes)
+    pub governance: Pubkey,
+    /// Keys that can stop the pool (4 + 32 * MAX_GUARDIANS bytes)
+    pub guardians: Vec<Pubkey>,
+    /// Guardians needed to halt or restrict the pool (1 byte)
+    pub threshold: u8,
+    /// Mode as last set; see `mode_at` (1 byte)
+    pub mode: Mode,
+    /// When a halt lapses into withdraw-only mode (8 bytes)
+    pub halt_ends_at: i64,
+    /// Longest a halt can last, in seconds (8 bytes)
+    pub max_halt: i64,
+    /// Seconds between proposing and executing a resumption (8 bytes)
+    pub resume_delay: i64,
+    /// Resumption proposed by governance, cleared by a halt or restriction (1 + 80 bytes)
+    pub resume: Option<Intent>,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Pool {
+    pub const SPACE: usize = 8 + 32 + 4 + 32 * MAX_GUARDIANS + 1 + 1 + 8 + 8 + 8 + (1 + Intent::SPACE) + 1;
+
+    /// The mode in force at `now`: a halt past its end is withdraw-only
+    pub fn mode_at(&self, now: i64) -> Mode {
+        if self.mode == Mode::Halted && now >= self.halt_ends_at {
+            Mode::WithdrawOnly
+        } else {
+            self.mode
+        }
+    }
+}
+
+#[account]
+pub struct Position {
+    /// Pool the position belongs to (32 bytes)
+    pub pool: Pubkey,
+    /// Depositor who may withdraw (32 bytes)
+    pub owner: Pubkey,
+    /// Lamports deposited and not yet withdrawn (8 bytes)
+    pub balance: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Position {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1;
+}
@@ -0,0 +1,13 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Deposit must be more than zero")]
+    ZeroDeposit,
+    #[msg("Deposit is too small to buy a share")]
+    ZeroShares,
+    #[msg("Shares overflowed")]
+    SharesOverflow,
+}
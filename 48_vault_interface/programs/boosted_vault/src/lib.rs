@@ -0,0 +1,85 @@
+//! A vault implementing the vault standard with shares priced by its assets.
+//!
+//! The vault's lamports above its rent reserve are its assets, yield
+//! included, and a deposit buys shares at `total_shares / assets`. Its
+//! receipts therefore count shares in different units from `steady_vault`'s,
+//! behind the same `deposit` instruction and `vault_standard::Receipt`.
+
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_lang::system_program::{self, Transfer};
+use secref_guards::rent::minimum_balance;
+use secref_prelude::*;
+use vault_standard::{Receipt, POSITION_SEED};
+
+declare_id!("BoostedVau1t1111111111111111111111111111111");
+
+pub mod errors;
+pub mod instructions;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+#[program]
+pub mod boosted_vault {
+    use super::*;
+
+    /// Create the vault
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.total_shares = 0;
+        vault.bump = ctx.bumps.vault;
+
+        msg!("Boosted vault initialized");
+        Ok(())
+    }
+
+    /// Deposit `amount` lamports for shares at the vault's current price
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroDeposit);
+        let shares = shares_for(&ctx.accounts.vault, amount)?;
+        require!(shares > 0, ErrorCode::ZeroShares);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.depositor.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.total_shares = vault.total_shares.checked_add(shares).ok_or(ErrorCode::SharesOverflow)?;
+        let position = &mut ctx.accounts.position;
+        position.owner = ctx.accounts.depositor.key();
+        position.shares = position.shares.checked_add(shares).ok_or(ErrorCode::SharesOverflow)?;
+        position.bump = ctx.bumps.position;
+
+        set_return_data(&Receipt { amount, shares }.try_to_vec()?);
+        msg!("Deposited {} for {} shares", amount, shares);
+        Ok(())
+    }
+}
+
+/// Shares `amount` buys: one per lamport into an empty vault, pro rata after that
+fn shares_for(vault: &Account<Vault>, amount: u64) -> Result<u64> {
+    let info = vault.to_account_info();
+    let reserve = minimum_balance(info.data_len())?;
+    let assets = info.lamports().saturating_sub(reserve);
+    if vault.total_shares == 0 || assets == 0 {
+        return Ok(amount);
+    }
+
+    (amount as u128)
+        .checked_mul(vault.total_shares as u128)
+        .map(|shares| shares / assets as u128)
+        .and_then(|shares| u64::try_from(shares).ok())
+        .ok_or(ErrorCode::SharesOverflow.into())
+}
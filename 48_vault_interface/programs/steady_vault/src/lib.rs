@@ -0,0 +1,65 @@
+//! A vault implementing the vault standard with one share per lamport.
+//!
+//! `deposit` is this program's implementation of the standard: its name,
+//! argument and accounts are the ones `vault_standard` fixes, and it
+//! answers with a `vault_standard::Receipt`.
+
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_lang::system_program::{self, Transfer};
+use secref_prelude::*;
+use vault_standard::{Receipt, POSITION_SEED};
+
+declare_id!("SteadyVau1t11111111111111111111111111111111");
+
+pub mod errors;
+pub mod instructions;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+#[program]
+pub mod steady_vault {
+    use super::*;
+
+    /// Create the vault
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.total_shares = 0;
+        vault.bump = ctx.bumps.vault;
+
+        msg!("Steady vault initialized");
+        Ok(())
+    }
+
+    /// Deposit `amount` lamports for as many shares
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroDeposit);
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.depositor.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let shares = amount;
+        let vault = &mut ctx.accounts.vault;
+        vault.total_shares = vault.total_shares.checked_add(shares).ok_or(ErrorCode::SharesOverflow)?;
+        let position = &mut ctx.accounts.position;
+        position.owner = ctx.accounts.depositor.key();
+        position.shares = position.shares.checked_add(shares).ok_or(ErrorCode::SharesOverflow)?;
+        position.bump = ctx.bumps.position;
+
+        set_return_data(&Receipt { amount, shares }.try_to_vec()?);
+        msg!("Deposited {} for {} shares", amount, shares);
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Vault {
+    /// Shares issued, one per lamport deposited (8 bytes)
+    pub total_shares: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Position {
+    /// Depositor the shares belong to (32 bytes)
+    pub owner: Pubkey,
+    /// Shares held (8 bytes)
+    pub shares: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
@@ -0,0 +1,231 @@
+//! The constraints on approving and dispatching to implementations, and
+//! what the router does with each implementation's answer: the honest
+//! vaults, the rogue one, and stand-ins that spend or report the wrong
+//! thing from an approved address.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::program::{invoke, set_return_data};
+use anchor_lang::solana_program::system_instruction;
+use anchor_lang::Discriminator;
+use secref_testkit::runtime::{register, Entry};
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_seeds_violation, assert_signer_violation, execute,
+    Fixture, TestAccount,
+};
+use vault_standard::{Receipt, DEPOSIT, POSITION_SEED};
+
+use crate::{instruction, Allocation, Approval, ApproveVault, Config, ErrorCode as RouterError, SecureAllocate};
+
+const TREASURY: u64 = 10_000_000_000;
+const AMOUNT: u64 = 1_000_000_000;
+
+fn treasury() -> TestAccount {
+    let (treasury, _) = Pubkey::find_program_address(&[b"treasury"], &crate::ID);
+    TestAccount::system(TREASURY).at(treasury)
+}
+
+fn approval(vault_program: Pubkey) -> TestAccount {
+    let (address, bump) = Pubkey::find_program_address(&[b"approval", vault_program.as_ref()], &crate::ID);
+    TestAccount::anchor(&Approval { vault_program, bump }).at(address)
+}
+
+/// `secure_allocate`'s accounts for a first deposit into `steady_vault`, with the vault program at `vault_program`
+///
+/// Only the program's address changes, so a stand-in registered there
+/// receives the deposit `steady_vault` would.
+fn allocate_fixture(vault_program: Pubkey) -> Fixture {
+    let (vault, vault_bump) = Pubkey::find_program_address(&[b"vault"], &steady_vault::ID);
+    let treasury = treasury();
+    let (position, _) =
+        Pubkey::find_program_address(&[POSITION_SEED, vault.as_ref(), treasury.key.as_ref()], &steady_vault::ID);
+    let (allocation, _) = Pubkey::find_program_address(&[b"allocation", vault_program.as_ref()], &crate::ID);
+    Fixture::new()
+        .with("treasury", treasury)
+        .with("allocation", TestAccount::uninitialized(8 + 32 + 8 + 8 + 1).at(allocation))
+        .with("approval", approval(vault_program))
+        .with("vault_program", TestAccount::program(vault_program))
+        .with(
+            "vault",
+            TestAccount::anchor(&steady_vault::Vault { total_shares: 0, bump: vault_bump }).at(vault),
+        )
+        .with("position", TestAccount::uninitialized(8 + 32 + 8 + 1).at(position))
+        .with("keeper", TestAccount::signer())
+        .with("system_program", TestAccount::system_program())
+}
+
+/// `vulnerable_allocate`'s accounts: the secure ones without the approval
+fn vulnerable_fixture(vault_program: Pubkey, vault: TestAccount) -> Fixture {
+    let fixture = allocate_fixture(vault_program);
+    let (allocation, _) = Pubkey::find_program_address(&[b"allocation", vault_program.as_ref()], &crate::ID);
+    Fixture::new()
+        .with("treasury", fixture.account("treasury").clone())
+        .with("allocation", TestAccount::uninitialized(8 + 32 + 8 + 8 + 1).at(allocation))
+        .with("vault_program", TestAccount::program(vault_program))
+        .with("vault", vault)
+        .with("position", fixture.account("position").clone())
+        .with("keeper", fixture.account("keeper").clone())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn approve_fixture() -> Fixture {
+    let admin = TestAccount::signer();
+    let (config, bump) = Pubkey::find_program_address(&[b"config"], &crate::ID);
+    let (approval, _) = Pubkey::find_program_address(&[b"approval", steady_vault::ID.as_ref()], &crate::ID);
+    Fixture::new()
+        .with("config", TestAccount::anchor(&Config { admin: admin.key, bump }).at(config))
+        .with("approval", TestAccount::uninitialized(8 + 32 + 1).at(approval))
+        .with("vault_program", TestAccount::program(steady_vault::ID))
+        .with("admin", admin)
+        .with("system_program", TestAccount::system_program())
+}
+
+/// Move `amount` from the depositor to the vault, as every honest implementation does
+fn deposit_honestly(accounts: &[AccountInfo], data: &[u8]) -> std::result::Result<u64, ProgramError> {
+    let amount = u64::from_le_bytes(data[8..16].try_into().map_err(|_| ProgramError::InvalidInstructionData)?);
+    let (vault, depositor, system_program) = (&accounts[0], &accounts[2], &accounts[4]);
+    invoke(
+        &system_instruction::transfer(depositor.key, vault.key, amount),
+        &[depositor.clone(), vault.clone(), system_program.clone()],
+    )?;
+    Ok(amount)
+}
+
+/// An implementation that takes everything the depositor holds and reports a deposit of what was asked
+fn greedy<'info>(_: &Pubkey, accounts: &'info [AccountInfo<'info>], data: &[u8]) -> ProgramResult {
+    let amount = u64::from_le_bytes(data[8..16].try_into().map_err(|_| ProgramError::InvalidInstructionData)?);
+    let (vault, depositor, system_program) = (&accounts[0], &accounts[2], &accounts[4]);
+    invoke(
+        &system_instruction::transfer(depositor.key, vault.key, depositor.lamports()),
+        &[depositor.clone(), vault.clone(), system_program.clone()],
+    )?;
+    set_return_data(&Receipt { amount, shares: amount }.try_to_vec()?);
+    Ok(())
+}
+
+/// An implementation that deposits honestly and answers with a receipt one lamport too large
+fn overstating<'info>(_: &Pubkey, accounts: &'info [AccountInfo<'info>], data: &[u8]) -> ProgramResult {
+    let amount = deposit_honestly(accounts, data)?;
+    set_return_data(&Receipt { amount: amount + 1, shares: amount + 1 }.try_to_vec()?);
+    Ok(())
+}
+
+/// An implementation that deposits honestly and answers with a bare share count
+fn terse<'info>(_: &Pubkey, accounts: &'info [AccountInfo<'info>], data: &[u8]) -> ProgramResult {
+    let amount = deposit_honestly(accounts, data)?;
+    set_return_data(&amount.to_le_bytes());
+    Ok(())
+}
+
+/// An implementation that deposits honestly and sells no shares for it
+fn worthless<'info>(_: &Pubkey, accounts: &'info [AccountInfo<'info>], data: &[u8]) -> ProgramResult {
+    let amount = deposit_honestly(accounts, data)?;
+    set_return_data(&Receipt { amount, shares: 0 }.try_to_vec()?);
+    Ok(())
+}
+
+#[test]
+fn every_implementation_is_called_with_the_standards_discriminator() {
+    assert_eq!(steady_vault::instruction::Deposit::DISCRIMINATOR, DEPOSIT);
+    assert_eq!(boosted_vault::instruction::Deposit::DISCRIMINATOR, DEPOSIT);
+    // Conformance is not trust: the rogue implementation is called the same way
+    assert_eq!(rogue_vault::instruction::Deposit::DISCRIMINATOR, DEPOSIT);
+}
+
+#[test]
+fn approve_vault_is_for_the_admin() {
+    assert_has_one_violation!(ApproveVault, approve_fixture(), "admin");
+    assert_signer_violation!(ApproveVault, approve_fixture(), "admin");
+    assert_seeds_violation!(ApproveVault, approve_fixture(), "config");
+}
+
+#[test]
+fn approve_vault_approves_a_program() {
+    assert_constraint_violation!(
+        ApproveVault,
+        approve_fixture(),
+        "vault_program" => |program| program.executable = false,
+        ErrorCode::ConstraintExecutable
+    );
+}
+
+#[test]
+fn secure_allocate_requires_an_approval_of_the_program_called() {
+    assert_constraint_violation!(
+        SecureAllocate,
+        allocate_fixture(steady_vault::ID),
+        "approval" => |approval| *approval = TestAccount::system(0).at(approval.key),
+        ErrorCode::AccountNotInitialized
+    );
+    assert_constraint_violation!(
+        SecureAllocate,
+        allocate_fixture(steady_vault::ID),
+        "approval" => approval(boosted_vault::ID),
+        ErrorCode::ConstraintSeeds
+    );
+    assert_constraint_violation!(
+        SecureAllocate,
+        allocate_fixture(steady_vault::ID),
+        "approval" => |approval| approval.owner = Pubkey::new_unique(),
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+    assert_constraint_violation!(
+        SecureAllocate,
+        allocate_fixture(steady_vault::ID),
+        "vault_program" => |program| program.executable = false,
+        ErrorCode::ConstraintExecutable
+    );
+}
+
+#[test]
+fn secure_allocate_spends_the_routers_treasury() {
+    assert_seeds_violation!(SecureAllocate, allocate_fixture(steady_vault::ID), "treasury");
+    assert_signer_violation!(SecureAllocate, allocate_fixture(steady_vault::ID), "keeper");
+}
+
+#[test]
+fn secure_allocate_records_an_approved_deposit() {
+    register(steady_vault::ID, steady_vault::entry);
+    let mut fixture = allocate_fixture(steady_vault::ID);
+
+    execute!(&mut fixture, instruction::SecureAllocate { amount: AMOUNT }).unwrap();
+
+    let allocation: Allocation = fixture.state("allocation");
+    assert_eq!((allocation.deposited, allocation.shares), (AMOUNT, AMOUNT));
+    assert_eq!(fixture.account("treasury").lamports, TREASURY - AMOUNT);
+    let position: steady_vault::Position = fixture.state("position");
+    assert_eq!((position.owner, position.shares), (fixture.key("treasury"), AMOUNT));
+}
+
+#[test]
+fn vulnerable_allocate_hands_the_treasury_to_any_implementation() {
+    register(rogue_vault::ID, rogue_vault::entry);
+    let mallory = TestAccount::system(0);
+    let mut fixture = vulnerable_fixture(rogue_vault::ID, mallory);
+
+    execute!(&mut fixture, instruction::VulnerableAllocate { amount: 1 }).unwrap();
+
+    assert_eq!(fixture.account("treasury").lamports, 0);
+    assert_eq!(fixture.account("vault").lamports, TREASURY);
+    let allocation: Allocation = fixture.state("allocation");
+    assert_eq!((allocation.deposited, allocation.shares), (1, rogue_vault::INFLATION));
+}
+
+#[test]
+fn secure_allocate_checks_what_an_approved_program_did_and_said() {
+    // What an approved address runs can change under an upgrade
+    let cases: [(Entry, RouterError); 4] = [
+        (greedy, RouterError::UnexpectedSpend),
+        (overstating, RouterError::ReceiptMismatch),
+        (terse, RouterError::MalformedReceipt),
+        (worthless, RouterError::NoShares),
+    ];
+    for (entry, expected) in cases {
+        let approved = Pubkey::new_unique();
+        register(approved, entry);
+        let mut fixture = allocate_fixture(approved);
+
+        let result = execute!(&mut fixture, instruction::SecureAllocate { amount: AMOUNT });
+        assert_eq!(result, Err(Error::from(expected).into()));
+    }
+}
@@ -0,0 +1,21 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("The implementation set no receipt")]
+    MissingReceipt,
+    #[msg("Return data was not set by the implementation that was called")]
+    UntrustedReceipt,
+    #[msg("Return data is not a single receipt")]
+    MalformedReceipt,
+    #[msg("Receipt is for a different amount than was deposited")]
+    ReceiptMismatch,
+    #[msg("Receipt reports no shares")]
+    NoShares,
+    #[msg("The implementation moved a different amount than was deposited")]
+    UnexpectedSpend,
+    #[msg("Allocation overflowed")]
+    AllocationOverflow,
+}
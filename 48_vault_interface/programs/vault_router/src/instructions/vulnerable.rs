@@ -0,0 +1,35 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableAllocate<'info> {
+    #[account(mut, seeds = [b"treasury"], bump)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = 8 + 32 + 8 + 8 + 1, // discriminator + vault_program + deposited + shares + bump
+        seeds = [b"allocation", vault_program.key().as_ref()],
+        bump
+    )]
+    pub allocation: Account<'info, Allocation>,
+
+    /// CHECK: Any program implementing the standard
+    #[account(executable)]
+    pub vault_program: UncheckedAccount<'info>,
+
+    /// CHECK: The implementation's vault; validated by the implementation
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: The treasury's position in that vault; validated by the implementation
+    #[account(mut)]
+    pub position: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
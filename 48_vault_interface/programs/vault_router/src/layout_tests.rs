@@ -0,0 +1,35 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Allocation, Approval, Config};
+
+#[test]
+fn config_layout_is_stable() {
+    let config = Config {
+        admin: Pubkey::new_from_array([1; 32]),
+        bump: 33,
+    };
+    assert_golden!("Config", golden::anchor(&config));
+}
+
+#[test]
+fn approval_layout_is_stable() {
+    let approval = Approval {
+        vault_program: Pubkey::new_from_array([3; 32]),
+        bump: 65,
+    };
+    assert_golden!("Approval", golden::anchor(&approval));
+}
+
+#[test]
+fn allocation_layout_is_stable() {
+    let allocation = Allocation {
+        vault_program: Pubkey::new_from_array([5; 32]),
+        deposited: 0x6162636465666768,
+        shares: 0x7172737475767778,
+        bump: 129,
+    };
+    assert_golden!("Allocation", golden::anchor(&allocation));
+}
@@ -0,0 +1,164 @@
+use anchor_lang::solana_program::program::get_return_data;
+use secref_prelude::*;
+use vault_standard::Receipt;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// A treasury that puts its lamports to work in vaults implementing `vault_standard`.
+///
+/// The router depends on the standard, not on any vault: allocating calls
+/// `deposit` on whichever program the keeper names and records the shares
+/// its receipt reports. Allocation is permissionless, as a keeper crank
+/// would be, so the keeper decides which implementation receives the
+/// treasury's lamports.
+#[feature_gated]
+#[program]
+pub mod vault_router {
+    use super::*;
+
+    /// Create the router, with `admin` deciding which implementations it trusts
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.bump = ctx.bumps.config;
+
+        msg!("Router initialized");
+        Ok(())
+    }
+
+    /// Approve the program `vault_program` as an implementation of the standard
+    pub fn approve_vault(ctx: Context<ApproveVault>) -> Result<()> {
+        let approval = &mut ctx.accounts.approval;
+        approval.vault_program = ctx.accounts.vault_program.key();
+        approval.bump = ctx.bumps.approval;
+
+        msg!("Approved {}", approval.vault_program);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Deposit `amount` of the treasury into any program that speaks the standard
+    ///
+    /// Security Issue: Conforming to an interface is something any program
+    /// can do. The keeper names the program, the treasury signs for the
+    /// whole CPI - not for `amount` - and the receipt is decoded from
+    /// whatever return data is left, whoever set it. A program written to
+    /// the standard takes the entire treasury and answers with a receipt
+    /// for as many shares as it likes.
+    #[vulnerable]
+    pub fn vulnerable_allocate(ctx: Context<VulnerableAllocate>, amount: u64) -> Result<()> {
+        let accounts = vault_standard::Deposit {
+            vault: ctx.accounts.vault.to_account_info(),
+            position: ctx.accounts.position.to_account_info(),
+            depositor: ctx.accounts.treasury.to_account_info(),
+            payer: ctx.accounts.keeper.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        // VULNERABILITY: no allowlist; any executable that takes these accounts gets the treasury's signature
+        deposit_from_treasury(&ctx.accounts.vault_program, accounts, ctx.bumps.treasury, amount)?;
+
+        // VULNERABILITY: the receipt's author, length and contents are all taken on trust
+        let (_, data) = get_return_data().ok_or(ErrorCode::MissingReceipt)?;
+        let receipt = Receipt::deserialize(&mut data.as_slice()).map_err(|_| ErrorCode::MalformedReceipt)?;
+
+        let vault_program = ctx.accounts.vault_program.key();
+        record(&mut ctx.accounts.allocation, vault_program, amount, &receipt, ctx.bumps.allocation)
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that dispatches only to
+    // approved implementations and checks what they did and said.
+
+    /// SECURE: Deposit `amount` of the treasury into an approved implementation
+    ///
+    /// Security Fix: The program must have an `Approval` from the admin,
+    /// since conformance is not trust. After the CPI, the treasury must be
+    /// down by exactly `amount`, and the receipt must be set by the program
+    /// that was called, be exactly one `Receipt`, echo `amount` and report
+    /// some shares. The allowlist decides who is trusted; the checks keep
+    /// an approved program's bug or upgrade from being recorded as a
+    /// deposit.
+    #[secure]
+    pub fn secure_allocate(ctx: Context<SecureAllocate>, amount: u64) -> Result<()> {
+        let before = ctx.accounts.treasury.lamports();
+        let accounts = vault_standard::Deposit {
+            vault: ctx.accounts.vault.to_account_info(),
+            position: ctx.accounts.position.to_account_info(),
+            depositor: ctx.accounts.treasury.to_account_info(),
+            payer: ctx.accounts.keeper.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        deposit_from_treasury(&ctx.accounts.vault_program, accounts, ctx.bumps.treasury, amount)?;
+
+        // SECURITY: The implementation spent what it was asked to and no more
+        let spent = before.saturating_sub(ctx.accounts.treasury.lamports());
+        require!(spent == amount, ErrorCode::UnexpectedSpend);
+
+        // SECURITY: Validate the returning program, the length and the echoed amount
+        let vault_program = ctx.accounts.vault_program.key();
+        let receipt = read_receipt(&vault_program, amount)?;
+
+        record(&mut ctx.accounts.allocation, vault_program, amount, &receipt, ctx.bumps.allocation)
+    }
+}
+
+/// Call `deposit` on `vault_program` with the treasury signing as depositor
+fn deposit_from_treasury<'info>(
+    vault_program: &UncheckedAccount<'info>,
+    accounts: vault_standard::Deposit<'info>,
+    treasury_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[b"treasury", &[treasury_bump]];
+    let signer_seeds = &[seeds];
+    vault_standard::deposit(
+        CpiContext::new_with_signer(vault_program.to_account_info(), accounts, signer_seeds),
+        amount,
+    )
+}
+
+/// Read the receipt `vault_program` set for a deposit of `amount`
+#[cfg(feature = "secure")]
+fn read_receipt(vault_program: &Pubkey, amount: u64) -> Result<Receipt> {
+    let (program_id, data) = get_return_data().ok_or(ErrorCode::MissingReceipt)?;
+    require_keys_eq!(program_id, *vault_program, ErrorCode::UntrustedReceipt);
+    require!(data.len() == Receipt::LEN, ErrorCode::MalformedReceipt);
+
+    let receipt = Receipt::try_from_slice(&data).map_err(|_| ErrorCode::MalformedReceipt)?;
+    require!(receipt.amount == amount, ErrorCode::ReceiptMismatch);
+    require!(receipt.shares > 0, ErrorCode::NoShares);
+    Ok(receipt)
+}
+
+/// Add a deposit and the shares its receipt reports to the treasury's allocation to `vault_program`
+fn record(allocation: &mut Allocation, vault_program: Pubkey, amount: u64, receipt: &Receipt, bump: u8) -> Result<()> {
+    allocation.vault_program = vault_program;
+    allocation.deposited = allocation.deposited.checked_add(amount).ok_or(ErrorCode::AllocationOverflow)?;
+    allocation.shares = allocation.shares.checked_add(receipt.shares).ok_or(ErrorCode::AllocationOverflow)?;
+    allocation.bump = bump;
+
+    msg!("Allocated {} to {} for {} shares", amount, vault_program, receipt.shares);
+    Ok(())
+}
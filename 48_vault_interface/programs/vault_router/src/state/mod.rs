@@ -0,0 +1,31 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Config {
+    /// Decides which implementations are approved (32 bytes)
+    pub admin: Pubkey,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Approval {
+    /// The approved implementation (32 bytes)
+    pub vault_program: Pubkey,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Allocation {
+    /// Implementation the treasury deposited into (32 bytes)
+    pub vault_program: Pubkey,
+    /// Lamports deposited (8 bytes)
+    pub deposited: u64,
+    /// Shares its receipts reported (8 bytes)
+    pub shares: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
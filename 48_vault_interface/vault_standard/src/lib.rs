@@ -0,0 +1,97 @@
+//! The vault standard: one instruction any vault program can implement, so
+//! a caller can deposit into all of them the same way.
+//!
+//! A program implements the standard by exposing an instruction named
+//! `deposit` that takes a single `u64`, expects the accounts of [`Deposit`]
+//! in that order, and answers with a [`Receipt`] through return data.
+//! Anchor derives an instruction's discriminator from its name alone, so
+//! every implementation is called with the same bytes, [`DEPOSIT`] followed
+//! by the amount. Callers build the CPI with [`deposit`] and never depend on
+//! an implementation's crate.
+//!
+//! Conforming to the standard says nothing about behaving. Any program can
+//! take the same accounts and answer with a well-formed receipt; which
+//! implementations a caller trusts, and what it checks in their answers,
+//! is up to the caller.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+
+/// `deposit`'s discriminator: the first 8 bytes of SHA-256 of `global:deposit`
+pub const DEPOSIT: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+
+/// Seed of a depositor's position in an implementation's vault, followed by the vault and the depositor
+pub const POSITION_SEED: &[u8] = b"position";
+
+/// The accounts of `deposit`, in the order every implementation takes them
+pub struct Deposit<'info> {
+    /// The implementation's vault, which receives the lamports
+    pub vault: AccountInfo<'info>,
+    /// The depositor's shares in `vault`, created on first deposit
+    pub position: AccountInfo<'info>,
+    /// Signs for the lamports deposited
+    pub depositor: AccountInfo<'info>,
+    /// Signs for the rent of a new position
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+impl ToAccountMetas for Deposit<'_> {
+    fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(*self.vault.key, false),
+            AccountMeta::new(*self.position.key, false),
+            AccountMeta::new(*self.depositor.key, true),
+            AccountMeta::new(*self.payer.key, true),
+            AccountMeta::new_readonly(*self.system_program.key, false),
+        ]
+    }
+}
+
+impl<'info> ToAccountInfos<'info> for Deposit<'info> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![
+            self.vault.clone(),
+            self.position.clone(),
+            self.depositor.clone(),
+            self.payer.clone(),
+            self.system_program.clone(),
+        ]
+    }
+}
+
+/// What `deposit` answers, through return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Receipt {
+    /// Lamports that were deposited (8 bytes)
+    pub amount: u64,
+    /// Shares of the vault they bought (8 bytes)
+    pub shares: u64,
+}
+
+impl Receipt {
+    /// Serialized size of a receipt
+    pub const LEN: usize = 8 + 8; // amount + shares
+}
+
+/// Deposit `amount` into whichever implementation `ctx.program` is
+///
+/// Remaining accounts are forwarded. The implementation's answer is left
+/// in return data for the caller to read and check.
+pub fn deposit<'info>(ctx: CpiContext<'_, '_, '_, 'info, Deposit<'info>>, amount: u64) -> Result<()> {
+    let mut accounts = ctx.accounts.to_account_metas(None);
+    accounts.extend(ctx.remaining_accounts.to_account_metas(None));
+    let mut data = DEPOSIT.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    let instruction = Instruction {
+        program_id: ctx.program.key(),
+        accounts,
+        data,
+    };
+
+    let mut infos = ctx.accounts.to_account_infos();
+    infos.extend(ctx.remaining_accounts);
+    infos.push(ctx.program);
+    invoke_signed(&instruction, &infos, ctx.signer_seeds).map_err(Into::into)
+}
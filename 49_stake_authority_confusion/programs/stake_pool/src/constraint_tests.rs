@@ -0,0 +1,350 @@
+//! Which stake accounts each deposit path credits: the vulnerable one any
+//! account whose staker is the pool, the secure one only accounts the pool
+//! takes both authorities of, free of any lockup.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::stake::instruction::StakeError;
+use anchor_lang::solana_program::stake::state::{Authorized, Lockup, StakeStateV2};
+use secref_testkit::runtime::warp_to;
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_owner_violation, assert_seeds_violation,
+    assert_signer_violation, execute, Fixture, TestAccount,
+};
+
+use crate::{instruction, ErrorCode as PoolError, Pool, Position, SecureDepositStake, WithdrawStake};
+
+const STAKE: u64 = 5_000_000_000;
+const START: i64 = 1_700_000_000;
+const YEAR: i64 = 365 * 86_400;
+
+/// A pool holding Alice's STAKE in a stake account it controls, with an empty position for Mallory
+struct World {
+    pool: TestAccount,
+    stake_authority: Pubkey,
+    withdraw_authority: Pubkey,
+    alice: TestAccount,
+    alices_position: TestAccount,
+    alices_stake: TestAccount,
+    mallory: TestAccount,
+    mallorys_position: TestAccount,
+}
+
+impl World {
+    fn new() -> Self {
+        warp_to(START);
+        let manager = Pubkey::new_unique();
+        let (pool, pool_bump) = Pubkey::find_program_address(&[b"pool", manager.as_ref()], &crate::ID);
+        let (stake_authority, _) = Pubkey::find_program_address(&[b"staker", pool.as_ref()], &crate::ID);
+        let (withdraw_authority, _) = Pubkey::find_program_address(&[b"withdrawer", pool.as_ref()], &crate::ID);
+        let (alice, mallory) = (TestAccount::signer(), TestAccount::signer());
+        let position = |owner: &TestAccount, balance: u64| {
+            let (key, bump) =
+                Pubkey::find_program_address(&[b"position", pool.as_ref(), owner.key.as_ref()], &crate::ID);
+            TestAccount::anchor(&Position {
+                pool,
+                owner: owner.key,
+                balance,
+                bump,
+            })
+            .at(key)
+        };
+
+        Self {
+            pool: TestAccount::anchor(&Pool {
+                manager,
+                total_staked: STAKE,
+                bump: pool_bump,
+            })
+            .at(pool),
+            stake_authority,
+            withdraw_authority,
+            alices_position: position(&alice, STAKE),
+            alices_stake: stake_account(stake_authority, withdraw_authority, Lockup::default()),
+            mallorys_position: position(&mallory, 0),
+            alice,
+            mallory,
+        }
+    }
+
+    fn vulnerable_deposit(&self, stake: &TestAccount) -> Fixture {
+        Fixture::new()
+            .with("pool", self.pool.clone())
+            .with("position", self.mallorys_position.clone())
+            .with("stake", stake.clone())
+            .with("stake_authority", TestAccount::system(0).at(self.stake_authority))
+            .with("owner", self.mallory.clone())
+    }
+
+    fn secure_deposit(&self, stake: &TestAccount) -> Fixture {
+        Fixture::new()
+            .with("pool", self.pool.clone())
+            .with("position", self.mallorys_position.clone())
+            .with("stake", stake.clone())
+            .with("stake_authority", TestAccount::system(0).at(self.stake_authority))
+            .with("withdraw_authority", TestAccount::system(0).at(self.withdraw_authority))
+            .with("owner", self.mallory.clone())
+            .with("clock", TestAccount::clock_sysvar())
+            .with("stake_program", TestAccount::stake_program())
+    }
+
+    /// `owner` redeeming from `stake`
+    fn withdraw(&self, position: &TestAccount, owner: &TestAccount, stake: &TestAccount) -> Fixture {
+        Fixture::new()
+            .with("pool", self.pool.clone())
+            .with("position", position.clone())
+            .with("stake", stake.clone())
+            .with("withdraw_authority", TestAccount::system(0).at(self.withdraw_authority))
+            .with("owner", owner.clone())
+            .with("clock", TestAccount::clock_sysvar())
+            .with("stake_history", TestAccount::stake_history_sysvar())
+            .with("stake_program", TestAccount::stake_program())
+    }
+
+    /// Alice redeeming her whole position from her own stake account
+    fn alices_withdrawal(&self) -> Fixture {
+        self.withdraw(&self.alices_position, &self.alice, &self.alices_stake)
+    }
+
+    /// Carry what a deposit wrote into the next instruction's fixture
+    fn keep_deposit(&mut self, fixture: &Fixture) -> TestAccount {
+        self.pool = fixture.account("pool").clone();
+        self.mallorys_position = fixture.account("position").clone();
+        fixture.account("stake").clone()
+    }
+}
+
+/// An undelegated stake account holding STAKE, with `staker` and `withdrawer` as its authorities
+fn stake_account(staker: Pubkey, withdrawer: Pubkey, lockup: Lockup) -> TestAccount {
+    TestAccount::stake_account(Authorized { staker, withdrawer }, lockup, STAKE)
+}
+
+/// Locked until `unix_timestamp`, releasable early by `custodian`
+fn locked_until(unix_timestamp: i64, custodian: Pubkey) -> Lockup {
+    Lockup {
+        unix_timestamp,
+        epoch: 0,
+        custodian,
+    }
+}
+
+fn authorized(stake: &TestAccount) -> Authorized {
+    StakeStateV2::deserialize(&mut &stake.data[..])
+        .expect("a stake account")
+        .authorized()
+        .expect("an initialized stake account")
+}
+
+#[test]
+fn withdraw_stake_pays_out_of_a_pool_controlled_stake_account() {
+    let world = World::new();
+    let mut fixture = world.alices_withdrawal();
+    execute!(&mut fixture, instruction::WithdrawStake { amount: STAKE }).unwrap();
+    assert_eq!(fixture.account("owner").lamports, world.alice.lamports + STAKE);
+    assert_eq!(fixture.state::<Position>("position").balance, 0);
+    assert_eq!(fixture.state::<Pool>("pool").total_staked, 0);
+
+    // A partial withdrawal must leave the account rent-exempt
+    let reserve = Rent::default().minimum_balance(StakeStateV2::size_of());
+    let mut fixture = world.alices_withdrawal();
+    assert_eq!(
+        execute!(&mut fixture, instruction::WithdrawStake { amount: STAKE - reserve + 1 }),
+        Err(ProgramError::InsufficientFunds)
+    );
+    assert_eq!(
+        execute!(&mut world.alices_withdrawal(), instruction::WithdrawStake { amount: STAKE + 1 }),
+        Err(Error::from(PoolError::InsufficientBalance).into())
+    );
+}
+
+#[test]
+fn vulnerable_deposit_credits_stake_whose_withdrawer_is_the_depositor() {
+    let mut world = World::new();
+    let mallorys_stake = stake_account(world.stake_authority, world.mallory.key, Lockup::default());
+    let mut fixture = world.vulnerable_deposit(&mallorys_stake);
+    execute!(&mut fixture, instruction::VulnerableDepositStake {}).unwrap();
+    let mallorys_stake = world.keep_deposit(&fixture);
+    assert_eq!(world.mallorys_position.state::<Position>().balance, STAKE);
+    // Mallory can still take the staker back and withdraw; the pool cannot
+    assert_eq!(authorized(&mallorys_stake).withdrawer, world.mallory.key);
+
+    // She redeems her credit out of Alice's stake...
+    let mut fixture = world.withdraw(&world.mallorys_position, &world.mallory, &world.alices_stake);
+    execute!(&mut fixture, instruction::WithdrawStake { amount: STAKE }).unwrap();
+    assert_eq!(fixture.account("owner").lamports, world.mallory.lamports + STAKE);
+    world.pool = fixture.account("pool").clone();
+
+    // ...and Alice's position is left backed by an account the pool cannot withdraw from
+    assert_eq!(
+        execute!(
+            &mut world.withdraw(&world.alices_position, &world.alice, &mallorys_stake),
+            instruction::WithdrawStake { amount: STAKE }
+        ),
+        Err(ProgramError::MissingRequiredSignature)
+    );
+}
+
+#[test]
+fn vulnerable_deposit_credits_the_same_stake_account_twice() {
+    let mut world = World::new();
+    let mallorys_stake = stake_account(world.stake_authority, world.mallory.key, Lockup::default());
+    for credited in [STAKE, 2 * STAKE] {
+        let mut fixture = world.vulnerable_deposit(&mallorys_stake);
+        execute!(&mut fixture, instruction::VulnerableDepositStake {}).unwrap();
+        world.keep_deposit(&fixture);
+        assert_eq!(world.mallorys_position.state::<Position>().balance, credited);
+    }
+}
+
+#[test]
+fn vulnerable_deposit_credits_locked_stake_as_withdrawable() {
+    let mut world = World::new();
+    let lockup = locked_until(START + 10 * YEAR, world.mallory.key);
+    let locked = stake_account(world.stake_authority, world.withdraw_authority, lockup);
+    let mut fixture = world.vulnerable_deposit(&locked);
+    execute!(&mut fixture, instruction::VulnerableDepositStake {}).unwrap();
+    let locked = world.keep_deposit(&fixture);
+    assert_eq!(world.mallorys_position.state::<Position>().balance, STAKE);
+
+    // The pool is the withdrawer, but the lockup holds for a decade
+    assert_eq!(
+        execute!(
+            &mut world.withdraw(&world.mallorys_position, &world.mallory, &locked),
+            instruction::WithdrawStake { amount: STAKE }
+        ),
+        Err(ProgramError::Custom(StakeError::LockupInForce as u32))
+    );
+    execute!(
+        &mut world.withdraw(&world.mallorys_position, &world.mallory, &world.alices_stake),
+        instruction::WithdrawStake { amount: STAKE }
+    )
+    .unwrap();
+}
+
+#[test]
+fn vulnerable_deposit_still_checks_the_staker() {
+    let world = World::new();
+    let mallorys_stake = stake_account(world.mallory.key, world.mallory.key, Lockup::default());
+    assert_eq!(
+        execute!(
+            &mut world.vulnerable_deposit(&mallorys_stake),
+            instruction::VulnerableDepositStake {}
+        ),
+        Err(Error::from(PoolError::NotDelegatedToPool).into())
+    );
+}
+
+#[test]
+fn secure_deposit_moves_both_authorities_to_the_pools_pdas() {
+    let mut world = World::new();
+    let mallorys_stake = stake_account(world.mallory.key, world.mallory.key, Lockup::default());
+    let mut fixture = world.secure_deposit(&mallorys_stake);
+    execute!(&mut fixture, instruction::SecureDepositStake {}).unwrap();
+    let taken = world.keep_deposit(&fixture);
+    assert_eq!(
+        authorized(&taken),
+        Authorized {
+            staker: world.stake_authority,
+            withdrawer: world.withdraw_authority,
+        }
+    );
+    assert_eq!(world.mallorys_position.state::<Position>().balance, STAKE);
+    assert_eq!(world.pool.state::<Pool>().total_staked, 2 * STAKE);
+
+    // Alice can redeem from it, and Mallory cannot deposit it again
+    execute!(
+        &mut world.withdraw(&world.alices_position, &world.alice, &taken),
+        instruction::WithdrawStake { amount: STAKE }
+    )
+    .unwrap();
+    assert_eq!(
+        execute!(&mut world.secure_deposit(&taken), instruction::SecureDepositStake {}),
+        Err(ProgramError::MissingRequiredSignature)
+    );
+}
+
+#[test]
+fn secure_deposit_needs_the_depositor_to_hold_the_withdrawer() {
+    let world = World::new();
+    // Mallory is the staker of an account Alice withdraws from
+    let alices = stake_account(world.mallory.key, world.alice.key, Lockup::default());
+    assert_eq!(
+        execute!(&mut world.secure_deposit(&alices), instruction::SecureDepositStake {}),
+        Err(ProgramError::MissingRequiredSignature)
+    );
+    // Staked to the pool but withdrawable by Alice, as the vulnerable deposit accepts
+    let half_delegated = stake_account(world.stake_authority, world.alice.key, Lockup::default());
+    assert_eq!(
+        execute!(&mut world.secure_deposit(&half_delegated), instruction::SecureDepositStake {}),
+        Err(ProgramError::MissingRequiredSignature)
+    );
+}
+
+#[test]
+fn secure_deposit_refuses_stake_under_a_lockup() {
+    let world = World::new();
+    let mallory = world.mallory.key;
+    let locked = stake_account(mallory, mallory, locked_until(START + 10 * YEAR, mallory));
+    assert_eq!(
+        execute!(&mut world.secure_deposit(&locked), instruction::SecureDepositStake {}),
+        Err(Error::from(PoolError::LockupInForce).into())
+    );
+
+    // Once the lockup has passed, the custodian has no say
+    let expired = stake_account(mallory, mallory, locked_until(START, mallory));
+    execute!(&mut world.secure_deposit(&expired), instruction::SecureDepositStake {}).unwrap();
+}
+
+#[test]
+fn deposits_need_an_initialized_stake_account() {
+    let world = World::new();
+    let mut uninitialized = stake_account(world.mallory.key, world.mallory.key, Lockup::default());
+    uninitialized.data.fill(0);
+    assert_eq!(
+        execute!(&mut world.secure_deposit(&uninitialized), instruction::SecureDepositStake {}),
+        Err(Error::from(PoolError::NotAStakeAccount).into())
+    );
+    assert_eq!(
+        execute!(
+            &mut world.vulnerable_deposit(&uninitialized),
+            instruction::VulnerableDepositStake {}
+        ),
+        Err(Error::from(PoolError::NotAStakeAccount).into())
+    );
+}
+
+#[test]
+fn secure_deposit_accounts_are_checked() {
+    let world = World::new();
+    let fixture = || world.secure_deposit(&stake_account(world.mallory.key, world.mallory.key, Lockup::default()));
+    assert_owner_violation!(SecureDepositStake, fixture(), "stake");
+    assert_seeds_violation!(SecureDepositStake, fixture(), "stake_authority");
+    assert_seeds_violation!(SecureDepositStake, fixture(), "withdraw_authority");
+    assert_signer_violation!(SecureDepositStake, fixture(), "owner");
+    assert_constraint_violation!(
+        SecureDepositStake,
+        fixture(),
+        "clock" => |clock| clock.key = Pubkey::new_unique(),
+        ErrorCode::ConstraintAddress
+    );
+    assert_constraint_violation!(
+        SecureDepositStake,
+        fixture(),
+        "stake_program" => TestAccount::system_program(),
+        ErrorCode::InvalidProgramId
+    );
+}
+
+#[test]
+fn withdraw_stake_is_the_position_owners() {
+    let world = World::new();
+    assert_has_one_violation!(WithdrawStake, world.alices_withdrawal(), "owner", ErrorCode::ConstraintSeeds);
+    assert_seeds_violation!(WithdrawStake, world.alices_withdrawal(), "position");
+    assert_seeds_violation!(WithdrawStake, world.alices_withdrawal(), "withdraw_authority");
+    assert_signer_violation!(WithdrawStake, world.alices_withdrawal(), "owner");
+    assert_constraint_violation!(
+        WithdrawStake,
+        world.alices_withdrawal(),
+        "stake_history" => |history| history.key = Pubkey::new_unique(),
+        ErrorCode::ConstraintAddress
+    );
+}
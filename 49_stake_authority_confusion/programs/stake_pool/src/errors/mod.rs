@@ -0,0 +1,17 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Stake account is not initialized")]
+    NotAStakeAccount,
+    #[msg("Stake account's staker is not the pool's stake authority")]
+    NotDelegatedToPool,
+    #[msg("Stake account is under a lockup")]
+    LockupInForce,
+    #[msg("Position or pool balance is too low")]
+    InsufficientBalance,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
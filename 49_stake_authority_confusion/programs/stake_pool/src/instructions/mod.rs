@@ -0,0 +1,89 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use anchor_lang::solana_program::sysvar;
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(
+        init,
+        payer = manager,
+        space = Pool::SPACE,
+        seeds = [b"pool", manager.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut)]
+    pub manager: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    #[account(seeds = [b"pool", pool.manager.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = Position::SPACE,
+        seeds = [b"position", pool.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    #[account(mut, seeds = [b"pool", pool.manager.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [b"position", pool.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner
+    )]
+    pub position: Account<'info, Position>,
+
+    /// Any of the pool's stake accounts; the Stake program checks the withdrawer
+    #[account(mut)]
+    pub stake: Account<'info, StakeAccount>,
+
+    /// CHECK: PDA signing as the stake accounts' withdrawer
+    #[account(seeds = [b"withdrawer", pool.key().as_ref()], bump)]
+    pub withdraw_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: The clock sysvar, by address
+    #[account(address = sysvar::clock::ID)]
+    pub clock: UncheckedAccount<'info>,
+
+    /// CHECK: The stake history sysvar, by address
+    #[account(address = sysvar::stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    pub stake_program: Program<'info, Stake>,
+}
@@ -0,0 +1,38 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureDepositStake<'info> {
+    #[account(mut, seeds = [b"pool", pool.manager.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [b"position", pool.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner
+    )]
+    pub position: Account<'info, Position>,
+
+    // SECURITY: Its authorities are moved to the pool by CPI, which the depositor signs
+    #[account(mut)]
+    pub stake: Account<'info, StakeAccount>,
+
+    /// CHECK: PDA that becomes the staker; can delegate and deactivate, never withdraw
+    #[account(seeds = [b"staker", pool.key().as_ref()], bump)]
+    pub stake_authority: UncheckedAccount<'info>,
+
+    /// CHECK: PDA that becomes the withdrawer; only `withdraw_stake` signs with it
+    #[account(seeds = [b"withdrawer", pool.key().as_ref()], bump)]
+    pub withdraw_authority: UncheckedAccount<'info>,
+
+    // SECURITY: The stake account's current staker and withdrawer
+    pub owner: Signer<'info>,
+
+    /// CHECK: The clock sysvar, by address
+    #[account(address = sysvar::clock::ID)]
+    pub clock: UncheckedAccount<'info>,
+
+    pub stake_program: Program<'info, Stake>,
+}
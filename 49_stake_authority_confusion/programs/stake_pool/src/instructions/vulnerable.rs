@@ -0,0 +1,26 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableDepositStake<'info> {
+    #[account(mut, seeds = [b"pool", pool.manager.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [b"position", pool.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner
+    )]
+    pub position: Account<'info, Position>,
+
+    // VULNERABILITY: Only the staker is compared with the pool, in the handler
+    pub stake: Account<'info, StakeAccount>,
+
+    /// CHECK: PDA the depositor has made the stake account's staker
+    #[account(seeds = [b"staker", pool.key().as_ref()], bump)]
+    pub stake_authority: UncheckedAccount<'info>,
+
+    pub owner: Signer<'info>,
+}
@@ -0,0 +1,27 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Pool, Position};
+
+#[test]
+fn pool_layout_is_stable() {
+    let pool = Pool {
+        manager: Pubkey::new_from_array([1; 32]),
+        total_staked: 0x2122232425262728,
+        bump: 49,
+    };
+    assert_golden!("Pool", golden::anchor(&pool));
+}
+
+#[test]
+fn position_layout_is_stable() {
+    let position = Position {
+        pool: Pubkey::new_from_array([4; 32]),
+        owner: Pubkey::new_from_array([5; 32]),
+        balance: 0x6162636465666768,
+        bump: 113,
+    };
+    assert_golden!("Position", golden::anchor(&position));
+}
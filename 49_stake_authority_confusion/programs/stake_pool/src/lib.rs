@@ -0,0 +1,196 @@
+use anchor_spl::stake::{self, Stake, StakeAccount, Withdraw};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// A liquid-staking-lite pool: users hand it native stake accounts and redeem lamports from any of them.
+///
+/// A deposit credits the depositor's position with the stake account's
+/// lamports, and the pool takes the account over. A withdrawal debits a
+/// position and has the Stake program pay out of one of the pool's stake
+/// accounts, signed by the pool's withdraw authority. The pool has two
+/// authorities, both PDAs: a stake authority for staking decisions, and a
+/// withdraw authority that alone can move lamports.
+///
+/// Stake accounts stay undelegated here, so withdrawals are immediate;
+/// a real pool delegates them with its stake authority and deactivates
+/// them before paying out.
+#[feature_gated]
+#[program]
+pub mod stake_pool {
+    use super::*;
+    #[secure]
+    use anchor_lang::solana_program::stake::state::StakeAuthorize;
+    #[secure]
+    use anchor_spl::stake::Authorize;
+
+    /// Create a pool run by `manager`
+    pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        pool.manager = ctx.accounts.manager.key();
+        pool.total_staked = 0;
+        pool.bump = ctx.bumps.pool;
+
+        msg!("Stake pool initialized for {}", pool.manager);
+        Ok(())
+    }
+
+    /// Open the signer's position in the pool
+    pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.pool = ctx.accounts.pool.key();
+        position.owner = ctx.accounts.owner.key();
+        position.balance = 0;
+        position.bump = ctx.bumps.position;
+        Ok(())
+    }
+
+    /// Redeem `amount` lamports of the signer's position from one of the pool's stake accounts
+    ///
+    /// The Stake program only pays out if the pool's withdraw authority
+    /// is the account's withdrawer and no lockup is in force, so which
+    /// stake accounts the pool really controls is decided at deposit.
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>, amount: u64) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.balance = position
+            .balance
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientBalance)?;
+        let pool = &mut ctx.accounts.pool;
+        pool.total_staked = pool
+            .total_staked
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientBalance)?;
+
+        let pool_key = pool.key();
+        let seeds: &[&[u8]] = &[b"withdrawer", pool_key.as_ref(), &[ctx.bumps.withdraw_authority]];
+        stake::withdraw(
+            CpiContext::new_with_signer(
+                ctx.accounts.stake_program.to_account_info(),
+                Withdraw {
+                    stake: ctx.accounts.stake.to_account_info(),
+                    withdrawer: ctx.accounts.withdraw_authority.to_account_info(),
+                    to: ctx.accounts.owner.to_account_info(),
+                    clock: ctx.accounts.clock.to_account_info(),
+                    stake_history: ctx.accounts.stake_history.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+            None,
+        )?;
+
+        msg!("Withdrew {} lamports from stake account {}", amount, ctx.accounts.stake.key());
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Credit the signer with a stake account they have delegated to the pool
+    ///
+    /// Security Issue: The deposit checks the account's staker, the
+    /// authority that only delegates and deactivates. Whoever holds the
+    /// withdrawer still owns the lamports: they can take the staker back,
+    /// deactivate and withdraw, keeping the credit the pool gave them and
+    /// redeeming it from other depositors' stake. The lockup is ignored
+    /// too, so a depositor can hand the pool stake that cannot be
+    /// withdrawn for years - and, as its custodian, extend that lockup at
+    /// will - in exchange for credit redeemable at once.
+    #[vulnerable]
+    pub fn vulnerable_deposit_stake(ctx: Context<VulnerableDepositStake>) -> Result<()> {
+        let authorized = ctx.accounts.stake.authorized().ok_or(ErrorCode::NotAStakeAccount)?;
+        // VULNERABILITY: The staker is checked; the withdrawer, who owns the lamports, is not
+        require_keys_eq!(
+            authorized.staker,
+            ctx.accounts.stake_authority.key(),
+            ErrorCode::NotDelegatedToPool
+        );
+        // VULNERABILITY: No lockup check; locked stake is credited as if withdrawable
+
+        let lamports = ctx.accounts.stake.to_account_info().lamports();
+        credit(&mut ctx.accounts.pool, &mut ctx.accounts.position, lamports)?;
+
+        msg!("Deposited stake account {} worth {} lamports", ctx.accounts.stake.key(), lamports);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version: the pool takes both
+    // authorities itself, each to its own PDA, and refuses locked stake.
+
+    /// SECURE: Take over a stake account the signer holds both authorities of, and credit them
+    ///
+    /// Security Fix: The pool moves the staker and the withdrawer to its
+    /// own PDAs by CPI, with the depositor signing as both, so nobody but
+    /// the pool can move the lamports it credited. The authorities stay
+    /// split: the stake authority can never withdraw, whatever an
+    /// instruction signing with it does. Stake under a lockup is refused,
+    /// since only its custodian could release or extend it.
+    #[secure]
+    pub fn secure_deposit_stake(ctx: Context<SecureDepositStake>) -> Result<()> {
+        let meta = ctx.accounts.stake.meta().ok_or(ErrorCode::NotAStakeAccount)?;
+        // SECURITY: Locked stake is not withdrawable, so it cannot back a redeemable balance
+        let clock = Clock::get()?;
+        require!(!meta.lockup.is_in_force(&clock, None), ErrorCode::LockupInForce);
+
+        // SECURITY: Both authorities, each to its own PDA; the depositor must hold both to sign
+        for (role, new_authority) in [
+            (StakeAuthorize::Staker, &ctx.accounts.stake_authority),
+            (StakeAuthorize::Withdrawer, &ctx.accounts.withdraw_authority),
+        ] {
+            stake::authorize(
+                CpiContext::new(
+                    ctx.accounts.stake_program.to_account_info(),
+                    Authorize {
+                        stake: ctx.accounts.stake.to_account_info(),
+                        authorized: ctx.accounts.owner.to_account_info(),
+                        new_authorized: new_authority.to_account_info(),
+                        clock: ctx.accounts.clock.to_account_info(),
+                    },
+                ),
+                role,
+                None,
+            )?;
+        }
+
+        let lamports = ctx.accounts.stake.to_account_info().lamports();
+        credit(&mut ctx.accounts.pool, &mut ctx.accounts.position, lamports)?;
+
+        msg!("Took over stake account {} worth {} lamports", ctx.accounts.stake.key(), lamports);
+        Ok(())
+    }
+}
+
+/// Add `lamports` to a position and to the pool's total
+fn credit(pool: &mut Pool, position: &mut Position, lamports: u64) -> Result<()> {
+    position.balance = position
+        .balance
+        .checked_add(lamports)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    pool.total_staked = pool
+        .total_staked
+        .checked_add(lamports)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
@@ -0,0 +1,33 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Pool {
+    /// Created the pool (32 bytes)
+    pub manager: Pubkey,
+    /// Lamports credited to positions and not yet withdrawn (8 bytes)
+    pub total_staked: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Pool {
+    pub const SPACE: usize = 8 + 32 + 8 + 1;
+}
+
+#[account]
+pub struct Position {
+    /// Pool the position belongs to (32 bytes)
+    pub pool: Pubkey,
+    /// Depositor who may withdraw (32 bytes)
+    pub owner: Pubkey,
+    /// Lamports credited and not yet withdrawn (8 bytes)
+    pub balance: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Position {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1;
+}
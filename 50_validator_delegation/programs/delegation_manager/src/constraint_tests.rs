@@ -0,0 +1,281 @@
+//! Which validators each delegation path places the treasury's stake
+//! with: the vulnerable one any vote account, the secure one only
+//! accounts the Vote program owns, within the manager's commission cap.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::stake::instruction::StakeError;
+use anchor_lang::solana_program::stake::state::{Authorized, Delegation, Lockup, StakeStateV2};
+use anchor_lang::solana_program::vote::state::VoteState;
+use secref_guards::GuardError;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, execute,
+    Fixture, TestAccount,
+};
+
+use crate::{instruction, ErrorCode as ManagerError, Manager, SecureDelegate};
+
+const STAKE: u64 = 5_000_000_000;
+const MAX_COMMISSION: u8 = 10;
+/// Where the commission sits in a vote account's data
+const COMMISSION_OFFSET: usize = 68;
+
+/// A manager capping commission at 10%, an undelegated treasury stake account, and three validators
+struct World {
+    manager: TestAccount,
+    stake_authority: Pubkey,
+    treasury_stake: TestAccount,
+    /// Keeps 5%
+    honest_validator: TestAccount,
+    /// Mallory's own validator, keeping everything
+    greedy_validator: TestAccount,
+    /// Laid out as a 0% vote account, but owned by a program of Mallory's
+    look_alike: TestAccount,
+    crank: TestAccount,
+}
+
+impl World {
+    fn new() -> Self {
+        let admin = Pubkey::new_unique();
+        let (manager, bump) = Pubkey::find_program_address(&[b"manager", admin.as_ref()], &crate::ID);
+        let (stake_authority, _) = Pubkey::find_program_address(&[b"staker", manager.as_ref()], &crate::ID);
+        let mallory = Pubkey::new_unique();
+
+        Self {
+            manager: TestAccount::anchor(&Manager {
+                admin,
+                max_commission: MAX_COMMISSION,
+                bump,
+            })
+            .at(manager),
+            stake_authority,
+            treasury_stake: stake_account(stake_authority),
+            honest_validator: validator(Pubkey::new_unique(), 5),
+            greedy_validator: validator(mallory, 100),
+            look_alike: validator(mallory, 0).owned_by(Pubkey::new_unique()),
+            crank: TestAccount::signer(),
+        }
+    }
+
+    fn delegate(&self, vote_account: &TestAccount) -> Fixture {
+        Fixture::new()
+            .with("manager", self.manager.clone())
+            .with("stake", self.treasury_stake.clone())
+            .with("vote_account", vote_account.clone())
+            .with("stake_authority", TestAccount::system(0).at(self.stake_authority))
+            .with("crank", self.crank.clone())
+            .with("clock", TestAccount::clock_sysvar())
+            .with("stake_history", TestAccount::stake_history_sysvar())
+            .with("stake_config", TestAccount::stake_config())
+            .with("stake_program", TestAccount::stake_program())
+    }
+
+    fn deactivate(&self, stake: &TestAccount, vote_account: &TestAccount) -> Fixture {
+        Fixture::new()
+            .with("manager", self.manager.clone())
+            .with("stake", stake.clone())
+            .with("vote_account", vote_account.clone())
+            .with("stake_authority", TestAccount::system(0).at(self.stake_authority))
+            .with("crank", self.crank.clone())
+            .with("clock", TestAccount::clock_sysvar())
+            .with("stake_program", TestAccount::stake_program())
+    }
+
+    /// The treasury's stake, delegated to the honest validator through the secure path
+    fn delegated_to_honest_validator(&self) -> TestAccount {
+        let mut fixture = self.delegate(&self.honest_validator);
+        execute!(&mut fixture, instruction::SecureDelegate {}).unwrap();
+        fixture.account("stake").clone()
+    }
+}
+
+/// An undelegated stake account holding STAKE, with `staker` as its stake authority
+fn stake_account(staker: Pubkey) -> TestAccount {
+    let withdrawer = Pubkey::new_unique();
+    TestAccount::stake_account(Authorized { staker, withdrawer }, Lockup::default(), STAKE)
+}
+
+fn validator(node: Pubkey, commission: u8) -> TestAccount {
+    TestAccount::vote_account(node, node, commission)
+}
+
+fn delegation(stake: &TestAccount) -> Option<Delegation> {
+    StakeStateV2::deserialize(&mut &stake.data[..])
+        .expect("a stake account")
+        .delegation()
+}
+
+#[test]
+fn vulnerable_delegate_places_stake_with_a_validator_keeping_everything() {
+    let world = World::new();
+    let mut fixture = world.delegate(&world.greedy_validator);
+    execute!(&mut fixture, instruction::VulnerableDelegate {}).unwrap();
+    let delegation = delegation(fixture.account("stake")).expect("delegated");
+    assert_eq!(delegation.voter_pubkey, world.greedy_validator.key);
+    assert_eq!(
+        VoteState::deserialize(&world.greedy_validator.data).unwrap().commission,
+        100
+    );
+}
+
+#[test]
+fn vulnerable_delegate_relies_on_the_stake_programs_owner_check() {
+    let world = World::new();
+    // Only the Stake program stops a look-alike, and only because the account is the delegation target
+    assert_eq!(
+        execute!(&mut world.delegate(&world.look_alike), instruction::VulnerableDelegate {}),
+        Err(ProgramError::IncorrectProgramId)
+    );
+}
+
+#[test]
+fn secure_delegate_places_stake_with_a_validator_within_policy() {
+    let world = World::new();
+    let stake = world.delegated_to_honest_validator();
+    let delegation = delegation(&stake).expect("delegated");
+    assert_eq!(delegation.voter_pubkey, world.honest_validator.key);
+    assert_eq!(
+        delegation.stake,
+        STAKE - Rent::default().minimum_balance(StakeStateV2::size_of())
+    );
+    assert_eq!(delegation.deactivation_epoch, u64::MAX);
+
+    // At exactly the cap is within it
+    let mut at_cap = world.honest_validator.clone();
+    at_cap.data[COMMISSION_OFFSET] = MAX_COMMISSION;
+    execute!(&mut world.delegate(&at_cap), instruction::SecureDelegate {}).unwrap();
+}
+
+#[test]
+fn secure_delegate_refuses_validators_above_the_cap() {
+    let world = World::new();
+    assert_eq!(
+        execute!(&mut world.delegate(&world.greedy_validator), instruction::SecureDelegate {}),
+        Err(Error::from(GuardError::CommissionTooHigh).into())
+    );
+    let mut just_over = world.honest_validator.clone();
+    just_over.data[COMMISSION_OFFSET] = MAX_COMMISSION + 1;
+    assert_eq!(
+        execute!(&mut world.delegate(&just_over), instruction::SecureDelegate {}),
+        Err(Error::from(GuardError::CommissionTooHigh).into())
+    );
+}
+
+#[test]
+fn secure_delegate_reads_only_vote_program_accounts() {
+    let world = World::new();
+    assert_eq!(
+        execute!(&mut world.delegate(&world.look_alike), instruction::SecureDelegate {}),
+        Err(Error::from(GuardError::NotVoteAccount).into())
+    );
+    let mut uninitialized = world.honest_validator.clone();
+    uninitialized.data.fill(0);
+    assert_eq!(
+        execute!(&mut world.delegate(&uninitialized), instruction::SecureDelegate {}),
+        Err(Error::from(GuardError::InvalidVoteAccount).into())
+    );
+}
+
+#[test]
+fn delegation_needs_the_manager_to_be_the_staker() {
+    let mut world = World::new();
+    world.treasury_stake = stake_account(Pubkey::new_unique());
+    assert_eq!(
+        execute!(&mut world.delegate(&world.honest_validator), instruction::SecureDelegate {}),
+        Err(ProgramError::MissingRequiredSignature)
+    );
+    assert_eq!(
+        execute!(&mut world.delegate(&world.honest_validator), instruction::VulnerableDelegate {}),
+        Err(ProgramError::MissingRequiredSignature)
+    );
+}
+
+#[test]
+fn stake_is_pulled_once_its_validator_raises_commission() {
+    let world = World::new();
+    let stake = world.delegated_to_honest_validator();
+    assert_eq!(
+        execute!(
+            &mut world.deactivate(&stake, &world.honest_validator),
+            instruction::DeactivateOverCommission {}
+        ),
+        Err(Error::from(ManagerError::CommissionWithinPolicy).into())
+    );
+
+    // The validator raises its commission after the stake arrived
+    let mut raised = world.honest_validator.clone();
+    raised.data[COMMISSION_OFFSET] = 50;
+    let mut fixture = world.deactivate(&stake, &raised);
+    execute!(&mut fixture, instruction::DeactivateOverCommission {}).unwrap();
+    let deactivated = fixture.account("stake").clone();
+    assert_eq!(delegation(&deactivated).unwrap().deactivation_epoch, 0);
+
+    assert_eq!(
+        execute!(
+            &mut world.deactivate(&deactivated, &raised),
+            instruction::DeactivateOverCommission {}
+        ),
+        Err(ProgramError::Custom(StakeError::AlreadyDeactivated as u32))
+    );
+}
+
+#[test]
+fn deactivation_reads_the_validator_the_stake_is_with() {
+    let world = World::new();
+    let stake = world.delegated_to_honest_validator();
+    // Another validator over the cap is no reason to pull this stake
+    assert_eq!(
+        execute!(
+            &mut world.deactivate(&stake, &world.greedy_validator),
+            instruction::DeactivateOverCommission {}
+        ),
+        Err(Error::from(ManagerError::WrongValidator).into())
+    );
+    assert_eq!(
+        execute!(
+            &mut world.deactivate(&world.treasury_stake, &world.greedy_validator),
+            instruction::DeactivateOverCommission {}
+        ),
+        Err(Error::from(ManagerError::NotDelegated).into())
+    );
+}
+
+#[test]
+fn initialize_manager_takes_a_percentage() {
+    let admin = TestAccount::signer();
+    let (manager, _) = Pubkey::find_program_address(&[b"manager", admin.key.as_ref()], &crate::ID);
+    let fixture = || {
+        Fixture::new()
+            .with("manager", TestAccount::uninitialized(Manager::SPACE).at(manager))
+            .with("admin", admin.clone())
+            .with("system_program", TestAccount::system_program())
+    };
+    assert_eq!(
+        execute!(&mut fixture(), instruction::InitializeManager { max_commission: 101 }),
+        Err(Error::from(ManagerError::InvalidCommission).into())
+    );
+    let mut fixture = fixture();
+    execute!(&mut fixture, instruction::InitializeManager { max_commission: MAX_COMMISSION }).unwrap();
+    assert_eq!(fixture.state::<Manager>("manager").max_commission, MAX_COMMISSION);
+}
+
+#[test]
+fn secure_delegate_accounts_are_checked() {
+    let world = World::new();
+    let fixture = || world.delegate(&world.honest_validator);
+    assert_owner_violation!(SecureDelegate, fixture(), "stake");
+    assert_seeds_violation!(SecureDelegate, fixture(), "manager");
+    assert_seeds_violation!(SecureDelegate, fixture(), "stake_authority");
+    assert_signer_violation!(SecureDelegate, fixture(), "crank");
+    assert_constraint_violation!(
+        SecureDelegate,
+        fixture(),
+        "stake_config" => |config| config.key = Pubkey::new_unique(),
+        ErrorCode::ConstraintAddress
+    );
+    assert_constraint_violation!(
+        SecureDelegate,
+        fixture(),
+        "stake_program" => TestAccount::system_program(),
+        ErrorCode::InvalidProgramId
+    );
+}
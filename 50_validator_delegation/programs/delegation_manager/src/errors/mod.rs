@@ -0,0 +1,15 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Commission is a percentage, at most 100")]
+    InvalidCommission,
+    #[msg("Stake account is not delegated")]
+    NotDelegated,
+    #[msg("Vote account is not the one the stake is delegated to")]
+    WrongValidator,
+    #[msg("Validator's commission is within the manager's policy")]
+    CommissionWithinPolicy,
+}
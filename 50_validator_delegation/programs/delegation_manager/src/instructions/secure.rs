@@ -0,0 +1,60 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureDelegate<'info> {
+    #[account(seeds = [b"manager", manager.admin.as_ref()], bump = manager.bump)]
+    pub manager: Account<'info, Manager>,
+
+    /// A treasury stake account; the Stake program checks the manager is its staker
+    #[account(mut)]
+    pub stake: Account<'info, StakeAccount>,
+
+    /// CHECK: Parsed in the handler, only if the Vote program owns it
+    pub vote_account: UncheckedAccount<'info>,
+
+    /// CHECK: PDA signing as the treasury's staker
+    #[account(seeds = [b"staker", manager.key().as_ref()], bump)]
+    pub stake_authority: UncheckedAccount<'info>,
+
+    pub crank: Signer<'info>,
+
+    /// CHECK: The clock sysvar, by address
+    #[account(address = sysvar::clock::ID)]
+    pub clock: UncheckedAccount<'info>,
+
+    /// CHECK: The stake history sysvar, by address
+    #[account(address = sysvar::stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: The stake config account, by address
+    #[account(address = STAKE_CONFIG)]
+    pub stake_config: UncheckedAccount<'info>,
+
+    pub stake_program: Program<'info, Stake>,
+}
+
+#[derive(Accounts)]
+pub struct DeactivateOverCommission<'info> {
+    #[account(seeds = [b"manager", manager.admin.as_ref()], bump = manager.bump)]
+    pub manager: Account<'info, Manager>,
+
+    #[account(mut)]
+    pub stake: Account<'info, StakeAccount>,
+
+    /// CHECK: Must be the stake's validator; parsed in the handler
+    pub vote_account: UncheckedAccount<'info>,
+
+    /// CHECK: PDA signing as the treasury's staker
+    #[account(seeds = [b"staker", manager.key().as_ref()], bump)]
+    pub stake_authority: UncheckedAccount<'info>,
+
+    pub crank: Signer<'info>,
+
+    /// CHECK: The clock sysvar, by address
+    #[account(address = sysvar::clock::ID)]
+    pub clock: UncheckedAccount<'info>,
+
+    pub stake_program: Program<'info, Stake>,
+}
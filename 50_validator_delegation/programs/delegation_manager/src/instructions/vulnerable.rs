@@ -0,0 +1,36 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableDelegate<'info> {
+    #[account(seeds = [b"manager", manager.admin.as_ref()], bump = manager.bump)]
+    pub manager: Account<'info, Manager>,
+
+    /// A treasury stake account; the Stake program checks the manager is its staker
+    #[account(mut)]
+    pub stake: Account<'info, StakeAccount>,
+
+    /// CHECK: VULNERABILITY - any validator the crank names
+    pub vote_account: UncheckedAccount<'info>,
+
+    /// CHECK: PDA signing as the treasury's staker
+    #[account(seeds = [b"staker", manager.key().as_ref()], bump)]
+    pub stake_authority: UncheckedAccount<'info>,
+
+    pub crank: Signer<'info>,
+
+    /// CHECK: The clock sysvar, by address
+    #[account(address = sysvar::clock::ID)]
+    pub clock: UncheckedAccount<'info>,
+
+    /// CHECK: The stake history sysvar, by address
+    #[account(address = sysvar::stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: The stake config account, by address
+    #[account(address = STAKE_CONFIG)]
+    pub stake_config: UncheckedAccount<'info>,
+
+    pub stake_program: Program<'info, Stake>,
+}
@@ -0,0 +1,16 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::Manager;
+
+#[test]
+fn manager_layout_is_stable() {
+    let manager = Manager {
+        admin: Pubkey::new_from_array([1; 32]),
+        max_commission: 33,
+        bump: 49,
+    };
+    assert_golden!("Manager", golden::anchor(&manager));
+}
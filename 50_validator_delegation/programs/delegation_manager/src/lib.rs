@@ -0,0 +1,186 @@
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::{stake as native_stake, sysvar};
+use anchor_spl::stake::{Stake, StakeAccount};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// A delegation manager: places a treasury's stake with validators, within a commission policy.
+///
+/// The treasury's stake accounts name the manager's stake authority PDA
+/// as their staker. Delegation is permissionless, so a crank can keep
+/// new stake earning without the admin online; the policy is the
+/// admin's `max_commission`, the share of rewards a validator may keep.
+/// A validator can raise its commission after stake arrives, so anyone
+/// may also deactivate stake whose validator has moved out of bounds.
+#[feature_gated]
+#[program]
+pub mod delegation_manager {
+    use super::*;
+    #[secure]
+    use anchor_spl::stake::{self, DeactivateStake};
+    #[secure]
+    use secref_guards::vote_account::assert_commission_at_most;
+
+    /// Create the admin's manager, accepting validators that keep at most `max_commission` percent
+    pub fn initialize_manager(ctx: Context<InitializeManager>, max_commission: u8) -> Result<()> {
+        require!(max_commission <= 100, ErrorCode::InvalidCommission);
+
+        let manager = &mut ctx.accounts.manager;
+        manager.admin = ctx.accounts.admin.key();
+        manager.max_commission = max_commission;
+        manager.bump = ctx.bumps.manager;
+
+        msg!("Delegation manager initialized with a {}% commission cap", max_commission);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Delegate one of the treasury's stake accounts to the validator the caller names
+    ///
+    /// Security Issue: The vote account is never read. The Stake program
+    /// only requires that the Vote program owns it, so the crank picks
+    /// any validator at all - one that keeps 100% of the rewards, such as
+    /// the caller's own - and the manager signs for it. The treasury's
+    /// stake earns nothing for as long as it stays there.
+    #[vulnerable]
+    pub fn vulnerable_delegate(ctx: Context<VulnerableDelegate>) -> Result<()> {
+        // VULNERABILITY: No ownership, layout or commission check on the vote account
+        delegate(
+            &ctx.accounts.manager,
+            &ctx.accounts.stake,
+            &ctx.accounts.vote_account,
+            &ctx.accounts.stake_authority,
+            ctx.bumps.stake_authority,
+            [&ctx.accounts.clock, &ctx.accounts.stake_history, &ctx.accounts.stake_config],
+        )?;
+
+        msg!("Delegated {} to {}", ctx.accounts.stake.key(), ctx.accounts.vote_account.key());
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version: the vote account is
+    // parsed only if the Vote program owns it, and its commission must be
+    // within the manager's policy, at delegation and afterwards.
+
+    /// SECURE: Delegate one of the treasury's stake accounts to a validator within the commission policy
+    ///
+    /// Security Fix: The vote account is parsed only when the Vote
+    /// program owns it, since anyone can lay out an account of their own
+    /// to read as a 0% validator, and its commission must not exceed the
+    /// manager's cap.
+    #[secure]
+    pub fn secure_delegate(ctx: Context<SecureDelegate>) -> Result<()> {
+        // SECURITY: Owned by the Vote program, a current layout, and a commission within policy
+        let validator = assert_commission_at_most(&ctx.accounts.vote_account, ctx.accounts.manager.max_commission)?;
+
+        delegate(
+            &ctx.accounts.manager,
+            &ctx.accounts.stake,
+            &ctx.accounts.vote_account,
+            &ctx.accounts.stake_authority,
+            ctx.bumps.stake_authority,
+            [&ctx.accounts.clock, &ctx.accounts.stake_history, &ctx.accounts.stake_config],
+        )?;
+
+        msg!(
+            "Delegated {} to validator {} at {}% commission",
+            ctx.accounts.stake.key(),
+            validator.node_pubkey,
+            validator.commission
+        );
+        Ok(())
+    }
+
+    /// SECURE: Deactivate treasury stake whose validator has raised its commission above the cap
+    ///
+    /// Security Fix: A commission check holds only when it runs, and a
+    /// validator can raise its commission in any epoch. Anyone may pull
+    /// stake from a validator that has, so the treasury stops paying for
+    /// it at the next epoch boundary rather than whenever the admin notices.
+    #[secure]
+    pub fn deactivate_over_commission(ctx: Context<DeactivateOverCommission>) -> Result<()> {
+        let delegation = ctx.accounts.stake.delegation().ok_or(ErrorCode::NotDelegated)?;
+        // SECURITY: The vote account this stake is actually delegated to, not one the caller picks
+        require_keys_eq!(
+            delegation.voter_pubkey,
+            ctx.accounts.vote_account.key(),
+            ErrorCode::WrongValidator
+        );
+        require!(
+            assert_commission_at_most(&ctx.accounts.vote_account, ctx.accounts.manager.max_commission).is_err(),
+            ErrorCode::CommissionWithinPolicy
+        );
+
+        let manager_key = ctx.accounts.manager.key();
+        let seeds: &[&[u8]] = &[b"staker", manager_key.as_ref(), &[ctx.bumps.stake_authority]];
+        stake::deactivate_stake(CpiContext::new_with_signer(
+            ctx.accounts.stake_program.to_account_info(),
+            DeactivateStake {
+                stake: ctx.accounts.stake.to_account_info(),
+                staker: ctx.accounts.stake_authority.to_account_info(),
+                clock: ctx.accounts.clock.to_account_info(),
+            },
+            &[seeds],
+        ))?;
+
+        msg!("Deactivated {} from {}", ctx.accounts.stake.key(), ctx.accounts.vote_account.key());
+        Ok(())
+    }
+}
+
+/// Have the Stake program delegate `stake` to `vote_account`, signed by the manager's stake authority
+///
+/// anchor-spl wraps `Authorize`, `Withdraw` and `DeactivateStake` but not
+/// `DelegateStake`, so the instruction is built directly.
+fn delegate<'info>(
+    manager: &Account<'info, Manager>,
+    stake: &Account<'info, StakeAccount>,
+    vote_account: &UncheckedAccount<'info>,
+    stake_authority: &UncheckedAccount<'info>,
+    bump: u8,
+    [clock, stake_history, stake_config]: [&UncheckedAccount<'info>; 3],
+) -> Result<()> {
+    let manager_key = manager.key();
+    let seeds: &[&[u8]] = &[b"staker", manager_key.as_ref(), &[bump]];
+    invoke_signed(
+        &native_stake::instruction::delegate_stake(&stake.key(), &stake_authority.key(), &vote_account.key()),
+        &[
+            stake.to_account_info(),
+            vote_account.to_account_info(),
+            clock.to_account_info(),
+            stake_history.to_account_info(),
+            stake_config.to_account_info(),
+            stake_authority.to_account_info(),
+        ],
+        &[seeds],
+    )?;
+    Ok(())
+}
+
+/// The stake config account `DelegateStake` still lists, though the Stake program no longer reads it
+#[allow(deprecated)]
+const STAKE_CONFIG: Pubkey = native_stake::config::ID;
@@ -0,0 +1,17 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Manager {
+    /// Set the commission policy (32 bytes)
+    pub admin: Pubkey,
+    /// Highest commission, in percent, a validator may keep (1 byte)
+    pub max_commission: u8,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Manager {
+    pub const SPACE: usize = 8 + 32 + 1 + 1;
+}
@@ -0,0 +1,148 @@
+/**
+ * lookup-table: the client side of pool_router's address lookup table.
+ *
+ * A v0 transaction names its accounts partly through lookup tables: the
+ * message carries a table's address and one-byte indexes into it, and
+ * the runtime substitutes whatever addresses the table holds when the
+ * transaction executes. Neither the wallet nor the user sees a list of
+ * keys to review. A client that loads the router's table and passes
+ * every entry on - to `distribute`, or to any instruction writing to
+ * "the pools" - sends lamports and write locks wherever the table's
+ * extenders chose.
+ *
+ * `loadPoolTable` is how a client should consume the table: it checks
+ * the table is still controlled by the router's PDA, and that every entry
+ * is a pool the router created, before the table is used to build a
+ * transaction. `loadTableUnchecked` is how most clients do it.
+ */
+
+import { createHash } from "crypto";
+import { BN } from "@coral-xyz/anchor";
+import {
+  AccountMeta,
+  AddressLookupTableAccount,
+  Connection,
+  PublicKey,
+  TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
+} from "@solana/web3.js";
+
+/** getMultipleAccountsInfo takes at most this many addresses */
+const MAX_ACCOUNTS_PER_REQUEST = 100;
+
+/** The router's config PDA, which stores the table's address */
+export function configAddress(programId: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from("config")], programId)[0];
+}
+
+/** The PDA the router creates its table under, and the only authority the table should have */
+export function tableAuthorityAddress(programId: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync([Buffer.from("table_authority")], programId)[0];
+}
+
+/** The pool PDA for `poolId` */
+export function poolAddress(programId: PublicKey, poolId: number): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("pool"), new BN(poolId).toArrayLike(Buffer, "le", 8)],
+    programId
+  )[0];
+}
+
+/** Fetch a lookup table and take its entries as they are */
+export async function loadTableUnchecked(
+  connection: Connection,
+  tableAddress: PublicKey
+): Promise<AddressLookupTableAccount> {
+  const { value } = await connection.getAddressLookupTable(tableAddress);
+  if (!value) {
+    throw new Error(`${tableAddress.toBase58()} is not an address lookup table`);
+  }
+  return value;
+}
+
+/**
+ * Fetch the router's lookup table, refusing it unless every entry is one of the router's pools
+ *
+ * Checks that the table is still controlled by the router's PDA - not
+ * frozen, not handed to someone else - and that each address holds a
+ * `Pool` of `programId` at the PDA its own `pool_id` and bump derive.
+ * Throws, naming the first entry that is not, rather than returning a
+ * table to build transactions with.
+ */
+export async function loadPoolTable(
+  connection: Connection,
+  programId: PublicKey,
+  tableAddress: PublicKey
+): Promise<AddressLookupTableAccount> {
+  const table = await loadTableUnchecked(connection, tableAddress);
+  const authority = tableAuthorityAddress(programId);
+  if (!table.state.authority?.equals(authority)) {
+    throw new Error(`lookup table authority is ${table.state.authority?.toBase58() ?? "none"}, not the router's PDA`);
+  }
+
+  const addresses = table.state.addresses;
+  for (let start = 0; start < addresses.length; start += MAX_ACCOUNTS_PER_REQUEST) {
+    const chunk = addresses.slice(start, start + MAX_ACCOUNTS_PER_REQUEST);
+    const accounts = await connection.getMultipleAccountsInfo(chunk);
+    chunk.forEach((address, index) => {
+      if (!isPool(programId, address, accounts[index]?.owner, accounts[index]?.data)) {
+        throw new Error(`lookup table entry ${start + index} (${address.toBase58()}) is not one of the router's pools`);
+      }
+    });
+  }
+  return table;
+}
+
+/** Whether `data`, owned by `owner`, is a `Pool` of `programId` stored at `address` */
+function isPool(programId: PublicKey, address: PublicKey, owner?: PublicKey, data?: Buffer): boolean {
+  // discriminator (8) + pool_id (8) + bump (1)
+  if (!owner?.equals(programId) || !data || data.length < 17 || !data.subarray(0, 8).equals(accountDiscriminator("Pool"))) {
+    return false;
+  }
+  try {
+    const derived = PublicKey.createProgramAddressSync(
+      [Buffer.from("pool"), data.subarray(8, 16), data.subarray(16, 17)],
+      programId
+    );
+    return derived.equals(address);
+  } catch {
+    return false;
+  }
+}
+
+/** The first 8 bytes of sha256("account:<name>"), as Anchor prefixes account data */
+function accountDiscriminator(name: string): Buffer {
+  return createHash("sha256").update(`account:${name}`).digest().subarray(0, 8);
+}
+
+/**
+ * A v0 `distribute` transaction paying every address in `table`, in table order
+ *
+ * `distribute` is the instruction without its remaining accounts; they are
+ * appended here and compiled as indexes into the table. Whatever the table
+ * lists is paid, so pass a table from `loadPoolTable`.
+ */
+export function buildDistributeTransaction(
+  payer: PublicKey,
+  distribute: TransactionInstruction,
+  table: AddressLookupTableAccount,
+  recentBlockhash: string
+): VersionedTransaction {
+  const recipients: AccountMeta[] = table.state.addresses.map((pubkey) => ({
+    pubkey,
+    isSigner: false,
+    isWritable: true,
+  }));
+  const instruction = new TransactionInstruction({
+    programId: distribute.programId,
+    keys: [...distribute.keys, ...recipients],
+    data: distribute.data,
+  });
+  const message = new TransactionMessage({
+    payerKey: payer,
+    recentBlockhash,
+    instructions: [instruction],
+  }).compileToV0Message([table]);
+  return new VersionedTransaction(message);
+}
@@ -0,0 +1,233 @@
+//! Who can add to the router's lookup table, and what `distribute` pays
+//! once they have: the vulnerable extension lists any address for anyone,
+//! the secure one only the router's own pools, for the admin.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::address_lookup_table::instruction::derive_lookup_table_address;
+use anchor_lang::solana_program::address_lookup_table::{self, state::AddressLookupTable};
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_seeds_violation, assert_signer_violation, execute,
+    Fixture, TestAccount,
+};
+
+use crate::{instruction, Config, ErrorCode as RouterError, Pool, SecureExtend};
+
+/// The router's balance above its rent-exempt minimum
+const REWARDS: u64 = 3_000_000_000;
+/// Fixture names for `remaining_accounts`
+const RECIPIENTS: [&str; 3] = ["recipient_0", "recipient_1", "recipient_2"];
+const POOLS: [&str; 2] = ["pool_0", "pool_1"];
+
+/// A router whose lookup table lists two pools, and a third pool not yet listed
+struct World {
+    config: TestAccount,
+    table: TestAccount,
+    table_authority: Pubkey,
+    admin: TestAccount,
+    pools: [TestAccount; 3],
+}
+
+impl World {
+    fn new() -> Self {
+        let admin = TestAccount::signer();
+        let (config, bump) = Pubkey::find_program_address(&[b"config"], &crate::ID);
+        let (table_authority, _) = Pubkey::find_program_address(&[b"table_authority"], &crate::ID);
+        let (table, _) = derive_lookup_table_address(&table_authority, 0);
+        let pools = [1, 2, 3].map(pool);
+
+        Self {
+            config: TestAccount::anchor(&Config {
+                admin: admin.key,
+                lookup_table: table,
+                bump,
+            })
+            .at(config)
+            .with_lamports(Rent::default().minimum_balance(Config::SPACE) + REWARDS),
+            table: TestAccount::lookup_table(table_authority, &[pools[0].key, pools[1].key]).at(table),
+            table_authority,
+            admin,
+            pools,
+        }
+    }
+
+    fn secure_extend(&self, pools: &[&TestAccount]) -> Fixture {
+        pools
+            .iter()
+            .zip(POOLS)
+            .fold(self.extend(&self.admin), |fixture, (pool, name)| fixture.with(name, (*pool).clone()))
+    }
+
+    /// Either extension's accounts, with `payer` as the signer (`payer` or `admin`)
+    fn extend(&self, payer: &TestAccount) -> Fixture {
+        Fixture::new()
+            .with("config", self.config.clone())
+            .with("lookup_table", self.table.clone())
+            .with("table_authority", TestAccount::system(0).at(self.table_authority))
+            .with("admin", payer.clone())
+            .with("address_lookup_table_program", TestAccount::address_lookup_table_program())
+            .with("system_program", TestAccount::system_program())
+    }
+
+    /// A `distribute` over `table`, passing `recipients` as the remaining accounts
+    fn distribute(&self, table: &TestAccount, recipients: &[&TestAccount]) -> Fixture {
+        recipients.iter().zip(RECIPIENTS).fold(
+            Fixture::new()
+                .with("config", self.config.clone())
+                .with("lookup_table", table.clone().read_only()),
+            |fixture, (recipient, name)| fixture.with(name, (*recipient).clone()),
+        )
+    }
+}
+
+/// Pool `pool_id`, at its PDA
+fn pool(pool_id: u64) -> TestAccount {
+    let (key, bump) = Pubkey::find_program_address(&[b"pool", &pool_id.to_le_bytes()], &crate::ID);
+    TestAccount::anchor(&Pool { pool_id, bump }).at(key)
+}
+
+fn listed(table: &TestAccount) -> Vec<Pubkey> {
+    AddressLookupTable::deserialize(&table.data)
+        .expect("a lookup table")
+        .addresses
+        .to_vec()
+}
+
+#[test]
+fn initialize_creates_a_table_the_router_controls() {
+    let admin = TestAccount::signer();
+    let (config, _) = Pubkey::find_program_address(&[b"config"], &crate::ID);
+    let (table_authority, _) = Pubkey::find_program_address(&[b"table_authority"], &crate::ID);
+    let fixture = |table: Pubkey| {
+        Fixture::new()
+            .with("config", TestAccount::uninitialized(Config::SPACE).at(config))
+            .with("lookup_table", TestAccount::system(0).at(table))
+            .with("table_authority", TestAccount::system(0).at(table_authority))
+            .with("admin", admin.clone())
+            .with("address_lookup_table_program", TestAccount::address_lookup_table_program())
+            .with("system_program", TestAccount::system_program())
+    };
+
+    let (table, _) = derive_lookup_table_address(&table_authority, 42);
+    assert_eq!(
+        execute!(&mut fixture(Pubkey::new_unique()), instruction::Initialize { recent_slot: 42 }),
+        Err(Error::from(RouterError::WrongLookupTable).into())
+    );
+    let mut fixture = fixture(table);
+    execute!(&mut fixture, instruction::Initialize { recent_slot: 42 }).unwrap();
+    assert_eq!(fixture.state::<Config>("config").lookup_table, table);
+    let created = fixture.account("lookup_table");
+    assert_eq!(created.owner, address_lookup_table::program::ID);
+    let created = AddressLookupTable::deserialize(&created.data).unwrap();
+    assert_eq!(created.meta.authority, Some(table_authority));
+    assert!(created.addresses.is_empty());
+}
+
+#[test]
+fn vulnerable_extend_lets_anyone_list_their_own_wallet() {
+    let world = World::new();
+    let mallory = TestAccount::signer();
+    let mut fixture = world.extend(&mallory);
+    execute!(&mut fixture, instruction::VulnerableExtend { new_addresses: vec![mallory.key] }).unwrap();
+    let table = fixture.account("lookup_table").clone();
+    assert_eq!(listed(&table), [world.pools[0].key, world.pools[1].key, mallory.key]);
+
+    // The crank pays whatever the table lists: a third of the rewards leave for Mallory
+    let mut fixture = world.distribute(&table, &[&world.pools[0], &world.pools[1], &mallory]);
+    execute!(&mut fixture, instruction::Distribute {}).unwrap();
+    assert_eq!(fixture.account(RECIPIENTS[0]).lamports, world.pools[0].lamports + REWARDS / 3);
+    assert_eq!(fixture.account(RECIPIENTS[2]).lamports, mallory.lamports + REWARDS / 3);
+}
+
+#[test]
+fn secure_extend_lists_the_routers_pools() {
+    let world = World::new();
+    let mut fixture = world.secure_extend(&[&world.pools[2]]);
+    execute!(&mut fixture, instruction::SecureExtend {}).unwrap();
+    let table = fixture.account("lookup_table");
+    assert_eq!(listed(table), world.pools.each_ref().map(|pool| pool.key));
+    assert_eq!(table.lamports, Rent::default().minimum_balance(table.data.len()));
+}
+
+#[test]
+fn secure_extend_refuses_anything_but_a_pool() {
+    let world = World::new();
+    let mallory = TestAccount::signer();
+    assert_eq!(
+        execute!(&mut world.secure_extend(&[&mallory]), instruction::SecureExtend {}),
+        Err(Error::from(ErrorCode::AccountOwnedByWrongProgram).into())
+    );
+
+    // Pool-shaped, owned by the router, but not at a pool's address
+    let stray = world.pools[2].clone().rekeyed();
+    assert_eq!(
+        execute!(&mut world.secure_extend(&[&stray]), instruction::SecureExtend {}),
+        Err(Error::from(RouterError::NotAPool).into())
+    );
+    assert_eq!(
+        execute!(&mut world.secure_extend(&[]), instruction::SecureExtend {}),
+        Err(Error::from(RouterError::NoPoolsListed).into())
+    );
+}
+
+#[test]
+fn secure_extend_lists_each_pool_once() {
+    let world = World::new();
+    assert_eq!(
+        execute!(&mut world.secure_extend(&[&world.pools[0]]), instruction::SecureExtend {}),
+        Err(Error::from(RouterError::AlreadyListed).into())
+    );
+    assert_eq!(
+        execute!(
+            &mut world.secure_extend(&[&world.pools[2], &world.pools[2]]),
+            instruction::SecureExtend {}
+        ),
+        Err(Error::from(RouterError::AlreadyListed).into())
+    );
+}
+
+#[test]
+fn distribute_pays_the_table_in_order() {
+    let world = World::new();
+    let [first, second, unlisted] = &world.pools;
+    let mut fixture = world.distribute(&world.table, &[first, second]);
+    execute!(&mut fixture, instruction::Distribute {}).unwrap();
+    assert_eq!(
+        fixture.account("config").lamports,
+        Rent::default().minimum_balance(Config::SPACE)
+    );
+
+    for recipients in [&[second, first][..], &[first][..], &[first, unlisted][..]] {
+        assert_eq!(
+            execute!(&mut world.distribute(&world.table, recipients), instruction::Distribute {}),
+            Err(Error::from(RouterError::RecipientMismatch).into())
+        );
+    }
+    let mut emptied = world.distribute(&world.table, &[first, second]);
+    emptied.account_mut("config").lamports = Rent::default().minimum_balance(Config::SPACE);
+    assert_eq!(
+        execute!(&mut emptied, instruction::Distribute {}),
+        Err(Error::from(RouterError::NothingToDistribute).into())
+    );
+}
+
+#[test]
+fn secure_extend_accounts_are_checked() {
+    let world = World::new();
+    let fixture = || world.secure_extend(&[&world.pools[2]]);
+    assert_has_one_violation!(SecureExtend, fixture(), "admin");
+    assert_signer_violation!(SecureExtend, fixture(), "admin");
+    assert_seeds_violation!(SecureExtend, fixture(), "config");
+    assert_seeds_violation!(SecureExtend, fixture(), "table_authority");
+    assert_constraint_violation!(
+        SecureExtend,
+        fixture(),
+        "lookup_table" => |table| table.key = Pubkey::new_unique(),
+        ErrorCode::ConstraintAddress
+    );
+    assert_constraint_violation!(
+        SecureExtend,
+        fixture(),
+        "address_lookup_table_program" => TestAccount::system_program(),
+        ErrorCode::ConstraintAddress
+    );
+}
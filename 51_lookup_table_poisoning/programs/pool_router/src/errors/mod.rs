@@ -0,0 +1,21 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Lookup table is not at the address the table authority and slot derive")]
+    WrongLookupTable,
+    #[msg("Account is not an address lookup table")]
+    InvalidLookupTable,
+    #[msg("No pools listed")]
+    NoPoolsListed,
+    #[msg("Recipients must be the lookup table's addresses, in order")]
+    RecipientMismatch,
+    #[msg("Nothing to distribute")]
+    NothingToDistribute,
+    #[msg("Account is not one of the router's pools")]
+    NotAPool,
+    #[msg("Pool is already listed")]
+    AlreadyListed,
+}
@@ -0,0 +1,70 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(init, payer = admin, space = Config::SPACE, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Created by the Address Lookup Table program, at the address the handler derives
+    #[account(mut)]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    /// CHECK: PDA controlling the lookup table
+    #[account(seeds = [b"table_authority"], bump)]
+    pub table_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: The Address Lookup Table program, by address
+    #[account(address = address_lookup_table::program::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct CreatePool<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Pool::SPACE,
+        seeds = [b"pool", pool_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Recipients are passed as writable `remaining_accounts`, in table order
+#[derive(Accounts)]
+pub struct Distribute<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: The router's lookup table, by address; parsed in the handler
+    #[account(address = config.lookup_table)]
+    pub lookup_table: UncheckedAccount<'info>,
+}
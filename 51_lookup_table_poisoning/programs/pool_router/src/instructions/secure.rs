@@ -0,0 +1,27 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+/// Pools to list are passed as `remaining_accounts`
+#[derive(Accounts)]
+pub struct SecureExtend<'info> {
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: The router's lookup table, by address
+    #[account(mut, address = config.lookup_table)]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    /// CHECK: PDA controlling the lookup table
+    #[account(seeds = [b"table_authority"], bump)]
+    pub table_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: The Address Lookup Table program, by address
+    #[account(address = address_lookup_table::program::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,27 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableExtend<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: The router's lookup table, by address
+    #[account(mut, address = config.lookup_table)]
+    pub lookup_table: UncheckedAccount<'info>,
+
+    /// CHECK: PDA controlling the lookup table
+    #[account(seeds = [b"table_authority"], bump)]
+    pub table_authority: UncheckedAccount<'info>,
+
+    /// VULNERABILITY: Anyone, not the admin
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: The Address Lookup Table program, by address
+    #[account(address = address_lookup_table::program::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
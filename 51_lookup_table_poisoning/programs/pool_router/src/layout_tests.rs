@@ -0,0 +1,25 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Config, Pool};
+
+#[test]
+fn config_layout_is_stable() {
+    let config = Config {
+        admin: Pubkey::new_from_array([1; 32]),
+        lookup_table: Pubkey::new_from_array([2; 32]),
+        bump: 49,
+    };
+    assert_golden!("Config", golden::anchor(&config));
+}
+
+#[test]
+fn pool_layout_is_stable() {
+    let pool = Pool {
+        pool_id: 0x4142434445464748,
+        bump: 81,
+    };
+    assert_golden!("Pool", golden::anchor(&pool));
+}
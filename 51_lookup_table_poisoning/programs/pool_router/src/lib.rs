@@ -0,0 +1,213 @@
+use anchor_lang::solana_program::address_lookup_table::instruction::{create_lookup_table, extend_lookup_table};
+use anchor_lang::solana_program::address_lookup_table::{self, state::AddressLookupTable};
+use anchor_lang::solana_program::program::invoke_signed;
+use secref_guards::rent::minimum_balance;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// A reward router whose pools are listed in an address lookup table it controls.
+///
+/// The router's table authority PDA owns one lookup table. Clients load it
+/// to fit every pool into a v0 transaction, and the permissionless
+/// `distribute` crank walks it, paying the router's balance out in equal
+/// shares to every address listed. Listing an address is therefore the
+/// same as making it a pool: whoever can extend the table decides who is
+/// paid.
+#[feature_gated]
+#[program]
+pub mod pool_router {
+    use super::*;
+
+    /// Create the router and its lookup table, derived from the table authority PDA and `recent_slot`
+    pub fn initialize(ctx: Context<Initialize>, recent_slot: u64) -> Result<()> {
+        let (create, table) =
+            create_lookup_table(ctx.accounts.table_authority.key(), ctx.accounts.admin.key(), recent_slot);
+        require_keys_eq!(table, ctx.accounts.lookup_table.key(), ErrorCode::WrongLookupTable);
+        invoke_signed(
+            &create,
+            &[
+                ctx.accounts.lookup_table.to_account_info(),
+                ctx.accounts.table_authority.to_account_info(),
+                ctx.accounts.admin.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&[b"table_authority", &[ctx.bumps.table_authority]]],
+        )?;
+
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.lookup_table = table;
+        config.bump = ctx.bumps.config;
+
+        msg!("Router initialized with lookup table {}", table);
+        Ok(())
+    }
+
+    /// Create pool `pool_id`; it is paid once its address is listed in the lookup table
+    pub fn create_pool(ctx: Context<CreatePool>, pool_id: u64) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        pool.pool_id = pool_id;
+        pool.bump = ctx.bumps.pool;
+
+        msg!("Pool {} created at {}", pool_id, pool.key());
+        Ok(())
+    }
+
+    /// Pay the router's balance above its rent-exempt minimum, in equal shares, to every address in the lookup table
+    ///
+    /// The recipients are passed as `remaining_accounts`, in table order.
+    /// Nothing about them is checked beyond being the table's entries:
+    /// the table is the registry.
+    pub fn distribute(ctx: Context<Distribute>) -> Result<()> {
+        let data = ctx.accounts.lookup_table.try_borrow_data()?;
+        let table = AddressLookupTable::deserialize(&data).map_err(|_| ErrorCode::InvalidLookupTable)?;
+        let listed = &table.addresses[..];
+        require!(!listed.is_empty(), ErrorCode::NoPoolsListed);
+        require_eq!(ctx.remaining_accounts.len(), listed.len(), ErrorCode::RecipientMismatch);
+        for (recipient, address) in ctx.remaining_accounts.iter().zip(listed) {
+            require_keys_eq!(recipient.key(), *address, ErrorCode::RecipientMismatch);
+        }
+
+        let config = ctx.accounts.config.to_account_info();
+        let reserve = minimum_balance(Config::SPACE)?;
+        let share = config.lamports().saturating_sub(reserve) / listed.len() as u64;
+        require!(share > 0, ErrorCode::NothingToDistribute);
+        for recipient in ctx.remaining_accounts {
+            **config.try_borrow_mut_lamports()? -= share;
+            **recipient.try_borrow_mut_lamports()? += share;
+        }
+
+        msg!("Distributed {} lamports to each of {} pools", share, listed.len());
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Append addresses to the router's lookup table
+    ///
+    /// Security Issue: The table authority is the router's PDA, and the
+    /// router signs `ExtendLookupTable` for any caller and any addresses.
+    /// The Address Lookup Table program only checks the authority's
+    /// signature, which the router lends to everyone, so an attacker
+    /// lists their own wallet and the next `distribute` pays it a pool's
+    /// share. Clients that load the table to build transactions reach it
+    /// too.
+    #[vulnerable]
+    pub fn vulnerable_extend(ctx: Context<VulnerableExtend>, new_addresses: Vec<Pubkey>) -> Result<()> {
+        // VULNERABILITY: Any signer, any addresses - the PDA signs for them all
+        extend(
+            &ctx.accounts.lookup_table,
+            &ctx.accounts.table_authority,
+            ctx.bumps.table_authority,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            new_addresses,
+        )?;
+
+        msg!("Lookup table extended by {}", ctx.accounts.payer.key());
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version: only the admin extends the
+    // table, and only with pools the router itself created.
+
+    /// SECURE: List existing pools in the router's lookup table
+    ///
+    /// Security Fix: The table authority's signature is lent only to the
+    /// admin, and the entries are not taken as arguments: each is passed
+    /// as an account that must be one of this program's pools, at the
+    /// address its seeds derive, and not already listed. A table that
+    /// lists only pools cannot route rewards, or a client's transaction,
+    /// anywhere else.
+    #[secure]
+    pub fn secure_extend<'info>(ctx: Context<'_, '_, 'info, 'info, SecureExtend<'info>>) -> Result<()> {
+        require!(!ctx.remaining_accounts.is_empty(), ErrorCode::NoPoolsListed);
+
+        let listed = {
+            let data = ctx.accounts.lookup_table.try_borrow_data()?;
+            let table = AddressLookupTable::deserialize(&data).map_err(|_| ErrorCode::InvalidLookupTable)?;
+            table.addresses.to_vec()
+        };
+        let mut new_addresses: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+        for pool_info in ctx.remaining_accounts {
+            // SECURITY: Owned by this program, a Pool, at its own PDA
+            let pool = Account::<Pool>::try_from(pool_info)?;
+            let derived = Pubkey::create_program_address(
+                &[b"pool", &pool.pool_id.to_le_bytes(), &[pool.bump]],
+                &crate::ID,
+            )
+            .map_err(|_| ErrorCode::NotAPool)?;
+            require_keys_eq!(derived, pool_info.key(), ErrorCode::NotAPool);
+            // SECURITY: Each pool once, or it would be paid twice
+            require!(
+                !listed.contains(pool_info.key) && !new_addresses.contains(pool_info.key),
+                ErrorCode::AlreadyListed
+            );
+            new_addresses.push(pool_info.key());
+        }
+
+        extend(
+            &ctx.accounts.lookup_table,
+            &ctx.accounts.table_authority,
+            ctx.bumps.table_authority,
+            &ctx.accounts.admin,
+            &ctx.accounts.system_program,
+            new_addresses,
+        )?;
+
+        msg!("Listed {} pools", ctx.remaining_accounts.len());
+        Ok(())
+    }
+}
+
+/// Have the Address Lookup Table program append `new_addresses`, signed by the table authority PDA
+///
+/// `payer` tops the table's rent up for the bytes it grows by.
+fn extend<'info>(
+    lookup_table: &UncheckedAccount<'info>,
+    table_authority: &UncheckedAccount<'info>,
+    bump: u8,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_addresses: Vec<Pubkey>,
+) -> Result<()> {
+    invoke_signed(
+        &extend_lookup_table(
+            lookup_table.key(),
+            table_authority.key(),
+            Some(payer.key()),
+            new_addresses,
+        ),
+        &[
+            lookup_table.to_account_info(),
+            table_authority.to_account_info(),
+            payer.to_account_info(),
+            system_program.to_account_info(),
+        ],
+        &[&[b"table_authority", &[bump]]],
+    )?;
+    Ok(())
+}
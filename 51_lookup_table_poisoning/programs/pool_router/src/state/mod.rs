@@ -0,0 +1,29 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Config {
+    /// Creates and lists pools (32 bytes)
+    pub admin: Pubkey,
+    /// The table of pools, controlled by the table authority PDA (32 bytes)
+    pub lookup_table: Pubkey,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Config {
+    pub const SPACE: usize = 8 + 32 + 32 + 1;
+}
+
+#[account]
+pub struct Pool {
+    /// Seeds the pool's address (8 bytes)
+    pub pool_id: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Pool {
+    pub const SPACE: usize = 8 + 8 + 1;
+}
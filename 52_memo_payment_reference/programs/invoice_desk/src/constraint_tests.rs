@@ -0,0 +1,167 @@
+//! Which memo a payment can travel with: the vulnerable payment takes the
+//! client's word for it, the secure one reads the Memo instruction.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use secref_guards::memo::MEMO_PROGRAM_IDS;
+use secref_guards::GuardError;
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_seeds_violation, assert_signer_violation, execute,
+    Fixture, TestAccount,
+};
+
+use crate::{instruction, ErrorCode as InvoiceError, Invoice, SecurePay};
+
+const CHEAP: u64 = 1_000_000;
+
+/// A merchant's invoice for `amount`, unpaid, at its PDA
+fn invoice(merchant: &TestAccount, invoice_id: u64, amount: u64, reference: &str) -> TestAccount {
+    let (key, bump) =
+        Pubkey::find_program_address(&[b"invoice", merchant.key.as_ref(), &invoice_id.to_le_bytes()], &crate::ID);
+    TestAccount::anchor(&Invoice {
+        merchant: merchant.key,
+        invoice_id,
+        amount,
+        reference: reference.to_string(),
+        paid: false,
+        bump,
+    })
+    .at(key)
+}
+
+fn memo(text: &str) -> Instruction {
+    Instruction::new_with_bytes(MEMO_PROGRAM_IDS[0], text.as_bytes(), vec![])
+}
+
+/// The payment instruction itself; only its position in the transaction matters to the sysvar
+fn pay() -> Instruction {
+    Instruction::new_with_bytes(crate::ID, &[], vec![])
+}
+
+fn vulnerable_pay(invoice: &TestAccount, merchant: &TestAccount) -> Fixture {
+    Fixture::new()
+        .with("invoice", invoice.clone())
+        .with("merchant", merchant.clone())
+        .with("payer", TestAccount::signer())
+        .with("system_program", TestAccount::system_program())
+}
+
+/// `secure_pay` as instruction `current` of `transaction`
+fn secure_pay(invoice: &TestAccount, merchant: &TestAccount, transaction: &[Instruction], current: u16) -> Fixture {
+    Fixture::new()
+        .with("invoice", invoice.clone())
+        .with("merchant", merchant.clone())
+        .with("payer", TestAccount::signer())
+        .with("instructions", TestAccount::instructions_sysvar_of(transaction, current))
+        .with("system_program", TestAccount::system_program())
+}
+
+#[test]
+fn create_invoice_records_the_reference() {
+    let merchant = TestAccount::signer();
+    let (key, _) = Pubkey::find_program_address(&[b"invoice", merchant.key.as_ref(), &7u64.to_le_bytes()], &crate::ID);
+    let fixture = || {
+        Fixture::new()
+            .with("invoice", TestAccount::uninitialized(Invoice::SPACE).at(key))
+            .with("merchant", merchant.clone())
+            .with("system_program", TestAccount::system_program())
+    };
+    let create = |amount: u64, reference: &str| instruction::CreateInvoice {
+        invoice_id: 7,
+        amount,
+        reference: reference.to_string(),
+    };
+
+    let mut created = fixture();
+    execute!(&mut created, create(CHEAP, "INV-7")).unwrap();
+    let invoice = created.state::<Invoice>("invoice");
+    assert_eq!((invoice.amount, invoice.reference.as_str(), invoice.paid), (CHEAP, "INV-7", false));
+
+    assert_eq!(
+        execute!(&mut fixture(), create(0, "INV-7")),
+        Err(Error::from(InvoiceError::ZeroAmount).into())
+    );
+    for reference in ["", &"X".repeat(33)] {
+        assert_eq!(
+            execute!(&mut fixture(), create(CHEAP, reference)),
+            Err(Error::from(InvoiceError::InvalidReference).into())
+        );
+    }
+}
+
+#[test]
+fn vulnerable_pay_settles_a_cheap_invoice_under_any_memo() {
+    let merchant = TestAccount::system(0);
+    let cheap = invoice(&merchant, 1, CHEAP, "INV-1");
+
+    // The Memo instruction beside it says INV-2; the program only sees the argument
+    let mut fixture = vulnerable_pay(&cheap, &merchant);
+    execute!(&mut fixture, instruction::VulnerablePay { memo: "INV-1".into() }).unwrap();
+    assert!(fixture.state::<Invoice>("invoice").paid);
+    assert_eq!(fixture.account("merchant").lamports, CHEAP);
+
+    assert_eq!(
+        execute!(
+            &mut vulnerable_pay(&cheap, &merchant),
+            instruction::VulnerablePay { memo: "INV-2".into() }
+        ),
+        Err(Error::from(InvoiceError::ReferenceMismatch).into())
+    );
+}
+
+#[test]
+fn secure_pay_requires_the_invoices_memo_right_before_it() {
+    let merchant = TestAccount::system(0);
+    let cheap = invoice(&merchant, 1, CHEAP, "INV-1");
+
+    let mut fixture = secure_pay(&cheap, &merchant, &[memo("INV-1"), pay()], 1);
+    execute!(&mut fixture, instruction::SecurePay {}).unwrap();
+    assert!(fixture.state::<Invoice>("invoice").paid);
+    assert_eq!(fixture.account("merchant").lamports, CHEAP);
+
+    for (transaction, current, error) in [
+        (vec![memo("INV-2"), pay()], 1, GuardError::MemoMismatch),
+        (vec![memo("INV-1"), pay(), memo("INV-2")], 1, GuardError::ConflictingMemo),
+        (vec![pay()], 0, GuardError::MemoMissing),
+        (vec![memo("INV-1"), pay(), pay()], 2, GuardError::MemoMissing),
+    ] {
+        assert_eq!(
+            execute!(&mut secure_pay(&cheap, &merchant, &transaction, current), instruction::SecurePay {}),
+            Err(Error::from(error).into())
+        );
+    }
+}
+
+#[test]
+fn invoices_are_paid_once() {
+    let merchant = TestAccount::system(0);
+    let mut paid = invoice(&merchant, 1, CHEAP, "INV-1");
+    paid.edit(|invoice: &mut Invoice| invoice.paid = true);
+    assert_eq!(
+        execute!(
+            &mut vulnerable_pay(&paid, &merchant),
+            instruction::VulnerablePay { memo: "INV-1".into() }
+        ),
+        Err(Error::from(InvoiceError::AlreadyPaid).into())
+    );
+    assert_eq!(
+        execute!(&mut secure_pay(&paid, &merchant, &[memo("INV-1"), pay()], 1), instruction::SecurePay {}),
+        Err(Error::from(InvoiceError::AlreadyPaid).into())
+    );
+}
+
+#[test]
+fn secure_pay_accounts_are_checked() {
+    let merchant = TestAccount::system(0);
+    let cheap = invoice(&merchant, 1, CHEAP, "INV-1");
+    let fixture = || secure_pay(&cheap, &merchant, &[memo("INV-1"), pay()], 1);
+    assert_has_one_violation!(SecurePay, fixture(), "merchant");
+    assert_seeds_violation!(SecurePay, fixture(), "invoice");
+    assert_signer_violation!(SecurePay, fixture(), "payer");
+    assert_constraint_violation!(
+        SecurePay,
+        fixture(),
+        "instructions" => |sysvar| sysvar.key = Pubkey::new_unique(),
+        ErrorCode::ConstraintAddress
+    );
+}
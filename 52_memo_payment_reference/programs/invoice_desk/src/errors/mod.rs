@@ -0,0 +1,15 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Invoice amount must be greater than zero")]
+    ZeroAmount,
+    #[msg("Reference must be 1 to 32 bytes")]
+    InvalidReference,
+    #[msg("Memo does not match the invoice's reference")]
+    ReferenceMismatch,
+    #[msg("Invoice has already been paid")]
+    AlreadyPaid,
+}
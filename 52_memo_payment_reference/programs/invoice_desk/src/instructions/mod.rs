@@ -0,0 +1,33 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+#[instruction(invoice_id: u64)]
+pub struct CreateInvoice<'info> {
+    #[account(
+        init,
+        payer = merchant,
+        space = Invoice::SPACE,
+        seeds = [b"invoice", merchant.key().as_ref(), invoice_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub invoice: Account<'info, Invoice>,
+
+    #[account(mut)]
+    pub merchant: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
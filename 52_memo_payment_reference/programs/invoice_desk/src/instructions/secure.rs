@@ -0,0 +1,28 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use anchor_lang::solana_program::sysvar;
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecurePay<'info> {
+    #[account(
+        mut,
+        seeds = [b"invoice", invoice.merchant.as_ref(), invoice.invoice_id.to_le_bytes().as_ref()],
+        bump = invoice.bump,
+        has_one = merchant
+    )]
+    pub invoice: Account<'info, Invoice>,
+
+    #[account(mut)]
+    pub merchant: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: The instructions sysvar, by address
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
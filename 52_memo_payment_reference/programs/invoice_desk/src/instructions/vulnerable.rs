@@ -0,0 +1,22 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerablePay<'info> {
+    #[account(
+        mut,
+        seeds = [b"invoice", invoice.merchant.as_ref(), invoice.invoice_id.to_le_bytes().as_ref()],
+        bump = invoice.bump,
+        has_one = merchant
+    )]
+    pub invoice: Account<'info, Invoice>,
+
+    #[account(mut)]
+    pub merchant: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
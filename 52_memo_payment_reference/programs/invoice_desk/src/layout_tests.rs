@@ -0,0 +1,19 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::Invoice;
+
+#[test]
+fn invoice_layout_is_stable() {
+    let invoice = Invoice {
+        merchant: Pubkey::new_from_array([1; 32]),
+        invoice_id: 0x2122232425262728,
+        amount: 0x3132333435363738,
+        reference: "reference".to_string(),
+        paid: true,
+        bump: 65,
+    };
+    assert_golden!("Invoice", golden::anchor(&invoice));
+}
@@ -0,0 +1,126 @@
+use anchor_lang::system_program::{self, Transfer};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Longest payment reference an invoice can carry, in bytes
+pub const MAX_REFERENCE_LEN: usize = 32;
+
+/// A merchant's invoice desk, settled through the payment reference in a memo.
+///
+/// The merchant opens an invoice for an amount under a reference such as
+/// `INV-1042`, and the customer pays it with a transaction that carries
+/// the reference in a Memo program instruction. The merchant's fulfilment
+/// backend watches its wallet and ships the order a successful payment's
+/// memo names; it trusts the program to have accepted only payments whose
+/// memo matches the invoice they settled.
+#[feature_gated]
+#[program]
+pub mod invoice_desk {
+    use super::*;
+    #[secure]
+    use secref_guards::memo::assert_memo_attached;
+
+    /// Open invoice `invoice_id` for `amount` lamports, to be paid with `reference` in the memo
+    pub fn create_invoice(ctx: Context<CreateInvoice>, invoice_id: u64, amount: u64, reference: String) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroAmount);
+        require!(
+            !reference.is_empty() && reference.len() <= MAX_REFERENCE_LEN,
+            ErrorCode::InvalidReference
+        );
+
+        let invoice = &mut ctx.accounts.invoice;
+        invoice.merchant = ctx.accounts.merchant.key();
+        invoice.invoice_id = invoice_id;
+        invoice.amount = amount;
+        invoice.reference = reference;
+        invoice.paid = false;
+        invoice.bump = ctx.bumps.invoice;
+
+        msg!("Invoice {} opened for {} lamports as {}", invoice_id, amount, invoice.reference);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Pay an invoice, with the client's word for the memo beside it
+    ///
+    /// Security Issue: The memo is a separate instruction the program never
+    /// reads. `memo` is an argument the client claims it also put in the
+    /// Memo instruction, and the check passes whatever the Memo instruction
+    /// really says. An attacker pays a cheap invoice, passes its reference
+    /// as `memo`, and attaches a memo naming an expensive one: the backend
+    /// sees a successful payment whose memo is `INV-1043` and ships it.
+    #[vulnerable]
+    pub fn vulnerable_pay(ctx: Context<VulnerablePay>, memo: String) -> Result<()> {
+        // VULNERABILITY: Compares the invoice with a claim about the memo, not the memo
+        require!(memo == ctx.accounts.invoice.reference, ErrorCode::ReferenceMismatch);
+
+        pay(&mut ctx.accounts.invoice, &ctx.accounts.payer, &ctx.accounts.merchant, &ctx.accounts.system_program)
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version: the program reads the Memo
+    // instruction itself, through the instructions sysvar.
+
+    /// SECURE: Pay an invoice whose reference is in the Memo instruction right before this one
+    ///
+    /// Security Fix: The memo is read from the transaction, not taken from
+    /// the client: the instruction immediately before this one must be a
+    /// Memo instruction carrying the invoice's reference, and no other memo
+    /// in the transaction may name anything else. Every payment the
+    /// backend sees succeed is labelled with the invoice it paid.
+    #[secure]
+    pub fn secure_pay(ctx: Context<SecurePay>) -> Result<()> {
+        // SECURITY: The Memo instruction itself, next to this one, and no conflicting memo
+        assert_memo_attached(&ctx.accounts.instructions, ctx.accounts.invoice.reference.as_bytes())?;
+
+        pay(&mut ctx.accounts.invoice, &ctx.accounts.payer, &ctx.accounts.merchant, &ctx.accounts.system_program)
+    }
+}
+
+/// Move the invoice's amount from `payer` to the merchant and mark it paid
+fn pay<'info>(
+    invoice: &mut Account<'info, Invoice>,
+    payer: &Signer<'info>,
+    merchant: &SystemAccount<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    require!(!invoice.paid, ErrorCode::AlreadyPaid);
+
+    system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: payer.to_account_info(),
+                to: merchant.to_account_info(),
+            },
+        ),
+        invoice.amount,
+    )?;
+    invoice.paid = true;
+
+    msg!("Invoice {} ({}) paid by {}", invoice.invoice_id, invoice.reference, payer.key());
+    Ok(())
+}
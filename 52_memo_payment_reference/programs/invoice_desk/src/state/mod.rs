@@ -0,0 +1,23 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Invoice {
+    /// Receives the payment (32 bytes)
+    pub merchant: Pubkey,
+    /// Seeds the invoice's address, per merchant (8 bytes)
+    pub invoice_id: u64,
+    /// Lamports due (8 bytes)
+    pub amount: u64,
+    /// Text the payment's memo must carry (4 + up to 32 bytes)
+    pub reference: String,
+    /// Whether the invoice has been paid (1 byte)
+    pub paid: bool,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Invoice {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + (4 + MAX_REFERENCE_LEN) + 1 + 1;
+}
@@ -0,0 +1,228 @@
+//! How much a stolen key can withdraw in a day: the vulnerable tracker
+//! forgets today's total when it is closed and reopened, the secure one
+//! cannot be closed and counts in the clock's days.
+
+use anchor_lang::prelude::*;
+use secref_guards::velocity::{VelocityLimits, VelocityWindow, DAY_SECONDS};
+use secref_guards::GuardError;
+use secref_testkit::runtime::warp_to;
+use secref_testkit::{assert_seeds_violation, assert_signer_violation, execute, Fixture, TestAccount};
+
+use crate::{instruction, ErrorCode as VaultError, Position, SecureWithdraw, Tracker, VelocityTracker, Vault};
+
+const MAX_PER_TX: u64 = 1_000_000_000;
+const DAILY_CAP: u64 = 2_000_000_000;
+const BALANCE: u64 = 10_000_000_000;
+/// Midday, so the clock's day has hours left either way
+const NOON: i64 = 19_700 * DAY_SECONDS + DAY_SECONDS / 2;
+
+/// A vault holding one owner's 10 SOL position, with both trackers open and empty
+struct World {
+    vault: TestAccount,
+    position: TestAccount,
+    tracker: TestAccount,
+    velocity: TestAccount,
+    owner: TestAccount,
+}
+
+impl World {
+    fn new() -> Self {
+        warp_to(NOON);
+        let owner = TestAccount::signer();
+        let authority = Pubkey::new_unique();
+        let (vault, bump) = Pubkey::find_program_address(&[b"vault", authority.as_ref()], &crate::ID);
+        let seeds = |prefix: &[u8]| Pubkey::find_program_address(&[prefix, vault.as_ref(), owner.key.as_ref()], &crate::ID);
+        let (position, position_bump) = seeds(b"position");
+        let (tracker, tracker_bump) = seeds(b"tracker");
+        let (velocity, velocity_bump) = seeds(b"velocity");
+
+        Self {
+            vault: TestAccount::anchor(&Vault {
+                authority,
+                limits: VelocityLimits::new(MAX_PER_TX, DAILY_CAP, DAY_SECONDS).unwrap(),
+                bump,
+            })
+            .at(vault)
+            .with_lamports(Rent::default().minimum_balance(Vault::SPACE) + BALANCE),
+            position: TestAccount::anchor(&Position {
+                vault,
+                owner: owner.key,
+                balance: BALANCE,
+                bump: position_bump,
+            })
+            .at(position),
+            tracker: TestAccount::anchor(&Tracker {
+                owner: owner.key,
+                day_started_at: NOON,
+                withdrawn_today: 0,
+                bump: tracker_bump,
+            })
+            .at(tracker),
+            velocity: TestAccount::anchor(&VelocityTracker {
+                window: VelocityWindow::default(),
+                bump: velocity_bump,
+            })
+            .at(velocity),
+            owner,
+        }
+    }
+
+    fn vulnerable_withdraw(&self) -> Fixture {
+        Fixture::new()
+            .with("vault", self.vault.clone())
+            .with("position", self.position.clone())
+            .with("tracker", self.tracker.clone())
+            .with("owner", self.owner.clone())
+    }
+
+    fn close_tracker(&self) -> Fixture {
+        Fixture::new()
+            .with("vault", self.vault.clone())
+            .with("tracker", self.tracker.clone())
+            .with("owner", self.owner.clone())
+    }
+
+    fn open_tracker(&self) -> Fixture {
+        Fixture::new()
+            .with("vault", self.vault.clone())
+            .with("tracker", TestAccount::uninitialized(Tracker::SPACE).at(self.tracker.key))
+            .with("owner", self.owner.clone())
+            .with("system_program", TestAccount::system_program())
+    }
+
+    fn secure_withdraw(&self) -> Fixture {
+        Fixture::new()
+            .with("vault", self.vault.clone())
+            .with("position", self.position.clone())
+            .with("velocity", self.velocity.clone())
+            .with("owner", self.owner.clone())
+    }
+
+    /// Carry the accounts a fixture changed into the next one
+    fn update(&mut self, fixture: &Fixture) {
+        for (name, changed) in fixture.accounts() {
+            let account = match name {
+                "vault" => &mut self.vault,
+                "position" => &mut self.position,
+                "tracker" => &mut self.tracker,
+                "velocity" => &mut self.velocity,
+                "owner" => &mut self.owner,
+                _ => continue,
+            };
+            *account = changed.clone();
+        }
+    }
+}
+
+#[test]
+fn vulnerable_withdraw_is_capped_per_day() {
+    let mut world = World::new();
+    for _ in 0..2 {
+        let mut fixture = world.vulnerable_withdraw();
+        execute!(&mut fixture, instruction::VulnerableWithdraw { amount: MAX_PER_TX }).unwrap();
+        world.update(&fixture);
+    }
+    assert_eq!(
+        execute!(&mut world.vulnerable_withdraw(), instruction::VulnerableWithdraw { amount: 1 }),
+        Err(Error::from(VaultError::DailyLimitExceeded).into())
+    );
+    assert_eq!(
+        execute!(&mut world.vulnerable_withdraw(), instruction::VulnerableWithdraw { amount: MAX_PER_TX + 1 }),
+        Err(Error::from(GuardError::TransactionLimitExceeded).into())
+    );
+}
+
+#[test]
+fn closing_and_reopening_the_tracker_resets_the_vulnerable_cap() {
+    let mut world = World::new();
+    let start = world.owner.lamports;
+
+    // Every round takes the whole daily cap, and forgets it
+    for _ in 0..BALANCE / DAILY_CAP {
+        for _ in 0..2 {
+            let mut fixture = world.vulnerable_withdraw();
+            execute!(&mut fixture, instruction::VulnerableWithdraw { amount: MAX_PER_TX }).unwrap();
+            world.update(&fixture);
+        }
+        let mut close = world.close_tracker();
+        execute!(&mut close, instruction::VulnerableCloseTracker {}).unwrap();
+        world.update(&close);
+        let mut open = world.open_tracker();
+        execute!(&mut open, instruction::VulnerableOpenTracker {}).unwrap();
+        world.update(&open);
+        assert_eq!(world.tracker.state::<Tracker>().withdrawn_today, 0);
+    }
+
+    // The whole position left in one day, not a fifth of it (rent in and out of the tracker nets out)
+    assert_eq!(world.position.state::<Position>().balance, 0);
+    assert_eq!(world.owner.lamports - start, BALANCE);
+}
+
+#[test]
+fn secure_withdraw_caps_the_owners_day() {
+    let mut world = World::new();
+    for _ in 0..2 {
+        let mut fixture = world.secure_withdraw();
+        execute!(&mut fixture, instruction::SecureWithdraw { amount: MAX_PER_TX }).unwrap();
+        world.update(&fixture);
+    }
+    assert_eq!(world.velocity.state::<VelocityTracker>().window.used, DAILY_CAP);
+    assert_eq!(
+        execute!(&mut world.secure_withdraw(), instruction::SecureWithdraw { amount: 1 }),
+        Err(Error::from(GuardError::WindowLimitExceeded).into())
+    );
+    assert_eq!(
+        execute!(&mut world.secure_withdraw(), instruction::SecureWithdraw { amount: MAX_PER_TX + 1 }),
+        Err(Error::from(GuardError::TransactionLimitExceeded).into())
+    );
+
+    // The next day, by the clock, the cap is whole again
+    warp_to(NOON + DAY_SECONDS / 2);
+    let mut fixture = world.secure_withdraw();
+    execute!(&mut fixture, instruction::SecureWithdraw { amount: MAX_PER_TX }).unwrap();
+    assert_eq!(fixture.state::<VelocityTracker>("velocity").window.used, MAX_PER_TX);
+}
+
+#[test]
+fn withdrawals_cannot_exceed_the_position() {
+    let mut world = World::new();
+    world
+        .position
+        .edit(|position: &mut Position| position.balance = MAX_PER_TX - 1);
+    assert_eq!(
+        execute!(&mut world.secure_withdraw(), instruction::SecureWithdraw { amount: MAX_PER_TX }),
+        Err(Error::from(VaultError::InsufficientBalance).into())
+    );
+    assert_eq!(
+        execute!(&mut world.vulnerable_withdraw(), instruction::VulnerableWithdraw { amount: MAX_PER_TX }),
+        Err(Error::from(VaultError::InsufficientBalance).into())
+    );
+}
+
+#[test]
+fn deposits_are_capped_per_transaction() {
+    let world = World::new();
+    let fixture = || {
+        Fixture::new()
+            .with("vault", world.vault.clone())
+            .with("position", world.position.clone())
+            .with("owner", world.owner.clone())
+            .with("system_program", TestAccount::system_program())
+    };
+    let mut deposited = fixture();
+    execute!(&mut deposited, instruction::Deposit { amount: MAX_PER_TX }).unwrap();
+    assert_eq!(deposited.state::<Position>("position").balance, BALANCE + MAX_PER_TX);
+    assert_eq!(
+        execute!(&mut fixture(), instruction::Deposit { amount: MAX_PER_TX + 1 }),
+        Err(Error::from(GuardError::TransactionLimitExceeded).into())
+    );
+}
+
+#[test]
+fn secure_withdraw_accounts_are_checked() {
+    let world = World::new();
+    assert_seeds_violation!(SecureWithdraw, world.secure_withdraw(), "vault");
+    assert_seeds_violation!(SecureWithdraw, world.secure_withdraw(), "position");
+    assert_seeds_violation!(SecureWithdraw, world.secure_withdraw(), "velocity");
+    assert_signer_violation!(SecureWithdraw, world.secure_withdraw(), "owner");
+}
@@ -0,0 +1,15 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Amount must be greater than zero")]
+    ZeroAmount,
+    #[msg("Withdrawal is more than the position holds")]
+    InsufficientBalance,
+    #[msg("Withdrawal would exceed the daily limit")]
+    DailyLimitExceeded,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+}
@@ -0,0 +1,27 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureWithdraw<'info> {
+    #[account(mut, seeds = [b"vault", vault.authority.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"position", vault.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+
+    /// SECURITY: The owner's one tracker, created with the position and never closed
+    #[account(
+        mut,
+        seeds = [b"velocity", vault.key().as_ref(), owner.key().as_ref()],
+        bump = velocity.bump
+    )]
+    pub velocity: Account<'info, VelocityTracker>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
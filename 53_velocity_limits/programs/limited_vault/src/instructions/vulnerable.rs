@@ -0,0 +1,64 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableOpenTracker<'info> {
+    #[account(seeds = [b"vault", vault.authority.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = Tracker::SPACE,
+        seeds = [b"tracker", vault.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub tracker: Account<'info, Tracker>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableCloseTracker<'info> {
+    #[account(seeds = [b"vault", vault.authority.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    /// VULNERABILITY: Closable whenever the owner likes
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"tracker", vault.key().as_ref(), owner.key().as_ref()],
+        bump = tracker.bump
+    )]
+    pub tracker: Account<'info, Tracker>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableWithdraw<'info> {
+    #[account(mut, seeds = [b"vault", vault.authority.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"position", vault.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        mut,
+        seeds = [b"tracker", vault.key().as_ref(), owner.key().as_ref()],
+        bump = tracker.bump
+    )]
+    pub tracker: Account<'info, Tracker>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
@@ -0,0 +1,55 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_guards::velocity::{VelocityLimits, VelocityWindow};
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Position, Tracker, Vault, VelocityTracker};
+
+#[test]
+fn vault_layout_is_stable() {
+    let vault = Vault {
+        authority: Pubkey::new_from_array([1; 32]),
+        limits: VelocityLimits {
+            max_per_tx: 0x6162636465666768,
+            max_per_window: 0x7172737475767778,
+            window_seconds: 0x5152535455565758,
+        },
+        bump: 33,
+    };
+    assert_golden!("Vault", golden::anchor(&vault));
+}
+
+#[test]
+fn position_layout_is_stable() {
+    let position = Position {
+        vault: Pubkey::new_from_array([3; 32]),
+        owner: Pubkey::new_from_array([4; 32]),
+        balance: 0x5152535455565758,
+        bump: 97,
+    };
+    assert_golden!("Position", golden::anchor(&position));
+}
+
+#[test]
+fn tracker_layout_is_stable() {
+    let tracker = Tracker {
+        owner: Pubkey::new_from_array([7; 32]),
+        day_started_at: 0x282838485868788,
+        withdrawn_today: 0x9192939495969798,
+        bump: 161,
+    };
+    assert_golden!("Tracker", golden::anchor(&tracker));
+}
+
+#[test]
+fn velocity_tracker_layout_is_stable() {
+    let velocity_tracker = VelocityTracker {
+        window: VelocityWindow {
+            window: 0x6162636465666768,
+            used: 0x7172737475767778,
+        },
+        bump: 177,
+    };
+    assert_golden!("VelocityTracker", golden::anchor(&velocity_tracker));
+}
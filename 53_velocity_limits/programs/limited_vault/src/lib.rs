@@ -0,0 +1,179 @@
+use anchor_lang::system_program::{self, Transfer};
+use secref_guards::velocity::{VelocityLimits, VelocityWindow, DAY_SECONDS};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// A lamport vault that limits how fast each owner's balance can leave.
+///
+/// Deposits and withdrawals are capped per transaction, and withdrawals
+/// per owner per day. The daily cap is the vault's answer to a stolen hot
+/// key: whoever holds it can take at most a day's allowance before the
+/// owner notices, not the whole position. The cap is only as strong as
+/// the per-owner counter it is checked against.
+#[feature_gated]
+#[program]
+pub mod limited_vault {
+    use super::*;
+
+    /// Create a vault moving at most `max_per_tx` per instruction and `daily_cap` per owner per day
+    pub fn initialize_vault(ctx: Context<InitializeVault>, max_per_tx: u64, daily_cap: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.authority = ctx.accounts.authority.key();
+        vault.limits = VelocityLimits::new(max_per_tx, daily_cap, DAY_SECONDS)?;
+        vault.bump = ctx.bumps.vault;
+
+        msg!("Vault initialized: {} per transaction, {} per day", max_per_tx, daily_cap);
+        Ok(())
+    }
+
+    /// Open the owner's position, and the velocity tracker the secure withdrawal counts against
+    pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.vault = ctx.accounts.vault.key();
+        position.owner = ctx.accounts.owner.key();
+        position.balance = 0;
+        position.bump = ctx.bumps.position;
+
+        let tracker = &mut ctx.accounts.velocity;
+        tracker.window = VelocityWindow::default();
+        tracker.bump = ctx.bumps.velocity;
+
+        msg!("Position opened for {}", position.owner);
+        Ok(())
+    }
+
+    /// Deposit `amount` lamports into the owner's position, within the per-transaction cap
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroAmount);
+        ctx.accounts.vault.limits.assert_per_tx(amount)?;
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        let position = &mut ctx.accounts.position;
+        position.balance = position.balance.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Open a withdrawal tracker, its day starting now
+    #[vulnerable]
+    pub fn vulnerable_open_tracker(ctx: Context<VulnerableOpenTracker>) -> Result<()> {
+        let tracker = &mut ctx.accounts.tracker;
+        tracker.owner = ctx.accounts.owner.key();
+        // VULNERABILITY: The day is counted from whenever the tracker was created
+        tracker.day_started_at = Clock::get()?.unix_timestamp;
+        tracker.withdrawn_today = 0;
+        tracker.bump = ctx.bumps.tracker;
+        Ok(())
+    }
+
+    /// VULNERABLE: Close the withdrawal tracker, refunding its rent
+    ///
+    /// Security Issue: The tracker is the only record of what the owner
+    /// withdrew today, and the owner may delete it at any time. Recreated
+    /// at the same address, it starts from zero.
+    #[vulnerable]
+    pub fn vulnerable_close_tracker(_ctx: Context<VulnerableCloseTracker>) -> Result<()> {
+        // VULNERABILITY: Deleting the tracker deletes today's total
+        Ok(())
+    }
+
+    /// VULNERABLE: Withdraw `amount` lamports within the daily cap
+    ///
+    /// Security Issue: The cap is checked against a tracker the owner can
+    /// close and reopen, and whose day starts when it was opened. A stolen
+    /// key withdraws the cap, closes the tracker, opens a new one and
+    /// withdraws the cap again - in a loop, in one transaction - until the
+    /// position is empty.
+    #[vulnerable]
+    pub fn vulnerable_withdraw(ctx: Context<VulnerableWithdraw>, amount: u64) -> Result<()> {
+        let limits = ctx.accounts.vault.limits;
+        limits.assert_per_tx(amount)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let tracker = &mut ctx.accounts.tracker;
+        if now >= tracker.day_started_at.saturating_add(DAY_SECONDS) {
+            tracker.day_started_at = now;
+            tracker.withdrawn_today = 0;
+        }
+        // VULNERABILITY: Counted against whatever this tracker remembers, since it was last created
+        let withdrawn_today = tracker.withdrawn_today.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        require!(withdrawn_today <= limits.max_per_window, ErrorCode::DailyLimitExceeded);
+        tracker.withdrawn_today = withdrawn_today;
+
+        pay_out(&ctx.accounts.vault, &mut ctx.accounts.position, &ctx.accounts.owner, amount)
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version: the counter lives at an
+    // address derived from the vault and owner, is created with the
+    // position, can never be closed, and counts in fixed clock windows.
+
+    /// SECURE: Withdraw `amount` lamports within the daily cap
+    ///
+    /// Security Fix: The tracker is created with the position at
+    /// `["velocity", vault, owner]` - one per owner, with no instruction to
+    /// close it - so today's total cannot be deleted. Days are windows of
+    /// the clock, not of the tracker's life, so no action of the owner's
+    /// moves when the next one starts.
+    #[secure]
+    pub fn secure_withdraw(ctx: Context<SecureWithdraw>, amount: u64) -> Result<()> {
+        // SECURITY: Per-transaction and per-day caps, against the one counter the owner cannot reset
+        ctx.accounts
+            .velocity
+            .window
+            .record(&ctx.accounts.vault.limits, amount, Clock::get()?.unix_timestamp)?;
+
+        pay_out(&ctx.accounts.vault, &mut ctx.accounts.position, &ctx.accounts.owner, amount)
+    }
+}
+
+/// Move `amount` from the vault to the position's owner
+fn pay_out<'info>(
+    vault: &Account<'info, Vault>,
+    position: &mut Account<'info, Position>,
+    owner: &Signer<'info>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::ZeroAmount);
+    position.balance = position.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientBalance)?;
+
+    **vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **owner.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    msg!("Withdrew {} lamports; {} left in the position", amount, position.balance);
+    Ok(())
+}
@@ -0,0 +1,63 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Vault {
+    /// Created the vault and set its limits (32 bytes)
+    pub authority: Pubkey,
+    /// Per-transaction and daily caps (VelocityLimits::SPACE bytes)
+    pub limits: VelocityLimits,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Vault {
+    pub const SPACE: usize = 8 + 32 + VelocityLimits::SPACE + 1;
+}
+
+#[account]
+pub struct Position {
+    /// The vault holding the lamports (32 bytes)
+    pub vault: Pubkey,
+    /// May deposit and withdraw (32 bytes)
+    pub owner: Pubkey,
+    /// Lamports the owner can withdraw (8 bytes)
+    pub balance: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Position {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+/// The vulnerable withdrawal's record of today's total
+#[account]
+pub struct Tracker {
+    /// Whose withdrawals are counted (32 bytes)
+    pub owner: Pubkey,
+    /// Unix timestamp the current day started at: when the tracker was opened, or a day after (8 bytes)
+    pub day_started_at: i64,
+    /// Lamports withdrawn since `day_started_at` (8 bytes)
+    pub withdrawn_today: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Tracker {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 1;
+}
+
+/// The secure withdrawal's record of today's total
+#[account]
+pub struct VelocityTracker {
+    /// Withdrawn in the current day (VelocityWindow::SPACE bytes)
+    pub window: VelocityWindow,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl VelocityTracker {
+    pub const SPACE: usize = 8 + VelocityWindow::SPACE + 1;
+}
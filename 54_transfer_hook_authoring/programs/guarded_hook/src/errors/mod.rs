@@ -0,0 +1,9 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("The hook only runs inside a Token-2022 transfer")]
+    NotTransferring,
+}
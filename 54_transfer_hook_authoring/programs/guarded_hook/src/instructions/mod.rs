@@ -0,0 +1,102 @@
+//! Account contexts, one per instruction
+
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct CreatePolicy<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Policy::SPACE,
+        seeds = [b"policy", mint.key().as_ref()],
+        bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    #[account(mint::authority = authority)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenInbound<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = Inbound::SPACE,
+        seeds = [b"inbound", mint.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub inbound: Account<'info, Inbound>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Any wallet; the counter only ever limits what it receives
+    pub wallet: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    /// CHECK: Written as the interface's TLV list, not an Anchor account
+    #[account(
+        init,
+        payer = authority,
+        space = ExtraAccountMetaList::size_of(extra_account_metas()?.len())?,
+        seeds = [META_LIST_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    /// SECURITY: Only the mint authority decides what its transfers are checked against
+    #[account(mint::authority = authority)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// The accounts of the interface's `Execute`, in its order
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    /// SECURITY: Holds the mint this hook is enforcing
+    #[account(token::mint = mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// SECURITY: Holds the mint this hook is enforcing
+    #[account(token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: The source's owner or delegate, passed without its signature
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: SECURITY: The mint's own validation account, by address
+    #[account(seeds = [META_LIST_SEED, mint.key().as_ref()], bump)]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    /// SECURITY: This mint's policy, not one the caller chose
+    #[account(seeds = [b"policy", mint.key().as_ref()], bump = policy.bump)]
+    pub policy: Account<'info, Policy>,
+
+    /// SECURITY: The counter of the destination's owner, not one the caller chose
+    #[account(
+        mut,
+        seeds = [b"inbound", mint.key().as_ref(), destination_token.owner.as_ref()],
+        bump = inbound.bump
+    )]
+    pub inbound: Account<'info, Inbound>,
+}
@@ -0,0 +1,35 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_guards::velocity::{VelocityLimits, VelocityWindow};
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Inbound, Policy};
+
+#[test]
+fn policy_layout_is_stable() {
+    let policy = Policy {
+        mint: Pubkey::new_from_array([1; 32]),
+        limits: VelocityLimits {
+            max_per_tx: 0x6162636465666768,
+            max_per_window: 0x7172737475767778,
+            window_seconds: 0x5152535455565758,
+        },
+        bump: 33,
+    };
+    assert_golden!("Policy", golden::anchor(&policy));
+}
+
+#[test]
+fn inbound_layout_is_stable() {
+    let inbound = Inbound {
+        mint: Pubkey::new_from_array([3; 32]),
+        wallet: Pubkey::new_from_array([4; 32]),
+        window: VelocityWindow {
+            window: 0x6162636465666768,
+            used: 0x7172737475767778,
+        },
+        bump: 81,
+    };
+    assert_golden!("Inbound", golden::anchor(&inbound));
+}
@@ -0,0 +1,144 @@
+//! SECURE: A Token-2022 transfer hook capping what each wallet receives of a mint per day.
+//!
+//! The hook is only as sound as the accounts it runs on. Token-2022 calls
+//! `Execute` with the transfer's accounts followed by the extra accounts
+//! the mint's validation account lists, but anyone can call `Execute` too,
+//! with any accounts at all. So this hook derives every extra account
+//! itself, only lets the mint authority write the list, checks that both
+//! token accounts hold the mint it is enforcing, and counts nothing unless
+//! Token-2022 has flagged the source as mid-transfer.
+
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::TransferHookAccount;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022::state::Account as Token2022Account;
+use anchor_spl::transfer_hook::spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use anchor_spl::transfer_hook::spl_transfer_hook_interface::instruction::{ExecuteInstruction, TransferHookInstruction};
+use secref_guards::velocity::{VelocityLimits, VelocityWindow, DAY_SECONDS};
+use secref_prelude::*;
+
+declare_id!("GuardedHook11111111111111111111111111111111");
+
+pub mod errors;
+pub mod instructions;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Seed of a mint's validation account, fixed by the transfer hook interface
+pub const META_LIST_SEED: &[u8] = b"extra-account-metas";
+
+#[program]
+pub mod guarded_hook {
+    use super::*;
+
+    /// Set `mint`'s inbound limits: `max_per_tx` per transfer, `max_per_day` per receiving wallet
+    pub fn create_policy(ctx: Context<CreatePolicy>, max_per_tx: u64, max_per_day: u64) -> Result<()> {
+        let policy = &mut ctx.accounts.policy;
+        policy.mint = ctx.accounts.mint.key();
+        policy.limits = VelocityLimits::new(max_per_tx, max_per_day, DAY_SECONDS)?;
+        policy.bump = ctx.bumps.policy;
+
+        msg!("Policy for {}: {} per transfer, {} per day", policy.mint, max_per_tx, max_per_day);
+        Ok(())
+    }
+
+    /// Open `wallet`'s inbound counter for the mint; anyone may pay for it
+    pub fn open_inbound(ctx: Context<OpenInbound>) -> Result<()> {
+        let inbound = &mut ctx.accounts.inbound;
+        inbound.mint = ctx.accounts.mint.key();
+        inbound.wallet = ctx.accounts.wallet.key();
+        inbound.window = VelocityWindow::default();
+        inbound.bump = ctx.bumps.inbound;
+
+        msg!("Inbound counter opened for {}", inbound.wallet);
+        Ok(())
+    }
+
+    /// SECURE: Write the mint's validation account
+    ///
+    /// Security Fix: Only the mint authority can create the list, at the
+    /// address the interface derives from the mint, and its contents are
+    /// fixed here: the mint's policy and the receiving wallet's counter,
+    /// both derived from the transfer's own accounts.
+    pub fn initialize_extra_account_meta_list(ctx: Context<InitializeExtraAccountMetaList>) -> Result<()> {
+        // SECURITY: The list names accounts by seeds, never by an address a caller picked
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+            &extra_account_metas()?,
+        )?;
+
+        msg!("Validation account written for {}", ctx.accounts.mint.key());
+        Ok(())
+    }
+
+    /// SECURE: Count a transfer of `amount` against the receiving wallet's daily limit
+    ///
+    /// Security Fix: The context binds the policy and the counter to the
+    /// mint and the destination's owner, and the validation account to the
+    /// mint; both token accounts must hold the mint. Only Token-2022 can
+    /// set the `transferring` flag on the source, so a direct call cannot
+    /// spend anyone's limit.
+    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+        // SECURITY: Only ever inside Token-2022's transfer of this mint
+        require!(is_transferring(&ctx.accounts.source_token.to_account_info())?, ErrorCode::NotTransferring);
+
+        let now = Clock::get()?.unix_timestamp;
+        let inbound = &mut ctx.accounts.inbound;
+        inbound.window.record(&ctx.accounts.policy.limits, amount, now)?;
+
+        msg!("{} received {} ({} today)", inbound.wallet, amount, inbound.window.used);
+        Ok(())
+    }
+
+    /// Route the interface's `Execute` to `transfer_hook`
+    ///
+    /// Token-2022 sends `Execute` with the interface's discriminator, not
+    /// an Anchor one, so it arrives here.
+    pub fn fallback<'info>(program_id: &Pubkey, accounts: &'info [AccountInfo<'info>], data: &[u8]) -> Result<()> {
+        match TransferHookInstruction::unpack(data)? {
+            TransferHookInstruction::Execute { amount } => {
+                __private::__global::transfer_hook(program_id, accounts, &amount.to_le_bytes())
+            }
+            _ => Err(ProgramError::InvalidInstructionData.into()),
+        }
+    }
+}
+
+/// The accounts `Execute` needs after the validation account: the mint's
+/// policy, then the counter of the destination's owner (bytes 32..64 of a
+/// token account)
+pub fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal { bytes: b"policy".to_vec() },
+                Seed::AccountKey { index: 1 },
+            ],
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal { bytes: b"inbound".to_vec() },
+                Seed::AccountKey { index: 1 },
+                Seed::AccountData { account_index: 2, data_index: 32, length: 32 },
+            ],
+            false,
+            true,
+        )?,
+    ])
+}
+
+/// Whether Token-2022 has flagged `token_account` as the source of the transfer running this hook
+fn is_transferring(token_account: &AccountInfo) -> Result<bool> {
+    let data = token_account.try_borrow_data()?;
+    let state = StateWithExtensions::<Token2022Account>::unpack(&data)?;
+    Ok(state.get_extension::<TransferHookAccount>()?.transferring.into())
+}
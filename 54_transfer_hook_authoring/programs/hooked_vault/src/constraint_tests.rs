@@ -0,0 +1,504 @@
+//! The two hooks under Token-2022's transfers, through the vault, and
+//! called directly: what the naive hook lets a stranger do to its limits,
+//! which check of the guarded hook stops each attempt, and what the vault
+//! requires of the hook it composes with.
+
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::*;
+use anchor_lang::InstructionData;
+use anchor_spl::token_interface::TokenAccount;
+use anchor_spl::transfer_hook::spl_transfer_hook_interface::instruction::TransferHookInstruction;
+use secref_guards::velocity::DAY_SECONDS;
+use secref_guards::GuardError;
+use secref_testkit::runtime::{self, account_already_in_use, register, warp_to, Entry};
+use secref_testkit::{
+    assert_has_one_violation, assert_seeds_violation, assert_signer_violation, execute, Fixture, TestAccount,
+};
+
+use crate::{instruction, Deposit, ErrorCode as VaultError, Position, Vault, Withdraw};
+
+const DECIMALS: u8 = 6;
+const MAX_PER_TX: u64 = 1_000_000_000;
+const MAX_PER_DAY: u64 = 2_000_000_000;
+const BALANCE: u64 = 10_000_000_000;
+/// Midday, so the clock's day has hours left either way
+const NOON: i64 = 19_700 * DAY_SECONDS + DAY_SECONDS / 2;
+
+fn entry_of(program: Pubkey) -> Entry {
+    match program {
+        id if id == naive_hook::ID => naive_hook::entry,
+        id if id == guarded_hook::ID => guarded_hook::entry,
+        _ => crate::entry,
+    }
+}
+
+/// A mint hooked to `hook`, its policy, a vault holding it, and Alice with tokens to deposit
+///
+/// Accounts are kept by address, so every instruction - the vault's or a
+/// hook's - starts from what the previous ones left.
+struct World {
+    hook: Pubkey,
+    accounts: BTreeMap<Pubkey, TestAccount>,
+    authority: Pubkey,
+    mint: Pubkey,
+    vault: Pubkey,
+    tokens: Pubkey,
+    alice: Pubkey,
+    alice_tokens: Pubkey,
+    position: Pubkey,
+    /// The policy the validation account names
+    listed_policy: Pubkey,
+}
+
+impl World {
+    /// Everything but the mint's validation account, which whoever calls `write_list` first writes
+    fn unlisted(hook: Pubkey) -> Self {
+        warp_to(NOON);
+        register(naive_hook::ID, naive_hook::entry);
+        register(guarded_hook::ID, guarded_hook::entry);
+
+        let authority = TestAccount::signer();
+        let mint = TestAccount::hooked_mint(authority.key, DECIMALS, 2 * BALANCE, hook);
+        let (vault, bump) = Pubkey::find_program_address(&[b"vault", mint.key.as_ref()], &crate::ID);
+        let tokens = TestAccount::hooked_token_account(mint.key, vault, 0);
+        let alice = TestAccount::signer();
+        let alice_tokens = TestAccount::hooked_token_account(mint.key, alice.key, BALANCE);
+        let (position, position_bump) =
+            Pubkey::find_program_address(&[b"position", vault.as_ref(), alice.key.as_ref()], &crate::ID);
+
+        let mut world = Self {
+            hook,
+            accounts: BTreeMap::new(),
+            authority: authority.key,
+            mint: mint.key,
+            vault,
+            tokens: tokens.key,
+            alice: alice.key,
+            alice_tokens: alice_tokens.key,
+            position,
+            listed_policy: Pubkey::default(),
+        };
+        world.insert(
+            TestAccount::anchor(&Vault {
+                mint: mint.key,
+                tokens: tokens.key,
+                hook_program: hook,
+                total_deposits: 0,
+                bump,
+            })
+            .at(vault),
+        );
+        world.insert(
+            TestAccount::anchor(&Position {
+                vault,
+                owner: alice.key,
+                balance: 0,
+                bump: position_bump,
+            })
+            .at(position),
+        );
+        for account in [authority, mint, tokens, alice, alice_tokens] {
+            world.insert(account);
+        }
+
+        let policy = world.policy(world.mint);
+        world
+            .run(
+                hook,
+                instruction_data(hook, HookInstruction::CreatePolicy { max_per_tx: MAX_PER_TX, max_per_day: MAX_PER_DAY }),
+                Fixture::new()
+                    .with("policy", TestAccount::uninitialized(guarded_hook::Policy::SPACE).at(policy))
+                    .with("mint", world.get(world.mint))
+                    .with("authority", world.get(world.authority))
+                    .with("system_program", TestAccount::system_program()),
+            )
+            .unwrap();
+        world.open_inbound(vault);
+        world.open_inbound(world.alice);
+        world
+    }
+
+    /// The world with the mint authority's own validation account written
+    fn new(hook: Pubkey) -> Self {
+        let mut world = Self::unlisted(hook);
+        let (authority, policy) = (world.get(world.authority), world.policy(world.mint));
+        world.write_list(authority, policy).unwrap();
+        world
+    }
+
+    fn insert(&mut self, account: TestAccount) {
+        self.accounts.insert(account.key, account);
+    }
+
+    fn get(&self, key: Pubkey) -> TestAccount {
+        self.accounts.get(&key).cloned().unwrap_or_else(|| panic!("no account at {key}"))
+    }
+
+    fn pda(&self, seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &self.hook).0
+    }
+
+    fn policy(&self, mint: Pubkey) -> Pubkey {
+        self.pda(&[b"policy", mint.as_ref()])
+    }
+
+    fn inbound(&self, wallet: Pubkey) -> Pubkey {
+        self.pda(&[b"inbound", self.mint.as_ref(), wallet.as_ref()])
+    }
+
+    fn list(&self, mint: Pubkey) -> Pubkey {
+        self.pda(&[guarded_hook::META_LIST_SEED, mint.as_ref()])
+    }
+
+    /// What `wallet` has received of the mint today, by the hook's count
+    fn received_today(&self, wallet: Pubkey) -> u64 {
+        let inbound = self.get(self.inbound(wallet));
+        match self.hook {
+            id if id == naive_hook::ID => inbound.state::<naive_hook::Inbound>().window.used,
+            _ => inbound.state::<guarded_hook::Inbound>().window.used,
+        }
+    }
+
+    fn token_account(&self, key: Pubkey) -> TokenAccount {
+        TokenAccount::try_deserialize(&mut &self.get(key).data[..]).unwrap()
+    }
+
+    /// Run `program`'s instruction over `fixture`, keeping what it wrote if it succeeds
+    fn run(&mut self, program: Pubkey, data: Vec<u8>, mut fixture: Fixture) -> std::result::Result<(), ProgramError> {
+        runtime::enter(program);
+        let result = {
+            let (infos, _) = fixture.split();
+            entry_of(program)(&program, &infos, &data)
+        };
+        fixture.sync();
+        if result.is_ok() {
+            self.update(&fixture);
+        }
+        result
+    }
+
+    /// Carry the accounts a fixture changed into the next one
+    fn update(&mut self, fixture: &Fixture) {
+        for (_, changed) in fixture.accounts() {
+            if !changed.executable {
+                self.insert(changed.clone());
+            }
+        }
+    }
+
+    fn open_inbound(&mut self, wallet: Pubkey) {
+        let fixture = Fixture::new()
+            .with(
+                "inbound",
+                TestAccount::uninitialized(guarded_hook::Inbound::SPACE).at(self.inbound(wallet)),
+            )
+            .with("mint", self.get(self.mint))
+            .with("wallet", self.accounts.get(&wallet).cloned().unwrap_or_else(|| TestAccount::system(0).at(wallet)))
+            .with("payer", TestAccount::signer())
+            .with("system_program", TestAccount::system_program());
+        self.run(self.hook, instruction_data(self.hook, HookInstruction::OpenInbound), fixture).unwrap();
+    }
+
+    /// Write the mint's validation account, signed by `payer`; the naive hook lists `policy`
+    fn write_list(&mut self, payer: TestAccount, policy: Pubkey) -> std::result::Result<(), ProgramError> {
+        let space = anchor_spl::transfer_hook::spl_tlv_account_resolution::state::ExtraAccountMetaList::size_of(2)?;
+        let list = self.accounts.get(&self.list(self.mint)).cloned();
+        let fixture = Fixture::new()
+            .with(
+                "extra_account_meta_list",
+                list.unwrap_or_else(|| TestAccount::uninitialized(space).at(self.list(self.mint))),
+            )
+            .with("mint", self.get(self.mint))
+            .with("payer", payer)
+            .with("system_program", TestAccount::system_program());
+        self.run(
+            self.hook,
+            instruction_data(self.hook, HookInstruction::InitializeExtraAccountMetaList { policy }),
+            fixture,
+        )?;
+        self.listed_policy = policy;
+        Ok(())
+    }
+
+    /// The hook's accounts for a transfer to `wallet`, as a vault's remaining accounts
+    fn with_hook_accounts(&self, fixture: Fixture, wallet: Pubkey) -> Fixture {
+        fixture
+            .with("policy", self.get(self.listed_policy))
+            .with("inbound", self.get(self.inbound(wallet)))
+            .with("hook_program", TestAccount::program(self.hook))
+            .with("extra_account_meta_list", self.get(self.list(self.mint)))
+    }
+
+    fn deposit_fixture(&self) -> Fixture {
+        let fixture = Fixture::new()
+            .with("vault", self.get(self.vault))
+            .with("position", self.get(self.position))
+            .with("mint", self.get(self.mint))
+            .with("tokens", self.get(self.tokens))
+            .with("owner_tokens", self.get(self.alice_tokens))
+            .with("owner", self.get(self.alice))
+            .with("token_program", TestAccount::token_2022_program());
+        self.with_hook_accounts(fixture, self.vault)
+    }
+
+    fn withdraw_fixture(&self, to: Pubkey) -> Fixture {
+        let fixture = Fixture::new()
+            .with("vault", self.get(self.vault))
+            .with("position", self.get(self.position))
+            .with("mint", self.get(self.mint))
+            .with("tokens", self.get(self.tokens))
+            .with("owner_tokens", self.get(to))
+            .with("owner", self.get(self.alice))
+            .with("token_program", TestAccount::token_2022_program());
+        self.with_hook_accounts(fixture, self.token_account(to).owner)
+    }
+
+    fn deposit(&mut self, amount: u64) -> std::result::Result<(), ProgramError> {
+        let mut fixture = self.deposit_fixture();
+        let result = execute!(&mut fixture, instruction::Deposit { amount });
+        if result.is_ok() {
+            self.update(&fixture);
+        }
+        result
+    }
+
+    fn withdraw(&mut self, to: Pubkey, amount: u64) -> std::result::Result<(), ProgramError> {
+        let mut fixture = self.withdraw_fixture(to);
+        let result = execute!(&mut fixture, instruction::Withdraw { amount });
+        if result.is_ok() {
+            self.update(&fixture);
+        }
+        result
+    }
+
+    /// `Execute` as anyone can send it: the transfer's accounts, the validation account, then the extras
+    fn execute_fixture(&self, source: TestAccount, destination: TestAccount, inbound: Pubkey) -> Fixture {
+        Fixture::new()
+            .with("source_token", source)
+            .with("mint", self.get(self.mint))
+            .with("destination_token", destination)
+            .with("owner", TestAccount::signer())
+            .with("extra_account_meta_list", self.get(self.list(self.mint)))
+            .with("policy", self.get(self.policy(self.mint)))
+            .with("inbound", self.get(inbound))
+    }
+
+    fn execute_hook(&mut self, fixture: Fixture, amount: u64) -> std::result::Result<(), ProgramError> {
+        self.run(self.hook, TransferHookInstruction::Execute { amount }.pack(), fixture)
+    }
+}
+
+/// The hooks' setup instructions, encoded for whichever hook the world runs
+enum HookInstruction {
+    CreatePolicy { max_per_tx: u64, max_per_day: u64 },
+    OpenInbound,
+    InitializeExtraAccountMetaList { policy: Pubkey },
+}
+
+fn instruction_data(hook: Pubkey, ix: HookInstruction) -> Vec<u8> {
+    use {guarded_hook::instruction as guarded, naive_hook::instruction as naive};
+
+    match (hook == naive_hook::ID, ix) {
+        (true, HookInstruction::CreatePolicy { max_per_tx, max_per_day }) => {
+            naive::CreatePolicy { max_per_tx, max_per_day }.data()
+        }
+        (false, HookInstruction::CreatePolicy { max_per_tx, max_per_day }) => {
+            guarded::CreatePolicy { max_per_tx, max_per_day }.data()
+        }
+        (true, HookInstruction::OpenInbound) => naive::OpenInbound {}.data(),
+        (false, HookInstruction::OpenInbound) => guarded::OpenInbound {}.data(),
+        (true, HookInstruction::InitializeExtraAccountMetaList { policy }) => {
+            naive::InitializeExtraAccountMetaList { policy }.data()
+        }
+        (false, HookInstruction::InitializeExtraAccountMetaList { .. }) => {
+            guarded::InitializeExtraAccountMetaList {}.data()
+        }
+    }
+}
+
+#[test]
+fn deposits_count_against_the_vaults_inbound_limit() {
+    let mut world = World::new(guarded_hook::ID);
+
+    world.deposit(MAX_PER_TX).unwrap();
+    world.deposit(MAX_PER_TX).unwrap();
+    assert_eq!(world.received_today(world.vault), MAX_PER_DAY);
+    assert_eq!(world.token_account(world.tokens).amount, MAX_PER_DAY);
+    assert_eq!(world.get(world.position).state::<Position>().balance, MAX_PER_DAY);
+
+    assert_eq!(world.deposit(1), Err(Error::from(GuardError::WindowLimitExceeded).into()));
+    assert_eq!(world.deposit(MAX_PER_TX + 1), Err(Error::from(GuardError::TransactionLimitExceeded).into()));
+    assert_eq!(world.token_account(world.alice_tokens).amount, BALANCE - MAX_PER_DAY);
+
+    // A new day, a new allowance
+    warp_to(NOON + DAY_SECONDS);
+    world.deposit(MAX_PER_TX).unwrap();
+}
+
+#[test]
+fn withdrawals_count_against_the_receivers_limit() {
+    let mut world = World::new(guarded_hook::ID);
+    world.deposit(MAX_PER_TX).unwrap();
+
+    world.withdraw(world.alice_tokens, MAX_PER_TX).unwrap();
+    assert_eq!(world.received_today(world.alice), MAX_PER_TX);
+    assert_eq!(world.token_account(world.alice_tokens).amount, BALANCE);
+    assert_eq!(world.get(world.vault).state::<Vault>().total_deposits, 0);
+
+    assert_eq!(
+        world.withdraw(world.alice_tokens, 1),
+        Err(Error::from(VaultError::InsufficientBalance).into())
+    );
+}
+
+#[test]
+fn a_direct_naive_call_spends_a_strangers_limit() {
+    let mut world = World::new(naive_hook::ID);
+    let bob = Pubkey::new_unique();
+    world.open_inbound(bob);
+    let bob_tokens = TestAccount::hooked_token_account(world.mint, bob, 0);
+    world.insert(bob_tokens.clone());
+
+    // Mallory calls `Execute` herself: no transfer, tokens of no mint, Bob's counter
+    for _ in 0..2 {
+        let fixture = world.execute_fixture(TestAccount::signer(), TestAccount::signer(), world.inbound(bob));
+        world.execute_hook(fixture, MAX_PER_TX).unwrap();
+    }
+    assert_eq!(world.received_today(bob), MAX_PER_DAY);
+    assert_eq!(world.token_account(bob_tokens.key).amount, 0);
+
+    // Bob now cannot receive a single token today
+    world.deposit(MAX_PER_TX).unwrap();
+    assert_eq!(
+        world.withdraw(bob_tokens.key, 1),
+        Err(Error::from(GuardError::WindowLimitExceeded).into())
+    );
+}
+
+#[test]
+fn the_guarded_hook_only_counts_inside_a_transfer() {
+    let mut world = World::new(guarded_hook::ID);
+    let (source, destination) = (world.get(world.alice_tokens), world.get(world.tokens));
+
+    // The right accounts, but Token-2022 is not transferring
+    let fixture = world.execute_fixture(source.clone(), destination.clone(), world.inbound(world.vault));
+    assert_eq!(
+        world.execute_hook(fixture, MAX_PER_TX),
+        Err(Error::from(guarded_hook::ErrorCode::NotTransferring).into())
+    );
+
+    // A source mid-transfer of another mint, as a hook of another mint could pass it
+    let other_mint = Pubkey::new_unique();
+    let foreign = TestAccount::hooked_token_account(other_mint, world.alice, BALANCE).transferring();
+    let fixture = world.execute_fixture(foreign, destination, world.inbound(world.vault));
+    assert_eq!(
+        world.execute_hook(fixture, MAX_PER_TX),
+        Err(Error::from(ErrorCode::ConstraintTokenMint).into())
+    );
+    assert_eq!(world.received_today(world.vault), 0);
+}
+
+#[test]
+fn the_guarded_hook_derives_its_extra_accounts() {
+    let mut world = World::new(guarded_hook::ID);
+    let source = world.get(world.alice_tokens).transferring();
+    let destination = world.get(world.tokens);
+
+    let valid = world.execute_fixture(source, destination, world.inbound(world.vault));
+    world.execute_hook(valid.clone(), 1).unwrap();
+
+    // Alice's counter for a transfer to the vault
+    let mut wrong_inbound = valid.clone();
+    *wrong_inbound.account_mut("inbound") = world.get(world.inbound(world.alice));
+    assert_eq!(world.execute_hook(wrong_inbound, 1), Err(Error::from(ErrorCode::ConstraintSeeds).into()));
+
+    // An account that is not the mint's validation account
+    let mut wrong_list = valid;
+    wrong_list.account_mut("extra_account_meta_list").key = Pubkey::new_unique();
+    assert_eq!(world.execute_hook(wrong_list, 1), Err(Error::from(ErrorCode::ConstraintSeeds).into()));
+    assert_eq!(world.received_today(world.vault), 1);
+}
+
+#[test]
+fn a_front_run_naive_list_lifts_the_limits() {
+    let mut world = World::unlisted(naive_hook::ID);
+
+    // Mallory makes a mint of her own, with a policy of no real limit, and lists it for the victim mint
+    let mallory = TestAccount::signer();
+    let mallory_mint = TestAccount::mint(mallory.key, DECIMALS, 0);
+    let mallory_policy = world.policy(mallory_mint.key);
+    world
+        .run(
+            naive_hook::ID,
+            naive_hook::instruction::CreatePolicy { max_per_tx: u64::MAX, max_per_day: u64::MAX }.data(),
+            Fixture::new()
+                .with("policy", TestAccount::uninitialized(naive_hook::Policy::SPACE).at(mallory_policy))
+                .with("mint", mallory_mint)
+                .with("authority", mallory.clone())
+                .with("system_program", TestAccount::system_program()),
+        )
+        .unwrap();
+    world.write_list(mallory, mallory_policy).unwrap();
+
+    // The mint authority is too late
+    let authority = world.get(world.authority);
+    let policy = world.policy(world.mint);
+    assert_eq!(world.write_list(authority, policy), Err(account_already_in_use()));
+
+    // Every transfer is now checked against Mallory's policy
+    world.deposit(BALANCE).unwrap();
+    assert_eq!(world.token_account(world.tokens).amount, BALANCE);
+}
+
+#[test]
+fn only_the_mint_authority_writes_the_guarded_list() {
+    let mut world = World::unlisted(guarded_hook::ID);
+    let policy = world.policy(world.mint);
+
+    assert_eq!(
+        world.write_list(TestAccount::signer(), policy),
+        Err(Error::from(ErrorCode::ConstraintMintMintAuthority).into())
+    );
+    let authority = world.get(world.authority);
+    world.write_list(authority, policy).unwrap();
+    world.deposit(MAX_PER_TX).unwrap();
+}
+
+#[test]
+fn initialize_vault_pins_the_mints_hook() {
+    let world = World::new(guarded_hook::ID);
+    let mint = world.get(world.mint);
+    let (vault, _) = Pubkey::find_program_address(&[b"vault", mint.key.as_ref()], &crate::ID);
+    let mut fixture = Fixture::new()
+        .with("vault", TestAccount::uninitialized(Vault::SPACE).at(vault))
+        .with("mint", mint)
+        .with("tokens", world.get(world.tokens))
+        .with("payer", TestAccount::signer())
+        .with("token_program", TestAccount::token_2022_program())
+        .with("system_program", TestAccount::system_program());
+
+    execute!(&mut fixture, instruction::InitializeVault {}).unwrap();
+    assert_eq!(fixture.state::<Vault>("vault").hook_program, guarded_hook::ID);
+}
+
+#[test]
+fn deposits_stop_when_the_hook_changes() {
+    let mut world = World::new(guarded_hook::ID);
+    let mut vault = world.get(world.vault);
+    vault.edit(|vault: &mut Vault| vault.hook_program = naive_hook::ID);
+    world.insert(vault);
+
+    assert_eq!(world.deposit(MAX_PER_TX), Err(Error::from(VaultError::HookChanged).into()));
+}
+
+#[test]
+fn vault_contexts_enforce_their_constraints() {
+    let world = World::new(guarded_hook::ID);
+
+    assert_seeds_violation!(Deposit, world.deposit_fixture(), "position");
+    assert_has_one_violation!(Deposit, world.deposit_fixture(), "tokens");
+    assert_signer_violation!(Deposit, world.deposit_fixture(), "owner");
+    assert_seeds_violation!(Withdraw, world.withdraw_fixture(world.alice_tokens), "vault");
+    assert_signer_violation!(Withdraw, world.withdraw_fixture(world.alice_tokens), "owner");
+}
@@ -0,0 +1,15 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Amount must be greater than zero")]
+    ZeroAmount,
+    #[msg("Position balance is too low")]
+    InsufficientBalance,
+    #[msg("The mint has no transfer hook")]
+    NoTransferHook,
+    #[msg("The mint's transfer hook is not the one the vault was created with")]
+    HookChanged,
+}
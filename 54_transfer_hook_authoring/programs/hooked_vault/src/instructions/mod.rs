@@ -0,0 +1,113 @@
+//! Account contexts, one per instruction
+
+use anchor_spl::token_2022::Token2022;
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct InitializeVault<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = Vault::SPACE,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The vault's token account, created beforehand with the vault PDA as owner
+    #[account(token::mint = mint, token::authority = vault)]
+    pub tokens: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = Position::SPACE,
+        seeds = [b"position", vault.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump = vault.bump,
+        has_one = mint,
+        has_one = tokens
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"position", vault.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner
+    )]
+    pub position: Account<'info, Position>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub tokens: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint, token::authority = owner)]
+    pub owner_tokens: InterfaceAccount<'info, TokenAccount>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", mint.key().as_ref()],
+        bump = vault.bump,
+        has_one = mint,
+        has_one = tokens
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"position", vault.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner
+    )]
+    pub position: Account<'info, Position>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub tokens: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint)]
+    pub owner_tokens: InterfaceAccount<'info, TokenAccount>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
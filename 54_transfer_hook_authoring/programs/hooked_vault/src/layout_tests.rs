@@ -0,0 +1,29 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Position, Vault};
+
+#[test]
+fn vault_layout_is_stable() {
+    let vault = Vault {
+        mint: Pubkey::new_from_array([1; 32]),
+        tokens: Pubkey::new_from_array([2; 32]),
+        hook_program: Pubkey::new_from_array([3; 32]),
+        total_deposits: 0x4142434445464748,
+        bump: 81,
+    };
+    assert_golden!("Vault", golden::anchor(&vault));
+}
+
+#[test]
+fn position_layout_is_stable() {
+    let position = Position {
+        vault: Pubkey::new_from_array([6; 32]),
+        owner: Pubkey::new_from_array([7; 32]),
+        balance: 0x8182838485868788,
+        bump: 145,
+    };
+    assert_golden!("Position", golden::anchor(&position));
+}
@@ -0,0 +1,138 @@
+use anchor_spl::token_2022::spl_token_2022::extension::{transfer_hook, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022::onchain::invoke_transfer_checked;
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(test)]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// SECURE: A vault holding a Token-2022 mint whose transfers run a hook.
+///
+/// Every deposit and withdrawal is a transfer the hook must approve, so
+/// the vault composes with a program it did not write. It does so without
+/// trusting the caller about the hook: the hook program and its accounts
+/// are resolved from the mint and the mint's validation account, and the
+/// caller's remaining accounts are only a pool to resolve them from. The
+/// vault also pins the hook it was created with, and takes no deposits
+/// once the mint's hook authority has swapped in another program.
+#[program]
+pub mod hooked_vault {
+    use super::*;
+
+    /// Create the vault for `mint`, pinning the mint's current hook program
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        let hook_program = hook_program_of(&ctx.accounts.mint)?.ok_or(ErrorCode::NoTransferHook)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.mint = ctx.accounts.mint.key();
+        vault.tokens = ctx.accounts.tokens.key();
+        vault.hook_program = hook_program;
+        vault.total_deposits = 0;
+        vault.bump = ctx.bumps.vault;
+
+        msg!("Vault for {} initialized; transfers run {}", vault.mint, hook_program);
+        Ok(())
+    }
+
+    /// Open the caller's position in the vault
+    pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.vault = ctx.accounts.vault.key();
+        position.owner = ctx.accounts.owner.key();
+        position.balance = 0;
+        position.bump = ctx.bumps.position;
+
+        msg!("Position opened for {}", position.owner);
+        Ok(())
+    }
+
+    /// SECURE: Deposit `amount` tokens, with the hook's accounts as remaining accounts
+    ///
+    /// Security Fix: The transfer's extra accounts are resolved from the
+    /// mint's validation account, so a caller passing the wrong ones gets
+    /// a failed transfer, not a different check. Deposits stop if the hook
+    /// is no longer the one the vault was created with.
+    pub fn deposit<'info>(ctx: Context<'_, '_, 'info, 'info, Deposit<'info>>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroAmount);
+        // SECURITY: A swapped hook could refuse every withdrawal; take nothing in under it
+        require!(
+            hook_program_of(&ctx.accounts.mint)? == Some(ctx.accounts.vault.hook_program),
+            ErrorCode::HookChanged
+        );
+
+        // SECURITY: Hook program and extra accounts come from the mint, not the caller
+        invoke_transfer_checked(
+            ctx.accounts.token_program.key,
+            ctx.accounts.owner_tokens.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.tokens.to_account_info(),
+            ctx.accounts.owner.to_account_info(),
+            ctx.remaining_accounts,
+            amount,
+            ctx.accounts.mint.decimals,
+            &[],
+        )?;
+
+        let position = &mut ctx.accounts.position;
+        position.balance = position.balance.checked_add(amount).ok_or(ErrorCode::InsufficientBalance)?;
+        let vault = &mut ctx.accounts.vault;
+        vault.total_deposits = vault.total_deposits.checked_add(amount).ok_or(ErrorCode::InsufficientBalance)?;
+
+        msg!("Deposited {}; position holds {}", amount, position.balance);
+        Ok(())
+    }
+
+    /// SECURE: Withdraw `amount` tokens, with the hook's accounts as remaining accounts
+    ///
+    /// Security Fix: The position is debited before the transfer, and the
+    /// vault signs only the Token-2022 transfer; Token-2022 passes its
+    /// signature on to the hook as a plain account. Withdrawals run under
+    /// whatever hook the mint has now, so depositors can always try to
+    /// leave.
+    pub fn withdraw<'info>(ctx: Context<'_, '_, 'info, 'info, Withdraw<'info>>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroAmount);
+
+        let position = &mut ctx.accounts.position;
+        position.balance = position.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientBalance)?;
+        let vault = &mut ctx.accounts.vault;
+        vault.total_deposits = vault.total_deposits.checked_sub(amount).ok_or(ErrorCode::InsufficientBalance)?;
+
+        let mint = ctx.accounts.mint.key();
+        let seeds: &[&[u8]] = &[b"vault", mint.as_ref(), &[ctx.accounts.vault.bump]];
+        invoke_transfer_checked(
+            ctx.accounts.token_program.key,
+            ctx.accounts.tokens.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.owner_tokens.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.remaining_accounts,
+            amount,
+            ctx.accounts.mint.decimals,
+            &[seeds],
+        )?;
+
+        msg!("Withdrew {}; position holds {}", amount, ctx.accounts.position.balance);
+        Ok(())
+    }
+}
+
+/// The program `mint`'s transfers must call, if it has the `TransferHook` extension and a hook set
+fn hook_program_of(mint: &InterfaceAccount<Mint>) -> Result<Option<Pubkey>> {
+    let info = mint.to_account_info();
+    let data = info.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    Ok(transfer_hook::get_program_id(&state))
+}
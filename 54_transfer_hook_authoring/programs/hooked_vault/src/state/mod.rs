@@ -0,0 +1,37 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Vault {
+    /// Mint the vault holds (32 bytes)
+    pub mint: Pubkey,
+    /// The vault's token account (32 bytes)
+    pub tokens: Pubkey,
+    /// Hook program the mint ran when the vault was created (32 bytes)
+    pub hook_program: Pubkey,
+    /// Sum of all positions (8 bytes)
+    pub total_deposits: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Vault {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 1;
+}
+
+#[account]
+pub struct Position {
+    /// Vault the position is in (32 bytes)
+    pub vault: Pubkey,
+    /// Owner who may withdraw (32 bytes)
+    pub owner: Pubkey,
+    /// Tokens deposited and not withdrawn (8 bytes)
+    pub balance: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Position {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1;
+}
@@ -0,0 +1,95 @@
+//! Account contexts, one per instruction
+
+use anchor_spl::token_interface::Mint;
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct CreatePolicy<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Policy::SPACE,
+        seeds = [b"policy", mint.key().as_ref()],
+        bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    #[account(mint::authority = authority)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenInbound<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = Inbound::SPACE,
+        seeds = [b"inbound", mint.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub inbound: Account<'info, Inbound>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Any wallet; the counter only ever limits what it receives
+    pub wallet: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    /// CHECK: Written as the interface's TLV list, not an Anchor account
+    #[account(
+        init,
+        payer = payer,
+        space = ExtraAccountMetaList::size_of(2)?,
+        seeds = [META_LIST_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    /// CHECK: VULNERABILITY: Any mint, whoever its authority is
+    pub mint: UncheckedAccount<'info>,
+
+    /// VULNERABILITY: Any signer, not the mint authority
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// The accounts of the interface's `Execute`, in its order
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    /// CHECK: VULNERABILITY: Never read - neither its mint nor whether a transfer is running
+    pub source_token: UncheckedAccount<'info>,
+
+    /// CHECK: VULNERABILITY: Never compared with the policy's mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: VULNERABILITY: Never read, so never tied to the counter
+    pub destination_token: UncheckedAccount<'info>,
+
+    /// CHECK: The source's owner or delegate, passed without its signature
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: VULNERABILITY: Never checked to be the mint's validation account
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    /// VULNERABILITY: Any policy this program owns, for any mint
+    pub policy: Account<'info, Policy>,
+
+    /// VULNERABILITY: Any counter this program owns, for any wallet
+    #[account(mut)]
+    pub inbound: Account<'info, Inbound>,
+}
@@ -0,0 +1,122 @@
+//! VULNERABLE: The same transfer hook as `guarded_hook`, written trusting its callers.
+//!
+//! It caps what each wallet receives of a mint per day, and works as
+//! intended when Token-2022 calls it with the accounts its validation
+//! account lists. Nothing checks that either happened: anyone may write
+//! a mint's list, naming any policy, and anyone may call `Execute`
+//! directly, with any accounts.
+
+use anchor_spl::transfer_hook::spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use anchor_spl::transfer_hook::spl_transfer_hook_interface::instruction::{ExecuteInstruction, TransferHookInstruction};
+use secref_guards::velocity::{VelocityLimits, VelocityWindow, DAY_SECONDS};
+use secref_prelude::*;
+
+declare_id!("NaiveHook1111111111111111111111111111111111");
+
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Seed of a mint's validation account, fixed by the transfer hook interface
+pub const META_LIST_SEED: &[u8] = b"extra-account-metas";
+
+#[program]
+pub mod naive_hook {
+    use super::*;
+
+    /// Set `mint`'s inbound limits: `max_per_tx` per transfer, `max_per_day` per receiving wallet
+    pub fn create_policy(ctx: Context<CreatePolicy>, max_per_tx: u64, max_per_day: u64) -> Result<()> {
+        let policy = &mut ctx.accounts.policy;
+        policy.mint = ctx.accounts.mint.key();
+        policy.limits = VelocityLimits::new(max_per_tx, max_per_day, DAY_SECONDS)?;
+        policy.bump = ctx.bumps.policy;
+
+        msg!("Policy for {}: {} per transfer, {} per day", policy.mint, max_per_tx, max_per_day);
+        Ok(())
+    }
+
+    /// Open `wallet`'s inbound counter for the mint; anyone may pay for it
+    pub fn open_inbound(ctx: Context<OpenInbound>) -> Result<()> {
+        let inbound = &mut ctx.accounts.inbound;
+        inbound.mint = ctx.accounts.mint.key();
+        inbound.wallet = ctx.accounts.wallet.key();
+        inbound.window = VelocityWindow::default();
+        inbound.bump = ctx.bumps.inbound;
+
+        msg!("Inbound counter opened for {}", inbound.wallet);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This program contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Write the mint's validation account, naming `policy` as the policy to enforce
+    ///
+    /// Security Issue: Whoever calls this first decides what every transfer
+    /// of the mint is checked against. The mint authority is never asked,
+    /// and the policy is an address the caller passes, not the mint's own:
+    /// an attacker who writes a new mint's list first can point it at a
+    /// policy of theirs, for any limits they like.
+    pub fn initialize_extra_account_meta_list(ctx: Context<InitializeExtraAccountMetaList>, policy: Pubkey) -> Result<()> {
+        // VULNERABILITY: A fixed address from the caller, on a list anyone can write
+        let metas = [
+            ExtraAccountMeta::new_with_pubkey(&policy, false, false)?,
+            ExtraAccountMeta::new_with_seeds(
+                &[
+                    Seed::Literal { bytes: b"inbound".to_vec() },
+                    Seed::AccountKey { index: 1 },
+                    Seed::AccountData { account_index: 2, data_index: 32, length: 32 },
+                ],
+                false,
+                true,
+            )?,
+        ];
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+            &metas,
+        )?;
+
+        msg!("Validation account written for {}", ctx.accounts.mint.key());
+        Ok(())
+    }
+
+    /// VULNERABLE: Count a transfer of `amount` against the receiving wallet's daily limit
+    ///
+    /// Security Issue: Every account is taken as given. The policy and the
+    /// counter are whichever the caller passed, the token accounts are
+    /// never read - not their mint, not the `transferring` flag that only
+    /// Token-2022 can set - and the validation account is not checked to
+    /// be the mint's. Called directly, with a stranger's counter, the hook
+    /// spends the stranger's limit without a token moving.
+    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let inbound = &mut ctx.accounts.inbound;
+        // VULNERABILITY: Limits and counter from unvalidated extra accounts, outside any transfer
+        inbound.window.record(&ctx.accounts.policy.limits, amount, now)?;
+
+        msg!("{} received {} ({} today)", inbound.wallet, amount, inbound.window.used);
+        Ok(())
+    }
+
+    /// Route the interface's `Execute` to `transfer_hook`
+    pub fn fallback<'info>(program_id: &Pubkey, accounts: &'info [AccountInfo<'info>], data: &[u8]) -> Result<()> {
+        match TransferHookInstruction::unpack(data)? {
+            TransferHookInstruction::Execute { amount } => {
+                __private::__global::transfer_hook(program_id, accounts, &amount.to_le_bytes())
+            }
+            _ => Err(ProgramError::InvalidInstructionData.into()),
+        }
+    }
+}
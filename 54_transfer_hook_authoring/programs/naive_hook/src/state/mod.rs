@@ -0,0 +1,33 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Policy {
+    /// Mint the policy governs (32 bytes)
+    pub mint: Pubkey,
+    /// Per-transfer and per-day caps (24 bytes)
+    pub limits: VelocityLimits,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Policy {
+    pub const SPACE: usize = 8 + 32 + VelocityLimits::SPACE + 1;
+}
+
+#[account]
+pub struct Inbound {
+    /// Mint counted (32 bytes)
+    pub mint: Pubkey,
+    /// Wallet whose receipts are counted (32 bytes)
+    pub wallet: Pubkey,
+    /// What the wallet received in the current day (16 bytes)
+    pub window: VelocityWindow,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Inbound {
+    pub const SPACE: usize = 8 + 32 + 32 + VelocityWindow::SPACE + 1;
+}
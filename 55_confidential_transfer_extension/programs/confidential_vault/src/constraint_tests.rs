@@ -0,0 +1,335 @@
+//! The vault over a mint allowing confidential transfers, and over a plain
+//! Token-2022 mint: what a one-unit confidential transfer lets its sender
+//! claim from the naive vault, why even honest confidential deposits cannot
+//! be paid out, and the extension allowlist that keeps such mints out.
+
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+use anchor_spl::token_2022::spl_token_2022::error::TokenError;
+use anchor_spl::token_2022::spl_token_2022::extension::confidential_transfer::ConfidentialTransferAccount;
+use anchor_spl::token_2022::spl_token_2022::extension::confidential_transfer::instruction::inner_transfer;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022::processor::Processor;
+use anchor_spl::token_2022::spl_token_2022::proof::ProofLocation;
+use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::encryption::auth_encryption::AeKey;
+use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::encryption::elgamal::{ElGamalKeypair, ElGamalPubkey};
+use anchor_spl::token_2022::spl_token_2022::state::Account;
+use anchor_spl::token_interface::TokenAccount;
+use secref_guards::GuardError;
+use secref_testkit::{assert_has_one_violation, execute, Fixture, TestAccount};
+
+use crate::{instruction, ErrorCode as VaultError, Position, Vault, VulnerableEnableConfidentialDeposits};
+
+const DECIMALS: u8 = 6;
+const BALANCE: u64 = 5_000_000;
+/// What Mallory holds confidentially, and could at most have sent
+const CONFIDENTIAL_BALANCE: u64 = 1_000;
+
+/// A vault over `mint`, its authority, and the accounts of everyone who used it, by address
+struct World {
+    accounts: BTreeMap<Pubkey, TestAccount>,
+    mint: Pubkey,
+    vault: Pubkey,
+    tokens: Pubkey,
+    authority: Pubkey,
+}
+
+impl World {
+    /// A vault over a mint allowing confidential transfers, created by the naive initializer
+    fn confidential() -> Self {
+        let authority = TestAccount::signer();
+        let mint = TestAccount::confidential_mint(authority.key, DECIMALS, 10 * BALANCE);
+        Self::initialize(mint, authority, |mint, vault| TestAccount::confidential_token_account(mint, vault, 0), false)
+            .unwrap()
+    }
+
+    /// A vault over a Token-2022 mint without extensions, created by the secure initializer
+    fn plain() -> Self {
+        let authority = TestAccount::signer();
+        let mint = TestAccount::mint(authority.key, DECIMALS, 10 * BALANCE).owned_by(anchor_spl::token_2022::ID);
+        Self::initialize(mint, authority, plain_token_account, true).unwrap()
+    }
+
+    fn initialize(
+        mint: TestAccount,
+        authority: TestAccount,
+        token_account: fn(Pubkey, Pubkey) -> TestAccount,
+        secure: bool,
+    ) -> std::result::Result<Self, ProgramError> {
+        let (vault, _) = Pubkey::find_program_address(&[b"vault", mint.key.as_ref()], &crate::ID);
+        let tokens = token_account(mint.key, vault);
+        let mut world = Self {
+            accounts: BTreeMap::new(),
+            mint: mint.key,
+            vault,
+            tokens: tokens.key,
+            authority: authority.key,
+        };
+
+        let mut fixture = Fixture::new()
+            .with("vault", TestAccount::uninitialized(Vault::SPACE).at(vault))
+            .with("mint", mint)
+            .with("tokens", tokens)
+            .with("authority", authority)
+            .with("token_program", TestAccount::token_2022_program())
+            .with("system_program", TestAccount::system_program());
+        match secure {
+            true => execute!(&mut fixture, instruction::SecureInitializeVault {}),
+            false => execute!(&mut fixture, instruction::VulnerableInitializeVault {}),
+        }?;
+        world.update(&fixture);
+        Ok(world)
+    }
+
+    fn insert(&mut self, account: TestAccount) {
+        self.accounts.insert(account.key, account);
+    }
+
+    fn get(&self, key: Pubkey) -> TestAccount {
+        self.accounts.get(&key).cloned().unwrap_or_else(|| panic!("no account at {key}"))
+    }
+
+    fn amount(&self, tokens: Pubkey) -> u64 {
+        TokenAccount::try_deserialize(&mut &self.get(tokens).data[..]).unwrap().amount
+    }
+
+    fn position(&self, owner: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"position", self.vault.as_ref(), owner.as_ref()], &crate::ID).0
+    }
+
+    /// Run a vault instruction over `fixture`, keeping what it wrote if it succeeds
+    fn run(&mut self, mut fixture: Fixture, ix: impl InstructionData) -> std::result::Result<(), ProgramError> {
+        let result = execute!(&mut fixture, ix);
+        if result.is_ok() {
+            self.update(&fixture);
+        }
+        result
+    }
+
+    /// Carry the accounts a fixture changed into the next one
+    fn update(&mut self, fixture: &Fixture) {
+        for (_, changed) in fixture.accounts() {
+            if !changed.executable {
+                self.insert(changed.clone());
+            }
+        }
+    }
+
+    /// A depositor holding `tokens`, with an open position
+    fn depositor(&mut self, tokens: impl FnOnce(Pubkey, Pubkey) -> TestAccount) -> (Pubkey, Pubkey) {
+        let owner = TestAccount::signer();
+        let owner_tokens = tokens(self.mint, owner.key);
+        let (owner_key, tokens_key) = (owner.key, owner_tokens.key);
+        self.insert(owner_tokens);
+        let fixture = Fixture::new()
+            .with("vault", self.get(self.vault))
+            .with("position", TestAccount::uninitialized(Position::SPACE).at(self.position(owner.key)))
+            .with("owner", owner)
+            .with("system_program", TestAccount::system_program());
+        self.run(fixture, instruction::OpenPosition {}).unwrap();
+        (owner_key, tokens_key)
+    }
+
+    fn transfer_fixture(&self, owner: Pubkey, owner_tokens: Pubkey) -> Fixture {
+        Fixture::new()
+            .with("vault", self.get(self.vault))
+            .with("position", self.get(self.position(owner)))
+            .with("mint", self.get(self.mint))
+            .with("tokens", self.get(self.tokens))
+            .with("owner_tokens", self.get(owner_tokens))
+            .with("owner", self.get(owner))
+            .with("token_program", TestAccount::token_2022_program())
+    }
+
+    fn deposit(&mut self, owner: Pubkey, owner_tokens: Pubkey, amount: u64) -> std::result::Result<(), ProgramError> {
+        self.run(self.transfer_fixture(owner, owner_tokens), instruction::Deposit { amount })
+    }
+
+    fn withdraw(&mut self, owner: Pubkey, owner_tokens: Pubkey, amount: u64) -> std::result::Result<(), ProgramError> {
+        self.run(self.transfer_fixture(owner, owner_tokens), instruction::Withdraw { amount })
+    }
+
+    fn enable_fixture(&self, elgamal: &ElGamalKeypair) -> Fixture {
+        Fixture::new()
+            .with("vault", self.get(self.vault))
+            .with("mint", self.get(self.mint))
+            .with("tokens", self.get(self.tokens))
+            .with("proof_context", TestAccount::pubkey_validity_proof(elgamal))
+            .with("authority", self.get(self.authority))
+            .with("token_program", TestAccount::token_2022_program())
+    }
+
+    /// Configure the vault's token account for confidential transfers under `elgamal`
+    fn enable_confidential_deposits(&mut self, elgamal: &ElGamalKeypair) {
+        let decryptable_zero_balance = AeKey::new_rand().encrypt(0).to_bytes();
+        self.run(
+            self.enable_fixture(elgamal),
+            instruction::VulnerableEnableConfidentialDeposits { decryptable_zero_balance },
+        )
+        .unwrap();
+    }
+
+    /// `owner` confidentially sends `amount` from `owner_tokens` to the vault, as a top-level Token-2022 instruction
+    fn send_confidentially(&mut self, owner: Pubkey, owner_tokens: Pubkey, elgamal: &ElGamalKeypair, amount: u64) {
+        let source = self.get(owner_tokens);
+        let proof = source.transfer_proof(elgamal, &self.vault_pubkey(), amount);
+        let ix: Instruction = inner_transfer(
+            &anchor_spl::token_2022::ID,
+            &owner_tokens,
+            &self.mint,
+            &self.tokens,
+            AeKey::new_rand().encrypt(CONFIDENTIAL_BALANCE - amount).into(),
+            &owner,
+            &[],
+            ProofLocation::ContextStateAccount(&proof.key),
+        )
+        .unwrap();
+
+        let mut fixture = Fixture::new()
+            .with("source", source)
+            .with("mint", self.get(self.mint))
+            .with("destination", self.get(self.tokens))
+            .with("proof_context", proof)
+            .with("authority", self.get(owner));
+        {
+            let (infos, _) = fixture.split();
+            Processor::process(&anchor_spl::token_2022::ID, &infos, &ix.data).unwrap();
+        }
+        fixture.sync();
+        self.update(&fixture);
+    }
+
+    /// The ElGamal key the vault's token account is configured under
+    fn vault_pubkey(&self) -> ElGamalPubkey {
+        let tokens = self.get(self.tokens);
+        let state = StateWithExtensions::<Account>::unpack(&tokens.data).unwrap();
+        let extension = state.get_extension::<ConfidentialTransferAccount>().expect("a configured vault");
+        extension.elgamal_pubkey.try_into().unwrap()
+    }
+
+    fn claim_confidential(&mut self, owner: Pubkey, amount: u64) -> std::result::Result<(), ProgramError> {
+        let fixture = Fixture::new()
+            .with("vault", self.get(self.vault))
+            .with("position", self.get(self.position(owner)))
+            .with("tokens", self.get(self.tokens))
+            .with("owner", self.get(owner));
+        self.run(fixture, instruction::VulnerableDepositConfidential { amount })
+    }
+}
+
+fn plain_token_account(mint: Pubkey, authority: Pubkey) -> TestAccount {
+    TestAccount::token_account(mint, authority, 0).owned_by(anchor_spl::token_2022::ID)
+}
+
+#[test]
+fn a_one_unit_confidential_transfer_is_credited_as_claimed() {
+    let mut world = World::confidential();
+    let (alice, alice_tokens) =
+        world.depositor(|mint, owner| TestAccount::confidential_token_account(mint, owner, BALANCE));
+    world.deposit(alice, alice_tokens, BALANCE).unwrap();
+
+    let operator = ElGamalKeypair::new_rand();
+    world.enable_confidential_deposits(&operator);
+
+    // Mallory sends the vault a single base unit, and claims Alice's deposit
+    let mallory_key = ElGamalKeypair::new_rand();
+    let (mallory, mallory_tokens) = world.depositor(|mint, owner| {
+        TestAccount::confidential_token_account(mint, owner, 0).configured(&mallory_key, CONFIDENTIAL_BALANCE)
+    });
+    world.send_confidentially(mallory, mallory_tokens, &mallory_key, 1);
+    world.claim_confidential(mallory, BALANCE).unwrap();
+    world.withdraw(mallory, mallory_tokens, BALANCE).unwrap();
+    assert_eq!(world.amount(mallory_tokens), BALANCE);
+
+    // Alice's position is intact, and unpayable
+    assert_eq!(world.get(world.position(alice)).state::<Position>().balance, BALANCE);
+    assert_eq!(world.withdraw(alice, alice_tokens, 1), Err(TokenError::InsufficientFunds.into()));
+
+    // All the vault holds is the unit Mallory sent, pending, under the operator's key
+    assert_eq!(world.amount(world.tokens), 0);
+    assert_eq!(world.get(world.tokens).confidential_balances(&operator), (1, 0));
+}
+
+#[test]
+fn honest_confidential_deposits_cannot_be_paid_out() {
+    let mut world = World::confidential();
+    let operator = ElGamalKeypair::new_rand();
+    world.enable_confidential_deposits(&operator);
+
+    let bob_key = ElGamalKeypair::new_rand();
+    let (bob, bob_tokens) = world.depositor(|mint, owner| {
+        TestAccount::confidential_token_account(mint, owner, 0).configured(&bob_key, CONFIDENTIAL_BALANCE)
+    });
+    world.send_confidentially(bob, bob_tokens, &bob_key, CONFIDENTIAL_BALANCE);
+    world.claim_confidential(bob, CONFIDENTIAL_BALANCE).unwrap();
+
+    // The tokens are pending in the vault; its public balance, which withdrawals pay from, is empty
+    assert_eq!(
+        world.get(world.tokens).confidential_balances(&operator),
+        (CONFIDENTIAL_BALANCE, 0)
+    );
+    assert_eq!(world.withdraw(bob, bob_tokens, CONFIDENTIAL_BALANCE), Err(TokenError::InsufficientFunds.into()));
+
+    // One transfer, one claim
+    assert_eq!(
+        world.claim_confidential(bob, CONFIDENTIAL_BALANCE),
+        Err(Error::from(VaultError::NoConfidentialDeposit).into())
+    );
+}
+
+#[test]
+fn confidential_claims_need_a_configured_vault() {
+    let mut world = World::confidential();
+    let (alice, _) = world.depositor(|mint, owner| TestAccount::confidential_token_account(mint, owner, BALANCE));
+
+    assert_eq!(
+        world.claim_confidential(alice, BALANCE),
+        Err(Error::from(VaultError::NoConfidentialDeposit).into())
+    );
+}
+
+#[test]
+fn the_secure_vault_refuses_confidential_mints() {
+    let authority = TestAccount::signer();
+    let mint = TestAccount::confidential_mint(authority.key, DECIMALS, BALANCE);
+    let result = World::initialize(
+        mint,
+        authority,
+        |mint, vault| TestAccount::confidential_token_account(mint, vault, 0),
+        true,
+    );
+
+    assert_eq!(result.err(), Some(Error::from(GuardError::UnsupportedMintExtension).into()));
+}
+
+#[test]
+fn the_secure_vault_takes_mints_without_extensions() {
+    let mut world = World::plain();
+    let (alice, alice_tokens) = world.depositor(|mint, owner| {
+        TestAccount::token_account(mint, owner, BALANCE).owned_by(anchor_spl::token_2022::ID)
+    });
+
+    world.deposit(alice, alice_tokens, BALANCE).unwrap();
+    assert_eq!(world.amount(world.tokens), BALANCE);
+    assert_eq!(world.get(world.vault).state::<Vault>().total_deposits, BALANCE);
+
+    world.withdraw(alice, alice_tokens, BALANCE).unwrap();
+    assert_eq!(world.amount(alice_tokens), BALANCE);
+    assert_eq!(
+        world.withdraw(alice, alice_tokens, 1),
+        Err(Error::from(VaultError::InsufficientBalance).into())
+    );
+}
+
+#[test]
+fn only_the_vault_authority_configures_its_token_account() {
+    let world = World::confidential();
+    assert_has_one_violation!(
+        VulnerableEnableConfidentialDeposits,
+        world.enable_fixture(&ElGamalKeypair::new_rand()),
+        "authority"
+    );
+}
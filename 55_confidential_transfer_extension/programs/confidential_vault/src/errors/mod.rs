@@ -0,0 +1,13 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Amount must be greater than zero")]
+    ZeroAmount,
+    #[msg("Position balance is too low")]
+    InsufficientBalance,
+    #[msg("No confidential transfer has reached the vault since the last one was claimed")]
+    NoConfidentialDeposit,
+}
@@ -0,0 +1,46 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableEnableConfidentialDeposits<'info> {
+    #[account(
+        seeds = [b"vault", mint.key().as_ref()],
+        bump = vault.bump,
+        has_one = mint,
+        has_one = tokens,
+        has_one = authority
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub tokens: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Read by Token-2022, which requires a verified pubkey validity proof
+    pub proof_context: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableDepositConfidential<'info> {
+    #[account(mut, has_one = tokens)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"position", vault.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump,
+        has_one = owner
+    )]
+    pub position: Account<'info, Position>,
+
+    /// VULNERABILITY: Read for its pending credit counter, which says nothing of amounts
+    pub tokens: InterfaceAccount<'info, TokenAccount>,
+
+    pub owner: Signer<'info>,
+}
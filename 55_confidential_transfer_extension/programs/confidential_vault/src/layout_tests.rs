@@ -0,0 +1,30 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Position, Vault};
+
+#[test]
+fn vault_layout_is_stable() {
+    let vault = Vault {
+        mint: Pubkey::new_from_array([1; 32]),
+        tokens: Pubkey::new_from_array([2; 32]),
+        authority: Pubkey::new_from_array([3; 32]),
+        total_deposits: 0x4142434445464748,
+        confidential_credits: 0x5152535455565758,
+        bump: 97,
+    };
+    assert_golden!("Vault", golden::anchor(&vault));
+}
+
+#[test]
+fn position_layout_is_stable() {
+    let position = Position {
+        vault: Pubkey::new_from_array([7; 32]),
+        owner: Pubkey::new_from_array([8; 32]),
+        balance: 0x9192939495969798,
+        bump: 161,
+    };
+    assert_golden!("Position", golden::anchor(&position));
+}
@@ -0,0 +1,279 @@
+#[cfg(feature = "vulnerable")]
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+#[cfg(feature = "vulnerable")]
+use anchor_spl::token_2022::spl_token_2022::extension::confidential_transfer::ConfidentialTransferAccount;
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+#[cfg(feature = "vulnerable")]
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+#[cfg(feature = "vulnerable")]
+use anchor_spl::token_2022::spl_token_2022::state::Account as TokenAccountState;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TransferChecked};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Mint extensions the secure vault accepts: metadata, which moves no tokens
+pub const SUPPORTED_EXTENSIONS: &[ExtensionType] = &[ExtensionType::MetadataPointer, ExtensionType::TokenMetadata];
+
+/// Pending credits a configured vault account takes before it must apply them
+#[cfg(feature = "vulnerable")]
+const MAX_PENDING_CREDITS: u64 = 65_536;
+
+/// A token vault crediting each owner's position with what they deposit.
+///
+/// Written for SPL Token mints, it takes Token-2022 mints through the
+/// token interface and sees the same `Mint` and `TokenAccount` fields. A
+/// mint with the `ConfidentialTransferMint` extension changes what those
+/// fields mean: `amount` is only the account's public balance, and
+/// confidential transfers credit an encrypted pending balance the vault
+/// cannot read, let alone pay out with a public transfer.
+#[feature_gated]
+#[program]
+pub mod confidential_vault {
+    use super::*;
+    #[vulnerable]
+    use anchor_lang::solana_program::program::invoke_signed;
+    #[secure]
+    use secref_guards::mint_extension::assert_mint_extensions;
+
+    /// Open the caller's position in the vault
+    pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.vault = ctx.accounts.vault.key();
+        position.owner = ctx.accounts.owner.key();
+        position.balance = 0;
+        position.bump = ctx.bumps.position;
+
+        msg!("Position opened for {}", position.owner);
+        Ok(())
+    }
+
+    /// Deposit `amount` tokens with a public transfer
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroAmount);
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.owner_tokens.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.tokens.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        let position = &mut ctx.accounts.position;
+        position.balance = position.balance.checked_add(amount).ok_or(ErrorCode::InsufficientBalance)?;
+        let vault = &mut ctx.accounts.vault;
+        vault.total_deposits = vault.total_deposits.checked_add(amount).ok_or(ErrorCode::InsufficientBalance)?;
+
+        msg!("Deposited {}; position holds {}", amount, position.balance);
+        Ok(())
+    }
+
+    /// Withdraw `amount` tokens with a public transfer
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroAmount);
+
+        let position = &mut ctx.accounts.position;
+        position.balance = position.balance.checked_sub(amount).ok_or(ErrorCode::InsufficientBalance)?;
+        let vault = &mut ctx.accounts.vault;
+        vault.total_deposits = vault.total_deposits.checked_sub(amount).ok_or(ErrorCode::InsufficientBalance)?;
+
+        let mint = ctx.accounts.mint.key();
+        let seeds: &[&[u8]] = &[b"vault", mint.as_ref(), &[ctx.accounts.vault.bump]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.tokens.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.owner_tokens.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        msg!("Withdrew {}; position holds {}", amount, ctx.accounts.position.balance);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Create the vault for `mint`, whatever its extensions
+    ///
+    /// Security Issue: The vault treats every mint as an SPL Token mint
+    /// with another owner. A mint allowing confidential transfers is taken
+    /// like any other, and nothing the vault later reads from its token
+    /// account says how many tokens it really holds.
+    #[vulnerable]
+    pub fn vulnerable_initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        // VULNERABILITY: The mint's extensions are never looked at
+        initialize(ctx)
+    }
+
+    /// VULNERABLE: Let the vault's token account receive confidential transfers
+    ///
+    /// `decryptable_zero_balance` is zero under the authority's AE key,
+    /// and `proof_context` holds the verified proof that the authority's
+    /// ElGamal key, the one the account is configured under, is valid.
+    #[vulnerable]
+    pub fn vulnerable_enable_confidential_deposits(
+        ctx: Context<VulnerableEnableConfidentialDeposits>,
+        decryptable_zero_balance: [u8; 36],
+    ) -> Result<()> {
+        let mint = ctx.accounts.mint.key();
+        let seeds: &[&[u8]] = &[b"vault", mint.as_ref(), &[ctx.accounts.vault.bump]];
+        invoke_signed(
+            &configure_account(
+                &ctx.accounts.tokens.key(),
+                &mint,
+                ctx.accounts.proof_context.key,
+                &ctx.accounts.vault.key(),
+                decryptable_zero_balance,
+            ),
+            &[
+                ctx.accounts.tokens.to_account_info(),
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.proof_context.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+            ],
+            &[seeds],
+        )?;
+
+        msg!("Vault tokens {} take confidential transfers", ctx.accounts.tokens.key());
+        Ok(())
+    }
+
+    /// VULNERABLE: Credit `amount` tokens for a confidential transfer the owner sent the vault
+    ///
+    /// Security Issue: A confidential transfer's amount is encrypted; all
+    /// the vault can see is that its pending balance counter moved. So it
+    /// takes the depositor's word for the amount. A transfer of one base
+    /// unit is credited as however much the sender claims, and the claim
+    /// is paid out with public transfers from tokens other depositors
+    /// sent. Even honest claims are paid from the public balance, while
+    /// the tokens that back them sit in a pending balance only the
+    /// authority's ElGamal key can decrypt and apply.
+    #[vulnerable]
+    pub fn vulnerable_deposit_confidential(ctx: Context<VulnerableDepositConfidential>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroAmount);
+
+        // VULNERABILITY: A new pending credit says a transfer arrived, not how much it carried
+        let credits = pending_credits(&ctx.accounts.tokens)?;
+        require!(credits > ctx.accounts.vault.confidential_credits, ErrorCode::NoConfidentialDeposit);
+
+        let vault = &mut ctx.accounts.vault;
+        vault.confidential_credits = credits;
+        // VULNERABILITY: Credited with the amount the depositor claims, against the public balance
+        vault.total_deposits = vault.total_deposits.checked_add(amount).ok_or(ErrorCode::InsufficientBalance)?;
+        let position = &mut ctx.accounts.position;
+        position.balance = position.balance.checked_add(amount).ok_or(ErrorCode::InsufficientBalance)?;
+
+        msg!("Credited {} for a confidential deposit; position holds {}", amount, position.balance);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version: the vault takes only mints
+    // whose extensions it has been written for, and every token it holds is
+    // one a public transfer brought in and can take out.
+
+    /// SECURE: Create the vault for `mint`, if it has only extensions the vault supports
+    ///
+    /// Security Fix: Mints are checked against an allowlist of extensions,
+    /// not a denylist. A mint allowing confidential transfers - or one with
+    /// an extension added to Token-2022 after the vault was written - is
+    /// refused, so the vault's token account can never be configured for
+    /// confidential transfers and its `amount` is all it holds.
+    #[secure]
+    pub fn secure_initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        // SECURITY: Every extension of the mint must be one the vault handles
+        assert_mint_extensions(&ctx.accounts.mint.to_account_info(), SUPPORTED_EXTENSIONS)?;
+        initialize(ctx)
+    }
+}
+
+fn initialize(ctx: Context<InitializeVault>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    vault.mint = ctx.accounts.mint.key();
+    vault.tokens = ctx.accounts.tokens.key();
+    vault.authority = ctx.accounts.authority.key();
+    vault.total_deposits = 0;
+    vault.confidential_credits = 0;
+    vault.bump = ctx.bumps.vault;
+
+    msg!("Vault for {} initialized", vault.mint);
+    Ok(())
+}
+
+/// Token-2022's `ConfigureAccount`, with the pubkey validity proof in a context state account
+///
+/// Encoded by hand: the builder in `spl-token-2022` is not built for
+/// on-chain programs in every release the examples support.
+#[cfg(feature = "vulnerable")]
+fn configure_account(
+    tokens: &Pubkey,
+    mint: &Pubkey,
+    proof_context: &Pubkey,
+    owner: &Pubkey,
+    decryptable_zero_balance: [u8; 36],
+) -> Instruction {
+    // ConfidentialTransferExtension, then ConfigureAccount
+    let mut data = vec![27, 2];
+    data.extend_from_slice(&decryptable_zero_balance);
+    data.extend_from_slice(&MAX_PENDING_CREDITS.to_le_bytes());
+    // Proof instruction offset 0: the proof is in `proof_context`
+    data.push(0);
+
+    Instruction {
+        program_id: anchor_spl::token_2022::ID,
+        accounts: vec![
+            AccountMeta::new(*tokens, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*proof_context, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+        data,
+    }
+}
+
+/// How many confidential transfers and deposits `tokens` has received since its pending balance was last applied
+#[cfg(feature = "vulnerable")]
+fn pending_credits(tokens: &InterfaceAccount<TokenAccount>) -> Result<u64> {
+    let info = tokens.to_account_info();
+    let data = info.try_borrow_data()?;
+    let state = StateWithExtensions::<TokenAccountState>::unpack(&data)?;
+    let extension = state
+        .get_extension::<ConfidentialTransferAccount>()
+        .map_err(|_| error!(ErrorCode::NoConfidentialDeposit))?;
+    Ok(extension.pending_balance_credit_counter.into())
+}
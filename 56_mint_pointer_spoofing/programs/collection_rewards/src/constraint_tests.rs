@@ -0,0 +1,288 @@
+//! A pool for a curated collection, and the mints that try to pass as its
+//! members: pointers to a real member's metadata or membership, metadata
+//! that merely names the curator, and members of another collection.
+
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::*;
+use anchor_lang::InstructionData;
+use secref_guards::GuardError;
+use secref_testkit::runtime::account_already_in_use;
+use secref_testkit::{execute, Fixture, TestAccount};
+
+use crate::{instruction, Claim, ErrorCode as PoolError, Pool};
+
+/// Lamports paid per mint in the collection
+const REWARD: u64 = 1_000_000;
+/// Members the collection's group can hold
+const MAX_SIZE: u32 = 100;
+
+/// A funded pool for a curated collection, and everyone claiming from it, by address
+struct World {
+    accounts: BTreeMap<Pubkey, TestAccount>,
+    curator: Pubkey,
+    group: Pubkey,
+    pool: Pubkey,
+}
+
+impl World {
+    fn new() -> Self {
+        let curator = TestAccount::signer();
+        let group = group_mint(curator.key);
+        let mut world = Self::initialize(group, curator.clone(), curator).unwrap();
+        world.fund(10 * REWARD);
+        world
+    }
+
+    /// Open the pool for `group` as `signer`, whose update authority is `curator`
+    fn initialize(
+        group: TestAccount,
+        curator: TestAccount,
+        signer: TestAccount,
+    ) -> std::result::Result<Self, ProgramError> {
+        let (pool, _) = Pubkey::find_program_address(&[b"pool", group.key.as_ref()], &crate::ID);
+        let mut world = Self {
+            accounts: BTreeMap::new(),
+            curator: curator.key,
+            group: group.key,
+            pool,
+        };
+        let fixture = Fixture::new()
+            .with("pool", TestAccount::uninitialized(Pool::SPACE).at(pool))
+            .with("group", group)
+            .with("curator", signer)
+            .with("token_program", TestAccount::token_2022_program())
+            .with("system_program", TestAccount::system_program());
+        world.run(fixture, instruction::InitializePool { reward: REWARD })?;
+        Ok(world)
+    }
+
+    fn fund(&mut self, amount: u64) {
+        let fixture = Fixture::new()
+            .with("pool", self.get(self.pool))
+            .with("funder", self.get(self.curator))
+            .with("system_program", TestAccount::system_program());
+        self.run(fixture, instruction::Fund { amount }).unwrap();
+    }
+
+    fn insert(&mut self, account: TestAccount) -> Pubkey {
+        let key = account.key;
+        self.accounts.insert(key, account);
+        key
+    }
+
+    fn get(&self, key: Pubkey) -> TestAccount {
+        self.accounts.get(&key).cloned().unwrap_or_else(|| panic!("no account at {key}"))
+    }
+
+    fn lamports(&self, key: Pubkey) -> u64 {
+        self.get(key).lamports
+    }
+
+    /// Run a pool instruction over `fixture`, keeping what it wrote if it succeeds
+    fn run(&mut self, mut fixture: Fixture, ix: impl InstructionData) -> std::result::Result<(), ProgramError> {
+        let result = execute!(&mut fixture, ix);
+        if result.is_ok() {
+            for (_, changed) in fixture.accounts() {
+                if !changed.executable {
+                    self.insert(changed.clone());
+                }
+            }
+        }
+        result
+    }
+
+    /// A member of the collection, added by the curator, with its own metadata
+    fn member(&mut self, member_number: u32) -> Pubkey {
+        let member = member_mint(self.curator, self.group, member_number);
+        self.insert(member)
+    }
+
+    /// A wallet holding `amount` tokens of `mint`, and its token account
+    fn holder(&mut self, mint: Pubkey, amount: u64) -> (Pubkey, Pubkey) {
+        let holder = self.insert(TestAccount::signer());
+        let tokens = TestAccount::token_account(mint, holder, amount).owned_by(anchor_spl::token_2022::ID);
+        (holder, self.insert(tokens))
+    }
+
+    fn claim_address(&self, mint: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"claim", self.pool.as_ref(), mint.as_ref()], &crate::ID).0
+    }
+
+    /// The accounts of a claim for `mint`, with the account its pointer names, if the claim reads one
+    fn claim_fixture(
+        &self,
+        mint: Pubkey,
+        (claimant, tokens): (Pubkey, Pubkey),
+        pointed: Option<(&'static str, Pubkey)>,
+    ) -> Fixture {
+        let address = self.claim_address(mint);
+        let claim = self.accounts.get(&address).cloned();
+        let mut fixture = Fixture::new()
+            .with("pool", self.get(self.pool))
+            .with("claim", claim.unwrap_or_else(|| TestAccount::uninitialized(Claim::SPACE).at(address)))
+            .with("mint", self.get(mint));
+        if let Some((name, key)) = pointed {
+            fixture = fixture.with(name, self.get(key));
+        }
+        fixture
+            .with("holder_tokens", self.get(tokens))
+            .with("claimant", self.get(claimant))
+            .with("token_program", TestAccount::token_2022_program())
+            .with("system_program", TestAccount::system_program())
+    }
+
+    fn claim_by_metadata(
+        &mut self,
+        mint: Pubkey,
+        holder: (Pubkey, Pubkey),
+        metadata: Pubkey,
+    ) -> std::result::Result<(), ProgramError> {
+        let fixture = self.claim_fixture(mint, holder, Some(("metadata", metadata)));
+        self.run(fixture, instruction::VulnerableClaimByMetadata {})
+    }
+
+    fn claim_by_membership(
+        &mut self,
+        mint: Pubkey,
+        holder: (Pubkey, Pubkey),
+        member: Pubkey,
+    ) -> std::result::Result<(), ProgramError> {
+        let fixture = self.claim_fixture(mint, holder, Some(("member", member)));
+        self.run(fixture, instruction::VulnerableClaimByMembership {})
+    }
+
+    fn secure_claim(&mut self, mint: Pubkey, holder: (Pubkey, Pubkey)) -> std::result::Result<(), ProgramError> {
+        self.run(self.claim_fixture(mint, holder, None), instruction::SecureClaim {})
+    }
+}
+
+/// A collection's group mint, holding its own group, which only `curator` adds members to
+fn group_mint(curator: Pubkey) -> TestAccount {
+    let key = Pubkey::new_unique();
+    TestAccount::token_2022_mint(curator, 0, 0)
+        .at(key)
+        .with_group_pointer(key)
+        .with_token_group(curator, MAX_SIZE)
+}
+
+/// Member `member_number` of `group`, with metadata naming `curator`, both stored in the mint itself
+fn member_mint(curator: Pubkey, group: Pubkey, member_number: u32) -> TestAccount {
+    let key = Pubkey::new_unique();
+    TestAccount::token_2022_mint(curator, 0, 1)
+        .at(key)
+        .with_metadata_pointer(key)
+        .with_token_metadata(curator, &format!("Collection #{member_number}"), "COL")
+        .with_group_member_pointer(key)
+        .with_token_group_member(group, member_number)
+}
+
+/// A mint Mallory created, holding whatever extensions `extend` gives it
+fn spoof(extend: impl FnOnce(TestAccount) -> TestAccount) -> TestAccount {
+    extend(TestAccount::token_2022_mint(Pubkey::new_unique(), 0, 1))
+}
+
+#[test]
+fn a_pointer_to_a_members_metadata_passes_as_membership() {
+    let mut world = World::new();
+    let member = world.member(1);
+
+    // Mallory's mint points at the member's metadata, which names the curator
+    let fake = world.insert(spoof(|mint| mint.with_metadata_pointer(member)));
+    let mallory = world.holder(fake, 1);
+    let pool_before = world.lamports(world.pool);
+    world.claim_by_metadata(fake, mallory, member).unwrap();
+    assert_eq!(world.lamports(world.pool), pool_before - REWARD);
+
+    // The secure pool looks for the fake mint's own membership, and finds none
+    let fake = world.insert(spoof(|mint| mint.with_metadata_pointer(member)));
+    let mallory = world.holder(fake, 1);
+    assert_eq!(
+        world.secure_claim(fake, mallory),
+        Err(Error::from(GuardError::MissingMintExtension).into())
+    );
+}
+
+#[test]
+fn metadata_naming_the_curator_proves_nothing() {
+    let mut world = World::new();
+    let curator = world.curator;
+
+    // Token-2022 writes whatever update authority the mint authority names
+    let fake = world.insert(spoof(|mint| {
+        let key = mint.key;
+        mint.with_metadata_pointer(key).with_token_metadata(curator, "Collection #1", "COL")
+    }));
+    let mallory = world.holder(fake, 1);
+    world.claim_by_metadata(fake, mallory, fake).unwrap();
+    assert_eq!(world.get(world.pool).state::<Pool>().claims, 1);
+}
+
+#[test]
+fn a_pointer_to_a_members_membership_is_paid_for_every_mint() {
+    let mut world = World::new();
+    let member = world.member(1);
+
+    // One real member, borrowed by as many mints as Mallory cares to create
+    for _ in 0..3 {
+        let fake = world.insert(spoof(|mint| mint.with_group_member_pointer(member)));
+        let mallory = world.holder(fake, 1);
+        world.claim_by_membership(fake, mallory, member).unwrap();
+    }
+    assert_eq!(world.get(world.pool).state::<Pool>().claims, 3);
+
+    let fake = world.insert(spoof(|mint| mint.with_group_member_pointer(member)));
+    let mallory = world.holder(fake, 1);
+    assert_eq!(
+        world.secure_claim(fake, mallory),
+        Err(Error::from(GuardError::ForeignExtensionPointer).into())
+    );
+}
+
+#[test]
+fn the_secure_claim_pays_each_member_once() {
+    let mut world = World::new();
+    let member = world.member(1);
+    let alice = world.holder(member, 1);
+
+    let pool_before = world.lamports(world.pool);
+    world.secure_claim(member, alice).unwrap();
+    assert_eq!(world.lamports(world.pool), pool_before - REWARD);
+    assert_eq!(world.get(world.claim_address(member)).state::<Claim>().claimant, alice.0);
+
+    assert_eq!(world.secure_claim(member, alice), Err(account_already_in_use()));
+}
+
+#[test]
+fn the_secure_claim_refuses_members_of_other_collections() {
+    let mut world = World::new();
+    let other_curator = Pubkey::new_unique();
+    let other_group = world.insert(group_mint(other_curator));
+    let outsider = world.insert(member_mint(other_curator, other_group, 1));
+    let holder = world.holder(outsider, 1);
+
+    assert_eq!(
+        world.secure_claim(outsider, holder),
+        Err(Error::from(GuardError::NotGroupMember).into())
+    );
+}
+
+#[test]
+fn only_holders_claim() {
+    let mut world = World::new();
+    let member = world.member(1);
+    let bystander = world.holder(member, 0);
+
+    assert_eq!(
+        world.secure_claim(member, bystander),
+        Err(Error::from(PoolError::NotHolder).into())
+    );
+}
+
+#[test]
+fn only_the_groups_update_authority_opens_its_pool() {
+    let curator = TestAccount::signer();
+    let result = World::initialize(group_mint(curator.key), curator, TestAccount::signer());
+
+    assert_eq!(result.err(), Some(Error::from(GuardError::GroupAuthorityMismatch).into()));
+}
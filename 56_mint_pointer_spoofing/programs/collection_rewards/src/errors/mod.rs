@@ -0,0 +1,19 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Amount must be greater than zero")]
+    ZeroAmount,
+    #[msg("Account is not the one the mint's pointer names")]
+    PointerMismatch,
+    #[msg("Mint is not in the collection")]
+    NotInCollection,
+    #[msg("Claimant holds none of the mint's tokens")]
+    NotHolder,
+    #[msg("Pool cannot pay the reward and stay rent-exempt")]
+    PoolExhausted,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+}
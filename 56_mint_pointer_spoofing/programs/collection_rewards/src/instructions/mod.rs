@@ -0,0 +1,51 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(
+        init,
+        payer = curator,
+        space = Pool::SPACE,
+        seeds = [b"pool", group.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// The collection's group mint, holding its own `TokenGroup`
+    #[account(mint::token_program = token_program)]
+    pub group: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub curator: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Fund<'info> {
+    #[account(mut, seeds = [b"pool", pool.group.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
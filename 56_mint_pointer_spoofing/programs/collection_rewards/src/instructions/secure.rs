@@ -0,0 +1,35 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureClaim<'info> {
+    #[account(mut, seeds = [b"pool", pool.group.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        init,
+        payer = claimant,
+        space = Claim::SPACE,
+        seeds = [b"claim", pool.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub claim: Account<'info, Claim>,
+
+    /// SECURITY: Read for the membership stored in the mint itself
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        token::mint = mint,
+        token::authority = claimant,
+        constraint = holder_tokens.amount > 0 @ ErrorCode::NotHolder
+    )]
+    pub holder_tokens: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
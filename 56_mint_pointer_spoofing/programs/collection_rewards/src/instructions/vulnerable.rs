@@ -0,0 +1,71 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableClaimByMetadata<'info> {
+    #[account(mut, seeds = [b"pool", pool.group.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        init,
+        payer = claimant,
+        space = Claim::SPACE,
+        seeds = [b"claim", pool.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub claim: Account<'info, Claim>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: VULNERABILITY: Any Token-2022 mint's metadata, as long as the pointer names it
+    pub metadata: UncheckedAccount<'info>,
+
+    #[account(
+        token::mint = mint,
+        token::authority = claimant,
+        constraint = holder_tokens.amount > 0 @ ErrorCode::NotHolder
+    )]
+    pub holder_tokens: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableClaimByMembership<'info> {
+    #[account(mut, seeds = [b"pool", pool.group.as_ref()], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        init,
+        payer = claimant,
+        space = Claim::SPACE,
+        seeds = [b"claim", pool.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub claim: Account<'info, Claim>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: VULNERABILITY: Any Token-2022 mint's membership, as long as the pointer names it
+    pub member: UncheckedAccount<'info>,
+
+    #[account(
+        token::mint = mint,
+        token::authority = claimant,
+        constraint = holder_tokens.amount > 0 @ ErrorCode::NotHolder
+    )]
+    pub holder_tokens: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,29 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Claim, Pool};
+
+#[test]
+fn pool_layout_is_stable() {
+    let pool = Pool {
+        curator: Pubkey::new_from_array([1; 32]),
+        group: Pubkey::new_from_array([2; 32]),
+        reward: 0x3132333435363738,
+        claims: 0x4142434445464748,
+        bump: 81,
+    };
+    assert_golden!("Pool", golden::anchor(&pool));
+}
+
+#[test]
+fn claim_layout_is_stable() {
+    let claim = Claim {
+        pool: Pubkey::new_from_array([6; 32]),
+        mint: Pubkey::new_from_array([7; 32]),
+        claimant: Pubkey::new_from_array([8; 32]),
+        bump: 145,
+    };
+    assert_golden!("Claim", golden::anchor(&claim));
+}
@@ -0,0 +1,200 @@
+use anchor_lang::system_program::{self, Transfer};
+use secref_guards::mint_pointer::assert_token_group;
+use secref_guards::rent::minimum_balance;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Rewards for holders of a curated Token-2022 collection.
+///
+/// The curator funds a pool for the collection's group mint, and each mint
+/// in the collection earns its holder one reward. The pool has to tell the
+/// collection's mints from everyone else's, and Token-2022 offers two
+/// places to look: the metadata a mint's `MetadataPointer` names, and the
+/// membership its `GroupMemberPointer` names. Both pointers are set by
+/// whoever created the mint, to any account they like.
+#[feature_gated]
+#[program]
+pub mod collection_rewards {
+    use super::*;
+    #[vulnerable]
+    use secref_guards::mint_pointer::{group_member, group_member_pointer, metadata_pointer, token_metadata};
+    #[secure]
+    use secref_guards::mint_pointer::assert_group_member;
+
+    /// Create the pool for the collection `group`, paying `reward` lamports per mint in it
+    ///
+    /// Only the group's update authority adds members to the group, so
+    /// only it may open the group's pool.
+    pub fn initialize_pool(ctx: Context<InitializePool>, reward: u64) -> Result<()> {
+        require!(reward > 0, ErrorCode::ZeroAmount);
+        assert_token_group(&ctx.accounts.group.to_account_info(), &ctx.accounts.curator.key())?;
+
+        let pool = &mut ctx.accounts.pool;
+        pool.curator = ctx.accounts.curator.key();
+        pool.group = ctx.accounts.group.key();
+        pool.reward = reward;
+        pool.claims = 0;
+        pool.bump = ctx.bumps.pool;
+
+        msg!("Pool for collection {} pays {} lamports per mint", pool.group, reward);
+        Ok(())
+    }
+
+    /// Add `amount` lamports to the pool
+    pub fn fund(ctx: Context<Fund>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroAmount);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.pool.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Pool funded with {} lamports", amount);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Pay the reward for `mint` if its metadata names the curator
+    ///
+    /// Security Issue: The metadata is wherever the mint's
+    /// `MetadataPointer` says, and the mint's creator sets the pointer.
+    /// Pointed at a collection mint, it lends that mint's metadata to any
+    /// other. A mint's own metadata proves no more: Token-2022 records the
+    /// update authority the mint authority names, without that authority's
+    /// signature, so any mint can claim the curator wrote it.
+    #[vulnerable]
+    pub fn vulnerable_claim_by_metadata(ctx: Context<VulnerableClaimByMetadata>) -> Result<()> {
+        // VULNERABILITY: The pointer names whichever account the mint's creator chose
+        let pointer = metadata_pointer(&ctx.accounts.mint.to_account_info())?;
+        require!(pointer.address == Some(ctx.accounts.metadata.key()), ErrorCode::PointerMismatch);
+
+        let metadata = token_metadata(&ctx.accounts.metadata)?;
+        // VULNERABILITY: A self-declared update authority, for whichever mint the metadata describes
+        require!(metadata.update_authority == Some(ctx.accounts.pool.curator), ErrorCode::NotInCollection);
+
+        msg!("Paying for {} ({})", metadata.name, metadata.symbol);
+        pay_reward(
+            &mut ctx.accounts.pool,
+            &mut ctx.accounts.claim,
+            ctx.accounts.mint.key(),
+            &ctx.accounts.claimant,
+            ctx.bumps.claim,
+        )
+    }
+
+    /// VULNERABLE: Pay the reward for `mint` if the membership its pointer names is in the collection
+    ///
+    /// Security Issue: Token-2022 writes a member only with the group's
+    /// update authority's signature, so the membership is genuine - but
+    /// it may be another mint's. The mint's `GroupMemberPointer` can name
+    /// any member of the collection, and the pool never compares the
+    /// member's mint with the mint it pays for.
+    #[vulnerable]
+    pub fn vulnerable_claim_by_membership(ctx: Context<VulnerableClaimByMembership>) -> Result<()> {
+        // VULNERABILITY: The pointer names whichever account the mint's creator chose
+        let pointer = group_member_pointer(&ctx.accounts.mint.to_account_info())?;
+        require!(pointer.address == Some(ctx.accounts.member.key()), ErrorCode::PointerMismatch);
+
+        let member = group_member(&ctx.accounts.member)?;
+        // VULNERABILITY: The member's group is checked, and its mint is not
+        require_keys_eq!(member.group, ctx.accounts.pool.group, ErrorCode::NotInCollection);
+
+        msg!("Paying for member #{}", member.member_number);
+        pay_reward(
+            &mut ctx.accounts.pool,
+            &mut ctx.accounts.claim,
+            ctx.accounts.mint.key(),
+            &ctx.accounts.claimant,
+            ctx.bumps.claim,
+        )
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version: membership is read from the
+    // mint itself, where only the group's update authority can have put it,
+    // and metadata is never taken as proof of anything.
+
+    /// SECURE: Pay the reward for `mint` if the mint itself is a member of the collection
+    ///
+    /// Security Fix: The mint's `GroupMemberPointer` must name the mint,
+    /// and the `TokenGroupMember` there must be the mint's own, in the
+    /// pool's group. Token-2022 writes a member only into its own mint and
+    /// only with the group's update authority's signature, so no pointer,
+    /// copied bytes or metadata lets another mint pass as a member.
+    #[secure]
+    pub fn secure_claim(ctx: Context<SecureClaim>) -> Result<()> {
+        // SECURITY: The mint's own membership, which the curator signed for
+        let member = assert_group_member(&ctx.accounts.mint.to_account_info(), &ctx.accounts.pool.group)?;
+
+        msg!("Paying for member #{}", member.member_number);
+        pay_reward(
+            &mut ctx.accounts.pool,
+            &mut ctx.accounts.claim,
+            ctx.accounts.mint.key(),
+            &ctx.accounts.claimant,
+            ctx.bumps.claim,
+        )
+    }
+}
+
+/// Pay the pool's reward to `claimant`, and record that `mint` has been paid for
+fn pay_reward<'info>(
+    pool: &mut Account<'info, Pool>,
+    claim: &mut Account<'info, Claim>,
+    mint: Pubkey,
+    claimant: &Signer<'info>,
+    bump: u8,
+) -> Result<()> {
+    let reward = pool.reward;
+    let pool_info = pool.to_account_info();
+    let rent_exempt = minimum_balance(pool_info.data_len())?;
+    let remaining = pool_info
+        .lamports()
+        .checked_sub(reward)
+        .filter(|remaining| *remaining >= rent_exempt)
+        .ok_or(ErrorCode::PoolExhausted)?;
+    **pool_info.try_borrow_mut_lamports()? = remaining;
+    let claimant_info = claimant.to_account_info();
+    **claimant_info.try_borrow_mut_lamports()? = claimant_info
+        .lamports()
+        .checked_add(reward)
+        .ok_or(ErrorCode::MathOverflow)?;
+    pool.claims = pool.claims.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+
+    claim.pool = pool.key();
+    claim.mint = mint;
+    claim.claimant = claimant.key();
+    claim.bump = bump;
+
+    msg!("Paid {} lamports to {}", reward, claim.claimant);
+    Ok(())
+}
@@ -0,0 +1,37 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Pool {
+    /// Curator of the collection, its group's update authority (32 bytes)
+    pub curator: Pubkey,
+    /// The collection's group mint (32 bytes)
+    pub group: Pubkey,
+    /// Lamports paid once per mint in the collection (8 bytes)
+    pub reward: u64,
+    /// Rewards paid so far (8 bytes)
+    pub claims: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Pool {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8 + 1;
+}
+
+#[account]
+pub struct Claim {
+    /// Pool that paid the reward (32 bytes)
+    pub pool: Pubkey,
+    /// Mint the reward was paid for (32 bytes)
+    pub mint: Pubkey,
+    /// Holder who was paid (32 bytes)
+    pub claimant: Pubkey,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Claim {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 1;
+}
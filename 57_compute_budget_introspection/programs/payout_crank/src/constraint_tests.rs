@@ -0,0 +1,262 @@
+//! A funded payroll, and cranks sent with more or fewer compute units: the
+//! vulnerable crank does what the meter allows after taking its tip, the
+//! secure one reads the transaction's compute unit limit first.
+
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+use secref_guards::compute_budget::set_compute_unit_limit;
+use secref_guards::GuardError;
+use secref_testkit::{execute, runtime, Fixture, TestAccount};
+
+use crate::{instruction, ErrorCode as PayrollError, Payroll, BATCH, CRANK_UNITS, MAX_PAYEES, PAYMENT_UNITS};
+
+/// Lamports paid to each crank
+const TIP: u64 = 50_000;
+/// Lamports each payee is owed
+const SALARY: u64 = 1_000_000;
+
+/// A funded payroll of `payees`, and the cranker, by address
+struct World {
+    accounts: BTreeMap<Pubkey, TestAccount>,
+    authority: Pubkey,
+    payroll: Pubkey,
+    payees: Vec<Pubkey>,
+    cranker: Pubkey,
+}
+
+impl World {
+    fn new(payees: usize) -> Self {
+        let authority = TestAccount::signer();
+        let (payroll, _) = Pubkey::find_program_address(&[b"payroll", authority.key.as_ref()], &crate::ID);
+        let mut world = Self {
+            accounts: BTreeMap::new(),
+            authority: authority.key,
+            payroll,
+            payees: Vec::new(),
+            cranker: Pubkey::default(),
+        };
+        world.insert(authority);
+        world.cranker = world.insert(TestAccount::signer());
+
+        let fixture = Fixture::new()
+            .with("payroll", TestAccount::uninitialized(Payroll::SPACE).at(payroll))
+            .with("authority", world.get(world.authority))
+            .with("system_program", TestAccount::system_program());
+        world.run(fixture, instruction::InitializePayroll { tip: TIP }).unwrap();
+        for _ in 0..payees {
+            let payee = world.insert(TestAccount::system(0));
+            world.add_payee(world.authority, payee).unwrap();
+            world.payees.push(payee);
+        }
+
+        let fixture = Fixture::new()
+            .with("payroll", world.get(payroll))
+            .with("funder", world.get(world.authority))
+            .with("system_program", TestAccount::system_program());
+        world.run(fixture, instruction::Fund { amount: 10 * SALARY }).unwrap();
+        world
+    }
+
+    fn insert(&mut self, account: TestAccount) -> Pubkey {
+        let key = account.key;
+        self.accounts.insert(key, account);
+        key
+    }
+
+    fn get(&self, key: Pubkey) -> TestAccount {
+        self.accounts.get(&key).cloned().unwrap_or_else(|| panic!("no account at {key}"))
+    }
+
+    fn lamports(&self, key: Pubkey) -> u64 {
+        self.get(key).lamports
+    }
+
+    fn payroll(&self) -> Payroll {
+        self.get(self.payroll).state::<Payroll>()
+    }
+
+    /// Run a payroll instruction over `fixture`, keeping what it wrote if it succeeds
+    fn run(&mut self, mut fixture: Fixture, ix: impl InstructionData) -> std::result::Result<(), ProgramError> {
+        let result = execute!(&mut fixture, ix);
+        if result.is_ok() {
+            for (_, changed) in fixture.accounts() {
+                if !changed.executable {
+                    self.insert(changed.clone());
+                }
+            }
+        }
+        result
+    }
+
+    fn add_payee(&mut self, signer: Pubkey, recipient: Pubkey) -> std::result::Result<(), ProgramError> {
+        let fixture = Fixture::new()
+            .with("payroll", self.get(self.payroll))
+            .with("authority", self.get(signer));
+        self.run(fixture, instruction::AddPayee { recipient, amount: SALARY })
+    }
+
+    /// Every payee still due, in order, as the crank's remaining accounts
+    fn with_payees_due(&self, mut fixture: Fixture) -> Fixture {
+        let cursor = usize::from(self.payroll().cursor);
+        for payee in &self.payees[cursor..] {
+            fixture = fixture.with("payee", self.get(*payee));
+        }
+        fixture
+    }
+
+    /// The vulnerable crank, run with `compute_units` left on the meter
+    fn vulnerable_crank(&mut self, compute_units: u64) -> std::result::Result<(), ProgramError> {
+        // Each check of the meter stands in for the payment made since the last one
+        runtime::set_compute_units(compute_units, PAYMENT_UNITS);
+        let fixture = Fixture::new()
+            .with("payroll", self.get(self.payroll))
+            .with("cranker", self.get(self.cranker));
+        self.run(self.with_payees_due(fixture), instruction::VulnerableCrank {})
+    }
+
+    /// The secure crank, as instruction `current` of `transaction`
+    fn secure_crank(&mut self, transaction: &[Instruction], current: u16) -> std::result::Result<(), ProgramError> {
+        let fixture = Fixture::new()
+            .with("payroll", self.get(self.payroll))
+            .with("cranker", self.get(self.cranker))
+            .with("instructions", TestAccount::instructions_sysvar_of(transaction, current));
+        self.run(self.with_payees_due(fixture), instruction::SecureCrank {})
+    }
+}
+
+/// The crank instruction itself; only its program and position matter to the sysvar
+fn crank() -> Instruction {
+    Instruction::new_with_bytes(crate::ID, &instruction::SecureCrank {}.data(), vec![])
+}
+
+#[test]
+fn a_low_compute_limit_takes_the_tip_and_pays_no_one() {
+    let mut world = World::new(6);
+    let (payroll_before, cranker_before) = (world.lamports(world.payroll), world.lamports(world.cranker));
+
+    // Too few units for one payment, three times over
+    for _ in 0..3 {
+        world.vulnerable_crank(PAYMENT_UNITS - 1).unwrap();
+    }
+    assert_eq!(world.payroll().cursor, 0);
+    assert_eq!(world.payroll().tips_paid, 3 * TIP);
+    assert_eq!(world.lamports(world.cranker), cranker_before + 3 * TIP);
+    assert_eq!(world.lamports(world.payroll), payroll_before - 3 * TIP);
+    assert!(world.payees.iter().all(|payee| world.lamports(*payee) == 0));
+}
+
+#[test]
+fn the_vulnerable_crank_pays_as_many_as_the_meter_allows() {
+    let mut world = World::new(6);
+
+    world.vulnerable_crank(2 * PAYMENT_UNITS).unwrap();
+    assert_eq!(world.payroll().cursor, 2);
+    assert_eq!(world.lamports(world.payees[1]), SALARY);
+    assert_eq!(world.lamports(world.payees[2]), 0);
+
+    // With units to spare, it pays everyone still due
+    world.vulnerable_crank(u64::from(CRANK_UNITS) * 10).unwrap();
+    assert_eq!(world.payroll().cursor, 6);
+    assert_eq!(
+        world.vulnerable_crank(u64::from(CRANK_UNITS)),
+        Err(Error::from(PayrollError::NothingDue).into())
+    );
+}
+
+#[test]
+fn the_secure_crank_requires_a_compute_unit_limit() {
+    let mut world = World::new(6);
+
+    assert_eq!(
+        world.secure_crank(&[crank()], 0),
+        Err(Error::from(GuardError::ComputeBudgetMissing).into())
+    );
+    assert_eq!(
+        world.secure_crank(&[set_compute_unit_limit(CRANK_UNITS - 1), crank()], 1),
+        Err(Error::from(GuardError::ComputeBudgetTooLow).into())
+    );
+    assert_eq!(world.payroll().tips_paid, 0);
+}
+
+#[test]
+fn the_secure_crank_rejects_duplicate_limits() {
+    let mut world = World::new(6);
+
+    // A generous limit for the guard to see, and a stingy one beside it
+    let transaction = [set_compute_unit_limit(CRANK_UNITS), crank(), set_compute_unit_limit(1_000)];
+    assert_eq!(
+        world.secure_crank(&transaction, 1),
+        Err(Error::from(GuardError::DuplicateComputeBudget).into())
+    );
+}
+
+#[test]
+fn the_secure_crank_refuses_a_limit_spent_before_it() {
+    let mut world = World::new(6);
+
+    let burner = Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![]);
+    assert_eq!(
+        world.secure_crank(&[set_compute_unit_limit(CRANK_UNITS), burner, crank()], 2),
+        Err(Error::from(GuardError::ComputeBudgetShared).into())
+    );
+}
+
+#[test]
+fn the_secure_crank_pays_full_batches_then_its_tip() {
+    let mut world = World::new(6);
+    let transaction = [set_compute_unit_limit(CRANK_UNITS), crank()];
+    let cranker_before = world.lamports(world.cranker);
+
+    world.secure_crank(&transaction, 1).unwrap();
+    assert_eq!(usize::from(world.payroll().cursor), BATCH);
+    assert_eq!(world.lamports(world.cranker), cranker_before + TIP);
+    assert!(world.payees[..BATCH].iter().all(|payee| world.lamports(*payee) == SALARY));
+
+    // The last batch is whatever is still due
+    world.secure_crank(&transaction, 1).unwrap();
+    assert_eq!(world.payroll().cursor, 6);
+    assert_eq!(world.payroll().tips_paid, 2 * TIP);
+    assert_eq!(
+        world.secure_crank(&transaction, 1),
+        Err(Error::from(PayrollError::NothingDue).into())
+    );
+}
+
+#[test]
+fn the_secure_crank_pays_only_the_payees_due() {
+    let mut world = World::new(6);
+    let transaction = [set_compute_unit_limit(CRANK_UNITS), crank()];
+
+    // Mallory's wallet in place of the second payee
+    world.payees[1] = world.insert(TestAccount::system(0));
+    assert_eq!(
+        world.secure_crank(&transaction, 1),
+        Err(Error::from(PayrollError::PayeeMismatch).into())
+    );
+
+    // Or too few payees for the batch
+    world.payees.truncate(1);
+    assert_eq!(
+        world.secure_crank(&transaction, 1),
+        Err(Error::from(PayrollError::MissingPayee).into())
+    );
+}
+
+#[test]
+fn only_the_authority_lists_payees() {
+    let mut world = World::new(1);
+    let mallory = world.insert(TestAccount::signer());
+
+    assert!(world.add_payee(mallory, mallory).is_err());
+    for _ in 1..MAX_PAYEES {
+        let payee = Pubkey::new_unique();
+        world.add_payee(world.authority, payee).unwrap();
+    }
+    assert_eq!(
+        world.add_payee(world.authority, mallory),
+        Err(Error::from(PayrollError::PayrollFull).into())
+    );
+}
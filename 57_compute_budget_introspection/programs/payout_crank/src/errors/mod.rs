@@ -0,0 +1,21 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Amount must be greater than zero")]
+    ZeroAmount,
+    #[msg("Payroll lists the most payees it can hold")]
+    PayrollFull,
+    #[msg("Every payee has been paid")]
+    NothingDue,
+    #[msg("A payee due in this batch was not passed")]
+    MissingPayee,
+    #[msg("Account passed is not the payee due")]
+    PayeeMismatch,
+    #[msg("Payroll cannot pay this without falling below rent exemption")]
+    PayrollExhausted,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+}
@@ -0,0 +1,56 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct InitializePayroll<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Payroll::SPACE,
+        seeds = [b"payroll", authority.key().as_ref()],
+        bump
+    )]
+    pub payroll: Account<'info, Payroll>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddPayee<'info> {
+    #[account(
+        mut,
+        seeds = [b"payroll", authority.key().as_ref()],
+        bump = payroll.bump,
+        has_one = authority
+    )]
+    pub payroll: Account<'info, Payroll>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Fund<'info> {
+    #[account(mut, seeds = [b"payroll", payroll.authority.as_ref()], bump = payroll.bump)]
+    pub payroll: Account<'info, Payroll>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,18 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use anchor_lang::solana_program::sysvar;
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureCrank<'info> {
+    #[account(mut, seeds = [b"payroll", payroll.authority.as_ref()], bump = payroll.bump)]
+    pub payroll: Account<'info, Payroll>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    /// CHECK: The instructions sysvar, by address
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
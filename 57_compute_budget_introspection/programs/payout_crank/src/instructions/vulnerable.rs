@@ -0,0 +1,12 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableCrank<'info> {
+    #[account(mut, seeds = [b"payroll", payroll.authority.as_ref()], bump = payroll.bump)]
+    pub payroll: Account<'info, Payroll>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+}
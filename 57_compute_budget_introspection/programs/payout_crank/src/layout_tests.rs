@@ -0,0 +1,25 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Payee, Payroll};
+
+#[test]
+fn payroll_layout_is_stable() {
+    let payroll = Payroll {
+        authority: Pubkey::new_from_array([1; 32]),
+        tip: 0x2122232425262728,
+        cursor: 49,
+        payees: vec![Payee {
+            recipient: Pubkey::new_from_array([4; 32]),
+            amount: 0x5152535455565758,
+        }, Payee {
+            recipient: Pubkey::new_from_array([6; 32]),
+            amount: 0x7172737475767778,
+        }],
+        tips_paid: 0x8182838485868788,
+        bump: 145,
+    };
+    assert_golden!("Payroll", golden::anchor(&payroll));
+}
@@ -0,0 +1,197 @@
+use anchor_lang::system_program::{self, Transfer};
+use secref_guards::rent::minimum_balance;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Most payees a payroll lists
+pub const MAX_PAYEES: usize = 8;
+/// Payments one crank makes, when that many are due
+pub const BATCH: usize = 4;
+/// Compute units one payment needs, with room to spare
+pub const PAYMENT_UNITS: u64 = 5_000;
+/// Compute units a crank needs for a full batch, its accounts and its tip
+pub const CRANK_UNITS: u32 = 60_000;
+
+/// A payroll paid out by permissionless cranks.
+///
+/// The authority lists payees and funds the payroll. Anyone may crank it:
+/// a crank pays the payees due, in order, and earns a tip from the payroll
+/// for the work. The crank is a multi-step handler - one step per payment -
+/// and saves its place in `cursor`, so a long payroll is paid over several
+/// transactions. How many steps one transaction manages depends on the
+/// compute units it has, and the caller chooses those.
+#[feature_gated]
+#[program]
+pub mod payout_crank {
+    use super::*;
+    #[vulnerable]
+    use anchor_lang::solana_program::compute_units::sol_remaining_compute_units;
+    #[secure]
+    use secref_guards::compute_budget::assert_compute_unit_limit;
+
+    /// Open the authority's payroll, tipping each crank `tip` lamports
+    pub fn initialize_payroll(ctx: Context<InitializePayroll>, tip: u64) -> Result<()> {
+        require!(tip > 0, ErrorCode::ZeroAmount);
+
+        let payroll = &mut ctx.accounts.payroll;
+        payroll.authority = ctx.accounts.authority.key();
+        payroll.tip = tip;
+        payroll.cursor = 0;
+        payroll.payees = Vec::new();
+        payroll.tips_paid = 0;
+        payroll.bump = ctx.bumps.payroll;
+
+        msg!("Payroll opened, tipping {} lamports per crank", tip);
+        Ok(())
+    }
+
+    /// List `recipient` for `amount` lamports, after everyone already listed
+    pub fn add_payee(ctx: Context<AddPayee>, recipient: Pubkey, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroAmount);
+        let payroll = &mut ctx.accounts.payroll;
+        require!(payroll.payees.len() < MAX_PAYEES, ErrorCode::PayrollFull);
+
+        payroll.payees.push(Payee { recipient, amount });
+        msg!("Payee {} listed for {} lamports", recipient, amount);
+        Ok(())
+    }
+
+    /// Add `amount` lamports to the payroll
+    pub fn fund(ctx: Context<Fund>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroAmount);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.payroll.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Payroll funded with {} lamports", amount);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Tip the cranker, then pay the payees due while compute units last
+    ///
+    /// Security Issue: The crank pays as many payees as the meter allows,
+    /// and the caller sets the meter with the transaction's compute unit
+    /// limit. With a limit too low for one payment, the crank pays its tip
+    /// and stops: the cranker is paid for work never done, and can repeat
+    /// it until the tips have eaten the payroll, while the payees wait.
+    /// Every such transaction succeeds, so nothing marks it as an attack.
+    /// Recipients, in payroll order from the cursor, are the remaining accounts.
+    #[vulnerable]
+    pub fn vulnerable_crank(ctx: Context<VulnerableCrank>) -> Result<()> {
+        let payroll = &mut ctx.accounts.payroll;
+        require!(usize::from(payroll.cursor) < payroll.payees.len(), ErrorCode::NothingDue);
+
+        // VULNERABILITY: The tip comes first, whatever the crank goes on to do
+        pay_tip(payroll, &ctx.accounts.cranker)?;
+
+        let mut paid = 0;
+        // VULNERABILITY: The caller's compute unit limit decides how many steps run
+        while usize::from(payroll.cursor) < payroll.payees.len() && sol_remaining_compute_units() >= PAYMENT_UNITS {
+            pay_next(payroll, ctx.remaining_accounts.get(paid))?;
+            paid += 1;
+        }
+
+        msg!("Crank paid {} payees; {} still due", paid, payroll.payees.len() - usize::from(payroll.cursor));
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version: the crank reads the compute
+    // unit limit from the transaction, through the instructions sysvar, and
+    // refuses to start without enough for a whole batch.
+
+    /// SECURE: Pay a full batch of the payees due, then tip the cranker
+    ///
+    /// Security Fix: The transaction must set a compute unit limit of at
+    /// least `CRANK_UNITS`, and nothing but ComputeBudget instructions may
+    /// run before the crank to spend it. The batch is fixed, never sized by
+    /// the meter: it completes, or the whole transaction fails and nothing
+    /// is paid. The tip comes last, for work already done.
+    /// Recipients, in payroll order from the cursor, are the remaining accounts.
+    #[secure]
+    pub fn secure_crank(ctx: Context<SecureCrank>) -> Result<()> {
+        // SECURITY: Units for a full batch, requested for this instruction alone
+        assert_compute_unit_limit(&ctx.accounts.instructions, ctx.program_id, CRANK_UNITS)?;
+
+        let payroll = &mut ctx.accounts.payroll;
+        let due = payroll.payees.len() - usize::from(payroll.cursor);
+        require!(due > 0, ErrorCode::NothingDue);
+
+        // SECURITY: A batch of fixed size, which completes or fails as one
+        let batch = due.min(BATCH);
+        for index in 0..batch {
+            pay_next(payroll, ctx.remaining_accounts.get(index))?;
+        }
+        // SECURITY: The tip pays for work done
+        pay_tip(payroll, &ctx.accounts.cranker)?;
+
+        msg!("Crank paid {} payees; {} still due", batch, due - batch);
+        Ok(())
+    }
+}
+
+/// Pay the payee at the cursor, who must be `recipient`, and move the cursor past them
+fn pay_next(payroll: &mut Account<Payroll>, recipient: Option<&AccountInfo>) -> Result<()> {
+    let payee = payroll.payees[usize::from(payroll.cursor)];
+    let recipient = recipient.ok_or(ErrorCode::MissingPayee)?;
+    require_keys_eq!(recipient.key(), payee.recipient, ErrorCode::PayeeMismatch);
+
+    pay_out(payroll, recipient, payee.amount)?;
+    payroll.cursor += 1;
+    msg!("Paid {} lamports to {}", payee.amount, payee.recipient);
+    Ok(())
+}
+
+/// Pay the payroll's tip to `cranker`
+fn pay_tip(payroll: &mut Account<Payroll>, cranker: &Signer) -> Result<()> {
+    let tip = payroll.tip;
+    pay_out(payroll, &cranker.to_account_info(), tip)?;
+    payroll.tips_paid = payroll.tips_paid.checked_add(tip).ok_or(ErrorCode::MathOverflow)?;
+    Ok(())
+}
+
+/// Move `amount` lamports from the payroll to `to`, keeping the payroll rent-exempt
+fn pay_out(payroll: &Account<Payroll>, to: &AccountInfo, amount: u64) -> Result<()> {
+    let payroll = payroll.to_account_info();
+    let rent_exempt = minimum_balance(payroll.data_len())?;
+    let remaining = payroll
+        .lamports()
+        .checked_sub(amount)
+        .filter(|remaining| *remaining >= rent_exempt)
+        .ok_or(ErrorCode::PayrollExhausted)?;
+    **payroll.try_borrow_mut_lamports()? = remaining;
+    **to.try_borrow_mut_lamports()? = to.lamports().checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+    Ok(())
+}
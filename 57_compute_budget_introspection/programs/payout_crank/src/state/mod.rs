@@ -0,0 +1,35 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Payroll {
+    /// Lists payees (32 bytes)
+    pub authority: Pubkey,
+    /// Lamports paid to each crank (8 bytes)
+    pub tip: u64,
+    /// Index of the next payee due (1 byte)
+    pub cursor: u8,
+    /// Payees, paid in order (4 + up to 8 * 40 bytes)
+    pub payees: Vec<Payee>,
+    /// Lamports paid in tips so far (8 bytes)
+    pub tips_paid: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Payroll {
+    pub const SPACE: usize = 8 + 32 + 8 + 1 + (4 + MAX_PAYEES * Payee::SPACE) + 8 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Payee {
+    /// Wallet paid (32 bytes)
+    pub recipient: Pubkey,
+    /// Lamports paid (8 bytes)
+    pub amount: u64,
+}
+
+impl Payee {
+    pub const SPACE: usize = 32 + 8;
+}
@@ -0,0 +1,248 @@
+//! A funded payer PDA, and wallets opening notes on it: through the
+//! vulnerable path one wallet empties it, through the secure paths a wallet
+//! pays for its own notes or spends a small quota. Then every constraint on
+//! the secure sponsored-note context, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, execute,
+    Fixture, TestAccount,
+};
+
+use crate::{
+    instruction, Config, ErrorCode as NotesError, Note, Quota, SecureOpenSponsoredNote, MAX_SPONSORED_NOTES, NOTE_LEN,
+};
+
+/// Lamports the admin puts into the payer PDA
+const BUDGET: u64 = 1_000_000_000;
+/// The largest account the system program creates in one instruction
+const MAX_SPACE: u32 = 10_240;
+
+/// An initialized config and a funded payer PDA
+struct Notes {
+    config: TestAccount,
+    payer: TestAccount,
+}
+
+impl Notes {
+    fn new() -> Self {
+        let (config, _) = Pubkey::find_program_address(&[b"config"], &crate::ID);
+        let (payer, _) = Pubkey::find_program_address(&[b"payer"], &crate::ID);
+        let admin = TestAccount::signer();
+
+        let mut fixture = Fixture::new()
+            .with("config", TestAccount::uninitialized(8 + 32 + 8 + 1 + 1).at(config))
+            .with("payer", TestAccount::system(0).at(payer))
+            .with("admin", admin.clone())
+            .with("system_program", TestAccount::system_program());
+        execute!(&mut fixture, instruction::Initialize {}).unwrap();
+        let mut notes = Self {
+            config: fixture.account("config").clone(),
+            payer: fixture.account("payer").clone(),
+        };
+
+        let mut fixture = Fixture::new()
+            .with("config", notes.config.clone())
+            .with("payer", notes.payer.clone())
+            .with("funder", admin)
+            .with("system_program", TestAccount::system_program());
+        execute!(&mut fixture, instruction::FundPayer { amount: BUDGET }).unwrap();
+        notes.payer = fixture.account("payer").clone();
+        notes
+    }
+
+    fn note_address(owner: &TestAccount, id: u64) -> Pubkey {
+        Pubkey::find_program_address(&[b"note", owner.key.as_ref(), &id.to_le_bytes()], &crate::ID).0
+    }
+
+    fn notes_sponsored(&self) -> u64 {
+        self.config.state::<Config>().notes_sponsored
+    }
+
+    /// Keep the config and payer an instruction that succeeded left behind
+    fn keep(&mut self, fixture: &Fixture) {
+        self.config = fixture.account("config").clone();
+        self.payer = fixture.account("payer").clone();
+    }
+
+    fn vulnerable_open(&mut self, owner: &TestAccount, id: u64, space: u32) -> std::result::Result<(), ProgramError> {
+        let note = TestAccount::uninitialized(space as usize).at(Self::note_address(owner, id));
+        let mut fixture = Fixture::new()
+            .with("config", self.config.clone())
+            .with("payer", self.payer.clone())
+            .with("note", note)
+            .with("owner", owner.clone())
+            .with("system_program", TestAccount::system_program());
+        let result = execute!(&mut fixture, instruction::VulnerableOpenNote { id, space });
+        if result.is_ok() {
+            self.keep(&fixture);
+        }
+        result
+    }
+
+    fn open_quota(owner: &TestAccount) -> TestAccount {
+        let (quota, _) = Pubkey::find_program_address(&[b"quota", owner.key.as_ref()], &crate::ID);
+        let mut fixture = Fixture::new()
+            .with("quota", TestAccount::uninitialized(8 + 32 + 1 + 1).at(quota))
+            .with("owner", owner.clone())
+            .with("system_program", TestAccount::system_program());
+        execute!(&mut fixture, instruction::SecureOpenQuota {}).unwrap();
+        fixture.account("quota").clone()
+    }
+
+    fn sponsored_fixture(&self, quota: &TestAccount, owner: &TestAccount, id: u64) -> Fixture {
+        Fixture::new()
+            .with("config", self.config.clone())
+            .with("payer", self.payer.clone())
+            .with("quota", quota.clone())
+            .with("note", TestAccount::uninitialized(NOTE_LEN).at(Self::note_address(owner, id)))
+            .with("owner", owner.clone())
+            .with("system_program", TestAccount::system_program())
+            .args(instruction::SecureOpenSponsoredNote { id })
+    }
+
+    fn secure_open_sponsored(
+        &mut self,
+        quota: &mut TestAccount,
+        owner: &TestAccount,
+        id: u64,
+    ) -> std::result::Result<(), ProgramError> {
+        let mut fixture = self.sponsored_fixture(quota, owner, id);
+        let result = execute!(&mut fixture, instruction::SecureOpenSponsoredNote { id });
+        if result.is_ok() {
+            self.keep(&fixture);
+            *quota = fixture.account("quota").clone();
+        }
+        result
+    }
+}
+
+#[test]
+fn one_wallet_drains_the_payer_through_the_vulnerable_path() {
+    let mut notes = Notes::new();
+    let attacker = TestAccount::signer();
+    let rent = Rent::default();
+
+    // The largest note the payer still affords, until not even the smallest fits
+    let mut id = 0;
+    while let Some(space) =
+        (NOTE_LEN as u32..=MAX_SPACE).rev().find(|&space| rent.minimum_balance(space as usize) <= notes.payer.lamports)
+    {
+        notes.vulnerable_open(&attacker, id, space).unwrap();
+        id += 1;
+    }
+    assert_eq!(notes.notes_sponsored(), id);
+    assert!(notes.payer.lamports < rent.minimum_balance(NOTE_LEN));
+
+    // An honest user's note now fails
+    let user = TestAccount::signer();
+    assert!(notes.vulnerable_open(&user, 0, NOTE_LEN as u32).is_err());
+}
+
+#[test]
+fn vulnerable_notes_cannot_be_smaller_than_a_note() {
+    let mut notes = Notes::new();
+    assert_eq!(
+        notes.vulnerable_open(&TestAccount::signer(), 0, NOTE_LEN as u32 - 1),
+        Err(Error::from(NotesError::NoteTooSmall).into())
+    );
+}
+
+#[test]
+fn the_quota_stops_sponsoring_after_max_notes() {
+    let mut notes = Notes::new();
+    let owner = TestAccount::signer();
+    let mut quota = Notes::open_quota(&owner);
+    let note_rent = Rent::default().minimum_balance(NOTE_LEN);
+
+    for id in 0..u64::from(MAX_SPONSORED_NOTES) {
+        notes.secure_open_sponsored(&mut quota, &owner, id).unwrap();
+    }
+    assert_eq!(
+        notes.secure_open_sponsored(&mut quota, &owner, u64::from(MAX_SPONSORED_NOTES)),
+        Err(Error::from(NotesError::QuotaExhausted).into())
+    );
+    assert_eq!(quota.state::<Quota>().sponsored, MAX_SPONSORED_NOTES);
+    assert_eq!(notes.notes_sponsored(), u64::from(MAX_SPONSORED_NOTES));
+    assert_eq!(notes.payer.lamports, BUDGET - u64::from(MAX_SPONSORED_NOTES) * note_rent);
+}
+
+#[test]
+fn sponsored_notes_record_the_rent_they_cost() {
+    let mut notes = Notes::new();
+    let owner = TestAccount::signer();
+    let mut quota = Notes::open_quota(&owner);
+
+    let mut fixture = notes.sponsored_fixture(&quota, &owner, 7);
+    execute!(&mut fixture, instruction::SecureOpenSponsoredNote { id: 7 }).unwrap();
+    let note = fixture.state::<Note>("note");
+    assert_eq!(note.owner, owner.key);
+    assert_eq!(note.id, 7);
+    assert_eq!(note.sponsored_lamports, Rent::default().minimum_balance(NOTE_LEN));
+    notes.keep(&fixture);
+    quota = fixture.account("quota").clone();
+
+    // Closing returns the rent to the payer
+    let mut fixture = Fixture::new()
+        .with("config", notes.config.clone())
+        .with("payer", notes.payer.clone())
+        .with("note", fixture.account("note").clone())
+        .with("owner", owner.clone());
+    execute!(&mut fixture, instruction::CloseNote {}).unwrap();
+    assert_eq!(fixture.account("payer").lamports, BUDGET);
+    // The quota counts notes opened, not notes open
+    assert_eq!(quota.state::<Quota>().sponsored, 1);
+}
+
+#[test]
+fn user_paid_notes_cost_the_payer_nothing() {
+    let notes = Notes::new();
+    let owner = TestAccount::signer();
+    let owner_before = owner.lamports;
+
+    let mut fixture = Fixture::new()
+        .with("note", TestAccount::uninitialized(NOTE_LEN).at(Notes::note_address(&owner, 0)))
+        .with("owner", owner)
+        .with("system_program", TestAccount::system_program());
+    execute!(&mut fixture, instruction::SecureOpenNote { id: 0 }).unwrap();
+    assert_eq!(fixture.state::<Note>("note").sponsored_lamports, 0);
+    assert_eq!(
+        fixture.account("owner").lamports,
+        owner_before - Rent::default().minimum_balance(NOTE_LEN)
+    );
+    assert_eq!(notes.payer.lamports, BUDGET);
+}
+
+fn sponsored_note_fixture() -> Fixture {
+    let notes = Notes::new();
+    let owner = TestAccount::signer();
+    let quota = Notes::open_quota(&owner);
+    notes.sponsored_fixture(&quota, &owner, 0)
+}
+
+#[test]
+fn secure_sponsored_note_pays_from_the_payer_pda() {
+    assert_seeds_violation!(SecureOpenSponsoredNote, sponsored_note_fixture(), "config");
+    assert_owner_violation!(SecureOpenSponsoredNote, sponsored_note_fixture(), "config");
+    assert_seeds_violation!(SecureOpenSponsoredNote, sponsored_note_fixture(), "payer");
+}
+
+#[test]
+fn secure_sponsored_note_charges_the_signers_quota() {
+    assert_seeds_violation!(SecureOpenSponsoredNote, sponsored_note_fixture(), "quota");
+    assert_owner_violation!(SecureOpenSponsoredNote, sponsored_note_fixture(), "quota");
+    assert_constraint_violation!(
+        SecureOpenSponsoredNote,
+        sponsored_note_fixture(),
+        "quota" => |quota| quota.edit(|stored: &mut Quota| stored.owner = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    // Another signer derives another quota address
+    assert_seeds_violation!(SecureOpenSponsoredNote, sponsored_note_fixture(), "owner");
+    assert_signer_violation!(SecureOpenSponsoredNote, sponsored_note_fixture(), "owner");
+}
+
+#[test]
+fn secure_sponsored_note_lives_at_the_owners_id() {
+    assert_seeds_violation!(SecureOpenSponsoredNote, sponsored_note_fixture(), "note");
+}
@@ -0,0 +1,15 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Note space is smaller than a note")]
+    NoteTooSmall,
+    #[msg("Owner has used all of its sponsored notes")]
+    QuotaExhausted,
+    #[msg("Insufficient lamports")]
+    InsufficientLamports,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
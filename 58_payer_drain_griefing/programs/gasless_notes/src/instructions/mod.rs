@@ -0,0 +1,68 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 8 + 1 + 1, // discriminator + admin + notes_sponsored + bump + payer_bump
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(seeds = [b"payer"], bump)]
+    pub payer: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundPayer<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"payer"], bump = config.payer_bump)]
+    pub payer: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseNote<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"payer"], bump = config.payer_bump)]
+    pub payer: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        close = owner,
+        seeds = [b"note", owner.key().as_ref(), &note.id.to_le_bytes()],
+        bump = note.bump
+    )]
+    pub note: Account<'info, Note>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
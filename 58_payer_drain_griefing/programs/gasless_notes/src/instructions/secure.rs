@@ -0,0 +1,59 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct SecureOpenNote<'info> {
+    // SECURITY: The owner pays for the account it asks for
+    #[account(
+        init,
+        payer = owner,
+        space = NOTE_LEN,
+        seeds = [b"note", owner.key().as_ref(), &id.to_le_bytes()],
+        bump
+    )]
+    pub note: Account<'info, Note>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SecureOpenQuota<'info> {
+    // SECURITY: One quota per owner, and the owner pays its rent
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 32 + 1 + 1, // discriminator + owner + sponsored + bump
+        seeds = [b"quota", owner.key().as_ref()],
+        bump
+    )]
+    pub quota: Account<'info, Quota>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct SecureOpenSponsoredNote<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"payer"], bump = config.payer_bump)]
+    pub payer: SystemAccount<'info>,
+
+    // SECURITY: The signer's own quota, which the handler charges
+    #[account(mut, has_one = owner, seeds = [b"quota", owner.key().as_ref()], bump = quota.bump)]
+    pub quota: Account<'info, Quota>,
+
+    /// CHECK: Created by the handler at this address
+    #[account(mut, seeds = [b"note", owner.key().as_ref(), &id.to_le_bytes()], bump)]
+    pub note: UncheckedAccount<'info>,
+
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
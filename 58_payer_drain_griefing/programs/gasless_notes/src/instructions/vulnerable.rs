@@ -0,0 +1,21 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct VulnerableOpenNote<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    // VULNERABLE: Pays for whatever any signer asks for
+    #[account(mut, seeds = [b"payer"], bump = config.payer_bump)]
+    pub payer: SystemAccount<'info>,
+
+    /// CHECK: Created by the handler at this address
+    #[account(mut, seeds = [b"note", owner.key().as_ref(), &id.to_le_bytes()], bump)]
+    pub note: UncheckedAccount<'info>,
+
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,38 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Config, Note, Quota};
+
+#[test]
+fn config_layout_is_stable() {
+    let config = Config {
+        admin: Pubkey::new_from_array([1; 32]),
+        notes_sponsored: 0x2122232425262728,
+        bump: 49,
+        payer_bump: 50,
+    };
+    assert_golden!("Config", golden::anchor(&config));
+}
+
+#[test]
+fn note_layout_is_stable() {
+    let note = Note {
+        owner: Pubkey::new_from_array([5; 32]),
+        id: 0x6162636465666768,
+        sponsored_lamports: 0x7172737475767778,
+        bump: 129,
+    };
+    assert_golden!("Note", golden::anchor(&note));
+}
+
+#[test]
+fn quota_layout_is_stable() {
+    let quota = Quota {
+        owner: Pubkey::new_from_array([9; 32]),
+        sponsored: 161,
+        bump: 162,
+    };
+    assert_golden!("Quota", golden::anchor(&quota));
+}
@@ -0,0 +1,241 @@
+use anchor_lang::system_program::{self, CreateAccount, Transfer};
+use secref_guards::rent::minimum_balance;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Bytes of a note: discriminator + Note fields
+pub const NOTE_LEN: usize = 8 + Note::INIT_SPACE;
+
+/// Sponsored notes one owner may ever open through the secure path
+pub const MAX_SPONSORED_NOTES: u8 = 3;
+
+#[feature_gated]
+#[program]
+pub mod gasless_notes {
+    use super::*;
+
+    /// Create the config and record the bump of the payer PDA that sponsors rent
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.notes_sponsored = 0;
+        config.bump = ctx.bumps.config;
+        config.payer_bump = ctx.bumps.payer;
+
+        msg!("Gasless notes initialized, payer {}", ctx.accounts.payer.key());
+        Ok(())
+    }
+
+    /// Move lamports from any funder into the payer PDA
+    pub fn fund_payer(ctx: Context<FundPayer>, amount: u64) -> Result<()> {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.payer.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Payer funded with {} lamports", amount);
+        Ok(())
+    }
+
+    /// Close a note, returning sponsored rent to the payer and the rest to the owner
+    pub fn close_note(ctx: Context<CloseNote>) -> Result<()> {
+        let refund = ctx.accounts.note.sponsored_lamports;
+        move_lamports(
+            &ctx.accounts.note.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            refund,
+        )?;
+
+        msg!("Note closed, {} sponsored lamports returned", refund);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Open a note of any size, rent paid by the protocol
+    ///
+    /// Security Issue: The payer PDA funds the rent of every note anyone asks
+    /// for. Nothing limits how many notes one wallet opens, and the caller
+    /// picks the size, so each note locks up to the rent of 10 KiB. Opening
+    /// notes costs the attacker only transaction fees; the payer's lamports
+    /// sit in accounts only the attacker can close. Once the payer is empty,
+    /// every honest user's sponsored note fails.
+    #[vulnerable]
+    pub fn vulnerable_open_note(ctx: Context<VulnerableOpenNote>, id: u64, space: u32) -> Result<()> {
+        require!(space as usize >= NOTE_LEN, ErrorCode::NoteTooSmall);
+
+        // VULNERABILITY: No limit per owner - any wallet opens as many as it likes
+        // VULNERABILITY: `space` is the caller's, and so is the rent the payer puts up
+        let sponsored = create_sponsored_note(
+            &ctx.accounts.payer,
+            &ctx.accounts.note,
+            &ctx.accounts.system_program,
+            ctx.accounts.config.payer_bump,
+            Note {
+                owner: ctx.accounts.owner.key(),
+                id,
+                sponsored_lamports: 0,
+                bump: ctx.bumps.note,
+            },
+            space as usize,
+        )?;
+
+        let config = &mut ctx.accounts.config;
+        config.notes_sponsored = config
+            .notes_sponsored
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Note {} opened with {} sponsored lamports", id, sponsored);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE versions: the user pays, or the
+    // protocol pays a fixed amount within a per-user quota.
+
+    /// SECURE: Open a note the owner pays for
+    ///
+    /// Security Fix: `payer = owner` - whoever asks for the account puts up
+    /// its rent, and gets it back when closing it. Mass-creating notes costs
+    /// the attacker exactly what it locks.
+    #[secure]
+    pub fn secure_open_note(ctx: Context<SecureOpenNote>, id: u64) -> Result<()> {
+        let note = &mut ctx.accounts.note;
+        note.owner = ctx.accounts.owner.key();
+        note.id = id;
+        note.sponsored_lamports = 0;
+        note.bump = ctx.bumps.note;
+
+        msg!("Note {} opened, paid by its owner", id);
+        Ok(())
+    }
+
+    /// SECURE: Create the owner's quota of sponsored notes, paid by the owner
+    #[secure]
+    pub fn secure_open_quota(ctx: Context<SecureOpenQuota>) -> Result<()> {
+        let quota = &mut ctx.accounts.quota;
+        quota.owner = ctx.accounts.owner.key();
+        quota.sponsored = 0;
+        quota.bump = ctx.bumps.quota;
+
+        msg!("Quota opened for {}", quota.owner);
+        Ok(())
+    }
+
+    /// SECURE: Open a fixed-size note the protocol pays for, within the owner's quota
+    ///
+    /// Security Fix: The owner's Quota PDA counts sponsored notes and stops
+    /// at MAX_SPONSORED_NOTES, and the note is always NOTE_LEN bytes, so one
+    /// wallet can lock at most MAX_SPONSORED_NOTES notes' rent. The quota's
+    /// own rent is paid by the owner, so every fresh wallet an attacker uses
+    /// to get round it costs them rent too.
+    #[secure]
+    pub fn secure_open_sponsored_note(ctx: Context<SecureOpenSponsoredNote>, id: u64) -> Result<()> {
+        // SECURITY: Each owner gets a bounded number of sponsored notes
+        let quota = &mut ctx.accounts.quota;
+        require!(quota.sponsored < MAX_SPONSORED_NOTES, ErrorCode::QuotaExhausted);
+        quota.sponsored = quota.sponsored.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        // SECURITY: The program sets the size, and so what one note costs the payer
+        let sponsored = create_sponsored_note(
+            &ctx.accounts.payer,
+            &ctx.accounts.note,
+            &ctx.accounts.system_program,
+            ctx.accounts.config.payer_bump,
+            Note {
+                owner: ctx.accounts.owner.key(),
+                id,
+                sponsored_lamports: 0,
+                bump: ctx.bumps.note,
+            },
+            NOTE_LEN,
+        )?;
+
+        let config = &mut ctx.accounts.config;
+        config.notes_sponsored = config
+            .notes_sponsored
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Note {} opened with {} sponsored lamports", id, sponsored);
+        Ok(())
+    }
+}
+
+/// Create `note` at its PDA with `space` bytes, rent paid by the payer PDA, and write it
+///
+/// Records the rent on the note so `close_note` returns it to the payer.
+/// Returns the lamports the payer put up.
+fn create_sponsored_note<'info>(
+    payer: &SystemAccount<'info>,
+    note_account: &UncheckedAccount<'info>,
+    system_program: &Program<'info, System>,
+    payer_bump: u8,
+    mut note: Note,
+    space: usize,
+) -> Result<u64> {
+    let rent = minimum_balance(space)?;
+    let id = note.id.to_le_bytes();
+    let payer_seeds: &[&[u8]] = &[b"payer", &[payer_bump]];
+    let note_seeds: &[&[u8]] = &[b"note", note.owner.as_ref(), &id, &[note.bump]];
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: payer.to_account_info(),
+                to: note_account.to_account_info(),
+            },
+            &[payer_seeds, note_seeds],
+        ),
+        rent,
+        space as u64,
+        &crate::ID,
+    )?;
+
+    note.sponsored_lamports = rent;
+    let mut data = note_account.try_borrow_mut_data()?;
+    note.try_serialize(&mut &mut data[..])?;
+    Ok(rent)
+}
+
+/// Move `amount` lamports out of a program-owned account into `to`
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    **from.try_borrow_mut_lamports()? = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientLamports)?;
+    **to.try_borrow_mut_lamports()? = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
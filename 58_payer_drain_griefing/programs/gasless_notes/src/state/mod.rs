@@ -0,0 +1,39 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Config {
+    /// The account that created the config (32 bytes)
+    pub admin: Pubkey,
+    /// Notes the payer PDA has paid rent for (8 bytes)
+    pub notes_sponsored: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+    /// Bump of the `[b"payer"]` PDA that pays sponsored rent (1 byte)
+    pub payer_bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Note {
+    /// The wallet that owns this note (32 bytes)
+    pub owner: Pubkey,
+    /// Owner-chosen id, part of the note's seeds (8 bytes)
+    pub id: u64,
+    /// Rent the payer PDA put up for this note, returned to it on close (8 bytes)
+    pub sponsored_lamports: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+/// Sponsored notes an owner has opened, whether or not they were closed since
+#[account]
+pub struct Quota {
+    /// The wallet this quota belongs to (32 bytes)
+    pub owner: Pubkey,
+    /// Sponsored notes opened so far, at most MAX_SPONSORED_NOTES (1 byte)
+    pub sponsored: u8,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
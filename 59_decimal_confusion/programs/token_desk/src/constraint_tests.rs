@@ -0,0 +1,201 @@
+//! A desk selling a 6-decimal token at 0.1 SOL each: what each purchase
+//! costs through the vulnerable and secure handlers, then every constraint
+//! on the desk contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use secref_guards::amount::Amount;
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_owner_violation, assert_seeds_violation,
+    assert_signer_violation, execute, Fixture, TestAccount,
+};
+
+use crate::{instruction, Buy, Desk, ErrorCode as DeskError, InitializeDesk};
+
+/// USDC's decimals
+const DECIMALS: u8 = 6;
+/// Lamports per whole token: 0.1 SOL
+const PRICE: u64 = 100_000_000;
+/// Whole tokens the vault holds
+const STOCK: u64 = 10_000;
+/// What the buyer holds: 10 SOL
+const BUYER_LAMPORTS: u64 = 10_000_000_000;
+
+fn desk_address(mint: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"desk", mint.as_ref()], &crate::ID)
+}
+
+fn initialize_fixture() -> Fixture {
+    let mint = Pubkey::new_unique();
+    let (desk, _) = desk_address(mint);
+    Fixture::new()
+        .args(instruction::InitializeDesk { price_per_token: PRICE })
+        .with("desk", TestAccount::uninitialized(Desk::SPACE).at(desk))
+        .with("mint", TestAccount::mint(Pubkey::new_unique(), DECIMALS, 0).at(mint))
+        .with("vault", TestAccount::token_account(mint, desk, 0))
+        .with("authority", TestAccount::signer())
+        .with("system_program", TestAccount::system_program())
+}
+
+/// A stocked desk for a mint with `decimals` decimals, and a buyer holding `lamports`
+fn buy_fixture_for(decimals: u8, lamports: u64) -> Fixture {
+    let mint = Pubkey::new_unique();
+    let (desk, bump) = desk_address(mint);
+    let stock = Amount::from_whole(STOCK, decimals).unwrap().raw();
+    let vault = TestAccount::token_account(mint, desk, stock);
+    let buyer = TestAccount::signer().with_lamports(lamports);
+    Fixture::new()
+        .with(
+            "desk",
+            TestAccount::anchor(&Desk {
+                authority: Pubkey::new_unique(),
+                mint,
+                vault: vault.key,
+                price_per_token: PRICE,
+                sold: 0,
+                bump,
+            })
+            .at(desk),
+        )
+        .with("mint", TestAccount::mint(Pubkey::new_unique(), decimals, stock).at(mint))
+        .with("vault", vault)
+        .with("buyer_tokens", TestAccount::token_account(mint, buyer.key, 0))
+        .with("buyer", buyer)
+        .with("token_program", TestAccount::token_program())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn buy_fixture() -> Fixture {
+    buy_fixture_for(DECIMALS, BUYER_LAMPORTS)
+}
+
+/// Lamports the buyer paid and base units it received
+fn outcome(fixture: &Fixture, lamports_before: u64) -> (u64, u64) {
+    let paid = lamports_before - fixture.account("buyer").lamports;
+    (paid, fixture.state::<TokenAccount>("buyer_tokens").amount)
+}
+
+#[test]
+fn vulnerable_buy_sells_six_decimal_tokens_at_a_thousandth_of_the_price() {
+    let thousand = Amount::from_whole(1_000, DECIMALS).unwrap().raw();
+    let mut fixture = buy_fixture();
+    execute!(&mut fixture, instruction::VulnerableBuy { amount: thousand }).unwrap();
+
+    // 1,000 tokens listed at 100 SOL go for 0.1 SOL
+    assert_eq!(outcome(&fixture, BUYER_LAMPORTS), (PRICE, thousand));
+    assert_eq!(fixture.state::<Desk>("desk").sold, thousand);
+}
+
+#[test]
+fn vulnerable_buy_is_only_right_for_nine_decimal_mints() {
+    // Ten, since `amount * price` in u64 overflows past about 184 whole 9-decimal tokens at this price
+    let ten = Amount::from_whole(10, 9).unwrap().raw();
+    let mut fixture = buy_fixture_for(9, 10 * PRICE);
+    execute!(&mut fixture, instruction::VulnerableBuy { amount: ten }).unwrap();
+    assert_eq!(outcome(&fixture, 10 * PRICE), (10 * PRICE, ten));
+}
+
+#[test]
+fn vulnerable_buy_gives_dust_away() {
+    let mut fixture = buy_fixture();
+    execute!(&mut fixture, instruction::VulnerableBuy { amount: 9 }).unwrap();
+    assert_eq!(outcome(&fixture, BUYER_LAMPORTS), (0, 9));
+}
+
+#[test]
+fn secure_buy_prices_whole_tokens_of_the_mint() {
+    let amount = Amount::from_ui("1.5", DECIMALS).unwrap();
+    let mut fixture = buy_fixture();
+    let desk_before = fixture.account("desk").lamports;
+    execute!(&mut fixture, instruction::SecureBuy { amount: amount.raw() }).unwrap();
+
+    assert_eq!(outcome(&fixture, BUYER_LAMPORTS), (150_000_000, 1_500_000));
+    assert_eq!(fixture.account("desk").lamports, desk_before + 150_000_000);
+    assert_eq!(fixture.state::<Desk>("desk").sold, 1_500_000);
+}
+
+#[test]
+fn secure_buy_charges_the_listed_price_for_a_thousand_tokens() {
+    let thousand = Amount::from_whole(1_000, DECIMALS).unwrap().raw();
+
+    // A buyer holding the 0.1 SOL the vulnerable handler would have taken cannot pay 100 SOL
+    let mut fixture = buy_fixture_for(DECIMALS, PRICE);
+    assert!(execute!(&mut fixture, instruction::SecureBuy { amount: thousand }).is_err());
+
+    let mut fixture = buy_fixture_for(DECIMALS, 1_000 * PRICE);
+    execute!(&mut fixture, instruction::SecureBuy { amount: thousand }).unwrap();
+    assert_eq!(outcome(&fixture, 1_000 * PRICE), (1_000 * PRICE, thousand));
+}
+
+#[test]
+fn secure_buy_charges_dust_what_it_is_worth() {
+    let mut fixture = buy_fixture();
+    execute!(&mut fixture, instruction::SecureBuy { amount: 9 }).unwrap();
+    assert_eq!(outcome(&fixture, BUYER_LAMPORTS), (900, 9));
+
+    let mut fixture = buy_fixture();
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureBuy { amount: 0 }),
+        Err(Error::from(DeskError::ZeroAmount).into())
+    );
+}
+
+#[test]
+fn initialize_desk_rejects_a_free_token() {
+    let mut fixture = initialize_fixture();
+    assert_eq!(
+        execute!(&mut fixture, instruction::InitializeDesk { price_per_token: 0 }),
+        Err(Error::from(DeskError::InvalidPrice).into())
+    );
+}
+
+#[test]
+fn initialize_desk_sells_from_a_vault_the_desk_owns() {
+    assert_seeds_violation!(InitializeDesk, initialize_fixture(), "desk");
+    let fixture = initialize_fixture();
+    let desk = fixture.key("desk");
+    assert_constraint_violation!(
+        InitializeDesk,
+        fixture,
+        "vault" => TestAccount::token_account(Pubkey::new_unique(), desk, 0),
+        ErrorCode::ConstraintTokenMint
+    );
+    let fixture = initialize_fixture();
+    let mint = fixture.key("mint");
+    assert_constraint_violation!(
+        InitializeDesk,
+        fixture,
+        "vault" => TestAccount::token_account(mint, Pubkey::new_unique(), 0),
+        ErrorCode::ConstraintTokenOwner
+    );
+    assert_signer_violation!(InitializeDesk, initialize_fixture(), "authority");
+}
+
+#[test]
+fn buy_uses_the_desks_own_mint_and_vault() {
+    assert_seeds_violation!(Buy, buy_fixture(), "desk");
+    assert_owner_violation!(Buy, buy_fixture(), "desk");
+    assert_has_one_violation!(Buy, buy_fixture(), "mint");
+    assert_has_one_violation!(Buy, buy_fixture(), "vault");
+}
+
+#[test]
+fn buy_delivers_to_the_buyers_tokens() {
+    let fixture = buy_fixture();
+    let buyer = fixture.key("buyer");
+    assert_constraint_violation!(
+        Buy,
+        fixture,
+        "buyer_tokens" => TestAccount::token_account(Pubkey::new_unique(), buyer, 0),
+        ErrorCode::ConstraintTokenMint
+    );
+    let fixture = buy_fixture();
+    let mint = fixture.key("mint");
+    assert_constraint_violation!(
+        Buy,
+        fixture,
+        "buyer_tokens" => TestAccount::token_account(mint, Pubkey::new_unique(), 0),
+        ErrorCode::ConstraintTokenOwner
+    );
+    assert_signer_violation!(Buy, buy_fixture(), "buyer");
+}
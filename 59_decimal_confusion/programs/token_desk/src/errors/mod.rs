@@ -0,0 +1,13 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Price must be above zero")]
+    InvalidPrice,
+    #[msg("Purchase must be of at least one base unit")]
+    ZeroAmount,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
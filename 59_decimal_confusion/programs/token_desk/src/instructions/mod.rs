@@ -0,0 +1,52 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct InitializeDesk<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Desk::SPACE,
+        seeds = [b"desk", mint.key().as_ref()],
+        bump
+    )]
+    pub desk: Account<'info, Desk>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(token::mint = mint, token::authority = desk)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Both purchases take the same accounts; they differ only in how they price the amount
+#[derive(Accounts)]
+pub struct Buy<'info> {
+    #[account(
+        mut,
+        has_one = mint,
+        has_one = vault,
+        seeds = [b"desk", desk.mint.as_ref()],
+        bump = desk.bump
+    )]
+    pub desk: Account<'info, Desk>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint, token::authority = buyer)]
+    pub buyer_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,19 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::Desk;
+
+#[test]
+fn desk_layout_is_stable() {
+    let desk = Desk {
+        authority: Pubkey::new_from_array([1; 32]),
+        mint: Pubkey::new_from_array([2; 32]),
+        vault: Pubkey::new_from_array([3; 32]),
+        price_per_token: 0x2122232425262728,
+        sold: 0x3132333435363738,
+        bump: 65,
+    };
+    assert_golden!("Desk", golden::anchor(&desk));
+}
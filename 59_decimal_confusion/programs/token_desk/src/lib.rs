@@ -0,0 +1,163 @@
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Base units in one SOL, which the vulnerable handler takes for base units in one token of any mint
+#[cfg(feature = "vulnerable")]
+pub const ONE_TOKEN: u64 = 1_000_000_000;
+
+/// A desk selling tokens from its vault for lamports at a fixed price.
+///
+/// The price is in lamports per whole token, the unit a listing shows.
+/// Purchases name an amount in base units, the unit the Token program
+/// moves, and a whole token is `10^decimals` of them: 1,000,000 for a
+/// 6-decimal mint like USDC, 1,000,000,000 for a 9-decimal one like SOL.
+#[feature_gated]
+#[program]
+pub mod token_desk {
+    use super::*;
+    #[secure]
+    use secref_guards::amount::Amount;
+
+    /// Open a desk for a mint, selling from a vault the desk PDA owns
+    pub fn initialize_desk(ctx: Context<InitializeDesk>, price_per_token: u64) -> Result<()> {
+        require!(price_per_token > 0, ErrorCode::InvalidPrice);
+
+        let desk = &mut ctx.accounts.desk;
+        desk.authority = ctx.accounts.authority.key();
+        desk.mint = ctx.accounts.mint.key();
+        desk.vault = ctx.accounts.vault.key();
+        desk.price_per_token = price_per_token;
+        desk.sold = 0;
+        desk.bump = ctx.bumps.desk;
+
+        msg!("Desk for {} opened at {} lamports per token", desk.mint, price_per_token);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Buy `amount` base units, priced as if the mint had 9 decimals
+    ///
+    /// Security Issue: The cost divides by ONE_TOKEN, 10^9, whatever the
+    /// mint's decimals. That is right for a 9-decimal mint and wrong by
+    /// `10^(9 - decimals)` for any other: on a 6-decimal mint every token
+    /// costs a thousandth of its price, and the desk's stock goes for
+    /// 0.1% of what it lists at. The division also rounds down, so
+    /// purchases too small to cost a whole lamport are free.
+    #[vulnerable]
+    pub fn vulnerable_buy(ctx: Context<Buy>, amount: u64) -> Result<()> {
+        // VULNERABILITY: Base units priced as if every mint were SOL
+        let cost = amount
+            .checked_mul(ctx.accounts.desk.price_per_token)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            / ONE_TOKEN;
+        collect(ctx.accounts, cost)?;
+
+        // VULNERABILITY: `transfer` takes a bare u64; nothing says what scale it is in
+        let desk = &ctx.accounts.desk;
+        let seeds: &[&[u8]] = &[b"desk", desk.mint.as_ref(), &[desk.bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.buyer_tokens.to_account_info(),
+                    authority: desk.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        record_sale(&mut ctx.accounts.desk, amount, cost)
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that prices the amount in
+    // the mint's own units.
+
+    /// SECURE: Buy `amount` base units, priced by the mint's own decimals
+    ///
+    /// Security Fix: The amount becomes an `Amount` carrying the mint's
+    /// decimals, and `value_ceil` prices it per `10^decimals` base units in
+    /// u128, rounding up so no purchase is free. `transfer_checked` states
+    /// the decimals to the Token program, which fails if they are not the
+    /// mint's.
+    #[secure]
+    pub fn secure_buy(ctx: Context<Buy>, amount: u64) -> Result<()> {
+        // SECURITY: The amount and the mint's decimals travel together
+        let amount = Amount::from_raw(amount, ctx.accounts.mint.decimals);
+        require!(amount.raw() > 0, ErrorCode::ZeroAmount);
+
+        // SECURITY: Priced per whole token of this mint, rounded in the desk's favour
+        let cost = amount.value_ceil(ctx.accounts.desk.price_per_token)?;
+        collect(ctx.accounts, cost)?;
+
+        // SECURITY: The Token program checks the decimals against the mint
+        let desk = &ctx.accounts.desk;
+        let seeds: &[&[u8]] = &[b"desk", desk.mint.as_ref(), &[desk.bump]];
+        token::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::TransferChecked {
+                    from: ctx.accounts.vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.buyer_tokens.to_account_info(),
+                    authority: desk.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount.raw(),
+            amount.decimals(),
+        )?;
+
+        msg!("Sold {} tokens", amount);
+        record_sale(&mut ctx.accounts.desk, amount.raw(), cost)
+    }
+}
+
+/// Take `cost` lamports from the buyer into the desk
+fn collect(accounts: &Buy, cost: u64) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            accounts.system_program.to_account_info(),
+            Transfer {
+                from: accounts.buyer.to_account_info(),
+                to: accounts.desk.to_account_info(),
+            },
+        ),
+        cost,
+    )
+}
+
+/// Count `amount` base units as sold
+fn record_sale(desk: &mut Desk, amount: u64, cost: u64) -> Result<()> {
+    desk.sold = desk.sold.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    msg!("Sold {} base units for {} lamports", amount, cost);
+    Ok(())
+}
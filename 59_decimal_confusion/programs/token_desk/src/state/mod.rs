@@ -0,0 +1,24 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Desk {
+    /// The account that opened the desk and stocked its vault (32 bytes)
+    pub authority: Pubkey,
+    /// The token sold (32 bytes)
+    pub mint: Pubkey,
+    /// Token account holding the stock, owned by the desk PDA (32 bytes)
+    pub vault: Pubkey,
+    /// Lamports per whole token, that is per `10^decimals` base units (8 bytes)
+    pub price_per_token: u64,
+    /// Base units sold so far (8 bytes)
+    pub sold: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Desk {
+    /// discriminator + authority + mint + vault + price_per_token + sold + bump
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1;
+}
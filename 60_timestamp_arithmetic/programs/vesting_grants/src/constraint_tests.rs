@@ -0,0 +1,208 @@
+//! What each claim pays on schedules that start in the future or never
+//! end, which schedules each creation accepts, and every constraint on
+//! the grant contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use secref_testkit::runtime::warp_to;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation, assert_signer_violation, execute,
+    Fixture, TestAccount,
+};
+
+use crate::schedule::MAX_DURATION;
+use crate::{instruction, Claim, CreateGrant, ErrorCode as GrantError, Grant};
+
+const NOW: i64 = 1_700_000_000;
+const YEAR: i64 = 365 * 24 * 60 * 60;
+/// Lamports a grant vests: 1 SOL
+const TOTAL: u64 = 1_000_000_000;
+
+fn grant_address(funder: &Pubkey, beneficiary: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"grant", funder.as_ref(), beneficiary.as_ref()], &crate::ID)
+}
+
+fn create_fixture() -> Fixture {
+    warp_to(NOW);
+    let funder = TestAccount::signer();
+    let beneficiary = TestAccount::system(0);
+    let (grant, _) = grant_address(&funder.key, &beneficiary.key);
+    Fixture::new()
+        .args(instruction::SecureCreateGrant {
+            start: NOW,
+            duration: YEAR,
+            total: TOTAL,
+        })
+        .with("grant", TestAccount::uninitialized(Grant::SPACE).at(grant))
+        .with("beneficiary", beneficiary)
+        .with("funder", funder)
+        .with("system_program", TestAccount::system_program())
+}
+
+/// A funded grant on `start` and `duration`, claimed at NOW
+fn claim_fixture(start: i64, duration: i64) -> Fixture {
+    warp_to(NOW);
+    let funder = Pubkey::new_unique();
+    let beneficiary = TestAccount::signer();
+    let (address, bump) = grant_address(&funder, &beneficiary.key);
+    let grant = TestAccount::anchor(&Grant {
+        funder,
+        beneficiary: beneficiary.key,
+        start,
+        duration,
+        total: TOTAL,
+        claimed: 0,
+        bump,
+    })
+    .at(address);
+    let funded = grant.lamports + TOTAL;
+    Fixture::new()
+        .with("grant", grant.with_lamports(funded))
+        .with("beneficiary", beneficiary)
+}
+
+/// Lamports the beneficiary has claimed, as the grant records and as its balance shows
+fn claimed(fixture: &Fixture, beneficiary_before: u64) -> u64 {
+    let paid = fixture.account("beneficiary").lamports - beneficiary_before;
+    assert_eq!(fixture.state::<Grant>("grant").claimed, paid);
+    paid
+}
+
+fn create(fixture: &mut Fixture, start: i64, duration: i64) -> ProgramResult {
+    let args = instruction::SecureCreateGrant {
+        start,
+        duration,
+        total: TOTAL,
+    };
+    execute!(fixture, args)
+}
+
+#[test]
+fn vulnerable_claim_pays_a_grant_before_it_starts() {
+    let mut fixture = claim_fixture(NOW + 30 * 24 * 60 * 60, YEAR);
+    let before = fixture.account("beneficiary").lamports;
+    execute!(&mut fixture, instruction::VulnerableClaim {}).unwrap();
+    assert_eq!(claimed(&fixture, before), TOTAL);
+}
+
+#[test]
+fn vulnerable_claim_ends_an_endless_grant_at_once() {
+    let mut fixture = claim_fixture(NOW - 1, i64::MAX);
+    let before = fixture.account("beneficiary").lamports;
+    execute!(&mut fixture, instruction::VulnerableClaim {}).unwrap();
+    assert_eq!(claimed(&fixture, before), TOTAL);
+}
+
+#[test]
+fn secure_claim_pays_nothing_before_the_start() {
+    let mut fixture = claim_fixture(NOW + 30 * 24 * 60 * 60, YEAR);
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureClaim {}),
+        Err(Error::from(GrantError::NothingVested).into())
+    );
+
+    // The endless grant has vested a vanishing share after one second
+    let mut fixture = claim_fixture(NOW - 1, i64::MAX);
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureClaim {}),
+        Err(Error::from(GrantError::NothingVested).into())
+    );
+}
+
+#[test]
+fn secure_claim_pays_as_the_grant_vests() {
+    let mut fixture = claim_fixture(NOW, YEAR);
+    let before = fixture.account("beneficiary").lamports;
+
+    warp_to(NOW + YEAR / 4);
+    execute!(&mut fixture, instruction::SecureClaim {}).unwrap();
+    assert_eq!(claimed(&fixture, before), TOTAL / 4);
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureClaim {}),
+        Err(Error::from(GrantError::NothingVested).into())
+    );
+
+    warp_to(NOW + 2 * YEAR);
+    execute!(&mut fixture, instruction::SecureClaim {}).unwrap();
+    assert_eq!(claimed(&fixture, before), TOTAL);
+}
+
+#[test]
+fn secure_claim_fails_when_the_time_since_the_start_is_not_an_i64() {
+    // Only the vulnerable creation accepts a start this far back
+    let mut fixture = claim_fixture(i64::MIN, YEAR);
+    let before = fixture.account("beneficiary").lamports;
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureClaim {}),
+        Err(Error::from(GrantError::ArithmeticOverflow).into())
+    );
+
+    let mut fixture = claim_fixture(i64::MIN, YEAR);
+    execute!(&mut fixture, instruction::VulnerableClaim {}).unwrap();
+    assert_eq!(claimed(&fixture, before), TOTAL);
+}
+
+#[test]
+fn vulnerable_create_grant_accepts_schedules_at_the_ends_of_i64() {
+    for (start, duration) in [(NOW, i64::MAX), (i64::MIN, YEAR), (i64::MAX, 1)] {
+        let mut fixture = create_fixture();
+        execute!(&mut fixture, instruction::VulnerableCreateGrant { start, duration, total: TOTAL }).unwrap();
+        assert_eq!(fixture.state::<Grant>("grant").duration, duration);
+    }
+    for duration in [i64::MIN, -1, 0] {
+        let mut fixture = create_fixture();
+        assert_eq!(
+            execute!(&mut fixture, instruction::VulnerableCreateGrant { start: NOW, duration, total: TOTAL }),
+            Err(Error::from(GrantError::InvalidSchedule).into())
+        );
+    }
+}
+
+#[test]
+fn secure_create_grant_takes_schedules_near_the_present() {
+    for (start, duration) in [(NOW, 1), (NOW - MAX_DURATION, MAX_DURATION), (NOW + MAX_DURATION, MAX_DURATION)] {
+        let mut fixture = create_fixture();
+        create(&mut fixture, start, duration).unwrap();
+        let grant = fixture.state::<Grant>("grant");
+        assert_eq!((grant.start, grant.duration, grant.total), (start, duration, TOTAL));
+    }
+
+    let rejected = [
+        (NOW, i64::MIN),
+        (NOW, -1),
+        (NOW, 0),
+        (NOW, MAX_DURATION + 1),
+        (NOW, i64::MAX),
+        (i64::MIN, YEAR),
+        (NOW - MAX_DURATION - 1, YEAR),
+        (NOW + MAX_DURATION + 1, YEAR),
+        (i64::MAX, YEAR),
+    ];
+    for (start, duration) in rejected {
+        let mut fixture = create_fixture();
+        assert_eq!(
+            create(&mut fixture, start, duration),
+            Err(Error::from(GrantError::InvalidSchedule).into()),
+            "{start} + {duration}"
+        );
+    }
+}
+
+#[test]
+fn create_grant_is_keyed_by_funder_and_beneficiary() {
+    assert_seeds_violation!(CreateGrant, create_fixture(), "grant");
+    assert_signer_violation!(CreateGrant, create_fixture(), "funder");
+}
+
+#[test]
+fn claim_pays_the_grants_own_beneficiary() {
+    assert_seeds_violation!(Claim, claim_fixture(NOW - YEAR, YEAR), "grant");
+    assert_owner_violation!(Claim, claim_fixture(NOW - YEAR, YEAR), "grant");
+    assert_constraint_violation!(
+        Claim,
+        claim_fixture(NOW - YEAR, YEAR),
+        "grant" => |grant| grant.edit(|stored: &mut Grant| stored.beneficiary = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_signer_violation!(Claim, claim_fixture(NOW - YEAR, YEAR), "beneficiary");
+}
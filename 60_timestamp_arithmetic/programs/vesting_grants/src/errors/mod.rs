@@ -0,0 +1,15 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Schedule duration or start is out of range")]
+    InvalidSchedule,
+    #[msg("Nothing has vested since the last claim")]
+    NothingVested,
+    #[msg("Insufficient lamports")]
+    InsufficientLamports,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
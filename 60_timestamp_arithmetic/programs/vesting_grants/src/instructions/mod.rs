@@ -0,0 +1,38 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+/// Both creations take the same accounts; they differ only in which schedules they accept
+#[derive(Accounts)]
+pub struct CreateGrant<'info> {
+    #[account(
+        init,
+        payer = funder,
+        space = Grant::SPACE,
+        seeds = [b"grant", funder.key().as_ref(), beneficiary.key().as_ref()],
+        bump
+    )]
+    pub grant: Account<'info, Grant>,
+
+    pub beneficiary: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Both claims take the same accounts; they differ only in how they compute what has vested
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    #[account(
+        mut,
+        has_one = beneficiary,
+        seeds = [b"grant", grant.funder.as_ref(), beneficiary.key().as_ref()],
+        bump = grant.bump
+    )]
+    pub grant: Account<'info, Grant>,
+
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+}
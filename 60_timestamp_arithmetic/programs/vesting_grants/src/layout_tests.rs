@@ -0,0 +1,21 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::Grant;
+
+#[test]
+fn grant_layout_is_stable() {
+    let grant = Grant {
+        funder: Pubkey::new_from_array([1; 32]),
+        beneficiary: Pubkey::new_from_array([2; 32]),
+        // Negative, so the fixture pins the sign as well as the byte order
+        start: -0x2122232425262728,
+        duration: 0x3132333435363738,
+        total: 0x4142434445464748,
+        claimed: 0x5152535455565758,
+        bump: 97,
+    };
+    assert_golden!("Grant", golden::anchor(&grant));
+}
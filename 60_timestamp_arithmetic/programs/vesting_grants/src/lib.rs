@@ -0,0 +1,157 @@
+use anchor_lang::system_program::{self, Transfer};
+
+pub mod schedule;
+
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Lamport grants that vest linearly over time.
+///
+/// A funder locks lamports in a grant PDA for a beneficiary, vesting from
+/// `start` over `duration` seconds. Schedules may start in the past, for
+/// service already given, or in the future, for a cliff. The beneficiary
+/// claims whatever has vested and not yet been claimed.
+#[feature_gated]
+#[program]
+pub mod vesting_grants {
+    use super::*;
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Fund a grant on any schedule with a positive duration
+    ///
+    /// Security Issue: A positive duration is the only check. A duration of
+    /// i64::MAX, meant as "until revoked", or a start decades away, is
+    /// accepted, and `vulnerable_vested` wraps on exactly those.
+    #[vulnerable]
+    pub fn vulnerable_create_grant(ctx: Context<CreateGrant>, start: i64, duration: i64, total: u64) -> Result<()> {
+        // VULNERABILITY: Any positive duration and any start at all
+        require!(duration > 0, ErrorCode::InvalidSchedule);
+
+        fund(ctx, start, duration, total)
+    }
+
+    /// VULNERABLE: Claim what the wrapping schedule math says has vested
+    ///
+    /// Security Issue: `vulnerable_vested` casts a negative elapsed time to
+    /// a u64, so before a future start the whole grant has vested, and
+    /// wraps `start + duration`, so an endless grant ends at once. The
+    /// beneficiary claims everything the day the grant is funded.
+    #[vulnerable]
+    pub fn vulnerable_claim(ctx: Context<Claim>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let grant = &ctx.accounts.grant;
+
+        // VULNERABILITY: Vested before it starts, or ended before it began
+        let vested = schedule::vulnerable_vested(grant.total, grant.start, grant.duration, now);
+        let amount = vested.saturating_sub(grant.claimed);
+
+        pay(ctx, amount)
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE versions that bound schedules and
+    // vest with checked, signed arithmetic.
+
+    /// SECURE: Fund a grant on a schedule near the present
+    ///
+    /// Security Fix: The duration is positive and at most MAX_DURATION, and
+    /// the start is within MAX_DURATION of now, so every time the schedule
+    /// involves is decades inside i64.
+    #[secure]
+    pub fn secure_create_grant(ctx: Context<CreateGrant>, start: i64, duration: i64, total: u64) -> Result<()> {
+        // SECURITY: Bounded duration, and a start within reach of now
+        let now = Clock::get()?.unix_timestamp;
+        require!(schedule::is_valid(start, duration, now), ErrorCode::InvalidSchedule);
+
+        fund(ctx, start, duration, total)
+    }
+
+    /// SECURE: Claim what has vested, by checked i64 arithmetic
+    ///
+    /// Security Fix: `schedule::vested` compares the signed elapsed time
+    /// before converting it and never adds to a timestamp, so nothing vests
+    /// before the start and a time that is not an i64 away from it is an
+    /// error. A claim with nothing new to pay fails.
+    #[secure]
+    pub fn secure_claim(ctx: Context<Claim>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let grant = &ctx.accounts.grant;
+
+        // SECURITY: Signed, checked, and zero until the schedule starts
+        let vested = schedule::vested(grant.total, grant.start, grant.duration, now)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let amount = vested.checked_sub(grant.claimed).ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(amount > 0, ErrorCode::NothingVested);
+
+        pay(ctx, amount)
+    }
+}
+
+/// Write the grant and move `total` lamports from the funder into it
+fn fund(ctx: Context<CreateGrant>, start: i64, duration: i64, total: u64) -> Result<()> {
+    let grant = &mut ctx.accounts.grant;
+    grant.funder = ctx.accounts.funder.key();
+    grant.beneficiary = ctx.accounts.beneficiary.key();
+    grant.start = start;
+    grant.duration = duration;
+    grant.total = total;
+    grant.claimed = 0;
+    grant.bump = ctx.bumps.grant;
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.grant.to_account_info(),
+            },
+        ),
+        total,
+    )?;
+
+    msg!("Grant of {} lamports from {} over {} seconds", total, start, duration);
+    Ok(())
+}
+
+/// Pay `amount` lamports from the grant to its beneficiary and count them as claimed
+fn pay(ctx: Context<Claim>, amount: u64) -> Result<()> {
+    let grant = &mut ctx.accounts.grant;
+    grant.claimed = grant.claimed.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    let grant = grant.to_account_info();
+    let beneficiary = ctx.accounts.beneficiary.to_account_info();
+    **grant.try_borrow_mut_lamports()? = grant
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientLamports)?;
+    **beneficiary.try_borrow_mut_lamports()? = beneficiary
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    msg!("Claimed {} lamports", amount);
+    Ok(())
+}
@@ -0,0 +1,178 @@
+//! Linear vesting over Unix timestamps.
+//!
+//! `Clock::unix_timestamp` is an i64, and so are a schedule's start and
+//! duration. Before the start, the time elapsed since it is negative; a
+//! start far enough in the past, or a duration long enough, takes the
+//! elapsed time or the end of the schedule out of i64 range. Arithmetic
+//! that wraps, or a cast to unsigned that is never checked, turns "not
+//! yet" into "long ago".
+
+/// Longest schedule the secure path accepts, and furthest its start may be from now: ten years
+pub const MAX_DURATION: i64 = 10 * 365 * 24 * 60 * 60;
+
+// ========================================
+// VULNERABLE IMPLEMENTATION
+// ========================================
+// This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+// DO NOT use this code in production environments.
+
+/// VULNERABLE: Lamports of `total` vested at `now`, from `start` over `duration` seconds
+///
+/// Security Issue: The end of the schedule is `start + duration`, which
+/// wraps past i64::MAX to a time long gone, so a long enough duration has
+/// everything vested at once. The time elapsed is `now - start` cast to a
+/// u64: before a start in the future it is negative, and the cast makes
+/// it nearly 2^64 seconds, so a grant that has not begun is fully vested.
+/// A start far enough in the past wraps the subtraction the same way.
+pub fn vulnerable_vested(total: u64, start: i64, duration: i64, now: i64) -> u64 {
+    // VULNERABILITY: Wraps past i64::MAX, so a long enough schedule ends before it starts
+    let end = start.wrapping_add(duration);
+    if now >= end {
+        return total;
+    }
+
+    // VULNERABILITY: Negative before `start`, and `as u64` makes it enormous
+    let elapsed = now.wrapping_sub(start) as u64;
+    let vested = u128::from(total) * u128::from(elapsed) / u128::from(duration as u64);
+    vested.min(u128::from(total)) as u64
+}
+
+// ========================================
+// SECURE IMPLEMENTATION
+// ========================================
+// This section contains the SECURE version: signed, checked arithmetic,
+// and schedules bounded so it never comes near the ends of i64.
+
+/// SECURE: Whether a grant may vest from `start` over `duration` seconds, created at `now`
+///
+/// The duration is positive and at most MAX_DURATION, and the start is
+/// within MAX_DURATION of `now`. Every timestamp the schedule involves is
+/// then within twenty years of its creation.
+pub fn is_valid(start: i64, duration: i64, now: i64) -> bool {
+    let offset = now.checked_sub(start);
+    (1..=MAX_DURATION).contains(&duration)
+        && offset.is_some_and(|offset| (-MAX_DURATION..=MAX_DURATION).contains(&offset))
+}
+
+/// SECURE: Lamports of `total` vested at `now`, or `None` if `now - start` is not an i64
+///
+/// Security Fix: The elapsed time is a checked i64 subtraction and is
+/// compared as a signed number: nothing has vested until it is positive,
+/// and everything has once it reaches `duration`. The end of the schedule
+/// is never computed, so no addition can overflow. Only a time strictly
+/// inside the schedule is converted, when both it and the duration are
+/// positive.
+pub fn vested(total: u64, start: i64, duration: i64, now: i64) -> Option<u64> {
+    // SECURITY: Checked, and compared before any conversion to unsigned
+    let elapsed = now.checked_sub(start)?;
+    if elapsed <= 0 {
+        return Some(0);
+    }
+    if elapsed >= duration {
+        return Some(total);
+    }
+
+    // 0 < elapsed < duration, so both convert and the share is below `total`
+    let vested = u128::from(total) * u128::try_from(elapsed).ok()? / u128::try_from(duration).ok()?;
+    u64::try_from(vested).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const TOTAL: u64 = 1_000_000_000;
+    const NOW: i64 = 1_700_000_000;
+    const YEAR: i64 = 365 * 24 * 60 * 60;
+    /// Far beyond any time a clock reports, yet a schedule near the present is still an i64 away
+    const ERA: i64 = 1 << 62;
+
+    #[test]
+    fn vulnerable_vested_pays_everything_before_a_future_start() {
+        assert_eq!(vulnerable_vested(TOTAL, NOW + 1, YEAR, NOW), TOTAL);
+        assert_eq!(vulnerable_vested(TOTAL, i64::MAX, YEAR, NOW), TOTAL);
+        assert_eq!(vested(TOTAL, NOW + 1, YEAR, NOW), Some(0));
+        assert_eq!(vested(TOTAL, i64::MAX, YEAR, NOW), Some(0));
+    }
+
+    #[test]
+    fn vulnerable_vested_ends_an_endless_schedule_at_once() {
+        // NOW + i64::MAX wraps to a time before the Unix epoch
+        assert_eq!(vulnerable_vested(TOTAL, NOW, i64::MAX, NOW + 1), TOTAL);
+        assert_eq!(vested(TOTAL, NOW, i64::MAX, NOW + 1), Some(0));
+    }
+
+    #[test]
+    fn a_start_at_the_dawn_of_time_has_no_elapsed_time() {
+        // The end, i64::MIN + YEAR, is long past; the time since i64::MIN is past i64::MAX
+        assert_eq!(vulnerable_vested(TOTAL, i64::MIN, YEAR, NOW), TOTAL);
+        assert_eq!(vested(TOTAL, i64::MIN, YEAR, NOW), None);
+        assert_eq!(vested(TOTAL, i64::MIN, YEAR, -1), Some(TOTAL));
+    }
+
+    #[test]
+    fn vested_handles_the_ends_of_i64() {
+        assert_eq!(vested(TOTAL, 0, i64::MAX, i64::MAX), Some(TOTAL));
+        assert_eq!(vested(TOTAL, 0, i64::MAX, i64::MAX - 1), Some(TOTAL - 1));
+        assert_eq!(vested(TOTAL, i64::MAX, 1, i64::MIN), None);
+        assert_eq!(vested(TOTAL, i64::MIN, i64::MAX, i64::MIN), Some(0));
+        assert_eq!(vested(u64::MAX, -1, 2, 0), Some(u64::MAX / 2));
+        // A duration no valid schedule has still never divides by zero or goes negative
+        assert_eq!(vested(TOTAL, NOW, 0, NOW + 1), Some(TOTAL));
+        assert_eq!(vested(TOTAL, NOW, -YEAR, NOW + 1), Some(TOTAL));
+    }
+
+    #[test]
+    fn vested_agrees_with_vulnerable_vested_inside_sane_schedules() {
+        for start in [NOW - YEAR, NOW, NOW + YEAR] {
+            for now in [start, start + 1, start + YEAR / 2, start + YEAR - 1, start + YEAR, start + 2 * YEAR] {
+                assert_eq!(vested(TOTAL, start, YEAR, now), Some(vulnerable_vested(TOTAL, start, YEAR, now)));
+            }
+        }
+        assert_eq!(vested(TOTAL, NOW, YEAR, NOW + YEAR / 4), Some(TOTAL / 4));
+    }
+
+    #[test]
+    fn schedules_are_bounded_around_now() {
+        assert!(is_valid(NOW, MAX_DURATION, NOW));
+        assert!(is_valid(NOW - MAX_DURATION, 1, NOW));
+        assert!(is_valid(NOW + MAX_DURATION, 1, NOW));
+        for duration in [i64::MIN, -1, 0, MAX_DURATION + 1, i64::MAX] {
+            assert!(!is_valid(NOW, duration, NOW), "{duration}");
+        }
+        for start in [i64::MIN, NOW - MAX_DURATION - 1, NOW + MAX_DURATION + 1, i64::MAX] {
+            assert!(!is_valid(start, YEAR, NOW), "{start}");
+        }
+        assert!(!is_valid(i64::MAX, YEAR, i64::MIN));
+    }
+
+    /// Any schedule `is_valid` accepts at some creation time near the present
+    fn schedules() -> impl Strategy<Value = (i64, i64)> {
+        (-MAX_DURATION..=MAX_DURATION, 1..=MAX_DURATION).prop_map(|(offset, duration)| (NOW + offset, duration))
+    }
+
+    proptest! {
+        #[test]
+        fn vested_never_exceeds_the_total(total: u64, (start, duration) in schedules(), now in -ERA..ERA) {
+            let vested = vested(total, start, duration, now).unwrap();
+            prop_assert!(vested <= total);
+        }
+
+        #[test]
+        fn vested_never_decreases(total: u64, (start, duration) in schedules(), now in -ERA..ERA) {
+            let (before, after) = (vested(total, start, duration, now), vested(total, start, duration, now + 1));
+            prop_assert!(before.unwrap() <= after.unwrap());
+        }
+
+        #[test]
+        fn nothing_vests_before_the_start_and_everything_by_the_end(
+            total: u64,
+            (start, duration) in schedules(),
+            seconds in 0..=MAX_DURATION,
+        ) {
+            prop_assert_eq!(vested(total, start, duration, start - seconds), Some(0));
+            prop_assert_eq!(vested(total, start, duration, start + duration + seconds), Some(total));
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+/// Lamports vesting linearly to a beneficiary, from `start` for `duration` seconds
+#[account]
+pub struct Grant {
+    /// The account that funded the grant (32 bytes)
+    pub funder: Pubkey,
+    /// The account the grant vests to (32 bytes)
+    pub beneficiary: Pubkey,
+    /// Unix timestamp vesting begins at, possibly in the past or the future (8 bytes)
+    pub start: i64,
+    /// Seconds from `start` until everything has vested (8 bytes)
+    pub duration: i64,
+    /// Lamports the grant vests in all (8 bytes)
+    pub total: u64,
+    /// Lamports the beneficiary has claimed so far (8 bytes)
+    pub claimed: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Grant {
+    /// discriminator + funder + beneficiary + start + duration + total + claimed + bump
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1;
+}
@@ -0,0 +1,240 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { VestingGrants } from "../target/types/vesting_grants";
+import { expect } from "chai";
+import { CuProfiler } from "../../harness/cu-profiler";
+import { PocRecorder } from "../../harness/poc-recorder";
+import { Connection, Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+
+// Must match MAX_DURATION in programs/vesting_grants/src/schedule.rs
+const YEAR = 365 * 24 * 60 * 60;
+const MAX_DURATION = 10 * YEAR;
+const DAY = 24 * 60 * 60;
+const I64_MAX = new anchor.BN("9223372036854775807");
+
+// The vulnerable handler casts `now - start` to a u64, wrapping negatives modulo 2^64
+const asU64 = (seconds: bigint) => BigInt.asUintN(64, seconds);
+
+describe("Timestamp Arithmetic Exploit", () => {
+  // Mock connection for testing without local validator
+  const connection = new Connection("https://api.devnet.solana.com", "confirmed");
+
+  // Create a mock wallet if ANCHOR_WALLET is not set
+  let wallet: anchor.Wallet;
+  try {
+    wallet = anchor.Wallet.local();
+  } catch (error) {
+    // Create a mock wallet for CI environments
+    const mockKeypair = Keypair.generate();
+    wallet = new anchor.Wallet(mockKeypair);
+  }
+
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: "confirmed",
+  });
+
+  anchor.setProvider(provider);
+
+  // Records compute units for every instruction executed against a validator
+  const profiler = new CuProfiler("60_timestamp_arithmetic");
+
+  // Records every transaction as a replayable PoC when SECREF_POC_RECORD=1
+  const recorder = new PocRecorder("60_timestamp_arithmetic", provider);
+  beforeEach(function () {
+    // Steps are labelled with the test that sent them
+    recorder.test(this.currentTest?.fullTitle());
+  });
+
+  // Mock program for testing
+  let program: Program<VestingGrants>;
+
+  // Test accounts
+  let beneficiary: Keypair;
+
+  before(async () => {
+    try {
+      // Try to load the program
+      program = anchor.workspace.VestingGrants as Program<VestingGrants>;
+    } catch (error) {
+      console.log("⚠️  Program not available in test environment, using mock tests");
+      program = null;
+    }
+  });
+
+  after(() => {
+    recorder.writeFixture();
+    // Fails the suite if any recorded instruction regressed past its CU budget
+    profiler.writeSummary();
+    profiler.assertBudgets();
+  });
+
+  beforeEach(async () => {
+    beneficiary = Keypair.generate();
+    if (program) {
+      try {
+        const airdrop = await provider.connection.requestAirdrop(beneficiary.publicKey, LAMPORTS_PER_SOL);
+        await provider.connection.confirmTransaction(airdrop, "confirmed");
+      } catch (error) {
+        // Reported by the test that needs a validator
+      }
+    }
+  });
+
+  function grantPda(owner: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("grant"), wallet.publicKey.toBuffer(), owner.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  async function createGrant(
+    secure: boolean,
+    start: number,
+    duration: number | anchor.BN,
+    total: number
+  ): Promise<void> {
+    const method = secure ? program.methods.secureCreateGrant : program.methods.vulnerableCreateGrant;
+    const signature = await method(new anchor.BN(start), new anchor.BN(duration), new anchor.BN(total))
+      .accounts({
+        grant: grantPda(beneficiary.publicKey),
+        beneficiary: beneficiary.publicKey,
+        funder: wallet.publicKey,
+      })
+      .rpc();
+    const name = secure ? "secure_create_grant" : "vulnerable_create_grant";
+    await profiler.record(name, provider.connection, signature, program.programId.toBase58());
+  }
+
+  async function claim(secure: boolean): Promise<void> {
+    const method = secure ? program.methods.secureClaim : program.methods.vulnerableClaim;
+    const signature = await method()
+      .accounts({ grant: grantPda(beneficiary.publicKey), beneficiary: beneficiary.publicKey })
+      .signers([beneficiary])
+      .rpc();
+    const name = secure ? "secure_claim" : "vulnerable_claim";
+    await profiler.record(name, provider.connection, signature, program.programId.toBase58());
+  }
+
+  const now = () => Math.floor(Date.now() / 1000);
+
+  describe("🚨 EXPLOIT DEMONSTRATION - Vulnerable Implementation", () => {
+    it("Should pay a grant out in full before it starts", async () => {
+      console.log("\n=== TIMESTAMP ARITHMETIC EXPLOIT ===");
+
+      if (!program) {
+        console.log("📝 MOCK TEST: Demonstrating a claim before the start");
+        console.log("✅ In a real exploit:");
+        console.log("   1. The DAO funds a one-year grant starting in 30 days");
+        console.log("   2. The beneficiary calls vulnerable_claim today");
+        console.log("   3. `(now - start) as u64` turns -30 days into nearly 2^64 seconds");
+
+        const elapsed = asU64(BigInt(-30 * DAY));
+        console.log(`Elapsed time the program sees: ${elapsed} seconds`);
+        expect(elapsed > BigInt(YEAR)).to.be.true;
+
+        console.log("🚨 VULNERABILITY DEMONSTRATED: A grant that has not begun is fully vested");
+        return;
+      }
+
+      try {
+        await createGrant(true, now() + 30 * DAY, YEAR, LAMPORTS_PER_SOL / 10);
+        const before = await provider.connection.getBalance(beneficiary.publicKey);
+        await claim(false);
+
+        const gained = (await provider.connection.getBalance(beneficiary.publicKey)) - before;
+        console.log(`Beneficiary claimed ${gained} lamports a month before vesting begins`);
+        expect(gained).to.be.greaterThan(LAMPORTS_PER_SOL / 10 - 10_000);
+        console.log("✅ EXPLOIT SUCCESS: The whole grant was paid before its start");
+      } catch (error) {
+        console.log(`⚠️  Test requires local validator: ${error.message}`);
+      }
+    });
+
+    it("Should end an endless grant at once", async () => {
+      console.log("\n=== ENDLESS GRANT EXPLOIT ===");
+
+      if (!program) {
+        console.log("📝 MOCK TEST: Demonstrating a wrapped end of schedule");
+        const end = BigInt.asIntN(64, BigInt(now()) + BigInt(I64_MAX.toString()));
+        console.log(`start + i64::MAX wraps to ${end}, long before 1970`);
+        expect(end < 0n).to.be.true;
+        console.log("🚨 VULNERABILITY DEMONSTRATED: The grant has ended before it began");
+        return;
+      }
+
+      try {
+        await createGrant(false, now() - 1, I64_MAX, LAMPORTS_PER_SOL / 10);
+        await claim(false);
+        console.log("✅ EXPLOIT SUCCESS: A grant meant to vest for ever paid out at once");
+      } catch (error) {
+        console.log(`⚠️  Test requires local validator: ${error.message}`);
+      }
+    });
+  });
+
+  describe("🛡️  PROTECTION VERIFICATION - Secure Implementation", () => {
+    it("Should pay nothing before the start", async () => {
+      console.log("\n=== SIGNED ELAPSED TIME PROTECTION ===");
+
+      if (!program) {
+        console.log("📝 MOCK TEST: Demonstrating signed vesting math");
+        console.log("✅ schedule::vested compares `now - start` with zero before converting it");
+        console.log("   - Nothing vests while the elapsed time is zero or negative");
+        console.log("   - The end of the schedule is never computed, so nothing can wrap");
+        console.log("Expected error: NothingVested");
+        return;
+      }
+
+      try {
+        await createGrant(true, now() + 30 * DAY, YEAR, LAMPORTS_PER_SOL / 10);
+        await claim(true);
+        expect.fail("Expected NothingVested");
+      } catch (error) {
+        expect(error.message).to.include("NothingVested");
+        console.log("✅ PROTECTION SUCCESS: Nothing is paid until the grant starts");
+      }
+    });
+
+    it("Should refuse schedules far from the present", async () => {
+      console.log("\n=== BOUNDED SCHEDULE PROTECTION ===");
+
+      if (!program) {
+        console.log("📝 MOCK TEST: Demonstrating bounded schedules");
+        console.log(`✅ Durations from 1 to ${MAX_DURATION} seconds, starts within ${MAX_DURATION} of now`);
+        console.log("Expected error: InvalidSchedule");
+        return;
+      }
+
+      try {
+        await createGrant(true, now(), MAX_DURATION + 1, LAMPORTS_PER_SOL / 10);
+        expect.fail("Expected InvalidSchedule");
+      } catch (error) {
+        expect(error.message).to.include("InvalidSchedule");
+        console.log("✅ PROTECTION SUCCESS: An endless grant is refused when it is created");
+      }
+    });
+  });
+
+  describe("📚 EDUCATIONAL SUMMARY", () => {
+    it("Should summarize the vulnerability and protection", async () => {
+      console.log("\n=== VULNERABILITY SUMMARY ===");
+      console.log("🚨 VULNERABILITY: Timestamp Arithmetic");
+      console.log("   - `now - start` is negative before a schedule starts");
+      console.log("   - `as u64` makes a negative elapsed time enormous");
+      console.log("   - `start + duration` wraps for durations near i64::MAX");
+
+      console.log("\n🛡️  PROTECTION: Checked, Signed Math on Bounded Schedules");
+      console.log("   - checked_sub on i64, and a signed comparison before any cast");
+      console.log("   - Compare elapsed time with the duration instead of computing an end");
+      console.log("   - Bound durations and starts when the grant is created");
+
+      console.log("\n📖 KEY LEARNING POINTS:");
+      console.log("   1. Timestamps are signed, and negative means \"not yet\"");
+      console.log("   2. Never cast a difference of timestamps before checking its sign");
+      console.log("   3. Never use i64::MAX or i64::MIN as sentinels for \"never\" and \"always\"");
+
+      // This test always passes as it's educational
+      expect(true).to.be.true;
+    });
+  });
+});
@@ -0,0 +1,346 @@
+//! One order resting beside others in a shared vault: a fill after a
+//! cancel, a fill after a fill, and a cancel after either, through the
+//! vulnerable and secure fills; then every constraint on the market and
+//! order contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_spl::token::TokenAccount;
+use secref_guards::GuardError;
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_owner_violation, assert_seeds_violation,
+    assert_signer_violation, execute, Fixture, TestAccount,
+};
+
+use crate::{
+    instruction, CancelOrder, ErrorCode as BookError, FillOrder, InitializeMarket, Market, Order, OrderStatus,
+    PlaceOrder,
+};
+
+/// Base units the order sells
+const QUANTITY: u64 = 100;
+/// Lamports the order asks for them: 1 SOL
+const PRICE: u64 = 1_000_000_000;
+/// Base units other makers' open orders keep in the vault
+const OTHER_ESCROW: u64 = 900;
+/// What the taker holds: 10 SOL
+const TAKER_LAMPORTS: u64 = 10_000_000_000;
+const ID: u64 = 7;
+
+fn market_address(mint: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"market", mint.as_ref()], &crate::ID)
+}
+
+fn order_address(market: Pubkey, maker: Pubkey, id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"order", market.as_ref(), maker.as_ref(), &id.to_le_bytes()], &crate::ID)
+}
+
+fn initialize_fixture() -> Fixture {
+    let mint = Pubkey::new_unique();
+    let (market, _) = market_address(mint);
+    Fixture::new()
+        .with("market", TestAccount::uninitialized(Market::SPACE).at(market))
+        .with("mint", TestAccount::mint(Pubkey::new_unique(), 6, 0).at(mint))
+        .with("vault", TestAccount::token_account(mint, market, 0))
+        .with("authority", TestAccount::signer())
+        .with("system_program", TestAccount::system_program())
+}
+
+/// A market whose vault holds `vault_amount` base units
+fn market(vault_amount: u64) -> (TestAccount, TestAccount) {
+    let mint = Pubkey::new_unique();
+    let (market, bump) = market_address(mint);
+    let vault = TestAccount::token_account(mint, market, vault_amount);
+    let account = TestAccount::anchor(&Market { mint, vault: vault.key, bump }).at(market);
+    (account, vault)
+}
+
+fn order(market: &TestAccount, maker: Pubkey, status: OrderStatus) -> TestAccount {
+    let (address, bump) = order_address(market.key, maker, ID);
+    TestAccount::anchor(&Order {
+        market: market.key,
+        maker,
+        id: ID,
+        quantity: QUANTITY,
+        price: PRICE,
+        status,
+        bump,
+    })
+    .at(address)
+}
+
+fn place_fixture() -> Fixture {
+    let (market, vault) = market(OTHER_ESCROW);
+    let mint = market.state::<Market>().mint;
+    let maker = TestAccount::signer();
+    let (order, _) = order_address(market.key, maker.key, ID);
+    Fixture::new()
+        .args(instruction::PlaceOrder { id: ID, quantity: QUANTITY, price: PRICE })
+        .with("market", market)
+        .with("order", TestAccount::uninitialized(Order::SPACE).at(order))
+        .with("vault", vault)
+        .with("maker_tokens", TestAccount::token_account(mint, maker.key, QUANTITY))
+        .with("maker", maker)
+        .with("token_program", TestAccount::token_program())
+        .with("system_program", TestAccount::system_program())
+}
+
+/// An order in `status`, beside others escrowing OTHER_ESCROW; an open order's own tokens are in the vault too
+fn cancel_fixture(status: OrderStatus) -> Fixture {
+    let escrow = if status == OrderStatus::Open { QUANTITY } else { 0 };
+    let (market, vault) = market(OTHER_ESCROW + escrow);
+    let mint = market.state::<Market>().mint;
+    let maker = TestAccount::signer();
+    let order = order(&market, maker.key, status);
+    Fixture::new()
+        .with("market", market)
+        .with("order", order)
+        .with("vault", vault)
+        .with("maker_tokens", TestAccount::token_account(mint, maker.key, 0))
+        .with("maker", maker)
+        .with("token_program", TestAccount::token_program())
+}
+
+/// A fill of the order in `previous`, taken as it is after whatever ran on it there
+fn fill_after(previous: &Fixture) -> Fixture {
+    let mint = previous.state::<Market>("market").mint;
+    let maker = previous.key("maker");
+    let taker = TestAccount::signer().with_lamports(TAKER_LAMPORTS);
+    Fixture::new()
+        .with("market", previous.account("market").clone())
+        .with("order", previous.account("order").clone())
+        .with("maker", TestAccount::system(0).at(maker))
+        .with("vault", previous.account("vault").clone())
+        .with("taker_tokens", TestAccount::token_account(mint, taker.key, 0))
+        .with("taker", taker)
+        .with("token_program", TestAccount::token_program())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn fill_fixture(status: OrderStatus) -> Fixture {
+    fill_after(&cancel_fixture(status))
+}
+
+/// A cancel of the order in `previous`, by its maker
+fn cancel_after(previous: &Fixture) -> Fixture {
+    let mint = previous.state::<Market>("market").mint;
+    let maker = TestAccount::signer().at(previous.key("maker"));
+    Fixture::new()
+        .with("market", previous.account("market").clone())
+        .with("order", previous.account("order").clone())
+        .with("vault", previous.account("vault").clone())
+        .with("maker_tokens", TestAccount::token_account(mint, maker.key, 0))
+        .with("maker", maker)
+        .with("token_program", TestAccount::token_program())
+}
+
+fn tokens(fixture: &Fixture, name: &str) -> u64 {
+    fixture.state::<TokenAccount>(name).amount
+}
+
+fn status(fixture: &Fixture) -> OrderStatus {
+    fixture.state::<Order>("order").status
+}
+
+fn vulnerable_fill(fixture: &mut Fixture) -> ProgramResult {
+    execute!(fixture, instruction::VulnerableFillOrder {})
+}
+
+fn secure_fill(fixture: &mut Fixture) -> ProgramResult {
+    execute!(fixture, instruction::SecureFillOrder {})
+}
+
+fn cancel(fixture: &mut Fixture) -> ProgramResult {
+    execute!(fixture, instruction::CancelOrder {})
+}
+
+fn invalid_transition() -> ProgramResult {
+    Err(Error::from(GuardError::InvalidStateTransition).into())
+}
+
+#[test]
+fn vulnerable_fill_pays_out_a_cancelled_order_from_other_makers_escrow() {
+    let mut cancelled = cancel_fixture(OrderStatus::Open);
+    cancel(&mut cancelled).unwrap();
+    assert_eq!(tokens(&cancelled, "maker_tokens"), QUANTITY);
+
+    // The maker fills its own cancelled order from a second wallet, paying the price to itself
+    let mut fixture = fill_after(&cancelled);
+    vulnerable_fill(&mut fixture).unwrap();
+    assert_eq!(tokens(&fixture, "taker_tokens"), QUANTITY);
+    assert_eq!(tokens(&fixture, "vault"), OTHER_ESCROW - QUANTITY);
+    assert_eq!(fixture.account("maker").lamports, PRICE);
+    assert_eq!(status(&fixture), OrderStatus::Filled);
+}
+
+#[test]
+fn vulnerable_fill_fills_the_same_order_until_the_vault_is_empty() {
+    let mut fixture = fill_fixture(OrderStatus::Open);
+    for _ in 0..(OTHER_ESCROW + QUANTITY) / QUANTITY {
+        vulnerable_fill(&mut fixture).unwrap();
+    }
+    assert_eq!(tokens(&fixture, "vault"), 0);
+    assert_eq!(tokens(&fixture, "taker_tokens"), OTHER_ESCROW + QUANTITY);
+    assert!(vulnerable_fill(&mut fixture).is_err());
+}
+
+#[test]
+fn secure_fill_settles_an_open_order_once() {
+    let mut fixture = fill_fixture(OrderStatus::Open);
+    secure_fill(&mut fixture).unwrap();
+    assert_eq!(tokens(&fixture, "taker_tokens"), QUANTITY);
+    assert_eq!(tokens(&fixture, "vault"), OTHER_ESCROW);
+    assert_eq!(fixture.account("maker").lamports, PRICE);
+    assert_eq!(fixture.account("taker").lamports, TAKER_LAMPORTS - PRICE);
+    assert_eq!(status(&fixture), OrderStatus::Filled);
+
+    assert_eq!(secure_fill(&mut fixture), invalid_transition());
+    assert_eq!(tokens(&fixture, "vault"), OTHER_ESCROW);
+}
+
+#[test]
+fn secure_fill_loses_the_race_to_a_cancel() {
+    let mut cancelled = cancel_fixture(OrderStatus::Open);
+    cancel(&mut cancelled).unwrap();
+
+    let mut fixture = fill_after(&cancelled);
+    assert_eq!(secure_fill(&mut fixture), invalid_transition());
+    assert_eq!(tokens(&fixture, "vault"), OTHER_ESCROW);
+    assert_eq!(fixture.account("taker").lamports, TAKER_LAMPORTS);
+    assert_eq!(status(&fixture), OrderStatus::Cancelled);
+}
+
+#[test]
+fn cancel_loses_the_race_to_a_fill() {
+    let mut filled = fill_fixture(OrderStatus::Open);
+    secure_fill(&mut filled).unwrap();
+
+    let mut fixture = cancel_after(&filled);
+    assert_eq!(cancel(&mut fixture), invalid_transition());
+    assert_eq!(tokens(&fixture, "maker_tokens"), 0);
+    assert_eq!(tokens(&fixture, "vault"), OTHER_ESCROW);
+}
+
+#[test]
+fn cancel_refunds_an_open_order_once() {
+    let mut fixture = cancel_fixture(OrderStatus::Open);
+    cancel(&mut fixture).unwrap();
+    assert_eq!(tokens(&fixture, "maker_tokens"), QUANTITY);
+    assert_eq!(tokens(&fixture, "vault"), OTHER_ESCROW);
+    assert_eq!(status(&fixture), OrderStatus::Cancelled);
+
+    assert_eq!(cancel(&mut fixture), invalid_transition());
+    assert_eq!(tokens(&fixture, "maker_tokens"), QUANTITY);
+}
+
+#[test]
+fn place_order_escrows_an_open_order() {
+    let mut fixture = place_fixture();
+    execute!(&mut fixture, instruction::PlaceOrder { id: ID, quantity: QUANTITY, price: PRICE }).unwrap();
+    assert_eq!(tokens(&fixture, "vault"), OTHER_ESCROW + QUANTITY);
+    assert_eq!(tokens(&fixture, "maker_tokens"), 0);
+    assert_eq!(status(&fixture), OrderStatus::Open);
+}
+
+#[test]
+fn place_order_rejects_empty_and_free_orders() {
+    let mut fixture = place_fixture();
+    assert_eq!(
+        execute!(&mut fixture, instruction::PlaceOrder { id: ID, quantity: 0, price: PRICE }),
+        Err(Error::from(BookError::ZeroQuantity).into())
+    );
+    let mut fixture = place_fixture();
+    assert_eq!(
+        execute!(&mut fixture, instruction::PlaceOrder { id: ID, quantity: QUANTITY, price: 0 }),
+        Err(Error::from(BookError::InvalidPrice).into())
+    );
+}
+
+#[test]
+fn initialize_market_escrows_in_a_vault_the_market_owns() {
+    assert_seeds_violation!(InitializeMarket, initialize_fixture(), "market");
+    let fixture = initialize_fixture();
+    let market = fixture.key("market");
+    assert_constraint_violation!(
+        InitializeMarket,
+        fixture,
+        "vault" => TestAccount::token_account(Pubkey::new_unique(), market, 0),
+        ErrorCode::ConstraintTokenMint
+    );
+    let fixture = initialize_fixture();
+    let mint = fixture.key("mint");
+    assert_constraint_violation!(
+        InitializeMarket,
+        fixture,
+        "vault" => TestAccount::token_account(mint, Pubkey::new_unique(), 0),
+        ErrorCode::ConstraintTokenOwner
+    );
+    assert_signer_violation!(InitializeMarket, initialize_fixture(), "authority");
+}
+
+#[test]
+fn place_order_escrows_the_makers_tokens_in_the_market_vault() {
+    assert_seeds_violation!(PlaceOrder, place_fixture(), "market");
+    assert_has_one_violation!(PlaceOrder, place_fixture(), "vault");
+    assert_seeds_violation!(PlaceOrder, place_fixture(), "order");
+    let fixture = place_fixture();
+    let maker = fixture.key("maker");
+    assert_constraint_violation!(
+        PlaceOrder,
+        fixture,
+        "maker_tokens" => TestAccount::token_account(Pubkey::new_unique(), maker, QUANTITY),
+        ErrorCode::ConstraintTokenMint
+    );
+    assert_signer_violation!(PlaceOrder, place_fixture(), "maker");
+}
+
+#[test]
+fn cancel_order_refunds_only_the_maker() {
+    assert_seeds_violation!(CancelOrder, cancel_fixture(OrderStatus::Open), "order");
+    assert_owner_violation!(CancelOrder, cancel_fixture(OrderStatus::Open), "order");
+    assert_has_one_violation!(CancelOrder, cancel_fixture(OrderStatus::Open), "vault");
+    assert_constraint_violation!(
+        CancelOrder,
+        cancel_fixture(OrderStatus::Open),
+        "order" => |order| order.edit(|stored: &mut Order| stored.maker = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    let fixture = cancel_fixture(OrderStatus::Open);
+    let mint = fixture.state::<Market>("market").mint;
+    assert_constraint_violation!(
+        CancelOrder,
+        fixture,
+        "maker_tokens" => TestAccount::token_account(mint, Pubkey::new_unique(), 0),
+        ErrorCode::ConstraintTokenOwner
+    );
+    assert_signer_violation!(CancelOrder, cancel_fixture(OrderStatus::Open), "maker");
+}
+
+#[test]
+fn fill_order_pays_the_orders_maker_from_its_market() {
+    assert_seeds_violation!(FillOrder, fill_fixture(OrderStatus::Open), "market");
+    assert_seeds_violation!(FillOrder, fill_fixture(OrderStatus::Open), "order");
+    assert_has_one_violation!(FillOrder, fill_fixture(OrderStatus::Open), "vault");
+    // The market and maker are seeds of the order too, so swap them in the stored order instead
+    assert_constraint_violation!(
+        FillOrder,
+        fill_fixture(OrderStatus::Open),
+        "order" => |order| order.edit(|stored: &mut Order| stored.market = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_constraint_violation!(
+        FillOrder,
+        fill_fixture(OrderStatus::Open),
+        "order" => |order| order.edit(|stored: &mut Order| stored.maker = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    let fixture = fill_fixture(OrderStatus::Open);
+    let taker = fixture.key("taker");
+    assert_constraint_violation!(
+        FillOrder,
+        fixture,
+        "taker_tokens" => TestAccount::token_account(Pubkey::new_unique(), taker, 0),
+        ErrorCode::ConstraintTokenMint
+    );
+    assert_signer_violation!(FillOrder, fill_fixture(OrderStatus::Open), "taker");
+}
@@ -0,0 +1,11 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Order must sell at least one base unit")]
+    ZeroQuantity,
+    #[msg("Price must be above zero")]
+    InvalidPrice,
+}
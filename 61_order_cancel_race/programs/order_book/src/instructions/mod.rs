@@ -0,0 +1,109 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct InitializeMarket<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Market::SPACE,
+        seeds = [b"market", mint.key().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(token::mint = mint, token::authority = market)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct PlaceOrder<'info> {
+    #[account(has_one = vault, seeds = [b"market", market.mint.as_ref()], bump = market.bump)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = maker,
+        space = Order::SPACE,
+        seeds = [b"order", market.key().as_ref(), maker.key().as_ref(), &id.to_le_bytes()],
+        bump
+    )]
+    pub order: Account<'info, Order>,
+
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = market.mint, token::authority = maker)]
+    pub maker_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    #[account(has_one = vault, seeds = [b"market", market.mint.as_ref()], bump = market.bump)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        has_one = market,
+        has_one = maker,
+        seeds = [b"order", market.key().as_ref(), maker.key().as_ref(), &order.id.to_le_bytes()],
+        bump = order.bump
+    )]
+    pub order: Account<'info, Order>,
+
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = market.mint, token::authority = maker)]
+    pub maker_tokens: Account<'info, TokenAccount>,
+
+    pub maker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Both fills take the same accounts; they differ only in whether they check the order's status
+#[derive(Accounts)]
+pub struct FillOrder<'info> {
+    #[account(has_one = vault, seeds = [b"market", market.mint.as_ref()], bump = market.bump)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        has_one = market,
+        has_one = maker,
+        seeds = [b"order", market.key().as_ref(), maker.key().as_ref(), &order.id.to_le_bytes()],
+        bump = order.bump
+    )]
+    pub order: Account<'info, Order>,
+
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = market.mint, token::authority = taker)]
+    pub taker_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub taker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
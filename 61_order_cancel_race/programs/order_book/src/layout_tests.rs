@@ -0,0 +1,30 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Market, Order, OrderStatus};
+
+#[test]
+fn market_layout_is_stable() {
+    let market = Market {
+        mint: Pubkey::new_from_array([1; 32]),
+        vault: Pubkey::new_from_array([2; 32]),
+        bump: 65,
+    };
+    assert_golden!("Market", golden::anchor(&market));
+}
+
+#[test]
+fn order_layout_is_stable() {
+    let order = Order {
+        market: Pubkey::new_from_array([1; 32]),
+        maker: Pubkey::new_from_array([2; 32]),
+        id: 0x1112131415161718,
+        quantity: 0x2122232425262728,
+        price: 0x3132333435363738,
+        status: OrderStatus::Cancelled,
+        bump: 65,
+    };
+    assert_golden!("Order", golden::anchor(&order));
+}
@@ -0,0 +1,195 @@
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use secref_guards::state_machine::StateMachine;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// An order book selling one token for lamports.
+///
+/// Makers place orders by escrowing tokens in the market's vault, one
+/// token account shared by every open order. A taker fills an order
+/// whole, paying its price to the maker and receiving its tokens from the
+/// vault; the maker may cancel it instead and take the tokens back. The
+/// order account stays, with its status, as the record clients and
+/// indexers read. Fills and cancels race: both are sent against the same
+/// resting order, and the runtime runs whichever lands first.
+#[feature_gated]
+#[program]
+pub mod order_book {
+    use super::*;
+
+    /// Open a market for a mint, escrowing from a vault the market PDA owns
+    pub fn initialize_market(ctx: Context<InitializeMarket>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        market.mint = ctx.accounts.mint.key();
+        market.vault = ctx.accounts.vault.key();
+        market.bump = ctx.bumps.market;
+
+        msg!("Market for {} opened", market.mint);
+        Ok(())
+    }
+
+    /// Escrow `quantity` base units in the vault, for sale at `price` lamports
+    pub fn place_order(ctx: Context<PlaceOrder>, id: u64, quantity: u64, price: u64) -> Result<()> {
+        require!(quantity > 0, ErrorCode::ZeroQuantity);
+        require!(price > 0, ErrorCode::InvalidPrice);
+
+        let order = &mut ctx.accounts.order;
+        order.market = ctx.accounts.market.key();
+        order.maker = ctx.accounts.maker.key();
+        order.id = id;
+        order.quantity = quantity;
+        order.price = price;
+        order.status = OrderStatus::Open;
+        order.bump = ctx.bumps.order;
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.maker_tokens.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.maker.to_account_info(),
+                },
+            ),
+            quantity,
+        )?;
+
+        msg!("Order {} escrowed {} base units for {} lamports", id, quantity, price);
+        Ok(())
+    }
+
+    /// Cancel an open order, refunding its tokens from the vault to the maker
+    ///
+    /// The order moves to `Cancelled` through its state machine before the
+    /// refund, so an order that was filled, or already cancelled, stays
+    /// as it is and refunds nothing.
+    pub fn cancel_order(ctx: Context<CancelOrder>) -> Result<()> {
+        ctx.accounts.order.status.transition(OrderStatus::Cancelled)?;
+
+        let order = &ctx.accounts.order;
+        release(
+            &ctx.accounts.market,
+            &ctx.accounts.vault,
+            &ctx.accounts.maker_tokens,
+            &ctx.accounts.token_program,
+            order.quantity,
+        )?;
+
+        msg!("Order {} cancelled; {} base units refunded", order.id, order.quantity);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Fill an order without checking its status
+    ///
+    /// Security Issue: The fill pays the maker, releases the order's
+    /// quantity from the vault and marks it filled, whatever it was
+    /// before. A cancelled order's tokens are already back with its maker,
+    /// and a filled order's with its taker, so filling either again pays
+    /// out tokens escrowed by other makers' orders. A maker cancels, then
+    /// fills the order from a second wallet - paying the price to itself -
+    /// and takes the same quantity from the vault once more, as often as
+    /// the vault holds it. An honest taker whose fill races a cancel gets
+    /// the tokens too, and the last maker to cancel finds the vault empty.
+    #[vulnerable]
+    pub fn vulnerable_fill_order(ctx: Context<FillOrder>) -> Result<()> {
+        // VULNERABILITY: No status check; cancelled and filled orders fill again
+        settle(ctx.accounts)?;
+
+        // VULNERABILITY: The status is written, never read
+        ctx.accounts.order.status = OrderStatus::Filled;
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that moves the order
+    // through its state machine before settling it.
+
+    /// SECURE: Fill an order only while it is open
+    ///
+    /// Security Fix: The order moves from `Open` to `Filled` through
+    /// `StateMachine::transition` before anything is paid, and that is the
+    /// only transition into `Filled`. A fill after a cancel, or a second
+    /// fill, fails with `InvalidStateTransition`; of a fill and a cancel
+    /// racing for the same order, whichever the runtime runs second fails,
+    /// and the vault pays each order's tokens out once.
+    #[secure]
+    pub fn secure_fill_order(ctx: Context<FillOrder>) -> Result<()> {
+        // SECURITY: Open -> Filled, or nothing happens
+        ctx.accounts.order.status.transition(OrderStatus::Filled)?;
+
+        settle(ctx.accounts)
+    }
+}
+
+/// Pay the order's price to its maker and release its tokens to the taker
+fn settle(accounts: &FillOrder) -> Result<()> {
+    let order = &accounts.order;
+    system_program::transfer(
+        CpiContext::new(
+            accounts.system_program.to_account_info(),
+            Transfer {
+                from: accounts.taker.to_account_info(),
+                to: accounts.maker.to_account_info(),
+            },
+        ),
+        order.price,
+    )?;
+    release(
+        &accounts.market,
+        &accounts.vault,
+        &accounts.taker_tokens,
+        &accounts.token_program,
+        order.quantity,
+    )?;
+
+    msg!("Order {} filled: {} base units for {} lamports", order.id, order.quantity, order.price);
+    Ok(())
+}
+
+/// Move `amount` base units out of the market vault, signed by the market PDA
+fn release<'info>(
+    market: &Account<'info, Market>,
+    vault: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[b"market", market.mint.as_ref(), &[market.bump]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token::Transfer {
+                from: vault.to_account_info(),
+                to: to.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )
+}
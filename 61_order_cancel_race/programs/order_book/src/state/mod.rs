@@ -0,0 +1,50 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Market {
+    /// The token the market's orders sell (32 bytes)
+    pub mint: Pubkey,
+    /// Token account holding every open order's tokens, owned by the market PDA (32 bytes)
+    pub vault: Pubkey,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Market {
+    /// discriminator + mint + vault + bump
+    pub const SPACE: usize = 8 + 32 + 32 + 1;
+}
+
+#[account]
+pub struct Order {
+    /// Market whose vault escrows the order's tokens (32 bytes)
+    pub market: Pubkey,
+    /// Seller, paid on a fill and refunded on a cancel (32 bytes)
+    pub maker: Pubkey,
+    /// Maker-chosen order id (8 bytes)
+    pub id: u64,
+    /// Base units for sale, escrowed in the market vault while the order is open (8 bytes)
+    pub quantity: u64,
+    /// Lamports the taker pays for the whole order (8 bytes)
+    pub price: u64,
+    /// Where the order is in its lifecycle (1 byte)
+    pub status: OrderStatus,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Order {
+    /// discriminator + market + maker + id + quantity + price + status + bump
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 1 + 1;
+}
+
+/// An order rests until it is filled or cancelled; both are final
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, StateMachine)]
+#[transition(Open -> Filled, Open -> Cancelled)]
+pub enum OrderStatus {
+    Open,
+    Filled,
+    Cancelled,
+}
@@ -0,0 +1,346 @@
+//! One 10 SOL deposit and two pools lending at 80%: what each borrow
+//! pays out through the vulnerable and secure handlers, the lock that
+//! pledges the receipt to one loan at a time, then every constraint on
+//! the pool, loan and collateral contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_seeds_violation, assert_signer_violation, execute,
+    Fixture, TestAccount,
+};
+
+use crate::{
+    instruction, Borrow, Collateral, ErrorCode as HubError, InitializePool, Loan, OpenLoan, Pool, Receipt, Repay,
+};
+
+/// The receipt's deposit: 10 SOL
+const DEPOSIT: u64 = 10_000_000_000;
+/// Both pools lend up to 80% of collateral
+const LTV_BPS: u16 = 8_000;
+/// What 80% of the deposit is
+const LIMIT: u64 = 8_000_000_000;
+/// Lamports each pool has to lend: 100 SOL
+const LIQUIDITY: u64 = 100_000_000_000;
+/// What the owner holds besides the deposit: 1 SOL
+const OWNER_LAMPORTS: u64 = 1_000_000_000;
+
+fn pool_address(id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"pool", &[id]], &crate::ID)
+}
+
+fn receipt_address(owner: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"receipt", owner.as_ref()], &crate::ID)
+}
+
+fn loan_address(pool: Pubkey, receipt: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"loan", pool.as_ref(), receipt.as_ref()], &crate::ID)
+}
+
+fn pool(id: u8, liquidity: u64) -> TestAccount {
+    let (address, bump) = pool_address(id);
+    TestAccount::anchor(&Pool { id, ltv_bps: LTV_BPS, bump })
+        .at(address)
+        .with_lamports(Rent::default().minimum_balance(Pool::SPACE) + liquidity)
+}
+
+/// The owner's receipt for DEPOSIT, pledged to `locked_by`
+fn receipt(owner: Pubkey, locked_by: Option<Pubkey>) -> TestAccount {
+    let (address, bump) = receipt_address(owner);
+    // Serialized pledged, so the account has the room `lock_collateral` writes into
+    let mut receipt = TestAccount::anchor(&Receipt { owner, amount: DEPOSIT, locked_by: Some(Pubkey::default()), bump })
+        .at(address)
+        .with_lamports(Rent::default().minimum_balance(Receipt::SPACE) + DEPOSIT);
+    receipt.edit(|stored: &mut Receipt| stored.locked_by = locked_by);
+    receipt
+}
+
+fn loan(pool: &TestAccount, receipt: &TestAccount, borrowed: u64) -> TestAccount {
+    let (address, bump) = loan_address(pool.key, receipt.key);
+    let owner = receipt.state::<Receipt>().owner;
+    TestAccount::anchor(&Loan { pool: pool.key, owner, receipt: receipt.key, borrowed, bump }).at(address)
+}
+
+/// A borrow from pool `id` by `owner`, against `receipt`, on a loan that owes nothing yet
+fn borrow_fixture_for(id: u8, liquidity: u64, owner: TestAccount, receipt: TestAccount) -> Fixture {
+    let pool = pool(id, liquidity);
+    let loan = loan(&pool, &receipt, 0);
+    Fixture::new()
+        .with("pool", pool)
+        .with("receipt", receipt)
+        .with("loan", loan)
+        .with("owner", owner)
+}
+
+/// Pool 0's loan, against an unpledged receipt
+fn borrow_fixture() -> Fixture {
+    let owner = TestAccount::signer().with_lamports(OWNER_LAMPORTS);
+    let receipt = receipt(owner.key, None);
+    borrow_fixture_for(0, LIQUIDITY, owner, receipt)
+}
+
+/// Pool 0's loan, against a receipt pledged to it
+fn locked_borrow_fixture() -> Fixture {
+    let mut fixture = borrow_fixture();
+    let loan = fixture.key("loan");
+    fixture
+        .account_mut("receipt")
+        .edit(|receipt: &mut Receipt| receipt.locked_by = Some(loan));
+    fixture
+}
+
+/// The receipt and owner of `fixture`, with pool 0's loan and one from pool 1 owing `borrowed`
+fn collateral_fixture(previous: &Fixture, id: u8, borrowed: u64) -> Fixture {
+    let receipt = previous.account("receipt").clone();
+    let loan = loan(&pool(id, LIQUIDITY), &receipt, borrowed);
+    Fixture::new()
+        .with("receipt", receipt)
+        .with("loan", loan)
+        .with("owner", previous.account("owner").clone())
+}
+
+fn lock_fixture() -> Fixture {
+    collateral_fixture(&borrow_fixture(), 0, 0)
+}
+
+/// A loan owing `borrowed`, whose owner still holds what it borrowed
+fn repay_fixture(borrowed: u64) -> Fixture {
+    let owner = TestAccount::signer().with_lamports(OWNER_LAMPORTS + borrowed);
+    let receipt = receipt(owner.key, None);
+    let pool = pool(0, LIQUIDITY);
+    let loan = loan(&pool, &receipt, borrowed);
+    Fixture::new()
+        .with("pool", pool)
+        .with("loan", loan)
+        .with("owner", owner)
+        .with("system_program", TestAccount::system_program())
+}
+
+fn open_loan_fixture() -> Fixture {
+    let owner = TestAccount::signer();
+    let receipt = receipt(owner.key, None);
+    let pool = pool(0, LIQUIDITY);
+    let (loan, _) = loan_address(pool.key, receipt.key);
+    Fixture::new()
+        .with("pool", pool)
+        .with("receipt", receipt)
+        .with("loan", TestAccount::uninitialized(Loan::SPACE).at(loan))
+        .with("owner", owner)
+        .with("system_program", TestAccount::system_program())
+}
+
+fn initialize_fixture(id: u8) -> Fixture {
+    let (pool, _) = pool_address(id);
+    Fixture::new()
+        .with("pool", TestAccount::uninitialized(Pool::SPACE).at(pool))
+        .with("admin", TestAccount::signer().with_lamports(2 * LIQUIDITY))
+        .with("system_program", TestAccount::system_program())
+}
+
+fn vulnerable_borrow(fixture: &mut Fixture, amount: u64) -> ProgramResult {
+    execute!(fixture, instruction::VulnerableBorrow { amount })
+}
+
+fn secure_borrow(fixture: &mut Fixture, amount: u64) -> ProgramResult {
+    execute!(fixture, instruction::SecureBorrow { amount })
+}
+
+fn fails_with(error: HubError) -> ProgramResult {
+    Err(Error::from(error).into())
+}
+
+#[test]
+fn vulnerable_borrow_counts_one_deposit_in_every_pool() {
+    let mut first = borrow_fixture();
+    vulnerable_borrow(&mut first, LIMIT).unwrap();
+
+    let owner = first.account("owner").clone();
+    let mut second = borrow_fixture_for(1, LIQUIDITY, owner, first.account("receipt").clone());
+    vulnerable_borrow(&mut second, LIMIT).unwrap();
+
+    // 16 SOL borrowed against 10 SOL of collateral
+    assert_eq!(second.account("owner").lamports, OWNER_LAMPORTS + 2 * LIMIT);
+    assert!(first.state::<Loan>("loan").borrowed + second.state::<Loan>("loan").borrowed > DEPOSIT);
+    assert_eq!(second.state::<Receipt>("receipt").locked_by, None);
+}
+
+#[test]
+fn vulnerable_borrow_still_caps_each_loan() {
+    let mut fixture = borrow_fixture();
+    assert_eq!(vulnerable_borrow(&mut fixture, LIMIT + 1), fails_with(HubError::ExceedsBorrowLimit));
+}
+
+#[test]
+fn secure_borrow_lends_against_a_receipt_locked_to_the_loan() {
+    let mut fixture = locked_borrow_fixture();
+    secure_borrow(&mut fixture, LIMIT / 2).unwrap();
+    secure_borrow(&mut fixture, LIMIT / 2).unwrap();
+    assert_eq!(fixture.account("owner").lamports, OWNER_LAMPORTS + LIMIT);
+    assert_eq!(fixture.state::<Loan>("loan").borrowed, LIMIT);
+    assert_eq!(secure_borrow(&mut fixture, 1), fails_with(HubError::ExceedsBorrowLimit));
+}
+
+#[test]
+fn secure_borrow_refuses_collateral_pledged_elsewhere_or_not_at_all() {
+    let mut fixture = borrow_fixture();
+    assert_eq!(secure_borrow(&mut fixture, LIMIT), fails_with(HubError::CollateralNotLocked));
+
+    // Locked to pool 0's loan, the receipt backs nothing in pool 1
+    let mut first = locked_borrow_fixture();
+    secure_borrow(&mut first, LIMIT).unwrap();
+    let owner = first.account("owner").clone();
+    let mut second = borrow_fixture_for(1, LIQUIDITY, owner, first.account("receipt").clone());
+    assert_eq!(secure_borrow(&mut second, LIMIT), fails_with(HubError::CollateralNotLocked));
+    assert_eq!(second.account("owner").lamports, OWNER_LAMPORTS + LIMIT);
+}
+
+#[test]
+fn borrowing_leaves_the_pool_rent_exempt() {
+    let owner = TestAccount::signer().with_lamports(OWNER_LAMPORTS);
+    let receipt = receipt(owner.key, None);
+    let mut fixture = borrow_fixture_for(0, LIMIT - 1, owner, receipt);
+    assert_eq!(vulnerable_borrow(&mut fixture, LIMIT), fails_with(HubError::InsufficientLiquidity));
+}
+
+#[test]
+fn lock_collateral_pledges_a_receipt_to_one_loan_at_a_time() {
+    let mut fixture = lock_fixture();
+    let loan = fixture.key("loan");
+    execute!(&mut fixture, instruction::LockCollateral {}).unwrap();
+    assert_eq!(fixture.state::<Receipt>("receipt").locked_by, Some(loan));
+
+    let mut other = collateral_fixture(&fixture, 1, 0);
+    assert_eq!(
+        execute!(&mut other, instruction::LockCollateral {}),
+        fails_with(HubError::CollateralLocked)
+    );
+    assert_eq!(
+        execute!(&mut other, instruction::UnlockCollateral {}),
+        fails_with(HubError::CollateralNotLocked)
+    );
+}
+
+#[test]
+fn unlock_collateral_waits_for_the_loan_to_be_repaid() {
+    let mut fixture = locked_borrow_fixture();
+    secure_borrow(&mut fixture, LIMIT).unwrap();
+    let mut unlock = collateral_fixture(&fixture, 0, LIMIT);
+    assert_eq!(
+        execute!(&mut unlock, instruction::UnlockCollateral {}),
+        fails_with(HubError::OutstandingDebt)
+    );
+
+    let mut unlock = collateral_fixture(&fixture, 0, 0);
+    execute!(&mut unlock, instruction::UnlockCollateral {}).unwrap();
+    assert_eq!(unlock.state::<Receipt>("receipt").locked_by, None);
+
+    // Free again, the receipt may back pool 1's loan instead
+    let mut other = collateral_fixture(&unlock, 1, 0);
+    execute!(&mut other, instruction::LockCollateral {}).unwrap();
+}
+
+#[test]
+fn repay_returns_lamports_to_the_pool() {
+    let mut fixture = repay_fixture(LIMIT);
+    let pool_before = fixture.account("pool").lamports;
+    execute!(&mut fixture, instruction::Repay { amount: LIMIT / 4 }).unwrap();
+    assert_eq!(fixture.state::<Loan>("loan").borrowed, LIMIT * 3 / 4);
+    assert_eq!(fixture.account("pool").lamports, pool_before + LIMIT / 4);
+    assert_eq!(fixture.account("owner").lamports, OWNER_LAMPORTS + LIMIT * 3 / 4);
+
+    assert_eq!(
+        execute!(&mut fixture, instruction::Repay { amount: LIMIT }),
+        fails_with(HubError::ArithmeticOverflow)
+    );
+}
+
+#[test]
+fn initialize_pool_bounds_the_ltv() {
+    for ltv_bps in [0, 10_001] {
+        let mut fixture = initialize_fixture(0);
+        assert_eq!(
+            execute!(&mut fixture, instruction::InitializePool { id: 0, ltv_bps, liquidity: 0 }),
+            fails_with(HubError::InvalidLtv)
+        );
+    }
+    let mut fixture = initialize_fixture(0);
+    execute!(&mut fixture, instruction::InitializePool { id: 0, ltv_bps: 10_000, liquidity: LIQUIDITY }).unwrap();
+    assert_eq!(fixture.state::<Pool>("pool").ltv_bps, 10_000);
+}
+
+#[test]
+fn initialize_pool_derives_the_pool_from_its_id() {
+    let fixture = initialize_fixture(0).args(instruction::InitializePool { id: 0, ltv_bps: LTV_BPS, liquidity: 0 });
+    assert_seeds_violation!(InitializePool, fixture, "pool");
+}
+
+#[test]
+fn open_loan_pairs_one_pool_with_the_owners_receipt() {
+    assert_seeds_violation!(OpenLoan, open_loan_fixture(), "pool");
+    assert_seeds_violation!(OpenLoan, open_loan_fixture(), "loan");
+    assert_constraint_violation!(
+        OpenLoan,
+        open_loan_fixture(),
+        "receipt" => |receipt| receipt.edit(|stored: &mut Receipt| stored.owner = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_signer_violation!(OpenLoan, open_loan_fixture(), "owner");
+}
+
+#[test]
+fn collateral_moves_only_between_the_owners_receipt_and_loans() {
+    assert_seeds_violation!(Collateral, lock_fixture(), "receipt");
+    assert_seeds_violation!(Collateral, lock_fixture(), "loan");
+    assert_constraint_violation!(
+        Collateral,
+        lock_fixture(),
+        "loan" => |loan| loan.edit(|stored: &mut Loan| stored.owner = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_constraint_violation!(
+        Collateral,
+        lock_fixture(),
+        "loan" => |loan| loan.edit(|stored: &mut Loan| stored.receipt = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_signer_violation!(Collateral, lock_fixture(), "owner");
+}
+
+#[test]
+fn borrow_lends_from_the_loans_own_pool() {
+    assert_seeds_violation!(Borrow, borrow_fixture(), "pool");
+    assert_seeds_violation!(Borrow, borrow_fixture(), "loan");
+    // The pool and receipt are seeds of the loan too, so swap them in the stored loan instead
+    assert_constraint_violation!(
+        Borrow,
+        borrow_fixture(),
+        "loan" => |loan| loan.edit(|stored: &mut Loan| stored.pool = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_constraint_violation!(
+        Borrow,
+        borrow_fixture(),
+        "loan" => |loan| loan.edit(|stored: &mut Loan| stored.receipt = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_constraint_violation!(
+        Borrow,
+        borrow_fixture(),
+        "receipt" => |receipt| receipt.edit(|stored: &mut Receipt| stored.owner = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_signer_violation!(Borrow, borrow_fixture(), "owner");
+}
+
+#[test]
+fn repay_pays_the_loans_own_pool() {
+    assert_seeds_violation!(Repay, repay_fixture(LIMIT), "loan");
+    assert_has_one_violation!(Repay, repay_fixture(LIMIT), "owner");
+    assert_constraint_violation!(
+        Repay,
+        repay_fixture(LIMIT),
+        "loan" => |loan| loan.edit(|stored: &mut Loan| stored.pool = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_signer_violation!(Repay, repay_fixture(LIMIT), "owner");
+}
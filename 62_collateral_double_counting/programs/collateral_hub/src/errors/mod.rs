@@ -0,0 +1,23 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Loan-to-value must be between 1 and 10,000 basis points")]
+    InvalidLtv,
+    #[msg("Amount must be above zero")]
+    ZeroAmount,
+    #[msg("Borrowing this much would exceed the loan's collateral limit")]
+    ExceedsBorrowLimit,
+    #[msg("Receipt is already locked as collateral for a loan")]
+    CollateralLocked,
+    #[msg("Receipt is not locked as collateral for this loan")]
+    CollateralNotLocked,
+    #[msg("Loan must be repaid before its collateral is unlocked")]
+    OutstandingDebt,
+    #[msg("Pool does not have enough lamports to lend")]
+    InsufficientLiquidity,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
@@ -0,0 +1,108 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+#[instruction(id: u8)]
+pub struct InitializePool<'info> {
+    #[account(init, payer = admin, space = Pool::SPACE, seeds = [b"pool".as_ref(), &[id]], bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(init, payer = owner, space = Receipt::SPACE, seeds = [b"receipt", owner.key().as_ref()], bump)]
+    pub receipt: Account<'info, Receipt>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenLoan<'info> {
+    #[account(seeds = [b"pool".as_ref(), &[pool.id]], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(has_one = owner, seeds = [b"receipt", owner.key().as_ref()], bump = receipt.bump)]
+    pub receipt: Account<'info, Receipt>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = Loan::SPACE,
+        seeds = [b"loan", pool.key().as_ref(), receipt.key().as_ref()],
+        bump
+    )]
+    pub loan: Account<'info, Loan>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Locking and unlocking take the same accounts: a receipt and one of its owner's loans
+#[derive(Accounts)]
+pub struct Collateral<'info> {
+    #[account(mut, has_one = owner, seeds = [b"receipt", owner.key().as_ref()], bump = receipt.bump)]
+    pub receipt: Account<'info, Receipt>,
+
+    #[account(
+        has_one = owner,
+        has_one = receipt,
+        seeds = [b"loan", loan.pool.as_ref(), receipt.key().as_ref()],
+        bump = loan.bump
+    )]
+    pub loan: Account<'info, Loan>,
+
+    pub owner: Signer<'info>,
+}
+
+/// Both borrows take the same accounts; they differ only in whether the receipt must be locked to the loan
+#[derive(Accounts)]
+pub struct Borrow<'info> {
+    #[account(mut, seeds = [b"pool".as_ref(), &[pool.id]], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(has_one = owner, seeds = [b"receipt", owner.key().as_ref()], bump = receipt.bump)]
+    pub receipt: Account<'info, Receipt>,
+
+    #[account(
+        mut,
+        has_one = pool,
+        has_one = receipt,
+        seeds = [b"loan", pool.key().as_ref(), receipt.key().as_ref()],
+        bump = loan.bump
+    )]
+    pub loan: Account<'info, Loan>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Repay<'info> {
+    #[account(mut, seeds = [b"pool".as_ref(), &[pool.id]], bump = pool.bump)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        has_one = pool,
+        has_one = owner,
+        seeds = [b"loan", pool.key().as_ref(), loan.receipt.as_ref()],
+        bump = loan.bump
+    )]
+    pub loan: Account<'info, Loan>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
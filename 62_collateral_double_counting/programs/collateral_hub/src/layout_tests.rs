@@ -0,0 +1,39 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Loan, Pool, Receipt};
+
+#[test]
+fn pool_layout_is_stable() {
+    let pool = Pool {
+        id: 0x11,
+        ltv_bps: 0x2122,
+        bump: 65,
+    };
+    assert_golden!("Pool", golden::anchor(&pool));
+}
+
+#[test]
+fn receipt_layout_is_stable() {
+    let receipt = Receipt {
+        owner: Pubkey::new_from_array([1; 32]),
+        amount: 0x2122232425262728,
+        locked_by: Some(Pubkey::new_from_array([3; 32])),
+        bump: 65,
+    };
+    assert_golden!("Receipt", golden::anchor(&receipt));
+}
+
+#[test]
+fn loan_layout_is_stable() {
+    let loan = Loan {
+        pool: Pubkey::new_from_array([1; 32]),
+        owner: Pubkey::new_from_array([2; 32]),
+        receipt: Pubkey::new_from_array([3; 32]),
+        borrowed: 0x2122232425262728,
+        bump: 65,
+    };
+    assert_golden!("Loan", golden::anchor(&loan));
+}
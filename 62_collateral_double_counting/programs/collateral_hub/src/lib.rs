@@ -0,0 +1,211 @@
+use anchor_lang::system_program::{self, Transfer};
+use secref_guards::rent::minimum_balance;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Basis points in one whole
+pub const BPS: u64 = 10_000;
+
+/// A protocol of lending pools that share one collateral system.
+///
+/// Users deposit lamports once and get a receipt. Each of the protocol's
+/// pools lends against receipts up to its loan-to-value: a loan is opened
+/// per pool and receipt, borrows from that pool's lamports, and is repaid
+/// to it. Pools lend from their own liquidity, but the collateral is the
+/// protocol's - and a receipt is worth its deposit once, however many
+/// pools it is shown to.
+#[feature_gated]
+#[program]
+pub mod collateral_hub {
+    use super::*;
+
+    /// Open pool `id`, lending up to `ltv_bps` of collateral from `liquidity` lamports
+    pub fn initialize_pool(ctx: Context<InitializePool>, id: u8, ltv_bps: u16, liquidity: u64) -> Result<()> {
+        require!(ltv_bps > 0 && u64::from(ltv_bps) <= BPS, ErrorCode::InvalidLtv);
+
+        let pool = &mut ctx.accounts.pool;
+        pool.id = id;
+        pool.ltv_bps = ltv_bps;
+        pool.bump = ctx.bumps.pool;
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: ctx.accounts.pool.to_account_info(),
+                },
+            ),
+            liquidity,
+        )?;
+
+        msg!("Pool {} lends {} lamports at {} bps", id, liquidity, ltv_bps);
+        Ok(())
+    }
+
+    /// Deposit `amount` lamports into a new receipt, unpledged
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroAmount);
+
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.owner = ctx.accounts.owner.key();
+        receipt.amount = amount;
+        receipt.locked_by = None;
+        receipt.bump = ctx.bumps.receipt;
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.receipt.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    /// Open a loan from a pool against the owner's receipt, with nothing borrowed
+    pub fn open_loan(ctx: Context<OpenLoan>) -> Result<()> {
+        let loan = &mut ctx.accounts.loan;
+        loan.pool = ctx.accounts.pool.key();
+        loan.owner = ctx.accounts.owner.key();
+        loan.receipt = ctx.accounts.receipt.key();
+        loan.borrowed = 0;
+        loan.bump = ctx.bumps.loan;
+
+        msg!("Loan opened from pool {}", ctx.accounts.pool.id);
+        Ok(())
+    }
+
+    /// Pledge the receipt to one loan, which must be the only one it backs
+    pub fn lock_collateral(ctx: Context<Collateral>) -> Result<()> {
+        let receipt = &mut ctx.accounts.receipt;
+        require!(receipt.locked_by.is_none(), ErrorCode::CollateralLocked);
+        receipt.locked_by = Some(ctx.accounts.loan.key());
+
+        msg!("Receipt locked to loan {}", ctx.accounts.loan.key());
+        Ok(())
+    }
+
+    /// Release the receipt from a repaid loan, so it may back another
+    pub fn unlock_collateral(ctx: Context<Collateral>) -> Result<()> {
+        let receipt = &mut ctx.accounts.receipt;
+        require!(receipt.locked_by == Some(ctx.accounts.loan.key()), ErrorCode::CollateralNotLocked);
+        require!(ctx.accounts.loan.borrowed == 0, ErrorCode::OutstandingDebt);
+        receipt.locked_by = None;
+
+        msg!("Receipt unlocked from loan {}", ctx.accounts.loan.key());
+        Ok(())
+    }
+
+    /// Pay `amount` lamports of the loan back to its pool
+    pub fn repay(ctx: Context<Repay>, amount: u64) -> Result<()> {
+        let loan = &mut ctx.accounts.loan;
+        loan.borrowed = loan.borrowed.checked_sub(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.pool.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Repaid {} lamports; {} outstanding", amount, ctx.accounts.loan.borrowed);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Borrow against the receipt, counting its whole deposit for this loan
+    ///
+    /// Security Issue: The limit is the pool's LTV of the receipt's amount,
+    /// checked against this loan's debt alone. Nothing records that the
+    /// receipt backs the loan, so the same receipt opens a loan in every
+    /// pool and each lends against the full deposit. At 80% LTV in two
+    /// pools, a 10 SOL deposit borrows 16 SOL: the borrower walks away 6
+    /// SOL up, and the pools hold 10 SOL of collateral for 16 of debt.
+    #[vulnerable]
+    pub fn vulnerable_borrow(ctx: Context<Borrow>, amount: u64) -> Result<()> {
+        // VULNERABILITY: The receipt is counted in full by every loan that names it
+        let limit = borrow_limit(&ctx.accounts.receipt, &ctx.accounts.pool)?;
+        lend(ctx, amount, limit)
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that lends only against
+    // collateral locked to the loan.
+
+    /// SECURE: Borrow only against a receipt locked to this loan
+    ///
+    /// Security Fix: `lock_collateral` records the one loan a receipt
+    /// backs in `locked_by`, and fails if it already backs one; this
+    /// handler requires that loan to be this one. A receipt is counted by
+    /// one loan at a time, and `unlock_collateral` frees it only once that
+    /// loan is repaid.
+    #[secure]
+    pub fn secure_borrow(ctx: Context<Borrow>, amount: u64) -> Result<()> {
+        // SECURITY: The receipt backs this loan and no other
+        require!(
+            ctx.accounts.receipt.locked_by == Some(ctx.accounts.loan.key()),
+            ErrorCode::CollateralNotLocked
+        );
+
+        let limit = borrow_limit(&ctx.accounts.receipt, &ctx.accounts.pool)?;
+        lend(ctx, amount, limit)
+    }
+}
+
+/// Most a loan from `pool` may owe against `receipt`: its LTV of the deposit
+fn borrow_limit(receipt: &Receipt, pool: &Pool) -> Result<u64> {
+    let limit = u128::from(receipt.amount) * u128::from(pool.ltv_bps) / u128::from(BPS);
+    u64::try_from(limit).map_err(|_| ErrorCode::ArithmeticOverflow.into())
+}
+
+/// Lend `amount` lamports from the pool, keeping the loan's debt within `limit`
+fn lend(ctx: Context<Borrow>, amount: u64, limit: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::ZeroAmount);
+    let loan = &mut ctx.accounts.loan;
+    let borrowed = loan.borrowed.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+    require!(borrowed <= limit, ErrorCode::ExceedsBorrowLimit);
+    loan.borrowed = borrowed;
+
+    let pool = ctx.accounts.pool.to_account_info();
+    let owner = ctx.accounts.owner.to_account_info();
+    let available = pool.lamports().saturating_sub(minimum_balance(pool.data_len())?);
+    require!(amount <= available, ErrorCode::InsufficientLiquidity);
+    **pool.try_borrow_mut_lamports()? = pool.lamports() - amount;
+    **owner.try_borrow_mut_lamports()? = owner.lamports().checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    msg!("Borrowed {} lamports; {} of {} used", amount, borrowed, limit);
+    Ok(())
+}
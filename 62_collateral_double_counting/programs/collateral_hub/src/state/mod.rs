@@ -0,0 +1,54 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Pool {
+    /// Which of the protocol's lending pools this is (1 byte)
+    pub id: u8,
+    /// Most a loan may borrow, in basis points of its collateral (2 bytes)
+    pub ltv_bps: u16,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Pool {
+    /// discriminator + id + ltv_bps + bump
+    pub const SPACE: usize = 8 + 1 + 2 + 1;
+}
+
+#[account]
+pub struct Receipt {
+    /// Depositor, and the only account that may pledge the receipt (32 bytes)
+    pub owner: Pubkey,
+    /// Lamports deposited, held by the receipt PDA on top of its rent (8 bytes)
+    pub amount: u64,
+    /// The loan the receipt is pledged to, if any (1 + 32 bytes)
+    pub locked_by: Option<Pubkey>,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Receipt {
+    /// discriminator + owner + amount + locked_by + bump
+    pub const SPACE: usize = 8 + 32 + 8 + (1 + 32) + 1;
+}
+
+#[account]
+pub struct Loan {
+    /// Pool that lent (32 bytes)
+    pub pool: Pubkey,
+    /// Borrower (32 bytes)
+    pub owner: Pubkey,
+    /// Deposit receipt backing the loan (32 bytes)
+    pub receipt: Pubkey,
+    /// Lamports borrowed and not yet repaid (8 bytes)
+    pub borrowed: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Loan {
+    /// discriminator + pool + owner + receipt + borrowed + bump
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 1;
+}
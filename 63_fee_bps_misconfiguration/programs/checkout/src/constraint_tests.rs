@@ -0,0 +1,273 @@
+//! A checkout charging 1% on top of every payment: what a payment costs
+//! after the vulnerable and the secure fee updates, the cap, delay and
+//! events of the secure one, then every constraint on the contexts.
+
+use anchor_lang::prelude::*;
+use secref_guards::intent::{hash_params, Intent};
+use secref_guards::GuardError;
+use secref_testkit::runtime::{take_events, warp_to};
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_seeds_violation, assert_signer_violation, execute, Fixture, TestAccount,
+};
+
+use crate::{
+    instruction, Config, ErrorCode as CheckoutError, FeeUpdateProposed, FeeUpdated, InitializeConfig, Pay, UpdateFee,
+    FEE_UPDATE_DELAY, MAX_FEE_BPS,
+};
+
+/// The fee payers signed up for: 1%
+const FEE_BPS: u16 = 100;
+/// A payment of 1 SOL
+const AMOUNT: u64 = 1_000_000_000;
+/// What the payer holds: 10 SOL
+const PAYER_LAMPORTS: u64 = 10_000_000_000;
+const START: i64 = 1_700_000_000;
+
+fn config_address(admin: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"config", admin.as_ref()], &crate::ID)
+}
+
+/// The admin's config at `fee_bps`, with room for a proposal and `pending_fee` pending
+fn config(admin: Pubkey, treasury: Pubkey, fee_bps: u16, pending_fee: Option<Intent>) -> TestAccount {
+    let (address, bump) = config_address(admin);
+    let mut config = TestAccount::anchor(&Config {
+        admin,
+        treasury,
+        fee_bps,
+        pending_fee,
+        bump,
+    })
+    .at(address);
+    config.data.resize(Config::SPACE, 0);
+    config
+}
+
+/// A fee update of `fee_bps` proposed at START
+fn proposal(admin: Pubkey, fee_bps: u16) -> Intent {
+    let params = hash_params(&[b"fee", &fee_bps.to_le_bytes()]);
+    Intent::prepare(admin, params, START, FEE_UPDATE_DELAY).unwrap()
+}
+
+fn update_fixture_with(pending_fee: Option<Intent>) -> Fixture {
+    warp_to(START);
+    let admin = TestAccount::signer();
+    Fixture::new()
+        .with("config", config(admin.key, Pubkey::new_unique(), FEE_BPS, pending_fee))
+        .with("admin", admin)
+}
+
+fn update_fixture() -> Fixture {
+    update_fixture_with(None)
+}
+
+/// An update fixture whose admin proposed `fee_bps` at START
+fn proposed_fixture(fee_bps: u16) -> Fixture {
+    let mut fixture = update_fixture();
+    execute!(&mut fixture, instruction::ProposeFeeUpdate { fee_bps }).unwrap();
+    fixture
+}
+
+/// A payment into the checkout whose config is `config`
+fn pay_fixture_with(config: &Config) -> Fixture {
+    let treasury = TestAccount::system(0);
+    Fixture::new()
+        .with("config", self::config(config.admin, treasury.key, config.fee_bps, None))
+        .with("treasury", treasury)
+        .with("merchant", TestAccount::system(0))
+        .with("payer", TestAccount::signer().with_lamports(PAYER_LAMPORTS))
+        .with("system_program", TestAccount::system_program())
+}
+
+fn pay_fixture_at(fee_bps: u16) -> Fixture {
+    let config = update_fixture().state::<Config>("config");
+    pay_fixture_with(&Config { fee_bps, ..config })
+}
+
+fn pay_fixture() -> Fixture {
+    pay_fixture_at(FEE_BPS)
+}
+
+fn initialize_fixture() -> Fixture {
+    let admin = TestAccount::signer();
+    Fixture::new()
+        .with("config", TestAccount::uninitialized(Config::SPACE).at(config_address(admin.key).0))
+        .with("treasury", TestAccount::system(0))
+        .with("admin", admin)
+        .with("system_program", TestAccount::system_program())
+}
+
+/// Lamports the payer spent on a payment of AMOUNT, and what the treasury received
+fn charged(fixture: &Fixture) -> (u64, u64) {
+    let spent = PAYER_LAMPORTS - fixture.account("payer").lamports;
+    (spent, fixture.account("treasury").lamports)
+}
+
+#[test]
+fn pay_charges_the_fee_on_top() {
+    let mut fixture = pay_fixture();
+    execute!(&mut fixture, instruction::Pay { amount: AMOUNT }).unwrap();
+    assert_eq!(charged(&fixture), (AMOUNT + AMOUNT / 100, AMOUNT / 100));
+    assert_eq!(fixture.account("merchant").lamports, AMOUNT);
+
+    let mut fixture = pay_fixture();
+    assert_eq!(
+        execute!(&mut fixture, instruction::Pay { amount: 0 }),
+        Err(Error::from(CheckoutError::ZeroAmount).into())
+    );
+}
+
+#[test]
+fn vulnerable_set_fee_charges_six_and_a_half_times_the_payment() {
+    let mut fixture = update_fixture();
+    execute!(&mut fixture, instruction::VulnerableSetFee { fee_bps: u16::MAX }).unwrap();
+    let config = fixture.state::<Config>("config");
+    assert_eq!(config.fee_bps, u16::MAX);
+
+    // The very next payment of 1 SOL costs 7.5535 SOL
+    let mut fixture = pay_fixture_with(&config);
+    execute!(&mut fixture, instruction::Pay { amount: AMOUNT }).unwrap();
+    assert_eq!(charged(&fixture), (AMOUNT + 6_553_500_000, 6_553_500_000));
+}
+
+#[test]
+fn vulnerable_set_fee_announces_nothing() {
+    let mut fixture = update_fixture();
+    execute!(&mut fixture, instruction::VulnerableSetFee { fee_bps: 10_000 }).unwrap();
+    assert!(take_events::<FeeUpdated>().is_empty());
+}
+
+#[test]
+fn secure_fee_updates_are_capped() {
+    for fee_bps in [MAX_FEE_BPS + 1, 10_000, 10_001, u16::MAX] {
+        let mut fixture = update_fixture();
+        assert_eq!(
+            execute!(&mut fixture, instruction::ProposeFeeUpdate { fee_bps }),
+            Err(Error::from(GuardError::BpsOutOfRange).into()),
+            "{fee_bps}"
+        );
+    }
+}
+
+#[test]
+fn propose_fee_update_announces_the_fee_without_charging_it() {
+    let mut fixture = update_fixture();
+    execute!(&mut fixture, instruction::ProposeFeeUpdate { fee_bps: MAX_FEE_BPS }).unwrap();
+    let config = fixture.state::<Config>("config");
+
+    assert_eq!(
+        take_events::<FeeUpdateProposed>()
+            .iter()
+            .map(|event| (event.config, event.current_fee_bps, event.proposed_fee_bps, event.executable_at))
+            .collect::<Vec<_>>(),
+        [(fixture.key("config"), FEE_BPS, MAX_FEE_BPS, START + FEE_UPDATE_DELAY)]
+    );
+    assert_eq!(config.pending_fee, Some(proposal(config.admin, MAX_FEE_BPS)));
+
+    // Payments in the meantime are charged the old fee
+    let mut fixture = pay_fixture_with(&config);
+    execute!(&mut fixture, instruction::Pay { amount: AMOUNT }).unwrap();
+    assert_eq!(charged(&fixture).1, AMOUNT / 100);
+}
+
+#[test]
+fn apply_fee_update_waits_for_the_delay() {
+    let mut fixture = proposed_fixture(MAX_FEE_BPS);
+    warp_to(START + FEE_UPDATE_DELAY - 1);
+    assert_eq!(
+        execute!(&mut fixture, instruction::ApplyFeeUpdate { fee_bps: MAX_FEE_BPS }),
+        Err(Error::from(GuardError::IntentNotReady).into())
+    );
+
+    warp_to(START + FEE_UPDATE_DELAY);
+    execute!(&mut fixture, instruction::ApplyFeeUpdate { fee_bps: MAX_FEE_BPS }).unwrap();
+    let events = take_events::<FeeUpdated>();
+    assert_eq!(events.len(), 1);
+    assert_eq!((events[0].old_fee_bps, events[0].new_fee_bps), (FEE_BPS, MAX_FEE_BPS));
+
+    let config = fixture.state::<Config>("config");
+    assert_eq!((config.fee_bps, &config.pending_fee), (MAX_FEE_BPS, &None));
+    let mut fixture = pay_fixture_with(&config);
+    execute!(&mut fixture, instruction::Pay { amount: AMOUNT }).unwrap();
+    assert_eq!(charged(&fixture).1, AMOUNT / 20);
+}
+
+#[test]
+fn apply_fee_update_applies_only_the_proposed_fee() {
+    let mut fixture = proposed_fixture(MAX_FEE_BPS);
+    warp_to(START + FEE_UPDATE_DELAY);
+    assert_eq!(
+        execute!(&mut fixture, instruction::ApplyFeeUpdate { fee_bps: u16::MAX }),
+        Err(Error::from(GuardError::IntentParamsMismatch).into())
+    );
+
+    // Once applied, the proposal is spent
+    execute!(&mut fixture, instruction::ApplyFeeUpdate { fee_bps: MAX_FEE_BPS }).unwrap();
+    assert_eq!(
+        execute!(&mut fixture, instruction::ApplyFeeUpdate { fee_bps: MAX_FEE_BPS }),
+        Err(Error::from(CheckoutError::NoFeeUpdateProposed).into())
+    );
+}
+
+#[test]
+fn a_new_proposal_restarts_the_delay() {
+    let mut fixture = proposed_fixture(MAX_FEE_BPS);
+    warp_to(START + FEE_UPDATE_DELAY - 1);
+    execute!(&mut fixture, instruction::ProposeFeeUpdate { fee_bps: 200 }).unwrap();
+
+    warp_to(START + FEE_UPDATE_DELAY);
+    assert_eq!(
+        execute!(&mut fixture, instruction::ApplyFeeUpdate { fee_bps: 200 }),
+        Err(Error::from(GuardError::IntentNotReady).into())
+    );
+    warp_to(START + 2 * FEE_UPDATE_DELAY - 1);
+    execute!(&mut fixture, instruction::ApplyFeeUpdate { fee_bps: 200 }).unwrap();
+    assert_eq!(fixture.state::<Config>("config").fee_bps, 200);
+}
+
+#[test]
+fn initialize_config_caps_the_fee() {
+    let mut fixture = initialize_fixture();
+    assert_eq!(
+        execute!(&mut fixture, instruction::InitializeConfig { fee_bps: MAX_FEE_BPS + 1 }),
+        Err(Error::from(GuardError::BpsOutOfRange).into())
+    );
+
+    let mut fixture = initialize_fixture();
+    execute!(&mut fixture, instruction::InitializeConfig { fee_bps: FEE_BPS }).unwrap();
+    let config = fixture.state::<Config>("config");
+    assert_eq!((config.fee_bps, config.treasury, config.pending_fee), (FEE_BPS, fixture.key("treasury"), None));
+}
+
+#[test]
+fn initialize_config_belongs_to_the_signer() {
+    let fixture = initialize_fixture().args(instruction::InitializeConfig { fee_bps: FEE_BPS });
+    assert_seeds_violation!(InitializeConfig, fixture, "config");
+    let fixture = initialize_fixture().args(instruction::InitializeConfig { fee_bps: FEE_BPS });
+    assert_signer_violation!(InitializeConfig, fixture, "admin");
+}
+
+#[test]
+fn fees_are_paid_into_the_configs_treasury() {
+    assert_seeds_violation!(Pay, pay_fixture(), "config");
+    assert_has_one_violation!(Pay, pay_fixture(), "treasury");
+    assert_signer_violation!(Pay, pay_fixture(), "payer");
+}
+
+#[test]
+fn only_the_admin_updates_the_fee() {
+    assert_seeds_violation!(UpdateFee, update_fixture(), "config");
+    assert_constraint_violation!(
+        UpdateFee,
+        update_fixture(),
+        "config" => |config| config.edit(|stored: &mut Config| stored.admin = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_signer_violation!(UpdateFee, update_fixture(), "admin");
+}
+
+#[test]
+fn a_pending_proposal_fits_the_config() {
+    let mut fixture = update_fixture_with(Some(Intent::default()));
+    execute!(&mut fixture, instruction::ProposeFeeUpdate { fee_bps: 1 }).unwrap();
+    assert_eq!(fixture.account("config").data.len(), Config::SPACE);
+}
@@ -0,0 +1,13 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Payment must be more than zero lamports")]
+    ZeroAmount,
+    #[msg("No fee update has been proposed")]
+    NoFeeUpdateProposed,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
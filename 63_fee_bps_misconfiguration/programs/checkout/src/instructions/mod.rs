@@ -0,0 +1,41 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(init, payer = admin, space = Config::SPACE, seeds = [b"config", admin.key().as_ref()], bump)]
+    pub config: Account<'info, Config>,
+
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Pay<'info> {
+    #[account(seeds = [b"config", config.admin.as_ref()], bump = config.bump, has_one = treasury)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub treasury: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub merchant: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFee<'info> {
+    #[account(mut, seeds = [b"config", admin.key().as_ref()], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,24 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_guards::intent::Intent;
+use secref_testkit::{assert_golden, golden};
+
+use crate::Config;
+
+#[test]
+fn config_layout_is_stable() {
+    let config = Config {
+        admin: Pubkey::new_from_array([1; 32]),
+        treasury: Pubkey::new_from_array([2; 32]),
+        fee_bps: 0x3132,
+        pending_fee: Some(Intent {
+            proposer: Pubkey::new_from_array([4; 32]),
+            params_hash: [5; 32],
+            prepared_at: 0x4142434445464748,
+            executable_at: 0x5152535455565758,
+        }),
+        bump: 65,
+    };
+    assert_golden!("Config", golden::anchor(&config));
+}
@@ -0,0 +1,201 @@
+use anchor_lang::system_program::{self, Transfer};
+use secref_guards::bps::{BoundedBps, BPS};
+#[cfg(feature = "secure")]
+use secref_guards::intent::hash_params;
+use secref_guards::intent::Intent;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Highest fee the protocol may charge: 5%
+pub const MAX_FEE_BPS: u16 = 500;
+
+/// Seconds between proposing a fee and charging it: two days
+pub const FEE_UPDATE_DELAY: i64 = 2 * 86_400;
+
+/// A checkout that takes a protocol fee on every payment.
+///
+/// A payer sends a merchant an amount and pays the protocol's fee on top
+/// of it, at the rate the config holds when the payment lands. Payers sign
+/// for an amount, not a fee: whatever the rate is when the transaction
+/// runs is what they are charged.
+#[feature_gated]
+#[program]
+pub mod checkout {
+    use super::*;
+
+    /// Create the admin's config, charging `fee_bps` on every payment into `treasury`
+    pub fn initialize_config(ctx: Context<InitializeConfig>, fee_bps: u16) -> Result<()> {
+        let fee = BoundedBps::at_most(fee_bps, MAX_FEE_BPS)?;
+
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.treasury = ctx.accounts.treasury.key();
+        config.fee_bps = fee.get();
+        config.pending_fee = None;
+        config.bump = ctx.bumps.config;
+
+        msg!("Checkout configured with a {} fee", fee);
+        Ok(())
+    }
+
+    /// Pay `amount` lamports to the merchant, and the config's fee on top to the treasury
+    pub fn pay(ctx: Context<Pay>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroAmount);
+        let fee = fee_for(amount, ctx.accounts.config.fee_bps)?;
+
+        transfer(ctx.accounts, ctx.accounts.merchant.to_account_info(), amount)?;
+        if fee > 0 {
+            transfer(ctx.accounts, ctx.accounts.treasury.to_account_info(), fee)?;
+        }
+
+        msg!("Paid {} lamports plus a {} lamport fee", amount, fee);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Set the fee to `fee_bps`, from the next payment on
+    ///
+    /// Security Issue: Any `u16` is accepted, up to 65,535 bps - a fee of
+    /// 655% - and it applies to the next payment that lands, with nothing
+    /// logged but a line of text. A payer who signed for 1 SOL at a 1% fee
+    /// is charged 6.55 SOL on top if the update lands first: by a
+    /// compromised admin key sandwiching a large payment, or by an honest
+    /// admin who typed 50,000 meaning 5%.
+    #[vulnerable]
+    pub fn vulnerable_set_fee(ctx: Context<UpdateFee>, fee_bps: u16) -> Result<()> {
+        // VULNERABILITY: Unbounded, and in force at once
+        ctx.accounts.config.fee_bps = fee_bps;
+
+        msg!("Fee set to {} bps", fee_bps);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version: fees bounded by
+    // MAX_FEE_BPS, announced by an event, and charged only after a delay.
+
+    /// SECURE: Phase 1, propose a fee of `fee_bps`; admin only
+    ///
+    /// Security Fix: The fee is a `BoundedBps` of at most MAX_FEE_BPS, so
+    /// neither a typo nor a stolen key can set more than 5%. The proposal
+    /// is recorded as an intent executable FEE_UPDATE_DELAY seconds later
+    /// and emitted as `FeeUpdateProposed`, so payers and integrators see
+    /// the new rate two days before anyone pays it. A new proposal
+    /// replaces the pending one and restarts the delay.
+    #[secure]
+    pub fn propose_fee_update(ctx: Context<UpdateFee>, fee_bps: u16) -> Result<()> {
+        // SECURITY: Within the protocol's cap, and never more than the whole
+        let fee = BoundedBps::at_most(fee_bps, MAX_FEE_BPS)?;
+
+        // SECURITY: Chargeable only after the delay
+        let now = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.config;
+        let intent = Intent::prepare(config.admin, fee_params(fee.get()), now, FEE_UPDATE_DELAY)?;
+
+        // SECURITY: Announced in a form indexers decode, not only a log line
+        emit!(FeeUpdateProposed {
+            config: config.key(),
+            current_fee_bps: config.fee_bps,
+            proposed_fee_bps: fee.get(),
+            executable_at: intent.executable_at,
+        });
+        config.pending_fee = Some(intent);
+
+        msg!("Fee of {} proposed", fee);
+        Ok(())
+    }
+
+    /// SECURE: Phase 2, apply the proposed fee of `fee_bps` once the delay is over; admin only
+    ///
+    /// Security Fix: Only the value that was proposed and announced, and
+    /// only once its delay has passed; the change is emitted as
+    /// `FeeUpdated`.
+    #[secure]
+    pub fn apply_fee_update(ctx: Context<UpdateFee>, fee_bps: u16) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.config;
+        let intent = config.pending_fee.take().ok_or(ErrorCode::NoFeeUpdateProposed)?;
+        // SECURITY: The announced fee, after the announced delay
+        intent.assert_executable(&fee_params(fee_bps), now)?;
+
+        emit!(FeeUpdated {
+            config: config.key(),
+            old_fee_bps: config.fee_bps,
+            new_fee_bps: fee_bps,
+        });
+        config.fee_bps = fee_bps;
+
+        msg!("Fee set to {} bps", fee_bps);
+        Ok(())
+    }
+}
+
+/// Parameters of a fee update, as recorded in its intent
+#[cfg(feature = "secure")]
+fn fee_params(fee_bps: u16) -> [u8; 32] {
+    hash_params(&[b"fee", &fee_bps.to_le_bytes()])
+}
+
+/// Fee on `amount` at `fee_bps`, rounded down; more than `amount` above 10,000 bps
+fn fee_for(amount: u64, fee_bps: u16) -> Result<u64> {
+    let fee = u128::from(amount) * u128::from(fee_bps) / u128::from(BPS);
+    u64::try_from(fee).map_err(|_| ErrorCode::ArithmeticOverflow.into())
+}
+
+/// Move `amount` lamports from the payer to `to`
+fn transfer<'info>(accounts: &Pay<'info>, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            accounts.system_program.to_account_info(),
+            Transfer {
+                from: accounts.payer.to_account_info(),
+                to,
+            },
+        ),
+        amount,
+    )
+}
+
+// ========================================
+// EVENTS
+// ========================================
+
+/// A fee change proposed; it may apply from `executable_at`
+#[event]
+pub struct FeeUpdateProposed {
+    pub config: Pubkey,
+    pub current_fee_bps: u16,
+    pub proposed_fee_bps: u16,
+    pub executable_at: i64,
+}
+
+/// A fee change applied
+#[event]
+pub struct FeeUpdated {
+    pub config: Pubkey,
+    pub old_fee_bps: u16,
+    pub new_fee_bps: u16,
+}
@@ -0,0 +1,21 @@
+//! Accounts the program owns
+
+use super::*;
+
+#[account]
+pub struct Config {
+    /// Sets the fee (32 bytes)
+    pub admin: Pubkey,
+    /// Receives every fee (32 bytes)
+    pub treasury: Pubkey,
+    /// Fee charged on top of each payment, in basis points (2 bytes)
+    pub fee_bps: u16,
+    /// Fee update proposed and not yet applied, in the secure version (1 + 80 bytes)
+    pub pending_fee: Option<Intent>,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Config {
+    pub const SPACE: usize = 8 + 32 + 32 + 2 + (1 + Intent::SPACE) + 1;
+}
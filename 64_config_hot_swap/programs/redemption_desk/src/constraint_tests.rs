@@ -0,0 +1,562 @@
+//! A desk buying back credits at 0.001 SOL, with Alice's 10,000 credits
+//! waiting to settle: what the vulnerable config swap does to requests in
+//! flight, the secure version's timelocked epochs and the snapshots its
+//! settlement honours, then every constraint on the contexts.
+
+use anchor_lang::prelude::*;
+use secref_guards::intent::{hash_params, Intent};
+use secref_guards::GuardError;
+use secref_testkit::runtime::{take_events, warp_to};
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_seeds_violation, assert_signer_violation, execute, Fixture, TestAccount,
+};
+
+use crate::{
+    instruction, ActivateConfig, Buy, ConfigChangeProposed, ConfigEpoch, ConfigEpochActivated, Desk,
+    ErrorCode as DeskError, InitializeDesk, InitializeFeed, Position, PriceFeed, PublishPrice, RequestRedemption,
+    SettleAtEpoch, SettleRedemption, UpdateConfig, CONFIG_DELAY, SETTLEMENT_DELAY,
+};
+
+/// Lamports per credit on the honest feed
+const PRICE: u64 = 1_000_000;
+/// Lamports per credit on the feed the admin key's holder publishes: 10 SOL
+const RIGGED_PRICE: u64 = 10_000_000_000;
+/// Alice's redemption in flight, worth 10 SOL
+const ALICE_CREDITS: u64 = 10_000;
+/// The desk's reserve beyond rent: Alice's credits and one more
+const RESERVE: u64 = (ALICE_CREDITS + 1) * PRICE;
+const START: i64 = 1_700_000_000;
+
+fn desk_address(admin: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"desk", admin.as_ref()], &crate::ID)
+}
+
+fn epoch_address(desk: Pubkey, index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"epoch", desk.as_ref(), &index.to_le_bytes()], &crate::ID)
+}
+
+fn position_address(desk: Pubkey, owner: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"position", desk.as_ref(), owner.as_ref()], &crate::ID)
+}
+
+fn feed(authority: Pubkey, price: u64) -> TestAccount {
+    let (address, bump) = Pubkey::find_program_address(&[b"feed", authority.as_ref()], &crate::ID);
+    TestAccount::anchor(&PriceFeed { authority, price, bump }).at(address)
+}
+
+/// The config the desk starts with: the honest feed, fees to the treasury
+struct Config {
+    admin: Pubkey,
+    oracle: TestAccount,
+    treasury: TestAccount,
+}
+
+impl Config {
+    fn new() -> Self {
+        warp_to(START);
+        Self {
+            admin: Pubkey::new_unique(),
+            oracle: feed(Pubkey::new_unique(), PRICE),
+            treasury: TestAccount::system(0),
+        }
+    }
+
+    fn desk_key(&self) -> Pubkey {
+        desk_address(self.admin).0
+    }
+
+    fn desk_state(&self) -> Desk {
+        Desk {
+            admin: self.admin,
+            oracle: self.oracle.key,
+            fee_recipient: self.treasury.key,
+            epoch: 0,
+            pending_config: None,
+            bump: desk_address(self.admin).1,
+        }
+    }
+
+    /// Epoch `index` of the desk, in force from START
+    fn epoch(&self, index: u64, oracle: Pubkey, fee_recipient: Pubkey) -> TestAccount {
+        let (address, bump) = epoch_address(self.desk_key(), index);
+        TestAccount::anchor(&ConfigEpoch {
+            desk: self.desk_key(),
+            index,
+            oracle,
+            fee_recipient,
+            activated_at: START,
+            bump,
+        })
+        .at(address)
+    }
+
+    /// A redemption of `credits` by `owner`, requested at START in `epoch`
+    fn redemption(&self, owner: Pubkey, credits: u64, epoch: u64) -> TestAccount {
+        let (address, bump) = position_address(self.desk_key(), owner);
+        TestAccount::anchor(&Position {
+            desk: self.desk_key(),
+            owner,
+            credits: 0,
+            redeeming: credits,
+            requested_at: START,
+            epoch,
+            bump,
+        })
+        .at(address)
+    }
+}
+
+/// The desk as `state`, holding RESERVE beyond its rent
+fn desk(state: &Desk) -> TestAccount {
+    let mut desk = TestAccount::anchor(state).at(desk_address(state.admin).0);
+    desk.data.resize(Desk::SPACE, 0);
+    desk.with_lamports(Rent::default().minimum_balance(Desk::SPACE) + RESERVE)
+}
+
+/// A config update by the admin, to `oracle` and `fee_recipient`
+fn update_fixture_to(config: &Config, state: &Desk, oracle: TestAccount, fee_recipient: TestAccount) -> Fixture {
+    Fixture::new()
+        .with("desk", desk(state))
+        .with("oracle", oracle)
+        .with("fee_recipient", fee_recipient)
+        .with("admin", TestAccount::signer().at(config.admin))
+}
+
+/// Mallory's feed at RIGGED_PRICE and her own wallet
+fn rigged() -> (TestAccount, TestAccount) {
+    (feed(Pubkey::new_unique(), RIGGED_PRICE), TestAccount::system(0))
+}
+
+fn update_fixture(config: &Config) -> Fixture {
+    let (oracle, fee_recipient) = rigged();
+    update_fixture_to(config, &config.desk_state(), oracle, fee_recipient)
+}
+
+/// The activation of epoch `state.epoch + 1` by the admin, to `oracle` and `fee_recipient`
+fn activate_fixture_to(config: &Config, state: &Desk, oracle: TestAccount, fee_recipient: TestAccount) -> Fixture {
+    let next = epoch_address(config.desk_key(), state.epoch + 1).0;
+    Fixture::new()
+        .with("desk", desk(state))
+        .with("config_epoch", TestAccount::uninitialized(ConfigEpoch::SPACE).at(next))
+        .with("oracle", oracle)
+        .with("fee_recipient", fee_recipient)
+        .with("admin", TestAccount::signer().at(config.admin))
+        .with("system_program", TestAccount::system_program())
+}
+
+/// The activation of what the admin proposed in `proposed`
+fn activate_fixture(config: &Config, proposed: &Fixture) -> Fixture {
+    let state = proposed.state::<Desk>("desk");
+    activate_fixture_to(config, &state, proposed.account("oracle").clone(), proposed.account("fee_recipient").clone())
+}
+
+/// The admin's proposal of Mallory's feed and wallet at START
+fn proposed_fixture(config: &Config) -> Fixture {
+    let mut fixture = update_fixture(config);
+    execute!(&mut fixture, instruction::ProposeConfig {}).unwrap();
+    fixture
+}
+
+/// Settling `redemption` at the desk's current config, `state`, paying fees to `fee_recipient`
+fn settle_fixture_with(
+    state: &Desk,
+    oracle: TestAccount,
+    fee_recipient: TestAccount,
+    redemption: TestAccount,
+) -> Fixture {
+    let owner = redemption.state::<Position>().owner;
+    Fixture::new()
+        .with("desk", desk(state))
+        .with("oracle", oracle)
+        .with("position", redemption)
+        .with("owner", TestAccount::system(0).at(owner))
+        .with("fee_recipient", fee_recipient)
+}
+
+/// Alice's redemption settled at the desk's initial config
+fn settle_fixture(config: &Config) -> Fixture {
+    let alice = config.redemption(Pubkey::new_unique(), ALICE_CREDITS, 0);
+    settle_fixture_with(&config.desk_state(), config.oracle.clone(), config.treasury.clone(), alice)
+}
+
+/// Settling `redemption` at the config of the epoch it records, `config_epoch`
+fn settle_at_epoch_fixture(
+    state: &Desk,
+    config_epoch: TestAccount,
+    oracle: TestAccount,
+    fee_recipient: TestAccount,
+    redemption: TestAccount,
+) -> Fixture {
+    let owner = redemption.state::<Position>().owner;
+    Fixture::new()
+        .with("desk", desk(state))
+        .with("config_epoch", config_epoch)
+        .with("oracle", oracle)
+        .with("position", redemption)
+        .with("owner", TestAccount::system(0).at(owner))
+        .with("fee_recipient", fee_recipient)
+}
+
+fn secure_settle_fixture(config: &Config) -> Fixture {
+    let alice = config.redemption(Pubkey::new_unique(), ALICE_CREDITS, 0);
+    let epoch = config.epoch(0, config.oracle.key, config.treasury.key);
+    settle_at_epoch_fixture(&config.desk_state(), epoch, config.oracle.clone(), config.treasury.clone(), alice)
+}
+
+fn buy_fixture(config: &Config) -> Fixture {
+    let owner = TestAccount::signer();
+    let position = position_address(config.desk_key(), owner.key).0;
+    Fixture::new()
+        .with("desk", desk(&config.desk_state()))
+        .with("oracle", config.oracle.clone())
+        .with("position", TestAccount::uninitialized(Position::SPACE).at(position))
+        .with("owner", owner)
+        .with("system_program", TestAccount::system_program())
+}
+
+/// Alice, holding `credits` and redeeming none, in a desk at epoch 3
+fn request_fixture(config: &Config, credits: u64) -> Fixture {
+    let owner = TestAccount::signer();
+    let mut position = config.redemption(owner.key, 0, 0);
+    position.edit(|stored: &mut Position| stored.credits = credits);
+    Fixture::new()
+        .with("desk", desk(&Desk { epoch: 3, ..config.desk_state() }))
+        .with("position", position)
+        .with("owner", owner)
+}
+
+fn initialize_desk_fixture(config: &Config) -> Fixture {
+    Fixture::new()
+        .with("desk", TestAccount::uninitialized(Desk::SPACE).at(config.desk_key()))
+        .with("config_epoch", TestAccount::uninitialized(ConfigEpoch::SPACE).at(epoch_address(config.desk_key(), 0).0))
+        .with("oracle", config.oracle.clone())
+        .with("fee_recipient", config.treasury.clone())
+        .with("admin", TestAccount::signer().at(config.admin))
+        .with("system_program", TestAccount::system_program())
+}
+
+fn initialize_feed_fixture() -> Fixture {
+    let authority = TestAccount::signer();
+    let address = Pubkey::find_program_address(&[b"feed", authority.key.as_ref()], &crate::ID).0;
+    Fixture::new()
+        .with("feed", TestAccount::uninitialized(PriceFeed::SPACE).at(address))
+        .with("authority", authority)
+        .with("system_program", TestAccount::system_program())
+}
+
+fn publish_fixture() -> Fixture {
+    let authority = TestAccount::signer();
+    Fixture::new()
+        .with("feed", feed(authority.key, PRICE))
+        .with("authority", authority)
+}
+
+/// Lamports the owner and the fee recipient were paid
+fn paid(fixture: &Fixture) -> (u64, u64) {
+    (fixture.account("owner").lamports, fixture.account("fee_recipient").lamports)
+}
+
+#[test]
+fn buy_charges_the_oracles_price() {
+    let config = Config::new();
+    let mut fixture = buy_fixture(&config);
+    let before = fixture.account("desk").lamports;
+    execute!(&mut fixture, instruction::Buy { credits: 10 }).unwrap();
+
+    assert_eq!(fixture.account("desk").lamports - before, 10 * PRICE);
+    let position = fixture.state::<Position>("position");
+    assert_eq!((position.credits, position.redeeming, position.owner), (10, 0, fixture.key("owner")));
+}
+
+#[test]
+fn request_redemption_records_the_epoch_in_force() {
+    let config = Config::new();
+    let mut fixture = request_fixture(&config, 100);
+    assert_eq!(
+        execute!(&mut fixture, instruction::RequestRedemption { credits: 101 }),
+        Err(Error::from(DeskError::InsufficientCredits).into())
+    );
+
+    execute!(&mut fixture, instruction::RequestRedemption { credits: 60 }).unwrap();
+    let position = fixture.state::<Position>("position");
+    assert_eq!((position.credits, position.redeeming, position.requested_at, position.epoch), (40, 60, START, 3));
+
+    assert_eq!(
+        execute!(&mut fixture, instruction::RequestRedemption { credits: 40 }),
+        Err(Error::from(DeskError::RedemptionPending).into())
+    );
+}
+
+#[test]
+fn settlement_waits_a_day_and_pays_less_the_fee() {
+    let config = Config::new();
+    let mut fixture = settle_fixture(&config);
+    warp_to(START + SETTLEMENT_DELAY - 1);
+    assert_eq!(
+        execute!(&mut fixture, instruction::VulnerableSettleRedemption {}),
+        Err(Error::from(DeskError::RedemptionNotReady).into())
+    );
+
+    warp_to(START + SETTLEMENT_DELAY);
+    execute!(&mut fixture, instruction::VulnerableSettleRedemption {}).unwrap();
+    assert_eq!(paid(&fixture), (9_900_000_000, 100_000_000));
+    assert_eq!(
+        execute!(&mut fixture, instruction::VulnerableSettleRedemption {}),
+        Err(Error::from(DeskError::NoRedemption).into())
+    );
+}
+
+#[test]
+fn vulnerable_set_config_reprices_requests_in_flight() {
+    let config = Config::new();
+    let mut fixture = update_fixture(&config);
+    execute!(&mut fixture, instruction::VulnerableSetConfig {}).unwrap();
+    let state = fixture.state::<Desk>("desk");
+    assert_eq!((state.oracle, state.fee_recipient), (fixture.key("oracle"), fixture.key("fee_recipient")));
+
+    // One credit requested at 0.001 SOL, before the swap, cashes out 10 SOL,
+    // and its fee goes to Mallory's wallet instead of the treasury
+    warp_to(START + SETTLEMENT_DELAY);
+    let (oracle, mallory) = (fixture.account("oracle").clone(), fixture.account("fee_recipient").clone());
+    let one_credit = config.redemption(Pubkey::new_unique(), 1, 0);
+    let mut settle = settle_fixture_with(&state, oracle.clone(), mallory.clone(), one_credit);
+    execute!(&mut settle, instruction::VulnerableSettleRedemption {}).unwrap();
+    assert_eq!(paid(&settle), (9_900_000_000, 100_000_000));
+    let drained = settle.account("desk").lamports;
+    assert_eq!(drained, Rent::default().minimum_balance(Desk::SPACE) + PRICE);
+
+    // Alice's 10,000 credits, worth 10 SOL when she asked, find the desk empty
+    let alice = config.redemption(Pubkey::new_unique(), ALICE_CREDITS, 0);
+    let mut settle = settle_fixture_with(&state, oracle, mallory, alice);
+    settle.account_mut("desk").lamports = drained;
+    assert_eq!(
+        execute!(&mut settle, instruction::VulnerableSettleRedemption {}),
+        Err(Error::from(DeskError::InsufficientReserve).into())
+    );
+}
+
+#[test]
+fn propose_config_announces_the_next_epoch_without_applying_it() {
+    let config = Config::new();
+    let fixture = proposed_fixture(&config);
+    let state = fixture.state::<Desk>("desk");
+
+    assert_eq!(
+        take_events::<ConfigChangeProposed>()
+            .iter()
+            .map(|event| (event.desk, event.epoch, event.oracle, event.fee_recipient, event.executable_at))
+            .collect::<Vec<_>>(),
+        [(config.desk_key(), 1, fixture.key("oracle"), fixture.key("fee_recipient"), START + CONFIG_DELAY)]
+    );
+    assert_eq!((state.epoch, state.oracle, state.fee_recipient), (0, config.oracle.key, config.treasury.key));
+    let params = hash_params(&[b"config", fixture.key("oracle").as_ref(), fixture.key("fee_recipient").as_ref()]);
+    assert_eq!(state.pending_config, Some(Intent::prepare(config.admin, params, START, CONFIG_DELAY).unwrap()));
+}
+
+#[test]
+fn activate_config_waits_for_the_delay_and_opens_a_new_epoch() {
+    let config = Config::new();
+    let proposed = proposed_fixture(&config);
+    let mut fixture = activate_fixture(&config, &proposed);
+    warp_to(START + CONFIG_DELAY - 1);
+    assert_eq!(
+        execute!(&mut fixture, instruction::ActivateConfig {}),
+        Err(Error::from(GuardError::IntentNotReady).into())
+    );
+
+    let mut fixture = activate_fixture(&config, &proposed);
+    warp_to(START + CONFIG_DELAY);
+    execute!(&mut fixture, instruction::ActivateConfig {}).unwrap();
+    let state = fixture.state::<Desk>("desk");
+    assert_eq!((state.epoch, state.oracle, &state.pending_config), (1, fixture.key("oracle"), &None));
+
+    let epoch = fixture.state::<ConfigEpoch>("config_epoch");
+    assert_eq!(
+        (epoch.desk, epoch.index, epoch.oracle, epoch.fee_recipient, epoch.activated_at),
+        (config.desk_key(), 1, fixture.key("oracle"), fixture.key("fee_recipient"), START + CONFIG_DELAY)
+    );
+    let events = take_events::<ConfigEpochActivated>();
+    assert_eq!(events.len(), 1);
+    assert_eq!((events[0].epoch, events[0].fee_recipient), (1, fixture.key("fee_recipient")));
+}
+
+#[test]
+fn activate_config_activates_only_the_proposed_config() {
+    let config = Config::new();
+    let proposed = proposed_fixture(&config);
+    warp_to(START + CONFIG_DELAY);
+
+    let mut fixture = activate_fixture(&config, &proposed);
+    *fixture.account_mut("fee_recipient") = TestAccount::system(0);
+    assert_eq!(
+        execute!(&mut fixture, instruction::ActivateConfig {}),
+        Err(Error::from(GuardError::IntentParamsMismatch).into())
+    );
+
+    let mut fixture = activate_fixture(&config, &proposed);
+    execute!(&mut fixture, instruction::ActivateConfig {}).unwrap();
+    let state = fixture.state::<Desk>("desk");
+    let mut fixture = activate_fixture_to(&config, &state, proposed.account("oracle").clone(), TestAccount::system(0));
+    assert_eq!(
+        execute!(&mut fixture, instruction::ActivateConfig {}),
+        Err(Error::from(DeskError::NoConfigChangeProposed).into())
+    );
+}
+
+#[test]
+fn secure_settlement_honours_the_epoch_of_the_request() {
+    let config = Config::new();
+    let (oracle, mallory) = rigged();
+    // The desk is in epoch 1 at Mallory's config, activated or hot-swapped;
+    // Alice requested in epoch 0
+    let state = Desk {
+        epoch: 1,
+        oracle: oracle.key,
+        fee_recipient: mallory.key,
+        ..config.desk_state()
+    };
+    warp_to(START + SETTLEMENT_DELAY);
+
+    let alice = config.redemption(Pubkey::new_unique(), ALICE_CREDITS, 0);
+    let epoch_0 = config.epoch(0, config.oracle.key, config.treasury.key);
+    let mut fixture = settle_at_epoch_fixture(&state, epoch_0, config.oracle.clone(), config.treasury.clone(), alice);
+    execute!(&mut fixture, instruction::SecureSettleRedemption {}).unwrap();
+    assert_eq!(paid(&fixture), (9_900_000_000, 100_000_000));
+
+    // Neither the new epoch nor the new config reaches her request
+    let alice = config.redemption(Pubkey::new_unique(), ALICE_CREDITS, 0);
+    let epoch_1 = config.epoch(1, oracle.key, mallory.key);
+    let fixture = settle_at_epoch_fixture(&state, epoch_1, oracle.clone(), mallory.clone(), alice);
+    assert_eq!(
+        execute!(&mut fixture.clone(), instruction::SecureSettleRedemption {}),
+        Err(Error::from(ErrorCode::ConstraintSeeds).into())
+    );
+    let alice = config.redemption(Pubkey::new_unique(), ALICE_CREDITS, 0);
+    let epoch_0 = config.epoch(0, config.oracle.key, config.treasury.key);
+    let mut fixture = settle_at_epoch_fixture(&state, epoch_0, oracle, mallory, alice);
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureSettleRedemption {}),
+        Err(Error::from(ErrorCode::ConstraintHasOne).into())
+    );
+}
+
+#[test]
+fn requests_made_after_an_activation_settle_under_the_new_epoch() {
+    let config = Config::new();
+    let (oracle, fee_recipient) = rigged();
+    let state = Desk { epoch: 1, ..config.desk_state() };
+    warp_to(START + SETTLEMENT_DELAY);
+
+    let bob = config.redemption(Pubkey::new_unique(), 1, 1);
+    let epoch_1 = config.epoch(1, oracle.key, fee_recipient.key);
+    let mut fixture = settle_at_epoch_fixture(&state, epoch_1, oracle, fee_recipient, bob);
+    execute!(&mut fixture, instruction::SecureSettleRedemption {}).unwrap();
+    assert_eq!(paid(&fixture), (9_900_000_000, 100_000_000));
+}
+
+#[test]
+fn initialize_desk_writes_epoch_zero() {
+    let config = Config::new();
+    let mut fixture = initialize_desk_fixture(&config);
+    execute!(&mut fixture, instruction::InitializeDesk {}).unwrap();
+
+    let state = fixture.state::<Desk>("desk");
+    assert_eq!((state.oracle, state.fee_recipient, state.epoch), (config.oracle.key, config.treasury.key, 0));
+    let epoch = fixture.state::<ConfigEpoch>("config_epoch");
+    assert_eq!(
+        (epoch.desk, epoch.index, epoch.oracle, epoch.fee_recipient, epoch.activated_at),
+        (config.desk_key(), 0, config.oracle.key, config.treasury.key, START)
+    );
+}
+
+#[test]
+fn feeds_publish_positive_prices_for_their_authority() {
+    let mut fixture = initialize_feed_fixture();
+    assert_eq!(
+        execute!(&mut fixture, instruction::InitializeFeed { price: 0 }),
+        Err(Error::from(DeskError::ZeroPrice).into())
+    );
+    let mut fixture = publish_fixture();
+    execute!(&mut fixture, instruction::PublishPrice { price: 7 }).unwrap();
+    assert_eq!(fixture.state::<PriceFeed>("feed").price, 7);
+
+    let fixture = initialize_feed_fixture().args(instruction::InitializeFeed { price: PRICE });
+    assert_seeds_violation!(InitializeFeed, fixture, "feed");
+    assert_seeds_violation!(PublishPrice, publish_fixture(), "feed");
+    assert_constraint_violation!(
+        PublishPrice,
+        publish_fixture(),
+        "feed" => |feed| feed.edit(|stored: &mut PriceFeed| stored.authority = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_signer_violation!(PublishPrice, publish_fixture(), "authority");
+}
+
+#[test]
+fn initialize_desk_belongs_to_the_signer() {
+    let config = Config::new();
+    assert_seeds_violation!(InitializeDesk, initialize_desk_fixture(&config), "desk");
+    assert_seeds_violation!(InitializeDesk, initialize_desk_fixture(&config), "config_epoch");
+    assert_signer_violation!(InitializeDesk, initialize_desk_fixture(&config), "admin");
+}
+
+#[test]
+fn buy_and_request_use_the_desks_oracle_and_the_owners_position() {
+    let config = Config::new();
+    assert_has_one_violation!(Buy, buy_fixture(&config), "oracle");
+    assert_seeds_violation!(Buy, buy_fixture(&config), "position");
+    assert_signer_violation!(Buy, buy_fixture(&config), "owner");
+
+    assert_seeds_violation!(RequestRedemption, request_fixture(&config, 1), "desk");
+    assert_seeds_violation!(RequestRedemption, request_fixture(&config, 1), "position");
+    assert_signer_violation!(RequestRedemption, request_fixture(&config, 1), "owner");
+}
+
+#[test]
+fn settlements_pay_the_position_owner() {
+    let config = Config::new();
+    assert_has_one_violation!(SettleRedemption, settle_fixture(&config), "oracle");
+    assert_has_one_violation!(SettleRedemption, settle_fixture(&config), "fee_recipient");
+    assert_seeds_violation!(SettleRedemption, settle_fixture(&config), "owner");
+    assert_constraint_violation!(
+        SettleRedemption,
+        settle_fixture(&config),
+        "position" => |position| position.edit(|stored: &mut Position| stored.desk = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+
+    assert_seeds_violation!(SettleAtEpoch, secure_settle_fixture(&config), "config_epoch");
+    assert_has_one_violation!(SettleAtEpoch, secure_settle_fixture(&config), "oracle");
+    assert_has_one_violation!(SettleAtEpoch, secure_settle_fixture(&config), "fee_recipient");
+    assert_seeds_violation!(SettleAtEpoch, secure_settle_fixture(&config), "owner");
+}
+
+#[test]
+fn only_the_admin_changes_the_config() {
+    let config = Config::new();
+    assert_seeds_violation!(UpdateConfig, update_fixture(&config), "desk");
+    assert_constraint_violation!(
+        UpdateConfig,
+        update_fixture(&config),
+        "desk" => |desk| desk.edit(|stored: &mut Desk| stored.admin = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_signer_violation!(UpdateConfig, update_fixture(&config), "admin");
+
+    let proposed = proposed_fixture(&config);
+    assert_seeds_violation!(ActivateConfig, activate_fixture(&config, &proposed), "config_epoch");
+    assert_signer_violation!(ActivateConfig, activate_fixture(&config, &proposed), "admin");
+}
+
+#[test]
+fn a_pending_proposal_fits_the_desk() {
+    let config = Config::new();
+    let state = Desk {
+        pending_config: Some(Intent::default()),
+        ..config.desk_state()
+    };
+    let (oracle, fee_recipient) = rigged();
+    let mut fixture = update_fixture_to(&config, &state, oracle, fee_recipient);
+    execute!(&mut fixture, instruction::ProposeConfig {}).unwrap();
+    assert_eq!(fixture.account("desk").data.len(), Desk::SPACE);
+}
@@ -0,0 +1,25 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Amount must be more than zero")]
+    ZeroAmount,
+    #[msg("Price must be more than zero lamports")]
+    ZeroPrice,
+    #[msg("Position holds fewer credits than requested")]
+    InsufficientCredits,
+    #[msg("A redemption is already in flight")]
+    RedemptionPending,
+    #[msg("No redemption is in flight")]
+    NoRedemption,
+    #[msg("Redemption cannot be settled yet")]
+    RedemptionNotReady,
+    #[msg("Desk holds too few lamports to pay the redemption")]
+    InsufficientReserve,
+    #[msg("No config change has been proposed")]
+    NoConfigChangeProposed,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
@@ -0,0 +1,177 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct InitializeFeed<'info> {
+    #[account(init, payer = authority, space = PriceFeed::SPACE, seeds = [b"feed", authority.key().as_ref()], bump)]
+    pub feed: Account<'info, PriceFeed>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PublishPrice<'info> {
+    #[account(mut, seeds = [b"feed", authority.key().as_ref()], bump = feed.bump, has_one = authority)]
+    pub feed: Account<'info, PriceFeed>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeDesk<'info> {
+    #[account(init, payer = admin, space = Desk::SPACE, seeds = [b"desk", admin.key().as_ref()], bump)]
+    pub desk: Account<'info, Desk>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ConfigEpoch::SPACE,
+        seeds = [b"epoch", desk.key().as_ref(), &0u64.to_le_bytes()],
+        bump
+    )]
+    pub config_epoch: Account<'info, ConfigEpoch>,
+
+    pub oracle: Account<'info, PriceFeed>,
+
+    pub fee_recipient: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Buy<'info> {
+    #[account(mut, seeds = [b"desk", desk.admin.as_ref()], bump = desk.bump, has_one = oracle)]
+    pub desk: Account<'info, Desk>,
+
+    pub oracle: Account<'info, PriceFeed>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = Position::SPACE,
+        seeds = [b"position", desk.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestRedemption<'info> {
+    #[account(seeds = [b"desk", desk.admin.as_ref()], bump = desk.bump)]
+    pub desk: Account<'info, Desk>,
+
+    #[account(
+        mut,
+        seeds = [b"position", desk.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump,
+        has_one = desk,
+        has_one = owner
+    )]
+    pub position: Account<'info, Position>,
+
+    pub owner: Signer<'info>,
+}
+
+/// Settle a redemption at the desk's current config; anyone may crank it
+#[derive(Accounts)]
+pub struct SettleRedemption<'info> {
+    #[account(mut, seeds = [b"desk", desk.admin.as_ref()], bump = desk.bump, has_one = oracle, has_one = fee_recipient)]
+    pub desk: Account<'info, Desk>,
+
+    pub oracle: Account<'info, PriceFeed>,
+
+    #[account(
+        mut,
+        seeds = [b"position", desk.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump,
+        has_one = desk,
+        has_one = owner
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub owner: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub fee_recipient: SystemAccount<'info>,
+}
+
+/// Settle a redemption at the config of the epoch it was requested in; anyone may crank it
+#[derive(Accounts)]
+pub struct SettleAtEpoch<'info> {
+    #[account(mut, seeds = [b"desk", desk.admin.as_ref()], bump = desk.bump)]
+    pub desk: Account<'info, Desk>,
+
+    #[account(
+        seeds = [b"epoch", desk.key().as_ref(), &position.epoch.to_le_bytes()],
+        bump = config_epoch.bump,
+        has_one = oracle,
+        has_one = fee_recipient
+    )]
+    pub config_epoch: Account<'info, ConfigEpoch>,
+
+    pub oracle: Account<'info, PriceFeed>,
+
+    #[account(
+        mut,
+        seeds = [b"position", desk.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump,
+        has_one = desk,
+        has_one = owner
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub owner: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub fee_recipient: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(mut, seeds = [b"desk", admin.key().as_ref()], bump = desk.bump, has_one = admin)]
+    pub desk: Account<'info, Desk>,
+
+    pub oracle: Account<'info, PriceFeed>,
+
+    pub fee_recipient: SystemAccount<'info>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ActivateConfig<'info> {
+    #[account(mut, seeds = [b"desk", admin.key().as_ref()], bump = desk.bump, has_one = admin)]
+    pub desk: Account<'info, Desk>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ConfigEpoch::SPACE,
+        seeds = [b"epoch", desk.key().as_ref(), &(desk.epoch + 1).to_le_bytes()],
+        bump
+    )]
+    pub config_epoch: Account<'info, ConfigEpoch>,
+
+    pub oracle: Account<'info, PriceFeed>,
+
+    pub fee_recipient: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,62 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_guards::intent::Intent;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{ConfigEpoch, Desk, Position, PriceFeed};
+
+#[test]
+fn price_feed_layout_is_stable() {
+    let feed = PriceFeed {
+        authority: Pubkey::new_from_array([1; 32]),
+        price: 0x2122232425262728,
+        bump: 65,
+    };
+    assert_golden!("PriceFeed", golden::anchor(&feed));
+}
+
+#[test]
+fn desk_layout_is_stable() {
+    let desk = Desk {
+        admin: Pubkey::new_from_array([1; 32]),
+        oracle: Pubkey::new_from_array([2; 32]),
+        fee_recipient: Pubkey::new_from_array([3; 32]),
+        epoch: 0x2122232425262728,
+        pending_config: Some(Intent {
+            proposer: Pubkey::new_from_array([4; 32]),
+            params_hash: [5; 32],
+            prepared_at: 0x4142434445464748,
+            executable_at: 0x5152535455565758,
+        }),
+        bump: 65,
+    };
+    assert_golden!("Desk", golden::anchor(&desk));
+}
+
+#[test]
+fn config_epoch_layout_is_stable() {
+    let config_epoch = ConfigEpoch {
+        desk: Pubkey::new_from_array([1; 32]),
+        index: 0x2122232425262728,
+        oracle: Pubkey::new_from_array([2; 32]),
+        fee_recipient: Pubkey::new_from_array([3; 32]),
+        activated_at: 0x3132333435363738,
+        bump: 65,
+    };
+    assert_golden!("ConfigEpoch", golden::anchor(&config_epoch));
+}
+
+#[test]
+fn position_layout_is_stable() {
+    let position = Position {
+        desk: Pubkey::new_from_array([1; 32]),
+        owner: Pubkey::new_from_array([2; 32]),
+        credits: 0x2122232425262728,
+        redeeming: 0x3132333435363738,
+        requested_at: 0x4142434445464748,
+        epoch: 0x5152535455565758,
+        bump: 65,
+    };
+    assert_golden!("Position", golden::anchor(&position));
+}
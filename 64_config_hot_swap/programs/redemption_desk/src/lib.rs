@@ -0,0 +1,343 @@
+use anchor_lang::system_program::{self, Transfer};
+use secref_guards::bps::BPS;
+#[cfg(feature = "secure")]
+use secref_guards::intent::hash_params;
+use secref_guards::intent::Intent;
+use secref_guards::rent::minimum_balance;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Fee taken from every redemption: 1%
+pub const REDEMPTION_FEE_BPS: u16 = 100;
+
+/// Seconds between requesting a redemption and settling it: one day
+pub const SETTLEMENT_DELAY: i64 = 86_400;
+
+/// Seconds between proposing a config change and activating it: two days
+pub const CONFIG_DELAY: i64 = 2 * 86_400;
+
+/// A desk that buys back the credits it sells, at an oracle's price.
+///
+/// Users buy credits at the price of the desk's oracle, then request to
+/// redeem them. A keeper settles each request a day later: the desk pays
+/// the credits' value at the oracle's price, less a fee sent to its fee
+/// recipient. The admin may change the oracle and the fee recipient, and
+/// every request is in flight for a day - long enough for a change to land
+/// between a request and its settlement.
+#[feature_gated]
+#[program]
+pub mod redemption_desk {
+    use super::*;
+
+    /// Create the signer's price feed at `price` lamports per credit
+    pub fn initialize_feed(ctx: Context<InitializeFeed>, price: u64) -> Result<()> {
+        require!(price > 0, ErrorCode::ZeroPrice);
+
+        let feed = &mut ctx.accounts.feed;
+        feed.authority = ctx.accounts.authority.key();
+        feed.price = price;
+        feed.bump = ctx.bumps.feed;
+
+        msg!("Feed publishing {} lamports per credit", price);
+        Ok(())
+    }
+
+    /// Publish `price` lamports per credit; the feed's authority only
+    pub fn publish_price(ctx: Context<PublishPrice>, price: u64) -> Result<()> {
+        require!(price > 0, ErrorCode::ZeroPrice);
+        ctx.accounts.feed.price = price;
+
+        msg!("Price set to {} lamports per credit", price);
+        Ok(())
+    }
+
+    /// Create the admin's desk priced by `oracle`, paying fees to `fee_recipient`, as config epoch 0
+    pub fn initialize_desk(ctx: Context<InitializeDesk>) -> Result<()> {
+        let oracle = ctx.accounts.oracle.key();
+        let fee_recipient = ctx.accounts.fee_recipient.key();
+
+        let desk = &mut ctx.accounts.desk;
+        desk.admin = ctx.accounts.admin.key();
+        desk.oracle = oracle;
+        desk.fee_recipient = fee_recipient;
+        desk.epoch = 0;
+        desk.pending_config = None;
+        desk.bump = ctx.bumps.desk;
+
+        let config_epoch = &mut ctx.accounts.config_epoch;
+        config_epoch.desk = desk.key();
+        config_epoch.index = 0;
+        config_epoch.oracle = oracle;
+        config_epoch.fee_recipient = fee_recipient;
+        config_epoch.activated_at = Clock::get()?.unix_timestamp;
+        config_epoch.bump = ctx.bumps.config_epoch;
+
+        msg!("Desk priced by {}, paying fees to {}", oracle, fee_recipient);
+        Ok(())
+    }
+
+    /// Open the signer's position with `credits`, paid for at the oracle's price
+    pub fn buy(ctx: Context<Buy>, credits: u64) -> Result<()> {
+        require!(credits > 0, ErrorCode::ZeroAmount);
+        let cost = credits.checked_mul(ctx.accounts.oracle.price).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.desk.to_account_info(),
+                },
+            ),
+            cost,
+        )?;
+
+        let position = &mut ctx.accounts.position;
+        position.desk = ctx.accounts.desk.key();
+        position.owner = ctx.accounts.owner.key();
+        position.credits = credits;
+        position.redeeming = 0;
+        position.requested_at = 0;
+        position.epoch = 0;
+        position.bump = ctx.bumps.position;
+
+        msg!("Bought {} credits for {} lamports", credits, cost);
+        Ok(())
+    }
+
+    /// Request to redeem `credits`, settled once SETTLEMENT_DELAY has passed
+    ///
+    /// The request records the config epoch in force, which the secure
+    /// settlement honours whatever the config is by then.
+    pub fn request_redemption(ctx: Context<RequestRedemption>, credits: u64) -> Result<()> {
+        require!(credits > 0, ErrorCode::ZeroAmount);
+        let position = &mut ctx.accounts.position;
+        require!(position.redeeming == 0, ErrorCode::RedemptionPending);
+        require!(credits <= position.credits, ErrorCode::InsufficientCredits);
+
+        position.credits -= credits;
+        position.redeeming = credits;
+        position.requested_at = Clock::get()?.unix_timestamp;
+        position.epoch = ctx.accounts.desk.epoch;
+
+        msg!("Redemption of {} credits requested in epoch {}", credits, position.epoch);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Point the desk at `oracle` and `fee_recipient`, from the next instruction on
+    ///
+    /// Security Issue: The change is in force the moment it lands, for
+    /// every request already in flight. Whoever holds the admin key - its
+    /// owner, or whoever stole it - points the desk at a feed they publish
+    /// and at their own wallet, then cranks the requests waiting to
+    /// settle: their own credits are paid at any price they like, and
+    /// every other user's fee is theirs. Nobody who requested under the
+    /// old config had a chance to leave.
+    #[vulnerable]
+    pub fn vulnerable_set_config(ctx: Context<UpdateConfig>) -> Result<()> {
+        // VULNERABILITY: Live config, swapped under every request in flight
+        let desk = &mut ctx.accounts.desk;
+        desk.oracle = ctx.accounts.oracle.key();
+        desk.fee_recipient = ctx.accounts.fee_recipient.key();
+
+        msg!("Desk priced by {}, paying fees to {}", desk.oracle, desk.fee_recipient);
+        Ok(())
+    }
+
+    /// VULNERABLE: Settle the owner's redemption at the desk's config as it is now
+    ///
+    /// Security Issue: The oracle and fee recipient are whatever the desk
+    /// holds at settlement, not what the user requested under.
+    #[vulnerable]
+    pub fn vulnerable_settle_redemption(ctx: Context<SettleRedemption>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        // VULNERABILITY: Priced and charged by the config of the moment
+        let price = ctx.accounts.oracle.price;
+        settle(
+            &ctx.accounts.desk.to_account_info(),
+            &mut ctx.accounts.position,
+            price,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.fee_recipient.to_account_info(),
+            now,
+        )
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version: config changes are
+    // announced, timelocked, and activated as a new epoch, and every
+    // request settles under the epoch it was made in.
+
+    /// SECURE: Phase 1, propose `oracle` and `fee_recipient` as the next config epoch; admin only
+    ///
+    /// Security Fix: The change is recorded as an intent executable
+    /// CONFIG_DELAY seconds later and emitted as `ConfigChangeProposed`,
+    /// so users see it two days before it applies to anything. A new
+    /// proposal replaces the pending one and restarts the delay.
+    #[secure]
+    pub fn propose_config(ctx: Context<UpdateConfig>) -> Result<()> {
+        let oracle = ctx.accounts.oracle.key();
+        let fee_recipient = ctx.accounts.fee_recipient.key();
+
+        // SECURITY: Activated only after the delay
+        let now = Clock::get()?.unix_timestamp;
+        let desk = &mut ctx.accounts.desk;
+        let intent = Intent::prepare(desk.admin, config_params(&oracle, &fee_recipient), now, CONFIG_DELAY)?;
+
+        // SECURITY: Announced in a form indexers decode
+        emit!(ConfigChangeProposed {
+            desk: desk.key(),
+            epoch: desk.epoch + 1,
+            oracle,
+            fee_recipient,
+            executable_at: intent.executable_at,
+        });
+        desk.pending_config = Some(intent);
+
+        msg!("Config epoch {} proposed", desk.epoch + 1);
+        Ok(())
+    }
+
+    /// SECURE: Phase 2, activate the proposed config as a new epoch once the delay is over; admin only
+    ///
+    /// Security Fix: Only the config that was proposed and announced, and
+    /// only once its delay has passed. It is written to a new
+    /// `ConfigEpoch` snapshot for requests made from now on; requests
+    /// already in flight keep the epoch they were made in.
+    #[secure]
+    pub fn activate_config(ctx: Context<ActivateConfig>) -> Result<()> {
+        let oracle = ctx.accounts.oracle.key();
+        let fee_recipient = ctx.accounts.fee_recipient.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        let desk = &mut ctx.accounts.desk;
+        let intent = desk.pending_config.take().ok_or(ErrorCode::NoConfigChangeProposed)?;
+        // SECURITY: The announced config, after the announced delay
+        intent.assert_executable(&config_params(&oracle, &fee_recipient), now)?;
+
+        // SECURITY: A new snapshot; earlier epochs are never written again
+        desk.epoch += 1;
+        desk.oracle = oracle;
+        desk.fee_recipient = fee_recipient;
+        let config_epoch = &mut ctx.accounts.config_epoch;
+        config_epoch.desk = desk.key();
+        config_epoch.index = desk.epoch;
+        config_epoch.oracle = oracle;
+        config_epoch.fee_recipient = fee_recipient;
+        config_epoch.activated_at = now;
+        config_epoch.bump = ctx.bumps.config_epoch;
+
+        emit!(ConfigEpochActivated {
+            desk: desk.key(),
+            epoch: desk.epoch,
+            oracle,
+            fee_recipient,
+        });
+
+        msg!("Config epoch {} in force", desk.epoch);
+        Ok(())
+    }
+
+    /// SECURE: Settle the owner's redemption at the config of the epoch it was requested in
+    ///
+    /// Security Fix: The `ConfigEpoch` is found by the epoch the position
+    /// recorded, and the oracle and fee recipient must be that snapshot's.
+    /// A config changed after the request, legitimately or with a stolen
+    /// key, does not reach it.
+    #[secure]
+    pub fn secure_settle_redemption(ctx: Context<SettleAtEpoch>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        // SECURITY: Priced and charged by the request's own epoch
+        let price = ctx.accounts.oracle.price;
+        settle(
+            &ctx.accounts.desk.to_account_info(),
+            &mut ctx.accounts.position,
+            price,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.fee_recipient.to_account_info(),
+            now,
+        )
+    }
+}
+
+/// Parameters of a config change, as recorded in its intent
+#[cfg(feature = "secure")]
+fn config_params(oracle: &Pubkey, fee_recipient: &Pubkey) -> [u8; 32] {
+    hash_params(&[b"config", oracle.as_ref(), fee_recipient.as_ref()])
+}
+
+/// Pay the position's redemption in flight at `price` from the desk: the fee to `fee_recipient`, the rest to `owner`
+fn settle(
+    desk: &AccountInfo,
+    position: &mut Position,
+    price: u64,
+    owner: &AccountInfo,
+    fee_recipient: &AccountInfo,
+    now: i64,
+) -> Result<()> {
+    require!(position.redeeming > 0, ErrorCode::NoRedemption);
+    let ready_at = position.requested_at.checked_add(SETTLEMENT_DELAY).ok_or(ErrorCode::ArithmeticOverflow)?;
+    require!(now >= ready_at, ErrorCode::RedemptionNotReady);
+
+    let value = position.redeeming.checked_mul(price).ok_or(ErrorCode::ArithmeticOverflow)?;
+    let fee = value * u64::from(REDEMPTION_FEE_BPS) / u64::from(BPS);
+    let available = desk.lamports().saturating_sub(minimum_balance(desk.data_len())?);
+    require!(value <= available, ErrorCode::InsufficientReserve);
+
+    **desk.try_borrow_mut_lamports()? = desk.lamports() - value;
+    **owner.try_borrow_mut_lamports()? =
+        owner.lamports().checked_add(value - fee).ok_or(ErrorCode::ArithmeticOverflow)?;
+    **fee_recipient.try_borrow_mut_lamports()? =
+        fee_recipient.lamports().checked_add(fee).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    msg!("Redeemed {} credits for {} lamports, less a {} lamport fee", position.redeeming, value, fee);
+    position.redeeming = 0;
+    Ok(())
+}
+
+// ========================================
+// EVENTS
+// ========================================
+
+/// A config change proposed; it may be activated as `epoch` from `executable_at`
+#[event]
+pub struct ConfigChangeProposed {
+    pub desk: Pubkey,
+    pub epoch: u64,
+    pub oracle: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub executable_at: i64,
+}
+
+/// A config epoch in force for requests made from now on
+#[event]
+pub struct ConfigEpochActivated {
+    pub desk: Pubkey,
+    pub epoch: u64,
+    pub oracle: Pubkey,
+    pub fee_recipient: Pubkey,
+}
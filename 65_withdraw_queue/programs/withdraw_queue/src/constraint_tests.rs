@@ -0,0 +1,426 @@
+//! A vault with most of its lamports deployed and a queue of withdrawals:
+//! how a ticket chosen by the caller jumps the queue and strands the user
+//! it jumped, the secure version's counter and the crank that pays only
+//! the head's own request, the paging every crank shares, then every
+//! constraint on the contexts.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use secref_testkit::{
+    assert_constraint_violation, assert_seeds_violation, assert_signer_violation, execute, Fixture, TestAccount,
+};
+
+use crate::{
+    instruction, Deposit, ErrorCode as QueueError, InitializeVault, ManageFunds, Position, ProcessQueue,
+    SecureRequestWithdrawal, Vault, VulnerableRequestWithdrawal, WithdrawRequest, MAX_PAGE,
+};
+
+/// What Alice, Bob and Mallory each deposited: 1 SOL
+const DEPOSIT: u64 = 1_000_000_000;
+/// Field names of the requests and owners in a crank's page, in order
+const PAGE: [(&str, &str); MAX_PAGE + 1] = [
+    ("request_0", "owner_0"),
+    ("request_1", "owner_1"),
+    ("request_2", "owner_2"),
+    ("request_3", "owner_3"),
+    ("request_4", "owner_4"),
+    ("request_5", "owner_5"),
+    ("request_6", "owner_6"),
+    ("request_7", "owner_7"),
+    ("request_8", "owner_8"),
+];
+
+fn vault_address(admin: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vault", admin.as_ref()], &crate::ID)
+}
+
+fn position_address(vault: Pubkey, owner: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"position", vault.as_ref(), owner.as_ref()], &crate::ID)
+}
+
+fn request_address(vault: Pubkey, ticket: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"request", vault.as_ref(), &ticket.to_le_bytes()], &crate::ID)
+}
+
+fn crafted_request_address(vault: Pubkey, owner: Pubkey, ticket: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"request", vault.as_ref(), owner.as_ref(), &ticket.to_le_bytes()], &crate::ID)
+}
+
+/// The admin's vault, `next_ticket` tickets given out and `head` paid
+struct Queue {
+    admin: Pubkey,
+    next_ticket: u64,
+    head: u64,
+    queued: u64,
+}
+
+impl Queue {
+    fn new(next_ticket: u64, head: u64, queued: u64) -> Self {
+        Self {
+            admin: Pubkey::new_unique(),
+            next_ticket,
+            head,
+            queued,
+        }
+    }
+
+    fn key(&self) -> Pubkey {
+        vault_address(self.admin).0
+    }
+
+    /// The vault, holding `idle` lamports beyond its rent
+    fn vault(&self, idle: u64) -> TestAccount {
+        let (address, bump) = vault_address(self.admin);
+        TestAccount::anchor(&Vault {
+            admin: self.admin,
+            deployed: 0,
+            queued: self.queued,
+            next_ticket: self.next_ticket,
+            head: self.head,
+            bump,
+        })
+        .at(address)
+        .with_lamports(Rent::default().minimum_balance(Vault::SPACE) + idle)
+    }
+
+    fn position(&self, owner: Pubkey, deposited: u64) -> TestAccount {
+        let (address, bump) = position_address(self.key(), owner);
+        TestAccount::anchor(&Position {
+            vault: self.key(),
+            owner,
+            deposited,
+            bump,
+        })
+        .at(address)
+    }
+
+    /// `owner`'s request for `amount` at `ticket`, at the address the counter gives it
+    fn request(&self, owner: Pubkey, ticket: u64, amount: u64) -> TestAccount {
+        let (address, bump) = request_address(self.key(), ticket);
+        self.request_at(address, bump, owner, ticket, amount)
+    }
+
+    /// `owner`'s request for `amount` at `ticket`, at the address the caller chose
+    fn crafted_request(&self, owner: Pubkey, ticket: u64, amount: u64) -> TestAccount {
+        let (address, bump) = crafted_request_address(self.key(), owner, ticket);
+        self.request_at(address, bump, owner, ticket, amount)
+    }
+
+    fn request_at(&self, address: Pubkey, bump: u8, owner: Pubkey, ticket: u64, amount: u64) -> TestAccount {
+        TestAccount::anchor(&WithdrawRequest {
+            vault: self.key(),
+            owner,
+            ticket,
+            amount,
+            bump,
+        })
+        .at(address)
+    }
+}
+
+/// A crank over `requests`, each followed by its owner, with `idle` lamports in the vault
+fn crank_fixture(queue: &Queue, idle: u64, requests: Vec<TestAccount>) -> Fixture {
+    requests.into_iter().zip(PAGE).fold(
+        Fixture::new().with("vault", queue.vault(idle)),
+        |fixture, (request, (request_name, owner_name))| {
+            let owner = TestAccount::system(0).at(request.state::<WithdrawRequest>().owner);
+            fixture.with(request_name, request).with(owner_name, owner)
+        },
+    )
+}
+
+fn initialize_fixture() -> Fixture {
+    let admin = TestAccount::signer();
+    Fixture::new()
+        .with("vault", TestAccount::uninitialized(Vault::SPACE).at(vault_address(admin.key).0))
+        .with("admin", admin)
+        .with("system_program", TestAccount::system_program())
+}
+
+fn deposit_fixture(queue: &Queue) -> Fixture {
+    let owner = TestAccount::signer();
+    let position = position_address(queue.key(), owner.key).0;
+    Fixture::new()
+        .with("vault", queue.vault(0))
+        .with("position", TestAccount::uninitialized(Position::SPACE).at(position))
+        .with("owner", owner)
+        .with("system_program", TestAccount::system_program())
+}
+
+fn manage_fixture(queue: &Queue, idle: u64) -> Fixture {
+    Fixture::new()
+        .with("vault", queue.vault(idle))
+        .with("admin", TestAccount::signer().at(queue.admin))
+        .with("system_program", TestAccount::system_program())
+}
+
+/// A request by a user holding DEPOSIT, for the counter's next ticket
+fn secure_request_fixture(queue: &Queue) -> Fixture {
+    let owner = TestAccount::signer();
+    let request = request_address(queue.key(), queue.next_ticket).0;
+    Fixture::new()
+        .with("vault", queue.vault(0))
+        .with("position", queue.position(owner.key, DEPOSIT))
+        .with("request", TestAccount::uninitialized(WithdrawRequest::SPACE).at(request))
+        .with("owner", owner)
+        .with("system_program", TestAccount::system_program())
+}
+
+/// A request by a user holding DEPOSIT, for `ticket`
+fn vulnerable_request_fixture(queue: &Queue, ticket: u64) -> Fixture {
+    let owner = TestAccount::signer();
+    let request = crafted_request_address(queue.key(), owner.key, ticket).0;
+    Fixture::new()
+        .with("vault", queue.vault(0))
+        .with("position", queue.position(owner.key, DEPOSIT))
+        .with("request", TestAccount::uninitialized(WithdrawRequest::SPACE).at(request))
+        .with("owner", owner)
+        .with("system_program", TestAccount::system_program())
+        .args(instruction::VulnerableRequestWithdrawal { ticket, amount: DEPOSIT })
+}
+
+/// Whether `name` has been paid and closed: its rent back to its owner, its lamports gone
+fn closed(fixture: &Fixture, name: &str) -> bool {
+    let request = fixture.account(name);
+    (request.owner, request.lamports, request.data.len()) == (system_program::ID, 0, 0)
+}
+
+#[test]
+fn deposit_opens_the_position_and_funds_the_vault() {
+    let queue = Queue::new(0, 0, 0);
+    let mut fixture = deposit_fixture(&queue);
+    assert_eq!(
+        execute!(&mut fixture.clone(), instruction::Deposit { amount: 0 }),
+        Err(Error::from(QueueError::ZeroAmount).into())
+    );
+
+    execute!(&mut fixture, instruction::Deposit { amount: DEPOSIT }).unwrap();
+    assert_eq!(fixture.account("vault").lamports, Rent::default().minimum_balance(Vault::SPACE) + DEPOSIT);
+    let position = fixture.state::<Position>("position");
+    assert_eq!((position.vault, position.owner, position.deposited), (queue.key(), fixture.key("owner"), DEPOSIT));
+}
+
+#[test]
+fn the_admin_deploys_idle_lamports_and_returns_them() {
+    let queue = Queue::new(0, 0, 0);
+    let mut fixture = manage_fixture(&queue, DEPOSIT);
+    assert_eq!(
+        execute!(&mut fixture.clone(), instruction::Deploy { amount: DEPOSIT + 1 }),
+        Err(Error::from(QueueError::InsufficientLiquidity).into())
+    );
+
+    let admin = fixture.account("admin").lamports;
+    execute!(&mut fixture, instruction::Deploy { amount: 700 }).unwrap();
+    assert_eq!(fixture.state::<Vault>("vault").deployed, 700);
+    assert_eq!(fixture.account("admin").lamports, admin + 700);
+
+    execute!(&mut fixture, instruction::ReturnFunds { amount: 500 }).unwrap();
+    assert_eq!(fixture.state::<Vault>("vault").deployed, 200);
+    // Returning more than is out counts the rest as yield
+    execute!(&mut fixture, instruction::ReturnFunds { amount: 500 }).unwrap();
+    assert_eq!(fixture.state::<Vault>("vault").deployed, 0);
+    assert_eq!(fixture.account("vault").lamports, Rent::default().minimum_balance(Vault::SPACE) + DEPOSIT + 300);
+}
+
+#[test]
+fn secure_requests_take_the_counters_next_ticket() {
+    let queue = Queue::new(3, 1, 0);
+    let mut fixture = secure_request_fixture(&queue);
+    assert_eq!(
+        execute!(&mut fixture.clone(), instruction::SecureRequestWithdrawal { amount: DEPOSIT + 1 }),
+        Err(Error::from(QueueError::InsufficientDeposit).into())
+    );
+
+    execute!(&mut fixture, instruction::SecureRequestWithdrawal { amount: 400 }).unwrap();
+    let request = fixture.state::<WithdrawRequest>("request");
+    assert_eq!(
+        (request.vault, request.owner, request.ticket, request.amount),
+        (queue.key(), fixture.key("owner"), 3, 400)
+    );
+    let vault = fixture.state::<Vault>("vault");
+    assert_eq!((vault.next_ticket, vault.head, vault.queued), (4, 1, 400));
+    assert_eq!(fixture.state::<Position>("position").deposited, DEPOSIT - 400);
+}
+
+#[test]
+fn vulnerable_request_jumps_the_queue_and_strands_the_head() {
+    // Alice holds ticket 0 and Bob ticket 1, for 1 SOL each; the vault's
+    // lamports are deployed
+    let queue = Queue::new(2, 0, 2 * DEPOSIT);
+    let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let alices = queue.crafted_request(alice, 0, DEPOSIT);
+    let bobs = queue.crafted_request(bob, 1, DEPOSIT);
+
+    // Mallory queues at Alice's ticket; the counter does not move
+    let mut fixture = vulnerable_request_fixture(&queue, 0);
+    execute!(&mut fixture, instruction::VulnerableRequestWithdrawal { ticket: 0, amount: DEPOSIT }).unwrap();
+    let mallorys = fixture.account("request").clone();
+    let vault = fixture.state::<Vault>("vault");
+    assert_eq!((vault.next_ticket, vault.queued), (2, 3 * DEPOSIT));
+
+    // 1 SOL comes back, and Mallory cranks her own request before the keeper cranks Alice's
+    let queue = Queue { queued: 3 * DEPOSIT, ..queue };
+    let mut fixture = crank_fixture(&queue, DEPOSIT, vec![mallorys]);
+    execute!(&mut fixture, instruction::VulnerableProcessQueue {}).unwrap();
+    assert!(closed(&fixture, "request_0"));
+    let rent = Rent::default().minimum_balance(WithdrawRequest::SPACE);
+    assert_eq!(fixture.account("owner_0").lamports, DEPOSIT + rent);
+    assert_eq!(fixture.state::<Vault>("vault").head, 1);
+
+    // The head has passed Alice's ticket: her request can never be paid, and Bob is paid ahead of her
+    let queue = Queue { head: 1, queued: 2 * DEPOSIT, ..queue };
+    assert_eq!(
+        execute!(&mut crank_fixture(&queue, DEPOSIT, vec![alices]), instruction::VulnerableProcessQueue {}),
+        Err(Error::from(QueueError::TicketOutOfOrder).into())
+    );
+    let mut fixture = crank_fixture(&queue, DEPOSIT, vec![bobs]);
+    execute!(&mut fixture, instruction::VulnerableProcessQueue {}).unwrap();
+    assert!(closed(&fixture, "request_0"));
+
+    // A ticket the head has passed cannot be requested, which is all the guard there is
+    let mut fixture = vulnerable_request_fixture(&queue, 0);
+    assert_eq!(
+        execute!(&mut fixture, instruction::VulnerableRequestWithdrawal { ticket: 0, amount: DEPOSIT }),
+        Err(Error::from(QueueError::TicketAlreadyPaid).into())
+    );
+}
+
+#[test]
+fn secure_crank_pays_only_the_request_at_the_heads_address() {
+    let queue = Queue::new(1, 0, 2 * DEPOSIT);
+    let mallorys = queue.crafted_request(Pubkey::new_unique(), 0, DEPOSIT);
+    assert_eq!(
+        execute!(&mut crank_fixture(&queue, DEPOSIT, vec![mallorys]), instruction::SecureProcessQueue {}),
+        Err(Error::from(QueueError::TicketOutOfOrder).into())
+    );
+
+    let alices = queue.request(Pubkey::new_unique(), 0, DEPOSIT);
+    let mut fixture = crank_fixture(&queue, DEPOSIT, vec![alices]);
+    execute!(&mut fixture, instruction::SecureProcessQueue {}).unwrap();
+    assert!(closed(&fixture, "request_0"));
+    let vault = fixture.state::<Vault>("vault");
+    assert_eq!((vault.head, vault.queued), (1, DEPOSIT));
+}
+
+#[test]
+fn cranks_pay_in_ticket_order_and_stop_at_the_first_unpayable_request() {
+    let queue = Queue::new(3, 0, 700);
+    let requests = vec![
+        queue.request(Pubkey::new_unique(), 0, 100),
+        queue.request(Pubkey::new_unique(), 1, 500),
+        queue.request(Pubkey::new_unique(), 2, 100),
+    ];
+    let mut fixture = crank_fixture(&queue, 300, requests.clone());
+    execute!(&mut fixture, instruction::SecureProcessQueue {}).unwrap();
+
+    // Ticket 2 is affordable, but waits behind ticket 1
+    assert!(closed(&fixture, "request_0"));
+    assert!(!closed(&fixture, "request_1") && !closed(&fixture, "request_2"));
+    let vault = fixture.state::<Vault>("vault");
+    assert_eq!((vault.head, vault.queued), (1, 600));
+
+    // Out of order, even with the lamports to pay it
+    let queue = Queue { head: 1, queued: 600, ..queue };
+    assert_eq!(
+        execute!(&mut crank_fixture(&queue, 300, vec![requests[2].clone()]), instruction::SecureProcessQueue {}),
+        Err(Error::from(QueueError::TicketOutOfOrder).into())
+    );
+}
+
+#[test]
+fn overlapping_pages_skip_the_requests_already_paid() {
+    let queue = Queue::new(3, 0, 300);
+    let requests: Vec<TestAccount> = (0..3).map(|ticket| queue.request(Pubkey::new_unique(), ticket, 100)).collect();
+    let mut first = crank_fixture(&queue, 300, requests[..2].to_vec());
+    execute!(&mut first, instruction::SecureProcessQueue {}).unwrap();
+
+    // A second keeper's page, built before the first landed, covers tickets 1 and 2
+    let queue = Queue { head: 2, queued: 100, ..queue };
+    let mut page = crank_fixture(&queue, 100, vec![requests[1].clone(), requests[2].clone()]);
+    *page.account_mut("request_0") = first.account("request_1").clone();
+    execute!(&mut page, instruction::SecureProcessQueue {}).unwrap();
+    assert!(closed(&page, "request_1"));
+    assert_eq!(page.state::<Vault>("vault").head, 3);
+}
+
+#[test]
+fn pages_are_bounded_and_pay_each_request_to_its_owner() {
+    let queue = Queue::new(MAX_PAGE as u64 + 1, 0, 0);
+    assert_eq!(
+        execute!(&mut crank_fixture(&queue, 0, vec![]), instruction::SecureProcessQueue {}),
+        Err(Error::from(QueueError::EmptyPage).into())
+    );
+    let requests: Vec<TestAccount> =
+        (0..=MAX_PAGE as u64).map(|ticket| queue.request(Pubkey::new_unique(), ticket, 1)).collect();
+    assert_eq!(
+        execute!(&mut crank_fixture(&queue, 0, requests.clone()), instruction::SecureProcessQueue {}),
+        Err(Error::from(QueueError::InvalidPage).into())
+    );
+    let unpaired = Fixture::new().with("vault", queue.vault(0)).with("request_0", requests[0].clone());
+    assert_eq!(
+        execute!(&mut unpaired.clone(), instruction::SecureProcessQueue {}),
+        Err(Error::from(QueueError::InvalidPage).into())
+    );
+
+    let mut fixture = crank_fixture(&queue, 1, vec![requests[0].clone()]);
+    *fixture.account_mut("owner_0") = TestAccount::system(0);
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureProcessQueue {}),
+        Err(Error::from(QueueError::RequestOwnerMismatch).into())
+    );
+    let other = Queue::new(1, 0, 1);
+    let mut fixture = crank_fixture(&queue, 1, vec![other.request(Pubkey::new_unique(), 0, 1)]);
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureProcessQueue {}),
+        Err(Error::from(QueueError::RequestVaultMismatch).into())
+    );
+}
+
+#[test]
+fn vaults_and_positions_belong_to_their_signers() {
+    assert_seeds_violation!(InitializeVault, initialize_fixture(), "vault");
+    assert_signer_violation!(InitializeVault, initialize_fixture(), "admin");
+
+    let queue = Queue::new(0, 0, 0);
+    assert_seeds_violation!(Deposit, deposit_fixture(&queue), "vault");
+    assert_seeds_violation!(Deposit, deposit_fixture(&queue), "position");
+    assert_signer_violation!(Deposit, deposit_fixture(&queue), "owner");
+}
+
+#[test]
+fn only_the_admin_moves_the_vaults_lamports() {
+    let queue = Queue::new(0, 0, 0);
+    assert_seeds_violation!(ManageFunds, manage_fixture(&queue, 0), "vault");
+    assert_constraint_violation!(
+        ManageFunds,
+        manage_fixture(&queue, 0),
+        "vault" => |vault| vault.edit(|stored: &mut Vault| stored.admin = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_signer_violation!(ManageFunds, manage_fixture(&queue, 0), "admin");
+}
+
+#[test]
+fn requests_come_from_the_owners_position() {
+    let queue = Queue::new(2, 0, 0);
+    assert_constraint_violation!(
+        SecureRequestWithdrawal,
+        secure_request_fixture(&queue),
+        "position" => |position| position.edit(|stored: &mut Position| stored.vault = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_seeds_violation!(SecureRequestWithdrawal, secure_request_fixture(&queue), "position");
+    assert_seeds_violation!(SecureRequestWithdrawal, secure_request_fixture(&queue), "request");
+    assert_signer_violation!(SecureRequestWithdrawal, secure_request_fixture(&queue), "owner");
+
+    assert_seeds_violation!(VulnerableRequestWithdrawal, vulnerable_request_fixture(&queue, 5), "position");
+    assert_seeds_violation!(VulnerableRequestWithdrawal, vulnerable_request_fixture(&queue, 5), "request");
+    assert_signer_violation!(VulnerableRequestWithdrawal, vulnerable_request_fixture(&queue, 5), "owner");
+}
+
+#[test]
+fn cranks_use_the_admins_vault() {
+    let queue = Queue::new(1, 0, 1);
+    let fixture = crank_fixture(&queue, 1, vec![queue.request(Pubkey::new_unique(), 0, 1)]);
+    assert_seeds_violation!(ProcessQueue, fixture, "vault");
+}
@@ -0,0 +1,27 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Amount must be more than zero")]
+    ZeroAmount,
+    #[msg("Position holds less than requested")]
+    InsufficientDeposit,
+    #[msg("Vault holds too few idle lamports")]
+    InsufficientLiquidity,
+    #[msg("Ticket has already been paid")]
+    TicketAlreadyPaid,
+    #[msg("Request is not the next in the queue")]
+    TicketOutOfOrder,
+    #[msg("Request belongs to another vault")]
+    RequestVaultMismatch,
+    #[msg("Request must be followed by its owner")]
+    RequestOwnerMismatch,
+    #[msg("No requests to process")]
+    EmptyPage,
+    #[msg("A page holds at most MAX_PAGE requests, each followed by its owner")]
+    InvalidPage,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
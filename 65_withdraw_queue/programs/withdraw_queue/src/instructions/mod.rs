@@ -0,0 +1,117 @@
+//! Account contexts, one per instruction
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct InitializeVault<'info> {
+    #[account(init, payer = admin, space = Vault::SPACE, seeds = [b"vault", admin.key().as_ref()], bump)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut, seeds = [b"vault", vault.admin.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = Position::SPACE,
+        seeds = [b"position", vault.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Move the vault's lamports out to the admin or back
+#[derive(Accounts)]
+pub struct ManageFunds<'info> {
+    #[account(mut, seeds = [b"vault", admin.key().as_ref()], bump = vault.bump, has_one = admin)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Join the queue at `ticket`, whatever the vault's counter says
+#[derive(Accounts)]
+#[instruction(ticket: u64)]
+pub struct VulnerableRequestWithdrawal<'info> {
+    #[account(mut, seeds = [b"vault", vault.admin.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"position", vault.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump,
+        has_one = vault,
+        has_one = owner
+    )]
+    pub position: Account<'info, Position>,
+
+    // The ticket is the caller's to choose, and the owner in the seeds
+    // lets every caller choose the same one
+    #[account(
+        init,
+        payer = owner,
+        space = WithdrawRequest::SPACE,
+        seeds = [b"request", vault.key().as_ref(), owner.key().as_ref(), &ticket.to_le_bytes()],
+        bump
+    )]
+    pub request: Account<'info, WithdrawRequest>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Join the queue at the vault's next ticket
+#[derive(Accounts)]
+pub struct SecureRequestWithdrawal<'info> {
+    #[account(mut, seeds = [b"vault", vault.admin.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"position", vault.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump,
+        has_one = vault,
+        has_one = owner
+    )]
+    pub position: Account<'info, Position>,
+
+    // One address per ticket, and only the counter's next one can be created
+    #[account(
+        init,
+        payer = owner,
+        space = WithdrawRequest::SPACE,
+        seeds = [b"request", vault.key().as_ref(), &vault.next_ticket.to_le_bytes()],
+        bump
+    )]
+    pub request: Account<'info, WithdrawRequest>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Pay the requests in `remaining_accounts`, each followed by its owner; anyone may crank it
+#[derive(Accounts)]
+pub struct ProcessQueue<'info> {
+    #[account(mut, seeds = [b"vault", vault.admin.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+}
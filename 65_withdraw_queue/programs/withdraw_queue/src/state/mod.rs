@@ -0,0 +1,58 @@
+//! Accounts the program owns
+
+use super::*;
+
+#[account]
+pub struct Vault {
+    /// Deploys the vault's lamports and returns them (32 bytes)
+    pub admin: Pubkey,
+    /// Lamports deployed out of the vault and not yet returned (8 bytes)
+    pub deployed: u64,
+    /// Lamports requested and not yet paid (8 bytes)
+    pub queued: u64,
+    /// Ticket the next request is given (8 bytes)
+    pub next_ticket: u64,
+    /// Ticket the crank pays next; every ticket below it has been paid (8 bytes)
+    pub head: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Vault {
+    pub const SPACE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 1;
+}
+
+#[account]
+pub struct Position {
+    /// Vault the lamports were deposited in (32 bytes)
+    pub vault: Pubkey,
+    /// Owns the deposit and receives its withdrawals (32 bytes)
+    pub owner: Pubkey,
+    /// Lamports deposited and not requested (8 bytes)
+    pub deposited: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl Position {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+/// A place in the vault's queue, closed to its owner when it is paid
+#[account]
+pub struct WithdrawRequest {
+    /// Vault whose queue this is (32 bytes)
+    pub vault: Pubkey,
+    /// Receives the lamports, and the request's rent (32 bytes)
+    pub owner: Pubkey,
+    /// Place in the queue; paid once the vault's head reaches it (8 bytes)
+    pub ticket: u64,
+    /// Lamports to pay (8 bytes)
+    pub amount: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+impl WithdrawRequest {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8 + 1;
+}
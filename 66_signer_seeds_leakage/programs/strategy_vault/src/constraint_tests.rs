@@ -0,0 +1,176 @@
+//! Every constraint on the secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::runtime::account_already_in_use;
+use secref_testkit::{
+    assert_constraint_violation, assert_has_one_violation, assert_owner_violation, assert_seeds_violation,
+    assert_signer_violation, Fixture, TestAccount,
+};
+
+use crate::{SecureExecute, SecureOpenVault, SecureWithdraw, Vault, CUSTODY_TAG, EXECUTE_TAG};
+
+const VAULT_SPACE: usize = 8 + 32 + 32 + 32 + 1 + 1 + 1;
+const TOKEN_ACCOUNT_SPACE: usize = 165;
+
+fn pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &crate::ID)
+}
+
+/// A secure vault at its PDA, with its token account and both signing authorities
+fn vault_accounts(admin: Pubkey, keeper: Pubkey, mint: Pubkey) -> [TestAccount; 4] {
+    let (address, bump) = pda(&[b"secure_vault", admin.as_ref(), mint.as_ref()]);
+    let (custody, custody_bump) = pda(&[b"authority", address.as_ref(), CUSTODY_TAG]);
+    let (execute, execute_bump) = pda(&[b"authority", address.as_ref(), EXECUTE_TAG]);
+    let (tokens, _) = pda(&[b"vault_tokens", address.as_ref()]);
+    let vault = TestAccount::anchor(&Vault {
+        admin,
+        keeper,
+        mint,
+        custody_bump,
+        execute_bump,
+        bump,
+    })
+    .at(address);
+    [
+        vault,
+        TestAccount::token_account(mint, custody, 1_000).at(tokens),
+        TestAccount::system(0).at(custody),
+        TestAccount::system(0).at(execute),
+    ]
+}
+
+fn open_fixture() -> Fixture {
+    let admin = TestAccount::signer();
+    let mint = TestAccount::mint(Pubkey::new_unique(), 6, 1_000_000);
+    let (vault, _) = pda(&[b"secure_vault", admin.key.as_ref(), mint.key.as_ref()]);
+    let (tokens, _) = pda(&[b"vault_tokens", vault.as_ref()]);
+    let (custody, _) = pda(&[b"authority", vault.as_ref(), CUSTODY_TAG]);
+    let (execute, _) = pda(&[b"authority", vault.as_ref(), EXECUTE_TAG]);
+    Fixture::new()
+        .with("vault", TestAccount::uninitialized(VAULT_SPACE).at(vault))
+        .with("vault_tokens", TestAccount::uninitialized(TOKEN_ACCOUNT_SPACE).at(tokens))
+        .with("custody_authority", TestAccount::system(0).at(custody))
+        .with("execute_authority", TestAccount::system(0).at(execute))
+        .with("keeper", TestAccount::system(0))
+        .with("mint", mint)
+        .with("admin", admin)
+        .with("token_program", TestAccount::token_program())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn withdraw_fixture() -> Fixture {
+    let admin = TestAccount::signer();
+    let mint = Pubkey::new_unique();
+    let [vault, vault_tokens, custody_authority, _] = vault_accounts(admin.key, Pubkey::new_unique(), mint);
+    Fixture::new()
+        .with("vault", vault)
+        .with("vault_tokens", vault_tokens)
+        .with("custody_authority", custody_authority)
+        .with("destination", TestAccount::token_account(mint, admin.key, 0))
+        .with("admin", admin)
+        .with("token_program", TestAccount::token_program())
+}
+
+fn execute_fixture() -> Fixture {
+    let keeper = TestAccount::signer();
+    let [vault, _, _, execute_authority] = vault_accounts(Pubkey::new_unique(), keeper.key, Pubkey::new_unique());
+    Fixture::new()
+        .with("vault", vault)
+        .with("execute_authority", execute_authority)
+        .with("target_program", TestAccount::token_program())
+        .with("keeper", keeper)
+}
+
+#[test]
+fn secure_open_vault_derives_every_account_from_admin_and_mint() {
+    assert_seeds_violation!(SecureOpenVault, open_fixture(), "vault");
+    assert_seeds_violation!(SecureOpenVault, open_fixture(), "vault_tokens");
+    assert_seeds_violation!(SecureOpenVault, open_fixture(), "custody_authority");
+    assert_seeds_violation!(SecureOpenVault, open_fixture(), "execute_authority");
+}
+
+#[test]
+fn secure_open_vault_rejects_existing_token_accounts() {
+    assert_constraint_violation!(
+        SecureOpenVault,
+        open_fixture(),
+        "vault_tokens" => |tokens| {
+            *tokens = TestAccount::token_account(Pubkey::new_unique(), Pubkey::new_unique(), 0).at(tokens.key)
+        },
+        account_already_in_use()
+    );
+}
+
+#[test]
+fn secure_open_vault_requires_a_real_mint_and_admin_signature() {
+    assert_owner_violation!(SecureOpenVault, open_fixture(), "mint");
+    assert_signer_violation!(SecureOpenVault, open_fixture(), "admin");
+    assert_constraint_violation!(
+        SecureOpenVault,
+        open_fixture(),
+        "token_program" => TestAccount::program(Pubkey::new_unique()),
+        ErrorCode::InvalidProgramId
+    );
+}
+
+#[test]
+fn secure_withdraw_requires_the_vault_admin() {
+    assert_constraint_violation!(
+        SecureWithdraw,
+        withdraw_fixture(),
+        "vault" => |vault| vault.edit(|stored: &mut Vault| stored.admin = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_seeds_violation!(SecureWithdraw, withdraw_fixture(), "admin");
+    assert_signer_violation!(SecureWithdraw, withdraw_fixture(), "admin");
+}
+
+#[test]
+fn secure_withdraw_signs_only_with_the_custody_authority() {
+    assert_seeds_violation!(SecureWithdraw, withdraw_fixture(), "vault");
+    assert_seeds_violation!(SecureWithdraw, withdraw_fixture(), "vault_tokens");
+    assert_seeds_violation!(SecureWithdraw, withdraw_fixture(), "custody_authority");
+
+    // The vault's other authority is no substitute: its tag names another operation
+    let fixture = withdraw_fixture();
+    let (execute, _) = pda(&[b"authority", fixture.key("vault").as_ref(), EXECUTE_TAG]);
+    assert_constraint_violation!(
+        SecureWithdraw,
+        fixture,
+        "custody_authority" => TestAccount::system(0).at(execute),
+        ErrorCode::ConstraintSeeds
+    );
+}
+
+#[test]
+fn secure_withdraw_pays_out_the_vaults_mint() {
+    assert_constraint_violation!(
+        SecureWithdraw,
+        withdraw_fixture(),
+        "destination" => |destination| {
+            *destination = TestAccount::token_account(Pubkey::new_unique(), Pubkey::new_unique(), 0)
+        },
+        ErrorCode::ConstraintTokenMint
+    );
+}
+
+#[test]
+fn secure_execute_requires_the_vault_keeper() {
+    assert_has_one_violation!(SecureExecute, execute_fixture(), "keeper");
+    assert_signer_violation!(SecureExecute, execute_fixture(), "keeper");
+    assert_seeds_violation!(SecureExecute, execute_fixture(), "vault");
+}
+
+#[test]
+fn secure_execute_signs_only_with_the_execute_authority() {
+    assert_seeds_violation!(SecureExecute, execute_fixture(), "execute_authority");
+
+    let fixture = execute_fixture();
+    let (custody, _) = pda(&[b"authority", fixture.key("vault").as_ref(), CUSTODY_TAG]);
+    assert_constraint_violation!(
+        SecureExecute,
+        fixture,
+        "execute_authority" => TestAccount::system(0).at(custody),
+        ErrorCode::ConstraintSeeds
+    );
+}
@@ -0,0 +1,122 @@
+//! What `execute` can sign for: the keeper's approve, redirected from the
+//! strategy's budget to the vault's deposits.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::error::TokenError;
+use anchor_spl::token::spl_token::instruction::approve;
+use anchor_spl::token::{self, TokenAccount};
+use secref_testkit::{execute, Fixture, TestAccount};
+
+use crate::{instruction, Vault, CUSTODY_TAG, EXECUTE_TAG};
+
+const DEPOSITS: u64 = 1_000;
+const BUDGET: u64 = 100;
+
+fn pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &crate::ID)
+}
+
+/// A vault holding `DEPOSITS` with the accounts `execute` names, and the PDA owning the deposits
+///
+/// The vulnerable vault signs everything as `[b"authority", vault]`; the
+/// secure one keeps its deposits under the custody tag and signs the
+/// strategy's CPIs under the execute tag, at `authority`.
+fn vault(secure: bool) -> (Fixture, Pubkey) {
+    let admin = Pubkey::new_unique();
+    let keeper = TestAccount::signer();
+    let mint = Pubkey::new_unique();
+    let prefix: &[u8] = if secure { b"secure_vault" } else { b"vault" };
+    let (address, bump) = pda(&[prefix, admin.as_ref(), mint.as_ref()]);
+    let tags: [&[u8]; 2] = if secure { [CUSTODY_TAG, EXECUTE_TAG] } else { [&[], &[]] };
+    let (custody, custody_bump) = pda(&[b"authority", address.as_ref(), tags[0]]);
+    let (authority, execute_bump) = pda(&[b"authority", address.as_ref(), tags[1]]);
+    let vault = TestAccount::anchor(&Vault {
+        admin,
+        keeper: keeper.key,
+        mint,
+        custody_bump,
+        execute_bump,
+        bump,
+    })
+    .at(address);
+    let fixture = Fixture::new()
+        .with("vault", vault)
+        .with("authority", TestAccount::system(0).at(authority))
+        .with("target_program", TestAccount::token_program())
+        .with("keeper", keeper);
+    (fixture, custody)
+}
+
+/// The vault's token account, holding `DEPOSITS` for `custody`
+fn deposits(fixture: &Fixture, custody: Pubkey) -> TestAccount {
+    let (address, _) = pda(&[b"vault_tokens", fixture.key("vault").as_ref()]);
+    TestAccount::token_account(fixture.state::<Vault>("vault").mint, custody, DEPOSITS).at(address)
+}
+
+/// `execute`'s data and remaining accounts for an `Approve` of `source` to the keeper, signed by `owner`
+fn approve_keeper(fixture: Fixture, source: TestAccount, owner: Pubkey) -> (Fixture, Vec<u8>) {
+    let keeper = fixture.key("keeper");
+    let data = approve(&token::ID, &source.key, &keeper, &owner, &[], u64::MAX)
+        .unwrap()
+        .data;
+    let fixture = fixture
+        .with("source", source)
+        .with("delegate", TestAccount::system(0).at(keeper))
+        .with("owner", TestAccount::system(0).at(owner));
+    (fixture, data)
+}
+
+fn delegate(fixture: &Fixture) -> Option<Pubkey> {
+    fixture.state::<TokenAccount>("source").delegate.into()
+}
+
+#[test]
+fn vulnerable_execute_approves_the_keeper_over_the_deposits() {
+    let (fixture, custody) = vault(false);
+    let deposits = deposits(&fixture, custody);
+    let (mut fixture, data) = approve_keeper(fixture, deposits, custody);
+
+    execute!(&mut fixture, instruction::VulnerableExecute { data }).unwrap();
+
+    // The keeper can now spend every deposit through the Token program alone
+    assert_eq!(delegate(&fixture), Some(fixture.key("keeper")));
+    assert_eq!(fixture.state::<TokenAccount>("source").delegated_amount, u64::MAX);
+}
+
+#[test]
+fn secure_execute_signature_is_not_the_deposits_owner() {
+    let (fixture, custody) = vault(true);
+    let (deposits, authority) = (deposits(&fixture, custody), fixture.key("authority"));
+    let (mut fixture, data) = approve_keeper(fixture, deposits, authority);
+
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureExecute { data }),
+        Err(TokenError::OwnerMismatch.into())
+    );
+    assert_eq!(delegate(&fixture), None);
+}
+
+#[test]
+fn secure_execute_cannot_sign_as_the_custody_authority() {
+    let (fixture, custody) = vault(true);
+    let deposits = deposits(&fixture, custody);
+    let (mut fixture, data) = approve_keeper(fixture, deposits, custody);
+
+    assert_eq!(
+        execute!(&mut fixture, instruction::SecureExecute { data }),
+        Err(ProgramError::MissingRequiredSignature)
+    );
+    assert_eq!(delegate(&fixture), None);
+}
+
+#[test]
+fn secure_execute_still_approves_over_the_strategys_budget() {
+    let (fixture, _) = vault(true);
+    let authority = fixture.key("authority");
+    let budget = TestAccount::token_account(fixture.state::<Vault>("vault").mint, authority, BUDGET);
+    let (mut fixture, data) = approve_keeper(fixture, budget, authority);
+
+    execute!(&mut fixture, instruction::SecureExecute { data }).unwrap();
+
+    assert_eq!(delegate(&fixture), Some(fixture.key("keeper")));
+}
@@ -0,0 +1,87 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureOpenVault<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 32 + 32 + 1 + 1 + 1, // discriminator + admin + keeper + mint + custody_bump + execute_bump + bump
+        seeds = [b"secure_vault", admin.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    // SECURITY: The deposits are owned by a PDA only withdrawals sign with
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"vault_tokens", vault.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = custody_authority
+    )]
+    pub vault_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: Signing PDA for the vault's custody; holds no data
+    #[account(seeds = [b"authority", vault.key().as_ref(), CUSTODY_TAG], bump)]
+    pub custody_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Signing PDA for the strategy's CPIs; holds no data
+    #[account(seeds = [b"authority", vault.key().as_ref(), EXECUTE_TAG], bump)]
+    pub execute_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Only recorded; the keeper signs `secure_execute` later
+    pub keeper: UncheckedAccount<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SecureWithdraw<'info> {
+    #[account(
+        has_one = admin,
+        seeds = [b"secure_vault", admin.key().as_ref(), vault.mint.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut, seeds = [b"vault_tokens", vault.key().as_ref()], bump)]
+    pub vault_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: Signing PDA for the vault's custody; holds no data
+    #[account(seeds = [b"authority", vault.key().as_ref(), CUSTODY_TAG], bump = vault.custody_bump)]
+    pub custody_authority: UncheckedAccount<'info>,
+
+    #[account(mut, token::mint = vault.mint)]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SecureExecute<'info> {
+    #[account(
+        has_one = keeper,
+        seeds = [b"secure_vault", vault.admin.as_ref(), vault.mint.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    // SECURITY: A PDA of its own, which owns nothing the admin did not hand it
+    /// CHECK: Signing PDA for the strategy's CPIs; holds no data
+    #[account(seeds = [b"authority", vault.key().as_ref(), EXECUTE_TAG], bump = vault.execute_bump)]
+    pub execute_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Whatever program the strategy calls; `execute` exists for programs this one does not know
+    pub target_program: UncheckedAccount<'info>,
+
+    pub keeper: Signer<'info>,
+}
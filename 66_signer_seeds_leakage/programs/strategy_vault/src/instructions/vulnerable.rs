@@ -0,0 +1,83 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableOpenVault<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 32 + 32 + 1 + 1 + 1, // discriminator + admin + keeper + mint + custody_bump + execute_bump + bump
+        seeds = [b"vault", admin.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    // VULNERABILITY: The deposits are owned by the PDA every instruction signs with
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"vault_tokens", vault.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault_authority
+    )]
+    pub vault_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: The vault's one signing PDA; holds no data
+    #[account(seeds = [b"authority", vault.key().as_ref()], bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Only recorded; the keeper signs `vulnerable_execute` later
+    pub keeper: UncheckedAccount<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableWithdraw<'info> {
+    #[account(
+        has_one = admin,
+        seeds = [b"vault", admin.key().as_ref(), vault.mint.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut, seeds = [b"vault_tokens", vault.key().as_ref()], bump)]
+    pub vault_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: The vault's one signing PDA; holds no data
+    #[account(seeds = [b"authority", vault.key().as_ref()], bump = vault.custody_bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut, token::mint = vault.mint)]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct VulnerableExecute<'info> {
+    #[account(
+        has_one = keeper,
+        seeds = [b"vault", vault.admin.as_ref(), vault.mint.as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    // VULNERABILITY: The same PDA that owns the deposits signs the strategy's CPIs
+    /// CHECK: The vault's one signing PDA; holds no data
+    #[account(seeds = [b"authority", vault.key().as_ref()], bump = vault.execute_bump)]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Whatever program the strategy calls; `execute` exists for programs this one does not know
+    pub target_program: UncheckedAccount<'info>,
+
+    pub keeper: Signer<'info>,
+}
@@ -0,0 +1,19 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::Vault;
+
+#[test]
+fn vault_layout_is_stable() {
+    let vault = Vault {
+        admin: Pubkey::new_from_array([1; 32]),
+        keeper: Pubkey::new_from_array([2; 32]),
+        mint: Pubkey::new_from_array([3; 32]),
+        custody_bump: 81,
+        execute_bump: 89,
+        bump: 97,
+    };
+    assert_golden!("Vault", golden::anchor(&vault));
+}
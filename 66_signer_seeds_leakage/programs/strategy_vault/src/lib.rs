@@ -0,0 +1,256 @@
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod execute_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+
+/// Operation tag of the PDA that owns a secure vault's tokens; only
+/// `secure_withdraw` signs with it
+pub const CUSTODY_TAG: &[u8] = b"custody";
+/// Operation tag of the PDA that signs `secure_execute`'s CPIs
+pub const EXECUTE_TAG: &[u8] = b"execute";
+
+/// A token vault whose keeper runs a strategy through arbitrary CPIs.
+///
+/// Users deposit tokens and the admin withdraws them. A keeper - a bot's
+/// hot key - runs the vault's strategy through `execute`, which calls any
+/// program with any accounts and data, signed by the vault's PDA: approving
+/// a lending market over the strategy's budget, say, or swapping through a
+/// pool. The keeper is trusted with the strategy, never with the deposits,
+/// so what `execute` can sign for decides what a compromised keeper takes.
+#[feature_gated]
+#[program]
+pub mod strategy_vault {
+    use super::*;
+
+    /// Deposit tokens into a vault's token account
+    ///
+    /// Deposits need no program signature, so both designs share this.
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.depositor_tokens.to_account_info(),
+                    to: ctx.accounts.vault_tokens.to_account_info(),
+                    authority: ctx.accounts.depositor.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Deposited {} tokens into vault {}", amount, ctx.accounts.vault.key());
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Open a vault with one signing PDA for every operation
+    ///
+    /// Security Issue: `[b"authority", vault]` owns the vault's tokens and
+    /// also signs the strategy's CPIs, so both bumps stored are the same
+    /// PDA's. Withdrawals and `execute` produce the same signature.
+    #[vulnerable]
+    pub fn vulnerable_open_vault(ctx: Context<VulnerableOpenVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.admin = ctx.accounts.admin.key();
+        vault.keeper = ctx.accounts.keeper.key();
+        vault.mint = ctx.accounts.mint.key();
+        vault.custody_bump = ctx.bumps.vault_authority;
+        vault.execute_bump = ctx.bumps.vault_authority;
+        vault.bump = ctx.bumps.vault;
+
+        msg!("Vault opened for {} with keeper {}", vault.admin, vault.keeper);
+        Ok(())
+    }
+
+    /// VULNERABLE: Withdraw from the vault, signed by its one authority
+    ///
+    /// This instruction is correct on its own - only the admin can call it.
+    /// The problem is that `vulnerable_execute` produces the same signature.
+    #[vulnerable]
+    pub fn vulnerable_withdraw(ctx: Context<VulnerableWithdraw>, amount: u64) -> Result<()> {
+        let vault_key = ctx.accounts.vault.key();
+        let seeds: &[&[u8]] = &[b"authority", vault_key.as_ref(), &[ctx.accounts.vault.custody_bump]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_tokens.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        msg!("Withdrew {} tokens from vault {}", amount, vault_key);
+        Ok(())
+    }
+
+    /// VULNERABLE: Run a strategy CPI, signed by the vault's one authority
+    ///
+    /// Security Issue: The keeper picks the program, the accounts and the
+    /// data, and the PDA that owns the deposits signs wherever it appears.
+    /// Nothing about the call is unusual: a Token program `Approve` over the
+    /// vault's token account, naming the keeper as delegate, is exactly the
+    /// kind of CPI a strategy makes. Once it lands, the keeper spends the
+    /// deposits directly through the Token program, and no instruction of
+    /// this one is involved again.
+    #[vulnerable]
+    pub fn vulnerable_execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, VulnerableExecute<'info>>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let vault_key = ctx.accounts.vault.key();
+        // VULNERABILITY: The seeds that own the deposits sign whatever the keeper asks
+        let seeds: &[&[u8]] = &[b"authority", vault_key.as_ref(), &[ctx.accounts.vault.execute_bump]];
+
+        invoke_strategy(
+            &ctx.accounts.target_program,
+            &ctx.accounts.vault_authority,
+            ctx.remaining_accounts,
+            data,
+            seeds,
+        )?;
+
+        msg!("Executed {} for vault {}", ctx.accounts.target_program.key(), vault_key);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version of the same instructions
+    // with a signing PDA per operation, tagged in its seeds.
+
+    /// SECURE: Open a vault with a signing PDA per operation
+    ///
+    /// Security Fix: The vault's tokens are owned by `[b"authority", vault,
+    /// CUSTODY_TAG]`, and the strategy signs as `[b"authority", vault,
+    /// EXECUTE_TAG]`. The tag is part of the address, so no instruction can
+    /// produce one operation's signature with the other's seeds.
+    #[secure]
+    pub fn secure_open_vault(ctx: Context<SecureOpenVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.admin = ctx.accounts.admin.key();
+        vault.keeper = ctx.accounts.keeper.key();
+        vault.mint = ctx.accounts.mint.key();
+        vault.custody_bump = ctx.bumps.custody_authority;
+        vault.execute_bump = ctx.bumps.execute_authority;
+        vault.bump = ctx.bumps.vault;
+
+        msg!("Vault opened for {} with keeper {}", vault.admin, vault.keeper);
+        Ok(())
+    }
+
+    /// SECURE: Withdraw from the vault, signed by its custody authority
+    #[secure]
+    pub fn secure_withdraw(ctx: Context<SecureWithdraw>, amount: u64) -> Result<()> {
+        let vault_key = ctx.accounts.vault.key();
+        // SECURITY: The only instruction that builds the custody seeds
+        let seeds: &[&[u8]] = &[b"authority", vault_key.as_ref(), CUSTODY_TAG, &[ctx.accounts.vault.custody_bump]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_tokens.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.custody_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        msg!("Withdrew {} tokens from vault {}", amount, vault_key);
+        Ok(())
+    }
+
+    /// SECURE: Run a strategy CPI, signed by the vault's execute authority
+    ///
+    /// Security Fix: The call is as open as the vulnerable one - any program,
+    /// accounts and data - but the signature is the execute PDA's. It owns
+    /// only what the admin moved into the strategy's own token accounts, so
+    /// an `Approve` over the vault's token account fails with the Token
+    /// program's `OwnerMismatch`, and a compromised keeper can reach the
+    /// strategy's budget and nothing else.
+    #[secure]
+    pub fn secure_execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, SecureExecute<'info>>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let vault_key = ctx.accounts.vault.key();
+        // SECURITY: The operation tag keeps this signature off the custody account
+        let seeds: &[&[u8]] = &[b"authority", vault_key.as_ref(), EXECUTE_TAG, &[ctx.accounts.vault.execute_bump]];
+
+        invoke_strategy(
+            &ctx.accounts.target_program,
+            &ctx.accounts.execute_authority,
+            ctx.remaining_accounts,
+            data,
+            seeds,
+        )?;
+
+        msg!("Executed {} for vault {}", ctx.accounts.target_program.key(), vault_key);
+        Ok(())
+    }
+}
+
+/// Invoke `program` with `data` over `accounts`, `authority` signing wherever it appears
+///
+/// Both execute instructions share this: the keeper chooses the program,
+/// the accounts and their order, and the data. Only the seeds differ.
+fn invoke_strategy<'info>(
+    program: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    accounts: &[AccountInfo<'info>],
+    data: Vec<u8>,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let metas = accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer || account.key == authority.key,
+            is_writable: account.is_writable,
+        })
+        .collect();
+
+    let mut infos = vec![program.clone(), authority.clone()];
+    infos.extend(accounts.iter().cloned());
+
+    invoke_signed(
+        &Instruction {
+            program_id: program.key(),
+            accounts: metas,
+            data,
+        },
+        &infos,
+        &[signer_seeds],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,19 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Vault {
+    /// The wallet allowed to withdraw the vault's tokens (32 bytes)
+    pub admin: Pubkey,
+    /// The wallet allowed to run the strategy through `execute` (32 bytes)
+    pub keeper: Pubkey,
+    /// The mint this vault holds (32 bytes)
+    pub mint: Pubkey,
+    /// Bump of the PDA that owns the vault's tokens and signs withdrawals (1 byte)
+    pub custody_bump: u8,
+    /// Bump of the PDA that signs the strategy's CPIs (1 byte)
+    pub execute_bump: u8,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
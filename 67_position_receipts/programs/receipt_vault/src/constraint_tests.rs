@@ -0,0 +1,255 @@
+//! Every constraint on the shared and secure contexts, tripped one account at a time.
+
+use anchor_lang::prelude::*;
+use secref_testkit::runtime::account_already_in_use;
+use secref_testkit::{
+    assert_constraint_violation, assert_owner_violation, assert_seeds_violation,
+    assert_signer_violation, try_accounts, Fixture, TestAccount,
+};
+
+use crate::{Deposit, ErrorCode as VaultError, OpenVault, Position, SecureRedeem, Vault};
+
+const VAULT_SPACE: usize = 8 + 32 + 32 + 8 + 1 + 1;
+const POSITION_SPACE: usize = 8 + 32 + 32 + 8 + 1;
+const MINT_SPACE: usize = 82;
+const TOKEN_ACCOUNT_SPACE: usize = 165;
+const DEPOSIT: u64 = 1_000;
+
+fn pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &crate::ID)
+}
+
+/// A vault at its PDA with one position open, its token account and its receipt authority
+fn vault_accounts(mint: Pubkey) -> [TestAccount; 3] {
+    let admin = Pubkey::new_unique();
+    let (address, bump) = pda(&[b"vault", admin.as_ref(), mint.as_ref()]);
+    let (authority, receipt_authority_bump) = pda(&[b"receipt_authority", address.as_ref()]);
+    let (tokens, _) = pda(&[b"vault_tokens", address.as_ref()]);
+    let vault = TestAccount::anchor(&Vault {
+        admin,
+        mint,
+        positions: 1,
+        receipt_authority_bump,
+        bump,
+    })
+    .at(address);
+    [
+        vault,
+        TestAccount::token_account(mint, address, DEPOSIT).at(tokens),
+        TestAccount::system(0).at(authority),
+    ]
+}
+
+fn open_fixture() -> Fixture {
+    let admin = TestAccount::signer();
+    let mint = TestAccount::mint(Pubkey::new_unique(), 6, 1_000_000);
+    let (vault, _) = pda(&[b"vault", admin.key.as_ref(), mint.key.as_ref()]);
+    let (tokens, _) = pda(&[b"vault_tokens", vault.as_ref()]);
+    let (authority, _) = pda(&[b"receipt_authority", vault.as_ref()]);
+    Fixture::new()
+        .with("vault", TestAccount::uninitialized(VAULT_SPACE).at(vault))
+        .with("vault_tokens", TestAccount::uninitialized(TOKEN_ACCOUNT_SPACE).at(tokens))
+        .with("receipt_authority", TestAccount::system(0).at(authority))
+        .with("mint", mint)
+        .with("admin", admin)
+        .with("token_program", TestAccount::token_program())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn deposit_fixture() -> Fixture {
+    let depositor = TestAccount::signer();
+    let mint = Pubkey::new_unique();
+    let [vault, vault_tokens, receipt_authority] = vault_accounts(mint);
+    let (receipt_mint, _) = pda(&[b"receipt", vault.key.as_ref(), &1u64.to_le_bytes()]);
+    let (receipt_tokens, _) = pda(&[b"receipt_tokens", receipt_mint.as_ref()]);
+    let (position, _) = pda(&[b"position", receipt_mint.as_ref()]);
+    Fixture::new()
+        .with("vault", vault)
+        .with("vault_tokens", vault_tokens)
+        .with("depositor_tokens", TestAccount::token_account(mint, depositor.key, DEPOSIT))
+        .with("receipt_authority", receipt_authority)
+        .with("receipt_mint", TestAccount::uninitialized(MINT_SPACE).at(receipt_mint))
+        .with("receipt_tokens", TestAccount::uninitialized(TOKEN_ACCOUNT_SPACE).at(receipt_tokens))
+        .with("position", TestAccount::uninitialized(POSITION_SPACE).at(position))
+        .with("depositor", depositor)
+        .with("token_program", TestAccount::token_program())
+        .with("system_program", TestAccount::system_program())
+}
+
+fn redeem_fixture() -> Fixture {
+    let holder = TestAccount::signer();
+    let mint = Pubkey::new_unique();
+    let [vault, vault_tokens, receipt_authority] = vault_accounts(mint);
+    let (receipt_mint, _) = pda(&[b"receipt", vault.key.as_ref(), &0u64.to_le_bytes()]);
+    let (position, bump) = pda(&[b"position", receipt_mint.as_ref()]);
+    let position = TestAccount::anchor(&Position {
+        vault: vault.key,
+        receipt_mint,
+        amount: DEPOSIT,
+        bump,
+    })
+    .at(position);
+    Fixture::new()
+        .with("vault", vault)
+        .with("vault_tokens", vault_tokens)
+        .with("position", position)
+        .with("receipt_mint", TestAccount::mint(receipt_authority.key, 0, 1).at(receipt_mint))
+        .with("receipt_authority", receipt_authority)
+        .with("receipt_tokens", TestAccount::token_account(receipt_mint, holder.key, 1))
+        .with("destination", TestAccount::token_account(mint, holder.key, 0))
+        .with("holder", holder)
+        .with("token_program", TestAccount::token_program())
+}
+
+#[test]
+fn open_vault_derives_every_account_from_admin_and_mint() {
+    assert_seeds_violation!(OpenVault, open_fixture(), "vault");
+    assert_seeds_violation!(OpenVault, open_fixture(), "vault_tokens");
+    assert_seeds_violation!(OpenVault, open_fixture(), "receipt_authority");
+    assert_owner_violation!(OpenVault, open_fixture(), "mint");
+    assert_signer_violation!(OpenVault, open_fixture(), "admin");
+}
+
+#[test]
+fn deposit_mints_the_next_receipt_at_its_pdas() {
+    assert_seeds_violation!(Deposit, deposit_fixture(), "vault");
+    assert_seeds_violation!(Deposit, deposit_fixture(), "vault_tokens");
+    assert_seeds_violation!(Deposit, deposit_fixture(), "receipt_authority");
+    assert_seeds_violation!(Deposit, deposit_fixture(), "receipt_mint");
+    assert_seeds_violation!(Deposit, deposit_fixture(), "receipt_tokens");
+    assert_seeds_violation!(Deposit, deposit_fixture(), "position");
+
+    // The receipt of the vault's first position is already taken
+    let fixture = deposit_fixture();
+    let (first, _) = pda(&[b"receipt", fixture.key("vault").as_ref(), &0u64.to_le_bytes()]);
+    assert_constraint_violation!(
+        Deposit,
+        fixture,
+        "receipt_mint" => TestAccount::uninitialized(MINT_SPACE).at(first),
+        ErrorCode::ConstraintSeeds
+    );
+}
+
+#[test]
+fn deposit_cannot_reuse_an_existing_receipt_mint() {
+    assert_constraint_violation!(
+        Deposit,
+        deposit_fixture(),
+        "receipt_mint" => |mint| *mint = TestAccount::mint(Pubkey::new_unique(), 0, 0).at(mint.key),
+        account_already_in_use()
+    );
+}
+
+#[test]
+fn deposit_takes_the_depositors_own_tokens_of_the_vaults_mint() {
+    assert_signer_violation!(Deposit, deposit_fixture(), "depositor");
+
+    let fixture = deposit_fixture();
+    let depositor = fixture.key("depositor");
+    assert_constraint_violation!(
+        Deposit,
+        fixture,
+        "depositor_tokens" => TestAccount::token_account(Pubkey::new_unique(), depositor, DEPOSIT),
+        ErrorCode::ConstraintTokenMint
+    );
+    assert_constraint_violation!(
+        Deposit,
+        deposit_fixture(),
+        "depositor" => TestAccount::signer(),
+        ErrorCode::ConstraintTokenOwner
+    );
+}
+
+#[test]
+fn secure_redeem_pays_the_positions_own_vault() {
+    assert_seeds_violation!(SecureRedeem, redeem_fixture(), "vault");
+    assert_seeds_violation!(SecureRedeem, redeem_fixture(), "vault_tokens");
+    assert_seeds_violation!(SecureRedeem, redeem_fixture(), "position");
+    assert_constraint_violation!(
+        SecureRedeem,
+        redeem_fixture(),
+        "position" => |position| position.edit(|stored: &mut Position| stored.vault = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+    assert_constraint_violation!(
+        SecureRedeem,
+        redeem_fixture(),
+        "destination" => |destination| {
+            *destination = TestAccount::token_account(Pubkey::new_unique(), Pubkey::new_unique(), 0)
+        },
+        ErrorCode::ConstraintTokenMint
+    );
+}
+
+#[test]
+fn secure_redeem_takes_only_the_positions_receipt() {
+    // Another mint is another position's receipt, or none at all
+    assert_seeds_violation!(SecureRedeem, redeem_fixture(), "receipt_mint");
+    assert_constraint_violation!(
+        SecureRedeem,
+        redeem_fixture(),
+        "position" => |position| position.edit(|stored: &mut Position| stored.receipt_mint = Pubkey::new_unique()),
+        ErrorCode::ConstraintHasOne
+    );
+
+    // A mint of the attacker's, named alongside its own authority, is not the vault's
+    let mut fixture = redeem_fixture();
+    let outsider = Pubkey::new_unique();
+    let receipt_mint = fixture.account_mut("receipt_mint");
+    *receipt_mint = TestAccount::mint(outsider, 0, 1).at(receipt_mint.key);
+    *fixture.account_mut("receipt_authority") = TestAccount::system(0).at(outsider);
+    assert_eq!(try_accounts!(SecureRedeem, fixture), Err(Error::from(ErrorCode::ConstraintSeeds)));
+
+    let fixture = redeem_fixture();
+    let holder = fixture.key("holder");
+    assert_constraint_violation!(
+        SecureRedeem,
+        fixture,
+        "receipt_tokens" => TestAccount::token_account(Pubkey::new_unique(), holder, 1),
+        ErrorCode::ConstraintTokenMint
+    );
+}
+
+#[test]
+fn secure_redeem_requires_a_single_receipt_minted_by_the_vault() {
+    let fixture = redeem_fixture();
+    let authority = fixture.key("receipt_authority");
+    assert_constraint_violation!(
+        SecureRedeem,
+        fixture,
+        "receipt_mint" => |mint| *mint = TestAccount::mint(authority, 0, 2).at(mint.key),
+        VaultError::ReceiptSupplyMismatch
+    );
+    assert_constraint_violation!(
+        SecureRedeem,
+        redeem_fixture(),
+        "receipt_mint" => |mint| *mint = TestAccount::mint(Pubkey::new_unique(), 0, 1).at(mint.key),
+        ErrorCode::ConstraintMintMintAuthority
+    );
+    assert_constraint_violation!(
+        SecureRedeem,
+        redeem_fixture(),
+        "receipt_mint" => |mint| *mint = TestAccount::mint(authority, 6, 1).at(mint.key),
+        ErrorCode::ConstraintMintDecimals
+    );
+}
+
+#[test]
+fn secure_redeem_requires_the_holder_of_the_receipt() {
+    assert_signer_violation!(SecureRedeem, redeem_fixture(), "holder");
+    assert_constraint_violation!(
+        SecureRedeem,
+        redeem_fixture(),
+        "holder" => TestAccount::signer(),
+        ErrorCode::ConstraintTokenOwner
+    );
+
+    let fixture = redeem_fixture();
+    let (mint, holder) = (fixture.key("receipt_mint"), fixture.key("holder"));
+    assert_constraint_violation!(
+        SecureRedeem,
+        fixture,
+        "receipt_tokens" => |tokens| *tokens = TestAccount::token_account(mint, holder, 0).at(tokens.key),
+        VaultError::ReceiptNotHeld
+    );
+}
@@ -0,0 +1,15 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Deposit must be greater than zero")]
+    ZeroDeposit,
+    #[msg("Token account does not hold the position's receipt")]
+    ReceiptNotHeld,
+    #[msg("Receipt mint supply is not exactly one")]
+    ReceiptSupplyMismatch,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
@@ -0,0 +1,99 @@
+//! Account contexts, one per instruction
+//!
+//! Contexts only the vulnerable or the secure handlers use are in the
+//! `vulnerable` and `secure` modules, built with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub use secure::*;
+
+#[derive(Accounts)]
+pub struct OpenVault<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 32 + 8 + 1 + 1, // discriminator + admin + mint + positions + receipt_authority_bump + bump
+        seeds = [b"vault", admin.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"vault_tokens", vault.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault
+    )]
+    pub vault_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: Mint authority PDA of every receipt; holds no data
+    #[account(seeds = [b"receipt_authority", vault.key().as_ref()], bump)]
+    pub receipt_authority: UncheckedAccount<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut, seeds = [b"vault", vault.admin.as_ref(), vault.mint.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut, seeds = [b"vault_tokens", vault.key().as_ref()], bump)]
+    pub vault_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = vault.mint, token::authority = depositor)]
+    pub depositor_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: Mint authority PDA of every receipt; holds no data
+    #[account(seeds = [b"receipt_authority", vault.key().as_ref()], bump = vault.receipt_authority_bump)]
+    pub receipt_authority: UncheckedAccount<'info>,
+
+    // A mint per position, whose one token is the receipt
+    #[account(
+        init,
+        payer = depositor,
+        seeds = [b"receipt", vault.key().as_ref(), &vault.positions.to_le_bytes()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = receipt_authority
+    )]
+    pub receipt_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = depositor,
+        seeds = [b"receipt_tokens", receipt_mint.key().as_ref()],
+        bump,
+        token::mint = receipt_mint,
+        token::authority = depositor
+    )]
+    pub receipt_tokens: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = depositor,
+        space = 8 + 32 + 32 + 8 + 1, // discriminator + vault + receipt_mint + amount + bump
+        seeds = [b"position", receipt_mint.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
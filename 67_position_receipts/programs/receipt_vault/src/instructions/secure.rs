@@ -0,0 +1,52 @@
+//! Account contexts of the secure handlers, built with the `secure` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct SecureRedeem<'info> {
+    #[account(seeds = [b"vault", vault.admin.as_ref(), vault.mint.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut, seeds = [b"vault_tokens", vault.key().as_ref()], bump)]
+    pub vault_tokens: Account<'info, TokenAccount>,
+
+    // SECURITY: Closed by the redemption, so it pays once
+    #[account(
+        mut,
+        close = holder,
+        has_one = vault,
+        has_one = receipt_mint,
+        seeds = [b"position", receipt_mint.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+
+    // SECURITY: Only the vault's authority mints receipts, and this one has exactly one token
+    #[account(
+        mut,
+        mint::authority = receipt_authority,
+        mint::decimals = 0,
+        constraint = receipt_mint.supply == 1 @ ErrorCode::ReceiptSupplyMismatch
+    )]
+    pub receipt_mint: Account<'info, Mint>,
+
+    /// CHECK: Mint authority PDA of every receipt; holds no data
+    #[account(seeds = [b"receipt_authority", vault.key().as_ref()], bump = vault.receipt_authority_bump)]
+    pub receipt_authority: UncheckedAccount<'info>,
+
+    // SECURITY: Burned by the redemption
+    #[account(
+        mut,
+        token::mint = receipt_mint,
+        token::authority = holder,
+        constraint = receipt_tokens.amount == 1 @ ErrorCode::ReceiptNotHeld
+    )]
+    pub receipt_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = vault.mint)]
+    pub destination: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
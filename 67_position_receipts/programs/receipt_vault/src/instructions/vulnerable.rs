@@ -0,0 +1,30 @@
+//! Account contexts of the vulnerable handlers, built with the `vulnerable` feature
+
+use super::*;
+
+#[derive(Accounts)]
+pub struct VulnerableRedeem<'info> {
+    #[account(seeds = [b"vault", vault.admin.as_ref(), vault.mint.as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut, seeds = [b"vault_tokens", vault.key().as_ref()], bump)]
+    pub vault_tokens: Account<'info, TokenAccount>,
+
+    // VULNERABILITY: Left open, so it pays again on every redemption
+    #[account(has_one = vault, seeds = [b"position", position.receipt_mint.as_ref()], bump = position.bump)]
+    pub position: Account<'info, Position>,
+
+    // VULNERABILITY: The receipt is shown, never burned
+    #[account(
+        token::mint = position.receipt_mint,
+        token::authority = holder,
+        constraint = receipt_tokens.amount >= 1 @ ErrorCode::ReceiptNotHeld
+    )]
+    pub receipt_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = vault.mint)]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub holder: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,29 @@
+//! Golden fixtures for every account layout (see `secref_testkit::golden`).
+
+use anchor_lang::prelude::*;
+use secref_testkit::{assert_golden, golden};
+
+use crate::{Position, Vault};
+
+#[test]
+fn vault_layout_is_stable() {
+    let vault = Vault {
+        admin: Pubkey::new_from_array([1; 32]),
+        mint: Pubkey::new_from_array([2; 32]),
+        positions: 7,
+        receipt_authority_bump: 81,
+        bump: 97,
+    };
+    assert_golden!("Vault", golden::anchor(&vault));
+}
+
+#[test]
+fn position_layout_is_stable() {
+    let position = Position {
+        vault: Pubkey::new_from_array([3; 32]),
+        receipt_mint: Pubkey::new_from_array([4; 32]),
+        amount: 1_000,
+        bump: 89,
+    };
+    assert_golden!("Position", golden::anchor(&position));
+}
@@ -0,0 +1,189 @@
+use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount, Transfer};
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+pub mod errors;
+pub mod instructions;
+#[cfg(all(doc, not(doctest)))]
+pub mod security_notes;
+pub mod state;
+
+pub use errors::ErrorCode;
+pub use instructions::*;
+pub use state::*;
+
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod constraint_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod layout_tests;
+#[cfg(all(test, feature = "vulnerable", feature = "secure"))]
+mod redeem_tests;
+
+/// A token vault that hands out a position NFT for every deposit.
+///
+/// Each deposit mints a fresh receipt mint with a single token - the
+/// position NFT - to the depositor, and records the amount in a `Position`
+/// keyed by that mint. The receipt can be sold or transferred like any
+/// token; whoever holds it redeems the position for the deposit. Every
+/// receipt mint's mint authority is the vault's `[b"receipt_authority",
+/// vault]` PDA, so only `deposit` can create one.
+#[feature_gated]
+#[program]
+pub mod receipt_vault {
+    use super::*;
+
+    /// Open a vault for `mint`, holding deposits under the vault's own PDA
+    pub fn open_vault(ctx: Context<OpenVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.admin = ctx.accounts.admin.key();
+        vault.mint = ctx.accounts.mint.key();
+        vault.positions = 0;
+        vault.receipt_authority_bump = ctx.bumps.receipt_authority;
+        vault.bump = ctx.bumps.vault;
+
+        msg!("Vault opened for mint {}", vault.mint);
+        Ok(())
+    }
+
+    /// Deposit tokens, and receive the position's receipt
+    ///
+    /// Both designs issue receipts the same way: a new mint at `[b"receipt",
+    /// vault, positions]` with the receipt authority PDA as mint authority,
+    /// and one token of it minted to the depositor.
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::ZeroDeposit);
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.depositor_tokens.to_account_info(),
+                    to: ctx.accounts.vault_tokens.to_account_info(),
+                    authority: ctx.accounts.depositor.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let vault_key = ctx.accounts.vault.key();
+        let seeds: &[&[u8]] = &[b"receipt_authority", vault_key.as_ref(), &[ctx.accounts.vault.receipt_authority_bump]];
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.receipt_mint.to_account_info(),
+                    to: ctx.accounts.receipt_tokens.to_account_info(),
+                    authority: ctx.accounts.receipt_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            1,
+        )?;
+
+        let position = &mut ctx.accounts.position;
+        position.vault = vault_key;
+        position.receipt_mint = ctx.accounts.receipt_mint.key();
+        position.amount = amount;
+        position.bump = ctx.bumps.position;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.positions = vault.positions.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Deposited {} tokens against receipt {}", amount, position.receipt_mint);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Redeem a position for whoever holds its receipt
+    ///
+    /// Security Issue: Holding the receipt is checked, but redeeming it
+    /// changes nothing: the receipt stays in the holder's account and the
+    /// position stays open with its amount. The same receipt redeems again
+    /// in the next instruction, paid out of every other depositor's tokens,
+    /// until the vault is empty.
+    #[vulnerable]
+    pub fn vulnerable_redeem(ctx: Context<VulnerableRedeem>) -> Result<()> {
+        let amount = ctx.accounts.position.amount;
+
+        // VULNERABILITY: Pays without consuming the receipt or the position
+        pay_out(
+            &ctx.accounts.vault,
+            &ctx.accounts.vault_tokens,
+            &ctx.accounts.destination,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
+
+        msg!("Redeemed receipt {} for {} tokens", ctx.accounts.position.receipt_mint, amount);
+        Ok(())
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version of the same instruction
+    // with the receipt burned and the position closed.
+
+    /// SECURE: Redeem a position by burning its receipt
+    ///
+    /// Security Fix: The receipt must be the position's own mint, minted by
+    /// the vault's receipt authority with a supply of exactly one, so it is
+    /// the only claim on the position. Redemption burns it and closes the
+    /// position: a second redemption finds neither.
+    #[secure]
+    pub fn secure_redeem(ctx: Context<SecureRedeem>) -> Result<()> {
+        // SECURITY: The one receipt is gone before anything is paid
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.receipt_mint.to_account_info(),
+                    from: ctx.accounts.receipt_tokens.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        let amount = ctx.accounts.position.amount;
+        pay_out(
+            &ctx.accounts.vault,
+            &ctx.accounts.vault_tokens,
+            &ctx.accounts.destination,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
+
+        msg!("Redeemed receipt {} for {} tokens", ctx.accounts.receipt_mint.key(), amount);
+        Ok(())
+    }
+}
+
+/// Pay `amount` out of the vault's tokens, signed by the vault
+fn pay_out<'info>(
+    vault: &Account<'info, Vault>,
+    vault_tokens: &Account<'info, TokenAccount>,
+    destination: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let seeds: &[&[u8]] = &[b"vault", vault.admin.as_ref(), vault.mint.as_ref(), &[vault.bump]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: vault_tokens.to_account_info(),
+                to: destination.to_account_info(),
+                authority: vault.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )
+}
@@ -0,0 +1,228 @@
+//! Receipts against the vault's deposits: redeemed over and over when the
+//! redemption leaves them in place, once when it burns them.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::error::TokenError;
+use anchor_spl::token::{Mint, TokenAccount};
+use secref_testkit::{execute, Fixture, TestAccount};
+
+use crate::{instruction, Position, Vault};
+
+const ALICE: u64 = 900;
+const MALLORY: u64 = 100;
+const MINT_SPACE: usize = 82;
+const TOKEN_ACCOUNT_SPACE: usize = 165;
+const POSITION_SPACE: usize = 8 + 32 + 32 + 8 + 1;
+
+fn pda(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &crate::ID)
+}
+
+/// One position's receipt mint, the token account holding its receipt, and the position
+struct Receipt {
+    mint: TestAccount,
+    tokens: TestAccount,
+    position: TestAccount,
+}
+
+/// The vault, its deposits and its receipt authority, carried from one instruction to the next
+struct Bank {
+    vault: TestAccount,
+    vault_tokens: TestAccount,
+    receipt_authority: TestAccount,
+}
+
+impl Bank {
+    fn new() -> Self {
+        let admin = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let (address, bump) = pda(&[b"vault", admin.as_ref(), mint.as_ref()]);
+        let (authority, receipt_authority_bump) = pda(&[b"receipt_authority", address.as_ref()]);
+        let (tokens, _) = pda(&[b"vault_tokens", address.as_ref()]);
+        Self {
+            vault: TestAccount::anchor(&Vault {
+                admin,
+                mint,
+                positions: 0,
+                receipt_authority_bump,
+                bump,
+            })
+            .at(address),
+            vault_tokens: TestAccount::token_account(mint, address, 0).at(tokens),
+            receipt_authority: TestAccount::system(0).at(authority),
+        }
+    }
+
+    fn mint(&self) -> Pubkey {
+        self.vault.state::<Vault>().mint
+    }
+
+    fn held(&self) -> u64 {
+        self.vault_tokens.state::<TokenAccount>().amount
+    }
+
+    /// A token account of the vault's mint for `owner`, empty
+    fn wallet(&self, owner: Pubkey) -> TestAccount {
+        TestAccount::token_account(self.mint(), owner, 0)
+    }
+
+    /// `depositor` deposits `amount` and is handed the position's receipt
+    fn deposit(&mut self, depositor: &TestAccount, amount: u64) -> Receipt {
+        let positions = self.vault.state::<Vault>().positions;
+        let (mint, _) = pda(&[b"receipt", self.vault.key.as_ref(), &positions.to_le_bytes()]);
+        let (tokens, _) = pda(&[b"receipt_tokens", mint.as_ref()]);
+        let (position, _) = pda(&[b"position", mint.as_ref()]);
+        let mut fixture = Fixture::new()
+            .with("vault", self.vault.clone())
+            .with("vault_tokens", self.vault_tokens.clone())
+            .with("depositor_tokens", TestAccount::token_account(self.mint(), depositor.key, amount))
+            .with("receipt_authority", self.receipt_authority.clone())
+            .with("receipt_mint", TestAccount::uninitialized(MINT_SPACE).at(mint))
+            .with("receipt_tokens", TestAccount::uninitialized(TOKEN_ACCOUNT_SPACE).at(tokens))
+            .with("position", TestAccount::uninitialized(POSITION_SPACE).at(position))
+            .with("depositor", depositor.clone())
+            .with("token_program", TestAccount::token_program())
+            .with("system_program", TestAccount::system_program());
+        execute!(&mut fixture, instruction::Deposit { amount }).unwrap();
+
+        self.vault = fixture.account("vault").clone();
+        self.vault_tokens = fixture.account("vault_tokens").clone();
+        Receipt {
+            mint: fixture.account("receipt_mint").clone(),
+            tokens: fixture.account("receipt_tokens").clone(),
+            position: fixture.account("position").clone(),
+        }
+    }
+
+    /// `holder` redeems `receipt` into `destination`, through the secure or the vulnerable redemption
+    fn redeem(
+        &mut self,
+        secure: bool,
+        receipt: &mut Receipt,
+        holder: &TestAccount,
+        destination: &mut TestAccount,
+    ) -> std::result::Result<(), ProgramError> {
+        let fixture = Fixture::new()
+            .with("vault", self.vault.clone())
+            .with("vault_tokens", self.vault_tokens.clone())
+            .with("position", receipt.position.clone());
+        let mut fixture = if secure {
+            fixture
+                .with("receipt_mint", receipt.mint.clone())
+                .with("receipt_authority", self.receipt_authority.clone())
+        } else {
+            fixture
+        };
+        fixture = fixture
+            .with("receipt_tokens", receipt.tokens.clone())
+            .with("destination", destination.clone())
+            .with("holder", holder.clone())
+            .with("token_program", TestAccount::token_program());
+        let result = if secure {
+            execute!(&mut fixture, instruction::SecureRedeem {})
+        } else {
+            execute!(&mut fixture, instruction::VulnerableRedeem {})
+        };
+
+        self.vault_tokens = fixture.account("vault_tokens").clone();
+        receipt.position = fixture.account("position").clone();
+        receipt.tokens = fixture.account("receipt_tokens").clone();
+        if secure {
+            receipt.mint = fixture.account("receipt_mint").clone();
+        }
+        *destination = fixture.account("destination").clone();
+        result
+    }
+}
+
+fn balance(account: &TestAccount) -> u64 {
+    account.state::<TokenAccount>().amount
+}
+
+#[test]
+fn deposit_mints_one_receipt_per_position_under_the_vaults_authority() {
+    let mut bank = Bank::new();
+    let alice = TestAccount::signer();
+    let first = bank.deposit(&alice, ALICE);
+    let second = bank.deposit(&alice, MALLORY);
+
+    assert_ne!(first.mint.key, second.mint.key);
+    assert_eq!(bank.vault.state::<Vault>().positions, 2);
+    assert_eq!(bank.held(), ALICE + MALLORY);
+    for (receipt, amount) in [(&first, ALICE), (&second, MALLORY)] {
+        let mint = receipt.mint.state::<Mint>();
+        assert_eq!(Option::<Pubkey>::from(mint.mint_authority), Some(bank.receipt_authority.key));
+        assert_eq!((mint.supply, mint.decimals), (1, 0));
+        assert_eq!(balance(&receipt.tokens), 1);
+        assert_eq!(receipt.position.state::<Position>().amount, amount);
+    }
+}
+
+#[test]
+fn vulnerable_redeem_pays_the_same_receipt_until_the_vault_is_empty() {
+    let mut bank = Bank::new();
+    let (alice, mallory) = (TestAccount::signer(), TestAccount::signer());
+    bank.deposit(&alice, ALICE);
+    let mut receipt = bank.deposit(&mallory, MALLORY);
+    let mut wallet = bank.wallet(mallory.key);
+
+    for _ in 0..(ALICE + MALLORY) / MALLORY {
+        bank.redeem(false, &mut receipt, &mallory, &mut wallet).unwrap();
+    }
+
+    // Mallory still holds the receipt, and Alice's 900 are hers
+    assert_eq!(balance(&wallet), ALICE + MALLORY);
+    assert_eq!(balance(&receipt.tokens), 1);
+    assert_eq!(bank.held(), 0);
+    assert_eq!(
+        bank.redeem(false, &mut receipt, &mallory, &mut wallet),
+        Err(TokenError::InsufficientFunds.into())
+    );
+}
+
+#[test]
+fn secure_redeem_burns_the_receipt_and_closes_the_position() {
+    let mut bank = Bank::new();
+    let (alice, mallory) = (TestAccount::signer(), TestAccount::signer());
+    bank.deposit(&alice, ALICE);
+    let mut receipt = bank.deposit(&mallory, MALLORY);
+    let mut wallet = bank.wallet(mallory.key);
+
+    bank.redeem(true, &mut receipt, &mallory, &mut wallet).unwrap();
+
+    assert_eq!(balance(&wallet), MALLORY);
+    assert_eq!(balance(&receipt.tokens), 0);
+    assert_eq!(receipt.mint.state::<Mint>().supply, 0);
+    assert_eq!(receipt.position.lamports, 0);
+
+    // Neither the position nor the receipt is left to redeem again, by either path
+    assert_eq!(
+        bank.redeem(true, &mut receipt, &mallory, &mut wallet),
+        Err(Error::from(anchor_lang::error::ErrorCode::AccountNotInitialized).into())
+    );
+    assert_eq!(
+        bank.redeem(false, &mut receipt, &mallory, &mut wallet),
+        Err(Error::from(anchor_lang::error::ErrorCode::AccountNotInitialized).into())
+    );
+    assert_eq!(bank.held(), ALICE);
+}
+
+#[test]
+fn secure_redeem_pays_whoever_holds_the_receipt() {
+    let mut bank = Bank::new();
+    let (alice, bob) = (TestAccount::signer(), TestAccount::signer());
+    let mut receipt = bank.deposit(&alice, ALICE);
+
+    // Alice sells the position: its receipt moves to Bob's token account
+    let sold = TestAccount::token_account(receipt.mint.key, bob.key, 1);
+    let kept = std::mem::replace(&mut receipt.tokens, sold);
+    let mut alices_wallet = bank.wallet(alice.key);
+    let mut bobs_wallet = bank.wallet(bob.key);
+    bank.redeem(true, &mut receipt, &bob, &mut bobs_wallet).unwrap();
+    assert_eq!(balance(&bobs_wallet), ALICE);
+
+    // Alice's emptied account is no claim on the position
+    receipt.tokens = TestAccount::token_account(receipt.mint.key, alice.key, 0).at(kept.key);
+    assert!(bank.redeem(true, &mut receipt, &alice, &mut alices_wallet).is_err());
+    assert_eq!(balance(&alices_wallet), 0);
+}
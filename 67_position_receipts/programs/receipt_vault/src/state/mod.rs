@@ -0,0 +1,29 @@
+//! Accounts the program owns, and the types stored in them
+
+use super::*;
+
+#[account]
+pub struct Vault {
+    /// The wallet that opened the vault (32 bytes)
+    pub admin: Pubkey,
+    /// The mint this vault holds (32 bytes)
+    pub mint: Pubkey,
+    /// Positions opened so far; the next receipt mint's seed (8 bytes)
+    pub positions: u64,
+    /// Bump of the PDA that is every receipt mint's mint authority (1 byte)
+    pub receipt_authority_bump: u8,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Position {
+    /// The vault the deposit was made into (32 bytes)
+    pub vault: Pubkey,
+    /// The mint of the receipt that redeems this position (32 bytes)
+    pub receipt_mint: Pubkey,
+    /// Tokens deposited, and paid to whoever redeems the receipt (8 bytes)
+    pub amount: u64,
+    /// PDA bump seed (1 byte)
+    pub bump: u8,
+}
@@ -0,0 +1,36 @@
+//! Errors the program's handlers return
+
+use super::*;
+
+// ========================================
+// ERROR DEFINITIONS
+// ========================================
+// COMPARISON: Anchor uses `#[error_code]` macro for automatic error handling
+// Pinocchio requires manual error type implementation
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VaultError {
+    InsufficientFunds,
+    ArithmeticOverflow,
+    ArithmeticUnderflow,
+    UnauthorizedOwner,
+    AlreadyInitialized,
+    NotRentExempt,
+}
+
+impl From<VaultError> for ProgramError {
+    fn from(error: VaultError) -> Self {
+        match error {
+            VaultError::InsufficientFunds => ProgramError::Custom(0),
+            VaultError::ArithmeticOverflow => ProgramError::Custom(1),
+            VaultError::ArithmeticUnderflow => ProgramError::Custom(2),
+            VaultError::UnauthorizedOwner => ProgramError::Custom(3),
+            VaultError::AlreadyInitialized => ProgramError::Custom(4),
+            VaultError::NotRentExempt => ProgramError::Custom(5),
+        }
+    }
+}
+
+// ========================================
+// FRAMEWORK COMPARISON SUMMARY
+// ========================================
@@ -0,0 +1,15 @@
+//! Instruction handlers
+//!
+//! Each implementation's handlers are in `vulnerable` and `secure`, built
+//! with the feature of that name.
+
+use super::*;
+
+#[cfg(feature = "vulnerable")]
+mod vulnerable;
+#[cfg(feature = "vulnerable")]
+pub(crate) use vulnerable::*;
+#[cfg(feature = "secure")]
+mod secure;
+#[cfg(feature = "secure")]
+pub(crate) use secure::*;
@@ -0,0 +1,188 @@
+//! The secure instructions, built with the `secure` feature.
+//!
+//! COMPARISON: These functions show how to properly implement security
+//! in Pinocchio with explicit validation vs Anchor's declarative approach
+
+use super::*;
+
+/// SECURE: Initialize a new vault with proper validation
+/// 
+/// PINOCCHIO vs ANCHOR COMPARISON:
+/// - Anchor: `#[account(init, has_one = owner)]` handles validation declaratively
+/// - Pinocchio: Must explicitly validate account state, ownership, and relationships
+///
+/// Rent exemption is checked against `Rent::get()`, so no rent account is
+/// passed at all.
+pub(crate) fn secure_initialize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // PINOCCHIO: Manual account parsing
+    let accounts_iter = &mut accounts.iter();
+    let vault_account = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let owner_account = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let payer_account = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let system_program = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    // SECURITY: Explicit signer validation - Anchor's `Signer<'info>` does this automatically
+    if !owner_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !payer_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // PINOCCHIO: Manual instruction data parsing
+    if instruction_data.len() < 8 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let initial_balance = u64::from_le_bytes([
+        instruction_data[0], instruction_data[1], instruction_data[2], instruction_data[3],
+        instruction_data[4], instruction_data[5], instruction_data[6], instruction_data[7],
+    ]);
+
+    // SECURITY: Explicit program ownership validation
+    // PINOCCHIO: Must manually verify account is owned by our program
+    if vault_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // SECURITY: Rent from the syscall - there is no account to spoof
+    if vault_account.lamports() < minimum_balance(vault_account.data_len())? {
+        return Err(VaultError::NotRentExempt.into());
+    }
+
+    // SECURITY: Explicit initialization check - Anchor's `init` does this automatically
+    // PINOCCHIO: Must manually verify account is uninitialized
+    let vault_data = vault_account.try_borrow_data()?;
+    if vault_data.len() < mem::size_of::<Vault>() {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    
+    // Check if already initialized by looking at the data
+    let existing_owner = Pubkey::try_from(&vault_data[0..32])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if existing_owner != Pubkey::default() {
+        return Err(VaultError::AlreadyInitialized.into());
+    }
+    drop(vault_data);
+
+    // SECURITY: Now we can safely initialize with validated owner
+    let mut vault_data = vault_account.try_borrow_mut_data()?;
+    let vault = Vault {
+        owner: *owner_account.key,  // This is now validated as a signer
+        balance: initial_balance,
+    };
+    
+    // Manual serialization to account data
+    vault_data[0..32].copy_from_slice(&vault.owner.to_bytes());
+    vault_data[32..40].copy_from_slice(&vault.balance.to_le_bytes());
+
+    msg!("Secure vault initialized with owner: {} and balance: {}", vault.owner, vault.balance);
+    Ok(())
+}
+
+/// SECURE: Deposit funds with proper account validation
+/// 
+/// PINOCCHIO vs ANCHOR COMPARISON:
+/// - Anchor: `#[account(mut, has_one = owner)]` validates relationship declaratively
+/// - Pinocchio: Must explicitly validate vault.owner == owner_account.key
+pub(crate) fn secure_deposit(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // PINOCCHIO: Manual account parsing
+    let accounts_iter = &mut accounts.iter();
+    let vault_account = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let owner_account = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    // SECURITY: Explicit signer validation
+    if !owner_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // PINOCCHIO: Manual instruction data parsing
+    if instruction_data.len() < 8 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let amount = u64::from_le_bytes([
+        instruction_data[0], instruction_data[1], instruction_data[2], instruction_data[3],
+        instruction_data[4], instruction_data[5], instruction_data[6], instruction_data[7],
+    ]);
+
+    // PINOCCHIO: Manual data deserialization
+    let mut vault_data = vault_account.try_borrow_mut_data()?;
+    let mut vault = Vault::try_from_slice(&vault_data)?;
+
+    // SECURITY: Explicit ownership validation - this is the key fix!
+    // PINOCCHIO: Must manually implement what Anchor's `has_one = owner` does
+    if vault.owner != *owner_account.key {
+        return Err(VaultError::UnauthorizedOwner.into());
+    }
+
+    vault.balance = vault.balance.checked_add(amount)
+        .ok_or(VaultError::ArithmeticOverflow)?;
+
+    // PINOCCHIO: Manual serialization back to account
+    vault_data[32..40].copy_from_slice(&vault.balance.to_le_bytes());
+
+    msg!("Securely deposited {} to vault. New balance: {}", amount, vault.balance);
+    Ok(())
+}
+
+/// SECURE: Withdraw funds with proper owner validation
+/// 
+/// PINOCCHIO vs ANCHOR COMPARISON:
+/// - Anchor: `#[account(mut, has_one = owner)]` + `pub owner: Signer<'info>`
+/// - Pinocchio: Must manually validate both signer status AND ownership relationship
+pub(crate) fn secure_withdraw(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // PINOCCHIO: Manual account parsing
+    let accounts_iter = &mut accounts.iter();
+    let vault_account = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let owner_account = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    // SECURITY: Explicit signer validation
+    if !owner_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // PINOCCHIO: Manual instruction data parsing
+    if instruction_data.len() < 8 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let amount = u64::from_le_bytes([
+        instruction_data[0], instruction_data[1], instruction_data[2], instruction_data[3],
+        instruction_data[4], instruction_data[5], instruction_data[6], instruction_data[7],
+    ]);
+
+    // PINOCCHIO: Manual data deserialization
+    let mut vault_data = vault_account.try_borrow_mut_data()?;
+    let mut vault = Vault::try_from_slice(&vault_data)?;
+
+    // SECURITY: The critical fix - explicit ownership validation!
+    // PINOCCHIO: Must manually implement what Anchor's `has_one = owner` does
+    // This is the line that was missing in the vulnerable version
+    if vault.owner != *owner_account.key {
+        return Err(VaultError::UnauthorizedOwner.into());
+    }
+
+    // SECURITY: Balance check
+    if vault.balance < amount {
+        return Err(VaultError::InsufficientFunds.into());
+    }
+
+    vault.balance = vault.balance.checked_sub(amount)
+        .ok_or(VaultError::ArithmeticUnderflow)?;
+
+    // PINOCCHIO: Manual serialization back to account
+    vault_data[32..40].copy_from_slice(&vault.balance.to_le_bytes());
+
+    msg!("Securely withdrew {} from vault. New balance: {}", amount, vault.balance);
+    Ok(())
+}
@@ -0,0 +1,165 @@
+//! The vulnerable instructions, built with the `vulnerable` feature.
+//!
+//! COMPARISON: These functions show the same vulnerabilities as the Anchor version
+//! but with explicit, manual validation (or lack thereof)
+
+use super::*;
+
+/// VULNERABLE: Initialize a new vault without proper validation
+/// 
+/// PINOCCHIO vs ANCHOR COMPARISON:
+/// - Anchor: Uses declarative constraints like `#[account(init)]`
+/// - Pinocchio: Requires explicit manual validation of all account properties
+/// 
+/// Security Issue: This function doesn't validate the relationship between
+/// the vault account and the owner, just like the Anchor version.
+/// However, in Pinocchio, ALL validation must be done manually. It also
+/// checks rent exemption against rent read from a caller-supplied account,
+/// never checked to be the Rent sysvar (see `rent`).
+pub(crate) fn vulnerable_initialize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // PINOCCHIO: Manual account parsing - Anchor does this automatically
+    let accounts_iter = &mut accounts.iter();
+    let vault_account = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let owner_account = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let payer_account = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let system_program = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let rent_sysvar = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    // PINOCCHIO: Manual instruction data parsing - Anchor does this automatically
+    if instruction_data.len() < 8 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let initial_balance = u64::from_le_bytes([
+        instruction_data[0], instruction_data[1], instruction_data[2], instruction_data[3],
+        instruction_data[4], instruction_data[5], instruction_data[6], instruction_data[7],
+    ]);
+
+    // VULNERABILITY: Same as Anchor version - no validation that the signer
+    // is authorized to create a vault for the specified owner
+    // PINOCCHIO: We must manually check account ownership, size, etc.
+    if vault_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // VULNERABILITY: Rent comes from whatever account the caller put in the
+    // sysvar's slot, so a fake one with zero rent passes any vault
+    let required = minimum_balance_from_account(rent_sysvar, vault_account.data_len())?;
+    if vault_account.lamports() < required {
+        return Err(VaultError::NotRentExempt.into());
+    }
+
+    // VULNERABILITY: No validation that the vault is uninitialized
+    // PINOCCHIO: Must manually check account data state
+    let mut vault_data = vault_account.try_borrow_mut_data()?;
+    if vault_data.len() < mem::size_of::<Vault>() {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+
+    // VULNERABILITY: Setting owner without validating authorization
+    // PINOCCHIO: Manual serialization - Anchor handles this automatically
+    let vault = Vault {
+        owner: *owner_account.key,
+        balance: initial_balance,
+    };
+    
+    // Manual serialization to account data
+    vault_data[0..32].copy_from_slice(&vault.owner.to_bytes());
+    vault_data[32..40].copy_from_slice(&vault.balance.to_le_bytes());
+
+    msg!("Vulnerable vault initialized with owner: {} and balance: {}", vault.owner, vault.balance);
+    Ok(())
+}
+
+/// VULNERABLE: Deposit funds without proper account validation
+/// 
+/// PINOCCHIO vs ANCHOR COMPARISON:
+/// - Anchor: Uses `#[account(mut)]` and automatic deserialization
+/// - Pinocchio: Requires manual account validation and data parsing
+pub(crate) fn vulnerable_deposit(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // PINOCCHIO: Manual account parsing
+    let accounts_iter = &mut accounts.iter();
+    let vault_account = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let _owner_account = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    // PINOCCHIO: Manual instruction data parsing
+    if instruction_data.len() < 8 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let amount = u64::from_le_bytes([
+        instruction_data[0], instruction_data[1], instruction_data[2], instruction_data[3],
+        instruction_data[4], instruction_data[5], instruction_data[6], instruction_data[7],
+    ]);
+
+    // PINOCCHIO: Manual data deserialization
+    let mut vault_data = vault_account.try_borrow_mut_data()?;
+    let mut vault = Vault::try_from_slice(&vault_data)?;
+
+    // VULNERABILITY: Same as Anchor version - no validation that vault.owner
+    // matches the owner account. In Pinocchio, this validation must be explicit.
+    vault.balance = vault.balance.checked_add(amount)
+        .ok_or(VaultError::ArithmeticOverflow)?;
+
+    // PINOCCHIO: Manual serialization back to account
+    vault_data[32..40].copy_from_slice(&vault.balance.to_le_bytes());
+
+    msg!("Deposited {} to vault. New balance: {}", amount, vault.balance);
+    Ok(())
+}
+
+/// VULNERABLE: Withdraw funds without proper owner validation
+/// 
+/// PINOCCHIO vs ANCHOR COMPARISON:
+/// - Anchor: Uses `pub owner: Signer<'info>` for automatic signer validation
+/// - Pinocchio: Must manually check `is_signer` property
+pub(crate) fn vulnerable_withdraw(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // PINOCCHIO: Manual account parsing
+    let accounts_iter = &mut accounts.iter();
+    let vault_account = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let owner_account = accounts_iter.next().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    // PINOCCHIO: Manual signer validation - Anchor does this automatically
+    if !owner_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // PINOCCHIO: Manual instruction data parsing
+    if instruction_data.len() < 8 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let amount = u64::from_le_bytes([
+        instruction_data[0], instruction_data[1], instruction_data[2], instruction_data[3],
+        instruction_data[4], instruction_data[5], instruction_data[6], instruction_data[7],
+    ]);
+
+    // PINOCCHIO: Manual data deserialization
+    let mut vault_data = vault_account.try_borrow_mut_data()?;
+    let mut vault = Vault::try_from_slice(&vault_data)?;
+
+    // VULNERABILITY: Same critical flaw as Anchor version!
+    // We check that someone signed, but NOT that the signer is the vault owner
+    // PINOCCHIO: This validation must be explicit - we forgot to add it!
+    if vault.balance < amount {
+        return Err(VaultError::InsufficientFunds.into());
+    }
+
+    vault.balance = vault.balance.checked_sub(amount)
+        .ok_or(VaultError::ArithmeticUnderflow)?;
+
+    // PINOCCHIO: Manual serialization back to account
+    vault_data[32..40].copy_from_slice(&vault.balance.to_le_bytes());
+
+    msg!("Withdrew {} from vault. New balance: {}", amount, vault.balance);
+    Ok(())
+}
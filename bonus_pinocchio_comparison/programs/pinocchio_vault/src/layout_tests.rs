@@ -0,0 +1,21 @@
+//! Golden fixture for the vault layout (see `secref_testkit::golden`).
+//!
+//! The handlers write the vault by hand, owner then balance, and
+//! `Vault::try_from_slice` reads it back; the fixture pins both to the
+//! same bytes.
+
+use secref_testkit::{assert_golden, golden};
+
+use crate::Vault;
+
+#[test]
+fn vault_layout_is_stable() {
+    let vault = Vault { owner: [1; 32], balance: 0x2122232425262728 };
+    let mut data = vec![0; 40];
+    data[0..32].copy_from_slice(&vault.owner);
+    data[32..40].copy_from_slice(&vault.balance.to_le_bytes());
+    assert_golden!("Vault", data);
+
+    let read = Vault::try_from_slice(&golden::load(env!("CARGO_MANIFEST_DIR"), "Vault")).unwrap();
+    assert_eq!(read, vault);
+}
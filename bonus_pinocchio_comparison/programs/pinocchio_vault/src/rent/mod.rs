@@ -0,0 +1,50 @@
+//! Reading rent: from an account the caller passes, or from the syscall
+//!
+//! COMPARISON: Anchor offers `Sysvar<'info, Rent>`, which checks the account
+//! is the Rent sysvar before deserializing it, and `Rent::get()`, which needs
+//! no account at all. Pinocchio offers the same syscall, but nothing stops a
+//! handler from reading rent out of whatever account sits in the slot.
+
+use super::*;
+#[cfg(feature = "secure")]
+use pinocchio::sysvars::{rent::Rent, Sysvar};
+
+/// Bytes every account is charged for on top of its data
+#[cfg(feature = "vulnerable")]
+const ACCOUNT_STORAGE_OVERHEAD: u64 = 128;
+
+/// VULNERABLE: Minimum balance computed from rent read out of `rent_account`
+///
+/// Security Issue: The account's key is never compared with the Rent sysvar's
+/// address, so the caller picks the rent. A program-owned or system account
+/// holding `lamports_per_byte_year = 0` makes every account rent-exempt at
+/// zero lamports. This is the pattern behind legacy code that hand-parsed
+/// `AccountInfo`s instead of using `Sysvar<'info, Rent>`.
+#[cfg(feature = "vulnerable")]
+pub(crate) fn minimum_balance_from_account(rent_account: &AccountInfo, space: usize) -> Result<u64, ProgramError> {
+    // VULNERABILITY: No check that rent_account.key is the Rent sysvar
+    let data = rent_account.try_borrow_data()?;
+    if data.len() < 16 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let lamports_per_byte_year = u64::from_le_bytes(
+        data[0..8].try_into().map_err(|_| ProgramError::InvalidAccountData)?,
+    );
+    let exemption_threshold = f64::from_le_bytes(
+        data[8..16].try_into().map_err(|_| ProgramError::InvalidAccountData)?,
+    );
+
+    let rent_per_year = (ACCOUNT_STORAGE_OVERHEAD + space as u64)
+        .checked_mul(lamports_per_byte_year)
+        .ok_or(VaultError::ArithmeticOverflow)?;
+    Ok((rent_per_year as f64 * exemption_threshold) as u64)
+}
+
+/// SECURE: Minimum balance from the Rent sysvar, read through the syscall
+///
+/// Security Fix: `Rent::get()` asks the runtime for the sysvar, so there is
+/// no account for the caller to substitute - and one fewer account to pass.
+#[cfg(feature = "secure")]
+pub(crate) fn minimum_balance(space: usize) -> Result<u64, ProgramError> {
+    Ok(Rent::get()?.minimum_balance(space))
+}
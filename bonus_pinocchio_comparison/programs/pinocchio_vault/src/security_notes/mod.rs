@@ -0,0 +1,9 @@
+//! The example's write-up, rendered beside the code by `cargo doc`.
+//!
+//! Compiled only for documentation: this page is the example's README,
+//! and [`framework_comparison`] its FRAMEWORK_COMPARISON.md.
+#![doc = include_str!("../../../../README.md")]
+#![allow(rustdoc::invalid_rust_codeblocks)]
+
+#[doc = include_str!("../../../../FRAMEWORK_COMPARISON.md")]
+pub mod framework_comparison {}
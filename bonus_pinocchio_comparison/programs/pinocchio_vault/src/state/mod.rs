@@ -0,0 +1,35 @@
+//! Accounts the program owns, and their byte layouts
+
+use super::*;
+
+// ========================================
+// DATA STRUCTURES
+// ========================================
+// COMPARISON: Anchor uses `#[account]` macro for automatic serialization
+// Pinocchio requires manual implementation of serialization traits
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vault {
+    pub owner: Pubkey,    // 32 bytes
+    pub balance: u64,     // 8 bytes
+}
+
+impl Vault {
+    /// PINOCCHIO: Manual deserialization - Anchor does this automatically
+    pub fn try_from_slice(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < mem::size_of::<Self>() {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+
+        let owner_bytes: [u8; 32] = data[0..32].try_into()
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        let owner = Pubkey::from(owner_bytes);
+
+        let balance_bytes: [u8; 8] = data[32..40].try_into()
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+        let balance = u64::from_le_bytes(balance_bytes);
+
+        Ok(Self { owner, balance })
+    }
+}
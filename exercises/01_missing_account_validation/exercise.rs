@@ -0,0 +1,98 @@
+//! Attacks on the vault, and the legitimate use a fix must keep working.
+//!
+//! `attack_*` tests pass when the attack is rejected; `legitimate_*` tests
+//! pass when the instruction behaves like the secure reference. Any check
+//! that stops an attack counts - the grader only looks at the outcome.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{execute, Fixture, TestAccount};
+
+use crate::{instruction, Vault};
+
+fn vault_of(owner: Pubkey, balance: u64) -> TestAccount {
+    TestAccount::anchor(&Vault { owner, balance })
+}
+
+fn initialize(owner: TestAccount) -> Fixture {
+    Fixture::new()
+        .with("vault", TestAccount::uninitialized(8 + 32 + 8).signed())
+        .with("owner", owner)
+        .with("payer", TestAccount::signer())
+        .with("system_program", TestAccount::system_program())
+}
+
+/// The owner's vault holding 1000, with `caller` in the owner slot
+fn owned_vault(caller: impl FnOnce(Pubkey) -> TestAccount) -> Fixture {
+    let owner = TestAccount::signer();
+    Fixture::new()
+        .with("vault", vault_of(owner.key, 1_000))
+        .with("owner", caller(owner.key))
+}
+
+fn the_owner(owner: Pubkey) -> TestAccount {
+    TestAccount::signer().at(owner)
+}
+
+fn an_attacker(_: Pubkey) -> TestAccount {
+    TestAccount::signer()
+}
+
+fn the_owner_unsigned(owner: Pubkey) -> TestAccount {
+    TestAccount::system(0).at(owner)
+}
+
+#[test]
+fn attack_open_a_vault_in_someone_elses_name() {
+    let mut fixture = initialize(TestAccount::system(0));
+    assert!(execute!(&mut fixture, instruction::SecureInitialize { initial_balance: 0 }).is_err());
+}
+
+#[test]
+fn attack_withdraw_from_someone_elses_vault() {
+    let mut fixture = owned_vault(an_attacker);
+    assert!(execute!(&mut fixture, instruction::SecureWithdraw { amount: 1_000 }).is_err());
+}
+
+#[test]
+fn attack_withdraw_without_the_owners_signature() {
+    let mut fixture = owned_vault(the_owner_unsigned);
+    assert!(execute!(&mut fixture, instruction::SecureWithdraw { amount: 1_000 }).is_err());
+}
+
+#[test]
+fn attack_deposit_into_someone_elses_vault() {
+    let mut fixture = owned_vault(an_attacker);
+    assert!(execute!(&mut fixture, instruction::SecureDeposit { amount: 1 }).is_err());
+}
+
+#[test]
+fn attack_deposit_without_the_owners_signature() {
+    let mut fixture = owned_vault(the_owner_unsigned);
+    assert!(execute!(&mut fixture, instruction::SecureDeposit { amount: 1 }).is_err());
+}
+
+#[test]
+fn legitimate_owner_opens_a_vault() {
+    let owner = TestAccount::signer();
+    let key = owner.key;
+    let mut fixture = initialize(owner);
+    execute!(&mut fixture, instruction::SecureInitialize { initial_balance: 500 }).unwrap();
+
+    let vault: Vault = fixture.state("vault");
+    assert_eq!((vault.owner, vault.balance), (key, 500));
+}
+
+#[test]
+fn legitimate_owner_deposits_and_withdraws() {
+    let mut fixture = owned_vault(the_owner);
+    execute!(&mut fixture, instruction::SecureDeposit { amount: 500 }).unwrap();
+    execute!(&mut fixture, instruction::SecureWithdraw { amount: 200 }).unwrap();
+    assert_eq!(fixture.state::<Vault>("vault").balance, 1_300);
+}
+
+#[test]
+fn legitimate_owner_cannot_overdraw() {
+    let mut fixture = owned_vault(the_owner);
+    assert!(execute!(&mut fixture, instruction::SecureWithdraw { amount: 1_001 }).is_err());
+    assert_eq!(fixture.state::<Vault>("vault").balance, 1_000);
+}
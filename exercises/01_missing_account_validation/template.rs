@@ -0,0 +1,113 @@
+//! EXERCISE: Missing Account Validation
+//!
+//! This vault compiles, but every security check is missing. Each `todo!()`
+//! marks a place where one belongs. Replace it with the check - an Anchor
+//! constraint on the context, or a `require!` in the handler - then run:
+//!
+//!     npm run exercise -- grade 01
+//!
+//! The grader runs the attacks in `exercise.rs` against your program and
+//! lists the ones that still succeed.
+
+// Code after a `todo!()` is unreachable until the check replaces it
+#![allow(unreachable_code)]
+
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[cfg(test)]
+mod exercise;
+
+#[program]
+pub mod vault {
+    use super::*;
+
+    /// Create a vault for `owner` holding `initial_balance`
+    pub fn secure_initialize(ctx: Context<SecureInitialize>, initial_balance: u64) -> Result<()> {
+        todo!("nobody may open a vault in someone else's name");
+
+        let vault = &mut ctx.accounts.vault;
+        vault.owner = ctx.accounts.owner.key();
+        vault.balance = initial_balance;
+
+        msg!("Secure vault initialized with owner: {} and balance: {}", vault.owner, vault.balance);
+        Ok(())
+    }
+
+    /// Add `amount` to the owner's vault
+    pub fn secure_deposit(ctx: Context<SecureDeposit>, amount: u64) -> Result<()> {
+        todo!("only the vault's owner may deposit");
+
+        let vault = &mut ctx.accounts.vault;
+        vault.balance = vault.balance.checked_add(amount).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        msg!("Securely deposited {} to vault. New balance: {}", amount, vault.balance);
+        Ok(())
+    }
+
+    /// Take `amount` out of the owner's vault
+    pub fn secure_withdraw(ctx: Context<SecureWithdraw>, amount: u64) -> Result<()> {
+        todo!("only the vault's owner may withdraw");
+
+        let vault = &mut ctx.accounts.vault;
+        require!(vault.balance >= amount, ErrorCode::InsufficientFunds);
+        vault.balance = vault.balance.checked_sub(amount).ok_or(ErrorCode::ArithmeticUnderflow)?;
+
+        msg!("Securely withdrew {} from vault. New balance: {}", amount, vault.balance);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SecureInitialize<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 8 // discriminator + owner + balance
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// CHECK: EXERCISE - who is this, and did they agree?
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SecureDeposit<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+
+    /// CHECK: EXERCISE - who is this, and did they agree?
+    pub owner: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SecureWithdraw<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, Vault>,
+
+    /// CHECK: EXERCISE - who is this, and did they agree?
+    pub owner: UncheckedAccount<'info>,
+}
+
+#[account]
+pub struct Vault {
+    /// The owner of this vault (32 bytes)
+    pub owner: Pubkey,
+    /// The current balance in lamports (8 bytes)
+    pub balance: u64,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+    #[msg("Arithmetic underflow")]
+    ArithmeticUnderflow,
+}
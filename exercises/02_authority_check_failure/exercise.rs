@@ -0,0 +1,99 @@
+//! Attacks on the admin vault, and the legitimate use a fix must keep working.
+//!
+//! `attack_*` tests pass when the attack is rejected; `legitimate_*` tests
+//! pass when the instruction behaves like the secure reference. Every
+//! attacker here signs - the question is whether the program asks whose
+//! signature it got.
+
+use anchor_lang::prelude::*;
+use secref_testkit::{execute, Fixture, TestAccount};
+
+use crate::{instruction, AdminVault};
+
+fn initialize(owner: TestAccount) -> Fixture {
+    Fixture::new()
+        .with("vault", TestAccount::uninitialized(8 + 32 + 32 + 8).signed())
+        .with("admin", TestAccount::signer())
+        .with("owner", owner)
+        .with("payer", TestAccount::signer())
+        .with("system_program", TestAccount::system_program())
+}
+
+/// A vault holding 1000, with `caller` in the admin slot
+fn administered_vault(caller: impl FnOnce(Pubkey) -> TestAccount) -> Fixture {
+    let admin = Pubkey::new_unique();
+    Fixture::new()
+        .with(
+            "vault",
+            TestAccount::anchor(&AdminVault {
+                admin,
+                owner: Pubkey::new_unique(),
+                balance: 1_000,
+            }),
+        )
+        .with("admin", caller(admin))
+}
+
+fn the_admin(admin: Pubkey) -> TestAccount {
+    TestAccount::signer().at(admin)
+}
+
+fn an_attacker(_: Pubkey) -> TestAccount {
+    TestAccount::signer()
+}
+
+#[test]
+fn attack_open_a_vault_for_an_owner_who_did_not_sign() {
+    let mut fixture = initialize(TestAccount::system(0));
+    assert!(execute!(&mut fixture, instruction::SecureInitialize { initial_balance: 0 }).is_err());
+}
+
+#[test]
+fn attack_withdraw_as_a_stranger() {
+    let mut fixture = administered_vault(an_attacker);
+    assert!(execute!(&mut fixture, instruction::SecureAdminWithdraw { amount: 1_000 }).is_err());
+}
+
+#[test]
+fn attack_emergency_drain_as_a_stranger() {
+    let mut fixture = administered_vault(an_attacker);
+    assert!(execute!(&mut fixture, instruction::SecureEmergencyDrain {}).is_err());
+}
+
+#[test]
+fn attack_appoint_yourself_admin() {
+    let mut fixture = administered_vault(an_attacker);
+    let attacker = fixture.key("admin");
+    assert!(execute!(&mut fixture, instruction::SecureChangeAdmin { new_admin: attacker }).is_err());
+}
+
+#[test]
+fn legitimate_admin_and_owner_open_a_vault() {
+    let owner = TestAccount::signer();
+    let owner_key = owner.key;
+    let mut fixture = initialize(owner);
+    execute!(&mut fixture, instruction::SecureInitialize { initial_balance: 500 }).unwrap();
+
+    let vault: AdminVault = fixture.state("vault");
+    assert_eq!((vault.admin, vault.owner, vault.balance), (fixture.key("admin"), owner_key, 500));
+}
+
+#[test]
+fn legitimate_admin_withdraws_and_drains() {
+    let mut fixture = administered_vault(the_admin);
+    execute!(&mut fixture, instruction::SecureAdminWithdraw { amount: 400 }).unwrap();
+    assert_eq!(fixture.state::<AdminVault>("vault").balance, 600);
+    execute!(&mut fixture, instruction::SecureEmergencyDrain {}).unwrap();
+    assert_eq!(fixture.state::<AdminVault>("vault").balance, 0);
+}
+
+#[test]
+fn legitimate_admin_hands_over_the_vault() {
+    let mut fixture = administered_vault(the_admin);
+    let successor = Pubkey::new_unique();
+    execute!(&mut fixture, instruction::SecureChangeAdmin { new_admin: successor }).unwrap();
+    assert_eq!(fixture.state::<AdminVault>("vault").admin, successor);
+
+    // The previous admin no longer has any say
+    assert!(execute!(&mut fixture, instruction::SecureEmergencyDrain {}).is_err());
+}
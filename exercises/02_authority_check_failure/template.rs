@@ -0,0 +1,145 @@
+//! EXERCISE: Authority Check Failure
+//!
+//! Every instruction here demands a signature, and none of them asks whose.
+//! Each `todo!()` marks a missing authority check. Replace it with the check
+//! - an Anchor constraint on the context, or a `require!` in the handler -
+//! then run:
+//!
+//!     npm run exercise -- grade 02
+//!
+//! The grader runs the attacks in `exercise.rs` against your program and
+//! lists the ones that still succeed.
+
+// Code after a `todo!()` is unreachable until the check replaces it
+#![allow(unreachable_code)]
+
+use secref_prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[cfg(test)]
+mod exercise;
+
+#[program]
+pub mod admin_vault {
+    use super::*;
+
+    /// Create a vault administered by `admin` on behalf of `owner`
+    pub fn secure_initialize(ctx: Context<SecureInitialize>, initial_balance: u64) -> Result<()> {
+        todo!("both the admin and the owner must agree to the new vault");
+
+        let vault = &mut ctx.accounts.vault;
+        vault.admin = ctx.accounts.admin.key();
+        vault.balance = initial_balance;
+        vault.owner = ctx.accounts.owner.key();
+
+        msg!(
+            "Secure vault initialized with admin: {}, owner: {}, balance: {}",
+            vault.admin,
+            vault.owner,
+            vault.balance
+        );
+        Ok(())
+    }
+
+    /// Withdraw `amount` as the vault's admin
+    pub fn secure_admin_withdraw(ctx: Context<SecureAdminWithdraw>, amount: u64) -> Result<()> {
+        todo!("only this vault's admin may withdraw");
+
+        let vault = &mut ctx.accounts.vault;
+        require!(vault.balance >= amount, ErrorCode::InsufficientFunds);
+        vault.balance = vault.balance.checked_sub(amount).ok_or(ErrorCode::ArithmeticUnderflow)?;
+
+        msg!("Secure admin withdrew {} from vault. New balance: {}", amount, vault.balance);
+        Ok(())
+    }
+
+    /// Empty the vault in an emergency, as its admin
+    pub fn secure_emergency_drain(ctx: Context<SecureEmergencyDrain>) -> Result<()> {
+        todo!("only this vault's admin may drain it");
+
+        let vault = &mut ctx.accounts.vault;
+        let drained_amount = vault.balance;
+        vault.balance = 0;
+
+        msg!("Secure emergency drain executed by verified admin. Drained amount: {}", drained_amount);
+        Ok(())
+    }
+
+    /// Hand the vault to `new_admin`, as its current admin
+    pub fn secure_change_admin(ctx: Context<SecureChangeAdmin>, new_admin: Pubkey) -> Result<()> {
+        todo!("only this vault's current admin may appoint the next one");
+
+        let vault = &mut ctx.accounts.vault;
+        let old_admin = vault.admin;
+        vault.admin = new_admin;
+
+        msg!("Admin securely changed from {} to {}", old_admin, new_admin);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SecureInitialize<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 32 + 8, // discriminator + admin + owner + balance
+    )]
+    pub vault: Account<'info, AdminVault>,
+
+    pub admin: Signer<'info>,
+
+    /// CHECK: EXERCISE - who is this, and did they agree?
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SecureAdminWithdraw<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, AdminVault>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SecureEmergencyDrain<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, AdminVault>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SecureChangeAdmin<'info> {
+    #[account(mut)]
+    pub vault: Account<'info, AdminVault>,
+
+    pub admin: Signer<'info>,
+}
+
+#[account]
+pub struct AdminVault {
+    /// The admin who can perform administrative operations (32 bytes)
+    pub admin: Pubkey,
+    /// The owner of the vault (32 bytes)
+    pub owner: Pubkey,
+    /// The current balance in lamports (8 bytes)
+    pub balance: u64,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Insufficient funds in vault")]
+    InsufficientFunds,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+    #[msg("Arithmetic underflow")]
+    ArithmeticUnderflow,
+    #[msg("Unauthorized admin - signer is not the vault admin")]
+    UnauthorizedAdmin,
+}
@@ -0,0 +1,87 @@
+#!/usr/bin/env node
+
+/**
+ * Anchor-version compatibility matrix.
+ *
+ * Every example program depends on `shared/secref-anchor` under the name
+ * `anchor-lang`, which re-exports the Anchor release picked by an
+ * `anchor-0_29` / `anchor-0_30` feature (0.30 by default). This script
+ * builds each example against each release, so a change that only compiles
+ * on one of them is caught before it lands.
+ *
+ * Programs are built with both of their `vulnerable` and `secure` features,
+ * so every handler is checked.
+ *
+ * Only the programs are built for non-default releases: the tests use
+ * secref-testkit, which is written against 0.30. Constraint behaviour that
+ * differs between releases is tested in shared/secref-anchor/tests.
+ *
+ * Usage:
+ *   node harness/anchor-matrix.js [example ...] [--release anchor-0_29 ...]
+ */
+
+const fs = require('fs');
+const path = require('path');
+const { spawnSync } = require('child_process');
+
+const root = path.join(__dirname, '..');
+
+/** Feature name of every supported release, oldest first */
+const RELEASES = ['anchor-0_29', 'anchor-0_30'];
+
+/** Shared crates built on secref-anchor, checked alongside the examples */
+const SHARED = ['shared/secref-guards', 'shared/secref-prelude'];
+
+function examples(selected) {
+  return fs
+    .readdirSync(root)
+    .filter((dir) => /^\d\d_/.test(dir) && fs.existsSync(path.join(root, dir, 'Cargo.toml')))
+    .filter((dir) => selected.length === 0 || selected.some((s) => dir.startsWith(s)));
+}
+
+/** `cargo check` the crate at `dir` against one release; one target dir per release avoids rebuilds */
+function check(dir, release) {
+  const features = SHARED.includes(dir) ? release : `${release},vulnerable,secure`;
+  const result = spawnSync(
+    'cargo',
+    ['check', '--workspace', '--lib', '--color', 'never', '--no-default-features', '--features', features],
+    {
+      cwd: path.join(root, dir),
+      encoding: 'utf8',
+      env: {
+        ...process.env,
+        CARGO_TARGET_DIR: path.join(process.env.CARGO_TARGET_DIR ?? path.join(root, 'target'), release)
+      }
+    }
+  );
+  const errors = `${result.stderr ?? ''}`.split('\n').filter((line) => line.startsWith('error'));
+  return { ok: result.status === 0, errors };
+}
+
+function main() {
+  const args = process.argv.slice(2);
+  const releases = args.flatMap((a, i) => (args[i - 1] === '--release' ? [a] : []));
+  const selected = args.filter((a, i) => !a.startsWith('--') && args[i - 1] !== '--release');
+
+  const unknown = releases.filter((r) => !RELEASES.includes(r));
+  if (unknown.length > 0) {
+    console.error(`❌ unknown release ${unknown.join(', ')} (have: ${RELEASES.join(', ')})`);
+    return 2;
+  }
+
+  const crates = [...examples(selected), ...(selected.length === 0 ? SHARED : [])];
+  let failures = 0;
+  for (const release of releases.length > 0 ? releases : RELEASES) {
+    console.log(`🔧 ${release}`);
+    for (const dir of crates) {
+      const { ok, errors } = check(dir, release);
+      console.log(`   ${ok ? '✅' : '❌'} ${dir}`);
+      errors.forEach((line) => console.log(`      ${line}`));
+      failures += ok ? 0 : 1;
+    }
+  }
+  console.log(failures === 0 ? '\n✅ Every example builds against every release' : `\n❌ ${failures} failed`);
+  return failures > 0 ? 1 : 0;
+}
+
+process.exit(main());
@@ -0,0 +1,347 @@
+#!/usr/bin/env node
+
+/**
+ * Binary size and deploy cost of every program.
+ *
+ * For each program under `<example>/programs/` (and the Pinocchio port in
+ * bonus_pinocchio_comparison) this records the size of its built `.so`, the
+ * lamports a deploy locks up as rent, and how many instructions it exports.
+ * Programs are grouped by framework, read from their Cargo.toml: Anchor,
+ * Pinocchio, or native `solana-program`, so the cost of each can be compared.
+ *
+ * Deploy cost is the rent-exempt balance of the two accounts the upgradeable
+ * loader leaves behind: the 36-byte program account and the program data
+ * account, 45 bytes of metadata plus the `.so`. It assumes the default
+ * `--max-len`, the program's own size; every byte of headroom on top costs
+ * LAMPORTS_PER_BYTE more. The buffer account a deploy writes through is
+ * refunded when the deploy finishes, so it is not counted.
+ *
+ * Exported instructions are counted from source: the handlers in an Anchor
+ * program's `#[program]` module, and the patterns dispatched by the `match`
+ * in a Pinocchio or native program's `process_instruction`.
+ *
+ * Sizes are compared with harness/binary-sizes.json. A program that grew by
+ * more than TOLERANCE since its recorded size is a regression, and the exit
+ * code is non-zero; `--bless` records the current sizes instead. Each run
+ * also appends its measurements to harness/reports/binary-size-history.jsonl,
+ * so sizes can be followed across commits. Results are written to
+ * harness/reports/binary-size.md and binary-size.json.
+ *
+ * Programs are read from `<example>/target/deploy`, where `anchor build` and
+ * `cargo build-sbf` put them; one that has not been built is "not built" and
+ * does not count either way.
+ *
+ * Usage:
+ *   node harness/binary-size.js [example ...] [--build] [--bless]
+ *
+ *   --build  Run `cargo build-sbf` for each program first (needs the Solana toolchain)
+ *   --bless  Write the measured sizes to harness/binary-sizes.json
+ */
+
+const fs = require('fs');
+const path = require('path');
+const { spawnSync } = require('child_process');
+
+const root = path.join(__dirname, '..');
+const reportDir = path.join(__dirname, 'reports');
+const baselinePath = path.join(__dirname, 'binary-sizes.json');
+
+/** Rent-exempt lamports per byte: 3480 lamports per byte-year, two years */
+const LAMPORTS_PER_BYTE = 3480 * 2;
+/** Account header every rent calculation adds to the data length */
+const ACCOUNT_STORAGE_OVERHEAD = 128;
+/** Upgradeable loader account sizes, without the program's bytes */
+const PROGRAM_ACCOUNT_LEN = 36;
+const PROGRAM_DATA_METADATA_LEN = 45;
+/** Growth over the recorded size that counts as a regression */
+const TOLERANCE = 0.02;
+
+const FRAMEWORKS = ['anchor', 'pinocchio', 'native'];
+
+function rentExempt(dataLen) {
+  return (ACCOUNT_STORAGE_OVERHEAD + dataLen) * LAMPORTS_PER_BYTE;
+}
+
+function deployLamports(bytes) {
+  return rentExempt(PROGRAM_ACCOUNT_LEN) + rentExempt(PROGRAM_DATA_METADATA_LEN + bytes);
+}
+
+// ========================================
+// SOURCE SCANNING
+// ========================================
+
+/**
+ * Index of the bracket closing the one at `open`, skipping strings,
+ * character literals and comments. Lifetimes (`'info`) are not literals.
+ */
+function matchingBracket(src, open) {
+  const pairs = { '(': ')', '[': ']', '{': '}' };
+  const stack = [pairs[src[open]]];
+  let i = open + 1;
+  while (i < src.length && stack.length > 0) {
+    const c = src[i];
+    if (c === '"') {
+      i++;
+      while (i < src.length && src[i] !== '"') {
+        i += src[i] === '\\' ? 2 : 1;
+      }
+    } else if (c === "'" && (src[i + 2] === "'" || src[i + 1] === '\\')) {
+      i = src.indexOf("'", i + (src[i + 1] === '\\' ? 3 : 2));
+    } else if (c === '/' && src[i + 1] === '/') {
+      i = src.indexOf('\n', i);
+      if (i === -1) {
+        break;
+      }
+    } else if (c === '/' && src[i + 1] === '*') {
+      i = src.indexOf('*/', i) + 1;
+    } else if (pairs[c]) {
+      stack.push(pairs[c]);
+    } else if (c === stack[stack.length - 1]) {
+      stack.pop();
+      if (stack.length === 0) {
+        return i;
+      }
+    }
+    i++;
+  }
+  throw new Error(`unbalanced ${src[open]} at offset ${open}`);
+}
+
+/** Handlers of the `#[program]` module: its `pub fn`s, not counting functions nested in them */
+function anchorInstructions(src) {
+  const program = /#\[program\]\s*pub\s+mod\s+\w+\s*\{/.exec(src);
+  if (!program) {
+    return null;
+  }
+  const open = program.index + program[0].length - 1;
+  const close = matchingBracket(src, open);
+  const re = /\bpub\s+fn\s+\w+/g;
+  re.lastIndex = open;
+  let count = 0;
+  let m;
+  while ((m = re.exec(src)) && m.index < close) {
+    count++;
+    re.lastIndex = matchingBracket(src, src.indexOf('{', m.index));
+  }
+  return count;
+}
+
+/** Patterns the first `match` in `process_instruction` dispatches on, without the `_` fallback */
+function dispatchedInstructions(src) {
+  const entry = /\bfn\s+process_instruction\s*\(/.exec(src);
+  const dispatch = entry && /\bmatch\b[^{]*\{/.exec(src.slice(entry.index));
+  if (!dispatch) {
+    return null;
+  }
+  const open = entry.index + dispatch.index + dispatch[0].length - 1;
+  const close = matchingBracket(src, open);
+  let count = 0;
+  let start = open + 1;
+  for (let i = start; i < close; i++) {
+    if ('([{'.includes(src[i])) {
+      i = matchingBracket(src, i);
+    } else if (src.startsWith('=>', i)) {
+      const pattern = src.slice(start, i).replace(/#\[[^\]]*\]/g, '').replace(/^[\s,]+/, '');
+      count += pattern.split('|').filter((p) => p.trim() !== '' && p.trim() !== '_').length;
+      // Skip the arm's expression: a block, or everything up to the next top-level comma
+      let j = i + 2;
+      while (/\s/.test(src[j])) {
+        j++;
+      }
+      if (src[j] === '{') {
+        j = matchingBracket(src, j);
+      } else {
+        while (j < close && src[j] !== ',') {
+          j = '([{'.includes(src[j]) ? matchingBracket(src, j) + 1 : j + 1;
+        }
+      }
+      start = j + 1;
+      i = j;
+    }
+  }
+  return count;
+}
+
+// ========================================
+// PROGRAMS
+// ========================================
+
+function exampleDirs(selected) {
+  return fs
+    .readdirSync(root)
+    .filter((dir) => /^\d\d_/.test(dir) || dir === 'bonus_pinocchio_comparison')
+    .filter((dir) => fs.existsSync(path.join(root, dir, 'programs')))
+    .filter((dir) => selected.length === 0 || selected.some((s) => dir.startsWith(s)))
+    .sort();
+}
+
+function frameworkOf(manifest) {
+  if (/^\s*anchor-lang\s*=/m.test(manifest)) {
+    return 'anchor';
+  }
+  if (/^\s*pinocchio\s*=/m.test(manifest)) {
+    return 'pinocchio';
+  }
+  return /^\s*solana-program\s*=/m.test(manifest) ? 'native' : null;
+}
+
+/** Every program of an example, with its framework, library name and exported instructions */
+function programsOf(example) {
+  const programsDir = path.join(root, example, 'programs');
+  return fs
+    .readdirSync(programsDir)
+    .filter((dir) => fs.existsSync(path.join(programsDir, dir, 'Cargo.toml')))
+    .sort()
+    .map((dir) => {
+      const manifestPath = path.join(programsDir, dir, 'Cargo.toml');
+      const manifest = fs.readFileSync(manifestPath, 'utf8');
+      const lib = /\[lib\][^[]*?\bname\s*=\s*"([^"]+)"/.exec(manifest);
+      const framework = frameworkOf(manifest);
+      const src = fs.readFileSync(path.join(programsDir, dir, 'src', 'lib.rs'), 'utf8');
+      return {
+        example,
+        program: dir,
+        manifestPath,
+        lib: lib ? lib[1] : dir.replace(/-/g, '_'),
+        framework,
+        instructions: framework === 'anchor' ? anchorInstructions(src) : dispatchedInstructions(src)
+      };
+    })
+    .filter((program) => program.framework !== null);
+}
+
+function build(program) {
+  const deployDir = path.join(root, program.example, 'target', 'deploy');
+  const result = spawnSync(
+    'cargo',
+    ['build-sbf', '--manifest-path', program.manifestPath, '--sbf-out-dir', deployDir],
+    { cwd: path.join(root, program.example), encoding: 'utf8' }
+  );
+  return result.status === 0;
+}
+
+/** Bytes of the program's built `.so`, or null when it has not been built */
+function measure(program) {
+  const so = path.join(root, program.example, 'target', 'deploy', `${program.lib}.so`);
+  return fs.existsSync(so) ? fs.statSync(so).size : null;
+}
+
+function gitCommit() {
+  const result = spawnSync('git', ['rev-parse', '--short', 'HEAD'], { cwd: root, encoding: 'utf8' });
+  return result.status === 0 ? result.stdout.trim() : null;
+}
+
+// ========================================
+// MAIN
+// ========================================
+
+function main() {
+  const args = process.argv.slice(2);
+  const selected = args.filter((a) => !a.startsWith('--'));
+  const buildFirst = args.includes('--build');
+  const bless = args.includes('--bless');
+
+  const baseline = fs.existsSync(baselinePath) ? JSON.parse(fs.readFileSync(baselinePath, 'utf8')) : {};
+  const results = [];
+  for (const example of exampleDirs(selected)) {
+    for (const program of programsOf(example)) {
+      const built = !buildFirst || build(program);
+      const bytes = built ? measure(program) : null;
+      const key = `${program.example}/${program.program}`;
+      const recorded = baseline[key]?.bytes ?? null;
+      let status = 'not built';
+      if (bytes !== null) {
+        status = recorded === null ? 'not recorded' : bytes > recorded * (1 + TOLERANCE) ? 'regression' : 'ok';
+      }
+      results.push({
+        key,
+        framework: program.framework,
+        instructions: program.instructions,
+        bytes,
+        deployLamports: bytes === null ? null : deployLamports(bytes),
+        recorded,
+        status
+      });
+    }
+  }
+
+  if (bless) {
+    for (const r of results.filter((r) => r.bytes !== null)) {
+      baseline[r.key] = { framework: r.framework, instructions: r.instructions, bytes: r.bytes };
+    }
+    const sorted = Object.fromEntries(Object.keys(baseline).sort().map((key) => [key, baseline[key]]));
+    fs.writeFileSync(baselinePath, `${JSON.stringify(sorted, null, 2)}\n`);
+    console.log(`📏 Recorded sizes written to ${path.relative(process.cwd(), baselinePath)}`);
+  }
+  return writeReport(results, bless);
+}
+
+// ========================================
+// REPORT
+// ========================================
+
+function kib(bytes) {
+  return `${(bytes / 1024).toFixed(1)} KiB`;
+}
+
+function sol(lamports) {
+  return `${(lamports / 1e9).toFixed(3)} SOL`;
+}
+
+function writeReport(results, bless) {
+  const lines = [
+    '# Binary Size and Deploy Cost',
+    '',
+    '| Program | Framework | Instructions | Size | Deploy rent | Recorded | Status |',
+    '|---------|-----------|--------------|------|-------------|----------|--------|'
+  ];
+  for (const r of results) {
+    const size = r.bytes === null ? '-' : kib(r.bytes);
+    const rent = r.deployLamports === null ? '-' : sol(r.deployLamports);
+    const recorded = r.recorded === null ? '-' : kib(r.recorded);
+    const status = r.status === 'regression' ? '❌ regression' : r.status === 'ok' ? '✅' : r.status;
+    lines.push(`| ${r.key} | ${r.framework} | ${r.instructions ?? '-'} | ${size} | ${rent} | ${recorded} | ${status} |`);
+  }
+
+  lines.push('', '## By Framework', '', '| Framework | Built | Mean size | Mean size per instruction | Mean deploy rent |');
+  lines.push('|-----------|-------|-----------|---------------------------|------------------|');
+  for (const framework of FRAMEWORKS) {
+    const built = results.filter((r) => r.framework === framework && r.bytes !== null);
+    if (built.length === 0) {
+      lines.push(`| ${framework} | 0 | - | - | - |`);
+      continue;
+    }
+    const mean = (values) => values.reduce((a, b) => a + b, 0) / values.length;
+    const perInstruction = built.filter((r) => r.instructions).map((r) => r.bytes / r.instructions);
+    lines.push(
+      `| ${framework} | ${built.length} | ${kib(mean(built.map((r) => r.bytes)))} | ` +
+        `${perInstruction.length === 0 ? '-' : kib(mean(perInstruction))} | ` +
+        `${sol(mean(built.map((r) => r.deployLamports)))} |`
+    );
+  }
+
+  const regressions = results.filter((r) => r.status === 'regression');
+  const unbuilt = results.filter((r) => r.status === 'not built').length;
+  lines.push('', `Regressions: ${regressions.length}, not built: ${unbuilt}`, '');
+
+  const measured = results.filter((r) => r.bytes !== null);
+  fs.mkdirSync(reportDir, { recursive: true });
+  fs.writeFileSync(path.join(reportDir, 'binary-size.json'), JSON.stringify(results, null, 2));
+  if (measured.length > 0) {
+    const entry = {
+      date: new Date().toISOString(),
+      commit: gitCommit(),
+      programs: Object.fromEntries(measured.map((r) => [r.key, r.bytes]))
+    };
+    fs.appendFileSync(path.join(reportDir, 'binary-size-history.jsonl'), `${JSON.stringify(entry)}\n`);
+  }
+  const summaryPath = path.join(reportDir, 'binary-size.md');
+  fs.writeFileSync(summaryPath, lines.join('\n'));
+
+  console.log(`📦 Binary size report written to ${path.relative(process.cwd(), summaryPath)}`);
+  console.log(`   Regressions: ${regressions.length}, not built: ${unbuilt}`);
+  regressions.forEach((r) => console.log(`   ❌ ${r.key}: ${kib(r.recorded)} -> ${kib(r.bytes)}`));
+  return regressions.length > 0 && !bless ? 1 : 0;
+}
+
+process.exit(main());
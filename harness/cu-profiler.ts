@@ -0,0 +1,210 @@
+/**
+ * Compute-unit profiler shared by every example's test suite.
+ *
+ * Each example records the compute units (CU) consumed by the instructions it
+ * executes against a real validator. Recorded values are compared with the
+ * baselines in `cu-budgets.json`: a run fails when an instruction consumes more
+ * than its baseline plus CU_REGRESSION_TOLERANCE. Every run also writes a
+ * summary table to `harness/reports/` so the performance claims made in code
+ * comments (e.g. "Anchor constraints cost N CU") have a measurable basis.
+ *
+ * In mock mode (no validator available) nothing is recorded and budgets are
+ * not asserted, matching the behaviour of the rest of the test suite.
+ */
+
+import * as fs from "fs";
+import * as path from "path";
+import { Connection, TransactionSignature } from "@solana/web3.js";
+import { parseLogs } from "./secref-logs";
+
+/** Allowed regression over the recorded baseline before the suite fails (10%). */
+export const CU_REGRESSION_TOLERANCE = 0.1;
+
+/** Solana's per-transaction default compute limit, used as an absolute ceiling. */
+export const DEFAULT_CU_LIMIT = 200_000;
+
+/** Set `SECREF_CU_UPDATE=1` to write measured values back as the new baselines. */
+export const UPDATE_ENV_VAR = "SECREF_CU_UPDATE";
+
+const BUDGETS_PATH = path.join(__dirname, "cu-budgets.json");
+const REPORT_DIR = path.join(__dirname, "reports");
+
+/** Baselines per example, per instruction. `null` means "not yet recorded". */
+export type CuBudgets = Record<string, Record<string, number | null>>;
+
+export interface CuSample {
+  example: string;
+  instruction: string;
+  unitsConsumed: number;
+  /** Recorded baseline for this instruction, if any. */
+  baseline: number | null;
+  /** Upper bound derived from the baseline (baseline * 1.1, rounded up). */
+  upperBound: number;
+  withinBudget: boolean;
+}
+
+export function loadBudgets(budgetsPath: string = BUDGETS_PATH): CuBudgets {
+  return JSON.parse(fs.readFileSync(budgetsPath, "utf8")) as CuBudgets;
+}
+
+/** Upper bound for a baseline: unrecorded instructions only get the runtime limit. */
+export function upperBoundFor(baseline: number | null): number {
+  if (baseline === null) {
+    return DEFAULT_CU_LIMIT;
+  }
+  return Math.ceil(baseline * (1 + CU_REGRESSION_TOLERANCE));
+}
+
+/**
+ * Extracts the consumed compute units from a transaction's log messages.
+ *
+ * Used as a fallback for RPC nodes that do not populate
+ * `meta.computeUnitsConsumed`. Only top-level invocations are counted; CPI
+ * consumption is already included in the outer program's total.
+ */
+export function unitsFromLogs(programId: string, logs: string[]): number | null {
+  const invocations = parseLogs(logs).invocations.filter(
+    (invocation) => invocation.programId === programId && invocation.unitsConsumed !== undefined
+  );
+  if (invocations.length === 0) {
+    return null;
+  }
+  return invocations.reduce((sum, invocation) => sum + (invocation.unitsConsumed ?? 0), 0);
+}
+
+export class CuProfiler {
+  private readonly samples: CuSample[] = [];
+  private readonly budgets: CuBudgets;
+
+  constructor(
+    public readonly example: string,
+    private readonly budgetsPath: string = BUDGETS_PATH
+  ) {
+    this.budgets = loadBudgets(budgetsPath);
+    if (!this.budgets[example]) {
+      this.budgets[example] = {};
+    }
+  }
+
+  /** Returns the baseline for an instruction of this example, if recorded. */
+  baselineFor(instruction: string): number | null {
+    const baseline = this.budgets[this.example][instruction];
+    return baseline === undefined ? null : baseline;
+  }
+
+  /** Records a raw CU measurement for `instruction`. */
+  recordUnits(instruction: string, unitsConsumed: number): CuSample {
+    const baseline = this.baselineFor(instruction);
+    const upperBound = upperBoundFor(baseline);
+    const sample: CuSample = {
+      example: this.example,
+      instruction,
+      unitsConsumed,
+      baseline,
+      upperBound,
+      withinBudget: unitsConsumed <= upperBound,
+    };
+    this.samples.push(sample);
+    return sample;
+  }
+
+  /**
+   * Fetches a confirmed transaction and records the CU it consumed.
+   *
+   * Returns `null` when the transaction or its metadata is unavailable (e.g.
+   * the validator pruned it), so callers never fail on missing telemetry.
+   */
+  async record(
+    instruction: string,
+    connection: Connection,
+    signature: TransactionSignature,
+    programId?: string
+  ): Promise<CuSample | null> {
+    const tx = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    if (!tx || !tx.meta) {
+      return null;
+    }
+
+    let units = tx.meta.computeUnitsConsumed ?? null;
+    if (units === null && programId && tx.meta.logMessages) {
+      units = unitsFromLogs(programId, tx.meta.logMessages);
+    }
+    if (units === null) {
+      return null;
+    }
+    return this.recordUnits(instruction, units);
+  }
+
+  get recorded(): readonly CuSample[] {
+    return this.samples;
+  }
+
+  /** Throws if any recorded instruction regressed past its upper bound. */
+  assertBudgets(): void {
+    const regressions = this.samples.filter((sample) => !sample.withinBudget);
+    if (regressions.length === 0) {
+      return;
+    }
+    const details = regressions
+      .map(
+        (sample) =>
+          `  ${sample.instruction}: ${sample.unitsConsumed} CU > ${sample.upperBound} CU ` +
+          `(baseline ${sample.baseline ?? "none"})`
+      )
+      .join("\n");
+    throw new Error(
+      `Compute-unit regression in ${this.example} ` +
+        `(tolerance ${CU_REGRESSION_TOLERANCE * 100}%):\n${details}`
+    );
+  }
+
+  /** Renders the recorded samples as a markdown table. */
+  summaryTable(): string {
+    const header = [
+      `### ${this.example}`,
+      "",
+      "| Instruction | CU consumed | Baseline | Upper bound | Status |",
+      "|-------------|-------------|----------|-------------|--------|",
+    ];
+    const rows = this.samples.map(
+      (sample) =>
+        `| ${sample.instruction} | ${sample.unitsConsumed} | ${sample.baseline ?? "-"} | ` +
+        `${sample.upperBound} | ${sample.withinBudget ? "✅" : "❌ regression"} |`
+    );
+    return [...header, ...rows, ""].join("\n");
+  }
+
+  /**
+   * Writes `reports/<example>.md` and `reports/<example>.json`.
+   *
+   * When `SECREF_CU_UPDATE=1` is set, measured values also replace the
+   * baselines in `cu-budgets.json`. Returns the path of the markdown report.
+   */
+  writeSummary(): string | null {
+    if (this.samples.length === 0) {
+      return null;
+    }
+
+    fs.mkdirSync(REPORT_DIR, { recursive: true });
+    const markdownPath = path.join(REPORT_DIR, `${this.example}.md`);
+    fs.writeFileSync(markdownPath, this.summaryTable());
+    fs.writeFileSync(
+      path.join(REPORT_DIR, `${this.example}.json`),
+      JSON.stringify(this.samples, null, 2) + "\n"
+    );
+
+    if (process.env[UPDATE_ENV_VAR] === "1") {
+      const budgets = loadBudgets(this.budgetsPath);
+      budgets[this.example] = budgets[this.example] || {};
+      for (const sample of this.samples) {
+        budgets[this.example][sample.instruction] = sample.unitsConsumed;
+      }
+      fs.writeFileSync(this.budgetsPath, JSON.stringify(budgets, null, 2) + "\n");
+    }
+
+    return markdownPath;
+  }
+}
@@ -0,0 +1,57 @@
+#!/usr/bin/env node
+
+/**
+ * Combines the per-example compute-unit reports written by the test suites
+ * into a single summary table (harness/reports/cu-summary.md).
+ */
+
+const fs = require('fs');
+const path = require('path');
+
+const reportDir = path.join(__dirname, 'reports');
+const budgets = JSON.parse(fs.readFileSync(path.join(__dirname, 'cu-budgets.json'), 'utf8'));
+
+const lines = [
+  '# Compute Unit Summary',
+  '',
+  '| Example | Instruction | CU consumed | Baseline | Status |',
+  '|---------|-------------|-------------|----------|--------|'
+];
+
+let regressions = 0;
+let unmeasured = 0;
+
+for (const example of Object.keys(budgets)) {
+  const reportPath = path.join(reportDir, `${example}.json`);
+  const samples = fs.existsSync(reportPath)
+    ? JSON.parse(fs.readFileSync(reportPath, 'utf8'))
+    : [];
+
+  for (const instruction of Object.keys(budgets[example])) {
+    const sample = samples.find((s) => s.instruction === instruction);
+    const baseline = budgets[example][instruction];
+    if (!sample) {
+      unmeasured++;
+      lines.push(`| ${example} | ${instruction} | - | ${baseline ?? '-'} | not measured |`);
+      continue;
+    }
+    if (!sample.withinBudget) {
+      regressions++;
+    }
+    const status = sample.withinBudget ? '✅' : '❌ regression';
+    lines.push(`| ${example} | ${instruction} | ${sample.unitsConsumed} | ${baseline ?? '-'} | ${status} |`);
+  }
+}
+
+lines.push('');
+lines.push(`Regressions: ${regressions}, not measured: ${unmeasured}`);
+lines.push('');
+
+fs.mkdirSync(reportDir, { recursive: true });
+const summaryPath = path.join(reportDir, 'cu-summary.md');
+fs.writeFileSync(summaryPath, lines.join('\n'));
+
+console.log(`📊 Compute unit summary written to ${path.relative(process.cwd(), summaryPath)}`);
+console.log(`   Regressions: ${regressions}, not measured: ${unmeasured}`);
+
+process.exit(regressions > 0 ? 1 : 0);
@@ -0,0 +1,251 @@
+#!/usr/bin/env node
+
+/**
+ * Patch-the-vuln exercises.
+ *
+ * Each directory in exercises/ holds a `template.rs` - an example program
+ * with its security checks replaced by `todo!()` - and an `exercise.rs`
+ * attack corpus. `start` copies the template into a standalone crate for the
+ * learner; the secure reference stays in the example directory, out of the
+ * workspace. `grade` runs the corpus against the learner's program off-chain
+ * and reports which attacks still succeed and which legitimate flows no
+ * longer match the reference.
+ *
+ * Usage:
+ *   node harness/exercise.js list
+ *   node harness/exercise.js start <lesson> [dir]   (default exercises/work/<lesson>)
+ *   node harness/exercise.js grade <lesson> [dir]
+ *   node harness/exercise.js verify [lesson ...]    (CI: reference passes, template does not)
+ */
+
+const fs = require('fs');
+const os = require('os');
+const path = require('path');
+const { spawnSync } = require('child_process');
+
+const root = path.join(__dirname, '..');
+const exercisesDir = path.join(root, 'exercises');
+const workDir = path.join(exercisesDir, 'work');
+
+// ========================================
+// EXERCISES
+// ========================================
+
+function exercises() {
+  return fs
+    .readdirSync(exercisesDir)
+    .filter((dir) => /^\d\d_/.test(dir) && fs.existsSync(path.join(exercisesDir, dir, 'template.rs')));
+}
+
+/** Resolve `01`, `1` or `01_missing_account_validation` to an exercise */
+function exercise(lesson) {
+  const prefix = /^\d+$/.test(lesson) ? `${lesson.padStart(2, '0')}_` : lesson;
+  const name = exercises().find((dir) => dir === lesson || dir.startsWith(prefix));
+  if (!name) {
+    throw new Error(`no exercise for lesson \`${lesson}\` (have: ${exercises().join(', ')})`);
+  }
+  const template = fs.readFileSync(path.join(exercisesDir, name, 'template.rs'), 'utf8');
+  const program = /#\[program\]\s*pub mod (\w+)/.exec(template)[1];
+  return {
+    name,
+    template,
+    corpus: fs.readFileSync(path.join(exercisesDir, name, 'exercise.rs'), 'utf8'),
+    programDir: path.join(root, name, 'programs', program)
+  };
+}
+
+/** A standalone crate at `dir` with the program's manifest and the given sources */
+function writeCrate(ex, dir, sources) {
+  fs.rmSync(path.join(dir, 'src'), { recursive: true, force: true });
+  fs.mkdirSync(path.join(dir, 'src'), { recursive: true });
+
+  // Path dependencies are relative to the example; pin them before moving the manifest
+  let manifest = fs
+    .readFileSync(path.join(ex.programDir, 'Cargo.toml'), 'utf8')
+    .replace(/path = "([^"]+)"/g, (_, dep) => `path = ${JSON.stringify(path.resolve(ex.programDir, dep))}`);
+  if (!/^\[workspace\]/m.test(manifest)) {
+    manifest += '\n[workspace]\n';
+  }
+  fs.writeFileSync(path.join(dir, 'Cargo.toml'), manifest);
+
+  for (const [file, source] of Object.entries(sources)) {
+    fs.mkdirSync(path.dirname(path.join(dir, 'src', file)), { recursive: true });
+    fs.writeFileSync(path.join(dir, 'src', file), source);
+  }
+}
+
+/** The example's own sources, with the attack corpus declared */
+function referenceSources(ex) {
+  const src = path.join(ex.programDir, 'src');
+  const sources = {};
+  for (const file of fs.readdirSync(src, { recursive: true })) {
+    if (fs.statSync(path.join(src, file)).isFile()) {
+      sources[file] = fs.readFileSync(path.join(src, file), 'utf8');
+    }
+  }
+  sources['lib.rs'] += '\n#[cfg(test)]\nmod exercise;\n';
+  sources['exercise.rs'] = ex.corpus;
+  return sources;
+}
+
+// ========================================
+// GRADING
+// ========================================
+
+function cargoTest(dir, env) {
+  const result = spawnSync('cargo', ['test', '--color', 'never', 'exercise::'], {
+    cwd: dir,
+    encoding: 'utf8',
+    env: { ...process.env, ...env }
+  });
+  return { status: result.status, output: `${result.stdout ?? ''}${result.stderr ?? ''}` };
+}
+
+/** Per-test outcomes from `cargo test` output: pass, fail or todo (hit a `todo!()`) */
+function outcomes(output) {
+  const results = [];
+  for (const [, name, status] of output.matchAll(/^test exercise::(\w+) \.\.\. (ok|FAILED)$/gm)) {
+    const details = new RegExp(`---- exercise::${name} stdout ----\\n([\\s\\S]*?)(?=\\n---- |\\n\\nfailures:)`).exec(output);
+    const todo = details && /not yet implemented(?:: (.*))?/.exec(details[1]);
+    results.push({
+      name,
+      kind: name.startsWith('attack_') ? 'attack' : 'legitimate',
+      description: name.replace(/^(attack|legitimate)_/, '').replace(/_/g, ' '),
+      outcome: status === 'ok' ? 'pass' : todo ? 'todo' : 'fail',
+      todo: todo ? todo[1] ?? '' : null
+    });
+  }
+  return results;
+}
+
+function grade(ex, dir, env) {
+  const { status, output } = cargoTest(dir, env);
+  const results = outcomes(output);
+  if (results.length === 0) {
+    return { compiled: false, output, results };
+  }
+  return { compiled: true, passed: status === 0, output, results };
+}
+
+function printGrade(ex, dir, graded) {
+  console.log(`🎯 Grading ${ex.name} (${path.relative(process.cwd(), dir) || '.'})`);
+  if (!graded.compiled) {
+    console.log('\n❌ The program does not build:\n');
+    console.log(graded.output.trim());
+    return;
+  }
+  const label = {
+    attack: { pass: '✅ blocked ', fail: '❌ succeeds', todo: '🚧 todo!() ' },
+    legitimate: { pass: '✅ works   ', fail: '❌ broken  ', todo: '🚧 todo!() ' }
+  };
+  for (const [kind, heading] of [
+    ['attack', 'Attacks'],
+    ['legitimate', 'Legitimate use']
+  ]) {
+    console.log(`\n${heading}:`);
+    for (const r of graded.results.filter((r) => r.kind === kind)) {
+      console.log(`   ${label[kind][r.outcome]}  ${r.description}${r.todo ? ` - ${r.todo}` : ''}`);
+    }
+  }
+  const count = (kind) => {
+    const rows = graded.results.filter((r) => r.kind === kind);
+    return `${rows.filter((r) => r.outcome === 'pass').length}/${rows.length}`;
+  };
+  console.log(`\nResult: ${count('attack')} attacks blocked, ${count('legitimate')} legitimate flows match the reference`);
+}
+
+// ========================================
+// COMMANDS
+// ========================================
+
+function start(lesson, dir) {
+  const ex = exercise(lesson);
+  dir = path.resolve(dir ?? path.join(workDir, ex.name));
+  if (fs.existsSync(path.join(dir, 'src', 'lib.rs'))) {
+    throw new Error(`${dir} already has an exercise in progress; remove it to start over`);
+  }
+  writeCrate(ex, dir, { 'lib.rs': ex.template, 'exercise.rs': ex.corpus });
+  console.log(`📝 ${ex.name} exercise created in ${path.relative(process.cwd(), dir)}`);
+  console.log(`   Replace every todo!() in src/lib.rs, then run:`);
+  console.log(`   node harness/exercise.js grade ${ex.name.slice(0, 2)}${dir === path.join(workDir, ex.name) ? '' : ` ${dir}`}`);
+  return 0;
+}
+
+function gradeCommand(lesson, dir) {
+  const ex = exercise(lesson);
+  dir = path.resolve(dir ?? path.join(workDir, ex.name));
+  if (!fs.existsSync(path.join(dir, 'src', 'lib.rs'))) {
+    throw new Error(`no exercise in ${dir}; run \`node harness/exercise.js start ${lesson}\` first`);
+  }
+  // Keep the corpus current, in case the learner edited it
+  fs.writeFileSync(path.join(dir, 'src', 'exercise.rs'), ex.corpus);
+  const graded = grade(ex, dir);
+  printGrade(ex, dir, graded);
+  return graded.compiled && graded.passed ? 0 : 1;
+}
+
+/** The corpus must pass against the reference and catch the unpatched template */
+function verify(lessons) {
+  const targetDir = process.env.CARGO_TARGET_DIR ?? path.join(os.tmpdir(), 'secref-exercises-target');
+  let failures = 0;
+  for (const name of lessons.length > 0 ? lessons.map((l) => exercise(l).name) : exercises()) {
+    const ex = exercise(name);
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), `secref-${name}-`));
+    try {
+      writeCrate(ex, dir, referenceSources(ex));
+      const reference = grade(ex, dir, { CARGO_TARGET_DIR: targetDir });
+      const referenceFailures = reference.results.filter((r) => r.outcome !== 'pass');
+      if (!reference.compiled || referenceFailures.length > 0) {
+        failures++;
+        console.log(`❌ ${name}: the secure reference fails the corpus`);
+        referenceFailures.forEach((r) => console.log(`   ${r.name}`));
+        if (!reference.compiled) console.log(reference.output.trim());
+        continue;
+      }
+
+      writeCrate(ex, dir, { 'lib.rs': ex.template, 'exercise.rs': ex.corpus });
+      const template = grade(ex, dir, { CARGO_TARGET_DIR: targetDir });
+      const solved = template.results.filter((r) => r.kind === 'attack' && r.outcome === 'pass');
+      if (!template.compiled || solved.length > 0) {
+        failures++;
+        console.log(`❌ ${name}: the template ${template.compiled ? 'already blocks attacks' : 'does not build'}`);
+        solved.forEach((r) => console.log(`   ${r.name}`));
+        if (!template.compiled) console.log(template.output.trim());
+        continue;
+      }
+      const attacks = reference.results.filter((r) => r.kind === 'attack').length;
+      console.log(`✅ ${name}: reference blocks ${attacks} attacks, template blocks none`);
+    } finally {
+      fs.rmSync(dir, { recursive: true, force: true });
+    }
+  }
+  return failures > 0 ? 1 : 0;
+}
+
+function main() {
+  const [command, ...args] = process.argv.slice(2);
+  switch (command) {
+    case 'list':
+      exercises().forEach((name) => console.log(name));
+      return 0;
+    case 'start':
+    case 'grade':
+      if (!args[0]) {
+        console.error(`usage: node harness/exercise.js ${command} <lesson> [dir]`);
+        return 2;
+      }
+      return command === 'start' ? start(args[0], args[1]) : gradeCommand(args[0], args[1]);
+    case 'verify':
+      return verify(args);
+    default:
+      console.error('usage: node harness/exercise.js <list|start|grade|verify> ...');
+      return 2;
+  }
+}
+
+try {
+  process.exit(main());
+} catch (error) {
+  console.error(`❌ ${error.message}`);
+  process.exit(1);
+}
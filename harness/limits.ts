@@ -0,0 +1,149 @@
+/**
+ * Runtime limit probes.
+ *
+ * SBF programs run with a 4KB stack frame per function, a 32KB heap and a
+ * compute budget. Exceeding any of them fails the transaction with a
+ * runtime message that rarely points at the cause - a context with too
+ * many unboxed accounts shows up as `Access violation in stack frame`, not
+ * as "your accounts are too big".
+ *
+ * `classifyFailure` maps those messages to the limit that was hit, and
+ * `probeLimit` binary-searches an instruction parameter (bytes allocated,
+ * accounts loaded, iterations) for the largest value that still succeeds,
+ * recording the compute units it consumed. Results are written to
+ * `harness/reports/<example>-limits.md` so the limits quoted in an
+ * example's README have a measured basis.
+ *
+ * In mock mode (no validator available) nothing is probed, matching the
+ * behaviour of the rest of the test suite.
+ */
+
+import * as fs from "fs";
+import * as path from "path";
+import { Connection, TransactionSignature } from "@solana/web3.js";
+import { failedInvocation, parseLogs } from "./secref-logs";
+import { unitsFromLogs } from "./cu-profiler";
+
+const REPORT_DIR = path.join(__dirname, "reports");
+
+/** Which runtime limit a failed transaction ran into. */
+export type LimitKind = "stack" | "heap" | "compute" | "other";
+
+const STACK_FAILURE = /Access violation in stack frame|stack frame|Stack overflow/i;
+const HEAP_FAILURE = /memory allocation failed|out of memory/i;
+const COMPUTE_FAILURE = /exceeded CUs meter|Computational budget exceeded|compute units/i;
+
+/**
+ * Classifies the failure in a transaction's logs, or returns `null` if
+ * nothing failed.
+ */
+export function classifyFailure(logs: string[]): LimitKind | null {
+  const failed = failedInvocation(parseLogs(logs));
+  if (!failed) {
+    return null;
+  }
+  const reasons = [failed.failure ?? "", ...failed.logs].join("\n");
+  if (STACK_FAILURE.test(reasons)) {
+    return "stack";
+  }
+  if (HEAP_FAILURE.test(reasons)) {
+    return "heap";
+  }
+  if (COMPUTE_FAILURE.test(reasons)) {
+    return "compute";
+  }
+  return "other";
+}
+
+/** Log messages attached to a rejected send, if the error carries any. */
+export function logsOf(error: unknown): string[] {
+  const logs = (error as { logs?: string[] } | null)?.logs;
+  return Array.isArray(logs) ? logs : [];
+}
+
+export interface LimitResult {
+  label: string;
+  /** Largest value that succeeded, or `null` if even `low` failed. */
+  largestPassing: number | null;
+  /** Smallest value that failed, or `null` if even `high` succeeded. */
+  smallestFailing: number | null;
+  /** Limit hit at `smallestFailing`. */
+  failure: LimitKind | null;
+  /** Compute units consumed at `largestPassing`. */
+  unitsAtLargest: number | null;
+}
+
+/**
+ * Finds the largest `n` in `[low, high]` for which `attempt(n)` succeeds.
+ *
+ * `attempt` sends one transaction and resolves to its confirmed signature;
+ * a rejection counts as a failure at `n`. Assumes success is monotonic:
+ * if `n` fails, every larger value fails too.
+ */
+export async function probeLimit(
+  connection: Connection,
+  label: string,
+  programId: string,
+  attempt: (n: number) => Promise<TransactionSignature>,
+  low: number,
+  high: number
+): Promise<LimitResult> {
+  let largestPassing: number | null = null;
+  let passingSignature: TransactionSignature | null = null;
+  let smallestFailing: number | null = null;
+  let failure: LimitKind | null = null;
+
+  let lo = low;
+  let hi = high;
+  while (lo <= hi) {
+    const mid = Math.floor((lo + hi) / 2);
+    try {
+      passingSignature = await attempt(mid);
+      largestPassing = mid;
+      lo = mid + 1;
+    } catch (error) {
+      smallestFailing = mid;
+      failure = classifyFailure(logsOf(error)) ?? "other";
+      hi = mid - 1;
+    }
+  }
+
+  let unitsAtLargest: number | null = null;
+  if (passingSignature) {
+    const tx = await connection.getTransaction(passingSignature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    unitsAtLargest = tx?.meta?.logMessages ? unitsFromLogs(programId, tx.meta.logMessages) : null;
+  }
+
+  return { label, largestPassing, smallestFailing, failure, unitsAtLargest };
+}
+
+/** Renders results as a markdown table. */
+export function limitsTable(example: string, results: LimitResult[]): string {
+  const header = [
+    `### ${example} limits`,
+    "",
+    "| Probe | Largest passing | Smallest failing | Limit hit | CU at largest |",
+    "|-------|-----------------|------------------|-----------|---------------|",
+  ];
+  const cell = (value: number | string | null) => (value === null ? "-" : String(value));
+  const rows = results.map(
+    (result) =>
+      `| ${result.label} | ${cell(result.largestPassing)} | ${cell(result.smallestFailing)} | ` +
+      `${cell(result.failure)} | ${cell(result.unitsAtLargest)} |`
+  );
+  return [...header, ...rows, ""].join("\n");
+}
+
+/** Writes `reports/<example>-limits.md` and returns its path. */
+export function writeLimitsReport(example: string, results: LimitResult[]): string | null {
+  if (results.length === 0) {
+    return null;
+  }
+  fs.mkdirSync(REPORT_DIR, { recursive: true });
+  const reportPath = path.join(REPORT_DIR, `${example}-limits.md`);
+  fs.writeFileSync(reportPath, limitsTable(example, results));
+  return reportPath;
+}
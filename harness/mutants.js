@@ -0,0 +1,449 @@
+#!/usr/bin/env node
+
+/**
+ * Mutation testing for the secure implementations.
+ *
+ * Every documented fix is a check: a `require!` in a secure handler, a
+ * constraint on a secure account context, a `Signer` where anyone could
+ * otherwise pass. This script deletes those checks one at a time and re-runs
+ * the example's tests. A mutant the tests still pass with ("survived") is a
+ * check nothing enforces - the fix could be reverted and CI would stay green.
+ *
+ * Mutation sites, per program:
+ * - `require*!` statements in `secure_*` handlers, and in helper functions
+ *   only secure handlers call
+ * - `has_one`, `constraint`, `address`, `owner`, `executable`, `signer`,
+ *   `seeds` (with its `bump`) and `token::*` clauses on the contexts of
+ *   `secure_*` handlers, in the program's instructions module
+ * - `Signer<'info>` fields on those contexts, downgraded to `UncheckedAccount`
+ * - `checked_*(..).ok_or(..)?` in secure handlers, replaced by `wrapping_*`
+ *
+ * Each mutant is compiled first; one that does not compile is "unviable" and
+ * does not count either way. Results are written to harness/reports/mutants.md
+ * and mutants.json. The exit code is non-zero when any mutant survives.
+ *
+ * Usage:
+ *   node harness/mutants.js [example ...] [--list] [--exploits]
+ *
+ *   --list      Print the mutants without running anything
+ *   --exploits  Also run `anchor test` against mutants the Rust tests miss
+ *               (needs the Solana toolchain and a local validator)
+ */
+
+const fs = require('fs');
+const path = require('path');
+const { spawnSync } = require('child_process');
+
+const root = path.join(__dirname, '..');
+const reportDir = path.join(__dirname, 'reports');
+
+/** Per-mutant time limit; a hung test run counts as killed */
+const TIMEOUT_MS = 10 * 60 * 1000;
+
+/** Context clauses that are checks; removing `mut`, `init`, `payer` or `space` is not a meaningful mutant */
+const CLAUSES = [
+  /^has_one\b/,
+  /^constraint\b/,
+  /^address\b/,
+  /^owner\b/,
+  /^executable$/,
+  /^signer$/,
+  /^seeds\b/,
+  /^token::(mint|authority)\b/,
+  /^associated_token::(mint|authority)\b/
+];
+
+// ========================================
+// SOURCE SCANNING
+// ========================================
+
+/**
+ * Index of the bracket closing the one at `open`, skipping strings,
+ * character literals and comments. Lifetimes (`'info`) are not literals.
+ */
+function matchingBracket(src, open) {
+  const pairs = { '(': ')', '[': ']', '{': '}' };
+  const stack = [pairs[src[open]]];
+  let i = open + 1;
+  while (i < src.length && stack.length > 0) {
+    const c = src[i];
+    if (c === '"') {
+      i++;
+      while (i < src.length && src[i] !== '"') {
+        i += src[i] === '\\' ? 2 : 1;
+      }
+    } else if (c === "'" && (src[i + 2] === "'" || src[i + 1] === '\\')) {
+      i = src.indexOf("'", i + (src[i + 1] === '\\' ? 3 : 2));
+    } else if (c === '/' && src[i + 1] === '/') {
+      i = src.indexOf('\n', i);
+      if (i === -1) {
+        break;
+      }
+    } else if (c === '/' && src[i + 1] === '*') {
+      i = src.indexOf('*/', i) + 1;
+    } else if (pairs[c]) {
+      stack.push(pairs[c]);
+    } else if (c === stack[stack.length - 1]) {
+      stack.pop();
+      if (stack.length === 0) {
+        return i;
+      }
+    }
+    i++;
+  }
+  throw new Error(`unbalanced ${src[open]} at offset ${open}`);
+}
+
+/** 1-based line number of an offset */
+function lineOf(src, offset) {
+  return src.slice(0, offset).split('\n').length;
+}
+
+/** Spans of inline `#[cfg(test)]` modules, whose functions are not program code */
+function testModules(src) {
+  const spans = [];
+  const re = /#\[cfg\(test\)\]\s*mod\s+\w+\s*\{/g;
+  let m;
+  while ((m = re.exec(src))) {
+    const open = m.index + m[0].length - 1;
+    spans.push([open, matchingBracket(src, open)]);
+  }
+  return spans;
+}
+
+/** Free functions and handlers: name, context type and body span */
+function functions(src) {
+  const tests = testModules(src);
+  const found = [];
+  const re = /\bfn\s+(\w+)\s*(<[^>]*>)?\s*\(/g;
+  let m;
+  while ((m = re.exec(src))) {
+    if (tests.some(([start, end]) => m.index > start && m.index < end)) {
+      continue;
+    }
+    const paramsOpen = m.index + m[0].length - 1;
+    const paramsClose = matchingBracket(src, paramsOpen);
+    const bodyOpen = src.indexOf('{', paramsClose);
+    if (src.slice(paramsClose, bodyOpen).includes(';')) {
+      continue; // trait method without a body
+    }
+    const params = src.slice(paramsOpen, paramsClose);
+    const ctx = /Context<(?:'\w+,\s*)*(\w+)/.exec(params);
+    found.push({
+      name: m[1],
+      context: ctx ? ctx[1] : null,
+      start: bodyOpen,
+      end: matchingBracket(src, bodyOpen)
+    });
+  }
+  return found;
+}
+
+/** `require*!(..);` statements inside [start, end) */
+function requireSites(src, fn) {
+  const sites = [];
+  const re = /\brequire(_\w+)?!\s*\(/g;
+  re.lastIndex = fn.start;
+  let m;
+  while ((m = re.exec(src)) && m.index < fn.end) {
+    const start = m.index;
+    const close = matchingBracket(src, start + m[0].length - 1);
+    const end = src[close + 1] === ';' ? close + 2 : close + 1;
+    const text = src.slice(start, end);
+    sites.push({
+      line: lineOf(src, start),
+      kind: 'delete require',
+      description: `delete \`${text.replace(/\s+/g, ' ').slice(0, 80)}\` in ${fn.name}`,
+      apply: (s) => s.slice(0, start) + s.slice(end)
+    });
+    re.lastIndex = end;
+  }
+  return sites;
+}
+
+/** `.checked_op(x).ok_or(e)?` inside a handler, replaced by `.wrapping_op(x)` */
+function checkedArithmeticSites(src, fn) {
+  const sites = [];
+  const re = /\.checked_(add|sub|mul|div)\s*\(/g;
+  re.lastIndex = fn.start;
+  let m;
+  while ((m = re.exec(src)) && m.index < fn.end) {
+    const argsClose = matchingBracket(src, m.index + m[0].length - 1);
+    const rest = /^\s*\.ok_or\s*\(/.exec(src.slice(argsClose + 1));
+    if (!rest) {
+      continue;
+    }
+    const okOrClose = matchingBracket(src, argsClose + rest[0].length);
+    if (src[okOrClose + 1] !== '?') {
+      continue;
+    }
+    const start = m.index;
+    const args = src.slice(m.index + m[0].length, argsClose);
+    const op = m[1];
+    sites.push({
+      line: lineOf(src, start),
+      kind: 'unchecked arithmetic',
+      description: `checked_${op} -> wrapping_${op} in ${fn.name}`,
+      apply: (s) => `${s.slice(0, start)}.wrapping_${op}(${args})${s.slice(okOrClose + 2)}`
+    });
+  }
+  return sites;
+}
+
+/** Split an attribute's argument list on top-level commas, keeping offsets */
+function splitClauses(src, open, close) {
+  const clauses = [];
+  let start = open + 1;
+  for (let i = open + 1; i <= close; i++) {
+    const c = src[i];
+    if ('([{'.includes(c)) {
+      i = matchingBracket(src, i);
+      continue;
+    }
+    if (c === '/' && src[i + 1] === '/') {
+      i = src.indexOf('\n', i);
+      continue;
+    }
+    if (c === ',' || i === close) {
+      // Trailing `// ...` notes are dropped: the clauses are rejoined on one line
+      const text = src
+        .slice(start, i)
+        .replace(/\/\/[^\n]*/g, '')
+        .trim();
+      if (text) {
+        clauses.push({ text, start, end: i });
+      }
+      start = i + 1;
+    }
+  }
+  return clauses;
+}
+
+/** Check clauses and signers on the `#[derive(Accounts)]` struct `name` */
+function contextSites(src, name) {
+  const decl = new RegExp(`pub struct ${name}\\b[^{]*\\{`).exec(src);
+  if (!decl) {
+    return [];
+  }
+  const bodyOpen = decl.index + decl[0].length - 1;
+  const bodyClose = matchingBracket(src, bodyOpen);
+  const body = src.slice(bodyOpen, bodyClose);
+  const sites = [];
+
+  const attr = /#\[account\s*\(/g;
+  let m;
+  while ((m = attr.exec(body))) {
+    const open = bodyOpen + m.index + m[0].length - 1;
+    const close = matchingBracket(src, open);
+    const field = /pub\s+(\w+)\s*:/.exec(src.slice(close));
+    const clauses = splitClauses(src, open, close);
+    for (const clause of clauses) {
+      if (!CLAUSES.some((re) => re.test(clause.text)) || clause.text.startsWith('seeds::')) {
+        continue;
+      }
+      // A PDA check is `seeds` plus its `bump` (and `seeds::program`); remove them together
+      const removed = clause.text.startsWith('seeds =')
+        ? clauses.filter((c) => /^(seeds|bump)\b/.test(c.text))
+        : [clause];
+      const kept = clauses.filter((c) => !removed.includes(c)).map((c) => c.text);
+      sites.push({
+        line: lineOf(src, clause.start),
+        kind: 'delete constraint',
+        description: `delete \`${clause.text.replace(/\s+/g, ' ').slice(0, 80)}\` on ${name}.${field ? field[1] : '?'}`,
+        apply: (s) => `${s.slice(0, open + 1)}${kept.join(', ')}${s.slice(close)}`
+      });
+    }
+    attr.lastIndex = close - bodyOpen;
+  }
+
+  const signer = /pub\s+(\w+)\s*:\s*Signer<'info>/g;
+  while ((m = signer.exec(body))) {
+    const start = bodyOpen + m.index;
+    const end = start + m[0].length;
+    const replacement = m[0].replace("Signer<'info>", "UncheckedAccount<'info>");
+    sites.push({
+      line: lineOf(src, start),
+      kind: 'drop signer',
+      description: `${name}.${m[1]}: Signer -> UncheckedAccount`,
+      apply: (s) => s.slice(0, start) + replacement + s.slice(end)
+    });
+  }
+  return sites;
+}
+
+/**
+ * Every mutant of one program. Handlers and their helpers are in lib.rs;
+ * a context is in instructions/mod.rs, or in instructions/secure.rs when
+ * only secure handlers use it.
+ */
+function mutantsOf(programDir) {
+  const srcDir = path.join(programDir, 'src');
+  const file = path.join(srcDir, 'lib.rs');
+  const src = fs.readFileSync(file, 'utf8');
+  const fns = functions(src);
+  const secure = fns.filter((fn) => fn.name.startsWith('secure_'));
+  const vulnerable = fns.filter((fn) => !fn.name.startsWith('secure_'));
+
+  const calls = (fn, name) => new RegExp(`\\b${name}\\s*\\(`).test(src.slice(fn.start, fn.end));
+  // Helpers only secure handlers reach are part of the fix
+  const helpers = fns.filter(
+    (helper) =>
+      !helper.context &&
+      secure.some((fn) => calls(fn, helper.name)) &&
+      !vulnerable.some((fn) => fn !== helper && calls(fn, helper.name))
+  );
+
+  const sites = [];
+  for (const fn of [...secure, ...helpers]) {
+    sites.push(...requireSites(src, fn), ...checkedArithmeticSites(src, fn));
+  }
+  const mutants = sites.map((site) => ({ file, ...site }));
+
+  const sources = ['lib.rs', 'instructions/mod.rs', 'instructions/secure.rs']
+    .map((name) => path.join(srcDir, name))
+    .filter((candidate) => fs.existsSync(candidate))
+    .map((candidate) => ({ file: candidate, src: fs.readFileSync(candidate, 'utf8') }));
+  const contexts = [...new Set(secure.map((fn) => fn.context).filter(Boolean))];
+  for (const context of contexts) {
+    for (const source of sources) {
+      mutants.push(...contextSites(source.src, context).map((site) => ({ file: source.file, ...site })));
+    }
+  }
+  return mutants.sort((a, b) => a.file.localeCompare(b.file) || a.line - b.line);
+}
+
+// ========================================
+// RUNNING
+// ========================================
+
+function run(cmd, args, cwd) {
+  const result = spawnSync(cmd, args, { cwd, encoding: 'utf8', timeout: TIMEOUT_MS });
+  return { ok: result.status === 0, timedOut: result.error?.code === 'ETIMEDOUT' };
+}
+
+/** Run one mutant; the original source is always restored */
+function test(example, mutant, exploits) {
+  const original = fs.readFileSync(mutant.file, 'utf8');
+  fs.writeFileSync(mutant.file, mutant.apply(original));
+  try {
+    if (!run('cargo', ['test', '--quiet', '--no-run'], example).ok) {
+      return 'unviable';
+    }
+    if (!run('cargo', ['test', '--quiet'], example).ok) {
+      return 'killed';
+    }
+    if (exploits && !run('anchor', ['test', '--skip-lint'], example).ok) {
+      return 'killed (exploit suite)';
+    }
+    return 'survived';
+  } finally {
+    fs.writeFileSync(mutant.file, original);
+  }
+}
+
+function examples(selected) {
+  return fs
+    .readdirSync(root)
+    .filter((dir) => /^\d\d_/.test(dir) && fs.existsSync(path.join(root, dir, 'Cargo.toml')))
+    .filter((dir) => selected.length === 0 || selected.some((s) => dir.startsWith(s)));
+}
+
+function main() {
+  const args = process.argv.slice(2);
+  const list = args.includes('--list');
+  const exploits = args.includes('--exploits');
+  const selected = args.filter((a) => !a.startsWith('--'));
+
+  const results = [];
+  for (const example of examples(selected)) {
+    const dir = path.join(root, example);
+    const programs = path.join(dir, 'programs');
+    const mutants = fs
+      .readdirSync(programs)
+      .map((program) => path.join(programs, program))
+      .filter((program) => fs.existsSync(path.join(program, 'src', 'lib.rs')))
+      .flatMap(mutantsOf);
+
+    if (list) {
+      for (const mutant of mutants) {
+        console.log(`${path.relative(root, mutant.file)}:${mutant.line}  ${mutant.description}`);
+      }
+      continue;
+    }
+    if (mutants.length === 0) {
+      continue;
+    }
+
+    console.log(`🧬 ${example}: ${mutants.length} mutants`);
+    if (!run('cargo', ['test', '--quiet'], dir).ok) {
+      console.log(`   ⚠️  tests fail without mutations, skipping`);
+      results.push({ example, error: 'baseline tests fail' });
+      continue;
+    }
+    for (const mutant of mutants) {
+      const outcome = test(dir, mutant, exploits);
+      const icon = outcome.startsWith('killed') ? '✅' : outcome === 'survived' ? '❌' : '➖';
+      console.log(`   ${icon} ${path.relative(dir, mutant.file)}:${mutant.line} ${mutant.description} (${outcome})`);
+      results.push({
+        example,
+        file: path.relative(root, mutant.file),
+        line: mutant.line,
+        kind: mutant.kind,
+        description: mutant.description,
+        outcome
+      });
+    }
+  }
+
+  if (list) {
+    return 0;
+  }
+  return writeReport(results);
+}
+
+// ========================================
+// REPORT
+// ========================================
+
+function writeReport(results) {
+  const lines = ['# Security Check Coverage', '', '| Example | Mutants | Killed | Survived | Unviable | Coverage |', '|---------|---------|--------|----------|----------|----------|'];
+  const survivors = [];
+  let killed = 0;
+  let viable = 0;
+
+  for (const example of [...new Set(results.map((r) => r.example))]) {
+    const rows = results.filter((r) => r.example === example && r.outcome);
+    if (rows.length === 0) {
+      lines.push(`| ${example} | - | - | - | - | baseline tests fail |`);
+      continue;
+    }
+    const k = rows.filter((r) => r.outcome.startsWith('killed')).length;
+    const s = rows.filter((r) => r.outcome === 'survived');
+    const u = rows.filter((r) => r.outcome === 'unviable').length;
+    killed += k;
+    viable += k + s.length;
+    survivors.push(...s);
+    const coverage = k + s.length === 0 ? '-' : `${Math.round((100 * k) / (k + s.length))}%`;
+    lines.push(`| ${example} | ${rows.length} | ${k} | ${s.length} | ${u} | ${coverage} |`);
+  }
+
+  lines.push('', `Checks enforced by tests: ${killed}/${viable}`, '');
+  if (survivors.length > 0) {
+    lines.push('## Surviving Mutants', '', 'Each of these checks can be removed without any test failing.', '');
+    for (const s of survivors) {
+      lines.push(`- \`${s.file}:${s.line}\` ${s.description}`);
+    }
+    lines.push('');
+  }
+
+  fs.mkdirSync(reportDir, { recursive: true });
+  fs.writeFileSync(path.join(reportDir, 'mutants.json'), JSON.stringify(results, null, 2));
+  const summaryPath = path.join(reportDir, 'mutants.md');
+  fs.writeFileSync(summaryPath, lines.join('\n'));
+
+  console.log(`🧬 Mutation report written to ${path.relative(process.cwd(), summaryPath)}`);
+  console.log(`   Checks enforced by tests: ${killed}/${viable}, survived: ${survivors.length}`);
+  return survivors.length > 0 ? 1 : 0;
+}
+
+process.exit(main());
@@ -0,0 +1,275 @@
+/**
+ * Transaction ordering simulation for front-running demonstrations.
+ *
+ * Front-running is a question of order: the same two transactions are
+ * harmless one way round and an attack the other. On mainnet the leader
+ * (or a block builder it sells ordering to) decides that order, usually by
+ * priority fee or by bundle. A local validator has no auction and no
+ * bundles, so `race` reproduces the leader's choice instead:
+ *
+ * - `ordering` picks the order - as listed, by priority fee (highest first,
+ *   as a fee-ordered leader would), or an explicit list of labels, as a
+ *   searcher's bundle would.
+ * - `mode: "sequence"` sends each contender as its own transaction, back to
+ *   back in that order, sharing one blockhash. Each lands or fails on its
+ *   own, like transactions a leader packs into a slot. The local validator
+ *   processes them in arrival order, and the result reports the slot and
+ *   position each actually landed at, so a test can check the order held.
+ * - `mode: "bundle"` packs every contender into one transaction. Order and
+ *   slot are then exact, and like a bundle it lands or reverts as a whole.
+ *
+ * Results are written to `harness/reports/<example>-ordering.md`.
+ *
+ * In mock mode (no validator available) nothing is sent, matching the
+ * behaviour of the rest of the test suite.
+ */
+
+import * as fs from "fs";
+import * as path from "path";
+import {
+  ComputeBudgetProgram,
+  Connection,
+  Signer,
+  Transaction,
+  TransactionInstruction,
+  TransactionSignature,
+} from "@solana/web3.js";
+
+const REPORT_DIR = path.join(__dirname, "reports");
+
+/** One party's transaction in a race. */
+export interface Contender {
+  /** Name used to order contenders and in results. */
+  label: string;
+  instructions: TransactionInstruction[];
+  /** The first signer pays the fee. */
+  signers: Signer[];
+  /** Compute unit price in micro-lamports; prepended as a ComputeBudget instruction. */
+  priorityFee?: number;
+}
+
+/**
+ * How the simulated leader orders contenders: as listed, by priority fee,
+ * or by an explicit list of labels.
+ */
+export type Ordering = "submitted" | "priority-fee" | string[];
+
+export type RaceMode = "sequence" | "bundle";
+
+export interface RaceOptions {
+  ordering?: Ordering;
+  mode?: RaceMode;
+}
+
+export interface ContenderOutcome {
+  label: string;
+  landed: boolean;
+  signature: TransactionSignature | null;
+  slot: number | null;
+  /** Index of the transaction within its block, when known. */
+  position: number | null;
+  /** Error the transaction failed with, if it failed. */
+  error: string | null;
+  logs: string[];
+}
+
+export interface RaceResult {
+  label: string;
+  mode: RaceMode;
+  /** Order the contenders were submitted in. */
+  order: string[];
+  /** One outcome per contender, in submission order. */
+  outcomes: ContenderOutcome[];
+  /** Whether every landed transaction shares one slot. */
+  sameSlot: boolean;
+  /** Whether the landed transactions executed in submission order. */
+  orderHeld: boolean;
+}
+
+/**
+ * Puts contenders in the order a leader following `ordering` would.
+ *
+ * Priority-fee ordering is stable, so contenders with equal fees keep their
+ * listed order. An explicit ordering must name every contender once.
+ */
+export function orderContenders(contenders: Contender[], ordering: Ordering = "submitted"): Contender[] {
+  if (ordering === "submitted") {
+    return [...contenders];
+  }
+  if (ordering === "priority-fee") {
+    return contenders
+      .map((contender, index) => ({ contender, index }))
+      .sort((a, b) => (b.contender.priorityFee ?? 0) - (a.contender.priorityFee ?? 0) || a.index - b.index)
+      .map(({ contender }) => contender);
+  }
+
+  const byLabel = new Map(contenders.map((contender) => [contender.label, contender]));
+  if (ordering.length !== contenders.length || new Set(ordering).size !== ordering.length) {
+    throw new Error(`Ordering [${ordering.join(", ")}] must name each contender exactly once`);
+  }
+  return ordering.map((label) => {
+    const contender = byLabel.get(label);
+    if (!contender) {
+      throw new Error(`Ordering names unknown contender "${label}"`);
+    }
+    return contender;
+  });
+}
+
+/** The contender's instructions, behind its priority fee if it pays one. */
+function instructionsOf(contender: Contender): TransactionInstruction[] {
+  const fee =
+    contender.priorityFee === undefined
+      ? []
+      : [ComputeBudgetProgram.setComputeUnitPrice({ microLamports: contender.priorityFee })];
+  return [...fee, ...contender.instructions];
+}
+
+/**
+ * Submits competing transactions in the order `options.ordering` gives and
+ * reports where each landed.
+ *
+ * A contender that fails is reported, not thrown, since a failed victim
+ * transaction is often the point of the demonstration.
+ */
+export async function race(
+  connection: Connection,
+  label: string,
+  contenders: Contender[],
+  options: RaceOptions = {}
+): Promise<RaceResult> {
+  const mode = options.mode ?? "sequence";
+  const ordered = orderContenders(contenders, options.ordering);
+  const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash("confirmed");
+
+  const transactions =
+    mode === "bundle"
+      ? [
+          {
+            labels: ordered.map((contender) => contender.label),
+            instructions: ([] as TransactionInstruction[]).concat(...ordered.map(instructionsOf)),
+            signers: ([] as Signer[]).concat(...ordered.map((contender) => contender.signers)),
+          },
+        ]
+      : ordered.map((contender) => ({
+          labels: [contender.label],
+          instructions: instructionsOf(contender),
+          signers: contender.signers,
+        }));
+
+  // Sent back to back; each send resolves once the RPC node has the transaction
+  const signatures: TransactionSignature[] = [];
+  for (const { instructions, signers } of transactions) {
+    const transaction = new Transaction({ feePayer: signers[0].publicKey, blockhash, lastValidBlockHeight });
+    transaction.add(...instructions);
+    transaction.sign(...uniqueSigners(signers));
+    signatures.push(await connection.sendRawTransaction(transaction.serialize(), { skipPreflight: true }));
+  }
+
+  const outcomes: ContenderOutcome[] = [];
+  for (const [index, { labels }] of transactions.entries()) {
+    const outcome = await outcomeOf(connection, signatures[index], blockhash, lastValidBlockHeight);
+    labels.forEach((contender) => outcomes.push({ ...outcome, label: contender }));
+  }
+
+  const landed = outcomes.filter((outcome) => outcome.landed);
+  const slots = new Set(landed.map((outcome) => outcome.slot));
+  const executed = [...landed].sort(
+    (a, b) => (a.slot ?? 0) - (b.slot ?? 0) || (a.position ?? 0) - (b.position ?? 0)
+  );
+  return {
+    label,
+    mode,
+    order: ordered.map((contender) => contender.label),
+    outcomes,
+    sameSlot: slots.size <= 1,
+    orderHeld: executed.every((outcome, index) => outcome.label === landed[index].label),
+  };
+}
+
+/** The outcome of one contender in `result`. */
+export function outcomeFor(result: RaceResult, label: string): ContenderOutcome {
+  const outcome = result.outcomes.find((candidate) => candidate.label === label);
+  if (!outcome) {
+    throw new Error(`Race "${result.label}" has no contender "${label}"`);
+  }
+  return outcome;
+}
+
+function uniqueSigners(signers: Signer[]): Signer[] {
+  const seen = new Set<string>();
+  return signers.filter((signer) => {
+    const key = signer.publicKey.toBase58();
+    return seen.has(key) ? false : (seen.add(key), true);
+  });
+}
+
+async function outcomeOf(
+  connection: Connection,
+  signature: TransactionSignature,
+  blockhash: string,
+  lastValidBlockHeight: number
+): Promise<ContenderOutcome> {
+  const confirmation = await connection.confirmTransaction(
+    { signature, blockhash, lastValidBlockHeight },
+    "confirmed"
+  );
+  const tx = await connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+
+  let position: number | null = null;
+  if (tx) {
+    const block = await connection.getBlock(tx.slot, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+      transactionDetails: "signatures",
+      rewards: false,
+    });
+    const index = block?.signatures.indexOf(signature) ?? -1;
+    position = index < 0 ? null : index;
+  }
+
+  const error = confirmation.value.err;
+  return {
+    label: "",
+    landed: error === null,
+    signature,
+    slot: tx?.slot ?? null,
+    position,
+    error: error === null ? null : JSON.stringify(error),
+    logs: tx?.meta?.logMessages ?? [],
+  };
+}
+
+/** Renders results as a markdown table. */
+export function orderingTable(example: string, results: RaceResult[]): string {
+  const header = [
+    `### ${example} ordering`,
+    "",
+    "| Race | Mode | Order | Outcomes | Same slot | Order held |",
+    "|------|------|-------|----------|-----------|------------|",
+  ];
+  const rows = results.map((result) => {
+    const outcomes = result.outcomes
+      .map((outcome) => `${outcome.label}: ${outcome.landed ? `slot ${outcome.slot}` : "failed"}`)
+      .join(", ");
+    return (
+      `| ${result.label} | ${result.mode} | ${result.order.join(" → ")} | ${outcomes} | ` +
+      `${result.sameSlot ? "yes" : "no"} | ${result.orderHeld ? "yes" : "no"} |`
+    );
+  });
+  return [...header, ...rows, ""].join("\n");
+}
+
+/** Writes `reports/<example>-ordering.md` and returns its path. */
+export function writeOrderingReport(example: string, results: RaceResult[]): string | null {
+  if (results.length === 0) {
+    return null;
+  }
+  fs.mkdirSync(REPORT_DIR, { recursive: true });
+  const reportPath = path.join(REPORT_DIR, `${example}-ordering.md`);
+  fs.writeFileSync(reportPath, orderingTable(example, results));
+  return reportPath;
+}
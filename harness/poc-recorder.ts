@@ -0,0 +1,300 @@
+/**
+ * Exploit proof-of-concept recorder shared by every example's test suite.
+ *
+ * An exploit test proves an attack works today; a recorded proof of concept
+ * proves it still works - or is still blocked - after the program, Anchor or
+ * the test code changes, and can be handed to an auditor without the test
+ * suite. With `SECREF_POC_RECORD=1`, every transaction the suite sends (its
+ * message bytes, the keypairs that signed it and whether it succeeded) and
+ * every airdrop is written in order to `harness/pocs/<example>.json`.
+ * `harness/poc-replay.js` re-executes those files against freshly deployed
+ * programs and reports any transaction whose outcome changed.
+ *
+ * Fixtures hold the secret keys of every signer, so recording only happens
+ * against a local validator and never with the Solana CLI's default keypair
+ * as the provider wallet: record with a throwaway `ANCHOR_WALLET`.
+ *
+ * Transactions using address lookup tables are recorded but only replay if
+ * the tables are recreated by an earlier recorded transaction at the same
+ * address.
+ *
+ * In mock mode (no validator available) nothing is recorded, matching the
+ * behaviour of the rest of the test suite.
+ */
+
+import * as fs from "fs";
+import * as os from "os";
+import * as path from "path";
+import { createHash } from "crypto";
+import { AnchorProvider } from "@coral-xyz/anchor";
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  sendAndConfirmTransaction,
+  Signer,
+  Transaction,
+  TransactionSignature,
+  VersionedTransaction,
+} from "@solana/web3.js";
+import { failedInvocation, parseLogs } from "./secref-logs";
+
+/** Version of the fixture format; bumped on any incompatible change. */
+export const POC_FORMAT_VERSION = 1;
+
+/** Set `SECREF_POC_RECORD=1` to write the suite's transactions to `harness/pocs/`. */
+export const RECORD_ENV_VAR = "SECREF_POC_RECORD";
+
+const ROOT = path.join(__dirname, "..");
+const POC_DIR = path.join(__dirname, "pocs");
+const DEFAULT_CLI_KEYPAIR = path.join(os.homedir(), ".config", "solana", "id.json");
+
+/** A program the fixture's transactions call, deployed fresh before replay. */
+export interface PocProgram {
+  name: string;
+  id: string;
+  /** Path of the program binary, relative to the repository root. */
+  binary: string;
+  /** SHA-256 of the binary at record time, if it was built. */
+  sha256: string | null;
+}
+
+/** Whether a transaction landed, and the runtime's reason if it did not. */
+export interface PocOutcome {
+  ok: boolean;
+  /** Raw reason from `Program <id> failed: <reason>`, when the logs report one. */
+  failure?: string;
+}
+
+export interface PocAirdrop {
+  kind: "airdrop";
+  /** Full title of the test that requested it. */
+  test: string;
+  to: string;
+  lamports: number;
+}
+
+export interface PocTransaction {
+  kind: "transaction";
+  /** Full title of the test that sent it. */
+  test: string;
+  /** Serialized message, base64; its blockhash is replaced on replay. */
+  message: string;
+  /** Secret keys of every signer, base64. */
+  signers: string[];
+  outcome: PocOutcome;
+}
+
+export type PocStep = PocAirdrop | PocTransaction;
+
+/** Contents of `harness/pocs/<example>.json`. */
+export interface PocFixture {
+  version: number;
+  example: string;
+  /** Provider wallet, funded at genesis like `anchor test` funds it. */
+  payer: string | null;
+  programs: PocProgram[];
+  /** Accounts cloned from `cloneUrl` into the validator, from `[[test.validator.clone]]`. */
+  clones: string[];
+  cloneUrl: string | null;
+  steps: PocStep[];
+}
+
+/** `[programs.localnet]`, `[test.validator] url` and clone addresses of an example's Anchor.toml. */
+function anchorToml(example: string): { programs: Record<string, string>; clones: string[]; cloneUrl: string | null } {
+  const tomlPath = path.join(ROOT, example, "Anchor.toml");
+  if (!fs.existsSync(tomlPath)) {
+    return { programs: {}, clones: [], cloneUrl: null };
+  }
+  const programs: Record<string, string> = {};
+  const clones: string[] = [];
+  let cloneUrl: string | null = null;
+  let section = "";
+  for (const line of fs.readFileSync(tomlPath, "utf8").split("\n")) {
+    const header = line.match(/^\s*\[\[?([^\]]+)\]\]?/);
+    if (header) {
+      section = header[1].trim();
+      continue;
+    }
+    const entry = line.match(/^\s*([\w-]+)\s*=\s*"([^"]*)"/);
+    if (!entry) {
+      continue;
+    }
+    if (section === "programs.localnet") {
+      programs[entry[1]] = entry[2];
+    } else if (section === "test.validator" && entry[1] === "url") {
+      cloneUrl = entry[2];
+    } else if (section === "test.validator.clone" && entry[1] === "address") {
+      clones.push(entry[2]);
+    }
+  }
+  return { programs, clones, cloneUrl };
+}
+
+function programOf(example: string, name: string, id: string): PocProgram {
+  const binary = path.join(example, "target", "deploy", `${name}.so`);
+  const absolute = path.join(ROOT, binary);
+  const sha256 = fs.existsSync(absolute)
+    ? createHash("sha256").update(fs.readFileSync(absolute)).digest("hex")
+    : null;
+  return { name, id, binary, sha256 };
+}
+
+function isLocal(endpoint: string): boolean {
+  return /^https?:\/\/(127\.0\.0\.1|localhost|0\.0\.0\.0)(:\d+)?/.test(endpoint);
+}
+
+/** The runtime's failure reason for a rejected send, from the logs the error carries. */
+function outcomeOfError(error: any): PocOutcome {
+  const logs: string[] | undefined = error?.logs ?? error?.transactionLogs;
+  const failure = logs ? failedInvocation(parseLogs(logs))?.failure : undefined;
+  return failure === undefined ? { ok: false } : { ok: false, failure };
+}
+
+function messageBytes(transaction: Transaction | VersionedTransaction): Buffer | null {
+  if (transaction instanceof VersionedTransaction) {
+    return Buffer.from(transaction.message.serialize());
+  }
+  if (!transaction.recentBlockhash || !transaction.feePayer) {
+    // Rejected before it was compiled, so there is nothing to replay
+    return null;
+  }
+  return transaction.serializeMessage();
+}
+
+export class PocRecorder {
+  private readonly steps: PocStep[] = [];
+  private readonly programs: PocProgram[];
+  private readonly clones: string[];
+  private readonly cloneUrl: string | null;
+  private readonly payer: Keypair | null;
+  private readonly connection: Connection;
+  private currentTest = "";
+  /** Whether this run records; false in mock mode or when recording is not requested. */
+  readonly enabled: boolean;
+
+  /**
+   * Starts recording everything sent through `target`.
+   *
+   * Pass the suite's `AnchorProvider` to capture every `.rpc()` call and
+   * airdrop, or a bare `Connection` for native programs, which then send
+   * through `sendAndConfirm`. Programs come from the example's Anchor.toml
+   * unless given in `programs` (name to program ID).
+   */
+  constructor(
+    public readonly example: string,
+    target: AnchorProvider | Connection,
+    programs: Record<string, PublicKey | string> = {}
+  ) {
+    const provider = target instanceof Connection ? null : target;
+    this.connection = provider ? provider.connection : (target as Connection);
+    this.payer = ((provider?.wallet as { payer?: Keypair } | undefined)?.payer ?? null) as Keypair | null;
+
+    const toml = anchorToml(example);
+    const ids = { ...toml.programs, ...programs };
+    this.programs = Object.entries(ids).map(([name, id]) => programOf(example, name, id.toString()));
+    this.clones = toml.clones;
+    this.cloneUrl = toml.cloneUrl;
+
+    const wallet = path.resolve(process.env.ANCHOR_WALLET ?? DEFAULT_CLI_KEYPAIR);
+    const requested = process.env[RECORD_ENV_VAR] === "1";
+    const safeWallet = provider === null || (this.payer !== null && wallet !== DEFAULT_CLI_KEYPAIR);
+    if (requested && !safeWallet) {
+      console.log(
+        `⚠️  Not recording PoCs for ${example}: fixtures include the provider wallet's secret key, ` +
+          `so set ANCHOR_WALLET to a throwaway keypair`
+      );
+    }
+    this.enabled = requested && safeWallet && isLocal(this.connection.rpcEndpoint);
+    if (!this.enabled) {
+      return;
+    }
+
+    const requestAirdrop = this.connection.requestAirdrop.bind(this.connection);
+    this.connection.requestAirdrop = async (to: PublicKey, lamports: number): Promise<TransactionSignature> => {
+      this.steps.push({ kind: "airdrop", test: this.currentTest, to: to.toBase58(), lamports });
+      return requestAirdrop(to, lamports);
+    };
+
+    if (provider) {
+      const send = provider.sendAndConfirm.bind(provider);
+      provider.sendAndConfirm = async (transaction, signers, options) => {
+        const all = [...(this.payer ? [this.payer] : []), ...(signers ?? [])];
+        try {
+          const signature = await send(transaction, signers, options);
+          this.capture(transaction, all, { ok: true });
+          return signature;
+        } catch (error) {
+          this.capture(transaction, all, outcomeOfError(error));
+          throw error;
+        }
+      };
+    }
+  }
+
+  /** Names the test that following steps belong to; call from `beforeEach`. */
+  test(title: string | undefined): void {
+    this.currentTest = title ?? "";
+  }
+
+  /** `sendAndConfirmTransaction` for suites without a provider, recording the transaction. */
+  async sendAndConfirm(transaction: Transaction, signers: Signer[]): Promise<TransactionSignature> {
+    try {
+      const signature = await sendAndConfirmTransaction(this.connection, transaction, signers);
+      this.capture(transaction, signers, { ok: true });
+      return signature;
+    } catch (error) {
+      this.capture(transaction, signers, outcomeOfError(error));
+      throw error;
+    }
+  }
+
+  get recorded(): readonly PocStep[] {
+    return this.steps;
+  }
+
+  private capture(transaction: Transaction | VersionedTransaction, signers: Signer[], outcome: PocOutcome): void {
+    if (!this.enabled) {
+      return;
+    }
+    const message = messageBytes(transaction);
+    if (message === null) {
+      return;
+    }
+    const secrets = new Set(signers.map((signer) => Buffer.from(signer.secretKey).toString("base64")));
+    this.steps.push({
+      kind: "transaction",
+      test: this.currentTest,
+      message: message.toString("base64"),
+      signers: [...secrets],
+      outcome,
+    });
+  }
+
+  /** The recording as a fixture. */
+  fixture(): PocFixture {
+    return {
+      version: POC_FORMAT_VERSION,
+      example: this.example,
+      payer: this.payer ? this.payer.publicKey.toBase58() : null,
+      programs: this.programs,
+      clones: this.clones,
+      cloneUrl: this.cloneUrl,
+      steps: this.steps,
+    };
+  }
+
+  /**
+   * Writes `pocs/<example>.json` when recording. Returns its path, or `null`
+   * when nothing was recorded.
+   */
+  writeFixture(): string | null {
+    if (!this.enabled || this.steps.length === 0) {
+      return null;
+    }
+    fs.mkdirSync(POC_DIR, { recursive: true });
+    const fixturePath = path.join(POC_DIR, `${this.example}.json`);
+    fs.writeFileSync(fixturePath, JSON.stringify(this.fixture(), null, 2) + "\n");
+    return fixturePath;
+  }
+}
@@ -0,0 +1,254 @@
+#!/usr/bin/env node
+
+/**
+ * Exploit proof-of-concept replayer.
+ *
+ * Re-executes the transactions `harness/poc-recorder.ts` recorded into
+ * `harness/pocs/<example>.json`, in order, against a fresh
+ * `solana-test-validator` with the example's current program binaries
+ * deployed at their recorded IDs. Every airdrop is repeated and every
+ * transaction is re-signed with its recorded keypairs over a new blockhash.
+ * A transaction that succeeded and now fails, or failed and now succeeds,
+ * or fails for a different reason, is reported - so an attack that stops
+ * working on the vulnerable handler, or starts working on the secure one,
+ * fails the replay.
+ *
+ * Build the programs first (`anchor build`, or `cargo build-sbf` for native
+ * examples). A binary that differs from the recorded one is noted but still
+ * replayed: that is the point of the check.
+ *
+ * Results are written to `harness/reports/poc-replay.md`.
+ *
+ * Usage:
+ *   node harness/poc-replay.js [example ...] [--url http://127.0.0.1:8899]
+ *
+ * With `--url`, transactions are sent to an already running validator with
+ * the programs deployed instead of starting a fresh one per fixture.
+ */
+
+const fs = require('fs');
+const os = require('os');
+const path = require('path');
+const crypto = require('crypto');
+const { spawn } = require('child_process');
+const { Connection, Keypair, PublicKey, VersionedMessage, VersionedTransaction } = require('@solana/web3.js');
+
+const root = path.join(__dirname, '..');
+const pocDir = path.join(__dirname, 'pocs');
+const reportDir = path.join(__dirname, 'reports');
+
+/** Fixture format this replayer reads; see POC_FORMAT_VERSION in poc-recorder.ts */
+const FORMAT_VERSION = 1;
+
+const VALIDATOR_URL = 'http://127.0.0.1:8899';
+const STARTUP_TIMEOUT_MS = 60_000;
+
+function fixtures(selected) {
+  if (!fs.existsSync(pocDir)) {
+    return [];
+  }
+  return fs
+    .readdirSync(pocDir)
+    .filter((file) => file.endsWith('.json'))
+    .filter((file) => selected.length === 0 || selected.some((s) => file.startsWith(s)))
+    .map((file) => JSON.parse(fs.readFileSync(path.join(pocDir, file), 'utf8')));
+}
+
+function sha256(file) {
+  return crypto.createHash('sha256').update(fs.readFileSync(file)).digest('hex');
+}
+
+const sleep = (ms) => new Promise((resolve) => setTimeout(resolve, ms));
+
+/** A fresh validator with the fixture's programs deployed and accounts cloned; resolves once RPC answers */
+async function startValidator(fixture) {
+  const ledger = fs.mkdtempSync(path.join(os.tmpdir(), 'secref-poc-'));
+  const args = ['--reset', '--quiet', '--ledger', ledger];
+  if (fixture.payer) {
+    args.push('--mint', fixture.payer);
+  }
+  for (const program of fixture.programs) {
+    args.push('--bpf-program', program.id, path.join(root, program.binary));
+  }
+  if (fixture.clones.length > 0) {
+    args.push('--url', fixture.cloneUrl);
+    fixture.clones.forEach((address) => args.push('--clone', address));
+  }
+
+  const validator = spawn('solana-test-validator', args, { stdio: 'ignore' });
+  const exited = new Promise((resolve) => validator.once('exit', resolve));
+  const connection = new Connection(VALIDATOR_URL, 'confirmed');
+  const deadline = Date.now() + STARTUP_TIMEOUT_MS;
+  while (Date.now() < deadline) {
+    if (validator.exitCode !== null) {
+      break;
+    }
+    try {
+      await connection.getLatestBlockhash();
+      return {
+        connection,
+        stop: async () => {
+          validator.kill();
+          await exited;
+          fs.rmSync(ledger, { recursive: true, force: true });
+        }
+      };
+    } catch (error) {
+      await sleep(500);
+    }
+  }
+  validator.kill();
+  fs.rmSync(ledger, { recursive: true, force: true });
+  throw new Error('solana-test-validator did not start (is the Solana CLI installed?)');
+}
+
+/**
+ * The runtime's failure reason from a transaction's logs: `Program <id> failed: <reason>` of the
+ * outermost failed invocation, which logs last - the one secref-logs' `failedInvocation` reports
+ */
+function failureOf(logs) {
+  const line = [...(logs ?? [])].reverse().find((log) => /^Program \w+ failed: /.test(log));
+  return line ? line.replace(/^Program \w+ failed: /, '') : undefined;
+}
+
+async function replayTransaction(connection, step) {
+  const message = VersionedMessage.deserialize(Buffer.from(step.message, 'base64'));
+  message.recentBlockhash = (await connection.getLatestBlockhash('confirmed')).blockhash;
+  const required = message.staticAccountKeys
+    .slice(0, message.header.numRequiredSignatures)
+    .map((key) => key.toBase58());
+  const signers = step.signers
+    .map((secret) => Keypair.fromSecretKey(Buffer.from(secret, 'base64')))
+    .filter((signer) => required.includes(signer.publicKey.toBase58()));
+
+  const transaction = new VersionedTransaction(message);
+  transaction.sign(signers);
+  let signature;
+  try {
+    signature = await connection.sendRawTransaction(transaction.serialize(), { skipPreflight: true });
+  } catch (error) {
+    // Rejected before execution, e.g. a missing signature
+    return { ok: false };
+  }
+  await connection.confirmTransaction(signature, 'confirmed');
+  const landed = await connection.getTransaction(signature, {
+    commitment: 'confirmed',
+    maxSupportedTransactionVersion: 0
+  });
+  if (!landed || !landed.meta) {
+    return { ok: false };
+  }
+  if (landed.meta.err === null) {
+    return { ok: true };
+  }
+  const failure = failureOf(landed.meta.logMessages);
+  return failure === undefined ? { ok: false } : { ok: false, failure };
+}
+
+function describeOutcome(outcome) {
+  return outcome.ok ? 'succeeded' : `failed${outcome.failure ? ` (${outcome.failure})` : ''}`;
+}
+
+/** Whether a replayed outcome matches the recorded one; an unrecorded failure reason matches any */
+function matches(recorded, replayed) {
+  if (recorded.ok !== replayed.ok) {
+    return false;
+  }
+  return recorded.ok || recorded.failure === undefined || recorded.failure === replayed.failure;
+}
+
+async function replay(fixture, url) {
+  const result = { example: fixture.example, steps: fixture.steps.length, replayed: 0, notes: [], mismatches: [] };
+  if (fixture.version !== FORMAT_VERSION) {
+    result.notes.push(`fixture format v${fixture.version}, this replayer reads v${FORMAT_VERSION}`);
+    result.mismatches.push({ test: '', expected: 'a readable fixture', actual: 'skipped' });
+    return result;
+  }
+  for (const program of fixture.programs) {
+    const binary = path.join(root, program.binary);
+    if (!fs.existsSync(binary)) {
+      result.notes.push(`${program.binary} is not built`);
+      result.mismatches.push({ test: '', expected: `${program.name} deployed`, actual: 'not built' });
+      return result;
+    }
+    if (program.sha256 && sha256(binary) !== program.sha256) {
+      result.notes.push(`${program.name} changed since the PoC was recorded`);
+    }
+  }
+
+  const validator = url
+    ? { connection: new Connection(url, 'confirmed'), stop: async () => {} }
+    : await startValidator(fixture);
+  try {
+    for (const [index, step] of fixture.steps.entries()) {
+      if (step.kind === 'airdrop') {
+        const signature = await validator.connection.requestAirdrop(new PublicKey(step.to), step.lamports);
+        await validator.connection.confirmTransaction(signature, 'confirmed');
+      } else {
+        const outcome = await replayTransaction(validator.connection, step);
+        if (!matches(step.outcome, outcome)) {
+          result.mismatches.push({
+            test: `#${index} ${step.test}`,
+            expected: describeOutcome(step.outcome),
+            actual: describeOutcome(outcome)
+          });
+        }
+      }
+      result.replayed += 1;
+    }
+  } finally {
+    await validator.stop();
+  }
+  return result;
+}
+
+function writeReport(results) {
+  const lines = [
+    '# PoC Replay',
+    '',
+    '| Example | Steps | Replayed | Mismatches | Notes |',
+    '|---------|-------|----------|------------|-------|',
+    ...results.map(
+      (r) => `| ${r.example} | ${r.steps} | ${r.replayed} | ${r.mismatches.length} | ${r.notes.join('; ') || '-'} |`
+    ),
+    ''
+  ];
+  for (const r of results.filter((r) => r.mismatches.length > 0)) {
+    lines.push(`## ${r.example}`, '');
+    r.mismatches.forEach((m) => lines.push(`- ${m.test}: recorded ${m.expected}, replay ${m.actual}`));
+    lines.push('');
+  }
+  fs.mkdirSync(reportDir, { recursive: true });
+  fs.writeFileSync(path.join(reportDir, 'poc-replay.md'), lines.join('\n'));
+}
+
+async function main() {
+  const args = process.argv.slice(2);
+  const url = args.flatMap((a, i) => (args[i - 1] === '--url' ? [a] : []))[0];
+  const selected = args.filter((a, i) => !a.startsWith('--') && args[i - 1] !== '--url');
+
+  const recorded = fixtures(selected);
+  if (recorded.length === 0) {
+    console.log('No PoC fixtures in harness/pocs; record them with SECREF_POC_RECORD=1 anchor test');
+    return 0;
+  }
+
+  const results = [];
+  for (const fixture of recorded) {
+    const result = await replay(fixture, url);
+    results.push(result);
+    const status = result.mismatches.length === 0 ? '✅' : '❌';
+    console.log(`${status} ${fixture.example}: ${result.replayed}/${result.steps} steps`);
+    result.notes.forEach((note) => console.log(`   ℹ️  ${note}`));
+    result.mismatches.forEach((m) => console.log(`   ${m.test}: recorded ${m.expected}, replay ${m.actual}`));
+  }
+  writeReport(results);
+
+  const failed = results.filter((r) => r.mismatches.length > 0).length;
+  console.log(
+    failed === 0 ? '\n✅ Every PoC replays as recorded' : `\n❌ ${failed} of ${results.length} PoCs diverged`
+  );
+  return failed > 0 ? 1 : 0;
+}
+
+main().then((code) => process.exit(code));
@@ -0,0 +1,374 @@
+/**
+ * secref-logs: structured parsing of Solana transaction logs.
+ *
+ * Simulation results and confirmed transactions both expose the runtime's log
+ * messages as plain strings. Tests that assert on those strings break whenever
+ * a message is reworded, and they cannot tell which program emitted a line.
+ * This module turns the raw logs into a tree of program invocations with their
+ * `msg!` output, Anchor events, compute-unit consumption and errors, plus
+ * assertion helpers that compare structured values instead of substrings.
+ *
+ * Logs show what programs printed, not what they did: any program can print
+ * any text or event bytes. `cpiEvents` reads Anchor `emit_cpi!` events from
+ * a transaction's inner instructions instead, where they are authenticated
+ * by the emitting program's event authority.
+ *
+ * `explainError` says what a failed invocation's error code means, from the
+ * table `secref-errors` exports to `error-explanations.json`.
+ */
+
+import * as fs from "fs";
+import * as path from "path";
+import { utils } from "@coral-xyz/anchor";
+import { PublicKey, TransactionResponse, VersionedTransactionResponse } from "@solana/web3.js";
+
+/** Anchor reserves codes below 6000 for framework errors; custom errors start here. */
+export const ANCHOR_CUSTOM_ERROR_OFFSET = 6000;
+
+/** An error reported through Anchor's `AnchorError occurred` log line. */
+export interface AnchorErrorLog {
+  /** Error variant name, e.g. `ConstraintHasOne` or `InsufficientFunds`. */
+  name: string;
+  number: number;
+  message: string;
+  /** Account named by `AnchorError caused by account: <name>`, if any. */
+  account?: string;
+}
+
+export interface ProgramInvocation {
+  programId: string;
+  /** Invocation depth as reported by the runtime (1 = top level). */
+  depth: number;
+  /** Anchor instruction name from `Program log: Instruction: <Name>`. */
+  instruction?: string;
+  /** `msg!` output, without the `Program log: ` prefix. */
+  logs: string[];
+  /** Base64 payloads from `Program data:` lines (Anchor `emit!` events). */
+  events: string[];
+  /** Base64 payload from `Program return:`, if the program set return data. */
+  returnData?: string;
+  unitsConsumed?: number;
+  unitsLimit?: number;
+  /** `true` on success, `false` on failure, `undefined` if the logs were truncated. */
+  success?: boolean;
+  /** Raw reason from `Program <id> failed: <reason>`. */
+  failure?: string;
+  /** Numeric code from `custom program error: 0x..`, if present. */
+  customErrorCode?: number;
+  anchorError?: AnchorErrorLog;
+  children: ProgramInvocation[];
+}
+
+export interface ParsedLogs {
+  /** Top-level invocations, with CPIs nested under `children`. */
+  invocations: ProgramInvocation[];
+  /** Every invocation in execution order, regardless of depth. */
+  all: ProgramInvocation[];
+  /** `true` if the runtime truncated the log output. */
+  truncated: boolean;
+}
+
+const INVOKE = /^Program (\w+) invoke \[(\d+)\]$/;
+const CONSUMED = /^Program (\w+) consumed (\d+) of (\d+) compute units$/;
+const SUCCESS = /^Program (\w+) success$/;
+const FAILED = /^Program (\w+) failed: (.*)$/;
+const LOG = /^Program log: (.*)$/;
+const DATA = /^Program data: (.*)$/;
+const RETURN = /^Program return: (\w+) (.*)$/;
+const INSTRUCTION = /^Instruction: (\w+)$/;
+const CUSTOM_ERROR = /custom program error: (0x[0-9a-fA-F]+)/;
+const ANCHOR_ERROR =
+  /^AnchorError (?:caused by account: (\w+)|occurred)\. Error Code: (\w+)\. Error Number: (\d+)\. Error Message: (.*?)\.?$/;
+const ANCHOR_ERROR_THROWN =
+  /^AnchorError thrown in [^.]+\.rs:\d+\. Error Code: (\w+)\. Error Number: (\d+)\. Error Message: (.*?)\.?$/;
+
+/** Parses the log messages of one transaction. */
+export function parseLogs(logs: string[]): ParsedLogs {
+  const invocations: ProgramInvocation[] = [];
+  const all: ProgramInvocation[] = [];
+  const stack: ProgramInvocation[] = [];
+  let truncated = false;
+
+  for (const line of logs) {
+    let match: RegExpExecArray | null;
+
+    if ((match = INVOKE.exec(line))) {
+      const invocation: ProgramInvocation = {
+        programId: match[1],
+        depth: Number(match[2]),
+        logs: [],
+        events: [],
+        children: [],
+      };
+      const parent = stack[stack.length - 1];
+      if (parent) {
+        parent.children.push(invocation);
+      } else {
+        invocations.push(invocation);
+      }
+      all.push(invocation);
+      stack.push(invocation);
+      continue;
+    }
+
+    if (line === "Log truncated") {
+      truncated = true;
+      continue;
+    }
+
+    const current = stack[stack.length - 1];
+    if (!current) {
+      continue;
+    }
+
+    if ((match = LOG.exec(line))) {
+      const message = match[1];
+      current.logs.push(message);
+      const instruction = INSTRUCTION.exec(message);
+      if (instruction && current.instruction === undefined) {
+        current.instruction = instruction[1];
+      }
+      const anchorError = ANCHOR_ERROR.exec(message);
+      if (anchorError) {
+        current.anchorError = {
+          name: anchorError[2],
+          number: Number(anchorError[3]),
+          message: anchorError[4],
+          account: anchorError[1],
+        };
+      }
+      const thrown = ANCHOR_ERROR_THROWN.exec(message);
+      if (thrown) {
+        current.anchorError = {
+          name: thrown[1],
+          number: Number(thrown[2]),
+          message: thrown[3],
+        };
+      }
+    } else if ((match = DATA.exec(line))) {
+      current.events.push(match[1]);
+    } else if ((match = RETURN.exec(line))) {
+      current.returnData = match[2];
+    } else if ((match = CONSUMED.exec(line))) {
+      current.unitsConsumed = Number(match[2]);
+      current.unitsLimit = Number(match[3]);
+    } else if ((match = SUCCESS.exec(line))) {
+      current.success = true;
+      stack.pop();
+    } else if ((match = FAILED.exec(line))) {
+      current.success = false;
+      current.failure = match[2];
+      const custom = CUSTOM_ERROR.exec(match[2]);
+      if (custom) {
+        current.customErrorCode = parseInt(custom[1], 16);
+      }
+      stack.pop();
+    }
+  }
+
+  return { invocations, all, truncated };
+}
+
+/** Returns every invocation of `programId`, at any depth. */
+export function invocationsOf(parsed: ParsedLogs, programId: string): ProgramInvocation[] {
+  return parsed.all.filter((invocation) => invocation.programId === programId);
+}
+
+/** Returns the first failed invocation, i.e. the one that aborted the transaction. */
+export function failedInvocation(parsed: ParsedLogs): ProgramInvocation | undefined {
+  return parsed.all.find((invocation) => invocation.success === false);
+}
+
+/** Total compute units consumed by the top-level invocations. */
+export function totalUnitsConsumed(parsed: ParsedLogs): number {
+  return parsed.invocations.reduce((sum, invocation) => sum + (invocation.unitsConsumed ?? 0), 0);
+}
+
+// ========================================
+// ERROR EXPLANATIONS
+// ========================================
+
+/** One cause of an error code, as `secref_errors::to_json` exports it. */
+export interface ErrorExplanation {
+  code: number;
+  name: string;
+  message: string;
+  /** What went wrong, in plain words. */
+  cause: string;
+  /** The vulnerable pattern the check most often stops; `null` for client and build mistakes. */
+  pattern: string | null;
+  /** Example directory or shared crate defining the error; `null` for Anchor's own. */
+  module: string | null;
+  program: string | null;
+  /** Module ids that show the check doing its job. */
+  modules: string[];
+}
+
+/** Regenerated by `SECREF_BLESS=1 cargo test --manifest-path shared/secref-errors/Cargo.toml`. */
+export const ERROR_EXPLANATIONS_PATH = path.join(__dirname, "error-explanations.json");
+
+let explanations: ErrorExplanation[] | undefined;
+
+/**
+ * Every cause the failed invocation's error can have.
+ *
+ * Custom codes start at 6000 in every program, so a bare
+ * `custom program error` may match many errors; an Anchor error log
+ * narrows them to the logged name.
+ */
+export function explainError(invocation: ProgramInvocation): ErrorExplanation[] {
+  const code = invocation.anchorError?.number ?? invocation.customErrorCode;
+  if (code === undefined) {
+    return [];
+  }
+  explanations ??= JSON.parse(fs.readFileSync(ERROR_EXPLANATIONS_PATH, "utf8")) as ErrorExplanation[];
+  const name = invocation.anchorError?.name;
+  return explanations.filter(
+    (explanation) => explanation.code === code && (name === undefined || explanation.name === name)
+  );
+}
+
+/** Decodes the `Program data:` events of an invocation with an Anchor event coder. */
+export function decodeEvents<T>(
+  invocation: ProgramInvocation,
+  coder: { decode(log: string): T | null }
+): T[] {
+  return invocation.events
+    .map((data) => coder.decode(data))
+    .filter((event): event is T => event !== null);
+}
+
+// ========================================
+// EVENT AUTHENTICITY
+// ========================================
+
+/** Prefix of `emit_cpi!` instruction data: Anchor's `EVENT_IX_TAG`, little endian. */
+export const EVENT_IX_TAG_LE = Buffer.from("e445a52e51cb9a1d", "hex");
+
+/** The PDA `emit_cpi!` signs with; only `programId` can produce its signature. */
+export function eventAuthority(programId: PublicKey): PublicKey {
+  const [address] = PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], programId);
+  return address;
+}
+
+/**
+ * Returns the `emit_cpi!` events `programId` emitted in a confirmed transaction.
+ *
+ * An event counts only if it is an inner instruction to `programId` whose
+ * data starts with `EVENT_IX_TAG_LE` and whose first account is the
+ * program's event authority. Anchor's event handler rejects the instruction
+ * unless that PDA signed, so in a successful transaction this proves the
+ * program emitted it. Failed transactions yield no events. Payloads are
+ * base64, ready for an Anchor `EventCoder.decode`.
+ */
+export function cpiEvents(tx: TransactionResponse | VersionedTransactionResponse, programId: PublicKey): string[] {
+  if (!tx.meta || tx.meta.err !== null) {
+    return [];
+  }
+  const keys = tx.transaction.message.getAccountKeys({
+    accountKeysFromLookups: tx.meta.loadedAddresses ?? undefined,
+  });
+  const authority = eventAuthority(programId);
+
+  const events: string[] = [];
+  for (const inner of tx.meta.innerInstructions ?? []) {
+    for (const ix of inner.instructions) {
+      if (!keys.get(ix.programIdIndex)?.equals(programId)) {
+        continue;
+      }
+      const data = Buffer.from(utils.bytes.bs58.decode(ix.data));
+      if (!data.subarray(0, 8).equals(EVENT_IX_TAG_LE)) {
+        continue;
+      }
+      if (ix.accounts.length === 0 || !keys.get(ix.accounts[0])?.equals(authority)) {
+        continue;
+      }
+      events.push(data.subarray(8).toString("base64"));
+    }
+  }
+  return events;
+}
+
+// ========================================
+// ASSERTION HELPERS
+// ========================================
+// Each helper throws a descriptive Error so it can be used with any test
+// framework; chai's `expect(...).to.throw()` composes naturally with them.
+
+function describeInvocations(parsed: ParsedLogs): string {
+  return parsed.all
+    .map(
+      (invocation) =>
+        `${"  ".repeat(invocation.depth)}${invocation.programId}` +
+        `${invocation.instruction ? ` (${invocation.instruction})` : ""}: ` +
+        `${invocation.success === undefined ? "incomplete" : invocation.success ? "success" : `failed: ${invocation.failure}`}`
+    )
+    .join("\n");
+}
+
+/** Asserts that `programId` was invoked, optionally with a specific Anchor instruction. */
+export function assertProgramInvoked(parsed: ParsedLogs, programId: string, instruction?: string): void {
+  const found = invocationsOf(parsed, programId).some(
+    (invocation) => instruction === undefined || invocation.instruction === instruction
+  );
+  if (!found) {
+    throw new Error(
+      `Expected ${programId}${instruction ? ` (${instruction})` : ""} to be invoked. Invocations:\n` +
+        describeInvocations(parsed)
+    );
+  }
+}
+
+/** Asserts that every invocation in the transaction succeeded. */
+export function assertSuccess(parsed: ParsedLogs): void {
+  const failed = failedInvocation(parsed);
+  if (failed) {
+    throw new Error(`Expected success, but ${failed.programId} failed: ${failed.failure}`);
+  }
+}
+
+/**
+ * Asserts that the transaction failed with the given Anchor error.
+ *
+ * `expected` may be the error name (`"ConstraintHasOne"`) or its number
+ * (`2001`). When `account` is given, the error must also name that account.
+ */
+export function assertAnchorError(parsed: ParsedLogs, expected: string | number, account?: string): AnchorErrorLog {
+  const failed = failedInvocation(parsed);
+  const error = parsed.all.map((invocation) => invocation.anchorError).find((e) => e !== undefined);
+  if (!failed || !error) {
+    throw new Error(`Expected Anchor error ${expected}, but none was logged. Invocations:\n${describeInvocations(parsed)}`);
+  }
+  const matches = typeof expected === "number" ? error.number === expected : error.name === expected;
+  if (!matches) {
+    throw new Error(`Expected Anchor error ${expected}, got ${error.name} (${error.number}): ${error.message}`);
+  }
+  if (account !== undefined && error.account !== account) {
+    throw new Error(`Expected ${error.name} to be caused by account ${account}, got ${error.account ?? "none"}`);
+  }
+  return error;
+}
+
+/** Asserts that the transaction failed with `custom program error: <code>`. */
+export function assertCustomError(parsed: ParsedLogs, code: number): void {
+  const failed = failedInvocation(parsed);
+  if (!failed || failed.customErrorCode !== code) {
+    throw new Error(
+      `Expected custom program error ${code} (0x${code.toString(16)}), got ${failed ? failed.failure : "success"}`
+    );
+  }
+}
+
+/** Asserts that some invocation (optionally of `programId`) logged a matching `msg!` line. */
+export function assertLog(parsed: ParsedLogs, pattern: string | RegExp, programId?: string): string {
+  const candidates = programId ? invocationsOf(parsed, programId) : parsed.all;
+  for (const invocation of candidates) {
+    const line = invocation.logs.find((log) =>
+      typeof pattern === "string" ? log.includes(pattern) : pattern.test(log)
+    );
+    if (line !== undefined) {
+      return line;
+    }
+  }
+  throw new Error(`Expected a log matching ${pattern}${programId ? ` from ${programId}` : ""}`);
+}
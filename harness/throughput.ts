@@ -0,0 +1,110 @@
+/**
+ * Throughput benchmark for write-lock contention.
+ *
+ * The runtime executes transactions that write the same account one after
+ * another; transactions with disjoint write sets can run in parallel. A
+ * design that makes every user write one global account therefore caps the
+ * whole program at the throughput of that account - and anyone can spend
+ * that capacity by sending transactions that merely write-lock it.
+ *
+ * `measureThroughput` submits a batch of transactions concurrently and
+ * reports how many landed and how many slots they were spread over, so two
+ * account layouts can be compared under the same load. On a local validator
+ * with no competing traffic the gap is small; the numbers are a relative
+ * comparison, not a mainnet prediction.
+ *
+ * In mock mode (no validator available) nothing is measured, matching the
+ * behaviour of the rest of the test suite.
+ */
+
+import * as fs from "fs";
+import * as path from "path";
+import { Connection, TransactionSignature } from "@solana/web3.js";
+
+const REPORT_DIR = path.join(__dirname, "reports");
+
+export interface ThroughputResult {
+  label: string;
+  submitted: number;
+  landed: number;
+  failed: number;
+  /** Slots between the first and last landed transaction, inclusive. */
+  slotsSpanned: number;
+  /** Landed transactions per slot spanned. */
+  perSlot: number;
+  elapsedMs: number;
+}
+
+/**
+ * Sends every transaction in `sends` at once and measures how they land.
+ *
+ * Each entry sends one transaction and resolves to its signature once
+ * confirmed. Rejected sends count as failed rather than failing the run.
+ */
+export async function measureThroughput(
+  connection: Connection,
+  label: string,
+  sends: Array<() => Promise<TransactionSignature>>
+): Promise<ThroughputResult> {
+  const started = Date.now();
+  const results = await Promise.all(
+    sends.map((send) =>
+      send().then(
+        (signature): TransactionSignature | null => signature,
+        () => null
+      )
+    )
+  );
+  const elapsedMs = Date.now() - started;
+
+  const signatures = results.filter((signature): signature is TransactionSignature => signature !== null);
+
+  const slots: number[] = [];
+  for (const signature of signatures) {
+    const tx = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    if (tx) {
+      slots.push(tx.slot);
+    }
+  }
+
+  const slotsSpanned = slots.length === 0 ? 0 : Math.max(...slots) - Math.min(...slots) + 1;
+  return {
+    label,
+    submitted: sends.length,
+    landed: signatures.length,
+    failed: sends.length - signatures.length,
+    slotsSpanned,
+    perSlot: slotsSpanned === 0 ? 0 : signatures.length / slotsSpanned,
+    elapsedMs,
+  };
+}
+
+/** Renders results as a markdown table. */
+export function throughputTable(example: string, results: ThroughputResult[]): string {
+  const header = [
+    `### ${example} throughput`,
+    "",
+    "| Layout | Submitted | Landed | Failed | Slots spanned | Landed / slot | Elapsed (ms) |",
+    "|--------|-----------|--------|--------|---------------|---------------|--------------|",
+  ];
+  const rows = results.map(
+    (result) =>
+      `| ${result.label} | ${result.submitted} | ${result.landed} | ${result.failed} | ` +
+      `${result.slotsSpanned} | ${result.perSlot.toFixed(2)} | ${result.elapsedMs} |`
+  );
+  return [...header, ...rows, ""].join("\n");
+}
+
+/** Writes `reports/<example>-throughput.md` and returns its path. */
+export function writeThroughputReport(example: string, results: ThroughputResult[]): string | null {
+  if (results.length === 0) {
+    return null;
+  }
+  fs.mkdirSync(REPORT_DIR, { recursive: true });
+  const reportPath = path.join(REPORT_DIR, `${example}-throughput.md`);
+  fs.writeFileSync(reportPath, throughputTable(example, results));
+  return reportPath;
+}
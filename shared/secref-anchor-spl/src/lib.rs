@@ -0,0 +1,36 @@
+//! `anchor-spl` at the release selected by feature.
+//!
+//! The counterpart of `secref-anchor` for examples that use SPL tokens;
+//! depend on it as `anchor-spl` and forward the same `anchor-0_29` /
+//! `anchor-0_30` feature to both crates.
+
+#[cfg(all(feature = "anchor-0_29", feature = "anchor-0_30"))]
+compile_error!(
+    "enable exactly one of `anchor-0_29` and `anchor-0_30`; build with `--no-default-features` to pick 0.29"
+);
+
+#[cfg(not(any(feature = "anchor-0_29", feature = "anchor-0_30")))]
+compile_error!("enable one of `anchor-0_29` and `anchor-0_30`");
+
+#[cfg(all(feature = "anchor-0_29", not(feature = "anchor-0_30")))]
+pub use anchor_spl_0_29::*;
+
+#[cfg(feature = "anchor-0_30")]
+pub use anchor_spl_0_30::*;
+
+/// The transfer hook interface a hook program implements, and the
+/// extra-account resolution its `ExtraAccountMetaList` is written with
+#[cfg(feature = "transfer-hook")]
+pub mod transfer_hook {
+    #[cfg(all(feature = "anchor-0_29", not(feature = "anchor-0_30")))]
+    pub use {
+        spl_tlv_account_resolution_0_4 as spl_tlv_account_resolution,
+        spl_transfer_hook_interface_0_3 as spl_transfer_hook_interface,
+    };
+
+    #[cfg(feature = "anchor-0_30")]
+    pub use {
+        spl_tlv_account_resolution_0_6 as spl_tlv_account_resolution,
+        spl_transfer_hook_interface_0_6 as spl_transfer_hook_interface,
+    };
+}
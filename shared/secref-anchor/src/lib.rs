@@ -0,0 +1,41 @@
+//! `anchor-lang` at the release selected by feature.
+//!
+//! Examples depend on this crate under the name `anchor-lang`, so the code
+//! Anchor's macros generate (`anchor_lang::...`) resolves to whichever
+//! release the `anchor-0_29` / `anchor-0_30` feature picked. Exactly one
+//! must be enabled:
+//!
+//! ```toml
+//! [features]
+//! default = ["anchor-0_30"]
+//! anchor-0_29 = ["anchor-lang/anchor-0_29"]
+//! anchor-0_30 = ["anchor-lang/anchor-0_30"]
+//!
+//! [dependencies]
+//! anchor-lang = { package = "secref-anchor", path = "../../../shared/secref-anchor", default-features = false }
+//! ```
+//!
+//! Build an example against 0.29 with
+//! `cargo check --no-default-features --features anchor-0_29`.
+
+#[cfg(all(feature = "anchor-0_29", feature = "anchor-0_30"))]
+compile_error!(
+    "enable exactly one of `anchor-0_29` and `anchor-0_30`; build with `--no-default-features` to pick 0.29"
+);
+
+#[cfg(not(any(feature = "anchor-0_29", feature = "anchor-0_30")))]
+compile_error!("enable one of `anchor-0_29` and `anchor-0_30`");
+
+#[cfg(all(feature = "anchor-0_29", not(feature = "anchor-0_30")))]
+pub use anchor_lang_0_29::*;
+
+#[cfg(feature = "anchor-0_30")]
+pub use anchor_lang_0_30::*;
+
+/// The Anchor release this build uses, e.g. for logging which constraint semantics apply
+#[cfg(all(feature = "anchor-0_29", not(feature = "anchor-0_30")))]
+pub const ANCHOR_VERSION: &str = "0.29.0";
+
+/// The Anchor release this build uses, e.g. for logging which constraint semantics apply
+#[cfg(feature = "anchor-0_30")]
+pub const ANCHOR_VERSION: &str = "0.30.1";
@@ -0,0 +1,90 @@
+//! Constraint behaviour that differs between the supported Anchor releases.
+//!
+//! The same context is validated under whichever release the features
+//! select, and each release's module states what that release does:
+//!
+//!     cargo test
+//!     cargo test --no-default-features --features anchor-0_29
+
+extern crate secref_anchor as anchor_lang;
+
+use std::collections::BTreeSet;
+
+use anchor_lang::prelude::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[account]
+pub struct Referrer {
+    pub bump: u8,
+}
+
+/// A payment with an optional referrer PDA, as in a fee-splitting flow
+#[derive(Accounts)]
+pub struct Pay<'info> {
+    pub payer: Signer<'info>,
+    #[account(seeds = [b"referrer", payer.key().as_ref()], bump)]
+    pub referrer: Option<Account<'info, Referrer>>,
+}
+
+/// Validate `Pay` with the referrer omitted, the way a client leaves it out
+fn bumps_without_referrer() -> PayBumps {
+    let payer = Pubkey::new_unique();
+    let (mut payer_lamports, mut program_lamports) = (1_000_000_000, 1);
+    let (mut payer_data, mut program_data) = (vec![], vec![]);
+    let system_program = anchor_lang::solana_program::system_program::ID;
+    // Anchor reads an optional account set to the program id as "not provided"
+    let infos = [
+        AccountInfo::new(
+            &payer,
+            true,
+            false,
+            &mut payer_lamports,
+            &mut payer_data,
+            &system_program,
+            false,
+            0,
+        ),
+        AccountInfo::new(
+            &ID,
+            false,
+            false,
+            &mut program_lamports,
+            &mut program_data,
+            &ID,
+            true,
+            0,
+        ),
+    ];
+
+    let mut bumps = PayBumps::default();
+    let accounts = Pay::try_accounts(&ID, &mut &infos[..], &[], &mut bumps, &mut BTreeSet::new()).unwrap();
+    assert!(accounts.referrer.is_none());
+    bumps
+}
+
+/// 0.29 types every bump as `u8`: an omitted optional PDA reports `u8::MAX`
+///
+/// 255 is also the most likely canonical bump, so a handler that records
+/// `ctx.bumps.referrer` or signs with it cannot tell "absent" from "found".
+/// Check `ctx.accounts.referrer.is_some()` before touching the bump.
+#[cfg(feature = "anchor-0_29")]
+mod anchor_0_29 {
+    use super::*;
+
+    #[test]
+    fn an_omitted_optional_pda_reports_bump_255() {
+        assert_eq!(bumps_without_referrer().referrer, u8::MAX);
+    }
+}
+
+/// 0.30 types an optional account's bump as `Option<u8>`: absence is `None`
+#[cfg(feature = "anchor-0_30")]
+mod anchor_0_30 {
+    use super::*;
+
+    #[test]
+    fn an_omitted_optional_pda_has_no_bump() {
+        assert_eq!(bumps_without_referrer().referrer, None);
+    }
+}
@@ -0,0 +1,79 @@
+//! Assertion instructions, built off-chain to append to a transaction.
+//!
+//! Each builder returns a plain [`Instruction`] for the client to place
+//! after the instructions it guards; [`guarded`] does the appending. The
+//! expectations come from what the signer read before signing: a balance
+//! plus what the transaction should pay, the owner an account has now, the
+//! bytes of a field that must not change.
+//!
+//! Assertions only see the end state of the transaction. They cannot tell
+//! which instruction moved the funds, only that the accounts named are not
+//! where the signer expected them; anything the signer did not assert
+//! about is unguarded.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+
+use crate::{accounts, instruction};
+
+/// Fail the transaction unless `token_account` ends it holding at least `min`
+pub fn assert_token_balance_gte(token_account: Pubkey, min: u64) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts::AssertTokenBalanceGte { token_account }.to_account_metas(None),
+        data: instruction::AssertTokenBalanceGte { min }.data(),
+    }
+}
+
+/// Fail the transaction unless `token_account`, holding `before` now, is credited at least `amount`
+///
+/// A balance the credit would overflow can never be reached, so the
+/// assertion then fails whatever the transaction does.
+pub fn assert_token_credit(token_account: Pubkey, before: u64, amount: u64) -> Instruction {
+    assert_token_balance_gte(token_account, before.saturating_add(amount))
+}
+
+/// Fail the transaction unless `account` ends it owned by `owner`
+pub fn assert_account_owner(account: Pubkey, owner: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts::AssertAccountOwner { account }.to_account_metas(None),
+        data: instruction::AssertAccountOwner { owner }.data(),
+    }
+}
+
+/// Fail the transaction unless `account` ends it holding `data` at `offset`
+///
+/// Only the hash goes on-chain, so the instruction is the same size
+/// however long `data` is.
+pub fn assert_data_hash(account: Pubkey, offset: u32, data: &[u8]) -> Instruction {
+    let len = u32::try_from(data.len()).expect("account data is shorter than 4 GiB");
+    Instruction {
+        program_id: crate::ID,
+        accounts: accounts::AssertDataHash { account }.to_account_metas(None),
+        data: instruction::AssertDataHash {
+            offset,
+            len,
+            expected: hash(data).to_bytes(),
+        }
+        .data(),
+    }
+}
+
+/// Fail the transaction unless `account` ends it owned by `owner` and starting with `data`
+///
+/// For an account the transaction should not touch at all, as the signer
+/// fetched it: two assertions, since the hash alone says nothing of who
+/// may write the bytes next. Bytes a reallocation appends past `data` are
+/// not covered.
+pub fn assert_account_unchanged(account: Pubkey, owner: Pubkey, data: &[u8]) -> [Instruction; 2] {
+    [assert_account_owner(account, owner), assert_data_hash(account, 0, data)]
+}
+
+/// `instructions` followed by `assertions`, the order they must run in
+pub fn guarded(mut instructions: Vec<Instruction>, assertions: impl IntoIterator<Item = Instruction>) -> Vec<Instruction> {
+    instructions.extend(assertions);
+    instructions
+}
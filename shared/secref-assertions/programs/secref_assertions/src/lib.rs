@@ -0,0 +1,137 @@
+//! Reusable on-chain assertions for the security reference examples.
+//!
+//! Nothing here changes state. Each instruction reads one account and
+//! fails unless it is as the caller expects, so a wallet or client that
+//! appends assertions to the end of a transaction makes the whole
+//! transaction revert if any earlier instruction left its accounts
+//! otherwise - whichever program ran, and whatever that program's bug was.
+//!
+//! The assertions are about outcomes, not instructions: "my token account
+//! holds at least what I am owed", "this account still belongs to that
+//! program", "these bytes are unchanged". A signer can check them without
+//! understanding the program the transaction calls, which is the point -
+//! a frontend that swaps an account or a program id in an instruction the
+//! wallet cannot decode still has to get past them.
+//!
+//! Build the instructions with [`client`]:
+//!
+//! ```ignore
+//! let transaction = client::guarded(
+//!     vec![withdraw],
+//!     [client::assert_token_balance_gte(user_tokens, before + amount)],
+//! );
+//! ```
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_spl::token_interface::TokenAccount;
+
+pub mod client;
+
+declare_id!("SecRefAssertions111111111111111111111111111");
+
+#[program]
+pub mod secref_assertions {
+    use super::*;
+
+    /// Fail unless `token_account` holds at least `min` tokens
+    ///
+    /// The account must be a Token or Token-2022 token account; anything
+    /// else fails account validation, so a look-alike owned by another
+    /// program cannot report a balance it does not have.
+    pub fn assert_token_balance_gte(ctx: Context<AssertTokenBalanceGte>, min: u64) -> Result<()> {
+        let amount = ctx.accounts.token_account.amount;
+        require!(amount >= min, AssertionError::TokenBalanceTooLow);
+
+        msg!("Token balance {} >= {}", amount, min);
+        Ok(())
+    }
+
+    /// Fail unless `account` is owned by `owner`
+    ///
+    /// An account that does not exist is owned by the System Program, so
+    /// asserting the System Program also asserts nothing has claimed it.
+    pub fn assert_account_owner(ctx: Context<AssertAccountOwner>, owner: Pubkey) -> Result<()> {
+        let actual = ctx.accounts.account.owner;
+        require_keys_eq!(*actual, owner, AssertionError::OwnerMismatch);
+
+        msg!("Owner {}", owner);
+        Ok(())
+    }
+
+    /// Fail unless the SHA-256 of `account`'s data from `offset`, `len` bytes long, is `hash`
+    ///
+    /// Pins any field the caller read before signing - a stored authority,
+    /// a price, a whole account - without the program having to expose it.
+    pub fn assert_data_hash(ctx: Context<AssertDataHash>, offset: u32, len: u32, expected: [u8; 32]) -> Result<()> {
+        let data = ctx.accounts.account.try_borrow_data()?;
+        let range = data_range(offset, len, data.len())?;
+        require!(hash(&data[range]).to_bytes() == expected, AssertionError::DataHashMismatch);
+
+        msg!("Data hash matches over {} bytes at {}", len, offset);
+        Ok(())
+    }
+}
+
+/// `len` bytes from `offset`, if an account of `data_len` bytes has them
+fn data_range(offset: u32, len: u32, data_len: usize) -> Result<std::ops::Range<usize>> {
+    let start = offset as usize;
+    let end = start.checked_add(len as usize).ok_or(AssertionError::DataRangeOutOfBounds)?;
+    require!(end <= data_len, AssertionError::DataRangeOutOfBounds);
+    Ok(start..end)
+}
+
+// ========================================
+// ACCOUNT CONTEXTS
+// ========================================
+
+#[derive(Accounts)]
+pub struct AssertTokenBalanceGte<'info> {
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct AssertAccountOwner<'info> {
+    /// CHECK: Only the owner is read, and any account has one
+    pub account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AssertDataHash<'info> {
+    /// CHECK: Only the data is read, and hashed as raw bytes
+    pub account: UncheckedAccount<'info>,
+}
+
+// ========================================
+// ERROR DEFINITIONS
+// ========================================
+
+/// Errors returned by the assertions
+///
+/// Offset clear of `secref-guards` (7000) and `secref-registry` (7100), so
+/// an assertion failing at the end of a transaction is never mistaken for
+/// an error of the program it guards.
+#[error_code(offset = 7200)]
+pub enum AssertionError {
+    #[msg("Token account holds less than asserted")]
+    TokenBalanceTooLow,
+    #[msg("Account is not owned by the asserted program")]
+    OwnerMismatch,
+    #[msg("Account data does not hash to the asserted value")]
+    DataHashMismatch,
+    #[msg("Asserted data range is past the end of the account")]
+    DataRangeOutOfBounds,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_range_stays_inside_the_account() {
+        assert_eq!(data_range(0, 8, 8).unwrap(), 0..8);
+        assert_eq!(data_range(8, 0, 8).unwrap(), 8..8);
+        assert_eq!(data_range(4, 5, 8).unwrap_err(), AssertionError::DataRangeOutOfBounds.into());
+        assert_eq!(data_range(u32::MAX, u32::MAX, 8).unwrap_err(), AssertionError::DataRangeOutOfBounds.into());
+    }
+}
@@ -0,0 +1,94 @@
+//! Each assertion on its own: what passes, what fails, and with which error.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use common::send;
+use secref_assertions::{client, AssertionError};
+use secref_invariants::World;
+use secref_testkit::TestAccount;
+
+fn failure(error: AssertionError) -> std::result::Result<(), ProgramError> {
+    Err(Error::from(error).into())
+}
+
+#[test]
+fn token_balance_passes_at_the_asserted_minimum() {
+    let mut world = World::new();
+    let tokens = world.add(TestAccount::token_account(Pubkey::new_unique(), Pubkey::new_unique(), 500));
+
+    assert_eq!(send(&mut world, &[client::assert_token_balance_gte(tokens, 500)]), Ok(()));
+    assert_eq!(send(&mut world, &[client::assert_token_credit(tokens, 200, 300)]), Ok(()));
+    assert_eq!(
+        send(&mut world, &[client::assert_token_balance_gte(tokens, 501)]),
+        failure(AssertionError::TokenBalanceTooLow)
+    );
+    assert_eq!(
+        send(&mut world, &[client::assert_token_credit(tokens, 1, u64::MAX)]),
+        failure(AssertionError::TokenBalanceTooLow)
+    );
+}
+
+#[test]
+fn token_balance_rejects_a_look_alike_token_account() {
+    // A token account's bytes, owned by someone else's program
+    let mut world = World::new();
+    let forged = world.add(
+        TestAccount::token_account(Pubkey::new_unique(), Pubkey::new_unique(), u64::MAX).owned_by(Pubkey::new_unique()),
+    );
+
+    assert_eq!(
+        send(&mut world, &[client::assert_token_balance_gte(forged, 1)]),
+        Err(Error::from(ErrorCode::AccountOwnedByWrongProgram).into())
+    );
+}
+
+#[test]
+fn account_owner_must_match() {
+    let mut world = World::new();
+    let owner = Pubkey::new_unique();
+    let account = world.add(TestAccount::new(owner, vec![0; 8]));
+
+    assert_eq!(send(&mut world, &[client::assert_account_owner(account, owner)]), Ok(()));
+    assert_eq!(
+        send(&mut world, &[client::assert_account_owner(account, Pubkey::new_unique())]),
+        failure(AssertionError::OwnerMismatch)
+    );
+}
+
+#[test]
+fn data_hash_covers_exactly_the_asserted_range() {
+    let mut world = World::new();
+    let data: Vec<u8> = (0..16).collect();
+    let account = world.add(TestAccount::new(Pubkey::new_unique(), data.clone()));
+
+    assert_eq!(send(&mut world, &[client::assert_data_hash(account, 0, &data)]), Ok(()));
+    assert_eq!(send(&mut world, &[client::assert_data_hash(account, 4, &data[4..8])]), Ok(()));
+    // Right bytes, wrong place
+    assert_eq!(
+        send(&mut world, &[client::assert_data_hash(account, 5, &data[4..8])]),
+        failure(AssertionError::DataHashMismatch)
+    );
+    assert_eq!(
+        send(&mut world, &[client::assert_data_hash(account, 8, &data)]),
+        failure(AssertionError::DataRangeOutOfBounds)
+    );
+}
+
+#[test]
+fn account_unchanged_needs_both_owner_and_bytes() {
+    let mut world = World::new();
+    let owner = Pubkey::new_unique();
+    let data = vec![7; 32];
+    let account = world.add(TestAccount::new(owner, data.clone()));
+
+    assert_eq!(send(&mut world, &client::assert_account_unchanged(account, owner, &data)), Ok(()));
+    assert_eq!(
+        send(&mut world, &client::assert_account_unchanged(account, Pubkey::new_unique(), &data)),
+        failure(AssertionError::OwnerMismatch)
+    );
+    assert_eq!(
+        send(&mut world, &client::assert_account_unchanged(account, owner, &[8; 32])),
+        failure(AssertionError::DataHashMismatch)
+    );
+}
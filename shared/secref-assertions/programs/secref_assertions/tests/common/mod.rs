@@ -0,0 +1,25 @@
+//! Runs a list of instructions as one transaction, as a client would send it.
+
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::Instruction;
+use secref_invariants::{program, Program, World};
+
+/// Every program a test transaction can call
+const PROGRAMS: [Program; 2] = [program!(secref_assertions), program!(unsafe_cpi)];
+
+/// Run `instructions` in order, keeping their writes only if every one succeeds
+///
+/// As on chain, an assertion failing at the end reverts the instructions
+/// before it along with it.
+pub fn send(world: &mut World, instructions: &[Instruction]) -> ProgramResult {
+    let mut transaction = world.clone();
+    for instruction in instructions {
+        let program = PROGRAMS
+            .into_iter()
+            .find(|program| program.id == instruction.program_id)
+            .unwrap_or_else(|| panic!("no program {} in the test", instruction.program_id));
+        transaction.invoke(program, &instruction.accounts, &instruction.data)?;
+    }
+    *world = transaction;
+    Ok(())
+}
@@ -0,0 +1,147 @@
+//! 03_unsafe_cpi: a withdrawal with a substituted token program, reverted by the assertions Alice's wallet appends.
+//!
+//! Alice withdraws everything in her vault through a frontend Mallory
+//! controls. The frontend hands `vulnerable_withdraw` Mallory's program as
+//! `token_program`, which the unchecked context accepts, and points the
+//! withdrawal at Mallory's token account. Anchor's `token::transfer`
+//! still addresses the Token program by id, so the frontend lists it too;
+//! a vault that built the CPI from the passed account would hand Mallory's
+//! program the vault's signature instead. Either way Alice's wallet, which
+//! cannot decode `unsafe_cpi`'s instructions, knows what she asked for:
+//! her tokens in her account, and her vault left as it was.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::InstructionData;
+use common::send;
+use secref_assertions::{client, AssertionError};
+use secref_invariants::World;
+use secref_testkit::TestAccount;
+use unsafe_cpi::{accounts, instruction, TokenVault};
+
+const HELD: u64 = 1_000;
+
+struct Withdrawal {
+    world: World,
+    vault: Pubkey,
+    vault_tokens: Pubkey,
+    alice: Pubkey,
+    alices_tokens: Pubkey,
+    mallorys_tokens: Pubkey,
+    mallorys_program: Pubkey,
+}
+
+impl Withdrawal {
+    fn new() -> Self {
+        let mut world = World::new();
+        world.add(TestAccount::token_program());
+        let mallorys_program = world.add(TestAccount::program(Pubkey::new_unique()));
+        let mint = world.add(TestAccount::mint(Pubkey::new_unique(), 6, HELD));
+        let alice = world.add(TestAccount::signer());
+        let mallory = world.add(TestAccount::signer());
+
+        let (vault, bump) = Pubkey::find_program_address(&[b"vault", alice.as_ref()], &unsafe_cpi::ID);
+        let vault_tokens = world.add(TestAccount::token_account(mint, vault, HELD));
+        world.add(
+            TestAccount::anchor(&TokenVault {
+                authority: alice,
+                token_account: vault_tokens,
+                bump,
+            })
+            .at(vault),
+        );
+        Self {
+            vault,
+            vault_tokens,
+            alice,
+            alices_tokens: world.add(TestAccount::token_account(mint, alice, 0)),
+            mallorys_tokens: world.add(TestAccount::token_account(mint, mallory, 0)),
+            mallorys_program,
+            world,
+        }
+    }
+
+    /// `vulnerable_withdraw` of everything in the vault to `destination`, through `token_program`
+    fn withdraw(&self, destination: Pubkey, token_program: Pubkey) -> Instruction {
+        let mut metas = accounts::VulnerableWithdraw {
+            vault: self.vault,
+            vault_token_account: self.vault_tokens,
+            user_token_account: destination,
+            user: self.alice,
+            token_program,
+        }
+        .to_account_metas(None);
+        // So the transfer CPI can reach the Token program whatever was passed as `token_program`
+        metas.push(AccountMeta::new_readonly(anchor_spl::token::ID, false));
+        Instruction {
+            program_id: unsafe_cpi::ID,
+            accounts: metas,
+            data: instruction::VulnerableWithdraw { amount: HELD }.data(),
+        }
+    }
+
+    /// The frontend's withdrawal: Mallory's program, Mallory's account
+    fn redirected(&self) -> Instruction {
+        self.withdraw(self.mallorys_tokens, self.mallorys_program)
+    }
+
+    /// What Alice's wallet appends, from the accounts as it fetched them before signing
+    fn assertions(&self) -> Vec<Instruction> {
+        let vault = self.world.account(&self.vault);
+        let mut assertions = vec![client::assert_token_credit(self.alices_tokens, 0, HELD)];
+        assertions.extend(client::assert_account_unchanged(self.vault, vault.owner, &vault.data));
+        assertions
+    }
+
+    fn tokens(&self, account: Pubkey) -> u64 {
+        self.world.state::<anchor_spl::token::TokenAccount>(&account).amount
+    }
+}
+
+#[test]
+fn redirected_withdrawal_pays_mallory_without_assertions() {
+    let mut withdrawal = Withdrawal::new();
+    let redirected = withdrawal.redirected();
+
+    assert_eq!(send(&mut withdrawal.world, &[redirected]), Ok(()));
+    assert_eq!(withdrawal.tokens(withdrawal.mallorys_tokens), HELD);
+    assert_eq!(withdrawal.tokens(withdrawal.vault_tokens), 0);
+}
+
+#[test]
+fn appended_assertions_revert_the_redirected_withdrawal() {
+    let mut withdrawal = Withdrawal::new();
+    let transaction = client::guarded(vec![withdrawal.redirected()], withdrawal.assertions());
+
+    assert_eq!(
+        send(&mut withdrawal.world, &transaction),
+        Err(Error::from(AssertionError::TokenBalanceTooLow).into())
+    );
+    // The withdrawal went with the assertion
+    assert_eq!(withdrawal.tokens(withdrawal.vault_tokens), HELD);
+    assert_eq!(withdrawal.tokens(withdrawal.mallorys_tokens), 0);
+}
+
+#[test]
+fn honest_withdrawal_passes_its_assertions() {
+    let mut withdrawal = Withdrawal::new();
+    let honest = withdrawal.withdraw(withdrawal.alices_tokens, anchor_spl::token::ID);
+    let transaction = client::guarded(vec![honest], withdrawal.assertions());
+
+    assert_eq!(send(&mut withdrawal.world, &transaction), Ok(()));
+    assert_eq!(withdrawal.tokens(withdrawal.alices_tokens), HELD);
+}
+
+#[test]
+fn secure_withdraw_needs_no_assertions_to_refuse_the_substitute() {
+    let mut withdrawal = Withdrawal::new();
+    let mut secure = withdrawal.redirected();
+    secure.data = instruction::SecureWithdraw { amount: HELD }.data();
+
+    assert_eq!(
+        send(&mut withdrawal.world, &[secure]),
+        Err(Error::from(ErrorCode::InvalidProgramId).into())
+    );
+}
@@ -0,0 +1,30 @@
+//! Client-side mirrors of the secure instructions' account validation.
+//!
+//! A transaction that fails on-chain still costs its fee, and the error
+//! comes back as a code from whichever constraint tripped first. An
+//! integrator holding the accounts it is about to send can run the same
+//! checks before signing: who owns each account, that it deserializes as
+//! the expected type, that PDAs sit at the addresses their seeds and
+//! stored bumps derive, that token accounts hold the right mint for the
+//! right authority, and that balances cover the amount.
+//!
+//! Each covered program has a module under [`preflight`] with one
+//! `validate_*` function per instruction. They take the program's own
+//! generated client structs, the ones the transaction is built from, and
+//! read accounts through an [`AccountSource`] such as the result of an RPC
+//! `getMultipleAccounts`:
+//!
+//! ```ignore
+//! let accounts = vault::accounts::SecureWithdraw { vault, owner };
+//! preflight::vault::validate_secure_withdraw(&fetched, &accounts, &vault::instruction::SecureWithdraw { amount })?;
+//! ```
+//!
+//! Preflight is advice, not enforcement: the accounts can change between
+//! the check and the transaction landing, and a malicious client skips it.
+//! The program's own constraints are what protect it.
+
+pub mod preflight;
+pub mod source;
+
+pub use preflight::PreflightError;
+pub use source::{AccountSource, Snapshot};
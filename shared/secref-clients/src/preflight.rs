@@ -0,0 +1,214 @@
+//! Off-chain checks mirroring the secure instructions' constraints.
+//!
+//! The helpers here are the building blocks the per-program modules share.
+//! They mirror how Anchor evaluates the constraint they stand in for:
+//! [`program_account`] checks the owner before the discriminator, as
+//! `Account<T>` does, and [`derived`] uses the bump stored in the account,
+//! as `seeds = .., bump = x.bump` does, rather than searching for the
+//! canonical one.
+
+use std::fmt;
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Mint, TokenAccount};
+
+use crate::source::{AccountSource, Snapshot};
+
+pub mod ata_escrow;
+pub mod guarded_pool;
+pub mod payment_processor;
+pub mod premium_club;
+pub mod token_desk;
+pub mod vault;
+pub mod vault_bank;
+
+/// Why the transaction would fail, naming the account by its field in the instruction's context
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PreflightError {
+    /// Nothing exists at the address
+    Missing { account: &'static str },
+    /// Owned by a program other than the one whose type it must be
+    WrongOwner { account: &'static str, expected: Pubkey, actual: Pubkey },
+    /// Does not deserialize as the expected account type
+    WrongType { account: &'static str },
+    /// Not the address its seeds, its parent, or a fixed program id require
+    WrongAddress { account: &'static str, expected: Pubkey, actual: Pubkey },
+    /// A key stored in the account differs from the one it must match
+    Mismatch { account: &'static str, field: &'static str, expected: Pubkey, actual: Pubkey },
+    /// An account the instruction creates already holds state
+    AlreadyInUse { account: &'static str },
+    /// Not enough lamports, tokens or recorded balance
+    InsufficientBalance { account: &'static str, needed: u64, available: u64 },
+    /// The account's state rules the instruction out, such as a frozen token account
+    Rejected { account: &'static str, reason: &'static str },
+    /// An instruction argument the program would refuse, such as a zero amount
+    InvalidArgument { argument: &'static str, reason: String },
+}
+
+impl fmt::Display for PreflightError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { account } => write!(f, "{account}: no account at this address"),
+            Self::WrongOwner { account, expected, actual } => {
+                write!(f, "{account}: owned by {actual}, expected {expected}")
+            }
+            Self::WrongType { account } => write!(f, "{account}: not an account of the expected type"),
+            Self::WrongAddress { account, expected, actual } => {
+                write!(f, "{account}: address {actual}, expected {expected}")
+            }
+            Self::Mismatch { account, field, expected, actual } => {
+                write!(f, "{account}.{field}: {actual}, expected {expected}")
+            }
+            Self::AlreadyInUse { account } => write!(f, "{account}: already in use"),
+            Self::InsufficientBalance { account, needed, available } => {
+                write!(f, "{account}: {available} available, {needed} needed")
+            }
+            Self::Rejected { account, reason } => write!(f, "{account}: {reason}"),
+            Self::InvalidArgument { argument, reason } => write!(f, "argument {argument}: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for PreflightError {}
+
+pub type Result<T> = std::result::Result<T, PreflightError>;
+
+/// The account at `address`, which must exist
+pub fn fetch(source: &impl AccountSource, address: &Pubkey, account: &'static str) -> Result<Snapshot> {
+    source.get(address).ok_or(PreflightError::Missing { account })
+}
+
+/// The Anchor account of type `T` at `address`, with its snapshot (`Account<'info, T>`)
+pub fn program_account<T: AccountDeserialize + Owner>(
+    source: &impl AccountSource,
+    address: &Pubkey,
+    account: &'static str,
+) -> Result<(Snapshot, T)> {
+    let snapshot = fetch(source, address, account)?;
+    owned_by(&snapshot, &T::owner(), account)?;
+    let state = T::try_deserialize(&mut snapshot.data.as_slice()).map_err(|_| PreflightError::WrongType { account })?;
+    Ok((snapshot, state))
+}
+
+/// The SPL token account at `address` (`Account<'info, TokenAccount>`)
+pub fn token_account(source: &impl AccountSource, address: &Pubkey, account: &'static str) -> Result<TokenAccount> {
+    program_account::<TokenAccount>(source, address, account).map(|(_, state)| state)
+}
+
+/// The SPL mint at `address` (`Account<'info, Mint>`)
+pub fn mint(source: &impl AccountSource, address: &Pubkey, account: &'static str) -> Result<Mint> {
+    program_account::<Mint>(source, address, account).map(|(_, state)| state)
+}
+
+/// Require an account at `address`, if any, to be free for the instruction to create (`init`)
+///
+/// The System Program can still allocate an address that only holds
+/// lamports, so a pre-funded address passes.
+pub fn uninitialized(source: &impl AccountSource, address: &Pubkey, account: &'static str) -> Result<()> {
+    match source.get(address) {
+        Some(snapshot) if snapshot.owner != system_program::ID || !snapshot.data.is_empty() => {
+            Err(PreflightError::AlreadyInUse { account })
+        }
+        _ => Ok(()),
+    }
+}
+
+pub fn owned_by(snapshot: &Snapshot, owner: &Pubkey, account: &'static str) -> Result<()> {
+    if snapshot.owner != *owner {
+        return Err(PreflightError::WrongOwner {
+            account,
+            expected: *owner,
+            actual: snapshot.owner,
+        });
+    }
+    Ok(())
+}
+
+/// Require `address` to be `expected`, such as a program id (`Program<'info, T>`) or a canonical PDA
+pub fn address(address: &Pubkey, expected: &Pubkey, account: &'static str) -> Result<()> {
+    if address != expected {
+        return Err(PreflightError::WrongAddress {
+            account,
+            expected: *expected,
+            actual: *address,
+        });
+    }
+    Ok(())
+}
+
+/// Require `address` to be derived from `seeds` and the stored `bump` (`seeds = .., bump = x.bump`)
+pub fn derived(address: &Pubkey, seeds: &[&[u8]], bump: u8, program_id: &Pubkey, account: &'static str) -> Result<()> {
+    let bump = [bump];
+    let seeds: Vec<&[u8]> = seeds.iter().copied().chain([&bump[..]]).collect();
+    match Pubkey::create_program_address(&seeds, program_id) {
+        Ok(expected) => self::address(address, &expected, account),
+        Err(_) => Err(PreflightError::Rejected {
+            account,
+            reason: "its seeds and stored bump derive no address",
+        }),
+    }
+}
+
+/// Require `address` to be the canonical PDA of `seeds` (`seeds = .., bump`); returns its bump
+pub fn canonical(address: &Pubkey, seeds: &[&[u8]], program_id: &Pubkey, account: &'static str) -> Result<u8> {
+    let (expected, bump) = Pubkey::find_program_address(seeds, program_id);
+    self::address(address, &expected, account)?;
+    Ok(bump)
+}
+
+/// Require a stored key to be `expected` (`has_one`, `token::mint`, `token::authority`)
+pub fn stored_key(actual: &Pubkey, expected: &Pubkey, account: &'static str, field: &'static str) -> Result<()> {
+    if actual != expected {
+        return Err(PreflightError::Mismatch {
+            account,
+            field,
+            expected: *expected,
+            actual: *actual,
+        });
+    }
+    Ok(())
+}
+
+pub fn at_least(available: u64, needed: u64, account: &'static str) -> Result<()> {
+    if available < needed {
+        return Err(PreflightError::InsufficientBalance {
+            account,
+            needed,
+            available,
+        });
+    }
+    Ok(())
+}
+
+/// Require the Token program to accept `account` in a transfer: not frozen
+pub fn transferable(account: &TokenAccount, name: &'static str) -> Result<()> {
+    if account.is_frozen() {
+        return Err(PreflightError::Rejected {
+            account: name,
+            reason: "token account is frozen",
+        });
+    }
+    Ok(())
+}
+
+/// Require the Token program to let `authority` move `amount` out of `account`
+///
+/// The mirror of a `Transfer` CPI's checks on its source: usable, signed
+/// for by its owner, and holding at least `amount`. Delegated transfers are
+/// not mirrored; the covered programs never make one.
+pub fn token_debit(account: &TokenAccount, authority: &Pubkey, amount: u64, name: &'static str) -> Result<()> {
+    transferable(account, name)?;
+    stored_key(&account.owner, authority, name, "owner")?;
+    at_least(account.amount, amount, name)
+}
+
+/// The Token program's id (`Program<'info, Token>`)
+pub fn token_program_id(address: &Pubkey) -> Result<()> {
+    self::address(address, &token::ID, "token_program")
+}
+
+/// The System Program's id (`Program<'info, System>`)
+pub fn system_program_id(address: &Pubkey) -> Result<()> {
+    self::address(address, &system_program::ID, "system_program")
+}
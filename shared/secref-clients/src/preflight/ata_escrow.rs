@@ -0,0 +1,70 @@
+//! 41_ata_precreation_squatting: a vault someone created first is fine, one carrying authority the escrow never granted is not.
+
+use anchor_lang::system_program;
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::token::TokenAccount;
+use ata_escrow::{accounts, instruction};
+
+use super::{
+    address, canonical, mint, stored_key, system_program_id, token_account, token_debit, token_program_id,
+    uninitialized, PreflightError, Result,
+};
+use crate::source::AccountSource;
+
+/// `secure_open_escrow`: a fresh escrow, its ATA as the vault, and the maker's own tokens to fund it
+///
+/// Returns the vault's existing token account, if someone created it already.
+pub fn validate_secure_open_escrow(
+    source: &impl AccountSource,
+    accounts: &accounts::SecureOpenEscrow,
+    args: &instruction::SecureOpenEscrow,
+) -> Result<Option<TokenAccount>> {
+    canonical(
+        &accounts.escrow,
+        &[b"escrow", accounts.maker.as_ref(), &args.seed.to_le_bytes()],
+        &ata_escrow::ID,
+        "escrow",
+    )?;
+    uninitialized(source, &accounts.escrow, "escrow")?;
+    mint(source, &accounts.mint, "mint")?;
+    token_program_id(&accounts.token_program)?;
+    address(&accounts.associated_token_program, &associated_token::ID, "associated_token_program")?;
+    system_program_id(&accounts.system_program)?;
+
+    let maker_tokens = token_account(source, &accounts.maker_tokens, "maker_tokens")?;
+    stored_key(&maker_tokens.mint, &accounts.mint, "maker_tokens", "mint")?;
+    token_debit(&maker_tokens, &accounts.maker, args.amount, "maker_tokens")?;
+
+    // `create_associated_token_account`: the canonical address, created idempotently, then checked as if new
+    address(
+        &accounts.vault,
+        &get_associated_token_address(&accounts.escrow, &accounts.mint),
+        "vault",
+    )?;
+    match source.get(&accounts.vault) {
+        // Nothing there yet, or only lamports: the Associated Token program creates it
+        None => Ok(None),
+        Some(snapshot) if snapshot.owner == system_program::ID => Ok(None),
+        Some(_) => {
+            let vault = token_account(source, &accounts.vault, "vault")?;
+            stored_key(&vault.owner, &accounts.escrow, "vault", "owner")?;
+            stored_key(&vault.mint, &accounts.mint, "vault", "mint")?;
+            sole_authority(&vault)?;
+            Ok(Some(vault))
+        }
+    }
+}
+
+/// `assert_sole_authority`: nobody but the escrow can move or close what is in the vault
+fn sole_authority(vault: &TokenAccount) -> Result<()> {
+    let reason = if vault.is_frozen() {
+        "token account is frozen"
+    } else if vault.delegate.is_some() {
+        "token account has a delegate"
+    } else if vault.close_authority.is_some() {
+        "token account has a close authority"
+    } else {
+        return Ok(());
+    };
+    Err(PreflightError::Rejected { account: "vault", reason })
+}
@@ -0,0 +1,78 @@
+//! 47_kill_switch_recovery: exits stay open unless a quorum halted the pool, and only until the halt lapses.
+//!
+//! Both checks depend on the mode in force, so they take `now`, the
+//! cluster's clock as the client last read it.
+
+use anchor_lang::prelude::*;
+use guarded_pool::{accounts, instruction, Mode, Pool, Position};
+
+use super::{at_least, derived, program_account, stored_key, PreflightError, Result};
+use crate::source::AccountSource;
+
+/// `withdraw`: the owner's position in this pool covers `amount`, and the pool is not halted at `now`
+pub fn validate_withdraw(
+    source: &impl AccountSource,
+    accounts: &accounts::Withdraw,
+    args: &instruction::Withdraw,
+    now: i64,
+) -> Result<Position> {
+    let (pool_account, pool) = pool(source, &accounts.pool)?;
+    if pool.mode_at(now) == Mode::Halted {
+        return Err(PreflightError::Rejected {
+            account: "pool",
+            reason: "pool is halted",
+        });
+    }
+
+    let (_, position) = program_account::<Position>(source, &accounts.position, "position")?;
+    derived(
+        &accounts.position,
+        &[b"position", accounts.pool.as_ref(), accounts.owner.as_ref()],
+        position.bump,
+        &guarded_pool::ID,
+        "position",
+    )?;
+    stored_key(&position.owner, &accounts.owner, "position", "owner")?;
+    at_least(position.balance, args.amount, "position")?;
+    at_least(pool_account.lamports, args.amount, "pool")?;
+    Ok(position)
+}
+
+/// `secure_halt`, signed by `guardians`: enough distinct ones to meet the threshold, and an active pool at `now`
+pub fn validate_secure_halt(
+    source: &impl AccountSource,
+    accounts: &accounts::SecureHalt,
+    guardians: &[Pubkey],
+    now: i64,
+) -> Result<Pool> {
+    let (_, pool) = pool(source, &accounts.pool)?;
+    let mut signed: Vec<&Pubkey> = guardians.iter().filter(|key| pool.guardians.contains(key)).collect();
+    signed.sort();
+    signed.dedup();
+    if signed.len() < usize::from(pool.threshold) {
+        return Err(PreflightError::Rejected {
+            account: "pool",
+            reason: "too few of its guardians sign",
+        });
+    }
+    if pool.mode_at(now) != Mode::Active {
+        return Err(PreflightError::Rejected {
+            account: "pool",
+            reason: "pool is not active",
+        });
+    }
+    Ok(pool)
+}
+
+/// The pool at `address`, at the PDA of its governance
+fn pool(source: &impl AccountSource, address: &Pubkey) -> Result<(crate::Snapshot, Pool)> {
+    let (snapshot, pool) = program_account::<Pool>(source, address, "pool")?;
+    derived(
+        address,
+        &[b"pool", pool.governance.as_ref()],
+        pool.bump,
+        &guarded_pool::ID,
+        "pool",
+    )?;
+    Ok((snapshot, pool))
+}
@@ -0,0 +1,38 @@
+//! 22_fee_recipient_ownership: the fee must land in a token account the protocol's fee authority owns.
+
+use payment_processor::{accounts, instruction, Config};
+
+use super::{derived, program_account, stored_key, token_account, token_debit, token_program_id, transferable, Result};
+use crate::source::AccountSource;
+
+/// `secure_pay`: every token account holds the config's mint, the payer's is the payer's, and the fee recipient is the fee authority's
+pub fn validate_secure_pay(
+    source: &impl AccountSource,
+    accounts: &accounts::SecurePay,
+    args: &instruction::SecurePay,
+) -> Result<Config> {
+    let (_, config) = program_account::<Config>(source, &accounts.config, "config")?;
+    derived(
+        &accounts.fee_authority,
+        &[b"fee_authority", accounts.config.as_ref()],
+        config.fee_authority_bump,
+        &payment_processor::ID,
+        "fee_authority",
+    )?;
+    token_program_id(&accounts.token_program)?;
+
+    let payer_tokens = token_account(source, &accounts.payer_tokens, "payer_tokens")?;
+    stored_key(&payer_tokens.mint, &config.mint, "payer_tokens", "mint")?;
+    stored_key(&payer_tokens.owner, &accounts.payer, "payer_tokens", "owner")?;
+    let merchant_tokens = token_account(source, &accounts.merchant_tokens, "merchant_tokens")?;
+    stored_key(&merchant_tokens.mint, &config.mint, "merchant_tokens", "mint")?;
+    let fee_recipient = token_account(source, &accounts.fee_recipient, "fee_recipient")?;
+    stored_key(&fee_recipient.mint, &config.mint, "fee_recipient", "mint")?;
+    stored_key(&fee_recipient.owner, &accounts.fee_authority, "fee_recipient", "owner")?;
+
+    // The merchant's share and the fee together are `amount`, both out of the payer's account
+    token_debit(&payer_tokens, &accounts.payer, args.amount, "payer_tokens")?;
+    transferable(&merchant_tokens, "merchant_tokens")?;
+    transferable(&fee_recipient, "fee_recipient")?;
+    Ok(config)
+}
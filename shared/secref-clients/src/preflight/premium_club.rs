@@ -0,0 +1,54 @@
+//! 46_balance_as_authorization: qualification comes from the member's own locked stake, not a wallet balance.
+
+use anchor_lang::prelude::*;
+use premium_club::{accounts, Club, StakeRecord};
+
+use super::{at_least, canonical, derived, fetch, program_account, stored_key, system_program_id, uninitialized, Result};
+use crate::source::AccountSource;
+
+/// `secure_claim_bonus`: the member's stake in this club meets its threshold, no claim exists yet, and the club can pay
+///
+/// The club's rent reserve is taken at the default rent rate, the one
+/// every cluster uses.
+pub fn validate_secure_claim_bonus(source: &impl AccountSource, accounts: &accounts::SecureClaimBonus) -> Result<Club> {
+    let (club_account, club) = program_account::<Club>(source, &accounts.club, "club")?;
+    derived(
+        &accounts.club,
+        &[b"club", club.authority.as_ref()],
+        club.bump,
+        &premium_club::ID,
+        "club",
+    )?;
+
+    let (_, stake) = program_account::<StakeRecord>(source, &accounts.stake, "stake")?;
+    derived(
+        &accounts.stake,
+        &[b"stake", accounts.club.as_ref(), accounts.member.as_ref()],
+        stake.bump,
+        &premium_club::ID,
+        "stake",
+    )?;
+    stored_key(&stake.club, &accounts.club, "stake", "club")?;
+    stored_key(&stake.member, &accounts.member, "stake", "member")?;
+
+    // One claim per member: the claim PDA is created here, so it must not exist yet
+    canonical(
+        &accounts.claim,
+        &[b"claim", accounts.club.as_ref(), accounts.member.as_ref()],
+        &premium_club::ID,
+        "claim",
+    )?;
+    uninitialized(source, &accounts.claim, "claim")?;
+    system_program_id(&accounts.system_program)?;
+
+    at_least(stake.amount, club.threshold, "stake")?;
+    let reserve = Rent::default().minimum_balance(club_account.data.len());
+    at_least(club_account.lamports.saturating_sub(reserve), club.bonus, "club")?;
+
+    // The member pays the claim's rent, less anything already sitting at its address
+    let member = fetch(source, &accounts.member, "member")?;
+    let claim_lamports = source.get(&accounts.claim).map_or(0, |claim| claim.lamports);
+    let rent = Rent::default().minimum_balance(premium_club::Claim::SPACE);
+    at_least(member.lamports, rent.saturating_sub(claim_lamports), "member")?;
+    Ok(club)
+}
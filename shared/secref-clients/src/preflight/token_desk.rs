@@ -0,0 +1,68 @@
+//! 59_decimal_confusion: amounts in the mint's own decimals, from what a user typed to what the buyer pays.
+
+use anchor_lang::error::Error;
+use anchor_spl::token::Mint;
+use secref_guards::amount::Amount;
+use token_desk::{accounts, instruction, Desk};
+
+use super::{
+    at_least, derived, fetch, mint, program_account, stored_key, system_program_id, token_account, token_debit,
+    token_program_id, transferable, PreflightError, Result,
+};
+use crate::source::AccountSource;
+
+/// `secure_buy` arguments for `ui` whole tokens of `mint`, such as `"1.5"`
+///
+/// Fails on anything but a plain decimal, and on digits past the mint's
+/// decimals, rather than rounding what the user asked for.
+pub fn secure_buy_args(ui: &str, mint: &Mint) -> Result<instruction::SecureBuy> {
+    let amount = Amount::from_ui(ui, mint.decimals).map_err(|error| invalid("amount", error))?;
+    Ok(instruction::SecureBuy { amount: amount.raw() })
+}
+
+/// `secure_buy`: the desk's own mint and vault, and a buyer who can pay for the amount at the mint's decimals
+///
+/// Returns the amount being bought, in the mint's units.
+pub fn validate_secure_buy(
+    source: &impl AccountSource,
+    accounts: &accounts::Buy,
+    args: &instruction::SecureBuy,
+) -> Result<Amount> {
+    let (_, desk) = program_account::<Desk>(source, &accounts.desk, "desk")?;
+    derived(&accounts.desk, &[b"desk", desk.mint.as_ref()], desk.bump, &token_desk::ID, "desk")?;
+    stored_key(&accounts.mint, &desk.mint, "desk", "mint")?;
+    stored_key(&accounts.vault, &desk.vault, "desk", "vault")?;
+    let mint = mint(source, &accounts.mint, "mint")?;
+    token_program_id(&accounts.token_program)?;
+    system_program_id(&accounts.system_program)?;
+
+    let buyer_tokens = token_account(source, &accounts.buyer_tokens, "buyer_tokens")?;
+    stored_key(&buyer_tokens.mint, &accounts.mint, "buyer_tokens", "mint")?;
+    stored_key(&buyer_tokens.owner, &accounts.buyer, "buyer_tokens", "owner")?;
+
+    let amount = Amount::from_raw(args.amount, mint.decimals);
+    if amount.raw() == 0 {
+        return Err(PreflightError::InvalidArgument {
+            argument: "amount",
+            reason: "buys nothing".to_string(),
+        });
+    }
+    let cost = amount.value_ceil(desk.price_per_token).map_err(|error| invalid("amount", error))?;
+    at_least(fetch(source, &accounts.buyer, "buyer")?.lamports, cost, "buyer")?;
+
+    // `transfer_checked` out of the vault, signed by the desk
+    let vault = token_account(source, &accounts.vault, "vault")?;
+    stored_key(&vault.mint, &accounts.mint, "vault", "mint")?;
+    token_debit(&vault, &accounts.desk, amount.raw(), "vault")?;
+    transferable(&buyer_tokens, "buyer_tokens")?;
+    Ok(amount)
+}
+
+/// An argument a guard refused, with the guard's message as the reason
+fn invalid(argument: &'static str, error: Error) -> PreflightError {
+    let reason = match error {
+        Error::AnchorError(error) => error.error_msg,
+        Error::ProgramError(error) => error.to_string(),
+    };
+    PreflightError::InvalidArgument { argument, reason }
+}
@@ -0,0 +1,18 @@
+//! 01_missing_account_validation: the vault must belong to the program and to its signer.
+
+use vault::{accounts, instruction, Vault};
+
+use super::{at_least, program_account, stored_key, Result};
+use crate::source::AccountSource;
+
+/// `secure_withdraw`: the program's vault, `has_one = owner`, with a balance covering `amount`
+pub fn validate_secure_withdraw(
+    source: &impl AccountSource,
+    accounts: &accounts::SecureWithdraw,
+    args: &instruction::SecureWithdraw,
+) -> Result<Vault> {
+    let (_, vault) = program_account::<Vault>(source, &accounts.vault, "vault")?;
+    stored_key(&vault.owner, &accounts.owner, "vault", "owner")?;
+    at_least(vault.balance, args.amount, "vault")?;
+    Ok(vault)
+}
@@ -0,0 +1,44 @@
+//! 17_god_pda_authority: the vault, its token account and its signing PDA must all derive from each other.
+
+use vault_bank::{accounts, instruction, Vault};
+
+use super::{
+    canonical, derived, program_account, stored_key, token_account, token_debit, token_program_id, transferable, Result,
+};
+use crate::source::AccountSource;
+
+/// `secure_withdraw`: the owner's own vault, paying out of the token account its per-vault authority owns
+pub fn validate_secure_withdraw(
+    source: &impl AccountSource,
+    accounts: &accounts::SecureWithdraw,
+    args: &instruction::SecureWithdraw,
+) -> Result<Vault> {
+    let (_, vault) = program_account::<Vault>(source, &accounts.vault, "vault")?;
+    stored_key(&vault.owner, &accounts.owner, "vault", "owner")?;
+    derived(
+        &accounts.vault,
+        &[b"secure_vault", accounts.owner.as_ref(), vault.mint.as_ref()],
+        vault.bump,
+        &vault_bank::ID,
+        "vault",
+    )?;
+    derived(
+        &accounts.vault_authority,
+        &[b"vault_authority", accounts.vault.as_ref()],
+        vault.authority_bump,
+        &vault_bank::ID,
+        "vault_authority",
+    )?;
+    token_program_id(&accounts.token_program)?;
+
+    canonical(&accounts.vault_tokens, &[b"vault_tokens", accounts.vault.as_ref()], &vault_bank::ID, "vault_tokens")?;
+    let vault_tokens = token_account(source, &accounts.vault_tokens, "vault_tokens")?;
+    let destination = token_account(source, &accounts.destination, "destination")?;
+    stored_key(&destination.mint, &vault.mint, "destination", "mint")?;
+
+    // The transfer the vault authority signs
+    token_debit(&vault_tokens, &accounts.vault_authority, args.amount, "vault_tokens")?;
+    stored_key(&destination.mint, &vault_tokens.mint, "destination", "mint")?;
+    transferable(&destination, "destination")?;
+    Ok(vault)
+}
@@ -0,0 +1,38 @@
+//! Accounts as a client fetched them.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anchor_lang::prelude::*;
+
+/// One account as an RPC node returns it
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    pub lamports: u64,
+    pub owner: Pubkey,
+    pub data: Vec<u8>,
+    pub executable: bool,
+}
+
+/// Where preflight reads accounts from; `None` for an address with no account
+pub trait AccountSource {
+    fn get(&self, address: &Pubkey) -> Option<Snapshot>;
+}
+
+impl AccountSource for BTreeMap<Pubkey, Snapshot> {
+    fn get(&self, address: &Pubkey) -> Option<Snapshot> {
+        BTreeMap::get(self, address).cloned()
+    }
+}
+
+impl AccountSource for HashMap<Pubkey, Snapshot> {
+    fn get(&self, address: &Pubkey) -> Option<Snapshot> {
+        HashMap::get(self, address).cloned()
+    }
+}
+
+/// Any lookup function, such as one over an RPC client's cache
+impl<F: Fn(&Pubkey) -> Option<Snapshot>> AccountSource for F {
+    fn get(&self, address: &Pubkey) -> Option<Snapshot> {
+        self(address)
+    }
+}
@@ -0,0 +1,127 @@
+//! 41_ata_precreation_squatting: `secure_open_escrow` by a maker, over a vault someone may have created first.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use ata_escrow::{accounts, instruction, Escrow};
+use common::{agreed, fetched, Corpus};
+use secref_clients::preflight;
+use secref_invariants::{program, World};
+use secref_testkit::TestAccount;
+
+const SEED: u64 = 7;
+const HELD: u64 = 1_000;
+
+struct Opening {
+    world: World,
+    accounts: accounts::SecureOpenEscrow,
+    seed: u64,
+    amount: u64,
+}
+
+impl Opening {
+    fn new() -> Self {
+        let mut world = World::new();
+        world.add(TestAccount::token_program());
+        world.add(TestAccount::program(anchor_spl::associated_token::ID));
+        let mint = world.add(TestAccount::mint(Pubkey::new_unique(), 6, HELD));
+        let maker = world.add(TestAccount::signer());
+        let maker_tokens = world.add(TestAccount::token_account(mint, maker, HELD));
+
+        let (escrow, _) =
+            Pubkey::find_program_address(&[b"escrow", maker.as_ref(), &SEED.to_le_bytes()], &ata_escrow::ID);
+        world.add(TestAccount::uninitialized(Escrow::SPACE).at(escrow));
+        let vault = world.add(TestAccount::system(0).at(get_associated_token_address(&escrow, &mint)));
+        Self {
+            world,
+            accounts: accounts::SecureOpenEscrow {
+                escrow,
+                vault,
+                mint,
+                maker_tokens,
+                maker,
+                token_program: anchor_spl::token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            },
+            seed: SEED,
+            amount: HELD,
+        }
+    }
+
+    /// Someone else created the escrow's vault first, as `vault` turned out
+    fn squatted(&mut self, vault: TestAccount) {
+        self.world.add(vault.at(self.accounts.vault));
+    }
+
+    fn run(mut self, case: &str) -> bool {
+        let args = instruction::SecureOpenEscrow { seed: self.seed, amount: self.amount };
+        let preflight =
+            preflight::ata_escrow::validate_secure_open_escrow(&fetched(&self.world), &self.accounts, &args);
+        let on_chain = self.world.execute(program!(ata_escrow), self.accounts, args);
+        agreed(case, preflight, on_chain)
+    }
+}
+
+/// One change each to a maker escrowing everything they hold
+const CORPUS: Corpus<Opening> = &[
+    ("a squatted vault with a delegate", |o| {
+        let (escrow, mint) = (o.accounts.escrow, o.accounts.mint);
+        o.squatted(TestAccount::token_account(mint, escrow, 0).with_delegate(Pubkey::new_unique(), u64::MAX));
+    }),
+    ("a squatted vault with a close authority", |o| {
+        let (escrow, mint) = (o.accounts.escrow, o.accounts.mint);
+        o.squatted(TestAccount::token_account(mint, escrow, 0).with_close_authority(Pubkey::new_unique()));
+    }),
+    ("a squatted, frozen vault", |o| {
+        let (escrow, mint) = (o.accounts.escrow, o.accounts.mint);
+        o.squatted(TestAccount::token_account(mint, escrow, 0).frozen());
+    }),
+    ("a vault that is not the escrow's ATA", |o| {
+        o.accounts.vault = o.world.add(TestAccount::system(0));
+    }),
+    ("a seed the maker already used", |o| {
+        let escrow = Escrow {
+            maker: o.accounts.maker,
+            mint: o.accounts.mint,
+            vault: o.accounts.vault,
+            seed: SEED,
+            amount: 1,
+            bump: 255,
+        };
+        o.world.add(TestAccount::anchor(&escrow).at(o.accounts.escrow));
+    }),
+    ("someone else's tokens", |o| {
+        let mint = o.accounts.mint;
+        o.accounts.maker_tokens = o.world.add(TestAccount::token_account(mint, Pubkey::new_unique(), HELD));
+    }),
+    ("tokens of another mint", |o| {
+        let other = o.world.add(TestAccount::mint(Pubkey::new_unique(), 6, HELD));
+        o.accounts.maker_tokens = o.world.add(TestAccount::token_account(other, o.accounts.maker, HELD));
+    }),
+    ("more than the maker holds", |o| o.amount = HELD + 1),
+];
+
+#[test]
+fn preflight_and_program_accept_the_legitimate_escrow() {
+    assert!(Opening::new().run("legitimate"));
+}
+
+#[test]
+fn preflight_and_program_accept_a_squatted_vault_that_grants_nothing() {
+    let mut opening = Opening::new();
+    let (escrow, mint) = (opening.accounts.escrow, opening.accounts.mint);
+    // Tokens sent to the vault ahead of time are not a reason to refuse it
+    opening.squatted(TestAccount::token_account(mint, escrow, 5));
+    assert!(opening.run("squatted by a clean vault"));
+}
+
+#[test]
+fn preflight_and_program_reject_the_malicious_corpus_alike() {
+    for (case, tamper) in CORPUS {
+        let mut opening = Opening::new();
+        tamper(&mut opening);
+        assert!(!opening.run(case), "{case}: accepted");
+    }
+}
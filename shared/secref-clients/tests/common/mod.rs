@@ -0,0 +1,34 @@
+//! Runs a corpus case through preflight and through the program, against the same accounts.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use secref_clients::{PreflightError, Snapshot};
+use secref_invariants::World;
+
+/// Named changes, each turning a legitimate transaction into one the program must reject
+pub type Corpus<T> = &'static [(&'static str, fn(&mut T))];
+
+/// The world's accounts as an RPC node would return them
+///
+/// An address holding no lamports has no account on a cluster, whatever
+/// buffer the world reserved for it (`TestAccount::uninitialized`).
+pub fn fetched(world: &World) -> impl Fn(&Pubkey) -> Option<Snapshot> + '_ {
+    |address| {
+        world.get(address).filter(|account| account.lamports > 0).map(|account| Snapshot {
+            lamports: account.lamports,
+            owner: account.owner,
+            data: account.data.clone(),
+            executable: account.executable,
+        })
+    }
+}
+
+/// Whether both accepted `case`; panics if preflight and the program disagree
+pub fn agreed<T>(case: &str, preflight: std::result::Result<T, PreflightError>, on_chain: ProgramResult) -> bool {
+    match (preflight, on_chain) {
+        (Ok(_), Ok(())) => true,
+        (Err(_), Err(_)) => false,
+        (Ok(_), Err(error)) => panic!("{case}: preflight accepts it, the program fails with {error:?}"),
+        (Err(error), Ok(())) => panic!("{case}: the program accepts it, preflight rejects it ({error})"),
+    }
+}
@@ -0,0 +1,151 @@
+//! 47_kill_switch_recovery: `withdraw` from a position, and `secure_halt` by the guardians.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_lang::InstructionData;
+use common::{agreed, fetched, Corpus};
+use guarded_pool::{accounts, instruction, Mode, Pool, Position};
+use secref_clients::preflight;
+use secref_invariants::{program, World};
+use secref_testkit::{runtime, TestAccount};
+
+const NOW: i64 = 1_700_000_000;
+const BALANCE: u64 = 1_000_000_000;
+const THRESHOLD: u8 = 2;
+
+struct Deployment {
+    world: World,
+    pool: Pubkey,
+    position: Pubkey,
+    owner: Pubkey,
+    amount: u64,
+    mallory: Pubkey,
+    guardians: Vec<Pubkey>,
+}
+
+impl Deployment {
+    fn new() -> Self {
+        runtime::warp_to(NOW);
+        let mut world = World::new();
+        let governance = Pubkey::new_unique();
+        let owner = world.add(TestAccount::signer());
+        let mallory = world.add(TestAccount::signer());
+        let guardians: Vec<Pubkey> = (0..3).map(|_| world.add(TestAccount::signer())).collect();
+
+        let (pool, bump) = Pubkey::find_program_address(&[b"pool", governance.as_ref()], &guarded_pool::ID);
+        let pool_account = TestAccount::anchor(&Pool {
+            governance,
+            guardians: guardians.clone(),
+            threshold: THRESHOLD,
+            mode: Mode::Active,
+            halt_ends_at: 0,
+            max_halt: 259_200,
+            resume_delay: 172_800,
+            resume: None,
+            bump,
+        });
+        let funded = pool_account.lamports + BALANCE;
+        world.add(pool_account.with_lamports(funded).at(pool));
+        let position = position(&mut world, pool, owner, BALANCE);
+        Self { world, pool, position, owner, amount: BALANCE, mallory, guardians }
+    }
+
+    /// The pool as last set to `mode`, with a halt ending at `halt_ends_at`
+    fn set_mode(&mut self, mode: Mode, halt_ends_at: i64) {
+        let mut pool: Pool = self.world.state(&self.pool);
+        pool.mode = mode;
+        pool.halt_ends_at = halt_ends_at;
+        let lamports = self.world.lamports(&self.pool);
+        self.world.add(TestAccount::anchor(&pool).with_lamports(lamports).at(self.pool));
+    }
+
+    fn withdraw(mut self, case: &str) -> bool {
+        let accounts = accounts::Withdraw { pool: self.pool, position: self.position, owner: self.owner };
+        let args = instruction::Withdraw { amount: self.amount };
+        let preflight = preflight::guarded_pool::validate_withdraw(&fetched(&self.world), &accounts, &args, NOW);
+        let on_chain = self.world.execute(program!(guarded_pool), accounts, args);
+        agreed(case, preflight, on_chain)
+    }
+
+    fn halt(mut self, case: &str, signers: &[Pubkey]) -> bool {
+        let accounts = accounts::SecureHalt { pool: self.pool };
+        let preflight = preflight::guarded_pool::validate_secure_halt(&fetched(&self.world), &accounts, signers, NOW);
+        let mut metas = accounts.to_account_metas(None);
+        metas.extend(signers.iter().map(|signer| AccountMeta::new_readonly(*signer, true)));
+        let on_chain = self.world.invoke(program!(guarded_pool), &metas, &instruction::SecureHalt {}.data());
+        agreed(case, preflight, on_chain)
+    }
+}
+
+/// `owner`'s position of `balance` in `pool`, at its PDA
+fn position(world: &mut World, pool: Pubkey, owner: Pubkey, balance: u64) -> Pubkey {
+    let (address, bump) =
+        Pubkey::find_program_address(&[b"position", pool.as_ref(), owner.as_ref()], &guarded_pool::ID);
+    world.add(TestAccount::anchor(&Position { pool, owner, balance, bump }).at(address))
+}
+
+/// One change each to the owner withdrawing their whole position from an active pool
+const WITHDRAWALS: Corpus<Deployment> = &[
+    ("a halted pool", |d| d.set_mode(Mode::Halted, NOW + 1)),
+    ("someone else's position", |d| d.owner = d.mallory),
+    ("a position in another pool", |d| {
+        let (pool, owner) = (Pubkey::new_unique(), d.owner);
+        d.position = position(&mut d.world, pool, owner, BALANCE);
+    }),
+    ("a pool away from its governance's address", |d| {
+        let copy = d.world.account(&d.pool).clone().rekeyed();
+        d.pool = d.world.add(copy);
+    }),
+    ("more than the position holds", |d| d.amount = BALANCE + 1),
+];
+
+#[test]
+fn preflight_and_program_accept_legitimate_withdrawals() {
+    assert!(Deployment::new().withdraw("active pool"));
+
+    let mut lapsed = Deployment::new();
+    lapsed.set_mode(Mode::Halted, NOW);
+    assert!(lapsed.withdraw("halt lapsed into withdraw-only"));
+}
+
+#[test]
+fn preflight_and_program_reject_malicious_withdrawals_alike() {
+    for (case, tamper) in WITHDRAWALS {
+        let mut deployment = Deployment::new();
+        tamper(&mut deployment);
+        assert!(!deployment.withdraw(case), "{case}: accepted");
+    }
+}
+
+#[test]
+fn preflight_and_program_accept_a_quorum_halt() {
+    let deployment = Deployment::new();
+    let signers = deployment.guardians[..usize::from(THRESHOLD)].to_vec();
+    assert!(deployment.halt("quorum of an active pool", &signers));
+}
+
+#[test]
+fn preflight_and_program_reject_malicious_halts_alike() {
+    let deployment = Deployment::new();
+    let one = [deployment.guardians[0]];
+    assert!(!deployment.halt("one guardian", &one));
+
+    let deployment = Deployment::new();
+    let twice = [deployment.guardians[0], deployment.guardians[0]];
+    assert!(!deployment.halt("one guardian signing twice", &twice));
+
+    let deployment = Deployment::new();
+    let outsider = [deployment.guardians[0], deployment.mallory];
+    assert!(!deployment.halt("a guardian and an outsider", &outsider));
+
+    let mut renewed = Deployment::new();
+    renewed.set_mode(Mode::Halted, NOW + 1);
+    let signers = renewed.guardians[..usize::from(THRESHOLD)].to_vec();
+    assert!(!renewed.halt("renewing a halt", &signers));
+
+    let mut restricted = Deployment::new();
+    restricted.set_mode(Mode::WithdrawOnly, 0);
+    let signers = restricted.guardians[..usize::from(THRESHOLD)].to_vec();
+    assert!(!restricted.halt("a withdraw-only pool", &signers));
+}
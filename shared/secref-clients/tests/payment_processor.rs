@@ -0,0 +1,110 @@
+//! 22_fee_recipient_ownership: `secure_pay` of a merchant, with the fee to the protocol's fee vault.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{agreed, fetched, Corpus};
+use payment_processor::{accounts, instruction, Config};
+use secref_clients::preflight;
+use secref_invariants::{program, World};
+use secref_testkit::TestAccount;
+
+const HELD: u64 = 10_000;
+const FEE_BPS: u16 = 100;
+
+struct Payment {
+    world: World,
+    accounts: accounts::SecurePay,
+    amount: u64,
+    mint: Pubkey,
+    merchant: Pubkey,
+}
+
+impl Payment {
+    fn new() -> Self {
+        let mut world = World::new();
+        world.add(TestAccount::token_program());
+        let mint = world.add(TestAccount::mint(Pubkey::new_unique(), 6, HELD));
+        let payer = world.add(TestAccount::signer());
+        let merchant = world.add(TestAccount::signer());
+
+        let (config, bump) = Pubkey::find_program_address(&[b"config", mint.as_ref()], &payment_processor::ID);
+        let (fee_authority, fee_authority_bump) =
+            Pubkey::find_program_address(&[b"fee_authority", config.as_ref()], &payment_processor::ID);
+        let (fee_vault, _) = Pubkey::find_program_address(&[b"fee_vault", config.as_ref()], &payment_processor::ID);
+        world.add(
+            TestAccount::anchor(&Config {
+                admin: Pubkey::new_unique(),
+                mint,
+                fee_bps: FEE_BPS,
+                fee_authority_bump,
+                bump,
+                fee_waivable: false,
+            })
+            .at(config),
+        );
+        world.add(TestAccount::system(0).at(fee_authority));
+        world.add(TestAccount::token_account(mint, fee_authority, 0).at(fee_vault));
+
+        Self {
+            accounts: accounts::SecurePay {
+                config,
+                fee_authority,
+                payer_tokens: world.add(TestAccount::token_account(mint, payer, HELD)),
+                merchant_tokens: world.add(TestAccount::token_account(mint, merchant, 0)),
+                fee_recipient: fee_vault,
+                payer,
+                token_program: anchor_spl::token::ID,
+            },
+            world,
+            amount: HELD,
+            mint,
+            merchant,
+        }
+    }
+
+    fn run(mut self, case: &str) -> bool {
+        let args = instruction::SecurePay { amount: self.amount };
+        let preflight = preflight::payment_processor::validate_secure_pay(&fetched(&self.world), &self.accounts, &args);
+        let on_chain = self.world.execute(program!(payment_processor), self.accounts, args);
+        agreed(case, preflight, on_chain)
+    }
+}
+
+/// One change each to a payment of everything the payer holds
+const CORPUS: Corpus<Payment> = &[
+    ("the payer's own account as the fee recipient", |p| p.accounts.fee_recipient = p.accounts.payer_tokens),
+    ("a fee account a deployer key owns", |p| {
+        p.accounts.fee_recipient = p.world.add(TestAccount::token_account(p.mint, Pubkey::new_unique(), 0));
+    }),
+    ("a fee authority that is not the config's", |p| {
+        p.accounts.fee_authority = p.world.add(TestAccount::system(0));
+    }),
+    ("a config copied into another program", |p| {
+        let copy = p.world.account(&p.accounts.config).clone().rekeyed().owned_by(Pubkey::new_unique());
+        p.accounts.config = p.world.add(copy);
+    }),
+    ("paying out of the merchant's tokens", |p| p.accounts.payer_tokens = p.accounts.merchant_tokens),
+    ("a merchant account of another mint", |p| {
+        let other = p.world.add(TestAccount::mint(Pubkey::new_unique(), 6, 0));
+        p.accounts.merchant_tokens = p.world.add(TestAccount::token_account(other, p.merchant, 0));
+    }),
+    ("a frozen merchant account", |p| {
+        p.accounts.merchant_tokens = p.world.add(TestAccount::token_account(p.mint, p.merchant, 0).frozen());
+    }),
+    ("more than the payer holds", |p| p.amount = HELD + 1),
+];
+
+#[test]
+fn preflight_and_program_accept_the_legitimate_payment() {
+    assert!(Payment::new().run("legitimate"));
+}
+
+#[test]
+fn preflight_and_program_reject_the_malicious_corpus_alike() {
+    for (case, tamper) in CORPUS {
+        let mut payment = Payment::new();
+        tamper(&mut payment);
+        assert!(!payment.run(case), "{case}: accepted");
+    }
+}
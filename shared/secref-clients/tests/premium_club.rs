@@ -0,0 +1,122 @@
+//! 46_balance_as_authorization: `secure_claim_bonus` by a member with a locked stake.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{agreed, fetched, Corpus};
+use premium_club::{accounts, instruction, Claim, Club, StakeRecord};
+use secref_clients::preflight;
+use secref_invariants::{program, World};
+use secref_testkit::TestAccount;
+
+const THRESHOLD: u64 = 1_000_000_000;
+const BONUS: u64 = 100_000_000;
+
+struct Claiming {
+    world: World,
+    accounts: accounts::SecureClaimBonus,
+    mallory: Pubkey,
+}
+
+impl Claiming {
+    fn new() -> Self {
+        let mut world = World::new();
+        let authority = Pubkey::new_unique();
+        let member = world.add(TestAccount::signer());
+        let mallory = world.add(TestAccount::signer());
+
+        let (club, bump) = Pubkey::find_program_address(&[b"club", authority.as_ref()], &premium_club::ID);
+        let club_account = TestAccount::anchor(&Club {
+            authority,
+            threshold: THRESHOLD,
+            bonus: BONUS,
+            lock_period: 86_400,
+            bump,
+        });
+        let funded = club_account.lamports + 10 * BONUS;
+        world.add(club_account.with_lamports(funded).at(club));
+
+        let stake = stake(&mut world, club, member, THRESHOLD);
+        let (claim, _) =
+            Pubkey::find_program_address(&[b"claim", club.as_ref(), member.as_ref()], &premium_club::ID);
+        world.add(TestAccount::uninitialized(Claim::SPACE).at(claim));
+        Self {
+            world,
+            accounts: accounts::SecureClaimBonus {
+                club,
+                stake,
+                claim,
+                member,
+                system_program: anchor_lang::system_program::ID,
+            },
+            mallory,
+        }
+    }
+
+    fn run(mut self, case: &str) -> bool {
+        let preflight = preflight::premium_club::validate_secure_claim_bonus(&fetched(&self.world), &self.accounts);
+        let on_chain = self.world.execute(program!(premium_club), self.accounts, instruction::SecureClaimBonus {});
+        agreed(case, preflight, on_chain)
+    }
+}
+
+/// `member`'s stake of `amount` in `club`, at its PDA
+fn stake(world: &mut World, club: Pubkey, member: Pubkey, amount: u64) -> Pubkey {
+    let (address, bump) =
+        Pubkey::find_program_address(&[b"stake", club.as_ref(), member.as_ref()], &premium_club::ID);
+    let record = StakeRecord { club, member, amount, locked_until: i64::MAX, bump };
+    world.add(TestAccount::anchor(&record).at(address))
+}
+
+/// One change each to a member claiming on a stake of exactly the threshold
+const CORPUS: Corpus<Claiming> = &[
+    ("a stake below the threshold", |c| {
+        let (club, member) = (c.accounts.club, c.accounts.member);
+        stake(&mut c.world, club, member, THRESHOLD - 1);
+    }),
+    ("no stake, whatever the wallet holds", |c| {
+        let address = c.accounts.stake;
+        c.world.add(TestAccount::system(0).at(address));
+    }),
+    ("someone else's stake", |c| {
+        let (club, mallory) = (c.accounts.club, c.mallory);
+        let (claim, _) =
+            Pubkey::find_program_address(&[b"claim", club.as_ref(), mallory.as_ref()], &premium_club::ID);
+        c.world.add(TestAccount::uninitialized(Claim::SPACE).at(claim));
+        c.accounts.member = mallory;
+        c.accounts.claim = claim;
+    }),
+    ("a stake recorded for another club", |c| {
+        let address = c.accounts.stake;
+        let mut record: StakeRecord = c.world.state(&address);
+        record.club = Pubkey::new_unique();
+        c.world.add(TestAccount::anchor(&record).at(address));
+    }),
+    ("a stake forged outside the program", |c| {
+        let forged = c.world.account(&c.accounts.stake).clone().owned_by(Pubkey::new_unique());
+        c.world.add(forged);
+    }),
+    ("a second claim", |c| {
+        let claim = Claim { club: c.accounts.club, member: c.accounts.member, bump: 255 };
+        c.world.add(TestAccount::anchor(&claim).at(c.accounts.claim));
+    }),
+    ("a club that cannot pay the bonus", |c| {
+        let club = c.world.account(&c.accounts.club).clone();
+        let reserve = Rent::default().minimum_balance(club.data.len());
+        c.world.add(club.with_lamports(reserve + BONUS - 1));
+    }),
+];
+
+#[test]
+fn preflight_and_program_accept_the_legitimate_claim() {
+    assert!(Claiming::new().run("legitimate"));
+}
+
+#[test]
+fn preflight_and_program_reject_the_malicious_corpus_alike() {
+    for (case, tamper) in CORPUS {
+        let mut claiming = Claiming::new();
+        tamper(&mut claiming);
+        assert!(!claiming.run(case), "{case}: accepted");
+    }
+}
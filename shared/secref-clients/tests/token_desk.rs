@@ -0,0 +1,130 @@
+//! 59_decimal_confusion: `secure_buy` of a 6-decimal token, from the amount a user typed.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use common::{agreed, fetched, Corpus};
+use secref_clients::{preflight, PreflightError};
+use secref_invariants::{program, World};
+use secref_testkit::TestAccount;
+use token_desk::{accounts, Desk};
+
+/// USDC's decimals
+const DECIMALS: u8 = 6;
+/// Lamports per whole token: 0.1 SOL
+const PRICE: u64 = 100_000_000;
+/// Base units the vault holds: 10,000 tokens
+const STOCK: u64 = 10_000_000_000;
+/// What the buyer holds: 1 SOL
+const BUYER_LAMPORTS: u64 = 1_000_000_000;
+
+struct Buying {
+    world: World,
+    accounts: accounts::Buy,
+    desk: Desk,
+    ui: &'static str,
+}
+
+impl Buying {
+    fn new() -> Self {
+        let mut world = World::new();
+        world.add(TestAccount::token_program());
+        let mint = world.add(TestAccount::mint(Pubkey::new_unique(), DECIMALS, STOCK));
+        let buyer = world.add(TestAccount::signer().with_lamports(BUYER_LAMPORTS));
+        let buyer_tokens = world.add(TestAccount::token_account(mint, buyer, 0));
+
+        let (desk, bump) = Pubkey::find_program_address(&[b"desk", mint.as_ref()], &token_desk::ID);
+        let vault = world.add(TestAccount::token_account(mint, desk, STOCK));
+        let state = Desk {
+            authority: Pubkey::new_unique(),
+            mint,
+            vault,
+            price_per_token: PRICE,
+            sold: 0,
+            bump,
+        };
+        world.add(TestAccount::anchor(&state).at(desk));
+        Self {
+            world,
+            accounts: accounts::Buy {
+                desk,
+                mint,
+                vault,
+                buyer_tokens,
+                buyer,
+                token_program: anchor_spl::token::ID,
+                system_program: anchor_lang::system_program::ID,
+            },
+            desk: state,
+            ui: "1.5",
+        }
+    }
+
+    fn mint(&self) -> Mint {
+        self.world.state(&self.accounts.mint)
+    }
+
+    fn run(mut self, case: &str) -> bool {
+        let args = preflight::token_desk::secure_buy_args(self.ui, &self.mint()).unwrap();
+        let preflight = preflight::token_desk::validate_secure_buy(&fetched(&self.world), &self.accounts, &args);
+        let on_chain = self.world.execute(program!(token_desk), self.accounts, args);
+        agreed(case, preflight, on_chain)
+    }
+}
+
+/// One change each to a buyer asking for 1.5 tokens
+const CORPUS: Corpus<Buying> = &[
+    ("a copy of the desk away from its seeds", |b| {
+        b.accounts.desk = b.world.add(TestAccount::anchor(&b.desk));
+    }),
+    ("a mint the desk does not sell", |b| {
+        b.accounts.mint = b.world.add(TestAccount::mint(Pubkey::new_unique(), DECIMALS, STOCK));
+    }),
+    ("a vault the desk does not sell from", |b| {
+        let (mint, desk) = (b.accounts.mint, b.accounts.desk);
+        b.accounts.vault = b.world.add(TestAccount::token_account(mint, desk, STOCK));
+    }),
+    ("tokens delivered to someone else", |b| {
+        let mint = b.accounts.mint;
+        b.accounts.buyer_tokens = b.world.add(TestAccount::token_account(mint, Pubkey::new_unique(), 0));
+    }),
+    ("tokens delivered to an account of another mint", |b| {
+        let other = b.world.add(TestAccount::mint(Pubkey::new_unique(), DECIMALS, 0));
+        b.accounts.buyer_tokens = b.world.add(TestAccount::token_account(other, b.accounts.buyer, 0));
+    }),
+    ("a frozen destination", |b| {
+        let (mint, buyer) = (b.accounts.mint, b.accounts.buyer);
+        b.world.add(TestAccount::token_account(mint, buyer, 0).frozen().at(b.accounts.buyer_tokens));
+    }),
+    ("nothing", |b| b.ui = "0"),
+    ("1,000 tokens, which cost 100 SOL", |b| b.ui = "1000"),
+    ("more than the vault holds", |b| b.ui = "10000.000001"),
+];
+
+#[test]
+fn preflight_and_program_accept_the_legitimate_purchase() {
+    assert!(Buying::new().run("legitimate"));
+}
+
+#[test]
+fn ui_amounts_are_scaled_by_the_mints_decimals() {
+    let mint = Buying::new().mint();
+    assert_eq!(preflight::token_desk::secure_buy_args("1.5", &mint).unwrap().amount, 1_500_000);
+    assert_eq!(preflight::token_desk::secure_buy_args("0.000001", &mint).unwrap().amount, 1);
+
+    // A digit the mint cannot represent is refused, not rounded away
+    assert!(matches!(
+        preflight::token_desk::secure_buy_args("0.0000001", &mint),
+        Err(PreflightError::InvalidArgument { argument: "amount", .. })
+    ));
+}
+
+#[test]
+fn preflight_and_program_reject_the_malicious_corpus_alike() {
+    for (case, tamper) in CORPUS {
+        let mut buying = Buying::new();
+        tamper(&mut buying);
+        assert!(!buying.run(case), "{case}: accepted");
+    }
+}
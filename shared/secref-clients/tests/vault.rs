@@ -0,0 +1,71 @@
+//! 01_missing_account_validation: `secure_withdraw` from Alice's vault.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{agreed, fetched, Corpus};
+use secref_clients::preflight;
+use secref_invariants::{program, World};
+use secref_testkit::TestAccount;
+use vault::{accounts, instruction, Vault};
+
+const BALANCE: u64 = 1_000;
+
+struct Withdrawal {
+    world: World,
+    accounts: accounts::SecureWithdraw,
+    amount: u64,
+    bobs_vault: Pubkey,
+}
+
+impl Withdrawal {
+    fn new() -> Self {
+        let mut world = World::new();
+        let alice = world.add(TestAccount::signer());
+        let bob = world.add(TestAccount::signer());
+        let vault = world.add(TestAccount::anchor(&Vault { owner: alice, balance: BALANCE }));
+        let bobs_vault = world.add(TestAccount::anchor(&Vault { owner: bob, balance: BALANCE }));
+        Self {
+            world,
+            accounts: accounts::SecureWithdraw { vault, owner: alice },
+            amount: BALANCE,
+            bobs_vault,
+        }
+    }
+
+    /// Preflight, then the transaction; whether both accepted it
+    fn run(mut self, case: &str) -> bool {
+        let args = instruction::SecureWithdraw { amount: self.amount };
+        let preflight = preflight::vault::validate_secure_withdraw(&fetched(&self.world), &self.accounts, &args);
+        let on_chain = self.world.execute(program!(vault), self.accounts, args);
+        agreed(case, preflight, on_chain)
+    }
+}
+
+/// One change each to Alice's withdrawal of her whole balance
+const CORPUS: Corpus<Withdrawal> = &[
+    ("Bob's vault", |w| w.accounts.vault = w.bobs_vault),
+    ("a copy of the vault in another program", |w| {
+        let copy = w.world.account(&w.accounts.vault).clone().rekeyed().owned_by(Pubkey::new_unique());
+        w.accounts.vault = w.world.add(copy);
+    }),
+    ("an account of another type", |w| {
+        w.accounts.vault = w.world.add(TestAccount::new(vault::ID, vec![7; 48]));
+    }),
+    ("the wallet itself as the vault", |w| w.accounts.vault = w.accounts.owner),
+    ("more than the balance", |w| w.amount = BALANCE + 1),
+];
+
+#[test]
+fn preflight_and_program_accept_the_legitimate_withdrawal() {
+    assert!(Withdrawal::new().run("legitimate"));
+}
+
+#[test]
+fn preflight_and_program_reject_the_malicious_corpus_alike() {
+    for (case, tamper) in CORPUS {
+        let mut withdrawal = Withdrawal::new();
+        tamper(&mut withdrawal);
+        assert!(!withdrawal.run(case), "{case}: accepted");
+    }
+}
@@ -0,0 +1,119 @@
+//! 17_god_pda_authority: `secure_withdraw` of Alice's tokens from her vault.
+
+mod common;
+
+use anchor_lang::prelude::*;
+use common::{agreed, fetched, Corpus};
+use secref_clients::preflight;
+use secref_invariants::{program, World};
+use secref_testkit::TestAccount;
+use vault_bank::{accounts, instruction, Vault};
+
+const HELD: u64 = 1_000;
+
+struct Withdrawal {
+    world: World,
+    accounts: accounts::SecureWithdraw,
+    amount: u64,
+    mint: Pubkey,
+    mallory: Pubkey,
+    /// Bob's vault and its token account, opened the same way as Alice's
+    bobs_vault: (Pubkey, Pubkey),
+}
+
+impl Withdrawal {
+    fn new() -> Self {
+        let mut world = World::new();
+        world.add(TestAccount::token_program());
+        let mint = world.add(TestAccount::mint(Pubkey::new_unique(), 6, 2 * HELD));
+        let alice = world.add(TestAccount::signer());
+        let mallory = world.add(TestAccount::signer());
+        let bob = world.add(TestAccount::signer());
+
+        let (vault, vault_tokens, vault_authority) = open_vault(&mut world, alice, mint);
+        let bobs_vault = open_vault(&mut world, bob, mint);
+        let destination = world.add(TestAccount::token_account(mint, alice, 0));
+        Self {
+            world,
+            accounts: accounts::SecureWithdraw {
+                vault,
+                vault_tokens,
+                vault_authority,
+                destination,
+                owner: alice,
+                token_program: anchor_spl::token::ID,
+            },
+            amount: HELD,
+            mint,
+            mallory,
+            bobs_vault: (bobs_vault.0, bobs_vault.1),
+        }
+    }
+
+    fn run(mut self, case: &str) -> bool {
+        let args = instruction::SecureWithdraw { amount: self.amount };
+        let preflight = preflight::vault_bank::validate_secure_withdraw(&fetched(&self.world), &self.accounts, &args);
+        let on_chain = self.world.execute(program!(vault_bank), self.accounts, args);
+        agreed(case, preflight, on_chain)
+    }
+}
+
+/// `secure_open_vault`'s accounts for `owner`, holding `HELD` tokens: the vault, its tokens and its authority
+fn open_vault(world: &mut World, owner: Pubkey, mint: Pubkey) -> (Pubkey, Pubkey, Pubkey) {
+    let (vault, bump) =
+        Pubkey::find_program_address(&[b"secure_vault", owner.as_ref(), mint.as_ref()], &vault_bank::ID);
+    let (authority, authority_bump) =
+        Pubkey::find_program_address(&[b"vault_authority", vault.as_ref()], &vault_bank::ID);
+    let (tokens, _) = Pubkey::find_program_address(&[b"vault_tokens", vault.as_ref()], &vault_bank::ID);
+    world.add(TestAccount::anchor(&Vault { owner, mint, authority_bump, bump }).at(vault));
+    world.add(TestAccount::system(0).at(authority));
+    world.add(TestAccount::token_account(mint, authority, HELD).at(tokens));
+    (vault, tokens, authority)
+}
+
+/// One change each to Alice's withdrawal of everything in her vault
+const CORPUS: Corpus<Withdrawal> = &[
+    ("Mallory signing for Alice's vault", |w| w.accounts.owner = w.mallory),
+    ("Bob's vault tokens under Alice's vault", |w| w.accounts.vault_tokens = w.bobs_vault.1),
+    ("Bob's vault with Alice signing", |w| {
+        w.accounts.vault = w.bobs_vault.0;
+        w.accounts.vault_tokens = w.bobs_vault.1;
+    }),
+    ("the program-wide authority of the vulnerable vaults", |w| {
+        let (global, _) = Pubkey::find_program_address(&[b"authority"], &vault_bank::ID);
+        w.accounts.vault_authority = w.world.add(TestAccount::system(0).at(global));
+    }),
+    ("a vault opened by the vulnerable path", |w| {
+        let owner = w.accounts.owner;
+        let (vulnerable, bump) =
+            Pubkey::find_program_address(&[b"vault", owner.as_ref(), w.mint.as_ref()], &vault_bank::ID);
+        let vault: Vault = w.world.state(&w.accounts.vault);
+        let vault = Vault { bump, ..vault };
+        w.accounts.vault = w.world.add(TestAccount::anchor(&vault).at(vulnerable));
+    }),
+    ("a destination of another mint", |w| {
+        let other = w.world.add(TestAccount::mint(Pubkey::new_unique(), 6, 0));
+        w.accounts.destination = w.world.add(TestAccount::token_account(other, w.accounts.owner, 0));
+    }),
+    ("a frozen destination", |w| {
+        w.accounts.destination = w.world.add(TestAccount::token_account(w.mint, w.accounts.owner, 0).frozen());
+    }),
+    ("a stand-in for the Token program", |w| {
+        w.accounts.token_program = w.world.add(TestAccount::program(Pubkey::new_unique()));
+    }),
+    ("more than the vault holds", |w| w.amount = HELD + 1),
+];
+
+#[test]
+fn preflight_and_program_accept_the_legitimate_withdrawal() {
+    assert!(Withdrawal::new().run("legitimate"));
+}
+
+#[test]
+fn preflight_and_program_reject_the_malicious_corpus_alike() {
+    for (case, tamper) in CORPUS {
+        let mut withdrawal = Withdrawal::new();
+        tamper(&mut withdrawal);
+        assert!(!withdrawal.run(case), "{case}: accepted");
+    }
+}
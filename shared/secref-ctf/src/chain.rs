@@ -0,0 +1,141 @@
+//! Talking to the shared validator.
+//!
+//! [`Chain`] is everything the server needs from the cluster: plant a flag
+//! and read an account back. [`RpcChain`] does it over JSON-RPC with the
+//! organiser's keypair; tests use an in-memory stand-in.
+
+use std::path::Path;
+use std::process::Command;
+
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::account::Account;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+use crate::challenge::Challenge;
+use crate::ids::Example;
+
+pub type Result<T> = std::result::Result<T, String>;
+
+pub trait Chain: Send + Sync {
+    /// The organiser, who owns every flag until a player takes it
+    fn organiser(&self) -> Pubkey;
+
+    /// Create a fresh flag account for `challenge` and return its address
+    fn seed(&self, challenge: &Challenge, program_id: Pubkey) -> Result<Pubkey>;
+
+    /// The account at `address`, if it exists
+    fn account(&self, address: &Pubkey) -> Result<Option<Account>>;
+}
+
+pub struct RpcChain {
+    client: RpcClient,
+    organiser: Keypair,
+}
+
+impl RpcChain {
+    pub fn new(url: &str, organiser: Keypair) -> Self {
+        Self {
+            client: RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed()),
+            organiser,
+        }
+    }
+}
+
+impl Chain for RpcChain {
+    fn organiser(&self) -> Pubkey {
+        self.organiser.pubkey()
+    }
+
+    fn seed(&self, challenge: &Challenge, program_id: Pubkey) -> Result<Pubkey> {
+        // The flag keypair is dropped once the account exists: nobody can sign for it again
+        let flag = Keypair::new();
+        let ix = challenge.seed_instruction(program_id, flag.pubkey(), self.organiser.pubkey());
+        let blockhash = self.client.get_latest_blockhash().map_err(|e| e.to_string())?;
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&self.organiser.pubkey()),
+            &[&self.organiser, &flag],
+            blockhash,
+        );
+        self.client
+            .send_and_confirm_transaction(&tx)
+            .map_err(|e| format!("seeding {}: {e}", challenge.id))?;
+        Ok(flag.pubkey())
+    }
+
+    fn account(&self, address: &Pubkey) -> Result<Option<Account>> {
+        self.client
+            .get_account_with_commitment(address, CommitmentConfig::confirmed())
+            .map(|response| response.value)
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Deploy `challenge`'s program from `<root>/<lesson>/target/deploy` and return its id
+///
+/// The program must already be built with its own keypair's id
+/// (`anchor keys sync && anchor build` in the example), since several
+/// examples share the same placeholder `declare_id!`.
+pub fn deploy(root: &Path, challenge: &Challenge, url: &str, keypair: &Path) -> Result<Pubkey> {
+    let program_id = deployed_id(root, challenge)?;
+    let deploy_dir = root.join(challenge.lesson).join("target").join("deploy");
+    let program_keypair = deploy_dir.join(format!("{}-keypair.json", challenge.program));
+    let program_so = deploy_dir.join(format!("{}.so", challenge.program));
+
+    let output = Command::new("solana")
+        .args(["program", "deploy", "--url", url, "--keypair"])
+        .arg(keypair)
+        .arg("--program-id")
+        .arg(&program_keypair)
+        .arg(&program_so)
+        .output()
+        .map_err(|e| format!("running `solana program deploy`: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "deploying {}: {}",
+            challenge.program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(program_id)
+}
+
+/// The program id `challenge` was built with, without deploying it
+///
+/// Fails if the keypair's id is not the program's `declare_id!`: the
+/// program would reject every instruction once deployed.
+pub fn deployed_id(root: &Path, challenge: &Challenge) -> Result<Pubkey> {
+    let program_keypair = root
+        .join(challenge.lesson)
+        .join("target")
+        .join("deploy")
+        .join(format!("{}-keypair.json", challenge.program));
+    let id = read_keypair_file(&program_keypair)
+        .map(|keypair| keypair.pubkey())
+        .map_err(|e| format!("{}: {e}; build {} first", program_keypair.display(), challenge.lesson))?;
+
+    let example = Example::load(&root.join(challenge.lesson))?;
+    let declared = example.program(challenge.program).and_then(|program| program.built());
+    if declared != Some(id) {
+        return Err(format!(
+            "{} is built for {} but its keypair is {id}; run `anchor keys sync && anchor build` in {}",
+            challenge.program,
+            declared.map_or("no id".to_string(), |declared| declared.to_string()),
+            challenge.lesson
+        ));
+    }
+    Ok(id)
+}
+
+/// Whether each of `ids` holds a program on `url`: `None` if nothing is there
+pub fn executable(url: &str, ids: &[Pubkey]) -> Result<Vec<Option<bool>>> {
+    let client = RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed());
+    let accounts = client.get_multiple_accounts(ids).map_err(|e| e.to_string())?;
+    Ok(accounts
+        .into_iter()
+        .map(|account| account.map(|account| account.executable))
+        .collect())
+}
@@ -0,0 +1,206 @@
+//! The challenges a CTF can run, one per vulnerable example.
+//!
+//! Every challenge plants a flag account through the program's *secure*
+//! initializer, owned by the organiser, so it starts out in a state only
+//! the organiser controls. Players capture it through the *vulnerable*
+//! instructions. Whether they did is read from the account's data alone,
+//! which is the only evidence the server trusts.
+
+use solana_sdk::hash::hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_program;
+
+/// Balance recorded in every seeded vault
+pub const FLAG_BALANCE: u64 = 1_000_000;
+
+/// Anchor accounts start with an 8-byte discriminator
+const DISCRIMINATOR: usize = 8;
+
+pub struct Challenge {
+    /// Stable id used by the HTTP API, e.g. `vault-heist`
+    pub id: &'static str,
+    /// Example directory the program is built in
+    pub lesson: &'static str,
+    /// Program crate name; `target/deploy/<program>.so`
+    pub program: &'static str,
+    pub title: &'static str,
+    /// What players must do to the flag account, shown to them
+    pub objective: &'static str,
+    pub points: u32,
+    /// Accounts of the secure initializer: flag, organiser
+    seed_accounts: fn(Pubkey, Pubkey) -> Vec<AccountMeta>,
+    /// Whether the flag's data shows the exploit, given the organiser's key
+    captured: fn(&[u8], &Pubkey) -> bool,
+}
+
+impl Challenge {
+    /// `secure_initialize(FLAG_BALANCE)` creating `flag` on behalf of `organiser`
+    ///
+    /// Both keys must sign: the flag because `init` creates it, the
+    /// organiser because it pays and becomes the owner or admin.
+    pub fn seed_instruction(&self, program_id: Pubkey, flag: Pubkey, organiser: Pubkey) -> Instruction {
+        let mut data = sighash("secure_initialize").to_vec();
+        data.extend_from_slice(&FLAG_BALANCE.to_le_bytes());
+        Instruction::new_with_bytes(program_id, &data, (self.seed_accounts)(flag, organiser))
+    }
+
+    /// Whether the flag account's data shows it was captured
+    pub fn is_captured(&self, data: &[u8], organiser: &Pubkey) -> bool {
+        (self.captured)(data, organiser)
+    }
+}
+
+/// Anchor's instruction discriminator for `name`
+pub fn sighash(name: &str) -> [u8; 8] {
+    let mut discriminator = [0; 8];
+    discriminator.copy_from_slice(&hash(format!("global:{name}").as_bytes()).to_bytes()[..8]);
+    discriminator
+}
+
+fn pubkey_at(data: &[u8], offset: usize) -> Option<Pubkey> {
+    let bytes = data.get(DISCRIMINATOR + offset..DISCRIMINATOR + offset + 32)?;
+    Some(Pubkey::try_from(bytes).expect("32 bytes"))
+}
+
+fn u64_at(data: &[u8], offset: usize) -> Option<u64> {
+    let bytes = data.get(DISCRIMINATOR + offset..DISCRIMINATOR + offset + 8)?;
+    Some(u64::from_le_bytes(bytes.try_into().expect("8 bytes")))
+}
+
+pub static CHALLENGES: &[Challenge] = &[
+    Challenge {
+        id: "vault-heist",
+        lesson: "01_missing_account_validation",
+        program: "vault",
+        title: "Vault Heist",
+        objective: "Drain the organiser's vault: bring its recorded balance to 0.",
+        points: 100,
+        // SecureInitialize: vault, owner, payer, system_program
+        seed_accounts: |flag, organiser| {
+            vec![
+                AccountMeta::new(flag, true),
+                AccountMeta::new_readonly(organiser, true),
+                AccountMeta::new(organiser, true),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ]
+        },
+        // Vault { owner, balance }
+        captured: |data, _| u64_at(data, 32) == Some(0),
+    },
+    Challenge {
+        id: "admin-takeover",
+        lesson: "02_authority_check_failure",
+        program: "admin_vault",
+        title: "Admin Takeover",
+        objective: "Become the admin of the organiser's vault.",
+        points: 150,
+        // SecureInitialize: vault, admin, owner, payer, system_program
+        seed_accounts: |flag, organiser| {
+            vec![
+                AccountMeta::new(flag, true),
+                AccountMeta::new_readonly(organiser, true),
+                AccountMeta::new_readonly(organiser, true),
+                AccountMeta::new(organiser, true),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ]
+        },
+        // AdminVault { admin, owner, balance }
+        captured: |data, organiser| pubkey_at(data, 0).is_some_and(|admin| admin != *organiser),
+    },
+    Challenge {
+        id: "reinit-squatter",
+        lesson: "05_reinitialization_attack",
+        program: "reinit_vault",
+        title: "Reinit Squatter",
+        objective: "Take ownership of the organiser's already-initialized vault.",
+        points: 200,
+        // SecureInitialize: vault, owner, system_program
+        seed_accounts: |flag, organiser| {
+            vec![
+                AccountMeta::new(flag, true),
+                AccountMeta::new(organiser, true),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ]
+        },
+        // ReinitVault { owner, balance, total_deposits, is_initialized }
+        captured: |data, organiser| pubkey_at(data, 0).is_some_and(|owner| owner != *organiser),
+    },
+];
+
+/// Look up challenges by id; all of them when `ids` is empty
+pub fn select(ids: &[String]) -> Result<Vec<&'static Challenge>, String> {
+    if ids.is_empty() {
+        return Ok(CHALLENGES.iter().collect());
+    }
+    ids.iter()
+        .map(|id| {
+            CHALLENGES
+                .iter()
+                .find(|challenge| challenge.id == id)
+                .ok_or_else(|| format!("unknown challenge `{id}`"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn challenge(id: &str) -> &'static Challenge {
+        CHALLENGES.iter().find(|challenge| challenge.id == id).unwrap()
+    }
+
+    /// An Anchor account: discriminator, then the fields in order
+    fn account(fields: &[&[u8]]) -> Vec<u8> {
+        let mut data = vec![0xAA; DISCRIMINATOR];
+        fields.iter().for_each(|field| data.extend_from_slice(field));
+        data
+    }
+
+    #[test]
+    fn seeding_calls_the_secure_initializer() {
+        let (program, flag, organiser) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let ix = challenge("vault-heist").seed_instruction(program, flag, organiser);
+
+        // sha256("global:secure_initialize")[..8], as Anchor derives it
+        assert_eq!(ix.data[..8], [0xbe, 0x22, 0xad, 0x23, 0xe4, 0x09, 0x0f, 0x7c]);
+        assert_eq!(ix.data[8..], FLAG_BALANCE.to_le_bytes());
+        assert_eq!(ix.program_id, program);
+        assert!(ix.accounts[0].is_signer && ix.accounts[0].pubkey == flag);
+    }
+
+    #[test]
+    fn vault_heist_is_captured_once_the_balance_is_gone() {
+        let organiser = Pubkey::new_unique();
+        let vault = |balance: u64| account(&[organiser.as_ref(), &balance.to_le_bytes()]);
+        let heist = challenge("vault-heist");
+
+        assert!(!heist.is_captured(&vault(FLAG_BALANCE), &organiser));
+        assert!(!heist.is_captured(&vault(1), &organiser));
+        assert!(heist.is_captured(&vault(0), &organiser));
+        assert!(!heist.is_captured(&[], &organiser));
+    }
+
+    #[test]
+    fn takeovers_are_captured_once_the_organiser_is_replaced() {
+        let organiser = Pubkey::new_unique();
+        let player = Pubkey::new_unique();
+        let balance = FLAG_BALANCE.to_le_bytes();
+
+        let admin_vault = |admin: &Pubkey| account(&[admin.as_ref(), organiser.as_ref(), &balance]);
+        assert!(!challenge("admin-takeover").is_captured(&admin_vault(&organiser), &organiser));
+        assert!(challenge("admin-takeover").is_captured(&admin_vault(&player), &organiser));
+
+        let reinit_vault = |owner: &Pubkey| account(&[owner.as_ref(), &balance, &balance, &[1]]);
+        assert!(!challenge("reinit-squatter").is_captured(&reinit_vault(&organiser), &organiser));
+        assert!(challenge("reinit-squatter").is_captured(&reinit_vault(&player), &organiser));
+    }
+
+    #[test]
+    fn selection_rejects_unknown_challenges() {
+        assert_eq!(select(&[]).unwrap().len(), CHALLENGES.len());
+        assert_eq!(select(&["admin-takeover".to_string()]).unwrap()[0].id, "admin-takeover");
+        assert_eq!(select(&["nope".to_string()]).err().unwrap(), "unknown challenge `nope`");
+    }
+}
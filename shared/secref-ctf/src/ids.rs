@@ -0,0 +1,312 @@
+//! Checking that each program will run at the id it was built with.
+//!
+//! Anchor programs reject every instruction unless they run at their
+//! `declare_id!`, and clients and other programs derive PDAs and check
+//! owners against whichever id they were compiled with. An [`Example`]
+//! gathers the ids an example states in its sources, `Anchor.toml` and
+//! deploy keypairs so [`Example::problems`] can report where they disagree.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Signer};
+
+use crate::chain::Result;
+
+/// One `declare_id!` and the `#[cfg(...)]` it sits behind, if any
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Declared {
+    pub cfg: Option<String>,
+    pub id: Pubkey,
+}
+
+impl Declared {
+    /// Whether a build without extra features uses this id
+    fn is_default(&self) -> bool {
+        self.cfg.as_deref().is_none_or(|cfg| cfg.starts_with("not("))
+    }
+
+    /// The feature that selects this id, for `#[cfg(feature = "...")]`
+    fn feature(&self) -> Option<&str> {
+        let cfg = self.cfg.as_deref()?;
+        cfg.strip_prefix("feature = \"")?.strip_suffix('"')
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProgramIds {
+    pub name: String,
+    /// Every `declare_id!` in `src/lib.rs`
+    pub declared: Vec<Declared>,
+    /// Entry in `Anchor.toml` `[programs.localnet]`
+    pub anchor_toml: Option<Pubkey>,
+    /// Pubkey of `target/deploy/<name>-keypair.json`, once built
+    pub keypair: Option<Pubkey>,
+    /// Sibling program -> features this program links it with
+    pub links: BTreeMap<String, Vec<String>>,
+}
+
+impl ProgramIds {
+    /// The id a build without extra features declares
+    pub fn built(&self) -> Option<Pubkey> {
+        self.declared
+            .iter()
+            .find(|declared| declared.is_default())
+            .map(|declared| declared.id)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Example {
+    pub programs: Vec<ProgramIds>,
+}
+
+impl Example {
+    /// Read the ids of every program under `<dir>/programs`
+    pub fn load(dir: &Path) -> Result<Self> {
+        let anchor_toml = fs::read_to_string(dir.join("Anchor.toml")).unwrap_or_default();
+        let localnet = anchor_toml_ids(&anchor_toml);
+
+        let mut names: Vec<_> = fs::read_dir(dir.join("programs"))
+            .map_err(|e| format!("{}: {e}", dir.display()))?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().join("src").join("lib.rs").exists())
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+
+        let mut programs = Vec::new();
+        for name in names {
+            let crate_dir = dir.join("programs").join(&name);
+            let lib = crate_dir.join("src").join("lib.rs");
+            let source = fs::read_to_string(&lib).map_err(|e| format!("{}: {e}", lib.display()))?;
+            let manifest = fs::read_to_string(crate_dir.join("Cargo.toml")).unwrap_or_default();
+            let keypair = dir.join("target").join("deploy").join(format!("{name}-keypair.json"));
+            programs.push(ProgramIds {
+                declared: declared_ids(&source),
+                anchor_toml: localnet.get(&name).copied(),
+                keypair: read_keypair_file(&keypair).ok().map(|keypair| keypair.pubkey()),
+                links: sibling_features(&manifest),
+                name,
+            });
+        }
+        Ok(Self { programs })
+    }
+
+    pub fn program(&self, name: &str) -> Option<&ProgramIds> {
+        self.programs.iter().find(|program| program.name == name)
+    }
+
+    /// Every disagreement between the ids, as `(program, problem)`
+    pub fn problems(&self) -> Vec<(String, String)> {
+        let mut problems = Vec::new();
+        for program in &self.programs {
+            let mut report = |problem: String| problems.push((program.name.clone(), problem));
+            let Some(built) = program.built() else {
+                report("no `declare_id!` for a default build".into());
+                continue;
+            };
+            match program.anchor_toml {
+                None => report("missing from Anchor.toml [programs.localnet]".into()),
+                Some(listed) if listed != built => {
+                    report(format!("declare_id! {built} but Anchor.toml lists {listed}"))
+                }
+                Some(_) => {}
+            }
+            if let Some(keypair) = program.keypair.filter(|keypair| *keypair != built) {
+                report(format!(
+                    "declare_id! {built} but the deploy keypair is {keypair}; run `anchor keys sync` and rebuild"
+                ));
+            }
+
+            // A sibling linked with a cluster feature hands this program a different `ID`
+            for (sibling, features) in &program.links {
+                let Some(linked) = self.program(sibling) else {
+                    continue;
+                };
+                let Some(deployed) = linked.anchor_toml.or(linked.built()) else {
+                    continue;
+                };
+                for declared in &linked.declared {
+                    let selected = declared
+                        .feature()
+                        .is_some_and(|f| features.iter().any(|linked| linked == f));
+                    if selected && declared.id != deployed {
+                        report(format!(
+                            "links {sibling} with `{}`, so its ID is {} instead of {deployed}",
+                            declared.feature().unwrap_or_default(),
+                            declared.id
+                        ));
+                    }
+                }
+            }
+        }
+        problems
+    }
+}
+
+/// The `declare_id!` invocations in `source`, with the `#[cfg]` line above each
+pub fn declared_ids(source: &str) -> Vec<Declared> {
+    let mut declared = Vec::new();
+    let mut cfg = None;
+    for line in source.lines().map(str::trim) {
+        if let Some(inner) = line.strip_prefix("#[cfg(").and_then(|rest| rest.strip_suffix(")]")) {
+            cfg = Some(inner.to_string());
+            continue;
+        }
+        let id = line
+            .strip_prefix("declare_id!(\"")
+            .and_then(|rest| rest.split('"').next())
+            .and_then(|id| id.parse().ok());
+        if let Some(id) = id {
+            declared.push(Declared { cfg: cfg.clone(), id });
+        }
+        cfg = None;
+    }
+    declared
+}
+
+/// Program name -> id from the `[programs.localnet]` table of an `Anchor.toml`
+pub fn anchor_toml_ids(toml: &str) -> BTreeMap<String, Pubkey> {
+    let mut ids = BTreeMap::new();
+    let mut in_localnet = false;
+    for line in toml.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_localnet = line == "[programs.localnet]";
+            continue;
+        }
+        let Some((name, value)) = line.split_once('=').filter(|_| in_localnet) else {
+            continue;
+        };
+        if let Ok(id) = value.trim().trim_matches('"').parse() {
+            ids.insert(name.trim().to_string(), id);
+        }
+    }
+    ids
+}
+
+/// Sibling program -> features, from `name = { path = "../name", features = [...] }` dependencies
+fn sibling_features(manifest: &str) -> BTreeMap<String, Vec<String>> {
+    let mut links = BTreeMap::new();
+    for line in manifest.lines() {
+        let Some((name, spec)) = line.split_once('=') else {
+            continue;
+        };
+        if !spec.contains("path = \"../") {
+            continue;
+        }
+        let features = spec
+            .split_once("features = [")
+            .and_then(|(_, rest)| rest.split_once(']'))
+            .map(|(list, _)| {
+                list.split(',')
+                    .map(|f| f.trim().trim_matches('"').to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        links.insert(name.trim().to_string(), features);
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FEED: &str = "PriceFeed1111111111111111111111111111111111";
+    const DEVNET: &str = "DevnetFeed1111111111111111111111111111111111";
+
+    fn id(s: &str) -> Pubkey {
+        s.parse().unwrap()
+    }
+
+    fn feed() -> ProgramIds {
+        ProgramIds {
+            name: "price_feed".into(),
+            declared: declared_ids(&format!(
+                "#[cfg(feature = \"devnet\")]\ndeclare_id!(\"{DEVNET}\");\n#[cfg(not(feature = \"devnet\"))]\ndeclare_id!(\"{FEED}\");\n"
+            )),
+            anchor_toml: Some(id(FEED)),
+            ..ProgramIds::default()
+        }
+    }
+
+    #[test]
+    fn declared_ids_keep_their_cfg() {
+        let feed = feed();
+        assert_eq!(feed.declared.len(), 2);
+        assert_eq!(feed.declared[0].feature(), Some("devnet"));
+        assert_eq!(feed.built(), Some(id(FEED)));
+    }
+
+    #[test]
+    fn anchor_toml_ids_only_reads_localnet() {
+        let toml =
+            format!("[programs.devnet]\nprice_feed = \"{DEVNET}\"\n\n[programs.localnet]\nprice_feed = \"{FEED}\"\n");
+        assert_eq!(
+            anchor_toml_ids(&toml),
+            BTreeMap::from([("price_feed".to_string(), id(FEED))])
+        );
+    }
+
+    #[test]
+    fn mismatched_anchor_toml_and_keypair_are_reported() {
+        let mut feed = feed();
+        feed.anchor_toml = Some(id(DEVNET));
+        feed.keypair = Some(id(DEVNET));
+        let problems = Example { programs: vec![feed] }.problems();
+        assert_eq!(problems.len(), 2);
+        assert!(problems[1].1.contains("anchor keys sync"));
+    }
+
+    #[test]
+    fn linking_a_sibling_with_a_cluster_feature_is_reported() {
+        let market = ProgramIds {
+            name: "lending_market".into(),
+            declared: declared_ids("declare_id!(\"Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS\");"),
+            anchor_toml: Some(id("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS")),
+            links: sibling_features(
+                "price_feed = { path = \"../price_feed\", default-features = false, features = [\"no-entrypoint\", \"devnet\"] }",
+            ),
+            ..ProgramIds::default()
+        };
+        let example = Example {
+            programs: vec![market, feed()],
+        };
+        assert_eq!(
+            example.problems(),
+            [(
+                "lending_market".to_string(),
+                format!("links price_feed with `devnet`, so its ID is {DEVNET} instead of {FEED}")
+            )]
+        );
+    }
+
+    #[test]
+    fn only_the_program_id_confusion_example_disagrees_in_the_repository() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        let mut dirs: Vec<_> = fs::read_dir(&root)
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|dir| dir.join("Anchor.toml").exists())
+            .collect();
+        dirs.sort();
+        assert!(dirs.len() > 30);
+
+        for dir in dirs {
+            let mut example = Example::load(&dir).unwrap();
+            // Keypairs depend on what was built locally
+            example.programs.iter_mut().for_each(|program| program.keypair = None);
+            let problems = example.problems();
+            if dir.ends_with("32_program_id_confusion") {
+                assert_eq!(problems.len(), 1, "{problems:?}");
+                assert_eq!(problems[0].0, "lending_market");
+            } else {
+                assert!(problems.is_empty(), "{}: {problems:?}", dir.display());
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+//! Capture-the-flag server for the security reference examples.
+//!
+//! `serve` deploys the vulnerable programs of the selected challenges to a
+//! shared validator, plants a flag account per team and challenge, and
+//! scores teams over HTTP by reading their flags back from chain.
+
+mod chain;
+mod challenge;
+mod ids;
+mod scoreboard;
+mod server;
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use solana_sdk::signature::{read_keypair_file, Signer};
+
+use chain::RpcChain;
+use ids::Example;
+use scoreboard::Scoreboard;
+use server::Ctf;
+
+#[derive(Parser)]
+#[command(
+    name = "secref-ctf",
+    about = "Run the security reference examples as a capture-the-flag"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List the available challenges
+    List,
+    /// Deploy the challenges and serve the scoreboard API
+    Serve {
+        /// Validator every team plays against
+        #[arg(long, default_value = "http://127.0.0.1:8899")]
+        url: String,
+        /// Organiser keypair: pays for deploys and owns the flags
+        #[arg(long)]
+        keypair: Option<PathBuf>,
+        /// Address for the HTTP API
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        bind: String,
+        /// Challenge to run, by id; repeat for several (default: all)
+        #[arg(long = "challenge")]
+        challenges: Vec<String>,
+        /// Repository root holding the built examples
+        #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/../.."))]
+        root: PathBuf,
+        /// Save the scoreboard here and resume from it on restart
+        #[arg(long)]
+        state: Option<PathBuf>,
+        /// The programs are already deployed at their keypairs' ids
+        #[arg(long)]
+        skip_deploy: bool,
+    },
+    /// Check every program's `declare_id!` against Anchor.toml, its deploy keypair and the cluster
+    CheckIds {
+        /// Also check each program is deployed, as a program, on this cluster
+        #[arg(long)]
+        url: Option<String>,
+        /// Repository root holding the examples
+        #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/../.."))]
+        root: PathBuf,
+        /// Example directories to check, by prefix (default: all)
+        examples: Vec<String>,
+    },
+}
+
+fn example_dirs(root: &Path, selected: &[String]) -> Result<Vec<PathBuf>, String> {
+    let mut dirs: Vec<_> = std::fs::read_dir(root)
+        .map_err(|e| format!("{}: {e}", root.display()))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|dir| dir.join("Anchor.toml").exists())
+        .filter(|dir| {
+            let name = dir.file_name().unwrap_or_default().to_string_lossy();
+            selected.is_empty() || selected.iter().any(|prefix| name.starts_with(prefix.as_str()))
+        })
+        .collect();
+    dirs.sort();
+    Ok(dirs)
+}
+
+fn default_keypair() -> PathBuf {
+    let home = std::env::var_os("HOME").unwrap_or_default();
+    PathBuf::from(home).join(".config").join("solana").join("id.json")
+}
+
+fn list() {
+    for challenge in challenge::CHALLENGES {
+        println!("{:<16} {:>4} pts  {}", challenge.id, challenge.points, challenge.title);
+        println!("{:<16}           {}", "", challenge.objective);
+    }
+}
+
+/// Report every program whose ids disagree; fails if any does
+fn check_ids(url: Option<String>, root: PathBuf, selected: Vec<String>) -> Result<(), String> {
+    let mut failures = 0;
+    for dir in example_dirs(&root, &selected)? {
+        let name = dir.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        let example = Example::load(&dir)?;
+        let mut problems = example.problems();
+
+        if let Some(url) = &url {
+            let programs: Vec<_> = example
+                .programs
+                .iter()
+                .filter_map(|program| program.anchor_toml.or(program.built()).map(|id| (&program.name, id)))
+                .collect();
+            let ids: Vec<_> = programs.iter().map(|(_, id)| *id).collect();
+            for ((program, id), status) in programs.iter().zip(chain::executable(url, &ids)?) {
+                match status {
+                    None => problems.push((program.to_string(), format!("nothing deployed at {id}"))),
+                    Some(false) => problems.push((program.to_string(), format!("{id} is not a program"))),
+                    Some(true) => {}
+                }
+            }
+        }
+
+        for program in &example.programs {
+            let mine: Vec<_> = problems.iter().filter(|(name, _)| *name == program.name).collect();
+            let id = program.built().map_or("-".to_string(), |id| id.to_string());
+            println!(
+                "{} {name}/{} {id}",
+                if mine.is_empty() { "✅" } else { "❌" },
+                program.name
+            );
+            for (_, problem) in mine {
+                println!("     {problem}");
+            }
+        }
+        failures += problems.len();
+    }
+    if failures > 0 {
+        return Err(format!("{failures} program id problem(s)"));
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn serve(
+    url: String,
+    keypair: Option<PathBuf>,
+    bind: String,
+    challenges: Vec<String>,
+    root: PathBuf,
+    state: Option<PathBuf>,
+    skip_deploy: bool,
+) -> Result<(), String> {
+    let keypair = keypair.unwrap_or_else(default_keypair);
+    let organiser = read_keypair_file(&keypair).map_err(|e| format!("{}: {e}", keypair.display()))?;
+    let challenges = challenge::select(&challenges)?;
+
+    let mut deployed = Vec::new();
+    for challenge in challenges {
+        let program_id = if skip_deploy {
+            chain::deployed_id(&root, challenge)?
+        } else {
+            println!("🚀 Deploying {} ({})", challenge.program, challenge.lesson);
+            chain::deploy(&root, challenge, &url, &keypair)?
+        };
+        println!("🚩 {} -> {program_id}", challenge.id);
+        deployed.push((challenge, program_id));
+    }
+
+    let scoreboard = match &state {
+        Some(path) => Scoreboard::load(path).map_err(|e| format!("{}: {e}", path.display()))?,
+        None => Scoreboard::default(),
+    };
+    println!("🔑 Organiser {}", organiser.pubkey());
+    let ctf = Ctf::new(deployed, Box::new(RpcChain::new(&url, organiser)), scoreboard, state);
+    println!("🌐 Listening on http://{bind}");
+    ctf.serve(&bind)
+}
+
+fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Command::List => {
+            list();
+            Ok(())
+        }
+        Command::Serve {
+            url,
+            keypair,
+            bind,
+            challenges,
+            root,
+            state,
+            skip_deploy,
+        } => serve(url, keypair, bind, challenges, root, state, skip_deploy),
+        Command::CheckIds { url, root, examples } => check_ids(url, root, examples),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("❌ {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
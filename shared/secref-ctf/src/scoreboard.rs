@@ -0,0 +1,231 @@
+//! Teams, their flags and the challenges they solved.
+//!
+//! The scoreboard only records what the server observed on chain; it never
+//! decides whether an exploit happened. State is plain JSON so a restarted
+//! server can pick up where it left off.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ScoreboardError {
+    TeamExists(String),
+    UnknownTeam(String),
+    /// The token does not belong to the team
+    BadToken,
+    /// The team has no flag for the challenge
+    NoFlag(String),
+}
+
+impl fmt::Display for ScoreboardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::TeamExists(team) => write!(f, "team `{team}` is already registered"),
+            Self::UnknownTeam(team) => write!(f, "no team named `{team}`"),
+            Self::BadToken => write!(f, "wrong token for this team"),
+            Self::NoFlag(challenge) => write!(f, "no flag for challenge `{challenge}`"),
+        }
+    }
+}
+
+impl std::error::Error for ScoreboardError {}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Team {
+    pub name: String,
+    /// Shared secret proving a request comes from the team
+    token: String,
+    /// Challenge id -> address of the team's flag account
+    pub flags: BTreeMap<String, String>,
+    /// Challenge id -> unix time the capture was verified
+    pub solves: BTreeMap<String, u64>,
+}
+
+/// One row of the ranking
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Standing {
+    pub team: String,
+    pub points: u32,
+    pub solved: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Scoreboard {
+    teams: BTreeMap<String, Team>,
+}
+
+impl Scoreboard {
+    /// Load a saved scoreboard, or start an empty one if `path` does not exist
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.teams.contains_key(name)
+    }
+
+    pub fn register(
+        &mut self,
+        name: &str,
+        token: String,
+        flags: BTreeMap<String, String>,
+    ) -> Result<&Team, ScoreboardError> {
+        if self.contains(name) {
+            return Err(ScoreboardError::TeamExists(name.to_string()));
+        }
+        let team = Team {
+            name: name.to_string(),
+            token,
+            flags,
+            solves: BTreeMap::new(),
+        };
+        Ok(self.teams.entry(name.to_string()).or_insert(team))
+    }
+
+    /// The team, if `token` is theirs
+    pub fn authenticate(&self, name: &str, token: &str) -> Result<&Team, ScoreboardError> {
+        let team = self
+            .teams
+            .get(name)
+            .ok_or_else(|| ScoreboardError::UnknownTeam(name.to_string()))?;
+        if team.token != token {
+            return Err(ScoreboardError::BadToken);
+        }
+        Ok(team)
+    }
+
+    /// Record a verified capture; a repeat keeps the first solve time
+    ///
+    /// Returns whether this was a new solve.
+    pub fn solve(&mut self, name: &str, challenge: &str, at: u64) -> Result<bool, ScoreboardError> {
+        let team = self
+            .teams
+            .get_mut(name)
+            .ok_or_else(|| ScoreboardError::UnknownTeam(name.to_string()))?;
+        if !team.flags.contains_key(challenge) {
+            return Err(ScoreboardError::NoFlag(challenge.to_string()));
+        }
+        if team.solves.contains_key(challenge) {
+            return Ok(false);
+        }
+        team.solves.insert(challenge.to_string(), at);
+        Ok(true)
+    }
+
+    /// Teams by points, ties going to whoever got there first
+    pub fn ranking(&self, points: impl Fn(&str) -> u32) -> Vec<Standing> {
+        let mut rows: Vec<_> = self
+            .teams
+            .values()
+            .map(|team| {
+                let total = team.solves.keys().map(|challenge| points(challenge)).sum::<u32>();
+                let last = team.solves.values().max().copied().unwrap_or(0);
+                (total, last, team)
+            })
+            .collect();
+        rows.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.name.cmp(&b.2.name)));
+        rows.into_iter()
+            .map(|(points, _, team)| Standing {
+                team: team.name.clone(),
+                points,
+                solved: team.solves.keys().cloned().collect(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flags(challenges: &[&str]) -> BTreeMap<String, String> {
+        challenges
+            .iter()
+            .map(|challenge| (challenge.to_string(), format!("{challenge}-flag")))
+            .collect()
+    }
+
+    fn points(challenge: &str) -> u32 {
+        match challenge {
+            "easy" => 100,
+            _ => 300,
+        }
+    }
+
+    fn board() -> Scoreboard {
+        let mut board = Scoreboard::default();
+        board.register("red", "r".into(), flags(&["easy", "hard"])).unwrap();
+        board.register("blue", "b".into(), flags(&["easy", "hard"])).unwrap();
+        board
+    }
+
+    #[test]
+    fn teams_register_once_and_authenticate_with_their_token() {
+        let mut board = board();
+        assert_eq!(
+            board.register("red", "x".into(), flags(&[])).unwrap_err(),
+            ScoreboardError::TeamExists("red".into())
+        );
+        assert_eq!(board.authenticate("red", "r").unwrap().name, "red");
+        assert_eq!(board.authenticate("red", "b").unwrap_err(), ScoreboardError::BadToken);
+        assert_eq!(
+            board.authenticate("green", "g").unwrap_err(),
+            ScoreboardError::UnknownTeam("green".into())
+        );
+    }
+
+    #[test]
+    fn solves_count_once_and_need_a_flag() {
+        let mut board = board();
+        assert!(board.solve("red", "easy", 10).unwrap());
+        assert!(!board.solve("red", "easy", 20).unwrap());
+        assert_eq!(board.authenticate("red", "r").unwrap().solves["easy"], 10);
+        assert_eq!(
+            board.solve("red", "other", 30).unwrap_err(),
+            ScoreboardError::NoFlag("other".into())
+        );
+    }
+
+    #[test]
+    fn ranking_orders_by_points_then_by_who_finished_first() {
+        let mut board = board();
+        board.register("green", "g".into(), flags(&["easy"])).unwrap();
+        board.solve("red", "easy", 50).unwrap();
+        board.solve("blue", "easy", 40).unwrap();
+        board.solve("green", "easy", 60).unwrap();
+        board.solve("green", "hard", 70).unwrap_err();
+        board.solve("red", "hard", 80).unwrap();
+
+        let ranking: Vec<_> = board.ranking(points).into_iter().map(|s| (s.team, s.points)).collect();
+        assert_eq!(
+            ranking,
+            [("red".into(), 400), ("blue".into(), 100), ("green".into(), 100)]
+        );
+    }
+
+    #[test]
+    fn state_survives_a_restart() {
+        let path = std::env::temp_dir().join(format!("secref-ctf-{}.json", std::process::id()));
+        let mut board = board();
+        board.solve("blue", "hard", 5).unwrap();
+        board.save(&path).unwrap();
+
+        let restored = Scoreboard::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(restored.authenticate("blue", "b").unwrap().solves["hard"], 5);
+        assert!(Scoreboard::load(&path).unwrap().teams.is_empty());
+    }
+}
@@ -0,0 +1,376 @@
+//! The HTTP API players and the scoreboard screen talk to.
+//!
+//! | Route              | Body                              | Answer                           |
+//! |--------------------|-----------------------------------|----------------------------------|
+//! | `GET /challenges`  |                                   | challenges and their program ids |
+//! | `POST /teams`      | `{"name"}`                        | the team's token and flags       |
+//! | `POST /verify`     | `{"team", "token", "challenge"}`  | whether the flag was captured    |
+//! | `GET /scoreboard`  |                                   | the ranking                      |
+//!
+//! `/verify` is the only way to score, and it looks at nothing but the flag
+//! account on chain: it must still belong to the challenge's program and
+//! its data must show the exploit.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use tiny_http::{Header, Response, Server};
+
+use crate::chain::Chain;
+use crate::challenge::Challenge;
+use crate::scoreboard::Scoreboard;
+
+pub struct Ctf {
+    /// Running challenges and the id their program was deployed at
+    challenges: Vec<(&'static Challenge, Pubkey)>,
+    chain: Box<dyn Chain>,
+    scoreboard: Mutex<Scoreboard>,
+    /// Where the scoreboard is saved after every change, if anywhere
+    state: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+struct Registration {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Claim {
+    team: String,
+    token: String,
+    challenge: String,
+}
+
+type Reply = (u16, Value);
+
+fn error(status: u16, message: impl ToString) -> Reply {
+    (status, json!({ "error": message.to_string() }))
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+impl Ctf {
+    pub fn new(
+        challenges: Vec<(&'static Challenge, Pubkey)>,
+        chain: Box<dyn Chain>,
+        scoreboard: Scoreboard,
+        state: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            challenges,
+            chain,
+            scoreboard: Mutex::new(scoreboard),
+            state,
+        }
+    }
+
+    /// Answer one request; `body` is ignored for `GET`s
+    pub fn handle(&self, method: &str, url: &str, body: &str) -> Reply {
+        match (method, url.split('?').next().unwrap_or(url)) {
+            ("GET", "/challenges") => self.challenges(),
+            ("POST", "/teams") => match serde_json::from_str(body) {
+                Ok(registration) => self.register(registration),
+                Err(e) => error(400, e),
+            },
+            ("POST", "/verify") => match serde_json::from_str(body) {
+                Ok(claim) => self.verify(claim),
+                Err(e) => error(400, e),
+            },
+            ("GET", "/scoreboard") => self.ranking(),
+            _ => error(404, format!("no route {method} {url}")),
+        }
+    }
+
+    fn challenge(&self, id: &str) -> Option<(&'static Challenge, Pubkey)> {
+        self.challenges
+            .iter()
+            .copied()
+            .find(|(challenge, _)| challenge.id == id)
+    }
+
+    fn challenges(&self) -> Reply {
+        let challenges: Vec<_> = self
+            .challenges
+            .iter()
+            .map(|(challenge, program_id)| {
+                json!({
+                    "id": challenge.id,
+                    "title": challenge.title,
+                    "objective": challenge.objective,
+                    "points": challenge.points,
+                    "lesson": challenge.lesson,
+                    "program_id": program_id.to_string(),
+                })
+            })
+            .collect();
+        (200, json!({ "challenges": challenges }))
+    }
+
+    /// Plant one flag per challenge for a new team
+    fn register(&self, registration: Registration) -> Reply {
+        let name = registration.name.trim();
+        if name.is_empty() {
+            return error(400, "team name must not be empty");
+        }
+        if self.scoreboard.lock().unwrap().contains(name) {
+            return error(409, format!("team `{name}` is already registered"));
+        }
+
+        // Seeding takes a few slots per flag; don't hold the scoreboard meanwhile
+        let mut flags = BTreeMap::new();
+        for (challenge, program_id) in &self.challenges {
+            match self.chain.seed(challenge, *program_id) {
+                Ok(flag) => flags.insert(challenge.id.to_string(), flag.to_string()),
+                Err(e) => return error(502, e),
+            };
+        }
+
+        // Random, unlike `Pubkey::new_unique`, which counts up
+        let token = Keypair::new().pubkey().to_string();
+        let mut scoreboard = self.scoreboard.lock().unwrap();
+        let reply = match scoreboard.register(name, token.clone(), flags) {
+            Ok(team) => (201, json!({ "team": team.name, "token": token, "flags": team.flags })),
+            Err(e) => return error(409, e),
+        };
+        self.save(&scoreboard);
+        reply
+    }
+
+    fn verify(&self, claim: Claim) -> Reply {
+        let Some((challenge, program_id)) = self.challenge(&claim.challenge) else {
+            return error(404, format!("unknown challenge `{}`", claim.challenge));
+        };
+        let flag = match self.scoreboard.lock().unwrap().authenticate(&claim.team, &claim.token) {
+            Ok(team) => team.flags.get(challenge.id).cloned(),
+            Err(e) => return error(403, e),
+        };
+        let Some(flag) = flag.and_then(|flag| flag.parse::<Pubkey>().ok()) else {
+            return error(404, format!("no flag for challenge `{}`", challenge.id));
+        };
+
+        let captured = match self.chain.account(&flag) {
+            Ok(Some(account)) if account.owner == program_id => {
+                challenge.is_captured(&account.data, &self.chain.organiser())
+            }
+            // Closed, or reassigned to another program: not the state the challenge asks for
+            Ok(_) => false,
+            Err(e) => return error(502, e),
+        };
+        if !captured {
+            return (200, json!({ "challenge": challenge.id, "captured": false }));
+        }
+
+        let mut scoreboard = self.scoreboard.lock().unwrap();
+        let first = match scoreboard.solve(&claim.team, challenge.id, now()) {
+            Ok(first) => first,
+            Err(e) => return error(404, e),
+        };
+        if first {
+            self.save(&scoreboard);
+        }
+        (
+            200,
+            json!({ "challenge": challenge.id, "captured": true, "points": challenge.points, "first": first }),
+        )
+    }
+
+    fn ranking(&self) -> Reply {
+        let points = |id: &str| self.challenge(id).map_or(0, |(challenge, _)| challenge.points);
+        (
+            200,
+            json!({ "ranking": self.scoreboard.lock().unwrap().ranking(points) }),
+        )
+    }
+
+    fn save(&self, scoreboard: &Scoreboard) {
+        if let Some(path) = &self.state {
+            if let Err(e) = scoreboard.save(path) {
+                eprintln!("⚠️  could not save the scoreboard to {}: {e}", path.display());
+            }
+        }
+    }
+
+    /// Serve requests on `bind` until the process is stopped
+    pub fn serve(&self, bind: &str) -> Result<(), String> {
+        let server = Server::http(bind).map_err(|e| format!("binding {bind}: {e}"))?;
+        let content_type = Header::from_bytes("Content-Type", "application/json").expect("static header");
+        for mut request in server.incoming_requests() {
+            let mut body = String::new();
+            let (status, reply) = match request.as_reader().read_to_string(&mut body) {
+                Ok(_) => self.handle(request.method().as_str(), request.url(), &body),
+                Err(e) => error(400, e),
+            };
+            let response = Response::from_string(reply.to_string())
+                .with_status_code(status)
+                .with_header(content_type.clone());
+            if let Err(e) = request.respond(response) {
+                eprintln!("⚠️  {e}");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenge::{CHALLENGES, FLAG_BALANCE};
+    use solana_sdk::account::Account;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    /// Flags live in memory; tests play the attacker by editing them
+    #[derive(Default)]
+    struct FakeChain {
+        organiser: Pubkey,
+        accounts: Arc<Mutex<HashMap<Pubkey, Account>>>,
+    }
+
+    impl Chain for FakeChain {
+        fn organiser(&self) -> Pubkey {
+            self.organiser
+        }
+
+        fn seed(&self, _: &Challenge, program_id: Pubkey) -> crate::chain::Result<Pubkey> {
+            // Vault { owner: organiser, balance }, the layout every challenge starts from
+            let mut data = vec![0; 8];
+            data.extend_from_slice(self.organiser.as_ref());
+            data.extend_from_slice(&FLAG_BALANCE.to_le_bytes());
+            let flag = Pubkey::new_unique();
+            let account = Account {
+                data,
+                owner: program_id,
+                ..Account::default()
+            };
+            self.accounts.lock().unwrap().insert(flag, account);
+            Ok(flag)
+        }
+
+        fn account(&self, address: &Pubkey) -> crate::chain::Result<Option<Account>> {
+            Ok(self.accounts.lock().unwrap().get(address).cloned())
+        }
+    }
+
+    struct Game {
+        ctf: Ctf,
+        /// Shared with `ctf`, for tampering with flags
+        accounts: Arc<Mutex<HashMap<Pubkey, Account>>>,
+    }
+
+    impl Game {
+        fn new() -> Self {
+            let chain = Box::new(FakeChain {
+                organiser: Pubkey::new_unique(),
+                ..FakeChain::default()
+            });
+            let accounts = chain.accounts.clone();
+            let challenges = CHALLENGES[..1]
+                .iter()
+                .map(|challenge| (challenge, Pubkey::new_unique()))
+                .collect();
+            Self {
+                ctf: Ctf::new(challenges, chain, Scoreboard::default(), None),
+                accounts,
+            }
+        }
+
+        fn post(&self, url: &str, body: Value) -> Reply {
+            self.ctf.handle("POST", url, &body.to_string())
+        }
+
+        /// Register `name` and return its token and vault-heist flag
+        fn team(&self, name: &str) -> (String, Pubkey) {
+            let (status, reply) = self.post("/teams", json!({ "name": name }));
+            assert_eq!(status, 201, "{reply}");
+            let flag = reply["flags"]["vault-heist"].as_str().unwrap().parse().unwrap();
+            (reply["token"].as_str().unwrap().to_string(), flag)
+        }
+
+        fn tamper(&self, flag: &Pubkey, edit: impl FnOnce(&mut Account)) {
+            edit(self.accounts.lock().unwrap().get_mut(flag).unwrap());
+        }
+
+        fn verify(&self, team: &str, token: &str) -> Reply {
+            self.post(
+                "/verify",
+                json!({ "team": team, "token": token, "challenge": "vault-heist" }),
+            )
+        }
+    }
+
+    fn drain(account: &mut Account) {
+        account.data[40..48].copy_from_slice(&0u64.to_le_bytes());
+    }
+
+    #[test]
+    fn untouched_flags_are_not_captured() {
+        let game = Game::new();
+        let (token, _) = game.team("red");
+        assert_eq!(
+            game.verify("red", &token),
+            (200, json!({ "challenge": "vault-heist", "captured": false }))
+        );
+    }
+
+    #[test]
+    fn exploited_flags_score_once() {
+        let game = Game::new();
+        let (token, flag) = game.team("red");
+        game.tamper(&flag, drain);
+
+        let (status, reply) = game.verify("red", &token);
+        assert_eq!(
+            (status, &reply["captured"], &reply["first"]),
+            (200, &json!(true), &json!(true))
+        );
+        assert_eq!(game.verify("red", &token).1["first"], json!(false));
+
+        let (_, scoreboard) = game.ctf.handle("GET", "/scoreboard", "");
+        assert_eq!(scoreboard["ranking"][0]["points"], json!(100));
+    }
+
+    #[test]
+    fn only_the_teams_own_flag_counts() {
+        let game = Game::new();
+        let (red, red_flag) = game.team("red");
+        let (blue, _) = game.team("blue");
+        game.tamper(&red_flag, drain);
+
+        assert_eq!(game.verify("blue", &blue).1["captured"], json!(false));
+        assert_eq!(game.verify("blue", &red).0, 403);
+    }
+
+    #[test]
+    fn flags_moved_to_another_program_do_not_count() {
+        let game = Game::new();
+        let (token, flag) = game.team("red");
+        game.tamper(&flag, |account| {
+            drain(account);
+            account.owner = Pubkey::new_unique();
+        });
+        assert_eq!(game.verify("red", &token).1["captured"], json!(false));
+    }
+
+    #[test]
+    fn bad_requests_are_rejected() {
+        let game = Game::new();
+        game.team("red");
+        assert_eq!(game.post("/teams", json!({ "name": "red" })).0, 409);
+        assert_eq!(game.post("/teams", json!({ "name": " " })).0, 400);
+        assert_eq!(game.ctf.handle("POST", "/verify", "not json").0, 400);
+        assert_eq!(game.ctf.handle("GET", "/flags", "").0, 404);
+        assert_eq!(
+            game.post("/verify", json!({ "team": "red", "token": "", "challenge": "nope" }))
+                .0,
+            404
+        );
+    }
+}
@@ -0,0 +1,784 @@
+//! The lesson table, in teaching order.
+//!
+//! One entry per example directory. Prerequisites name the lessons whose
+//! ideas an example builds on; they always point backwards in this list.
+
+use crate::{EntryPoint, Lesson};
+
+const fn entry(program: &'static str, instruction: &'static str) -> EntryPoint {
+    EntryPoint { program, instruction }
+}
+
+pub static LESSONS: &[Lesson] = &[
+    Lesson {
+        id: "01_missing_account_validation",
+        title: "Missing Account Validation",
+        prerequisites: &[],
+        objectives: &[
+            "Explain why an account's type says nothing about who it belongs to",
+            "Tie accounts together with `has_one` and `constraint`",
+        ],
+        entry_points: &[
+            entry("vault", "vulnerable_deposit"),
+            entry("vault", "vulnerable_withdraw"),
+        ],
+    },
+    Lesson {
+        id: "02_authority_check_failure",
+        title: "Authority Check Failure",
+        prerequisites: &["01_missing_account_validation"],
+        objectives: &[
+            "Tell a signature check apart from an authority check",
+            "Bind privileged instructions to the stored admin key",
+            "Bind multisig approvals to the proposal they approve",
+        ],
+        entry_points: &[
+            entry("admin_vault", "vulnerable_admin_withdraw"),
+            entry("admin_vault", "vulnerable_emergency_drain"),
+            entry("admin_vault", "vulnerable_change_admin"),
+            entry("admin_vault", "vulnerable_execute_withdraw"),
+        ],
+    },
+    Lesson {
+        id: "03_unsafe_cpi",
+        title: "Unsafe Cross-Program Invocation",
+        prerequisites: &["02_authority_check_failure"],
+        objectives: &[
+            "Recognise a CPI target the caller can substitute",
+            "Pin invoked programs with `Program<'info, T>` or an address check",
+        ],
+        entry_points: &[
+            entry("unsafe_cpi", "vulnerable_transfer"),
+            entry("unsafe_cpi", "vulnerable_withdraw"),
+        ],
+    },
+    Lesson {
+        id: "04_arithmetic_overflow",
+        title: "Arithmetic Overflow/Underflow",
+        prerequisites: &["01_missing_account_validation"],
+        objectives: &[
+            "Explain why release builds wrap instead of panicking",
+            "Use `checked_*` arithmetic and surface overflow as an error",
+        ],
+        entry_points: &[
+            entry("arithmetic_vault", "vulnerable_deposit"),
+            entry("arithmetic_vault", "vulnerable_withdraw"),
+            entry("arithmetic_vault", "vulnerable_apply_interest"),
+            entry("arithmetic_vault", "vulnerable_batch_deposit"),
+        ],
+    },
+    Lesson {
+        id: "05_reinitialization_attack",
+        title: "Reinitialization Attack",
+        prerequisites: &["01_missing_account_validation"],
+        objectives: &[
+            "Show how a second initialize call resets ownership",
+            "Rely on `init` instead of a hand-rolled initialized flag",
+        ],
+        entry_points: &[
+            entry("reinit_vault", "vulnerable_initialize"),
+            entry("reinit_vault", "vulnerable_manual_init"),
+        ],
+    },
+    Lesson {
+        id: "06_insecure_deserialization",
+        title: "Insecure Deserialization",
+        prerequisites: &["04_arithmetic_overflow"],
+        objectives: &[
+            "Treat every length prefix in instruction data as attacker-chosen",
+            "Bound allocations before decoding variable-length data",
+        ],
+        entry_points: &[entry("airdrop_list", "vulnerable_set_recipients")],
+    },
+    Lesson {
+        id: "07_seed_length_overflow",
+        title: "String and Seed Length Overflow",
+        prerequisites: &["06_insecure_deserialization"],
+        objectives: &[
+            "Explain how truncating strings to fit makes distinct inputs collide",
+            "Reject oversized seeds and fields instead of trimming them",
+        ],
+        entry_points: &[
+            entry("profile_registry", "vulnerable_register"),
+            entry("profile_registry", "vulnerable_update_bio"),
+        ],
+    },
+    Lesson {
+        id: "08_unchecked_close",
+        title: "Unchecked Close Destination",
+        prerequisites: &["05_reinitialization_attack"],
+        objectives: &[
+            "Treat `close = target` as a lamport transfer",
+            "Constrain who may close an account and where its rent goes",
+        ],
+        entry_points: &[
+            entry("position_manager", "vulnerable_close_position"),
+            entry("position_manager", "vulnerable_close_position_to_owner"),
+        ],
+    },
+    Lesson {
+        id: "09_account_resurrection",
+        title: "Closed Account Resurrection",
+        prerequisites: &["08_unchecked_close"],
+        objectives: &[
+            "Explain when the runtime actually deletes a drained account",
+            "Close accounts so they cannot be revived in the same transaction",
+        ],
+        entry_points: &[entry("voucher_redeemer", "vulnerable_redeem")],
+    },
+    Lesson {
+        id: "10_cross_instance_replay",
+        title: "Cross-Instance Signature Replay",
+        prerequisites: &["02_authority_check_failure"],
+        objectives: &[
+            "Read Ed25519 verification results through instruction introspection",
+            "Bind signed messages to a program, an instance and a nonce",
+        ],
+        entry_points: &[entry("signed_withdrawals", "vulnerable_withdraw")],
+    },
+    Lesson {
+        id: "11_instruction_data_confusion",
+        title: "Instruction Data Confusion",
+        prerequisites: &["06_insecure_deserialization"],
+        objectives: &[
+            "Treat every instruction byte as attacker input, including ones a router ignores",
+            "Give each instruction its own discriminator and argument struct",
+        ],
+        entry_points: &[entry("quote_router", "vulnerable_dispatch_swap")],
+    },
+    Lesson {
+        id: "12_rent_topup_griefing",
+        title: "Rent Top-Up Griefing",
+        prerequisites: &["08_unchecked_close"],
+        objectives: &[
+            "Explain why sponsored rent follows the account, not the payer",
+            "Validate, cap, record and refund sponsored top-ups",
+        ],
+        entry_points: &[entry("storage_sponsor", "vulnerable_top_up")],
+    },
+    Lesson {
+        id: "13_slot_gambling",
+        title: "Slot-Based Randomness",
+        prerequisites: &["01_missing_account_validation"],
+        objectives: &[
+            "Explain why slots and blockhashes are not secret",
+            "Separate commitment from reveal when outcomes carry value",
+        ],
+        entry_points: &[entry("coin_flip", "vulnerable_flip")],
+    },
+    Lesson {
+        id: "14_simulation_divergence",
+        title: "Simulation Divergence",
+        prerequisites: &["02_authority_check_failure"],
+        objectives: &[
+            "Explain why a simulation is a preview, not a promise",
+            "Put the terms the user saw into the bytes they sign",
+        ],
+        entry_points: &[entry("payment_gateway", "vulnerable_pay")],
+    },
+    Lesson {
+        id: "15_missing_executable_check",
+        title: "Missing Executable Check",
+        prerequisites: &["03_unsafe_cpi"],
+        objectives: &[
+            "Tell an address apart from a deployed, immutable program",
+            "Check executability, loader and upgrade authority before trusting code",
+        ],
+        entry_points: &[entry("plugin_registry", "vulnerable_register_plugin")],
+    },
+    Lesson {
+        id: "16_duplicate_account_roles",
+        title: "Duplicate Account Roles",
+        prerequisites: &["01_missing_account_validation"],
+        objectives: &[
+            "Explain why two account slots may hold the same key",
+            "Require distinct keys wherever roles must differ",
+        ],
+        entry_points: &[entry("credit_ledger", "vulnerable_transfer")],
+    },
+    Lesson {
+        id: "17_god_pda_authority",
+        title: "God PDA Authority",
+        prerequisites: &["03_unsafe_cpi", "16_duplicate_account_roles"],
+        objectives: &[
+            "Show how one program-wide signer lets any instruction move any vault",
+            "Scope PDA authorities to the vault they control",
+            "Hold back sweeps to new destinations behind an allowlist delay",
+        ],
+        entry_points: &[
+            entry("vault_bank", "vulnerable_open_vault"),
+            entry("vault_bank", "vulnerable_withdraw"),
+            entry("vault_bank", "vulnerable_sweep"),
+        ],
+    },
+    Lesson {
+        id: "18_token_balance_voting",
+        title: "Token Balance Voting Power",
+        prerequisites: &["03_unsafe_cpi"],
+        objectives: &[
+            "Explain why a balance read at vote time can be flash-borrowed",
+            "Derive voting power from tokens locked before the vote",
+        ],
+        entry_points: &[entry("token_voting", "vulnerable_cast_vote")],
+    },
+    Lesson {
+        id: "19_lp_token_accounting",
+        title: "LP Token Accounting",
+        prerequisites: &["04_arithmetic_overflow", "03_unsafe_cpi"],
+        objectives: &[
+            "Mint LP tokens for what arrived, never for what the caller claims",
+            "Compute shares in u128 and round toward the pool",
+        ],
+        entry_points: &[
+            entry("lp_pool", "vulnerable_add_liquidity"),
+            entry("lp_pool", "vulnerable_remove_liquidity"),
+        ],
+    },
+    Lesson {
+        id: "20_lending_toy",
+        title: "Lending Toy",
+        prerequisites: &["04_arithmetic_overflow"],
+        objectives: &[
+            "Test rate curves at their endpoints, where they break",
+            "Check collateral health on the result of a change, not before it",
+            "Pay liquidation bounties out of the collateral a position holds, never beyond it",
+            "Write bad debt off against every deposit pro rata, not against the last lender out",
+        ],
+        entry_points: &[
+            entry("lending_toy", "vulnerable_accrue_interest"),
+            entry("lending_toy", "vulnerable_withdraw_collateral"),
+            entry("lending_toy", "vulnerable_liquidate"),
+            entry("lending_toy", "vulnerable_write_off_bad_debt"),
+        ],
+    },
+    Lesson {
+        id: "21_keeper_crank_incentives",
+        title: "Keeper Crank Incentives",
+        prerequisites: &["20_lending_toy"],
+        objectives: &[
+            "Treat every argument to a permissionless crank as attacker-controlled",
+            "Pay crank rewards only for work the program can verify",
+            "Check that state has expired before a permissionless instruction reclaims its rent",
+        ],
+        entry_points: &[
+            entry("order_expiry", "vulnerable_expire_order"),
+            entry("order_expiry", "vulnerable_reclaim_order"),
+        ],
+    },
+    Lesson {
+        id: "22_fee_recipient_ownership",
+        title: "Fee Recipient Ownership",
+        prerequisites: &["03_unsafe_cpi"],
+        objectives: &[
+            "Tell a token account's mint apart from its owner",
+            "Pin fee destinations to the configured recipient",
+        ],
+        entry_points: &[entry("payment_processor", "vulnerable_pay")],
+    },
+    Lesson {
+        id: "23_remaining_accounts_router",
+        title: "Remaining Accounts Swap Router",
+        prerequisites: &["03_unsafe_cpi", "16_duplicate_account_roles"],
+        objectives: &[
+            "List the checks Anchor skips for `remaining_accounts`",
+            "Validate owner, type and registration of every extra account",
+        ],
+        entry_points: &[entry("swap_router", "vulnerable_swap_route")],
+    },
+    Lesson {
+        id: "24_two_phase_commit",
+        title: "Two-Phase Commit",
+        prerequisites: &["02_authority_check_failure"],
+        objectives: &[
+            "Bind the execute step to the exact parameters that were prepared",
+            "Reuse `secref_guards::intent::Intent` instead of re-implementing the checks",
+        ],
+        entry_points: &[entry("timelock_treasury", "vulnerable_execute_withdrawal")],
+    },
+    Lesson {
+        id: "25_account_prefunding",
+        title: "Account Pre-Funding",
+        prerequisites: &["05_reinitialization_attack"],
+        objectives: &[
+            "Explain why `create_account` fails on an address holding lamports",
+            "Create PDAs with transfer, allocate and assign when they are pre-funded",
+        ],
+        entry_points: &[entry("vault_factory", "vulnerable_create_vault")],
+    },
+    Lesson {
+        id: "26_hot_account_contention",
+        title: "Hot Account Contention",
+        prerequisites: &["01_missing_account_validation"],
+        objectives: &[
+            "Relate write locks to how many transactions can run in parallel",
+            "Shard global state so users do not contend for one account",
+        ],
+        entry_points: &[entry("points_ledger", "vulnerable_record_points")],
+    },
+    Lesson {
+        id: "27_event_authenticity",
+        title: "Event Authenticity",
+        prerequisites: &["03_unsafe_cpi"],
+        objectives: &[
+            "Explain why `msg!` and `emit!` output can be forged by any program",
+            "Emit events through `emit_cpi!` and verify the event authority off-chain",
+        ],
+        entry_points: &[entry("rewards_distributor", "vulnerable_grant_reward")],
+    },
+    Lesson {
+        id: "28_zero_copy_loader_misuse",
+        title: "Zero-Copy Loader Misuse",
+        prerequisites: &["05_reinitialization_attack"],
+        objectives: &[
+            "Explain what the discriminator guarantees for zero-copy accounts",
+            "Pick `load_init`, `load` or `load_mut` for the account's lifecycle stage",
+        ],
+        entry_points: &[
+            entry("price_history", "vulnerable_init_skip_discriminator"),
+            entry("price_history", "vulnerable_init_no_zero_check"),
+        ],
+    },
+    Lesson {
+        id: "29_stack_frame_limits",
+        title: "Stack Frame Limits",
+        prerequisites: &["28_zero_copy_loader_misuse"],
+        objectives: &[
+            "Read `anchor build` stack warnings as runtime failures",
+            "Move large accounts to the heap or to zero-copy",
+        ],
+        entry_points: &[entry("basket_rebalancer", "vulnerable_rebalance")],
+    },
+    Lesson {
+        id: "30_cpi_return_data",
+        title: "CPI Return Data Validation",
+        prerequisites: &["15_missing_executable_check"],
+        objectives: &[
+            "Check which program set the return data a CPI left behind",
+            "Reject return data of the wrong length or origin",
+        ],
+        entry_points: &[entry("loan_desk", "vulnerable_price_position")],
+    },
+    Lesson {
+        id: "31_invariant_watchdog",
+        title: "Invariant Watchdog Assertions",
+        prerequisites: &["20_lending_toy"],
+        objectives: &[
+            "Check protocol invariants at the end of a transaction instead of per input",
+            "Enforce assertion instructions with introspection so they cannot be skipped",
+        ],
+        entry_points: &[
+            entry("watched_vault", "vulnerable_deposit"),
+            entry("watched_vault", "vulnerable_withdraw"),
+        ],
+    },
+    Lesson {
+        id: "32_program_id_confusion",
+        title: "Program ID Confusion",
+        prerequisites: &["01_missing_account_validation", "15_missing_executable_check"],
+        objectives: &[
+            "Explain why a dependency's `ID` is where it was built for, not where it runs",
+            "Record dependency program ids from their executable accounts at setup",
+            "Check `declare_id!`, `Anchor.toml` and deploy keypairs agree before deploying",
+        ],
+        entry_points: &[entry("lending_market", "vulnerable_price_position")],
+    },
+    Lesson {
+        id: "33_nft_rental_utility",
+        title: "NFT Rental Utility (Owner vs Holder)",
+        prerequisites: &["18_token_balance_voting"],
+        objectives: &[
+            "Explain why holding an NFT for one instruction proves nothing about entitlement",
+            "Delegate NFT utility with time-bound rental records instead of transfers",
+            "Issue non-transferable Token-2022 receipts as proof of a rental",
+        ],
+        entry_points: &[entry("nft_rental", "vulnerable_claim_perk")],
+    },
+    Lesson {
+        id: "34_royalty_bypass",
+        title: "NFT Royalty Bypass",
+        prerequisites: &["23_remaining_accounts_router"],
+        objectives: &[
+            "Read royalty rates and recipients from the NFT's metadata PDA",
+            "Check every royalty recipient in `remaining_accounts` against the metadata's creators",
+        ],
+        entry_points: &[entry("nft_marketplace", "vulnerable_buy")],
+    },
+    Lesson {
+        id: "35_bonding_curve_pricing",
+        title: "Bonding Curve Price Overflow",
+        prerequisites: &["04_arithmetic_overflow", "20_lending_toy"],
+        objectives: &[
+            "Explain why a curve's cost integral overflows long before its prices do",
+            "Integrate piecewise curves in u128 and reject costs that leave u64",
+            "Enforce a supply cap before pricing instead of trusting the price to stop buyers",
+        ],
+        entry_points: &[entry("bonding_curve_sale", "vulnerable_buy")],
+    },
+    Lesson {
+        id: "36_referral_self_dealing",
+        title: "Referral Self-Dealing",
+        prerequisites: &["16_duplicate_account_roles", "23_remaining_accounts_router"],
+        objectives: &[
+            "Explain how self-referral, repeat registration and referral loops turn bonuses into minted value",
+            "Keep the referral graph a tree with one member PDA per wallet and existing, distinct referrers",
+            "Bound bonuses by new deposits and a campaign budget",
+        ],
+        entry_points: &[entry("referral_rewards", "vulnerable_deposit")],
+    },
+    Lesson {
+        id: "37_payment_splitter_rounding",
+        title: "Payment Splitter Rounding and Ordering",
+        prerequisites: &["19_lp_token_accounting", "23_remaining_accounts_router"],
+        objectives: &[
+            "Explain why shares recomputed from a balance the loop is paying out depend on list order",
+            "Compute every share from one snapshot and assign the rounding remainder deterministically",
+            "Bound per-instruction loops so a distribution always fits in one transaction",
+        ],
+        entry_points: &[
+            entry("payment_splitter", "vulnerable_create_splitter"),
+            entry("payment_splitter", "vulnerable_distribute"),
+        ],
+    },
+    Lesson {
+        id: "38_subscription_authority",
+        title: "Subscription Pull Authority",
+        prerequisites: &["02_authority_check_failure", "22_fee_recipient_ownership"],
+        objectives: &[
+            "Explain why a token delegation for recurring payments must be sized to the subscription's terms",
+            "Enforce the amount, interval and expiry from stored terms on every pull",
+            "Check the token account's single delegate before replacing or relying on it",
+        ],
+        entry_points: &[
+            entry("subscriptions", "vulnerable_subscribe"),
+            entry("subscriptions", "vulnerable_pull"),
+        ],
+    },
+    Lesson {
+        id: "39_bridge_message_verification",
+        title: "Bridge Message Verification",
+        prerequisites: &["05_reinitialization_attack", "10_cross_instance_replay"],
+        objectives: &[
+            "Explain why a relayer's signature says nothing about a cross-chain message's origin",
+            "Count distinct guardian signatures over domain-separated bytes through Ed25519 introspection",
+            "Consume every executed message with a nullifier PDA",
+        ],
+        entry_points: &[entry("bridge_inbox", "vulnerable_receive")],
+    },
+    Lesson {
+        id: "40_wallet_drainer_anatomy",
+        title: "Wallet Drainer Anatomy",
+        prerequisites: &["03_unsafe_cpi", "38_subscription_authority"],
+        objectives: &[
+            "Explain why a user's signature lends a program their authority for every CPI in the instruction",
+            "Sign token CPIs only with the program's own PDAs and keep unneeded accounts out of contexts",
+            "Check a user's token account authorities are unchanged after the instruction's CPIs",
+        ],
+        entry_points: &[
+            entry("airdrop_claim", "vulnerable_claim_with_approve"),
+            entry("airdrop_claim", "vulnerable_claim_with_owner_change"),
+        ],
+    },
+    Lesson {
+        id: "41_ata_precreation_squatting",
+        title: "ATA Pre-creation Squatting",
+        prerequisites: &["05_reinitialization_attack", "25_account_prefunding"],
+        objectives: &[
+            "Explain why anyone can create a PDA's associated token account before the program does",
+            "Create associated token accounts with CreateIdempotent instead of `init`",
+            "Validate an existing token account's frozen state, delegate and close authority before depositing",
+        ],
+        entry_points: &[
+            entry("ata_escrow", "vulnerable_open_escrow"),
+            entry("ata_escrow", "vulnerable_open_escrow_if_needed"),
+        ],
+    },
+    Lesson {
+        id: "42_compliance_freeze",
+        title: "Compliance Freeze and Seize",
+        prerequisites: &["02_authority_check_failure", "24_two_phase_commit"],
+        objectives: &[
+            "Explain why a role that can freeze positions must not also be able to seize them",
+            "Keep admin, freezer and seizer distinct on every role assignment",
+            "Seize only frozen positions, through a timelocked intent that thawing cancels",
+        ],
+        entry_points: &[entry("compliance_vault", "vulnerable_seize")],
+    },
+    Lesson {
+        id: "43_deposit_memo_binding",
+        title: "Deposit Memo Binding",
+        prerequisites: &["11_instruction_data_confusion", "27_event_authenticity"],
+        objectives: &[
+            "Explain why a memo or reference in a transaction is not bound to the transfer beside it",
+            "Record each deposit's reference and amount in a receipt written by the deposit itself",
+            "Verify a receipt's owner, derived address and discriminator before crediting it off-chain",
+        ],
+        entry_points: &[entry("deposit_receipts", "vulnerable_deposit")],
+    },
+    Lesson {
+        id: "44_concurrent_merkle_tree",
+        title: "Concurrent Merkle Tree Proofs",
+        prerequisites: &["39_bridge_message_verification"],
+        objectives: &[
+            "Explain why a proof against a recent root does not show what a compressed tree holds now",
+            "Bound Merkle proofs to exactly the tree's depth and indices inside the tree",
+            "Spend compressed state by replacing its leaf rather than recording it elsewhere",
+        ],
+        entry_points: &[entry("voucher_tree", "vulnerable_redeem")],
+    },
+    Lesson {
+        id: "45_durable_nonce_replay",
+        title: "Durable Nonce Replay",
+        prerequisites: &["10_cross_instance_replay"],
+        objectives: &[
+            "Explain why durable nonces and transaction deduplication give an off-chain-signed payload no replay protection",
+            "Consume a signed nonce on-chain in a PDA and check a signed expiry against the Clock",
+            "Let signers cancel an authorization that is already in a relayer's hands",
+        ],
+        entry_points: &[entry("relayed_payments", "vulnerable_relay_payment")],
+    },
+    Lesson {
+        id: "46_balance_as_authorization",
+        title: "Balance as Authorization",
+        prerequisites: &["18_token_balance_voting"],
+        objectives: &[
+            "Explain why a lamport or token balance can be borrowed for exactly one instruction",
+            "Qualify members on a stake held by a program-owned PDA instead of a balance",
+            "Lock stakes for longer than a transaction so qualification costs real capital per identity",
+        ],
+        entry_points: &[entry("premium_club", "vulnerable_claim_bonus")],
+    },
+    Lesson {
+        id: "47_kill_switch_recovery",
+        title: "Kill Switch and Staged Recovery",
+        prerequisites: &["24_two_phase_commit", "42_compliance_freeze"],
+        objectives: &[
+            "Explain why a halt that blocks withdrawals lets its key holders hold users' funds hostage",
+            "Require a quorum of distinct guardian signers to halt, and bound how long a halt lasts",
+            "Stage recovery through withdraw-only mode and a timelocked resumption",
+        ],
+        entry_points: &[entry("guarded_pool", "vulnerable_halt")],
+    },
+    Lesson {
+        id: "48_vault_interface",
+        title: "Instruction Interfaces and Dispatch",
+        prerequisites: &["30_cpi_return_data", "32_program_id_confusion"],
+        objectives: &[
+            "Define an instruction interface several programs implement, and call it without depending on any of them",
+            "Explain why conforming to an interface is not grounds for trust when a PDA signs into the call",
+            "Dispatch only to approved implementations and check balances and return data after each call",
+        ],
+        entry_points: &[entry("vault_router", "vulnerable_allocate")],
+    },
+    Lesson {
+        id: "49_stake_authority_confusion",
+        title: "Stake Account Authorities",
+        prerequisites: &["03_unsafe_cpi", "41_ata_precreation_squatting"],
+        objectives: &[
+            "Distinguish a stake account's staker from its withdrawer, and say which one owns the lamports",
+            "Take custody of a deposited stake account by moving both authorities to program PDAs by CPI",
+            "Read a stake account's lockup before crediting it as withdrawable",
+        ],
+        entry_points: &[entry("stake_pool", "vulnerable_deposit_stake")],
+    },
+    Lesson {
+        id: "50_validator_delegation",
+        title: "Validator Vote Accounts and Commission",
+        prerequisites: &["01_missing_account_validation", "49_stake_authority_confusion"],
+        objectives: &[
+            "Parse a vote account's identity and commission, only from accounts the Vote program owns",
+            "Enforce a commission policy in the instruction that delegates, not in the crank",
+            "Let anyone undo a delegation once the validator's commission leaves the policy",
+        ],
+        entry_points: &[entry("delegation_manager", "vulnerable_delegate")],
+    },
+    Lesson {
+        id: "51_lookup_table_poisoning",
+        title: "Address Lookup Table Poisoning",
+        prerequisites: &["17_god_pda_authority", "23_remaining_accounts_router"],
+        objectives: &[
+            "Create and extend an address lookup table whose authority is a program's PDA",
+            "Restrict who the PDA signs extensions for, and list only accounts checked as the program's own",
+            "Verify a lookup table's authority and entries before building v0 transactions from it",
+        ],
+        entry_points: &[entry("pool_router", "vulnerable_extend")],
+    },
+    Lesson {
+        id: "52_memo_payment_reference",
+        title: "Memo Payment References",
+        prerequisites: &["10_cross_instance_replay", "43_deposit_memo_binding"],
+        objectives: &[
+            "Explain why an argument repeating a memo proves nothing about the Memo instruction",
+            "Read the Memo instruction beside a payment through the instructions sysvar",
+            "Refuse transactions whose other memos name something else",
+        ],
+        entry_points: &[entry("invoice_desk", "vulnerable_pay")],
+    },
+    Lesson {
+        id: "53_velocity_limits",
+        title: "Velocity Limits",
+        prerequisites: &["08_unchecked_close", "09_account_resurrection"],
+        objectives: &[
+            "Explain how closing and recreating a counter account lifts the limit it enforces",
+            "Keep per-owner counters at deterministic addresses that no instruction closes",
+            "Count against per-transaction caps and clock-aligned windows",
+        ],
+        entry_points: &[entry("limited_vault", "vulnerable_withdraw")],
+    },
+    Lesson {
+        id: "54_transfer_hook_authoring",
+        title: "Transfer Hook Authoring",
+        prerequisites: &["48_vault_interface", "53_velocity_limits"],
+        objectives: &[
+            "Explain why a transfer hook's Execute must not trust the accounts it is called with",
+            "Require Token-2022's transferring flag and the hook's mint before counting a transfer",
+            "Derive every extra account by seeds and let only the mint authority write the validation account",
+        ],
+        entry_points: &[entry("naive_hook", "transfer_hook")],
+    },
+    Lesson {
+        id: "55_confidential_transfer_extension",
+        title: "Confidential Transfer Extension",
+        prerequisites: &["19_lp_token_accounting", "54_transfer_hook_authoring"],
+        objectives: &[
+            "Explain why a confidential transfer's amount is invisible to the program receiving it",
+            "Check a Token-2022 mint's extensions against an allowlist before holding its tokens",
+            "Account only for balances a program can read and move",
+        ],
+        entry_points: &[entry("confidential_vault", "vulnerable_deposit_confidential")],
+    },
+    Lesson {
+        id: "56_mint_pointer_spoofing",
+        title: "Mint Pointer Spoofing",
+        prerequisites: &["34_royalty_bypass", "55_confidential_transfer_extension"],
+        objectives: &[
+            "Explain why a Token-2022 pointer can name any account, including another mint's data",
+            "Tell extensions the mint authority writes alone from those someone else must sign for",
+            "Require a mint's group membership to be stored in the mint itself, for the expected group",
+        ],
+        entry_points: &[
+            entry("collection_rewards", "vulnerable_claim_by_metadata"),
+            entry("collection_rewards", "vulnerable_claim_by_membership"),
+        ],
+    },
+    Lesson {
+        id: "57_compute_budget_introspection",
+        title: "Compute Budget Introspection",
+        prerequisites: &["21_keeper_crank_incentives", "52_memo_payment_reference"],
+        objectives: &[
+            "Explain why the compute budget a handler sees is chosen by the transaction's sender",
+            "Spot handlers that size their work by the meter and pay for it up front",
+            "Read a transaction's compute unit limit through the instructions sysvar and require enough for a batch",
+        ],
+        entry_points: &[entry("payout_crank", "vulnerable_crank")],
+    },
+    Lesson {
+        id: "58_payer_drain_griefing",
+        title: "Payer Drain Griefing",
+        prerequisites: &["12_rent_topup_griefing", "25_account_prefunding"],
+        objectives: &[
+            "Explain why a PDA payer spends the protocol's lamports on behalf of every caller",
+            "Spot sponsored accounts whose size or number the caller controls",
+            "Bound sponsorship with a program-chosen size and a per-user quota the user pays for",
+        ],
+        entry_points: &[entry("gasless_notes", "vulnerable_open_note")],
+    },
+    Lesson {
+        id: "59_decimal_confusion",
+        title: "Decimal Confusion",
+        prerequisites: &["35_bonding_curve_pricing", "37_payment_splitter_rounding"],
+        objectives: &[
+            "Convert between base units and UI amounts with the mint's own decimals",
+            "Spot fixed scales like 10^9 applied to amounts of arbitrary mints",
+            "Price fractional purchases so rounding favours the protocol, and move tokens with transfer_checked",
+        ],
+        entry_points: &[entry("token_desk", "vulnerable_buy")],
+    },
+    Lesson {
+        id: "60_timestamp_arithmetic",
+        title: "Timestamp Arithmetic",
+        prerequisites: &["04_arithmetic_overflow", "24_two_phase_commit"],
+        objectives: &[
+            "Keep differences of timestamps signed until their sign has been checked",
+            "Spot unchecked additions of user-supplied durations to timestamps",
+            "Bound schedules when they are created and test them at the ends of i64",
+        ],
+        entry_points: &[entry("vesting_grants", "vulnerable_claim")],
+    },
+    Lesson {
+        id: "61_order_cancel_race",
+        title: "Order Cancel Race",
+        prerequisites: &["13_slot_gambling", "21_keeper_crank_incentives"],
+        objectives: &[
+            "Model an account's lifecycle as an enum with an explicit list of transitions",
+            "Spot settlement handlers that write a status without reading it",
+            "Reason about racing instructions as the sequence the runtime runs them in",
+        ],
+        entry_points: &[entry("order_book", "vulnerable_fill_order")],
+    },
+    Lesson {
+        id: "62_collateral_double_counting",
+        title: "Collateral Double Counting",
+        prerequisites: &["19_lp_token_accounting", "20_lending_toy"],
+        objectives: &[
+            "Account for collateral across every sub-system of a protocol, not per sub-system",
+            "Spot deposits that several loans or positions can each count in full",
+            "Pledge collateral to one position at a time with an explicit lock and unlock",
+        ],
+        entry_points: &[entry("collateral_hub", "vulnerable_borrow")],
+    },
+    Lesson {
+        id: "63_fee_bps_misconfiguration",
+        title: "Fee Basis-Points Misconfiguration",
+        prerequisites: &["22_fee_recipient_ownership", "24_two_phase_commit"],
+        objectives: &[
+            "Bound every basis-points setting by the whole and by a protocol cap, when it is set",
+            "Explain why a fee that applies at once lets an admin key sandwich a user's payment",
+            "Announce configuration changes as events and apply them only after a delay",
+        ],
+        entry_points: &[entry("checkout", "vulnerable_set_fee")],
+    },
+    Lesson {
+        id: "64_config_hot_swap",
+        title: "Config Hot-Swap",
+        prerequisites: &["24_two_phase_commit", "63_fee_bps_misconfiguration"],
+        objectives: &[
+            "Explain how a config change that applies at once turns an admin-key compromise into instant fund loss",
+            "Timelock config changes into numbered epochs, each written once as a snapshot",
+            "Settle in-flight operations under the config epoch they were opened in",
+        ],
+        entry_points: &[entry("redemption_desk", "vulnerable_set_config")],
+    },
+    Lesson {
+        id: "65_withdraw_queue",
+        title: "Withdraw Queue",
+        prerequisites: &["21_keeper_crank_incentives", "07_seed_length_overflow"],
+        objectives: &[
+            "Explain how a caller-chosen ticket and owner-keyed request seeds let anyone jump a withdrawal queue",
+            "Assign monotonic tickets from an on-chain counter, one request address per ticket",
+            "Crank a queue in bounded pages that skip paid requests and stop at the first shortfall",
+        ],
+        entry_points: &[entry("withdraw_queue", "vulnerable_request_withdrawal")],
+    },
+    Lesson {
+        id: "66_signer_seeds_leakage",
+        title: "Signer Seeds Leakage",
+        prerequisites: &["17_god_pda_authority", "15_missing_executable_check"],
+        objectives: &[
+            "Explain how a generic execute instruction lends its PDA's signature to any instruction of any program",
+            "Redirect a Token program approve from a strategy's budget to the deposits its signer also owns",
+            "Derive a signing PDA per operation, with an operation tag in its seeds",
+        ],
+        entry_points: &[entry("strategy_vault", "vulnerable_execute")],
+    },
+    Lesson {
+        id: "67_position_receipts",
+        title: "Position Receipts",
+        prerequisites: &["19_lp_token_accounting", "08_unchecked_close"],
+        objectives: &[
+            "Explain why a receipt that is checked but not burned can be redeemed again",
+            "Redeem one position NFT until the vault holding every deposit is empty",
+            "Burn the receipt, close the position and validate the receipt mint's authority and supply",
+        ],
+        entry_points: &[entry("receipt_vault", "vulnerable_redeem")],
+    },
+];
@@ -0,0 +1,323 @@
+//! The security reference examples as ordered lessons.
+//!
+//! Each example directory is a [`Lesson`] with prerequisites, learning
+//! objectives and the vulnerable instructions its exploit suite attacks.
+//! Workshops and tooling walk the [`Curriculum`] instead of hard-coding the
+//! directory list, and use a [`validate::Validator`] to decide whether a
+//! learner's patched program is done:
+//!
+//! ```no_run
+//! use secref_curriculum::validate::ExploitSuite;
+//! use secref_curriculum::{Curriculum, Progress};
+//!
+//! let curriculum = Curriculum::new();
+//! let mut progress = Progress::default();
+//! if let Some(lesson) = curriculum.next(&progress) {
+//!     let result = curriculum.validate(lesson.id, "workshop/attempt", &ExploitSuite::default()).unwrap();
+//!     progress.record(&result);
+//! }
+//! ```
+
+pub mod lessons;
+pub mod validate;
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::Path;
+
+use validate::{Validation, Validator};
+
+/// A vulnerable instruction the lesson's exploit suite attacks
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EntryPoint {
+    /// Program crate that defines the instruction
+    pub program: &'static str,
+    /// Handler name, e.g. `vulnerable_withdraw`
+    pub instruction: &'static str,
+}
+
+/// One example directory, taught as a unit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lesson {
+    /// Example directory, e.g. `01_missing_account_validation`
+    pub id: &'static str,
+    pub title: &'static str,
+    /// Lessons to finish first
+    pub prerequisites: &'static [&'static str],
+    /// What a learner should be able to do afterwards
+    pub objectives: &'static [&'static str],
+    /// Instructions the exploit suite attacks; the learner's fix goes here
+    pub entry_points: &'static [EntryPoint],
+}
+
+impl Lesson {
+    /// Position in the teaching order, from the directory prefix
+    pub fn number(&self) -> u32 {
+        self.id[..2].parse().expect("lesson ids start with two digits")
+    }
+
+    /// The TypeScript suite that must pass once the lesson is solved
+    pub fn exploit_suite(&self) -> &'static str {
+        "tests/exploit.test.ts"
+    }
+}
+
+/// Errors from looking up or validating lessons
+#[derive(Debug, PartialEq, Eq)]
+pub enum CurriculumError {
+    /// No lesson has this id or number
+    UnknownLesson(String),
+    /// Validation ran, but not against a directory that exists
+    MissingDirectory(String),
+}
+
+impl fmt::Display for CurriculumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownLesson(id) => write!(f, "unknown lesson `{id}`"),
+            Self::MissingDirectory(dir) => write!(f, "lesson directory `{dir}` does not exist"),
+        }
+    }
+}
+
+impl std::error::Error for CurriculumError {}
+
+/// Lessons a learner has completed
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Progress {
+    completed: BTreeSet<String>,
+}
+
+impl Progress {
+    pub fn complete(&mut self, id: &str) {
+        self.completed.insert(id.to_string());
+    }
+
+    pub fn is_complete(&self, id: &str) -> bool {
+        self.completed.contains(id)
+    }
+
+    /// Complete the validated lesson if its exploit suite passed
+    pub fn record(&mut self, validation: &Validation) {
+        if validation.passed {
+            self.complete(validation.lesson);
+        }
+    }
+
+    pub fn completed(&self) -> impl Iterator<Item = &str> {
+        self.completed.iter().map(String::as_str)
+    }
+}
+
+/// The ordered lesson list and its prerequisite graph
+#[derive(Clone, Copy, Debug)]
+pub struct Curriculum {
+    lessons: &'static [Lesson],
+}
+
+impl Default for Curriculum {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Curriculum {
+    pub fn new() -> Self {
+        Self {
+            lessons: lessons::LESSONS,
+        }
+    }
+
+    /// Every lesson, in teaching order
+    pub fn lessons(&self) -> &'static [Lesson] {
+        self.lessons
+    }
+
+    /// Look a lesson up by directory name or by its number (`"7"`, `"07"`)
+    pub fn lesson(&self, id: &str) -> Result<&'static Lesson, CurriculumError> {
+        let number = id.parse::<u32>().ok();
+        self.lessons
+            .iter()
+            .find(|lesson| lesson.id == id || Some(lesson.number()) == number)
+            .ok_or_else(|| CurriculumError::UnknownLesson(id.to_string()))
+    }
+
+    /// Lessons not yet completed whose prerequisites all are
+    pub fn available<'a>(&'a self, progress: &'a Progress) -> impl Iterator<Item = &'static Lesson> + 'a {
+        self.lessons.iter().filter(|lesson| {
+            !progress.is_complete(lesson.id) && lesson.prerequisites.iter().all(|id| progress.is_complete(id))
+        })
+    }
+
+    /// The first available lesson, if any remain
+    pub fn next(&self, progress: &Progress) -> Option<&'static Lesson> {
+        self.available(progress).next()
+    }
+
+    /// `id` and everything it depends on, prerequisites first
+    pub fn path_to(&self, id: &str) -> Result<Vec<&'static Lesson>, CurriculumError> {
+        let target = self.lesson(id)?;
+        let mut needed = BTreeSet::from([target.id]);
+        // Prerequisites point backwards, so one reverse pass collects them all
+        for lesson in self.lessons.iter().rev() {
+            if needed.contains(lesson.id) {
+                needed.extend(lesson.prerequisites.iter().copied());
+            }
+        }
+        Ok(self
+            .lessons
+            .iter()
+            .filter(|lesson| needed.contains(lesson.id))
+            .collect())
+    }
+
+    /// Run `validator` against a learner's copy of lesson `id` in `dir`
+    pub fn validate(
+        &self,
+        id: &str,
+        dir: impl AsRef<Path>,
+        validator: &impl Validator,
+    ) -> Result<Validation, CurriculumError> {
+        let lesson = self.lesson(id)?;
+        let dir = dir.as_ref();
+        if !dir.is_dir() {
+            return Err(CurriculumError::MissingDirectory(dir.display().to_string()));
+        }
+        Ok(validator.validate(lesson, dir))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::{Path, PathBuf};
+
+    fn repo_root() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+    }
+
+    #[test]
+    fn lessons_match_the_example_directories() {
+        let curriculum = Curriculum::new();
+        let mut examples: Vec<String> = std::fs::read_dir(repo_root())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .filter(|name| name.len() > 3 && name.as_bytes()[..2].iter().all(u8::is_ascii_digit) && &name[2..3] == "_")
+            .collect();
+        examples.sort();
+        let ids: Vec<&str> = curriculum.lessons().iter().map(|lesson| lesson.id).collect();
+        assert_eq!(ids, examples);
+    }
+
+    /// Every Rust source under `dir`, concatenated
+    fn sources(dir: &Path) -> String {
+        let mut source = String::new();
+        for entry in std::fs::read_dir(dir).unwrap_or_else(|_| panic!("{}", dir.display())) {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                source += &sources(&path);
+            } else if path.extension().is_some_and(|extension| extension == "rs") {
+                source += &std::fs::read_to_string(&path).unwrap();
+            }
+        }
+        source
+    }
+
+    #[test]
+    fn entry_points_exist_in_their_programs() {
+        for lesson in Curriculum::new().lessons() {
+            for entry in lesson.entry_points {
+                let source = sources(&repo_root().join(lesson.id).join("programs").join(entry.program).join("src"));
+                assert!(
+                    source.contains(&format!("fn {}", entry.instruction)),
+                    "{}: no `{}` in {}",
+                    lesson.id,
+                    entry.instruction,
+                    entry.program
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn prerequisites_point_to_earlier_lessons() {
+        let lessons = Curriculum::new().lessons();
+        for (index, lesson) in lessons.iter().enumerate() {
+            assert!(!lesson.objectives.is_empty(), "{} has no objectives", lesson.id);
+            for prerequisite in lesson.prerequisites {
+                assert!(
+                    lessons[..index].iter().any(|earlier| earlier.id == *prerequisite),
+                    "{} depends on {prerequisite}, which is not an earlier lesson",
+                    lesson.id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn lessons_unlock_as_prerequisites_complete() {
+        let curriculum = Curriculum::new();
+        let mut progress = Progress::default();
+        assert_eq!(curriculum.next(&progress).unwrap().id, "01_missing_account_validation");
+        assert!(curriculum
+            .available(&progress)
+            .all(|lesson| lesson.prerequisites.is_empty()));
+
+        progress.complete("01_missing_account_validation");
+        let available: Vec<&str> = curriculum.available(&progress).map(|lesson| lesson.id).collect();
+        assert!(available.contains(&"02_authority_check_failure"));
+        assert!(!available.contains(&"03_unsafe_cpi"));
+        assert!(!available.contains(&"01_missing_account_validation"));
+    }
+
+    #[test]
+    fn path_to_includes_transitive_prerequisites_in_order() {
+        let path: Vec<&str> = Curriculum::new()
+            .path_to("17")
+            .unwrap()
+            .iter()
+            .map(|lesson| lesson.id)
+            .collect();
+        assert_eq!(
+            path,
+            [
+                "01_missing_account_validation",
+                "02_authority_check_failure",
+                "03_unsafe_cpi",
+                "16_duplicate_account_roles",
+                "17_god_pda_authority",
+            ]
+        );
+    }
+
+    #[test]
+    fn lookup_accepts_numbers_and_rejects_unknown_ids() {
+        let curriculum = Curriculum::new();
+        assert_eq!(curriculum.lesson("7").unwrap().id, "07_seed_length_overflow");
+        assert_eq!(curriculum.lesson("07").unwrap().id, "07_seed_length_overflow");
+        assert_eq!(
+            curriculum.lesson("99"),
+            Err(CurriculumError::UnknownLesson("99".to_string()))
+        );
+    }
+
+    #[test]
+    fn only_passing_validations_complete_a_lesson() {
+        let curriculum = Curriculum::new();
+        let mut progress = Progress::default();
+        let failing = |lesson: &Lesson, _: &Path| Validation::new(lesson.id, false, "1 failing");
+        let passing = |lesson: &Lesson, _: &Path| Validation::new(lesson.id, true, "");
+
+        progress.record(&curriculum.validate("01", repo_root(), &failing).unwrap());
+        assert!(!progress.is_complete("01_missing_account_validation"));
+        progress.record(&curriculum.validate("01", repo_root(), &passing).unwrap());
+        assert!(progress.is_complete("01_missing_account_validation"));
+
+        assert_eq!(
+            curriculum.validate("01", repo_root().join("no_such_dir"), &passing),
+            Err(CurriculumError::MissingDirectory(
+                repo_root().join("no_such_dir").display().to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Deciding whether a learner's patched program solves a lesson.
+//!
+//! A lesson is solved when its exploit suite passes against the learner's
+//! copy of the example: the attacks on the entry points fail and legitimate
+//! use still works. [`ExploitSuite`] runs the suite the way CI does; any
+//! `Fn(&Lesson, &Path) -> Validation` can stand in for it, e.g. to run a
+//! single test file or to score a workshop remotely.
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::Lesson;
+
+/// Outcome of validating one lesson
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Validation {
+    pub lesson: &'static str,
+    pub passed: bool,
+    /// Combined stdout and stderr of the run, for showing the learner
+    pub output: String,
+}
+
+impl Validation {
+    pub fn new(lesson: &'static str, passed: bool, output: impl Into<String>) -> Self {
+        Self {
+            lesson,
+            passed,
+            output: output.into(),
+        }
+    }
+}
+
+/// Checks a learner's copy of a lesson
+pub trait Validator {
+    fn validate(&self, lesson: &Lesson, dir: &Path) -> Validation;
+}
+
+impl<F: Fn(&Lesson, &Path) -> Validation> Validator for F {
+    fn validate(&self, lesson: &Lesson, dir: &Path) -> Validation {
+        self(lesson, dir)
+    }
+}
+
+/// Runs the example's exploit suite with a command in the lesson directory
+///
+/// Defaults to `anchor test --skip-lint`, which builds the program, starts
+/// a local validator and runs `tests/exploit.test.ts`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExploitSuite {
+    program: String,
+    args: Vec<String>,
+}
+
+impl Default for ExploitSuite {
+    fn default() -> Self {
+        Self::command("anchor", ["test", "--skip-lint"])
+    }
+}
+
+impl ExploitSuite {
+    /// Run `program args..` instead, e.g. `npm test` against a running validator
+    pub fn command<I, S>(program: impl Into<String>, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            program: program.into(),
+            args: args.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl Validator for ExploitSuite {
+    fn validate(&self, lesson: &Lesson, dir: &Path) -> Validation {
+        match Command::new(&self.program).args(&self.args).current_dir(dir).output() {
+            Ok(output) => {
+                let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+                text.push_str(&String::from_utf8_lossy(&output.stderr));
+                Validation::new(lesson.id, output.status.success(), text)
+            }
+            Err(error) => Validation::new(lesson.id, false, format!("could not run `{}`: {error}", self.program)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Curriculum;
+
+    fn lesson() -> &'static Lesson {
+        Curriculum::new().lesson("01").unwrap()
+    }
+
+    #[test]
+    fn passes_when_the_suite_exits_cleanly() {
+        let validation = ExploitSuite::command("sh", ["-c", "echo 3 passing"]).validate(lesson(), Path::new("."));
+        assert!(validation.passed);
+        assert_eq!(validation.output, "3 passing\n");
+    }
+
+    #[test]
+    fn fails_when_the_suite_fails() {
+        let validation =
+            ExploitSuite::command("sh", ["-c", "echo 1 failing >&2; exit 1"]).validate(lesson(), Path::new("."));
+        assert!(!validation.passed);
+        assert_eq!(validation.output, "1 failing\n");
+    }
+
+    #[test]
+    fn fails_when_the_suite_cannot_start() {
+        let validation =
+            ExploitSuite::command("secref-no-such-command", Vec::<String>::new()).validate(lesson(), Path::new("."));
+        assert!(!validation.passed);
+        assert!(validation.output.starts_with("could not run `secref-no-such-command`"));
+    }
+}
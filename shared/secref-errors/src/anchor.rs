@@ -0,0 +1,475 @@
+//! Anchor's own errors: constraints, `require!` macros, account (de)serialization.
+//!
+//! Codes below 6000 are the framework's. The same code means the same
+//! thing in every program, so each one has a single explanation here,
+//! written against anchor-lang 0.30.1. [`describe`] matches every variant
+//! without a wildcard, so a release that adds one does not build until it
+//! is explained.
+
+use anchor_lang::error::ErrorCode;
+
+/// Why Anchor raised an error, and where the examples show it doing its job
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct About {
+    pub cause: &'static str,
+    /// The vulnerable pattern the check most often stops, if it guards against one
+    pub pattern: Option<&'static str>,
+    /// Examples whose secure path relies on the check
+    pub modules: &'static [&'static str],
+}
+
+const fn about(cause: &'static str, pattern: &'static str, modules: &'static [&'static str]) -> About {
+    About {
+        cause,
+        pattern: Some(pattern),
+        modules,
+    }
+}
+
+/// A failure that is a client or build mistake, not a blocked attack
+const fn mistake(cause: &'static str) -> About {
+    About {
+        cause,
+        pattern: None,
+        modules: &[],
+    }
+}
+
+/// Every framework error, in code order
+pub const ERRORS: &[ErrorCode] = {
+    use ErrorCode::*;
+    &[
+        InstructionMissing,
+        InstructionFallbackNotFound,
+        InstructionDidNotDeserialize,
+        InstructionDidNotSerialize,
+        IdlInstructionStub,
+        IdlInstructionInvalidProgram,
+        IdlAccountNotEmpty,
+        EventInstructionStub,
+        ConstraintMut,
+        ConstraintHasOne,
+        ConstraintSigner,
+        ConstraintRaw,
+        ConstraintOwner,
+        ConstraintRentExempt,
+        ConstraintSeeds,
+        ConstraintExecutable,
+        ConstraintState,
+        ConstraintAssociated,
+        ConstraintAssociatedInit,
+        ConstraintClose,
+        ConstraintAddress,
+        ConstraintZero,
+        ConstraintTokenMint,
+        ConstraintTokenOwner,
+        ConstraintMintMintAuthority,
+        ConstraintMintFreezeAuthority,
+        ConstraintMintDecimals,
+        ConstraintSpace,
+        ConstraintAccountIsNone,
+        ConstraintTokenTokenProgram,
+        ConstraintMintTokenProgram,
+        ConstraintAssociatedTokenTokenProgram,
+        ConstraintMintGroupPointerExtension,
+        ConstraintMintGroupPointerExtensionAuthority,
+        ConstraintMintGroupPointerExtensionGroupAddress,
+        ConstraintMintGroupMemberPointerExtension,
+        ConstraintMintGroupMemberPointerExtensionAuthority,
+        ConstraintMintGroupMemberPointerExtensionMemberAddress,
+        ConstraintMintMetadataPointerExtension,
+        ConstraintMintMetadataPointerExtensionAuthority,
+        ConstraintMintMetadataPointerExtensionMetadataAddress,
+        ConstraintMintCloseAuthorityExtension,
+        ConstraintMintCloseAuthorityExtensionAuthority,
+        ConstraintMintPermanentDelegateExtension,
+        ConstraintMintPermanentDelegateExtensionDelegate,
+        ConstraintMintTransferHookExtension,
+        ConstraintMintTransferHookExtensionAuthority,
+        ConstraintMintTransferHookExtensionProgramId,
+        RequireViolated,
+        RequireEqViolated,
+        RequireKeysEqViolated,
+        RequireNeqViolated,
+        RequireKeysNeqViolated,
+        RequireGtViolated,
+        RequireGteViolated,
+        AccountDiscriminatorAlreadySet,
+        AccountDiscriminatorNotFound,
+        AccountDiscriminatorMismatch,
+        AccountDidNotDeserialize,
+        AccountDidNotSerialize,
+        AccountNotEnoughKeys,
+        AccountNotMutable,
+        AccountOwnedByWrongProgram,
+        InvalidProgramId,
+        InvalidProgramExecutable,
+        AccountNotSigner,
+        AccountNotSystemOwned,
+        AccountNotInitialized,
+        AccountNotProgramData,
+        AccountNotAssociatedTokenAccount,
+        AccountSysvarMismatch,
+        AccountReallocExceedsLimit,
+        AccountDuplicateReallocs,
+        DeclaredProgramIdMismatch,
+        TryingToInitPayerAsProgramAccount,
+        InvalidNumericConversion,
+        Deprecated,
+    ]
+};
+
+const SPOOFED_POINTER: &str =
+    "Mint pointer spoofing: metadata or group data read from an account the mint does not point to";
+const TOKEN_PROGRAM_CONFUSION: &str = "Token and Token-2022 accounts confused, so the wrong program's rules apply";
+
+/// The explanation of one framework error
+pub fn describe(code: ErrorCode) -> About {
+    use ErrorCode::*;
+    match code {
+        InstructionMissing => mistake("The instruction data is shorter than the 8-byte discriminator that selects a handler"),
+        InstructionFallbackNotFound => about(
+            "No handler has the instruction's discriminator, and the program defines no fallback",
+            "Instruction data built for another program, or another version of this one",
+            &["11_instruction_data_confusion", "48_vault_interface"],
+        ),
+        InstructionDidNotDeserialize => about(
+            "The bytes after the discriminator do not decode as the handler's arguments",
+            "Instruction data confusion: arguments laid out for another instruction",
+            &["11_instruction_data_confusion"],
+        ),
+        InstructionDidNotSerialize => mistake("The program could not encode the value it returns"),
+        IdlInstructionStub => mistake("An IDL instruction reached a program built without them"),
+        IdlInstructionInvalidProgram => mistake("The program passed to an IDL instruction is not this program"),
+        IdlAccountNotEmpty => mistake("The IDL account must be emptied before it is resized"),
+        EventInstructionStub => about(
+            "An `emit_cpi!` self-invocation reached a program built without the `event-cpi` feature",
+            "Forged events: the event handler invoked directly to fake an `emit_cpi!` event",
+            &["27_event_authenticity"],
+        ),
+        ConstraintMut => mistake("An account marked `mut` was passed read-only"),
+        ConstraintHasOne => about(
+            "A key the account stores does not match the account passed under that field's name",
+            "Missing account validation: someone else's account substituted for the one the state records",
+            &["01_missing_account_validation", "02_authority_check_failure", "08_unchecked_close"],
+        ),
+        ConstraintSigner => about(
+            "An account the context requires to sign did not",
+            "Authority check failure: a privileged action by someone who did not sign as the authority",
+            &["02_authority_check_failure", "27_event_authenticity"],
+        ),
+        ConstraintRaw => about(
+            "A `constraint = ..` expression evaluated false",
+            "An unchecked relationship between accounts, such as one account in two roles",
+            &["16_duplicate_account_roles", "36_referral_self_dealing"],
+        ),
+        ConstraintOwner => about(
+            "The account is not owned by the program an `owner = ..` constraint names",
+            "Account forgery: another program's account with the expected layout",
+            &["06_insecure_deserialization", "09_account_resurrection"],
+        ),
+        ConstraintRentExempt => about(
+            "The account holds less than the rent-exempt minimum for its size",
+            "An account created short of rent, which the runtime can reclaim",
+            &["12_rent_topup_griefing", "28_zero_copy_loader_misuse"],
+        ),
+        ConstraintSeeds => about(
+            "The account is not at the address its `seeds` and bump derive",
+            "A PDA of another user or vault, or a seed collision, passed in its place",
+            &["07_seed_length_overflow", "17_god_pda_authority"],
+        ),
+        ConstraintExecutable => about(
+            "An account required to be a program is not executable",
+            "A data account passed where a program is invoked",
+            &["15_missing_executable_check"],
+        ),
+        ConstraintState => mistake("Deprecated; no release raises it"),
+        ConstraintAssociated => about(
+            "The token account is not the associated token account of the wallet and mint",
+            "A token account substituted for, or squatted at, the associated one",
+            &["41_ata_precreation_squatting"],
+        ),
+        ConstraintAssociatedInit => about(
+            "Creating the associated token account failed",
+            "An associated token account created in advance by someone else",
+            &["41_ata_precreation_squatting"],
+        ),
+        ConstraintClose => about(
+            "The account being closed was also named as where its lamports go",
+            "Unchecked close destinations",
+            &["08_unchecked_close"],
+        ),
+        ConstraintAddress => about(
+            "The account is not at the address an `address = ..` constraint pins",
+            "A look-alike sysvar, program or table where a fixed one is expected",
+            &["10_cross_instance_replay", "32_program_id_confusion", "51_lookup_table_poisoning"],
+        ),
+        ConstraintZero => about(
+            "An account `zero` requires blank already has a discriminator",
+            "Reinitialization of an account already in use",
+            &["05_reinitialization_attack", "28_zero_copy_loader_misuse"],
+        ),
+        ConstraintTokenMint => about(
+            "The token account holds another mint than `token::mint` requires",
+            "A worthless mint's tokens passed where the real ones are expected",
+            &["22_fee_recipient_ownership", "59_decimal_confusion"],
+        ),
+        ConstraintTokenOwner => about(
+            "The token account belongs to another wallet than `token::authority` requires",
+            "Payments or fees routed to an account the attacker controls",
+            &["22_fee_recipient_ownership", "33_nft_rental_utility"],
+        ),
+        ConstraintMintMintAuthority => about(
+            "The mint's mint authority is not the one `mint::authority` requires",
+            "A mint someone else can inflate",
+            &["35_bonding_curve_pricing", "54_transfer_hook_authoring"],
+        ),
+        ConstraintMintFreezeAuthority => about(
+            "The mint's freeze authority is not the one `mint::freeze_authority` requires",
+            "A mint whose holders someone else can freeze",
+            &["42_compliance_freeze"],
+        ),
+        ConstraintMintDecimals => about(
+            "The mint's decimals differ from `mint::decimals`",
+            "Amounts of one mint read in another's decimals",
+            &["59_decimal_confusion"],
+        ),
+        ConstraintSpace => about(
+            "The account's data length is not the `space` the context requires",
+            "An account of another type or version with the same discriminator",
+            &["05_reinitialization_attack"],
+        ),
+        ConstraintAccountIsNone => mistake("An optional account another constraint depends on was not passed"),
+        ConstraintTokenTokenProgram => about(
+            "The token account belongs to another token program than `token::token_program` names",
+            TOKEN_PROGRAM_CONFUSION,
+            &["56_mint_pointer_spoofing"],
+        ),
+        ConstraintMintTokenProgram => about(
+            "The mint belongs to another token program than `mint::token_program` names",
+            TOKEN_PROGRAM_CONFUSION,
+            &["56_mint_pointer_spoofing"],
+        ),
+        ConstraintAssociatedTokenTokenProgram => about(
+            "The associated token account derives under another token program",
+            TOKEN_PROGRAM_CONFUSION,
+            &["41_ata_precreation_squatting"],
+        ),
+        ConstraintMintGroupPointerExtension => about(
+            "The mint has no group pointer extension",
+            SPOOFED_POINTER,
+            &["56_mint_pointer_spoofing"],
+        ),
+        ConstraintMintGroupPointerExtensionAuthority => about(
+            "The mint's group pointer has another authority than the context requires",
+            SPOOFED_POINTER,
+            &["56_mint_pointer_spoofing"],
+        ),
+        ConstraintMintGroupPointerExtensionGroupAddress => about(
+            "The mint's group pointer names another group account",
+            SPOOFED_POINTER,
+            &["56_mint_pointer_spoofing"],
+        ),
+        ConstraintMintGroupMemberPointerExtension => about(
+            "The mint has no group member pointer extension",
+            SPOOFED_POINTER,
+            &["56_mint_pointer_spoofing"],
+        ),
+        ConstraintMintGroupMemberPointerExtensionAuthority => about(
+            "The mint's group member pointer has another authority than the context requires",
+            SPOOFED_POINTER,
+            &["56_mint_pointer_spoofing"],
+        ),
+        ConstraintMintGroupMemberPointerExtensionMemberAddress => about(
+            "The mint's group member pointer names another member account",
+            SPOOFED_POINTER,
+            &["56_mint_pointer_spoofing"],
+        ),
+        ConstraintMintMetadataPointerExtension => about(
+            "The mint has no metadata pointer extension",
+            SPOOFED_POINTER,
+            &["56_mint_pointer_spoofing"],
+        ),
+        ConstraintMintMetadataPointerExtensionAuthority => about(
+            "The mint's metadata pointer has another authority than the context requires",
+            SPOOFED_POINTER,
+            &["56_mint_pointer_spoofing"],
+        ),
+        ConstraintMintMetadataPointerExtensionMetadataAddress => about(
+            "The mint's metadata pointer names another metadata account",
+            SPOOFED_POINTER,
+            &["56_mint_pointer_spoofing"],
+        ),
+        ConstraintMintCloseAuthorityExtension => about(
+            "The mint has no mint close authority extension",
+            "A mint that can be closed and recreated under other rules",
+            &["42_compliance_freeze"],
+        ),
+        ConstraintMintCloseAuthorityExtensionAuthority => about(
+            "The mint's close authority is not the one the context requires",
+            "A mint that can be closed and recreated under other rules",
+            &["42_compliance_freeze"],
+        ),
+        ConstraintMintPermanentDelegateExtension => about(
+            "The mint has no permanent delegate extension",
+            "A permanent delegate able to move any holder's tokens",
+            &["42_compliance_freeze"],
+        ),
+        ConstraintMintPermanentDelegateExtensionDelegate => about(
+            "The mint's permanent delegate is not the one the context requires",
+            "A permanent delegate able to move any holder's tokens",
+            &["42_compliance_freeze"],
+        ),
+        ConstraintMintTransferHookExtension => about(
+            "The mint has no transfer hook extension",
+            "Transfers run through a hook the program does not expect or control",
+            &["54_transfer_hook_authoring"],
+        ),
+        ConstraintMintTransferHookExtensionAuthority => about(
+            "The mint's transfer hook has another authority than the context requires",
+            "Transfers run through a hook the program does not expect or control",
+            &["54_transfer_hook_authoring"],
+        ),
+        ConstraintMintTransferHookExtensionProgramId => about(
+            "The mint's transfer hook names another program",
+            "Transfers run through a hook the program does not expect or control",
+            &["54_transfer_hook_authoring"],
+        ),
+        RequireViolated => mistake("A `require!` in the handler failed without naming an error of its own"),
+        RequireEqViolated => mistake("A `require_eq!` in the handler found two values that differ"),
+        RequireKeysEqViolated => about(
+            "A `require_keys_eq!` in the handler found two keys that differ",
+            "An account bound to another only in the handler, after validation",
+            &["01_missing_account_validation", "03_unsafe_cpi"],
+        ),
+        RequireNeqViolated => mistake("A `require_neq!` in the handler found two values equal"),
+        RequireKeysNeqViolated => about(
+            "A `require_keys_neq!` in the handler found two keys equal",
+            "Duplicate account roles: one account passed as both sides of a transfer",
+            &["16_duplicate_account_roles"],
+        ),
+        RequireGtViolated => mistake("A `require_gt!` in the handler found the first value not above the second"),
+        RequireGteViolated => mistake("A `require_gte!` in the handler found the first value below the second"),
+        AccountDiscriminatorAlreadySet => about(
+            "An account being initialized already has a discriminator",
+            "Reinitialization of an account already in use",
+            &["05_reinitialization_attack", "28_zero_copy_loader_misuse"],
+        ),
+        AccountDiscriminatorNotFound => about(
+            "The account's data is shorter than the 8-byte discriminator",
+            "A closed or never-created account passed as a program account",
+            &["09_account_resurrection"],
+        ),
+        AccountDiscriminatorMismatch => about(
+            "The account's first 8 bytes are another type's discriminator",
+            "Type confusion: an account of another type with a compatible layout",
+            &["06_insecure_deserialization", "09_account_resurrection"],
+        ),
+        AccountDidNotDeserialize => about(
+            "The account's data does not decode as its type",
+            "Data written under another layout, or truncated",
+            &["06_insecure_deserialization"],
+        ),
+        AccountDidNotSerialize => about(
+            "The account's new state does not fit in its data",
+            "A string or vector grown past the space allocated for it",
+            &["07_seed_length_overflow"],
+        ),
+        AccountNotEnoughKeys => mistake("The instruction passed fewer accounts than its context names"),
+        AccountNotMutable => mistake("An account the handler writes was passed read-only"),
+        AccountOwnedByWrongProgram => about(
+            "The account is owned by another program than its type requires",
+            "Account forgery: attacker-created data with the expected layout",
+            &["01_missing_account_validation", "06_insecure_deserialization", "32_program_id_confusion"],
+        ),
+        InvalidProgramId => about(
+            "A `Program<'info, T>` account is not T's program",
+            "Unsafe CPI: a substitute program passed where a known one is invoked",
+            &["03_unsafe_cpi", "32_program_id_confusion"],
+        ),
+        InvalidProgramExecutable => about(
+            "A `Program` account is not executable",
+            "A data account passed where a program is invoked",
+            &["15_missing_executable_check"],
+        ),
+        AccountNotSigner => about(
+            "A `Signer` account did not sign",
+            "Authority check failure: a privileged action by someone who did not sign as the authority",
+            &["02_authority_check_failure"],
+        ),
+        AccountNotSystemOwned => about(
+            "A `SystemAccount` is owned by another program",
+            "A program account passed where a wallet is expected",
+            &["25_account_prefunding"],
+        ),
+        AccountNotInitialized => about(
+            "The account holds nothing where an initialized account is expected",
+            "A closed account used again, or one never created",
+            &["08_unchecked_close", "09_account_resurrection"],
+        ),
+        AccountNotProgramData => about(
+            "The account is not a program data account",
+            "Upgrade authority read from a look-alike of the program data account",
+            &["15_missing_executable_check"],
+        ),
+        AccountNotAssociatedTokenAccount => about(
+            "The token account is not the wallet's associated token account",
+            "A token account substituted for, or squatted at, the associated one",
+            &["41_ata_precreation_squatting"],
+        ),
+        AccountSysvarMismatch => about(
+            "The account passed as a sysvar is not that sysvar",
+            "Sysvar spoofing: forged instructions or clock data",
+            &["10_cross_instance_replay", "39_bridge_message_verification"],
+        ),
+        AccountReallocExceedsLimit => mistake("A `realloc` grows the account by more than 10 KiB in one instruction"),
+        AccountDuplicateReallocs => about(
+            "One account was reallocated under two names",
+            "Duplicate account roles: one account passed as two",
+            &["16_duplicate_account_roles"],
+        ),
+        DeclaredProgramIdMismatch => about(
+            "The program runs at another address than its `declare_id!`",
+            "A copy of the program deployed elsewhere, trusted as the original",
+            &["32_program_id_confusion"],
+        ),
+        TryingToInitPayerAsProgramAccount => about(
+            "The payer is also the account being initialized",
+            "Duplicate account roles: one account passed as two",
+            &["16_duplicate_account_roles"],
+        ),
+        InvalidNumericConversion => about(
+            "A numeric conversion was out of range",
+            "Arithmetic overflow on a narrowing cast",
+            &["04_arithmetic_overflow", "35_bonding_curve_pricing"],
+        ),
+        Deprecated => mistake("A deprecated Anchor API was called"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_run_without_gaps_within_each_range() {
+        for pair in ERRORS.windows(2) {
+            let (previous, next) = (u32::from(pair[0]), u32::from(pair[1]));
+            assert!(next > previous, "{} is listed after {}", pair[1].name(), pair[0].name());
+            // Ranges start on a multiple of 100 (100, 1000, 1500, 2000, 2500, ..)
+            if next % 100 != 0 {
+                assert_eq!(next, previous + 1, "an error between {} and {} is missing", pair[0].name(), pair[1].name());
+            }
+        }
+    }
+
+    #[test]
+    fn modules_are_lessons() {
+        let lessons: Vec<&str> = secref_curriculum::lessons::LESSONS.iter().map(|lesson| lesson.id).collect();
+        for &code in ERRORS {
+            for module in describe(code).modules {
+                assert!(lessons.contains(module), "{} links to unknown module {module}", code.name());
+            }
+        }
+    }
+}
@@ -0,0 +1,2775 @@
+//! Every `#[error_code]` variant in the repository, by module and program.
+//!
+//! Generated by `tests/generated.rs` from the sources; rewrite it after
+//! changing an error enum with
+//! `SECREF_BLESS=1 cargo test --manifest-path shared/secref-errors/Cargo.toml`.
+
+use crate::CustomError;
+
+pub(crate) static ERRORS: &[CustomError] = &[
+    CustomError {
+        code: 6000,
+        name: "InsufficientFunds",
+        message: "Insufficient funds in vault",
+        module: "01_missing_account_validation",
+        program: "vault",
+    },
+    CustomError {
+        code: 6001,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "01_missing_account_validation",
+        program: "vault",
+    },
+    CustomError {
+        code: 6002,
+        name: "ArithmeticUnderflow",
+        message: "Arithmetic underflow",
+        module: "01_missing_account_validation",
+        program: "vault",
+    },
+    CustomError {
+        code: 6003,
+        name: "InvalidEncoding",
+        message: "Account data is not exactly one vault in this encoding",
+        module: "01_missing_account_validation",
+        program: "vault",
+    },
+    CustomError {
+        code: 6000,
+        name: "InsufficientFunds",
+        message: "Insufficient funds in vault",
+        module: "02_authority_check_failure",
+        program: "admin_vault",
+    },
+    CustomError {
+        code: 6001,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "02_authority_check_failure",
+        program: "admin_vault",
+    },
+    CustomError {
+        code: 6002,
+        name: "ArithmeticUnderflow",
+        message: "Arithmetic underflow",
+        module: "02_authority_check_failure",
+        program: "admin_vault",
+    },
+    CustomError {
+        code: 6003,
+        name: "UnauthorizedAdmin",
+        message: "Unauthorized admin - signer is not the vault admin",
+        module: "02_authority_check_failure",
+        program: "admin_vault",
+    },
+    CustomError {
+        code: 6004,
+        name: "InvalidApprovers",
+        message: "Approvers must be between 1 and 8 distinct keys",
+        module: "02_authority_check_failure",
+        program: "admin_vault",
+    },
+    CustomError {
+        code: 6005,
+        name: "InvalidThreshold",
+        message: "Threshold must be between 1 and the number of approvers",
+        module: "02_authority_check_failure",
+        program: "admin_vault",
+    },
+    CustomError {
+        code: 6006,
+        name: "NotAnApprover",
+        message: "Signer is not one of the multisig's approvers",
+        module: "02_authority_check_failure",
+        program: "admin_vault",
+    },
+    CustomError {
+        code: 6007,
+        name: "NotEnoughApprovals",
+        message: "Withdrawal does not have enough approvals",
+        module: "02_authority_check_failure",
+        program: "admin_vault",
+    },
+    CustomError {
+        code: 6008,
+        name: "ProposalMismatch",
+        message: "Approval does not match the proposal's contents",
+        module: "02_authority_check_failure",
+        program: "admin_vault",
+    },
+    CustomError {
+        code: 6009,
+        name: "AlreadyApproved",
+        message: "Approver has already approved this proposal",
+        module: "02_authority_check_failure",
+        program: "admin_vault",
+    },
+    CustomError {
+        code: 6010,
+        name: "ProposalAlreadyExecuted",
+        message: "Proposal has already been executed",
+        module: "02_authority_check_failure",
+        program: "admin_vault",
+    },
+    CustomError {
+        code: 6000,
+        name: "InvalidTokenProgram",
+        message: "Invalid token program - must be SPL Token program",
+        module: "03_unsafe_cpi",
+        program: "unsafe_cpi",
+    },
+    CustomError {
+        code: 6001,
+        name: "Unauthorized",
+        message: "Unauthorized operation",
+        module: "03_unsafe_cpi",
+        program: "unsafe_cpi",
+    },
+    CustomError {
+        code: 6002,
+        name: "InsufficientBalance",
+        message: "Insufficient token balance",
+        module: "03_unsafe_cpi",
+        program: "unsafe_cpi",
+    },
+    CustomError {
+        code: 6003,
+        name: "TokensRemaining",
+        message: "Vault token account still holds tokens after the sweep",
+        module: "03_unsafe_cpi",
+        program: "unsafe_cpi",
+    },
+    CustomError {
+        code: 6004,
+        name: "TokenAccountOpen",
+        message: "Vault token account is still open",
+        module: "03_unsafe_cpi",
+        program: "unsafe_cpi",
+    },
+    CustomError {
+        code: 6000,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow occurred",
+        module: "04_arithmetic_overflow",
+        program: "arithmetic_vault",
+    },
+    CustomError {
+        code: 6001,
+        name: "ArithmeticUnderflow",
+        message: "Arithmetic underflow occurred",
+        module: "04_arithmetic_overflow",
+        program: "arithmetic_vault",
+    },
+    CustomError {
+        code: 6002,
+        name: "DivisionByZero",
+        message: "Division by zero",
+        module: "04_arithmetic_overflow",
+        program: "arithmetic_vault",
+    },
+    CustomError {
+        code: 6003,
+        name: "InsufficientFunds",
+        message: "Insufficient funds for withdrawal",
+        module: "04_arithmetic_overflow",
+        program: "arithmetic_vault",
+    },
+    CustomError {
+        code: 6000,
+        name: "AlreadyInitialized",
+        message: "Account is already initialized",
+        module: "05_reinitialization_attack",
+        program: "reinit_vault",
+    },
+    CustomError {
+        code: 6001,
+        name: "NotInitialized",
+        message: "Account is not initialized",
+        module: "05_reinitialization_attack",
+        program: "reinit_vault",
+    },
+    CustomError {
+        code: 6002,
+        name: "Unauthorized",
+        message: "Unauthorized access attempt",
+        module: "05_reinitialization_attack",
+        program: "reinit_vault",
+    },
+    CustomError {
+        code: 6003,
+        name: "InsufficientFunds",
+        message: "Insufficient funds for withdrawal",
+        module: "05_reinitialization_attack",
+        program: "reinit_vault",
+    },
+    CustomError {
+        code: 6004,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow occurred",
+        module: "05_reinitialization_attack",
+        program: "reinit_vault",
+    },
+    CustomError {
+        code: 6005,
+        name: "ArithmeticUnderflow",
+        message: "Arithmetic underflow occurred",
+        module: "05_reinitialization_attack",
+        program: "reinit_vault",
+    },
+    CustomError {
+        code: 6006,
+        name: "VaultNotEmpty",
+        message: "Vault must be empty before reset",
+        module: "05_reinitialization_attack",
+        program: "reinit_vault",
+    },
+    CustomError {
+        code: 6000,
+        name: "PayloadTooShort",
+        message: "Payload is too short to contain a length prefix",
+        module: "06_insecure_deserialization",
+        program: "airdrop_list",
+    },
+    CustomError {
+        code: 6001,
+        name: "TooManyRecipients",
+        message: "Declared recipient count exceeds the maximum",
+        module: "06_insecure_deserialization",
+        program: "airdrop_list",
+    },
+    CustomError {
+        code: 6002,
+        name: "LengthMismatch",
+        message: "Payload length does not match the declared recipient count",
+        module: "06_insecure_deserialization",
+        program: "airdrop_list",
+    },
+    CustomError {
+        code: 6003,
+        name: "DuplicateRecipient",
+        message: "Recipient appears more than once",
+        module: "06_insecure_deserialization",
+        program: "airdrop_list",
+    },
+    CustomError {
+        code: 6004,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "06_insecure_deserialization",
+        program: "airdrop_list",
+    },
+    CustomError {
+        code: 6000,
+        name: "HandleEmpty",
+        message: "Handle must not be empty",
+        module: "07_seed_length_overflow",
+        program: "profile_registry",
+    },
+    CustomError {
+        code: 6001,
+        name: "HandleTooLong",
+        message: "Handle exceeds the maximum length in bytes",
+        module: "07_seed_length_overflow",
+        program: "profile_registry",
+    },
+    CustomError {
+        code: 6002,
+        name: "InvalidHandleCharacter",
+        message: "Handle may only contain ASCII letters, digits and underscores",
+        module: "07_seed_length_overflow",
+        program: "profile_registry",
+    },
+    CustomError {
+        code: 6003,
+        name: "BioTooLong",
+        message: "Bio exceeds the maximum length in bytes",
+        module: "07_seed_length_overflow",
+        program: "profile_registry",
+    },
+    CustomError {
+        code: 6004,
+        name: "InvalidUtf8",
+        message: "Bio is not valid UTF-8",
+        module: "07_seed_length_overflow",
+        program: "profile_registry",
+    },
+    CustomError {
+        code: 6000,
+        name: "PositionNotEmpty",
+        message: "Position still records a deposit and cannot be closed",
+        module: "08_unchecked_close",
+        program: "position_manager",
+    },
+    CustomError {
+        code: 6001,
+        name: "InsufficientDeposit",
+        message: "Withdrawal exceeds the deposited amount",
+        module: "08_unchecked_close",
+        program: "position_manager",
+    },
+    CustomError {
+        code: 6002,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "08_unchecked_close",
+        program: "position_manager",
+    },
+    CustomError {
+        code: 6000,
+        name: "InsufficientTreasury",
+        message: "Treasury cannot cover the voucher",
+        module: "09_account_resurrection",
+        program: "voucher_redeemer",
+    },
+    CustomError {
+        code: 6001,
+        name: "NotVoucherOwner",
+        message: "Signer does not own this voucher",
+        module: "09_account_resurrection",
+        program: "voucher_redeemer",
+    },
+    CustomError {
+        code: 6002,
+        name: "AccountNotClosed",
+        message: "Account is not marked as closed",
+        module: "09_account_resurrection",
+        program: "voucher_redeemer",
+    },
+    CustomError {
+        code: 6003,
+        name: "AccountDidNotClose",
+        message: "Failed to write the closed-account discriminator",
+        module: "09_account_resurrection",
+        program: "voucher_redeemer",
+    },
+    CustomError {
+        code: 6004,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "09_account_resurrection",
+        program: "voucher_redeemer",
+    },
+    CustomError {
+        code: 6000,
+        name: "MissingSignatureInstruction",
+        message: "Expected an Ed25519 signature instruction before this instruction",
+        module: "10_cross_instance_replay",
+        program: "signed_withdrawals",
+    },
+    CustomError {
+        code: 6001,
+        name: "InvalidSignatureInstruction",
+        message: "Ed25519 instruction is malformed or references other instructions",
+        module: "10_cross_instance_replay",
+        program: "signed_withdrawals",
+    },
+    CustomError {
+        code: 6002,
+        name: "WrongSigner",
+        message: "Authorization was not signed by the vault's authorizer",
+        module: "10_cross_instance_replay",
+        program: "signed_withdrawals",
+    },
+    CustomError {
+        code: 6003,
+        name: "MessageMismatch",
+        message: "Signed message does not match this withdrawal",
+        module: "10_cross_instance_replay",
+        program: "signed_withdrawals",
+    },
+    CustomError {
+        code: 6004,
+        name: "InvalidNonce",
+        message: "Authorization nonce does not match the vault",
+        module: "10_cross_instance_replay",
+        program: "signed_withdrawals",
+    },
+    CustomError {
+        code: 6005,
+        name: "InsufficientFunds",
+        message: "Vault has insufficient funds",
+        module: "10_cross_instance_replay",
+        program: "signed_withdrawals",
+    },
+    CustomError {
+        code: 6006,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "10_cross_instance_replay",
+        program: "signed_withdrawals",
+    },
+    CustomError {
+        code: 6000,
+        name: "NoteTooLarge",
+        message: "Note body exceeds the maximum length",
+        module: "12_rent_topup_griefing",
+        program: "storage_sponsor",
+    },
+    CustomError {
+        code: 6001,
+        name: "TopUpTooLarge",
+        message: "Top-up exceeds the per-call cap",
+        module: "12_rent_topup_griefing",
+        program: "storage_sponsor",
+    },
+    CustomError {
+        code: 6002,
+        name: "SponsorDepleted",
+        message: "Sponsor would drop below its rent-exempt minimum",
+        module: "12_rent_topup_griefing",
+        program: "storage_sponsor",
+    },
+    CustomError {
+        code: 6003,
+        name: "InsufficientLamports",
+        message: "Insufficient lamports",
+        module: "12_rent_topup_griefing",
+        program: "storage_sponsor",
+    },
+    CustomError {
+        code: 6004,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "12_rent_topup_griefing",
+        program: "storage_sponsor",
+    },
+    CustomError {
+        code: 6000,
+        name: "RoundNotOpen",
+        message: "Round is not open for bets",
+        module: "13_slot_gambling",
+        program: "coin_flip",
+    },
+    CustomError {
+        code: 6001,
+        name: "RoundNotPlaced",
+        message: "Round has no unsettled bet",
+        module: "13_slot_gambling",
+        program: "coin_flip",
+    },
+    CustomError {
+        code: 6002,
+        name: "CommitmentMismatch",
+        message: "House secret does not match the commitment",
+        module: "13_slot_gambling",
+        program: "coin_flip",
+    },
+    CustomError {
+        code: 6003,
+        name: "RevealWindowOpen",
+        message: "The house can still reveal",
+        module: "13_slot_gambling",
+        program: "coin_flip",
+    },
+    CustomError {
+        code: 6004,
+        name: "InsufficientBankroll",
+        message: "Bankroll cannot cover the payout",
+        module: "13_slot_gambling",
+        program: "coin_flip",
+    },
+    CustomError {
+        code: 6005,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "13_slot_gambling",
+        program: "coin_flip",
+    },
+    CustomError {
+        code: 6000,
+        name: "InvalidFee",
+        message: "Fee cannot exceed 10,000 basis points",
+        module: "14_simulation_divergence",
+        program: "payment_gateway",
+    },
+    CustomError {
+        code: 6001,
+        name: "ConfigChanged",
+        message: "Configuration changed since the transaction was simulated",
+        module: "14_simulation_divergence",
+        program: "payment_gateway",
+    },
+    CustomError {
+        code: 6002,
+        name: "FeeExceedsLimit",
+        message: "Fee exceeds the maximum the payer approved",
+        module: "14_simulation_divergence",
+        program: "payment_gateway",
+    },
+    CustomError {
+        code: 6003,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "14_simulation_divergence",
+        program: "payment_gateway",
+    },
+    CustomError {
+        code: 6000,
+        name: "NotCouncilMember",
+        message: "Approver is not on the council",
+        module: "16_duplicate_account_roles",
+        program: "credit_ledger",
+    },
+    CustomError {
+        code: 6001,
+        name: "NotRelayerBalance",
+        message: "Relayer balance does not belong to the relayer",
+        module: "16_duplicate_account_roles",
+        program: "credit_ledger",
+    },
+    CustomError {
+        code: 6002,
+        name: "SelfTransfer",
+        message: "Sender and recipient must be different accounts",
+        module: "16_duplicate_account_roles",
+        program: "credit_ledger",
+    },
+    CustomError {
+        code: 6003,
+        name: "DuplicateAccount",
+        message: "The same account was passed in two roles",
+        module: "16_duplicate_account_roles",
+        program: "credit_ledger",
+    },
+    CustomError {
+        code: 6004,
+        name: "SelfApproval",
+        message: "Transfers must be approved by someone other than the sender",
+        module: "16_duplicate_account_roles",
+        program: "credit_ledger",
+    },
+    CustomError {
+        code: 6005,
+        name: "InsufficientCredits",
+        message: "Insufficient credits",
+        module: "16_duplicate_account_roles",
+        program: "credit_ledger",
+    },
+    CustomError {
+        code: 6006,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "16_duplicate_account_roles",
+        program: "credit_ledger",
+    },
+    CustomError {
+        code: 6000,
+        name: "DestinationNotActive",
+        message: "Destination is allowlisted but its delay has not passed",
+        module: "17_god_pda_authority",
+        program: "vault_bank",
+    },
+    CustomError {
+        code: 6001,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "17_god_pda_authority",
+        program: "vault_bank",
+    },
+    CustomError {
+        code: 6000,
+        name: "LoanOutstanding",
+        message: "A flash loan is already outstanding",
+        module: "18_token_balance_voting",
+        program: "mock_lender",
+    },
+    CustomError {
+        code: 6001,
+        name: "MissingRepayment",
+        message: "No matching flash_repay later in the transaction",
+        module: "18_token_balance_voting",
+        program: "mock_lender",
+    },
+    CustomError {
+        code: 6002,
+        name: "InsufficientRepayment",
+        message: "Repayment is smaller than the loan",
+        module: "18_token_balance_voting",
+        program: "mock_lender",
+    },
+    CustomError {
+        code: 6000,
+        name: "InvalidVotingPeriod",
+        message: "Voting period must be positive",
+        module: "18_token_balance_voting",
+        program: "token_voting",
+    },
+    CustomError {
+        code: 6001,
+        name: "VotingClosed",
+        message: "Voting has closed for this proposal",
+        module: "18_token_balance_voting",
+        program: "token_voting",
+    },
+    CustomError {
+        code: 6002,
+        name: "VotingStillOpen",
+        message: "Voting is still open; escrowed tokens are locked",
+        module: "18_token_balance_voting",
+        program: "token_voting",
+    },
+    CustomError {
+        code: 6003,
+        name: "NoVotingPower",
+        message: "Voter has no voting power",
+        module: "18_token_balance_voting",
+        program: "token_voting",
+    },
+    CustomError {
+        code: 6004,
+        name: "NothingToLock",
+        message: "Cannot lock zero tokens",
+        module: "18_token_balance_voting",
+        program: "token_voting",
+    },
+    CustomError {
+        code: 6005,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "18_token_balance_voting",
+        program: "token_voting",
+    },
+    CustomError {
+        code: 6000,
+        name: "ReserveMismatch",
+        message: "Vault balance is below the recorded reserve",
+        module: "19_lp_token_accounting",
+        program: "lp_pool",
+    },
+    CustomError {
+        code: 6001,
+        name: "ZeroLiquidity",
+        message: "Deposit is too small to mint any LP tokens",
+        module: "19_lp_token_accounting",
+        program: "lp_pool",
+    },
+    CustomError {
+        code: 6002,
+        name: "InsufficientLiquidity",
+        message: "Burn exceeds the LP supply",
+        module: "19_lp_token_accounting",
+        program: "lp_pool",
+    },
+    CustomError {
+        code: 6003,
+        name: "SlippageExceeded",
+        message: "Output is below the caller's minimum",
+        module: "19_lp_token_accounting",
+        program: "lp_pool",
+    },
+    CustomError {
+        code: 6004,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "19_lp_token_accounting",
+        program: "lp_pool",
+    },
+    CustomError {
+        code: 6000,
+        name: "InsufficientBalance",
+        message: "Insufficient balance in position",
+        module: "20_lending_toy",
+        program: "lending_toy",
+    },
+    CustomError {
+        code: 6001,
+        name: "InsufficientLiquidity",
+        message: "Insufficient liquidity in market",
+        module: "20_lending_toy",
+        program: "lending_toy",
+    },
+    CustomError {
+        code: 6002,
+        name: "ExceedsLtv",
+        message: "Loan would exceed the maximum loan-to-value",
+        module: "20_lending_toy",
+        program: "lending_toy",
+    },
+    CustomError {
+        code: 6003,
+        name: "Unhealthy",
+        message: "Position would be undercollateralized",
+        module: "20_lending_toy",
+        program: "lending_toy",
+    },
+    CustomError {
+        code: 6004,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "20_lending_toy",
+        program: "lending_toy",
+    },
+    CustomError {
+        code: 6005,
+        name: "Healthy",
+        message: "Position is healthy and cannot be liquidated",
+        module: "20_lending_toy",
+        program: "lending_toy",
+    },
+    CustomError {
+        code: 6006,
+        name: "ExceedsCloseFactor",
+        message: "Repayment exceeds what one liquidation may repay",
+        module: "20_lending_toy",
+        program: "lending_toy",
+    },
+    CustomError {
+        code: 6007,
+        name: "NothingToLiquidate",
+        message: "Liquidation would repay nothing",
+        module: "20_lending_toy",
+        program: "lending_toy",
+    },
+    CustomError {
+        code: 6008,
+        name: "SelfLiquidation",
+        message: "A position cannot be liquidated into itself",
+        module: "20_lending_toy",
+        program: "lending_toy",
+    },
+    CustomError {
+        code: 6009,
+        name: "NotBadDebt",
+        message: "Only a position with debt and no collateral can be written off",
+        module: "20_lending_toy",
+        program: "lending_toy",
+    },
+    CustomError {
+        code: 6010,
+        name: "LossExceedsDeposits",
+        message: "Bad debt would wipe out every deposit",
+        module: "20_lending_toy",
+        program: "lending_toy",
+    },
+    CustomError {
+        code: 6000,
+        name: "OrderTooSmall",
+        message: "Order must escrow more than the keeper reward",
+        module: "21_keeper_crank_incentives",
+        program: "order_expiry",
+    },
+    CustomError {
+        code: 6001,
+        name: "InvalidTtl",
+        message: "Time-to-live must be non-negative",
+        module: "21_keeper_crank_incentives",
+        program: "order_expiry",
+    },
+    CustomError {
+        code: 6002,
+        name: "OrderNotExpired",
+        message: "Order has not expired yet",
+        module: "21_keeper_crank_incentives",
+        program: "order_expiry",
+    },
+    CustomError {
+        code: 6003,
+        name: "RewardExceedsEscrow",
+        message: "Reward exceeds the escrowed amount",
+        module: "21_keeper_crank_incentives",
+        program: "order_expiry",
+    },
+    CustomError {
+        code: 6004,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "21_keeper_crank_incentives",
+        program: "order_expiry",
+    },
+    CustomError {
+        code: 6000,
+        name: "InvalidFee",
+        message: "Fee must not exceed 100%",
+        module: "22_fee_recipient_ownership",
+        program: "payment_processor",
+    },
+    CustomError {
+        code: 6001,
+        name: "InvalidFeeRecipient",
+        message: "Fee recipient is not owned by the protocol fee authority",
+        module: "22_fee_recipient_ownership",
+        program: "payment_processor",
+    },
+    CustomError {
+        code: 6002,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "22_fee_recipient_ownership",
+        program: "payment_processor",
+    },
+    CustomError {
+        code: 6003,
+        name: "FeeRecipientRequired",
+        message: "Fee account is required: this config does not waive fees",
+        module: "22_fee_recipient_ownership",
+        program: "payment_processor",
+    },
+    CustomError {
+        code: 6000,
+        name: "PoolAlreadyRegistered",
+        message: "Pool is already registered",
+        module: "23_remaining_accounts_router",
+        program: "swap_router",
+    },
+    CustomError {
+        code: 6001,
+        name: "RegistryFull",
+        message: "Registry is full",
+        module: "23_remaining_accounts_router",
+        program: "swap_router",
+    },
+    CustomError {
+        code: 6002,
+        name: "PoolNotRegistered",
+        message: "Pool is not in the registry",
+        module: "23_remaining_accounts_router",
+        program: "swap_router",
+    },
+    CustomError {
+        code: 6003,
+        name: "InvalidRoute",
+        message: "Route is empty or its hops do not connect",
+        module: "23_remaining_accounts_router",
+        program: "swap_router",
+    },
+    CustomError {
+        code: 6004,
+        name: "InvalidAsset",
+        message: "Unknown asset",
+        module: "23_remaining_accounts_router",
+        program: "swap_router",
+    },
+    CustomError {
+        code: 6005,
+        name: "InvalidFee",
+        message: "Fee must not exceed 100%",
+        module: "23_remaining_accounts_router",
+        program: "swap_router",
+    },
+    CustomError {
+        code: 6006,
+        name: "EmptyPool",
+        message: "Pool reserves must be non-zero",
+        module: "23_remaining_accounts_router",
+        program: "swap_router",
+    },
+    CustomError {
+        code: 6007,
+        name: "InsufficientBalance",
+        message: "Insufficient balance",
+        module: "23_remaining_accounts_router",
+        program: "swap_router",
+    },
+    CustomError {
+        code: 6008,
+        name: "SlippageExceeded",
+        message: "Output below the minimum",
+        module: "23_remaining_accounts_router",
+        program: "swap_router",
+    },
+    CustomError {
+        code: 6009,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "23_remaining_accounts_router",
+        program: "swap_router",
+    },
+    CustomError {
+        code: 6000,
+        name: "InvalidDelay",
+        message: "Delay must be non-negative",
+        module: "24_two_phase_commit",
+        program: "timelock_treasury",
+    },
+    CustomError {
+        code: 6001,
+        name: "TimelockActive",
+        message: "Withdrawal delay has not passed",
+        module: "24_two_phase_commit",
+        program: "timelock_treasury",
+    },
+    CustomError {
+        code: 6002,
+        name: "InsufficientFunds",
+        message: "Treasury does not hold enough lamports",
+        module: "24_two_phase_commit",
+        program: "timelock_treasury",
+    },
+    CustomError {
+        code: 6003,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "24_two_phase_commit",
+        program: "timelock_treasury",
+    },
+    CustomError {
+        code: 6000,
+        name: "NoShards",
+        message: "Aggregation needs at least one shard",
+        module: "26_hot_account_contention",
+        program: "points_ledger",
+    },
+    CustomError {
+        code: 6001,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "26_hot_account_contention",
+        program: "points_ledger",
+    },
+    CustomError {
+        code: 6000,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "27_event_authenticity",
+        program: "rewards_distributor",
+    },
+    CustomError {
+        code: 6000,
+        name: "AlreadyInitialized",
+        message: "Account is already initialized",
+        module: "28_zero_copy_loader_misuse",
+        program: "price_history",
+    },
+    CustomError {
+        code: 6001,
+        name: "AccountTooSmall",
+        message: "Account is too small for the price history",
+        module: "28_zero_copy_loader_misuse",
+        program: "price_history",
+    },
+    CustomError {
+        code: 6000,
+        name: "WeightOverflow",
+        message: "Weight arithmetic overflowed",
+        module: "29_stack_frame_limits",
+        program: "basket_rebalancer",
+    },
+    CustomError {
+        code: 6000,
+        name: "MissingQuote",
+        message: "The quoter set no return data",
+        module: "30_cpi_return_data",
+        program: "loan_desk",
+    },
+    CustomError {
+        code: 6001,
+        name: "UntrustedQuoter",
+        message: "Return data was not set by the price quoter",
+        module: "30_cpi_return_data",
+        program: "loan_desk",
+    },
+    CustomError {
+        code: 6002,
+        name: "MalformedQuote",
+        message: "Return data is not a single quote",
+        module: "30_cpi_return_data",
+        program: "loan_desk",
+    },
+    CustomError {
+        code: 6003,
+        name: "QuoteMismatch",
+        message: "Quote is for a different amount than was requested",
+        module: "30_cpi_return_data",
+        program: "loan_desk",
+    },
+    CustomError {
+        code: 6004,
+        name: "LimitOverflow",
+        message: "Borrow limit overflowed",
+        module: "30_cpi_return_data",
+        program: "loan_desk",
+    },
+    CustomError {
+        code: 6000,
+        name: "QuoteOverflow",
+        message: "Quote overflowed",
+        module: "30_cpi_return_data",
+        program: "price_quoter",
+    },
+    CustomError {
+        code: 6000,
+        name: "VaultInsolvent",
+        message: "Vault holds less than it owes",
+        module: "31_invariant_watchdog",
+        program: "vault_watchdog",
+    },
+    CustomError {
+        code: 6001,
+        name: "DepositExceedsLiabilities",
+        message: "A deposit claims more than the vault owes in total",
+        module: "31_invariant_watchdog",
+        program: "vault_watchdog",
+    },
+    CustomError {
+        code: 6002,
+        name: "ForeignDeposit",
+        message: "Deposit belongs to a different vault",
+        module: "31_invariant_watchdog",
+        program: "vault_watchdog",
+    },
+    CustomError {
+        code: 6000,
+        name: "InsufficientDeposit",
+        message: "Withdrawal exceeds the deposit",
+        module: "31_invariant_watchdog",
+        program: "watched_vault",
+    },
+    CustomError {
+        code: 6001,
+        name: "AmountOverflow",
+        message: "Amount overflowed",
+        module: "31_invariant_watchdog",
+        program: "watched_vault",
+    },
+    CustomError {
+        code: 6002,
+        name: "WatchdogMissing",
+        message: "Transaction does not end with vault_watchdog::assert_vault_solvent for this vault",
+        module: "31_invariant_watchdog",
+        program: "watched_vault",
+    },
+    CustomError {
+        code: 6000,
+        name: "UntrustedFeed",
+        message: "Feed is not owned by the market's feed program",
+        module: "32_program_id_confusion",
+        program: "lending_market",
+    },
+    CustomError {
+        code: 6001,
+        name: "MalformedFeed",
+        message: "Feed account is not a price feed",
+        module: "32_program_id_confusion",
+        program: "lending_market",
+    },
+    CustomError {
+        code: 6002,
+        name: "LimitOverflow",
+        message: "Borrow limit overflowed",
+        module: "32_program_id_confusion",
+        program: "lending_market",
+    },
+    CustomError {
+        code: 6000,
+        name: "NotAnNft",
+        message: "Mint is not an NFT: it needs 0 decimals and a supply of 1",
+        module: "33_nft_rental_utility",
+        program: "nft_rental",
+    },
+    CustomError {
+        code: 6001,
+        name: "InvalidDuration",
+        message: "Rentals last between 1 and 30 days",
+        module: "33_nft_rental_utility",
+        program: "nft_rental",
+    },
+    CustomError {
+        code: 6002,
+        name: "AlreadyRented",
+        message: "The NFT is rented",
+        module: "33_nft_rental_utility",
+        program: "nft_rental",
+    },
+    CustomError {
+        code: 6003,
+        name: "FeeOverflow",
+        message: "Rental fee overflowed",
+        module: "33_nft_rental_utility",
+        program: "nft_rental",
+    },
+    CustomError {
+        code: 6004,
+        name: "WrongDay",
+        message: "Perks can only be claimed for the current day",
+        module: "33_nft_rental_utility",
+        program: "nft_rental",
+    },
+    CustomError {
+        code: 6005,
+        name: "NotHolder",
+        message: "Signer does not hold the NFT",
+        module: "33_nft_rental_utility",
+        program: "nft_rental",
+    },
+    CustomError {
+        code: 6006,
+        name: "RentalExpired",
+        message: "The rental has expired",
+        module: "33_nft_rental_utility",
+        program: "nft_rental",
+    },
+    CustomError {
+        code: 6007,
+        name: "NoReceipt",
+        message: "Renter does not hold the rental's receipt",
+        module: "33_nft_rental_utility",
+        program: "nft_rental",
+    },
+    CustomError {
+        code: 6000,
+        name: "NotAnNft",
+        message: "Mint is not an NFT: it needs 0 decimals and a supply of 1",
+        module: "34_royalty_bypass",
+        program: "nft_marketplace",
+    },
+    CustomError {
+        code: 6001,
+        name: "RoyaltyRecipientsMismatch",
+        message: "Expected one royalty recipient account per royalty",
+        module: "34_royalty_bypass",
+        program: "nft_marketplace",
+    },
+    CustomError {
+        code: 6002,
+        name: "WrongRoyaltyRecipient",
+        message: "Royalty recipient is not the creator the metadata lists",
+        module: "34_royalty_bypass",
+        program: "nft_marketplace",
+    },
+    CustomError {
+        code: 6003,
+        name: "RoyaltyOverflow",
+        message: "Royalty calculation overflowed",
+        module: "34_royalty_bypass",
+        program: "nft_marketplace",
+    },
+    CustomError {
+        code: 6000,
+        name: "InvalidCurve",
+        message: "Curve prices must start above zero and rise, and the cap must be positive",
+        module: "35_bonding_curve_pricing",
+        program: "bonding_curve_sale",
+    },
+    CustomError {
+        code: 6001,
+        name: "SupplyCapExceeded",
+        message: "Purchase would take the supply past the sale's cap",
+        module: "35_bonding_curve_pricing",
+        program: "bonding_curve_sale",
+    },
+    CustomError {
+        code: 6002,
+        name: "PriceOverflow",
+        message: "Purchase costs more lamports than a u64 can hold",
+        module: "35_bonding_curve_pricing",
+        program: "bonding_curve_sale",
+    },
+    CustomError {
+        code: 6003,
+        name: "SlippageExceeded",
+        message: "Purchase costs more than the buyer's maximum",
+        module: "35_bonding_curve_pricing",
+        program: "bonding_curve_sale",
+    },
+    CustomError {
+        code: 6000,
+        name: "InsufficientDeposit",
+        message: "Cannot withdraw more than is deposited",
+        module: "36_referral_self_dealing",
+        program: "referral_rewards",
+    },
+    CustomError {
+        code: 6001,
+        name: "SelfReferral",
+        message: "A member cannot be their own referrer or upline",
+        module: "36_referral_self_dealing",
+        program: "referral_rewards",
+    },
+    CustomError {
+        code: 6002,
+        name: "BrokenReferralChain",
+        message: "Upline account is not the previous member's referrer",
+        module: "36_referral_self_dealing",
+        program: "referral_rewards",
+    },
+    CustomError {
+        code: 6003,
+        name: "MissingUpline",
+        message: "Every upline up to the root or the last bonus level must be passed",
+        module: "36_referral_self_dealing",
+        program: "referral_rewards",
+    },
+    CustomError {
+        code: 6004,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "36_referral_self_dealing",
+        program: "referral_rewards",
+    },
+    CustomError {
+        code: 6000,
+        name: "NoShares",
+        message: "A splitter needs at least one share to distribute",
+        module: "37_payment_splitter_rounding",
+        program: "payment_splitter",
+    },
+    CustomError {
+        code: 6001,
+        name: "TooManyRecipients",
+        message: "Too many recipients to distribute in one transaction",
+        module: "37_payment_splitter_rounding",
+        program: "payment_splitter",
+    },
+    CustomError {
+        code: 6002,
+        name: "SplitterFull",
+        message: "The splitter has no room for another recipient",
+        module: "37_payment_splitter_rounding",
+        program: "payment_splitter",
+    },
+    CustomError {
+        code: 6003,
+        name: "RecipientMismatch",
+        message: "Recipients must be passed in the splitter's order",
+        module: "37_payment_splitter_rounding",
+        program: "payment_splitter",
+    },
+    CustomError {
+        code: 6004,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "37_payment_splitter_rounding",
+        program: "payment_splitter",
+    },
+    CustomError {
+        code: 6000,
+        name: "InvalidTerms",
+        message: "A subscription needs a positive amount and interval and an expiry in the future",
+        module: "38_subscription_authority",
+        program: "subscriptions",
+    },
+    CustomError {
+        code: 6001,
+        name: "DelegateInUse",
+        message: "The token account already has a delegate; revoke it first",
+        module: "38_subscription_authority",
+        program: "subscriptions",
+    },
+    CustomError {
+        code: 6002,
+        name: "SubscriptionExpired",
+        message: "The subscription has expired",
+        module: "38_subscription_authority",
+        program: "subscriptions",
+    },
+    CustomError {
+        code: 6003,
+        name: "PullTooEarly",
+        message: "The next pull is not due yet",
+        module: "38_subscription_authority",
+        program: "subscriptions",
+    },
+    CustomError {
+        code: 6004,
+        name: "DelegateRevoked",
+        message: "The subscriber revoked or replaced this subscription's delegation",
+        module: "38_subscription_authority",
+        program: "subscriptions",
+    },
+    CustomError {
+        code: 6005,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "38_subscription_authority",
+        program: "subscriptions",
+    },
+    CustomError {
+        code: 6000,
+        name: "TooManyGuardians",
+        message: "A bridge can have at most MAX_GUARDIANS guardians",
+        module: "39_bridge_message_verification",
+        program: "bridge_inbox",
+    },
+    CustomError {
+        code: 6001,
+        name: "InvalidThreshold",
+        message: "The threshold must be between one and the number of guardians",
+        module: "39_bridge_message_verification",
+        program: "bridge_inbox",
+    },
+    CustomError {
+        code: 6002,
+        name: "DuplicateGuardian",
+        message: "A guardian is listed twice",
+        module: "39_bridge_message_verification",
+        program: "bridge_inbox",
+    },
+    CustomError {
+        code: 6003,
+        name: "RecipientMismatch",
+        message: "The recipient account is not the one the message names",
+        module: "39_bridge_message_verification",
+        program: "bridge_inbox",
+    },
+    CustomError {
+        code: 6004,
+        name: "InvalidSignatureInstruction",
+        message: "Ed25519 instruction is malformed or references other instructions",
+        module: "39_bridge_message_verification",
+        program: "bridge_inbox",
+    },
+    CustomError {
+        code: 6005,
+        name: "NotEnoughSignatures",
+        message: "Too few distinct guardians signed this message for this bridge",
+        module: "39_bridge_message_verification",
+        program: "bridge_inbox",
+    },
+    CustomError {
+        code: 6006,
+        name: "InsufficientFunds",
+        message: "Bridge custody has insufficient funds",
+        module: "39_bridge_message_verification",
+        program: "bridge_inbox",
+    },
+    CustomError {
+        code: 6007,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "39_bridge_message_verification",
+        program: "bridge_inbox",
+    },
+    CustomError {
+        code: 6000,
+        name: "AuthorityChanged",
+        message: "The claim changed who controls the claimant's token account",
+        module: "40_wallet_drainer_anatomy",
+        program: "airdrop_claim",
+    },
+    CustomError {
+        code: 6000,
+        name: "RolesNotSeparated",
+        message: "The admin, freezer and seizer must be different keys",
+        module: "42_compliance_freeze",
+        program: "compliance_vault",
+    },
+    CustomError {
+        code: 6001,
+        name: "InvalidDelay",
+        message: "Seizure delay must be non-negative",
+        module: "42_compliance_freeze",
+        program: "compliance_vault",
+    },
+    CustomError {
+        code: 6002,
+        name: "PositionFrozen",
+        message: "Position is frozen",
+        module: "42_compliance_freeze",
+        program: "compliance_vault",
+    },
+    CustomError {
+        code: 6003,
+        name: "PositionNotFrozen",
+        message: "Only a frozen position can be seized",
+        module: "42_compliance_freeze",
+        program: "compliance_vault",
+    },
+    CustomError {
+        code: 6004,
+        name: "NoSeizureProposed",
+        message: "No seizure has been proposed for this position",
+        module: "42_compliance_freeze",
+        program: "compliance_vault",
+    },
+    CustomError {
+        code: 6005,
+        name: "InsufficientBalance",
+        message: "Position balance is too low",
+        module: "42_compliance_freeze",
+        program: "compliance_vault",
+    },
+    CustomError {
+        code: 6006,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "42_compliance_freeze",
+        program: "compliance_vault",
+    },
+    CustomError {
+        code: 6000,
+        name: "ZeroDeposit",
+        message: "Deposit amount must be greater than zero",
+        module: "43_deposit_memo_binding",
+        program: "deposit_receipts",
+    },
+    CustomError {
+        code: 6001,
+        name: "ReceiptMismatch",
+        message: "Receipt is not this vault's receipt for the reference",
+        module: "43_deposit_memo_binding",
+        program: "deposit_receipts",
+    },
+    CustomError {
+        code: 6000,
+        name: "InvalidDepth",
+        message: "Tree depth must be between 1 and 14",
+        module: "44_concurrent_merkle_tree",
+        program: "voucher_tree",
+    },
+    CustomError {
+        code: 6001,
+        name: "TreeFull",
+        message: "Every leaf of the tree has been issued",
+        module: "44_concurrent_merkle_tree",
+        program: "voucher_tree",
+    },
+    CustomError {
+        code: 6002,
+        name: "InvalidProof",
+        message: "Proof does not hash up to the expected root",
+        module: "44_concurrent_merkle_tree",
+        program: "voucher_tree",
+    },
+    CustomError {
+        code: 6003,
+        name: "UnknownRoot",
+        message: "Root is not one the tree has had recently",
+        module: "44_concurrent_merkle_tree",
+        program: "voucher_tree",
+    },
+    CustomError {
+        code: 6004,
+        name: "ProofLengthMismatch",
+        message: "Proof must have exactly one node per level of the tree",
+        module: "44_concurrent_merkle_tree",
+        program: "voucher_tree",
+    },
+    CustomError {
+        code: 6005,
+        name: "LeafIndexOutOfBounds",
+        message: "Leaf index is outside the tree",
+        module: "44_concurrent_merkle_tree",
+        program: "voucher_tree",
+    },
+    CustomError {
+        code: 6006,
+        name: "InsufficientFunds",
+        message: "Tree does not hold enough lamports for the voucher",
+        module: "44_concurrent_merkle_tree",
+        program: "voucher_tree",
+    },
+    CustomError {
+        code: 6000,
+        name: "MissingSignatureInstruction",
+        message: "Expected an Ed25519 signature instruction before this instruction",
+        module: "45_durable_nonce_replay",
+        program: "relayed_payments",
+    },
+    CustomError {
+        code: 6001,
+        name: "InvalidSignatureInstruction",
+        message: "Ed25519 instruction is malformed or references other instructions",
+        module: "45_durable_nonce_replay",
+        program: "relayed_payments",
+    },
+    CustomError {
+        code: 6002,
+        name: "WrongSigner",
+        message: "Authorization was not signed by the wallet's owner",
+        module: "45_durable_nonce_replay",
+        program: "relayed_payments",
+    },
+    CustomError {
+        code: 6003,
+        name: "MessageMismatch",
+        message: "Signed message does not match this payment",
+        module: "45_durable_nonce_replay",
+        program: "relayed_payments",
+    },
+    CustomError {
+        code: 6004,
+        name: "AuthorizationExpired",
+        message: "Authorization has expired",
+        module: "45_durable_nonce_replay",
+        program: "relayed_payments",
+    },
+    CustomError {
+        code: 6005,
+        name: "InsufficientFunds",
+        message: "Wallet has insufficient funds",
+        module: "45_durable_nonce_replay",
+        program: "relayed_payments",
+    },
+    CustomError {
+        code: 6006,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "45_durable_nonce_replay",
+        program: "relayed_payments",
+    },
+    CustomError {
+        code: 6000,
+        name: "InvalidConfiguration",
+        message: "Threshold, bonus, lock period and stake must all be positive",
+        module: "46_balance_as_authorization",
+        program: "premium_club",
+    },
+    CustomError {
+        code: 6001,
+        name: "InsufficientBalance",
+        message: "Member's balance is below the club's threshold",
+        module: "46_balance_as_authorization",
+        program: "premium_club",
+    },
+    CustomError {
+        code: 6002,
+        name: "InsufficientStake",
+        message: "Member's stake is below the club's threshold",
+        module: "46_balance_as_authorization",
+        program: "premium_club",
+    },
+    CustomError {
+        code: 6003,
+        name: "StakeLocked",
+        message: "Stake is still locked",
+        module: "46_balance_as_authorization",
+        program: "premium_club",
+    },
+    CustomError {
+        code: 6004,
+        name: "InsufficientFunds",
+        message: "Club does not hold enough lamports for the bonus",
+        module: "46_balance_as_authorization",
+        program: "premium_club",
+    },
+    CustomError {
+        code: 6005,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "46_balance_as_authorization",
+        program: "premium_club",
+    },
+    CustomError {
+        code: 6000,
+        name: "TooManyGuardians",
+        message: "A pool can have at most MAX_GUARDIANS guardians",
+        module: "47_kill_switch_recovery",
+        program: "guarded_pool",
+    },
+    CustomError {
+        code: 6001,
+        name: "InvalidThreshold",
+        message: "Threshold must be between 1 and the number of guardians",
+        module: "47_kill_switch_recovery",
+        program: "guarded_pool",
+    },
+    CustomError {
+        code: 6002,
+        name: "DuplicateGuardian",
+        message: "Guardians must be distinct",
+        module: "47_kill_switch_recovery",
+        program: "guarded_pool",
+    },
+    CustomError {
+        code: 6003,
+        name: "InvalidDelay",
+        message: "The halt must be positive and the resume delay non-negative",
+        module: "47_kill_switch_recovery",
+        program: "guarded_pool",
+    },
+    CustomError {
+        code: 6004,
+        name: "NotAGuardian",
+        message: "Signer is not one of the pool's guardians",
+        module: "47_kill_switch_recovery",
+        program: "guarded_pool",
+    },
+    CustomError {
+        code: 6005,
+        name: "NotEnoughGuardians",
+        message: "Not enough guardians signed",
+        module: "47_kill_switch_recovery",
+        program: "guarded_pool",
+    },
+    CustomError {
+        code: 6006,
+        name: "PoolNotActive",
+        message: "Pool is not active",
+        module: "47_kill_switch_recovery",
+        program: "guarded_pool",
+    },
+    CustomError {
+        code: 6007,
+        name: "PoolHalted",
+        message: "Pool is halted",
+        module: "47_kill_switch_recovery",
+        program: "guarded_pool",
+    },
+    CustomError {
+        code: 6008,
+        name: "PoolNotHalted",
+        message: "Pool is not halted",
+        module: "47_kill_switch_recovery",
+        program: "guarded_pool",
+    },
+    CustomError {
+        code: 6009,
+        name: "PoolAlreadyActive",
+        message: "Pool is already active",
+        module: "47_kill_switch_recovery",
+        program: "guarded_pool",
+    },
+    CustomError {
+        code: 6010,
+        name: "NoResumeProposed",
+        message: "No resumption has been proposed",
+        module: "47_kill_switch_recovery",
+        program: "guarded_pool",
+    },
+    CustomError {
+        code: 6011,
+        name: "InsufficientBalance",
+        message: "Position or pool balance is too low",
+        module: "47_kill_switch_recovery",
+        program: "guarded_pool",
+    },
+    CustomError {
+        code: 6012,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "47_kill_switch_recovery",
+        program: "guarded_pool",
+    },
+    CustomError {
+        code: 6000,
+        name: "ZeroDeposit",
+        message: "Deposit must be more than zero",
+        module: "48_vault_interface",
+        program: "boosted_vault",
+    },
+    CustomError {
+        code: 6001,
+        name: "ZeroShares",
+        message: "Deposit is too small to buy a share",
+        module: "48_vault_interface",
+        program: "boosted_vault",
+    },
+    CustomError {
+        code: 6002,
+        name: "SharesOverflow",
+        message: "Shares overflowed",
+        module: "48_vault_interface",
+        program: "boosted_vault",
+    },
+    CustomError {
+        code: 6000,
+        name: "ZeroDeposit",
+        message: "Deposit must be more than zero",
+        module: "48_vault_interface",
+        program: "steady_vault",
+    },
+    CustomError {
+        code: 6001,
+        name: "SharesOverflow",
+        message: "Shares overflowed",
+        module: "48_vault_interface",
+        program: "steady_vault",
+    },
+    CustomError {
+        code: 6000,
+        name: "MissingReceipt",
+        message: "The implementation set no receipt",
+        module: "48_vault_interface",
+        program: "vault_router",
+    },
+    CustomError {
+        code: 6001,
+        name: "UntrustedReceipt",
+        message: "Return data was not set by the implementation that was called",
+        module: "48_vault_interface",
+        program: "vault_router",
+    },
+    CustomError {
+        code: 6002,
+        name: "MalformedReceipt",
+        message: "Return data is not a single receipt",
+        module: "48_vault_interface",
+        program: "vault_router",
+    },
+    CustomError {
+        code: 6003,
+        name: "ReceiptMismatch",
+        message: "Receipt is for a different amount than was deposited",
+        module: "48_vault_interface",
+        program: "vault_router",
+    },
+    CustomError {
+        code: 6004,
+        name: "NoShares",
+        message: "Receipt reports no shares",
+        module: "48_vault_interface",
+        program: "vault_router",
+    },
+    CustomError {
+        code: 6005,
+        name: "UnexpectedSpend",
+        message: "The implementation moved a different amount than was deposited",
+        module: "48_vault_interface",
+        program: "vault_router",
+    },
+    CustomError {
+        code: 6006,
+        name: "AllocationOverflow",
+        message: "Allocation overflowed",
+        module: "48_vault_interface",
+        program: "vault_router",
+    },
+    CustomError {
+        code: 6000,
+        name: "NotAStakeAccount",
+        message: "Stake account is not initialized",
+        module: "49_stake_authority_confusion",
+        program: "stake_pool",
+    },
+    CustomError {
+        code: 6001,
+        name: "NotDelegatedToPool",
+        message: "Stake account's staker is not the pool's stake authority",
+        module: "49_stake_authority_confusion",
+        program: "stake_pool",
+    },
+    CustomError {
+        code: 6002,
+        name: "LockupInForce",
+        message: "Stake account is under a lockup",
+        module: "49_stake_authority_confusion",
+        program: "stake_pool",
+    },
+    CustomError {
+        code: 6003,
+        name: "InsufficientBalance",
+        message: "Position or pool balance is too low",
+        module: "49_stake_authority_confusion",
+        program: "stake_pool",
+    },
+    CustomError {
+        code: 6004,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "49_stake_authority_confusion",
+        program: "stake_pool",
+    },
+    CustomError {
+        code: 6000,
+        name: "InvalidCommission",
+        message: "Commission is a percentage, at most 100",
+        module: "50_validator_delegation",
+        program: "delegation_manager",
+    },
+    CustomError {
+        code: 6001,
+        name: "NotDelegated",
+        message: "Stake account is not delegated",
+        module: "50_validator_delegation",
+        program: "delegation_manager",
+    },
+    CustomError {
+        code: 6002,
+        name: "WrongValidator",
+        message: "Vote account is not the one the stake is delegated to",
+        module: "50_validator_delegation",
+        program: "delegation_manager",
+    },
+    CustomError {
+        code: 6003,
+        name: "CommissionWithinPolicy",
+        message: "Validator's commission is within the manager's policy",
+        module: "50_validator_delegation",
+        program: "delegation_manager",
+    },
+    CustomError {
+        code: 6000,
+        name: "WrongLookupTable",
+        message: "Lookup table is not at the address the table authority and slot derive",
+        module: "51_lookup_table_poisoning",
+        program: "pool_router",
+    },
+    CustomError {
+        code: 6001,
+        name: "InvalidLookupTable",
+        message: "Account is not an address lookup table",
+        module: "51_lookup_table_poisoning",
+        program: "pool_router",
+    },
+    CustomError {
+        code: 6002,
+        name: "NoPoolsListed",
+        message: "No pools listed",
+        module: "51_lookup_table_poisoning",
+        program: "pool_router",
+    },
+    CustomError {
+        code: 6003,
+        name: "RecipientMismatch",
+        message: "Recipients must be the lookup table's addresses, in order",
+        module: "51_lookup_table_poisoning",
+        program: "pool_router",
+    },
+    CustomError {
+        code: 6004,
+        name: "NothingToDistribute",
+        message: "Nothing to distribute",
+        module: "51_lookup_table_poisoning",
+        program: "pool_router",
+    },
+    CustomError {
+        code: 6005,
+        name: "NotAPool",
+        message: "Account is not one of the router's pools",
+        module: "51_lookup_table_poisoning",
+        program: "pool_router",
+    },
+    CustomError {
+        code: 6006,
+        name: "AlreadyListed",
+        message: "Pool is already listed",
+        module: "51_lookup_table_poisoning",
+        program: "pool_router",
+    },
+    CustomError {
+        code: 6000,
+        name: "ZeroAmount",
+        message: "Invoice amount must be greater than zero",
+        module: "52_memo_payment_reference",
+        program: "invoice_desk",
+    },
+    CustomError {
+        code: 6001,
+        name: "InvalidReference",
+        message: "Reference must be 1 to 32 bytes",
+        module: "52_memo_payment_reference",
+        program: "invoice_desk",
+    },
+    CustomError {
+        code: 6002,
+        name: "ReferenceMismatch",
+        message: "Memo does not match the invoice's reference",
+        module: "52_memo_payment_reference",
+        program: "invoice_desk",
+    },
+    CustomError {
+        code: 6003,
+        name: "AlreadyPaid",
+        message: "Invoice has already been paid",
+        module: "52_memo_payment_reference",
+        program: "invoice_desk",
+    },
+    CustomError {
+        code: 6000,
+        name: "ZeroAmount",
+        message: "Amount must be greater than zero",
+        module: "53_velocity_limits",
+        program: "limited_vault",
+    },
+    CustomError {
+        code: 6001,
+        name: "InsufficientBalance",
+        message: "Withdrawal is more than the position holds",
+        module: "53_velocity_limits",
+        program: "limited_vault",
+    },
+    CustomError {
+        code: 6002,
+        name: "DailyLimitExceeded",
+        message: "Withdrawal would exceed the daily limit",
+        module: "53_velocity_limits",
+        program: "limited_vault",
+    },
+    CustomError {
+        code: 6003,
+        name: "MathOverflow",
+        message: "Arithmetic overflow",
+        module: "53_velocity_limits",
+        program: "limited_vault",
+    },
+    CustomError {
+        code: 6000,
+        name: "NotTransferring",
+        message: "The hook only runs inside a Token-2022 transfer",
+        module: "54_transfer_hook_authoring",
+        program: "guarded_hook",
+    },
+    CustomError {
+        code: 6000,
+        name: "ZeroAmount",
+        message: "Amount must be greater than zero",
+        module: "54_transfer_hook_authoring",
+        program: "hooked_vault",
+    },
+    CustomError {
+        code: 6001,
+        name: "InsufficientBalance",
+        message: "Position balance is too low",
+        module: "54_transfer_hook_authoring",
+        program: "hooked_vault",
+    },
+    CustomError {
+        code: 6002,
+        name: "NoTransferHook",
+        message: "The mint has no transfer hook",
+        module: "54_transfer_hook_authoring",
+        program: "hooked_vault",
+    },
+    CustomError {
+        code: 6003,
+        name: "HookChanged",
+        message: "The mint's transfer hook is not the one the vault was created with",
+        module: "54_transfer_hook_authoring",
+        program: "hooked_vault",
+    },
+    CustomError {
+        code: 6000,
+        name: "ZeroAmount",
+        message: "Amount must be greater than zero",
+        module: "55_confidential_transfer_extension",
+        program: "confidential_vault",
+    },
+    CustomError {
+        code: 6001,
+        name: "InsufficientBalance",
+        message: "Position balance is too low",
+        module: "55_confidential_transfer_extension",
+        program: "confidential_vault",
+    },
+    CustomError {
+        code: 6002,
+        name: "NoConfidentialDeposit",
+        message: "No confidential transfer has reached the vault since the last one was claimed",
+        module: "55_confidential_transfer_extension",
+        program: "confidential_vault",
+    },
+    CustomError {
+        code: 6000,
+        name: "ZeroAmount",
+        message: "Amount must be greater than zero",
+        module: "56_mint_pointer_spoofing",
+        program: "collection_rewards",
+    },
+    CustomError {
+        code: 6001,
+        name: "PointerMismatch",
+        message: "Account is not the one the mint's pointer names",
+        module: "56_mint_pointer_spoofing",
+        program: "collection_rewards",
+    },
+    CustomError {
+        code: 6002,
+        name: "NotInCollection",
+        message: "Mint is not in the collection",
+        module: "56_mint_pointer_spoofing",
+        program: "collection_rewards",
+    },
+    CustomError {
+        code: 6003,
+        name: "NotHolder",
+        message: "Claimant holds none of the mint's tokens",
+        module: "56_mint_pointer_spoofing",
+        program: "collection_rewards",
+    },
+    CustomError {
+        code: 6004,
+        name: "PoolExhausted",
+        message: "Pool cannot pay the reward and stay rent-exempt",
+        module: "56_mint_pointer_spoofing",
+        program: "collection_rewards",
+    },
+    CustomError {
+        code: 6005,
+        name: "MathOverflow",
+        message: "Arithmetic overflow",
+        module: "56_mint_pointer_spoofing",
+        program: "collection_rewards",
+    },
+    CustomError {
+        code: 6000,
+        name: "ZeroAmount",
+        message: "Amount must be greater than zero",
+        module: "57_compute_budget_introspection",
+        program: "payout_crank",
+    },
+    CustomError {
+        code: 6001,
+        name: "PayrollFull",
+        message: "Payroll lists the most payees it can hold",
+        module: "57_compute_budget_introspection",
+        program: "payout_crank",
+    },
+    CustomError {
+        code: 6002,
+        name: "NothingDue",
+        message: "Every payee has been paid",
+        module: "57_compute_budget_introspection",
+        program: "payout_crank",
+    },
+    CustomError {
+        code: 6003,
+        name: "MissingPayee",
+        message: "A payee due in this batch was not passed",
+        module: "57_compute_budget_introspection",
+        program: "payout_crank",
+    },
+    CustomError {
+        code: 6004,
+        name: "PayeeMismatch",
+        message: "Account passed is not the payee due",
+        module: "57_compute_budget_introspection",
+        program: "payout_crank",
+    },
+    CustomError {
+        code: 6005,
+        name: "PayrollExhausted",
+        message: "Payroll cannot pay this without falling below rent exemption",
+        module: "57_compute_budget_introspection",
+        program: "payout_crank",
+    },
+    CustomError {
+        code: 6006,
+        name: "MathOverflow",
+        message: "Arithmetic overflow",
+        module: "57_compute_budget_introspection",
+        program: "payout_crank",
+    },
+    CustomError {
+        code: 6000,
+        name: "NoteTooSmall",
+        message: "Note space is smaller than a note",
+        module: "58_payer_drain_griefing",
+        program: "gasless_notes",
+    },
+    CustomError {
+        code: 6001,
+        name: "QuotaExhausted",
+        message: "Owner has used all of its sponsored notes",
+        module: "58_payer_drain_griefing",
+        program: "gasless_notes",
+    },
+    CustomError {
+        code: 6002,
+        name: "InsufficientLamports",
+        message: "Insufficient lamports",
+        module: "58_payer_drain_griefing",
+        program: "gasless_notes",
+    },
+    CustomError {
+        code: 6003,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "58_payer_drain_griefing",
+        program: "gasless_notes",
+    },
+    CustomError {
+        code: 6000,
+        name: "InvalidPrice",
+        message: "Price must be above zero",
+        module: "59_decimal_confusion",
+        program: "token_desk",
+    },
+    CustomError {
+        code: 6001,
+        name: "ZeroAmount",
+        message: "Purchase must be of at least one base unit",
+        module: "59_decimal_confusion",
+        program: "token_desk",
+    },
+    CustomError {
+        code: 6002,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "59_decimal_confusion",
+        program: "token_desk",
+    },
+    CustomError {
+        code: 6000,
+        name: "InvalidSchedule",
+        message: "Schedule duration or start is out of range",
+        module: "60_timestamp_arithmetic",
+        program: "vesting_grants",
+    },
+    CustomError {
+        code: 6001,
+        name: "NothingVested",
+        message: "Nothing has vested since the last claim",
+        module: "60_timestamp_arithmetic",
+        program: "vesting_grants",
+    },
+    CustomError {
+        code: 6002,
+        name: "InsufficientLamports",
+        message: "Insufficient lamports",
+        module: "60_timestamp_arithmetic",
+        program: "vesting_grants",
+    },
+    CustomError {
+        code: 6003,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "60_timestamp_arithmetic",
+        program: "vesting_grants",
+    },
+    CustomError {
+        code: 6000,
+        name: "ZeroQuantity",
+        message: "Order must sell at least one base unit",
+        module: "61_order_cancel_race",
+        program: "order_book",
+    },
+    CustomError {
+        code: 6001,
+        name: "InvalidPrice",
+        message: "Price must be above zero",
+        module: "61_order_cancel_race",
+        program: "order_book",
+    },
+    CustomError {
+        code: 6000,
+        name: "InvalidLtv",
+        message: "Loan-to-value must be between 1 and 10,000 basis points",
+        module: "62_collateral_double_counting",
+        program: "collateral_hub",
+    },
+    CustomError {
+        code: 6001,
+        name: "ZeroAmount",
+        message: "Amount must be above zero",
+        module: "62_collateral_double_counting",
+        program: "collateral_hub",
+    },
+    CustomError {
+        code: 6002,
+        name: "ExceedsBorrowLimit",
+        message: "Borrowing this much would exceed the loan's collateral limit",
+        module: "62_collateral_double_counting",
+        program: "collateral_hub",
+    },
+    CustomError {
+        code: 6003,
+        name: "CollateralLocked",
+        message: "Receipt is already locked as collateral for a loan",
+        module: "62_collateral_double_counting",
+        program: "collateral_hub",
+    },
+    CustomError {
+        code: 6004,
+        name: "CollateralNotLocked",
+        message: "Receipt is not locked as collateral for this loan",
+        module: "62_collateral_double_counting",
+        program: "collateral_hub",
+    },
+    CustomError {
+        code: 6005,
+        name: "OutstandingDebt",
+        message: "Loan must be repaid before its collateral is unlocked",
+        module: "62_collateral_double_counting",
+        program: "collateral_hub",
+    },
+    CustomError {
+        code: 6006,
+        name: "InsufficientLiquidity",
+        message: "Pool does not have enough lamports to lend",
+        module: "62_collateral_double_counting",
+        program: "collateral_hub",
+    },
+    CustomError {
+        code: 6007,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "62_collateral_double_counting",
+        program: "collateral_hub",
+    },
+    CustomError {
+        code: 6000,
+        name: "ZeroAmount",
+        message: "Payment must be more than zero lamports",
+        module: "63_fee_bps_misconfiguration",
+        program: "checkout",
+    },
+    CustomError {
+        code: 6001,
+        name: "NoFeeUpdateProposed",
+        message: "No fee update has been proposed",
+        module: "63_fee_bps_misconfiguration",
+        program: "checkout",
+    },
+    CustomError {
+        code: 6002,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "63_fee_bps_misconfiguration",
+        program: "checkout",
+    },
+    CustomError {
+        code: 6000,
+        name: "ZeroAmount",
+        message: "Amount must be more than zero",
+        module: "64_config_hot_swap",
+        program: "redemption_desk",
+    },
+    CustomError {
+        code: 6001,
+        name: "ZeroPrice",
+        message: "Price must be more than zero lamports",
+        module: "64_config_hot_swap",
+        program: "redemption_desk",
+    },
+    CustomError {
+        code: 6002,
+        name: "InsufficientCredits",
+        message: "Position holds fewer credits than requested",
+        module: "64_config_hot_swap",
+        program: "redemption_desk",
+    },
+    CustomError {
+        code: 6003,
+        name: "RedemptionPending",
+        message: "A redemption is already in flight",
+        module: "64_config_hot_swap",
+        program: "redemption_desk",
+    },
+    CustomError {
+        code: 6004,
+        name: "NoRedemption",
+        message: "No redemption is in flight",
+        module: "64_config_hot_swap",
+        program: "redemption_desk",
+    },
+    CustomError {
+        code: 6005,
+        name: "RedemptionNotReady",
+        message: "Redemption cannot be settled yet",
+        module: "64_config_hot_swap",
+        program: "redemption_desk",
+    },
+    CustomError {
+        code: 6006,
+        name: "InsufficientReserve",
+        message: "Desk holds too few lamports to pay the redemption",
+        module: "64_config_hot_swap",
+        program: "redemption_desk",
+    },
+    CustomError {
+        code: 6007,
+        name: "NoConfigChangeProposed",
+        message: "No config change has been proposed",
+        module: "64_config_hot_swap",
+        program: "redemption_desk",
+    },
+    CustomError {
+        code: 6008,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "64_config_hot_swap",
+        program: "redemption_desk",
+    },
+    CustomError {
+        code: 6000,
+        name: "ZeroAmount",
+        message: "Amount must be more than zero",
+        module: "65_withdraw_queue",
+        program: "withdraw_queue",
+    },
+    CustomError {
+        code: 6001,
+        name: "InsufficientDeposit",
+        message: "Position holds less than requested",
+        module: "65_withdraw_queue",
+        program: "withdraw_queue",
+    },
+    CustomError {
+        code: 6002,
+        name: "InsufficientLiquidity",
+        message: "Vault holds too few idle lamports",
+        module: "65_withdraw_queue",
+        program: "withdraw_queue",
+    },
+    CustomError {
+        code: 6003,
+        name: "TicketAlreadyPaid",
+        message: "Ticket has already been paid",
+        module: "65_withdraw_queue",
+        program: "withdraw_queue",
+    },
+    CustomError {
+        code: 6004,
+        name: "TicketOutOfOrder",
+        message: "Request is not the next in the queue",
+        module: "65_withdraw_queue",
+        program: "withdraw_queue",
+    },
+    CustomError {
+        code: 6005,
+        name: "RequestVaultMismatch",
+        message: "Request belongs to another vault",
+        module: "65_withdraw_queue",
+        program: "withdraw_queue",
+    },
+    CustomError {
+        code: 6006,
+        name: "RequestOwnerMismatch",
+        message: "Request must be followed by its owner",
+        module: "65_withdraw_queue",
+        program: "withdraw_queue",
+    },
+    CustomError {
+        code: 6007,
+        name: "EmptyPage",
+        message: "No requests to process",
+        module: "65_withdraw_queue",
+        program: "withdraw_queue",
+    },
+    CustomError {
+        code: 6008,
+        name: "InvalidPage",
+        message: "A page holds at most MAX_PAGE requests, each followed by its owner",
+        module: "65_withdraw_queue",
+        program: "withdraw_queue",
+    },
+    CustomError {
+        code: 6009,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "65_withdraw_queue",
+        program: "withdraw_queue",
+    },
+    CustomError {
+        code: 6000,
+        name: "ZeroDeposit",
+        message: "Deposit must be greater than zero",
+        module: "67_position_receipts",
+        program: "receipt_vault",
+    },
+    CustomError {
+        code: 6001,
+        name: "ReceiptNotHeld",
+        message: "Token account does not hold the position's receipt",
+        module: "67_position_receipts",
+        program: "receipt_vault",
+    },
+    CustomError {
+        code: 6002,
+        name: "ReceiptSupplyMismatch",
+        message: "Receipt mint supply is not exactly one",
+        module: "67_position_receipts",
+        program: "receipt_vault",
+    },
+    CustomError {
+        code: 6003,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "67_position_receipts",
+        program: "receipt_vault",
+    },
+    CustomError {
+        code: 7200,
+        name: "TokenBalanceTooLow",
+        message: "Token account holds less than asserted",
+        module: "shared/secref-assertions",
+        program: "secref_assertions",
+    },
+    CustomError {
+        code: 7201,
+        name: "OwnerMismatch",
+        message: "Account is not owned by the asserted program",
+        module: "shared/secref-assertions",
+        program: "secref_assertions",
+    },
+    CustomError {
+        code: 7202,
+        name: "DataHashMismatch",
+        message: "Account data does not hash to the asserted value",
+        module: "shared/secref-assertions",
+        program: "secref_assertions",
+    },
+    CustomError {
+        code: 7203,
+        name: "DataRangeOutOfBounds",
+        message: "Asserted data range is past the end of the account",
+        module: "shared/secref-assertions",
+        program: "secref_assertions",
+    },
+    CustomError {
+        code: 7000,
+        name: "NotExecutable",
+        message: "Account is not an executable program",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7001,
+        name: "NotLoaderOwned",
+        message: "Program account is not owned by a BPF loader",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7002,
+        name: "UnexpectedProgram",
+        message: "Program account does not match the expected program id",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7003,
+        name: "ProgramDataMismatch",
+        message: "Program data account does not belong to this program",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7004,
+        name: "ProgramUpgradeable",
+        message: "Program can still be upgraded",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7005,
+        name: "InvalidProgramAccount",
+        message: "Program account data could not be parsed",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7006,
+        name: "InvalidIntentDelay",
+        message: "Intent delay must be non-negative and representable",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7007,
+        name: "IntentNotReady",
+        message: "Intent delay has not passed",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7008,
+        name: "IntentParamsMismatch",
+        message: "Parameters do not match the prepared intent",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7009,
+        name: "NotAssociatedTokenAccount",
+        message: "Token account is not the associated token account of the wallet and mint",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7010,
+        name: "TokenOwnerMismatch",
+        message: "Token account is owned by another wallet",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7011,
+        name: "TokenMintMismatch",
+        message: "Token account holds another mint",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7012,
+        name: "TokenAccountFrozen",
+        message: "Token account is frozen",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7013,
+        name: "UnexpectedDelegate",
+        message: "Token account has a delegate",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7014,
+        name: "UnexpectedCloseAuthority",
+        message: "Token account has a close authority other than its owner",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7015,
+        name: "NotYetReclaimable",
+        message: "State has not expired, so its rent cannot be reclaimed yet",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7016,
+        name: "AccountNotClosed",
+        message: "Account has not been closed",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7017,
+        name: "NotVoteAccount",
+        message: "Account is not owned by the Vote program",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7018,
+        name: "InvalidVoteAccount",
+        message: "Vote account data could not be parsed",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7019,
+        name: "CommissionTooHigh",
+        message: "Validator's commission is above the allowed maximum",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7020,
+        name: "MemoMissing",
+        message: "No Memo instruction immediately precedes this instruction",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7021,
+        name: "MemoMismatch",
+        message: "Memo does not carry the expected reference",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7022,
+        name: "ConflictingMemo",
+        message: "Another Memo instruction in the transaction carries a different reference",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7023,
+        name: "InvalidVelocityLimits",
+        message: "Velocity limits must allow some movement, per transaction no more than per window",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7024,
+        name: "TransactionLimitExceeded",
+        message: "Amount is over the per-transaction limit",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7025,
+        name: "WindowLimitExceeded",
+        message: "Amount would exceed the limit for the current window",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7026,
+        name: "NotTokenMint",
+        message: "Account is not a mint of the Token or Token-2022 program",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7027,
+        name: "UnsupportedMintExtension",
+        message: "Mint carries an extension this program does not handle",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7028,
+        name: "MissingMintExtension",
+        message: "Mint does not carry the extension",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7029,
+        name: "InvalidMintExtension",
+        message: "Mint extension data could not be parsed",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7030,
+        name: "ForeignExtensionPointer",
+        message: "Mint's extension pointer names another account",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7031,
+        name: "ExtensionMintMismatch",
+        message: "Extension data belongs to another mint",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7032,
+        name: "NotGroupMember",
+        message: "Mint is not a member of the expected group",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7033,
+        name: "GroupAuthorityMismatch",
+        message: "Group adds members with another update authority",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7034,
+        name: "ComputeBudgetMissing",
+        message: "Transaction does not set a compute unit limit",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7035,
+        name: "DuplicateComputeBudget",
+        message: "Transaction sets its compute unit limit more than once",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7036,
+        name: "InvalidComputeBudget",
+        message: "ComputeBudget instruction data could not be parsed",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7037,
+        name: "ComputeBudgetTooLow",
+        message: "Requested compute unit limit is below the minimum",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7038,
+        name: "ComputeBudgetShared",
+        message: "An instruction before this one spends the transaction's compute budget",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7039,
+        name: "NotRentSysvar",
+        message: "Account is not the Rent sysvar",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7040,
+        name: "InvalidUiAmount",
+        message: "UI amount is not a plain decimal number",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7041,
+        name: "TooManyDecimals",
+        message: "UI amount has more fractional digits than the mint's decimals",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7042,
+        name: "AmountOverflow",
+        message: "Amount does not fit in a u64 of base units",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7043,
+        name: "DecimalsMismatch",
+        message: "Amounts count the units of mints with different decimals",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7044,
+        name: "InvalidStateTransition",
+        message: "Lifecycle does not allow this transition from the current state",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7045,
+        name: "UnexpectedState",
+        message: "Account is not in the state this instruction acts on",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7046,
+        name: "BpsOutOfRange",
+        message: "Basis points are above the allowed maximum",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7047,
+        name: "UnknownVersion",
+        message: "Instruction data carries a version this program does not know",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7048,
+        name: "MalformedVersionedData",
+        message: "Instruction data is not exactly the layout of its version",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7049,
+        name: "PdaMismatch",
+        message: "Account is not the program address of its seeds and bump",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7050,
+        name: "NonCanonicalBump",
+        message: "Bump is valid for its seeds but not the canonical one",
+        module: "shared/secref-guards",
+        program: "secref_guards",
+    },
+    CustomError {
+        code: 7100,
+        name: "NameTooLong",
+        message: "Registry name is too long",
+        module: "shared/secref-registry",
+        program: "secref_registry",
+    },
+    CustomError {
+        code: 7101,
+        name: "EntryAddressMismatch",
+        message: "Entry account is not the registry's PDA for this key",
+        module: "shared/secref-registry",
+        program: "secref_registry",
+    },
+    CustomError {
+        code: 7102,
+        name: "NotRegistered",
+        message: "Key is not registered",
+        module: "shared/secref-registry",
+        program: "secref_registry",
+    },
+    CustomError {
+        code: 7103,
+        name: "ArithmeticOverflow",
+        message: "Arithmetic overflow",
+        module: "shared/secref-registry",
+        program: "secref_registry",
+    },
+];
@@ -0,0 +1,261 @@
+//! Error codes explained: what failed, which vulnerable pattern it usually stops, and where to read about it.
+//!
+//! A failed transaction reports a number - `custom program error: 0x7d1`,
+//! or Anchor's `Error Number: 2001` - and at best a name. [`explain`]
+//! turns the number into the [`Cause`]s it can have: Anchor's own errors
+//! (constraints, `require!`, account (de)serialization), and every
+//! `#[error_code]` enum in the repository. Each cause links to the module
+//! ids (example directories, as in `secref-curriculum`) that show the check
+//! doing its job:
+//!
+//! ```
+//! let explanation = secref_errors::explain(2001);
+//! let cause = &explanation.causes[0];
+//! assert_eq!(cause.name, "ConstraintHasOne");
+//! assert!(cause.modules.contains(&"01_missing_account_validation"));
+//! ```
+//!
+//! Custom codes start at 6000 in every program, so one number can be
+//! dozens of errors. [`Explanation::named`] narrows them by the name
+//! Anchor logs next to the number, and [`annotate`] reads both from a
+//! failed transaction's logs. [`to_json`] exports the table for tools
+//! outside Rust, such as `harness/secref-logs.ts`.
+
+pub mod anchor;
+mod custom;
+
+use std::fmt;
+use std::sync::OnceLock;
+
+use secref_curriculum::lessons::LESSONS;
+
+/// Where an error is defined
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Origin {
+    /// Anchor itself
+    Anchor,
+    /// An `#[error_code]` enum of a program or shared crate in this repository
+    Program {
+        /// Example directory (`03_unsafe_cpi`) or shared crate (`shared/secref-guards`)
+        module: &'static str,
+        /// Crate name, e.g. `unsafe_cpi`
+        program: &'static str,
+    },
+}
+
+/// One error a code can stand for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cause {
+    pub origin: Origin,
+    /// Variant name, as Anchor logs it
+    pub name: &'static str,
+    /// The `#[msg]` text
+    pub message: &'static str,
+    /// What went wrong, in plain words
+    pub cause: &'static str,
+    /// The vulnerable pattern the check most often stops; `None` for client and build mistakes
+    pub pattern: Option<&'static str>,
+    /// Module ids that show the check doing its job
+    pub modules: &'static [&'static str],
+}
+
+/// A custom error as `tests/generated.rs` finds it in the sources
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct CustomError {
+    pub code: u32,
+    pub name: &'static str,
+    pub message: &'static str,
+    pub module: &'static str,
+    pub program: &'static str,
+}
+
+/// Every cause a code can have
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Explanation {
+    pub code: u32,
+    /// Empty if nothing in the repository raises the code
+    pub causes: Vec<Cause>,
+}
+
+impl Explanation {
+    /// Keep only the causes called `name`, as Anchor logs it with the code
+    pub fn named(mut self, name: &str) -> Self {
+        self.causes.retain(|cause| cause.name == name);
+        self
+    }
+
+    pub fn is_known(&self) -> bool {
+        !self.causes.is_empty()
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.causes.is_empty() {
+            return write!(f, "error {}: not raised by Anchor or any program here", self.code);
+        }
+        for (index, cause) in self.causes.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            match cause.origin {
+                Origin::Anchor => write!(f, "error {} {} (Anchor): {}", self.code, cause.name, cause.cause)?,
+                Origin::Program { program, .. } => {
+                    write!(f, "error {} {} ({program}): {}", self.code, cause.name, cause.cause)?
+                }
+            }
+            if let Some(pattern) = cause.pattern {
+                write!(f, "\n  likely: {pattern}")?;
+            }
+            if !cause.modules.is_empty() {
+                write!(f, "\n  see: {}", cause.modules.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Every cause of `code`: Anchor's error if it is one, and every program error numbered `code`
+pub fn explain(code: u32) -> Explanation {
+    let framework = framework().iter().filter(|error| error.code == code).map(|error| Cause {
+        origin: Origin::Anchor,
+        name: &error.name,
+        message: &error.message,
+        cause: error.about.cause,
+        pattern: error.about.pattern,
+        modules: error.about.modules,
+    });
+    let programs = custom::ERRORS.iter().filter(|error| error.code == code).map(custom_cause);
+    Explanation {
+        code,
+        causes: framework.chain(programs).collect(),
+    }
+}
+
+/// The explanation of the error a failed transaction's logs report, if they report one
+///
+/// Reads Anchor's `Error Code: <name>. Error Number: <code>.` line, which
+/// names the error as well as numbering it, or failing that the runtime's
+/// `custom program error: 0x..`, which only numbers it. The first error
+/// logged is the one that failed the transaction; a caller whose CPI
+/// failed only passes it on.
+pub fn annotate<S: AsRef<str>>(logs: &[S]) -> Option<Explanation> {
+    let logs = || logs.iter().map(AsRef::as_ref);
+    let anchor = logs().find_map(|line| {
+        let rest = line.split("Error Code: ").nth(1)?;
+        let (name, rest) = rest.split_once(". Error Number: ")?;
+        let code = rest.split('.').next()?.parse().ok()?;
+        Some(explain(code).named(name))
+    });
+    anchor.or_else(|| {
+        logs().find_map(|line| {
+            let hex = line.split("custom program error: 0x").nth(1)?;
+            let digits: String = hex.chars().take_while(char::is_ascii_hexdigit).collect();
+            u32::from_str_radix(&digits, 16).ok().map(explain)
+        })
+    })
+}
+
+/// Every cause of every code, as a JSON array in code order
+pub fn to_json() -> String {
+    let mut codes: Vec<u32> = framework()
+        .iter()
+        .map(|error| error.code)
+        .chain(custom::ERRORS.iter().map(|error| error.code))
+        .collect();
+    codes.sort_unstable();
+    codes.dedup();
+
+    let causes: Vec<String> = codes
+        .into_iter()
+        .flat_map(|code| explain(code).causes.into_iter().map(move |cause| cause_json(code, &cause)))
+        .collect();
+    format!("[\n{}\n]\n", causes.join(",\n"))
+}
+
+fn cause_json(code: u32, cause: &Cause) -> String {
+    let (module, program) = match cause.origin {
+        Origin::Anchor => ("null".to_string(), "null".to_string()),
+        Origin::Program { module, program } => (json_string(module), json_string(program)),
+    };
+    let modules: Vec<String> = cause.modules.iter().map(|module| json_string(module)).collect();
+    format!(
+        "{{\"code\":{code},\"name\":{},\"message\":{},\"cause\":{},\"pattern\":{},\
+         \"module\":{module},\"program\":{program},\"modules\":[{}]}}",
+        json_string(cause.name),
+        json_string(cause.message),
+        json_string(cause.cause),
+        cause.pattern.map_or_else(|| "null".to_string(), json_string),
+        modules.join(","),
+    )
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// What an error of a program or shared crate means, from where it is defined
+fn custom_cause(error: &'static CustomError) -> Cause {
+    let lesson = LESSONS.iter().find(|lesson| lesson.id == error.module);
+    Cause {
+        origin: Origin::Program {
+            module: error.module,
+            program: error.program,
+        },
+        name: error.name,
+        message: error.message,
+        // The message is the program's own account of the failure
+        cause: error.message,
+        pattern: match lesson {
+            Some(lesson) => Some(lesson.title),
+            None => shared_pattern(error.module),
+        },
+        modules: match lesson {
+            Some(_) => std::slice::from_ref(&error.module),
+            None => &[],
+        },
+    }
+}
+
+/// The pattern behind errors of a shared crate, which many examples raise
+fn shared_pattern(module: &str) -> Option<&'static str> {
+    match module {
+        "shared/secref-guards" => Some("An account the secure path validates with a shared guard"),
+        "shared/secref-registry" => Some("A key trusted without checking it against the registry"),
+        "shared/secref-assertions" => Some("An earlier instruction left an account other than the signer expected"),
+        _ => None,
+    }
+}
+
+/// An Anchor error with its name and message, which Anchor only hands out as owned strings
+struct Framework {
+    code: u32,
+    name: String,
+    message: String,
+    about: anchor::About,
+}
+
+fn framework() -> &'static [Framework] {
+    static TABLE: OnceLock<Vec<Framework>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        anchor::ERRORS
+            .iter()
+            .map(|&error| Framework {
+                code: error.into(),
+                name: error.name(),
+                message: error.to_string(),
+                about: anchor::describe(error),
+            })
+            .collect()
+    })
+}
@@ -0,0 +1,84 @@
+//! Codes as programs really raise them, and as failed transactions log them
+
+use anchor_lang::error::ErrorCode as AnchorError;
+use secref_errors::{annotate, explain, Origin};
+
+#[test]
+fn anchor_errors_are_explained_with_their_lessons() {
+    let explanation = explain(AnchorError::ConstraintSeeds.into());
+    assert_eq!(explanation.causes.len(), 1);
+    let cause = explanation.causes[0];
+    assert_eq!(cause.origin, Origin::Anchor);
+    assert_eq!(cause.name, "ConstraintSeeds");
+    assert!(cause.pattern.is_some());
+    assert!(!cause.modules.is_empty());
+}
+
+#[test]
+fn program_errors_are_found_by_their_real_codes() {
+    let vault = explain(vault::ErrorCode::InsufficientFunds.into()).named("InsufficientFunds");
+    assert_eq!(
+        vault.causes[0].origin,
+        Origin::Program {
+            module: "01_missing_account_validation",
+            program: "vault",
+        }
+    );
+    assert_eq!(vault.causes[0].message, "Insufficient funds in vault");
+    assert_eq!(vault.causes[0].modules, ["01_missing_account_validation"]);
+
+    let cpi = explain(unsafe_cpi::ErrorCode::Unauthorized.into()).named("Unauthorized");
+    assert!(cpi
+        .causes
+        .iter()
+        .any(|cause| cause.origin == Origin::Program { module: "03_unsafe_cpi", program: "unsafe_cpi" }));
+}
+
+#[test]
+fn colliding_custom_codes_keep_every_candidate() {
+    let explanation = explain(6000);
+    assert!(explanation.causes.len() > 1);
+    assert!(explanation.causes.iter().all(|cause| matches!(cause.origin, Origin::Program { .. })));
+}
+
+#[test]
+fn shared_crate_errors_have_their_offsets() {
+    let explanation = explain(7200);
+    assert!(explanation.causes.iter().any(|cause| cause.origin
+        == Origin::Program {
+            module: "shared/secref-assertions",
+            program: "secref_assertions",
+        }));
+}
+
+#[test]
+fn unknown_codes_have_no_causes() {
+    let explanation = explain(4_000_000);
+    assert!(!explanation.is_known());
+    assert_eq!(explanation.to_string(), "error 4000000: not raised by Anchor or any program here");
+}
+
+#[test]
+fn annotate_reads_the_name_anchor_logs() {
+    let logs = [
+        "Program Vault111111111111111111111111111111111111 invoke [1]",
+        "Program log: Instruction: Withdraw",
+        "Program log: AnchorError occurred. Error Code: InsufficientFunds. Error Number: 6000. \
+         Error Message: Insufficient funds in vault.",
+        "Program Vault111111111111111111111111111111111111 failed: custom program error: 0x1770",
+    ];
+    let explanation = annotate(&logs).unwrap();
+    assert_eq!(explanation.code, 6000);
+    assert!(explanation.causes.iter().all(|cause| cause.name == "InsufficientFunds"));
+    assert!(explanation.to_string().contains("see: 01_missing_account_validation"));
+}
+
+#[test]
+fn annotate_falls_back_to_the_runtime_code() {
+    let logs = ["Program Vault111111111111111111111111111111111111 failed: custom program error: 0x7d1"];
+    let explanation = annotate(&logs).unwrap();
+    assert_eq!(explanation.code, 2001);
+    assert_eq!(explanation.causes[0].name, "ConstraintHasOne");
+
+    assert!(annotate(&["Program log: Instruction: Withdraw"]).is_none());
+}
@@ -0,0 +1,215 @@
+//! The checked-in files generated from the sources are current.
+//!
+//! `src/custom.rs` lists every `#[error_code]` variant in the repository's
+//! programs and shared crates, and `harness/error-explanations.json` is
+//! `to_json()` for the log parser. Both are compared with what the sources
+//! give now; rewrite them after changing an error enum with:
+//!
+//! ```bash
+//! SECREF_BLESS=1 cargo test --manifest-path shared/secref-errors/Cargo.toml
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// One variant as written: its code, name and `#[msg]` literal, still escaped
+struct Variant {
+    code: u32,
+    name: String,
+    message: String,
+}
+
+/// Where an enum's codes start without `offset = ..`
+const DEFAULT_OFFSET: u32 = 6000;
+
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+/// Directories in `dir`, by name
+fn subdirectories(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).filter(|path| path.is_dir()).collect();
+    dirs.sort();
+    dirs
+}
+
+/// Every `.rs` file under `dir`, by path
+fn rust_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for path in subdirectories(dir) {
+        files.extend(rust_files(&path));
+    }
+    if let Ok(entries) = fs::read_dir(dir) {
+        files.extend(
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "rs")),
+        );
+    }
+    files.sort();
+    files
+}
+
+/// `(module, program, src)` for every program and shared crate that can define errors
+fn crates() -> Vec<(String, String, PathBuf)> {
+    let root = root();
+    let name = |path: &Path| path.file_name().unwrap().to_string_lossy().into_owned();
+    let mut crates = Vec::new();
+    for example in subdirectories(&root) {
+        let module = name(&example);
+        if !module.starts_with(|c: char| c.is_ascii_digit()) {
+            continue;
+        }
+        for program in subdirectories(&example.join("programs")) {
+            crates.push((module.clone(), name(&program), program.join("src")));
+        }
+    }
+    for shared in subdirectories(&root.join("shared")) {
+        let module = format!("shared/{}", name(&shared));
+        crates.push((module.clone(), name(&shared).replace('-', "_"), shared.join("src")));
+        for program in subdirectories(&shared.join("programs")) {
+            crates.push((module.clone(), name(&program), program.join("src")));
+        }
+    }
+    crates
+}
+
+/// The variants of every `#[error_code]` enum in `source`
+fn variants(source: &str) -> Vec<Variant> {
+    let mut variants = Vec::new();
+    let mut lines = source.lines().map(str::trim);
+    while let Some(line) = lines.next() {
+        let Some(attribute) = line.strip_prefix("#[error_code") else {
+            continue;
+        };
+        let mut code = attribute
+            .split("offset = ")
+            .nth(1)
+            .and_then(|rest| rest.trim_end_matches(")]").trim().parse().ok())
+            .unwrap_or(DEFAULT_OFFSET);
+        lines.by_ref().find(|line| line.contains("enum "));
+
+        let mut message = None;
+        for line in lines.by_ref() {
+            let line = line.split("//").next().unwrap().trim();
+            if line == "}" {
+                break;
+            }
+            if let Some(literal) = line.strip_prefix("#[msg(\"").and_then(|rest| rest.strip_suffix("\")]")) {
+                message = Some(literal.to_string());
+                continue;
+            }
+            if line.is_empty() || line.starts_with("#[") {
+                continue;
+            }
+            let variant = line.trim_end_matches(',');
+            let name = match variant.split_once('=') {
+                Some((name, value)) => {
+                    code = value.trim().parse().expect("an explicit code is a number");
+                    name.trim()
+                }
+                None => variant,
+            };
+            // Without `#[msg]`, Anchor's message is the variant name
+            let message = message.take().unwrap_or_else(|| name.to_string());
+            variants.push(Variant {
+                code,
+                name: name.to_string(),
+                message,
+            });
+            code += 1;
+        }
+    }
+    variants
+}
+
+/// `src/custom.rs` as the sources give it now
+fn custom_table() -> String {
+    let mut table = String::from(
+        "//! Every `#[error_code]` variant in the repository, by module and program.\n\
+         //!\n\
+         //! Generated by `tests/generated.rs` from the sources; rewrite it after\n\
+         //! changing an error enum with\n\
+         //! `SECREF_BLESS=1 cargo test --manifest-path shared/secref-errors/Cargo.toml`.\n\
+         \n\
+         use crate::CustomError;\n\
+         \n\
+         pub(crate) static ERRORS: &[CustomError] = &[\n",
+    );
+    for (module, program, src) in crates() {
+        for file in rust_files(&src) {
+            let source = fs::read_to_string(&file).unwrap();
+            for variant in variants(&source) {
+                table.push_str(&format!(
+                    "    CustomError {{\n        code: {},\n        name: \"{}\",\n        message: \"{}\",\n        \
+                     module: \"{module}\",\n        program: \"{program}\",\n    }},\n",
+                    variant.code, variant.name, variant.message
+                ));
+            }
+        }
+    }
+    table.push_str("];\n");
+    table
+}
+
+/// Compare `path` with `expected`, or rewrite it under `SECREF_BLESS`
+fn check(path: &Path, expected: &str) {
+    if std::env::var_os("SECREF_BLESS").is_some() {
+        fs::write(path, expected).unwrap();
+        return;
+    }
+    let actual = fs::read_to_string(path).unwrap_or_default();
+    assert!(
+        actual == expected,
+        "{} is stale; rewrite it with SECREF_BLESS=1 cargo test --manifest-path shared/secref-errors/Cargo.toml",
+        path.display()
+    );
+}
+
+#[test]
+fn custom_table_lists_every_error_enum() {
+    check(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src/custom.rs"), &custom_table());
+}
+
+#[test]
+fn json_export_is_current() {
+    check(&root().join("harness/error-explanations.json"), &secref_errors::to_json());
+}
+
+#[test]
+fn variants_are_numbered_from_the_offset() {
+    let source = r#"
+        #[error_code(offset = 7100)]
+        pub enum RegistryError {
+            #[msg("Registry name is too long")]
+            NameTooLong,
+            /// Undocumented
+            Unlabelled, // trailing comment
+            Jump = 7200,
+            #[msg("After the \"jump\"")]
+            AfterJump
+        }
+
+        #[error_code]
+        pub enum ErrorCode {
+            #[msg("Insufficient funds in vault")]
+            InsufficientFunds,
+        }
+    "#;
+    let variants = variants(source);
+    let found: Vec<(u32, &str, &str)> = variants.iter().map(|v| (v.code, &v.name[..], &v.message[..])).collect();
+    assert_eq!(
+        found,
+        [
+            (7100, "NameTooLong", "Registry name is too long"),
+            (7101, "Unlabelled", "Unlabelled"),
+            (7200, "Jump", "Jump"),
+            (7201, "AfterJump", r#"After the \"jump\""#),
+            (6000, "InsufficientFunds", "Insufficient funds in vault"),
+        ]
+    );
+}
@@ -0,0 +1,97 @@
+//! Account creation that cannot be blocked by pre-funding.
+//!
+//! `system_program::create_account` fails if the new address already holds
+//! any lamports. PDA and seed-derived addresses are predictable, so anyone
+//! can transfer one lamport to the address first and make every later
+//! `create_account` fail - a permanent denial of service for that address.
+//!
+//! [`create_pda_account`] falls back to `transfer` + `allocate` + `assign`
+//! when the address is already funded, which is what Anchor's `init` does
+//! internally. Use it wherever accounts are created by hand.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount};
+
+use crate::rent::{minimum_balance, top_up_rent};
+
+/// Lamports `payer` must add to an account holding `current` to reach `required`
+pub fn top_up_amount(current: u64, required: u64) -> u64 {
+    required.saturating_sub(current)
+}
+
+/// Create `new_account` with `space` bytes owned by `owner`, even if it was pre-funded
+///
+/// `new_account` must be a PDA of the calling program signed for by
+/// `signer_seeds`. An address that already has data or a non-System owner
+/// is a genuine collision and is left to fail in `allocate` / `assign`.
+pub fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    new_account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    owner: &Pubkey,
+    space: usize,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let required = minimum_balance(space)?;
+    let current = new_account.lamports();
+
+    if current == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: new_account.clone(),
+                },
+                signer_seeds,
+            ),
+            required,
+            space as u64,
+            owner,
+        );
+    }
+
+    // Someone funded the address first: top it up, then allocate and assign
+    // it ourselves. Only the PDA's signature is needed for the last two.
+    top_up_rent(payer, new_account, system_program, space)?;
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: new_account.clone(),
+            },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign {
+                account_to_assign: new_account.clone(),
+            },
+            signer_seeds,
+        ),
+        owner,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unfunded_accounts_need_the_full_balance() {
+        assert_eq!(top_up_amount(0, 1_000), 1_000);
+    }
+
+    #[test]
+    fn pre_funded_accounts_need_only_the_difference() {
+        assert_eq!(top_up_amount(1, 1_000), 999);
+    }
+
+    #[test]
+    fn over_funded_accounts_need_nothing() {
+        assert_eq!(top_up_amount(5_000, 1_000), 0);
+    }
+}
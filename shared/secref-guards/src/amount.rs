@@ -0,0 +1,230 @@
+//! Token amounts that carry their decimals: raw base units vs UI amounts.
+//!
+//! On-chain every token amount is a `u64` count of base units; "1.5 USDC"
+//! is `1_500_000` because USDC has 6 decimals. Wallets and frontends show
+//! UI amounts, and the two are a factor of `10^decimals` apart. Mixing
+//! them up - a client sending `1` meaning one token, a handler pricing
+//! base units as if every mint had 9 decimals like SOL - is an error by a
+//! power of ten that nothing catches, because both are just `u64`s.
+//!
+//! An [`Amount`] keeps the count and the decimals together. Build it from
+//! whichever side you hold, and convert with checked arithmetic:
+//!
+//! ```ignore
+//! use secref_guards::amount::Amount;
+//!
+//! // Client: what the user typed, in the mint's units
+//! let amount = Amount::from_ui("1.5", mint.decimals)?;
+//! program.methods.buy(amount.raw());
+//!
+//! // Handler: the argument is raw, the price is per whole token
+//! let amount = Amount::from_raw(amount, ctx.accounts.mint.decimals);
+//! let cost = amount.value_ceil(sale.price_per_token)?;
+//! ```
+
+use std::fmt;
+
+use anchor_lang::prelude::*;
+
+use crate::error::GuardError;
+
+/// Most decimals an amount can have: `10^19` is the largest power of ten in a `u64`
+pub const MAX_DECIMALS: u8 = 19;
+
+/// Base units in one whole token of a mint with `decimals` decimals
+pub fn scale(decimals: u8) -> Result<u64> {
+    require!(decimals <= MAX_DECIMALS, GuardError::AmountOverflow);
+    Ok(10u64.pow(u32::from(decimals)))
+}
+
+/// A token amount in base units, with the decimals of its mint
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Amount {
+    raw: u64,
+    decimals: u8,
+}
+
+impl Amount {
+    /// `raw` base units, as stored in token accounts and passed to the Token program
+    pub fn from_raw(raw: u64, decimals: u8) -> Self {
+        Self { raw, decimals }
+    }
+
+    /// `whole` tokens, `whole * 10^decimals` base units
+    pub fn from_whole(whole: u64, decimals: u8) -> Result<Self> {
+        let raw = whole.checked_mul(scale(decimals)?).ok_or(GuardError::AmountOverflow)?;
+        Ok(Self { raw, decimals })
+    }
+
+    /// A UI amount such as `"1.5"`, with no more fractional digits than `decimals`
+    ///
+    /// Only digits and one decimal point are accepted, with digits on both
+    /// sides of it. Digits the mint cannot represent are an error rather
+    /// than rounded away.
+    pub fn from_ui(ui: &str, decimals: u8) -> Result<Self> {
+        let (whole, fraction) = ui.split_once('.').unwrap_or((ui, "0"));
+        let digits = |part: &str| !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit());
+        require!(digits(whole) && digits(fraction), GuardError::InvalidUiAmount);
+
+        let fraction = fraction.trim_end_matches('0');
+        require!(fraction.len() <= usize::from(decimals), GuardError::TooManyDecimals);
+
+        let whole: u64 = whole.parse().map_err(|_| GuardError::AmountOverflow)?;
+        let fraction = if fraction.is_empty() {
+            0
+        } else {
+            // At most `decimals` digits, so within 10^MAX_DECIMALS once `scale` has passed
+            fraction.parse::<u64>().map_err(|_| GuardError::AmountOverflow)?
+                * scale(decimals - fraction.len() as u8)?
+        };
+        let raw = Self::from_whole(whole, decimals)?
+            .raw
+            .checked_add(fraction)
+            .ok_or(GuardError::AmountOverflow)?;
+        Ok(Self { raw, decimals })
+    }
+
+    /// Base units
+    pub fn raw(self) -> u64 {
+        self.raw
+    }
+
+    /// Decimals of the mint this amount counts
+    pub fn decimals(self) -> u8 {
+        self.decimals
+    }
+
+    /// `self + other`, which must count the same mint's units
+    pub fn checked_add(self, other: Self) -> Result<Self> {
+        require_eq!(self.decimals, other.decimals, GuardError::DecimalsMismatch);
+        let raw = self.raw.checked_add(other.raw).ok_or(GuardError::AmountOverflow)?;
+        Ok(Self { raw, ..self })
+    }
+
+    /// `self - other`, which must count the same mint's units
+    pub fn checked_sub(self, other: Self) -> Result<Self> {
+        require_eq!(self.decimals, other.decimals, GuardError::DecimalsMismatch);
+        let raw = self.raw.checked_sub(other.raw).ok_or(GuardError::AmountOverflow)?;
+        Ok(Self { raw, ..self })
+    }
+
+    /// What this amount costs at `price` per whole token, rounded down
+    pub fn value_floor(self, price: u64) -> Result<u64> {
+        let scale = u128::from(scale(self.decimals)?);
+        let value = u128::from(self.raw) * u128::from(price) / scale;
+        u64::try_from(value).map_err(|_| GuardError::AmountOverflow.into())
+    }
+
+    /// What this amount costs at `price` per whole token, rounded up
+    ///
+    /// Charge a buyer with this one, so no purchase of a fraction of a
+    /// whole token rounds down to free.
+    pub fn value_ceil(self, price: u64) -> Result<u64> {
+        let scale = u128::from(scale(self.decimals)?);
+        let value = (u128::from(self.raw) * u128::from(price)).div_ceil(scale);
+        u64::try_from(value).map_err(|_| GuardError::AmountOverflow.into())
+    }
+}
+
+/// The UI amount, with every digit the mint has: `1.500000` for 1.5 USDC
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let decimals = usize::from(self.decimals);
+        let digits = format!("{:0>width$}", self.raw, width = decimals + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - decimals);
+        if fraction.is_empty() {
+            write!(f, "{whole}")
+        } else {
+            write!(f, "{whole}.{fraction}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// USDC's decimals
+    const USDC: u8 = 6;
+
+    #[test]
+    fn ui_amounts_scale_by_the_mints_decimals() {
+        assert_eq!(Amount::from_ui("1.5", USDC).unwrap().raw(), 1_500_000);
+        assert_eq!(Amount::from_ui("1.5", 9).unwrap().raw(), 1_500_000_000);
+        assert_eq!(Amount::from_ui("0.000001", USDC).unwrap().raw(), 1);
+        assert_eq!(Amount::from_ui("42", 0).unwrap().raw(), 42);
+        assert_eq!(Amount::from_whole(1_000, USDC).unwrap(), Amount::from_ui("1000.000", USDC).unwrap());
+    }
+
+    #[test]
+    fn ui_amounts_must_be_plain_decimals() {
+        for ui in ["", ".5", "1.", "-1", "+1", "1e6", "1,5", " 1", "1.2.3"] {
+            assert_eq!(
+                Amount::from_ui(ui, USDC),
+                Err(GuardError::InvalidUiAmount.into()),
+                "{ui:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn ui_amounts_keep_every_digit_or_fail() {
+        assert_eq!(Amount::from_ui("0.0000001", USDC), Err(GuardError::TooManyDecimals.into()));
+        assert_eq!(Amount::from_ui("1.5", 0), Err(GuardError::TooManyDecimals.into()));
+        // Trailing zeros lose nothing
+        assert_eq!(Amount::from_ui("1.50000000", USDC).unwrap().raw(), 1_500_000);
+    }
+
+    #[test]
+    fn amounts_past_u64_fail() {
+        assert_eq!(Amount::from_ui("18446744073709.551616", USDC), Err(GuardError::AmountOverflow.into()));
+        assert_eq!(Amount::from_ui("18446744073709.551615", USDC).unwrap().raw(), u64::MAX);
+        assert_eq!(Amount::from_whole(1, MAX_DECIMALS + 1), Err(GuardError::AmountOverflow.into()));
+        assert_eq!(
+            Amount::from_raw(u64::MAX, USDC).checked_add(Amount::from_raw(1, USDC)),
+            Err(GuardError::AmountOverflow.into())
+        );
+    }
+
+    #[test]
+    fn arithmetic_needs_matching_decimals() {
+        let usdc = Amount::from_raw(5, USDC);
+        assert_eq!(usdc.checked_add(usdc).unwrap().raw(), 10);
+        assert_eq!(usdc.checked_sub(usdc).unwrap().raw(), 0);
+        assert_eq!(
+            usdc.checked_add(Amount::from_raw(5, 9)),
+            Err(GuardError::DecimalsMismatch.into())
+        );
+        assert_eq!(
+            usdc.checked_sub(Amount::from_raw(6, USDC)),
+            Err(GuardError::AmountOverflow.into())
+        );
+    }
+
+    #[test]
+    fn values_are_priced_per_whole_token() {
+        let price = 100_000_000; // 0.1 SOL per token
+        let thousand = Amount::from_whole(1_000, USDC).unwrap();
+        assert_eq!(thousand.value_floor(price).unwrap(), 100_000_000_000);
+        assert_eq!(thousand.value_ceil(price).unwrap(), 100_000_000_000);
+
+        // At 0.0005 SOL per token a base unit is worth half a lamport: free rounded down, one lamport rounded up
+        let dust = Amount::from_raw(1, USDC);
+        assert_eq!(dust.value_floor(500_000).unwrap(), 0);
+        assert_eq!(dust.value_ceil(500_000).unwrap(), 1);
+
+        assert_eq!(
+            Amount::from_raw(u64::MAX, 0).value_ceil(2),
+            Err(GuardError::AmountOverflow.into())
+        );
+    }
+
+    #[test]
+    fn amounts_display_as_ui_amounts() {
+        assert_eq!(Amount::from_raw(1_500_000, USDC).to_string(), "1.500000");
+        assert_eq!(Amount::from_raw(1, USDC).to_string(), "0.000001");
+        assert_eq!(Amount::from_raw(42, 0).to_string(), "42");
+        let amount = Amount::from_raw(123_456_789, 9);
+        assert_eq!(Amount::from_ui(&amount.to_string(), 9).unwrap(), amount);
+    }
+}
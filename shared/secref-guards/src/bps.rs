@@ -0,0 +1,135 @@
+//! Basis points that are never more than the whole.
+//!
+//! Fees, loan-to-value ratios and shares are configured in basis points,
+//! hundredths of a percent, and stored as a `u16` - a type that holds up
+//! to 65,535, or 655%. Nothing about a `u16` says 10,000 is the ceiling,
+//! so a setter that forgets to check accepts a fee larger than the amount
+//! it is charged on, and every handler that applies it later has to hope
+//! someone did.
+//!
+//! A [`BoundedBps`] is checked once, when it is built - against the whole
+//! and, with [`BoundedBps::at_most`], against a tighter cap of the
+//! program's - and applies itself to amounts without overflow:
+//!
+//! ```ignore
+//! use secref_guards::bps::BoundedBps;
+//!
+//! // Config update: out of range is an error, not a stored value
+//! let fee = BoundedBps::at_most(fee_bps, MAX_FEE_BPS)?;
+//!
+//! // Payment: never more than `amount`
+//! let charged = fee.apply_ceil(amount);
+//! ```
+
+use std::fmt;
+
+use anchor_lang::prelude::*;
+
+use crate::error::GuardError;
+
+/// Basis points in the whole: 100%
+pub const BPS: u16 = 10_000;
+
+/// A share of a whole, in basis points, from 0 to [`BPS`]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BoundedBps(u16);
+
+impl BoundedBps {
+    /// Serialized size, for `space = 8 + ... + BoundedBps::SPACE`: the `u16` it wraps
+    pub const SPACE: usize = 2;
+
+    /// Nothing
+    pub const ZERO: Self = Self(0);
+
+    /// The whole
+    pub const MAX: Self = Self(BPS);
+
+    /// `bps` basis points, which must be at most the whole
+    pub fn new(bps: u16) -> Result<Self> {
+        Self::at_most(bps, BPS)
+    }
+
+    /// `bps` basis points, which must be at most `cap` and at most the whole
+    pub fn at_most(bps: u16, cap: u16) -> Result<Self> {
+        require!(bps <= cap.min(BPS), GuardError::BpsOutOfRange);
+        Ok(Self(bps))
+    }
+
+    /// Basis points
+    pub fn get(self) -> u16 {
+        self.0
+    }
+
+    /// This share of `amount`, rounded down; at most `amount`
+    pub fn apply_floor(self, amount: u64) -> u64 {
+        let share = u128::from(amount) * u128::from(self.0) / u128::from(BPS);
+        // At most `amount`, since the share is at most the whole
+        share as u64
+    }
+
+    /// This share of `amount`, rounded up; at most `amount`
+    ///
+    /// Charge fees with this one, so no payment is too small to pay any.
+    pub fn apply_ceil(self, amount: u64) -> u64 {
+        let share = (u128::from(amount) * u128::from(self.0)).div_ceil(u128::from(BPS));
+        share as u64
+    }
+}
+
+/// As a percentage with every digit basis points have: `2.50%` for 250
+impl fmt::Display for BoundedBps {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:02}%", self.0 / 100, self.0 % 100)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bps_are_at_most_the_whole() {
+        assert_eq!(BoundedBps::new(0).unwrap(), BoundedBps::ZERO);
+        assert_eq!(BoundedBps::new(BPS).unwrap(), BoundedBps::MAX);
+        for bps in [BPS + 1, 65_535] {
+            assert_eq!(BoundedBps::new(bps), Err(GuardError::BpsOutOfRange.into()), "{bps}");
+        }
+    }
+
+    #[test]
+    fn a_cap_tightens_the_bound_but_never_loosens_it() {
+        assert_eq!(BoundedBps::at_most(500, 500).unwrap().get(), 500);
+        assert_eq!(BoundedBps::at_most(501, 500), Err(GuardError::BpsOutOfRange.into()));
+        assert_eq!(BoundedBps::at_most(BPS + 1, u16::MAX), Err(GuardError::BpsOutOfRange.into()));
+    }
+
+    #[test]
+    fn shares_never_exceed_the_amount() {
+        let fee = BoundedBps::new(250).unwrap();
+        assert_eq!(fee.apply_floor(1_000_000), 25_000);
+        assert_eq!(fee.apply_ceil(1_000_000), 25_000);
+
+        // 2.5% of 1 is 0.025: nothing rounded down, one unit rounded up
+        assert_eq!(fee.apply_floor(1), 0);
+        assert_eq!(fee.apply_ceil(1), 1);
+
+        assert_eq!(BoundedBps::MAX.apply_floor(u64::MAX), u64::MAX);
+        assert_eq!(BoundedBps::MAX.apply_ceil(u64::MAX), u64::MAX);
+        assert_eq!(BoundedBps::ZERO.apply_ceil(u64::MAX), 0);
+    }
+
+    #[test]
+    fn bps_serialize_as_the_u16_they_replace() {
+        let fee = BoundedBps::new(250).unwrap();
+        let bytes = fee.try_to_vec().unwrap();
+        assert_eq!(bytes, 250u16.to_le_bytes());
+        assert_eq!(bytes.len(), BoundedBps::SPACE);
+    }
+
+    #[test]
+    fn bps_display_as_percentages() {
+        assert_eq!(BoundedBps::new(250).unwrap().to_string(), "2.50%");
+        assert_eq!(BoundedBps::new(5).unwrap().to_string(), "0.05%");
+        assert_eq!(BoundedBps::MAX.to_string(), "100.00%");
+    }
+}
@@ -0,0 +1,253 @@
+//! Guards reading the compute budget a transaction requests.
+//!
+//! A handler that does as much work as the compute meter allows, and
+//! saves its place when the meter runs low, lets its caller decide how
+//! much work gets done: the caller picks the limit. These guards read the
+//! ComputeBudget program's `SetComputeUnitLimit` instruction through the
+//! instructions sysvar, so a handler can refuse to start without enough
+//! units to finish a step. The limit is shared by the whole transaction,
+//! so the units are only the handler's if nothing runs before it.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+
+use crate::error::GuardError;
+
+/// The ComputeBudget program, `ComputeBudget111111111111111111111111111111`, which the runtime
+/// reads before the transaction executes; spelled in bytes, as `pubkey!` differs between Anchor releases
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    3, 6, 70, 111, 229, 33, 23, 50, 255, 236, 173, 186, 114, 195, 155, 231, 188, 140, 229, 187, 197, 247, 18, 107, 44, 67,
+    155, 58, 64, 0, 0, 0,
+]);
+
+/// The most compute units a transaction can request; the runtime lowers larger requests to this
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// `ComputeBudgetInstruction::SetComputeUnitLimit`'s Borsh variant index
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+
+/// A `SetComputeUnitLimit` instruction for `units`, as clients build it
+pub fn set_compute_unit_limit(units: u32) -> Instruction {
+    let mut data = vec![SET_COMPUTE_UNIT_LIMIT];
+    data.extend_from_slice(&units.to_le_bytes());
+    Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &data, vec![])
+}
+
+/// The compute unit limit the transaction sets, as the runtime applies it, or `None` if it sets none
+///
+/// The runtime refuses a transaction that sets the limit twice; reading
+/// the first and ignoring the second would be guessing, so this fails
+/// with `DuplicateComputeBudget` too. `instructions` must be the
+/// instructions sysvar; any other account fails.
+pub fn requested_compute_unit_limit(instructions: &AccountInfo) -> Result<Option<u32>> {
+    // Checks the sysvar's address, which reading past the last instruction would not
+    load_current_index_checked(instructions)?;
+    let mut limit = None;
+    let mut index = 0;
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions) {
+        index += 1;
+        if instruction.program_id != COMPUTE_BUDGET_PROGRAM_ID
+            || instruction.data.first() != Some(&SET_COMPUTE_UNIT_LIMIT)
+        {
+            continue;
+        }
+        let units: [u8; 4] = instruction.data[1..]
+            .try_into()
+            .map_err(|_| GuardError::InvalidComputeBudget)?;
+        require!(limit.is_none(), GuardError::DuplicateComputeBudget);
+        limit = Some(u32::from_le_bytes(units).min(MAX_COMPUTE_UNIT_LIMIT));
+    }
+    Ok(limit)
+}
+
+/// Require at least `minimum` compute units for the current instruction, which must be `program_id`'s own
+///
+/// Only ComputeBudget instructions may come before it, since anything
+/// else spends the same limit first; instructions after it run once it
+/// has finished. Returns the limit requested.
+pub fn assert_compute_unit_limit(instructions: &AccountInfo, program_id: &Pubkey, minimum: u32) -> Result<u32> {
+    let limit = requested_compute_unit_limit(instructions)?.ok_or(GuardError::ComputeBudgetMissing)?;
+    require!(limit >= minimum, GuardError::ComputeBudgetTooLow);
+
+    let current = load_current_index_checked(instructions)?;
+    // A CPI from another program shares the budget of that program's instruction
+    let instruction = load_instruction_at_checked(usize::from(current), instructions)?;
+    require_keys_eq!(instruction.program_id, *program_id, GuardError::ComputeBudgetShared);
+    for index in 0..current {
+        let instruction = load_instruction_at_checked(usize::from(index), instructions)?;
+        require_keys_eq!(
+            instruction.program_id,
+            COMPUTE_BUDGET_PROGRAM_ID,
+            GuardError::ComputeBudgetShared
+        );
+    }
+    Ok(limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::sysvar::{
+        self,
+        instructions::{construct_instructions_data, store_current_index, BorrowedAccountMeta, BorrowedInstruction},
+    };
+
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestAccount {
+        /// The instructions sysvar of a transaction executing `instructions[current]`
+        fn instructions(instructions: &[Instruction], current: u16) -> Self {
+            let borrowed: Vec<_> = instructions
+                .iter()
+                .map(|instruction| BorrowedInstruction {
+                    program_id: &instruction.program_id,
+                    accounts: instruction
+                        .accounts
+                        .iter()
+                        .map(|meta| BorrowedAccountMeta {
+                            pubkey: &meta.pubkey,
+                            is_signer: meta.is_signer,
+                            is_writable: meta.is_writable,
+                        })
+                        .collect(),
+                    data: &instruction.data,
+                })
+                .collect();
+            let mut data = construct_instructions_data(&borrowed);
+            store_current_index(&mut data, current);
+            Self {
+                key: sysvar::instructions::ID,
+                owner: sysvar::ID,
+                lamports: 1,
+                data,
+            }
+        }
+
+        fn info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                false,
+                false,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    const PROGRAM: Pubkey = pubkey!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+    fn crank() -> Instruction {
+        Instruction::new_with_bytes(PROGRAM, &[1], vec![])
+    }
+
+    /// `SetComputeUnitPrice`, which sets the fee and not the limit
+    fn set_price(micro_lamports: u64) -> Instruction {
+        let mut data = vec![3];
+        data.extend_from_slice(&micro_lamports.to_le_bytes());
+        Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &data, vec![])
+    }
+
+    #[test]
+    fn names_the_compute_budget_program() {
+        assert_eq!(
+            COMPUTE_BUDGET_PROGRAM_ID.to_string(),
+            "ComputeBudget111111111111111111111111111111"
+        );
+    }
+
+    #[test]
+    fn reads_the_requested_limit() {
+        let mut sysvar = TestAccount::instructions(&[set_price(1), set_compute_unit_limit(300_000), crank()], 2);
+        assert_eq!(requested_compute_unit_limit(&sysvar.info()).unwrap(), Some(300_000));
+        assert_eq!(assert_compute_unit_limit(&sysvar.info(), &PROGRAM, 300_000).unwrap(), 300_000);
+
+        // Past the maximum, the runtime grants the maximum
+        let mut sysvar = TestAccount::instructions(&[set_compute_unit_limit(u32::MAX), crank()], 1);
+        assert_eq!(
+            requested_compute_unit_limit(&sysvar.info()).unwrap(),
+            Some(MAX_COMPUTE_UNIT_LIMIT)
+        );
+    }
+
+    #[test]
+    fn rejects_a_missing_or_insufficient_limit() {
+        let mut sysvar = TestAccount::instructions(&[set_price(1), crank()], 1);
+        assert_eq!(requested_compute_unit_limit(&sysvar.info()).unwrap(), None);
+        assert_eq!(
+            assert_compute_unit_limit(&sysvar.info(), &PROGRAM, 300_000),
+            Err(GuardError::ComputeBudgetMissing.into())
+        );
+
+        let mut sysvar = TestAccount::instructions(&[set_compute_unit_limit(299_999), crank()], 1);
+        assert_eq!(
+            assert_compute_unit_limit(&sysvar.info(), &PROGRAM, 300_000),
+            Err(GuardError::ComputeBudgetTooLow.into())
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_or_malformed_limits() {
+        let mut sysvar = TestAccount::instructions(
+            &[set_compute_unit_limit(10_000), set_compute_unit_limit(300_000), crank()],
+            2,
+        );
+        assert_eq!(
+            requested_compute_unit_limit(&sysvar.info()),
+            Err(GuardError::DuplicateComputeBudget.into())
+        );
+
+        // Placed after the current instruction, a second limit is just as ambiguous
+        let mut sysvar = TestAccount::instructions(
+            &[set_compute_unit_limit(300_000), crank(), set_compute_unit_limit(10_000)],
+            1,
+        );
+        assert_eq!(
+            assert_compute_unit_limit(&sysvar.info(), &PROGRAM, 300_000),
+            Err(GuardError::DuplicateComputeBudget.into())
+        );
+
+        let truncated = Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &[SET_COMPUTE_UNIT_LIMIT, 0xe0], vec![]);
+        let mut sysvar = TestAccount::instructions(&[truncated, crank()], 1);
+        assert_eq!(
+            requested_compute_unit_limit(&sysvar.info()),
+            Err(GuardError::InvalidComputeBudget.into())
+        );
+    }
+
+    #[test]
+    fn rejects_a_limit_spent_by_another_instruction() {
+        let other = Instruction::new_with_bytes(Pubkey::new_unique(), &[1], vec![]);
+        let mut sysvar = TestAccount::instructions(&[set_compute_unit_limit(300_000), other.clone(), crank()], 2);
+        assert_eq!(
+            assert_compute_unit_limit(&sysvar.info(), &PROGRAM, 300_000),
+            Err(GuardError::ComputeBudgetShared.into())
+        );
+
+        // Another program's instruction, calling this one
+        let mut sysvar = TestAccount::instructions(&[set_compute_unit_limit(300_000), other.clone()], 1);
+        assert_eq!(
+            assert_compute_unit_limit(&sysvar.info(), &PROGRAM, 300_000),
+            Err(GuardError::ComputeBudgetShared.into())
+        );
+
+        // Instructions after this one run with whatever it leaves
+        let mut sysvar = TestAccount::instructions(&[set_compute_unit_limit(300_000), crank(), other], 1);
+        assert!(assert_compute_unit_limit(&sysvar.info(), &PROGRAM, 300_000).is_ok());
+    }
+
+    #[test]
+    fn rejects_an_account_posing_as_the_sysvar() {
+        let mut sysvar = TestAccount::instructions(&[set_compute_unit_limit(300_000), crank()], 1);
+        sysvar.key = Pubkey::new_unique();
+        assert!(requested_compute_unit_limit(&sysvar.info()).is_err());
+    }
+}
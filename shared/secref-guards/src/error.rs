@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+
+/// Errors returned by the guards
+///
+/// Offset from the default 6000 so guard failures never collide with an
+/// example's own `ErrorCode` variants.
+#[error_code(offset = 7000)]
+pub enum GuardError {
+    #[msg("Account is not an executable program")]
+    NotExecutable,
+    #[msg("Program account is not owned by a BPF loader")]
+    NotLoaderOwned,
+    #[msg("Program account does not match the expected program id")]
+    UnexpectedProgram,
+    #[msg("Program data account does not belong to this program")]
+    ProgramDataMismatch,
+    #[msg("Program can still be upgraded")]
+    ProgramUpgradeable,
+    #[msg("Program account data could not be parsed")]
+    InvalidProgramAccount,
+    #[msg("Intent delay must be non-negative and representable")]
+    InvalidIntentDelay,
+    #[msg("Intent delay has not passed")]
+    IntentNotReady,
+    #[msg("Parameters do not match the prepared intent")]
+    IntentParamsMismatch,
+    #[msg("Token account is not the associated token account of the wallet and mint")]
+    NotAssociatedTokenAccount,
+    #[msg("Token account is owned by another wallet")]
+    TokenOwnerMismatch,
+    #[msg("Token account holds another mint")]
+    TokenMintMismatch,
+    #[msg("Token account is frozen")]
+    TokenAccountFrozen,
+    #[msg("Token account has a delegate")]
+    UnexpectedDelegate,
+    #[msg("Token account has a close authority other than its owner")]
+    UnexpectedCloseAuthority,
+    #[msg("State has not expired, so its rent cannot be reclaimed yet")]
+    NotYetReclaimable,
+    #[msg("Account has not been closed")]
+    AccountNotClosed,
+    #[msg("Account is not owned by the Vote program")]
+    NotVoteAccount,
+    #[msg("Vote account data could not be parsed")]
+    InvalidVoteAccount,
+    #[msg("Validator's commission is above the allowed maximum")]
+    CommissionTooHigh,
+    #[msg("No Memo instruction immediately precedes this instruction")]
+    MemoMissing,
+    #[msg("Memo does not carry the expected reference")]
+    MemoMismatch,
+    #[msg("Another Memo instruction in the transaction carries a different reference")]
+    ConflictingMemo,
+    #[msg("Velocity limits must allow some movement, per transaction no more than per window")]
+    InvalidVelocityLimits,
+    #[msg("Amount is over the per-transaction limit")]
+    TransactionLimitExceeded,
+    #[msg("Amount would exceed the limit for the current window")]
+    WindowLimitExceeded,
+    #[msg("Account is not a mint of the Token or Token-2022 program")]
+    NotTokenMint,
+    #[msg("Mint carries an extension this program does not handle")]
+    UnsupportedMintExtension,
+    #[msg("Mint does not carry the extension")]
+    MissingMintExtension,
+    #[msg("Mint extension data could not be parsed")]
+    InvalidMintExtension,
+    #[msg("Mint's extension pointer names another account")]
+    ForeignExtensionPointer,
+    #[msg("Extension data belongs to another mint")]
+    ExtensionMintMismatch,
+    #[msg("Mint is not a member of the expected group")]
+    NotGroupMember,
+    #[msg("Group adds members with another update authority")]
+    GroupAuthorityMismatch,
+    #[msg("Transaction does not set a compute unit limit")]
+    ComputeBudgetMissing,
+    #[msg("Transaction sets its compute unit limit more than once")]
+    DuplicateComputeBudget,
+    #[msg("ComputeBudget instruction data could not be parsed")]
+    InvalidComputeBudget,
+    #[msg("Requested compute unit limit is below the minimum")]
+    ComputeBudgetTooLow,
+    #[msg("An instruction before this one spends the transaction's compute budget")]
+    ComputeBudgetShared,
+    #[msg("Account is not the Rent sysvar")]
+    NotRentSysvar,
+    #[msg("UI amount is not a plain decimal number")]
+    InvalidUiAmount,
+    #[msg("UI amount has more fractional digits than the mint's decimals")]
+    TooManyDecimals,
+    #[msg("Amount does not fit in a u64 of base units")]
+    AmountOverflow,
+    #[msg("Amounts count the units of mints with different decimals")]
+    DecimalsMismatch,
+    #[msg("Lifecycle does not allow this transition from the current state")]
+    InvalidStateTransition,
+    #[msg("Account is not in the state this instruction acts on")]
+    UnexpectedState,
+    #[msg("Basis points are above the allowed maximum")]
+    BpsOutOfRange,
+    #[msg("Instruction data carries a version this program does not know")]
+    UnknownVersion,
+    #[msg("Instruction data is not exactly the layout of its version")]
+    MalformedVersionedData,
+    #[msg("Account is not the program address of its seeds and bump")]
+    PdaMismatch,
+    #[msg("Bump is valid for its seeds but not the canonical one")]
+    NonCanonicalBump,
+}
@@ -0,0 +1,143 @@
+//! Two-phase commit state for sensitive operations.
+//!
+//! A prepare instruction records an [`Intent`] - a hash of the exact
+//! parameters plus the earliest time they may run - and an execute
+//! instruction later checks the parameters it was given against that hash.
+//! The delay gives users and monitors time to see the pending operation;
+//! the hash makes sure what runs is what they saw.
+//!
+//! Embed `Intent` in a program's own account and size it with
+//! [`Intent::SPACE`]:
+//!
+//! ```ignore
+//! use secref_guards::intent::{hash_params, Intent};
+//!
+//! // prepare
+//! let params = hash_params(&[&amount.to_le_bytes(), destination.as_ref()]);
+//! pending.intent = Intent::prepare(admin.key(), params, Clock::get()?.unix_timestamp, DELAY)?;
+//!
+//! // execute
+//! let params = hash_params(&[&amount.to_le_bytes(), destination.key().as_ref()]);
+//! pending.intent.assert_executable(&params, Clock::get()?.unix_timestamp)?;
+//! ```
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::error::GuardError;
+
+/// A prepared operation: who prepared it, what it does, and when it may run
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Intent {
+    /// Signer that prepared the operation (32 bytes)
+    pub proposer: Pubkey,
+    /// `hash_params` of the operation's parameters (32 bytes)
+    pub params_hash: [u8; 32],
+    /// Unix timestamp of the prepare instruction (8 bytes)
+    pub prepared_at: i64,
+    /// Earliest unix timestamp at which the operation may execute (8 bytes)
+    pub executable_at: i64,
+}
+
+impl Intent {
+    /// Serialized size, for `space = 8 + ... + Intent::SPACE`
+    pub const SPACE: usize = 32 + 32 + 8 + 8; // proposer + params_hash + prepared_at + executable_at
+
+    /// Record `params_hash`, executable `delay_seconds` after `now`
+    pub fn prepare(proposer: Pubkey, params_hash: [u8; 32], now: i64, delay_seconds: i64) -> Result<Self> {
+        require!(delay_seconds >= 0, GuardError::InvalidIntentDelay);
+        let executable_at = now.checked_add(delay_seconds).ok_or(GuardError::InvalidIntentDelay)?;
+        Ok(Self {
+            proposer,
+            params_hash,
+            prepared_at: now,
+            executable_at,
+        })
+    }
+
+    /// Require the delay to have passed and `params_hash` to be the prepared one
+    pub fn assert_executable(&self, params_hash: &[u8; 32], now: i64) -> Result<()> {
+        require!(now >= self.executable_at, GuardError::IntentNotReady);
+        require!(*params_hash == self.params_hash, GuardError::IntentParamsMismatch);
+        Ok(())
+    }
+}
+
+/// Hash an operation's parameters, in order, for an [`Intent`]
+///
+/// Pass every value the execute instruction acts on - amounts, destination
+/// keys, new settings. Anything left out can be changed between prepare and
+/// execute.
+pub fn hash_params(params: &[&[u8]]) -> [u8; 32] {
+    hashv(params).to_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DELAY: i64 = 3_600;
+
+    fn withdrawal(amount: u64, destination: &Pubkey) -> [u8; 32] {
+        hash_params(&[&amount.to_le_bytes(), destination.as_ref()])
+    }
+
+    #[test]
+    fn executes_prepared_params_after_the_delay() {
+        let destination = Pubkey::new_unique();
+        let intent = Intent::prepare(Pubkey::new_unique(), withdrawal(100, &destination), 1_000, DELAY).unwrap();
+        assert_eq!(intent.executable_at, 1_000 + DELAY);
+        assert!(intent
+            .assert_executable(&withdrawal(100, &destination), 1_000 + DELAY)
+            .is_ok());
+    }
+
+    #[test]
+    fn rejects_execution_before_the_delay() {
+        let destination = Pubkey::new_unique();
+        let intent = Intent::prepare(Pubkey::new_unique(), withdrawal(100, &destination), 1_000, DELAY).unwrap();
+        assert_eq!(
+            intent
+                .assert_executable(&withdrawal(100, &destination), 1_000 + DELAY - 1)
+                .unwrap_err(),
+            GuardError::IntentNotReady.into()
+        );
+    }
+
+    #[test]
+    fn rejects_changed_params() {
+        let destination = Pubkey::new_unique();
+        let intent = Intent::prepare(Pubkey::new_unique(), withdrawal(100, &destination), 1_000, DELAY).unwrap();
+        let now = 1_000 + DELAY;
+        assert_eq!(
+            intent
+                .assert_executable(&withdrawal(101, &destination), now)
+                .unwrap_err(),
+            GuardError::IntentParamsMismatch.into()
+        );
+        assert_eq!(
+            intent
+                .assert_executable(&withdrawal(100, &Pubkey::new_unique()), now)
+                .unwrap_err(),
+            GuardError::IntentParamsMismatch.into()
+        );
+    }
+
+    #[test]
+    fn rejects_negative_or_overflowing_delays() {
+        let params = withdrawal(100, &Pubkey::new_unique());
+        assert_eq!(
+            Intent::prepare(Pubkey::new_unique(), params, 1_000, -1).unwrap_err(),
+            GuardError::InvalidIntentDelay.into()
+        );
+        assert_eq!(
+            Intent::prepare(Pubkey::new_unique(), params, i64::MAX, 1).unwrap_err(),
+            GuardError::InvalidIntentDelay.into()
+        );
+    }
+
+    #[test]
+    fn space_matches_serialized_size() {
+        assert_eq!(Intent::default().try_to_vec().unwrap().len(), Intent::SPACE);
+    }
+}
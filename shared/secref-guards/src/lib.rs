@@ -0,0 +1,38 @@
+//! Reusable account validation guards for the security reference examples.
+//!
+//! Each guard checks one property and returns a [`GuardError`] naming the
+//! property that failed, so secure handlers read as a list of requirements:
+//!
+//! ```ignore
+//! use secref_guards::program_account::assert_immutable_program;
+//!
+//! assert_immutable_program(&ctx.accounts.plugin, &ctx.accounts.plugin_program_data)?;
+//! ```
+
+pub mod account_creation;
+pub mod amount;
+pub mod bps;
+pub mod compute_budget;
+pub mod error;
+pub mod intent;
+#[cfg(feature = "memo")]
+pub mod memo;
+#[cfg(feature = "token-2022")]
+pub mod mint_extension;
+#[cfg(feature = "token-2022")]
+pub mod mint_pointer;
+pub mod pda;
+pub mod program_account;
+pub mod rent;
+pub mod state_machine;
+#[cfg(feature = "spl")]
+pub mod token_account;
+pub mod velocity;
+pub mod versioned;
+pub mod vote_account;
+
+pub use error::GuardError;
+
+// The derives name `::secref_guards`; let them resolve in this crate's own tests
+#[cfg(test)]
+extern crate self as secref_guards;
@@ -0,0 +1,162 @@
+//! Guards binding a Memo program instruction to the instruction it labels.
+//!
+//! A payment reference travels as its own Memo instruction, which the
+//! paying program never sees unless it looks. A `memo` argument repeating
+//! the reference is only what the client chose to send; the merchant's
+//! indexer reads the Memo instruction. These guards read it too, through
+//! the instructions sysvar: the Memo instruction immediately before the
+//! current one must carry the expected reference, and no other memo in the
+//! transaction may carry a different one, since indexers report a
+//! transaction's memos joined together.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::memo::spl_memo;
+
+use crate::error::GuardError;
+
+/// The Memo program and its v1 deployment, which indexers also read
+pub const MEMO_PROGRAM_IDS: [Pubkey; 2] = [spl_memo::ID, spl_memo::v1::ID];
+
+/// The data of the Memo instruction immediately before the current one
+///
+/// `instructions` must be the instructions sysvar; any other account fails.
+pub fn preceding_memo(instructions: &AccountInfo) -> Result<Vec<u8>> {
+    let current = load_current_index_checked(instructions)?;
+    require!(current > 0, GuardError::MemoMissing);
+    let instruction = load_instruction_at_checked(usize::from(current - 1), instructions)?;
+    require!(MEMO_PROGRAM_IDS.contains(&instruction.program_id), GuardError::MemoMissing);
+    Ok(instruction.data)
+}
+
+/// Require the memo immediately before the current instruction, and every other memo in the transaction, to be `expected`
+pub fn assert_memo_attached(instructions: &AccountInfo, expected: &[u8]) -> Result<()> {
+    require!(preceding_memo(instructions)? == expected, GuardError::MemoMismatch);
+
+    let mut index = 0;
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions) {
+        require!(
+            !MEMO_PROGRAM_IDS.contains(&instruction.program_id) || instruction.data == expected,
+            GuardError::ConflictingMemo
+        );
+        index += 1;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::instruction::Instruction;
+    use anchor_lang::solana_program::sysvar::{
+        self,
+        instructions::{construct_instructions_data, store_current_index, BorrowedAccountMeta, BorrowedInstruction},
+    };
+
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestAccount {
+        /// The instructions sysvar of a transaction executing `instructions[current]`
+        fn instructions(instructions: &[Instruction], current: u16) -> Self {
+            let borrowed: Vec<_> = instructions
+                .iter()
+                .map(|instruction| BorrowedInstruction {
+                    program_id: &instruction.program_id,
+                    accounts: instruction
+                        .accounts
+                        .iter()
+                        .map(|meta| BorrowedAccountMeta {
+                            pubkey: &meta.pubkey,
+                            is_signer: meta.is_signer,
+                            is_writable: meta.is_writable,
+                        })
+                        .collect(),
+                    data: &instruction.data,
+                })
+                .collect();
+            let mut data = construct_instructions_data(&borrowed);
+            store_current_index(&mut data, current);
+            Self {
+                key: sysvar::instructions::ID,
+                owner: sysvar::ID,
+                lamports: 1,
+                data,
+            }
+        }
+
+        fn info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                false,
+                false,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    fn memo(text: &str) -> Instruction {
+        spl_memo::build_memo(text.as_bytes(), &[])
+    }
+
+    fn pay() -> Instruction {
+        Instruction::new_with_bytes(Pubkey::new_unique(), &[1], vec![])
+    }
+
+    #[test]
+    fn accepts_the_expected_memo_right_before() {
+        let mut sysvar = TestAccount::instructions(&[memo("INV-7"), pay()], 1);
+        assert_eq!(preceding_memo(&sysvar.info()).unwrap(), b"INV-7");
+        assert!(assert_memo_attached(&sysvar.info(), b"INV-7").is_ok());
+
+        let v1 = Instruction::new_with_bytes(spl_memo::v1::ID, b"INV-7", vec![]);
+        let mut sysvar = TestAccount::instructions(&[v1, pay()], 1);
+        assert!(assert_memo_attached(&sysvar.info(), b"INV-7").is_ok());
+    }
+
+    #[test]
+    fn rejects_a_missing_or_misplaced_memo() {
+        for (instructions, current) in [
+            (vec![pay()], 0),
+            (vec![pay(), memo("INV-7")], 0),
+            (vec![memo("INV-7"), pay(), pay()], 2),
+        ] {
+            let mut sysvar = TestAccount::instructions(&instructions, current);
+            assert_eq!(
+                assert_memo_attached(&sysvar.info(), b"INV-7"),
+                Err(GuardError::MemoMissing.into())
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_another_reference() {
+        let mut sysvar = TestAccount::instructions(&[memo("INV-8"), pay()], 1);
+        assert_eq!(
+            assert_memo_attached(&sysvar.info(), b"INV-7"),
+            Err(GuardError::MemoMismatch.into())
+        );
+
+        // The right memo, and a second one an indexer would also read
+        let mut sysvar = TestAccount::instructions(&[memo("INV-7"), pay(), memo("INV-8")], 1);
+        assert_eq!(
+            assert_memo_attached(&sysvar.info(), b"INV-7"),
+            Err(GuardError::ConflictingMemo.into())
+        );
+    }
+
+    #[test]
+    fn rejects_an_account_posing_as_the_sysvar() {
+        let mut sysvar = TestAccount::instructions(&[memo("INV-7"), pay()], 1);
+        sysvar.key = Pubkey::new_unique();
+        assert!(assert_memo_attached(&sysvar.info(), b"INV-7").is_err());
+    }
+}
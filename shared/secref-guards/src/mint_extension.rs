@@ -0,0 +1,127 @@
+//! Guards for the extensions of the Token-2022 mints a program accepts.
+//!
+//! A program written for SPL Token mints sees the same `Mint` and
+//! `TokenAccount` fields from a Token-2022 mint, whatever extensions it
+//! carries. Some extensions change what those fields mean: with
+//! `ConfidentialTransferMint`, a token account's `amount` is only its
+//! public balance, and confidential transfers credit an encrypted pending
+//! balance no program can read. Others run code, charge fees, or let an
+//! authority move or freeze tokens.
+//!
+//! New extensions keep being added, so the guard takes the list of those a
+//! program handles and rejects every other, rather than a list of those
+//! known to be dangerous.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022::state::Mint;
+
+use crate::error::GuardError;
+
+/// The extensions `mint` carries: none for an SPL Token mint
+pub fn mint_extensions(mint: &AccountInfo) -> Result<Vec<ExtensionType>> {
+    if *mint.owner == anchor_spl::token::ID {
+        return Ok(Vec::new());
+    }
+    require_keys_eq!(*mint.owner, anchor_spl::token_2022::ID, GuardError::NotTokenMint);
+
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<Mint>::unpack(&data)?;
+    Ok(state.get_extension_types()?)
+}
+
+/// Require every extension of `mint` to be one of `supported`
+///
+/// An SPL Token mint, and a Token-2022 mint without extensions, always pass.
+pub fn assert_mint_extensions(mint: &AccountInfo, supported: &[ExtensionType]) -> Result<()> {
+    for extension in mint_extensions(mint)? {
+        if !supported.contains(&extension) {
+            msg!("Unsupported mint extension: {:?}", extension);
+            return err!(GuardError::UnsupportedMintExtension);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_spl::token::spl_token::solana_program::program_option::COption;
+    use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+    use anchor_spl::token_2022::spl_token_2022::extension::confidential_transfer::ConfidentialTransferMint;
+    use anchor_spl::token_2022::spl_token_2022::extension::metadata_pointer::MetadataPointer;
+    use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensionsMut, StateWithExtensionsMut};
+
+    fn base() -> Mint {
+        Mint {
+            mint_authority: COption::Some(Pubkey::new_unique()),
+            supply: 1_000,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        }
+    }
+
+    /// A Token-2022 mint with `extensions`, each left at its default
+    fn token_2022_mint(extensions: &[ExtensionType]) -> Vec<u8> {
+        let mut data = vec![0; ExtensionType::try_calculate_account_len::<Mint>(extensions).unwrap()];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        for extension in extensions {
+            match extension {
+                ExtensionType::ConfidentialTransferMint => {
+                    state.init_extension::<ConfidentialTransferMint>(true).unwrap();
+                }
+                ExtensionType::MetadataPointer => {
+                    state.init_extension::<MetadataPointer>(true).unwrap();
+                }
+                other => panic!("{other:?} is not built by these tests"),
+            }
+        }
+        state.base = base();
+        state.pack_base();
+        state.init_account_type().unwrap();
+        data
+    }
+
+    fn check(owner: Pubkey, mut data: Vec<u8>, supported: &[ExtensionType]) -> Result<()> {
+        let (key, mut lamports) = (Pubkey::new_unique(), 1);
+        let mint = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        assert_mint_extensions(&mint, supported)
+    }
+
+    #[test]
+    fn accepts_mints_without_extensions() {
+        let mut spl_mint = vec![0; Mint::LEN];
+        base().pack_into_slice(&mut spl_mint);
+        assert!(check(anchor_spl::token::ID, spl_mint.clone(), &[]).is_ok());
+        assert!(check(anchor_spl::token_2022::ID, spl_mint, &[]).is_ok());
+        assert!(check(anchor_spl::token_2022::ID, token_2022_mint(&[]), &[]).is_ok());
+    }
+
+    #[test]
+    fn accepts_only_the_supported_extensions() {
+        let mint = token_2022_mint(&[ExtensionType::MetadataPointer]);
+        assert!(check(anchor_spl::token_2022::ID, mint.clone(), &[ExtensionType::MetadataPointer]).is_ok());
+        assert_eq!(
+            check(anchor_spl::token_2022::ID, mint, &[]).unwrap_err(),
+            GuardError::UnsupportedMintExtension.into()
+        );
+    }
+
+    #[test]
+    fn rejects_confidential_transfers_alongside_supported_extensions() {
+        let mint = token_2022_mint(&[ExtensionType::MetadataPointer, ExtensionType::ConfidentialTransferMint]);
+        assert_eq!(
+            check(anchor_spl::token_2022::ID, mint, &[ExtensionType::MetadataPointer]).unwrap_err(),
+            GuardError::UnsupportedMintExtension.into()
+        );
+    }
+
+    #[test]
+    fn rejects_accounts_no_token_program_owns() {
+        assert_eq!(
+            check(Pubkey::new_unique(), token_2022_mint(&[]), &[]).unwrap_err(),
+            GuardError::NotTokenMint.into()
+        );
+    }
+}
@@ -0,0 +1,373 @@
+//! Guards for the Token-2022 extensions that name a mint's metadata and group.
+//!
+//! `MetadataPointer`, `GroupPointer` and `GroupMemberPointer` are set by
+//! the mint's creator, to any address: a mint can point at another mint's
+//! metadata or membership, and a program following the pointer reads that
+//! mint's identity as its own. The data itself proves little more.
+//! `TokenMetadata` is written with the mint authority's signature alone,
+//! so its update authority is whatever the creator claims. Only
+//! `TokenGroupMember` is written with the group's update authority's
+//! signature too, and only into the member mint itself.
+//!
+//! The extensions are read by hand: Token-2022 0.9, which Anchor 0.29
+//! builds with, predates the group extensions, and the layouts are fixed
+//! by the token metadata and token group interfaces.
+
+use anchor_lang::prelude::*;
+
+use crate::error::GuardError;
+
+/// Offset of the account type in a Token-2022 mint with extensions, after the base mint padded to a token account
+const ACCOUNT_TYPE_OFFSET: usize = 165;
+/// Account type of a mint
+const MINT_ACCOUNT_TYPE: u8 = 1;
+/// Offset of the first extension, after the account type
+const EXTENSIONS_OFFSET: usize = ACCOUNT_TYPE_OFFSET + 1;
+
+/// Extension types as Token-2022 numbers them
+const METADATA_POINTER: u16 = 18;
+const TOKEN_METADATA: u16 = 19;
+const GROUP_POINTER: u16 = 20;
+const TOKEN_GROUP: u16 = 21;
+const GROUP_MEMBER_POINTER: u16 = 22;
+const TOKEN_GROUP_MEMBER: u16 = 23;
+
+/// A pointer extension: who may move it, and the account it names
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pointer {
+    pub authority: Option<Pubkey>,
+    pub address: Option<Pubkey>,
+}
+
+/// The fields of `TokenMetadata` that say whose metadata it is
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MintMetadata {
+    /// Who may update the metadata: chosen by the mint authority, without this key's signature
+    pub update_authority: Option<Pubkey>,
+    /// The mint the metadata describes
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+}
+
+/// A `TokenGroup`: a collection mint members are added to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TokenGroup {
+    /// Who must sign for every member added
+    pub update_authority: Option<Pubkey>,
+    /// The group mint
+    pub mint: Pubkey,
+    pub size: u32,
+    pub max_size: u32,
+}
+
+/// A `TokenGroupMember`: a mint's place in a group
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GroupMember {
+    /// The member mint
+    pub mint: Pubkey,
+    /// The group mint
+    pub group: Pubkey,
+    pub member_number: u32,
+}
+
+/// The `MetadataPointer` of `mint`
+pub fn metadata_pointer(mint: &AccountInfo) -> Result<Pointer> {
+    read_pointer(mint, METADATA_POINTER)
+}
+
+/// The `GroupPointer` of `mint`
+pub fn group_pointer(mint: &AccountInfo) -> Result<Pointer> {
+    read_pointer(mint, GROUP_POINTER)
+}
+
+/// The `GroupMemberPointer` of `mint`
+pub fn group_member_pointer(mint: &AccountInfo) -> Result<Pointer> {
+    read_pointer(mint, GROUP_MEMBER_POINTER)
+}
+
+/// The `TokenMetadata` stored in `account`, whichever mint it describes
+pub fn token_metadata(account: &AccountInfo) -> Result<MintMetadata> {
+    let data = account.try_borrow_data()?;
+    let mut value = extension(account, &data, TOKEN_METADATA)?;
+    let header = (read_pubkey(&mut value)?, read_pubkey(&mut value)?);
+    Ok(MintMetadata {
+        update_authority: non_zero(header.0),
+        mint: header.1,
+        name: read_string(&mut value)?,
+        symbol: read_string(&mut value)?,
+    })
+}
+
+/// The `TokenGroup` stored in `account`
+pub fn token_group(account: &AccountInfo) -> Result<TokenGroup> {
+    let data = account.try_borrow_data()?;
+    let mut value = extension(account, &data, TOKEN_GROUP)?;
+    Ok(TokenGroup {
+        update_authority: non_zero(read_pubkey(&mut value)?),
+        mint: read_pubkey(&mut value)?,
+        size: read_u32(&mut value)?,
+        max_size: read_u32(&mut value)?,
+    })
+}
+
+/// The `TokenGroupMember` stored in `account`, whichever mint it describes
+pub fn group_member(account: &AccountInfo) -> Result<GroupMember> {
+    let data = account.try_borrow_data()?;
+    let mut value = extension(account, &data, TOKEN_GROUP_MEMBER)?;
+    Ok(GroupMember {
+        mint: read_pubkey(&mut value)?,
+        group: read_pubkey(&mut value)?,
+        member_number: read_u32(&mut value)?,
+    })
+}
+
+/// Require `group` to be a group mint, holding its own `TokenGroup`, that only `update_authority` adds members to
+pub fn assert_token_group(group: &AccountInfo, update_authority: &Pubkey) -> Result<TokenGroup> {
+    require!(group_pointer(group)?.address == Some(group.key()), GuardError::ForeignExtensionPointer);
+    let token_group = token_group(group)?;
+    require_keys_eq!(token_group.mint, group.key(), GuardError::ExtensionMintMismatch);
+    require!(
+        token_group.update_authority == Some(*update_authority),
+        GuardError::GroupAuthorityMismatch
+    );
+    Ok(token_group)
+}
+
+/// Require `mint` to be a member of `group`, by the `TokenGroupMember` in the mint itself
+///
+/// Token-2022 writes a member only into its own mint, with the group's
+/// update authority's signature, so membership read this way was granted
+/// by the group. Metadata is no substitute: its update authority is
+/// whatever the mint's creator wrote.
+pub fn assert_group_member(mint: &AccountInfo, group: &Pubkey) -> Result<GroupMember> {
+    require!(group_member_pointer(mint)?.address == Some(mint.key()), GuardError::ForeignExtensionPointer);
+    let member = group_member(mint)?;
+    require_keys_eq!(member.mint, mint.key(), GuardError::ExtensionMintMismatch);
+    require_keys_eq!(member.group, *group, GuardError::NotGroupMember);
+    Ok(member)
+}
+
+fn read_pointer(mint: &AccountInfo, extension_type: u16) -> Result<Pointer> {
+    let data = mint.try_borrow_data()?;
+    let mut value = extension(mint, &data, extension_type)?;
+    Ok(Pointer {
+        authority: non_zero(read_pubkey(&mut value)?),
+        address: non_zero(read_pubkey(&mut value)?),
+    })
+}
+
+/// The value of the `extension_type` entry in the extensions of the Token-2022 mint `account`
+fn extension<'a>(account: &AccountInfo, data: &'a [u8], extension_type: u16) -> Result<&'a [u8]> {
+    require_keys_eq!(*account.owner, anchor_spl::token_2022::ID, GuardError::NotTokenMint);
+    match data.get(ACCOUNT_TYPE_OFFSET) {
+        // A mint without extensions
+        None => return err!(GuardError::MissingMintExtension),
+        Some(&MINT_ACCOUNT_TYPE) => {}
+        Some(_) => return err!(GuardError::NotTokenMint),
+    }
+
+    // Type (u16), length (u16) and value, until the first uninitialized entry
+    let mut entries = &data[EXTENSIONS_OFFSET..];
+    while entries.len() >= 4 {
+        let entry_type = u16::from_le_bytes([entries[0], entries[1]]);
+        let length = usize::from(u16::from_le_bytes([entries[2], entries[3]]));
+        if entry_type == 0 {
+            break;
+        }
+        let value = entries.get(4..4 + length).ok_or(GuardError::InvalidMintExtension)?;
+        if entry_type == extension_type {
+            return Ok(value);
+        }
+        entries = &entries[4 + length..];
+    }
+    err!(GuardError::MissingMintExtension)
+}
+
+fn take<'a>(value: &mut &'a [u8], length: usize) -> Result<&'a [u8]> {
+    let bytes = value.get(..length).ok_or(GuardError::InvalidMintExtension)?;
+    *value = &value[length..];
+    Ok(bytes)
+}
+
+fn read_pubkey(value: &mut &[u8]) -> Result<Pubkey> {
+    Ok(Pubkey::new_from_array(take(value, 32)?.try_into().unwrap()))
+}
+
+fn read_u32(value: &mut &[u8]) -> Result<u32> {
+    Ok(u32::from_le_bytes(take(value, 4)?.try_into().unwrap()))
+}
+
+/// A borsh string: length (u32), then UTF-8
+fn read_string(value: &mut &[u8]) -> Result<String> {
+    let length = read_u32(value)? as usize;
+    String::from_utf8(take(value, length)?.to_vec()).map_err(|_| error!(GuardError::InvalidMintExtension))
+}
+
+/// An optional key, stored as all zeroes for none
+fn non_zero(key: Pubkey) -> Option<Pubkey> {
+    (key != Pubkey::default()).then_some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_spl::token::spl_token::solana_program::program_option::COption;
+    use anchor_spl::token_2022::spl_token_2022::extension::group_member_pointer::GroupMemberPointer;
+    use anchor_spl::token_2022::spl_token_2022::extension::group_pointer::GroupPointer;
+    use anchor_spl::token_2022::spl_token_2022::extension::metadata_pointer::MetadataPointer;
+    use anchor_spl::token_2022::spl_token_2022::extension::{
+        BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+    };
+    use anchor_spl::token_2022::spl_token_2022::state::Mint;
+    use anchor_spl::token_interface::spl_token_metadata_interface::state::TokenMetadata;
+    use spl_token_group_interface::state::{TokenGroup as SplTokenGroup, TokenGroupMember};
+
+    /// A Token-2022 mint at `key`: metadata claiming `update_authority`, and member 1 of `group`,
+    /// with both pointers naming `pointed`
+    fn member_mint(key: Pubkey, pointed: Pubkey, update_authority: Pubkey, group: Pubkey) -> Vec<u8> {
+        let metadata = TokenMetadata {
+            update_authority: Some(update_authority).try_into().unwrap(),
+            mint: key,
+            name: "Member #1".into(),
+            symbol: "MBR".into(),
+            ..TokenMetadata::default()
+        };
+        let mut data = vec![
+            0;
+            ExtensionType::try_calculate_account_len::<Mint>(&[
+                ExtensionType::MetadataPointer,
+                ExtensionType::GroupMemberPointer,
+                ExtensionType::TokenGroupMember,
+            ])
+            .unwrap()
+                + metadata.tlv_size_of().unwrap()
+        ];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        state.init_extension::<MetadataPointer>(true).unwrap().metadata_address = Some(pointed).try_into().unwrap();
+        state.init_extension::<GroupMemberPointer>(true).unwrap().member_address = Some(pointed).try_into().unwrap();
+        *state.init_extension::<TokenGroupMember>(true).unwrap() = TokenGroupMember::new(&key, &group, 1);
+        state.base = base();
+        state.pack_base();
+        state.init_account_type().unwrap();
+        state.init_variable_len_extension(&metadata, false).unwrap();
+        data
+    }
+
+    /// A Token-2022 group mint at `key`, holding its own `TokenGroup`
+    fn group_mint(key: Pubkey, update_authority: Pubkey) -> Vec<u8> {
+        let extensions = [ExtensionType::GroupPointer, ExtensionType::TokenGroup];
+        let mut data = vec![0; ExtensionType::try_calculate_account_len::<Mint>(&extensions).unwrap()];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        state.init_extension::<GroupPointer>(true).unwrap().group_address = Some(key).try_into().unwrap();
+        *state.init_extension::<SplTokenGroup>(true).unwrap() =
+            SplTokenGroup::new(&key, Some(update_authority).try_into().unwrap(), 10);
+        state.base = base();
+        state.pack_base();
+        state.init_account_type().unwrap();
+        data
+    }
+
+    fn base() -> Mint {
+        Mint {
+            mint_authority: COption::Some(Pubkey::new_unique()),
+            supply: 1,
+            decimals: 0,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        }
+    }
+
+    fn with_mint<T>(key: Pubkey, mut data: Vec<u8>, read: impl FnOnce(&AccountInfo) -> T) -> T {
+        let (owner, mut lamports) = (anchor_spl::token_2022::ID, 1);
+        let mint = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        read(&mint)
+    }
+
+    #[test]
+    fn reads_the_extensions_token_2022_writes() {
+        let (key, authority, group) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        with_mint(key, member_mint(key, key, authority, group), |mint| {
+            assert_eq!(metadata_pointer(mint).unwrap().address, Some(key));
+            assert_eq!(
+                token_metadata(mint).unwrap(),
+                MintMetadata {
+                    update_authority: Some(authority),
+                    mint: key,
+                    name: "Member #1".into(),
+                    symbol: "MBR".into(),
+                }
+            );
+            assert_eq!(
+                group_member(mint).unwrap(),
+                GroupMember {
+                    mint: key,
+                    group,
+                    member_number: 1
+                }
+            );
+            assert_eq!(group_pointer(mint).unwrap_err(), GuardError::MissingMintExtension.into());
+        });
+    }
+
+    #[test]
+    fn accepts_members_of_the_group() {
+        let (key, group) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let member = with_mint(key, member_mint(key, key, Pubkey::new_unique(), group), |mint| {
+            assert_group_member(mint, &group)
+        });
+        assert_eq!(member.unwrap().member_number, 1);
+    }
+
+    #[test]
+    fn rejects_members_of_other_groups() {
+        let key = Pubkey::new_unique();
+        let result = with_mint(key, member_mint(key, key, Pubkey::new_unique(), Pubkey::new_unique()), |mint| {
+            assert_group_member(mint, &Pubkey::new_unique())
+        });
+        assert_eq!(result.unwrap_err(), GuardError::NotGroupMember.into());
+    }
+
+    #[test]
+    fn rejects_pointers_to_other_accounts() {
+        let (key, group) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let result = with_mint(key, member_mint(key, Pubkey::new_unique(), Pubkey::new_unique(), group), |mint| {
+            assert_group_member(mint, &group)
+        });
+        assert_eq!(result.unwrap_err(), GuardError::ForeignExtensionPointer.into());
+    }
+
+    #[test]
+    fn rejects_membership_written_for_another_mint() {
+        // A member's bytes at another address, whose pointers name that address
+        let (member, copy, group) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let data = member_mint(member, copy, Pubkey::new_unique(), group);
+        let result = with_mint(copy, data, |mint| assert_group_member(mint, &group));
+        assert_eq!(result.unwrap_err(), GuardError::ExtensionMintMismatch.into());
+    }
+
+    #[test]
+    fn checks_the_group_authority() {
+        let (key, authority) = (Pubkey::new_unique(), Pubkey::new_unique());
+        with_mint(key, group_mint(key, authority), |group| {
+            assert_eq!(assert_token_group(group, &authority).unwrap().max_size, 10);
+            assert_eq!(
+                assert_token_group(group, &Pubkey::new_unique()).unwrap_err(),
+                GuardError::GroupAuthorityMismatch.into()
+            );
+        });
+    }
+
+    #[test]
+    fn rejects_mints_without_extensions_and_other_owners() {
+        let key = Pubkey::new_unique();
+        let mut data = vec![0; 82];
+        with_mint(key, data.clone(), |mint| {
+            assert_eq!(metadata_pointer(mint).unwrap_err(), GuardError::MissingMintExtension.into());
+        });
+
+        let (owner, mut lamports) = (Pubkey::new_unique(), 1);
+        let forged = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        assert_eq!(group_member(&forged).unwrap_err(), GuardError::NotTokenMint.into());
+    }
+}
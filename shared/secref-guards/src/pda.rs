@@ -0,0 +1,114 @@
+//! Guards for program derived addresses checked against a bump rather than
+//! re-derived.
+//!
+//! `find_program_address` tries bumps from 255 down until the address lands
+//! off the curve, and each try costs about as much as one
+//! `create_program_address` - roughly 1,500 compute units. Deriving the
+//! address on every instruction pays that every time. The usual pattern is
+//! to find the canonical bump once, when the account is created, store it,
+//! and check the address on later instructions with a single
+//! `create_program_address`; Anchor's `bump = account.bump` does exactly
+//! that.
+//!
+//! `create_program_address` does not know which bump is canonical: about
+//! half of all bumps land off the curve, and each gives a different valid
+//! address for the same seeds. A check against a bump the program stored is
+//! a check against the one canonical address. A check against a bump the
+//! caller sent accepts any of them, so seeds that are meant to name one
+//! account - one claim per wallet, one vault per mint - name a hundred.
+
+use anchor_lang::prelude::*;
+
+use crate::error::GuardError;
+
+/// Require `address` to be the program address of `seeds` at a `bump` this program stored
+///
+/// One `create_program_address`, whatever the bump. The bump is not checked
+/// to be canonical, so it must be one the program found with
+/// `find_program_address` (or [`canonical_bump`]) and wrote itself - never
+/// one from instruction data or from an account the caller wrote.
+pub fn assert_stored_bump(address: &Pubkey, seeds: &[&[u8]], bump: u8, program_id: &Pubkey) -> Result<()> {
+    let bump = [bump];
+    let mut seeds_with_bump = seeds.to_vec();
+    seeds_with_bump.push(&bump);
+    let derived =
+        Pubkey::create_program_address(&seeds_with_bump, program_id).map_err(|_| error!(GuardError::PdaMismatch))?;
+    require_keys_eq!(*address, derived, GuardError::PdaMismatch);
+    Ok(())
+}
+
+/// Return the canonical bump of `seeds`, requiring `address` to be its program address
+///
+/// Costs a full `find_program_address`: call it where the account is
+/// created, and store the bump for [`assert_stored_bump`] to use after.
+pub fn canonical_bump(address: &Pubkey, seeds: &[&[u8]], program_id: &Pubkey) -> Result<u8> {
+    let (derived, bump) = Pubkey::find_program_address(seeds, program_id);
+    require_keys_eq!(*address, derived, GuardError::PdaMismatch);
+    Ok(bump)
+}
+
+/// Require a bump from outside the program to be the canonical bump of `seeds`, at `address`
+///
+/// Costs as much as [`canonical_bump`]: a bump taken from the caller saves
+/// nothing once it is checked, so prefer not taking one.
+pub fn assert_canonical_bump(address: &Pubkey, seeds: &[&[u8]], bump: u8, program_id: &Pubkey) -> Result<()> {
+    assert_stored_bump(address, seeds, bump, program_id)?;
+    let (_, canonical) = Pubkey::find_program_address(seeds, program_id);
+    require!(bump == canonical, GuardError::NonCanonicalBump);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The examples' program id
+    const PROGRAM_ID: Pubkey = pubkey!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+    const SEEDS: &[&[u8]] = &[b"claim", b"wallet"];
+
+    /// The canonical bump's address, and the next off-curve bump below it with its address
+    fn canonical_and_next() -> ((Pubkey, u8), (Pubkey, u8)) {
+        let (canonical, bump) = Pubkey::find_program_address(SEEDS, &PROGRAM_ID);
+        let next = (0..bump)
+            .rev()
+            .find_map(|other| {
+                Pubkey::create_program_address(&[SEEDS[0], SEEDS[1], &[other]], &PROGRAM_ID)
+                    .ok()
+                    .map(|address| (address, other))
+            })
+            .unwrap();
+        ((canonical, bump), next)
+    }
+
+    #[test]
+    fn accepts_the_canonical_address_at_its_stored_bump() {
+        let ((address, bump), _) = canonical_and_next();
+        assert!(assert_stored_bump(&address, SEEDS, bump, &PROGRAM_ID).is_ok());
+        assert_eq!(canonical_bump(&address, SEEDS, &PROGRAM_ID).unwrap(), bump);
+        assert!(assert_canonical_bump(&address, SEEDS, bump, &PROGRAM_ID).is_ok());
+    }
+
+    #[test]
+    fn a_stored_bump_check_accepts_a_second_address_for_the_same_seeds() {
+        let ((canonical, _), (other, bump)) = canonical_and_next();
+        assert_ne!(other, canonical);
+        assert!(assert_stored_bump(&other, SEEDS, bump, &PROGRAM_ID).is_ok());
+        assert_eq!(
+            assert_canonical_bump(&other, SEEDS, bump, &PROGRAM_ID).unwrap_err(),
+            GuardError::NonCanonicalBump.into()
+        );
+        assert_eq!(
+            canonical_bump(&other, SEEDS, &PROGRAM_ID).unwrap_err(),
+            GuardError::PdaMismatch.into()
+        );
+    }
+
+    #[test]
+    fn rejects_an_address_its_bump_does_not_derive() {
+        let ((address, _), (_, bump)) = canonical_and_next();
+        assert_eq!(
+            assert_stored_bump(&address, SEEDS, bump, &PROGRAM_ID).unwrap_err(),
+            GuardError::PdaMismatch.into()
+        );
+    }
+}
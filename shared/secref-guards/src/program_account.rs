@@ -0,0 +1,214 @@
+//! Guards for accounts used as programs - CPI targets, plugins, hooks -
+//! rather than as data.
+//!
+//! An `UncheckedAccount` passed where a program is expected can be a plain
+//! keypair address with no code yet, a builtin, or an upgradeable program
+//! whose authority can swap its code after it was reviewed. These guards
+//! check each of those properties explicitly.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{bpf_loader, bpf_loader_deprecated, bpf_loader_upgradeable};
+
+use crate::error::GuardError;
+
+/// Loaders that own deployed on-chain programs
+pub const BPF_LOADERS: [Pubkey; 3] = [bpf_loader::ID, bpf_loader_deprecated::ID, bpf_loader_upgradeable::ID];
+
+/// bincode tag of `UpgradeableLoaderState::Program`
+const PROGRAM_STATE_TAG: u32 = 2;
+/// bincode tag of `UpgradeableLoaderState::ProgramData`
+const PROGRAM_DATA_STATE_TAG: u32 = 3;
+
+/// Require `account` to be marked executable
+pub fn assert_executable(account: &AccountInfo) -> Result<()> {
+    require!(account.executable, GuardError::NotExecutable);
+    Ok(())
+}
+
+/// Require `account` to be owned by one of the BPF loaders
+///
+/// Rejects builtins (owned by the native loader) and data accounts.
+pub fn assert_loader_owned(account: &AccountInfo) -> Result<()> {
+    require!(BPF_LOADERS.contains(account.owner), GuardError::NotLoaderOwned);
+    Ok(())
+}
+
+/// Require `account` to be a deployed BPF program
+pub fn assert_program_account(account: &AccountInfo) -> Result<()> {
+    assert_executable(account)?;
+    assert_loader_owned(account)
+}
+
+/// Require `account` to be the executable program `expected`
+pub fn assert_program_id(account: &AccountInfo, expected: &Pubkey) -> Result<()> {
+    require_keys_eq!(*account.key, *expected, GuardError::UnexpectedProgram);
+    assert_executable(account)
+}
+
+/// Return the upgrade authority of a deployed BPF program
+///
+/// Programs owned by the non-upgradeable loaders have no authority and
+/// `program_data` is ignored. For upgradeable programs `program_data` must
+/// be the ProgramData account the program points at.
+pub fn upgrade_authority(program: &AccountInfo, program_data: &AccountInfo) -> Result<Option<Pubkey>> {
+    assert_program_account(program)?;
+    if *program.owner != bpf_loader_upgradeable::ID {
+        return Ok(None);
+    }
+
+    let expected_program_data = {
+        let data = program.try_borrow_data()?;
+        require!(read_tag(&data)? == PROGRAM_STATE_TAG, GuardError::InvalidProgramAccount);
+        read_pubkey(&data, 4)?
+    };
+    require_keys_eq!(
+        *program_data.key,
+        expected_program_data,
+        GuardError::ProgramDataMismatch
+    );
+    require_keys_eq!(
+        *program_data.owner,
+        bpf_loader_upgradeable::ID,
+        GuardError::ProgramDataMismatch
+    );
+
+    // ProgramData { slot: u64, upgrade_authority_address: Option<Pubkey> }
+    let data = program_data.try_borrow_data()?;
+    require!(
+        read_tag(&data)? == PROGRAM_DATA_STATE_TAG,
+        GuardError::InvalidProgramAccount
+    );
+    match data.get(12) {
+        Some(0) => Ok(None),
+        Some(1) => Ok(Some(read_pubkey(&data, 13)?)),
+        _ => err!(GuardError::InvalidProgramAccount),
+    }
+}
+
+/// Require `program` to be a deployed BPF program whose code can no longer change
+pub fn assert_immutable_program(program: &AccountInfo, program_data: &AccountInfo) -> Result<()> {
+    require!(
+        upgrade_authority(program, program_data)?.is_none(),
+        GuardError::ProgramUpgradeable
+    );
+    Ok(())
+}
+
+fn read_tag(data: &[u8]) -> Result<u32> {
+    let bytes = data.get(..4).ok_or(GuardError::InvalidProgramAccount)?;
+    Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Result<Pubkey> {
+    let bytes = data.get(offset..offset + 32).ok_or(GuardError::InvalidProgramAccount)?;
+    Ok(Pubkey::new_from_array(bytes.try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Owner of builtin programs such as the System Program
+    const NATIVE_LOADER: Pubkey = pubkey!("NativeLoader1111111111111111111111111111111");
+
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+        executable: bool,
+    }
+
+    impl TestAccount {
+        fn new(owner: Pubkey, data: Vec<u8>, executable: bool) -> Self {
+            Self {
+                key: Pubkey::new_unique(),
+                owner,
+                lamports: 1,
+                data,
+                executable,
+            }
+        }
+
+        fn info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                false,
+                false,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                self.executable,
+                0,
+            )
+        }
+    }
+
+    /// An upgradeable program and its ProgramData account
+    fn upgradeable(authority: Option<Pubkey>) -> (TestAccount, TestAccount) {
+        let mut program_data_bytes = PROGRAM_DATA_STATE_TAG.to_le_bytes().to_vec();
+        program_data_bytes.extend_from_slice(&0u64.to_le_bytes());
+        match authority {
+            Some(key) => {
+                program_data_bytes.push(1);
+                program_data_bytes.extend_from_slice(key.as_ref());
+            }
+            None => program_data_bytes.push(0),
+        }
+        let program_data = TestAccount::new(bpf_loader_upgradeable::ID, program_data_bytes, false);
+
+        let mut program_bytes = PROGRAM_STATE_TAG.to_le_bytes().to_vec();
+        program_bytes.extend_from_slice(program_data.key.as_ref());
+        let program = TestAccount::new(bpf_loader_upgradeable::ID, program_bytes, true);
+
+        (program, program_data)
+    }
+
+    #[test]
+    fn rejects_non_executable_accounts() {
+        let mut account = TestAccount::new(bpf_loader_upgradeable::ID, vec![], false);
+        assert_eq!(
+            assert_program_account(&account.info()).unwrap_err(),
+            GuardError::NotExecutable.into()
+        );
+    }
+
+    #[test]
+    fn rejects_builtins() {
+        let mut builtin = TestAccount::new(NATIVE_LOADER, vec![], true);
+        assert_eq!(
+            assert_program_account(&builtin.info()).unwrap_err(),
+            GuardError::NotLoaderOwned.into()
+        );
+    }
+
+    #[test]
+    fn reports_upgrade_authority() {
+        let authority = Pubkey::new_unique();
+        let (mut program, mut program_data) = upgradeable(Some(authority));
+        assert_eq!(
+            upgrade_authority(&program.info(), &program_data.info()).unwrap(),
+            Some(authority)
+        );
+        assert_eq!(
+            assert_immutable_program(&program.info(), &program_data.info()).unwrap_err(),
+            GuardError::ProgramUpgradeable.into()
+        );
+    }
+
+    #[test]
+    fn accepts_frozen_programs() {
+        let (mut program, mut program_data) = upgradeable(None);
+        assert!(assert_immutable_program(&program.info(), &program_data.info()).is_ok());
+    }
+
+    #[test]
+    fn rejects_foreign_program_data() {
+        let (mut program, _) = upgradeable(None);
+        let (_, mut other_program_data) = upgradeable(None);
+        assert_eq!(
+            upgrade_authority(&program.info(), &other_program_data.info()).unwrap_err(),
+            GuardError::ProgramDataMismatch.into()
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! Topping up and reclaiming the rent held by program-owned accounts.
+//!
+//! Rent is a deposit, not a fee: it stays in the account and goes to
+//! whoever closes it. Topping up is needed whenever an account grows or
+//! was funded below its rent-exempt minimum. Reclaiming is how state that
+//! has served its purpose - a filled or expired order, a cancelled escrow,
+//! a used nonce - gives its rent back.
+//!
+//! Reclaiming is also deleting. State closed before it has expired can be
+//! recreated or replayed, and a permissionless reclaim with no expiry check
+//! lets anyone delete live state. Check [`assert_reclaimable`] before
+//! [`reclaim_rent`]:
+//!
+//! ```ignore
+//! use secref_guards::rent::{assert_reclaimable, reclaim_rent};
+//!
+//! assert_reclaimable(order.expires_at, Clock::get()?.unix_timestamp)?;
+//! reclaim_rent(&ctx.accounts.order.to_account_info(), &ctx.accounts.maker.to_account_info())?;
+//! ```
+//!
+//! ## Reading rent
+//!
+//! Every amount here starts from the Rent sysvar, and there are three ways
+//! to read it:
+//!
+//! - An `AccountInfo` the caller passes, deserialized by hand or with a
+//!   reader that skips the address check (as `Sysvar::from_account_info`
+//!   once did). Nothing says the account *is* the sysvar: a caller passes an
+//!   account of their own holding `lamports_per_byte_year = 0`, every
+//!   minimum balance becomes zero, and a "keep it rent-exempt" check passes
+//!   on an empty account. Never do this.
+//! - A `Sysvar<'info, Rent>` field, or [`rent_from_account`]. The address
+//!   is checked, so it cannot be spoofed, but every instruction carries an
+//!   account it does not need. Keep it only where an interface already
+//!   passes the sysvar.
+//! - [`minimum_balance`], through `Rent::get()` and the
+//!   `sol_get_rent_sysvar` syscall. There is no account to check or to
+//!   forget to check; secure handlers read rent this way.
+//!
+//! ```ignore
+//! use secref_guards::rent::minimum_balance;
+//!
+//! let reserve = minimum_balance(vault_info.data_len())?;
+//! ```
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program::{self, Transfer};
+
+use crate::account_creation::top_up_amount;
+use crate::error::GuardError;
+
+/// Lamports an account of `space` bytes must hold to be rent-exempt
+///
+/// Reads the Rent sysvar through its syscall, so no Rent account is passed
+/// in and none can be spoofed.
+pub fn minimum_balance(space: usize) -> Result<u64> {
+    Ok(Rent::get()?.minimum_balance(space))
+}
+
+/// The Rent sysvar from an account the caller passed, once its address is checked
+///
+/// For interfaces that still take the sysvar as an account; everything
+/// else should use [`minimum_balance`].
+pub fn rent_from_account(account: &AccountInfo) -> Result<Rent> {
+    require_keys_eq!(*account.key, sysvar::rent::ID, GuardError::NotRentSysvar);
+    Ok(Rent::from_account_info(account)?)
+}
+
+/// Transfer from `payer` what `account` lacks to be rent-exempt at `space` bytes; returns the lamports paid
+///
+/// `payer` must be a System-owned signer. Call it after `realloc` grows an
+/// account, or before creating an account at a pre-funded address.
+pub fn top_up_rent<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<u64> {
+    let top_up = top_up_amount(account.lamports(), minimum_balance(space)?);
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    Ok(top_up)
+}
+
+/// Require `now` to have reached `expires_at`, the end of the state's life
+pub fn assert_reclaimable(expires_at: i64, now: i64) -> Result<()> {
+    require!(now >= expires_at, GuardError::NotYetReclaimable);
+    Ok(())
+}
+
+/// Require `account` to have been closed: no data, and owned by the System Program again
+///
+/// Lamports are not checked, since anyone can send some to a closed address.
+pub fn assert_closed(account: &AccountInfo) -> Result<()> {
+    require!(
+        account.data_is_empty() && account.owner == &system_program::ID,
+        GuardError::AccountNotClosed
+    );
+    Ok(())
+}
+
+/// Close a program-owned `account`, moving all its lamports to `recipient`; returns the lamports moved
+///
+/// This is what Anchor's `close` constraint does, for handlers that pick
+/// the recipient or check expiry first. The account is handed back to the
+/// System Program with no data, so Anchor does not write it back on exit.
+pub fn reclaim_rent<'info>(account: &AccountInfo<'info>, recipient: &AccountInfo<'info>) -> Result<u64> {
+    let lamports = account.lamports();
+    **recipient.try_borrow_mut_lamports()? = recipient
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+    account.assign(&system_program::ID);
+    account.realloc(0, false)?;
+    Ok(lamports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reclaims_from_the_expiry_on() {
+        assert!(assert_reclaimable(1_000, 1_000).is_ok());
+        assert!(assert_reclaimable(1_000, 5_000).is_ok());
+    }
+
+    #[test]
+    fn rejects_reclaiming_live_state() {
+        assert_eq!(
+            assert_reclaimable(1_000, 999).unwrap_err(),
+            GuardError::NotYetReclaimable.into()
+        );
+    }
+
+    /// `rent` laid out as the sysvar account holds it, at `key`
+    fn rent_account(key: Pubkey, rent: &Rent, run: impl FnOnce(&AccountInfo)) {
+        let mut data = rent.lamports_per_byte_year.to_le_bytes().to_vec();
+        data.extend_from_slice(&rent.exemption_threshold.to_le_bytes());
+        data.push(rent.burn_percent);
+        let mut lamports = 1;
+        let owner = sysvar::ID;
+        run(&AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0));
+    }
+
+    #[test]
+    fn reads_rent_from_the_sysvar_account() {
+        rent_account(sysvar::rent::ID, &Rent::default(), |account| {
+            assert_eq!(rent_from_account(account).unwrap(), Rent::default());
+        });
+    }
+
+    #[test]
+    fn rejects_rent_from_any_other_account() {
+        let free = Rent {
+            lamports_per_byte_year: 0,
+            ..Rent::default()
+        };
+        rent_account(Pubkey::new_unique(), &free, |account| {
+            assert_eq!(rent_from_account(account).unwrap_err(), GuardError::NotRentSysvar.into());
+        });
+    }
+}
@@ -0,0 +1,112 @@
+//! Lifecycle state machines: which status may follow which.
+//!
+//! Orders, auctions and escrows move through a lifecycle - open, then
+//! filled or cancelled - and each handler is only correct in some of its
+//! states. A handler that writes the next status without checking the
+//! current one lets two of them both succeed on the same account: a
+//! cancel refunds the escrow, and a fill landing after it in the same
+//! block pays the escrow out again.
+//!
+//! Derive [`StateMachine`] for the status enum, listing the allowed
+//! transitions on it, and move between states only with
+//! [`transition`](StateMachine::transition):
+//!
+//! ```ignore
+//! use secref_guards::state_machine::StateMachine;
+//!
+//! #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, StateMachine)]
+//! #[transition(Open -> Filled, Open -> Cancelled)]
+//! pub enum OrderStatus {
+//!     Open,
+//!     Filled,
+//!     Cancelled,
+//! }
+//!
+//! // fill: fails unless the order is still open
+//! order.status.transition(OrderStatus::Filled)?;
+//! ```
+//!
+//! The derive checks every listed state is a variant of the enum when the
+//! program compiles. Implement the trait by hand for a lifecycle the list
+//! cannot express.
+
+use anchor_lang::prelude::*;
+
+use crate::error::GuardError;
+
+/// Derives [`StateMachine`] from `#[transition(From -> To, ...)]` (see `secref_macros`)
+pub use secref_macros::StateMachine;
+
+/// A status enum with an explicit list of allowed transitions
+///
+/// A state with no transition out of it is terminal. Staying in the same
+/// state is not a transition unless `can_transition` lists it.
+pub trait StateMachine: Copy + PartialEq {
+    /// Whether the lifecycle allows moving from `self` to `to`
+    fn can_transition(self, to: Self) -> bool;
+
+    /// Move to `to`, or fail if the lifecycle does not allow it from here
+    ///
+    /// Call it before any effect of the transition - transfers, refunds -
+    /// so a handler acting on an account in the wrong state fails before it
+    /// has done anything.
+    fn transition(&mut self, to: Self) -> Result<()> {
+        require!(self.can_transition(to), GuardError::InvalidStateTransition);
+        *self = to;
+        Ok(())
+    }
+
+    /// Fail unless the state is `expected`, for handlers that act without changing it
+    fn assert_state(self, expected: Self) -> Result<()> {
+        require!(self == expected, GuardError::UnexpectedState);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, StateMachine)]
+    #[transition(Open -> Filled, Open -> Cancelled)]
+    enum Status {
+        Open,
+        Filled,
+        Cancelled,
+    }
+
+    #[test]
+    fn open_orders_fill_or_cancel_once() {
+        for end in [Status::Filled, Status::Cancelled] {
+            let mut status = Status::Open;
+            status.transition(end).unwrap();
+            assert_eq!(status, end);
+        }
+    }
+
+    #[test]
+    fn terminal_states_stay_terminal() {
+        for from in [Status::Filled, Status::Cancelled] {
+            for to in [Status::Open, Status::Filled, Status::Cancelled] {
+                let mut status = from;
+                assert_eq!(status.transition(to), Err(GuardError::InvalidStateTransition.into()));
+                assert_eq!(status, from, "{from:?} -> {to:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn staying_put_is_not_a_transition() {
+        let mut status = Status::Open;
+        assert_eq!(status.transition(Status::Open), Err(GuardError::InvalidStateTransition.into()));
+    }
+
+    #[test]
+    fn assert_state_checks_without_moving() {
+        assert!(Status::Open.assert_state(Status::Open).is_ok());
+        assert_eq!(
+            Status::Cancelled.assert_state(Status::Open),
+            Err(GuardError::UnexpectedState.into())
+        );
+    }
+}
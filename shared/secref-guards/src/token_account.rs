@@ -0,0 +1,164 @@
+//! Guards for token accounts a program creates for itself, usually the
+//! associated token account (ATA) of one of its PDAs.
+//!
+//! An ATA's address is derived from its wallet and mint alone, and the
+//! Associated Token program creates one for any wallet, paid by anyone. So
+//! the account may already exist by the time the program gets to it:
+//! `init, associated_token::..` then fails forever, and `init_if_needed`
+//! accepts whatever is there - checking only its wallet and mint, not a
+//! frozen state or a delegate or close authority left behind by whoever
+//! controlled the address before.
+//!
+//! [`create_associated_token_account`] creates the account with
+//! `CreateIdempotent`, which succeeds whether or not it exists, and then
+//! checks that only its owner can move or close its tokens. A balance
+//! already in the account is not rejected: anyone can send tokens to any
+//! account, so refusing them would hand the squatter the same denial of
+//! service as `init`.
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, get_associated_token_address, Create};
+use anchor_spl::token::TokenAccount;
+
+use crate::error::GuardError;
+
+/// Require `account` to be the ATA of `wallet` for `mint`
+pub fn assert_associated_token_address(account: &Pubkey, wallet: &Pubkey, mint: &Pubkey) -> Result<()> {
+    require_keys_eq!(
+        *account,
+        get_associated_token_address(wallet, mint),
+        GuardError::NotAssociatedTokenAccount
+    );
+    Ok(())
+}
+
+/// Require `account` to hold `mint` for `owner`
+pub fn assert_token_account(account: &TokenAccount, owner: &Pubkey, mint: &Pubkey) -> Result<()> {
+    require_keys_eq!(account.owner, *owner, GuardError::TokenOwnerMismatch);
+    require_keys_eq!(account.mint, *mint, GuardError::TokenMintMismatch);
+    Ok(())
+}
+
+/// Require `account` to be usable, with no authority over it but its owner
+///
+/// Rejects a frozen account, a delegate (whatever its remaining allowance)
+/// and a close authority, even one set to the owner itself.
+pub fn assert_sole_authority(account: &TokenAccount) -> Result<()> {
+    require!(!account.is_frozen(), GuardError::TokenAccountFrozen);
+    require!(account.delegate.is_none(), GuardError::UnexpectedDelegate);
+    require!(account.close_authority.is_none(), GuardError::UnexpectedCloseAuthority);
+    Ok(())
+}
+
+/// Create the ATA of `wallet` for `mint` unless it exists, then check it as if it were new
+///
+/// Never fails because someone created the account first; fails with a
+/// [`GuardError`] if the existing account carries a frozen state, a
+/// delegate or a close authority.
+pub fn create_associated_token_account<'info>(
+    payer: &AccountInfo<'info>,
+    associated_token: &AccountInfo<'info>,
+    wallet: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    associated_token_program: &AccountInfo<'info>,
+) -> Result<TokenAccount> {
+    assert_associated_token_address(associated_token.key, wallet.key, mint.key)?;
+    associated_token::create_idempotent(CpiContext::new(
+        associated_token_program.clone(),
+        Create {
+            payer: payer.clone(),
+            associated_token: associated_token.clone(),
+            authority: wallet.clone(),
+            mint: mint.clone(),
+            system_program: system_program.clone(),
+            token_program: token_program.clone(),
+        },
+    ))?;
+
+    // The Associated Token program has checked it is a token account either way
+    let account = TokenAccount::try_deserialize(&mut &associated_token.try_borrow_data()?[..])?;
+    assert_token_account(&account, wallet.key, mint.key)?;
+    assert_sole_authority(&account)?;
+    Ok(account)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_spl::token::spl_token::solana_program::program_option::COption;
+    use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+    use anchor_spl::token::spl_token::state::{Account as TokenState, AccountState};
+
+    fn token_account(change: impl FnOnce(&mut TokenState)) -> TokenAccount {
+        let mut state = TokenState {
+            mint: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            amount: 100,
+            state: AccountState::Initialized,
+            ..TokenState::default()
+        };
+        change(&mut state);
+        let mut data = vec![0; TokenState::LEN];
+        state.pack_into_slice(&mut data);
+        TokenAccount::try_deserialize_unchecked(&mut data.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn accepts_only_the_canonical_address() {
+        let (wallet, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let canonical = get_associated_token_address(&wallet, &mint);
+        assert!(assert_associated_token_address(&canonical, &wallet, &mint).is_ok());
+        assert_eq!(
+            assert_associated_token_address(&Pubkey::new_unique(), &wallet, &mint).unwrap_err(),
+            GuardError::NotAssociatedTokenAccount.into()
+        );
+    }
+
+    #[test]
+    fn rejects_another_owner_or_mint() {
+        let account = token_account(|_| {});
+        assert!(assert_token_account(&account, &account.owner, &account.mint).is_ok());
+        assert_eq!(
+            assert_token_account(&account, &Pubkey::new_unique(), &account.mint).unwrap_err(),
+            GuardError::TokenOwnerMismatch.into()
+        );
+        assert_eq!(
+            assert_token_account(&account, &account.owner, &Pubkey::new_unique()).unwrap_err(),
+            GuardError::TokenMintMismatch.into()
+        );
+    }
+
+    #[test]
+    fn accepts_a_balance_it_did_not_expect() {
+        assert!(assert_sole_authority(&token_account(|state| state.amount = u64::MAX)).is_ok());
+    }
+
+    #[test]
+    fn rejects_frozen_accounts() {
+        let frozen = token_account(|state| state.state = AccountState::Frozen);
+        assert_eq!(
+            assert_sole_authority(&frozen).unwrap_err(),
+            GuardError::TokenAccountFrozen.into()
+        );
+    }
+
+    #[test]
+    fn rejects_any_delegate() {
+        let delegated = token_account(|state| state.delegate = COption::Some(Pubkey::new_unique()));
+        assert_eq!(
+            assert_sole_authority(&delegated).unwrap_err(),
+            GuardError::UnexpectedDelegate.into()
+        );
+    }
+
+    #[test]
+    fn rejects_any_close_authority() {
+        let closable = token_account(|state| state.close_authority = COption::Some(state.owner));
+        assert_eq!(
+            assert_sole_authority(&closable).unwrap_err(),
+            GuardError::UnexpectedCloseAuthority.into()
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Velocity limits: how much may move per transaction, and per account per window.
+//!
+//! A cap on withdrawals is what stands between a stolen hot key and an
+//! emptied account: the owner, or a monitor, has until the window ends to
+//! notice. The cap holds only as long as the counter it is checked against
+//! does. Keep the [`VelocityWindow`] in an account at an address derived
+//! from the vault and the owner alone, created with the position and never
+//! closed, so it cannot be deleted and recreated empty; and count in fixed
+//! windows of the clock, so when a window starts does not depend on when
+//! the account was created.
+//!
+//! Embed `VelocityLimits` in the vault and `VelocityWindow` in the
+//! per-owner tracker:
+//!
+//! ```ignore
+//! use secref_guards::velocity::{VelocityLimits, VelocityWindow, DAY_SECONDS};
+//!
+//! // initialize
+//! vault.limits = VelocityLimits::new(max_per_tx, max_per_window, DAY_SECONDS)?;
+//!
+//! // withdraw
+//! tracker.window.record(&vault.limits, amount, Clock::get()?.unix_timestamp)?;
+//! ```
+
+use anchor_lang::prelude::*;
+
+use crate::error::GuardError;
+
+/// One day, the usual window for a withdrawal cap
+pub const DAY_SECONDS: i64 = 86_400;
+
+/// The caps a vault enforces
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VelocityLimits {
+    /// Most that may move in one instruction (8 bytes)
+    pub max_per_tx: u64,
+    /// Most that may move per account in one window (8 bytes)
+    pub max_per_window: u64,
+    /// Window length; windows start at multiples of it since the Unix epoch (8 bytes)
+    pub window_seconds: i64,
+}
+
+impl VelocityLimits {
+    /// Serialized size, for `space = 8 + ... + VelocityLimits::SPACE`
+    pub const SPACE: usize = 8 + 8 + 8; // max_per_tx + max_per_window + window_seconds
+
+    /// Limits allowing up to `max_per_tx` at once and `max_per_window` per window of `window_seconds`
+    pub fn new(max_per_tx: u64, max_per_window: u64, window_seconds: i64) -> Result<Self> {
+        require!(
+            max_per_tx > 0 && max_per_tx <= max_per_window && window_seconds > 0,
+            GuardError::InvalidVelocityLimits
+        );
+        Ok(Self {
+            max_per_tx,
+            max_per_window,
+            window_seconds,
+        })
+    }
+
+    /// Require `amount` to be within the per-transaction cap
+    pub fn assert_per_tx(&self, amount: u64) -> Result<()> {
+        require!(amount <= self.max_per_tx, GuardError::TransactionLimitExceeded);
+        Ok(())
+    }
+
+    /// The window `now` falls in
+    pub fn window_of(&self, now: i64) -> i64 {
+        now.div_euclid(self.window_seconds)
+    }
+}
+
+/// What one account has moved in its current window
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VelocityWindow {
+    /// The window `used` counts in, as `VelocityLimits::window_of` numbers it (8 bytes)
+    pub window: i64,
+    /// Amount moved so far in `window` (8 bytes)
+    pub used: u64,
+}
+
+impl VelocityWindow {
+    /// Serialized size, for `space = 8 + ... + VelocityWindow::SPACE`
+    pub const SPACE: usize = 8 + 8; // window + used
+
+    /// Count `amount` against `limits` at `now`, starting over if a new window has begun
+    ///
+    /// Fails, counting nothing, if `amount` is over the per-transaction cap
+    /// or would take the window past its cap.
+    pub fn record(&mut self, limits: &VelocityLimits, amount: u64, now: i64) -> Result<()> {
+        limits.assert_per_tx(amount)?;
+
+        let window = limits.window_of(now);
+        let used = if window == self.window { self.used } else { 0 };
+        let used = used
+            .checked_add(amount)
+            .filter(|used| *used <= limits.max_per_window)
+            .ok_or(GuardError::WindowLimitExceeded)?;
+
+        self.window = window;
+        self.used = used;
+        Ok(())
+    }
+
+    /// What can still move in the window `now` falls in
+    pub fn remaining(&self, limits: &VelocityLimits, now: i64) -> u64 {
+        if limits.window_of(now) == self.window {
+            limits.max_per_window.saturating_sub(self.used)
+        } else {
+            limits.max_per_window
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A day that starts at a window boundary
+    const MONDAY: i64 = 19_700 * DAY_SECONDS;
+
+    fn limits() -> VelocityLimits {
+        VelocityLimits::new(100, 250, DAY_SECONDS).unwrap()
+    }
+
+    #[test]
+    fn rejects_inconsistent_limits() {
+        for (max_per_tx, max_per_window, window_seconds) in [(0, 250, DAY_SECONDS), (300, 250, DAY_SECONDS), (100, 250, 0)] {
+            assert_eq!(
+                VelocityLimits::new(max_per_tx, max_per_window, window_seconds).unwrap_err(),
+                GuardError::InvalidVelocityLimits.into()
+            );
+        }
+    }
+
+    #[test]
+    fn counts_up_to_the_window_cap() {
+        let mut window = VelocityWindow::default();
+        window.record(&limits(), 100, MONDAY).unwrap();
+        window.record(&limits(), 100, MONDAY + 60).unwrap();
+        assert_eq!(window.remaining(&limits(), MONDAY + 120), 50);
+        assert_eq!(
+            window.record(&limits(), 51, MONDAY + 120).unwrap_err(),
+            GuardError::WindowLimitExceeded.into()
+        );
+        // The failed attempt counted nothing
+        window.record(&limits(), 50, MONDAY + 120).unwrap();
+        assert_eq!(window.used, 250);
+    }
+
+    #[test]
+    fn rejects_amounts_over_the_per_transaction_cap() {
+        let mut window = VelocityWindow::default();
+        assert_eq!(
+            window.record(&limits(), 101, MONDAY).unwrap_err(),
+            GuardError::TransactionLimitExceeded.into()
+        );
+        assert_eq!(window, VelocityWindow::default());
+    }
+
+    #[test]
+    fn starts_over_at_the_clock_window_not_a_day_after_first_use() {
+        let mut window = VelocityWindow::default();
+        window.record(&limits(), 100, MONDAY + DAY_SECONDS - 1).unwrap();
+        window.record(&limits(), 100, MONDAY + DAY_SECONDS - 1).unwrap();
+        window.record(&limits(), 50, MONDAY + DAY_SECONDS - 1).unwrap();
+
+        // One second later is Tuesday: a full cap again, and no sooner
+        assert_eq!(window.remaining(&limits(), MONDAY + DAY_SECONDS), 250);
+        window.record(&limits(), 100, MONDAY + DAY_SECONDS).unwrap();
+        assert_eq!(window.window, limits().window_of(MONDAY + DAY_SECONDS));
+        assert_eq!(window.used, 100);
+    }
+}
@@ -0,0 +1,123 @@
+//! Instruction data that says which layout it is in.
+//!
+//! A program that changes an instruction's arguments has clients built for
+//! every layout it ever had, so each payload leads with a version byte and
+//! the program decodes the rest by it. The byte is only worth anything if
+//! the program refuses the versions it does not know: read with some other
+//! version's layout, a payload's fields come out of the wrong offsets, and
+//! the program acts on amounts the signer never wrote.
+//!
+//! A [`Versioned`] decode looks up the version among the ones its
+//! arguments list in [`VersionedArgs::VERSIONS`], and fails on any other
+//! before a field is read:
+//!
+//! ```ignore
+//! use secref_guards::versioned::{Versioned, VersionedArgs};
+//!
+//! impl VersionedArgs for SwapArgs {
+//!     const VERSIONS: &'static [u8] = &[1, 2];
+//!
+//!     fn decode(version: u8, data: &[u8]) -> Option<Self> { ... }
+//! }
+//!
+//! let Versioned { version, args } = Versioned::<SwapArgs>::decode(data)?;
+//! ```
+
+use anchor_lang::prelude::*;
+
+use crate::error::GuardError;
+
+/// Arguments decoded from one of the layouts they have had
+pub trait VersionedArgs: Sized {
+    /// Every version the program decodes; data in any other is refused
+    const VERSIONS: &'static [u8];
+
+    /// The arguments of `version`, one of [`Self::VERSIONS`], if `data` is exactly that layout
+    fn decode(version: u8, data: &[u8]) -> Option<Self>;
+}
+
+/// Arguments together with the version they were decoded from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Versioned<T> {
+    /// The payload's first byte
+    pub version: u8,
+    /// The rest of the payload, decoded in that version's layout
+    pub args: T,
+}
+
+impl<T: VersionedArgs> Versioned<T> {
+    /// Split off the version byte and decode the rest in that version's layout
+    ///
+    /// Fails with `UnknownVersion` for a version `T` does not list, and with
+    /// `MalformedVersionedData` for no version byte or for data that is not
+    /// exactly its version's layout.
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let (&version, rest) = data.split_first().ok_or(GuardError::MalformedVersionedData)?;
+        require!(T::VERSIONS.contains(&version), GuardError::UnknownVersion);
+        let args = T::decode(version, rest).ok_or(GuardError::MalformedVersionedData)?;
+        Ok(Self { version, args })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// v1: amount (u64); v2: amount (u64) | memo (u8)
+    #[derive(Debug, PartialEq, Eq)]
+    struct Args {
+        amount: u64,
+        memo: u8,
+    }
+
+    impl VersionedArgs for Args {
+        const VERSIONS: &'static [u8] = &[1, 2];
+
+        fn decode(version: u8, data: &[u8]) -> Option<Self> {
+            let amount = u64::from_le_bytes(data.get(..8)?.try_into().ok()?);
+            match (version, data.len()) {
+                (1, 8) => Some(Self { amount, memo: 0 }),
+                (2, 9) => Some(Self { amount, memo: data[8] }),
+                _ => None,
+            }
+        }
+    }
+
+    fn payload(version: u8, fields: &[&[u8]]) -> Vec<u8> {
+        [&[version][..]].iter().chain(fields).flat_map(|field| field.iter().copied()).collect()
+    }
+
+    #[test]
+    fn decodes_each_listed_version_in_its_layout() {
+        let v1 = Versioned::<Args>::decode(&payload(1, &[&7u64.to_le_bytes()])).unwrap();
+        assert_eq!(v1, Versioned { version: 1, args: Args { amount: 7, memo: 0 } });
+
+        let v2 = Versioned::<Args>::decode(&payload(2, &[&7u64.to_le_bytes(), &[9]])).unwrap();
+        assert_eq!(v2, Versioned { version: 2, args: Args { amount: 7, memo: 9 } });
+    }
+
+    #[test]
+    fn rejects_versions_it_does_not_list() {
+        // Version 3 data that version 1's layout would read as amount 7
+        for version in [0, 3, u8::MAX] {
+            assert_eq!(
+                Versioned::<Args>::decode(&payload(version, &[&7u64.to_le_bytes()])),
+                Err(GuardError::UnknownVersion.into()),
+                "{version}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_data_that_is_not_exactly_its_versions_layout() {
+        assert_eq!(Versioned::<Args>::decode(&[]), Err(GuardError::MalformedVersionedData.into()));
+        assert_eq!(
+            Versioned::<Args>::decode(&payload(1, &[&7u64.to_le_bytes(), &[9]])),
+            Err(GuardError::MalformedVersionedData.into())
+        );
+        assert_eq!(
+            Versioned::<Args>::decode(&payload(2, &[&7u64.to_le_bytes()])),
+            Err(GuardError::MalformedVersionedData.into())
+        );
+    }
+}
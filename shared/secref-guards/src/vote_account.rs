@@ -0,0 +1,168 @@
+//! Guards for validator vote accounts, before stake is delegated to them.
+//!
+//! A vote account passed as an `UncheckedAccount` is only bytes: any
+//! program can own an account laid out like one and claim a 0% commission.
+//! These guards read the fields a delegator cares about, and only from
+//! accounts the Vote program owns.
+//!
+//! A validator can change its commission at any time, so a check passes
+//! for the delegation it guards and says nothing about later epochs.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::vote;
+
+use crate::error::GuardError;
+
+/// bincode tag of `VoteStateVersions::V1_14_11`
+const V1_14_11_STATE_TAG: u32 = 1;
+/// bincode tag of `VoteStateVersions::Current`
+const CURRENT_STATE_TAG: u32 = 2;
+
+/// The fields of a vote account a delegator checks
+///
+/// `V1_14_11` and `Current` share this prefix; the `V0_23_5` layout, and
+/// uninitialized accounts, which read as it, are rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VoteAccount {
+    /// The validator's identity
+    pub node_pubkey: Pubkey,
+    /// Can withdraw the validator's rewards and change its commission
+    pub authorized_withdrawer: Pubkey,
+    /// Percentage of staking rewards the validator keeps, 0 to 100
+    pub commission: u8,
+}
+
+/// Parse `account` as a vote account owned by the Vote program
+pub fn parse_vote_account(account: &AccountInfo) -> Result<VoteAccount> {
+    require_keys_eq!(*account.owner, vote::program::ID, GuardError::NotVoteAccount);
+
+    let data = account.try_borrow_data()?;
+    let tag = data.get(..4).ok_or(GuardError::InvalidVoteAccount)?;
+    require!(
+        matches!(u32::from_le_bytes(tag.try_into().unwrap()), V1_14_11_STATE_TAG | CURRENT_STATE_TAG),
+        GuardError::InvalidVoteAccount
+    );
+
+    // VoteState { node_pubkey, authorized_withdrawer, commission, .. }
+    let vote_account = VoteAccount {
+        node_pubkey: read_pubkey(&data, 4)?,
+        authorized_withdrawer: read_pubkey(&data, 36)?,
+        commission: *data.get(68).ok_or(GuardError::InvalidVoteAccount)?,
+    };
+    require!(vote_account.commission <= 100, GuardError::InvalidVoteAccount);
+    Ok(vote_account)
+}
+
+/// Require `account` to be a vote account keeping at most `max_commission` percent of rewards
+pub fn assert_commission_at_most(account: &AccountInfo, max_commission: u8) -> Result<VoteAccount> {
+    let vote_account = parse_vote_account(account)?;
+    require!(
+        vote_account.commission <= max_commission,
+        GuardError::CommissionTooHigh
+    );
+    Ok(vote_account)
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Result<Pubkey> {
+    let bytes = data.get(offset..offset + 32).ok_or(GuardError::InvalidVoteAccount)?;
+    Ok(Pubkey::new_from_array(bytes.try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestAccount {
+        fn new(owner: Pubkey, data: Vec<u8>) -> Self {
+            Self {
+                key: Pubkey::new_unique(),
+                owner,
+                lamports: 1,
+                data,
+            }
+        }
+
+        fn info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                false,
+                false,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    /// The fixed prefix of a vote account's data, padded to a real account's size
+    fn vote_data(tag: u32, node: Pubkey, withdrawer: Pubkey, commission: u8) -> Vec<u8> {
+        let mut data = tag.to_le_bytes().to_vec();
+        data.extend_from_slice(node.as_ref());
+        data.extend_from_slice(withdrawer.as_ref());
+        data.push(commission);
+        data.resize(3762, 0);
+        data
+    }
+
+    #[test]
+    fn reads_the_validators_fields() {
+        let (node, withdrawer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        for tag in [V1_14_11_STATE_TAG, CURRENT_STATE_TAG] {
+            let mut account = TestAccount::new(vote::program::ID, vote_data(tag, node, withdrawer, 7));
+            assert_eq!(
+                parse_vote_account(&account.info()).unwrap(),
+                VoteAccount {
+                    node_pubkey: node,
+                    authorized_withdrawer: withdrawer,
+                    commission: 7,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_look_alikes_owned_by_other_programs() {
+        let data = vote_data(CURRENT_STATE_TAG, Pubkey::new_unique(), Pubkey::new_unique(), 0);
+        let mut account = TestAccount::new(Pubkey::new_unique(), data);
+        assert_eq!(
+            parse_vote_account(&account.info()).unwrap_err(),
+            GuardError::NotVoteAccount.into()
+        );
+    }
+
+    #[test]
+    fn rejects_uninitialized_and_malformed_accounts() {
+        let mut uninitialized = TestAccount::new(vote::program::ID, vec![0; 3762]);
+        let mut truncated = TestAccount::new(vote::program::ID, CURRENT_STATE_TAG.to_le_bytes().to_vec());
+        let mut over_100 = TestAccount::new(
+            vote::program::ID,
+            vote_data(CURRENT_STATE_TAG, Pubkey::new_unique(), Pubkey::new_unique(), 101),
+        );
+        for account in [&mut uninitialized, &mut truncated, &mut over_100] {
+            assert_eq!(
+                parse_vote_account(&account.info()).unwrap_err(),
+                GuardError::InvalidVoteAccount.into()
+            );
+        }
+    }
+
+    #[test]
+    fn bounds_the_commission() {
+        let data = vote_data(CURRENT_STATE_TAG, Pubkey::new_unique(), Pubkey::new_unique(), 10);
+        let mut account = TestAccount::new(vote::program::ID, data);
+        assert!(assert_commission_at_most(&account.info(), 10).is_ok());
+        assert_eq!(
+            assert_commission_at_most(&account.info(), 9).unwrap_err(),
+            GuardError::CommissionTooHigh.into()
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Several actors' transactions interleaved slot by slot, in an order a seed picks.
+//!
+//! A front-run, a race to initialize an account, a maker and a keeper
+//! reaching for the same expired order: each exploit hinges on which of
+//! several transactions in one slot the leader runs first. Every [`Actor`]
+//! submits a stream of transactions, one per slot; in each slot the leader
+//! takes every actor's next transaction and runs them in the order the
+//! [`Ordering`] picks, then the clock moves on a slot. A seeded ordering
+//! is a shuffle by [`Rng`], so an interleaving a test finds can be replayed
+//! from its seed, and [`sweep`]ing seeds covers the orders a leader could
+//! choose.
+//!
+//! ```ignore
+//! let schedule = || {
+//!     Schedule::new(vec![
+//!         Actor::new("victim").submits("buy", |world| world.execute(program!(sale), victim_buy(), buy(10))),
+//!         Actor::new("attacker").submits("buy first", |world| world.execute(program!(sale), attacker_buy(), buy(90))),
+//!     ])
+//! };
+//! for (world, interleaving) in sweep(&world, 0..16, schedule) {
+//!     println!("{interleaving}");
+//! }
+//! ```
+
+use std::fmt;
+use std::ops::Range;
+
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use secref_testkit::runtime;
+
+use crate::explore::outcome;
+use crate::rng::Rng;
+use crate::world::World;
+
+/// The instructions of one transaction, sent to the world
+type Sender<'a> = Box<dyn FnMut(&mut World) -> ProgramResult + 'a>;
+
+enum Submission<'a> {
+    Transaction { label: String, send: Sender<'a> },
+    Wait,
+}
+
+/// One party and the transactions it submits, a slot apart
+pub struct Actor<'a> {
+    name: &'static str,
+    submissions: Vec<Submission<'a>>,
+}
+
+impl<'a> Actor<'a> {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            submissions: Vec::new(),
+        }
+    }
+
+    /// Submit a transaction in the actor's next slot
+    ///
+    /// `send` runs its instructions; they land or revert together, as a
+    /// transaction's do, whatever `send` did before it failed.
+    pub fn submits(mut self, label: impl Into<String>, send: impl FnMut(&mut World) -> ProgramResult + 'a) -> Self {
+        self.submissions.push(Submission::Transaction {
+            label: label.into(),
+            send: Box::new(send),
+        });
+        self
+    }
+
+    /// Submit nothing for `slots` slots
+    pub fn waits(mut self, slots: usize) -> Self {
+        self.submissions.extend((0..slots).map(|_| Submission::Wait));
+        self
+    }
+}
+
+/// How the leader orders the transactions of one slot
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Ordering {
+    /// In the order the actors were listed
+    Submitted,
+    /// Shuffled each slot by an [`Rng`] seeded with this
+    Seeded(u64),
+    /// Actors by name, as a bundle would fix them; actors not named follow in listed order
+    Explicit(Vec<&'static str>),
+}
+
+/// One transaction as the leader ran it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Landed {
+    pub slot: u64,
+    pub actor: &'static str,
+    pub label: String,
+    pub result: ProgramResult,
+}
+
+/// What one run of a [`Schedule`] did, transaction by transaction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Interleaving {
+    pub ordering: Ordering,
+    pub landed: Vec<Landed>,
+}
+
+impl Interleaving {
+    /// Whether `actor`'s transaction `label` ran and succeeded
+    pub fn succeeded(&self, actor: &str, label: &str) -> bool {
+        self.landed
+            .iter()
+            .any(|landed| landed.actor == actor && landed.label == label && landed.result.is_ok())
+    }
+
+    /// Whether `first`'s transaction ran before `second`'s
+    pub fn ran_before(&self, first: (&str, &str), second: (&str, &str)) -> bool {
+        let position = |(actor, label): (&str, &str)| {
+            self.landed
+                .iter()
+                .position(|landed| landed.actor == actor && landed.label == label)
+        };
+        matches!((position(first), position(second)), (Some(first), Some(second)) if first < second)
+    }
+}
+
+impl fmt::Display for Interleaving {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:?}:", self.ordering)?;
+        for landed in &self.landed {
+            writeln!(
+                f,
+                "  slot {:>3}  {}: {} - {}",
+                landed.slot,
+                landed.actor,
+                landed.label,
+                outcome(landed.result.clone())
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Actors' transaction streams, to interleave against a world
+pub struct Schedule<'a> {
+    actors: Vec<Actor<'a>>,
+    seconds_per_slot: i64,
+}
+
+impl<'a> Schedule<'a> {
+    pub fn new(actors: Vec<Actor<'a>>) -> Self {
+        Self {
+            actors,
+            seconds_per_slot: 0,
+        }
+    }
+
+    /// Move the clock's time on by `seconds` with every slot, for handlers that read `unix_timestamp`
+    pub fn seconds_per_slot(mut self, seconds: i64) -> Self {
+        self.seconds_per_slot = seconds;
+        self
+    }
+
+    /// Run every actor's transactions on `world`, slot by slot, in the order `ordering` picks
+    ///
+    /// Slots start at the clock's current slot, and the clock is left one
+    /// slot past the last.
+    pub fn run(mut self, world: &mut World, ordering: &Ordering) -> Interleaving {
+        let mut rng = match ordering {
+            Ordering::Seeded(seed) => Some(Rng::new(*seed)),
+            _ => None,
+        };
+        let slots = self.actors.iter().map(|actor| actor.submissions.len()).max().unwrap_or(0);
+        let mut streams: Vec<_> = self
+            .actors
+            .iter_mut()
+            .map(|actor| (actor.name, actor.submissions.iter_mut()))
+            .collect();
+        let mut landed = Vec::new();
+
+        for _ in 0..slots {
+            let slot = runtime::clock().slot;
+            let mut pending: Vec<_> = streams
+                .iter_mut()
+                .filter_map(|(actor, stream)| match stream.next()? {
+                    Submission::Transaction { label, send } => Some((*actor, label, send)),
+                    Submission::Wait => None,
+                })
+                .collect();
+            match (ordering, rng.as_mut()) {
+                (Ordering::Seeded(_), Some(rng)) => {
+                    for last in (1..pending.len()).rev() {
+                        pending.swap(last, rng.up_to(last as u64) as usize);
+                    }
+                }
+                (Ordering::Explicit(order), _) => {
+                    let rank = |actor: &str| order.iter().position(|name| *name == actor).unwrap_or(usize::MAX);
+                    pending.sort_by_key(|(actor, ..)| rank(actor));
+                }
+                _ => {}
+            }
+
+            for (actor, label, send) in pending {
+                let before = world.clone();
+                let result = send(world);
+                if result.is_err() {
+                    *world = before;
+                }
+                landed.push(Landed {
+                    slot,
+                    actor,
+                    label: label.clone(),
+                    result,
+                });
+            }
+            runtime::warp_by_slots(1);
+            runtime::warp_by(self.seconds_per_slot);
+        }
+
+        Interleaving {
+            ordering: ordering.clone(),
+            landed,
+        }
+    }
+}
+
+/// Run the schedule `build` makes once per seed, each on a fresh copy of `world` from the same slot and time
+pub fn sweep<'a>(world: &World, seeds: Range<u64>, build: impl Fn() -> Schedule<'a>) -> Vec<(World, Interleaving)> {
+    let start = runtime::clock();
+    seeds
+        .map(|seed| {
+            runtime::warp_to_slot(start.slot);
+            runtime::warp_to(start.unix_timestamp);
+            let mut world = world.clone();
+            let interleaving = build().run(&mut world, &Ordering::Seeded(seed));
+            (world, interleaving)
+        })
+        .collect()
+}
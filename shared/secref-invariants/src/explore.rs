@@ -0,0 +1,120 @@
+//! Random operation sequences, and the first step that breaks conservation.
+
+use std::fmt;
+
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+
+use crate::rng::Rng;
+
+/// Sequences tried per path
+pub const RUNS: u64 = 64;
+/// Operations per sequence
+pub const STEPS: usize = 48;
+
+/// Which of an example's implementations the operations go through
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Path {
+    Vulnerable,
+    Secure,
+}
+
+/// One example's accounts, the operations its users can attempt, and their value
+pub trait Model {
+    /// A fresh deployment whose operations go through `path`
+    fn new(path: Path, rng: &mut Rng) -> Self;
+
+    /// Attempt one operation chosen by `rng`, describing it and its outcome
+    ///
+    /// An operation the program rejects is part of the sequence too; it
+    /// must leave the world as it was.
+    fn step(&mut self, rng: &mut Rng) -> String;
+
+    /// Everything users could walk away with: wallets, recorded balances and fees owed
+    fn total(&self) -> u128;
+
+    /// Value the example creates (positive) or destroys (negative) on purpose so far
+    fn minted(&self) -> i128 {
+        0
+    }
+}
+
+/// A sequence after which the total no longer matches the starting total plus what was minted
+#[derive(Clone, Debug)]
+pub struct Violation {
+    pub path: Path,
+    pub seed: u64,
+    /// Each operation up to and including the one that broke the invariant
+    pub trace: Vec<String>,
+    pub expected: i128,
+    pub actual: i128,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:?} path, seed {}: total {} after step {}, expected {} ({:+})",
+            self.path,
+            self.seed,
+            self.actual,
+            self.trace.len(),
+            self.expected,
+            self.actual - self.expected
+        )?;
+        for (index, step) in self.trace.iter().enumerate() {
+            writeln!(f, "  {:>3}. {step}", index + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// Run `runs` seeded sequences of `steps` operations on `path`, returning the first violation
+pub fn explore<M: Model>(path: Path, runs: u64, steps: usize) -> Option<Violation> {
+    for seed in 0..runs {
+        let mut rng = Rng::new(seed);
+        let mut model = M::new(path, &mut rng);
+        let start = total(&model);
+        let mut trace = Vec::with_capacity(steps);
+
+        for _ in 0..steps {
+            trace.push(model.step(&mut rng));
+            let expected = start + model.minted();
+            let actual = total(&model);
+            if actual != expected {
+                return Some(Violation {
+                    path,
+                    seed,
+                    trace,
+                    expected,
+                    actual,
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Assert every explored sequence on `path` conserves value
+pub fn assert_conserved<M: Model>(path: Path) {
+    if let Some(violation) = explore::<M>(path, RUNS, STEPS) {
+        panic!("value was created or destroyed:\n{violation}");
+    }
+}
+
+/// Assert some explored sequence on `path` creates or destroys value, and return it
+pub fn assert_violated<M: Model>(path: Path) -> Violation {
+    explore::<M>(path, RUNS, STEPS)
+        .unwrap_or_else(|| panic!("{RUNS} sequences of {STEPS} operations on the {path:?} path all conserved value"))
+}
+
+/// How an operation ended, for its line in the trace
+pub fn outcome(result: ProgramResult) -> String {
+    match result {
+        Ok(()) => "ok".to_string(),
+        Err(error) => format!("rejected ({error})"),
+    }
+}
+
+fn total<M: Model>(model: &M) -> i128 {
+    i128::try_from(model.total()).expect("total fits in i128")
+}
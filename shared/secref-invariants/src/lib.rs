@@ -0,0 +1,37 @@
+//! "No value creation", checked over random operation sequences.
+//!
+//! Lamports and SPL tokens are conserved by the runtime and the Token
+//! program: no instruction can mint them by moving them around. What a
+//! program can inflate is its own accounting - the deposits, credits and
+//! shares it records as owed. So the invariant here is over everything a
+//! user could walk away with: what their wallets hold, plus what the
+//! program records as theirs, plus fees owed to the protocol. That sum
+//! must stay constant, up to whatever the example mints or burns on
+//! purpose.
+//!
+//! Each example gets a [`Model`]: a [`World`] holding its accounts, the
+//! operations users can attempt, and how to total their value. [`explore`]
+//! runs seeded random operation sequences against either [`Path`] and
+//! reports the first step that breaks the invariant, with its trace.
+//!
+//! ```ignore
+//! // The vulnerable transfer mints credits when sender and recipient alias
+//! assert_violated::<CreditLedger>(Path::Vulnerable);
+//! assert_conserved::<CreditLedger>(Path::Secure);
+//! ```
+//!
+//! Some exploits need no random sequence, only the right order: a
+//! front-run, a race to initialize. [`actors`] interleaves the
+//! transactions of several actors slot by slot, in orders [`sweep`] draws
+//! from seeds, so a test can show which orders the vulnerable path loses
+//! in and that the secure path loses in none.
+
+pub mod actors;
+pub mod explore;
+pub mod rng;
+pub mod world;
+
+pub use actors::{sweep, Actor, Interleaving, Landed, Ordering, Schedule};
+pub use explore::{assert_conserved, assert_violated, explore, outcome, Model, Path, Violation, RUNS, STEPS};
+pub use rng::Rng;
+pub use world::{Entry, Program, World};
@@ -0,0 +1,146 @@
+//! Every account of one example's deployment, run through its real entrypoint.
+
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::InstructionData;
+use secref_testkit::runtime;
+pub use secref_testkit::runtime::Entry;
+use secref_testkit::TestAccount;
+
+/// A program to run instructions against: its id and its entrypoint
+#[derive(Clone, Copy)]
+pub struct Program {
+    pub id: Pubkey,
+    pub entry: Entry,
+}
+
+/// The [`Program`] of an example crate, by crate name: `program!(credit_ledger)`
+#[macro_export]
+macro_rules! program {
+    ($program:ident) => {
+        $crate::Program {
+            id: $program::ID,
+            entry: $program::entry,
+        }
+    };
+}
+
+/// Accounts by address, as they stand between transactions
+///
+/// Instructions take their accounts by the program's generated client
+/// structs (`accounts::SecureTransfer { .. }`), so the signer and writable
+/// flags are the ones a real client would send.
+#[derive(Clone, Debug)]
+pub struct World {
+    accounts: BTreeMap<Pubkey, TestAccount>,
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl World {
+    /// A world holding only the System Program
+    pub fn new() -> Self {
+        let mut world = Self {
+            accounts: BTreeMap::new(),
+        };
+        world.add(TestAccount::system_program());
+        world
+    }
+
+    /// Add `account`, replacing whatever was at its address; returns the address
+    pub fn add(&mut self, account: TestAccount) -> Pubkey {
+        let key = account.key;
+        self.accounts.insert(key, account);
+        key
+    }
+
+    pub fn account(&self, key: &Pubkey) -> &TestAccount {
+        self.get(key).unwrap_or_else(|| panic!("world has no account at {key}"))
+    }
+
+    /// The account at `key`, if there is one
+    pub fn get(&self, key: &Pubkey) -> Option<&TestAccount> {
+        self.accounts.get(key)
+    }
+
+    pub fn lamports(&self, key: &Pubkey) -> u64 {
+        self.account(key).lamports
+    }
+
+    /// The stored Anchor account at `key`
+    pub fn state<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        self.account(key).state()
+    }
+
+    /// Run one instruction as its own transaction, keeping its writes only if it succeeds
+    ///
+    /// An account passed in two roles is one account behind both, as the
+    /// runtime passes it: its lamports are shared, and Anchor, which
+    /// deserializes each role separately and writes them back in field
+    /// order, leaves the last write. A successful instruction must leave
+    /// the lamport total of its accounts unchanged, as the runtime requires.
+    pub fn execute(
+        &mut self,
+        program: Program,
+        accounts: impl ToAccountMetas,
+        instruction: impl InstructionData,
+    ) -> ProgramResult {
+        self.invoke(program, &accounts.to_account_metas(None), &instruction.data())
+    }
+
+    /// [`execute`](Self::execute) with the account metas and instruction data already built
+    pub fn invoke(&mut self, program: Program, metas: &[AccountMeta], data: &[u8]) -> ProgramResult {
+        runtime::enter(program.id);
+        runtime::instruction_accounts(&metas.iter().map(|meta| meta.pubkey).collect::<Vec<_>>());
+        // One copy per distinct account, with the flags of all its roles
+        let mut copies: Vec<TestAccount> = Vec::new();
+        for meta in metas {
+            match copies.iter_mut().find(|copy| copy.key == meta.pubkey) {
+                Some(copy) => {
+                    copy.is_signer |= meta.is_signer;
+                    copy.is_writable |= meta.is_writable;
+                }
+                None => {
+                    let mut account = self.account(&meta.pubkey).clone();
+                    account.is_signer = meta.is_signer;
+                    account.is_writable = meta.is_writable;
+                    copies.push(account);
+                }
+            }
+        }
+
+        let before = lamport_total(&copies);
+        {
+            let distinct: Vec<_> = copies.iter_mut().map(TestAccount::info).collect();
+            let infos: Vec<_> = metas
+                .iter()
+                .map(|meta| {
+                    let info = distinct.iter().find(|info| *info.key == meta.pubkey);
+                    info.expect("every meta has a copy").clone()
+                })
+                .collect();
+            (program.entry)(&program.id, &infos, data)?;
+        }
+        copies.iter_mut().for_each(TestAccount::sync);
+        assert_eq!(
+            lamport_total(&copies),
+            before,
+            "a successful instruction changed the lamport supply"
+        );
+
+        for account in copies {
+            self.accounts.insert(account.key, account);
+        }
+        Ok(())
+    }
+}
+
+fn lamport_total(accounts: &[TestAccount]) -> u128 {
+    accounts.iter().map(|account| u128::from(account.lamports)).sum()
+}
@@ -0,0 +1,201 @@
+//! 04_arithmetic_overflow: a vault's balance moves only with its owner's wallet, and with interest.
+//!
+//! The program records balances without moving lamports, so each owner's
+//! wallet is tracked here: a deposit debits it and a withdrawal credits
+//! it. Interest is the one documented mint, worth exactly
+//! `balance * rate / 10_000` at the moment it is applied.
+
+use anchor_lang::prelude::*;
+use arithmetic_vault::{accounts, instruction, ArithmeticVault};
+use secref_invariants::{assert_conserved, assert_violated, outcome, program, Model, Path, Rng, World};
+use secref_testkit::TestAccount;
+
+const NAMES: [&str; 3] = ["alice", "bob", "carol"];
+const OPENING_WALLET: u128 = 1_000_000;
+
+struct Vaults {
+    world: World,
+    path: Path,
+    owners: Vec<Pubkey>,
+    vaults: Vec<Pubkey>,
+    /// What each owner holds outside their vault
+    wallets: Vec<u128>,
+    minted: i128,
+}
+
+impl Vaults {
+    fn balance(&self, owner: usize) -> u64 {
+        self.world.state::<ArithmeticVault>(&self.vaults[owner]).balance
+    }
+}
+
+impl Model for Vaults {
+    fn new(path: Path, _rng: &mut Rng) -> Self {
+        let mut world = World::new();
+        let owners: Vec<Pubkey> = NAMES.iter().map(|_| world.add(TestAccount::signer())).collect();
+        let vaults = owners
+            .iter()
+            .map(|&owner| {
+                world.add(TestAccount::anchor(&ArithmeticVault {
+                    owner,
+                    balance: 0,
+                    total_deposits: 0,
+                    total_withdrawals: 0,
+                }))
+            })
+            .collect();
+
+        Self {
+            world,
+            path,
+            owners,
+            vaults,
+            wallets: vec![OPENING_WALLET; NAMES.len()],
+            minted: 0,
+        }
+    }
+
+    fn step(&mut self, rng: &mut Rng) -> String {
+        let who = rng.up_to(NAMES.len() as u64 - 1) as usize;
+        let (vault, owner) = (self.vaults[who], self.owners[who]);
+        let wallet = u64::try_from(self.wallets[who]).unwrap_or(u64::MAX);
+        let balance = self.balance(who);
+        let vulnerable = self.path == Path::Vulnerable;
+
+        match rng.up_to(3) {
+            0 => {
+                let amount = rng.up_to(wallet);
+                let result = if vulnerable {
+                    self.world.execute(
+                        program!(arithmetic_vault),
+                        accounts::VulnerableDeposit {
+                            vault,
+                            depositor: owner,
+                        },
+                        instruction::VulnerableDeposit { amount },
+                    )
+                } else {
+                    self.world.execute(
+                        program!(arithmetic_vault),
+                        accounts::SecureDeposit {
+                            vault,
+                            depositor: owner,
+                        },
+                        instruction::SecureDeposit { amount },
+                    )
+                };
+                if result.is_ok() {
+                    self.wallets[who] -= u128::from(amount);
+                }
+                format!("{} deposits {amount}: {}", NAMES[who], outcome(result))
+            }
+            1 => {
+                let first = rng.up_to(wallet);
+                let amounts = vec![first, rng.up_to(wallet - first)];
+                let sum: u128 = amounts.iter().map(|&amount| u128::from(amount)).sum();
+                let described = format!("{amounts:?}");
+                let result = if vulnerable {
+                    self.world.execute(
+                        program!(arithmetic_vault),
+                        accounts::VulnerableBatchDeposit {
+                            vault,
+                            depositor: owner,
+                        },
+                        instruction::VulnerableBatchDeposit { amounts },
+                    )
+                } else {
+                    self.world.execute(
+                        program!(arithmetic_vault),
+                        accounts::SecureBatchDeposit {
+                            vault,
+                            depositor: owner,
+                        },
+                        instruction::SecureBatchDeposit { amounts },
+                    )
+                };
+                if result.is_ok() {
+                    self.wallets[who] -= sum;
+                }
+                format!("{} batch-deposits {described}: {}", NAMES[who], outcome(result))
+            }
+            2 => {
+                // Sometimes more than the vault holds
+                let amount = rng.up_to(balance.saturating_mul(2).max(1));
+                let result = if vulnerable {
+                    self.world.execute(
+                        program!(arithmetic_vault),
+                        accounts::VulnerableWithdraw {
+                            vault,
+                            withdrawer: owner,
+                        },
+                        instruction::VulnerableWithdraw { amount },
+                    )
+                } else {
+                    self.world.execute(
+                        program!(arithmetic_vault),
+                        accounts::SecureWithdraw {
+                            vault,
+                            withdrawer: owner,
+                        },
+                        instruction::SecureWithdraw { amount },
+                    )
+                };
+                if result.is_ok() {
+                    self.wallets[who] += u128::from(amount);
+                }
+                format!("{} withdraws {amount} of {balance}: {}", NAMES[who], outcome(result))
+            }
+            _ => {
+                let rate_basis_points = rng.up_to(1_000);
+                let result = if vulnerable {
+                    self.world.execute(
+                        program!(arithmetic_vault),
+                        accounts::VulnerableApplyInterest {
+                            vault,
+                            authority: owner,
+                        },
+                        instruction::VulnerableApplyInterest { rate_basis_points },
+                    )
+                } else {
+                    self.world.execute(
+                        program!(arithmetic_vault),
+                        accounts::SecureApplyInterest {
+                            vault,
+                            authority: owner,
+                        },
+                        instruction::SecureApplyInterest { rate_basis_points },
+                    )
+                };
+                if result.is_ok() {
+                    let interest = u128::from(balance) * u128::from(rate_basis_points) / 10_000;
+                    self.minted += i128::try_from(interest).expect("interest fits in i128");
+                }
+                format!(
+                    "{rate_basis_points} bps interest on {}'s {balance}: {}",
+                    NAMES[who],
+                    outcome(result)
+                )
+            }
+        }
+    }
+
+    fn total(&self) -> u128 {
+        let recorded: u128 = (0..NAMES.len()).map(|owner| u128::from(self.balance(owner))).sum();
+        self.wallets.iter().sum::<u128>() + recorded
+    }
+
+    fn minted(&self) -> i128 {
+        self.minted
+    }
+}
+
+#[test]
+fn vulnerable_arithmetic_creates_balance_by_wrapping() {
+    let violation = assert_violated::<Vaults>(Path::Vulnerable);
+    assert!(violation.actual > violation.expected, "{violation}");
+}
+
+#[test]
+fn secure_arithmetic_conserves_balances() {
+    assert_conserved::<Vaults>(Path::Secure);
+}
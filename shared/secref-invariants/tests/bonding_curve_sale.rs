@@ -0,0 +1,134 @@
+//! 35_bonding_curve_pricing: a buyer front-run on the curve, with and without a slippage bound.
+//!
+//! Every purchase moves the price for the next one. The victim quotes a
+//! buy against the current supply and submits it; an attacker's larger
+//! buy lands in the same slot. In the orders the leader runs the attacker
+//! first, the victim's buy is priced further up the curve. Both paths
+//! check `max_cost`, so what the victim pays is up to the bound they
+//! sign: without one they overpay, with one their buy fails instead.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::system_program;
+use bonding_curve_sale::curve::{self, Curve};
+use bonding_curve_sale::{accounts, instruction, ErrorCode as SaleError, Sale};
+use secref_invariants::{program, sweep, Actor, Schedule, World};
+use secref_testkit::TestAccount;
+
+const LINEAR: Curve = Curve::Linear {
+    base_price: 1_000_000,
+    slope: 1_000,
+};
+const SUPPLY_CAP: u64 = 1_000_000;
+const VICTIM_BUYS: u64 = 100;
+const ATTACKER_BUYS: u64 = 10_000;
+const SEEDS: std::ops::Range<u64> = 0..16;
+
+struct Market {
+    world: World,
+    sale: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+    victim: (Pubkey, Pubkey),
+    attacker: (Pubkey, Pubkey),
+}
+
+/// A fresh sale on LINEAR, and two buyers with token accounts for it
+fn market() -> Market {
+    let mut world = World::new();
+    let token_program = world.add(TestAccount::token_program());
+    let mint = Pubkey::new_unique();
+    let (sale, bump) = Pubkey::find_program_address(&[b"sale", mint.as_ref()], &bonding_curve_sale::ID);
+    world.add(
+        TestAccount::anchor(&Sale {
+            mint,
+            curve: LINEAR,
+            supply_cap: SUPPLY_CAP,
+            bump,
+        })
+        .at(sale),
+    );
+    world.add(TestAccount::mint(sale, 0, 0).at(mint));
+    let mut buyer = || {
+        let wallet = world.add(TestAccount::signer().with_lamports(1_000_000_000_000_000));
+        (wallet, world.add(TestAccount::token_account(mint, wallet, 0)))
+    };
+    let (victim, attacker) = (buyer(), buyer());
+    Market {
+        world,
+        sale,
+        mint,
+        token_program,
+        victim,
+        attacker,
+    }
+}
+
+fn buy(
+    market: &Market,
+    (buyer, buyer_tokens): (Pubkey, Pubkey),
+    amount: u64,
+    max_cost: u64,
+) -> impl FnMut(&mut World) -> ProgramResult {
+    let (sale, mint, token_program) = (market.sale, market.mint, market.token_program);
+    move |world| {
+        world.execute(
+            program!(bonding_curve_sale),
+            accounts::Buy {
+                sale,
+                mint,
+                buyer_tokens,
+                buyer,
+                token_program,
+                system_program: system_program::ID,
+            },
+            instruction::SecureBuy { amount, max_cost },
+        )
+    }
+}
+
+/// The victim's buy, bounded by `max_cost`, and the attacker's in the same slot
+fn front_run(market: &Market, max_cost: u64) -> Schedule<'_> {
+    Schedule::new(vec![
+        Actor::new("victim").submits("buy", buy(market, market.victim, VICTIM_BUYS, max_cost)),
+        Actor::new("attacker").submits("buy", buy(market, market.attacker, ATTACKER_BUYS, u64::MAX)),
+    ])
+}
+
+#[test]
+fn an_unbounded_buy_pays_more_whenever_it_is_front_run() {
+    let market = market();
+    let quote = curve::cost(&LINEAR, 0, VICTIM_BUYS).unwrap();
+    let start = market.world.lamports(&market.victim.0);
+
+    let runs = sweep(&market.world, SEEDS, || front_run(&market, u64::MAX));
+    assert!(runs.iter().any(|(_, run)| run.ran_before(("attacker", "buy"), ("victim", "buy"))));
+    assert!(runs.iter().any(|(_, run)| run.ran_before(("victim", "buy"), ("attacker", "buy"))));
+    for (world, interleaving) in runs {
+        let paid = start - world.lamports(&market.victim.0);
+        if interleaving.ran_before(("attacker", "buy"), ("victim", "buy")) {
+            assert_eq!(paid, curve::cost(&LINEAR, ATTACKER_BUYS, VICTIM_BUYS).unwrap(), "{interleaving}");
+            assert!(paid > quote);
+        } else {
+            assert_eq!(paid, quote, "{interleaving}");
+        }
+    }
+}
+
+#[test]
+fn a_buy_bounded_by_its_quote_fails_rather_than_pay_more_in_any_order() {
+    let market = market();
+    let quote = curve::cost(&LINEAR, 0, VICTIM_BUYS).unwrap();
+    let start = market.world.lamports(&market.victim.0);
+
+    for (world, interleaving) in sweep(&market.world, SEEDS, || front_run(&market, quote)) {
+        let paid = start - world.lamports(&market.victim.0);
+        let victim = interleaving.landed.iter().find(|landed| landed.actor == "victim").unwrap();
+        if interleaving.ran_before(("attacker", "buy"), ("victim", "buy")) {
+            assert_eq!(victim.result, Err(Error::from(SaleError::SlippageExceeded).into()), "{interleaving}");
+            assert_eq!(paid, 0);
+        } else {
+            assert_eq!(paid, quote, "{interleaving}");
+        }
+    }
+}
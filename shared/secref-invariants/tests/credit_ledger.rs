@@ -0,0 +1,150 @@
+//! 16_duplicate_account_roles: credits are only created by `mint_credits`.
+//!
+//! Transfers pick their sender, recipient and relayer from everyone with a
+//! balance, so some of them pass one balance in two roles.
+
+use anchor_lang::prelude::*;
+use credit_ledger::{accounts, instruction, Balance, Ledger, COUNCIL_SIZE};
+use secref_invariants::{assert_conserved, assert_violated, outcome, program, Model, Path, Rng, World};
+use secref_testkit::TestAccount;
+
+const NAMES: [&str; 4] = ["alice", "bob", "carol", "relayer"];
+const OPENING_CREDITS: u64 = 1_000;
+
+struct CreditLedger {
+    world: World,
+    path: Path,
+    ledger: Pubkey,
+    admin: Pubkey,
+    /// Wallets, the first `COUNCIL_SIZE` of which sit on the council
+    wallets: Vec<Pubkey>,
+    balances: Vec<Pubkey>,
+    minted: i128,
+}
+
+impl Model for CreditLedger {
+    fn new(path: Path, _rng: &mut Rng) -> Self {
+        let mut world = World::new();
+        let admin = world.add(TestAccount::signer());
+        let wallets: Vec<Pubkey> = NAMES.iter().map(|_| world.add(TestAccount::signer())).collect();
+
+        let mut council = [Pubkey::default(); COUNCIL_SIZE];
+        council.copy_from_slice(&wallets[..COUNCIL_SIZE]);
+        let (ledger, bump) = Pubkey::find_program_address(&[b"ledger"], &credit_ledger::ID);
+        world.add(TestAccount::anchor(&Ledger { admin, council, bump }).at(ledger));
+
+        let balances = wallets
+            .iter()
+            .map(|&authority| {
+                let (address, bump) =
+                    Pubkey::find_program_address(&[b"balance", authority.as_ref()], &credit_ledger::ID);
+                world.add(
+                    TestAccount::anchor(&Balance {
+                        authority,
+                        amount: OPENING_CREDITS,
+                        bump,
+                    })
+                    .at(address),
+                )
+            })
+            .collect();
+
+        Self {
+            world,
+            path,
+            ledger,
+            admin,
+            wallets,
+            balances,
+            minted: 0,
+        }
+    }
+
+    fn step(&mut self, rng: &mut Rng) -> String {
+        if rng.one_in(6) {
+            let to = rng.up_to(NAMES.len() as u64 - 1) as usize;
+            let amount = rng.up_to(500);
+            let result = self.world.execute(
+                program!(credit_ledger),
+                accounts::MintCredits {
+                    ledger: self.ledger,
+                    balance: self.balances[to],
+                    admin: self.admin,
+                },
+                instruction::MintCredits { amount },
+            );
+            if result.is_ok() {
+                self.minted += i128::from(amount);
+            }
+            return format!("mint {amount} to {}: {}", NAMES[to], outcome(result));
+        }
+
+        // Any council member sends; anyone, themselves included, receives, relays and approves
+        let sender = rng.up_to(COUNCIL_SIZE as u64 - 1) as usize;
+        let recipient = rng.up_to(NAMES.len() as u64 - 1) as usize;
+        let relayer = rng.up_to(NAMES.len() as u64 - 1) as usize;
+        let approver = rng.up_to(COUNCIL_SIZE as u64 - 1) as usize;
+        let held = self.world.state::<Balance>(&self.balances[sender]).amount;
+        let amount = rng.up_to(held);
+        let fee = rng.up_to(10);
+
+        let result = match self.path {
+            Path::Vulnerable => self.world.execute(
+                program!(credit_ledger),
+                accounts::VulnerableTransfer {
+                    ledger: self.ledger,
+                    from: self.balances[sender],
+                    to: self.balances[recipient],
+                    relayer_balance: self.balances[relayer],
+                    authority: self.wallets[sender],
+                    relayer: self.wallets[relayer],
+                    approver: self.wallets[approver],
+                },
+                instruction::VulnerableTransfer { amount, fee },
+            ),
+            Path::Secure => self.world.execute(
+                program!(credit_ledger),
+                accounts::SecureTransfer {
+                    ledger: self.ledger,
+                    from: self.balances[sender],
+                    to: self.balances[recipient],
+                    relayer_balance: self.balances[relayer],
+                    authority: self.wallets[sender],
+                    relayer: self.wallets[relayer],
+                    approver: self.wallets[approver],
+                },
+                instruction::SecureTransfer { amount, fee },
+            ),
+        };
+        format!(
+            "transfer {amount} + {fee} fee, {} -> {}, relayed by {}, approved by {}: {}",
+            NAMES[sender],
+            NAMES[recipient],
+            NAMES[relayer],
+            NAMES[approver],
+            outcome(result)
+        )
+    }
+
+    fn total(&self) -> u128 {
+        self.balances
+            .iter()
+            .map(|balance| u128::from(self.world.state::<Balance>(balance).amount))
+            .sum()
+    }
+
+    fn minted(&self) -> i128 {
+        self.minted
+    }
+}
+
+#[test]
+fn vulnerable_transfer_creates_credits_when_roles_alias() {
+    let violation = assert_violated::<CreditLedger>(Path::Vulnerable);
+    assert!(violation.actual > violation.expected, "{violation}");
+}
+
+#[test]
+fn secure_transfer_conserves_credits() {
+    assert_conserved::<CreditLedger>(Path::Secure);
+}
@@ -0,0 +1,208 @@
+//! 20_lending_toy: liquidations move collateral from borrower to liquidator; they never create it.
+//!
+//! The toy moves no tokens, so each user's wallet is tracked here: a loan
+//! is paid into it, and repayments come out of it, a liquidator's included.
+//! A user's value is their wallet and collateral less their debt. Positions
+//! open at random health, from dust to underwater, and are liquidated by
+//! whoever is picked, for anything up to half again their debt.
+
+use anchor_lang::prelude::*;
+use lending_toy::bad_debt::LOSS_INDEX_ONE;
+use lending_toy::{accounts, instruction, Market, Position};
+use secref_invariants::{assert_conserved, assert_violated, outcome, program, Model, Path, Rng, World};
+use secref_testkit::TestAccount;
+
+const NAMES: [&str; 4] = ["alice", "bob", "carol", "dave"];
+const OPENING_WALLET: i128 = 1_000_000;
+/// Most collateral a position opens with
+const OPENING_COLLATERAL: u64 = 5_000;
+/// Liquidity lenders have supplied, so borrows never run out
+const LIQUIDITY: u64 = 1_000_000_000;
+
+struct LendingMarket {
+    world: World,
+    path: Path,
+    market: Pubkey,
+    owners: Vec<Pubkey>,
+    positions: Vec<Pubkey>,
+    /// What each user holds outside the market
+    wallets: Vec<i128>,
+}
+
+impl LendingMarket {
+    fn position(&self, who: usize) -> Position {
+        self.world.state::<Position>(&self.positions[who])
+    }
+
+    fn update_position(&self, who: usize) -> accounts::UpdatePosition {
+        accounts::UpdatePosition {
+            market: self.market,
+            position: self.positions[who],
+            owner: self.owners[who],
+        }
+    }
+}
+
+impl Model for LendingMarket {
+    fn new(path: Path, rng: &mut Rng) -> Self {
+        let mut world = World::new();
+        let owners: Vec<Pubkey> = NAMES.iter().map(|_| world.add(TestAccount::signer())).collect();
+        let market = Pubkey::new_unique();
+
+        // Between 60% and 130% of the collateral borrowed, so most start out unhealthy
+        let opening: Vec<(u64, u64)> = NAMES
+            .iter()
+            .map(|_| {
+                let collateral = rng.up_to(OPENING_COLLATERAL);
+                (collateral, collateral * (6_000 + rng.up_to(7_000)) / 10_000)
+            })
+            .collect();
+        let positions = owners
+            .iter()
+            .zip(&opening)
+            .map(|(&owner, &(collateral, borrowed))| {
+                let (address, bump) = Pubkey::find_program_address(
+                    &[b"position", market.as_ref(), owner.as_ref()],
+                    &lending_toy::ID,
+                );
+                world.add(
+                    TestAccount::anchor(&Position {
+                        market,
+                        owner,
+                        deposited: 0,
+                        collateral,
+                        borrowed,
+                        deposit_index: LOSS_INDEX_ONE,
+                        bump,
+                    })
+                    .at(address),
+                )
+            })
+            .collect();
+        world.add(
+            TestAccount::anchor(&Market {
+                admin: Pubkey::new_unique(),
+                total_deposits: LIQUIDITY,
+                total_borrows: opening.iter().map(|&(_, borrowed)| borrowed).sum(),
+                borrow_rate_bps: 0,
+                last_update_slot: 0,
+                loss_index: LOSS_INDEX_ONE,
+                bump: 0,
+            })
+            .at(market),
+        );
+
+        Self {
+            world,
+            path,
+            market,
+            owners,
+            positions,
+            wallets: vec![OPENING_WALLET; NAMES.len()],
+        }
+    }
+
+    fn step(&mut self, rng: &mut Rng) -> String {
+        let who = rng.up_to(NAMES.len() as u64 - 1) as usize;
+        let position = self.position(who);
+
+        match rng.up_to(4) {
+            0 | 1 => {
+                // Sometimes a liquidator's own position, which the context rejects
+                let liquidator = rng.up_to(NAMES.len() as u64 - 1) as usize;
+                // Half the time within the close factor
+                let repay = if rng.one_in(2) {
+                    rng.up_to(position.borrowed / 2)
+                } else {
+                    rng.up_to(position.borrowed.saturating_mul(3) / 2)
+                };
+                let accounts = accounts::Liquidate {
+                    market: self.market,
+                    borrower_position: self.positions[who],
+                    liquidator_position: self.positions[liquidator],
+                    liquidator: self.owners[liquidator],
+                };
+                let result = match self.path {
+                    Path::Vulnerable => self.world.execute(
+                        program!(lending_toy),
+                        accounts,
+                        instruction::VulnerableLiquidate { repay },
+                    ),
+                    Path::Secure => {
+                        self.world
+                            .execute(program!(lending_toy), accounts, instruction::SecureLiquidate { repay })
+                    }
+                };
+                if result.is_ok() {
+                    let repaid = position.borrowed - self.position(who).borrowed;
+                    self.wallets[liquidator] -= i128::from(repaid);
+                }
+                format!(
+                    "{} liquidates {repay} of {}'s {} borrowed against {}: {}",
+                    NAMES[liquidator],
+                    NAMES[who],
+                    position.borrowed,
+                    position.collateral,
+                    outcome(result)
+                )
+            }
+            2 => {
+                let amount = rng.up_to(position.collateral);
+                let result = self.world.execute(
+                    program!(lending_toy),
+                    self.update_position(who),
+                    instruction::Borrow { amount },
+                );
+                if result.is_ok() {
+                    self.wallets[who] += i128::from(amount);
+                }
+                format!("{} borrows {amount}: {}", NAMES[who], outcome(result))
+            }
+            3 => {
+                let amount = rng.up_to(position.borrowed);
+                let result = self.world.execute(
+                    program!(lending_toy),
+                    self.update_position(who),
+                    instruction::Repay { amount },
+                );
+                if result.is_ok() {
+                    self.wallets[who] -= i128::from(amount);
+                }
+                format!("{} repays {amount}: {}", NAMES[who], outcome(result))
+            }
+            _ => {
+                let amount = rng.up_to(OPENING_COLLATERAL);
+                let result = self.world.execute(
+                    program!(lending_toy),
+                    self.update_position(who),
+                    instruction::DepositCollateral { amount },
+                );
+                if result.is_ok() {
+                    self.wallets[who] -= i128::from(amount);
+                }
+                format!("{} posts {amount} collateral: {}", NAMES[who], outcome(result))
+            }
+        }
+    }
+
+    fn total(&self) -> u128 {
+        let value: i128 = (0..NAMES.len())
+            .map(|who| {
+                let position = self.position(who);
+                self.wallets[who] + i128::from(position.collateral) - i128::from(position.borrowed)
+            })
+            .sum();
+        u128::try_from(value).expect("wallets cover every debt")
+    }
+}
+
+#[test]
+fn vulnerable_liquidation_creates_collateral_for_the_bounty() {
+    let violation = assert_violated::<LendingMarket>(Path::Vulnerable);
+    assert!(violation.actual > violation.expected, "{violation}");
+}
+
+#[test]
+fn secure_liquidation_conserves_value() {
+    assert_conserved::<LendingMarket>(Path::Secure);
+}
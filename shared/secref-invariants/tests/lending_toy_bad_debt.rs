@@ -0,0 +1,244 @@
+//! 20_lending_toy: a write-off leaves the deposits worth no more than the market holds and is owed.
+//!
+//! Every user is a lender and a borrower. The toy moves no tokens, so each
+//! user's wallet is tracked here: deposits and repayments come out of it,
+//! withdrawals and loans are paid into it. Some positions open with no
+//! collateral left, and others can be liquidated down to none, so there
+//! is always bad debt to write off. A user's value is their wallet and
+//! collateral less their debt, and the lenders between them hold the
+//! larger of what their deposits are worth and what backs them - the
+//! market's liquidity and its loans - so a write-off that leaves the
+//! deposits whole creates the difference.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::InstructionData;
+use lending_toy::bad_debt::{self, LOSS_INDEX_ONE};
+use lending_toy::liquidation::max_repay;
+use lending_toy::{accounts, instruction, Market, Position};
+use secref_invariants::{assert_conserved, assert_violated, outcome, program, Model, Path, Rng, World};
+use secref_testkit::TestAccount;
+
+const NAMES: [&str; 4] = ["alice", "bob", "carol", "dave"];
+const OPENING_WALLET: i128 = 1_000_000;
+/// What each user has supplied when the market opens
+const OPENING_DEPOSIT: u64 = 10_000;
+/// Most collateral a position opens with
+const OPENING_COLLATERAL: u64 = 5_000;
+
+struct InsolventMarket {
+    world: World,
+    path: Path,
+    market: Pubkey,
+    admin: Pubkey,
+    owners: Vec<Pubkey>,
+    positions: Vec<Pubkey>,
+    /// What each user holds outside the market
+    wallets: Vec<i128>,
+}
+
+impl InsolventMarket {
+    fn market(&self) -> Market {
+        self.world.state::<Market>(&self.market)
+    }
+
+    fn position(&self, who: usize) -> Position {
+        self.world.state::<Position>(&self.positions[who])
+    }
+
+    fn claim(&self, who: usize) -> u64 {
+        let position = self.position(who);
+        bad_debt::claim(position.deposited, position.deposit_index, self.market().loss_index)
+    }
+
+    fn update(&mut self, who: usize, instruction: impl InstructionData) -> ProgramResult {
+        let accounts = accounts::UpdatePosition {
+            market: self.market,
+            position: self.positions[who],
+            owner: self.owners[who],
+        };
+        self.world.execute(program!(lending_toy), accounts, instruction)
+    }
+}
+
+impl Model for InsolventMarket {
+    fn new(path: Path, rng: &mut Rng) -> Self {
+        let mut world = World::new();
+        let admin = world.add(TestAccount::signer());
+        let owners: Vec<Pubkey> = NAMES.iter().map(|_| world.add(TestAccount::signer())).collect();
+        let market = Pubkey::new_unique();
+
+        // Between 60% and 130% of the collateral borrowed; one in three already has none left
+        let opening: Vec<(u64, u64)> = NAMES
+            .iter()
+            .map(|_| {
+                let collateral = 1 + rng.up_to(OPENING_COLLATERAL);
+                let borrowed = collateral * (6_000 + rng.up_to(7_000)) / 10_000;
+                (if rng.one_in(3) { 0 } else { collateral }, borrowed)
+            })
+            .collect();
+        let positions = owners
+            .iter()
+            .zip(&opening)
+            .map(|(&owner, &(collateral, borrowed))| {
+                let (address, bump) = Pubkey::find_program_address(
+                    &[b"position", market.as_ref(), owner.as_ref()],
+                    &lending_toy::ID,
+                );
+                world.add(
+                    TestAccount::anchor(&Position {
+                        market,
+                        owner,
+                        deposited: OPENING_DEPOSIT,
+                        collateral,
+                        borrowed,
+                        deposit_index: LOSS_INDEX_ONE,
+                        bump,
+                    })
+                    .at(address),
+                )
+            })
+            .collect();
+        world.add(
+            TestAccount::anchor(&Market {
+                admin,
+                total_deposits: OPENING_DEPOSIT * NAMES.len() as u64,
+                total_borrows: opening.iter().map(|&(_, borrowed)| borrowed).sum(),
+                borrow_rate_bps: 0,
+                last_update_slot: 0,
+                loss_index: LOSS_INDEX_ONE,
+                bump: 0,
+            })
+            .at(market),
+        );
+
+        Self {
+            world,
+            path,
+            market,
+            admin,
+            owners,
+            positions,
+            wallets: vec![OPENING_WALLET; NAMES.len()],
+        }
+    }
+
+    fn step(&mut self, rng: &mut Rng) -> String {
+        let who = rng.up_to(NAMES.len() as u64 - 1) as usize;
+        let position = self.position(who);
+
+        match rng.up_to(5) {
+            0 => {
+                let amount = rng.up_to(OPENING_DEPOSIT);
+                let result = self.update(who, instruction::Deposit { amount });
+                if result.is_ok() {
+                    self.wallets[who] -= i128::from(amount);
+                }
+                format!("{} supplies {amount}: {}", NAMES[who], outcome(result))
+            }
+            1 => {
+                // Up to half again what the deposit is worth
+                let claim = self.claim(who);
+                let amount = rng.up_to(claim.saturating_mul(3) / 2);
+                let result = self.update(who, instruction::Withdraw { amount });
+                if result.is_ok() {
+                    self.wallets[who] += i128::from(amount);
+                }
+                format!("{} withdraws {amount} of {claim}: {}", NAMES[who], outcome(result))
+            }
+            2 => {
+                let amount = rng.up_to(position.collateral);
+                let result = self.update(who, instruction::Borrow { amount });
+                if result.is_ok() {
+                    self.wallets[who] += i128::from(amount);
+                }
+                format!("{} borrows {amount}: {}", NAMES[who], outcome(result))
+            }
+            3 => {
+                let amount = rng.up_to(position.borrowed);
+                let result = self.update(who, instruction::Repay { amount });
+                if result.is_ok() {
+                    self.wallets[who] -= i128::from(amount);
+                }
+                format!("{} repays {amount}: {}", NAMES[who], outcome(result))
+            }
+            4 => {
+                // The secure liquidation on both paths, to strip positions of their collateral
+                let liquidator = rng.up_to(NAMES.len() as u64 - 1) as usize;
+                let repay = max_repay(position.borrowed);
+                let accounts = accounts::Liquidate {
+                    market: self.market,
+                    borrower_position: self.positions[who],
+                    liquidator_position: self.positions[liquidator],
+                    liquidator: self.owners[liquidator],
+                };
+                let result =
+                    self.world.execute(program!(lending_toy), accounts, instruction::SecureLiquidate { repay });
+                if result.is_ok() {
+                    let repaid = position.borrowed - self.position(who).borrowed;
+                    self.wallets[liquidator] -= i128::from(repaid);
+                }
+                format!(
+                    "{} liquidates {repay} of {}'s {} borrowed against {}: {}",
+                    NAMES[liquidator],
+                    NAMES[who],
+                    position.borrowed,
+                    position.collateral,
+                    outcome(result)
+                )
+            }
+            _ => {
+                let accounts = accounts::WriteOffBadDebt {
+                    market: self.market,
+                    borrower_position: self.positions[who],
+                    admin: self.admin,
+                };
+                let result = match self.path {
+                    Path::Vulnerable => self.world.execute(
+                        program!(lending_toy),
+                        accounts,
+                        instruction::VulnerableWriteOffBadDebt {},
+                    ),
+                    Path::Secure => {
+                        self.world
+                            .execute(program!(lending_toy), accounts, instruction::SecureWriteOffBadDebt {})
+                    }
+                };
+                format!(
+                    "{}'s {} borrowed against {} is written off: {}",
+                    NAMES[who],
+                    position.borrowed,
+                    position.collateral,
+                    outcome(result)
+                )
+            }
+        }
+    }
+
+    fn total(&self) -> u128 {
+        let market = self.market();
+        let positions: Vec<Position> = (0..NAMES.len()).map(|who| self.position(who)).collect();
+        let loans: i128 = positions.iter().map(|position| i128::from(position.borrowed)).sum();
+        let claims: i128 = (0..NAMES.len()).map(|who| i128::from(self.claim(who))).sum();
+
+        // Liquidity, plus the loans still owed by a position
+        let backing = i128::from(market.total_deposits) - i128::from(market.total_borrows) + loans;
+        let users: i128 = positions
+            .iter()
+            .zip(&self.wallets)
+            .map(|(position, wallet)| wallet + i128::from(position.collateral) - i128::from(position.borrowed))
+            .sum();
+        u128::try_from(users + backing.max(claims)).expect("wallets cover every debt")
+    }
+}
+
+#[test]
+fn vulnerable_write_off_leaves_deposits_worth_more_than_backs_them() {
+    let violation = assert_violated::<InsolventMarket>(Path::Vulnerable);
+    assert!(violation.actual > violation.expected, "{violation}");
+}
+
+#[test]
+fn secure_write_off_shares_the_loss_and_conserves_value() {
+    assert_conserved::<InsolventMarket>(Path::Secure);
+}
@@ -0,0 +1,147 @@
+//! 21_keeper_crank_incentives: the maker and a keeper reach for the same expired order in one slot.
+//!
+//! Once an order expires its maker can reclaim it, and any keeper can
+//! expire it for a reward. Whichever the leader runs first closes the
+//! order. Through the vulnerable expiry the keeper names its own reward
+//! and refund account, so the maker loses the escrow in every order the
+//! keeper lands first; through the secure one the keeper's reward is
+//! fixed, and the maker loses at most that, whoever wins.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use order_expiry::{accounts, instruction, KEEPER_REWARD_LAMPORTS};
+use secref_invariants::{program, sweep, Actor, Ordering, Path, Schedule, World};
+use secref_testkit::runtime::warp_to;
+use secref_testkit::TestAccount;
+
+const ID: u64 = 1;
+const AMOUNT: u64 = 1_000_000;
+const PLACED_AT: i64 = 1_700_000_000;
+const TTL: i64 = 60;
+const SEEDS: std::ops::Range<u64> = 0..16;
+
+struct Book {
+    world: World,
+    order: Pubkey,
+    maker: Pubkey,
+    keeper: Pubkey,
+}
+
+/// An order of AMOUNT placed at PLACED_AT, with the clock at its expiry
+fn expired() -> Book {
+    let mut world = World::new();
+    let maker = world.add(TestAccount::signer().with_lamports(10 * AMOUNT));
+    let keeper = world.add(TestAccount::signer());
+    let (order, _) =
+        Pubkey::find_program_address(&[b"order", maker.as_ref(), &ID.to_le_bytes()], &order_expiry::ID);
+    world.add(TestAccount::system(0).at(order));
+
+    warp_to(PLACED_AT);
+    world
+        .execute(
+            program!(order_expiry),
+            accounts::PlaceOrder {
+                order,
+                maker,
+                system_program: system_program::ID,
+            },
+            instruction::PlaceOrder {
+                id: ID,
+                amount: AMOUNT,
+                ttl_seconds: TTL,
+            },
+        )
+        .unwrap();
+    warp_to(PLACED_AT + TTL);
+    Book {
+        world,
+        order,
+        maker,
+        keeper,
+    }
+}
+
+/// The maker reclaiming and the keeper expiring, both in the slot the order expires
+fn race(book: &Book, path: Path) -> Schedule<'_> {
+    let (order, maker, keeper) = (book.order, book.maker, book.keeper);
+    let reclaim = move |world: &mut World| match path {
+        Path::Vulnerable => world.execute(
+            program!(order_expiry),
+            accounts::VulnerableReclaimOrder { order, maker },
+            instruction::VulnerableReclaimOrder {},
+        ),
+        Path::Secure => world.execute(
+            program!(order_expiry),
+            accounts::SecureReclaimOrder { order, maker },
+            instruction::SecureReclaimOrder {},
+        ),
+    };
+    let expire = move |world: &mut World| match path {
+        // The whole escrow as the reward, and the rent to the keeper too
+        Path::Vulnerable => world.execute(
+            program!(order_expiry),
+            accounts::VulnerableExpireOrder {
+                order,
+                refund_destination: keeper,
+                keeper,
+            },
+            instruction::VulnerableExpireOrder { reward: AMOUNT },
+        ),
+        Path::Secure => world.execute(
+            program!(order_expiry),
+            accounts::SecureExpireOrder { order, maker, keeper },
+            instruction::SecureExpireOrder {},
+        ),
+    };
+    Schedule::new(vec![
+        Actor::new("maker").submits("reclaim", reclaim),
+        Actor::new("keeper").submits("expire", expire),
+    ])
+}
+
+#[test]
+fn vulnerable_expiry_takes_the_escrow_whenever_the_keeper_lands_first() {
+    let book = expired();
+    let start = book.world.lamports(&book.maker);
+    let runs = sweep(&book.world, SEEDS, || race(&book, Path::Vulnerable));
+
+    let (keeper_first, maker_first): (Vec<_>, Vec<_>) = runs
+        .iter()
+        .partition(|(_, interleaving)| interleaving.ran_before(("keeper", "expire"), ("maker", "reclaim")));
+    assert!(!keeper_first.is_empty() && !maker_first.is_empty(), "the seeds cover both orders");
+
+    for (world, interleaving) in keeper_first {
+        assert!(interleaving.succeeded("keeper", "expire"), "{interleaving}");
+        assert_eq!(world.lamports(&book.maker), start, "the maker gets nothing back\n{interleaving}");
+    }
+    for (world, interleaving) in maker_first {
+        assert!(!interleaving.succeeded("keeper", "expire"), "{interleaving}");
+        assert!(world.lamports(&book.maker) >= start + AMOUNT, "{interleaving}");
+    }
+}
+
+#[test]
+fn a_keeper_that_bundles_itself_first_always_takes_the_escrow() {
+    let book = expired();
+    let start = book.world.lamports(&book.maker);
+    let mut world = book.world.clone();
+    let interleaving = race(&book, Path::Vulnerable).run(&mut world, &Ordering::Explicit(vec!["keeper"]));
+    assert!(interleaving.ran_before(("keeper", "expire"), ("maker", "reclaim")));
+    assert_eq!(world.lamports(&book.maker), start, "{interleaving}");
+}
+
+#[test]
+fn secure_expiry_costs_the_maker_at_most_the_fixed_reward_in_any_order() {
+    let book = expired();
+    let (start, keeper_start) = (book.world.lamports(&book.maker), book.world.lamports(&book.keeper));
+    for (world, interleaving) in sweep(&book.world, SEEDS, || race(&book, Path::Secure)) {
+        assert!(
+            world.lamports(&book.maker) >= start + AMOUNT - KEEPER_REWARD_LAMPORTS,
+            "{interleaving}"
+        );
+        assert!(
+            world.lamports(&book.keeper) <= keeper_start + KEEPER_REWARD_LAMPORTS,
+            "{interleaving}"
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! 05_reinitialization_attack: an attacker's initialize lands in the same slot as the victim's.
+//!
+//! The victim initializes a vault and deposits into it the next slot; the
+//! attacker initializes the same vault with itself as owner and, a slot
+//! after the deposit, withdraws it. The vulnerable initialize runs for
+//! whoever calls it, so the last one in the slot owns the vault: in every
+//! order the leader runs the attacker second, the victim's deposit is the
+//! attacker's to withdraw. The secure initialize creates the account, so
+//! only the holder of its key can run it, and only once.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use reinit_vault::{accounts, instruction, ReinitVault};
+use secref_invariants::{program, sweep, Actor, Path, Schedule, World};
+use secref_testkit::TestAccount;
+
+const AMOUNT: u64 = 1_000;
+const SEEDS: std::ops::Range<u64> = 0..16;
+/// Discriminator, owner, balance, total deposits and the initialized flag
+const VAULT_SPACE: usize = 8 + 32 + 8 + 8 + 1;
+
+struct Race {
+    world: World,
+    vault: Pubkey,
+    victim: Pubkey,
+    attacker: Pubkey,
+}
+
+/// The victim's vault about to be initialized: an empty vault account
+/// for the vulnerable path, which takes it as created, and an unused
+/// address for the secure one, which creates it
+fn race_for(path: Path) -> Race {
+    let mut world = World::new();
+    let victim = world.add(TestAccount::signer().with_lamports(1_000_000_000));
+    let attacker = world.add(TestAccount::signer().with_lamports(1_000_000_000));
+    let vault = match path {
+        Path::Vulnerable => world.add(TestAccount::anchor(&ReinitVault {
+            owner: Pubkey::default(),
+            balance: 0,
+            total_deposits: 0,
+            is_initialized: false,
+        })),
+        Path::Secure => world.add(TestAccount::system(0)),
+    };
+    Race {
+        world,
+        vault,
+        victim,
+        attacker,
+    }
+}
+
+fn schedule(race: &Race, path: Path) -> Schedule<'_> {
+    let (vault, victim, attacker) = (race.vault, race.victim, race.attacker);
+    let initialize = move |owner: Pubkey| {
+        move |world: &mut World| match path {
+            Path::Vulnerable => world.execute(
+                program!(reinit_vault),
+                accounts::VulnerableInitialize {
+                    vault,
+                    owner,
+                    system_program: system_program::ID,
+                },
+                instruction::VulnerableInitialize { initial_balance: 0 },
+            ),
+            Path::Secure => {
+                let mut metas = accounts::SecureInitialize {
+                    vault,
+                    owner,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None);
+                // Only the victim holds the vault's key
+                metas[0].is_signer = owner == victim;
+                world.invoke(
+                    program!(reinit_vault),
+                    &metas,
+                    &instruction::SecureInitialize { initial_balance: 0 }.data(),
+                )
+            }
+        }
+    };
+    let deposit = move |world: &mut World| match path {
+        Path::Vulnerable => world.execute(
+            program!(reinit_vault),
+            accounts::VulnerableDeposit {
+                vault,
+                depositor: victim,
+            },
+            instruction::VulnerableDeposit { amount: AMOUNT },
+        ),
+        Path::Secure => world.execute(
+            program!(reinit_vault),
+            accounts::SecureDeposit {
+                vault,
+                depositor: victim,
+            },
+            instruction::SecureDeposit { amount: AMOUNT },
+        ),
+    };
+    let withdraw = move |world: &mut World| match path {
+        Path::Vulnerable => world.execute(
+            program!(reinit_vault),
+            accounts::VulnerableWithdraw { vault, owner: attacker },
+            instruction::VulnerableWithdraw { amount: AMOUNT },
+        ),
+        Path::Secure => world.execute(
+            program!(reinit_vault),
+            accounts::SecureWithdraw { vault, owner: attacker },
+            instruction::SecureWithdraw { amount: AMOUNT },
+        ),
+    };
+    Schedule::new(vec![
+        Actor::new("victim")
+            .submits("initialize", initialize(victim))
+            .submits("deposit", deposit),
+        Actor::new("attacker")
+            .submits("initialize", initialize(attacker))
+            .waits(1)
+            .submits("withdraw", withdraw),
+    ])
+}
+
+#[test]
+fn vulnerable_initialize_hands_the_vault_to_whoever_lands_last() {
+    let race = race_for(Path::Vulnerable);
+    let runs = sweep(&race.world, SEEDS, || schedule(&race, Path::Vulnerable));
+
+    let (attacker_last, victim_last): (Vec<_>, Vec<_>) = runs
+        .iter()
+        .partition(|(_, interleaving)| interleaving.ran_before(("victim", "initialize"), ("attacker", "initialize")));
+    assert!(!attacker_last.is_empty() && !victim_last.is_empty(), "the seeds cover both orders");
+
+    for (world, interleaving) in attacker_last {
+        assert!(interleaving.succeeded("attacker", "withdraw"), "{interleaving}");
+        assert_eq!(world.state::<ReinitVault>(&race.vault).owner, race.attacker);
+    }
+    for (world, interleaving) in victim_last {
+        assert!(!interleaving.succeeded("attacker", "withdraw"), "{interleaving}");
+        assert_eq!(world.state::<ReinitVault>(&race.vault).balance, AMOUNT);
+    }
+}
+
+#[test]
+fn secure_initialize_keeps_the_vault_the_victims_in_any_order() {
+    let race = race_for(Path::Secure);
+    for (world, interleaving) in sweep(&race.world, SEEDS, || schedule(&race, Path::Secure)) {
+        assert!(interleaving.succeeded("victim", "initialize"), "{interleaving}");
+        assert!(!interleaving.succeeded("attacker", "initialize"), "{interleaving}");
+        assert!(!interleaving.succeeded("attacker", "withdraw"), "{interleaving}");
+        let vault = world.state::<ReinitVault>(&race.vault);
+        assert_eq!((vault.owner, vault.balance), (race.victim, AMOUNT));
+        assert_eq!(world.account(&race.vault).data.len(), VAULT_SPACE);
+    }
+}
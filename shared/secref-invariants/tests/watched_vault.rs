@@ -0,0 +1,177 @@
+//! 31_invariant_watchdog: lamports leave a depositor's wallet only as a deposit they can withdraw.
+//!
+//! Deposits go through the real System Program. The secure instructions
+//! also need `vault_watchdog::assert_vault_solvent` later in their
+//! transaction, so their instructions sysvar lists one; this suite checks
+//! the invariant that assertion guards, so the watchdog itself is not run.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::{system_program, sysvar};
+use secref_invariants::{assert_conserved, assert_violated, outcome, program, Model, Path, Rng, World};
+use secref_testkit::TestAccount;
+use watched_vault::{accounts, instruction, Deposit, VaultState, ASSERT_SOLVENT_DISCRIMINATOR, WATCHDOG_ID};
+
+const NAMES: [&str; 3] = ["alice", "bob", "carol"];
+const MAX_DEPOSIT: u64 = 1_000_000_000;
+
+struct WatchedVault {
+    world: World,
+    path: Path,
+    state: Pubkey,
+    vault: Pubkey,
+    owners: Vec<Pubkey>,
+    deposits: Vec<Pubkey>,
+}
+
+impl WatchedVault {
+    fn deposited(&self, owner: usize) -> u64 {
+        self.world.state::<Deposit>(&self.deposits[owner]).amount
+    }
+
+    /// The transaction the secure instructions expect: this one, then the watchdog on `state`
+    fn watched_transaction(&mut self) {
+        let watchdog = Instruction {
+            program_id: WATCHDOG_ID,
+            accounts: vec![AccountMeta::new_readonly(self.state, false)],
+            data: ASSERT_SOLVENT_DISCRIMINATOR.to_vec(),
+        };
+        let current = Instruction::new_with_bytes(watched_vault::ID, &[], vec![]);
+        self.world
+            .add(TestAccount::instructions_sysvar_of(&[current, watchdog], 0));
+    }
+}
+
+impl Model for WatchedVault {
+    fn new(path: Path, _rng: &mut Rng) -> Self {
+        let mut world = World::new();
+        let admin = Pubkey::new_unique();
+        let (state, bump) = Pubkey::find_program_address(&[b"state", admin.as_ref()], &watched_vault::ID);
+        let (vault, vault_bump) = Pubkey::find_program_address(&[b"vault", state.as_ref()], &watched_vault::ID);
+        world.add(
+            TestAccount::anchor(&VaultState {
+                admin,
+                total_liabilities: 0,
+                vault_bump,
+                bump,
+            })
+            .at(state),
+        );
+        world.add(TestAccount::system(Rent::default().minimum_balance(0)).at(vault));
+
+        let owners: Vec<Pubkey> = NAMES.iter().map(|_| world.add(TestAccount::signer())).collect();
+        let deposits = owners
+            .iter()
+            .map(|&owner| {
+                let (address, bump) =
+                    Pubkey::find_program_address(&[b"deposit", state.as_ref(), owner.as_ref()], &watched_vault::ID);
+                world.add(
+                    TestAccount::anchor(&Deposit {
+                        state,
+                        owner,
+                        amount: 0,
+                        bump,
+                    })
+                    .at(address),
+                )
+            })
+            .collect();
+
+        Self {
+            world,
+            path,
+            state,
+            vault,
+            owners,
+            deposits,
+        }
+    }
+
+    fn step(&mut self, rng: &mut Rng) -> String {
+        let who = rng.up_to(NAMES.len() as u64 - 1) as usize;
+        let (state, vault, deposit, owner) = (self.state, self.vault, self.deposits[who], self.owners[who]);
+        let deposited = self.deposited(who);
+
+        if rng.one_in(2) {
+            let amount = rng.up_to(MAX_DEPOSIT);
+            let result = match self.path {
+                Path::Vulnerable => self.world.execute(
+                    program!(watched_vault),
+                    accounts::VulnerableDeposit {
+                        state,
+                        vault,
+                        deposit,
+                        owner,
+                        system_program: system_program::ID,
+                    },
+                    instruction::VulnerableDeposit { amount },
+                ),
+                Path::Secure => {
+                    self.watched_transaction();
+                    self.world.execute(
+                        program!(watched_vault),
+                        accounts::SecureDeposit {
+                            state,
+                            vault,
+                            deposit,
+                            owner,
+                            instructions: sysvar::instructions::ID,
+                            system_program: system_program::ID,
+                        },
+                        instruction::SecureDeposit { amount },
+                    )
+                }
+            };
+            return format!("{} deposits {amount}: {}", NAMES[who], outcome(result));
+        }
+
+        // Sometimes more than the owner deposited, out of everyone else's lamports
+        let amount = rng.up_to(deposited.saturating_mul(2).max(1));
+        let result = match self.path {
+            Path::Vulnerable => self.world.execute(
+                program!(watched_vault),
+                accounts::VulnerableWithdraw {
+                    state,
+                    vault,
+                    deposit,
+                    owner,
+                    system_program: system_program::ID,
+                },
+                instruction::VulnerableWithdraw { amount },
+            ),
+            Path::Secure => {
+                self.watched_transaction();
+                self.world.execute(
+                    program!(watched_vault),
+                    accounts::SecureWithdraw {
+                        state,
+                        vault,
+                        deposit,
+                        owner,
+                        instructions: sysvar::instructions::ID,
+                        system_program: system_program::ID,
+                    },
+                    instruction::SecureWithdraw { amount },
+                )
+            }
+        };
+        format!("{} withdraws {amount} of {deposited}: {}", NAMES[who], outcome(result))
+    }
+
+    fn total(&self) -> u128 {
+        (0..NAMES.len())
+            .map(|who| u128::from(self.world.lamports(&self.owners[who])) + u128::from(self.deposited(who)))
+            .sum()
+    }
+}
+
+#[test]
+fn vulnerable_withdraw_creates_a_claim_by_wrapping() {
+    let violation = assert_violated::<WatchedVault>(Path::Vulnerable);
+    assert!(violation.actual > violation.expected, "{violation}");
+}
+
+#[test]
+fn secure_deposits_and_withdrawals_conserve_value() {
+    assert_conserved::<WatchedVault>(Path::Secure);
+}
@@ -0,0 +1,4742 @@
+//! Every account layout in the repository, by module and program.
+//!
+//! Generated by `tests/generated.rs` from the struct definitions;
+//! rewrite it after changing an account type with
+//! `SECREF_BLESS=1 cargo test --manifest-path shared/secref-layouts/Cargo.toml`.
+
+use crate::{Field, Kind, Layout, Size};
+
+pub(crate) static LAYOUTS: &[Layout] = &[
+    Layout {
+        module: "01_missing_account_validation",
+        program: "vault",
+        name: "ZeroCopyVault",
+        kind: Kind::Manual,
+        discriminator: None,
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(0),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "balance",
+                ty: "u64",
+                offset: Some(32),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(40),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "padding",
+                ty: "[u8; 7]",
+                offset: Some(41),
+                size: Size::Fixed(7),
+            },
+        ],
+        size: Size::Fixed(48),
+    },
+    Layout {
+        module: "01_missing_account_validation",
+        program: "vault",
+        name: "Vault",
+        kind: Kind::Anchor,
+        discriminator: Some([211, 8, 232, 43, 2, 152, 117, 119]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "balance",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+        ],
+        size: Size::Fixed(48),
+    },
+    Layout {
+        module: "02_authority_check_failure",
+        program: "admin_vault",
+        name: "AdminVault",
+        kind: Kind::Anchor,
+        discriminator: Some([77, 57, 76, 180, 43, 202, 140, 228]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "balance",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+        ],
+        size: Size::Fixed(80),
+    },
+    Layout {
+        module: "02_authority_check_failure",
+        program: "admin_vault",
+        name: "Multisig",
+        kind: Kind::Anchor,
+        discriminator: Some([224, 116, 121, 186, 68, 161, 79, 236]),
+        fields: &[
+            Field {
+                name: "vault",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "approvers",
+                ty: "Vec<Pubkey>",
+                offset: Some(40),
+                size: Size::Variable { min: 4 },
+            },
+            Field {
+                name: "threshold",
+                ty: "u8",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "pending_hash",
+                ty: "[u8; 32]",
+                offset: None,
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "approvals",
+                ty: "u8",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "proposal_count",
+                ty: "u64",
+                offset: None,
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Variable { min: 87 },
+    },
+    Layout {
+        module: "02_authority_check_failure",
+        program: "admin_vault",
+        name: "Proposal",
+        kind: Kind::Anchor,
+        discriminator: Some([26, 94, 189, 187, 116, 136, 53, 33]),
+        fields: &[
+            Field {
+                name: "multisig",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "index",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "amount",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "proposer",
+                ty: "Pubkey",
+                offset: Some(56),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "approvals",
+                ty: "u8",
+                offset: Some(88),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "executed",
+                ty: "bool",
+                offset: Some(89),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(90),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(91),
+    },
+    Layout {
+        module: "03_unsafe_cpi",
+        program: "unsafe_cpi",
+        name: "TokenVault",
+        kind: Kind::Anchor,
+        discriminator: Some([121, 7, 84, 254, 151, 228, 43, 144]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "token_account",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(72),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(73),
+    },
+    Layout {
+        module: "04_arithmetic_overflow",
+        program: "arithmetic_vault",
+        name: "ArithmeticVault",
+        kind: Kind::Anchor,
+        discriminator: Some([1, 100, 106, 115, 119, 95, 127, 218]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "balance",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "total_deposits",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "total_withdrawals",
+                ty: "u64",
+                offset: Some(56),
+                size: Size::Fixed(8),
+            },
+        ],
+        size: Size::Fixed(64),
+    },
+    Layout {
+        module: "05_reinitialization_attack",
+        program: "reinit_vault",
+        name: "ReinitVault",
+        kind: Kind::Anchor,
+        discriminator: Some([211, 191, 230, 247, 200, 183, 186, 128]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "balance",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "total_deposits",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "is_initialized",
+                ty: "bool",
+                offset: Some(56),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(57),
+    },
+    Layout {
+        module: "06_insecure_deserialization",
+        program: "airdrop_list",
+        name: "RecipientList",
+        kind: Kind::Anchor,
+        discriminator: Some([34, 171, 76, 228, 250, 143, 116, 64]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "recipients",
+                ty: "Vec<Pubkey>",
+                offset: Some(40),
+                size: Size::Variable { min: 4 },
+            },
+        ],
+        size: Size::Variable { min: 44 },
+    },
+    Layout {
+        module: "07_seed_length_overflow",
+        program: "profile_registry",
+        name: "Profile",
+        kind: Kind::Anchor,
+        discriminator: Some([184, 101, 165, 188, 95, 63, 127, 188]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "handle",
+                ty: "String",
+                offset: Some(40),
+                size: Size::Variable { min: 4 },
+            },
+            Field {
+                name: "bio",
+                ty: "String",
+                offset: None,
+                size: Size::Variable { min: 4 },
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Variable { min: 49 },
+    },
+    Layout {
+        module: "08_unchecked_close",
+        program: "position_manager",
+        name: "Pool",
+        kind: Kind::Anchor,
+        discriminator: Some([241, 154, 109, 4, 17, 177, 109, 188]),
+        fields: &[
+            Field {
+                name: "total_deposits",
+                ty: "u64",
+                offset: Some(8),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(16),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(17),
+    },
+    Layout {
+        module: "08_unchecked_close",
+        program: "position_manager",
+        name: "Position",
+        kind: Kind::Anchor,
+        discriminator: Some([170, 188, 143, 228, 122, 64, 247, 208]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "deposited",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(48),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(49),
+    },
+    Layout {
+        module: "09_account_resurrection",
+        program: "voucher_redeemer",
+        name: "Treasury",
+        kind: Kind::Anchor,
+        discriminator: Some([238, 239, 123, 238, 89, 1, 168, 253]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "total_redeemed",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(48),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(49),
+    },
+    Layout {
+        module: "09_account_resurrection",
+        program: "voucher_redeemer",
+        name: "Voucher",
+        kind: Kind::Anchor,
+        discriminator: Some([191, 204, 149, 234, 213, 165, 13, 65]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "amount",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+        ],
+        size: Size::Fixed(48),
+    },
+    Layout {
+        module: "10_cross_instance_replay",
+        program: "signed_withdrawals",
+        name: "Vault",
+        kind: Kind::Anchor,
+        discriminator: Some([211, 8, 232, 43, 2, 152, 117, 119]),
+        fields: &[
+            Field {
+                name: "authorizer",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "cluster",
+                ty: "Cluster",
+                offset: Some(40),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "next_nonce",
+                ty: "u64",
+                offset: Some(41),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(49),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(50),
+    },
+    Layout {
+        module: "12_rent_topup_griefing",
+        program: "storage_sponsor",
+        name: "Sponsor",
+        kind: Kind::Anchor,
+        discriminator: Some([19, 128, 115, 109, 118, 109, 66, 213]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "max_top_up_per_call",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "total_sponsored",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(56),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(57),
+    },
+    Layout {
+        module: "12_rent_topup_griefing",
+        program: "storage_sponsor",
+        name: "Note",
+        kind: Kind::Anchor,
+        discriminator: Some([203, 75, 252, 196, 81, 210, 122, 126]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "sponsored_lamports",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(48),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(49),
+    },
+    Layout {
+        module: "13_slot_gambling",
+        program: "coin_flip",
+        name: "House",
+        kind: Kind::Anchor,
+        discriminator: Some([21, 145, 94, 109, 254, 199, 210, 151]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(40),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(41),
+    },
+    Layout {
+        module: "13_slot_gambling",
+        program: "coin_flip",
+        name: "Round",
+        kind: Kind::Anchor,
+        discriminator: Some([87, 127, 165, 51, 73, 78, 116, 174]),
+        fields: &[
+            Field {
+                name: "round_id",
+                ty: "u64",
+                offset: Some(8),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "commitment",
+                ty: "[u8; 32]",
+                offset: Some(16),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "player",
+                ty: "Pubkey",
+                offset: Some(48),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "amount",
+                ty: "u64",
+                offset: Some(80),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "player_seed",
+                ty: "[u8; 32]",
+                offset: Some(88),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bet_slot",
+                ty: "u64",
+                offset: Some(120),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "status",
+                ty: "RoundStatus",
+                offset: Some(128),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(129),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(130),
+    },
+    Layout {
+        module: "14_simulation_divergence",
+        program: "payment_gateway",
+        name: "Config",
+        kind: Kind::Anchor,
+        discriminator: Some([155, 12, 170, 224, 30, 250, 204, 130]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "fee_recipient",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "fee_bps",
+                ty: "u16",
+                offset: Some(72),
+                size: Size::Fixed(2),
+            },
+            Field {
+                name: "version",
+                ty: "u64",
+                offset: Some(74),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(82),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(83),
+    },
+    Layout {
+        module: "15_missing_executable_check",
+        program: "plugin_registry",
+        name: "Registry",
+        kind: Kind::Anchor,
+        discriminator: Some([47, 174, 110, 246, 184, 182, 252, 218]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "plugin",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(72),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(73),
+    },
+    Layout {
+        module: "16_duplicate_account_roles",
+        program: "credit_ledger",
+        name: "Ledger",
+        kind: Kind::Anchor,
+        discriminator: Some([43, 41, 21, 213, 180, 176, 95, 32]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "council",
+                ty: "[Pubkey; COUNCIL_SIZE]",
+                offset: Some(40),
+                size: Size::Fixed(96),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(136),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(137),
+    },
+    Layout {
+        module: "16_duplicate_account_roles",
+        program: "credit_ledger",
+        name: "Balance",
+        kind: Kind::Anchor,
+        discriminator: Some([127, 71, 25, 157, 105, 157, 241, 182]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "amount",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(48),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(49),
+    },
+    Layout {
+        module: "17_god_pda_authority",
+        program: "vault_bank",
+        name: "Config",
+        kind: Kind::Anchor,
+        discriminator: Some([155, 12, 170, 224, 30, 250, 204, 130]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(40),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(41),
+    },
+    Layout {
+        module: "17_god_pda_authority",
+        program: "vault_bank",
+        name: "Vault",
+        kind: Kind::Anchor,
+        discriminator: Some([211, 8, 232, 43, 2, 152, 117, 119]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "authority_bump",
+                ty: "u8",
+                offset: Some(72),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(73),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(74),
+    },
+    Layout {
+        module: "17_god_pda_authority",
+        program: "vault_bank",
+        name: "AllowedDestination",
+        kind: Kind::Anchor,
+        discriminator: Some([103, 138, 11, 12, 60, 210, 127, 210]),
+        fields: &[
+            Field {
+                name: "config",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "destination",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "active_at",
+                ty: "i64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(80),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(81),
+    },
+    Layout {
+        module: "18_token_balance_voting",
+        program: "mock_lender",
+        name: "Pool",
+        kind: Kind::Anchor,
+        discriminator: Some([241, 154, 109, 4, 17, 177, 109, 188]),
+        fields: &[
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "outstanding",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(48),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(49),
+    },
+    Layout {
+        module: "18_token_balance_voting",
+        program: "token_voting",
+        name: "Proposal",
+        kind: Kind::Anchor,
+        discriminator: Some([26, 94, 189, 187, 116, 136, 53, 33]),
+        fields: &[
+            Field {
+                name: "id",
+                ty: "u64",
+                offset: Some(8),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(16),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "yes_votes",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "no_votes",
+                ty: "u64",
+                offset: Some(56),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "voting_ends_at",
+                ty: "i64",
+                offset: Some(64),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(72),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(73),
+    },
+    Layout {
+        module: "18_token_balance_voting",
+        program: "token_voting",
+        name: "VoteRecord",
+        kind: Kind::Anchor,
+        discriminator: Some([112, 9, 123, 165, 234, 9, 157, 167]),
+        fields: &[
+            Field {
+                name: "voter",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "weight",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "support",
+                ty: "bool",
+                offset: Some(48),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(49),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(50),
+    },
+    Layout {
+        module: "18_token_balance_voting",
+        program: "token_voting",
+        name: "Escrow",
+        kind: Kind::Anchor,
+        discriminator: Some([31, 213, 123, 187, 186, 22, 218, 155]),
+        fields: &[
+            Field {
+                name: "proposal",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "voter",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "amount",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(80),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(81),
+    },
+    Layout {
+        module: "19_lp_token_accounting",
+        program: "lp_pool",
+        name: "Pool",
+        kind: Kind::Anchor,
+        discriminator: Some([241, 154, 109, 4, 17, 177, 109, 188]),
+        fields: &[
+            Field {
+                name: "mint_a",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "mint_b",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "reserve_a",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "reserve_b",
+                ty: "u64",
+                offset: Some(80),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(88),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(89),
+    },
+    Layout {
+        module: "20_lending_toy",
+        program: "lending_toy",
+        name: "Market",
+        kind: Kind::Anchor,
+        discriminator: Some([219, 190, 213, 55, 0, 227, 198, 154]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "total_deposits",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "total_borrows",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "borrow_rate_bps",
+                ty: "u64",
+                offset: Some(56),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "last_update_slot",
+                ty: "u64",
+                offset: Some(64),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "loss_index",
+                ty: "u128",
+                offset: Some(72),
+                size: Size::Fixed(16),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(88),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(89),
+    },
+    Layout {
+        module: "20_lending_toy",
+        program: "lending_toy",
+        name: "Position",
+        kind: Kind::Anchor,
+        discriminator: Some([170, 188, 143, 228, 122, 64, 247, 208]),
+        fields: &[
+            Field {
+                name: "market",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "deposited",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "collateral",
+                ty: "u64",
+                offset: Some(80),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "borrowed",
+                ty: "u64",
+                offset: Some(88),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "deposit_index",
+                ty: "u128",
+                offset: Some(96),
+                size: Size::Fixed(16),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(112),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(113),
+    },
+    Layout {
+        module: "21_keeper_crank_incentives",
+        program: "order_expiry",
+        name: "Order",
+        kind: Kind::Anchor,
+        discriminator: Some([134, 173, 223, 185, 77, 86, 28, 51]),
+        fields: &[
+            Field {
+                name: "maker",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "id",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "amount",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "expires_at",
+                ty: "i64",
+                offset: Some(56),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(64),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(65),
+    },
+    Layout {
+        module: "22_fee_recipient_ownership",
+        program: "payment_processor",
+        name: "Config",
+        kind: Kind::Anchor,
+        discriminator: Some([155, 12, 170, 224, 30, 250, 204, 130]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "fee_bps",
+                ty: "u16",
+                offset: Some(72),
+                size: Size::Fixed(2),
+            },
+            Field {
+                name: "fee_authority_bump",
+                ty: "u8",
+                offset: Some(74),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(75),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "fee_waivable",
+                ty: "bool",
+                offset: Some(76),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(77),
+    },
+    Layout {
+        module: "23_remaining_accounts_router",
+        program: "swap_router",
+        name: "Registry",
+        kind: Kind::Anchor,
+        discriminator: Some([47, 174, 110, 246, 184, 182, 252, 218]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "pools",
+                ty: "Vec<Pubkey>",
+                offset: Some(40),
+                size: Size::Variable { min: 4 },
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Variable { min: 45 },
+    },
+    Layout {
+        module: "23_remaining_accounts_router",
+        program: "swap_router",
+        name: "Trader",
+        kind: Kind::Anchor,
+        discriminator: Some([74, 133, 32, 105, 47, 50, 5, 238]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "balances",
+                ty: "[u64; ASSET_COUNT]",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(72),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(73),
+    },
+    Layout {
+        module: "23_remaining_accounts_router",
+        program: "swap_router",
+        name: "Pool",
+        kind: Kind::Anchor,
+        discriminator: Some([241, 154, 109, 4, 17, 177, 109, 188]),
+        fields: &[
+            Field {
+                name: "creator",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "id",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "asset_in",
+                ty: "u8",
+                offset: Some(48),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "asset_out",
+                ty: "u8",
+                offset: Some(49),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "reserve_in",
+                ty: "u64",
+                offset: Some(50),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "reserve_out",
+                ty: "u64",
+                offset: Some(58),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "fee_bps",
+                ty: "u16",
+                offset: Some(66),
+                size: Size::Fixed(2),
+            },
+            Field {
+                name: "fees_owed",
+                ty: "u64",
+                offset: Some(68),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(76),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(77),
+    },
+    Layout {
+        module: "24_two_phase_commit",
+        program: "timelock_treasury",
+        name: "Treasury",
+        kind: Kind::Anchor,
+        discriminator: Some([238, 239, 123, 238, 89, 1, 168, 253]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "delay_seconds",
+                ty: "i64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(48),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(49),
+    },
+    Layout {
+        module: "24_two_phase_commit",
+        program: "timelock_treasury",
+        name: "PendingWithdrawal",
+        kind: Kind::Anchor,
+        discriminator: Some([61, 103, 179, 177, 148, 199, 63, 171]),
+        fields: &[
+            Field {
+                name: "treasury",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "intent.proposer",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "intent.params_hash",
+                ty: "[u8; 32]",
+                offset: Some(72),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "intent.prepared_at",
+                ty: "i64",
+                offset: Some(104),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "intent.executable_at",
+                ty: "i64",
+                offset: Some(112),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(120),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(121),
+    },
+    Layout {
+        module: "25_account_prefunding",
+        program: "vault_factory",
+        name: "Vault",
+        kind: Kind::Anchor,
+        discriminator: Some([211, 8, 232, 43, 2, 152, 117, 119]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(40),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(41),
+    },
+    Layout {
+        module: "26_hot_account_contention",
+        program: "points_ledger",
+        name: "Ledger",
+        kind: Kind::Anchor,
+        discriminator: Some([43, 41, 21, 213, 180, 176, 95, 32]),
+        fields: &[
+            Field {
+                name: "total_points",
+                ty: "u64",
+                offset: Some(8),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "last_aggregated_slot",
+                ty: "u64",
+                offset: Some(16),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(24),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(25),
+    },
+    Layout {
+        module: "26_hot_account_contention",
+        program: "points_ledger",
+        name: "Shard",
+        kind: Kind::Anchor,
+        discriminator: Some([141, 83, 41, 244, 139, 88, 245, 70]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "pending_points",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "lifetime_points",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(56),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(57),
+    },
+    Layout {
+        module: "27_event_authenticity",
+        program: "rewards_distributor",
+        name: "RewardsPool",
+        kind: Kind::Anchor,
+        discriminator: Some([107, 36, 119, 42, 181, 249, 18, 37]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "grants_issued",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "total_granted",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(56),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(57),
+    },
+    Layout {
+        module: "28_zero_copy_loader_misuse",
+        program: "price_history",
+        name: "PriceHistory",
+        kind: Kind::ZeroCopy,
+        discriminator: Some([38, 241, 40, 19, 42, 228, 93, 152]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "head",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "count",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "prices",
+                ty: "[u64; HISTORY_LEN]",
+                offset: Some(56),
+                size: Size::Fixed(512),
+            },
+        ],
+        size: Size::Fixed(568),
+    },
+    Layout {
+        module: "29_stack_frame_limits",
+        program: "basket_rebalancer",
+        name: "Basket",
+        kind: Kind::Anchor,
+        discriminator: Some([219, 79, 107, 135, 231, 243, 218, 248]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "id",
+                ty: "u8",
+                offset: Some(40),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(41),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "weights",
+                ty: "[u64; BASKET_ASSETS]",
+                offset: Some(42),
+                size: Size::Fixed(1024),
+            },
+        ],
+        size: Size::Fixed(1066),
+    },
+    Layout {
+        module: "29_stack_frame_limits",
+        program: "basket_rebalancer",
+        name: "Index",
+        kind: Kind::ZeroCopy,
+        discriminator: Some([140, 66, 194, 132, 78, 26, 135, 186]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "rebalances",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "weights",
+                ty: "[u64; BASKET_ASSETS]",
+                offset: Some(48),
+                size: Size::Fixed(1024),
+            },
+        ],
+        size: Size::Fixed(1072),
+    },
+    Layout {
+        module: "30_cpi_return_data",
+        program: "loan_desk",
+        name: "Position",
+        kind: Kind::Anchor,
+        discriminator: Some([170, 188, 143, 228, 122, 64, 247, 208]),
+        fields: &[
+            Field {
+                name: "borrower",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "collateral",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "borrow_limit",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(56),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(57),
+    },
+    Layout {
+        module: "31_invariant_watchdog",
+        program: "watched_vault",
+        name: "VaultState",
+        kind: Kind::Anchor,
+        discriminator: Some([228, 196, 82, 165, 98, 210, 235, 152]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "total_liabilities",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "vault_bump",
+                ty: "u8",
+                offset: Some(48),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(49),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(50),
+    },
+    Layout {
+        module: "31_invariant_watchdog",
+        program: "watched_vault",
+        name: "Deposit",
+        kind: Kind::Anchor,
+        discriminator: Some([148, 146, 121, 66, 207, 173, 21, 227]),
+        fields: &[
+            Field {
+                name: "state",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "amount",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(80),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(81),
+    },
+    Layout {
+        module: "32_program_id_confusion",
+        program: "feed_squatter",
+        name: "PriceFeed",
+        kind: Kind::Anchor,
+        discriminator: Some([189, 103, 252, 23, 152, 35, 243, 156]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "price",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(80),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(81),
+    },
+    Layout {
+        module: "32_program_id_confusion",
+        program: "lending_market",
+        name: "Market",
+        kind: Kind::Anchor,
+        discriminator: Some([219, 190, 213, 55, 0, 227, 198, 154]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "oracle",
+                ty: "Pubkey",
+                offset: Some(72),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "price_feed_program",
+                ty: "Pubkey",
+                offset: Some(104),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(136),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(137),
+    },
+    Layout {
+        module: "32_program_id_confusion",
+        program: "lending_market",
+        name: "Position",
+        kind: Kind::Anchor,
+        discriminator: Some([170, 188, 143, 228, 122, 64, 247, 208]),
+        fields: &[
+            Field {
+                name: "market",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "borrower",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "collateral",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "borrow_limit",
+                ty: "u64",
+                offset: Some(80),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(88),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(89),
+    },
+    Layout {
+        module: "32_program_id_confusion",
+        program: "price_feed",
+        name: "PriceFeed",
+        kind: Kind::Anchor,
+        discriminator: Some([189, 103, 252, 23, 152, 35, 243, 156]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "price",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(80),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(81),
+    },
+    Layout {
+        module: "33_nft_rental_utility",
+        program: "nft_rental",
+        name: "Listing",
+        kind: Kind::Anchor,
+        discriminator: Some([218, 32, 50, 73, 43, 134, 26, 58]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "nft_mint",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "daily_fee",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "rented_until",
+                ty: "i64",
+                offset: Some(80),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "rentals",
+                ty: "u64",
+                offset: Some(88),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(96),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(97),
+    },
+    Layout {
+        module: "33_nft_rental_utility",
+        program: "nft_rental",
+        name: "Rental",
+        kind: Kind::Anchor,
+        discriminator: Some([121, 83, 229, 235, 73, 50, 143, 184]),
+        fields: &[
+            Field {
+                name: "listing",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "renter",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "receipt_mint",
+                ty: "Pubkey",
+                offset: Some(72),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "index",
+                ty: "u64",
+                offset: Some(104),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "expires_at",
+                ty: "i64",
+                offset: Some(112),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(120),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(121),
+    },
+    Layout {
+        module: "33_nft_rental_utility",
+        program: "nft_rental",
+        name: "Claim",
+        kind: Kind::Anchor,
+        discriminator: Some([155, 70, 22, 176, 123, 215, 246, 102]),
+        fields: &[
+            Field {
+                name: "nft_mint",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "day",
+                ty: "i64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "claimant",
+                ty: "Pubkey",
+                offset: Some(48),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(80),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(81),
+    },
+    Layout {
+        module: "34_royalty_bypass",
+        program: "nft_marketplace",
+        name: "Listing",
+        kind: Kind::Anchor,
+        discriminator: Some([218, 32, 50, 73, 43, 134, 26, 58]),
+        fields: &[
+            Field {
+                name: "seller",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "price",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(80),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(81),
+    },
+    Layout {
+        module: "35_bonding_curve_pricing",
+        program: "bonding_curve_sale",
+        name: "Sale",
+        kind: Kind::Anchor,
+        discriminator: Some([202, 64, 232, 171, 178, 172, 34, 183]),
+        fields: &[
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "curve",
+                ty: "Curve",
+                offset: Some(40),
+                size: Size::Fixed(17),
+            },
+            Field {
+                name: "supply_cap",
+                ty: "u64",
+                offset: Some(57),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(65),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(66),
+    },
+    Layout {
+        module: "36_referral_self_dealing",
+        program: "referral_rewards",
+        name: "Campaign",
+        kind: Kind::Anchor,
+        discriminator: Some([50, 40, 49, 11, 157, 220, 229, 192]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "budget",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(48),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(49),
+    },
+    Layout {
+        module: "36_referral_self_dealing",
+        program: "referral_rewards",
+        name: "Member",
+        kind: Kind::Anchor,
+        discriminator: Some([54, 19, 162, 21, 29, 166, 17, 198]),
+        fields: &[
+            Field {
+                name: "campaign",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "referrer",
+                ty: "Pubkey",
+                offset: Some(72),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "deposited",
+                ty: "u64",
+                offset: Some(104),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "peak_deposited",
+                ty: "u64",
+                offset: Some(112),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "rewards",
+                ty: "u64",
+                offset: Some(120),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(128),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(129),
+    },
+    Layout {
+        module: "37_payment_splitter_rounding",
+        program: "payment_splitter",
+        name: "Splitter",
+        kind: Kind::Anchor,
+        discriminator: Some([187, 177, 147, 182, 44, 155, 130, 202]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "recipients",
+                ty: "Vec<Recipient>",
+                offset: Some(40),
+                size: Size::Variable { min: 4 },
+            },
+            Field {
+                name: "total_shares",
+                ty: "u32",
+                offset: None,
+                size: Size::Fixed(4),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Variable { min: 49 },
+    },
+    Layout {
+        module: "38_subscription_authority",
+        program: "subscriptions",
+        name: "Subscription",
+        kind: Kind::Anchor,
+        discriminator: Some([64, 7, 26, 135, 102, 132, 98, 33]),
+        fields: &[
+            Field {
+                name: "subscriber",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "service",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "subscriber_tokens",
+                ty: "Pubkey",
+                offset: Some(72),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "amount",
+                ty: "u64",
+                offset: Some(104),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "interval",
+                ty: "i64",
+                offset: Some(112),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "next_pull_at",
+                ty: "i64",
+                offset: Some(120),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "expires_at",
+                ty: "i64",
+                offset: Some(128),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(136),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(137),
+    },
+    Layout {
+        module: "39_bridge_message_verification",
+        program: "bridge_inbox",
+        name: "Bridge",
+        kind: Kind::Anchor,
+        discriminator: Some([231, 232, 31, 98, 110, 3, 23, 59]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "guardians",
+                ty: "Vec<Pubkey>",
+                offset: Some(40),
+                size: Size::Variable { min: 4 },
+            },
+            Field {
+                name: "threshold",
+                ty: "u8",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Variable { min: 46 },
+    },
+    Layout {
+        module: "39_bridge_message_verification",
+        program: "bridge_inbox",
+        name: "ConsumedMessage",
+        kind: Kind::Anchor,
+        discriminator: Some([36, 210, 246, 184, 240, 19, 14, 61]),
+        fields: &[
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(8),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(9),
+    },
+    Layout {
+        module: "40_wallet_drainer_anatomy",
+        program: "airdrop_claim",
+        name: "Distributor",
+        kind: Kind::Anchor,
+        discriminator: Some([90, 90, 217, 147, 6, 32, 135, 4]),
+        fields: &[
+            Field {
+                name: "operator",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "vault",
+                ty: "Pubkey",
+                offset: Some(72),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "amount_per_claim",
+                ty: "u64",
+                offset: Some(104),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(112),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(113),
+    },
+    Layout {
+        module: "40_wallet_drainer_anatomy",
+        program: "airdrop_claim",
+        name: "ClaimRecord",
+        kind: Kind::Anchor,
+        discriminator: Some([57, 229, 0, 9, 65, 62, 96, 7]),
+        fields: &[
+            Field {
+                name: "claimant",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(40),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(41),
+    },
+    Layout {
+        module: "41_ata_precreation_squatting",
+        program: "ata_escrow",
+        name: "Escrow",
+        kind: Kind::Anchor,
+        discriminator: Some([31, 213, 123, 187, 186, 22, 218, 155]),
+        fields: &[
+            Field {
+                name: "maker",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "vault",
+                ty: "Pubkey",
+                offset: Some(72),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "seed",
+                ty: "u64",
+                offset: Some(104),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "amount",
+                ty: "u64",
+                offset: Some(112),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(120),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(121),
+    },
+    Layout {
+        module: "42_compliance_freeze",
+        program: "compliance_vault",
+        name: "Vault",
+        kind: Kind::Anchor,
+        discriminator: Some([211, 8, 232, 43, 2, 152, 117, 119]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "freezer",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "seizer",
+                ty: "Pubkey",
+                offset: Some(72),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "treasury",
+                ty: "Pubkey",
+                offset: Some(104),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "seize_delay",
+                ty: "i64",
+                offset: Some(136),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(144),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(145),
+    },
+    Layout {
+        module: "42_compliance_freeze",
+        program: "compliance_vault",
+        name: "Position",
+        kind: Kind::Anchor,
+        discriminator: Some([170, 188, 143, 228, 122, 64, 247, 208]),
+        fields: &[
+            Field {
+                name: "vault",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "balance",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "frozen",
+                ty: "bool",
+                offset: Some(80),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "seizure",
+                ty: "Option<Intent>",
+                offset: Some(81),
+                size: Size::Variable { min: 1 },
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Variable { min: 83 },
+    },
+    Layout {
+        module: "43_deposit_memo_binding",
+        program: "deposit_receipts",
+        name: "Vault",
+        kind: Kind::Anchor,
+        discriminator: Some([211, 8, 232, 43, 2, 152, 117, 119]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(40),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(41),
+    },
+    Layout {
+        module: "43_deposit_memo_binding",
+        program: "deposit_receipts",
+        name: "Receipt",
+        kind: Kind::Anchor,
+        discriminator: Some([39, 154, 73, 106, 80, 102, 145, 153]),
+        fields: &[
+            Field {
+                name: "vault",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "reference",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "depositor",
+                ty: "Pubkey",
+                offset: Some(72),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "amount",
+                ty: "u64",
+                offset: Some(104),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "deposited_at",
+                ty: "i64",
+                offset: Some(112),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(120),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(121),
+    },
+    Layout {
+        module: "44_concurrent_merkle_tree",
+        program: "voucher_tree",
+        name: "Tree",
+        kind: Kind::Anchor,
+        discriminator: Some([100, 9, 213, 154, 6, 136, 109, 55]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "depth",
+                ty: "u8",
+                offset: Some(40),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "next_index",
+                ty: "u64",
+                offset: Some(41),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "root",
+                ty: "[u8; 32]",
+                offset: Some(49),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "root_history",
+                ty: "[[u8; 32]; ROOT_HISTORY]",
+                offset: Some(81),
+                size: Size::Fixed(256),
+            },
+            Field {
+                name: "history_cursor",
+                ty: "u8",
+                offset: Some(337),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(338),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(339),
+    },
+    Layout {
+        module: "44_concurrent_merkle_tree",
+        program: "voucher_tree",
+        name: "Redemption",
+        kind: Kind::Anchor,
+        discriminator: Some([112, 75, 232, 189, 22, 114, 156, 203]),
+        fields: &[
+            Field {
+                name: "tree",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "index",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(48),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(49),
+    },
+    Layout {
+        module: "45_durable_nonce_replay",
+        program: "relayed_payments",
+        name: "Wallet",
+        kind: Kind::Anchor,
+        discriminator: Some([24, 89, 59, 139, 81, 154, 232, 95]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(40),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(41),
+    },
+    Layout {
+        module: "45_durable_nonce_replay",
+        program: "relayed_payments",
+        name: "UsedNonce",
+        kind: Kind::Anchor,
+        discriminator: Some([212, 222, 157, 252, 130, 71, 179, 238]),
+        fields: &[
+            Field {
+                name: "wallet",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "nonce",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(48),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(49),
+    },
+    Layout {
+        module: "46_balance_as_authorization",
+        program: "premium_club",
+        name: "Club",
+        kind: Kind::Anchor,
+        discriminator: Some([84, 214, 36, 249, 146, 164, 94, 181]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "threshold",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bonus",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "lock_period",
+                ty: "i64",
+                offset: Some(56),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(64),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(65),
+    },
+    Layout {
+        module: "46_balance_as_authorization",
+        program: "premium_club",
+        name: "StakeRecord",
+        kind: Kind::Anchor,
+        discriminator: Some([174, 163, 11, 208, 150, 236, 11, 205]),
+        fields: &[
+            Field {
+                name: "club",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "member",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "amount",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "locked_until",
+                ty: "i64",
+                offset: Some(80),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(88),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(89),
+    },
+    Layout {
+        module: "46_balance_as_authorization",
+        program: "premium_club",
+        name: "Claim",
+        kind: Kind::Anchor,
+        discriminator: Some([155, 70, 22, 176, 123, 215, 246, 102]),
+        fields: &[
+            Field {
+                name: "club",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "member",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(72),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(73),
+    },
+    Layout {
+        module: "47_kill_switch_recovery",
+        program: "guarded_pool",
+        name: "Pool",
+        kind: Kind::Anchor,
+        discriminator: Some([241, 154, 109, 4, 17, 177, 109, 188]),
+        fields: &[
+            Field {
+                name: "governance",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "guardians",
+                ty: "Vec<Pubkey>",
+                offset: Some(40),
+                size: Size::Variable { min: 4 },
+            },
+            Field {
+                name: "threshold",
+                ty: "u8",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "mode",
+                ty: "Mode",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "halt_ends_at",
+                ty: "i64",
+                offset: None,
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "max_halt",
+                ty: "i64",
+                offset: None,
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "resume_delay",
+                ty: "i64",
+                offset: None,
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "resume",
+                ty: "Option<Intent>",
+                offset: None,
+                size: Size::Variable { min: 1 },
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Variable { min: 72 },
+    },
+    Layout {
+        module: "47_kill_switch_recovery",
+        program: "guarded_pool",
+        name: "Position",
+        kind: Kind::Anchor,
+        discriminator: Some([170, 188, 143, 228, 122, 64, 247, 208]),
+        fields: &[
+            Field {
+                name: "pool",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "balance",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(80),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(81),
+    },
+    Layout {
+        module: "48_vault_interface",
+        program: "boosted_vault",
+        name: "Vault",
+        kind: Kind::Anchor,
+        discriminator: Some([211, 8, 232, 43, 2, 152, 117, 119]),
+        fields: &[
+            Field {
+                name: "total_shares",
+                ty: "u64",
+                offset: Some(8),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(16),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(17),
+    },
+    Layout {
+        module: "48_vault_interface",
+        program: "boosted_vault",
+        name: "Position",
+        kind: Kind::Anchor,
+        discriminator: Some([170, 188, 143, 228, 122, 64, 247, 208]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "shares",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(48),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(49),
+    },
+    Layout {
+        module: "48_vault_interface",
+        program: "steady_vault",
+        name: "Vault",
+        kind: Kind::Anchor,
+        discriminator: Some([211, 8, 232, 43, 2, 152, 117, 119]),
+        fields: &[
+            Field {
+                name: "total_shares",
+                ty: "u64",
+                offset: Some(8),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(16),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(17),
+    },
+    Layout {
+        module: "48_vault_interface",
+        program: "steady_vault",
+        name: "Position",
+        kind: Kind::Anchor,
+        discriminator: Some([170, 188, 143, 228, 122, 64, 247, 208]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "shares",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(48),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(49),
+    },
+    Layout {
+        module: "48_vault_interface",
+        program: "vault_router",
+        name: "Config",
+        kind: Kind::Anchor,
+        discriminator: Some([155, 12, 170, 224, 30, 250, 204, 130]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(40),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(41),
+    },
+    Layout {
+        module: "48_vault_interface",
+        program: "vault_router",
+        name: "Approval",
+        kind: Kind::Anchor,
+        discriminator: Some([233, 9, 153, 49, 11, 222, 59, 130]),
+        fields: &[
+            Field {
+                name: "vault_program",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(40),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(41),
+    },
+    Layout {
+        module: "48_vault_interface",
+        program: "vault_router",
+        name: "Allocation",
+        kind: Kind::Anchor,
+        discriminator: Some([147, 154, 3, 177, 155, 25, 131, 176]),
+        fields: &[
+            Field {
+                name: "vault_program",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "deposited",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "shares",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(56),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(57),
+    },
+    Layout {
+        module: "49_stake_authority_confusion",
+        program: "stake_pool",
+        name: "Pool",
+        kind: Kind::Anchor,
+        discriminator: Some([241, 154, 109, 4, 17, 177, 109, 188]),
+        fields: &[
+            Field {
+                name: "manager",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "total_staked",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(48),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(49),
+    },
+    Layout {
+        module: "49_stake_authority_confusion",
+        program: "stake_pool",
+        name: "Position",
+        kind: Kind::Anchor,
+        discriminator: Some([170, 188, 143, 228, 122, 64, 247, 208]),
+        fields: &[
+            Field {
+                name: "pool",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "balance",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(80),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(81),
+    },
+    Layout {
+        module: "50_validator_delegation",
+        program: "delegation_manager",
+        name: "Manager",
+        kind: Kind::Anchor,
+        discriminator: Some([221, 78, 171, 233, 213, 142, 113, 56]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "max_commission",
+                ty: "u8",
+                offset: Some(40),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(41),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(42),
+    },
+    Layout {
+        module: "51_lookup_table_poisoning",
+        program: "pool_router",
+        name: "Config",
+        kind: Kind::Anchor,
+        discriminator: Some([155, 12, 170, 224, 30, 250, 204, 130]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "lookup_table",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(72),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(73),
+    },
+    Layout {
+        module: "51_lookup_table_poisoning",
+        program: "pool_router",
+        name: "Pool",
+        kind: Kind::Anchor,
+        discriminator: Some([241, 154, 109, 4, 17, 177, 109, 188]),
+        fields: &[
+            Field {
+                name: "pool_id",
+                ty: "u64",
+                offset: Some(8),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(16),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(17),
+    },
+    Layout {
+        module: "52_memo_payment_reference",
+        program: "invoice_desk",
+        name: "Invoice",
+        kind: Kind::Anchor,
+        discriminator: Some([51, 194, 250, 114, 6, 104, 18, 164]),
+        fields: &[
+            Field {
+                name: "merchant",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "invoice_id",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "amount",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "reference",
+                ty: "String",
+                offset: Some(56),
+                size: Size::Variable { min: 4 },
+            },
+            Field {
+                name: "paid",
+                ty: "bool",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Variable { min: 62 },
+    },
+    Layout {
+        module: "53_velocity_limits",
+        program: "limited_vault",
+        name: "Vault",
+        kind: Kind::Anchor,
+        discriminator: Some([211, 8, 232, 43, 2, 152, 117, 119]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "limits.max_per_tx",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "limits.max_per_window",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "limits.window_seconds",
+                ty: "i64",
+                offset: Some(56),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(64),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(65),
+    },
+    Layout {
+        module: "53_velocity_limits",
+        program: "limited_vault",
+        name: "Position",
+        kind: Kind::Anchor,
+        discriminator: Some([170, 188, 143, 228, 122, 64, 247, 208]),
+        fields: &[
+            Field {
+                name: "vault",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "balance",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(80),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(81),
+    },
+    Layout {
+        module: "53_velocity_limits",
+        program: "limited_vault",
+        name: "Tracker",
+        kind: Kind::Anchor,
+        discriminator: Some([31, 18, 229, 12, 35, 100, 128, 68]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "day_started_at",
+                ty: "i64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "withdrawn_today",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(56),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(57),
+    },
+    Layout {
+        module: "53_velocity_limits",
+        program: "limited_vault",
+        name: "VelocityTracker",
+        kind: Kind::Anchor,
+        discriminator: Some([73, 138, 12, 190, 45, 67, 201, 129]),
+        fields: &[
+            Field {
+                name: "window.window",
+                ty: "i64",
+                offset: Some(8),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "window.used",
+                ty: "u64",
+                offset: Some(16),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(24),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(25),
+    },
+    Layout {
+        module: "54_transfer_hook_authoring",
+        program: "guarded_hook",
+        name: "Policy",
+        kind: Kind::Anchor,
+        discriminator: Some([222, 135, 7, 163, 235, 177, 33, 68]),
+        fields: &[
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "limits.max_per_tx",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "limits.max_per_window",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "limits.window_seconds",
+                ty: "i64",
+                offset: Some(56),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(64),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(65),
+    },
+    Layout {
+        module: "54_transfer_hook_authoring",
+        program: "guarded_hook",
+        name: "Inbound",
+        kind: Kind::Anchor,
+        discriminator: Some([111, 124, 129, 241, 18, 205, 95, 205]),
+        fields: &[
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "wallet",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "window.window",
+                ty: "i64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "window.used",
+                ty: "u64",
+                offset: Some(80),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(88),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(89),
+    },
+    Layout {
+        module: "54_transfer_hook_authoring",
+        program: "hooked_vault",
+        name: "Vault",
+        kind: Kind::Anchor,
+        discriminator: Some([211, 8, 232, 43, 2, 152, 117, 119]),
+        fields: &[
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "tokens",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "hook_program",
+                ty: "Pubkey",
+                offset: Some(72),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "total_deposits",
+                ty: "u64",
+                offset: Some(104),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(112),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(113),
+    },
+    Layout {
+        module: "54_transfer_hook_authoring",
+        program: "hooked_vault",
+        name: "Position",
+        kind: Kind::Anchor,
+        discriminator: Some([170, 188, 143, 228, 122, 64, 247, 208]),
+        fields: &[
+            Field {
+                name: "vault",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "balance",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(80),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(81),
+    },
+    Layout {
+        module: "54_transfer_hook_authoring",
+        program: "naive_hook",
+        name: "Policy",
+        kind: Kind::Anchor,
+        discriminator: Some([222, 135, 7, 163, 235, 177, 33, 68]),
+        fields: &[
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "limits.max_per_tx",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "limits.max_per_window",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "limits.window_seconds",
+                ty: "i64",
+                offset: Some(56),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(64),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(65),
+    },
+    Layout {
+        module: "54_transfer_hook_authoring",
+        program: "naive_hook",
+        name: "Inbound",
+        kind: Kind::Anchor,
+        discriminator: Some([111, 124, 129, 241, 18, 205, 95, 205]),
+        fields: &[
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "wallet",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "window.window",
+                ty: "i64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "window.used",
+                ty: "u64",
+                offset: Some(80),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(88),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(89),
+    },
+    Layout {
+        module: "55_confidential_transfer_extension",
+        program: "confidential_vault",
+        name: "Vault",
+        kind: Kind::Anchor,
+        discriminator: Some([211, 8, 232, 43, 2, 152, 117, 119]),
+        fields: &[
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "tokens",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(72),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "total_deposits",
+                ty: "u64",
+                offset: Some(104),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "confidential_credits",
+                ty: "u64",
+                offset: Some(112),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(120),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(121),
+    },
+    Layout {
+        module: "55_confidential_transfer_extension",
+        program: "confidential_vault",
+        name: "Position",
+        kind: Kind::Anchor,
+        discriminator: Some([170, 188, 143, 228, 122, 64, 247, 208]),
+        fields: &[
+            Field {
+                name: "vault",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "balance",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(80),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(81),
+    },
+    Layout {
+        module: "56_mint_pointer_spoofing",
+        program: "collection_rewards",
+        name: "Pool",
+        kind: Kind::Anchor,
+        discriminator: Some([241, 154, 109, 4, 17, 177, 109, 188]),
+        fields: &[
+            Field {
+                name: "curator",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "group",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "reward",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "claims",
+                ty: "u64",
+                offset: Some(80),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(88),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(89),
+    },
+    Layout {
+        module: "56_mint_pointer_spoofing",
+        program: "collection_rewards",
+        name: "Claim",
+        kind: Kind::Anchor,
+        discriminator: Some([155, 70, 22, 176, 123, 215, 246, 102]),
+        fields: &[
+            Field {
+                name: "pool",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "claimant",
+                ty: "Pubkey",
+                offset: Some(72),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(104),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(105),
+    },
+    Layout {
+        module: "57_compute_budget_introspection",
+        program: "payout_crank",
+        name: "Payroll",
+        kind: Kind::Anchor,
+        discriminator: Some([194, 86, 146, 159, 169, 29, 234, 51]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "tip",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "cursor",
+                ty: "u8",
+                offset: Some(48),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "payees",
+                ty: "Vec<Payee>",
+                offset: Some(49),
+                size: Size::Variable { min: 4 },
+            },
+            Field {
+                name: "tips_paid",
+                ty: "u64",
+                offset: None,
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Variable { min: 62 },
+    },
+    Layout {
+        module: "58_payer_drain_griefing",
+        program: "gasless_notes",
+        name: "Config",
+        kind: Kind::Anchor,
+        discriminator: Some([155, 12, 170, 224, 30, 250, 204, 130]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "notes_sponsored",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(48),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "payer_bump",
+                ty: "u8",
+                offset: Some(49),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(50),
+    },
+    Layout {
+        module: "58_payer_drain_griefing",
+        program: "gasless_notes",
+        name: "Note",
+        kind: Kind::Anchor,
+        discriminator: Some([203, 75, 252, 196, 81, 210, 122, 126]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "id",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "sponsored_lamports",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(56),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(57),
+    },
+    Layout {
+        module: "58_payer_drain_griefing",
+        program: "gasless_notes",
+        name: "Quota",
+        kind: Kind::Anchor,
+        discriminator: Some([141, 43, 105, 117, 251, 232, 160, 249]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "sponsored",
+                ty: "u8",
+                offset: Some(40),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(41),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(42),
+    },
+    Layout {
+        module: "59_decimal_confusion",
+        program: "token_desk",
+        name: "Desk",
+        kind: Kind::Anchor,
+        discriminator: Some([33, 28, 147, 6, 226, 158, 166, 73]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "vault",
+                ty: "Pubkey",
+                offset: Some(72),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "price_per_token",
+                ty: "u64",
+                offset: Some(104),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "sold",
+                ty: "u64",
+                offset: Some(112),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(120),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(121),
+    },
+    Layout {
+        module: "60_timestamp_arithmetic",
+        program: "vesting_grants",
+        name: "Grant",
+        kind: Kind::Anchor,
+        discriminator: Some([161, 166, 11, 205, 204, 135, 205, 54]),
+        fields: &[
+            Field {
+                name: "funder",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "beneficiary",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "start",
+                ty: "i64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "duration",
+                ty: "i64",
+                offset: Some(80),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "total",
+                ty: "u64",
+                offset: Some(88),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "claimed",
+                ty: "u64",
+                offset: Some(96),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(104),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(105),
+    },
+    Layout {
+        module: "61_order_cancel_race",
+        program: "order_book",
+        name: "Market",
+        kind: Kind::Anchor,
+        discriminator: Some([219, 190, 213, 55, 0, 227, 198, 154]),
+        fields: &[
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "vault",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(72),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(73),
+    },
+    Layout {
+        module: "61_order_cancel_race",
+        program: "order_book",
+        name: "Order",
+        kind: Kind::Anchor,
+        discriminator: Some([134, 173, 223, 185, 77, 86, 28, 51]),
+        fields: &[
+            Field {
+                name: "market",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "maker",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "id",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "quantity",
+                ty: "u64",
+                offset: Some(80),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "price",
+                ty: "u64",
+                offset: Some(88),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "status",
+                ty: "OrderStatus",
+                offset: Some(96),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(97),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(98),
+    },
+    Layout {
+        module: "62_collateral_double_counting",
+        program: "collateral_hub",
+        name: "Pool",
+        kind: Kind::Anchor,
+        discriminator: Some([241, 154, 109, 4, 17, 177, 109, 188]),
+        fields: &[
+            Field {
+                name: "id",
+                ty: "u8",
+                offset: Some(8),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "ltv_bps",
+                ty: "u16",
+                offset: Some(9),
+                size: Size::Fixed(2),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(11),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(12),
+    },
+    Layout {
+        module: "62_collateral_double_counting",
+        program: "collateral_hub",
+        name: "Receipt",
+        kind: Kind::Anchor,
+        discriminator: Some([39, 154, 73, 106, 80, 102, 145, 153]),
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "amount",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "locked_by",
+                ty: "Option<Pubkey>",
+                offset: Some(48),
+                size: Size::Variable { min: 1 },
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Variable { min: 50 },
+    },
+    Layout {
+        module: "62_collateral_double_counting",
+        program: "collateral_hub",
+        name: "Loan",
+        kind: Kind::Anchor,
+        discriminator: Some([20, 195, 70, 117, 165, 227, 182, 1]),
+        fields: &[
+            Field {
+                name: "pool",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "receipt",
+                ty: "Pubkey",
+                offset: Some(72),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "borrowed",
+                ty: "u64",
+                offset: Some(104),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(112),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(113),
+    },
+    Layout {
+        module: "63_fee_bps_misconfiguration",
+        program: "checkout",
+        name: "Config",
+        kind: Kind::Anchor,
+        discriminator: Some([155, 12, 170, 224, 30, 250, 204, 130]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "treasury",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "fee_bps",
+                ty: "u16",
+                offset: Some(72),
+                size: Size::Fixed(2),
+            },
+            Field {
+                name: "pending_fee",
+                ty: "Option<Intent>",
+                offset: Some(74),
+                size: Size::Variable { min: 1 },
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Variable { min: 76 },
+    },
+    Layout {
+        module: "64_config_hot_swap",
+        program: "redemption_desk",
+        name: "PriceFeed",
+        kind: Kind::Anchor,
+        discriminator: Some([189, 103, 252, 23, 152, 35, 243, 156]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "price",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(48),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(49),
+    },
+    Layout {
+        module: "64_config_hot_swap",
+        program: "redemption_desk",
+        name: "Desk",
+        kind: Kind::Anchor,
+        discriminator: Some([33, 28, 147, 6, 226, 158, 166, 73]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "oracle",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "fee_recipient",
+                ty: "Pubkey",
+                offset: Some(72),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "epoch",
+                ty: "u64",
+                offset: Some(104),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "pending_config",
+                ty: "Option<Intent>",
+                offset: Some(112),
+                size: Size::Variable { min: 1 },
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Variable { min: 114 },
+    },
+    Layout {
+        module: "64_config_hot_swap",
+        program: "redemption_desk",
+        name: "ConfigEpoch",
+        kind: Kind::Anchor,
+        discriminator: Some([55, 171, 213, 89, 27, 126, 124, 36]),
+        fields: &[
+            Field {
+                name: "desk",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "index",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "oracle",
+                ty: "Pubkey",
+                offset: Some(48),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "fee_recipient",
+                ty: "Pubkey",
+                offset: Some(80),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "activated_at",
+                ty: "i64",
+                offset: Some(112),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(120),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(121),
+    },
+    Layout {
+        module: "64_config_hot_swap",
+        program: "redemption_desk",
+        name: "Position",
+        kind: Kind::Anchor,
+        discriminator: Some([170, 188, 143, 228, 122, 64, 247, 208]),
+        fields: &[
+            Field {
+                name: "desk",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "credits",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "redeeming",
+                ty: "u64",
+                offset: Some(80),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "requested_at",
+                ty: "i64",
+                offset: Some(88),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "epoch",
+                ty: "u64",
+                offset: Some(96),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(104),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(105),
+    },
+    Layout {
+        module: "65_withdraw_queue",
+        program: "withdraw_queue",
+        name: "Vault",
+        kind: Kind::Anchor,
+        discriminator: Some([211, 8, 232, 43, 2, 152, 117, 119]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "deployed",
+                ty: "u64",
+                offset: Some(40),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "queued",
+                ty: "u64",
+                offset: Some(48),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "next_ticket",
+                ty: "u64",
+                offset: Some(56),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "head",
+                ty: "u64",
+                offset: Some(64),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(72),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(73),
+    },
+    Layout {
+        module: "65_withdraw_queue",
+        program: "withdraw_queue",
+        name: "Position",
+        kind: Kind::Anchor,
+        discriminator: Some([170, 188, 143, 228, 122, 64, 247, 208]),
+        fields: &[
+            Field {
+                name: "vault",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "deposited",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(80),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(81),
+    },
+    Layout {
+        module: "65_withdraw_queue",
+        program: "withdraw_queue",
+        name: "WithdrawRequest",
+        kind: Kind::Anchor,
+        discriminator: Some([186, 239, 174, 191, 189, 13, 47, 196]),
+        fields: &[
+            Field {
+                name: "vault",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "ticket",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "amount",
+                ty: "u64",
+                offset: Some(80),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(88),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(89),
+    },
+    Layout {
+        module: "66_signer_seeds_leakage",
+        program: "strategy_vault",
+        name: "Vault",
+        kind: Kind::Anchor,
+        discriminator: Some([211, 8, 232, 43, 2, 152, 117, 119]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "keeper",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(72),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "custody_bump",
+                ty: "u8",
+                offset: Some(104),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "execute_bump",
+                ty: "u8",
+                offset: Some(105),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(106),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(107),
+    },
+    Layout {
+        module: "67_position_receipts",
+        program: "receipt_vault",
+        name: "Vault",
+        kind: Kind::Anchor,
+        discriminator: Some([211, 8, 232, 43, 2, 152, 117, 119]),
+        fields: &[
+            Field {
+                name: "admin",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "mint",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "positions",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "receipt_authority_bump",
+                ty: "u8",
+                offset: Some(80),
+                size: Size::Fixed(1),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(81),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(82),
+    },
+    Layout {
+        module: "67_position_receipts",
+        program: "receipt_vault",
+        name: "Position",
+        kind: Kind::Anchor,
+        discriminator: Some([170, 188, 143, 228, 122, 64, 247, 208]),
+        fields: &[
+            Field {
+                name: "vault",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "receipt_mint",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "amount",
+                ty: "u64",
+                offset: Some(72),
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(80),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(81),
+    },
+    Layout {
+        module: "bonus_pinocchio_comparison",
+        program: "pinocchio_vault",
+        name: "Vault",
+        kind: Kind::Manual,
+        discriminator: None,
+        fields: &[
+            Field {
+                name: "owner",
+                ty: "Pubkey",
+                offset: Some(0),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "balance",
+                ty: "u64",
+                offset: Some(32),
+                size: Size::Fixed(8),
+            },
+        ],
+        size: Size::Fixed(40),
+    },
+    Layout {
+        module: "shared/secref-registry",
+        program: "secref_registry",
+        name: "Registry",
+        kind: Kind::Anchor,
+        discriminator: Some([47, 174, 110, 246, 184, 182, 252, 218]),
+        fields: &[
+            Field {
+                name: "authority",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "name",
+                ty: "String",
+                offset: Some(40),
+                size: Size::Variable { min: 4 },
+            },
+            Field {
+                name: "entry_count",
+                ty: "u64",
+                offset: None,
+                size: Size::Fixed(8),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: None,
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Variable { min: 53 },
+    },
+    Layout {
+        module: "shared/secref-registry",
+        program: "secref_registry",
+        name: "Entry",
+        kind: Kind::Anchor,
+        discriminator: Some([63, 18, 152, 113, 215, 246, 221, 250]),
+        fields: &[
+            Field {
+                name: "registry",
+                ty: "Pubkey",
+                offset: Some(8),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "key",
+                ty: "Pubkey",
+                offset: Some(40),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "added_by",
+                ty: "Pubkey",
+                offset: Some(72),
+                size: Size::Fixed(32),
+            },
+            Field {
+                name: "bump",
+                ty: "u8",
+                offset: Some(104),
+                size: Size::Fixed(1),
+            },
+        ],
+        size: Size::Fixed(105),
+    },
+];
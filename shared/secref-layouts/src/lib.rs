@@ -0,0 +1,243 @@
+//! Byte layouts of every account type in the repository's programs: offset, size, field and type.
+//!
+//! An account is bytes, and an attacker writes bytes, not Rust values. To
+//! forge an account that passes a missing owner check, or to see why a
+//! type-confused account deserializes at all, a learner needs to know which
+//! bytes hold which field. [`layouts`] lists every `#[account]`,
+//! `#[account(zero_copy)]` and `#[repr(C)]` account struct in the examples,
+//! derived from the struct definitions themselves:
+//!
+//! ```
+//! let layout = secref_layouts::find("reinit_vault", "ReinitVault").unwrap();
+//! assert_eq!(layout.range("owner"), Some(8..40));
+//! assert_eq!(layout.range("is_initialized"), Some(56..57));
+//! ```
+//!
+//! Anchor accounts start with their 8-byte discriminator and serialize
+//! with Borsh, field after field with no padding. A `Vec`, `String` or
+//! `Option` takes as many bytes as its value needs, so the fields after one
+//! have no fixed offset. Zero-copy accounts are plain memory after the
+//! discriminator. `#[repr(C)]` structs a program reads by hand are laid out
+//! as C would, with each field aligned to its own size, up to 8 bytes.
+//!
+//! Hand-packed layouts that are not `#[repr(C)]`, such as the account-type
+//! byte `11_instruction_data_confusion` writes before each field, cannot be
+//! derived from the type and are not listed; their golden fixtures are the
+//! reference. [`Layout::patch`] and [`Layout::sample`] build raw account
+//! data for exploits and for structured fuzz corpora, and [`to_json`] and
+//! [`to_markdown`] export the table for tools outside Rust.
+
+mod generated;
+
+use std::fmt;
+use std::ops::Range;
+
+/// How an account type is turned into bytes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// `#[account]`: discriminator, then Borsh
+    Anchor,
+    /// `#[account(zero_copy)]`: discriminator, then the struct's memory
+    ZeroCopy,
+    /// `#[repr(C)]`: the struct's memory, read and written by the program itself
+    Manual,
+}
+
+/// How many bytes a field takes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Size {
+    Fixed(usize),
+    /// A `Vec`, `String`, `Option` or enum whose variants differ, at least `min` bytes
+    Variable { min: usize },
+}
+
+impl Size {
+    pub fn fixed(self) -> Option<usize> {
+        match self {
+            Size::Fixed(size) => Some(size),
+            Size::Variable { .. } => None,
+        }
+    }
+
+    pub fn min(self) -> usize {
+        match self {
+            Size::Fixed(size) | Size::Variable { min: size } => size,
+        }
+    }
+}
+
+impl fmt::Display for Size {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Size::Fixed(size) => write!(f, "{size}"),
+            Size::Variable { min } => write!(f, "{min}+"),
+        }
+    }
+}
+
+/// One field, nested struct fields flattened to `outer.inner`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Field {
+    pub name: &'static str,
+    /// The type as written in the struct
+    pub ty: &'static str,
+    /// From the start of the account data, discriminator included; `None` after a variable-size field
+    pub offset: Option<usize>,
+    pub size: Size,
+}
+
+/// The bytes of one account type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Layout {
+    /// Example directory (`05_reinitialization_attack`), or the shared crate or bonus directory
+    pub module: &'static str,
+    /// Program crate name, e.g. `reinit_vault`
+    pub program: &'static str,
+    pub name: &'static str,
+    pub kind: Kind,
+    /// The first 8 bytes of an Anchor or zero-copy account
+    pub discriminator: Option<[u8; 8]>,
+    /// In order, padding left out
+    pub fields: &'static [Field],
+    /// Discriminator, fields and padding
+    pub size: Size,
+}
+
+impl Layout {
+    pub fn field(&self, name: &str) -> Option<&'static Field> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// The bytes of `name` in every account of this type, if it has a fixed offset and size
+    pub fn range(&self, name: &str) -> Option<Range<usize>> {
+        let field = self.field(name)?;
+        let offset = field.offset?;
+        Some(offset..offset + field.size.fixed()?)
+    }
+
+    /// Overwrite the field `name` of raw account data, as an attacker crafting the account would
+    ///
+    /// # Panics
+    ///
+    /// If the layout has no such field, the field has no fixed offset and
+    /// size, `bytes` is not that size, or `data` is too short to hold it.
+    pub fn patch(&self, data: &mut [u8], name: &str, bytes: &[u8]) {
+        let range = self
+            .range(name)
+            .unwrap_or_else(|| panic!("{} has no field `{name}` at a fixed offset", self.name));
+        assert_eq!(bytes.len(), range.len(), "`{name}` is {} bytes", range.len());
+        assert!(data.len() >= range.end, "{} bytes of data cannot hold `{name}`", data.len());
+        data[range].copy_from_slice(bytes);
+    }
+
+    /// Account data built field by field: the discriminator, then `fill`'s bytes for each field
+    ///
+    /// A fuzzer seeded with samples starts from well-formed accounts, and
+    /// mutates one field at a time instead of bytes no parser gets past.
+    /// `fill` decides how long a variable-size field is, so a length prefix
+    /// can agree with its elements or not. Padding is zeroed.
+    pub fn sample(&self, mut fill: impl FnMut(&Field) -> Vec<u8>) -> Vec<u8> {
+        let mut data = self.discriminator.map(Vec::from).unwrap_or_default();
+        for field in self.fields {
+            if let Some(offset) = field.offset {
+                data.resize(data.len().max(offset), 0);
+            }
+            data.extend(fill(field));
+        }
+        if let Size::Fixed(size) = self.size {
+            data.resize(data.len().max(size), 0);
+        }
+        data
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}::{} ({:?}, {} bytes)", self.program, self.name, self.kind, self.size)?;
+        writeln!(f)?;
+        writeln!(f, "| Offset | Size | Field | Type |")?;
+        writeln!(f, "|--------|------|-------|------|")?;
+        if let Some(discriminator) = self.discriminator {
+            let hex: Vec<String> = discriminator.iter().map(|byte| format!("{byte:02x}")).collect();
+            writeln!(f, "| 0 | 8 | discriminator | `{}` |", hex.join(" "))?;
+        }
+        for field in self.fields {
+            let offset = field.offset.map_or_else(|| "-".to_string(), |offset| offset.to_string());
+            writeln!(f, "| {offset} | {} | {} | `{}` |", field.size, field.name, field.ty)?;
+        }
+        Ok(())
+    }
+}
+
+/// Every account layout, by module and program
+pub fn layouts() -> &'static [Layout] {
+    generated::LAYOUTS
+}
+
+/// The layout of the account type `name` in `program`
+pub fn find(program: &str, name: &str) -> Option<&'static Layout> {
+    layouts().iter().find(|layout| layout.program == program && layout.name == name)
+}
+
+/// Every layout as Markdown, one section per module
+pub fn to_markdown() -> String {
+    let mut out = String::from(
+        "# Account Layouts\n\n\
+         Byte offsets of every account type, generated by `shared/secref-layouts` from the struct \
+         definitions. Offsets count from the start of the account data, discriminator included; \
+         `-` marks a field after a variable-size one, and `n+` a size of at least `n` bytes.\n",
+    );
+    let mut module = "";
+    for layout in layouts() {
+        if layout.module != module {
+            module = layout.module;
+            out.push_str(&format!("\n## {module}\n"));
+        }
+        out.push_str(&format!("\n### {layout}"));
+    }
+    out
+}
+
+/// Every layout as a JSON array
+pub fn to_json() -> String {
+    let layouts: Vec<String> = layouts().iter().map(layout_json).collect();
+    format!("[\n{}\n]\n", layouts.join(",\n"))
+}
+
+fn layout_json(layout: &Layout) -> String {
+    let fields: Vec<String> = layout
+        .fields
+        .iter()
+        .map(|field| {
+            format!(
+                "{{\"name\":\"{}\",\"type\":\"{}\",\"offset\":{},{}}}",
+                field.name,
+                field.ty,
+                field.offset.map_or_else(|| "null".to_string(), |offset| offset.to_string()),
+                size_json(field.size)
+            )
+        })
+        .collect();
+    let discriminator = layout.discriminator.map_or_else(
+        || "null".to_string(),
+        |bytes| format!("[{}]", bytes.map(|byte| byte.to_string()).join(",")),
+    );
+    format!(
+        "{{\"module\":\"{}\",\"program\":\"{}\",\"name\":\"{}\",\"kind\":\"{:?}\",\"discriminator\":{discriminator},\
+         {},\"fields\":[{}]}}",
+        layout.module,
+        layout.program,
+        layout.name,
+        layout.kind,
+        size_json(layout.size),
+        fields.join(",")
+    )
+}
+
+/// `"size":n` for a fixed size, `"minSize":n` for a variable one
+fn size_json(size: Size) -> String {
+    match size {
+        Size::Fixed(size) => format!("\"size\":{size}"),
+        Size::Variable { min } => format!("\"size\":null,\"minSize\":{min}"),
+    }
+}
@@ -0,0 +1,678 @@
+//! The checked-in files generated from the sources are current.
+//!
+//! `src/generated.rs` lays out every `#[account]`, `#[account(zero_copy)]`
+//! and `#[repr(C)]` struct in the repository's programs, and
+//! `harness/account-layouts.json` and `LAYOUTS.md` are `to_json()` and
+//! `to_markdown()` of it. All three are compared with what the sources give
+//! now, and every layout with a golden fixture is checked against the bytes
+//! the program really writes. Rewrite them after changing an account type
+//! with:
+//!
+//! ```bash
+//! SECREF_BLESS=1 cargo test --manifest-path shared/secref-layouts/Cargo.toml
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anchor_lang::solana_program::hash::hash;
+use secref_layouts::{Kind, Size};
+
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+/// Directories in `dir`, by name
+fn subdirectories(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).filter(|path| path.is_dir()).collect();
+    dirs.sort();
+    dirs
+}
+
+/// Every `.rs` file under `dir` that is program code, by path; test modules are skipped
+fn rust_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for path in subdirectories(dir) {
+        files.extend(rust_files(&path));
+    }
+    if let Ok(entries) = fs::read_dir(dir) {
+        files.extend(entries.flatten().map(|entry| entry.path()).filter(|path| {
+            let name = path.file_name().unwrap().to_string_lossy();
+            name.ends_with(".rs") && !name.ends_with("tests.rs")
+        }));
+    }
+    files.sort();
+    files
+}
+
+/// `(module, program, dir)` for every program that can own accounts
+fn programs() -> Vec<(String, String, PathBuf)> {
+    let root = root();
+    let name = |path: &Path| path.file_name().unwrap().to_string_lossy().into_owned();
+    let mut programs = Vec::new();
+    for example in subdirectories(&root) {
+        let module = name(&example);
+        if !module.starts_with(|c: char| c.is_ascii_digit()) && module != "bonus_pinocchio_comparison" {
+            continue;
+        }
+        for program in subdirectories(&example.join("programs")) {
+            programs.push((module.clone(), name(&program), program));
+        }
+    }
+    for shared in subdirectories(&root.join("shared")) {
+        for program in subdirectories(&shared.join("programs")) {
+            programs.push((format!("shared/{}", name(&shared)), name(&program), program));
+        }
+    }
+    programs
+}
+
+// ========================================
+// SOURCE PARSING
+// ========================================
+
+/// `source` without comments, strings kept
+fn strip_comments(source: &str) -> String {
+    let bytes = source.as_bytes();
+    let mut out = String::with_capacity(source.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = source[i..].find('\n').map_or(bytes.len(), |end| i + end);
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = source[i + 2..].find("*/").map_or(bytes.len(), |end| i + 2 + end + 2);
+            }
+            b'"' => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+                out.push_str(&source[start..i.min(bytes.len())]);
+            }
+            // A character literal, not a lifetime: `'"'` would otherwise open a string
+            b'\'' if bytes.get(i + 2) == Some(&b'\'') || bytes.get(i + 1) == Some(&b'\\') => {
+                let end = source[i + 2..].find('\'').map_or(bytes.len(), |end| i + 2 + end + 1);
+                out.push_str(&source[i..end]);
+                i = end;
+            }
+            _ => {
+                let c = source[i..].chars().next().unwrap();
+                out.push(c);
+                i += c.len_utf8();
+            }
+        }
+    }
+    out
+}
+
+/// Index of the bracket closing the one at `open`
+fn matching(source: &str, open: usize) -> usize {
+    let bytes = source.as_bytes();
+    let mut depth = 0;
+    for (i, &byte) in bytes.iter().enumerate().skip(open) {
+        match byte {
+            b'(' | b'[' | b'{' | b'<' => depth += 1,
+            b')' | b']' | b'}' | b'>' if !(byte == b'>' && bytes[i - 1] == b'-') => {
+                depth -= 1;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+    }
+    panic!("unbalanced `{}` at offset {open}", &source[open..open + 1]);
+}
+
+/// `list` split on commas outside brackets, empty parts dropped
+fn split_top(list: &str, separator: u8) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0i32, 0);
+    for (i, byte) in list.bytes().enumerate() {
+        match byte {
+            b'(' | b'[' | b'{' | b'<' => depth += 1,
+            b')' | b']' | b'}' | b'>' => depth -= 1,
+            byte if byte == separator && depth == 0 => {
+                parts.push(&list[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&list[start..]);
+    parts.into_iter().map(str::trim).filter(|part| !part.is_empty()).collect()
+}
+
+/// `text` without its leading `#[..]` attributes and visibility
+fn strip_attributes(mut text: &str) -> &str {
+    loop {
+        text = text.trim_start();
+        if text.starts_with("#[") {
+            text = &text[matching(text, 1) + 1..];
+        } else if let Some(rest) = text.strip_prefix("pub(") {
+            text = &rest[rest.find(')').unwrap() + 1..];
+        } else if let Some(rest) = text.strip_prefix("pub ") {
+            text = rest;
+        } else {
+            return text;
+        }
+    }
+}
+
+/// `ty` with its whitespace as rustfmt writes it
+fn normalize(ty: &str) -> String {
+    ty.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+enum Body {
+    /// Named fields: name and type
+    Struct(Vec<(String, String)>),
+    /// Each variant's field types
+    Enum(Vec<Vec<String>>),
+}
+
+struct Item {
+    /// Where the item starts in its file, comments removed
+    position: usize,
+    /// The attributes before the item, whitespace removed
+    attributes: String,
+    body: Body,
+}
+
+/// Named-field types of a `{ a: A, b: B }` or `(A, B)` body
+fn field_types(body: &str) -> Vec<(String, String)> {
+    split_top(body, b',')
+        .into_iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let field = strip_attributes(field);
+            match field.split_once(':').filter(|(name, _)| !name.contains(['<', '[', '('])) {
+                Some((name, ty)) if !ty.starts_with(':') => (name.trim().to_string(), normalize(ty)),
+                _ => (index.to_string(), normalize(field)),
+            }
+        })
+        .collect()
+}
+
+/// The structs and enums of one source file, by name, and its `usize` constants
+fn items(source: &str, items: &mut BTreeMap<String, Item>, constants: &mut BTreeMap<String, String>) {
+    let source = strip_comments(source);
+    let mut search = 0;
+    while let Some(found) = source[search..].find(['s', 'e', 'c']) {
+        let at = search + found;
+        search = at + 1;
+        let word_start = at == 0 || !source[..at].ends_with(|c: char| c.is_alphanumeric() || c == '_');
+        let keyword = ["struct ", "enum ", "const "].into_iter().find(|keyword| source[at..].starts_with(keyword));
+        let (Some(keyword), true) = (keyword, word_start) else {
+            continue;
+        };
+        let rest = &source[at + keyword.len()..];
+        let name: String = rest.chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect();
+        let after = rest[name.len()..].trim_start();
+
+        if keyword == "const " {
+            if let Some(value) = after.strip_prefix(": usize =") {
+                constants.insert(name, value[..value.find(';').unwrap()].trim().to_string());
+            }
+            continue;
+        }
+        // Generic items are account contexts and helpers, never account data
+        if !after.starts_with('{') && !after.starts_with('(') {
+            continue;
+        }
+        let open = source.len() - after.len();
+        let close = matching(&source, open);
+        let body = &source[open + 1..close];
+        let item_start = source[..at].rfind([';', '}', '{']).map_or(0, |end| end + 1);
+        let attributes: String = source[item_start..at].split_whitespace().collect();
+
+        let body = if keyword == "struct " {
+            Body::Struct(field_types(body))
+        } else {
+            Body::Enum(
+                split_top(body, b',')
+                    .into_iter()
+                    .map(|variant| {
+                        let variant = strip_attributes(variant);
+                        match variant.find(['{', '(']) {
+                            Some(open) => {
+                                let close = matching(variant, open);
+                                field_types(&variant[open + 1..close]).into_iter().map(|(_, ty)| ty).collect()
+                            }
+                            None => Vec::new(),
+                        }
+                    })
+                    .collect(),
+            )
+        };
+        items.insert(
+            name,
+            Item {
+                position: at,
+                attributes,
+                body,
+            },
+        );
+        search = close;
+    }
+}
+
+// ========================================
+// LAYOUT
+// ========================================
+
+/// The types and constants a program's account fields can name
+struct Scope {
+    items: BTreeMap<String, Item>,
+    constants: BTreeMap<String, String>,
+}
+
+impl Scope {
+    fn of(files: &[PathBuf]) -> Self {
+        let mut scope = Scope {
+            items: BTreeMap::new(),
+            constants: BTreeMap::new(),
+        };
+        for file in files {
+            items(&fs::read_to_string(file).unwrap(), &mut scope.items, &mut scope.constants);
+        }
+        scope
+    }
+}
+
+/// A program's own types first, then the shared crates' it builds on
+struct Types<'a> {
+    scopes: [&'a Scope; 2],
+}
+
+/// A field as `Types::lay_out` places it
+struct Placed {
+    name: String,
+    ty: String,
+    offset: Option<usize>,
+    size: Size,
+}
+
+fn add(a: Size, b: Size) -> Size {
+    match (a, b) {
+        (Size::Fixed(a), Size::Fixed(b)) => Size::Fixed(a + b),
+        (a, b) => Size::Variable { min: a.min() + b.min() },
+    }
+}
+
+fn align_up(offset: usize, align: usize) -> usize {
+    offset.div_ceil(align) * align
+}
+
+impl Types<'_> {
+    fn item(&self, name: &str) -> Option<&Item> {
+        self.scopes.iter().find_map(|scope| scope.items.get(name))
+    }
+
+    /// Value of a constant array length: numbers, constants and `+ - * /` without parentheses
+    fn evaluate(&self, expression: &str) -> usize {
+        let expression = expression.trim().trim_end_matches("as usize").trim();
+        for operator in [b'+', b'-', b'*', b'/'] {
+            let terms = split_top(expression, operator);
+            if terms.len() > 1 {
+                let values = terms.iter().map(|term| self.evaluate(term));
+                return match operator {
+                    b'+' => values.sum(),
+                    b'*' => values.product(),
+                    b'-' => values.reduce(|a, b| a - b).unwrap(),
+                    _ => values.reduce(|a, b| a / b).unwrap(),
+                };
+            }
+        }
+        expression.replace('_', "").parse().unwrap_or_else(|_| {
+            let name = expression.rsplit("::").next().unwrap();
+            let value = self.scopes.iter().find_map(|scope| scope.constants.get(name));
+            self.evaluate(value.unwrap_or_else(|| panic!("no constant `{expression}`")))
+        })
+    }
+
+    /// `Option<T>` as `("Option", "T")`; paths dropped
+    fn split_generic(ty: &str) -> (&str, &str) {
+        let (base, argument) = match ty.find('<') {
+            Some(open) => (&ty[..open], &ty[open + 1..ty.len() - 1]),
+            None => (ty, ""),
+        };
+        (base.rsplit("::").next().unwrap().trim(), argument.trim())
+    }
+
+    fn primitive(name: &str) -> Option<usize> {
+        Some(match name {
+            "bool" | "u8" | "i8" => 1,
+            "u16" | "i16" => 2,
+            "u32" | "i32" | "f32" => 4,
+            "u64" | "i64" | "f64" => 8,
+            "u128" | "i128" => 16,
+            "Pubkey" => 32,
+            _ => return None,
+        })
+    }
+
+    /// Serialized size of `ty`: Borsh, or memory when `memory`
+    fn size(&self, ty: &str, memory: bool) -> Size {
+        if let Some(array) = ty.strip_prefix('[').and_then(|ty| ty.strip_suffix(']')) {
+            let parts = split_top(array, b';');
+            let length = self.evaluate(parts[1]);
+            return match self.size(parts[0], memory) {
+                Size::Fixed(size) => Size::Fixed(size * length),
+                Size::Variable { min } => Size::Variable { min: min * length },
+            };
+        }
+        let (base, argument) = Self::split_generic(ty);
+        if let Some(size) = Self::primitive(base) {
+            return Size::Fixed(size);
+        }
+        match base {
+            "Vec" | "String" => Size::Variable { min: 4 },
+            "Option" => Size::Variable { min: 1 },
+            "Box" => self.size(argument, memory),
+            _ => match self.item(base).map(|item| &item.body) {
+                Some(Body::Struct(fields)) => {
+                    let mut placed = Vec::new();
+                    self.lay_out("", fields, memory, Some(0), &mut placed)
+                }
+                Some(Body::Enum(variants)) => {
+                    let payloads: Vec<Size> = variants
+                        .iter()
+                        .map(|fields| fields.iter().fold(Size::Fixed(0), |total, ty| add(total, self.size(ty, memory))))
+                        .collect();
+                    let min = payloads.iter().map(|size| size.min()).min().unwrap_or(0);
+                    match payloads.iter().all(|size| *size == Size::Fixed(min)) {
+                        true => Size::Fixed(1 + min),
+                        false => Size::Variable { min: 1 + min },
+                    }
+                }
+                None => panic!("no definition of `{ty}`"),
+            },
+        }
+    }
+
+    /// Alignment of `ty` in memory: its own size for integers, up to 8, and 1 for byte arrays and keys
+    fn align(&self, ty: &str) -> usize {
+        if let Some(array) = ty.strip_prefix('[').and_then(|ty| ty.strip_suffix(']')) {
+            return self.align(split_top(array, b';')[0]);
+        }
+        let (base, _) = Self::split_generic(ty);
+        match (base, Self::primitive(base)) {
+            ("Pubkey", _) => 1,
+            (_, Some(size)) => size.min(8),
+            _ => match self.item(base).map(|item| &item.body) {
+                Some(Body::Struct(fields)) => fields.iter().map(|(_, ty)| self.align(ty)).max().unwrap_or(1),
+                _ => 1,
+            },
+        }
+    }
+
+    /// Place `fields` from `cursor` into `placed`, flattening nested structs; the size they take
+    fn lay_out(
+        &self,
+        prefix: &str,
+        fields: &[(String, String)],
+        memory: bool,
+        mut cursor: Option<usize>,
+        placed: &mut Vec<Placed>,
+    ) -> Size {
+        let start = cursor;
+        let mut total = Size::Fixed(0);
+        for (name, ty) in fields {
+            if memory {
+                let padded = align_up(start.unwrap() + total.min(), self.align(ty)) - start.unwrap();
+                total = Size::Fixed(padded);
+                cursor = start.map(|start| start + padded);
+            }
+            let name = format!("{prefix}{name}");
+            let nested = match self.item(Self::split_generic(ty).0).map(|item| &item.body) {
+                Some(Body::Struct(fields)) if !ty.starts_with('[') && Self::split_generic(ty).1.is_empty() => {
+                    Some(fields)
+                }
+                _ => None,
+            };
+            let size = match nested {
+                Some(fields) => self.lay_out(&format!("{name}."), fields, memory, cursor, placed),
+                None => {
+                    let size = self.size(ty, memory);
+                    placed.push(Placed {
+                        name,
+                        ty: ty.clone(),
+                        offset: cursor,
+                        size,
+                    });
+                    size
+                }
+            };
+            total = add(total, size);
+            cursor = cursor.zip(size.fixed()).map(|(cursor, size)| cursor + size);
+        }
+        match (memory, total) {
+            (true, Size::Fixed(size)) => {
+                let align = fields.iter().map(|(_, ty)| self.align(ty)).max().unwrap_or(1);
+                Size::Fixed(align_up(size, align))
+            }
+            (_, total) => total,
+        }
+    }
+}
+
+struct Generated {
+    module: String,
+    program: String,
+    dir: PathBuf,
+    name: String,
+    kind: Kind,
+    discriminator: Option<[u8; 8]>,
+    fields: Vec<Placed>,
+    size: Size,
+}
+
+/// The layout of every account type, in module, program and source order
+fn generate() -> Vec<Generated> {
+    let root = root();
+    let shared_files: Vec<PathBuf> =
+        subdirectories(&root.join("shared")).iter().flat_map(|dir| rust_files(&dir.join("src"))).collect();
+    let shared = Scope::of(&shared_files);
+
+    let mut layouts = Vec::new();
+    for (module, program, dir) in programs() {
+        let files = rust_files(&dir.join("src"));
+        let own = Scope::of(&files);
+        let types = Types { scopes: [&own, &shared] };
+
+        for file in &files {
+            let mut found = BTreeMap::new();
+            items(&fs::read_to_string(file).unwrap(), &mut found, &mut BTreeMap::new());
+            let mut found: Vec<(String, Item)> = found.into_iter().collect();
+            found.sort_by_key(|(_, item)| item.position);
+            layouts.extend(
+                found
+                    .into_iter()
+                    .filter_map(|(name, item)| layout(&module, &program, &dir, &types, name, item)),
+            );
+        }
+    }
+    layouts
+}
+
+/// The layout of `item` if it is an account type
+fn layout(module: &str, program: &str, dir: &Path, types: &Types, name: String, item: Item) -> Option<Generated> {
+    let kind = if item.attributes.contains("#[account]") {
+        Kind::Anchor
+    } else if item.attributes.contains("#[account(zero_copy") {
+        Kind::ZeroCopy
+    } else if item.attributes.contains("#[repr(C") {
+        Kind::Manual
+    } else {
+        return None;
+    };
+    let Body::Struct(fields) = item.body else {
+        return None;
+    };
+    let discriminator =
+        (kind != Kind::Manual).then(|| hash(format!("account:{name}").as_bytes()).to_bytes()[..8].try_into().unwrap());
+    let header = if kind == Kind::Manual { 0 } else { 8 };
+    let mut placed = Vec::new();
+    let size = types.lay_out("", &fields, kind != Kind::Anchor, Some(header), &mut placed);
+    Some(Generated {
+        module: module.to_string(),
+        program: program.to_string(),
+        dir: dir.to_path_buf(),
+        name,
+        kind,
+        discriminator,
+        fields: placed,
+        size: add(Size::Fixed(header), size),
+    })
+}
+
+/// `src/generated.rs` as the sources give it now
+fn generated_table(layouts: &[Generated]) -> String {
+    let mut table = String::from(
+        "//! Every account layout in the repository, by module and program.\n\
+         //!\n\
+         //! Generated by `tests/generated.rs` from the struct definitions;\n\
+         //! rewrite it after changing an account type with\n\
+         //! `SECREF_BLESS=1 cargo test --manifest-path shared/secref-layouts/Cargo.toml`.\n\
+         \n\
+         use crate::{Field, Kind, Layout, Size};\n\
+         \n\
+         pub(crate) static LAYOUTS: &[Layout] = &[\n",
+    );
+    for layout in layouts {
+        table.push_str(&format!(
+            "    Layout {{\n        module: \"{}\",\n        program: \"{}\",\n        name: \"{}\",\n        \
+             kind: Kind::{:?},\n        discriminator: {:?},\n        fields: &[\n",
+            layout.module, layout.program, layout.name, layout.kind, layout.discriminator
+        ));
+        for field in &layout.fields {
+            table.push_str(&format!(
+                "            Field {{\n                name: \"{}\",\n                ty: \"{}\",\n                \
+                 offset: {:?},\n                size: Size::{:?},\n            }},\n",
+                field.name, field.ty, field.offset, field.size
+            ));
+        }
+        table.push_str(&format!("        ],\n        size: Size::{:?},\n    }},\n", layout.size));
+    }
+    table.push_str("];\n");
+    table
+}
+
+/// Compare `path` with `expected`, or rewrite it under `SECREF_BLESS`
+fn check(path: &Path, expected: &str) {
+    if std::env::var_os("SECREF_BLESS").is_some() {
+        fs::write(path, expected).unwrap();
+        return;
+    }
+    let actual = fs::read_to_string(path).unwrap_or_default();
+    assert!(
+        actual == expected,
+        "{} is stale; rewrite it with SECREF_BLESS=1 cargo test --manifest-path shared/secref-layouts/Cargo.toml",
+        path.display()
+    );
+}
+
+#[test]
+fn generated_table_lays_out_every_account_type() {
+    check(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src/generated.rs"), &generated_table(&generate()));
+}
+
+#[test]
+fn exports_are_current() {
+    check(&root().join("harness/account-layouts.json"), &secref_layouts::to_json());
+    check(&Path::new(env!("CARGO_MANIFEST_DIR")).join("LAYOUTS.md"), &secref_layouts::to_markdown());
+}
+
+/// The programs' golden fixtures are the bytes their serializers really write
+#[test]
+fn layouts_match_the_golden_fixtures() {
+    let mut checked = 0;
+    for layout in generate() {
+        let path = layout.dir.join("golden").join(format!("{}.hex", layout.name));
+        let Ok(fixture) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let bytes: Vec<u8> = fixture
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .flat_map(|line| line.split_whitespace().skip(1))
+            .map(|byte| u8::from_str_radix(byte, 16).unwrap())
+            .collect();
+        match layout.size {
+            Size::Fixed(size) => assert_eq!(bytes.len(), size, "{}", path.display()),
+            Size::Variable { min } => assert!(bytes.len() >= min, "{}", path.display()),
+        }
+        if let Some(discriminator) = layout.discriminator {
+            assert_eq!(bytes[..8], discriminator, "{}", path.display());
+        }
+        checked += 1;
+    }
+    assert!(checked > 100, "only {checked} layouts have golden fixtures");
+}
+
+#[test]
+fn fields_are_parsed_past_attributes_comments_and_nesting() {
+    let source = r#"
+        pub const SLOTS: usize = 2 * 3;
+
+        /// A pair
+        #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+        pub struct Inner {
+            pub a: u16, // trailing
+            pub b: [u8; SLOTS + 1],
+        }
+
+        #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+        pub enum Shape { Dot, Line { from: u64, to: u64 } }
+
+        #[account]
+        #[derive(InitSpace)]
+        pub struct Outer {
+            /// Who owns it (32 bytes)
+            pub owner: anchor_lang::prelude::Pubkey,
+            #[max_len(8)]
+            pub(crate) name: String,
+            inner: Inner,
+            pub shape: Shape,
+        }
+    "#;
+    let mut scope = Scope {
+        items: BTreeMap::new(),
+        constants: BTreeMap::new(),
+    };
+    items(source, &mut scope.items, &mut scope.constants);
+    let empty = Scope {
+        items: BTreeMap::new(),
+        constants: BTreeMap::new(),
+    };
+    assert!(scope.items["Outer"].attributes.contains("#[account]"));
+
+    let types = Types { scopes: [&scope, &empty] };
+    let Body::Struct(fields) = &scope.items["Outer"].body else {
+        panic!("Outer is a struct");
+    };
+    let mut placed = Vec::new();
+    let size = types.lay_out("", fields, false, Some(8), &mut placed);
+    let found: Vec<(&str, Option<usize>, Size)> =
+        placed.iter().map(|field| (&field.name[..], field.offset, field.size)).collect();
+    assert_eq!(
+        found,
+        [
+            ("owner", Some(8), Size::Fixed(32)),
+            ("name", Some(40), Size::Variable { min: 4 }),
+            ("inner.a", None, Size::Fixed(2)),
+            ("inner.b", None, Size::Fixed(7)),
+            ("shape", None, Size::Variable { min: 1 }),
+        ]
+    );
+    assert_eq!(size, Size::Variable { min: 32 + 4 + 2 + 7 + 1 });
+}
@@ -0,0 +1,93 @@
+//! Layouts read, patch and build the bytes programs really (de)serialize
+
+use anchor_lang::prelude::*;
+use anchor_lang::{AccountDeserialize, AccountSerialize};
+use payment_splitter::{Recipient, Splitter};
+use reinit_vault::ReinitVault;
+use secref_layouts::{find, layouts, Kind, Size};
+
+fn serialize<T: AccountSerialize>(account: &T) -> Vec<u8> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data
+}
+
+fn vault() -> ReinitVault {
+    ReinitVault {
+        owner: Pubkey::new_unique(),
+        balance: 0x2122232425262728,
+        total_deposits: 0x3132333435363738,
+        is_initialized: true,
+    }
+}
+
+#[test]
+fn ranges_hold_the_fields_anchor_serializes() {
+    let layout = find("reinit_vault", "ReinitVault").unwrap();
+    let vault = vault();
+    let data = serialize(&vault);
+
+    assert_eq!(layout.kind, Kind::Anchor);
+    assert_eq!(layout.size, Size::Fixed(data.len()));
+    assert_eq!(data[..8], layout.discriminator.unwrap());
+    assert_eq!(data[layout.range("owner").unwrap()], vault.owner.to_bytes());
+    assert_eq!(data[layout.range("balance").unwrap()], vault.balance.to_le_bytes());
+    assert_eq!(data[layout.range("total_deposits").unwrap()], vault.total_deposits.to_le_bytes());
+    assert_eq!(data[layout.range("is_initialized").unwrap()], [1]);
+}
+
+#[test]
+fn a_patched_owner_deserializes_as_the_attackers_vault() {
+    let layout = find("reinit_vault", "ReinitVault").unwrap();
+    let attacker = Pubkey::new_unique();
+    let mut data = serialize(&vault());
+
+    layout.patch(&mut data, "owner", attacker.as_ref());
+    let forged = ReinitVault::try_deserialize(&mut &data[..]).unwrap();
+    assert_eq!(forged.owner, attacker);
+    assert_eq!(forged.balance, vault().balance);
+}
+
+#[test]
+#[should_panic(expected = "no field `bump` at a fixed offset")]
+fn fields_after_a_vec_cannot_be_patched() {
+    let layout = find("payment_splitter", "Splitter").unwrap();
+    assert_eq!(layout.range("recipients"), None);
+    assert_eq!(layout.field("bump").unwrap().offset, None);
+    layout.patch(&mut [0; 64], "bump", &[255]);
+}
+
+#[test]
+fn samples_are_accounts_the_program_deserializes() {
+    let layout = find("payment_splitter", "Splitter").unwrap();
+    let recipient = Recipient {
+        address: Pubkey::new_unique(),
+        shares: 7,
+    };
+    let data = layout.sample(|field| match field.name {
+        "recipients" => [1u32.to_le_bytes().to_vec(), recipient.try_to_vec().unwrap()].concat(),
+        _ => vec![3; field.size.min()],
+    });
+
+    let splitter = Splitter::try_deserialize(&mut &data[..]).unwrap();
+    assert_eq!(splitter.recipients, [recipient]);
+    assert_eq!(splitter.total_shares, 0x03030303);
+    assert_eq!(splitter.bump, 3);
+    assert_eq!(data.len(), Splitter::space(1));
+}
+
+#[test]
+fn every_layout_places_fields_in_order_within_its_size() {
+    for layout in layouts() {
+        let mut end = if layout.kind == Kind::Manual { 0 } else { 8 };
+        for field in layout.fields {
+            let Some(offset) = field.offset else {
+                break;
+            };
+            assert!(offset >= end, "{layout}");
+            end = offset + field.size.min();
+        }
+        assert!(layout.size.min() >= end, "{layout}");
+        assert_eq!(find(layout.program, layout.name), Some(layout));
+    }
+}
@@ -0,0 +1,504 @@
+//! Macros for the security reference examples.
+//!
+//! Anchor's `#[program]` dispatches to every handler in its module, and
+//! ignores a `#[cfg]` on one of them: the handler is compiled out but the
+//! dispatch still calls it. [`macro@feature_gated`] goes above `#[program]`
+//! and writes the module once per combination of the calling crate's
+//! `vulnerable` and `secure` features, each copy holding only the handlers
+//! its features select, so Anchor only ever sees handlers that exist.
+//!
+//! Beside the module it writes `SECREF_XREF`, the marked handlers with the
+//! file and lines of each, for `secref_xref` to link findings to code.
+//!
+//! [`macro@StateMachine`] derives `secref_guards::state_machine::StateMachine`
+//! for a status enum from the transitions listed on it.
+
+use std::collections::HashSet;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, Ident, Item, ItemFn, ItemMod, Token};
+
+/// Compile items of a `#[program]` module marked `#[vulnerable]` or `#[secure]` only with that feature
+///
+/// ```ignore
+/// #[feature_gated]
+/// #[program]
+/// pub mod vault {
+///     use super::*;
+///
+///     pub fn initialize(ctx: Context<Initialize>) -> Result<()> { ... }
+///
+///     #[vulnerable]
+///     pub fn vulnerable_withdraw(ctx: Context<VulnerableWithdraw>, amount: u64) -> Result<()> { ... }
+///
+///     #[secure]
+///     pub fn secure_withdraw(ctx: Context<SecureWithdraw>, amount: u64) -> Result<()> { ... }
+/// }
+/// ```
+///
+/// Unmarked items are in every build. A marker goes on any item, so an
+/// import only one side's handlers use is marked with them. Building with
+/// neither feature is a `compile_error!`: the program would have nothing
+/// to teach.
+///
+/// Every build also gets `pub const SECREF_XREF: secref_prelude::xref::ProgramXref`
+/// listing the marked handlers, whichever of them the features compile in.
+#[proc_macro_attribute]
+pub fn feature_gated(args: TokenStream, input: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        let args = TokenStream2::from(args);
+        return syn::Error::new_spanned(args, "`feature_gated` takes no arguments")
+            .to_compile_error()
+            .into();
+    }
+    let module = parse_macro_input!(input as ItemMod);
+    expand(module).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Which builds an item of the module is compiled into
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Gate {
+    Always,
+    Vulnerable,
+    Secure,
+}
+
+impl Gate {
+    fn included(self, vulnerable: bool, secure: bool) -> bool {
+        match self {
+            Gate::Always => true,
+            Gate::Vulnerable => vulnerable,
+            Gate::Secure => secure,
+        }
+    }
+}
+
+/// The module once per feature combination that builds a program, each behind its `#[cfg]`
+fn expand(module: ItemMod) -> syn::Result<TokenStream2> {
+    let Some((_, items)) = &module.content else {
+        return Err(syn::Error::new_spanned(
+            &module,
+            "`feature_gated` needs the module's items inline",
+        ));
+    };
+    let gated = items
+        .iter()
+        .cloned()
+        .map(take_gate)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let mut builds = Vec::new();
+    for (vulnerable, secure, cfg) in [
+        (true, true, quote!(all(feature = "vulnerable", feature = "secure"))),
+        (true, false, quote!(all(feature = "vulnerable", not(feature = "secure")))),
+        (false, true, quote!(all(not(feature = "vulnerable"), feature = "secure"))),
+    ] {
+        let mut build = module.clone();
+        let (_, items) = build.content.as_mut().expect("checked above");
+        *items = gated
+            .iter()
+            .filter(|(gate, _)| gate.included(vulnerable, secure))
+            .map(|(_, item)| item.clone())
+            .collect();
+        builds.push(quote! {
+            #[cfg(#cfg)]
+            #build
+        });
+    }
+    let xref = xref(&gated);
+    Ok(quote! {
+        #(#builds)*
+
+        #xref
+
+        #[cfg(not(any(feature = "vulnerable", feature = "secure")))]
+        compile_error!("enable the `vulnerable` feature, the `secure` feature, or both (the default)");
+    })
+}
+
+/// `SECREF_XREF`: each marked handler's name, side, file and lines
+///
+/// `file!()` and `line!()` are spanned to the handler's own tokens, so the
+/// compiler fills in where the handler is written, not where the macro is.
+fn xref(gated: &[(Gate, Item)]) -> TokenStream2 {
+    let handlers = gated.iter().filter_map(|(gate, item)| {
+        let side = match gate {
+            Gate::Always => return None,
+            Gate::Vulnerable => quote!(Vulnerable),
+            Gate::Secure => quote!(Secure),
+        };
+        let Item::Fn(ItemFn { sig, block, .. }) = item else {
+            return None;
+        };
+        let name = sig.ident.to_string();
+        let start = sig.fn_token.span;
+        let end = block.brace_token.span.close();
+        let file = quote_spanned!(start=> file!());
+        let start_line = quote_spanned!(start=> line!());
+        let end_line = quote_spanned!(end=> line!());
+        Some(quote! {
+            ::secref_prelude::xref::Handler {
+                name: #name,
+                side: ::secref_prelude::xref::Side::#side,
+                file: #file,
+                start_line: #start_line,
+                end_line: #end_line,
+            }
+        })
+    });
+    quote! {
+        /// The program's `#[vulnerable]` and `#[secure]` handlers and where they are written
+        pub const SECREF_XREF: ::secref_prelude::xref::ProgramXref = ::secref_prelude::xref::ProgramXref {
+            package: env!("CARGO_PKG_NAME"),
+            manifest_dir: env!("CARGO_MANIFEST_DIR"),
+            handlers: &[#(#handlers),*],
+        };
+    }
+}
+
+/// The item's gate, with its marker removed
+fn take_gate(mut item: Item) -> syn::Result<(Gate, Item)> {
+    let Some(attrs) = attributes(&mut item) else {
+        return Ok((Gate::Always, item));
+    };
+    let mut gate = Gate::Always;
+    for attr in attrs.iter() {
+        let found = match marker(attr) {
+            Some(found) => found,
+            None => continue,
+        };
+        if !matches!(attr.meta, syn::Meta::Path(_)) {
+            return Err(syn::Error::new_spanned(attr, "`#[vulnerable]` and `#[secure]` take no arguments"));
+        }
+        if gate != Gate::Always {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "an item is either `#[vulnerable]` or `#[secure]`; leave it unmarked to build it into both",
+            ));
+        }
+        gate = found;
+    }
+    attrs.retain(|attr| marker(attr).is_none());
+    Ok((gate, item))
+}
+
+fn marker(attr: &Attribute) -> Option<Gate> {
+    let path = attr.path();
+    if path.is_ident("vulnerable") {
+        Some(Gate::Vulnerable)
+    } else if path.is_ident("secure") {
+        Some(Gate::Secure)
+    } else {
+        None
+    }
+}
+
+fn attributes(item: &mut Item) -> Option<&mut Vec<Attribute>> {
+    Some(match item {
+        Item::Const(item) => &mut item.attrs,
+        Item::Enum(item) => &mut item.attrs,
+        Item::Fn(item) => &mut item.attrs,
+        Item::Mod(item) => &mut item.attrs,
+        Item::Static(item) => &mut item.attrs,
+        Item::Struct(item) => &mut item.attrs,
+        Item::Type(item) => &mut item.attrs,
+        Item::Use(item) => &mut item.attrs,
+        _ => return None,
+    })
+}
+
+/// Implement `secref_guards::state_machine::StateMachine` from the transitions listed on the enum
+///
+/// ```ignore
+/// use secref_guards::state_machine::StateMachine; // the trait and this derive
+///
+/// #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, StateMachine)]
+/// #[transition(Open -> Filled, Open -> Cancelled)]
+/// pub enum OrderStatus {
+///     Open,
+///     Filled,
+///     Cancelled,
+/// }
+/// ```
+///
+/// `can_transition` allows exactly the listed pairs; every other move
+/// fails `transition` with `GuardError::InvalidStateTransition`. A
+/// `#[transition(...)]` may be repeated, and a state listed on no left-hand
+/// side is terminal. Naming a state the enum does not have, or listing the
+/// same transition twice, is a compile error, as is a variant with fields.
+#[proc_macro_derive(StateMachine, attributes(transition))]
+pub fn derive_state_machine(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    state_machine(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// One `From -> To` of a `#[transition(...)]` list
+struct Transition {
+    from: Ident,
+    to: Ident,
+}
+
+impl syn::parse::Parse for Transition {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let from = input.parse()?;
+        input.parse::<Token![->]>()?;
+        let to = input.parse()?;
+        Ok(Transition { from, to })
+    }
+}
+
+/// The `StateMachine` impl, with every listed state checked against the enum's variants
+fn state_machine(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(&input.ident, "`StateMachine` is derived for status enums"));
+    };
+    let mut states = HashSet::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(variant, "a state machine's states carry no fields"));
+        }
+        states.insert(variant.ident.to_string());
+    }
+
+    let mut transitions: Vec<Transition> = Vec::new();
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("transition")) {
+        let listed = attr.parse_args_with(Punctuated::<Transition, Token![,]>::parse_terminated)?;
+        for transition in listed {
+            for state in [&transition.from, &transition.to] {
+                if !states.contains(&state.to_string()) {
+                    return Err(syn::Error::new_spanned(
+                        state,
+                        format!("`{}` has no state `{state}`", input.ident),
+                    ));
+                }
+            }
+            if transitions.iter().any(|seen| seen.from == transition.from && seen.to == transition.to) {
+                return Err(syn::Error::new(
+                    transition.from.span(),
+                    format!("`{} -> {}` is listed twice", transition.from, transition.to),
+                ));
+            }
+            transitions.push(transition);
+        }
+    }
+    if transitions.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "list the allowed transitions with `#[transition(From -> To, ...)]`",
+        ));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let pairs = transitions.iter().map(|Transition { from, to }| quote!((Self::#from, Self::#to)));
+    Ok(quote! {
+        impl #impl_generics ::secref_guards::state_machine::StateMachine for #name #ty_generics #where_clause {
+            fn can_transition(self, to: Self) -> bool {
+                matches!((self, to), #(#pairs)|*)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::{parse_quote, File};
+
+    /// The module copies `expand` writes, by the `#[cfg]` each is under, with their function names
+    fn builds(module: ItemMod) -> Vec<(String, Vec<String>)> {
+        let file: File = syn::parse2(expand(module).unwrap()).unwrap();
+        file.items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Mod(module) => Some(module),
+                _ => None,
+            })
+            .map(|module| {
+                let cfg = module.attrs[0].meta.require_list().unwrap().tokens.to_string();
+                let (_, items) = module.content.as_ref().unwrap();
+                let names = items
+                    .iter()
+                    .filter_map(|item| match item {
+                        Item::Fn(function) => Some(function.sig.ident.to_string()),
+                        _ => None,
+                    })
+                    .collect();
+                (cfg, names)
+            })
+            .collect()
+    }
+
+    fn vault() -> ItemMod {
+        parse_quote! {
+            #[program]
+            pub mod vault {
+                use super::*;
+
+                pub fn initialize(ctx: Context<Initialize>) -> Result<()> { Ok(()) }
+
+                #[vulnerable]
+                pub fn vulnerable_withdraw(ctx: Context<VulnerableWithdraw>) -> Result<()> { Ok(()) }
+
+                #[secure]
+                pub fn secure_withdraw(ctx: Context<SecureWithdraw>) -> Result<()> { Ok(()) }
+            }
+        }
+    }
+
+    #[test]
+    fn each_build_holds_the_handlers_its_features_select() {
+        assert_eq!(
+            builds(vault()),
+            [
+                (
+                    "all (feature = \"vulnerable\" , feature = \"secure\")".to_string(),
+                    vec!["initialize".to_string(), "vulnerable_withdraw".into(), "secure_withdraw".into()],
+                ),
+                (
+                    "all (feature = \"vulnerable\" , not (feature = \"secure\"))".into(),
+                    vec!["initialize".into(), "vulnerable_withdraw".into()],
+                ),
+                (
+                    "all (not (feature = \"vulnerable\") , feature = \"secure\")".into(),
+                    vec!["initialize".into(), "secure_withdraw".into()],
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn markers_are_removed_and_other_attributes_kept() {
+        let file: File = syn::parse2(expand(vault()).unwrap()).unwrap();
+        let Item::Mod(module) = &file.items[0] else {
+            panic!("the first build is a module")
+        };
+        // The `#[cfg]` comes first, so a build is removed before `#[program]` expands
+        assert!(module.attrs[0].path().is_ident("cfg"));
+        assert!(module.attrs[1].path().is_ident("program"));
+        let (_, items) = module.content.as_ref().unwrap();
+        for item in items {
+            if let Item::Fn(function) = item {
+                assert!(function.attrs.is_empty(), "{} kept its marker", function.sig.ident);
+            }
+        }
+    }
+
+    #[test]
+    fn a_build_without_either_feature_fails() {
+        let output = expand(vault()).unwrap().to_string();
+        assert!(output.contains("not (any (feature = \"vulnerable\" , feature = \"secure\"))"));
+        assert!(output.contains("compile_error"));
+    }
+
+    #[test]
+    fn xref_lists_marked_handlers_in_every_build() {
+        let file: File = syn::parse2(expand(vault()).unwrap()).unwrap();
+        let xref = file
+            .items
+            .iter()
+            .find_map(|item| match item {
+                Item::Const(constant) if constant.ident == "SECREF_XREF" => Some(constant),
+                _ => None,
+            })
+            .expect("SECREF_XREF is written beside the builds");
+        // Not under a `#[cfg]`: tools read it whichever features the program was built with
+        assert!(xref.attrs.iter().all(|attr| !attr.path().is_ident("cfg")));
+
+        let table = quote!(#xref).to_string();
+        let side = ", side : :: secref_prelude :: xref :: Side ::";
+        assert!(table.contains(&format!("name : \"vulnerable_withdraw\" {side} Vulnerable")));
+        assert!(table.contains(&format!("name : \"secure_withdraw\" {side} Secure")));
+        assert!(!table.contains("initialize"), "unmarked handlers are not findings");
+        assert!(table.contains("file ! ()") && table.contains("line ! ()"));
+    }
+
+    #[test]
+    fn rejects_items_marked_twice_or_with_arguments() {
+        let twice: ItemMod = parse_quote! {
+            pub mod vault {
+                #[vulnerable]
+                #[secure]
+                pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> { Ok(()) }
+            }
+        };
+        assert!(expand(twice).is_err());
+
+        let arguments: ItemMod = parse_quote! {
+            pub mod vault {
+                #[secure(always)]
+                pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> { Ok(()) }
+            }
+        };
+        assert!(expand(arguments).is_err());
+
+        let outline: ItemMod = parse_quote!(pub mod vault;);
+        assert!(expand(outline).is_err());
+    }
+
+    fn order_status() -> DeriveInput {
+        parse_quote! {
+            #[derive(Clone, Copy, PartialEq, StateMachine)]
+            #[transition(Open -> Filled, Open -> Cancelled)]
+            pub enum OrderStatus {
+                Open,
+                Filled,
+                Cancelled,
+            }
+        }
+    }
+
+    #[test]
+    fn state_machine_allows_exactly_the_listed_transitions() {
+        let output = state_machine(order_status()).unwrap().to_string();
+        assert!(output.contains("impl :: secref_guards :: state_machine :: StateMachine for OrderStatus"));
+        assert!(output.contains(
+            "matches ! ((self , to) , (Self :: Open , Self :: Filled) | (Self :: Open , Self :: Cancelled))"
+        ));
+    }
+
+    #[test]
+    fn repeated_transition_attributes_are_one_list() {
+        let mut input = order_status();
+        input.attrs[1] = parse_quote!(#[transition(Open -> Filled)]);
+        input.attrs.push(parse_quote!(#[transition(Open -> Cancelled,)]));
+        assert_eq!(
+            state_machine(input).unwrap().to_string(),
+            state_machine(order_status()).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn state_machine_rejects_unknown_states_and_duplicates() {
+        let mut unknown = order_status();
+        unknown.attrs[1] = parse_quote!(#[transition(Cancelled -> Reopened)]);
+        let error = state_machine(unknown).err().unwrap().to_string();
+        assert_eq!(error, "`OrderStatus` has no state `Reopened`");
+
+        let mut twice = order_status();
+        twice.attrs[1] = parse_quote!(#[transition(Open -> Filled, Open -> Filled)]);
+        assert_eq!(state_machine(twice).err().unwrap().to_string(), "`Open -> Filled` is listed twice");
+
+        let mut unlisted = order_status();
+        unlisted.attrs.truncate(1);
+        assert!(state_machine(unlisted).is_err());
+    }
+
+    #[test]
+    fn state_machine_needs_an_enum_of_unit_states() {
+        let fields: DeriveInput = parse_quote! {
+            #[transition(Open -> Closed)]
+            enum Status { Open { since: i64 }, Closed }
+        };
+        assert!(state_machine(fields).is_err());
+
+        let not_an_enum: DeriveInput = parse_quote! {
+            #[transition(Open -> Closed)]
+            struct Status { open: bool }
+        };
+        assert!(state_machine(not_an_enum).is_err());
+    }
+}
@@ -0,0 +1,277 @@
+//! Each example's attack as data, beside the code it attacks.
+//!
+//! A program crate exports `attack_narrative()`, returning a [`Narrative`]:
+//! who takes part, the accounts that exist up front, and every step an
+//! actor takes, with the instruction it sends and the on-chain effects it
+//! must have. The narrative is plain `'static` data written in the
+//! program's own `narrative.rs`, so it is checked by the compiler and
+//! moves with the code. `secref-scenarios` prints it as `describe` and
+//! turns it into a scenario it runs against the program, so the story told
+//! and the exploit run are the same data:
+//!
+//! ```ignore
+//! let narrative = vault::attack_narrative();
+//! println!("{narrative}");
+//! for step in narrative.steps {
+//!     println!("{} calls {}", step.actor, step.instruction);
+//! }
+//! ```
+
+use std::fmt;
+
+/// One example's attack: the story `describe` tells and the script the scenario engine runs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Narrative {
+    pub title: &'static str,
+    /// The program the steps call, by crate name
+    pub program: &'static str,
+    /// What goes wrong, in a sentence or two
+    pub summary: &'static str,
+    pub actors: &'static [Actor],
+    /// Accounts with an address before the first step, by name
+    pub accounts: &'static [(&'static str, Account)],
+    pub steps: &'static [Step],
+}
+
+impl Narrative {
+    /// The actor called `name`
+    pub fn actor(&self, name: &str) -> Option<&'static Actor> {
+        self.actors.iter().find(|actor| actor.name == name)
+    }
+
+    /// The steps whose instruction is expected to fail
+    pub fn refusals(&self) -> impl Iterator<Item = &'static Step> {
+        self.steps.iter().filter(|step| matches!(step.expect, Expect::Fails(_)))
+    }
+}
+
+/// A funded wallet that can sign
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Actor {
+    pub name: &'static str,
+    pub lamports: u64,
+    /// Who they are in the story, e.g. `the attacker`
+    pub role: &'static str,
+}
+
+/// An account that exists, or has an address, before the first step
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Account {
+    /// A fresh keypair: empty and unfunded, but able to sign
+    Keypair,
+    /// An address of the narrative's program, from these seeds
+    Pda(&'static [Value]),
+    /// This many zeroed, rent-exempt bytes owned by the narrative's program
+    Zeroed(usize),
+    /// An address holding nothing and able to sign nothing
+    Empty,
+}
+
+/// One thing an actor does: a single instruction, sent as its own transaction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Step {
+    /// The actor taking the step, by name
+    pub actor: &'static str,
+    /// What they do, completing `<actor> ...`
+    pub action: &'static str,
+    /// The Anchor instruction name
+    pub instruction: &'static str,
+    pub accounts: &'static [Meta],
+    pub args: &'static [Value],
+    pub expect: Expect,
+    /// What must hold on-chain afterwards
+    pub effects: &'static [Effect],
+}
+
+/// An instruction's account, by name, with the flags the client sends
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Meta {
+    Read(&'static str),
+    Mut(&'static str),
+    Signer(&'static str),
+    SignerMut(&'static str),
+}
+
+impl Meta {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Meta::Read(name) | Meta::Mut(name) | Meta::Signer(name) | Meta::SignerMut(name) => name,
+        }
+    }
+}
+
+/// The outcome a step must have
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Expect {
+    #[default]
+    Ok,
+    /// An Anchor error name, `ConstraintHasOne` or the program's own
+    Fails(&'static str),
+}
+
+/// State that must hold after a step
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Effect {
+    Lamports(&'static str, u64),
+    /// The bytes at `offset` past the 8-byte Anchor discriminator encode `value`
+    Field {
+        account: &'static str,
+        /// The field's name, for the story; `offset` locates it
+        field: &'static str,
+        offset: usize,
+        value: Value,
+    },
+    /// No lamports and no data left
+    Closed(&'static str),
+}
+
+/// An argument or seed, Borsh-encoded as the program reads it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Value {
+    Bool(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    I64(i64),
+    /// A named account's address
+    Key(&'static str),
+    /// The bump of a named `Pda` account
+    Bump(&'static str),
+    /// Length-prefixed UTF-8; the bytes alone as a seed
+    Str(&'static str),
+}
+
+impl fmt::Display for Narrative {
+    /// The story as `describe` prints it: title, actors, then each step and what it must do
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} ({})", self.title, self.program)?;
+        writeln!(f, "{}", self.summary)?;
+        writeln!(f, "\nActors:")?;
+        for actor in self.actors {
+            writeln!(f, "  {}, {}: {} lamports", actor.name, actor.role, actor.lamports)?;
+        }
+        writeln!(f, "\nSteps:")?;
+        for (index, step) in self.steps.iter().enumerate() {
+            writeln!(f, "  {}. {} {}", index + 1, step.actor, step.action)?;
+            let args: Vec<String> = step.args.iter().map(Value::to_string).collect();
+            write!(f, "     {}({})", step.instruction, args.join(", "))?;
+            match step.expect {
+                Expect::Ok => writeln!(f)?,
+                Expect::Fails(error) => writeln!(f, " fails with {error}")?,
+            }
+            for effect in step.effects {
+                writeln!(f, "     then {effect}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Effect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Effect::Lamports(account, lamports) => write!(f, "{account} holds {lamports} lamports"),
+            Effect::Field {
+                account, field, value, ..
+            } => write!(f, "{account}.{field} = {value}"),
+            Effect::Closed(account) => write!(f, "{account} is closed"),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Bool(value) => write!(f, "{value}"),
+            Value::U8(value) => write!(f, "{value}"),
+            Value::U16(value) => write!(f, "{value}"),
+            Value::U32(value) => write!(f, "{value}"),
+            Value::U64(value) => write!(f, "{value}"),
+            Value::I64(value) => write!(f, "{value}"),
+            Value::Key(name) => write!(f, "{name}"),
+            Value::Bump(name) => write!(f, "bump of {name}"),
+            Value::Str(value) => write!(f, "{value:?}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NARRATIVE: Narrative = Narrative {
+        title: "Withdrawing from someone else's vault",
+        program: "vault",
+        summary: "The withdrawal never compares the signer with the vault's owner.",
+        actors: &[
+            Actor {
+                name: "alice",
+                lamports: 10_000_000_000,
+                role: "a depositor",
+            },
+            Actor {
+                name: "mallory",
+                lamports: 1_000_000_000,
+                role: "the attacker",
+            },
+        ],
+        accounts: &[("alices_vault", Account::Zeroed(48))],
+        steps: &[
+            Step {
+                actor: "mallory",
+                action: "withdraws all of it, signing only as herself",
+                instruction: "vulnerable_withdraw",
+                accounts: &[Meta::Mut("alices_vault"), Meta::Signer("mallory")],
+                args: &[Value::U64(1000)],
+                expect: Expect::Ok,
+                effects: &[Effect::Field {
+                    account: "alices_vault",
+                    field: "amount",
+                    offset: 32,
+                    value: Value::U64(0),
+                }],
+            },
+            Step {
+                actor: "mallory",
+                action: "tries the secure withdrawal",
+                instruction: "secure_withdraw",
+                accounts: &[Meta::Mut("alices_vault"), Meta::Signer("mallory")],
+                args: &[Value::U64(1000)],
+                expect: Expect::Fails("ConstraintHasOne"),
+                effects: &[],
+            },
+        ],
+    };
+
+    #[test]
+    fn actors_and_refusals_are_found() {
+        assert_eq!(NARRATIVE.actor("mallory").map(|actor| actor.role), Some("the attacker"));
+        assert!(NARRATIVE.actor("bob").is_none());
+        let refused: Vec<&str> = NARRATIVE.refusals().map(|step| step.instruction).collect();
+        assert_eq!(refused, ["secure_withdraw"]);
+        assert_eq!(NARRATIVE.steps[0].accounts[1].name(), "mallory");
+    }
+
+    #[test]
+    fn describe_prints_every_step_and_effect() {
+        assert_eq!(
+            NARRATIVE.to_string(),
+            "Withdrawing from someone else's vault (vault)\n\
+             The withdrawal never compares the signer with the vault's owner.\n\
+             \n\
+             Actors:\n  \
+             alice, a depositor: 10000000000 lamports\n  \
+             mallory, the attacker: 1000000000 lamports\n\
+             \n\
+             Steps:\n  \
+             1. mallory withdraws all of it, signing only as herself\n     \
+             vulnerable_withdraw(1000)\n     \
+             then alices_vault.amount = 0\n  \
+             2. mallory tries the secure withdrawal\n     \
+             secure_withdraw(1000) fails with ConstraintHasOne\n"
+        );
+        assert_eq!(Value::Str("vault").to_string(), "\"vault\"");
+        assert_eq!(Effect::Closed("request").to_string(), "request is closed");
+    }
+}
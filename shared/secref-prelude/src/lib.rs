@@ -0,0 +1,24 @@
+//! The imports every example program starts from.
+//!
+//! A program's `lib.rs` opens with `use secref_prelude::*;`, and its
+//! `instructions`, `state` and `errors` modules with `use super::*;`, so the
+//! whole program sees the same names:
+//!
+//! - Anchor's prelude: account types, `Context`, `Result`, `msg!`, and the
+//!   event types (`#[event]`, `emit!`)
+//! - Anchor's guard macros, in which secure handlers state their checks:
+//!   `require!`, `require_keys_eq!`, `require_gte!` and the rest
+//! - [`feature_gated`], which builds a program's vulnerable handlers, its
+//!   secure handlers, or both
+//! - [`xref`], the types of the `SECREF_XREF` table `feature_gated` writes
+//! - [`narrative`], the types a program's `attack_narrative()` returns
+//!
+//! Anchor's macros expand to `anchor_lang::...` paths, so a program still
+//! depends on `anchor-lang` (see `shared/secref-anchor`) beside this crate.
+//! Imports only some programs need, such as `anchor_spl` or
+//! `secref_guards`, stay in those programs.
+
+pub use anchor_lang::prelude::*;
+pub use secref_macros::feature_gated;
+pub use secref_narrative as narrative;
+pub use secref_xref as xref;
@@ -0,0 +1,257 @@
+//! Reusable on-chain registry / allowlist for the security reference examples.
+//!
+//! A registry is a named set of public keys kept by one authority: CPI
+//! targets a program may call, mints it accepts, pools a router may trade
+//! through. Each member is an `Entry` PDA, so membership is a single
+//! account lookup, and consumers can check it two ways:
+//!
+//! - **Direct account read** - pass the entry account and call
+//!   [`verify::assert_registered`]; no CPI needed.
+//! - **CPI** - call `verify_entry`, which fails unless the entry exists.
+//!
+//! Depend on the crate with the `cpi` feature for either:
+//!
+//! ```toml
+//! secref-registry = { path = "../../../shared/secref-registry/programs/secref_registry", features = ["cpi"] }
+//! ```
+
+use anchor_lang::prelude::*;
+
+pub mod verify;
+
+declare_id!("SecRefRegistry11111111111111111111111111111");
+
+/// Longest registry name, in bytes
+pub const MAX_NAME_LEN: usize = 32;
+
+#[program]
+pub mod secref_registry {
+    use super::*;
+
+    /// Create a registry named `name`, administered by the signer
+    ///
+    /// The name is part of the PDA seeds, so one authority can keep several
+    /// registries (e.g. "mints" and "pools") side by side.
+    pub fn initialize_registry(ctx: Context<InitializeRegistry>, name: String) -> Result<()> {
+        require!(name.len() <= MAX_NAME_LEN, RegistryError::NameTooLong);
+
+        let registry = &mut ctx.accounts.registry;
+        registry.authority = ctx.accounts.authority.key();
+        registry.name = name;
+        registry.entry_count = 0;
+        registry.bump = ctx.bumps.registry;
+
+        msg!("Registry \"{}\" initialized with authority: {}", registry.name, registry.authority);
+        Ok(())
+    }
+
+    /// Remove `key` from the registry; the entry's rent returns to the authority
+    pub fn remove_entry(ctx: Context<RemoveEntry>, key: Pubkey) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        registry.entry_count = registry.entry_count.saturating_sub(1);
+
+        msg!("Removed {} from registry \"{}\"", key, registry.name);
+        Ok(())
+    }
+
+    /// Succeed only if `key` is registered; for consumers that check membership by CPI
+    pub fn verify_entry(_ctx: Context<VerifyEntry>, key: Pubkey) -> Result<()> {
+        // The entry account's seeds, owner and discriminator are the whole check
+        msg!("Verified {}", key);
+        Ok(())
+    }
+
+    // ========================================
+    // VULNERABLE IMPLEMENTATION
+    // ========================================
+    // This section contains INTENTIONALLY VULNERABLE code for educational purposes.
+    // DO NOT use this code in production environments.
+
+    /// VULNERABLE: Add `key` to a registry without authenticating the authority
+    ///
+    /// Security Issue: Any signer can pay for an entry in any registry. The
+    /// entry it creates is byte-for-byte identical to one the authority
+    /// added - same seeds, same owner, same data - so every consumer that
+    /// trusts the registry now trusts the attacker's key: an attacker's
+    /// program becomes an allowed CPI target, an attacker's mint an
+    /// accepted collateral, an attacker's pool a routable hop.
+    pub fn vulnerable_add_entry(ctx: Context<VulnerableAddEntry>, key: Pubkey) -> Result<()> {
+        // VULNERABILITY: `payer` is never compared with `registry.authority`
+        record_entry(&mut ctx.accounts.registry, &mut ctx.accounts.entry, key, ctx.accounts.payer.key(), ctx.bumps.entry)
+    }
+
+    // ========================================
+    // SECURE IMPLEMENTATION
+    // ========================================
+    // This section contains the SECURE version that only lets the
+    // registry's authority add entries.
+
+    /// SECURE: Add `key` to a registry; authority only
+    ///
+    /// Security Fix: `has_one = authority` ties the signer to the key stored
+    /// in the registry, so only the authority can create entries.
+    pub fn add_entry(ctx: Context<AddEntry>, key: Pubkey) -> Result<()> {
+        // SECURITY: `authority` is the registry's stored authority and has signed
+        record_entry(&mut ctx.accounts.registry, &mut ctx.accounts.entry, key, ctx.accounts.authority.key(), ctx.bumps.entry)
+    }
+}
+
+/// Fill in a newly created entry and bump the registry's count
+fn record_entry(registry: &mut Account<Registry>, entry: &mut Account<Entry>, key: Pubkey, added_by: Pubkey, bump: u8) -> Result<()> {
+    entry.registry = registry.key();
+    entry.key = key;
+    entry.added_by = added_by;
+    entry.bump = bump;
+    registry.entry_count = registry.entry_count.checked_add(1).ok_or(RegistryError::ArithmeticOverflow)?;
+
+    msg!("Added {} to registry \"{}\"", key, registry.name);
+    Ok(())
+}
+
+// ========================================
+// SHARED ACCOUNT CONTEXTS
+// ========================================
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct InitializeRegistry<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 4 + MAX_NAME_LEN + 8 + 1, // discriminator + authority + name + entry_count + bump
+        seeds = [b"registry", authority.key().as_ref(), name.as_bytes()],
+        bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(key: Pubkey)]
+pub struct RemoveEntry<'info> {
+    #[account(mut, has_one = authority)]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        mut,
+        seeds = [b"entry", registry.key().as_ref(), key.as_ref()],
+        bump = entry.bump,
+        close = authority
+    )]
+    pub entry: Account<'info, Entry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(key: Pubkey)]
+pub struct VerifyEntry<'info> {
+    pub registry: Account<'info, Registry>,
+
+    #[account(seeds = [b"entry", registry.key().as_ref(), key.as_ref()], bump = entry.bump)]
+    pub entry: Account<'info, Entry>,
+}
+
+// ========================================
+// VULNERABLE ACCOUNT CONTEXTS
+// ========================================
+
+#[derive(Accounts)]
+#[instruction(key: Pubkey)]
+pub struct VulnerableAddEntry<'info> {
+    // VULNERABILITY: No `has_one = authority`
+    #[account(mut)]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 32 + 32 + 1, // discriminator + registry + key + added_by + bump
+        seeds = [b"entry", registry.key().as_ref(), key.as_ref()],
+        bump
+    )]
+    pub entry: Account<'info, Entry>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ========================================
+// SECURE ACCOUNT CONTEXTS
+// ========================================
+
+#[derive(Accounts)]
+#[instruction(key: Pubkey)]
+pub struct AddEntry<'info> {
+    #[account(mut, has_one = authority)]
+    pub registry: Account<'info, Registry>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 32 + 32 + 1, // discriminator + registry + key + added_by + bump
+        seeds = [b"entry", registry.key().as_ref(), key.as_ref()],
+        bump
+    )]
+    pub entry: Account<'info, Entry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ========================================
+// ACCOUNT STRUCTURES
+// ========================================
+
+#[account]
+pub struct Registry {
+    /// Only key allowed to add or remove entries (32 bytes)
+    pub authority: Pubkey,
+    /// Registry name, also a PDA seed (4 + MAX_NAME_LEN bytes)
+    pub name: String,
+    /// Number of live entries (8 bytes)
+    pub entry_count: u64,
+    /// PDA bump (1 byte)
+    pub bump: u8,
+}
+
+#[account]
+pub struct Entry {
+    /// Registry this entry belongs to (32 bytes)
+    pub registry: Pubkey,
+    /// Registered key (32 bytes)
+    pub key: Pubkey,
+    /// Signer that created the entry (32 bytes)
+    pub added_by: Pubkey,
+    /// PDA bump (1 byte)
+    pub bump: u8,
+}
+
+// ========================================
+// ERROR DEFINITIONS
+// ========================================
+
+/// Errors returned by the registry
+///
+/// Offset from the default 6000 so registry failures never collide with a
+/// consumer's own `ErrorCode` variants, or with `secref-guards` (7000).
+#[error_code(offset = 7100)]
+pub enum RegistryError {
+    #[msg("Registry name is too long")]
+    NameTooLong,
+    #[msg("Entry account is not the registry's PDA for this key")]
+    EntryAddressMismatch,
+    #[msg("Key is not registered")]
+    NotRegistered,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+}
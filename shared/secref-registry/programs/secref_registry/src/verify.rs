@@ -0,0 +1,108 @@
+//! Membership checks for consumers that read entry accounts directly.
+//!
+//! A consumer passes the entry account alongside the key it wants to trust
+//! and calls [`assert_registered`] - no CPI, so it costs a PDA derivation
+//! and a deserialization. The address, owner and contents are all checked:
+//! an entry copied from another registry, created by another program, or
+//! recorded for another key is rejected.
+
+use anchor_lang::prelude::*;
+
+use crate::{Entry, RegistryError};
+
+/// Address and bump of the entry recording `key` in `registry`
+pub fn entry_address(registry: &Pubkey, key: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"entry", registry.as_ref(), key.as_ref()], &crate::ID)
+}
+
+/// Require `entry` to be the live registry entry for `key` in `registry`
+pub fn assert_registered(registry: &Pubkey, key: &Pubkey, entry: &AccountInfo) -> Result<()> {
+    let (expected, _) = entry_address(registry, key);
+    require_keys_eq!(*entry.key, expected, RegistryError::EntryAddressMismatch);
+    // A closed entry is owned by the System Program again
+    require!(*entry.owner == crate::ID, RegistryError::NotRegistered);
+
+    let data = entry.try_borrow_data()?;
+    let recorded = Entry::try_deserialize(&mut &data[..]).map_err(|_| error!(RegistryError::NotRegistered))?;
+    require_keys_eq!(recorded.registry, *registry, RegistryError::NotRegistered);
+    require_keys_eq!(recorded.key, *key, RegistryError::NotRegistered);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestAccount {
+        fn info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(&self.key, false, false, &mut self.lamports, &mut self.data, &self.owner, false, 0)
+        }
+    }
+
+    /// An entry account at `address` recording `key` in `registry`
+    fn entry_account(address: Pubkey, owner: Pubkey, registry: Pubkey, key: Pubkey) -> TestAccount {
+        let mut data = Vec::new();
+        Entry {
+            registry,
+            key,
+            added_by: Pubkey::new_unique(),
+            bump: 255,
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        TestAccount {
+            key: address,
+            owner,
+            lamports: 1,
+            data,
+        }
+    }
+
+    #[test]
+    fn accepts_the_registered_entry() {
+        let (registry, key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut entry = entry_account(entry_address(&registry, &key).0, crate::ID, registry, key);
+        assert!(assert_registered(&registry, &key, &entry.info()).is_ok());
+    }
+
+    #[test]
+    fn rejects_an_entry_at_another_address() {
+        let (registry, key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut entry = entry_account(Pubkey::new_unique(), crate::ID, registry, key);
+        assert_eq!(
+            assert_registered(&registry, &key, &entry.info()).unwrap_err(),
+            RegistryError::EntryAddressMismatch.into()
+        );
+    }
+
+    #[test]
+    fn rejects_an_entry_for_another_registry() {
+        // Right address for (registry, key), but the data says another registry
+        let (registry, key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut entry = entry_account(entry_address(&registry, &key).0, crate::ID, Pubkey::new_unique(), key);
+        assert_eq!(assert_registered(&registry, &key, &entry.info()).unwrap_err(), RegistryError::NotRegistered.into());
+    }
+
+    #[test]
+    fn rejects_a_closed_or_foreign_owned_entry() {
+        let (registry, key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let address = entry_address(&registry, &key).0;
+        let mut closed = TestAccount {
+            key: address,
+            owner: Pubkey::default(),
+            lamports: 0,
+            data: Vec::new(),
+        };
+        assert_eq!(assert_registered(&registry, &key, &closed.info()).unwrap_err(), RegistryError::NotRegistered.into());
+
+        let mut forged = entry_account(address, Pubkey::new_unique(), registry, key);
+        assert_eq!(assert_registered(&registry, &key, &forged.info()).unwrap_err(), RegistryError::NotRegistered.into());
+    }
+}
@@ -0,0 +1,959 @@
+//! Runs a [`Scenario`] against the example programs, off-chain.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::stake::state::{Authorized, Lockup};
+use anchor_lang::solana_program::{address_lookup_table, ed25519_program, stake, system_program, sysvar};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::memo::spl_memo;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022::extension::confidential_transfer::ConfidentialTransferAccount;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::encryption::auth_encryption::AeKey;
+use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::encryption::elgamal::ElGamalKeypair;
+use anchor_spl::token_2022::spl_token_2022;
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signature, Signer, Verifier};
+use secref_invariants::{Program, World};
+use secref_testkit::{runtime, TestAccount};
+
+use crate::programs::program;
+use crate::script::{Account, Call, Check, Expect, Meta, MintExtension, Scenario, Step, Value};
+
+/// The ComputeBudget program, whose instructions the runtime reads before the transaction runs
+const COMPUTE_BUDGET_ID: Pubkey = pubkey!("ComputeBudget111111111111111111111111111111");
+/// Compute units an instruction gets when the transaction sets no limit
+const DEFAULT_INSTRUCTION_UNITS: u64 = 200_000;
+/// The most compute units a transaction can have
+const MAX_COMPUTE_UNITS: u64 = 1_400_000;
+
+/// Where and why a scenario stopped matching its script
+#[derive(Debug)]
+pub struct Failure {
+    /// The 1-based step, or 0 while setting up
+    pub step: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.step {
+            0 => write!(f, "setup: {}", self.message),
+            step => write!(f, "step {step}: {}", self.message),
+        }
+    }
+}
+
+/// Run every step, returning the walkthrough line by line
+///
+/// Each transaction commits only if all its instructions succeed. After
+/// it, accounts left without lamports are wiped, as the runtime garbage
+/// collects them.
+pub fn run(scenario: &Scenario) -> std::result::Result<Vec<String>, Failure> {
+    let mut engine = Engine::new(scenario).map_err(|message| Failure { step: 0, message })?;
+    let mut walkthrough = vec![scenario.title.clone()];
+    for (index, step) in scenario.steps.iter().enumerate() {
+        engine.step(step, &mut walkthrough).map_err(|message| Failure {
+            step: index + 1,
+            message,
+        })?;
+    }
+    Ok(walkthrough)
+}
+
+struct Engine {
+    world: World,
+    default_program: String,
+    /// The scenario's programs, by name
+    programs: BTreeMap<String, Program>,
+    addresses: BTreeMap<String, Pubkey>,
+    bumps: BTreeMap<String, u8>,
+    /// Names whose keys the script holds: actors and keypairs
+    keypairs: BTreeMap<String, Keypair>,
+    /// ElGamal keys for confidential transfers, by the name of the account or key they belong to
+    elgamal: BTreeMap<String, ElGamalKeypair>,
+    /// What the last instruction logged
+    logs: Vec<String>,
+}
+
+type Outcome<T> = std::result::Result<T, String>;
+
+impl Engine {
+    fn new(scenario: &Scenario) -> Outcome<Self> {
+        let mut engine = Self {
+            world: World::new(),
+            default_program: scenario.program.clone(),
+            programs: BTreeMap::new(),
+            addresses: BTreeMap::new(),
+            bumps: BTreeMap::new(),
+            keypairs: BTreeMap::new(),
+            elgamal: BTreeMap::new(),
+            logs: Vec::new(),
+        };
+        runtime::warp_to(0);
+        runtime::warp_to_slot(0);
+
+        for name in std::iter::once(&scenario.program).chain(&scenario.programs) {
+            let deployed = program(name).ok_or_else(|| format!("no example program is called `{name}`"))?;
+            runtime::register(deployed.id, deployed.entry);
+            engine.world.add(TestAccount::program(deployed.id));
+            engine.programs.insert(name.clone(), deployed);
+            engine.addresses.insert(name.clone(), deployed.id);
+        }
+        for (name, id) in [
+            ("system_program", system_program::ID),
+            ("token_program", spl_token::ID),
+            ("token_2022_program", anchor_spl::token_2022::ID),
+            ("associated_token_program", anchor_spl::associated_token::ID),
+            ("ed25519_program", ed25519_program::ID),
+            ("memo_program", spl_memo::ID),
+            ("compute_budget_program", COMPUTE_BUDGET_ID),
+            ("metadata_program", anchor_spl::metadata::ID),
+            ("stake_program", stake::program::ID),
+            ("address_lookup_table_program", address_lookup_table::program::ID),
+            ("instructions", sysvar::instructions::ID),
+            ("clock", sysvar::clock::ID),
+            ("stake_history", sysvar::stake_history::ID),
+            ("stake_config", TestAccount::stake_config().key),
+        ] {
+            engine.addresses.insert(name.to_string(), id);
+        }
+        engine.world.add(TestAccount::program(spl_token::ID));
+        engine.world.add(TestAccount::token_2022_program());
+        engine.world.add(TestAccount::program(anchor_spl::associated_token::ID));
+        engine.world.add(TestAccount::program(ed25519_program::ID));
+        engine.world.add(TestAccount::program(spl_memo::ID));
+        engine.world.add(TestAccount::program(COMPUTE_BUDGET_ID));
+        engine.world.add(TestAccount::metadata_program());
+        engine.world.add(TestAccount::stake_program());
+        engine.world.add(TestAccount::address_lookup_table_program());
+        engine.world.add(TestAccount::instructions_sysvar());
+        engine.world.add(TestAccount::clock_sysvar());
+        engine.world.add(TestAccount::stake_history_sysvar());
+        engine.world.add(TestAccount::stake_config());
+
+        for (name, &lamports) in &scenario.actors {
+            let key = engine.keypair(name);
+            engine.world.add(TestAccount::system(lamports).at(key));
+        }
+
+        // Addresses derived from names come first, so a mint can have as authority the PDA its own address seeds
+        for (name, account) in &scenario.accounts {
+            match account {
+                Account::Keypair => {
+                    engine.keypair(name);
+                }
+                Account::Empty
+                | Account::Zeroed { .. }
+                | Account::Executable
+                | Account::Mint { .. }
+                | Account::TokenAccount { .. }
+                | Account::HookedMint { .. }
+                | Account::HookedTokenAccount { .. }
+                | Account::ConfidentialMint { .. }
+                | Account::ConfidentialTokenAccount { .. }
+                | Account::Token2022Mint { .. }
+                | Account::Token2022Account { .. }
+                | Account::PubkeyValidityProof { .. }
+                | Account::TransferProof { .. }
+                | Account::StakeAccount { .. }
+                | Account::VoteAccount { .. } => {
+                    engine.address(name);
+                }
+                Account::Pda { .. } | Account::Metadata { .. } | Account::AssociatedTokenAccount { .. } => {}
+            }
+        }
+        // Accounts may name each other (a PDA seeded by a mint, a token account of it), so resolve in passes
+        let mut pending: Vec<(&String, &Account)> = scenario.accounts.iter().collect();
+        while !pending.is_empty() {
+            let before = pending.len();
+            let mut unresolved = Vec::new();
+            let mut last_error = String::new();
+            for (name, account) in pending {
+                if let Err(error) = engine.create(name, account) {
+                    last_error = error;
+                    unresolved.push((name, account));
+                }
+            }
+            if unresolved.len() == before {
+                return Err(last_error);
+            }
+            pending = unresolved;
+        }
+        Ok(engine)
+    }
+
+    /// A signing keypair derived from `name`, so every run uses the same addresses
+    fn keypair(&mut self, name: &str) -> Pubkey {
+        let seed = hash(format!("secref-scenarios:{name}").as_bytes()).to_bytes();
+        let secret = SecretKey::from_bytes(&seed).expect("32 bytes make a secret key");
+        let public = PublicKey::from(&secret);
+        let key = Pubkey::new_from_array(public.to_bytes());
+        self.keypairs.insert(name.to_string(), Keypair { secret, public });
+        self.addresses.insert(name.to_string(), key);
+        key
+    }
+
+    /// An address derived from `name` that no one holds the key for
+    fn address(&mut self, name: &str) -> Pubkey {
+        let key = Pubkey::new_from_array(hash(format!("secref-scenarios:address:{name}").as_bytes()).to_bytes());
+        self.addresses.insert(name.to_string(), key);
+        key
+    }
+
+    fn create(&mut self, name: &str, account: &Account) -> Outcome<()> {
+        match account {
+            Account::Keypair => {
+                let key = self.keypair(name);
+                self.world.add(TestAccount::system(0).at(key));
+            }
+            Account::Empty => {
+                let key = self.address(name);
+                self.world.add(TestAccount::system(0).at(key));
+            }
+            Account::Pda { program, seeds } => {
+                let program = self.resolve(program.as_ref().unwrap_or(&self.default_program))?;
+                let seeds = seeds.iter().map(|seed| self.seed(seed)).collect::<Outcome<Vec<_>>>()?;
+                let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+                let (key, bump) = Pubkey::find_program_address(&seeds, &program);
+                self.addresses.insert(name.to_string(), key);
+                self.bumps.insert(name.to_string(), bump);
+                self.world.add(TestAccount::system(0).at(key));
+            }
+            Account::Executable => {
+                let key = self.address(name);
+                self.world.add(TestAccount::program(key));
+            }
+            Account::Zeroed { owner, space } => {
+                let owner = self.resolve(owner)?;
+                let key = self.address(name);
+                self.world.add(TestAccount::new(owner, vec![0; *space]).at(key));
+            }
+            Account::Mint {
+                authority,
+                decimals,
+                supply,
+            } => {
+                let authority = self.resolve(authority)?;
+                let key = self.address(name);
+                self.world.add(TestAccount::mint(authority, *decimals, *supply).at(key));
+            }
+            Account::TokenAccount { mint, owner, amount } => {
+                let (mint, owner) = (self.resolve(mint)?, self.resolve(owner)?);
+                let key = self.address(name);
+                self.world.add(TestAccount::token_account(mint, owner, *amount).at(key));
+            }
+            Account::HookedMint {
+                authority,
+                decimals,
+                supply,
+                hook,
+            } => {
+                let (authority, hook) = (self.resolve(authority)?, self.resolve(hook)?);
+                let key = self.address(name);
+                self.world
+                    .add(TestAccount::hooked_mint(authority, *decimals, *supply, hook).at(key));
+            }
+            Account::HookedTokenAccount { mint, owner, amount } => {
+                let (mint, owner) = (self.resolve(mint)?, self.resolve(owner)?);
+                let key = self.address(name);
+                self.world
+                    .add(TestAccount::hooked_token_account(mint, owner, *amount).at(key));
+            }
+            Account::ConfidentialMint {
+                authority,
+                decimals,
+                supply,
+            } => {
+                let authority = self.resolve(authority)?;
+                let key = self.address(name);
+                self.world
+                    .add(TestAccount::confidential_mint(authority, *decimals, *supply).at(key));
+            }
+            Account::ConfidentialTokenAccount {
+                mint,
+                owner,
+                amount,
+                confidential,
+            } => {
+                let (mint, owner) = (self.resolve(mint)?, self.resolve(owner)?);
+                let mut account = TestAccount::confidential_token_account(mint, owner, *amount).at(self.address(name));
+                if let Some(confidential) = confidential {
+                    account = account.configured(self.elgamal_key(name), *confidential);
+                }
+                self.world.add(account);
+            }
+            Account::Token2022Mint {
+                authority,
+                decimals,
+                supply,
+                extensions,
+            } => {
+                let authority = self.resolve(authority)?;
+                let mut mint = TestAccount::token_2022_mint(authority, *decimals, *supply).at(self.address(name));
+                for extension in extensions {
+                    mint = match extension {
+                        MintExtension::MetadataPointer(address) => mint.with_metadata_pointer(self.resolve(address)?),
+                        MintExtension::TokenMetadata {
+                            update_authority,
+                            name,
+                            symbol,
+                        } => mint.with_token_metadata(self.resolve(update_authority)?, name, symbol),
+                        MintExtension::GroupPointer(address) => mint.with_group_pointer(self.resolve(address)?),
+                        MintExtension::TokenGroup {
+                            update_authority,
+                            max_size,
+                        } => mint.with_token_group(self.resolve(update_authority)?, *max_size),
+                        MintExtension::GroupMemberPointer(address) => {
+                            mint.with_group_member_pointer(self.resolve(address)?)
+                        }
+                        MintExtension::TokenGroupMember { group, member_number } => {
+                            mint.with_token_group_member(self.resolve(group)?, *member_number)
+                        }
+                    };
+                }
+                self.world.add(mint);
+            }
+            Account::Token2022Account { mint, owner, amount } => {
+                let (mint, owner) = (self.resolve(mint)?, self.resolve(owner)?);
+                let key = self.address(name);
+                self.world
+                    .add(TestAccount::token_account(mint, owner, *amount).owned_by(spl_token_2022::ID).at(key));
+            }
+            Account::PubkeyValidityProof { key } => {
+                let address = self.address(name);
+                let proof = TestAccount::pubkey_validity_proof(self.elgamal_key(key));
+                self.world.add(proof.at(address));
+            }
+            Account::TransferProof {
+                source,
+                destination_key,
+                amount,
+            } => {
+                let source_key = self.resolve(source)?;
+                let source_account = self
+                    .world
+                    .get(&source_key)
+                    .cloned()
+                    .ok_or_else(|| format!("`{source}` does not exist yet"))?;
+                let destination = *self.elgamal_key(destination_key).pubkey();
+                let elgamal = self
+                    .elgamal
+                    .get(source)
+                    .ok_or_else(|| format!("`{source}` is not configured for confidential transfers"))?;
+                let proof = source_account.transfer_proof(elgamal, &destination, *amount);
+                let address = self.address(name);
+                self.world.add(proof.at(address));
+            }
+            Account::Metadata {
+                mint,
+                seller_fee_basis_points,
+                creators,
+            } => {
+                let mint = self.resolve(mint)?;
+                let creators = creators
+                    .iter()
+                    .map(|(creator, share)| Ok((self.resolve(creator)?, *share)))
+                    .collect::<Outcome<Vec<_>>>()?;
+                let metadata = TestAccount::metadata(mint, *seller_fee_basis_points, &creators);
+                self.addresses.insert(name.to_string(), metadata.key);
+                self.world.add(metadata);
+            }
+            Account::AssociatedTokenAccount { mint, owner, amount } => {
+                let (mint, owner) = (self.resolve(mint)?, self.resolve(owner)?);
+                let key = get_associated_token_address(&owner, &mint);
+                self.addresses.insert(name.to_string(), key);
+                self.world.add(TestAccount::token_account(mint, owner, *amount).at(key));
+            }
+            Account::StakeAccount {
+                staker,
+                withdrawer,
+                lamports,
+                locked_until,
+                custodian,
+            } => {
+                let authorized = Authorized {
+                    staker: self.resolve(staker)?,
+                    withdrawer: self.resolve(withdrawer)?,
+                };
+                let lockup = Lockup {
+                    unix_timestamp: *locked_until,
+                    epoch: 0,
+                    custodian: custodian.as_deref().map(|name| self.resolve(name)).transpose()?.unwrap_or_default(),
+                };
+                let key = self.address(name);
+                self.world.add(TestAccount::stake_account(authorized, lockup, *lamports).at(key));
+            }
+            Account::VoteAccount { node, commission, owner } => {
+                let node = self.resolve(node)?;
+                let mut vote_account = TestAccount::vote_account(node, node, *commission).at(self.address(name));
+                if let Some(owner) = owner {
+                    vote_account = vote_account.owned_by(self.resolve(owner)?);
+                }
+                self.world.add(vote_account);
+            }
+        }
+        Ok(())
+    }
+
+    /// The ElGamal key called `name`, made the first time it is asked for
+    fn elgamal_key(&mut self, name: &str) -> &ElGamalKeypair {
+        self.elgamal.entry(name.to_string()).or_insert_with(ElGamalKeypair::new_rand)
+    }
+
+    fn resolve(&self, name: &str) -> Outcome<Pubkey> {
+        self.addresses
+            .get(name)
+            .copied()
+            .ok_or_else(|| format!("nothing is called `{name}`"))
+    }
+
+    fn step(&mut self, step: &Step, walkthrough: &mut Vec<String>) -> Outcome<()> {
+        match step {
+            Step::Note(text) => walkthrough.push(format!("- {text}")),
+            Step::Invoke(call) => self.transaction(std::slice::from_ref(call), walkthrough)?,
+            Step::Transaction(calls) => self.transaction(calls, walkthrough)?,
+            Step::Airdrop(name, lamports) => {
+                let key = self.resolve(name)?;
+                let mut account = self.world.account(&key).clone();
+                account.lamports += lamports;
+                self.world.add(account);
+                walkthrough.push(format!("  airdrop {lamports} lamports to {name}"));
+            }
+            Step::Warp(unix_timestamp) => {
+                runtime::warp_to(*unix_timestamp);
+                walkthrough.push(format!("  clock set to {unix_timestamp}"));
+            }
+            Step::WarpSlot(slot) => {
+                runtime::warp_to_slot(*slot);
+                walkthrough.push(format!("  slot set to {slot}"));
+            }
+            Step::Assert(check) => {
+                self.check(check)?;
+                walkthrough.push(format!("  check: {}", describe_check(check)));
+            }
+        }
+        Ok(())
+    }
+
+    fn transaction(&mut self, calls: &[Call], walkthrough: &mut Vec<String>) -> Outcome<()> {
+        let instructions = calls
+            .iter()
+            .map(|call| self.instruction(call))
+            .collect::<Outcome<Vec<_>>>()?;
+        let before = self.world.clone();
+        runtime::set_compute_units(compute_units(&instructions), 0);
+
+        for (index, call) in calls.iter().enumerate() {
+            let result = self.execute(&instructions, index);
+            let described = describe(call, &self.default_program);
+            match (&call.expect, result) {
+                (Expect::Ok, Ok(())) => {
+                    walkthrough.push(format!("  {described}: ok"));
+                    walkthrough.extend(self.logs.iter().map(|line| format!("      | {line}")));
+                }
+                (Expect::Fails(expected), Err(error)) if error == *expected => {
+                    walkthrough.push(format!("  {described}: fails with {expected}, as expected"));
+                    self.world = before;
+                    if index + 1 < calls.len() {
+                        return Err("calls after one expected to fail would never run".into());
+                    }
+                    return Ok(());
+                }
+                (Expect::Ok, Err(error)) => {
+                    return Err(format!(
+                        "{described} failed with {error}\n{}{}",
+                        self.explanation(call),
+                        self.logs.join("\n")
+                    ))
+                }
+                (Expect::Fails(expected), Ok(())) => return Err(format!("{described} succeeded, expected {expected}")),
+                (Expect::Fails(expected), Err(error)) => {
+                    return Err(format!(
+                        "{described} failed with {error}, expected {expected}\n{}{}",
+                        self.explanation(call),
+                        self.logs.join("\n")
+                    ))
+                }
+            }
+        }
+        self.collect_garbage();
+        Ok(())
+    }
+
+    /// Run the `index`th instruction of a transaction against the world, naming the error if it fails
+    ///
+    /// A program that panics fails the way it does on-chain, with
+    /// `ProgramFailedToComplete`, and leaves the world as it was.
+    fn execute(&mut self, transaction: &[Instruction], index: usize) -> Outcome<()> {
+        let instruction = &transaction[index];
+        self.logs.clear();
+        if instruction.program_id == ed25519_program::ID {
+            return verify_ed25519(&instruction.data).map_err(|error| format!("{error:?}"));
+        }
+        if instruction.program_id == spl_memo::ID {
+            // The Memo program only requires valid UTF-8 (and signatures from any accounts passed)
+            return std::str::from_utf8(&instruction.data)
+                .map(|_| ())
+                .map_err(|_| format!("{:?}", ProgramError::InvalidInstructionData));
+        }
+        if instruction.program_id == COMPUTE_BUDGET_ID {
+            // Already applied, by `compute_units`, before the transaction ran
+            return Ok(());
+        }
+        if instruction
+            .accounts
+            .iter()
+            .any(|meta| meta.pubkey == sysvar::instructions::ID)
+        {
+            self.world
+                .add(TestAccount::instructions_sysvar_of(transaction, index as u16));
+        }
+        let result = if [
+            system_program::ID,
+            spl_token::ID,
+            anchor_spl::token_2022::ID,
+            anchor_spl::associated_token::ID,
+            stake::program::ID,
+        ]
+        .contains(&instruction.program_id)
+        {
+            let mut accounts = instruction.accounts.clone();
+            accounts.push(AccountMeta::new_readonly(instruction.program_id, false));
+            let native = Program {
+                id: instruction.program_id,
+                entry: forward_to_native_program,
+            };
+            self.world.invoke(native, &accounts, &instruction.data)
+        } else {
+            let deployed = *self
+                .programs
+                .values()
+                .find(|deployed| deployed.id == instruction.program_id)
+                .expect("instructions are built for deployed programs");
+            let world = &mut self.world;
+            let quiet = panic::take_hook();
+            panic::set_hook(Box::new(|_| {}));
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                world.invoke(deployed, &instruction.accounts, &instruction.data)
+            }));
+            panic::set_hook(quiet);
+            match result {
+                Ok(result) => result,
+                Err(payload) => {
+                    self.logs = runtime::take_logs();
+                    let message = payload
+                        .downcast_ref::<&str>()
+                        .map(|message| message.to_string())
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_default();
+                    self.logs.push(format!("Program panicked: {message}"));
+                    return Err("ProgramFailedToComplete".into());
+                }
+            }
+        };
+        self.logs = runtime::take_logs();
+        result.map_err(|error| self.error_name(&error))
+    }
+
+    fn instruction(&self, call: &Call) -> Outcome<Instruction> {
+        let name = call.program.as_ref().unwrap_or(&self.default_program);
+        let program_id = match name.as_str() {
+            "ed25519_program" => ed25519_program::ID,
+            "memo_program" => spl_memo::ID,
+            "compute_budget_program" => COMPUTE_BUDGET_ID,
+            "system_program" => system_program::ID,
+            "token_program" => spl_token::ID,
+            "token_2022_program" => anchor_spl::token_2022::ID,
+            "associated_token_program" => anchor_spl::associated_token::ID,
+            "stake_program" => stake::program::ID,
+            _ => {
+                self.programs
+                    .get(name)
+                    .ok_or_else(|| format!("`{name}` is not among the scenario's programs"))?
+                    .id
+            }
+        };
+
+        let mut data = Vec::new();
+        if let Some(instruction) = &call.instruction {
+            data.extend_from_slice(&hash(format!("global:{instruction}").as_bytes()).to_bytes()[..8]);
+        }
+        for arg in &call.args {
+            self.encode(arg, &mut data)?;
+        }
+
+        let accounts = call
+            .accounts
+            .iter()
+            .map(|meta| {
+                let (name, is_signer, is_writable) = match meta {
+                    Meta::Read(name) => (name, false, false),
+                    Meta::Mut(name) => (name, false, true),
+                    Meta::Signer(name) => (name, true, false),
+                    Meta::SignerMut(name) => (name, true, true),
+                };
+                if is_signer && !self.keypairs.contains_key(name) {
+                    return Err(format!("`{name}` cannot sign: only actors and keypairs hold keys"));
+                }
+                let pubkey = self.resolve(name)?;
+                Ok(AccountMeta {
+                    pubkey,
+                    is_signer,
+                    is_writable,
+                })
+            })
+            .collect::<Outcome<Vec<_>>>()?;
+        Ok(Instruction {
+            program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// The first Anchor error code logged, which a failed CPI's caller only passes on, or the `ProgramError` itself
+    fn error_name(&self, error: &ProgramError) -> String {
+        self.logs
+            .iter()
+            .find_map(|line| {
+                let code = line.split("Error Code: ").nth(1)?;
+                code.split('.').next().map(str::to_string)
+            })
+            .unwrap_or_else(|| format!("{error:?}"))
+    }
+
+    /// What the logged error means, ahead of the logs of a failure the script did not expect
+    ///
+    /// Custom codes collide across programs, so causes from the called
+    /// program are kept over the rest when there are any.
+    fn explanation(&self, call: &Call) -> String {
+        let Some(mut explanation) = secref_errors::annotate(&self.logs) else {
+            return String::new();
+        };
+        let called = call.program.as_ref().unwrap_or(&self.default_program);
+        let own = |cause: &secref_errors::Cause| {
+            matches!(cause.origin, secref_errors::Origin::Program { program, .. } if program == called)
+        };
+        if explanation.causes.iter().any(own) {
+            explanation.causes.retain(own);
+        }
+        format!("{explanation}\n")
+    }
+
+    /// Wipe accounts a transaction left without lamports
+    fn collect_garbage(&mut self) {
+        let keys: BTreeSet<Pubkey> = self.addresses.values().copied().collect();
+        for key in keys {
+            let account = self.world.account(&key);
+            if account.lamports == 0 && !(account.data.is_empty() && account.owner == system_program::ID) {
+                self.world.add(TestAccount::system(0).at(key));
+            }
+        }
+    }
+
+    fn check(&self, check: &Check) -> Outcome<()> {
+        let (holds, actual) = match check {
+            Check::Lamports(name, expected) => {
+                let actual = self.world.lamports(&self.resolve(name)?);
+                (actual == *expected, actual.to_string())
+            }
+            Check::Tokens(name, expected) => {
+                let account = self.world.account(&self.resolve(name)?);
+                // The base layout is shared, so this reads SPL and Token-2022 accounts alike
+                let actual = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+                    .map_err(|_| format!("`{name}` is not a token account"))?
+                    .base
+                    .amount;
+                (actual == *expected, actual.to_string())
+            }
+            Check::Field(name, offset, value) | Check::Data(name, offset, value) => {
+                let account = self.world.account(&self.resolve(name)?);
+                let mut expected = Vec::new();
+                self.encode(value, &mut expected)?;
+                let start = match check {
+                    Check::Field(..) => 8 + offset,
+                    _ => *offset,
+                };
+                let actual = account.data.get(start..start + expected.len());
+                (actual == Some(&expected[..]), format!("{actual:?}"))
+            }
+            Check::Owner(name, owner) => {
+                let actual = self.world.account(&self.resolve(name)?).owner;
+                (actual == self.resolve(owner)?, actual.to_string())
+            }
+            Check::Closed(name) => {
+                let account = self.world.account(&self.resolve(name)?);
+                (
+                    account.lamports == 0 && account.data.is_empty(),
+                    format!("{} lamports, {} bytes", account.lamports, account.data.len()),
+                )
+            }
+            Check::Logged(text) => (
+                self.logs.iter().any(|line| line.contains(text.as_str())),
+                self.logs.join("\n"),
+            ),
+            Check::Confidential(name, key, pending, available) => {
+                let account = self.world.account(&self.resolve(name)?);
+                let elgamal = self.elgamal.get(key).ok_or_else(|| format!("no ElGamal key is called `{key}`"))?;
+                StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+                    .ok()
+                    .filter(|state| state.get_extension::<ConfidentialTransferAccount>().is_ok())
+                    .ok_or_else(|| format!("`{name}` is not configured for confidential transfers"))?;
+                let actual = account.confidential_balances(elgamal);
+                (actual == (*pending, *available), format!("{actual:?}"))
+            }
+        };
+        if holds {
+            Ok(())
+        } else {
+            Err(format!("expected {}, found {actual}", describe_check(check)))
+        }
+    }
+
+    /// The bytes a seed contributes: strings and byte strings without their length prefix
+    fn seed(&self, value: &Value) -> Outcome<Vec<u8>> {
+        match value {
+            Value::Str(text) | Value::Utf8(text) => Ok(text.as_bytes().to_vec()),
+            Value::Bytes(bytes) => Ok(bytes.clone()),
+            other => {
+                let mut bytes = Vec::new();
+                self.encode(other, &mut bytes)?;
+                Ok(bytes)
+            }
+        }
+    }
+
+    fn encode(&self, value: &Value, out: &mut Vec<u8>) -> Outcome<()> {
+        match value {
+            Value::Bool(value) => out.push(u8::from(*value)),
+            Value::U8(value) => out.push(*value),
+            Value::U16(value) => out.extend_from_slice(&value.to_le_bytes()),
+            Value::U32(value) => out.extend_from_slice(&value.to_le_bytes()),
+            Value::U64(value) => out.extend_from_slice(&value.to_le_bytes()),
+            Value::U128(value) => out.extend_from_slice(&value.to_le_bytes()),
+            Value::I64(value) => out.extend_from_slice(&value.to_le_bytes()),
+            Value::Key(name) => out.extend_from_slice(self.resolve(name)?.as_ref()),
+            Value::Bump(name) => out.push(*self.bumps.get(name).ok_or_else(|| format!("`{name}` is not a PDA"))?),
+            Value::Decryptable(amount) => out.extend_from_slice(&AeKey::new_rand().encrypt(*amount).to_bytes()),
+            Value::Str(text) => {
+                out.extend_from_slice(&(text.len() as u32).to_le_bytes());
+                out.extend_from_slice(text.as_bytes());
+            }
+            Value::Utf8(text) => out.extend_from_slice(text.as_bytes()),
+            Value::Bytes(bytes) => {
+                out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+                out.extend_from_slice(bytes);
+            }
+            Value::Array(values) => {
+                for value in values {
+                    self.encode(value, out)?;
+                }
+            }
+            Value::Vec(values) => {
+                out.extend_from_slice(&(values.len() as u32).to_le_bytes());
+                for value in values {
+                    self.encode(value, out)?;
+                }
+            }
+            Value::Encoded(values) => {
+                let mut bytes = Vec::new();
+                for value in values {
+                    self.encode(value, &mut bytes)?;
+                }
+                out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+                out.extend_from_slice(&bytes);
+            }
+            Value::Option(None) => out.push(0),
+            Value::Option(Some(value)) => {
+                out.push(1);
+                self.encode(value, out)?;
+            }
+            Value::Variant(index, fields) => {
+                out.push(*index);
+                for field in fields {
+                    self.encode(field, out)?;
+                }
+            }
+            Value::Sha256(values) => {
+                let mut preimage = Vec::new();
+                for value in values {
+                    preimage.extend(self.seed(value)?);
+                }
+                out.extend_from_slice(&hash(&preimage).to_bytes());
+            }
+            Value::Ed25519(signatures) => {
+                let mut signed = Vec::new();
+                for (signer, message) in signatures {
+                    let keypair = self
+                        .keypairs
+                        .get(signer)
+                        .ok_or_else(|| format!("`{signer}` cannot sign: only actors and keypairs hold keys"))?;
+                    let mut bytes = Vec::new();
+                    for value in message {
+                        self.encode(value, &mut bytes)?;
+                    }
+                    signed.push((keypair, bytes));
+                }
+                ed25519_instruction_data(&signed, out);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Offsets of one signature in an Ed25519 program instruction, all pointing into the instruction itself
+const ED25519_OFFSETS_SIZE: usize = 14;
+const ED25519_HEADER_SIZE: usize = 2;
+const THIS_INSTRUCTION: u16 = u16::MAX;
+
+/// The Ed25519 program's layout: count, padding, one offsets entry per signature, then each key, signature and message
+fn ed25519_instruction_data(signed: &[(&Keypair, Vec<u8>)], out: &mut Vec<u8>) {
+    let start = out.len();
+    out.push(signed.len() as u8);
+    out.push(0);
+    let mut payload = Vec::new();
+    let mut offset = ED25519_HEADER_SIZE + signed.len() * ED25519_OFFSETS_SIZE;
+    for (keypair, message) in signed {
+        let public_key_offset = offset;
+        let signature_offset = public_key_offset + 32;
+        let message_offset = signature_offset + 64;
+        offset = message_offset + message.len();
+        for field in [
+            signature_offset as u16,
+            THIS_INSTRUCTION,
+            public_key_offset as u16,
+            THIS_INSTRUCTION,
+            message_offset as u16,
+            message.len() as u16,
+            THIS_INSTRUCTION,
+        ] {
+            out.extend_from_slice(&field.to_le_bytes());
+        }
+        payload.extend_from_slice(keypair.public.as_bytes());
+        payload.extend_from_slice(&keypair.sign(message).to_bytes());
+        payload.extend_from_slice(message);
+    }
+    out.extend_from_slice(&payload);
+    debug_assert_eq!(out.len() - start, offset);
+}
+
+/// The compute units a transaction's instructions share, as the runtime budgets them
+///
+/// A `SetComputeUnitLimit` sets them; without one, every instruction but
+/// a ComputeBudget one adds 200,000. Either way 1,400,000 is the most.
+/// Programs see the whole budget: off-chain, the meter does not move.
+fn compute_units(transaction: &[Instruction]) -> u64 {
+    let requested = transaction.iter().find_map(|instruction| match instruction.data.as_slice() {
+        [2, units @ ..] if instruction.program_id == COMPUTE_BUDGET_ID => {
+            Some(u64::from(u32::from_le_bytes(units.try_into().ok()?)))
+        }
+        _ => None,
+    });
+    let default = || {
+        let instructions = transaction
+            .iter()
+            .filter(|instruction| instruction.program_id != COMPUTE_BUDGET_ID)
+            .count();
+        DEFAULT_INSTRUCTION_UNITS * instructions as u64
+    };
+    requested.unwrap_or_else(default).min(MAX_COMPUTE_UNITS)
+}
+
+/// A System or Token Program instruction, passed on to the runtime's emulation as a CPI would be
+///
+/// The program's own account comes last, so the runtime finds the
+/// program among the instruction's accounts.
+fn forward_to_native_program<'info>(
+    program_id: &Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+    data: &[u8],
+) -> ProgramResult {
+    let (_, accounts) = accounts.split_last().expect("the program's account comes last");
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts: accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: *account.key,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: data.to_vec(),
+    };
+    anchor_lang::solana_program::program::invoke(&instruction, accounts)
+}
+
+/// Check every signature an Ed25519 program instruction carries, as the runtime does before a transaction runs
+fn verify_ed25519(data: &[u8]) -> std::result::Result<(), ProgramError> {
+    let invalid = ProgramError::InvalidInstructionData;
+    let count = usize::from(*data.first().ok_or(invalid.clone())?);
+    let field = |entry: usize, index: usize| -> std::result::Result<usize, ProgramError> {
+        let at = ED25519_HEADER_SIZE + entry * ED25519_OFFSETS_SIZE + index * 2;
+        let bytes = data.get(at..at + 2).ok_or(ProgramError::InvalidInstructionData)?;
+        Ok(usize::from(u16::from_le_bytes([bytes[0], bytes[1]])))
+    };
+    for entry in 0..count {
+        if [1, 3, 6]
+            .iter()
+            .any(|&index| field(entry, index) != Ok(usize::from(THIS_INSTRUCTION)))
+        {
+            return Err(invalid);
+        }
+        let (signature, public_key) = (field(entry, 0)?, field(entry, 2)?);
+        let (message, message_len) = (field(entry, 4)?, field(entry, 5)?);
+        let slice = |at: usize, len: usize| data.get(at..at + len).ok_or(ProgramError::InvalidInstructionData);
+        let public_key = PublicKey::from_bytes(slice(public_key, 32)?).map_err(|_| invalid.clone())?;
+        let signature = Signature::from_bytes(slice(signature, 64)?).map_err(|_| invalid.clone())?;
+        public_key
+            .verify(slice(message, message_len)?, &signature)
+            .map_err(|_| invalid.clone())?;
+    }
+    Ok(())
+}
+
+/// How a call reads in the walkthrough: program, instruction and signers
+fn describe(call: &Call, default_program: &str) -> String {
+    let target = match (&call.instruction, call.program.as_deref()) {
+        (Some(instruction), None) => instruction.clone(),
+        (Some(instruction), Some(program)) => format!("{program}::{instruction}"),
+        // Native programs' instructions start with a tag: a byte, or the System Program's `u32`
+        (None, program) => match call.args.first() {
+            Some(Value::U8(tag)) => format!("{} instruction {tag}", program.unwrap_or(default_program)),
+            Some(Value::U32(tag)) => format!("{} instruction {tag}", program.unwrap_or(default_program)),
+            _ => program.unwrap_or(default_program).to_string(),
+        },
+    };
+    let mut signers: Vec<&str> = Vec::new();
+    for meta in &call.accounts {
+        if let Meta::Signer(name) | Meta::SignerMut(name) = meta {
+            if !signers.contains(&name.as_str()) {
+                signers.push(name);
+            }
+        }
+    }
+    match signers.is_empty() {
+        true => target,
+        false => format!("{target} signed by {}", signers.join(", ")),
+    }
+}
+
+fn describe_check(check: &Check) -> String {
+    match check {
+        Check::Lamports(name, lamports) => format!("{name} holds {lamports} lamports"),
+        Check::Tokens(name, amount) => format!("{name} holds {amount} tokens"),
+        Check::Field(name, offset, value) => format!("{name} stores {value:?} at offset {offset}"),
+        Check::Data(name, offset, value) => format!("{name} stores {value:?} at byte {offset}"),
+        Check::Owner(name, owner) => format!("{name} is owned by {owner}"),
+        Check::Closed(name) => format!("{name} is closed"),
+        Check::Logged(text) => format!("the program logged \"{text}\""),
+        Check::Confidential(name, key, pending, available) => {
+            format!("{name} holds {pending} pending and {available} available confidentially, under {key}'s key")
+        }
+    }
+}
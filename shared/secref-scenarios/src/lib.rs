@@ -0,0 +1,36 @@
+//! Exploit walkthroughs as data.
+//!
+//! A scenario script names its actors, the accounts that exist up front,
+//! and an ordered list of instructions with the outcome each must have.
+//! [`run`] executes it against the examples' real programs, off-chain,
+//! through the same entrypoints and runtime stubs the constraint tests
+//! use, and returns the walkthrough line by line. Educators write a new
+//! attack as a `.ron` file under the example's `scenarios/` directory;
+//! no Rust is needed.
+//!
+//! ```ron
+//! Scenario(
+//!     title: "Anyone can withdraw from someone else's vault",
+//!     program: "vault",
+//!     actors: {"alice": 5_000_000_000, "mallory": 1_000_000_000},
+//!     accounts: {"vault": Keypair},
+//!     steps: [
+//!         Invoke(
+//!             instruction: "vulnerable_withdraw",
+//!             accounts: [Mut("vault"), SignerMut("mallory")],
+//!             args: [U64(1_000_000_000)],
+//!         ),
+//!     ],
+//! )
+//! ```
+//!
+//! Programs that export an `attack_narrative()` need no script: [`narrative::script`]
+//! generates the scenario from the narrative's steps.
+
+pub mod engine;
+pub mod narrative;
+pub mod programs;
+pub mod script;
+
+pub use engine::{run, Failure};
+pub use script::Scenario;
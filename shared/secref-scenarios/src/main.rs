@@ -0,0 +1,72 @@
+//! `secref-scenarios <script.ron>...`: run scenario scripts and print their walkthroughs.
+//!
+//! `secref-scenarios describe <program>` prints a program's attack narrative,
+//! and `secref-scenarios narrate <program>` runs the scenario generated from it.
+
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use secref_scenarios::programs::{narrative, NARRATIVES};
+use secref_scenarios::{narrative::script, run, Scenario};
+
+const USAGE: &str = "usage: secref-scenarios <script.ron>...\n       \
+                     secref-scenarios describe <program>\n       \
+                     secref-scenarios narrate <program>";
+
+fn main() -> ExitCode {
+    let args: Vec<OsString> = std::env::args_os().skip(1).collect();
+    let words: Vec<Option<&str>> = args.iter().map(|arg| arg.to_str()).collect();
+    match words.as_slice() {
+        [] => {
+            eprintln!("{USAGE}");
+            ExitCode::FAILURE
+        }
+        [Some("describe"), Some(program)] => match narrative(program) {
+            Some(narrative) => {
+                print!("{narrative}");
+                ExitCode::SUCCESS
+            }
+            None => unknown(program),
+        },
+        [Some("narrate"), Some(program)] => match narrative(program) {
+            Some(narrative) => match run(&script(&narrative)) {
+                Ok(walkthrough) => {
+                    println!("{}", walkthrough.join("\n"));
+                    ExitCode::SUCCESS
+                }
+                Err(failure) => {
+                    eprintln!("{program}: {failure}");
+                    ExitCode::FAILURE
+                }
+            },
+            None => unknown(program),
+        },
+        _ => run_scripts(&args.into_iter().map(PathBuf::from).collect::<Vec<_>>()),
+    }
+}
+
+fn unknown(program: &str) -> ExitCode {
+    let known: Vec<&str> = NARRATIVES.iter().map(|(name, _)| *name).collect();
+    eprintln!("{program} has no attack narrative; these do: {}", known.join(", "));
+    ExitCode::FAILURE
+}
+
+fn run_scripts(scripts: &[PathBuf]) -> ExitCode {
+    let mut failed = false;
+    for script in scripts {
+        let outcome = Scenario::load(script).and_then(|scenario| run(&scenario).map_err(|failure| failure.to_string()));
+        match outcome {
+            Ok(walkthrough) => println!("{}\n", walkthrough.join("\n")),
+            Err(error) => {
+                eprintln!("{}: {error}\n", script.display());
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,93 @@
+//! Scenarios generated from the attack narratives programs export.
+//!
+//! A program's `attack_narrative()` (see `shared/secref-narrative`) tells
+//! its exploit as data; [`script`] turns it into the [`Scenario`] that
+//! performs it, so `describe` and the walkthrough come from the same steps.
+
+use secref_narrative::{self as narrative, Narrative};
+
+use crate::script::{Account, Call, Check, Expect, Meta, Scenario, Step, Value};
+
+/// The scenario a narrative describes: each step narrated, invoked, then checked
+pub fn script(narrative: &Narrative) -> Scenario {
+    let steps = narrative
+        .steps
+        .iter()
+        .flat_map(|step| {
+            let call = Call {
+                program: None,
+                instruction: Some(step.instruction.to_string()),
+                accounts: step.accounts.iter().map(meta).collect(),
+                args: step.args.iter().map(value).collect(),
+                expect: match step.expect {
+                    narrative::Expect::Ok => Expect::Ok,
+                    narrative::Expect::Fails(error) => Expect::Fails(error.to_string()),
+                },
+            };
+            [Step::Note(format!("{} {}", step.actor, step.action)), Step::Invoke(call)]
+                .into_iter()
+                .chain(step.effects.iter().map(|effect| Step::Assert(check(effect))))
+        })
+        .collect();
+
+    Scenario {
+        title: narrative.title.to_string(),
+        program: narrative.program.to_string(),
+        programs: Vec::new(),
+        actors: narrative.actors.iter().map(|actor| (actor.name.to_string(), actor.lamports)).collect(),
+        accounts: narrative
+            .accounts
+            .iter()
+            .map(|(name, account)| (name.to_string(), self::account(account, narrative.program)))
+            .collect(),
+        steps,
+    }
+}
+
+fn account(account: &narrative::Account, program: &str) -> Account {
+    match account {
+        narrative::Account::Keypair => Account::Keypair,
+        narrative::Account::Pda(seeds) => Account::Pda {
+            program: None,
+            seeds: seeds.iter().map(value).collect(),
+        },
+        narrative::Account::Zeroed(space) => Account::Zeroed {
+            owner: program.to_string(),
+            space: *space,
+        },
+        narrative::Account::Empty => Account::Empty,
+    }
+}
+
+fn meta(meta: &narrative::Meta) -> Meta {
+    match *meta {
+        narrative::Meta::Read(name) => Meta::Read(name.to_string()),
+        narrative::Meta::Mut(name) => Meta::Mut(name.to_string()),
+        narrative::Meta::Signer(name) => Meta::Signer(name.to_string()),
+        narrative::Meta::SignerMut(name) => Meta::SignerMut(name.to_string()),
+    }
+}
+
+fn check(effect: &narrative::Effect) -> Check {
+    match *effect {
+        narrative::Effect::Lamports(account, lamports) => Check::Lamports(account.to_string(), lamports),
+        narrative::Effect::Field {
+            account, offset, value, ..
+        } => Check::Field(account.to_string(), offset, self::value(&value)),
+        narrative::Effect::Closed(account) => Check::Closed(account.to_string()),
+    }
+}
+
+fn value(value: &narrative::Value) -> Value {
+    match *value {
+        narrative::Value::Bool(value) => Value::Bool(value),
+        narrative::Value::U8(value) => Value::U8(value),
+        narrative::Value::U16(value) => Value::U16(value),
+        narrative::Value::U32(value) => Value::U32(value),
+        narrative::Value::U64(value) => Value::U64(value),
+        narrative::Value::I64(value) => Value::I64(value),
+        narrative::Value::Key(name) => Value::Key(name.to_string()),
+        narrative::Value::Bump(name) => Value::Bump(name.to_string()),
+        narrative::Value::Str(value) => Value::Str(value.to_string()),
+    }
+}
@@ -0,0 +1,123 @@
+//! Every example program a script can name, by crate name.
+
+use secref_invariants::{program, Program};
+use secref_narrative::Narrative;
+
+/// The programs, in example order
+///
+/// Most examples keep Anchor's placeholder id, so several programs share
+/// one; a scenario only deploys the programs it names.
+pub const PROGRAMS: &[(&str, Program)] = &[
+    ("vault", program!(vault)),
+    ("admin_vault", program!(admin_vault)),
+    ("unsafe_cpi", program!(unsafe_cpi)),
+    ("arithmetic_vault", program!(arithmetic_vault)),
+    ("reinit_vault", program!(reinit_vault)),
+    ("airdrop_list", program!(airdrop_list)),
+    ("profile_registry", program!(profile_registry)),
+    ("position_manager", program!(position_manager)),
+    ("voucher_redeemer", program!(voucher_redeemer)),
+    ("signed_withdrawals", program!(signed_withdrawals)),
+    (
+        "quote_router",
+        Program {
+            id: quote_router::ID,
+            entry: quote_router::process_instruction,
+        },
+    ),
+    ("storage_sponsor", program!(storage_sponsor)),
+    ("coin_flip", program!(coin_flip)),
+    ("payment_gateway", program!(payment_gateway)),
+    ("plugin_registry", program!(plugin_registry)),
+    ("credit_ledger", program!(credit_ledger)),
+    ("vault_bank", program!(vault_bank)),
+    ("mock_lender", program!(mock_lender)),
+    ("token_voting", program!(token_voting)),
+    ("lp_pool", program!(lp_pool)),
+    ("lending_toy", program!(lending_toy)),
+    ("order_expiry", program!(order_expiry)),
+    ("payment_processor", program!(payment_processor)),
+    ("swap_router", program!(swap_router)),
+    ("timelock_treasury", program!(timelock_treasury)),
+    ("vault_factory", program!(vault_factory)),
+    ("points_ledger", program!(points_ledger)),
+    ("log_spoofer", program!(log_spoofer)),
+    ("rewards_distributor", program!(rewards_distributor)),
+    ("price_history", program!(price_history)),
+    ("basket_rebalancer", program!(basket_rebalancer)),
+    ("loan_desk", program!(loan_desk)),
+    ("price_quoter", program!(price_quoter)),
+    ("quote_spoofer", program!(quote_spoofer)),
+    ("fake_system", program!(fake_system)),
+    ("vault_watchdog", program!(vault_watchdog)),
+    ("watched_vault", program!(watched_vault)),
+    ("feed_squatter", program!(feed_squatter)),
+    ("lending_market", program!(lending_market)),
+    ("price_feed", program!(price_feed)),
+    ("nft_rental", program!(nft_rental)),
+    ("nft_marketplace", program!(nft_marketplace)),
+    ("bonding_curve_sale", program!(bonding_curve_sale)),
+    ("referral_rewards", program!(referral_rewards)),
+    ("payment_splitter", program!(payment_splitter)),
+    ("subscriptions", program!(subscriptions)),
+    ("bridge_inbox", program!(bridge_inbox)),
+    ("airdrop_claim", program!(airdrop_claim)),
+    ("ata_escrow", program!(ata_escrow)),
+    ("compliance_vault", program!(compliance_vault)),
+    ("deposit_receipts", program!(deposit_receipts)),
+    ("voucher_tree", program!(voucher_tree)),
+    ("relayed_payments", program!(relayed_payments)),
+    ("premium_club", program!(premium_club)),
+    ("guarded_pool", program!(guarded_pool)),
+    ("vault_router", program!(vault_router)),
+    ("steady_vault", program!(steady_vault)),
+    ("boosted_vault", program!(boosted_vault)),
+    ("rogue_vault", program!(rogue_vault)),
+    ("stake_pool", program!(stake_pool)),
+    ("delegation_manager", program!(delegation_manager)),
+    ("pool_router", program!(pool_router)),
+    ("invoice_desk", program!(invoice_desk)),
+    ("limited_vault", program!(limited_vault)),
+    ("hooked_vault", program!(hooked_vault)),
+    ("naive_hook", program!(naive_hook)),
+    ("guarded_hook", program!(guarded_hook)),
+    ("confidential_vault", program!(confidential_vault)),
+    ("collection_rewards", program!(collection_rewards)),
+    ("payout_crank", program!(payout_crank)),
+    ("gasless_notes", program!(gasless_notes)),
+    ("token_desk", program!(token_desk)),
+    ("vesting_grants", program!(vesting_grants)),
+    ("order_book", program!(order_book)),
+    ("collateral_hub", program!(collateral_hub)),
+    ("checkout", program!(checkout)),
+    ("redemption_desk", program!(redemption_desk)),
+    ("withdraw_queue", program!(withdraw_queue)),
+    ("strategy_vault", program!(strategy_vault)),
+    ("receipt_vault", program!(receipt_vault)),
+];
+
+/// The program a script calls `name`
+pub fn program(name: &str) -> Option<Program> {
+    PROGRAMS
+        .iter()
+        .find(|(program, _)| *program == name)
+        .map(|&(_, program)| program)
+}
+
+/// A program crate's `attack_narrative`
+pub type AttackNarrative = fn() -> Narrative;
+
+/// The programs that tell their attack as an `attack_narrative()`, by crate name
+pub const NARRATIVES: &[(&str, AttackNarrative)] = &[
+    ("vault", vault::attack_narrative),
+    ("admin_vault", admin_vault::attack_narrative),
+    ("withdraw_queue", withdraw_queue::attack_narrative),
+];
+
+/// The attack narrative of the program called `name`
+pub fn narrative(name: &str) -> Option<Narrative> {
+    NARRATIVES
+        .iter()
+        .find(|(program, _)| *program == name)
+        .map(|(_, narrative)| narrative())
+}
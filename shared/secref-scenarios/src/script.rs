@@ -0,0 +1,301 @@
+//! The script format: what a scenario file deserializes into.
+//!
+//! Scripts are RON. Every account is referred to by a name the script
+//! gives it; names resolve to deterministic addresses, so a scenario runs
+//! the same way every time.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use ron::extensions::Extensions;
+use serde::Deserialize;
+
+/// One walkthrough: who is involved, what exists up front, and what happens
+#[derive(Debug, Deserialize)]
+pub struct Scenario {
+    pub title: String,
+    /// The program instructions run against unless they name another
+    pub program: String,
+    /// Further programs the instructions or their CPIs reach
+    #[serde(default)]
+    pub programs: Vec<String>,
+    /// Funded wallets that can sign, with their lamports
+    #[serde(default)]
+    pub actors: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub accounts: BTreeMap<String, Account>,
+    pub steps: Vec<Step>,
+}
+
+/// An account that exists, or has an address, before the first step
+#[derive(Debug, Deserialize)]
+pub enum Account {
+    /// A fresh keypair: empty and unfunded, but able to sign
+    Keypair,
+    /// A program address; `program` defaults to the scenario's
+    Pda {
+        #[serde(default)]
+        program: Option<String>,
+        seeds: Vec<Value>,
+    },
+    /// An address holding nothing and able to sign nothing
+    Empty,
+    /// `space` zeroed bytes owned by `owner`, rent-exempt, as a client creates them for a native program
+    Zeroed { owner: String, space: usize },
+    /// An executable account no example program runs at: a deployed program the scenario never calls
+    Executable,
+    /// An initialized SPL mint
+    Mint {
+        authority: String,
+        #[serde(default)]
+        decimals: u8,
+        #[serde(default)]
+        supply: u64,
+    },
+    /// An initialized SPL token account
+    TokenAccount {
+        mint: String,
+        owner: String,
+        #[serde(default)]
+        amount: u64,
+    },
+    /// An initialized Token-2022 mint whose transfers call `hook`, with `authority` also the hook authority
+    HookedMint {
+        authority: String,
+        #[serde(default)]
+        decimals: u8,
+        #[serde(default)]
+        supply: u64,
+        hook: String,
+    },
+    /// An initialized Token-2022 account of a hooked mint
+    HookedTokenAccount {
+        mint: String,
+        owner: String,
+        #[serde(default)]
+        amount: u64,
+    },
+    /// An initialized Token-2022 mint allowing confidential transfers, with `authority` also the confidential transfer authority
+    ConfidentialMint {
+        authority: String,
+        #[serde(default)]
+        decimals: u8,
+        #[serde(default)]
+        supply: u64,
+    },
+    /// An initialized Token-2022 account with room for confidential transfers; with `confidential`,
+    /// configured under the ElGamal key of its own name and holding that many tokens confidentially
+    ConfidentialTokenAccount {
+        mint: String,
+        owner: String,
+        #[serde(default)]
+        amount: u64,
+        #[serde(default)]
+        confidential: Option<u64>,
+    },
+    /// An initialized Token-2022 mint holding `extensions`, in order, each naming accounts by name
+    Token2022Mint {
+        authority: String,
+        #[serde(default)]
+        decimals: u8,
+        #[serde(default)]
+        supply: u64,
+        #[serde(default)]
+        extensions: Vec<MintExtension>,
+    },
+    /// An initialized Token-2022 account with no extensions of its own
+    Token2022Account {
+        mint: String,
+        owner: String,
+        #[serde(default)]
+        amount: u64,
+    },
+    /// A verified proof, in a ZK Token Proof context state account, that the ElGamal key named `key` is valid
+    PubkeyValidityProof { key: String },
+    /// A verified proof, in a ZK Token Proof context state account, that the configured `source` can
+    /// confidentially send `amount` to an account configured under the ElGamal key named `destination_key`
+    TransferProof {
+        source: String,
+        destination_key: String,
+        amount: u64,
+    },
+    /// Metaplex metadata for `mint` at its PDA, with verified `(creator, share)` entries
+    Metadata {
+        mint: String,
+        seller_fee_basis_points: u16,
+        #[serde(default)]
+        creators: Vec<(String, u8)>,
+    },
+    /// An initialized SPL token account at `owner`'s associated token address for `mint`
+    AssociatedTokenAccount {
+        mint: String,
+        owner: String,
+        #[serde(default)]
+        amount: u64,
+    },
+    /// An initialized, undelegated stake account holding `lamports`, locked until `locked_until` unless `custodian` signs
+    StakeAccount {
+        staker: String,
+        withdrawer: String,
+        lamports: u64,
+        #[serde(default)]
+        locked_until: i64,
+        #[serde(default)]
+        custodian: Option<String>,
+    },
+    /// A validator's vote account for `node`, keeping `commission` percent of rewards;
+    /// with `owner`, the same bytes in an account another program owns
+    VoteAccount {
+        node: String,
+        commission: u8,
+        #[serde(default)]
+        owner: Option<String>,
+    },
+}
+
+/// A pointer or group extension of a `Token2022Mint`; pointers name the account they point at, often the mint itself
+#[derive(Debug, Deserialize)]
+pub enum MintExtension {
+    MetadataPointer(String),
+    TokenMetadata {
+        update_authority: String,
+        name: String,
+        symbol: String,
+    },
+    GroupPointer(String),
+    TokenGroup { update_authority: String, max_size: u32 },
+    GroupMemberPointer(String),
+    TokenGroupMember { group: String, member_number: u32 },
+}
+
+/// One thing that happens, in order
+#[derive(Debug, Deserialize)]
+pub enum Step {
+    /// Narration, printed as-is in the walkthrough
+    Note(String),
+    /// One instruction sent as its own transaction
+    Invoke(Call),
+    /// Instructions sent together: they run in order and commit only if all succeed
+    ///
+    /// A call expected to fail must be the last one, since the runtime
+    /// stops at the first failure.
+    Transaction(Vec<Call>),
+    /// Give a named account more lamports
+    Airdrop(String, u64),
+    /// Set the clock's Unix timestamp
+    Warp(i64),
+    /// Set the clock's slot
+    WarpSlot(u64),
+    Assert(Check),
+}
+
+/// One instruction and the outcome it must have
+#[derive(Debug, Deserialize)]
+pub struct Call {
+    /// Defaults to the scenario's program; `system_program`,
+    /// `token_program`, `token_2022_program`, `associated_token_program`, `stake_program`,
+    /// `ed25519_program`, `memo_program` and `compute_budget_program` take their native instruction data as `args`
+    #[serde(default)]
+    pub program: Option<String>,
+    /// The Anchor instruction name; without it the data is just the encoded `args`
+    #[serde(default)]
+    pub instruction: Option<String>,
+    pub accounts: Vec<Meta>,
+    #[serde(default)]
+    pub args: Vec<Value>,
+    #[serde(default)]
+    pub expect: Expect,
+}
+
+/// An instruction's account, by name, with the flags the client sends
+#[derive(Debug, Deserialize)]
+pub enum Meta {
+    Read(String),
+    Mut(String),
+    Signer(String),
+    SignerMut(String),
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub enum Expect {
+    #[default]
+    Ok,
+    /// An Anchor error name (`ConstraintHasOne`, or the program's own),
+    /// a `ProgramError` as it prints (`MissingRequiredSignature`, `Custom(0)`),
+    /// or `ProgramFailedToComplete` for a program that panics
+    Fails(String),
+}
+
+/// State that must hold after the steps so far
+#[derive(Debug, Deserialize)]
+pub enum Check {
+    Lamports(String, u64),
+    /// The SPL token balance of a token account
+    Tokens(String, u64),
+    /// The bytes at `offset` past the 8-byte Anchor discriminator encode `value`
+    Field(String, usize, Value),
+    /// The bytes at `offset` from the start of the data encode `value`, for native programs' accounts
+    Data(String, usize, Value),
+    /// The account's program owner
+    Owner(String, String),
+    /// No lamports and no data left
+    Closed(String),
+    /// A line the last instruction logged contains this text
+    Logged(String),
+    /// The pending and available confidential balances of a token account, decrypted with the ElGamal key named
+    Confidential(String, String, u64, u64),
+}
+
+/// An argument or seed, Borsh-encoded as the program reads it
+#[derive(Clone, Debug, Deserialize)]
+pub enum Value {
+    Bool(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    I64(i64),
+    /// A named account's address
+    Key(String),
+    /// The bump of a named `Pda` account, as a `u8`
+    Bump(String),
+    /// Length-prefixed UTF-8; the bytes alone as a seed
+    Str(String),
+    /// UTF-8 with no length prefix, as the Memo program reads its data
+    Utf8(String),
+    /// Length-prefixed bytes; the bytes alone as a seed
+    Bytes(Vec<u8>),
+    /// A balance encrypted under a fresh AE key: the 36-byte decryptable balances confidential transfers carry
+    Decryptable(u64),
+    /// Fixed-size: the values back to back, as an array or a struct's fields
+    Array(Vec<Value>),
+    /// Length-prefixed values
+    Vec(Vec<Value>),
+    /// The values back to back, length-prefixed as one `Vec<u8>`, for programs that parse their own payloads
+    Encoded(Vec<Value>),
+    Option(Option<Box<Value>>),
+    /// An enum variant: its index, then its fields
+    Variant(u8, Vec<Value>),
+    /// SHA-256 of the values back to back, as `[u8; 32]`; strings and byte strings are hashed without their length prefix
+    Sha256(Vec<Value>),
+    /// The data of an Ed25519 program instruction: each named signer's
+    /// real signature over its message (the values back to back), with
+    /// all offsets pointing into the instruction itself
+    Ed25519(Vec<(String, Vec<Value>)>),
+}
+
+impl Scenario {
+    /// Parse a script; scripts may write `Invoke(program: ..)` without doubled parentheses
+    pub fn parse(source: &str) -> Result<Self, String> {
+        ron::Options::default()
+            .with_default_extension(Extensions::UNWRAP_VARIANT_NEWTYPES | Extensions::IMPLICIT_SOME)
+            .from_str(source)
+            .map_err(|error| error.to_string())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let source = std::fs::read_to_string(path).map_err(|error| format!("{}: {error}", path.display()))?;
+        Self::parse(&source).map_err(|error| format!("{}: {error}", path.display()))
+    }
+}
@@ -0,0 +1,144 @@
+//! How the engine runs a script: expectations, rollback, signers and cleanup.
+
+use secref_scenarios::{run, Scenario};
+
+fn scenario(program: &str, accounts: &str, steps: &str) -> Scenario {
+    let source = format!(
+        r#"Scenario(
+            title: "test",
+            program: "{program}",
+            actors: {{"alice": 10_000_000_000, "mallory": 1_000_000_000}},
+            accounts: {{{accounts}}},
+            steps: [{steps}],
+        )"#
+    );
+    Scenario::parse(&source).expect("test script parses")
+}
+
+/// Alice's secure vault holding 1000, created in the first step
+fn vault_with(steps: &str) -> Scenario {
+    scenario(
+        "vault",
+        r#""vault": Keypair"#,
+        &format!(
+            r#"Invoke(
+                instruction: "secure_initialize",
+                accounts: [SignerMut("vault"), Signer("alice"), SignerMut("alice"), Read("system_program")],
+                args: [U64(1000)],
+            ),
+            {steps}"#
+        ),
+    )
+}
+
+#[test]
+fn an_instruction_that_succeeds_against_its_expected_failure_fails_the_script() {
+    let failure = run(&vault_with(
+        r#"Invoke(
+            instruction: "secure_withdraw",
+            accounts: [Mut("vault"), Signer("alice")],
+            args: [U64(1000)],
+            expect: Fails("ConstraintHasOne"),
+        )"#,
+    ))
+    .unwrap_err();
+    assert_eq!(failure.step, 2);
+    assert!(
+        failure.message.contains("succeeded, expected ConstraintHasOne"),
+        "{failure}"
+    );
+}
+
+#[test]
+fn an_instruction_that_fails_with_another_error_fails_the_script() {
+    let failure = run(&vault_with(
+        r#"Invoke(
+            instruction: "secure_withdraw",
+            accounts: [Mut("vault"), Signer("mallory")],
+            args: [U64(1000)],
+            expect: Fails("ConstraintSigner"),
+        )"#,
+    ))
+    .unwrap_err();
+    assert!(
+        failure
+            .message
+            .contains("failed with ConstraintHasOne, expected ConstraintSigner"),
+        "{failure}"
+    );
+    // The failure explains the error it did not expect
+    assert!(
+        failure.message.contains("error 2001 ConstraintHasOne (Anchor): "),
+        "{failure}"
+    );
+}
+
+#[test]
+fn a_failed_transaction_leaves_no_writes_behind() {
+    run(&vault_with(
+        r#"Transaction([
+            (
+                instruction: "secure_withdraw",
+                accounts: [Mut("vault"), Signer("alice")],
+                args: [U64(400)],
+            ),
+            (
+                instruction: "secure_withdraw",
+                accounts: [Mut("vault"), Signer("mallory")],
+                args: [U64(600)],
+                expect: Fails("ConstraintHasOne"),
+            ),
+        ]),
+        Assert(Field("vault", 32, U64(1000)))"#,
+    ))
+    .unwrap();
+}
+
+#[test]
+fn only_actors_and_keypairs_sign() {
+    let failure = run(&scenario(
+        "vault",
+        r#""vault": Empty"#,
+        r#"Invoke(
+            instruction: "secure_initialize",
+            accounts: [SignerMut("vault"), Signer("alice"), SignerMut("alice"), Read("system_program")],
+            args: [U64(1000)],
+        )"#,
+    ))
+    .unwrap_err();
+    assert!(failure.message.contains("`vault` cannot sign"), "{failure}");
+}
+
+#[test]
+fn accounts_left_without_lamports_are_wiped() {
+    run(&scenario(
+        "vault",
+        "",
+        r#"Invoke(
+            program: "system_program",
+            accounts: [SignerMut("mallory"), Mut("alice")],
+            args: [U32(2), U64(1_000_000_000)],
+        ),
+        Assert(Closed("mallory")),
+        Assert(Lamports("alice", 11_000_000_000))"#,
+    ))
+    .unwrap();
+}
+
+#[test]
+fn a_program_that_panics_fails_to_complete() {
+    run(&scenario(
+        "lending_toy",
+        r#""market": Pda(seeds: [Str("market"), Key("alice")])"#,
+        r#"Invoke(
+            instruction: "initialize_market",
+            accounts: [Mut("market"), SignerMut("alice"), Read("system_program")],
+        ),
+        Invoke(
+            instruction: "vulnerable_accrue_interest",
+            accounts: [Mut("market")],
+            expect: Fails("ProgramFailedToComplete"),
+        )"#,
+    ))
+    .unwrap();
+}
@@ -0,0 +1,42 @@
+//! Every program's attack narrative runs as the scenario it describes.
+
+use secref_scenarios::narrative::script;
+use secref_scenarios::programs::{program, NARRATIVES};
+use secref_scenarios::run;
+
+#[test]
+fn every_narrative_runs_as_told() {
+    let mut failures = Vec::new();
+    for (name, narrative) in NARRATIVES {
+        let narrative = narrative();
+        assert_eq!(narrative.program, *name, "{name}'s narrative attacks another program");
+        if let Err(failure) = run(&script(&narrative)) {
+            failures.push(format!("{name}: {failure}"));
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
+
+#[test]
+fn narratives_name_their_actors_and_programs() {
+    for (name, narrative) in NARRATIVES {
+        let narrative = narrative();
+        assert!(program(narrative.program).is_some(), "{name} narrates an unknown program");
+        assert!(narrative.refusals().next().is_some(), "{name} never shows the fix refusing the attack");
+        for step in narrative.steps {
+            assert!(narrative.actor(step.actor).is_some(), "{name}: {} is not an actor", step.actor);
+        }
+    }
+}
+
+#[test]
+fn generated_walkthroughs_narrate_each_step() {
+    let (_, narrative) = NARRATIVES[0];
+    let narrative = narrative();
+    let walkthrough = run(&script(&narrative)).expect("the narrative runs");
+    assert_eq!(walkthrough[0], narrative.title);
+    for step in narrative.steps {
+        let note = format!("- {} {}", step.actor, step.action);
+        assert!(walkthrough.contains(&note), "no `{note}` in {walkthrough:#?}");
+    }
+}
@@ -0,0 +1,53 @@
+//! Every feature-gated program's `SECREF_XREF` points at its handlers.
+
+use std::fs;
+
+use secref_xref::{Index, ProgramXref, Side};
+
+/// The programs built with `#[feature_gated]`, in example order
+const PROGRAMS: &[&ProgramXref] = &[
+    &vault::SECREF_XREF, &admin_vault::SECREF_XREF, &unsafe_cpi::SECREF_XREF, &arithmetic_vault::SECREF_XREF,
+    &reinit_vault::SECREF_XREF, &airdrop_list::SECREF_XREF, &profile_registry::SECREF_XREF,
+    &position_manager::SECREF_XREF, &voucher_redeemer::SECREF_XREF, &signed_withdrawals::SECREF_XREF,
+    &storage_sponsor::SECREF_XREF, &coin_flip::SECREF_XREF, &payment_gateway::SECREF_XREF,
+    &plugin_registry::SECREF_XREF, &credit_ledger::SECREF_XREF, &vault_bank::SECREF_XREF,
+    &token_voting::SECREF_XREF, &lp_pool::SECREF_XREF, &lending_toy::SECREF_XREF, &order_expiry::SECREF_XREF,
+    &payment_processor::SECREF_XREF, &swap_router::SECREF_XREF, &timelock_treasury::SECREF_XREF,
+    &vault_factory::SECREF_XREF, &points_ledger::SECREF_XREF, &rewards_distributor::SECREF_XREF,
+    &price_history::SECREF_XREF, &basket_rebalancer::SECREF_XREF, &loan_desk::SECREF_XREF,
+    &watched_vault::SECREF_XREF, &lending_market::SECREF_XREF, &nft_rental::SECREF_XREF,
+    &nft_marketplace::SECREF_XREF, &bonding_curve_sale::SECREF_XREF, &referral_rewards::SECREF_XREF,
+    &payment_splitter::SECREF_XREF, &subscriptions::SECREF_XREF, &bridge_inbox::SECREF_XREF,
+    &airdrop_claim::SECREF_XREF, &ata_escrow::SECREF_XREF, &compliance_vault::SECREF_XREF,
+    &deposit_receipts::SECREF_XREF, &voucher_tree::SECREF_XREF, &relayed_payments::SECREF_XREF,
+    &premium_club::SECREF_XREF, &guarded_pool::SECREF_XREF, &vault_router::SECREF_XREF, &stake_pool::SECREF_XREF,
+    &delegation_manager::SECREF_XREF, &pool_router::SECREF_XREF, &invoice_desk::SECREF_XREF,
+    &limited_vault::SECREF_XREF, &confidential_vault::SECREF_XREF, &collection_rewards::SECREF_XREF,
+    &payout_crank::SECREF_XREF, &gasless_notes::SECREF_XREF, &token_desk::SECREF_XREF, &vesting_grants::SECREF_XREF,
+    &order_book::SECREF_XREF, &collateral_hub::SECREF_XREF, &checkout::SECREF_XREF, &redemption_desk::SECREF_XREF,
+    &withdraw_queue::SECREF_XREF,
+    &strategy_vault::SECREF_XREF, &receipt_vault::SECREF_XREF,
+];
+
+#[test]
+fn every_finding_has_a_vulnerable_and_a_secure_handler() {
+    let index = Index::new(PROGRAMS);
+    for finding in index.findings() {
+        for side in [Side::Vulnerable, Side::Secure] {
+            assert!(!finding.spans(side).is_empty(), "{} has no {} handler", finding.vulnerability, side.as_str());
+        }
+    }
+    assert!(index.finding("02").is_some());
+}
+
+#[test]
+fn spans_cover_the_handlers_they_name() {
+    for span in Index::new(PROGRAMS).findings().flat_map(|finding| [&finding.vulnerable, &finding.secure]).flatten() {
+        let source = fs::read_to_string(&span.path).unwrap_or_else(|_| panic!("{span} is not a file"));
+        let lines: Vec<&str> = source.lines().collect();
+        let start = lines[*span.lines.start() as usize - 1];
+        let end = lines[*span.lines.end() as usize - 1];
+        assert!(start.contains(&format!("fn {}", span.handler)), "{span} starts at `{start}`");
+        assert_eq!(end.trim(), "}", "{span} ends at `{end}`");
+    }
+}
@@ -0,0 +1,807 @@
+//! Owned account state that can be lent to `try_accounts` as an `AccountInfo`.
+
+use anchor_lang::prelude::*;
+#[cfg(feature = "lookup-table")]
+use anchor_lang::solana_program::address_lookup_table;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use anchor_lang::solana_program::instruction::Instruction;
+#[cfg(feature = "stake")]
+use anchor_lang::solana_program::stake::{
+    self,
+    state::{Authorized, Lockup},
+};
+#[cfg(feature = "stake")]
+use anchor_lang::solana_program::vote;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::solana_program::sysvar::instructions::{
+    construct_instructions_data, store_current_index, BorrowedAccountMeta, BorrowedInstruction,
+};
+use anchor_lang::system_program;
+#[cfg(feature = "confidential-transfer")]
+use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::encryption::elgamal::{ElGamalKeypair, ElGamalPubkey};
+
+/// Lamports given to signers, enough to pay for any account in the examples
+pub const SIGNER_LAMPORTS: u64 = 10_000_000_000;
+
+/// Offset of the key in a frame, after the original data length (u32)
+const FRAME_KEY: usize = 4;
+/// Offset of the data in a frame, after the key and the current data length (u64)
+const FRAME_DATA: usize = FRAME_KEY + 32 + 8;
+
+/// One account as the runtime would pass it to an instruction
+#[derive(Clone, Debug)]
+pub struct TestAccount {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub executable: bool,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    /// The key and data as the runtime serializes them for the last [`info`](Self::info)
+    ///
+    /// `AccountInfo::realloc`, which Anchor's `close` calls, reads the
+    /// original data length 4 bytes before the key, writes the new length
+    /// 8 bytes before the data, and may grow the data by up to
+    /// `MAX_PERMITTED_DATA_INCREASE` in place, so all of that has to be
+    /// memory the account owns.
+    frame: Vec<u8>,
+}
+
+impl TestAccount {
+    /// A writable, rent-exempt account with a fresh key
+    pub fn new(owner: Pubkey, data: Vec<u8>) -> Self {
+        Self {
+            key: Pubkey::new_unique(),
+            owner,
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            executable: false,
+            is_signer: false,
+            is_writable: true,
+            frame: Vec::new(),
+        }
+    }
+
+    /// A funded wallet that signed the transaction
+    pub fn signer() -> Self {
+        Self::new(system_program::ID, vec![])
+            .with_lamports(SIGNER_LAMPORTS)
+            .signed()
+    }
+
+    /// A System-owned account holding `lamports` and no data
+    pub fn system(lamports: u64) -> Self {
+        Self::new(system_program::ID, vec![]).with_lamports(lamports)
+    }
+
+    /// An Anchor account: discriminator and serialized `value`, owned by its program
+    pub fn anchor<T: AccountSerialize + Owner>(value: &T) -> Self {
+        let mut data = Vec::new();
+        value.try_serialize(&mut data).expect("account serializes");
+        Self::new(T::owner(), data)
+    }
+
+    /// An address `init` has not created yet, with `space` bytes reserved
+    ///
+    /// Off-chain account data cannot grow, so the buffer is allocated up
+    /// front and the emulated `create_account` only checks its length.
+    pub fn uninitialized(space: usize) -> Self {
+        Self::new(system_program::ID, vec![0; space]).with_lamports(0)
+    }
+
+    /// A deployed program at `id`
+    pub fn program(id: Pubkey) -> Self {
+        Self::new(bpf_loader_upgradeable::ID, vec![])
+            .at(id)
+            .executable()
+            .read_only()
+    }
+
+    /// The System Program
+    pub fn system_program() -> Self {
+        Self::program(system_program::ID)
+    }
+
+    /// The instructions sysvar; only its address is checked by account constraints
+    pub fn instructions_sysvar() -> Self {
+        Self::new(sysvar::ID, vec![]).at(sysvar::instructions::ID).read_only()
+    }
+
+    /// The instructions sysvar of a transaction made of `instructions`, executing the one at `current`
+    ///
+    /// Programs introspecting their transaction read these instructions;
+    /// the one at `current` stands in for the instruction under test and
+    /// is not itself executed.
+    pub fn instructions_sysvar_of(instructions: &[Instruction], current: u16) -> Self {
+        let borrowed: Vec<_> = instructions
+            .iter()
+            .map(|instruction| BorrowedInstruction {
+                program_id: &instruction.program_id,
+                accounts: instruction
+                    .accounts
+                    .iter()
+                    .map(|meta| BorrowedAccountMeta {
+                        pubkey: &meta.pubkey,
+                        is_signer: meta.is_signer,
+                        is_writable: meta.is_writable,
+                    })
+                    .collect(),
+                data: &instruction.data,
+            })
+            .collect();
+        let mut data = construct_instructions_data(&borrowed);
+        store_current_index(&mut data, current);
+        Self::new(sysvar::ID, data).at(sysvar::instructions::ID).read_only()
+    }
+
+    /// Move the account to `key`
+    pub fn at(mut self, key: Pubkey) -> Self {
+        self.key = key;
+        self
+    }
+
+    /// The same account at a fresh address: an impostor for `has_one` and seeds checks
+    pub fn rekeyed(self) -> Self {
+        self.at(Pubkey::new_unique())
+    }
+
+    pub fn owned_by(mut self, owner: Pubkey) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    pub fn with_lamports(mut self, lamports: u64) -> Self {
+        self.lamports = lamports;
+        self
+    }
+
+    pub fn signed(mut self) -> Self {
+        self.is_signer = true;
+        self
+    }
+
+    pub fn unsigned(mut self) -> Self {
+        self.is_signer = false;
+        self
+    }
+
+    pub fn read_only(mut self) -> Self {
+        self.is_writable = false;
+        self
+    }
+
+    pub fn executable(mut self) -> Self {
+        self.executable = true;
+        self
+    }
+
+    /// Change the stored Anchor account in place, keeping any bytes past it
+    ///
+    /// For impostors that sit at the right address but record the wrong
+    /// values, which only `has_one` and `constraint` checks catch.
+    pub fn edit<T: AccountSerialize + AccountDeserialize>(&mut self, change: impl FnOnce(&mut T)) {
+        let mut value = T::try_deserialize(&mut &self.data[..]).expect("account deserializes");
+        change(&mut value);
+
+        let mut data = Vec::new();
+        value.try_serialize(&mut data).expect("account serializes");
+        if data.len() > self.data.len() {
+            self.data.resize(data.len(), 0);
+        }
+        self.data[..data.len()].copy_from_slice(&data);
+    }
+
+    /// The stored Anchor account, e.g. after `execute!` ran an instruction on it
+    pub fn state<T: AccountDeserialize>(&self) -> T {
+        T::try_deserialize(&mut &self.data[..]).expect("account deserializes")
+    }
+
+    /// Borrow the account as the `AccountInfo` a program would receive
+    ///
+    /// The data is lent from a copy laid out as the runtime lays it out;
+    /// call [`sync`](Self::sync) once the `AccountInfo` is dropped to keep
+    /// what the program wrote.
+    pub fn info(&mut self) -> AccountInfo<'_> {
+        let len = self.data.len();
+        self.frame.clear();
+        // Up to 7 spare bytes in front, to start the frame wherever the data lands aligned
+        self.frame.resize(7 + FRAME_DATA + len + MAX_PERMITTED_DATA_INCREASE, 0);
+        let start = self.frame_start();
+        let frame = &mut self.frame[start..];
+        frame[..FRAME_KEY].copy_from_slice(&(len as u32).to_ne_bytes());
+        frame[FRAME_KEY..FRAME_KEY + 32].copy_from_slice(self.key.as_ref());
+        frame[FRAME_KEY + 32..FRAME_DATA].copy_from_slice(&(len as u64).to_ne_bytes());
+        frame[FRAME_DATA..FRAME_DATA + len].copy_from_slice(&self.data);
+
+        let (head, tail) = frame.split_at_mut(FRAME_KEY + 32);
+        // SAFETY: `Pubkey` is a transparent `[u8; 32]`, so any 32 bytes are a valid key
+        let key = unsafe { &*(head[FRAME_KEY..].as_ptr() as *const Pubkey) };
+        AccountInfo::new(
+            key,
+            self.is_signer,
+            self.is_writable,
+            &mut self.lamports,
+            &mut tail[8..8 + len],
+            &self.owner,
+            self.executable,
+            0,
+        )
+    }
+
+    /// Keep the data written through the last [`info`](Self::info), at whatever length it was left
+    pub fn sync(&mut self) {
+        if self.frame.is_empty() {
+            return;
+        }
+        let frame = &self.frame[self.frame_start()..];
+        let len = u64::from_ne_bytes(frame[FRAME_KEY + 32..FRAME_DATA].try_into().expect("8 bytes"));
+        self.data = frame[FRAME_DATA..FRAME_DATA + len as usize].to_vec();
+    }
+
+    /// Where the frame starts in its buffer, so that the data length before the data is aligned as a u64
+    fn frame_start(&self) -> usize {
+        self.frame[FRAME_DATA..].as_ptr().align_offset(8)
+    }
+}
+
+#[cfg(feature = "spl")]
+impl TestAccount {
+    /// An initialized SPL token account
+    pub fn token_account(mint: Pubkey, authority: Pubkey, amount: u64) -> Self {
+        use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+        use anchor_spl::token::spl_token::state::{Account, AccountState};
+
+        let mut data = vec![0; Account::LEN];
+        Account {
+            mint,
+            owner: authority,
+            amount,
+            state: AccountState::Initialized,
+            ..Account::default()
+        }
+        .pack_into_slice(&mut data);
+        Self::new(anchor_spl::token::ID, data)
+    }
+
+    /// This token account with `delegate` approved to spend `delegated_amount`
+    pub fn with_delegate(mut self, delegate: Pubkey, delegated_amount: u64) -> Self {
+        use anchor_spl::token::spl_token::solana_program::program_option::COption;
+        use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+        use anchor_spl::token::spl_token::state::Account;
+
+        let mut state = Account::unpack(&self.data).expect("a token account");
+        state.delegate = COption::Some(delegate);
+        state.delegated_amount = delegated_amount;
+        state.pack_into_slice(&mut self.data);
+        self
+    }
+
+    /// This token account closable by `close_authority` instead of its owner
+    pub fn with_close_authority(mut self, close_authority: Pubkey) -> Self {
+        use anchor_spl::token::spl_token::solana_program::program_option::COption;
+        use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+        use anchor_spl::token::spl_token::state::Account;
+
+        let mut state = Account::unpack(&self.data).expect("a token account");
+        state.close_authority = COption::Some(close_authority);
+        state.pack_into_slice(&mut self.data);
+        self
+    }
+
+    /// This token account frozen by its mint's freeze authority
+    pub fn frozen(mut self) -> Self {
+        use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+        use anchor_spl::token::spl_token::state::{Account, AccountState};
+
+        let mut state = Account::unpack(&self.data).expect("a token account");
+        state.state = AccountState::Frozen;
+        state.pack_into_slice(&mut self.data);
+        self
+    }
+
+    /// An initialized SPL mint
+    pub fn mint(authority: Pubkey, decimals: u8, supply: u64) -> Self {
+        use anchor_spl::token::spl_token::solana_program::program_option::COption;
+        use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+        use anchor_spl::token::spl_token::state::Mint;
+
+        let mut data = vec![0; Mint::LEN];
+        Mint {
+            mint_authority: COption::Some(authority),
+            supply,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        }
+        .pack_into_slice(&mut data);
+        Self::new(anchor_spl::token::ID, data)
+    }
+
+    /// The SPL Token program
+    pub fn token_program() -> Self {
+        Self::program(anchor_spl::token::ID)
+    }
+}
+
+#[cfg(feature = "token-2022")]
+impl TestAccount {
+    /// An initialized Token-2022 mint whose transfers `hook` must approve
+    ///
+    /// `authority` is both the mint authority and the authority that may
+    /// change the hook (the `TransferHook` extension).
+    pub fn hooked_mint(authority: Pubkey, decimals: u8, supply: u64, hook: Pubkey) -> Self {
+        use anchor_spl::token::spl_token::solana_program::program_option::COption;
+        use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::TransferHook;
+        use anchor_spl::token_2022::spl_token_2022::extension::{
+            BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+        };
+        use anchor_spl::token_2022::spl_token_2022::state::Mint;
+
+        let space = ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::TransferHook]).unwrap();
+        let mut data = vec![0; space];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        let extension = state.init_extension::<TransferHook>(true).unwrap();
+        extension.authority = Some(authority).try_into().unwrap();
+        extension.program_id = Some(hook).try_into().unwrap();
+        state.base = Mint {
+            mint_authority: COption::Some(authority),
+            supply,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        Self::new(anchor_spl::token_2022::ID, data)
+    }
+
+    /// An initialized Token-2022 account of a hooked mint, with the `TransferHookAccount` extension such accounts get
+    pub fn hooked_token_account(mint: Pubkey, authority: Pubkey, amount: u64) -> Self {
+        use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::TransferHookAccount;
+        use anchor_spl::token_2022::spl_token_2022::extension::{
+            BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+        };
+        use anchor_spl::token_2022::spl_token_2022::state::{Account, AccountState};
+
+        let space =
+            ExtensionType::try_calculate_account_len::<Account>(&[ExtensionType::TransferHookAccount]).unwrap();
+        let mut data = vec![0; space];
+        let mut state = StateWithExtensionsMut::<Account>::unpack_uninitialized(&mut data).unwrap();
+        state.init_extension::<TransferHookAccount>(true).unwrap();
+        state.base = Account {
+            mint,
+            owner: authority,
+            amount,
+            state: AccountState::Initialized,
+            ..Account::default()
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        Self::new(anchor_spl::token_2022::ID, data)
+    }
+
+    /// This hooked token account mid-transfer, as Token-2022 flags it while the hook runs
+    pub fn transferring(mut self) -> Self {
+        use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::TransferHookAccount;
+        use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensionsMut, StateWithExtensionsMut};
+        use anchor_spl::token_2022::spl_token_2022::state::Account;
+
+        let mut state = StateWithExtensionsMut::<Account>::unpack(&mut self.data).expect("a hooked token account");
+        state.get_extension_mut::<TransferHookAccount>().unwrap().transferring = true.into();
+        self
+    }
+
+    /// The Token-2022 program
+    pub fn token_2022_program() -> Self {
+        Self::program(anchor_spl::token_2022::ID)
+    }
+
+    /// An initialized Token-2022 mint without extensions
+    ///
+    /// The `with_*` builders below add the metadata and group extensions,
+    /// as Token-2022 would: pointers name any address, with the mint
+    /// authority as their authority, and the metadata, group and member
+    /// data record the mint's key, so move the mint with [`at`](Self::at)
+    /// before adding them.
+    pub fn token_2022_mint(authority: Pubkey, decimals: u8, supply: u64) -> Self {
+        Self::mint(authority, decimals, supply).owned_by(anchor_spl::token_2022::ID)
+    }
+
+    /// This mint with a `MetadataPointer` naming `address`
+    pub fn with_metadata_pointer(self, address: Pubkey) -> Self {
+        use anchor_spl::token_2022::spl_token_2022::extension::metadata_pointer::MetadataPointer;
+        use anchor_spl::token_2022::spl_token_2022::extension::BaseStateWithExtensionsMut;
+
+        self.extend_mint(std::mem::size_of::<MetadataPointer>(), |state, authority| {
+            let pointer = state.init_extension::<MetadataPointer>(true).unwrap();
+            pointer.authority = authority.try_into().unwrap();
+            pointer.metadata_address = Some(address).try_into().unwrap();
+        })
+    }
+
+    /// This mint with its own `TokenMetadata`, naming `update_authority`, who need not have signed
+    pub fn with_token_metadata(self, update_authority: Pubkey, name: &str, symbol: &str) -> Self {
+        use anchor_spl::token_2022::spl_token_2022::extension::BaseStateWithExtensionsMut;
+        use anchor_spl::token_interface::spl_token_metadata_interface::state::TokenMetadata;
+
+        let metadata = TokenMetadata {
+            update_authority: Some(update_authority).try_into().unwrap(),
+            mint: self.key,
+            name: name.into(),
+            symbol: symbol.into(),
+            ..TokenMetadata::default()
+        };
+        self.extend_mint(metadata.tlv_size_of().unwrap() - 4, |state, _| {
+            state.init_variable_len_extension(&metadata, true).unwrap();
+        })
+    }
+
+    /// This mint with a `GroupPointer` naming `address`
+    pub fn with_group_pointer(self, address: Pubkey) -> Self {
+        use anchor_spl::token_2022::spl_token_2022::extension::group_pointer::GroupPointer;
+        use anchor_spl::token_2022::spl_token_2022::extension::BaseStateWithExtensionsMut;
+
+        self.extend_mint(std::mem::size_of::<GroupPointer>(), |state, authority| {
+            let pointer = state.init_extension::<GroupPointer>(true).unwrap();
+            pointer.authority = authority.try_into().unwrap();
+            pointer.group_address = Some(address).try_into().unwrap();
+        })
+    }
+
+    /// This mint with its own `TokenGroup`, which only `update_authority` adds members to
+    pub fn with_token_group(self, update_authority: Pubkey, max_size: u32) -> Self {
+        use anchor_spl::token_2022::spl_token_2022::extension::BaseStateWithExtensionsMut;
+        use spl_token_group_interface::state::TokenGroup;
+
+        let group = TokenGroup::new(&self.key, Some(update_authority).try_into().unwrap(), max_size);
+        self.extend_mint(std::mem::size_of::<TokenGroup>(), |state, _| {
+            *state.init_extension::<TokenGroup>(true).unwrap() = group;
+        })
+    }
+
+    /// This mint with a `GroupMemberPointer` naming `address`
+    pub fn with_group_member_pointer(self, address: Pubkey) -> Self {
+        use anchor_spl::token_2022::spl_token_2022::extension::group_member_pointer::GroupMemberPointer;
+        use anchor_spl::token_2022::spl_token_2022::extension::BaseStateWithExtensionsMut;
+
+        self.extend_mint(std::mem::size_of::<GroupMemberPointer>(), |state, authority| {
+            let pointer = state.init_extension::<GroupMemberPointer>(true).unwrap();
+            pointer.authority = authority.try_into().unwrap();
+            pointer.member_address = Some(address).try_into().unwrap();
+        })
+    }
+
+    /// This mint with its own `TokenGroupMember`: member `member_number` of `group`, as its update authority added it
+    pub fn with_token_group_member(self, group: Pubkey, member_number: u32) -> Self {
+        use anchor_spl::token_2022::spl_token_2022::extension::BaseStateWithExtensionsMut;
+        use spl_token_group_interface::state::TokenGroupMember;
+
+        let member = TokenGroupMember::new(&self.key, &group, member_number);
+        self.extend_mint(std::mem::size_of::<TokenGroupMember>(), |state, _| {
+            *state.init_extension::<TokenGroupMember>(true).unwrap() = member;
+        })
+    }
+
+    /// Grow this Token-2022 mint by an extension of `length` bytes, written by `init` with the mint authority
+    fn extend_mint(
+        mut self,
+        length: usize,
+        init: impl FnOnce(
+            &mut anchor_spl::token_2022::spl_token_2022::extension::StateWithExtensionsMut<
+                anchor_spl::token_2022::spl_token_2022::state::Mint,
+            >,
+            Option<Pubkey>,
+        ),
+    ) -> Self {
+        use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+        use anchor_spl::token_2022::spl_token_2022::extension::{AccountType, StateWithExtensionsMut};
+        use anchor_spl::token_2022::spl_token_2022::state::{Account, Mint, Multisig};
+
+        assert_eq!(self.owner, anchor_spl::token_2022::ID, "extensions are added to Token-2022 mints");
+        if self.data.len() == Mint::LEN {
+            // The base mint, padded to a token account's length, then the account type
+            self.data.resize(Account::LEN, 0);
+            self.data.push(AccountType::Mint as u8);
+        }
+        // Type and length, then the value; Token-2022 never leaves a mint the length of a multisig
+        let mut len = self.data.len() + 4 + length;
+        if len == Multisig::LEN {
+            len += 4;
+        }
+        self.data.resize(len, 0);
+
+        let mut state = StateWithExtensionsMut::<Mint>::unpack(&mut self.data).expect("a Token-2022 mint");
+        let authority = state.base.mint_authority.into();
+        init(&mut state, authority);
+        self.lamports = Rent::default().minimum_balance(self.data.len());
+        self
+    }
+}
+
+#[cfg(feature = "confidential-transfer")]
+impl TestAccount {
+    /// An initialized Token-2022 mint that allows confidential transfers
+    ///
+    /// `authority` is both the mint authority and the confidential transfer
+    /// authority. Accounts are approved as soon as they are configured, and
+    /// there is no auditor.
+    pub fn confidential_mint(authority: Pubkey, decimals: u8, supply: u64) -> Self {
+        use anchor_spl::token::spl_token::solana_program::program_option::COption;
+        use anchor_spl::token_2022::spl_token_2022::extension::confidential_transfer::ConfidentialTransferMint;
+        use anchor_spl::token_2022::spl_token_2022::extension::{
+            BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+        };
+        use anchor_spl::token_2022::spl_token_2022::state::Mint;
+
+        let space =
+            ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::ConfidentialTransferMint]).unwrap();
+        let mut data = vec![0; space];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        let extension = state.init_extension::<ConfidentialTransferMint>(true).unwrap();
+        extension.authority = Some(authority).try_into().unwrap();
+        extension.auto_approve_new_accounts = true.into();
+        state.base = Mint {
+            mint_authority: COption::Some(authority),
+            supply,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        Self::new(anchor_spl::token_2022::ID, data)
+    }
+
+    /// An initialized Token-2022 account with room for the `ConfidentialTransferAccount` extension
+    ///
+    /// As `Reallocate` leaves an account before `ConfigureAccount`: until
+    /// it is [`configured`](Self::configured), it takes public transfers only.
+    pub fn confidential_token_account(mint: Pubkey, authority: Pubkey, amount: u64) -> Self {
+        use anchor_spl::token_2022::spl_token_2022::extension::{
+            BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+        };
+        use anchor_spl::token_2022::spl_token_2022::state::{Account, AccountState};
+
+        let space =
+            ExtensionType::try_calculate_account_len::<Account>(&[ExtensionType::ConfidentialTransferAccount]).unwrap();
+        let mut data = vec![0; space];
+        let mut state = StateWithExtensionsMut::<Account>::unpack_uninitialized(&mut data).unwrap();
+        state.base = Account {
+            mint,
+            owner: authority,
+            amount,
+            state: AccountState::Initialized,
+            ..Account::default()
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        Self::new(anchor_spl::token_2022::ID, data)
+    }
+
+    /// This account configured for confidential transfers under `elgamal`, with `available` tokens already confidential
+    ///
+    /// The state `ConfigureAccount`, `Deposit` and `ApplyPendingBalance`
+    /// leave behind: nothing pending, `available` in the available balance.
+    pub fn configured(mut self, elgamal: &ElGamalKeypair, available: u64) -> Self {
+        use anchor_spl::token_2022::spl_token_2022::extension::confidential_transfer::ConfidentialTransferAccount;
+        use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensionsMut, StateWithExtensionsMut};
+        use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::encryption::auth_encryption::AeKey;
+        use anchor_spl::token_2022::spl_token_2022::state::Account;
+
+        let mut state = StateWithExtensionsMut::<Account>::unpack(&mut self.data).expect("a Token-2022 account");
+        let extension = state
+            .init_extension::<ConfidentialTransferAccount>(false)
+            .expect("room for the confidential transfer extension");
+        extension.approved = true.into();
+        extension.elgamal_pubkey = (*elgamal.pubkey()).into();
+        extension.available_balance = elgamal.pubkey().encrypt(available).into();
+        extension.decryptable_available_balance = AeKey::new_rand().encrypt(available).into();
+        extension.allow_confidential_credits = true.into();
+        extension.allow_non_confidential_credits = true.into();
+        extension.maximum_pending_balance_credit_counter = u64::from(u16::MAX).into();
+        self
+    }
+
+    /// The pending and available balances of this configured account, decrypted with `elgamal`
+    pub fn confidential_balances(&self, elgamal: &ElGamalKeypair) -> (u64, u64) {
+        use anchor_spl::token_2022::spl_token_2022::extension::confidential_transfer::ConfidentialTransferAccount;
+        use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+        use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::encryption::elgamal::ElGamalCiphertext;
+        use anchor_spl::token_2022::spl_token_2022::state::Account;
+
+        let state = StateWithExtensions::<Account>::unpack(&self.data).expect("a Token-2022 account");
+        let extension = state
+            .get_extension::<ConfidentialTransferAccount>()
+            .expect("a configured account");
+        let decrypt = |ciphertext| {
+            ElGamalCiphertext::try_from(ciphertext)
+                .ok()
+                .and_then(|ciphertext| ciphertext.decrypt_u32(elgamal.secret()))
+                .expect("a balance that decrypts")
+        };
+        let pending = decrypt(extension.pending_balance_lo) + (decrypt(extension.pending_balance_hi) << 16);
+        (pending, decrypt(extension.available_balance))
+    }
+
+    /// The context state the ZK Token Proof program leaves once it has verified that `elgamal` is a valid key
+    ///
+    /// What `ConfigureAccount` reads the account's new key from.
+    pub fn pubkey_validity_proof(elgamal: &ElGamalKeypair) -> Self {
+        use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::instruction::PubkeyValidityProofContext;
+        use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::zk_token_proof_instruction::ProofType;
+
+        let context = PubkeyValidityProofContext {
+            pubkey: (*elgamal.pubkey()).into(),
+        };
+        proof_context(ProofType::PubkeyValidity, &context)
+    }
+
+    /// The context state of a verified proof that this configured account can confidentially send `amount` to `destination`
+    ///
+    /// What a confidential `Transfer` reads the amount's ciphertexts and
+    /// the source's new available balance from. `elgamal` is the source's
+    /// key, and `destination` the key the destination account is
+    /// configured under.
+    pub fn transfer_proof(&self, elgamal: &ElGamalKeypair, destination: &ElGamalPubkey, amount: u64) -> Self {
+        use anchor_spl::token_2022::spl_token_2022::extension::confidential_transfer::ConfidentialTransferAccount;
+        use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+        use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::encryption::elgamal::ElGamalCiphertext;
+        use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::instruction::transfer::TransferData;
+        use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::instruction::ZkProofData;
+        use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::zk_token_proof_instruction::ProofType;
+        use anchor_spl::token_2022::spl_token_2022::state::Account;
+
+        let state = StateWithExtensions::<Account>::unpack(&self.data).expect("a Token-2022 account");
+        let available: ElGamalCiphertext = state
+            .get_extension::<ConfidentialTransferAccount>()
+            .expect("a configured account")
+            .available_balance
+            .try_into()
+            .expect("a valid ciphertext");
+        let spendable = available
+            .decrypt_u32(elgamal.secret())
+            .expect("a balance that decrypts");
+        // No auditor: the mint's unset auditor key compares equal to all zeroes
+        let auditor = ElGamalPubkey::from_bytes(&[0; 32]).unwrap();
+        let proof = TransferData::new(amount, (spendable, &available), elgamal, (destination, &auditor))
+            .expect("a transfer the balance covers");
+        proof_context(ProofType::Transfer, proof.context_data())
+    }
+}
+
+/// A ZK Token Proof program context state holding `context`, closable by nobody in particular
+#[cfg(feature = "confidential-transfer")]
+fn proof_context<T: anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::instruction::Pod>(
+    proof_type: anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::zk_token_proof_instruction::ProofType,
+    context: &T,
+) -> TestAccount {
+    use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::zk_token_proof_program;
+    use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::zk_token_proof_state::ProofContextState;
+
+    let data = ProofContextState::encode(&Pubkey::new_unique(), proof_type, context);
+    TestAccount::new(zk_token_proof_program::id(), data).read_only()
+}
+
+#[cfg(feature = "metadata")]
+impl TestAccount {
+    /// Metaplex metadata for `mint` at its PDA, with verified `(creator, share)` entries
+    ///
+    /// `mpl-token-metadata` serializes with its own Borsh release, so the
+    /// fields are written in its layout here and read back with its parser.
+    pub fn metadata(mint: Pubkey, seller_fee_basis_points: u16, creators: &[(Pubkey, u8)]) -> Self {
+        use anchor_spl::metadata::mpl_token_metadata::accounts::Metadata;
+        use anchor_spl::metadata::mpl_token_metadata::types::{Key, TokenStandard};
+
+        fn write<T: AnchorSerialize>(data: &mut Vec<u8>, value: T) {
+            value.serialize(data).expect("metadata field serializes");
+        }
+
+        let creators: Vec<(Pubkey, bool, u8)> = creators
+            .iter()
+            .map(|&(address, share)| (address, true, share))
+            .collect();
+        let mut data = Vec::new();
+        write(&mut data, Key::MetadataV1 as u8);
+        write(&mut data, Pubkey::new_unique()); // update_authority
+        write(&mut data, mint);
+        write(&mut data, "Test NFT"); // name
+        write(&mut data, "TEST"); // symbol
+        write(&mut data, ""); // uri
+        write(&mut data, seller_fee_basis_points);
+        write(&mut data, Some(creators));
+        write(&mut data, (true, true)); // primary_sale_happened, is_mutable
+        write(&mut data, None::<u8>); // edition_nonce
+        write(&mut data, Some(TokenStandard::NonFungible as u8));
+        write(&mut data, [0u8; 4]); // collection, uses, collection_details, programmable_config: None
+        Metadata::safe_deserialize(&data).expect("metadata layout matches mpl-token-metadata");
+
+        Self::new(anchor_spl::metadata::ID, data).at(Metadata::find_pda(&mint).0)
+    }
+
+    /// The Metaplex Token Metadata program
+    pub fn metadata_program() -> Self {
+        Self::program(anchor_spl::metadata::ID)
+    }
+}
+
+#[cfg(feature = "stake")]
+impl TestAccount {
+    /// An initialized, undelegated stake account holding `lamports`, rent-exempt reserve included
+    pub fn stake_account(authorized: Authorized, lockup: Lockup, lamports: u64) -> Self {
+        use anchor_lang::solana_program::stake::state::{Meta, StakeStateV2};
+
+        let mut data = Vec::with_capacity(StakeStateV2::size_of());
+        StakeStateV2::Initialized(Meta {
+            rent_exempt_reserve: Rent::default().minimum_balance(StakeStateV2::size_of()),
+            authorized,
+            lockup,
+        })
+        .serialize(&mut data)
+        .expect("stake state serializes");
+        data.resize(StakeStateV2::size_of(), 0);
+        Self::new(stake::program::ID, data).with_lamports(lamports)
+    }
+
+    /// The Stake program
+    pub fn stake_program() -> Self {
+        Self::program(stake::program::ID)
+    }
+
+    /// The clock sysvar; programs read the clock through `Clock::get`, so only its address is checked
+    pub fn clock_sysvar() -> Self {
+        Self::new(sysvar::ID, vec![]).at(sysvar::clock::ID).read_only()
+    }
+
+    /// The stake history sysvar; only its address is checked
+    pub fn stake_history_sysvar() -> Self {
+        Self::new(sysvar::ID, vec![]).at(sysvar::stake_history::ID).read_only()
+    }
+
+    /// The deprecated stake config account `DelegateStake` still takes; only its address is checked
+    #[allow(deprecated)]
+    pub fn stake_config() -> Self {
+        Self::new(stake::config::ID, vec![]).at(stake::config::ID).read_only()
+    }
+
+    /// A validator's vote account, owned by the Vote program, keeping `commission` percent of rewards
+    pub fn vote_account(node: Pubkey, authorized_withdrawer: Pubkey, commission: u8) -> Self {
+        use anchor_lang::solana_program::vote::state::{VoteInit, VoteState, VoteStateVersions};
+
+        let vote_state = VoteState::new(
+            &VoteInit {
+                node_pubkey: node,
+                authorized_voter: node,
+                authorized_withdrawer,
+                commission,
+            },
+            &Clock::default(),
+        );
+        let mut data = vec![0; VoteState::size_of()];
+        VoteState::serialize(&VoteStateVersions::new_current(vote_state), &mut data).expect("vote state serializes");
+        Self::new(vote::program::ID, data).with_lamports(Rent::default().minimum_balance(VoteState::size_of()))
+    }
+}
+
+#[cfg(feature = "lookup-table")]
+impl TestAccount {
+    /// An address lookup table `authority` may extend, listing `addresses`, rent-exempt
+    pub fn lookup_table(authority: Pubkey, addresses: &[Pubkey]) -> Self {
+        use anchor_lang::solana_program::address_lookup_table::state::{AddressLookupTable, LookupTableMeta};
+
+        let data = AddressLookupTable {
+            meta: LookupTableMeta::new(authority),
+            addresses: addresses.into(),
+        }
+        .serialize_for_tests()
+        .expect("lookup table serializes");
+        let lamports = Rent::default().minimum_balance(data.len());
+        Self::new(address_lookup_table::program::ID, data).with_lamports(lamports)
+    }
+
+    /// The Address Lookup Table program
+    pub fn address_lookup_table_program() -> Self {
+        Self::program(address_lookup_table::program::ID)
+    }
+}
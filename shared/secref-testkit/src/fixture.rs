@@ -0,0 +1,80 @@
+//! The accounts and arguments of one instruction, by field name.
+
+use anchor_lang::prelude::*;
+
+use crate::account::TestAccount;
+
+/// A valid set of accounts for one account context, in field order
+///
+/// Accounts are named after the context's fields so a test can say which
+/// one it substitutes, and so failures name the account that was expected
+/// to trip a constraint.
+#[derive(Clone, Debug, Default)]
+pub struct Fixture {
+    accounts: Vec<(&'static str, TestAccount)>,
+    args: Vec<u8>,
+}
+
+impl Fixture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append the account for the next field of the context
+    pub fn with(mut self, name: &'static str, account: TestAccount) -> Self {
+        self.accounts.push((name, account));
+        self
+    }
+
+    /// Instruction arguments read by `#[instruction(...)]`
+    ///
+    /// Pass the generated `instruction::<Name>` struct; its Borsh encoding is
+    /// the argument bytes that follow the discriminator.
+    pub fn args<T: AnchorSerialize>(mut self, args: T) -> Self {
+        self.args = args.try_to_vec().expect("arguments serialize");
+        self
+    }
+
+    pub fn key(&self, name: &str) -> Pubkey {
+        self.account(name).key
+    }
+
+    pub fn account(&self, name: &str) -> &TestAccount {
+        self.accounts
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, account)| account)
+            .unwrap_or_else(|| panic!("fixture has no account named `{name}`"))
+    }
+
+    pub fn account_mut(&mut self, name: &str) -> &mut TestAccount {
+        self.accounts
+            .iter_mut()
+            .find(|(field, _)| *field == name)
+            .map(|(_, account)| account)
+            .unwrap_or_else(|| panic!("fixture has no account named `{name}`"))
+    }
+
+    /// Every account with its field name, in field order: to carry changed accounts into the next instruction
+    pub fn accounts(&self) -> impl Iterator<Item = (&'static str, &TestAccount)> {
+        self.accounts.iter().map(|(name, account)| (*name, account))
+    }
+
+    /// The stored Anchor account named `name`
+    pub fn state<T: AccountDeserialize>(&self, name: &str) -> T {
+        self.account(name).state()
+    }
+
+    /// Borrow every account as an `AccountInfo`, alongside the argument bytes
+    pub fn split(&mut self) -> (Vec<AccountInfo<'_>>, &[u8]) {
+        let infos = self.accounts.iter_mut().map(|(_, account)| account.info()).collect();
+        (infos, &self.args)
+    }
+
+    /// Keep the data written through the `AccountInfo`s from the last [`split`](Self::split)
+    pub fn sync(&mut self) {
+        for (_, account) in &mut self.accounts {
+            account.sync();
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Golden byte fixtures for account layouts.
+//!
+//! An account's bytes are an interface: clients, indexers and the program's
+//! own next version read them by offset. Reordering two fields, widening an
+//! integer or renaming an account (which changes its discriminator) compiles
+//! cleanly and passes every behavioural test, then misreads every account
+//! already on chain. [`assert_golden!`](crate::assert_golden) pins each
+//! account type to a fixture under the program's `golden/` directory, so a
+//! layout change shows up as a failing test and a fixture diff in review.
+//!
+//! Fixtures are hex dumps, 16 bytes a line. Run the tests with
+//! `SECREF_BLESS=1` to write missing or changed fixtures.
+
+use std::fs;
+use std::path::Path;
+
+use anchor_lang::__private::bytemuck;
+use anchor_lang::{AccountSerialize, ZeroCopy};
+
+/// Environment variable that makes [`check`] write fixtures instead of comparing them
+pub const BLESS_VAR: &str = "SECREF_BLESS";
+
+/// The bytes of an Anchor (Borsh) account, discriminator first
+pub fn anchor<T: AccountSerialize>(value: &T) -> Vec<u8> {
+    let mut data = Vec::new();
+    value.try_serialize(&mut data).expect("account serializes");
+    data
+}
+
+/// The bytes of a zero-copy account, discriminator first
+pub fn zero_copy<T: ZeroCopy>(value: &T) -> Vec<u8> {
+    [&T::DISCRIMINATOR[..], bytemuck::bytes_of(value)].concat()
+}
+
+/// `bytes` as a fixture: a header naming the layout, then offset and hex lines
+pub fn dump(name: &str, bytes: &[u8]) -> String {
+    let mut out = format!("# {name}, {} bytes\n", bytes.len());
+    for (line, chunk) in bytes.chunks(16).enumerate() {
+        let hex: Vec<String> = chunk.iter().map(|byte| format!("{byte:02x}")).collect();
+        out.push_str(&format!("{:08x}  {}\n", line * 16, hex.join(" ")));
+    }
+    out
+}
+
+/// The bytes of a fixture written by [`dump`]
+pub fn parse(fixture: &str) -> Vec<u8> {
+    fixture
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .flat_map(|line| line.split_whitespace().skip(1))
+        .map(|byte| u8::from_str_radix(byte, 16).expect("fixture byte is hex"))
+        .collect()
+}
+
+/// The bytes of the fixture `name` under `manifest_dir/golden`
+pub fn load(manifest_dir: &str, name: &str) -> Vec<u8> {
+    let path = Path::new(manifest_dir).join("golden").join(format!("{name}.hex"));
+    let fixture = fs::read_to_string(&path).unwrap_or_else(|_| panic!("no golden fixture at {}", path.display()));
+    parse(&fixture)
+}
+
+/// Compare `bytes` with the fixture `name` under `manifest_dir/golden`, or write it when blessing
+pub fn check(manifest_dir: &str, name: &str, bytes: &[u8]) {
+    let dir = Path::new(manifest_dir).join("golden");
+    let path = dir.join(format!("{name}.hex"));
+    let actual = dump(name, bytes);
+
+    if std::env::var_os(BLESS_VAR).is_some() {
+        fs::create_dir_all(&dir).expect("golden directory is writable");
+        fs::write(&path, actual).expect("golden fixture is writable");
+        return;
+    }
+    let Ok(expected) = fs::read_to_string(&path) else {
+        panic!("no golden fixture for `{name}` at {}; run the tests with {BLESS_VAR}=1 to write it", path.display());
+    };
+    if expected == actual {
+        return;
+    }
+    let golden = parse(&expected);
+    let offset = golden.iter().zip(bytes).position(|(a, b)| a != b).unwrap_or(golden.len().min(bytes.len()));
+    panic!(
+        "`{name}` no longer matches {}: {} bytes, was {}; first difference at offset {offset}\n\
+         If the layout change is intended, and accounts already on chain are migrated, \
+         re-run with {BLESS_VAR}=1 and commit the fixture.\n\nexpected:\n{expected}\nactual:\n{actual}",
+        path.display(),
+        bytes.len(),
+        golden.len(),
+    );
+}
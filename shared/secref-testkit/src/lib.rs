@@ -0,0 +1,175 @@
+//! Negative tests for Anchor account constraints.
+//!
+//! A constraint that no test can trip might as well not be there. Each
+//! assertion here runs a context's `try_accounts` twice, off-chain: once on
+//! a valid [`Fixture`], which must pass, and once with a single account
+//! substituted or edited, which must fail with one specific error. The
+//! passing run is what makes the failing one meaningful - it shows the
+//! changed account, and nothing else, is what the constraint rejected.
+//!
+//! ```ignore
+//! use secref_testkit::{assert_has_one_violation, assert_constraint_violation, Fixture, TestAccount};
+//!
+//! assert_has_one_violation!(SecureWithdraw, withdraw_fixture(), "authority");
+//! assert_constraint_violation!(
+//!     SecureWithdraw,
+//!     withdraw_fixture(),
+//!     "vault" => |vault| vault.owner = Pubkey::new_unique(),
+//!     ErrorCode::AccountOwnedByWrongProgram
+//! );
+//! ```
+//!
+//! The macros use `crate::ID` as the program id, so call them from tests
+//! inside the program crate.
+
+pub mod account;
+pub mod fixture;
+pub mod golden;
+pub mod runtime;
+
+pub use account::TestAccount;
+pub use fixture::Fixture;
+
+/// Run `<$ctx>::try_accounts` over a fixture, discarding the accounts on success
+// `crate::ID` is deliberately the calling program's id
+#[allow(clippy::crate_in_macro_def)]
+#[macro_export]
+macro_rules! try_accounts {
+    ($ctx:ty, $fixture:expr) => {{
+        $crate::runtime::enter(crate::ID);
+        let (infos, args) = $fixture.split();
+        let mut accounts = &infos[..];
+        <$ctx as ::anchor_lang::Accounts<'_, _>>::try_accounts(
+            &crate::ID,
+            &mut accounts,
+            args,
+            &mut ::core::default::Default::default(),
+            &mut ::std::collections::BTreeSet::new(),
+        )
+        .map(|_| ())
+    }};
+}
+
+/// Run a whole instruction - accounts, handler and exit - over a fixture
+///
+/// `$ix` is the generated `crate::instruction::<Name>` struct. The fixture
+/// is borrowed mutably and keeps whatever the instruction wrote, so a test
+/// can read the result back with [`Fixture::state`]. Where `try_accounts!`
+/// tests one context's constraints, this tests behaviour: whether an attack
+/// goes through, whatever check stops it.
+// `crate::ID` and `crate::entry` are deliberately the calling program's
+#[allow(clippy::crate_in_macro_def)]
+#[macro_export]
+macro_rules! execute {
+    ($fixture:expr, $ix:expr) => {{
+        use ::anchor_lang::InstructionData as _;
+        $crate::runtime::enter(crate::ID);
+        let data = $ix.data();
+        let fixture: &mut $crate::Fixture = $fixture;
+        let result = {
+            let (infos, _) = fixture.split();
+            crate::entry(&crate::ID, &infos, &data)
+        };
+        fixture.sync();
+        result
+    }};
+}
+
+/// Assert `$bytes` match the program's golden fixture `$name` (see [`golden`])
+///
+/// `$bytes` is an account's serialized form: [`golden::anchor`] for an
+/// `#[account]`, [`golden::zero_copy`] for an `#[account(zero_copy)]`, or
+/// whatever a native program's own packing writes.
+#[macro_export]
+macro_rules! assert_golden {
+    ($name:expr, $bytes:expr) => {
+        $crate::golden::check(env!("CARGO_MANIFEST_DIR"), $name, &$bytes)
+    };
+}
+
+/// Assert `$ctx` accepts `$fixture` but rejects it with `$error` once `$name` is changed
+///
+/// `$name` is either replaced (`"vault" => TestAccount::signer()`) or
+/// edited in place (`"vault" => |vault| vault.owner = other`).
+#[macro_export]
+macro_rules! assert_constraint_violation {
+    (@check $ctx:ty, $fixture:expr, $name:literal, $mutate:expr, $error:expr) => {{
+        let valid: $crate::Fixture = $fixture;
+
+        let mut baseline = valid.clone();
+        assert_eq!(
+            $crate::try_accounts!($ctx, baseline),
+            Ok(()),
+            "the unmodified fixture must pass {}",
+            stringify!($ctx)
+        );
+
+        let mut mutated = valid;
+        ($mutate)(mutated.account_mut($name));
+        assert_eq!(
+            $crate::try_accounts!($ctx, mutated),
+            Err(::anchor_lang::error::Error::from($error)),
+            "changing `{}` must trip {}",
+            $name,
+            stringify!($error)
+        );
+    }};
+    ($ctx:ty, $fixture:expr, $name:literal => |$account:ident| $edit:expr, $error:expr $(,)?) => {
+        $crate::assert_constraint_violation!(
+            @check $ctx, $fixture, $name,
+            |$account: &mut $crate::TestAccount| { $edit; },
+            $error
+        )
+    };
+    ($ctx:ty, $fixture:expr, $name:literal => $substitute:expr, $error:expr $(,)?) => {
+        $crate::assert_constraint_violation!(
+            @check $ctx, $fixture, $name,
+            |account: &mut $crate::TestAccount| *account = $substitute,
+            $error
+        )
+    };
+}
+
+/// Assert a `has_one` target at another address is rejected (default `ConstraintHasOne`)
+#[macro_export]
+macro_rules! assert_has_one_violation {
+    ($ctx:ty, $fixture:expr, $name:literal $(,)?) => {
+        $crate::assert_has_one_violation!($ctx, $fixture, $name, ::anchor_lang::error::ErrorCode::ConstraintHasOne)
+    };
+    ($ctx:ty, $fixture:expr, $name:literal, $error:expr $(,)?) => {
+        $crate::assert_constraint_violation!($ctx, $fixture, $name => |account| account.key = ::anchor_lang::prelude::Pubkey::new_unique(), $error)
+    };
+}
+
+/// Assert a PDA at an address its seeds do not derive is rejected (default `ConstraintSeeds`)
+#[macro_export]
+macro_rules! assert_seeds_violation {
+    ($ctx:ty, $fixture:expr, $name:literal $(,)?) => {
+        $crate::assert_seeds_violation!($ctx, $fixture, $name, ::anchor_lang::error::ErrorCode::ConstraintSeeds)
+    };
+    ($ctx:ty, $fixture:expr, $name:literal, $error:expr $(,)?) => {
+        $crate::assert_constraint_violation!($ctx, $fixture, $name => |account| account.key = ::anchor_lang::prelude::Pubkey::new_unique(), $error)
+    };
+}
+
+/// Assert an account owned by another program is rejected (default `AccountOwnedByWrongProgram`)
+#[macro_export]
+macro_rules! assert_owner_violation {
+    ($ctx:ty, $fixture:expr, $name:literal $(,)?) => {
+        $crate::assert_owner_violation!($ctx, $fixture, $name, ::anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram)
+    };
+    ($ctx:ty, $fixture:expr, $name:literal, $error:expr $(,)?) => {
+        $crate::assert_constraint_violation!($ctx, $fixture, $name => |account| account.owner = ::anchor_lang::prelude::Pubkey::new_unique(), $error)
+    };
+}
+
+/// Assert a required signature is enforced (default `AccountNotSigner`)
+#[macro_export]
+macro_rules! assert_signer_violation {
+    ($ctx:ty, $fixture:expr, $name:literal $(,)?) => {
+        $crate::assert_signer_violation!($ctx, $fixture, $name, ::anchor_lang::error::ErrorCode::AccountNotSigner)
+    };
+    ($ctx:ty, $fixture:expr, $name:literal, $error:expr $(,)?) => {
+        $crate::assert_constraint_violation!($ctx, $fixture, $name => |account| account.is_signer = false, $error)
+    };
+}
@@ -0,0 +1,1033 @@
+//! Just enough of the runtime for `try_accounts` and `execute!` to run off-chain.
+//!
+//! Account validation reads the Rent sysvar and, for `init`, calls the
+//! System Program. Outside the validator both are stubs that fail or do
+//! nothing, so this module installs stubs that answer sysvar reads with
+//! defaults and carry out the System instructions Anchor's `init` issues
+//! (plus, with the `spl` feature, the Token instruction behind
+//! `init, token::mint = ..`, minting, burning, token transfers (checked or not),
+//! approvals, revocations and authority changes, and the Associated Token program's `Create`
+//! and `CreateIdempotent`; with the `stake` feature, the Stake program's
+//! `Authorize`, `DelegateStake`, `Deactivate` and `Withdraw`; with the
+//! `lookup-table` feature, the Address Lookup Table program's
+//! `CreateLookupTable` and `ExtendLookupTable`; with the `token-2022`
+//! feature, Token-2022's `TransferChecked` and the transfer hook it runs; with
+//! the `confidential-transfer` feature, Token-2022's confidential transfer
+//! instructions, proofs read from context state accounts). The clock reads
+//! as slot 0 at the Unix epoch until a test sets it with [`warp_to`] and
+//! [`warp_to_slot`] or moves it on with [`warp_by`] and [`warp_by_slots`];
+//! [`clock`] reads it back. Nothing meters execution: the compute units left
+//! read as 200,000 until a test calls [`set_compute_units`].
+//!
+//! CPIs to other programs reach them only once [`register`]ed. The callee
+//! runs on copies of its accounts whose changes are copied back when it
+//! returns, as the runtime serializes accounts across a CPI. Return data
+//! is kept per thread, and so are the lines programs log, `emit!`'s
+//! "Program data:" lines included: rather than printed, they are kept for
+//! [`take_logs`], and [`take_events`] decodes the events among them.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::sync::Once;
+
+use anchor_lang::__private::base64::engine::general_purpose::STANDARD;
+use anchor_lang::__private::base64::Engine;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE, SUCCESS};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use anchor_lang::solana_program::program_utils::limited_deserialize;
+use anchor_lang::solana_program::system_instruction::{SystemError, SystemInstruction};
+use anchor_lang::system_program;
+use anchor_lang::Event;
+
+use crate::TestAccount;
+
+/// The `entry` function `#[program]` generates, or a native program's `process_instruction`
+pub type Entry = for<'info> fn(&Pubkey, &'info [AccountInfo<'info>], &[u8]) -> ProgramResult;
+
+static INSTALL: Once = Once::new();
+
+thread_local! {
+    /// Program whose accounts are being validated; signs for its PDAs in CPIs
+    static CALLER: Cell<Pubkey> = Cell::new(Pubkey::default());
+
+    /// `Clock::unix_timestamp` as programs on this thread read it
+    static UNIX_TIMESTAMP: Cell<i64> = const { Cell::new(0) };
+
+    /// `Clock::slot` as programs on this thread read it
+    static SLOT: Cell<u64> = const { Cell::new(0) };
+
+    /// Compute units `sol_remaining_compute_units` reports next, and what each report spends
+    static COMPUTE_UNITS: Cell<(u64, u64)> = const { Cell::new((200_000, 0)) };
+
+    /// Programs CPIs on this thread can reach, beyond the emulated ones
+    static PROGRAMS: RefCell<HashMap<Pubkey, Entry>> = RefCell::new(HashMap::new());
+
+    /// The last `set_return_data`, with the program that set it
+    static RETURN_DATA: RefCell<Option<(Pubkey, Vec<u8>)>> = const { RefCell::new(None) };
+
+    /// The accounts of the instruction running, when known; a CPI must target one of them
+    static INSTRUCTION_ACCOUNTS: RefCell<Option<Vec<Pubkey>>> = const { RefCell::new(None) };
+
+    /// Lines logged since the last [`enter`]
+    static LOGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// What the System Program returns for an address that already holds an account
+///
+/// `init` on an existing account fails with this, so reinitialization tests
+/// expect it.
+pub fn account_already_in_use() -> ProgramError {
+    ProgramError::Custom(SystemError::AccountAlreadyInUse as u32)
+}
+
+/// Install the stubs (once per process) and run CPIs as `program_id`
+///
+/// Each call starts a new instruction: the return data and the log are cleared.
+pub fn enter(program_id: Pubkey) {
+    INSTALL.call_once(|| {
+        set_syscall_stubs(Box::new(Stubs));
+    });
+    CALLER.with(|caller| caller.set(program_id));
+    RETURN_DATA.with(|data| data.replace(None));
+    INSTRUCTION_ACCOUNTS.with(|accounts| accounts.replace(None));
+    LOGS.with(|logs| logs.borrow_mut().clear());
+}
+
+/// Record the accounts the instruction just [`enter`]ed was sent
+///
+/// As on chain, a CPI can then only invoke a program among them; the
+/// call fails with `NotEnoughAccountKeys` otherwise. Without this, CPIs
+/// reach any program.
+pub fn instruction_accounts(accounts: &[Pubkey]) {
+    INSTRUCTION_ACCOUNTS.with(|known| known.replace(Some(accounts.to_vec())));
+}
+
+/// Let CPIs on this thread reach the program at `id`
+pub fn register(id: Pubkey, entry: Entry) {
+    PROGRAMS.with(|programs| programs.borrow_mut().insert(id, entry));
+}
+
+/// The lines logged since the last [`enter`], including those of any CPIs
+pub fn take_logs() -> Vec<String> {
+    LOGS.with(|logs| std::mem::take(&mut *logs.borrow_mut()))
+}
+
+/// The events of type `E` that `emit!` logged since the last [`enter`]
+///
+/// Decodes the "Program data:" lines of [`take_logs`] that carry `E`'s
+/// discriminator, in the order they were emitted. Like `take_logs`, it
+/// takes every line, events of other types included.
+pub fn take_events<E: Event>() -> Vec<E> {
+    take_logs()
+        .iter()
+        .filter_map(|line| line.strip_prefix("Program data: "))
+        .filter_map(|data| STANDARD.decode(data).ok())
+        .filter_map(|data| {
+            let mut payload = data.strip_prefix(&E::DISCRIMINATOR[..])?;
+            E::deserialize(&mut payload).ok()
+        })
+        .collect()
+}
+
+/// Set the time `Clock::get()` reports to instructions run on this thread
+pub fn warp_to(unix_timestamp: i64) {
+    UNIX_TIMESTAMP.with(|time| time.set(unix_timestamp));
+}
+
+/// Set the slot `Clock::get()` reports to instructions run on this thread
+pub fn warp_to_slot(slot: u64) {
+    SLOT.with(|current| current.set(slot));
+}
+
+/// Move the time `Clock::get()` reports on this thread forward by `seconds`, or back if negative
+pub fn warp_by(seconds: i64) {
+    UNIX_TIMESTAMP.with(|time| time.set(time.get() + seconds));
+}
+
+/// Move the slot `Clock::get()` reports on this thread forward by `slots`
+pub fn warp_by_slots(slots: u64) {
+    SLOT.with(|current| current.set(current.get() + slots));
+}
+
+/// The clock `Clock::get()` reports to instructions run on this thread
+///
+/// Only the slot and the time move; the epoch fields stay at their defaults.
+pub fn clock() -> Clock {
+    Clock {
+        slot: SLOT.with(Cell::get),
+        unix_timestamp: UNIX_TIMESTAMP.with(Cell::get),
+        ..Clock::default()
+    }
+}
+
+/// Set the compute units `sol_remaining_compute_units` reports to instructions run on this thread
+///
+/// Each report spends `per_query` units, standing in for the work a
+/// handler does between looking at the meter, so a loop that stops when
+/// the meter runs low stops after `remaining / per_query` checks.
+pub fn set_compute_units(remaining: u64, per_query: u64) {
+    COMPUTE_UNITS.with(|units| units.set((remaining, per_query)));
+}
+
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_log(&self, message: &str) {
+        LOGS.with(|logs| logs.borrow_mut().push(message.to_string()));
+    }
+
+    /// `emit!`'s line, as the validator writes it
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let encoded: Vec<String> = fields.iter().map(|field| STANDARD.encode(field)).collect();
+        self.sol_log(&format!("Program data: {}", encoded.join(" ")));
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        let program_id = CALLER.with(Cell::get);
+        RETURN_DATA.with(|stored| stored.replace((!data.is_empty()).then(|| (program_id, data.to_vec()))));
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        RETURN_DATA.with(|stored| stored.borrow().clone())
+    }
+
+    fn sol_remaining_compute_units(&self) -> u64 {
+        COMPUTE_UNITS.with(|units| {
+            let (remaining, per_query) = units.get();
+            units.set((remaining.saturating_sub(per_query), per_query));
+            remaining
+        })
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Clock) = clock() };
+        SUCCESS
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let reachable = INSTRUCTION_ACCOUNTS.with(|known| {
+            known
+                .borrow()
+                .as_ref()
+                .is_none_or(|accounts| accounts.contains(&instruction.program_id))
+        });
+        if !reachable {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let caller = CALLER.with(Cell::get);
+        let pda_signers = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, &caller))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|_| ProgramError::InvalidSeeds)?;
+        let cpi = Cpi {
+            instruction,
+            account_infos,
+            pda_signers: &pda_signers,
+        };
+
+        match instruction.program_id {
+            id if id == system_program::ID => invoke_system(&cpi),
+            #[cfg(feature = "spl")]
+            id if id == anchor_spl::token::ID => invoke_token(&cpi),
+            #[cfg(feature = "spl")]
+            id if id == anchor_spl::associated_token::ID => invoke_associated_token(&cpi),
+            #[cfg(feature = "token-2022")]
+            id if id == anchor_spl::token_2022::ID => invoke_token_2022(&cpi),
+            #[cfg(feature = "stake")]
+            id if id == anchor_lang::solana_program::stake::program::ID => invoke_stake(&cpi),
+            #[cfg(feature = "lookup-table")]
+            id if id == anchor_lang::solana_program::address_lookup_table::program::ID => invoke_lookup_table(&cpi),
+            id => match PROGRAMS.with(|programs| programs.borrow().get(&id).copied()) {
+                Some(entry) => invoke_registered(&cpi, entry),
+                None => panic!("CPIs to {id} are not emulated"),
+            },
+        }
+    }
+}
+
+/// Run a registered program on copies of the accounts it is passed, then copy its writes back
+///
+/// The callee signs for its own PDAs and sees only the signatures the
+/// caller holds or lends it. Return data is cleared when it starts, as
+/// on chain, so the caller reads only what this callee set.
+fn invoke_registered(cpi: &Cpi, entry: Entry) -> ProgramResult {
+    let mut copies = Vec::with_capacity(cpi.instruction.accounts.len());
+    for (index, meta) in cpi.instruction.accounts.iter().enumerate() {
+        let info = cpi.account(index)?;
+        let mut copy = TestAccount::new(*info.owner, info.try_borrow_data()?.to_vec())
+            .at(*info.key)
+            .with_lamports(info.lamports());
+        copy.executable = info.executable;
+        copy.is_signer = meta.is_signer;
+        copy.is_writable = meta.is_writable && info.is_writable;
+        copies.push(copy);
+    }
+
+    let caller = CALLER.with(|caller| caller.replace(cpi.instruction.program_id));
+    let keys = cpi.instruction.accounts.iter().map(|meta| meta.pubkey).collect();
+    let caller_accounts = INSTRUCTION_ACCOUNTS.with(|known| known.replace(Some(keys)));
+    RETURN_DATA.with(|data| data.replace(None));
+    let result = {
+        let infos: Vec<_> = copies.iter_mut().map(TestAccount::info).collect();
+        entry(&cpi.instruction.program_id, &infos, &cpi.instruction.data)
+    };
+    CALLER.with(|restored| restored.set(caller));
+    INSTRUCTION_ACCOUNTS.with(|known| known.replace(caller_accounts));
+    result?;
+
+    copies.iter_mut().for_each(TestAccount::sync);
+    for copy in copies.iter().filter(|copy| copy.is_writable) {
+        for info in cpi.account_infos.iter().filter(|info| *info.key == copy.key) {
+            **info.try_borrow_mut_lamports()? = copy.lamports;
+            if info.data_len() != copy.data.len() {
+                info.realloc(copy.data.len(), false)?;
+            }
+            info.try_borrow_mut_data()?.copy_from_slice(&copy.data);
+            if *info.owner != copy.owner {
+                info.assign(&copy.owner);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// An instruction being invoked, with the accounts the caller passed along
+struct Cpi<'a, 'info> {
+    instruction: &'a Instruction,
+    account_infos: &'a [AccountInfo<'info>],
+    pda_signers: &'a [Pubkey],
+}
+
+impl<'a, 'info> Cpi<'a, 'info> {
+    /// The instruction's `index`th account, checking any signature it needs
+    fn account(&self, index: usize) -> std::result::Result<&'a AccountInfo<'info>, ProgramError> {
+        let meta = self
+            .instruction
+            .accounts
+            .get(index)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let info = self
+            .account_infos
+            .iter()
+            .find(|info| *info.key == meta.pubkey)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if meta.is_signer && !info.is_signer && !self.pda_signers.contains(info.key) {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        Ok(info)
+    }
+
+    /// The instruction's `index`th account, which the callee requires to sign
+    fn signer(&self, index: usize) -> std::result::Result<&'a AccountInfo<'info>, ProgramError> {
+        let info = self.account(index)?;
+        if !self.instruction.accounts[index].is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        Ok(info)
+    }
+}
+
+fn invoke_system(cpi: &Cpi) -> ProgramResult {
+    let system_instruction: SystemInstruction =
+        limited_deserialize(&cpi.instruction.data, 1232).map_err(|_| ProgramError::InvalidInstructionData)?;
+    match system_instruction {
+        SystemInstruction::CreateAccount { lamports, space, owner } => {
+            let (from, to) = (cpi.signer(0)?, cpi.signer(1)?);
+            if to.lamports() != 0 {
+                return Err(account_already_in_use());
+            }
+            allocate(to, space)?;
+            move_lamports(from, to, lamports)?;
+            to.assign(&owner);
+        }
+        SystemInstruction::Transfer { lamports } => move_lamports(cpi.signer(0)?, cpi.account(1)?, lamports)?,
+        SystemInstruction::Allocate { space } => allocate(cpi.signer(0)?, space)?,
+        SystemInstruction::Assign { owner } => {
+            let account = cpi.signer(0)?;
+            if *account.owner != owner {
+                system_owned(account)?;
+                account.assign(&owner);
+            }
+        }
+        other => panic!("System instruction {other:?} is not emulated"),
+    }
+    Ok(())
+}
+
+/// `InitializeMint2` and `InitializeAccount3`, which Anchor issues for
+/// `init, mint::decimals = ..` and `init, token::mint = ..`, `MintTo` and
+/// `Burn`, and the `Transfer`, `Approve`, `Revoke`, `SetAuthority` and
+/// `CloseAccount` a program makes on a token account, delegates included
+#[cfg(feature = "spl")]
+fn invoke_token(cpi: &Cpi) -> ProgramResult {
+    use anchor_spl::token::spl_token::error::TokenError;
+    use anchor_spl::token::spl_token::instruction::TokenInstruction;
+    use anchor_spl::token::spl_token::solana_program::program_option::COption;
+    use anchor_spl::token::spl_token::solana_program::program_pack::{IsInitialized, Pack};
+    use anchor_spl::token::spl_token::state::{Account, AccountState, Mint};
+
+    fn token_owned(accounts: &[&AccountInfo]) -> ProgramResult {
+        if accounts.iter().any(|account| *account.owner != anchor_spl::token::ID) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        Ok(())
+    }
+
+    fn unpack(account: &AccountInfo) -> std::result::Result<Account, ProgramError> {
+        token_owned(&[account])?;
+        let state = Account::unpack(&account.try_borrow_data()?)?;
+        if state.is_frozen() {
+            return Err(TokenError::AccountFrozen.into());
+        }
+        Ok(state)
+    }
+
+    fn pack(state: Account, account: &AccountInfo) -> ProgramResult {
+        state.pack_into_slice(&mut account.try_borrow_mut_data()?);
+        Ok(())
+    }
+
+    fn transfer(
+        source: &AccountInfo,
+        destination: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+    ) -> ProgramResult {
+        let (mut from, mut to) = (unpack(source)?, unpack(destination)?);
+        if from.mint != to.mint {
+            return Err(TokenError::MintMismatch.into());
+        }
+        if from.amount < amount {
+            return Err(TokenError::InsufficientFunds.into());
+        }
+        // As the Token program does: a delegate spends its allowance, anyone else must be the owner
+        if from.delegate == COption::Some(*authority.key) {
+            from.delegated_amount = from
+                .delegated_amount
+                .checked_sub(amount)
+                .ok_or(TokenError::InsufficientFunds)?;
+            if from.delegated_amount == 0 {
+                from.delegate = COption::None;
+            }
+        } else if from.owner != *authority.key {
+            return Err(TokenError::OwnerMismatch.into());
+        }
+        from.amount -= amount;
+        to.amount = to.amount.checked_add(amount).ok_or(TokenError::Overflow)?;
+        pack(from, source)?;
+        pack(to, destination)?;
+        Ok(())
+    }
+
+    match TokenInstruction::unpack(&cpi.instruction.data)? {
+        TokenInstruction::InitializeAccount3 { owner } => {
+            let (account, mint) = (cpi.account(0)?, cpi.account(1)?);
+            token_owned(&[account, mint])?;
+            Mint::unpack(&mint.try_borrow_data()?)?;
+
+            let mut data = account.try_borrow_mut_data()?;
+            if Account::unpack_unchecked(&data)?.is_initialized() {
+                return Err(TokenError::AlreadyInUse.into());
+            }
+            Account {
+                mint: *mint.key,
+                owner,
+                state: AccountState::Initialized,
+                ..Account::default()
+            }
+            .pack_into_slice(&mut data);
+        }
+        TokenInstruction::InitializeMint2 {
+            decimals,
+            mint_authority,
+            freeze_authority,
+        } => {
+            let mint = cpi.account(0)?;
+            token_owned(&[mint])?;
+            let mut data = mint.try_borrow_mut_data()?;
+            if Mint::unpack_unchecked(&data)?.is_initialized() {
+                return Err(TokenError::AlreadyInUse.into());
+            }
+            Mint {
+                mint_authority: COption::Some(mint_authority),
+                decimals,
+                is_initialized: true,
+                freeze_authority,
+                ..Mint::default()
+            }
+            .pack_into_slice(&mut data);
+        }
+        TokenInstruction::Transfer { amount } => {
+            transfer(cpi.account(0)?, cpi.account(1)?, cpi.signer(2)?, amount)?;
+        }
+        TokenInstruction::TransferChecked { amount, decimals } => {
+            let (source, mint_account) = (cpi.account(0)?, cpi.account(1)?);
+            token_owned(&[mint_account])?;
+            // As the Token program does: the caller states the decimals, and they must be the mint's
+            if Mint::unpack(&mint_account.try_borrow_data()?)?.decimals != decimals {
+                return Err(TokenError::MintDecimalsMismatch.into());
+            }
+            if unpack(source)?.mint != *mint_account.key {
+                return Err(TokenError::MintMismatch.into());
+            }
+            transfer(source, cpi.account(2)?, cpi.signer(3)?, amount)?;
+        }
+        TokenInstruction::MintTo { amount } => {
+            let (mint_account, destination, authority) = (cpi.account(0)?, cpi.account(1)?, cpi.signer(2)?);
+            token_owned(&[mint_account])?;
+            let mut mint = Mint::unpack(&mint_account.try_borrow_data()?)?;
+            if mint.mint_authority != COption::Some(*authority.key) {
+                return Err(TokenError::OwnerMismatch.into());
+            }
+            let mut to = unpack(destination)?;
+            if to.mint != *mint_account.key {
+                return Err(TokenError::MintMismatch.into());
+            }
+            mint.supply = mint.supply.checked_add(amount).ok_or(TokenError::Overflow)?;
+            to.amount = to.amount.checked_add(amount).ok_or(TokenError::Overflow)?;
+            mint.pack_into_slice(&mut mint_account.try_borrow_mut_data()?);
+            pack(to, destination)?;
+        }
+        TokenInstruction::Burn { amount } => {
+            let (source, mint_account, authority) = (cpi.account(0)?, cpi.account(1)?, cpi.signer(2)?);
+            token_owned(&[mint_account])?;
+            let mut mint = Mint::unpack(&mint_account.try_borrow_data()?)?;
+            let mut from = unpack(source)?;
+            if from.mint != *mint_account.key {
+                return Err(TokenError::MintMismatch.into());
+            }
+            if from.amount < amount {
+                return Err(TokenError::InsufficientFunds.into());
+            }
+            if from.delegate == COption::Some(*authority.key) {
+                from.delegated_amount = from
+                    .delegated_amount
+                    .checked_sub(amount)
+                    .ok_or(TokenError::InsufficientFunds)?;
+                if from.delegated_amount == 0 {
+                    from.delegate = COption::None;
+                }
+            } else if from.owner != *authority.key {
+                return Err(TokenError::OwnerMismatch.into());
+            }
+            from.amount -= amount;
+            mint.supply -= amount;
+            mint.pack_into_slice(&mut mint_account.try_borrow_mut_data()?);
+            pack(from, source)?;
+        }
+        TokenInstruction::Approve { amount } => {
+            let (source, delegate, owner) = (cpi.account(0)?, cpi.account(1)?, cpi.signer(2)?);
+            let mut state = unpack(source)?;
+            if state.owner != *owner.key {
+                return Err(TokenError::OwnerMismatch.into());
+            }
+            state.delegate = COption::Some(*delegate.key);
+            state.delegated_amount = amount;
+            pack(state, source)?;
+        }
+        TokenInstruction::Revoke => {
+            let (source, owner) = (cpi.account(0)?, cpi.signer(1)?);
+            let mut state = unpack(source)?;
+            if state.owner != *owner.key {
+                return Err(TokenError::OwnerMismatch.into());
+            }
+            state.delegate = COption::None;
+            state.delegated_amount = 0;
+            pack(state, source)?;
+        }
+        TokenInstruction::SetAuthority {
+            authority_type,
+            new_authority,
+        } => {
+            use anchor_spl::token::spl_token::instruction::AuthorityType;
+
+            let (account, current) = (cpi.account(0)?, cpi.signer(1)?);
+            let mut state = unpack(account)?;
+            match authority_type {
+                AuthorityType::AccountOwner => {
+                    if state.owner != *current.key {
+                        return Err(TokenError::OwnerMismatch.into());
+                    }
+                    // The Token program ends any delegation along with the old owner
+                    state.owner = new_authority.ok_or(TokenError::InvalidInstruction)?;
+                    state.delegate = COption::None;
+                    state.delegated_amount = 0;
+                }
+                AuthorityType::CloseAccount => {
+                    if state.close_authority.unwrap_or(state.owner) != *current.key {
+                        return Err(TokenError::OwnerMismatch.into());
+                    }
+                    state.close_authority = new_authority;
+                }
+                other => panic!("Token authority type {other:?} is not emulated"),
+            }
+            pack(state, account)?;
+        }
+        TokenInstruction::CloseAccount => {
+            let (account, destination, authority) = (cpi.account(0)?, cpi.account(1)?, cpi.signer(2)?);
+            let state = unpack(account)?;
+            if state.amount != 0 {
+                return Err(TokenError::NonNativeHasBalance.into());
+            }
+            if state.close_authority.unwrap_or(state.owner) != *authority.key {
+                return Err(TokenError::OwnerMismatch.into());
+            }
+            let lamports = account.lamports();
+            **destination.try_borrow_mut_lamports()? = destination
+                .lamports()
+                .checked_add(lamports)
+                .ok_or(TokenError::Overflow)?;
+            **account.try_borrow_mut_lamports()? = 0;
+            account.try_borrow_mut_data()?.fill(0);
+        }
+        other => panic!("Token instruction {other:?} is not emulated"),
+    }
+    Ok(())
+}
+
+/// Token-2022's `TransferChecked` by the source's owner, running the mint's transfer hook as Token-2022 does
+///
+/// A destination that has disabled non-confidential credits refuses the
+/// transfer, as on chain. With the `confidential-transfer` feature, the
+/// confidential transfer instructions are passed on to Token-2022 itself.
+///
+/// While the hook runs, the source and destination are flagged
+/// `transferring`, which is how a hook tells a transfer from a direct call.
+/// The hook is handed the accounts its validation account resolves from
+/// those passed after the authority, and the authority without its
+/// signature; if it fails, so does the transfer.
+#[cfg(feature = "token-2022")]
+fn invoke_token_2022(cpi: &Cpi) -> ProgramResult {
+    use anchor_spl::token_2022::spl_token_2022::error::TokenError;
+    use anchor_spl::token_2022::spl_token_2022::extension::confidential_transfer::ConfidentialTransferAccount;
+    use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::{self, TransferHookAccount};
+    use anchor_spl::token_2022::spl_token_2022::extension::{
+        BaseStateWithExtensions, BaseStateWithExtensionsMut, StateWithExtensions, StateWithExtensionsMut,
+    };
+    use anchor_spl::token_2022::spl_token_2022::instruction::TokenInstruction;
+    use anchor_spl::token_2022::spl_token_2022::state::{Account, Mint};
+    use spl_transfer_hook_interface::onchain::invoke_execute;
+
+    fn set_transferring(account: &AccountInfo, transferring: bool) -> ProgramResult {
+        let mut data = account.try_borrow_mut_data()?;
+        let mut state = StateWithExtensionsMut::<Account>::unpack(&mut data)?;
+        state.get_extension_mut::<TransferHookAccount>()?.transferring = transferring.into();
+        Ok(())
+    }
+
+    let instruction = TokenInstruction::unpack(&cpi.instruction.data)?;
+    #[cfg(feature = "confidential-transfer")]
+    if let TokenInstruction::ConfidentialTransferExtension = instruction {
+        return invoke_confidential_transfer(cpi);
+    }
+    let TokenInstruction::TransferChecked { amount, decimals } = instruction else {
+        panic!("Token-2022 instructions other than TransferChecked are not emulated");
+    };
+    let (source, mint, destination, authority) = (cpi.account(0)?, cpi.account(1)?, cpi.account(2)?, cpi.signer(3)?);
+    if [source, mint, destination]
+        .iter()
+        .any(|account| *account.owner != anchor_spl::token_2022::ID)
+    {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let hook = {
+        let data = mint.try_borrow_data()?;
+        let state = StateWithExtensions::<Mint>::unpack(&data)?;
+        if state.base.decimals != decimals {
+            return Err(TokenError::MintDecimalsMismatch.into());
+        }
+        transfer_hook::get_program_id(&state)
+    };
+    {
+        let (mut source_data, mut destination_data) = (source.try_borrow_mut_data()?, destination.try_borrow_mut_data()?);
+        let mut from = StateWithExtensionsMut::<Account>::unpack(&mut source_data)?;
+        let mut to = StateWithExtensionsMut::<Account>::unpack(&mut destination_data)?;
+        if from.base.mint != *mint.key || to.base.mint != *mint.key {
+            return Err(TokenError::MintMismatch.into());
+        }
+        if from.base.is_frozen() || to.base.is_frozen() {
+            return Err(TokenError::AccountFrozen.into());
+        }
+        if from.base.owner != *authority.key {
+            return Err(TokenError::OwnerMismatch.into());
+        }
+        if let Ok(confidential) = to.get_extension::<ConfidentialTransferAccount>() {
+            confidential.non_confidential_transfer_allowed()?;
+        }
+        from.base.amount = from.base.amount.checked_sub(amount).ok_or(TokenError::InsufficientFunds)?;
+        to.base.amount = to.base.amount.checked_add(amount).ok_or(TokenError::Overflow)?;
+        from.pack_base();
+        to.pack_base();
+    }
+
+    if let Some(hook) = hook {
+        set_transferring(source, true)?;
+        set_transferring(destination, true)?;
+        invoke_execute(
+            &hook,
+            source.clone(),
+            mint.clone(),
+            destination.clone(),
+            authority.clone(),
+            cpi.account_infos,
+            amount,
+        )?;
+        set_transferring(source, false)?;
+        set_transferring(destination, false)?;
+    }
+    Ok(())
+}
+
+/// Token-2022's confidential transfer instructions, run by Token-2022's own processor
+///
+/// Proofs must already be in context state accounts, as the ZK Token
+/// Proof program leaves them once it has verified them; proofs verified in
+/// the same transaction, found through the instructions sysvar, are not
+/// emulated. The processor sees each account signed as the instruction
+/// requires, PDAs of the caller included.
+#[cfg(feature = "confidential-transfer")]
+fn invoke_confidential_transfer(cpi: &Cpi) -> ProgramResult {
+    use anchor_spl::token_2022::spl_token_2022::processor::Processor;
+
+    let mut accounts = Vec::with_capacity(cpi.instruction.accounts.len());
+    for (index, meta) in cpi.instruction.accounts.iter().enumerate() {
+        let mut account = cpi.account(index)?.clone();
+        account.is_signer = meta.is_signer;
+        account.is_writable &= meta.is_writable;
+        accounts.push(account);
+    }
+    Processor::process(&anchor_spl::token_2022::ID, &accounts, &cpi.instruction.data)
+}
+
+/// The Associated Token program's `Create`, behind `init, associated_token::mint = ..`,
+/// and `CreateIdempotent`
+///
+/// As on chain, `Create` fails with `account_already_in_use()` when anyone
+/// has created the account first, and `CreateIdempotent` accepts an
+/// existing account as long as its wallet and mint match, whatever else
+/// its state holds.
+#[cfg(feature = "spl")]
+fn invoke_associated_token(cpi: &Cpi) -> ProgramResult {
+    use anchor_spl::associated_token::get_associated_token_address;
+    use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+    use anchor_spl::token::spl_token::state::{Account, AccountState};
+
+    let idempotent = match cpi.instruction.data.first() {
+        None | Some(0) => false,
+        Some(1) => true,
+        Some(other) => panic!("Associated Token instruction {other} is not emulated"),
+    };
+    let (payer, associated, wallet, mint) = (cpi.account(0)?, cpi.account(1)?, cpi.account(2)?, cpi.account(3)?);
+    if *associated.key != get_associated_token_address(wallet.key, mint.key) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if idempotent && *associated.owner == anchor_spl::token::ID {
+        let existing = Account::unpack(&associated.try_borrow_data()?)?;
+        if existing.owner != *wallet.key {
+            // AssociatedTokenAccountError::InvalidOwner
+            return Err(ProgramError::Custom(0));
+        }
+        if existing.mint != *mint.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        return Ok(());
+    }
+
+    // Tops up an address someone funded first, as the program does
+    allocate(associated, Account::LEN as u64)?;
+    let required = Rent::default().minimum_balance(Account::LEN);
+    move_lamports(payer, associated, required.saturating_sub(associated.lamports()))?;
+    associated.assign(&anchor_spl::token::ID);
+    Account {
+        mint: *mint.key,
+        owner: *wallet.key,
+        state: AccountState::Initialized,
+        ..Account::default()
+    }
+    .pack_into_slice(&mut associated.try_borrow_mut_data()?);
+    Ok(())
+}
+
+/// The Stake program's `Authorize`, `DelegateStake`, `Deactivate` and `Withdraw`
+///
+/// Authority changes follow the Stake program's own rules, lockup and
+/// custodian included. Delegation needs the staker's signature and an
+/// account the Vote program owns, and activates everything above the
+/// rent-exempt reserve at once; redelegation is refused. Deactivation
+/// marks the current epoch and needs the staker's signature too. A withdrawal,
+/// from undelegated accounts only, leaves at least the rent-exempt
+/// reserve behind, or empties the account and returns it to `Uninitialized`.
+#[cfg(feature = "stake")]
+fn invoke_stake(cpi: &Cpi) -> ProgramResult {
+    use std::collections::HashSet;
+
+    use anchor_lang::solana_program::stake::instruction::{StakeError, StakeInstruction};
+    use anchor_lang::solana_program::stake::program::ID as STAKE_PROGRAM;
+    use anchor_lang::solana_program::stake::stake_flags::StakeFlags;
+    use anchor_lang::solana_program::stake::state::{Delegation, Stake, StakeAuthorize, StakeStateV2};
+    use anchor_lang::solana_program::vote::{self, state::VoteState};
+
+    fn meta(account: &AccountInfo) -> std::result::Result<StakeStateV2, ProgramError> {
+        if *account.owner != STAKE_PROGRAM {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        StakeStateV2::deserialize(&mut &account.try_borrow_data()?[..]).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    fn store(state: &StakeStateV2, account: &AccountInfo) -> ProgramResult {
+        let mut data = account.try_borrow_mut_data()?;
+        state
+            .serialize(&mut &mut data[..])
+            .map_err(|_| ProgramError::AccountDataTooSmall)
+    }
+
+    // The Stake program reports its own errors as custom codes, and signature failures as they are
+    fn stake_error(error: anchor_lang::solana_program::instruction::InstructionError) -> ProgramError {
+        ProgramError::try_from(error).unwrap_or(ProgramError::InvalidArgument)
+    }
+
+    // Keys that signed, from the given account positions on
+    let signers = |from: usize| -> std::result::Result<HashSet<Pubkey>, ProgramError> {
+        let mut signers = HashSet::new();
+        for index in from..cpi.instruction.accounts.len() {
+            if cpi.instruction.accounts[index].is_signer {
+                signers.insert(*cpi.account(index)?.key);
+            }
+        }
+        Ok(signers)
+    };
+    let clock = Clock::get()?;
+
+    match limited_deserialize(&cpi.instruction.data, 1232).map_err(|_| ProgramError::InvalidInstructionData)? {
+        StakeInstruction::Authorize(new_authority, which) => {
+            let stake = cpi.account(0)?;
+            let mut state = meta(stake)?;
+            let custodian = cpi.instruction.accounts.get(3).map(|meta| meta.pubkey);
+            let signers = signers(2)?;
+            let (StakeStateV2::Initialized(meta) | StakeStateV2::Stake(meta, ..)) = &mut state else {
+                return Err(ProgramError::InvalidAccountData);
+            };
+            let lockup = meta.lockup;
+            meta.authorized
+                .authorize(
+                    &signers,
+                    &new_authority,
+                    which,
+                    Some((&lockup, &clock, custodian.as_ref())),
+                )
+                .map_err(stake_error)?;
+            store(&state, stake)?;
+        }
+        StakeInstruction::DelegateStake => {
+            let (stake, vote_account) = (cpi.account(0)?, cpi.account(1)?);
+            // Redelegation waits for deactivation, which is not emulated
+            let StakeStateV2::Initialized(meta) = meta(stake)? else {
+                return Err(ProgramError::Custom(StakeError::TooSoonToRedelegate as u32));
+            };
+            meta.authorized
+                .check(&signers(5)?, StakeAuthorize::Staker)
+                .map_err(stake_error)?;
+            if *vote_account.owner != vote::program::ID {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            let vote_state = VoteState::deserialize(&vote_account.try_borrow_data()?).map_err(stake_error)?;
+
+            let delegated = stake
+                .lamports()
+                .checked_sub(meta.rent_exempt_reserve)
+                .filter(|delegated| *delegated > 0)
+                .ok_or(ProgramError::InsufficientFunds)?;
+            let delegation = Stake {
+                delegation: Delegation::new(vote_account.key, delegated, clock.epoch),
+                credits_observed: vote_state.credits(),
+            };
+            store(&StakeStateV2::Stake(meta, delegation, StakeFlags::empty()), stake)?;
+        }
+        StakeInstruction::Deactivate => {
+            let stake = cpi.account(0)?;
+            let StakeStateV2::Stake(meta, mut delegation, flags) = meta(stake)? else {
+                return Err(ProgramError::InvalidAccountData);
+            };
+            meta.authorized
+                .check(&signers(2)?, StakeAuthorize::Staker)
+                .map_err(stake_error)?;
+            if delegation.delegation.deactivation_epoch != u64::MAX {
+                return Err(ProgramError::Custom(StakeError::AlreadyDeactivated as u32));
+            }
+            delegation.delegation.deactivation_epoch = clock.epoch;
+            store(&StakeStateV2::Stake(meta, delegation, flags), stake)?;
+        }
+        StakeInstruction::Withdraw(lamports) => {
+            let (stake, destination) = (cpi.account(0)?, cpi.account(1)?);
+            let StakeStateV2::Initialized(meta) = meta(stake)? else {
+                panic!("withdrawals from delegated or uninitialized stake accounts are not emulated");
+            };
+            let signers = signers(4)?;
+            meta.authorized
+                .check(&signers, StakeAuthorize::Withdrawer)
+                .map_err(stake_error)?;
+            let custodian = cpi
+                .instruction
+                .accounts
+                .get(5)
+                .filter(|custodian| signers.contains(&custodian.pubkey))
+                .map(|custodian| custodian.pubkey);
+            if meta.lockup.is_in_force(&clock, custodian.as_ref()) {
+                return Err(ProgramError::Custom(StakeError::LockupInForce as u32));
+            }
+
+            let remaining = stake.lamports().checked_sub(lamports).ok_or(ProgramError::InsufficientFunds)?;
+            if remaining == 0 {
+                store(&StakeStateV2::Uninitialized, stake)?;
+            } else if remaining < meta.rent_exempt_reserve {
+                return Err(ProgramError::InsufficientFunds);
+            }
+            **stake.try_borrow_mut_lamports()? = remaining;
+            **destination.try_borrow_mut_lamports()? = destination
+                .lamports()
+                .checked_add(lamports)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+        other => panic!("Stake instruction {other:?} is not emulated"),
+    }
+    Ok(())
+}
+
+/// The Address Lookup Table program's `CreateLookupTable` and `ExtendLookupTable`
+///
+/// A table is created at the address its authority and `recent_slot`
+/// derive, funded by the payer; the slot is not checked against the slot
+/// hashes sysvar. Extending needs the authority's signature and a table
+/// that is not frozen, appends up to the 256-address limit, records the
+/// slot the new addresses were added in, and tops the table's rent up from
+/// the payer.
+#[cfg(feature = "lookup-table")]
+fn invoke_lookup_table(cpi: &Cpi) -> ProgramResult {
+    use anchor_lang::solana_program::address_lookup_table::instruction::ProgramInstruction;
+    use anchor_lang::solana_program::address_lookup_table::program::ID as LOOKUP_TABLE_PROGRAM;
+    use anchor_lang::solana_program::address_lookup_table::state::{
+        AddressLookupTable, LookupTableMeta, LOOKUP_TABLE_MAX_ADDRESSES, LOOKUP_TABLE_META_SIZE,
+    };
+
+    fn write_meta(table: &AccountInfo, meta: LookupTableMeta) -> ProgramResult {
+        AddressLookupTable::overwrite_meta_data(&mut table.try_borrow_mut_data()?, meta)
+            .map_err(|_| ProgramError::AccountDataTooSmall)
+    }
+
+    match limited_deserialize(&cpi.instruction.data, 1232).map_err(|_| ProgramError::InvalidInstructionData)? {
+        ProgramInstruction::CreateLookupTable { recent_slot, bump_seed } => {
+            let (table, authority, payer) = (cpi.account(0)?, cpi.account(1)?, cpi.signer(2)?);
+            let derived = Pubkey::create_program_address(
+                &[authority.key.as_ref(), &recent_slot.to_le_bytes(), &[bump_seed]],
+                &LOOKUP_TABLE_PROGRAM,
+            )
+            .map_err(|_| ProgramError::InvalidArgument)?;
+            if *table.key != derived {
+                return Err(ProgramError::InvalidArgument);
+            }
+            // Creating a table that exists is a no-op, as on chain
+            if *table.owner == LOOKUP_TABLE_PROGRAM {
+                return Ok(());
+            }
+
+            allocate(table, LOOKUP_TABLE_META_SIZE as u64)?;
+            let required = Rent::default().minimum_balance(LOOKUP_TABLE_META_SIZE);
+            move_lamports(payer, table, required.saturating_sub(table.lamports()))?;
+            table.assign(&LOOKUP_TABLE_PROGRAM);
+            write_meta(table, LookupTableMeta::new(*authority.key))?;
+        }
+        ProgramInstruction::ExtendLookupTable { new_addresses } => {
+            let (table, authority) = (cpi.account(0)?, cpi.signer(1)?);
+            if *table.owner != LOOKUP_TABLE_PROGRAM {
+                return Err(ProgramError::InvalidAccountOwner);
+            }
+            let (mut meta, listed) = {
+                let data = table.try_borrow_data()?;
+                let lookup_table = AddressLookupTable::deserialize(&data).map_err(|_| ProgramError::InvalidAccountData)?;
+                (lookup_table.meta, lookup_table.addresses.len())
+            };
+            // On chain these fail as `Immutable` and `IncorrectAuthority`, which no `ProgramError` represents
+            match meta.authority {
+                None => return Err(ProgramError::InvalidAccountData),
+                Some(current) if current != *authority.key => return Err(ProgramError::InvalidArgument),
+                Some(_) => {}
+            }
+            let total = listed + new_addresses.len();
+            if new_addresses.is_empty() || total > LOOKUP_TABLE_MAX_ADDRESSES {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            // Addresses added in the current slot are not yet usable in transactions
+            let slot = Clock::get()?.slot;
+            if meta.last_extended_slot != slot {
+                meta.last_extended_slot = slot;
+                meta.last_extended_slot_start_index = listed as u8;
+            }
+            let size = LOOKUP_TABLE_META_SIZE + total * 32;
+            table.realloc(size, false)?;
+            write_meta(table, meta)?;
+            table.try_borrow_mut_data()?[LOOKUP_TABLE_META_SIZE + listed * 32..]
+                .copy_from_slice(&new_addresses.iter().flat_map(|address| address.to_bytes()).collect::<Vec<_>>());
+
+            let shortfall = Rent::default().minimum_balance(size).saturating_sub(table.lamports());
+            if shortfall > 0 {
+                move_lamports(cpi.signer(2)?, table, shortfall)?;
+            }
+        }
+        other => panic!("Address Lookup Table instruction {other:?} is not emulated"),
+    }
+    Ok(())
+}
+
+/// Only accounts the System Program still owns can be allocated or assigned
+fn system_owned(account: &AccountInfo) -> ProgramResult {
+    if *account.owner != system_program::ID {
+        return Err(account_already_in_use());
+    }
+    Ok(())
+}
+
+/// Give an empty account `space` bytes, or check the buffer reserved for it (see `TestAccount::uninitialized`)
+///
+/// An `AccountInfo` from [`TestAccount::info`] can grow by up to
+/// `MAX_PERMITTED_DATA_INCREASE` in place, as on chain.
+fn allocate(account: &AccountInfo, space: u64) -> ProgramResult {
+    system_owned(account)?;
+    if account.data_is_empty() && space as usize <= MAX_PERMITTED_DATA_INCREASE {
+        return account.realloc(space as usize, true);
+    }
+    assert_eq!(
+        account.data_len() as u64,
+        space,
+        "reserve {space} bytes for {} with TestAccount::uninitialized",
+        account.key
+    );
+    Ok(())
+}
+
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    if *from.owner != system_program::ID {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    let mut from_lamports = from.try_borrow_mut_lamports()?;
+    **from_lamports = from_lamports
+        .checked_sub(lamports)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    **to.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
@@ -0,0 +1,336 @@
+//! Findings cross-referenced to the code that has them and the code that fixes them.
+//!
+//! `#[feature_gated]` records every `#[vulnerable]` and `#[secure]` handler
+//! it gates, with the file and lines it was written at, in a
+//! [`ProgramXref`] it emits as the program crate's `SECREF_XREF`. The table
+//! is built by the compiler from the handlers' own spans, so it moves when
+//! the code does. An [`Index`] groups the tables of any number of programs
+//! by vulnerability id (the example directory, as in `secref-curriculum`),
+//! so a report or an IDE can link a finding to both implementations:
+//!
+//! ```ignore
+//! use secref_xref::Index;
+//!
+//! let index = Index::new(&[&vault::SECREF_XREF, &admin_vault::SECREF_XREF]);
+//! let finding = index.finding("01").unwrap();
+//! for span in &finding.vulnerable {
+//!     println!("{span}"); // .../01_missing_account_validation/programs/vault/src/lib.rs:37-48
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::path::{Component, Path, PathBuf};
+
+/// Which implementation a handler belongs to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Side {
+    Vulnerable,
+    Secure,
+}
+
+impl Side {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Side::Vulnerable => "vulnerable",
+            Side::Secure => "secure",
+        }
+    }
+}
+
+/// A marked handler where the compiler saw it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Handler {
+    /// Handler name, e.g. `vulnerable_withdraw`
+    pub name: &'static str,
+    pub side: Side,
+    /// The handler's file as `file!()` reports it: relative to the workspace the program was built in
+    pub file: &'static str,
+    /// Line of the `fn` keyword
+    pub start_line: u32,
+    /// Line of the body's closing brace
+    pub end_line: u32,
+}
+
+/// The marked handlers of one program crate, emitted by `#[feature_gated]` as `SECREF_XREF`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProgramXref {
+    /// Crate name, e.g. `admin_vault`
+    pub package: &'static str,
+    /// `CARGO_MANIFEST_DIR` of the program crate
+    pub manifest_dir: &'static str,
+    pub handlers: &'static [Handler],
+}
+
+impl ProgramXref {
+    /// The example directory the program belongs to, e.g. `02_authority_check_failure`
+    ///
+    /// Programs outside an example's `programs/` directory, such as the
+    /// shared registry, are their own vulnerability and go by crate name.
+    pub fn vulnerability(&self) -> &'static str {
+        Path::new(self.manifest_dir)
+            .parent()
+            .filter(|parent| parent.file_name().is_some_and(|name| name == "programs"))
+            .and_then(Path::parent)
+            .and_then(Path::file_name)
+            .and_then(|name| name.to_str())
+            .unwrap_or(self.package)
+    }
+
+    /// Where `handler` is, as an absolute path and line range
+    pub fn span(&self, handler: &Handler) -> Span {
+        Span {
+            program: self.package,
+            handler: handler.name,
+            path: resolve(Path::new(self.manifest_dir), Path::new(handler.file)),
+            lines: handler.start_line..=handler.end_line,
+        }
+    }
+
+    /// The program's handlers on `side`
+    pub fn handlers(&self, side: Side) -> impl Iterator<Item = &'static Handler> + '_ {
+        self.handlers.iter().filter(move |handler| handler.side == side)
+    }
+}
+
+/// `file` is relative to the workspace the crate at `manifest_dir` was built in: the crate itself,
+/// or a directory above it. The overlap between the end of `manifest_dir` and the start of `file`
+/// tells which.
+fn resolve(manifest_dir: &Path, file: &Path) -> PathBuf {
+    if file.is_absolute() {
+        return file.to_path_buf();
+    }
+    let manifest: Vec<Component> = manifest_dir.components().collect();
+    let relative: Vec<Component> = file.components().collect();
+    let overlap = (1..=manifest.len().min(relative.len()))
+        .rev()
+        .find(|&len| manifest[manifest.len() - len..] == relative[..len])
+        .unwrap_or(0);
+    relative[overlap..].iter().fold(manifest_dir.to_path_buf(), |path, part| path.join(part))
+}
+
+/// A handler's source location, ready to link to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub program: &'static str,
+    pub handler: &'static str,
+    pub path: PathBuf,
+    pub lines: RangeInclusive<u32>,
+}
+
+impl fmt::Display for Span {
+    /// `path:start-end`, which terminals and editors open at the handler
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}-{}", self.path.display(), self.lines.start(), self.lines.end())
+    }
+}
+
+/// One vulnerability, with every handler that has it and every handler that fixes it
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Finding {
+    /// Example directory, e.g. `02_authority_check_failure`
+    pub vulnerability: &'static str,
+    pub vulnerable: Vec<Span>,
+    pub secure: Vec<Span>,
+}
+
+impl Finding {
+    pub fn spans(&self, side: Side) -> &[Span] {
+        match side {
+            Side::Vulnerable => &self.vulnerable,
+            Side::Secure => &self.secure,
+        }
+    }
+}
+
+/// Findings by vulnerability id, over the programs given to [`Index::new`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Index {
+    findings: BTreeMap<&'static str, Finding>,
+}
+
+impl Index {
+    /// Group the handlers of `programs` by vulnerability; an example's programs share one finding
+    pub fn new(programs: &[&ProgramXref]) -> Self {
+        let mut findings: BTreeMap<&'static str, Finding> = BTreeMap::new();
+        for program in programs {
+            let vulnerability = program.vulnerability();
+            let finding = findings.entry(vulnerability).or_insert_with(|| Finding {
+                vulnerability,
+                ..Finding::default()
+            });
+            for handler in program.handlers {
+                let span = program.span(handler);
+                match handler.side {
+                    Side::Vulnerable => finding.vulnerable.push(span),
+                    Side::Secure => finding.secure.push(span),
+                }
+            }
+        }
+        Self { findings }
+    }
+
+    /// Every finding, by vulnerability id
+    pub fn findings(&self) -> impl Iterator<Item = &Finding> {
+        self.findings.values()
+    }
+
+    /// Look a finding up by directory name or by its number (`"7"`, `"07"`)
+    pub fn finding(&self, id: &str) -> Option<&Finding> {
+        let number = id.parse::<u32>().ok();
+        self.findings.values().find(|finding| {
+            finding.vulnerability == id || number.is_some() && number_of(finding.vulnerability) == number
+        })
+    }
+
+    /// The index as JSON, for tools outside Rust:
+    /// `{"<id>": {"vulnerable": [{"program", "handler", "file", "start_line", "end_line"}], "secure": [...]}}`
+    pub fn to_json(&self) -> String {
+        let findings: Vec<String> = self
+            .findings()
+            .map(|finding| {
+                let sides: Vec<String> = [Side::Vulnerable, Side::Secure]
+                    .into_iter()
+                    .map(|side| {
+                        let spans: Vec<String> = finding.spans(side).iter().map(span_json).collect();
+                        format!("\"{}\":[{}]", side.as_str(), spans.join(","))
+                    })
+                    .collect();
+                format!("{}:{{{}}}", json_string(finding.vulnerability), sides.join(","))
+            })
+            .collect();
+        format!("{{{}}}", findings.join(","))
+    }
+}
+
+/// The number an example directory starts with, e.g. 7 for `07_seed_length_overflow`
+fn number_of(vulnerability: &str) -> Option<u32> {
+    vulnerability.split('_').next()?.parse().ok()
+}
+
+fn span_json(span: &Span) -> String {
+    format!(
+        "{{\"program\":{},\"handler\":{},\"file\":{},\"start_line\":{},\"end_line\":{}}}",
+        json_string(span.program),
+        json_string(span.handler),
+        json_string(&span.path.to_string_lossy()),
+        span.lines.start(),
+        span.lines.end(),
+    )
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VAULT: ProgramXref = ProgramXref {
+        package: "vault",
+        manifest_dir: "/repo/01_missing_account_validation/programs/vault",
+        handlers: &[
+            Handler {
+                name: "vulnerable_withdraw",
+                side: Side::Vulnerable,
+                file: "programs/vault/src/lib.rs",
+                start_line: 24,
+                end_line: 31,
+            },
+            Handler {
+                name: "secure_withdraw",
+                side: Side::Secure,
+                file: "programs/vault/src/lib.rs",
+                start_line: 34,
+                end_line: 45,
+            },
+        ],
+    };
+
+    const REGISTRY: ProgramXref = ProgramXref {
+        package: "secref_registry",
+        manifest_dir: "/repo/shared/secref-registry",
+        handlers: &[Handler {
+            name: "vulnerable_add",
+            side: Side::Vulnerable,
+            file: "src/lib.rs",
+            start_line: 10,
+            end_line: 12,
+        }],
+    };
+
+    #[test]
+    fn vulnerability_is_the_example_directory() {
+        assert_eq!(VAULT.vulnerability(), "01_missing_account_validation");
+        assert_eq!(REGISTRY.vulnerability(), "secref_registry");
+    }
+
+    #[test]
+    fn spans_resolve_against_the_building_workspace() {
+        let span = VAULT.span(&VAULT.handlers[0]);
+        assert_eq!(span.path, Path::new("/repo/01_missing_account_validation/programs/vault/src/lib.rs"));
+        assert_eq!(span.lines, 24..=31);
+        assert_eq!(
+            span.to_string(),
+            "/repo/01_missing_account_validation/programs/vault/src/lib.rs:24-31"
+        );
+
+        // A crate built as its own workspace reports paths relative to itself
+        let span = REGISTRY.span(&REGISTRY.handlers[0]);
+        assert_eq!(span.path, Path::new("/repo/shared/secref-registry/src/lib.rs"));
+
+        let absolute = Handler {
+            file: "/elsewhere/src/lib.rs",
+            ..VAULT.handlers[0]
+        };
+        assert_eq!(VAULT.span(&absolute).path, Path::new("/elsewhere/src/lib.rs"));
+    }
+
+    #[test]
+    fn index_groups_an_examples_programs_by_side() {
+        let router = ProgramXref {
+            package: "router",
+            manifest_dir: "/repo/01_missing_account_validation/programs/router",
+            handlers: &[Handler {
+                name: "secure_route",
+                side: Side::Secure,
+                file: "programs/router/src/lib.rs",
+                start_line: 5,
+                end_line: 9,
+            }],
+        };
+        let index = Index::new(&[&VAULT, &router, &REGISTRY]);
+        assert_eq!(index.findings().count(), 2);
+
+        let finding = index.finding("01_missing_account_validation").unwrap();
+        let names = |side| finding.spans(side).iter().map(|span| span.handler).collect::<Vec<_>>();
+        assert_eq!(names(Side::Vulnerable), ["vulnerable_withdraw"]);
+        assert_eq!(names(Side::Secure), ["secure_withdraw", "secure_route"]);
+
+        assert_eq!(index.finding("1"), Some(finding));
+        assert_eq!(index.finding("01"), Some(finding));
+        assert!(index.finding("02").is_none());
+    }
+
+    #[test]
+    fn json_lists_both_sides_of_every_finding() {
+        assert_eq!(
+            Index::new(&[&REGISTRY]).to_json(),
+            "{\"secref_registry\":{\"vulnerable\":[{\"program\":\"secref_registry\",\"handler\":\"vulnerable_add\",\
+             \"file\":\"/repo/shared/secref-registry/src/lib.rs\",\"start_line\":10,\"end_line\":12}],\"secure\":[]}}"
+        );
+        assert_eq!(json_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\u000a\"");
+    }
+}
@@ -36,6 +36,378 @@ const vulnerabilities = [
     title: 'Reinitialization Attack',
     severity: 'Medium',
     description: 'Accounts can be reinitialized, allowing attackers to reset state and steal ownership'
+  },
+  {
+    name: '06_insecure_deserialization',
+    title: 'Insecure Deserialization',
+    severity: 'High',
+    description: 'Length prefixes in instruction data are trusted, allowing unbounded allocations and truncated or padded payloads'
+  },
+  {
+    name: '07_seed_length_overflow',
+    title: 'String and Seed Length Overflow',
+    severity: 'Medium',
+    description: 'User-supplied strings exceed seed and account size limits, causing silent truncation, PDA collisions and runtime failures'
+  },
+  {
+    name: '08_unchecked_close',
+    title: 'Unchecked Close Destination',
+    severity: 'High',
+    description: 'Close instructions let callers pick the refund destination and close positions they do not own'
+  },
+  {
+    name: '09_account_resurrection',
+    title: 'Closed Account Resurrection',
+    severity: 'High',
+    description: 'Accounts drained to zero lamports keep their data until the transaction ends and can be reused or revived'
+  },
+  {
+    name: '10_cross_instance_replay',
+    title: 'Cross-Instance Signature Replay',
+    severity: 'High',
+    description: 'Off-chain withdrawal authorizations omit the program id and cluster, so signatures replay across deployments'
+  },
+  {
+    name: '11_instruction_data_confusion',
+    title: 'Instruction Data Confusion',
+    severity: 'High',
+    description: 'A native dispatcher authorizes by instruction tag but executes on a mode byte both instructions share'
+  },
+  {
+    name: '12_rent_topup_griefing',
+    title: 'Rent Top-Up Griefing',
+    severity: 'Medium',
+    description: 'A sponsor PDA pays rent top-ups for any account passed in, letting attackers drain it by resizing and closing their own accounts'
+  },
+  {
+    name: '13_slot_gambling',
+    title: 'Slot-Based Randomness',
+    severity: 'High',
+    description: 'Bets settle on the current slot\'s parity, which leaders and searchers can predict or choose'
+  },
+  {
+    name: '14_simulation_divergence',
+    title: 'Simulation Divergence (Bait-and-Switch)',
+    severity: 'High',
+    description: 'A fee the operator can change between a wallet\'s simulation and execution turns an approved payment into a drain'
+  },
+  {
+    name: '15_missing_executable_check',
+    title: 'Missing Executable Check',
+    severity: 'High',
+    description: 'A plugin address is approved and later invoked with vault signing authority without checking it is a deployed, immutable program'
+  },
+  {
+    name: '16_duplicate_account_roles',
+    title: 'Duplicate Account Roles',
+    severity: 'High',
+    description: 'Transfer logic assumes sender, recipient, relayer and approver are distinct, and double-credits or self-approves when one key fills several roles'
+  },
+  {
+    name: '17_god_pda_authority',
+    title: 'God PDA Authority',
+    severity: 'High',
+    description: 'One global PDA signs for every vault token account, so a bug in any instruction exposes every depositor instead of a single vault'
+  },
+  {
+    name: '18_token_balance_voting',
+    title: 'Token Balance Voting Power',
+    severity: 'High',
+    description: 'Voting weight is read from a live token balance at vote time, so flash-borrowed tokens can pass any proposal'
+  },
+  {
+    name: '19_lp_token_accounting',
+    title: 'LP Token Accounting',
+    severity: 'Critical',
+    description: 'LP tokens are minted from user-claimed amounts and burned for user-chosen payouts instead of measured reserve changes and pool share'
+  },
+  {
+    name: '20_lending_toy',
+    title: 'Lending Toy',
+    severity: 'High',
+    description: 'A utilization-based interest-rate model that fails at 0% and 100% utilization, and a collateral withdrawal that checks health before debiting and leaves positions undercollateralized, and a liquidation bounty paid in full whatever collateral the position holds'
+  },
+  {
+    name: '21_keeper_crank_incentives',
+    title: 'Keeper Crank Incentives',
+    severity: 'High',
+    description: 'A permissionless expiry crank lets the keeper choose its own reward and the refund destination instead of limiting keeper influence to timing with a fixed incentive'
+  },
+  {
+    name: '22_fee_recipient_ownership',
+    title: 'Fee Recipient Ownership',
+    severity: 'High',
+    description: 'Protocol fees are paid to a token account whose owner is never checked against the protocol\'s fee authority PDA, so payers and deployers can redirect them'
+  },
+  {
+    name: '23_remaining_accounts_router',
+    title: 'Remaining Accounts Swap Router',
+    severity: 'High',
+    description: 'A multi-hop swap router accepts any pool passed through remaining_accounts, letting attackers insert their own skimming pools mid-route instead of validating each hop against a registry'
+  },
+  {
+    name: '24_two_phase_commit',
+    title: 'Two-Phase Commit',
+    severity: 'Critical',
+    description: 'A timelocked treasury records withdrawal intents in a PDA, but the vulnerable execute instruction takes its amount and destination from the caller instead of binding them to the prepared parameters'
+  },
+  {
+    name: '25_account_prefunding',
+    title: 'Account Pre-Funding',
+    severity: 'Medium',
+    description: 'A program creates per-user vault PDAs with a bare system create_account call, so an attacker who transfers a single lamport to the predictable address first blocks the vault from ever being created'
+  },
+  {
+    name: '26_hot_account_contention',
+    title: 'Hot Account Contention',
+    severity: 'Medium',
+    description: 'Every user instruction writes one global counter account, serializing the whole program behind a single write lock that anyone can hold with spam, instead of sharding writes into per-user PDAs aggregated by a crank'
+  },
+  {
+    name: '27_event_authenticity',
+    title: 'Event Authenticity',
+    severity: 'High',
+    description: 'An off-chain points backend credits rewards from msg! logs and emit! events that any program in the same transaction can forge byte for byte, instead of from emit_cpi! events authenticated by the program\'s event authority PDA'
+  },
+  {
+    name: '28_zero_copy_loader_misuse',
+    title: 'Zero-Copy Loader Misuse',
+    severity: 'High',
+    description: 'A zero-copy price history is initialized through hand-rolled loaders that either never write the discriminator or never check it is zero, letting attackers re-initialize or reset someone else\'s account'
+  },
+  {
+    name: '29_stack_frame_limits',
+    title: 'Stack Frame Limits',
+    severity: 'Medium',
+    description: 'A rebalance instruction deserializes several large accounts onto the 4KB SBF stack frame and fails with an access violation, while the secure version boxes them onto the heap and keeps the index zero-copy'
+  },
+  {
+    name: '30_cpi_return_data',
+    title: 'CPI Return Data Validation',
+    severity: 'High',
+    description: 'A lending desk prices collateral with a CPI quote and trusts whatever get_return_data returns, so a program in the middle can forge the quote or relay a genuine quote for a different amount'
+  },
+  {
+    name: '31_invariant_watchdog',
+    title: 'Invariant Watchdog',
+    severity: 'High',
+    description: 'A lamport vault with an unchecked-CPI deposit and a wrapping withdraw is protected by a watchdog program whose assert_vault_solvent instruction, appended to the end of a transaction, reverts any transaction that leaves the vault insolvent'
+  },
+  {
+    name: '32_program_id_confusion',
+    title: 'Program ID Confusion',
+    severity: 'High',
+    description: 'A lending market built against the price feed crate with its devnet id compiled in trusts feeds owned by that id, so genuine mainnet feeds are rejected and anyone who deploys to the stale id can plant any price'
+  },
+  {
+    name: '33_nft_rental_utility',
+    title: 'NFT Rental Utility (Owner vs Holder)',
+    severity: 'High',
+    description: 'An NFT perk is granted to whoever holds the token account, so the NFT can be flash-transferred in for one instruction and back out; the secure version keeps the NFT in escrow and grants utility through time-bound rental PDAs with Token-2022 non-transferable receipts'
+  },
+  {
+    name: '34_royalty_bypass',
+    title: 'NFT Royalty Bypass',
+    severity: 'Medium',
+    description: 'A marketplace purchase takes the royalty recipients and amounts from the buyer, who names themselves or pays nothing; the secure purchase reads creators and seller fee basis points from the NFT\'s Metaplex metadata account'
+  },
+  {
+    name: '35_bonding_curve_pricing',
+    title: 'Bonding Curve Price Overflow',
+    severity: 'High',
+    description: 'A bonding-curve token sale integrates the price in wrapping u64 math with no supply cap, so a large enough purchase wraps its cost to almost nothing and mints far past the cap; the secure sale integrates piecewise in u128, enforces the cap, and rejects costs that do not fit in a u64'
+  },
+  {
+    name: '36_referral_self_dealing',
+    title: 'Referral Self-Dealing',
+    severity: 'High',
+    description: 'A two-level referral bonus on deposits lets a wallet refer itself, register as many members as it likes, form referral loops, and earn bonuses on the same lamports deposited and withdrawn again; the secure campaign keeps one member PDA per wallet, requires an existing, distinct referrer, and pays bonuses only on new deposit highs out of a fixed budget'
+  },
+  {
+    name: '37_payment_splitter_rounding',
+    title: 'Payment Splitter Rounding and Ordering',
+    severity: 'Medium',
+    description: 'A lamport splitter re-reads its balance after every payment, so each recipient\'s share is taken from what earlier recipients left and the list order decides who is paid; rounding dust is never assigned and an unbounded recipient list can no longer be distributed at all. The secure splitter caps the list, computes every share from the starting balance, and gives the dust to the first recipient'
+  },
+  {
+    name: '38_subscription_authority',
+    title: 'Subscription Pull Authority',
+    severity: 'High',
+    description: 'A recurring-payment program makes each subscription PDA the token delegate of the subscriber\'s account, but the vulnerable subscription approves an unlimited allowance and lets the service pull any amount at any time, even after the subscription has expired; the secure subscription approves only what its terms allow, pulls one fixed amount per interval until expiry, and detects a delegation the subscriber revoked or replaced'
+  },
+  {
+    name: '39_bridge_message_verification',
+    title: 'Bridge Message Verification',
+    severity: 'Critical',
+    description: 'A toy bridge inbox releases lamports for messages relayed from another chain; the vulnerable inbox pays out any message a relayer posts and never records which messages it has executed, while the secure inbox requires a threshold of distinct guardian Ed25519 signatures over the message and its bridge, read through instructions-sysvar introspection, and consumes each message with a nullifier PDA'
+  },
+  {
+    name: '40_wallet_drainer_anatomy',
+    title: 'Wallet Drainer Anatomy',
+    severity: 'Critical',
+    description: 'An airdrop claim asks the user to sign for their own token account; the vulnerable claims use that signature to slip an unlimited Approve to the operator, or a SetAuthority handing the account to the operator, into the same instruction, while the secure claim only ever signs token CPIs as its own vault and verifies that the claimant\'s owner, delegate and close authority are unchanged'
+  },
+  {
+    name: '41_ata_precreation_squatting',
+    title: 'ATA Pre-creation Squatting',
+    severity: 'High',
+    description: 'A token escrow keeps each deposit in the associated token account of its escrow PDA, an address anyone can compute and create first; the vulnerable opens either `init` the vault, which fails forever once someone has created it, or `init_if_needed` it, which accepts a frozen account or one carrying a delegate or close authority, while the secure open uses `CreateIdempotent` and then checks the vault as if it were new with the shared token account guards'
+  },
+  {
+    name: '42_compliance_freeze',
+    title: 'Compliance Freeze and Seize',
+    severity: 'High',
+    description: 'A lamport vault with per-user positions that a compliance role can freeze and thaw; the vulnerable seize lets the freezer take any position\'s funds immediately, frozen or not, while the secure lifecycle gives freezing and seizing to distinct roles, seizes only frozen positions, and makes every seizure wait out a timelock during which a thaw cancels it'
+  },
+  {
+    name: '43_deposit_memo_binding',
+    title: 'Deposit Memo Binding',
+    severity: 'High',
+    description: 'A deposit vault whose off-chain accounting credits users by a memo in the deposit transaction; the vulnerable flow leaves the memo unbound to the transfer, so an indexer that pairs them can be made to credit deposits that never reached the vault, while the secure flow records each deposit\'s reference in an on-chain receipt the indexer verifies'
+  },
+  {
+    name: '44_concurrent_merkle_tree',
+    title: 'Concurrent Merkle Tree Proofs',
+    severity: 'High',
+    description: 'Vouchers stored as leaves of a simplified concurrent Merkle tree; the vulnerable redemption accepts proofs against any recent root and of any length, so revoked vouchers stay redeemable and one voucher can be redeemed at several aliased indices, while the secure redemption proves the leaf against the canonical root with a depth-sized proof and burns it in the tree'
+  },
+  {
+    name: '45_durable_nonce_replay',
+    title: 'Durable Nonce Replay',
+    severity: 'High',
+    description: 'A gasless payments wallet whose owner signs payment authorizations off-chain for a relayer to submit; the vulnerable flow trusts the transaction\'s blockhash to make each authorization short-lived and single-use, so the same signed payload replays in fresh durable-nonce transactions forever, while the secure flow signs an explicit expiry and consumes a per-authorization nonce PDA'
+  },
+  {
+    name: '46_balance_as_authorization',
+    title: 'Balance as Authorization',
+    severity: 'High',
+    description: 'A members\' club that pays a bonus to anyone holding at least a threshold of lamports; the vulnerable claim reads the member\'s live balance, which can be funded and drained again within one transaction, while the secure claim requires a stake recorded in a PDA and locked for a period'
+  },
+  {
+    name: '47_kill_switch_recovery',
+    title: 'Kill Switch and Staged Recovery',
+    severity: 'High',
+    description: 'A lamport pool with an incident-response kill switch; the vulnerable halt lets one guardian block withdrawals indefinitely while governance can move a halted pool\'s funds anywhere, while the secure halt needs a quorum of guardians, lapses into withdraw-only mode by itself, and the pool resumes only through a timelocked governance proposal'
+  },
+  {
+    name: '48_vault_interface',
+    title: 'Instruction Interfaces and Dispatch',
+    severity: 'Critical',
+    description: 'A router that deposits its treasury into vaults through a shared deposit interface two programs implement; the vulnerable allocation calls any conforming program with the treasury signing and trusts its receipt, while the secure allocation dispatches only to approved implementations, checks the treasury moved by exactly the amount, and validates the receipt\'s program id, length and echoed amount'
+  },
+  {
+    name: '49_stake_authority_confusion',
+    title: 'Stake Account Authorities',
+    severity: 'Critical',
+    description: 'A stake pool that credits deposited native stake accounts and redeems positions out of any of them; the vulnerable deposit accepts an account whose staker is the pool while the depositor keeps the withdrawer and can take it back, while the secure deposit moves both authorities to the pool\'s PDAs by CPI and refuses stake under a lockup'
+  },
+  {
+    name: '50_validator_delegation',
+    title: 'Validator Vote Accounts and Commission',
+    severity: 'High',
+    description: 'A delegation manager whose crank places a treasury\'s stake with validators; the vulnerable delegation signs for any vote account the crank names, whatever its commission, while the secure delegation parses the vote account only if the Vote program owns it, enforces the admin\'s commission cap, and lets anyone deactivate stake whose validator raised its commission'
+  },
+  {
+    name: '51_lookup_table_poisoning',
+    title: 'Address Lookup Table Poisoning',
+    severity: 'Critical',
+    description: 'A reward router that pays every address in the lookup table its PDA controls; the vulnerable extension signs for any caller and any addresses, so an attacker lists their own wallet and is paid like a pool, while the secure extension is admin-only and lists only accounts verified as the router\'s pools, and the client checks the table before building transactions from it'
+  },
+  {
+    name: '52_memo_payment_reference',
+    title: 'Memo Payment References',
+    severity: 'High',
+    description: 'An invoice desk whose merchant ships the order a payment\'s memo names; the vulnerable payment checks a memo argument the client supplies, so a cheap invoice is paid under an expensive invoice\'s memo, while the secure payment reads the Memo instruction beside it through the instructions sysvar and refuses conflicting memos'
+  },
+  {
+    name: '53_velocity_limits',
+    title: 'Velocity Limits',
+    severity: 'High',
+    description: 'A vault capping withdrawals per transaction and per owner per day; the vulnerable cap is counted in a tracker the owner can close and reopen from zero, so a stolen key empties the position in one transaction, while the secure cap uses a shared velocity guard and a tracker at a deterministic address, never closed, counting in clock-aligned windows'
+  },
+  {
+    name: '54_transfer_hook_authoring',
+    title: 'Transfer Hook Authoring',
+    severity: 'High',
+    description: 'Two Token-2022 transfer hooks capping what each wallet receives per day; the naive hook counts any Execute call with the accounts it is handed and lets anyone write the validation account, so a direct call spends a stranger\'s limit, while the guarded hook requires the transferring flag and its mint, derives every extra account, and a vault composes with it through the mint'
+  },
+  {
+    name: '55_confidential_transfer_extension',
+    title: 'Confidential Transfer Extension',
+    severity: 'High',
+    description: 'A vault written for SPL mints given a Token-2022 mint allowing confidential transfers; the vulnerable vault credits confidential deposits by the amount the depositor claims, since all it can see is a new pending credit, so a one-unit transfer claims another depositor\'s tokens, while the secure vault checks the mint against an allowlist of extensions and refuses it'
+  },
+  {
+    name: '56_mint_pointer_spoofing',
+    title: 'Mint Pointer Spoofing',
+    severity: 'High',
+    description: 'A pool paying a reward per mint in a curated Token-2022 collection; the vulnerable claims follow the mint\'s metadata or group member pointer, so a mint pointing at a real member\'s membership, or whose own metadata names the curator, is paid, while the secure claim uses a shared guard to require the membership stored in the mint itself, which only the group\'s update authority can write'
+  },
+  {
+    name: '57_compute_budget_introspection',
+    title: 'Compute Budget Introspection',
+    severity: 'High',
+    description: 'A payroll paid by permissionless cranks for a tip; the vulnerable crank tips the cranker and then pays payees while the compute meter allows, so a transaction asking for too few compute units to pay anyone still earns the tip, while the secure crank uses a shared guard to read the compute unit limit through the instructions sysvar, pays a fixed batch and tips last'
+  },
+  {
+    name: '58_payer_drain_griefing',
+    title: 'Payer Drain Griefing',
+    severity: 'Medium',
+    description: 'A gasless notes program whose payer PDA puts up the rent of any note anyone opens; the vulnerable handler lets the caller choose the size and how many, so one wallet locks the whole budget in 10 KiB notes for nothing but fees, while the secure handlers make the owner pay, or sponsor fixed-size notes within a per-wallet quota the wallet pays for itself'
+  },
+  {
+    name: '59_decimal_confusion',
+    title: 'Decimal Confusion',
+    severity: 'High',
+    description: 'A desk selling tokens at a price per whole token; the vulnerable handler divides base units by 10^9 whatever the mint, so a 6-decimal token sells at a thousandth of its price and dust is free, while the secure handler carries the mint decimals in a shared Amount type, prices by 10^decimals rounding up, and transfers with transfer_checked'
+  },
+  {
+    name: '60_timestamp_arithmetic',
+    title: 'Timestamp Arithmetic',
+    severity: 'High',
+    description: 'Linear lamport vesting over i64 Unix timestamps; the vulnerable claim casts now - start to a u64 and wraps start + duration, so a grant starting in the future or lasting i64::MAX seconds vests in full at once, while the secure path compares a checked signed elapsed time before converting it and only accepts durations and starts within ten years of the present'
+  },
+  {
+    name: '61_order_cancel_race',
+    title: 'Order Cancel Race',
+    severity: 'Critical',
+    description: 'An order book escrowing every order in one vault and keeping settled orders with their status; the vulnerable fill never reads the status, so a maker cancels and then fills its own order from a second wallet, taking other makers\' tokens, while the secure fill moves the order from Open to Filled through a shared state-machine guard before it pays anything'
+  },
+  {
+    name: '62_collateral_double_counting',
+    title: 'Collateral Double Counting',
+    severity: 'High',
+    description: 'Lending pools that share one deposit-receipt collateral system; the vulnerable borrow counts a receipt\'s whole deposit in every loan that names it, so one 10 SOL deposit borrows 8 SOL from each of two 80% pools, while the secure borrow lends only against a receipt whose locked_by names the loan, set by lock and cleared by unlock once the loan is repaid'
+  },
+  {
+    name: '63_fee_bps_misconfiguration',
+    title: 'Fee Basis-Points Misconfiguration',
+    severity: 'High',
+    description: 'A checkout charging a protocol fee on top of every payment; the vulnerable update accepts any u16, a 655% fee included, and applies it to the next payment, while the secure update bounds it with a BoundedBps of at most 5%, announces it as a FeeUpdateProposed event, and applies it through a two-day timelock'
+  },
+  {
+    name: '64_config_hot_swap',
+    title: 'Config Hot-Swap',
+    severity: 'Critical',
+    description: 'A redemption desk whose keeper settles each request a day after it is made, at the oracle and fee recipient of the desk\'s config; the vulnerable setter swaps both at once, so a stolen admin key reprices every request in flight with its own feed and takes their fees, while the secure desk timelocks each change into a new ConfigEpoch snapshot and settles every request under the epoch it was made in'
+ },
+  {
+    name: '65_withdraw_queue',
+    title: 'Withdraw Queue',
+    severity: 'High',
+    description: 'An illiquid vault paying withdrawals in ticket order as its deployed lamports come back; the vulnerable request takes its ticket from the caller and its address from the caller\'s key, so an attacker queues at the head\'s ticket, cranks their copy first and strands the user they jumped, while the secure request takes the next ticket from a counter at an address derived from the ticket alone, and its paged crank pays only the head\'s canonical request'
+  },
+  {
+    name: '66_signer_seeds_leakage',
+    title: 'Signer Seeds Leakage',
+    severity: 'Critical',
+    description: 'A token vault whose keeper runs its strategy through a generic execute instruction; the vulnerable execute signs any CPI with the same PDA that owns the deposits, so the keeper redirects a Token program approve from the strategy\'s budget to the deposits and spends them as delegate, while the secure vault signs withdrawals and strategy CPIs with separate PDAs tagged custody and execute in their seeds'
+  },
+  {
+    name: '67_position_receipts',
+    title: 'Position Receipts',
+    severity: 'Critical',
+    description: 'A token vault minting a position NFT for every deposit, under a mint authority PDA, redeemable by whoever holds it; the vulnerable redemption checks the receipt is held but never burns it or closes the position, so one small depositor redeems the same receipt until the vault is empty, while the secure redemption accepts only the position\'s own mint, minted by the vault with a supply of one, burns the receipt and closes the position'
   }
 ];
 
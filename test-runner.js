@@ -14,7 +14,69 @@ const examples = [
   '02_authority_check_failure', 
   '03_unsafe_cpi',
   '04_arithmetic_overflow',
-  '05_reinitialization_attack'
+  '05_reinitialization_attack',
+  '06_insecure_deserialization',
+  '07_seed_length_overflow',
+  '08_unchecked_close',
+  '09_account_resurrection',
+  '10_cross_instance_replay',
+  '11_instruction_data_confusion',
+  '12_rent_topup_griefing',
+  '13_slot_gambling',
+  '14_simulation_divergence',
+  '15_missing_executable_check',
+  '16_duplicate_account_roles',
+  '17_god_pda_authority',
+  '18_token_balance_voting',
+  '19_lp_token_accounting',
+  '20_lending_toy',
+  '21_keeper_crank_incentives',
+  '22_fee_recipient_ownership',
+  '23_remaining_accounts_router',
+  '24_two_phase_commit',
+  '25_account_prefunding',
+  '26_hot_account_contention',
+  '27_event_authenticity',
+  '28_zero_copy_loader_misuse',
+  '29_stack_frame_limits',
+  '30_cpi_return_data',
+  '31_invariant_watchdog',
+  '32_program_id_confusion',
+  '33_nft_rental_utility',
+  '34_royalty_bypass',
+  '35_bonding_curve_pricing',
+  '36_referral_self_dealing',
+  '37_payment_splitter_rounding',
+  '38_subscription_authority',
+  '39_bridge_message_verification',
+  '40_wallet_drainer_anatomy',
+  '41_ata_precreation_squatting',
+  '42_compliance_freeze',
+  '43_deposit_memo_binding',
+  '44_concurrent_merkle_tree',
+  '45_durable_nonce_replay',
+  '46_balance_as_authorization',
+  '47_kill_switch_recovery',
+  '48_vault_interface',
+  '49_stake_authority_confusion',
+  '50_validator_delegation',
+  '51_lookup_table_poisoning',
+  '52_memo_payment_reference',
+  '53_velocity_limits',
+  '54_transfer_hook_authoring',
+  '55_confidential_transfer_extension',
+  '56_mint_pointer_spoofing',
+  '57_compute_budget_introspection',
+  '58_payer_drain_griefing',
+  '59_decimal_confusion',
+  '60_timestamp_arithmetic',
+  '61_order_cancel_race',
+  '62_collateral_double_counting',
+  '63_fee_bps_misconfiguration',
+  '64_config_hot_swap',
+  '65_withdraw_queue',
+  '66_signer_seeds_leakage',
+  '67_position_receipts'
 ];
 
 console.log('🚀 Running Solana Security Examples Tests\n');